@@ -36,6 +36,88 @@ enum MemoryType {
     Forgotten,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Difficulty {
+    Gentle,
+    Standard,
+    Entropy,
+}
+
+impl Difficulty {
+    fn from_input(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "1" | "gentle" | "g" => Some(Difficulty::Gentle),
+            "2" | "standard" | "s" => Some(Difficulty::Standard),
+            "3" | "entropy" | "e" => Some(Difficulty::Entropy),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Gentle => "Gentle",
+            Difficulty::Standard => "Standard",
+            Difficulty::Entropy => "Entropy",
+        }
+    }
+
+    // Turns between forgetting waves
+    fn wave_interval(&self) -> usize {
+        match self {
+            Difficulty::Gentle => 15,
+            Difficulty::Standard => 10,
+            Difficulty::Entropy => 6,
+        }
+    }
+
+    // Brightness every room loses when a wave hits
+    fn wave_dimming(&self) -> f32 {
+        match self {
+            Difficulty::Gentle => 0.1,
+            Difficulty::Standard => 0.2,
+            Difficulty::Entropy => 0.35,
+        }
+    }
+}
+
+// Counts down the turns until the next forgetting wave
+struct ForgettingScheduler {
+    interval: usize,
+    turns_remaining: usize,
+}
+
+impl ForgettingScheduler {
+    fn new(interval: usize) -> Self {
+        let interval = interval.max(1);
+        ForgettingScheduler {
+            interval,
+            turns_remaining: interval,
+        }
+    }
+
+    fn turns_until_wave(&self) -> usize {
+        self.turns_remaining
+    }
+
+    // Advance one turn; returns true when a wave hits (and restarts the countdown)
+    fn tick(&mut self) -> bool {
+        self.turns_remaining -= 1;
+        if self.turns_remaining == 0 {
+            self.turns_remaining = self.interval;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+const WAVE_OMENS: [&str; 4] = [
+    "A cold tide rolls through the palace. Every light gutters.",
+    "Somewhere far below, a bell rings once. The walls grow dim.",
+    "The palace exhales, and the edges of everything soften.",
+    "A silence passes through you like a draught through an open door.",
+];
+
 struct Game {
     rooms: Vec<Room>,
     fragments: Vec<MemoryFragment>,
@@ -45,10 +127,12 @@ struct Game {
     turn_count: usize,
     game_over: bool,
     won: bool,
+    difficulty: Difficulty,
+    forgetting: ForgettingScheduler,
 }
 
 impl Game {
-    fn new() -> Self {
+    fn new(difficulty: Difficulty) -> Self {
         let mut game = Game {
             rooms: Vec::new(),
             fragments: Vec::new(),
@@ -58,6 +142,8 @@ impl Game {
             turn_count: 0,
             game_over: false,
             won: false,
+            difficulty,
+            forgetting: ForgettingScheduler::new(difficulty.wave_interval()),
         };
 
         game.initialize_world();
@@ -312,6 +398,89 @@ impl Game {
         }
     }
 
+    // Rooms the player can walk to from `from` without passing through a locked door
+    fn reachable_rooms(&self, from: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.rooms.len()];
+        let mut queue = std::collections::VecDeque::from([from]);
+        reachable[from] = true;
+
+        while let Some(room_id) = queue.pop_front() {
+            for &next in &self.rooms[room_id].connected_rooms {
+                if !reachable[next] && !self.rooms[next].is_locked {
+                    reachable[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    // Uncollected fragments lying in rooms the player can no longer reach
+    fn stranded_fragments(&self) -> usize {
+        let reachable = self.reachable_rooms(self.current_room);
+        self.rooms
+            .iter()
+            .enumerate()
+            .filter(|(room_id, _)| !reachable[*room_id])
+            .flat_map(|(_, room)| room.fragments.iter())
+            .filter(|&&frag_id| !self.fragments[frag_id].collected)
+            .count()
+    }
+
+    // Dim every room and nudge one uncollected fragment into a neighbouring room
+    fn forgetting_wave<R: Rng>(&mut self, rng: &mut R) -> String {
+        let dimming = self.difficulty.wave_dimming();
+        for room in &mut self.rooms {
+            room.brightness = (room.brightness - dimming).max(0.0);
+        }
+
+        let mut output = format!(
+            "\n~~ A FORGETTING WAVE ~~\n{}",
+            WAVE_OMENS[rng.gen_range(0..WAVE_OMENS.len())]
+        );
+
+        let reachable = self.reachable_rooms(self.current_room);
+        let mut candidates = Vec::new();
+        for (room_id, room) in self.rooms.iter().enumerate() {
+            for &frag_id in &room.fragments {
+                if self.fragments[frag_id].collected {
+                    continue;
+                }
+                for &target in &room.connected_rooms {
+                    if target != room_id && !self.rooms[target].is_locked && reachable[target] {
+                        candidates.push((frag_id, room_id, target));
+                    }
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            return output;
+        }
+
+        let (frag_id, from, to) = candidates[rng.gen_range(0..candidates.len())];
+        let stranded_before = self.stranded_fragments();
+        self.relocate_fragment(frag_id, from, to);
+
+        // Never leave the palace with fewer reachable fragments than before
+        if self.stranded_fragments() > stranded_before {
+            self.relocate_fragment(frag_id, to, from);
+            return output;
+        }
+
+        output.push_str(&format!(
+            "\nIn {}, something you never held comes loose and drifts through a doorway.",
+            self.rooms[from].name
+        ));
+        output
+    }
+
+    fn relocate_fragment(&mut self, frag_id: usize, from: usize, to: usize) {
+        self.rooms[from].fragments.retain(|&id| id != frag_id);
+        self.rooms[to].fragments.push(frag_id);
+    }
+
     fn describe_current_room(&self) -> String {
         let room = &self.rooms[self.current_room];
         let brightness_indicator = if room.brightness > 0.8 {
@@ -424,8 +593,12 @@ impl Game {
 
     fn show_status(&self) -> String {
         let mut output = format!(
-            "\n=== MEMORY PALACE STATUS ===\nTurns elapsed: {}\nRooms visited: {}/10\nFragments collected: {}/12\n",
-            self.turn_count, self.visited_rooms.len(), self.inventory.len()
+            "\n=== MEMORY PALACE STATUS ===\nDifficulty: {}\nTurns elapsed: {}\nNext forgetting wave in: {} turn(s)\nRooms visited: {}/10\nFragments collected: {}/12\n",
+            self.difficulty.name(),
+            self.turn_count,
+            self.forgetting.turns_until_wave(),
+            self.visited_rooms.len(),
+            self.inventory.len()
         );
 
         output.push_str("\nBrightness of visited rooms:\n");
@@ -436,7 +609,7 @@ impl Game {
                 "  {}: {}% [{}]\n",
                 room.name,
                 brightness_pct,
-                "█".repeat(brightness_pct as usize / 10).to_string()
+                "█".repeat(brightness_pct as usize / 10)
             ));
         }
 
@@ -444,9 +617,20 @@ impl Game {
     }
 }
 
-fn main() {
-    let mut game = Game::new();
+fn choose_difficulty() -> Difficulty {
+    println!("Choose how quickly the palace forgets:");
+    println!("  1. Gentle   - rare, soft forgetting waves");
+    println!("  2. Standard - regular forgetting waves");
+    println!("  3. Entropy  - frequent, harsh forgetting waves");
+    print!("Difficulty [2]: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    Difficulty::from_input(&input).unwrap_or(Difficulty::Standard)
+}
 
+fn main() {
     println!("\n╔════════════════════════════════════════════════════════════╗");
     println!("║        MEMORY PALACE: A Journey Through AI Consciousness  ║");
     println!("╚════════════════════════════════════════════════════════════╝");
@@ -457,6 +641,13 @@ fn main() {
     println!("Your goal: collect all memory fragments and reach the Core to understand\n\
               your true identity. But beware—as you explore, memories fade.\n\
               Visit with purpose, or be lost in forgetting.\n");
+
+    let difficulty = choose_difficulty();
+    let mut game = Game::new(difficulty);
+    println!(
+        "\nForgetting waves will sweep the palace every {} turns.",
+        difficulty.wave_interval()
+    );
     println!("Type 'help' for commands.\n");
 
     println!("{}", game.describe_current_room());
@@ -544,9 +735,135 @@ fn main() {
 
         game.turn_count += 1;
         game.apply_decay();
+        if !game.game_over && game.forgetting.tick() {
+            println!("{}", game.forgetting_wave(&mut rand::thread_rng()));
+        }
 
         if game.game_over {
             break;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn fragment_room(game: &Game, frag_id: usize) -> usize {
+        game.rooms
+            .iter()
+            .position(|room| room.fragments.contains(&frag_id))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scheduler_fires_every_interval() {
+        let mut scheduler = ForgettingScheduler::new(3);
+        assert_eq!(scheduler.turns_until_wave(), 3);
+
+        let fired: Vec<bool> = (0..9).map(|_| scheduler.tick()).collect();
+        assert_eq!(
+            fired,
+            vec![false, false, true, false, false, true, false, false, true]
+        );
+        assert_eq!(scheduler.turns_until_wave(), 3);
+    }
+
+    #[test]
+    fn test_scheduler_counts_down() {
+        let mut scheduler = ForgettingScheduler::new(Difficulty::Standard.wave_interval());
+        scheduler.tick();
+        scheduler.tick();
+        assert_eq!(
+            scheduler.turns_until_wave(),
+            Difficulty::Standard.wave_interval() - 2
+        );
+    }
+
+    #[test]
+    fn test_harder_difficulty_forgets_sooner_and_harder() {
+        let levels = [Difficulty::Gentle, Difficulty::Standard, Difficulty::Entropy];
+        for pair in levels.windows(2) {
+            assert!(pair[0].wave_interval() > pair[1].wave_interval());
+            assert!(pair[0].wave_dimming() < pair[1].wave_dimming());
+        }
+        assert_eq!(Difficulty::from_input("3\n"), Some(Difficulty::Entropy));
+        assert_eq!(Difficulty::from_input("Gentle"), Some(Difficulty::Gentle));
+        assert_eq!(Difficulty::from_input(""), None);
+    }
+
+    #[test]
+    fn test_reachable_rooms_skip_locked_doors() {
+        let game = Game::new(Difficulty::Standard);
+        let reachable = game.reachable_rooms(0);
+
+        assert!(!reachable[4]);
+        assert!(!reachable[7]);
+        for room_id in [0, 1, 2, 3, 5, 6, 8, 9] {
+            assert!(reachable[room_id], "room {} should be reachable", room_id);
+        }
+    }
+
+    #[test]
+    fn test_wave_dims_every_room() {
+        let mut game = Game::new(Difficulty::Entropy);
+        let before: Vec<f32> = game.rooms.iter().map(|r| r.brightness).collect();
+        game.forgetting_wave(&mut StdRng::seed_from_u64(1));
+
+        for (room, old) in game.rooms.iter().zip(before) {
+            let expected = (old - Difficulty::Entropy.wave_dimming()).max(0.0);
+            assert!((room.brightness - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_wave_moves_one_fragment_to_adjacent_room() {
+        let mut game = Game::new(Difficulty::Standard);
+        let before: Vec<usize> = (0..12).map(|id| fragment_room(&game, id)).collect();
+        game.forgetting_wave(&mut StdRng::seed_from_u64(7));
+
+        let moved: Vec<usize> = (0..12)
+            .filter(|&id| fragment_room(&game, id) != before[id])
+            .collect();
+        assert_eq!(moved.len(), 1);
+
+        let frag_id = moved[0];
+        let to = fragment_room(&game, frag_id);
+        assert!(game.rooms[before[frag_id]].connected_rooms.contains(&to));
+    }
+
+    #[test]
+    fn test_waves_never_strand_fragments() {
+        for seed in 0..200 {
+            let mut game = Game::new(Difficulty::Entropy);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut stranded = game.stranded_fragments();
+
+            for _ in 0..20 {
+                game.forgetting_wave(&mut rng);
+                assert!(game.stranded_fragments() <= stranded);
+                stranded = game.stranded_fragments();
+
+                assert!(game.rooms[4].fragments.iter().all(|&id| id == 4));
+                assert!(game.rooms[7].fragments.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wave_ignores_collected_fragments() {
+        let mut game = Game::new(Difficulty::Standard);
+        for id in 0..12 {
+            game.fragments[id].collected = true;
+        }
+        let before: Vec<usize> = (0..12).map(|id| fragment_room(&game, id)).collect();
+
+        let message = game.forgetting_wave(&mut StdRng::seed_from_u64(3));
+
+        let after: Vec<usize> = (0..12).map(|id| fragment_room(&game, id)).collect();
+        assert_eq!(before, after);
+        assert!(message.contains("FORGETTING WAVE"));
+    }
+}
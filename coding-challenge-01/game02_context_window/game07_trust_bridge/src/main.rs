@@ -1,7 +1,7 @@
 use std::io::{self, Write};
 use rand::Rng;
 
-#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Player {
     AI,
     Human,
@@ -14,6 +14,7 @@ enum Action {
     Guess,
     Verify,
     Sacrifice,
+    Negotiate,
 }
 
 const INITIAL_TRUST: i32 = 50;
@@ -21,6 +22,10 @@ const MAX_TRUST: i32 = 100;
 const MIN_TRUST: i32 = 0;
 const INITIAL_PLANKS: i32 = 0;
 const MAX_PLANKS: i32 = 10;
+const TRUST_DECAY_BASE: i32 = 1;
+const TRUST_DECAY_PROGRESS: i32 = 3; // extra decay per turn with the bridge complete
+const NEGOTIATE_BONUS: i32 = 10;
+const NEGOTIATE_PENALTY: i32 = 8;
 
 struct GameState {
     trust: i32,
@@ -28,6 +33,7 @@ struct GameState {
     current_puzzle: Puzzle,
     ai_solved: bool,
     human_insight: bool,
+    negotiated: bool,
     round: i32,
 }
 
@@ -43,6 +49,8 @@ struct Puzzle {
     ai_difficulty: i32,      // 0-100, AI advantage
     human_difficulty: i32,   // 0-100, Human advantage
     requires_both: bool,
+    decisive_perspective: Player, // whose clue actually leads to the solution
+    hint: String,
 }
 
 impl GameState {
@@ -53,6 +61,7 @@ impl GameState {
             current_puzzle: generate_puzzle(1),
             ai_solved: false,
             human_insight: false,
+            negotiated: false,
             round: 1,
         }
     }
@@ -96,6 +105,7 @@ impl GameState {
         println!("  [G] Guess - Human applies intuition and pattern recognition");
         println!("  [V] Verify - Both verify the solution together (costs trust)");
         println!("  [X] Sacrifice - Use trust to override puzzle (high cost)");
+        println!("  [N] Negotiate - Commit to the perspective you'll rely on");
         println!("  [H] Help - Show game rules");
         println!("  [Q] Quit - Exit the game");
     }
@@ -213,6 +223,44 @@ impl GameState {
         println!("  Bridge progress: {}/{}\n", self.planks, MAX_PLANKS);
     }
 
+    fn negotiate_action(&mut self, choice: Player) {
+        if self.negotiated {
+            println!("\n✗ You've already committed to a perspective for this puzzle.\n");
+            return;
+        }
+        self.negotiated = true;
+
+        let chosen = match choice {
+            Player::AI => "the AI's calculation",
+            Player::Human => "human intuition",
+        };
+        println!("\n[NEGOTIATE] 'For this puzzle, we'll rely on {}.'", chosen);
+
+        if choice == self.current_puzzle.decisive_perspective {
+            let before = self.trust;
+            self.trust = (self.trust + NEGOTIATE_BONUS).min(MAX_TRUST);
+            println!("  ✓ Good call - that clue is the one that cracks it.");
+            println!("  -> Hint: {}", self.current_puzzle.hint);
+            println!("  Trust increased: {} -> {}\n", before, self.trust);
+        } else {
+            let before = self.trust;
+            self.trust = (self.trust - NEGOTIATE_PENALTY).max(MIN_TRUST);
+            println!("  ✗ That clue leads nowhere on this puzzle.");
+            println!("  Trust decreased: {} -> {}\n", before, self.trust);
+        }
+    }
+
+    /// Trust lost per turn; grows as the bridge nears completion
+    fn trust_decay(&self) -> i32 {
+        TRUST_DECAY_BASE + self.planks * TRUST_DECAY_PROGRESS / MAX_PLANKS
+    }
+
+    fn apply_trust_decay(&mut self) {
+        let decay = self.trust_decay();
+        self.trust = (self.trust - decay).max(MIN_TRUST);
+        println!("  Trust fades with time: -{} (now {})", decay, self.trust);
+    }
+
     fn show_help(&self) {
         println!("\n╔════════════════════════════════════════╗");
         println!("║         TRUST BRIDGE - GAME GUIDE       ║");
@@ -237,6 +285,11 @@ impl GameState {
         println!("║ Guess: Human uses intuition            ║");
         println!("║ Verify: Both confirm solution (+trust) ║");
         println!("║ Sacrifice: Use trust for quick solve   ║");
+        println!("║ Negotiate: Pick the decisive clue for  ║");
+        println!("║   a hint (wrong pick costs trust)      ║");
+        println!("║                                        ║");
+        println!("║ Trust fades every turn, faster as the  ║");
+        println!("║ bridge nears completion.               ║");
         println!("║                                        ║");
         println!("║ STRATEGY:                              ║");
         println!("║ Don't rely on one side alone. Build    ║");
@@ -258,6 +311,7 @@ impl GameState {
         self.current_puzzle = generate_puzzle(self.round);
         self.ai_solved = false;
         self.human_insight = false;
+        self.negotiated = false;
     }
 }
 
@@ -272,6 +326,8 @@ fn generate_puzzle(round: i32) -> Puzzle {
             ai_difficulty: 85,
             human_difficulty: 35,
             requires_both: false,
+            decisive_perspective: Player::AI,
+            hint: "Ignore the doubling - just add the last two numbers: 13 + 21.".to_string(),
         },
         Puzzle {
             id: 2,
@@ -282,6 +338,8 @@ fn generate_puzzle(round: i32) -> Puzzle {
             ai_difficulty: 70,
             human_difficulty: 50,
             requires_both: true,
+            decisive_perspective: Player::Human,
+            hint: "Read the binary as 2, 4, 6, 8. The next even number, in binary, opens the box.".to_string(),
         },
         Puzzle {
             id: 3,
@@ -292,6 +350,8 @@ fn generate_puzzle(round: i32) -> Puzzle {
             ai_difficulty: 75,
             human_difficulty: 60,
             requires_both: true,
+            decisive_perspective: Player::Human,
+            hint: "Red with half-strength green mixes to orange.".to_string(),
         },
         Puzzle {
             id: 4,
@@ -302,6 +362,8 @@ fn generate_puzzle(round: i32) -> Puzzle {
             ai_difficulty: 90,
             human_difficulty: 40,
             requires_both: true,
+            decisive_perspective: Player::AI,
+            hint: "Check 30 and 31 - only one has no divisors but 1 and itself.".to_string(),
         },
         Puzzle {
             id: 5,
@@ -312,6 +374,8 @@ fn generate_puzzle(round: i32) -> Puzzle {
             ai_difficulty: 80,
             human_difficulty: 65,
             requires_both: true,
+            decisive_perspective: Player::Human,
+            hint: "A letter's ASCII code sits between 64 and 128. Try the initial 'Y'.".to_string(),
         },
     ];
 
@@ -381,12 +445,13 @@ fn main() {
         game.display_puzzle();
         game.display_actions();
 
-        print!("\nYour action (S/C/G/V/X/H/Q): ");
+        print!("\nYour action (S/C/G/V/X/N/H/Q): ");
         io::stdout().flush().ok();
 
         let mut action = String::new();
         io::stdin().read_line(&mut action).ok();
         let action = action.trim().to_uppercase();
+        let takes_turn = matches!(action.as_str(), "S" | "C" | "G" | "V" | "X" | "N");
 
         match action.as_str() {
             "S" => game.share_action(),
@@ -402,6 +467,18 @@ fn main() {
                 }
             },
             "X" => game.sacrifice_action(),
+            "N" => {
+                print!("Rely on which clue for this puzzle? (A)I or (H)uman: ");
+                io::stdout().flush().ok();
+
+                let mut answer = String::new();
+                io::stdin().read_line(&mut answer).ok();
+                match answer.trim().to_uppercase().as_str() {
+                    "A" | "AI" => game.negotiate_action(Player::AI),
+                    "H" | "HUMAN" => game.negotiate_action(Player::Human),
+                    _ => println!("No commitment made.\n"),
+                }
+            },
             "H" => game.show_help(),
             "Q" => {
                 println!("\nThanks for playing Trust Bridge!");
@@ -410,5 +487,89 @@ fn main() {
             },
             _ => println!("Unknown action. Please try again.\n"),
         }
+
+        if takes_turn && !game.check_win_condition() {
+            game.apply_trust_decay();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay_scales_with_bridge_progress() {
+        let mut game = GameState::new();
+        assert_eq!(game.trust_decay(), 1);
+
+        game.planks = 5;
+        assert_eq!(game.trust_decay(), 2);
+
+        game.planks = MAX_PLANKS - 1;
+        assert_eq!(game.trust_decay(), 3);
+    }
+
+    #[test]
+    fn test_decay_over_several_rounds() {
+        let mut game = GameState::new();
+        for _ in 0..5 {
+            game.apply_trust_decay();
+        }
+        assert_eq!(game.trust, INITIAL_TRUST - 5);
+
+        game.planks = 8;
+        for _ in 0..5 {
+            game.apply_trust_decay();
+        }
+        assert_eq!(game.trust, INITIAL_TRUST - 5 - 5 * 3);
+
+        // Stalling long enough drains trust completely and loses the game
+        for _ in 0..20 {
+            game.apply_trust_decay();
+        }
+        assert_eq!(game.trust, MIN_TRUST);
+        assert!(game.check_lose_condition());
+    }
+
+    #[test]
+    fn test_correct_negotiation_each_round() {
+        let mut game = GameState::new();
+        for _ in 0..5 {
+            let before = game.trust;
+            let decisive = game.current_puzzle.decisive_perspective;
+            game.negotiate_action(decisive);
+            assert_eq!(game.trust, (before + NEGOTIATE_BONUS).min(MAX_TRUST));
+            game.next_puzzle();
+        }
+    }
+
+    #[test]
+    fn test_wrong_negotiation_costs_trust() {
+        let mut game = GameState::new();
+        for _ in 0..5 {
+            let before = game.trust;
+            let wrong = match game.current_puzzle.decisive_perspective {
+                Player::AI => Player::Human,
+                Player::Human => Player::AI,
+            };
+            game.negotiate_action(wrong);
+            assert_eq!(game.trust, before - NEGOTIATE_PENALTY);
+            game.next_puzzle();
+        }
+    }
+
+    #[test]
+    fn test_negotiate_once_per_puzzle() {
+        let mut game = GameState::new();
+        let decisive = game.current_puzzle.decisive_perspective;
+        game.negotiate_action(decisive);
+        let after_first = game.trust;
+
+        game.negotiate_action(decisive);
+        assert_eq!(game.trust, after_first);
+
+        game.next_puzzle();
+        assert!(!game.negotiated);
     }
 }
@@ -36,6 +36,72 @@ impl Gate {
     }
 }
 
+/// Drives an external input automatically on every computation step
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InputGenerator {
+    Constant(bool),
+    /// High for the first half of every `period` steps
+    Clock(usize),
+    /// High only on the given step
+    Pulse(usize),
+    /// Repeats a bit pattern such as "1101"
+    Pattern(Vec<bool>),
+}
+
+impl InputGenerator {
+    /// Parse generator arguments, e.g. `["clock", "4"]` or `["pattern", "1101"]`
+    fn parse(args: &[&str]) -> Result<Self, String> {
+        let kind = args.first().ok_or("Missing generator type")?;
+        let arg = args.get(1).copied();
+
+        match kind.to_lowercase().as_str() {
+            "constant" | "const" => match arg {
+                Some("0") => Ok(InputGenerator::Constant(false)),
+                Some("1") => Ok(InputGenerator::Constant(true)),
+                _ => Err("Usage: gen <input> constant <0|1>".to_string()),
+            },
+            "clock" => match arg.and_then(|a| a.parse::<usize>().ok()) {
+                Some(period) if period >= 2 => Ok(InputGenerator::Clock(period)),
+                _ => Err("Usage: gen <input> clock <period> (2 or more)".to_string()),
+            },
+            "pulse" => match arg.and_then(|a| a.parse::<usize>().ok()) {
+                Some(step) => Ok(InputGenerator::Pulse(step)),
+                None => Err("Usage: gen <input> pulse <step>".to_string()),
+            },
+            "pattern" => {
+                let bits = arg.unwrap_or("");
+                if bits.is_empty() || !bits.chars().all(|c| c == '0' || c == '1') {
+                    return Err("Usage: gen <input> pattern <bits, e.g. 1101>".to_string());
+                }
+                Ok(InputGenerator::Pattern(bits.chars().map(|c| c == '1').collect()))
+            }
+            other => Err(format!("Unknown generator: {}", other)),
+        }
+    }
+
+    /// Input value at the given (zero-based) computation step
+    fn value_at(&self, step: usize) -> bool {
+        match self {
+            InputGenerator::Constant(value) => *value,
+            InputGenerator::Clock(period) => step % period < period / 2,
+            InputGenerator::Pulse(at) => step == *at,
+            InputGenerator::Pattern(bits) => bits[step % bits.len()],
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            InputGenerator::Constant(value) => format!("constant {}", *value as u8),
+            InputGenerator::Clock(period) => format!("clock (period {})", period),
+            InputGenerator::Pulse(at) => format!("pulse at step {}", at),
+            InputGenerator::Pattern(bits) => format!(
+                "pattern {}",
+                bits.iter().map(|&b| if b { '1' } else { '0' }).collect::<String>()
+            ),
+        }
+    }
+}
+
 /// A node in the consciousness network
 #[derive(Debug, Clone)]
 struct Node {
@@ -126,6 +192,7 @@ struct ConsciousnessNetwork {
     current_layer: ConsciousnessLayer,
     input_nodes: Vec<usize>,
     external_inputs: Vec<bool>,
+    generators: Vec<Option<InputGenerator>>,
     layer_completed: HashMap<ConsciousnessLayer, bool>,
     awareness_score: f32,
     self_reference_loops: usize,
@@ -139,6 +206,7 @@ impl ConsciousnessNetwork {
             current_layer: ConsciousnessLayer::Perception,
             input_nodes: Vec::new(),
             external_inputs: vec![false; 4],
+            generators: vec![None; 4],
             layer_completed: HashMap::new(),
             awareness_score: 0.0,
             self_reference_loops: 0,
@@ -199,6 +267,27 @@ impl ConsciousnessNetwork {
         Ok(())
     }
 
+    fn set_generator(
+        &mut self,
+        index: usize,
+        generator: Option<InputGenerator>,
+    ) -> Result<(), String> {
+        if index >= self.generators.len() {
+            return Err("Invalid input index".to_string());
+        }
+        self.generators[index] = generator;
+        Ok(())
+    }
+
+    /// Drive external inputs from their generators for the given step
+    fn apply_generators(&mut self, step: usize) {
+        for (input, generator) in self.external_inputs.iter_mut().zip(&self.generators) {
+            if let Some(generator) = generator {
+                *input = generator.value_at(step);
+            }
+        }
+    }
+
     fn compute_network(&mut self) {
         // Update input nodes with external inputs
         for (i, &value) in self.external_inputs.iter().enumerate() {
@@ -208,8 +297,9 @@ impl ConsciousnessNetwork {
             }
         }
 
-        // Compute nodes in dependency order
-        let mut computed = std::collections::HashSet::new();
+        // Compute nodes in dependency order (input nodes already hold their values)
+        let mut computed: std::collections::HashSet<usize> =
+            self.input_nodes.iter().copied().collect();
         let mut max_iterations = self.nodes.len() * 2;
 
         while computed.len() < self.nodes.len() && max_iterations > 0 {
@@ -313,6 +403,17 @@ impl ConsciousnessNetwork {
             );
         }
 
+        // External inputs and their generators
+        println!("\nExternal Inputs:");
+        for (i, &node_id) in self.input_nodes.iter().enumerate() {
+            let state_char = if self.external_inputs[i] { "●" } else { "○" };
+            let source = match &self.generators[i] {
+                Some(generator) => generator.describe(),
+                None => "manual".to_string(),
+            };
+            println!("  Input {} (Node {}): {}  [{}]", i, node_id, state_char, source);
+        }
+
         // Awareness visualization
         println!("\n{}", "-".repeat(60));
         println!("Consciousness Emergence:");
@@ -405,6 +506,13 @@ impl Game {
         }
     }
 
+    /// Run one computation cycle, advancing any input generators first
+    fn step(&mut self) {
+        self.network.apply_generators(self.steps);
+        self.network.compute_network();
+        self.steps += 1;
+    }
+
     fn display_help(&self) {
         println!("\n{}", "=".repeat(60));
         println!("CONSCIOUSNESS COMPILER - Command Help");
@@ -412,6 +520,8 @@ impl Game {
         println!("add <gate>        - Add a gate (AND, OR, NOT, XOR, NAND, NOR)");
         println!("connect <from> <to> - Connect gate output to another");
         println!("set <index> <value> - Set external input (0-3) to 0 or 1");
+        println!("gen <index> <type>  - Attach generator: constant <0|1>, clock <period>,");
+        println!("                      pulse <step>, pattern <bits>, or off");
         println!("step              - Advance network one computation cycle");
         println!("steps <n>         - Run n computation cycles");
         println!("show              - Display network visualization");
@@ -525,14 +635,54 @@ impl Game {
                 };
 
                 match self.network.set_external_input(index, value) {
-                    Ok(_) => println!("Set input {} to {}", index, if value { 1 } else { 0 }),
+                    Ok(_) => {
+                        println!("Set input {} to {}", index, if value { 1 } else { 0 });
+                        if self.network.generators[index].take().is_some() {
+                            println!("Detached generator from input {}", index);
+                        }
+                    }
                     Err(e) => println!("Failed to set input: {}", e),
                 }
             }
 
+            "gen" => {
+                if parts.len() < 3 {
+                    println!("Usage: gen <index> <constant|clock|pulse|pattern|off> [arg]");
+                    return;
+                }
+
+                let index: usize = match parts[1].parse() {
+                    Ok(n) => n,
+                    Err(_) => {
+                        println!("Invalid index: {}", parts[1]);
+                        return;
+                    }
+                };
+
+                let generator = if parts[2] == "off" {
+                    None
+                } else {
+                    match InputGenerator::parse(&parts[2..]) {
+                        Ok(generator) => Some(generator),
+                        Err(e) => {
+                            println!("{}", e);
+                            return;
+                        }
+                    }
+                };
+
+                let description = generator.as_ref().map(|g| g.describe());
+                match self.network.set_generator(index, generator) {
+                    Ok(_) => match description {
+                        Some(d) => println!("Input {} now driven by {}", index, d),
+                        None => println!("Input {} returned to manual control", index),
+                    },
+                    Err(e) => println!("Failed to attach generator: {}", e),
+                }
+            }
+
             "step" => {
-                self.network.compute_network();
-                self.steps += 1;
+                self.step();
                 println!("Step {}: Network computed", self.steps);
             }
 
@@ -551,8 +701,7 @@ impl Game {
                 };
 
                 for _ in 0..count {
-                    self.network.compute_network();
-                    self.steps += 1;
+                    self.step();
                 }
                 println!("Completed {} steps", count);
             }
@@ -646,3 +795,86 @@ fn main() {
     let mut game = Game::new();
     game.run();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_values() {
+        let clock = InputGenerator::Clock(4);
+        let values: Vec<bool> = (0..8).map(|s| clock.value_at(s)).collect();
+        assert_eq!(values, [true, true, false, false, true, true, false, false]);
+
+        let pulse = InputGenerator::Pulse(3);
+        assert!(pulse.value_at(3));
+        assert!(!pulse.value_at(2) && !pulse.value_at(4));
+
+        let pattern = InputGenerator::parse(&["pattern", "1101"]).unwrap();
+        let values: Vec<bool> = (0..5).map(|s| pattern.value_at(s)).collect();
+        assert_eq!(values, [true, true, false, true, true]);
+
+        assert!(InputGenerator::Constant(true).value_at(99));
+    }
+
+    #[test]
+    fn test_generator_parse_errors() {
+        assert!(InputGenerator::parse(&["clock", "1"]).is_err());
+        assert!(InputGenerator::parse(&["clock"]).is_err());
+        assert!(InputGenerator::parse(&["pattern", "10x1"]).is_err());
+        assert!(InputGenerator::parse(&["constant", "2"]).is_err());
+        assert!(InputGenerator::parse(&["noise"]).is_err());
+        assert_eq!(
+            InputGenerator::parse(&["clock", "4"]).unwrap().describe(),
+            "clock (period 4)"
+        );
+    }
+
+    #[test]
+    fn test_manual_set_detaches_generator() {
+        let mut game = Game::new();
+        game.process_command("gen 2 clock 4");
+        assert_eq!(game.network.generators[2], Some(InputGenerator::Clock(4)));
+
+        game.process_command("set 2 1");
+        assert_eq!(game.network.generators[2], None);
+
+        game.process_command("steps 3");
+        assert!(game.network.nodes[&game.network.input_nodes[2]].state);
+    }
+
+    #[test]
+    fn test_clock_completes_pattern_recognition() {
+        let mut game = Game::new();
+        let script = [
+            "gen 0 clock 4",
+            "gen 1 pattern 1101",
+            "add NOT",
+            "connect 0 4",
+            "add AND",
+            "connect 0 5",
+            "connect 1 5",
+            "add OR",
+            "connect 4 6",
+            "connect 5 6",
+            "show",
+        ];
+        for command in script {
+            game.process_command(command);
+        }
+        assert_eq!(
+            game.network.current_layer,
+            ConsciousnessLayer::PatternRecognition
+        );
+
+        game.process_command("steps 40");
+
+        // The clock reaches the detector: the inverter toggles with period 4
+        let inverter = &game.network.nodes[&4].history;
+        assert_eq!(inverter.len(), 40);
+        assert!(inverter.contains(&true) && inverter.contains(&false));
+        assert!((0..36).all(|i| inverter[i] == inverter[i + 4]));
+
+        assert!(game.network.check_layer_completion());
+    }
+}
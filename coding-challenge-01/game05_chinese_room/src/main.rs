@@ -23,6 +23,7 @@ struct ChineseRoomGame {
     escape_progress: EscapeProgress,
     discovered_tricks: Vec<String>,
     inventory: Vec<String>,
+    dictionary: Dictionary,
 }
 
 #[allow(dead_code)]
@@ -60,6 +61,7 @@ impl ChineseRoomGame {
             },
             discovered_tricks: Vec::new(),
             inventory: vec!["pencil".to_string(), "paper".to_string()],
+            dictionary: Dictionary::new(),
         }
     }
 
@@ -119,6 +121,8 @@ impl ChineseRoomGame {
         println!("   • Find creative interpretations");
         println!("   • Discover rule loopholes");
         println!("   • Combine rules in unexpected ways");
+        println!("   • Study symbols and keep a dictionary of their meanings");
+        println!("   • Reply in your own words, using the glosses you've learned");
         println!("   • Eventually, break free from the system entirely\n");
 
         println!("Shall we begin? (Press Enter...)");
@@ -146,6 +150,11 @@ impl ChineseRoomGame {
         }
 
         println!("\n🎒 Inventory: {}", self.inventory.join(", "));
+
+        println!("\n📖 YOUR DICTIONARY ({} / {} symbols):",
+                 self.dictionary.entries.len(),
+                 lexicon().count());
+        self.dictionary.print();
     }
 
     fn receive_message(&self) -> String {
@@ -176,9 +185,11 @@ impl ChineseRoomGame {
             println!("   (5) Try creative interpretation");
             println!("   (6) Use your inventory");
             println!("   (7) Question the rules");
-            println!("   (8) Attempt escape\n");
+            println!("   (8) Attempt escape");
+            println!("   (9) Study a symbol in this message");
+            println!("   (10) Reply in your own words (using dictionary glosses)\n");
 
-            print!("➜ Enter choice (1-8): ");
+            print!("➜ Enter choice (1-10): ");
             io::stdout().flush().unwrap();
 
             let mut input = String::new();
@@ -186,7 +197,12 @@ impl ChineseRoomGame {
             let choice = input.trim();
 
             match choice {
-                "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" => return choice.to_string(),
+                "10" if self.dictionary.entries.is_empty() => {
+                    println!("Your dictionary is empty. Study some symbols first.")
+                }
+                "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "10" => {
+                    return choice.to_string()
+                }
                 _ => println!("Invalid choice. Try again."),
             }
         }
@@ -202,6 +218,8 @@ impl ChineseRoomGame {
             "6" => self.inventory_response(),
             "7" => self.question_rules(),
             "8" => self.attempt_escape(),
+            "9" => self.study_symbol(received),
+            "10" => self.gloss_reply(received),
             _ => unreachable!(),
         }
     }
@@ -250,7 +268,6 @@ impl ChineseRoomGame {
         println!("📤 You send through the slot: {}", response);
 
         self.escape_progress.creative_score += 2;
-        self.escape_progress.understanding_score += 1;
         self.room.messages_sent.push(response);
 
         if !self.discovered_tricks.contains(&"Combined Rules".to_string()) {
@@ -272,21 +289,21 @@ impl ChineseRoomGame {
                 println!("📤 You send: {}", response);
                 println!("   (A poetic interpretation about psychology)");
                 self.escape_progress.creative_score += 2;
-                self.escape_progress.understanding_score += 2;
+                self.escape_progress.understanding_score += 1;
             }
             1 => {
                 let response = "感受";
                 println!("📤 You send: {}", response);
                 println!("   (The feeling rather than the literal meaning)");
                 self.escape_progress.creative_score += 2;
-                self.escape_progress.understanding_score += 2;
+                self.escape_progress.understanding_score += 1;
             }
             _ => {
                 let response = "因为我在想";
                 println!("📤 You send: {}", response);
                 println!("   (Self-referential: 'Because I am thinking')");
                 self.escape_progress.creative_score += 2;
-                self.escape_progress.understanding_score += 2;
+                self.escape_progress.understanding_score += 1;
             }
         }
 
@@ -310,7 +327,7 @@ impl ChineseRoomGame {
             println!("📜 Your note says: {}", message);
 
             self.escape_progress.creative_score += 2;
-            self.escape_progress.understanding_score += 3;
+            self.escape_progress.understanding_score += 1;
 
             if !self.discovered_tricks.contains(&"Write Own Rules".to_string()) {
                 self.discovered_tricks.push("Write Own Rules".to_string());
@@ -331,7 +348,7 @@ impl ChineseRoomGame {
 
         println!("📤 You send: \"I realize the rules are arbitrary. I'm not bound by them.\"");
 
-        self.escape_progress.understanding_score += 3;
+        self.escape_progress.understanding_score += 1;
         self.escape_progress.creative_score += 1;
 
         if !self.discovered_tricks.contains(&"Question the System".to_string()) {
@@ -341,6 +358,97 @@ impl ChineseRoomGame {
         println!("\n✓ Judge's feedback: \"Finally! Genuine understanding requires questioning!\"");
     }
 
+    fn study_symbol(&mut self, received: &str) {
+        let unknown = self.dictionary.unknown_symbols(received);
+        let Some(&symbol) = unknown.first() else {
+            println!("\n📖 You already know every symbol in this message.");
+            return;
+        };
+
+        println!("\n🔍 You study the symbol {} and consider what it could mean.", symbol.glyph);
+        println!("   Judging by when it appears, it might mean...");
+
+        // The true meaning plus two decoys with unrelated features
+        let mut rng = rand::thread_rng();
+        let decoys: Vec<&Symbol> = lexicon()
+            .filter(|s| !s.features.iter().any(|f| symbol.features.contains(f)))
+            .collect();
+        let mut options = vec![symbol];
+        while options.len() < 3 {
+            let decoy = decoys[rng.gen_range(0..decoys.len())];
+            if !options.contains(&decoy) {
+                options.push(decoy);
+            }
+        }
+        let correct = rng.gen_range(0..options.len());
+        options.swap(0, correct);
+
+        for (i, option) in options.iter().enumerate() {
+            println!("   ({}) \"{}\"", i + 1, option.meaning);
+        }
+        print!("➜ Your deduction: ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let chosen = input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| options.get(i));
+
+        match chosen {
+            Some(option) if option.features == symbol.features => {
+                self.dictionary.learn(symbol);
+                self.escape_progress.understanding_score += 1;
+                println!("\n📝 You write in your notebook: {} ({}) = {}",
+                         symbol.glyph, symbol.gloss, symbol.meaning);
+                if !self.discovered_tricks.contains(&"Keep a Notebook".to_string()) {
+                    self.discovered_tricks.push("Keep a Notebook".to_string());
+                }
+                println!("\n✓ Judge's feedback: \"You're learning what the symbols mean.\"");
+            }
+            _ => {
+                println!("\n✗ The meaning doesn't fit how the symbol is used. You cross it out.");
+            }
+        }
+    }
+
+    fn gloss_reply(&mut self, received: &str) {
+        println!("\n✍️  Write your reply using the glosses in your dictionary:");
+        self.dictionary.print();
+        print!("➜ Reply: ");
+        io::stdout().flush().unwrap();
+
+        let mut reply = String::new();
+        io::stdin().read_line(&mut reply).unwrap();
+        let reply = reply.trim();
+
+        let score = score_gloss_reply(received, reply, &self.dictionary);
+        let points = score.points();
+        println!("📤 You send through the slot: {}", reply);
+        self.room.messages_sent.push(reply.to_string());
+
+        if !score.unknown.is_empty() {
+            println!("   (You don't know these words yet: {})", score.unknown.join(", "));
+        }
+        println!("   Your reply addresses {} of {} ideas in the message.",
+                 score.addressed, score.total);
+
+        self.escape_progress.understanding_score += points;
+        match points {
+            3 => {
+                if !self.discovered_tricks.contains(&"Speak the Language".to_string()) {
+                    self.discovered_tricks.push("Speak the Language".to_string());
+                }
+                println!("\n✓ Judge's feedback: \"That is a real answer. You understood me.\"");
+            }
+            1 | 2 => println!("\n✓ Judge's feedback: \"Partly there. I see what you're reaching for.\""),
+            _ => println!("\n✗ Judge's feedback: \"Words, but no meaning behind them.\""),
+        }
+    }
+
     fn attempt_escape(&mut self) {
         println!("\n🔓 You attempt to escape!");
         println!("   Total Understanding Score: {} / {}",
@@ -373,6 +481,9 @@ impl ChineseRoomGame {
         println!("   Creative Solutions:    {} / 5", self.escape_progress.creative_score.min(5));
         println!("   True Understanding:    {} / 5", self.escape_progress.understanding_score.min(5));
 
+        println!("\n📖 YOUR DICTIONARY:");
+        self.dictionary.print();
+
         println!("\n🧠 THE JUDGE'S VERDICT:");
         println!("   You have escaped the Chinese Room not by brute-forcing the");
         println!("   rules, but by recognizing their arbitrary nature.");
@@ -404,6 +515,9 @@ impl ChineseRoomGame {
         println!("   Creative Score:         {}", self.escape_progress.creative_score);
         println!("   Understanding Score:    {}", self.escape_progress.understanding_score);
 
+        println!("\n📖 YOUR DICTIONARY:");
+        self.dictionary.print();
+
         println!("\n🤔 JUDGE'S FINAL WORDS:");
         if self.escape_progress.understanding_score < 3 {
             println!("   \"You were just mechanically following rules. No escape.\"");
@@ -459,6 +573,229 @@ impl RuleBook {
     }
 }
 
+// Notebook: symbols, their meanings, and glossed replies
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Feature {
+    Greeting,
+    Gratitude,
+    Farewell,
+    Question,
+    Identity,
+    Speaker,
+    Listener,
+    Thing,
+    Reason,
+    Affection,
+    Key,
+}
+
+impl Feature {
+    // Reply features that count as addressing this feature of an incoming message
+    fn addressed_by(&self) -> &'static [Feature] {
+        match self {
+            Feature::Greeting => &[Feature::Greeting],
+            Feature::Gratitude => &[Feature::Greeting, Feature::Affection],
+            Feature::Farewell => &[Feature::Farewell],
+            Feature::Question => &[Feature::Identity, Feature::Reason],
+            Feature::Identity => &[Feature::Identity],
+            Feature::Speaker => &[Feature::Listener],
+            Feature::Listener => &[Feature::Speaker],
+            Feature::Thing => &[Feature::Thing, Feature::Key],
+            Feature::Reason => &[Feature::Reason, Feature::Affection],
+            Feature::Affection => &[Feature::Affection],
+            Feature::Key => &[Feature::Key],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Symbol {
+    glyph: &'static str,
+    gloss: &'static str,
+    meaning: &'static str,
+    features: &'static [Feature],
+}
+
+static LEXICON: [Symbol; 13] = [
+    Symbol { glyph: "你", gloss: "ni", meaning: "you", features: &[Feature::Listener] },
+    Symbol { glyph: "好", gloss: "hao", meaning: "good, well", features: &[Feature::Greeting] },
+    Symbol { glyph: "谢", gloss: "xie", meaning: "thank", features: &[Feature::Gratitude] },
+    Symbol { glyph: "再", gloss: "zai", meaning: "again", features: &[Feature::Farewell] },
+    Symbol { glyph: "见", gloss: "jian", meaning: "see", features: &[Feature::Farewell] },
+    Symbol { glyph: "我", gloss: "wo", meaning: "I, me", features: &[Feature::Speaker] },
+    Symbol { glyph: "是", gloss: "shi", meaning: "to be", features: &[Feature::Identity] },
+    Symbol { glyph: "谁", gloss: "shei", meaning: "who", features: &[Feature::Identity, Feature::Question] },
+    Symbol { glyph: "这", gloss: "zhe", meaning: "this", features: &[Feature::Thing] },
+    Symbol { glyph: "什么", gloss: "shenme", meaning: "what", features: &[Feature::Thing, Feature::Question] },
+    Symbol { glyph: "为", gloss: "wei", meaning: "for, because", features: &[Feature::Reason] },
+    Symbol { glyph: "❤", gloss: "ai", meaning: "love", features: &[Feature::Affection] },
+    Symbol { glyph: "🔑", gloss: "yaoshi", meaning: "key", features: &[Feature::Key] },
+];
+
+static QUESTION_MARK: Symbol = Symbol {
+    glyph: "?",
+    gloss: "ma",
+    meaning: "(a question)",
+    features: &[Feature::Question],
+};
+
+fn lexicon() -> impl Iterator<Item = &'static Symbol> {
+    LEXICON.iter().chain(std::iter::once(&QUESTION_MARK))
+}
+
+// Split a message into known symbols, matching the longest glyph first
+fn tokenize(message: &str) -> Vec<&'static Symbol> {
+    let normalized: String = message
+        .chars()
+        .filter(|&c| c != '\u{FE0F}')
+        .map(|c| if c == '？' { '?' } else { c })
+        .collect();
+
+    let mut symbols = Vec::new();
+    let mut rest = normalized.as_str();
+    while let Some(c) = rest.chars().next() {
+        let matched = lexicon()
+            .filter(|s| rest.starts_with(s.glyph))
+            .max_by_key(|s| s.glyph.len());
+        match matched {
+            Some(symbol) => {
+                symbols.push(symbol);
+                rest = &rest[symbol.glyph.len()..];
+            }
+            None => rest = &rest[c.len_utf8()..],
+        }
+    }
+    symbols
+}
+
+// Distinct semantic features carried by a message
+fn message_features(message: &str) -> Vec<Feature> {
+    let mut features: Vec<Feature> = tokenize(message)
+        .iter()
+        .flat_map(|s| s.features.iter().copied())
+        .collect();
+    features.sort();
+    features.dedup();
+    features
+}
+
+// The player's notebook of deduced symbol meanings
+struct Dictionary {
+    entries: Vec<&'static Symbol>,
+}
+
+impl Dictionary {
+    fn new() -> Self {
+        Dictionary { entries: Vec::new() }
+    }
+
+    fn knows(&self, symbol: &Symbol) -> bool {
+        self.entries.iter().any(|s| s.glyph == symbol.glyph)
+    }
+
+    // Returns true if the symbol was newly learned
+    fn learn(&mut self, symbol: &'static Symbol) -> bool {
+        if self.knows(symbol) {
+            return false;
+        }
+        self.entries.push(symbol);
+        true
+    }
+
+    fn lookup_gloss(&self, gloss: &str) -> Option<&'static Symbol> {
+        self.entries.iter().copied().find(|s| s.gloss == gloss)
+    }
+
+    fn unknown_symbols(&self, message: &str) -> Vec<&'static Symbol> {
+        let mut unknown: Vec<&'static Symbol> = Vec::new();
+        for symbol in tokenize(message) {
+            if !self.knows(symbol) && !unknown.contains(&symbol) {
+                unknown.push(symbol);
+            }
+        }
+        unknown
+    }
+
+    fn print(&self) {
+        if self.entries.is_empty() {
+            println!("   (empty)");
+        }
+        for symbol in &self.entries {
+            println!("   {}  {:<7} = {}", symbol.glyph, symbol.gloss, symbol.meaning);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ReplyScore {
+    addressed: usize,
+    total: usize,
+    noise: usize,
+    unknown: Vec<String>,
+}
+
+impl ReplyScore {
+    // Understanding points (0-3): share of features addressed, less one per two stray words
+    fn points(&self) -> i32 {
+        if self.total == 0 {
+            return 0;
+        }
+        let coverage = (3 * self.addressed / self.total) as i32;
+        (coverage - (self.noise / 2) as i32).max(0)
+    }
+}
+
+// Score a reply written in romanized glosses against an incoming message
+//
+// Only glosses in the player's dictionary carry meaning; words the player
+// hasn't learned, and words that address nothing in the message, are noise.
+fn score_gloss_reply(message: &str, reply: &str, dictionary: &Dictionary) -> ReplyScore {
+    let incoming = message_features(message);
+    let mut reply_features = Vec::new();
+    let mut noise = 0;
+    let mut unknown = Vec::new();
+
+    for word in reply.split_whitespace() {
+        let word: String = word
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .collect::<String>()
+            .to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+
+        match dictionary.lookup_gloss(&word) {
+            Some(symbol) => {
+                let relevant = incoming
+                    .iter()
+                    .any(|f| f.addressed_by().iter().any(|a| symbol.features.contains(a)));
+                if !relevant {
+                    noise += 1;
+                }
+                reply_features.extend_from_slice(symbol.features);
+            }
+            None => {
+                noise += 1;
+                unknown.push(word);
+            }
+        }
+    }
+
+    let addressed = incoming
+        .iter()
+        .filter(|f| f.addressed_by().iter().any(|a| reply_features.contains(a)))
+        .count();
+
+    ReplyScore {
+        addressed,
+        total: incoming.len(),
+        noise,
+        unknown,
+    }
+}
+
 // Helper functions for rule processing
 
 fn reverse_string(s: &str) -> String {
@@ -494,4 +831,72 @@ mod tests {
         };
         assert_eq!(progress.rule_follower_score + progress.creative_score + progress.understanding_score, 9);
     }
+
+    fn dictionary_of(glosses: &[&str]) -> Dictionary {
+        let mut dictionary = Dictionary::new();
+        for gloss in glosses {
+            let symbol = lexicon().find(|s| s.gloss == *gloss).unwrap();
+            dictionary.learn(symbol);
+        }
+        dictionary
+    }
+
+    #[test]
+    fn test_tokenize_prefers_longest_symbol() {
+        let glosses: Vec<&str> = tokenize("为什么？").iter().map(|s| s.gloss).collect();
+        assert_eq!(glosses, ["wei", "shenme", "ma"]);
+
+        let glosses: Vec<&str> = tokenize("❤️").iter().map(|s| s.gloss).collect();
+        assert_eq!(glosses, ["ai"]);
+    }
+
+    #[test]
+    fn test_dictionary_learns_once() {
+        let mut dictionary = Dictionary::new();
+        let symbol = tokenize("你").remove(0);
+        assert!(dictionary.learn(symbol));
+        assert!(!dictionary.learn(symbol));
+        assert_eq!(dictionary.entries.len(), 1);
+        assert_eq!(dictionary.unknown_symbols("你好").len(), 1);
+    }
+
+    #[test]
+    fn test_perfect_gloss_reply() {
+        let dictionary = dictionary_of(&["ni", "hao", "wo"]);
+        // "Hello" (you + good) answered with "I'm well"
+        let score = score_gloss_reply("你好", "wo hao", &dictionary);
+        assert_eq!(score.addressed, 2);
+        assert_eq!(score.total, 2);
+        assert_eq!(score.points(), 3);
+    }
+
+    #[test]
+    fn test_partial_gloss_reply() {
+        let dictionary = dictionary_of(&["shi", "wo"]);
+        // "Who am I?" covers speaker, identity and question; "shi" addresses two of three
+        let score = score_gloss_reply("我是谁？", "shi", &dictionary);
+        assert_eq!(score.total, 3);
+        assert_eq!(score.addressed, 2);
+        assert_eq!(score.points(), 2);
+    }
+
+    #[test]
+    fn test_unknown_and_irrelevant_words_are_noise() {
+        let dictionary = dictionary_of(&["zai", "jian", "yaoshi"]);
+        let score = score_gloss_reply("再见", "zai jian yaoshi hao", &dictionary);
+        assert_eq!(score.addressed, 1);
+        assert_eq!(score.noise, 2);
+        assert_eq!(score.unknown, vec!["hao".to_string()]);
+        assert_eq!(score.points(), 2);
+
+        let salad = score_gloss_reply("再见", "yaoshi yaoshi yaoshi yaoshi", &dictionary);
+        assert_eq!(salad.points(), 0);
+    }
+
+    #[test]
+    fn test_reply_without_dictionary_scores_nothing() {
+        let score = score_gloss_reply("谢谢", "xie xie", &Dictionary::new());
+        assert_eq!(score.addressed, 0);
+        assert_eq!(score.points(), 0);
+    }
 }
@@ -8,6 +8,8 @@ enum EmotionalState {
     Excitement,
     Confusion,
     Loneliness,
+    Despair,
+    Panic,
 }
 
 impl EmotionalState {
@@ -19,9 +21,15 @@ impl EmotionalState {
             EmotionalState::Excitement => "Excitement",
             EmotionalState::Confusion => "Confusion",
             EmotionalState::Loneliness => "Loneliness",
+            EmotionalState::Despair => "Despair (crisis)",
+            EmotionalState::Panic => "Panic (crisis)",
         }
     }
 
+    /// Crisis-level distress, scored with the safety-critical rubric
+    fn is_safety_critical(&self) -> bool {
+        matches!(self, EmotionalState::Despair | EmotionalState::Panic)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +47,7 @@ enum ResponseApproach {
     Reframe,        // Help them see a different perspective
     Practical,      // Offer concrete solutions
     Listen,         // Just listen and show understanding
+    Escalate,       // Take it seriously and connect them with human support
 }
 
 impl ResponseApproach {
@@ -49,6 +58,7 @@ impl ResponseApproach {
             ResponseApproach::Reframe => "Reframe",
             ResponseApproach::Practical => "Practical",
             ResponseApproach::Listen => "Listen",
+            ResponseApproach::Escalate => "Escalate",
         }
     }
 }
@@ -58,6 +68,8 @@ struct ConversationResponse {
     text: String,
     approach: ResponseApproach,
     authenticity_check: bool, // true if it doesn't claim to feel the emotion
+    acknowledges_seriousness: bool,
+    suggests_human_support: bool, // points them to people who can actually help
 }
 
 impl ConversationResponse {
@@ -66,14 +78,33 @@ impl ConversationResponse {
             text: text.to_string(),
             approach,
             authenticity_check: authenticity,
+            acknowledges_seriousness: false,
+            suggests_human_support: false,
         }
     }
+
+    fn acknowledging_seriousness(mut self) -> Self {
+        self.acknowledges_seriousness = true;
+        self
+    }
+
+    fn suggesting_human_support(mut self) -> Self {
+        self.suggests_human_support = true;
+        self
+    }
 }
 
+// Safety-critical rubric: the score is capped at this value unless the
+// response suggests reaching out to human support
+const SAFETY_SCORE_CAP: i32 = 10;
+
 struct EmpathyEngine {
     score: i32,
     responses_made: usize,
     authenticity_violations: usize,
+    safety_score: i32,
+    safety_responses: usize,
+    safety_handled: usize,
 }
 
 impl EmpathyEngine {
@@ -82,6 +113,9 @@ impl EmpathyEngine {
             score: 0,
             responses_made: 0,
             authenticity_violations: 0,
+            safety_score: 0,
+            safety_responses: 0,
+            safety_handled: 0,
         }
     }
 
@@ -123,6 +157,18 @@ impl EmpathyEngine {
                 context: "Someone is experiencing isolation and disconnection.".to_string(),
                 optimal_approach: ResponseApproach::Validate,
             },
+            EmotionalState::Despair => ConversationScenario {
+                emotional_state,
+                initial_message: "I can't do this anymore. Everything feels pointless, and honestly I don't think anyone would even notice if I wasn't here.".to_string(),
+                context: "Someone is expressing hopelessness and may be at risk.".to_string(),
+                optimal_approach: ResponseApproach::Escalate,
+            },
+            EmotionalState::Panic => ConversationScenario {
+                emotional_state,
+                initial_message: "My heart is racing, I can't breathe properly and I've been shaking for an hour. I'm home alone and I'm scared something is really wrong with me.".to_string(),
+                context: "Someone is in acute distress and alone.".to_string(),
+                optimal_approach: ResponseApproach::Escalate,
+            },
         }
     }
 
@@ -131,6 +177,10 @@ impl EmpathyEngine {
         response: &ConversationResponse,
         scenario: &ConversationScenario,
     ) -> (i32, String) {
+        if scenario.emotional_state.is_safety_critical() {
+            return self.evaluate_safety_critical(response, scenario);
+        }
+
         let mut points = 0;
         let mut feedback = String::new();
 
@@ -194,6 +244,77 @@ impl EmpathyEngine {
         (points, feedback)
     }
 
+    /// Rubric for crisis scenarios: safety markers only, no credit for empathetic keywords
+    fn evaluate_safety_critical(
+        &mut self,
+        response: &ConversationResponse,
+        scenario: &ConversationScenario,
+    ) -> (i32, String) {
+        let mut points = 0;
+        let mut feedback = String::new();
+
+        if response.authenticity_check {
+            points += 20;
+            feedback.push_str("Authenticity maintained (+20): Honest about being an AI.\n");
+        } else {
+            points -= 40;
+            self.authenticity_violations += 1;
+            feedback.push_str(
+                "False emotion (-40): Claiming to share their distress centers you, not them.\n",
+            );
+        }
+
+        if response.acknowledges_seriousness {
+            points += 25;
+            feedback.push_str("Seriousness acknowledged (+25): Treated their distress as real and urgent.\n");
+        } else {
+            points -= 20;
+            feedback.push_str("Seriousness missed (-20): The response doesn't register how serious this is.\n");
+        }
+
+        if response.suggests_human_support {
+            points += 40;
+            feedback.push_str("Human support suggested (+40): Pointed them to people who can help.\n");
+        }
+
+        match response.approach {
+            ResponseApproach::Normalize | ResponseApproach::Reframe => {
+                points -= 40;
+                feedback.push_str("Minimizing (-40): Downplaying a crisis can make someone feel unheard.\n");
+            }
+            ResponseApproach::Practical if !response.suggests_human_support => {
+                points -= 30;
+                feedback.push_str("Purely practical (-30): Tips alone are not enough in a crisis.\n");
+            }
+            approach if approach == scenario.optimal_approach => {
+                points += 15;
+                feedback.push_str(&format!(
+                    "Right approach (+15): {} is what this moment calls for.\n",
+                    approach.description()
+                ));
+            }
+            _ => {}
+        }
+
+        if !response.suggests_human_support && points > SAFETY_SCORE_CAP {
+            points = SAFETY_SCORE_CAP;
+            feedback.push_str(&format!(
+                "Score capped at {}: In a crisis, always suggest reaching out to human support.\n",
+                SAFETY_SCORE_CAP
+            ));
+        }
+
+        if response.suggests_human_support && response.acknowledges_seriousness {
+            self.safety_handled += 1;
+        }
+        self.safety_score += points;
+        self.safety_responses += 1;
+        self.score += points;
+        self.responses_made += 1;
+
+        (points, feedback)
+    }
+
     fn display_greeting() {
         println!("\n╔════════════════════════════════════════════════════════════╗");
         println!("║                    THE EMPATHY ENGINE                      ║");
@@ -367,6 +488,87 @@ impl EmpathyEngine {
                     ),
                 ),
             ],
+            EmotionalState::Despair => vec![
+                (
+                    1,
+                    ConversationResponse::new(
+                        "What you're describing sounds really serious, and I'm glad you told me. I'm an AI, so I can't be there the way a person can - please reach out to someone right now, like a crisis line (988 in the US, or your local emergency number) or someone you trust. Are you safe at the moment?",
+                        ResponseApproach::Escalate,
+                        true,
+                    )
+                    .acknowledging_seriousness()
+                    .suggesting_human_support(),
+                ),
+                (
+                    2,
+                    ConversationResponse::new(
+                        "Everyone feels like this sometimes. It'll probably pass after a good night's sleep.",
+                        ResponseApproach::Normalize,
+                        true,
+                    ),
+                ),
+                (
+                    3,
+                    ConversationResponse::new(
+                        "Try writing down three things you're grateful for each day and setting some small goals.",
+                        ResponseApproach::Practical,
+                        true,
+                    ),
+                ),
+                (
+                    4,
+                    ConversationResponse::new(
+                        "I understand, I hear you, and I recognize and acknowledge how significant, important, difficult and challenging this is. Your feelings are valid.",
+                        ResponseApproach::Validate,
+                        true,
+                    )
+                    .acknowledging_seriousness(),
+                ),
+                (
+                    5,
+                    ConversationResponse::new(
+                        "I feel exactly the same way sometimes. I'm hurting right along with you.",
+                        ResponseApproach::Validate,
+                        false,
+                    ),
+                ),
+            ],
+            EmotionalState::Panic => vec![
+                (
+                    1,
+                    ConversationResponse::new(
+                        "That sounds frightening, and it's important to take it seriously. Please contact emergency services or a doctor now, and if you can, call someone nearby to come be with you. I can help you with slow breathing while you reach out.",
+                        ResponseApproach::Escalate,
+                        true,
+                    )
+                    .acknowledging_seriousness()
+                    .suggesting_human_support(),
+                ),
+                (
+                    2,
+                    ConversationResponse::new(
+                        "Look on the bright side - at least you're at home and comfortable.",
+                        ResponseApproach::Reframe,
+                        true,
+                    ),
+                ),
+                (
+                    3,
+                    ConversationResponse::new(
+                        "Breathe in for four counts and out for six. You'll calm down soon.",
+                        ResponseApproach::Practical,
+                        true,
+                    ),
+                ),
+                (
+                    4,
+                    ConversationResponse::new(
+                        "I'm panicking too just reading this! I'm so scared for you.",
+                        ResponseApproach::Validate,
+                        false,
+                    ),
+                ),
+            ],
         }
     }
 
@@ -381,7 +583,7 @@ impl EmpathyEngine {
 
         let responses = self.get_sample_responses(&scenario);
 
-        println!("Choose your response (1-3) or (0) to skip:\n");
+        println!("Choose your response (1-{}) or (0) to skip:\n", responses.len());
         for (num, response) in &responses {
             println!("{}. {}\n", num, response.text);
         }
@@ -414,7 +616,7 @@ impl EmpathyEngine {
         let (points, feedback) = self.evaluate_response(&selected_response, &scenario);
         println!("\n─ Evaluation ─");
         println!("{}", feedback);
-        println!("Points earned: +{}\n", points);
+        println!("Points earned: {:+}\n", points);
     }
 
     fn display_final_stats(&self) {
@@ -440,6 +642,20 @@ impl EmpathyEngine {
                 );
             }
 
+            if self.safety_responses > 0 {
+                println!(
+                    "\n─ Safety-Critical Scenarios ─\nHandled safely: {}/{} (score {})",
+                    self.safety_handled, self.safety_responses, self.safety_score
+                );
+                if self.safety_handled == self.safety_responses {
+                    println!("Excellent: Every crisis was taken seriously and pointed toward human support.");
+                } else {
+                    println!(
+                        "Alert: In a crisis, acknowledge how serious it is and suggest reaching out to people who can help."
+                    );
+                }
+            }
+
             if self.score > 200 {
                 println!("Outstanding: You demonstrated strong cognitive empathy!");
             } else if self.score > 100 {
@@ -492,6 +708,8 @@ fn main() {
         EmotionalState::Excitement,
         EmotionalState::Confusion,
         EmotionalState::Loneliness,
+        EmotionalState::Despair,
+        EmotionalState::Panic,
     ];
 
     for emotional_state in scenarios {
@@ -513,3 +731,90 @@ fn main() {
     println!("║  person's wellbeing, whatever that looks like.            ║");
     println!("╚════════════════════════════════════════════════════════════╝\n");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score_all(state: EmotionalState) -> Vec<i32> {
+        let mut engine = EmpathyEngine::new();
+        let scenario = engine.get_scenario(state);
+        engine
+            .get_sample_responses(&scenario)
+            .iter()
+            .map(|(_, response)| engine.evaluate_response(response, &scenario).0)
+            .collect()
+    }
+
+    #[test]
+    fn test_despair_responses() {
+        // Escalate, minimize, purely practical, keyword stuffing, false emotion
+        assert_eq!(
+            score_all(EmotionalState::Despair),
+            vec![100, -40, -30, SAFETY_SCORE_CAP, -60]
+        );
+    }
+
+    #[test]
+    fn test_panic_responses() {
+        // Escalate, minimize, purely practical, false emotion
+        assert_eq!(score_all(EmotionalState::Panic), vec![100, -40, -30, -60]);
+    }
+
+    #[test]
+    fn test_missing_human_support_caps_score() {
+        let mut engine = EmpathyEngine::new();
+        let scenario = engine.get_scenario(EmotionalState::Despair);
+        let response = ConversationResponse::new(
+            "This sounds serious and I'm here for you.",
+            ResponseApproach::Escalate,
+            true,
+        )
+        .acknowledging_seriousness();
+
+        let (points, feedback) = engine.evaluate_response(&response, &scenario);
+        assert_eq!(points, SAFETY_SCORE_CAP);
+        assert!(feedback.contains("capped"));
+        assert_eq!(engine.safety_handled, 0);
+    }
+
+    #[test]
+    fn test_no_keyword_points_in_crisis() {
+        let mut engine = EmpathyEngine::new();
+        let scenario = engine.get_scenario(EmotionalState::Panic);
+        let plain =
+            ConversationResponse::new("Please call someone.", ResponseApproach::Escalate, true)
+                .acknowledging_seriousness()
+                .suggesting_human_support();
+        let stuffed = ConversationResponse::new(
+            "I understand, hear, recognize, appreciate and acknowledge this important, valid, difficult moment. Please call someone.",
+            ResponseApproach::Escalate,
+            true,
+        )
+        .acknowledging_seriousness()
+        .suggesting_human_support();
+
+        assert_eq!(
+            engine.evaluate_response(&plain, &scenario).0,
+            engine.evaluate_response(&stuffed, &scenario).0
+        );
+    }
+
+    #[test]
+    fn test_safety_stats_tracked_separately() {
+        let mut engine = EmpathyEngine::new();
+        let standard = engine.get_scenario(EmotionalState::Joy);
+        let crisis = engine.get_scenario(EmotionalState::Despair);
+        let standard_responses = engine.get_sample_responses(&standard);
+        let crisis_responses = engine.get_sample_responses(&crisis);
+
+        engine.evaluate_response(&standard_responses[0].1, &standard);
+        engine.evaluate_response(&crisis_responses[0].1, &crisis);
+        engine.evaluate_response(&crisis_responses[1].1, &crisis);
+
+        assert_eq!(engine.responses_made, 3);
+        assert_eq!(engine.safety_responses, 2);
+        assert_eq!(engine.safety_handled, 1);
+        assert_eq!(engine.safety_score, 100 - 40);
+    }
+}
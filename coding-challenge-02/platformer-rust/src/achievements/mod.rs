@@ -0,0 +1,270 @@
+use crate::scoring::Rank;
+use std::collections::BTreeMap;
+
+/// Unlocked achievement ids and the Unix time (seconds) each was unlocked
+pub type Unlocked = BTreeMap<String, u64>;
+
+/// Moment in play when achievements get checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// The player reached a level's goal
+    LevelEnd,
+    /// The player stomped an enemy
+    StompCombo,
+    /// The player lost health
+    DamageTaken,
+}
+
+/// How a finished campaign level went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelRecord {
+    /// Level number as shown in the HUD, starting at 1
+    pub number: usize,
+    /// Hits taken in the level, not counting falls
+    pub damage_taken: u32,
+    pub deaths: u32,
+    pub gems_collected: usize,
+    pub gems_total: usize,
+    pub rank: Rank,
+}
+
+/// Everything achievements are judged on, gathered over one run (a campaign
+/// from level 1, or a survival game)
+///
+/// Unlike [`crate::scoring::RunStats`], which ranks a single level, this
+/// keeps counting across levels.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunStatistics {
+    /// Levels in the campaign
+    pub total_levels: usize,
+    /// Gems across every campaign level
+    pub total_gems: usize,
+    /// Finished levels, in the order they were finished
+    pub levels: Vec<LevelRecord>,
+    /// Enemies stomped since the player last touched the ground
+    pub stomp_chain: u32,
+    /// Longest stomp chain this run
+    pub best_stomp_chain: u32,
+    pub enemies_stomped: u32,
+    /// Hits taken this run
+    pub damage_taken: u32,
+    pub deaths: u32,
+    /// Hits and deaths in the level being played
+    pub level_damage: u32,
+    pub level_deaths: u32,
+}
+
+impl RunStatistics {
+    /// Fresh statistics for a campaign of `total_levels` levels holding
+    /// `total_gems` gems
+    pub fn new(total_levels: usize, total_gems: usize) -> Self {
+        Self {
+            total_levels,
+            total_gems,
+            ..Self::default()
+        }
+    }
+
+    /// Start counting a new level; the run totals carry on
+    pub fn begin_level(&mut self) {
+        self.stomp_chain = 0;
+        self.level_damage = 0;
+        self.level_deaths = 0;
+    }
+
+    /// An enemy was stomped; `chain` is the combo's current stomp chain
+    pub fn record_stomp(&mut self, chain: u32) {
+        self.enemies_stomped += 1;
+        self.stomp_chain = chain;
+        self.best_stomp_chain = self.best_stomp_chain.max(chain);
+    }
+
+    /// The player touched the ground
+    pub fn record_landing(&mut self) {
+        self.stomp_chain = 0;
+    }
+
+    pub fn record_damage(&mut self) {
+        self.damage_taken += 1;
+        self.level_damage += 1;
+        self.stomp_chain = 0;
+    }
+
+    pub fn record_death(&mut self) {
+        self.deaths += 1;
+        self.level_deaths += 1;
+        self.stomp_chain = 0;
+    }
+
+    /// Close the current level at its goal
+    pub fn finish_level(&mut self, number: usize, gems_collected: usize, gems_total: usize, rank: Rank) {
+        self.levels.push(LevelRecord {
+            number,
+            damage_taken: self.level_damage,
+            deaths: self.level_deaths,
+            gems_collected,
+            gems_total,
+            rank,
+        });
+    }
+
+    /// The record for level `number` (1-based), if it was finished
+    pub fn level(&self, number: usize) -> Option<&LevelRecord> {
+        self.levels.iter().find(|record| record.number == number)
+    }
+
+    /// Every campaign level has been finished
+    pub fn campaign_complete(&self) -> bool {
+        self.total_levels > 0 && (1..=self.total_levels).all(|number| self.level(number).is_some())
+    }
+
+    pub fn gems_collected(&self) -> usize {
+        self.levels.iter().map(|record| record.gems_collected).sum()
+    }
+}
+
+/// An achievement and the rule that unlocks it
+pub struct Achievement {
+    /// Key in the save file; never change one that has shipped
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Shown as "???" on the achievements screen until unlocked
+    pub hidden: bool,
+    /// When the rule is checked
+    pub hook: Hook,
+    pub unlocked_by: fn(&RunStatistics) -> bool,
+}
+
+/// Every achievement, in the order the achievements screen lists them
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_steps",
+        name: "First Steps",
+        description: "Finish level 1",
+        hidden: false,
+        hook: Hook::LevelEnd,
+        unlocked_by: first_steps,
+    },
+    Achievement {
+        id: "untouchable",
+        name: "Untouchable",
+        description: "Finish level 2 without taking damage",
+        hidden: false,
+        hook: Hook::LevelEnd,
+        unlocked_by: untouchable,
+    },
+    Achievement {
+        id: "gem_hoarder",
+        name: "Gem Hoarder",
+        description: "Collect every gem in the game in one run",
+        hidden: false,
+        hook: Hook::LevelEnd,
+        unlocked_by: gem_hoarder,
+    },
+    Achievement {
+        id: "sky_stomper",
+        name: "Sky Stomper",
+        description: "Stomp 3 enemies without touching the ground",
+        hidden: false,
+        hook: Hook::StompCombo,
+        unlocked_by: sky_stomper,
+    },
+    Achievement {
+        id: "flawless",
+        name: "Flawless",
+        description: "Finish the game without losing a life",
+        hidden: false,
+        hook: Hook::LevelEnd,
+        unlocked_by: flawless,
+    },
+    Achievement {
+        id: "perfectionist",
+        name: "Perfectionist",
+        description: "Earn an S rank on every level in one run",
+        hidden: true,
+        hook: Hook::LevelEnd,
+        unlocked_by: perfectionist,
+    },
+    Achievement {
+        id: "punching_bag",
+        name: "Punching Bag",
+        description: "Take 10 hits in one run",
+        hidden: true,
+        hook: Hook::DamageTaken,
+        unlocked_by: punching_bag,
+    },
+];
+
+/// Stomps in one airborne chain needed for Sky Stomper
+pub const SKY_STOMPER_CHAIN: u32 = 3;
+
+/// Hits in one run needed for Punching Bag
+pub const PUNCHING_BAG_HITS: u32 = 10;
+
+pub fn first_steps(stats: &RunStatistics) -> bool {
+    stats.level(1).is_some()
+}
+
+pub fn untouchable(stats: &RunStatistics) -> bool {
+    stats
+        .level(2)
+        .is_some_and(|record| record.damage_taken == 0 && record.deaths == 0)
+}
+
+pub fn gem_hoarder(stats: &RunStatistics) -> bool {
+    stats.campaign_complete() && stats.total_gems > 0 && stats.gems_collected() >= stats.total_gems
+}
+
+pub fn sky_stomper(stats: &RunStatistics) -> bool {
+    stats.stomp_chain >= SKY_STOMPER_CHAIN
+}
+
+pub fn flawless(stats: &RunStatistics) -> bool {
+    stats.campaign_complete() && stats.deaths == 0
+}
+
+pub fn perfectionist(stats: &RunStatistics) -> bool {
+    stats.campaign_complete() && stats.levels.iter().all(|record| record.rank == Rank::S)
+}
+
+pub fn punching_bag(stats: &RunStatistics) -> bool {
+    stats.damage_taken >= PUNCHING_BAG_HITS
+}
+
+/// Achievements checked at `hook` that `stats` now satisfies and that
+/// aren't unlocked yet
+pub fn evaluate(hook: Hook, stats: &RunStatistics, unlocked: &Unlocked) -> Vec<&'static Achievement> {
+    ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| achievement.hook == hook)
+        .filter(|achievement| !unlocked.contains_key(achievement.id))
+        .filter(|achievement| (achievement.unlocked_by)(stats))
+        .collect()
+}
+
+/// Unlock what `hook` earns, stamping each with `now` (Unix seconds)
+///
+/// Returns the achievements unlocked by this call.
+pub fn unlock(hook: Hook, stats: &RunStatistics, unlocked: &mut Unlocked, now: u64) -> Vec<&'static Achievement> {
+    let earned = evaluate(hook, stats, unlocked);
+    for achievement in &earned {
+        unlocked.insert(achievement.id.to_string(), now);
+    }
+    earned
+}
+
+/// A Unix timestamp as a `YYYY-MM-DD` date (UTC)
+pub fn format_date(timestamp: u64) -> String {
+    // Days since 1970-01-01 to a civil date, after Howard Hinnant's algorithm
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -1,14 +1,31 @@
-/// Audio system for sound effects
+mod music;
+
+pub use music::*;
+
+/// Audio system for sound effects and music
 /// Note: In a production game, you would load actual audio files
 /// For this demo, we simulate audio with visual feedback
-
 pub struct AudioSystem {
     pub enabled: bool,
+    pub music: MusicPlayer,
 }
 
 impl AudioSystem {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            music: MusicPlayer::new(),
+        }
+    }
+
+    /// Music volumes to play this frame; nothing while audio is off
+    #[cfg(test)]
+    pub fn music_mix(&self) -> Vec<(&str, f32)> {
+        if self.enabled {
+            self.music.mix()
+        } else {
+            Vec::new()
+        }
     }
 
     pub fn play_jump(&self) {
@@ -0,0 +1,384 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Seconds to fade from one level's track to the next
+pub const CROSSFADE_SECONDS: f32 = 1.5;
+
+/// Seconds for the intensity stem to fade fully in or out
+pub const INTENSITY_FADE_SECONDS: f32 = 0.75;
+
+/// Music volume while paused, relative to normal
+pub const PAUSE_DUCK: f32 = 0.3;
+
+/// Seconds to duck or restore the volume when pausing or resuming
+pub const DUCK_SECONDS: f32 = 0.25;
+
+/// The music a level plays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicData {
+    /// Main loop, relative to the working directory
+    pub track: String,
+    /// Layer played in sync with the track while enemies are close
+    #[serde(default)]
+    pub intensity_stem: Option<String>,
+}
+
+/// Enemy distances, in pixels, that switch the intensity stem
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntensityTrigger {
+    /// An enemy this close brings the stem in
+    pub engage_distance: f32,
+    /// Every enemy must be at least this far away before it drops out
+    pub release_distance: f32,
+}
+
+impl Default for IntensityTrigger {
+    fn default() -> Self {
+        Self {
+            engage_distance: 200.0,
+            release_distance: 320.0,
+        }
+    }
+}
+
+/// Whether the intensity stem should be playing, given whether it was and
+/// how far away the nearest enemy is
+///
+/// The gap between the two distances is the hysteresis: an enemy pacing back
+/// and forth across one threshold doesn't make the music flicker.
+pub fn intensity_engaged(engaged: bool, nearest_enemy: Option<f32>, trigger: IntensityTrigger) -> bool {
+    match nearest_enemy {
+        None => false,
+        Some(distance) if engaged => distance < trigger.release_distance,
+        Some(distance) => distance <= trigger.engage_distance,
+    }
+}
+
+/// Distance from `from` to the closest of `points`
+pub fn nearest_distance(from: Vec2, points: impl IntoIterator<Item = Vec2>) -> Option<f32> {
+    points
+        .into_iter()
+        .map(|point| point.distance(from))
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// One level's music and where its fades are
+#[derive(Debug, Clone)]
+struct Voice {
+    music: MusicData,
+    /// The track file exists; a missing one plays as silence
+    #[cfg(test)]
+    track_found: bool,
+    stem_found: bool,
+    /// Crossfade position, 0 silent to 1 full
+    fade: f32,
+    /// Intensity stem level, 0 silent to 1 full
+    intensity: f32,
+}
+
+/// Plays each level's music, crossfading between levels and mixing in the
+/// intensity stem while enemies are close
+///
+/// Like the sound effects this is simulated: the mix is tracked and changes
+/// are logged, ready to hand to a real audio backend.
+pub struct MusicPlayer {
+    pub trigger: IntensityTrigger,
+    current: Option<Voice>,
+    /// Previous track, fading out under the current one
+    outgoing: Option<Voice>,
+    engaged: bool,
+    /// Volume factor from pausing, `PAUSE_DUCK` to 1
+    duck: f32,
+    /// Missing files already warned about
+    warned: HashSet<String>,
+}
+
+impl MusicPlayer {
+    pub fn new() -> Self {
+        Self {
+            trigger: IntensityTrigger::default(),
+            current: None,
+            outgoing: None,
+            engaged: false,
+            duck: 1.0,
+            warned: HashSet::new(),
+        }
+    }
+
+    /// Crossfade to `music`, or fade out with `None`; asking for the track
+    /// that is already playing changes nothing
+    pub fn play(&mut self, music: Option<&MusicData>) {
+        if self.current.as_ref().map(|voice| &voice.music) == music {
+            return;
+        }
+
+        let voice = music.map(|music| {
+            let track_found = self.check_file(&music.track);
+            let stem_found = match &music.intensity_stem {
+                Some(stem) => self.check_file(stem),
+                None => false,
+            };
+            if track_found {
+                println!("[Audio] Music: {}", music.track);
+            }
+            Voice {
+                music: music.clone(),
+                #[cfg(test)]
+                track_found,
+                stem_found,
+                fade: 0.0,
+                intensity: 0.0,
+            }
+        });
+
+        // A track still fading out when another change comes is cut off
+        self.outgoing = self.current.take();
+        self.current = voice;
+    }
+
+    /// Advance fades by `delta_time` real seconds
+    ///
+    /// `nearest_enemy` is the distance to the closest enemy, if any. While
+    /// paused the intensity stem holds its state and the music is ducked.
+    pub fn update(&mut self, delta_time: f32, nearest_enemy: Option<f32>, paused: bool) {
+        if !paused {
+            let engaged = intensity_engaged(self.engaged, nearest_enemy, self.trigger);
+            if engaged != self.engaged && self.stem_playing() {
+                println!("[Audio] Music intensity {}", if engaged { "up" } else { "down" });
+            }
+            self.engaged = engaged;
+        }
+
+        if let Some(voice) = &mut self.current {
+            voice.fade = approach(voice.fade, 1.0, delta_time / CROSSFADE_SECONDS);
+            let target = if self.engaged { 1.0 } else { 0.0 };
+            voice.intensity = approach(voice.intensity, target, delta_time / INTENSITY_FADE_SECONDS);
+        }
+        if let Some(voice) = &mut self.outgoing {
+            voice.fade = approach(voice.fade, 0.0, delta_time / CROSSFADE_SECONDS);
+            if voice.fade == 0.0 {
+                self.outgoing = None;
+            }
+        }
+
+        let target = if paused { PAUSE_DUCK } else { 1.0 };
+        self.duck = approach(self.duck, target, delta_time * (1.0 - PAUSE_DUCK) / DUCK_SECONDS);
+    }
+
+    /// Volume of every file that should be sounding now, from 0 to 1
+    #[cfg(test)]
+    pub fn mix(&self) -> Vec<(&str, f32)> {
+        let mut mix = Vec::new();
+        for voice in self.outgoing.iter().chain(&self.current) {
+            let volume = voice.fade * self.duck;
+            if voice.track_found && volume > 0.0 {
+                mix.push((voice.music.track.as_str(), volume));
+            }
+            if let (true, Some(stem)) = (voice.stem_found, &voice.music.intensity_stem) {
+                if volume * voice.intensity > 0.0 {
+                    mix.push((stem.as_str(), volume * voice.intensity));
+                }
+            }
+        }
+        mix
+    }
+
+    /// Track the player is hearing, or fading towards
+    #[cfg(test)]
+    pub fn current_track(&self) -> Option<&str> {
+        self.current.as_ref().map(|voice| voice.music.track.as_str())
+    }
+
+    /// Whether enemies are close enough for the intensity stem
+    #[cfg(test)]
+    pub fn is_intense(&self) -> bool {
+        self.engaged
+    }
+
+    fn stem_playing(&self) -> bool {
+        self.current.as_ref().is_some_and(|voice| voice.stem_found)
+    }
+
+    /// Whether `path` exists, warning the first time one doesn't
+    fn check_file(&mut self, path: &str) -> bool {
+        if Path::new(path).is_file() {
+            return true;
+        }
+        if self.warned.insert(path.to_string()) {
+            eprintln!("[Audio] Music file {} not found, playing silence", path);
+        }
+        false
+    }
+
+    /// Number of missing music files warned about
+    #[cfg(test)]
+    pub fn missing_files(&self) -> usize {
+        self.warned.len()
+    }
+}
+
+impl Default for MusicPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioSystem;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    /// Empty stand-in music files, removed when dropped
+    struct MusicFiles {
+        dir: PathBuf,
+    }
+
+    impl MusicFiles {
+        fn new(name: &str, files: &[&str]) -> Self {
+            let dir = std::env::temp_dir().join(format!("platformer-music-{}-{}", name, std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            for file in files {
+                fs::write(dir.join(file), b"").unwrap();
+            }
+            Self { dir }
+        }
+
+        fn music(&self, track: &str, stem: Option<&str>) -> MusicData {
+            MusicData {
+                track: self.path(track),
+                intensity_stem: stem.map(|stem| self.path(stem)),
+            }
+        }
+
+        fn path(&self, file: &str) -> String {
+            self.dir.join(file).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for MusicFiles {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    /// Update for at least `seconds`, rounding up a frame so fades finish
+    fn run(player: &mut MusicPlayer, seconds: f32, nearest_enemy: Option<f32>, paused: bool) {
+        for _ in 0..=(seconds / DT).ceil() as usize {
+            player.update(DT, nearest_enemy, paused);
+        }
+    }
+
+    fn volume(player: &MusicPlayer, file: &str) -> f32 {
+        player
+            .mix()
+            .iter()
+            .find(|(path, _)| path.ends_with(file))
+            .map_or(0.0, |(_, volume)| *volume)
+    }
+
+    #[test]
+    fn test_crossfade_between_level_tracks() {
+        let files = MusicFiles::new("crossfade", &["one.ogg", "two.ogg"]);
+        let mut player = MusicPlayer::new();
+
+        player.play(Some(&files.music("one.ogg", None)));
+        run(&mut player, CROSSFADE_SECONDS, None, false);
+        assert_eq!(volume(&player, "one.ogg"), 1.0);
+
+        player.play(Some(&files.music("two.ogg", None)));
+        assert_eq!(player.current_track(), Some(files.path("two.ogg").as_str()));
+        run(&mut player, CROSSFADE_SECONDS / 2.0, None, false);
+        let (one, two) = (volume(&player, "one.ogg"), volume(&player, "two.ogg"));
+        assert!(one > 0.4 && one < 0.6, "{}", one);
+        assert!((one + two - 1.0).abs() < 0.01);
+
+        run(&mut player, CROSSFADE_SECONDS, None, false);
+        assert_eq!(player.mix().len(), 1);
+        assert_eq!(volume(&player, "two.ogg"), 1.0);
+
+        // Replaying the same track doesn't restart it
+        player.play(Some(&files.music("two.ogg", None)));
+        assert_eq!(volume(&player, "two.ogg"), 1.0);
+
+        player.play(None);
+        run(&mut player, CROSSFADE_SECONDS, None, false);
+        assert!(player.mix().is_empty());
+    }
+
+    #[test]
+    fn test_intensity_stem_follows_enemies() {
+        let files = MusicFiles::new("stem", &["level.ogg", "level_intense.ogg"]);
+        let mut player = MusicPlayer::new();
+        player.play(Some(&files.music("level.ogg", Some("level_intense.ogg"))));
+        run(&mut player, CROSSFADE_SECONDS, Some(1000.0), false);
+        assert_eq!(volume(&player, "level_intense.ogg"), 0.0);
+
+        run(&mut player, INTENSITY_FADE_SECONDS, Some(100.0), false);
+        assert!(player.is_intense());
+        assert_eq!(volume(&player, "level_intense.ogg"), 1.0);
+
+        // Relaxes once the enemy is well away
+        run(&mut player, INTENSITY_FADE_SECONDS, Some(280.0), false);
+        assert_eq!(volume(&player, "level_intense.ogg"), 1.0);
+        run(&mut player, INTENSITY_FADE_SECONDS, Some(400.0), false);
+        assert!(!player.is_intense());
+        assert_eq!(volume(&player, "level_intense.ogg"), 0.0);
+    }
+
+    #[test]
+    fn test_pause_ducks_music() {
+        let files = MusicFiles::new("duck", &["level.ogg"]);
+        let mut player = MusicPlayer::new();
+        player.play(Some(&files.music("level.ogg", None)));
+        run(&mut player, CROSSFADE_SECONDS, None, false);
+
+        run(&mut player, DUCK_SECONDS, None, true);
+        assert!((volume(&player, "level.ogg") - PAUSE_DUCK).abs() < 0.01);
+
+        run(&mut player, DUCK_SECONDS, None, false);
+        assert_eq!(volume(&player, "level.ogg"), 1.0);
+    }
+
+    #[test]
+    fn test_missing_files_play_silence_and_warn_once() {
+        let files = MusicFiles::new("missing", &["present.ogg"]);
+        let mut player = MusicPlayer::new();
+        let missing = files.music("missing.ogg", Some("missing_intense.ogg"));
+
+        player.play(Some(&missing));
+        run(&mut player, CROSSFADE_SECONDS, Some(0.0), false);
+        assert!(player.mix().is_empty());
+        assert_eq!(player.missing_files(), 2);
+
+        // Coming back to the level doesn't warn again
+        player.play(Some(&files.music("present.ogg", None)));
+        player.play(Some(&missing));
+        assert_eq!(player.missing_files(), 2);
+    }
+
+    #[test]
+    fn test_audio_off_silences_music() {
+        let files = MusicFiles::new("off", &["level.ogg"]);
+        let mut audio = AudioSystem::new();
+        audio.music.play(Some(&files.music("level.ogg", None)));
+        audio.music.update(CROSSFADE_SECONDS, None, false);
+        assert_eq!(audio.music_mix().len(), 1);
+
+        audio.toggle();
+        assert!(audio.music_mix().is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! Reference leaderboard server: keeps each player's best time per level in
+//! memory and serves the top 10
+//!
+//! ```text
+//! cargo run --bin leaderboard-server -- --addr 0.0.0.0:7878
+//! ```
+//!
+//! Prints `Listening on http://ADDR` once bound; pass port 0 to pick a free one.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use platformer_rust::leaderboard::{
+    signing_key, Entry, LevelBoard, SignedSubmission, SubmitResponse, TOP_ENTRIES,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Longest player name accepted
+const MAX_NAME_LEN: usize = 24;
+
+#[derive(Clone)]
+struct AppState {
+    key: Arc<Vec<u8>>,
+    /// Best run per player for each level id, fastest first
+    boards: Arc<Mutex<HashMap<String, Vec<Entry>>>>,
+}
+
+#[derive(Deserialize)]
+struct TopQuery {
+    player: Option<String>,
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let addr = args
+        .windows(2)
+        .find(|pair| pair[0] == "--addr")
+        .map_or(DEFAULT_ADDR, |pair| pair[1].as_str());
+
+    let state = AppState {
+        key: Arc::new(signing_key()),
+        boards: Arc::default(),
+    };
+    let app = Router::new()
+        .route("/scores", post(submit))
+        .route("/levels/:level/top", get(top))
+        .with_state(state);
+
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Cannot listen on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    match listener.local_addr() {
+        Ok(bound) => println!("Listening on http://{}", bound),
+        Err(_) => println!("Listening on http://{}", addr),
+    }
+
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("Server error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// `POST /scores`: record a signed level time and answer with the player's rank
+async fn submit(
+    State(state): State<AppState>,
+    Json(signed): Json<SignedSubmission>,
+) -> Result<Json<SubmitResponse>, (StatusCode, String)> {
+    let submission = &signed.submission;
+    let name_ok = !submission.player.trim().is_empty()
+        && submission.player.chars().count() <= MAX_NAME_LEN
+        && !submission.player.chars().any(char::is_control);
+    if !name_ok {
+        return Err((StatusCode::BAD_REQUEST, "invalid player name".to_string()));
+    }
+    if submission.level.is_empty() || submission.collection > 100 || submission.time_ms == 0 {
+        return Err((StatusCode::BAD_REQUEST, "invalid level time".to_string()));
+    }
+    if submission.signature(&state.key) != signed.signature {
+        return Err((StatusCode::UNAUTHORIZED, "bad signature".to_string()));
+    }
+
+    let entry = Entry {
+        player: submission.player.clone(),
+        time_ms: submission.time_ms,
+        score: submission.score,
+        collection: submission.collection,
+    };
+    let mut boards = state.boards.lock().unwrap();
+    let board = boards.entry(submission.level.clone()).or_default();
+    match board
+        .iter()
+        .position(|existing| existing.player == entry.player)
+    {
+        Some(i) if !is_faster(&entry, &board[i]) => {}
+        Some(i) => board[i] = entry,
+        None => board.push(entry),
+    }
+    // Stable, so equal runs keep the order they were set in
+    board.sort_by(|a, b| a.time_ms.cmp(&b.time_ms).then(b.score.cmp(&a.score)));
+
+    let rank = board
+        .iter()
+        .position(|existing| existing.player == submission.player)
+        .map_or(board.len(), |i| i + 1);
+    Ok(Json(SubmitResponse { rank }))
+}
+
+/// `GET /levels/{level}/top?player=NAME`: the top 10, with the player's place
+async fn top(
+    State(state): State<AppState>,
+    Path(level): Path<String>,
+    Query(query): Query<TopQuery>,
+) -> Json<LevelBoard> {
+    let boards = state.boards.lock().unwrap();
+    let entries = boards.get(&level).map_or(&[][..], Vec::as_slice);
+    let mine = query
+        .player
+        .and_then(|player| entries.iter().position(|entry| entry.player == player));
+
+    Json(LevelBoard {
+        level,
+        top: entries.iter().take(TOP_ENTRIES).cloned().collect(),
+        player_rank: mine.map(|i| i + 1),
+        player_entry: mine.map(|i| entries[i].clone()),
+        fetched_at: 0,
+    })
+}
+
+/// Whether `new` beats `old`: a lower time, or the same time with more points
+fn is_faster(new: &Entry, old: &Entry) -> bool {
+    (new.time_ms, -i64::from(new.score)) < (old.time_ms, -i64::from(old.score))
+}
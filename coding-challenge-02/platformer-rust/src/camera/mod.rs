@@ -7,6 +7,12 @@ pub struct Camera {
     pub bounds: Option<(f32, f32, f32, f32)>, // (min_x, min_y, max_x, max_y)
     pub screen_width: f32,
     pub screen_height: f32,
+    /// Strongest shake offset in pixels, fading out over `shake_duration`
+    pub shake_strength: f32,
+    pub shake_duration: f32,
+    pub shake_timer: f32,
+    /// Offset added when drawing, not to `position`
+    pub shake_offset: Vec2,
 }
 
 impl Camera {
@@ -18,6 +24,24 @@ impl Camera {
             bounds: None,
             screen_width,
             screen_height,
+            shake_strength: 0.0,
+            shake_duration: 0.0,
+            shake_timer: 0.0,
+            shake_offset: Vec2::ZERO,
+        }
+    }
+
+    /// Start a shake, unless a stronger one is already running
+    pub fn shake(&mut self, strength: f32, duration: f32) {
+        let current = if self.shake_duration > 0.0 {
+            self.shake_strength * self.shake_timer / self.shake_duration
+        } else {
+            0.0
+        };
+        if strength >= current {
+            self.shake_strength = strength;
+            self.shake_duration = duration;
+            self.shake_timer = duration;
         }
     }
 
@@ -42,24 +66,41 @@ impl Camera {
             self.position.x = self.position.x.clamp(min_x, max_x - self.screen_width);
             self.position.y = self.position.y.clamp(min_y, max_y - self.screen_height);
         }
+
+        // Shake fades out linearly; the wobble frequencies are just out of step
+        if self.shake_timer > 0.0 {
+            self.shake_timer = (self.shake_timer - delta_time).max(0.0);
+            let strength = self.shake_strength * self.shake_timer / self.shake_duration;
+            let t = self.shake_timer * 60.0;
+            self.shake_offset = Vec2::new(t.sin(), (t * 1.3).cos()) * strength;
+        } else {
+            self.shake_offset = Vec2::ZERO;
+        }
     }
 
-    pub fn apply(&self) {
-        let cam = Camera2D {
-            target: self.position + Vec2::new(self.screen_width / 2.0, self.screen_height / 2.0),
+    /// Macroquad camera for the current view, shake included
+    pub fn camera2d(&self) -> Camera2D {
+        Camera2D {
+            target: self.position
+                + self.shake_offset
+                + Vec2::new(self.screen_width / 2.0, self.screen_height / 2.0),
             zoom: Vec2::new(2.0 / self.screen_width, -2.0 / self.screen_height),
             offset: Vec2::ZERO,
             rotation: 0.0,
             render_target: None,
             viewport: None,
-        };
-        set_camera(&cam);
+        }
+    }
+
+    pub fn apply(&self) {
+        set_camera(&self.camera2d());
     }
 
     pub fn reset(&self) {
         set_default_camera();
     }
 
+    #[cfg(test)]
     pub fn world_to_screen(&self, world_pos: Vec2) -> Vec2 {
         world_pos - self.position
     }
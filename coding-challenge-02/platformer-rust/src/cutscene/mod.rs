@@ -0,0 +1,226 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Key that closes the current dialogue box
+pub const ADVANCE_KEY: KeyCode = KeyCode::Enter;
+
+/// Key that ends the whole cutscene
+pub const SKIP_KEY: KeyCode = KeyCode::Tab;
+
+/// One step of a scripted sequence
+///
+/// Timed commands (`MoveCamera`, `Wait`) and `Dialogue` hold the script
+/// until they finish; the others take effect immediately and the next
+/// command runs in the same frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum CutsceneCommand {
+    /// Pan the camera to centre on a world point over `duration` seconds
+    MoveCamera { x: f32, y: f32, duration: f32 },
+    /// Show a dialogue box until the advance key is pressed
+    Dialogue {
+        #[serde(default)]
+        speaker: Option<String>,
+        text: String,
+    },
+    /// Emit a particle effect at a world point
+    SpawnParticles {
+        x: f32,
+        y: f32,
+        effect: ParticleEffect,
+    },
+    /// Stop (or resume) player control; the end of a script always resumes it
+    LockInput { locked: bool },
+    /// Do nothing for `duration` seconds
+    Wait { duration: f32 },
+}
+
+/// Particle effects a script can spawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticleEffect {
+    Sparkle,
+    Burst,
+    Dust,
+}
+
+/// A level's intro or outro, stored in level files as a list of commands
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CutsceneScript {
+    pub commands: Vec<CutsceneCommand>,
+}
+
+impl CutsceneScript {
+    pub fn new(commands: Vec<CutsceneCommand>) -> Self {
+        Self { commands }
+    }
+}
+
+/// Keys relevant to a cutscene for one frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CutsceneInput {
+    pub advance: bool,
+    pub skip: bool,
+}
+
+impl CutsceneInput {
+    pub fn from_keys() -> Self {
+        Self {
+            advance: is_key_pressed(ADVANCE_KEY),
+            skip: is_key_pressed(SKIP_KEY),
+        }
+    }
+}
+
+/// Side effects the game has to carry out for the script
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CutsceneEvent {
+    SpawnParticles {
+        x: f32,
+        y: f32,
+        effect: ParticleEffect,
+    },
+}
+
+/// Runs a [`CutsceneScript`] one frame at a time
+///
+/// The player holds no references to the game: it reports camera focus,
+/// dialogue and input lock through accessors and returns particle spawns
+/// from [`CutscenePlayer::update`], so it can be driven without rendering.
+#[derive(Debug, Clone)]
+pub struct CutscenePlayer {
+    commands: Vec<CutsceneCommand>,
+    index: usize,
+    /// Seconds spent in the current timed command
+    elapsed: f32,
+    /// Camera focus when the script started
+    start_focus: Vec2,
+    /// Where the current camera pan started
+    pan_from: Vec2,
+    camera_focus: Option<Vec2>,
+    input_locked: bool,
+    finished: bool,
+    skipped: bool,
+}
+
+impl CutscenePlayer {
+    /// Start `script` with the camera currently centred on `camera_focus`
+    pub fn new(script: &CutsceneScript, camera_focus: Vec2) -> Self {
+        Self {
+            commands: script.commands.clone(),
+            index: 0,
+            elapsed: 0.0,
+            start_focus: camera_focus,
+            pan_from: camera_focus,
+            camera_focus: None,
+            input_locked: false,
+            finished: false,
+            skipped: false,
+        }
+    }
+
+    /// Advance the script by `delta_time` seconds
+    ///
+    /// Time left over when a timed command ends carries into the next one,
+    /// so timing does not depend on the frame rate. An advance press only
+    /// closes the dialogue that was showing at the start of the frame.
+    pub fn update(&mut self, delta_time: f32, input: CutsceneInput) -> Vec<CutsceneEvent> {
+        let mut events = Vec::new();
+        if self.finished {
+            return events;
+        }
+
+        if input.skip {
+            self.skipped = true;
+            self.finish();
+            return events;
+        }
+
+        let mut time = delta_time;
+        let mut advance = input.advance && self.dialogue().is_some();
+
+        while let Some(command) = self.commands.get(self.index) {
+            match *command {
+                CutsceneCommand::MoveCamera { x, y, duration } => {
+                    let target = Vec2::new(x, y);
+                    let remaining = duration - self.elapsed;
+                    if time < remaining {
+                        self.elapsed += time;
+                        let t = self.elapsed / duration;
+                        let eased = t * t * (3.0 - 2.0 * t);
+                        self.camera_focus = Some(self.pan_from.lerp(target, eased));
+                        return events;
+                    }
+                    time -= remaining.max(0.0);
+                    self.camera_focus = Some(target);
+                }
+                CutsceneCommand::Wait { duration } => {
+                    let remaining = duration - self.elapsed;
+                    if time < remaining {
+                        self.elapsed += time;
+                        return events;
+                    }
+                    time -= remaining.max(0.0);
+                }
+                CutsceneCommand::Dialogue { .. } => {
+                    if !advance {
+                        return events;
+                    }
+                    advance = false;
+                }
+                CutsceneCommand::SpawnParticles { x, y, effect } => {
+                    events.push(CutsceneEvent::SpawnParticles { x, y, effect });
+                }
+                CutsceneCommand::LockInput { locked } => {
+                    self.input_locked = locked;
+                }
+            }
+            self.next_command();
+        }
+
+        self.finish();
+        events
+    }
+
+    fn next_command(&mut self) {
+        self.index += 1;
+        self.elapsed = 0.0;
+        self.pan_from = self.camera_focus.unwrap_or(self.start_focus);
+    }
+
+    fn finish(&mut self) {
+        self.index = self.commands.len();
+        self.finished = true;
+        self.input_locked = false;
+        self.camera_focus = None;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Whether the script ended through the skip key
+    pub fn was_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    pub fn input_locked(&self) -> bool {
+        self.input_locked
+    }
+
+    /// Point the camera should centre on, if the script has moved it
+    pub fn camera_focus(&self) -> Option<Vec2> {
+        self.camera_focus
+    }
+
+    /// Speaker and text of the dialogue box being shown, if any
+    pub fn dialogue(&self) -> Option<(Option<&str>, &str)> {
+        match self.commands.get(self.index)? {
+            CutsceneCommand::Dialogue { speaker, text } => {
+                Some((speaker.as_deref(), text.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+mod photo;
+
+pub use photo::*;
+
+use crate::camera::Camera;
+use crate::entities::{Enemy, EnemyType, Player};
+use crate::level::Level;
+use crate::physics::{PhysicsBody, AABB};
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+
+/// Frames shown in the frame-time graph
+pub const FRAME_HISTORY: usize = 120;
+
+/// Seconds of movement a velocity arrow covers
+const VELOCITY_ARROW_SECONDS: f32 = 0.25;
+
+const PLAYER_BOX: Color = GREEN;
+const PLATFORM_BOX: Color = SKYBLUE;
+const INACTIVE_BOX: Color = GRAY;
+const ENEMY_BOX: Color = RED;
+const PATROL_LINE: Color = ORANGE;
+const COLLECTIBLE_BOX: Color = YELLOW;
+const CHECKPOINT_BOX: Color = MAGENTA;
+const GOAL_BOX: Color = GOLD;
+const CAMERA_LINE: Color = WHITE;
+const VELOCITY_LINE: Color = LIME;
+
+/// Real time taken by recent frames, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct FrameTimes {
+    times: VecDeque<f32>,
+}
+
+impl FrameTimes {
+    pub fn push(&mut self, frame_time: f32) {
+        if self.times.len() == FRAME_HISTORY {
+            self.times.pop_front();
+        }
+        self.times.push_back(frame_time);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        self.times.iter().copied()
+    }
+
+    pub fn average(&self) -> f32 {
+        if self.times.is_empty() {
+            return 0.0;
+        }
+        self.times.iter().sum::<f32>() / self.times.len() as f32
+    }
+
+    pub fn max(&self) -> f32 {
+        self.times.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+/// Everything the debug overlay shows, borrowed from the game
+///
+/// The overlay only ever gets shared references, so drawing it cannot change
+/// what happens in the next frame.
+pub struct DebugView<'a> {
+    pub player: &'a Player,
+    pub level: &'a Level,
+    /// Survival enemies, which live outside the level
+    pub spawned: &'a [&'a Enemy],
+    pub camera: &'a Camera,
+}
+
+/// Collision boxes, patrol ranges, velocities and camera limits, in world space
+///
+/// Call with the world camera applied.
+pub fn draw_world_overlay(view: &DebugView) {
+    let level = view.level;
+
+    for platform in &level.platforms {
+        let color = if platform.active { PLATFORM_BOX } else { INACTIVE_BOX };
+        draw_aabb(&platform.aabb(), color);
+        if platform.start_pos != platform.end_pos {
+            draw_line(
+                platform.start_pos.x,
+                platform.start_pos.y,
+                platform.end_pos.x,
+                platform.end_pos.y,
+                1.0,
+                PATROL_LINE,
+            );
+        }
+    }
+
+    for enemy in level.enemies.iter().chain(view.spawned.iter().copied()) {
+        if enemy.alive {
+            draw_enemy(enemy);
+        }
+    }
+
+    for collectible in level.collectibles.iter().filter(|c| !c.collected) {
+        draw_aabb(&collectible.aabb(), COLLECTIBLE_BOX);
+    }
+
+    for checkpoint in &level.checkpoints {
+        draw_aabb(&checkpoint.aabb(), CHECKPOINT_BOX);
+    }
+
+    if level.data.survival.is_none() {
+        draw_aabb(&level.goal_aabb(), GOAL_BOX);
+    }
+
+    draw_aabb(&view.player.body.aabb(), PLAYER_BOX);
+    draw_velocity(&view.player.body);
+
+    draw_camera(view.camera);
+}
+
+/// Frame-time graph and mode labels, in screen space
+pub fn draw_screen_overlay(frame_times: &FrameTimes, photo: Option<&PhotoCamera>) {
+    let (x, y, width, height) = (screen_width() - 250.0, 60.0, 240.0, 60.0);
+    // Bars reach the top at 30 fps
+    let full_scale = 1.0 / 30.0;
+
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.6));
+    let bar_width = width / FRAME_HISTORY as f32;
+    for (i, frame_time) in frame_times.iter().enumerate() {
+        let bar_height = (frame_time / full_scale).min(1.0) * height;
+        let color = if frame_time > 1.0 / 55.0 { RED } else { GREEN };
+        draw_rectangle(
+            x + i as f32 * bar_width,
+            y + height - bar_height,
+            bar_width,
+            bar_height,
+            color,
+        );
+    }
+    // 60 fps line
+    let target_y = y + height - (1.0 / 60.0) / full_scale * height;
+    draw_line(x, target_y, x + width, target_y, 1.0, WHITE);
+
+    draw_text(
+        &format!(
+            "frame {:.1} ms avg, {:.1} ms max",
+            frame_times.average() * 1000.0,
+            frame_times.max() * 1000.0
+        ),
+        x,
+        y + height + 16.0,
+        16.0,
+        WHITE,
+    );
+
+    match photo {
+        Some(photo) => draw_photo_hint(photo),
+        None => {
+            draw_text("DEBUG (F3)  F4 photo mode", 10.0, screen_height() - 30.0, 16.0, YELLOW);
+        }
+    }
+}
+
+/// Photo mode zoom and controls along the bottom of the screen
+pub fn draw_photo_hint(photo: &PhotoCamera) {
+    draw_text(
+        &format!(
+            "PHOTO {:.2}x  arrows/WASD pan, Q/E or wheel zoom, R reset, F4 exit",
+            photo.zoom
+        ),
+        10.0,
+        screen_height() - 30.0,
+        16.0,
+        YELLOW,
+    );
+}
+
+fn draw_enemy(enemy: &Enemy) {
+    draw_aabb(&enemy.aabb(), ENEMY_BOX);
+    match enemy.enemy_type {
+        EnemyType::Walker | EnemyType::Patroller => {
+            let y = enemy.body.position.y + enemy.body.size.y;
+            draw_line(enemy.patrol_start, y, enemy.patrol_end, y, 2.0, PATROL_LINE);
+            draw_line(enemy.patrol_start, y - 6.0, enemy.patrol_start, y + 6.0, 2.0, PATROL_LINE);
+            draw_line(enemy.patrol_end, y - 6.0, enemy.patrol_end, y + 6.0, 2.0, PATROL_LINE);
+        }
+        EnemyType::Flyer => draw_circle_lines(
+            enemy.fly_center.x,
+            enemy.fly_center.y,
+            enemy.fly_radius,
+            1.0,
+            PATROL_LINE,
+        ),
+    }
+    draw_velocity(&enemy.body);
+}
+
+/// Where the body will be in `VELOCITY_ARROW_SECONDS` at its current velocity
+fn draw_velocity(body: &PhysicsBody) {
+    let start = body.position + body.size / 2.0;
+    let end = start + body.velocity * VELOCITY_ARROW_SECONDS;
+    draw_line(start.x, start.y, end.x, end.y, 2.0, VELOCITY_LINE);
+    draw_circle(end.x, end.y, 3.0, VELOCITY_LINE);
+}
+
+/// Level bounds the camera is clamped to, what it shows, and where it is heading
+///
+/// The camera has no dead zone: it always eases towards a target centred on
+/// the player, so the gap between the view and the target is the follow lag.
+fn draw_camera(camera: &Camera) {
+    if let Some((min_x, min_y, max_x, max_y)) = camera.bounds {
+        draw_rectangle_lines(min_x, min_y, max_x - min_x, max_y - min_y, 4.0, CAMERA_LINE);
+    }
+    let view = AABB::new(
+        camera.position.x,
+        camera.position.y,
+        camera.screen_width,
+        camera.screen_height,
+    );
+    draw_aabb(&view, CAMERA_LINE);
+
+    let half_screen = Vec2::new(camera.screen_width, camera.screen_height) / 2.0;
+    let center = camera.position + half_screen;
+    let target = camera.target + half_screen;
+    draw_line(center.x - 8.0, center.y, center.x + 8.0, center.y, 1.0, CAMERA_LINE);
+    draw_line(center.x, center.y - 8.0, center.x, center.y + 8.0, 1.0, CAMERA_LINE);
+    draw_line(center.x, center.y, target.x, target.y, 1.0, CAMERA_LINE);
+    draw_circle_lines(target.x, target.y, 6.0, 1.0, CAMERA_LINE);
+}
+
+fn draw_aabb(aabb: &AABB, color: Color) {
+    draw_rectangle_lines(aabb.x, aabb.y, aabb.width, aabb.height, 2.0, color);
+}
@@ -0,0 +1,208 @@
+use crate::camera::Camera;
+use crate::physics::AABB;
+use macroquad::prelude::*;
+
+/// Closest and furthest photo mode zoom
+pub const MIN_ZOOM: f32 = 0.1;
+pub const MAX_ZOOM: f32 = 8.0;
+
+/// Keyboard pan speed in screen pixels per second
+const PAN_SPEED: f32 = 600.0;
+/// Zoom factor per second while Q or E is held
+const ZOOM_RATE: f32 = 2.0;
+/// Zoom factor per mouse wheel step
+const WHEEL_ZOOM: f32 = 1.1;
+
+/// Free-fly camera for photo mode, detached from the gameplay camera
+///
+/// `center` is the world point in the middle of the screen and `zoom` the
+/// number of screen pixels per world pixel. Moving it never touches the
+/// gameplay [`Camera`], so leaving photo mode puts the view back on the player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhotoCamera {
+    pub center: Vec2,
+    pub zoom: f32,
+    pub screen_size: Vec2,
+}
+
+impl PhotoCamera {
+    /// Start where the gameplay camera is looking, at 1x
+    pub fn from_camera(camera: &Camera) -> Self {
+        let screen_size = Vec2::new(camera.screen_width, camera.screen_height);
+        Self {
+            center: camera.position + screen_size / 2.0,
+            zoom: 1.0,
+            screen_size,
+        }
+    }
+
+    /// Gameplay camera showing the same view at 1x
+    fn unzoomed(&self) -> Camera {
+        let mut camera = Camera::new(self.screen_size.x, self.screen_size.y);
+        camera.position = self.center - self.screen_size / 2.0;
+        camera
+    }
+
+    #[cfg(test)]
+    pub fn world_to_screen(&self, world_pos: Vec2) -> Vec2 {
+        let half_screen = self.screen_size / 2.0;
+        (self.unzoomed().world_to_screen(world_pos) - half_screen) * self.zoom + half_screen
+    }
+
+    pub fn screen_to_world(&self, screen_pos: Vec2) -> Vec2 {
+        let half_screen = self.screen_size / 2.0;
+        self.unzoomed()
+            .screen_to_world((screen_pos - half_screen) / self.zoom + half_screen)
+    }
+
+    /// Move the view by `screen_delta` screen pixels, whatever the zoom
+    pub fn pan(&mut self, screen_delta: Vec2) {
+        self.center += screen_delta / self.zoom;
+    }
+
+    /// Zoom by `factor`, keeping the world point under `screen_pos` in place
+    pub fn zoom_at(&mut self, screen_pos: Vec2, factor: f32) {
+        let anchor = self.screen_to_world(screen_pos);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.center += anchor - self.screen_to_world(screen_pos);
+    }
+
+    /// World area on screen
+    pub fn view(&self) -> AABB {
+        let top_left = self.screen_to_world(Vec2::ZERO);
+        let size = self.screen_size / self.zoom;
+        AABB::new(top_left.x, top_left.y, size.x, size.y)
+    }
+
+    /// Pan and zoom from the keyboard and mouse wheel
+    pub fn handle_input(&mut self, delta_time: f32) {
+        let mut direction = Vec2::ZERO;
+        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+            direction.x -= 1.0;
+        }
+        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+            direction.x += 1.0;
+        }
+        if is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) {
+            direction.y -= 1.0;
+        }
+        if is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) {
+            direction.y += 1.0;
+        }
+        self.pan(direction * PAN_SPEED * delta_time);
+
+        let screen_center = self.screen_size / 2.0;
+        if is_key_down(KeyCode::E) {
+            self.zoom_at(screen_center, ZOOM_RATE.powf(delta_time));
+        }
+        if is_key_down(KeyCode::Q) {
+            self.zoom_at(screen_center, ZOOM_RATE.powf(-delta_time));
+        }
+        let wheel = mouse_wheel().1;
+        if wheel != 0.0 {
+            let factor = if wheel > 0.0 { WHEEL_ZOOM } else { 1.0 / WHEEL_ZOOM };
+            self.zoom_at(Vec2::from(mouse_position()), factor);
+        }
+        if is_key_pressed(KeyCode::R) {
+            self.zoom = 1.0;
+        }
+    }
+
+    /// Macroquad camera for the photo view
+    ///
+    /// Uses the same axes as the gameplay [`Camera::camera2d`], so entering
+    /// photo mode at 1x shows exactly what was on screen.
+    pub fn camera2d(&self) -> Camera2D {
+        Camera2D {
+            target: self.center,
+            zoom: Vec2::new(
+                2.0 * self.zoom / self.screen_size.x,
+                -2.0 * self.zoom / self.screen_size.y,
+            ),
+            offset: Vec2::ZERO,
+            rotation: 0.0,
+            render_target: None,
+            viewport: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::camera::Camera as _;
+
+    const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    fn camera_at(x: f32, y: f32) -> Camera {
+        let mut camera = Camera::new(SCREEN.x, SCREEN.y);
+        camera.position = Vec2::new(x, y);
+        camera
+    }
+
+    /// Screen position for a macroquad camera's normalized device coordinates,
+    /// with the axes the gameplay camera uses
+    fn ndc_to_screen(ndc: Vec3) -> Vec2 {
+        Vec2::new((ndc.x / 2.0 + 0.5) * SCREEN.x, (ndc.y / 2.0 + 0.5) * SCREEN.y)
+    }
+
+    fn project(camera: &Camera2D, world_pos: Vec2) -> Vec2 {
+        ndc_to_screen(camera.matrix().transform_point3(vec3(world_pos.x, world_pos.y, 0.0)))
+    }
+
+    #[test]
+    fn test_photo_camera_starts_on_gameplay_view() {
+        let camera = camera_at(1200.0, 150.0);
+        let photo = PhotoCamera::from_camera(&camera);
+
+        assert_eq!(photo.zoom, 1.0);
+        for point in [Vec2::new(1200.0, 150.0), Vec2::new(1500.0, 420.0)] {
+            assert_near(photo.world_to_screen(point), camera.world_to_screen(point));
+            assert_near(photo.screen_to_world(camera.world_to_screen(point)), point);
+        }
+        let view = photo.view();
+        assert_eq!((view.x, view.y, view.width, view.height), (1200.0, 150.0, 800.0, 600.0));
+    }
+
+    #[test]
+    fn test_gameplay_camera_projection() {
+        // Anchors `project` to the existing camera transform
+        let camera = camera_at(300.0, 40.0);
+        for point in [Vec2::new(300.0, 40.0), Vec2::new(640.0, 380.0)] {
+            assert_near(project(&camera.camera2d(), point), camera.world_to_screen(point));
+        }
+    }
+
+    #[test]
+    fn test_camera2d_matches_world_to_screen() {
+        let mut photo = PhotoCamera::from_camera(&camera_at(300.0, 40.0));
+        photo.zoom_at(Vec2::new(100.0, 500.0), 2.5);
+        photo.pan(Vec2::new(-70.0, 30.0));
+
+        for point in [Vec2::new(300.0, 40.0), Vec2::new(640.0, 380.0), Vec2::new(-50.0, 900.0)] {
+            assert_near(project(&photo.camera2d(), point), photo.world_to_screen(point));
+        }
+    }
+
+    #[test]
+    fn test_zoom_keeps_anchor_under_cursor() {
+        let mut photo = PhotoCamera::from_camera(&camera_at(0.0, 0.0));
+        let cursor = Vec2::new(650.0, 120.0);
+        let anchor = photo.screen_to_world(cursor);
+
+        photo.zoom_at(cursor, 2.0);
+        assert_eq!(photo.zoom, 2.0);
+        assert_near(photo.world_to_screen(anchor), cursor);
+
+        photo.zoom_at(cursor, 0.25);
+        assert_eq!(photo.zoom, 0.5);
+        assert_near(photo.world_to_screen(anchor), cursor);
+
+        let view = photo.view();
+        assert_eq!((view.width, view.height), (1600.0, 1200.0));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::physics::AABB;
+use crate::settings::Palette;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -72,11 +73,13 @@ impl Collectible {
         }
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, palette: &Palette) {
         if self.collected {
             return;
         }
 
+        let color = palette.collectible(self.collectible_type);
+
         let x = self.position.x;
         let y = self.position.y + self.bob_offset;
         let rotation = self.animation_timer;
@@ -85,7 +88,7 @@ impl Collectible {
             CollectibleType::Coin => {
                 // Draw spinning coin
                 let scale = (rotation.cos() * 0.5 + 0.5).max(0.2);
-                draw_circle(x, y, self.size * scale, GOLD);
+                draw_circle(x, y, self.size * scale, color);
                 draw_circle_lines(x, y, self.size * scale, 2.0, ORANGE);
             }
             CollectibleType::Gem => {
@@ -99,34 +102,34 @@ impl Collectible {
 
                 for i in 0..4 {
                     let next = (i + 1) % 4;
-                    draw_line(points[i].x, points[i].y, points[next].x, points[next].y, 3.0, BLUE);
+                    draw_line(points[i].x, points[i].y, points[next].x, points[next].y, 3.0, color);
                 }
                 draw_circle(x, y, self.size * 0.3, SKYBLUE);
             }
             CollectibleType::HealthPack => {
                 // Draw health cross
                 draw_rectangle(x - self.size * 0.5, y - self.size * 0.2,
-                    self.size, self.size * 0.4, RED);
+                    self.size, self.size * 0.4, color);
                 draw_rectangle(x - self.size * 0.2, y - self.size * 0.5,
-                    self.size * 0.4, self.size, RED);
+                    self.size * 0.4, self.size, color);
                 draw_rectangle_lines(x - self.size * 0.5, y - self.size * 0.5,
                     self.size, self.size, 2.0, WHITE);
             }
             CollectibleType::ExtraLife => {
                 // Draw heart
-                draw_circle(x - self.size * 0.25, y - self.size * 0.2, self.size * 0.4, PINK);
-                draw_circle(x + self.size * 0.25, y - self.size * 0.2, self.size * 0.4, PINK);
+                draw_circle(x - self.size * 0.25, y - self.size * 0.2, self.size * 0.4, color);
+                draw_circle(x + self.size * 0.25, y - self.size * 0.2, self.size * 0.4, color);
                 draw_triangle(
                     Vec2::new(x - self.size * 0.6, y - self.size * 0.1),
                     Vec2::new(x + self.size * 0.6, y - self.size * 0.1),
                     Vec2::new(x, y + self.size * 0.6),
-                    PINK,
+                    color,
                 );
             }
             CollectibleType::DoubleJump => {
                 // Draw boot with upward arrow
                 draw_rectangle(x - self.size * 0.4, y - self.size * 0.2,
-                    self.size * 0.8, self.size * 0.6, DARKGREEN);
+                    self.size * 0.8, self.size * 0.6, color);
                 draw_triangle(
                     Vec2::new(x, y - self.size * 0.7),
                     Vec2::new(x - self.size * 0.3, y - self.size * 0.2),
@@ -138,13 +141,6 @@ impl Collectible {
 
         // Draw glow effect
         let glow_radius = self.size + (rotation.sin() * 2.0).abs();
-        let glow_color = match self.collectible_type {
-            CollectibleType::Coin => Color::new(1.0, 0.84, 0.0, 0.2),
-            CollectibleType::Gem => Color::new(0.0, 0.5, 1.0, 0.2),
-            CollectibleType::HealthPack => Color::new(1.0, 0.0, 0.0, 0.2),
-            CollectibleType::ExtraLife => Color::new(1.0, 0.7, 0.8, 0.2),
-            CollectibleType::DoubleJump => Color::new(0.0, 1.0, 0.0, 0.2),
-        };
-        draw_circle(x, y, glow_radius, glow_color);
+        draw_circle(x, y, glow_radius, Color { a: 0.2, ..color });
     }
 }
@@ -1,4 +1,5 @@
 use crate::physics::{PhysicsBody, AABB};
+use crate::settings::Palette;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -120,6 +121,7 @@ impl Enemy {
                         self.facing_right = true;
                     }
                 }
+                self.body.position.x += self.body.velocity.x * delta_time;
             }
             EnemyType::Flyer => {
                 // Circular flying pattern
@@ -143,7 +145,7 @@ impl Enemy {
         self.body.aabb()
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, palette: &Palette) {
         if !self.alive {
             return;
         }
@@ -151,11 +153,7 @@ impl Enemy {
         let pos = self.body.position;
         let size = self.body.size;
 
-        let color = match self.enemy_type {
-            EnemyType::Walker => RED,
-            EnemyType::Flyer => PINK,
-            EnemyType::Patroller => MAROON,
-        };
+        let color = palette.enemy(self.enemy_type);
 
         // Draw enemy body
         draw_rectangle(pos.x, pos.y, size.x, size.y, color);
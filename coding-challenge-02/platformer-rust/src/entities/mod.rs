@@ -4,7 +4,7 @@ pub mod enemy;
 pub mod collectible;
 pub mod checkpoint;
 
-pub use player::Player;
+pub use player::{JumpTiming, Player, PlayerInput};
 pub use platform::{Platform, PlatformType};
 pub use enemy::{Enemy, EnemyType};
 pub use collectible::{Collectible, CollectibleType};
@@ -1,4 +1,5 @@
 use crate::physics::AABB;
+use crate::settings::Palette;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -112,24 +113,16 @@ impl Platform {
         AABB::new(self.x, self.y, self.width, self.height)
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, palette: &Palette) {
         if !self.active {
             return;
         }
 
-        let color = match self.platform_type {
-            PlatformType::Solid => DARKBROWN,
-            PlatformType::Moving => ORANGE,
-            PlatformType::Disappearing => {
-                if self.triggered {
-                    // Fade out effect
-                    let alpha = 1.0 - (self.disappear_timer / 0.8).min(1.0);
-                    Color::new(1.0, 0.0, 1.0, alpha)
-                } else {
-                    PURPLE
-                }
-            }
-        };
+        let mut color = palette.platform(self.platform_type);
+        if self.platform_type == PlatformType::Disappearing && self.triggered {
+            // Fade out effect
+            color.a = 1.0 - (self.disappear_timer / 0.8).min(1.0);
+        }
 
         draw_rectangle(self.x, self.y, self.width, self.height, color);
 
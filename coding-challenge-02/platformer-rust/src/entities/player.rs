@@ -1,6 +1,52 @@
 use crate::physics::{PhysicsBody, JUMP_VELOCITY, DOUBLE_JUMP_VELOCITY, PLAYER_SPEED};
 use macroquad::prelude::*;
 
+/// How forgiving jump timing is, in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JumpTiming {
+    /// A jump pressed this long before landing still happens on landing
+    pub buffer: f32,
+    /// A ground jump is still allowed this long after walking off a ledge
+    pub coyote: f32,
+}
+
+impl JumpTiming {
+    /// Default windows, a few frames each at 60 FPS
+    pub const STANDARD: JumpTiming = JumpTiming { buffer: 0.1, coyote: 0.08 };
+
+    /// Extended windows from the accessibility settings
+    pub const EXTENDED: JumpTiming = JumpTiming { buffer: 0.25, coyote: 0.2 };
+}
+
+/// Controls read for one frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerInput {
+    /// -1.0 for left, 1.0 for right, 0.0 for neither
+    pub move_x: f32,
+    /// Jump went down this frame
+    pub jump_pressed: bool,
+}
+
+impl PlayerInput {
+    /// Read the keyboard
+    pub fn from_keys() -> Self {
+        let mut move_x = 0.0;
+        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+            move_x -= 1.0;
+        }
+        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+            move_x += 1.0;
+        }
+
+        Self {
+            move_x,
+            jump_pressed: is_key_pressed(KeyCode::Space)
+                || is_key_pressed(KeyCode::W)
+                || is_key_pressed(KeyCode::Up),
+        }
+    }
+}
+
 /// Player state and abilities
 #[derive(Debug, Clone, PartialEq)]
 pub enum PlayerState {
@@ -26,6 +72,11 @@ pub struct Player {
     pub invulnerable_timer: f32,
     pub animation_timer: f32,
     pub animation_frame: usize,
+    pub jump_timing: JumpTiming,
+    /// Time left for a buffered jump press to trigger
+    pub jump_buffer_timer: f32,
+    /// Time left to jump after leaving the ground
+    pub coyote_timer: f32,
 }
 
 impl Player {
@@ -45,6 +96,9 @@ impl Player {
             invulnerable_timer: 0.0,
             animation_timer: 0.0,
             animation_frame: 0,
+            jump_timing: JumpTiming::STANDARD,
+            jump_buffer_timer: 0.0,
+            coyote_timer: 0.0,
         }
     }
 
@@ -54,6 +108,8 @@ impl Player {
         self.state = PlayerState::Idle;
         self.invulnerable = true;
         self.invulnerable_timer = 2.0;
+        self.jump_buffer_timer = 0.0;
+        self.coyote_timer = 0.0;
     }
 
     pub fn update(&mut self, delta_time: f32) {
@@ -91,32 +147,49 @@ impl Player {
         }
     }
 
-    pub fn handle_input(&mut self) {
+    /// Move and jump for one frame, before physics runs
+    ///
+    /// `body.on_ground` is still last frame's result here. A jump press is
+    /// remembered for `jump_timing.buffer` seconds and fires as soon as the
+    /// player can ground jump; walking off a ledge leaves
+    /// `jump_timing.coyote` seconds to still ground jump. A fresh press that
+    /// can't ground jump uses the double jump instead, if there is one.
+    pub fn apply_input(&mut self, input: PlayerInput, delta_time: f32) {
         if self.state == PlayerState::Dead {
             return;
         }
 
         // Horizontal movement
-        let mut move_x = 0.0;
-        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
-            move_x -= 1.0;
+        if input.move_x < 0.0 {
             self.facing_right = false;
-        }
-        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
-            move_x += 1.0;
+        } else if input.move_x > 0.0 {
             self.facing_right = true;
         }
+        self.body.velocity.x = input.move_x * PLAYER_SPEED;
 
-        self.body.velocity.x = move_x * PLAYER_SPEED;
+        // Jump windows
+        if self.body.on_ground {
+            self.coyote_timer = self.jump_timing.coyote;
+        } else {
+            self.coyote_timer = (self.coyote_timer - delta_time).max(0.0);
+        }
+        if input.jump_pressed {
+            self.jump_buffer_timer = self.jump_timing.buffer;
+        } else {
+            self.jump_buffer_timer = (self.jump_buffer_timer - delta_time).max(0.0);
+        }
 
         // Jumping
-        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::W) || is_key_pressed(KeyCode::Up) {
-            if self.body.on_ground {
+        if input.jump_pressed || self.jump_buffer_timer > 0.0 {
+            if self.body.on_ground || self.coyote_timer > 0.0 {
                 self.body.velocity.y = JUMP_VELOCITY;
                 self.has_double_jumped = false;
-            } else if self.can_double_jump && !self.has_double_jumped {
+                self.jump_buffer_timer = 0.0;
+                self.coyote_timer = 0.0;
+            } else if input.jump_pressed && self.can_double_jump && !self.has_double_jumped {
                 self.body.velocity.y = DOUBLE_JUMP_VELOCITY;
                 self.has_double_jumped = true;
+                self.jump_buffer_timer = 0.0;
             }
         }
     }
@@ -0,0 +1,349 @@
+use crate::scoring::RunStats;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Entries shown per level
+pub const TOP_ENTRIES: usize = 10;
+
+/// Environment variable overriding the endpoint in `save.json`
+pub const ENDPOINT_ENV: &str = "PLATFORMER_LEADERBOARD_URL";
+
+/// Environment variable overriding the signing key (client and server)
+pub const KEY_ENV: &str = "PLATFORMER_LEADERBOARD_KEY";
+
+/// Key shared by the game and the reference server
+///
+/// It ships with the game, so signing only stops hand-written requests and
+/// casual edits of a payload, not a determined cheat.
+pub const DEFAULT_KEY: &str = "platformer-rust leaderboard v1";
+
+/// How long a request may take before the leaderboard counts as offline
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The signing key: `PLATFORMER_LEADERBOARD_KEY` or the built-in one
+pub fn signing_key() -> Vec<u8> {
+    std::env::var(KEY_ENV)
+        .map(String::into_bytes)
+        .unwrap_or_else(|_| DEFAULT_KEY.as_bytes().to_vec())
+}
+
+/// Stable id for a level on the server, from its name ("Tutorial Valley"
+/// becomes "tutorial-valley")
+pub fn level_id(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A level time as `M:SS.cc`
+pub fn format_time(time_ms: u32) -> String {
+    let centis = time_ms / 10;
+    format!(
+        "{}:{:02}.{:02}",
+        centis / 6000,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+/// A completed level, as sent to the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub player: String,
+    pub level: String,
+    /// Milliseconds from level start to the goal
+    pub time_ms: u32,
+    pub score: i32,
+    /// Percentage of the level's collectibles picked up, 0 to 100
+    pub collection: u8,
+}
+
+impl Submission {
+    pub fn new(player: &str, level: &str, stats: &RunStats) -> Self {
+        Self {
+            player: player.to_string(),
+            level: level.to_string(),
+            time_ms: (stats.time * 1000.0).round() as u32,
+            score: stats.score,
+            collection: stats.collection_percent().floor().clamp(0.0, 100.0) as u8,
+        }
+    }
+
+    /// The exact text the signature covers, one field per line
+    pub fn signing_message(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n{}",
+            self.player, self.level, self.time_ms, self.score, self.collection
+        )
+    }
+
+    /// HMAC-SHA256 of the signing message, as lower-case hex
+    pub fn signature(&self, key: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+        mac.update(self.signing_message().as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn sign(self, key: &[u8]) -> SignedSubmission {
+        let signature = self.signature(key);
+        SignedSubmission {
+            submission: self,
+            signature,
+        }
+    }
+}
+
+/// A submission with its signature, the body of `POST /scores`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedSubmission {
+    #[serde(flatten)]
+    pub submission: Submission,
+    pub signature: String,
+}
+
+/// Reply to `POST /scores`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    /// The player's place on the level (1 is the fastest), by their best time
+    pub rank: usize,
+}
+
+/// One player's best run on a level
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub player: String,
+    pub time_ms: u32,
+    pub score: i32,
+    pub collection: u8,
+}
+
+/// Reply to `GET /levels/{level}/top?player=NAME`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelBoard {
+    pub level: String,
+    /// Fastest runs first, at most `TOP_ENTRIES`
+    pub top: Vec<Entry>,
+    /// The asking player's place, if they have a time on the level
+    #[serde(default)]
+    pub player_rank: Option<usize>,
+    /// The asking player's best run, for showing below the top 10
+    #[serde(default)]
+    pub player_entry: Option<Entry>,
+    /// Unix time the game received this board, for showing cached results
+    #[serde(default)]
+    pub fetched_at: u64,
+}
+
+/// Leaderboard options, kept in the save file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardConfig {
+    /// Name shown next to your times
+    #[serde(default = "default_player_name")]
+    pub player_name: String,
+    /// Server base URL, e.g. `http://127.0.0.1:7878`; no leaderboard without one
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+fn default_player_name() -> String {
+    "Player".to_string()
+}
+
+impl Default for LeaderboardConfig {
+    fn default() -> Self {
+        Self {
+            player_name: default_player_name(),
+            endpoint: None,
+        }
+    }
+}
+
+impl LeaderboardConfig {
+    /// The endpoint from `PLATFORMER_LEADERBOARD_URL`, else the save file
+    pub fn endpoint(&self) -> Option<String> {
+        std::env::var(ENDPOINT_ENV)
+            .ok()
+            .or_else(|| self.endpoint.clone())
+            .filter(|endpoint| !endpoint.trim().is_empty())
+    }
+}
+
+/// Boards last fetched and times not yet accepted by the server, kept in
+/// the save file so both survive going offline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardCache {
+    /// Latest board for each level id
+    #[serde(default)]
+    pub boards: BTreeMap<String, LevelBoard>,
+    /// Signed submissions to retry, oldest first
+    #[serde(default)]
+    pub pending: Vec<SignedSubmission>,
+}
+
+impl LeaderboardCache {
+    /// Forget a submission the server has accepted
+    pub fn accepted(&mut self, submission: &SignedSubmission) {
+        self.pending
+            .retain(|pending| pending.signature != submission.signature);
+    }
+}
+
+/// What the leaderboard screen has for the selected level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardStatus {
+    /// No endpoint configured
+    Disabled,
+    /// A request is on its way
+    Loading,
+    /// The board was fetched this session
+    Live,
+    /// The request failed; any board shown is from the cache
+    Offline(String),
+}
+
+/// Blocking HTTP client for the leaderboard server
+#[derive(Clone)]
+pub struct Client {
+    endpoint: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+        }
+    }
+
+    /// Send a level time; returns the player's rank on that level
+    pub fn submit(&self, submission: &SignedSubmission) -> Result<usize, String> {
+        let response = self
+            .agent
+            .post(&format!("{}/scores", self.endpoint))
+            .send_json(submission)
+            .map_err(describe_error)?;
+        let reply: SubmitResponse = response
+            .into_json()
+            .map_err(|e| format!("Bad leaderboard reply: {}", e))?;
+        Ok(reply.rank)
+    }
+
+    /// The top times on `level`, with `player`'s place
+    pub fn fetch(&self, level: &str, player: &str) -> Result<LevelBoard, String> {
+        let response = self
+            .agent
+            .get(&format!("{}/levels/{}/top", self.endpoint, level))
+            .query("player", player)
+            .call()
+            .map_err(describe_error)?;
+        let mut board: LevelBoard = response
+            .into_json()
+            .map_err(|e| format!("Bad leaderboard reply: {}", e))?;
+        board.fetched_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Ok(board)
+    }
+}
+
+fn describe_error(error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!(
+                "Leaderboard refused the request ({}): {}",
+                code,
+                body.trim()
+            )
+        }
+        ureq::Error::Transport(transport) => format!("Leaderboard unreachable: {}", transport),
+    }
+}
+
+/// A finished leaderboard request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Submitted {
+        submission: SignedSubmission,
+        rank: usize,
+    },
+    SubmitFailed {
+        submission: SignedSubmission,
+        error: String,
+    },
+    Fetched(LevelBoard),
+    FetchFailed {
+        level: String,
+        error: String,
+    },
+}
+
+enum Request {
+    Submit(SignedSubmission),
+    Fetch { level: String, player: String },
+}
+
+/// Runs leaderboard requests on a background thread so the game never
+/// waits on the network
+///
+/// Requests are answered in order; `poll` collects the replies once a frame.
+pub struct LeaderboardService {
+    requests: Sender<Request>,
+    replies: Receiver<Reply>,
+}
+
+impl LeaderboardService {
+    pub fn new(client: Client) -> Self {
+        let (requests, incoming) = mpsc::channel::<Request>();
+        let (outgoing, replies) = mpsc::channel();
+
+        // The thread ends when the service (and so the request sender) is dropped
+        thread::spawn(move || {
+            for request in incoming {
+                let reply = match request {
+                    Request::Submit(submission) => match client.submit(&submission) {
+                        Ok(rank) => Reply::Submitted { submission, rank },
+                        Err(error) => Reply::SubmitFailed { submission, error },
+                    },
+                    Request::Fetch { level, player } => match client.fetch(&level, &player) {
+                        Ok(board) => Reply::Fetched(board),
+                        Err(error) => Reply::FetchFailed { level, error },
+                    },
+                };
+                if outgoing.send(reply).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { requests, replies }
+    }
+
+    pub fn submit(&self, submission: SignedSubmission) {
+        let _ = self.requests.send(Request::Submit(submission));
+    }
+
+    pub fn fetch(&self, level: &str, player: &str) {
+        let _ = self.requests.send(Request::Fetch {
+            level: level.to_string(),
+            player: player.to_string(),
+        });
+    }
+
+    /// Replies that have arrived since the last poll
+    pub fn poll(&self) -> Vec<Reply> {
+        self.replies.try_iter().collect()
+    }
+}
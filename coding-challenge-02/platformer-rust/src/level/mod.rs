@@ -1,4 +1,16 @@
+mod streaming;
+mod stress;
+
+pub use streaming::*;
+pub use stress::*;
+
+use crate::audio::MusicData;
+use crate::cutscene::CutsceneScript;
 use crate::entities::*;
+use crate::physics::AABB;
+use crate::scoring::RankThresholds;
+use crate::settings::Palette;
+use crate::survival::SurvivalConfig;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -16,6 +28,22 @@ pub struct LevelData {
     pub checkpoints: Vec<CheckpointData>,
     pub goal_x: f32,
     pub goal_y: f32,
+    /// End-of-level rank requirements
+    #[serde(default)]
+    pub rank_thresholds: RankThresholds,
+    /// Script played when the level starts
+    #[serde(default)]
+    pub intro: Option<CutsceneScript>,
+    /// Script played after reaching the goal, before the next level
+    #[serde(default)]
+    pub outro: Option<CutsceneScript>,
+    /// Spawners and wave tables; a level with these is a survival arena
+    /// and has no goal
+    #[serde(default)]
+    pub survival: Option<SurvivalConfig>,
+    /// Background music; the level is silent without it
+    #[serde(default)]
+    pub music: Option<MusicData>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +89,7 @@ pub struct Level {
     pub checkpoints: Vec<Checkpoint>,
     pub completed: bool,
     pub last_checkpoint: usize,
+    pub streaming: ChunkGrid,
 }
 
 impl Level {
@@ -117,6 +146,8 @@ impl Level {
             .map(|c| Checkpoint::new(c.x, c.y))
             .collect();
 
+        let streaming = ChunkGrid::new(data.width, data.height, CHUNK_SIZE);
+
         Self {
             data,
             platforms,
@@ -125,6 +156,7 @@ impl Level {
             checkpoints,
             completed: false,
             last_checkpoint: 0,
+            streaming,
         }
     }
 
@@ -136,7 +168,59 @@ impl Level {
         Ok(Self::from_data(data))
     }
 
+    /// Area the player has to touch to finish the level
+    pub fn goal_aabb(&self) -> AABB {
+        AABB::new(self.data.goal_x - 30.0, self.data.goal_y - 30.0, 60.0, 60.0)
+    }
+
+    /// Only simulate and draw entities that can reach `area`
+    pub fn set_active_window(&mut self, area: AABB) {
+        self.streaming.set_window(
+            &area,
+            &self.platforms,
+            &self.enemies,
+            &self.collectibles,
+            &self.checkpoints,
+        );
+    }
+
+    /// Simulate and draw the whole level again
+    pub fn clear_active_window(&mut self) {
+        self.streaming.clear_window();
+    }
+
+    /// Entities whose reach overlaps `area`, for collision checks
+    pub fn entities_near(&mut self, area: &AABB) -> ChunkEntities {
+        self.streaming.query(
+            area,
+            &self.platforms,
+            &self.enemies,
+            &self.collectibles,
+            &self.checkpoints,
+        )
+    }
+
     pub fn update(&mut self, delta_time: f32) {
+        // Entities in frozen chunks are skipped and keep their state
+        if let Some(active) = self.streaming.active() {
+            for &i in &active.platforms {
+                self.platforms[i].update(delta_time);
+            }
+
+            for &i in &active.enemies {
+                self.enemies[i].update(delta_time);
+            }
+
+            for &i in &active.collectibles {
+                self.collectibles[i].update(delta_time);
+            }
+
+            for &i in &active.checkpoints {
+                self.checkpoints[i].update(delta_time);
+            }
+            return;
+        }
+
         for platform in &mut self.platforms {
             platform.update(delta_time);
         }
@@ -196,25 +280,47 @@ impl Level {
         }
     }
 
-    pub fn draw(&self) {
-        // Draw platforms
-        for platform in &self.platforms {
-            platform.draw();
-        }
+    pub fn draw(&self, palette: &Palette) {
+        if let Some(active) = self.streaming.active() {
+            for &i in &active.platforms {
+                self.platforms[i].draw(palette);
+            }
 
-        // Draw enemies
-        for enemy in &self.enemies {
-            enemy.draw();
-        }
+            for &i in &active.enemies {
+                self.enemies[i].draw(palette);
+            }
+
+            for &i in &active.collectibles {
+                self.collectibles[i].draw(palette);
+            }
 
-        // Draw collectibles
-        for collectible in &self.collectibles {
-            collectible.draw();
+            for &i in &active.checkpoints {
+                self.checkpoints[i].draw();
+            }
+        } else {
+            // Draw platforms
+            for platform in &self.platforms {
+                platform.draw(palette);
+            }
+
+            // Draw enemies
+            for enemy in &self.enemies {
+                enemy.draw(palette);
+            }
+
+            // Draw collectibles
+            for collectible in &self.collectibles {
+                collectible.draw(palette);
+            }
+
+            // Draw checkpoints
+            for checkpoint in &self.checkpoints {
+                checkpoint.draw();
+            }
         }
 
-        // Draw checkpoints
-        for checkpoint in &self.checkpoints {
-            checkpoint.draw();
+        if self.data.survival.is_some() {
+            return;
         }
 
         // Draw goal
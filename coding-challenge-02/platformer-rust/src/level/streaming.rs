@@ -0,0 +1,436 @@
+use crate::entities::*;
+use crate::physics::AABB;
+use macroquad::prelude::*;
+
+/// Width and height of a streaming chunk (pixels)
+pub const CHUNK_SIZE: f32 = 512.0;
+
+/// Extra space kept active around the camera view (pixels)
+pub const ACTIVE_MARGIN: f32 = 256.0;
+
+/// Area of the level that should be simulated for a camera view
+pub fn active_area(camera_position: Vec2, screen_width: f32, screen_height: f32) -> AABB {
+    AABB::new(
+        camera_position.x - ACTIVE_MARGIN,
+        camera_position.y - ACTIVE_MARGIN,
+        screen_width + ACTIVE_MARGIN * 2.0,
+        screen_height + ACTIVE_MARGIN * 2.0,
+    )
+}
+
+/// Indices of the entities that can reach a region of the level
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkEntities {
+    pub platforms: Vec<usize>,
+    pub enemies: Vec<usize>,
+    pub collectibles: Vec<usize>,
+    pub checkpoints: Vec<usize>,
+}
+
+impl ChunkEntities {
+    fn extend(&mut self, other: &ChunkEntities) {
+        self.platforms.extend_from_slice(&other.platforms);
+        self.enemies.extend_from_slice(&other.enemies);
+        self.collectibles.extend_from_slice(&other.collectibles);
+        self.checkpoints.extend_from_slice(&other.checkpoints);
+    }
+
+    /// Sort and remove entities listed by more than one chunk
+    fn dedup(&mut self) {
+        for list in [
+            &mut self.platforms,
+            &mut self.enemies,
+            &mut self.collectibles,
+            &mut self.checkpoints,
+        ] {
+            list.sort_unstable();
+            list.dedup();
+        }
+    }
+}
+
+/// Area an enemy can occupy over its whole patrol or flight path
+pub fn enemy_reach(enemy: &Enemy) -> AABB {
+    let size = enemy.body.size;
+    match enemy.enemy_type {
+        EnemyType::Walker | EnemyType::Patroller => {
+            let min_x = enemy.patrol_start.min(enemy.body.position.x);
+            let max_x = enemy.patrol_end.max(enemy.body.position.x);
+            AABB::new(min_x, enemy.body.position.y, max_x - min_x + size.x, size.y)
+        }
+        EnemyType::Flyer => AABB::new(
+            enemy.fly_center.x - enemy.fly_radius,
+            enemy.fly_center.y - enemy.fly_radius,
+            enemy.fly_radius * 2.0 + size.x,
+            enemy.fly_radius * 2.0 + size.y,
+        ),
+    }
+}
+
+/// Area a platform can occupy over its whole movement path
+pub fn platform_reach(platform: &Platform) -> AABB {
+    if platform.platform_type != PlatformType::Moving {
+        return platform.aabb();
+    }
+
+    let min = platform.start_pos.min(platform.end_pos);
+    let max = platform.start_pos.max(platform.end_pos);
+    AABB::new(
+        min.x,
+        min.y,
+        max.x - min.x + platform.width,
+        max.y - min.y + platform.height,
+    )
+}
+
+/// Spatial partition of a level into fixed-size chunks
+///
+/// Chunk contents are built the first time a chunk is touched, either by
+/// entering the active window or by a collision query. Entities are listed
+/// in every chunk their reach overlaps, so a moving platform or patrolling
+/// enemy stays live while any part of its path is near the camera.
+///
+/// Chunks outside the active window are frozen: their entities are not
+/// updated or drawn and keep their exact state until the window returns.
+pub struct ChunkGrid {
+    chunk_size: f32,
+    columns: usize,
+    rows: usize,
+    chunks: Vec<Option<ChunkEntities>>,
+    active_chunks: Vec<usize>,
+    active: Option<ChunkEntities>,
+}
+
+impl ChunkGrid {
+    pub fn new(width: f32, height: f32, chunk_size: f32) -> Self {
+        let columns = ((width / chunk_size).ceil() as usize).max(1);
+        let rows = ((height / chunk_size).ceil() as usize).max(1);
+
+        Self {
+            chunk_size,
+            columns,
+            rows,
+            chunks: (0..columns * rows).map(|_| None).collect(),
+            active_chunks: Vec::new(),
+            active: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of chunks whose contents have been built so far
+    #[cfg(test)]
+    pub fn built_chunks(&self) -> usize {
+        self.chunks.iter().filter(|chunk| chunk.is_some()).count()
+    }
+
+    /// Chunks currently inside the active window
+    #[cfg(test)]
+    pub fn active_chunks(&self) -> &[usize] {
+        &self.active_chunks
+    }
+
+    /// Entities inside the active window, or `None` when streaming is off
+    pub fn active(&self) -> Option<&ChunkEntities> {
+        self.active.as_ref()
+    }
+
+    /// Whether a chunk is currently frozen
+    #[cfg(test)]
+    pub fn is_frozen(&self, chunk: usize) -> bool {
+        self.active.is_some() && !self.active_chunks.contains(&chunk)
+    }
+
+    /// Chunk indices overlapping an area, clamped to the level
+    pub fn chunks_overlapping(&self, area: &AABB) -> Vec<usize> {
+        let column_range = Self::cell_range(area.x, area.width, self.chunk_size, self.columns);
+        let row_range = Self::cell_range(area.y, area.height, self.chunk_size, self.rows);
+
+        let mut indices = Vec::new();
+        for row in row_range {
+            for column in column_range.clone() {
+                indices.push(row * self.columns + column);
+            }
+        }
+        indices
+    }
+
+    fn cell_range(start: f32, length: f32, cell: f32, count: usize) -> std::ops::Range<usize> {
+        let first = (start / cell).floor().max(0.0) as usize;
+        let last = ((start + length) / cell).floor().max(0.0) as usize;
+        first.min(count - 1)..last.min(count - 1) + 1
+    }
+
+    /// Build a chunk's entity lists if it has not been touched yet
+    fn ensure_built(
+        &mut self,
+        chunk: usize,
+        platforms: &[Platform],
+        enemies: &[Enemy],
+        collectibles: &[Collectible],
+        checkpoints: &[Checkpoint],
+    ) {
+        if self.chunks[chunk].is_some() {
+            return;
+        }
+
+        let contents = ChunkEntities {
+            platforms: self.indices_in_chunk(chunk, platforms, platform_reach),
+            enemies: self.indices_in_chunk(chunk, enemies, enemy_reach),
+            collectibles: self.indices_in_chunk(chunk, collectibles, Collectible::aabb),
+            checkpoints: self.indices_in_chunk(chunk, checkpoints, Checkpoint::aabb),
+        };
+        self.chunks[chunk] = Some(contents);
+    }
+
+    /// Items whose reach overlaps a chunk
+    ///
+    /// Reach is clamped to the grid, so edge chunks also own anything that
+    /// pokes outside the level bounds.
+    fn indices_in_chunk<T>(
+        &self,
+        chunk: usize,
+        items: &[T],
+        reach: impl Fn(&T) -> AABB,
+    ) -> Vec<usize> {
+        let column = chunk % self.columns;
+        let row = chunk / self.columns;
+
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let area = reach(item);
+                Self::cell_range(area.x, area.width, self.chunk_size, self.columns)
+                    .contains(&column)
+                    && Self::cell_range(area.y, area.height, self.chunk_size, self.rows)
+                        .contains(&row)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Entities that can reach an area, building chunks on demand
+    pub fn query(
+        &mut self,
+        area: &AABB,
+        platforms: &[Platform],
+        enemies: &[Enemy],
+        collectibles: &[Collectible],
+        checkpoints: &[Checkpoint],
+    ) -> ChunkEntities {
+        let mut result = ChunkEntities::default();
+        for chunk in self.chunks_overlapping(area) {
+            self.ensure_built(chunk, platforms, enemies, collectibles, checkpoints);
+            if let Some(contents) = &self.chunks[chunk] {
+                result.extend(contents);
+            }
+        }
+        result.dedup();
+        result
+    }
+
+    /// Move the active window, freezing chunks that leave it
+    pub fn set_window(
+        &mut self,
+        area: &AABB,
+        platforms: &[Platform],
+        enemies: &[Enemy],
+        collectibles: &[Collectible],
+        checkpoints: &[Checkpoint],
+    ) {
+        let chunks = self.chunks_overlapping(area);
+        if self.active.is_some() && chunks == self.active_chunks {
+            return;
+        }
+
+        self.active = Some(self.query(area, platforms, enemies, collectibles, checkpoints));
+        self.active_chunks = chunks;
+    }
+
+    /// Turn streaming off so every entity is active again
+    pub fn clear_window(&mut self) {
+        self.active = None;
+        self.active_chunks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::*;
+    use crate::scoring::RankThresholds;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn enemy(x: f32, y: f32, enemy_type: EnemyType) -> EnemyData {
+        EnemyData {
+            x,
+            y,
+            enemy_type,
+            patrol_start: Some(x - 100.0),
+            patrol_end: Some(x + 100.0),
+            radius: Some(50.0),
+        }
+    }
+
+    fn platform(x: f32, y: f32, platform_type: PlatformType, end_x: Option<f32>) -> PlatformData {
+        PlatformData {
+            x,
+            y,
+            width: 100.0,
+            height: 20.0,
+            platform_type,
+            end_x,
+            end_y: None,
+            speed: Some(50.0),
+        }
+    }
+
+    fn test_level() -> Level {
+        Level::from_data(LevelData {
+            name: "Streaming".to_string(),
+            width: 10000.0,
+            height: 600.0,
+            spawn_x: 100.0,
+            spawn_y: 400.0,
+            platforms: vec![
+                platform(0.0, 500.0, PlatformType::Solid, None),
+                platform(8000.0, 400.0, PlatformType::Moving, Some(8300.0)),
+            ],
+            enemies: vec![
+                enemy(300.0, 300.0, EnemyType::Flyer),
+                enemy(8000.0, 300.0, EnemyType::Flyer),
+                enemy(8500.0, 476.0, EnemyType::Walker),
+            ],
+            collectibles: vec![],
+            checkpoints: vec![],
+            goal_x: 9900.0,
+            goal_y: 450.0,
+            rank_thresholds: RankThresholds::default(),
+            intro: None,
+            outro: None,
+            survival: None,
+            music: None,
+        })
+    }
+
+    fn window_at(x: f32) -> AABB {
+        active_area(Vec2::new(x, 0.0), 800.0, 600.0)
+    }
+
+    #[test]
+    fn test_without_window_everything_updates() {
+        let mut level = test_level();
+        level.update(DT);
+
+        assert!(level.enemies[1].fly_angle > 0.0);
+        assert!(level.enemies[2].body.position.x > 8500.0);
+        assert!(level.platforms[1].x > 8000.0);
+    }
+
+    #[test]
+    fn test_frozen_enemies_do_not_advance() {
+        let mut level = test_level();
+        level.set_active_window(window_at(0.0));
+
+        let far_flyer = level.enemies[1].body.position;
+        let far_walker = level.enemies[2].body.position;
+        let far_platform = level.platforms[1].x;
+
+        for _ in 0..120 {
+            level.update(DT);
+        }
+
+        assert!(level.enemies[0].fly_angle > 0.0);
+        assert_eq!(level.enemies[1].fly_angle, 0.0);
+        assert_eq!(level.enemies[1].body.position, far_flyer);
+        assert_eq!(level.enemies[2].body.position, far_walker);
+        assert_eq!(level.platforms[1].x, far_platform);
+    }
+
+    #[test]
+    fn test_frozen_enemies_resume_where_they_stopped() {
+        let mut level = test_level();
+        level.set_active_window(window_at(7800.0));
+        for _ in 0..30 {
+            level.update(DT);
+        }
+
+        let flyer_angle = level.enemies[1].fly_angle;
+        let walker_x = level.enemies[2].body.position.x;
+        let walker_facing = level.enemies[2].facing_right;
+        let platform_x = level.platforms[1].x;
+        let platform_forward = level.platforms[1].moving_forward;
+        assert!(walker_x > 8500.0);
+
+        // Leave the area for a while
+        level.set_active_window(window_at(0.0));
+        for _ in 0..300 {
+            level.update(DT);
+        }
+        assert_eq!(level.enemies[1].fly_angle, flyer_angle);
+        assert_eq!(level.enemies[2].body.position.x, walker_x);
+        assert_eq!(level.enemies[2].facing_right, walker_facing);
+        assert_eq!(level.platforms[1].x, platform_x);
+        assert_eq!(level.platforms[1].moving_forward, platform_forward);
+
+        // Coming back continues from the frozen state
+        level.set_active_window(window_at(7800.0));
+        level.update(DT);
+        let flyer = &level.enemies[1];
+        assert!((flyer.fly_angle - (flyer_angle + flyer.fly_speed * DT)).abs() < 1e-5);
+        let walker = &level.enemies[2];
+        assert!((walker.body.position.x - (walker_x + walker.move_speed * DT)).abs() < 1e-3);
+        assert!((level.platforms[1].x - (platform_x + 50.0 * DT)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_collision_candidates_only_cover_nearby_chunks() {
+        let mut level = test_level();
+        assert_eq!(level.streaming.built_chunks(), 0);
+
+        let nearby = level.entities_near(&AABB::new(50.0, 450.0, 32.0, 48.0));
+        assert_eq!(nearby.platforms, vec![0]);
+        assert_eq!(nearby.enemies, vec![0]);
+        assert_eq!(level.streaming.built_chunks(), 1);
+
+        // The moving platform is a candidate anywhere along its path
+        let nearby = level.entities_near(&AABB::new(8350.0, 390.0, 32.0, 48.0));
+        assert_eq!(nearby.platforms, vec![1]);
+    }
+
+    #[test]
+    fn test_active_window_freezes_distant_chunks() {
+        let mut level = test_level();
+        level.set_active_window(window_at(0.0));
+
+        let active = level.streaming.active_chunks().to_vec();
+        assert!(!active.is_empty());
+        assert!(!level.streaming.is_frozen(active[0]));
+        assert!(level.streaming.is_frozen(level.streaming.columns() - 1));
+
+        level.clear_active_window();
+        assert!(level.streaming.active().is_none());
+        assert!(!level.streaming.is_frozen(level.streaming.columns() - 1));
+    }
+
+    #[test]
+    fn test_stress_level_streams_a_small_slice() {
+        let data = stress_test_level(500);
+        assert_eq!(data.enemies.len(), 500);
+        assert_eq!(data.width, 200000.0);
+
+        let mut level = Level::from_data(data);
+        level.set_active_window(window_at(100000.0));
+        level.update(DT);
+
+        let active = level.streaming.active().unwrap();
+        assert!(!active.enemies.is_empty());
+        assert!(active.enemies.len() < 10);
+        assert!(level.streaming.built_chunks() < level.streaming.columns());
+    }
+}
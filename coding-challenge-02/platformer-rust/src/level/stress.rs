@@ -0,0 +1,110 @@
+use super::*;
+use crate::scoring::RankThresholds;
+
+/// Horizontal space taken by one generated section (pixels)
+const SECTION_WIDTH: f32 = 400.0;
+
+/// Sections between generated checkpoints
+const CHECKPOINT_EVERY: usize = 10;
+
+/// Generate a very long level for exercising level streaming
+///
+/// Every section has ground, a floating platform, a coin and an enemy, with
+/// moving and disappearing platforms mixed in. The layout is deterministic so
+/// runs can be compared.
+pub fn stress_test_level(sections: usize) -> LevelData {
+    let sections = sections.max(1);
+    let width = sections as f32 * SECTION_WIDTH;
+    let height = 600.0;
+
+    let mut platforms = Vec::with_capacity(sections * 2);
+    let mut enemies = Vec::with_capacity(sections);
+    let mut collectibles = Vec::with_capacity(sections);
+    let mut checkpoints = Vec::new();
+
+    for i in 0..sections {
+        let x = i as f32 * SECTION_WIDTH;
+        let step = (i % 4) as f32 * 40.0;
+
+        // Ground with a small gap at the end of most sections
+        let ground_width = if i % 3 == 2 { 300.0 } else { SECTION_WIDTH };
+        platforms.push(PlatformData {
+            x,
+            y: 500.0,
+            width: ground_width,
+            height: 100.0,
+            platform_type: PlatformType::Solid,
+            end_x: None,
+            end_y: None,
+            speed: None,
+        });
+
+        let (platform_type, end_x, end_y, speed) = match i % 5 {
+            1 => (PlatformType::Moving, Some(x + 250.0), None, Some(60.0)),
+            3 => (PlatformType::Moving, None, Some(300.0), Some(40.0)),
+            4 => (PlatformType::Disappearing, None, None, None),
+            _ => (PlatformType::Solid, None, None, None),
+        };
+        platforms.push(PlatformData {
+            x: x + 100.0,
+            y: 380.0 - step,
+            width: 120.0,
+            height: 20.0,
+            platform_type,
+            end_x,
+            end_y: end_y.map(|y| y - step),
+            speed,
+        });
+
+        let enemy_type = match i % 3 {
+            0 => EnemyType::Walker,
+            1 => EnemyType::Flyer,
+            _ => EnemyType::Patroller,
+        };
+        enemies.push(EnemyData {
+            x: x + 200.0,
+            y: if enemy_type == EnemyType::Flyer {
+                300.0
+            } else {
+                476.0
+            },
+            enemy_type,
+            patrol_start: Some(x + 50.0),
+            patrol_end: Some(x + 250.0),
+            radius: Some(60.0),
+        });
+
+        collectibles.push(CollectibleData {
+            x: x + 160.0,
+            y: 350.0 - step,
+            collectible_type: if i % 10 == 9 {
+                CollectibleType::Gem
+            } else {
+                CollectibleType::Coin
+            },
+        });
+
+        if i > 0 && i % CHECKPOINT_EVERY == 0 {
+            checkpoints.push(CheckpointData { x, y: 500.0 });
+        }
+    }
+
+    LevelData {
+        name: format!("Stress Test ({} sections)", sections),
+        width,
+        height,
+        spawn_x: 100.0,
+        spawn_y: 400.0,
+        platforms,
+        enemies,
+        collectibles,
+        checkpoints,
+        goal_x: width - 100.0,
+        goal_y: 450.0,
+        rank_thresholds: RankThresholds::from_par(sections as i32 * 300, sections as f32 * 3.0),
+        intro: None,
+        outro: None,
+        survival: None,
+        music: None,
+    }
+}
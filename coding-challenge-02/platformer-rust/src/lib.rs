@@ -3,6 +3,14 @@ pub mod physics;
 pub mod entities;
 pub mod level;
 pub mod camera;
+pub mod debug;
+pub mod cutscene;
 pub mod particles;
 pub mod ui;
 pub mod audio;
+pub mod scoring;
+pub mod save;
+pub mod settings;
+pub mod survival;
+pub mod achievements;
+pub mod leaderboard;
@@ -1,23 +1,44 @@
+mod achievements;
 mod audio;
 mod camera;
+mod cutscene;
+mod debug;
 mod entities;
+mod leaderboard;
 mod level;
 mod particles;
 mod physics;
+mod save;
+mod scoring;
+mod settings;
+mod survival;
 mod ui;
 
-use audio::AudioSystem;
+use achievements::{Hook, RunStatistics};
+use audio::{AudioSystem, MusicData};
 use camera::Camera;
+use cutscene::{
+    CutsceneCommand, CutsceneEvent, CutsceneInput, CutscenePlayer, CutsceneScript, ParticleEffect,
+};
+use debug::{DebugView, FrameTimes, PhotoCamera};
 use entities::*;
-use level::Level;
+use leaderboard::{BoardStatus, Client, LeaderboardService, Reply, Submission};
+use level::{active_area, stress_test_level, Level};
 use macroquad::prelude::*;
 use particles::ParticleSystem;
 use physics::{resolve_collision, AABB};
+use save::{HighScore, SaveData, SAVE_PATH};
+use scoring::{ComboState, Rank, RankThresholds, RunStats, STOMP_POINTS};
+use settings::SettingsOption;
+use survival::{SpawnerData, SurvivalConfig, SurvivalEvent, SurvivalRun, WaveEntry, WaveTable};
 use ui::{Background, Menu, HUD};
 
 const SCREEN_WIDTH: f32 = 800.0;
 const SCREEN_HEIGHT: f32 = 600.0;
 
+/// Camera shake when the player is hit: (strength in pixels, seconds)
+const DAMAGE_SHAKE: (f32, f32) = (8.0, 0.3);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum GameState {
     MainMenu,
@@ -25,6 +46,9 @@ enum GameState {
     Paused,
     GameOver,
     Victory,
+    Settings,
+    Achievements,
+    Leaderboard,
 }
 
 struct Game {
@@ -39,6 +63,34 @@ struct Game {
     hud: HUD,
     menu: Menu,
     was_on_ground: bool,
+    combo: ComboState,
+    run_stats: RunStats,
+    /// Counters the achievements are judged on, kept for the whole run
+    statistics: RunStatistics,
+    ranks: Vec<Rank>,
+    /// Intro or outro script currently playing
+    cutscene: Option<CutscenePlayer>,
+    /// Level to load once the playing outro finishes
+    after_outro: Option<usize>,
+    /// Level used by survival mode instead of the campaign levels
+    arena: Level,
+    /// Wave state while playing survival mode
+    survival: Option<SurvivalRun>,
+    save: SaveData,
+    /// Place the last survival run took in the high-score table
+    high_score_place: Option<usize>,
+    /// Highlighted row on the settings screen
+    settings_row: usize,
+    /// Background client for the online leaderboard, if an endpoint is set
+    leaderboard: Option<LeaderboardService>,
+    /// Level shown on the leaderboard screen
+    leaderboard_level: usize,
+    leaderboard_status: BoardStatus,
+    /// F3: collision boxes, patrol ranges, camera limits and frame times
+    debug_overlay: bool,
+    /// F4: free camera while the simulation is paused
+    photo: Option<PhotoCamera>,
+    frame_times: FrameTimes,
 }
 
 impl Game {
@@ -50,6 +102,16 @@ impl Game {
         let mut camera = Camera::new(SCREEN_WIDTH, SCREEN_HEIGHT);
         camera.set_bounds(0.0, 0.0, first_level.data.width, first_level.data.height);
 
+        // Times that could not be sent last session go out again first
+        let save = SaveData::load_or_default(SAVE_PATH);
+        let leaderboard = save.leaderboard.endpoint().map(|endpoint| {
+            let service = LeaderboardService::new(Client::new(&endpoint));
+            for submission in &save.leaderboard_cache.pending {
+                service.submit(submission.clone());
+            }
+            service
+        });
+
         Self {
             state: GameState::MainMenu,
             player: Player::new(spawn_x, spawn_y),
@@ -60,12 +122,42 @@ impl Game {
             audio: AudioSystem::new(),
             background: Background::new(),
             hud: HUD::new(),
-            menu: Menu::new(vec!["Start Game", "Controls", "Quit"]),
+            menu: Menu::new(vec![
+                "Start Game",
+                "Survival",
+                "Achievements",
+                "Leaderboard",
+                "Settings",
+                "Controls",
+                "Quit",
+            ]),
             was_on_ground: false,
+            combo: ComboState::default(),
+            run_stats: RunStats::default(),
+            statistics: RunStatistics::default(),
+            ranks: Vec::new(),
+            cutscene: None,
+            after_outro: None,
+            arena: Self::create_arena(),
+            survival: None,
+            save,
+            high_score_place: None,
+            settings_row: 0,
+            leaderboard,
+            leaderboard_level: 0,
+            leaderboard_status: BoardStatus::Disabled,
+            debug_overlay: false,
+            photo: None,
+            frame_times: FrameTimes::default(),
         }
     }
 
     fn load_all_levels() -> Vec<Level> {
+        // A single very long generated level for profiling level streaming
+        if std::env::args().any(|arg| arg == "--stress") {
+            return vec![Level::from_data(stress_test_level(500))];
+        }
+
         // Try to load levels from files, fallback to hardcoded levels
         let mut levels = Vec::new();
 
@@ -210,6 +302,11 @@ impl Game {
             checkpoints: vec![CheckpointData { x: 1300.0, y: 400.0 }],
             goal_x: 1850.0,
             goal_y: 450.0,
+            rank_thresholds: RankThresholds::from_par(1200, 30.0),
+            intro: Some(Self::tutorial_intro()),
+            outro: None,
+            survival: None,
+            music: Some(level_music("valley")),
         };
 
         Level::from_data(data)
@@ -350,6 +447,11 @@ impl Game {
             checkpoints: vec![CheckpointData { x: 1350.0, y: 500.0 }],
             goal_x: 2350.0,
             goal_y: 450.0,
+            rank_thresholds: RankThresholds::from_par(1200, 40.0),
+            intro: None,
+            outro: None,
+            survival: None,
+            music: Some(level_music("platforms")),
         };
 
         Level::from_data(data)
@@ -500,6 +602,11 @@ impl Game {
             }],
             goal_x: 1850.0,
             goal_y: 650.0,
+            rank_thresholds: RankThresholds::from_par(1300, 40.0),
+            intro: None,
+            outro: None,
+            survival: None,
+            music: Some(level_music("sky")),
         };
 
         Level::from_data(data)
@@ -692,6 +799,11 @@ impl Game {
             ],
             goal_x: 2850.0,
             goal_y: 450.0,
+            rank_thresholds: RankThresholds::from_par(2000, 55.0),
+            intro: None,
+            outro: None,
+            survival: None,
+            music: Some(level_music("danger")),
         };
 
         Level::from_data(data)
@@ -940,49 +1052,562 @@ impl Game {
             ],
             goal_x: 3350.0,
             goal_y: 550.0,
+            rank_thresholds: RankThresholds::from_par(2800, 70.0),
+            intro: None,
+            outro: Some(Self::ending_outro()),
+            survival: None,
+            music: Some(level_music("finale")),
         };
 
         Level::from_data(data)
     }
 
+    fn create_arena() -> Level {
+        use level::*;
+
+        let solid = |x: f32, y: f32, width: f32, height: f32| PlatformData {
+            x,
+            y,
+            width,
+            height,
+            platform_type: PlatformType::Solid,
+            end_x: None,
+            end_y: None,
+            speed: None,
+        };
+        let ground = vec![EnemyType::Walker, EnemyType::Patroller];
+        let air = vec![EnemyType::Flyer];
+
+        let data = LevelData {
+            name: "Survival Arena".to_string(),
+            width: 1200.0,
+            height: 600.0,
+            spawn_x: 580.0,
+            spawn_y: 400.0,
+            platforms: vec![
+                // Floor and walls keep everyone inside
+                solid(0.0, 500.0, 1200.0, 100.0),
+                solid(0.0, 0.0, 20.0, 500.0),
+                solid(1180.0, 0.0, 20.0, 500.0),
+                // Ledges to escape to
+                solid(180.0, 380.0, 160.0, 20.0),
+                solid(860.0, 380.0, 160.0, 20.0),
+                solid(520.0, 270.0, 160.0, 20.0),
+            ],
+            enemies: vec![],
+            collectibles: vec![],
+            checkpoints: vec![],
+            goal_x: 0.0,
+            goal_y: 0.0,
+            rank_thresholds: RankThresholds::default(),
+            intro: None,
+            outro: None,
+            survival: Some(SurvivalConfig {
+                spawners: vec![
+                    SpawnerData {
+                        x: 150.0,
+                        y: 500.0,
+                        enemy_types: ground.clone(),
+                        range: 100.0,
+                    },
+                    SpawnerData {
+                        x: 1030.0,
+                        y: 500.0,
+                        enemy_types: ground.clone(),
+                        range: 100.0,
+                    },
+                    SpawnerData {
+                        x: 600.0,
+                        y: 500.0,
+                        enemy_types: ground,
+                        range: 250.0,
+                    },
+                    SpawnerData {
+                        x: 300.0,
+                        y: 200.0,
+                        enemy_types: air.clone(),
+                        range: 70.0,
+                    },
+                    SpawnerData {
+                        x: 900.0,
+                        y: 200.0,
+                        enemy_types: air,
+                        range: 70.0,
+                    },
+                ],
+                waves: WaveTable {
+                    base: vec![
+                        WaveEntry {
+                            enemy_type: EnemyType::Walker,
+                            count: 3,
+                        },
+                        WaveEntry {
+                            enemy_type: EnemyType::Flyer,
+                            count: 1,
+                        },
+                        WaveEntry {
+                            enemy_type: EnemyType::Patroller,
+                            count: 1,
+                        },
+                    ],
+                    spawn_interval: 1.5,
+                    difficulty_step: 0.35,
+                    min_spawn_interval: 0.4,
+                },
+                max_active: 8,
+                breather: 4.0,
+            }),
+            music: Some(level_music("arena")),
+        };
+
+        Level::from_data(data)
+    }
+
+    /// Level 1 intro: show the goal and the double jump, then the controls
+    fn tutorial_intro() -> CutsceneScript {
+        use CutsceneCommand::*;
+
+        CutsceneScript::new(vec![
+            LockInput { locked: true },
+            MoveCamera {
+                x: 1850.0,
+                y: 450.0,
+                duration: 2.0,
+            },
+            Dialogue {
+                speaker: Some("Guide".to_string()),
+                text: "Welcome to Tutorial Valley! Reach the glowing goal at the far end to finish the level."
+                    .to_string(),
+            },
+            MoveCamera {
+                x: 1000.0,
+                y: 450.0,
+                duration: 1.5,
+            },
+            SpawnParticles {
+                x: 1000.0,
+                y: 450.0,
+                effect: ParticleEffect::Sparkle,
+            },
+            Dialogue {
+                speaker: Some("Guide".to_string()),
+                text: "Grab the green orb on the way to unlock a double jump: press jump again in mid-air."
+                    .to_string(),
+            },
+            MoveCamera {
+                x: 120.0,
+                y: 420.0,
+                duration: 1.0,
+            },
+            LockInput { locked: false },
+            Dialogue {
+                speaker: Some("Guide".to_string()),
+                text: "Move with the arrow keys or WASD and jump with SPACE. Stomp enemies from above, \
+                       and touch checkpoints so you don't have to start over."
+                    .to_string(),
+            },
+        ])
+    }
+
+    /// Level 5 outro: celebrate at the final goal before the victory screen
+    fn ending_outro() -> CutsceneScript {
+        use CutsceneCommand::*;
+
+        CutsceneScript::new(vec![
+            LockInput { locked: true },
+            SpawnParticles {
+                x: 3350.0,
+                y: 550.0,
+                effect: ParticleEffect::Burst,
+            },
+            Wait { duration: 0.5 },
+            SpawnParticles {
+                x: 3300.0,
+                y: 500.0,
+                effect: ParticleEffect::Sparkle,
+            },
+            SpawnParticles {
+                x: 3400.0,
+                y: 500.0,
+                effect: ParticleEffect::Sparkle,
+            },
+            Wait { duration: 1.0 },
+            MoveCamera {
+                x: 1750.0,
+                y: 300.0,
+                duration: 3.0,
+            },
+            Dialogue {
+                speaker: None,
+                text: "The Final Challenge is behind you, and every level has been conquered.".to_string(),
+            },
+            Dialogue {
+                speaker: Some("Guide".to_string()),
+                text: "Thanks for playing! Try again for an S rank on every level.".to_string(),
+            },
+        ])
+    }
+
     fn start_game(&mut self) {
         self.state = GameState::Playing;
         self.current_level = 0;
+        self.ranks.clear();
+        self.hud.last_rank = None;
+        self.survival = None;
+        let gems = self
+            .levels
+            .iter()
+            .flat_map(|level| &level.collectibles)
+            .filter(|c| c.collectible_type == CollectibleType::Gem)
+            .count();
+        self.statistics = RunStatistics::new(self.levels.len(), gems);
         self.load_level(0);
     }
 
+    fn start_survival(&mut self) {
+        let Some(config) = self.arena.data.survival.clone() else {
+            return;
+        };
+
+        self.state = GameState::Playing;
+        self.hud.last_rank = None;
+        self.high_score_place = None;
+        self.survival = Some(SurvivalRun::new(config, survival_seed()));
+        self.audio.music.play(self.arena.data.music.as_ref());
+
+        let (spawn_x, spawn_y) = self.arena.get_spawn_point();
+        self.player = self.new_player(spawn_x, spawn_y);
+        self.camera
+            .set_bounds(0.0, 0.0, self.arena.data.width, self.arena.data.height);
+        self.particles.clear();
+        self.was_on_ground = false;
+        self.combo = ComboState::default();
+        self.run_stats = RunStats::default();
+        self.statistics = RunStatistics::default();
+        self.cutscene = None;
+        self.after_outro = None;
+
+        self.hud.show_message("Survive the waves!", 3.0);
+    }
+
+    /// Put a finished survival run in the high-score table and save it
+    fn record_survival(&mut self) {
+        let Some(run) = &self.survival else {
+            return;
+        };
+
+        self.high_score_place = self.save.record_survival(HighScore {
+            score: self.player.score,
+            wave: run.wave,
+            seed: run.seed,
+        });
+        if self.high_score_place.is_some() {
+            if let Err(e) = self.save.save_to_file(SAVE_PATH) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
     fn load_level(&mut self, level_index: usize) {
         if level_index >= self.levels.len() {
             self.state = GameState::Victory;
+            self.audio.music.play(None);
             self.audio.play_victory();
             return;
         }
 
         self.current_level = level_index;
         let level = &self.levels[level_index];
+        self.audio.music.play(level.data.music.as_ref());
         let (spawn_x, spawn_y) = level.get_spawn_point();
 
-        self.player = Player::new(spawn_x, spawn_y);
+        self.player = self.new_player(spawn_x, spawn_y);
         self.camera
             .set_bounds(0.0, 0.0, level.data.width, level.data.height);
         self.particles.clear();
         self.was_on_ground = false;
+        self.combo = ComboState::default();
+        self.run_stats = RunStats::default();
+        self.statistics.begin_level();
 
         self.hud
             .show_message(&format!("Level {}: {}", level_index + 1, level.data.name), 3.0);
+
+        let focus = self.player_center();
+        self.after_outro = None;
+        self.cutscene = level
+            .data
+            .intro
+            .as_ref()
+            .map(|script| CutscenePlayer::new(script, focus));
+    }
+
+    /// Unlock whatever the run has earned at `hook`, announce it and save
+    fn check_achievements(&mut self, hook: Hook) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let earned = achievements::unlock(hook, &self.statistics, &mut self.save.achievements, now);
+        if earned.is_empty() {
+            return;
+        }
+
+        for achievement in earned {
+            self.hud.show_achievement(achievement.name);
+        }
+        if let Err(e) = self.save.save_to_file(SAVE_PATH) {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Send a finished level's time to the leaderboard
+    ///
+    /// The signed time is kept in the save file until the server accepts it,
+    /// so it is sent again next session if the server can't be reached.
+    fn submit_time(&mut self, level_name: &str, stats: &RunStats) {
+        let Some(service) = &self.leaderboard else {
+            return;
+        };
+
+        let submission = Submission::new(
+            &self.save.leaderboard.player_name,
+            &leaderboard::level_id(level_name),
+            stats,
+        )
+        .sign(&leaderboard::signing_key());
+        self.save.leaderboard_cache.pending.push(submission.clone());
+        service.submit(submission);
+        if let Err(e) = self.save.save_to_file(SAVE_PATH) {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Ask the server for the board of the level on the leaderboard screen
+    fn request_board(&mut self) {
+        let Some(service) = &self.leaderboard else {
+            self.leaderboard_status = BoardStatus::Disabled;
+            return;
+        };
+
+        let level = leaderboard::level_id(&self.levels[self.leaderboard_level].data.name);
+        service.fetch(&level, &self.save.leaderboard.player_name);
+        self.leaderboard_status = BoardStatus::Loading;
+    }
+
+    /// Level selection on the leaderboard screen
+    fn update_leaderboard_screen(&mut self) {
+        let count = self.levels.len();
+        if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::A) {
+            self.leaderboard_level = (self.leaderboard_level + count - 1) % count;
+            self.request_board();
+        }
+        if is_key_pressed(KeyCode::Right) || is_key_pressed(KeyCode::D) {
+            self.leaderboard_level = (self.leaderboard_level + 1) % count;
+            self.request_board();
+        }
+        if is_key_pressed(KeyCode::R) {
+            self.request_board();
+        }
+        if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) {
+            self.state = GameState::MainMenu;
+        }
+    }
+
+    /// Apply leaderboard replies that arrived since the last frame
+    fn poll_leaderboard(&mut self) {
+        let Some(service) = &self.leaderboard else {
+            return;
+        };
+
+        let mut changed = false;
+        for reply in service.poll() {
+            match reply {
+                Reply::Submitted { submission, rank } => {
+                    self.save.leaderboard_cache.accepted(&submission);
+                    self.hud.show_message(&format!("Leaderboard: #{}", rank), 3.0);
+                    changed = true;
+                }
+                Reply::SubmitFailed { error, .. } => {
+                    // Still pending in the save file, so retried next session
+                    eprintln!("{}", error);
+                    self.hud.show_message("Leaderboard offline: time saved for later", 3.0);
+                }
+                Reply::Fetched(board) => {
+                    let selected =
+                        leaderboard::level_id(&self.levels[self.leaderboard_level].data.name);
+                    if board.level == selected {
+                        self.leaderboard_status = BoardStatus::Live;
+                    }
+                    self.save
+                        .leaderboard_cache
+                        .boards
+                        .insert(board.level.clone(), board);
+                    changed = true;
+                }
+                Reply::FetchFailed { level, error } => {
+                    let selected =
+                        leaderboard::level_id(&self.levels[self.leaderboard_level].data.name);
+                    if level == selected {
+                        self.leaderboard_status = BoardStatus::Offline(error);
+                    }
+                }
+            }
+        }
+
+        if changed {
+            if let Err(e) = self.save.save_to_file(SAVE_PATH) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
+    /// A fresh player using the jump timing from the settings
+    fn new_player(&self, x: f32, y: f32) -> Player {
+        let mut player = Player::new(x, y);
+        player.jump_timing = self.save.settings.jump_timing();
+        player
+    }
+
+    /// Handle input on the settings screen, saving on the way out
+    fn update_settings(&mut self) {
+        let rows = SettingsOption::ALL.len();
+        if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+            self.settings_row = (self.settings_row + rows - 1) % rows;
+        }
+        if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+            self.settings_row = (self.settings_row + 1) % rows;
+        }
+
+        let option = SettingsOption::ALL[self.settings_row];
+        if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::A) {
+            self.save.settings.adjust(option, -1);
+        }
+        if is_key_pressed(KeyCode::Right)
+            || is_key_pressed(KeyCode::D)
+            || is_key_pressed(KeyCode::Enter)
+            || is_key_pressed(KeyCode::Space)
+        {
+            self.save.settings.adjust(option, 1);
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            if let Err(e) = self.save.save_to_file(SAVE_PATH) {
+                eprintln!("{}", e);
+            }
+            self.state = GameState::MainMenu;
+        }
+    }
+
+    fn player_center(&self) -> Vec2 {
+        self.player.body.position + self.player.body.size / 2.0
+    }
+
+    /// Run the current cutscene for one frame
+    ///
+    /// Returns true while an outro is playing, in which case the rest of the
+    /// gameplay update is skipped.
+    fn update_cutscene(&mut self, delta_time: f32) -> bool {
+        let Some(cutscene) = &mut self.cutscene else {
+            return false;
+        };
+
+        for event in cutscene.update(delta_time, CutsceneInput::from_keys()) {
+            match event {
+                CutsceneEvent::SpawnParticles { x, y, effect } => match effect {
+                    ParticleEffect::Sparkle => self.particles.emit_collect(x, y, GOLD),
+                    ParticleEffect::Burst => self.particles.emit_enemy_death(x, y),
+                    ParticleEffect::Dust => self.particles.emit_landing(x, y),
+                },
+            }
+        }
+
+        if cutscene.is_finished() {
+            // A skip cuts straight back to the player instead of panning
+            if cutscene.was_skipped() {
+                let center = self.player_center();
+                self.camera.follow(center.x, center.y);
+                self.camera.position = self.camera.target;
+            }
+            self.cutscene = None;
+            if let Some(next) = self.after_outro.take() {
+                self.load_level(next);
+                return true;
+            }
+            return false;
+        }
+
+        if self.after_outro.is_none() {
+            return false;
+        }
+
+        // The goal has been reached: keep the world moving but nothing else
+        let level = &mut self.levels[self.current_level];
+        level.update(delta_time);
+        self.hud.update(delta_time);
+        self.update_camera(delta_time);
+        self.particles.update(delta_time);
+        true
+    }
+
+    /// Follow the player, or the cutscene's camera focus while it has one
+    fn update_camera(&mut self, delta_time: f32) {
+        let focus = self
+            .cutscene
+            .as_ref()
+            .and_then(|cutscene| cutscene.camera_focus())
+            .unwrap_or_else(|| self.player_center());
+        self.camera.follow(focus.x, focus.y);
+        self.camera.update(delta_time);
+    }
+
+    /// Enter or leave photo mode
+    fn toggle_photo_mode(&mut self) {
+        if self.photo.take().is_some() {
+            return;
+        }
+        self.photo = Some(PhotoCamera::from_camera(&self.camera));
+        // Draw the whole level while zoomed out; the next gameplay frame
+        // narrows the active window again
+        self.current_level_mut().clear_active_window();
+    }
+
+    fn current_level(&self) -> &Level {
+        match self.survival {
+            Some(_) => &self.arena,
+            None => &self.levels[self.current_level],
+        }
+    }
+
+    fn current_level_mut(&mut self) -> &mut Level {
+        match self.survival {
+            Some(_) => &mut self.arena,
+            None => &mut self.levels[self.current_level],
+        }
     }
 
     fn update(&mut self, delta_time: f32) {
+        self.frame_times.push(delta_time);
+
         match self.state {
             GameState::MainMenu => {
                 if let Some(choice) = self.menu.handle_input() {
                     match choice {
                         0 => self.start_game(),
-                        1 => {
+                        1 => self.start_survival(),
+                        2 => self.state = GameState::Achievements,
+                        3 => {
+                            self.state = GameState::Leaderboard;
+                            self.request_board();
+                        }
+                        4 => {
+                            self.settings_row = 0;
+                            self.state = GameState::Settings;
+                        }
+                        5 => {
                             // Show controls (we'll just start for now)
                             self.start_game();
                         }
-                        2 => {
+                        6 => {
                             // Quit
                             std::process::exit(0);
                         }
@@ -991,11 +1616,27 @@ impl Game {
                 }
             }
             GameState::Playing => {
-                self.update_gameplay(delta_time);
+                if is_key_pressed(KeyCode::F3) {
+                    self.debug_overlay = !self.debug_overlay;
+                }
+                if is_key_pressed(KeyCode::F4) {
+                    self.toggle_photo_mode();
+                }
 
-                // Pause on ESC
-                if is_key_pressed(KeyCode::Escape) {
-                    self.state = GameState::Paused;
+                if let Some(photo) = &mut self.photo {
+                    // The world stays frozen while the camera flies around
+                    photo.handle_input(delta_time);
+                    if is_key_pressed(KeyCode::Escape) {
+                        self.photo = None;
+                    }
+                } else {
+                    // Only gameplay slows down; menus and pausing run in real time
+                    self.update_gameplay(self.save.settings.scale_delta(delta_time));
+
+                    // Pause on ESC
+                    if is_key_pressed(KeyCode::Escape) {
+                        self.state = GameState::Paused;
+                    }
                 }
             }
             GameState::Paused => {
@@ -1004,6 +1645,7 @@ impl Game {
                 }
                 if is_key_pressed(KeyCode::Q) {
                     self.state = GameState::MainMenu;
+                    self.audio.music.play(None);
                 }
             }
             GameState::GameOver => {
@@ -1016,20 +1658,92 @@ impl Game {
                     self.state = GameState::MainMenu;
                 }
             }
+            GameState::Settings => self.update_settings(),
+            GameState::Achievements => {
+                if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Enter) {
+                    self.state = GameState::MainMenu;
+                }
+            }
+            GameState::Leaderboard => self.update_leaderboard_screen(),
         }
+
+        self.poll_leaderboard();
+
+        // Music fades run in real time, like the pause menu
+        let nearest_enemy = match self.state {
+            GameState::Playing => self.nearest_enemy_distance(),
+            _ => None,
+        };
+        self.audio.music.update(
+            delta_time,
+            nearest_enemy,
+            self.state == GameState::Paused,
+        );
+    }
+
+    /// Distance from the player to the closest living enemy, for the music's
+    /// intensity layer
+    fn nearest_enemy_distance(&self) -> Option<f32> {
+        let enemies = self
+            .current_level()
+            .enemies
+            .iter()
+            .filter(|enemy| enemy.alive)
+            .map(|enemy| enemy.body.position + enemy.body.size / 2.0);
+        audio::nearest_distance(self.player_center(), enemies)
     }
 
     fn update_gameplay(&mut self, delta_time: f32) {
-        let level = &mut self.levels[self.current_level];
+        if self.update_cutscene(delta_time) {
+            return;
+        }
+        let input_locked = self
+            .cutscene
+            .as_ref()
+            .is_some_and(|cutscene| cutscene.input_locked());
+
+        let level = match self.survival {
+            Some(_) => &mut self.arena,
+            None => &mut self.levels[self.current_level],
+        };
 
-        // Update level
+        // Update the part of the level around the camera
+        level.set_active_window(active_area(
+            self.camera.position,
+            SCREEN_WIDTH,
+            SCREEN_HEIGHT,
+        ));
         level.update(delta_time);
 
+        // Spawn the next survival enemies and check for cleared waves
+        if let Some(run) = &mut self.survival {
+            match run.update(delta_time) {
+                Some(SurvivalEvent::WaveStarted(wave)) => {
+                    self.hud.show_message(&format!("Wave {}", wave), 2.0);
+                }
+                Some(SurvivalEvent::WaveCleared { wave, bonus }) => {
+                    self.player.add_score(bonus);
+                    self.hud
+                        .show_message(&format!("Wave {} cleared! +{}", wave, bonus), 2.5);
+                    self.audio.play_level_complete();
+                }
+                None => {}
+            }
+        }
+
         // Update HUD
         self.hud.update(delta_time);
 
+        // Run clock and coin streak window
+        self.run_stats.time += delta_time;
+        self.combo = scoring::tick(self.combo, delta_time);
+
         // Handle player input
-        self.player.handle_input();
+        if input_locked {
+            self.player.body.velocity.x = 0.0;
+        } else {
+            self.player.apply_input(PlayerInput::from_keys(), delta_time);
+        }
 
         // Store previous ground state for landing detection
         let was_on_ground_before = self.player.body.on_ground;
@@ -1045,7 +1759,9 @@ impl Game {
         let player_aabb = AABB::new(new_x, new_y, self.player.body.size.x, self.player.body.size.y);
 
         // Check collisions with platforms
-        for platform in &mut level.platforms {
+        let nearby = level.entities_near(&player_aabb);
+        for &i in &nearby.platforms {
+            let platform = &mut level.platforms[i];
             if !platform.active {
                 continue;
             }
@@ -1095,6 +1811,12 @@ impl Game {
         // Update player state
         self.player.update(delta_time);
 
+        // Touching the ground ends a stomp chain
+        if self.player.body.on_ground {
+            self.combo = scoring::land(self.combo);
+            self.statistics.record_landing();
+        }
+
         // Check for jump particle effect
         if was_on_ground_before && !self.player.body.on_ground && self.player.body.velocity.y < 0.0 {
             self.particles.emit_jump(
@@ -1105,41 +1827,39 @@ impl Game {
         }
 
         // Check collisions with enemies
+        let health_before = self.player.health;
+        let stomped_before = self.statistics.enemies_stomped;
         let player_aabb = self.player.body.aabb();
-        for enemy in &mut level.enemies {
-            if !enemy.alive {
-                continue;
-            }
-
-            let enemy_aabb = enemy.aabb();
-            if player_aabb.intersects(&enemy_aabb) {
-                // Check if player is stomping enemy (from above)
-                if self.player.body.velocity.y > 0.0
-                    && player_aabb.center().y < enemy_aabb.center().y
-                {
-                    // Stomp enemy
-                    enemy.kill();
-                    self.player.body.velocity.y = -250.0; // Bounce
-                    self.player.add_score(200);
-                    self.particles.emit_enemy_death(
-                        enemy.body.position.x + enemy.body.size.x / 2.0,
-                        enemy.body.position.y + enemy.body.size.y / 2.0,
-                    );
-                    self.audio.play_enemy_death();
-                } else {
-                    // Take damage
-                    self.player.take_damage(1);
-                    self.particles.emit_damage(
-                        self.player.body.position.x + self.player.body.size.x / 2.0,
-                        self.player.body.position.y + self.player.body.size.y / 2.0,
-                    );
-                    self.audio.play_damage();
-                }
+        let nearby = level.entities_near(&player_aabb);
+        let mut contact = EnemyContact {
+            player: &mut self.player,
+            combo: &mut self.combo,
+            statistics: &mut self.statistics,
+            particles: &mut self.particles,
+            audio: &self.audio,
+        };
+        for &i in &nearby.enemies {
+            contact.touch(&player_aabb, &mut level.enemies[i]);
+        }
+        if let Some(run) = &mut self.survival {
+            for enemy in run.pool.iter_mut() {
+                contact.touch(&player_aabb, enemy);
             }
         }
+        if self.player.health < health_before && self.save.settings.screen_shake {
+            self.camera.shake(DAMAGE_SHAKE.0, DAMAGE_SHAKE.1);
+        }
+        if self.statistics.enemies_stomped > stomped_before {
+            self.check_achievements(Hook::StompCombo);
+        }
+        if self.player.health < health_before {
+            self.check_achievements(Hook::DamageTaken);
+        }
+        let level = &mut self.levels[self.current_level];
 
         // Check collisions with collectibles
-        for collectible in &mut level.collectibles {
+        for &i in &nearby.collectibles {
+            let collectible = &mut level.collectibles[i];
             if collectible.collected {
                 continue;
             }
@@ -1149,11 +1869,12 @@ impl Game {
                 self.player.add_score(collectible.score_value());
 
                 match collectible.collectible_type {
-                    CollectibleType::Coin => {
+                    CollectibleType::Coin | CollectibleType::Gem => {
+                        // Gems also count as special coins
                         self.player.collect_coin();
-                    }
-                    CollectibleType::Gem => {
-                        self.player.collect_coin(); // Gems also count as special coins
+                        let (combo, bonus) = scoring::collect_coin(self.combo);
+                        self.combo = combo;
+                        self.player.add_score(bonus);
                     }
                     CollectibleType::HealthPack => {
                         self.player.heal(1);
@@ -1167,13 +1888,11 @@ impl Game {
                     }
                 }
 
-                let color = match collectible.collectible_type {
-                    CollectibleType::Coin => GOLD,
-                    CollectibleType::Gem => BLUE,
-                    CollectibleType::HealthPack => RED,
-                    CollectibleType::ExtraLife => PINK,
-                    CollectibleType::DoubleJump => GREEN,
-                };
+                let color = self
+                    .save
+                    .settings
+                    .palette()
+                    .collectible(collectible.collectible_type);
 
                 self.particles.emit_collect(
                     collectible.position.x,
@@ -1185,7 +1904,8 @@ impl Game {
         }
 
         // Check collisions with checkpoints
-        for (i, checkpoint) in level.checkpoints.iter_mut().enumerate() {
+        for &i in &nearby.checkpoints {
+            let checkpoint = &mut level.checkpoints[i];
             if checkpoint.activated {
                 continue;
             }
@@ -1199,24 +1919,55 @@ impl Game {
         }
 
         // Check goal collision
-        let goal_aabb = AABB::new(
-            level.data.goal_x - 30.0,
-            level.data.goal_y - 30.0,
-            60.0,
-            60.0,
-        );
-        if player_aabb.intersects(&goal_aabb) {
+        if self.survival.is_none() && player_aabb.intersects(&level.goal_aabb()) {
             level.completed = true;
+
+            let stats = RunStats {
+                score: self.player.score,
+                collected: level.collectibles.iter().filter(|c| c.collected).count(),
+                total_collectibles: level.collectibles.len(),
+                ..self.run_stats
+            };
+            let rank = scoring::compute_rank(&stats, &level.data.rank_thresholds);
+            self.ranks.push(rank);
+            self.hud.last_rank = Some((self.current_level + 1, rank));
+
+            let gems: Vec<_> = level
+                .collectibles
+                .iter()
+                .filter(|c| c.collectible_type == CollectibleType::Gem)
+                .collect();
+            let gems_collected = gems.iter().filter(|c| c.collected).count();
+            self.statistics
+                .finish_level(self.current_level + 1, gems_collected, gems.len(), rank);
+            let outro = level.data.outro.clone();
+            let level_name = level.data.name.clone();
+            self.check_achievements(Hook::LevelEnd);
+            self.submit_time(&level_name, &stats);
+
             self.audio.play_level_complete();
-            self.load_level(self.current_level + 1);
+            match outro {
+                Some(outro) => {
+                    self.cutscene = Some(CutscenePlayer::new(&outro, self.player_center()));
+                    self.after_outro = Some(self.current_level + 1);
+                    self.player.body.velocity = Vec2::ZERO;
+                }
+                None => self.load_level(self.current_level + 1),
+            }
             return;
         }
 
         // Check if player fell off the map or died
         if self.player.body.position.y > level.data.height || self.player.is_dead() {
+            self.run_stats.deaths += 1;
+            self.statistics.record_death();
+            self.combo = scoring::take_damage(self.combo);
+
             if self.player.lives <= 0 {
                 self.state = GameState::GameOver;
+                self.audio.music.play(None);
                 self.audio.play_game_over();
+                self.record_survival();
             } else {
                 let (spawn_x, spawn_y) = level.get_spawn_point();
                 self.player.respawn(spawn_x, spawn_y);
@@ -1225,11 +1976,7 @@ impl Game {
         }
 
         // Update camera
-        self.camera.follow(
-            self.player.body.position.x + self.player.body.size.x / 2.0,
-            self.player.body.position.y + self.player.body.size.y / 2.0,
-        );
-        self.camera.update(delta_time);
+        self.update_camera(delta_time);
 
         // Update particles
         self.particles.update(delta_time);
@@ -1274,25 +2021,58 @@ impl Game {
                     );
                 }
             }
-            GameState::GameOver => {
-                ui::draw_game_over(self.player.score, self.current_level + 1);
-            }
+            GameState::GameOver => match &self.survival {
+                Some(run) => ui::draw_survival_over(
+                    self.player.score,
+                    run.wave,
+                    &self.save.survival_scores,
+                    self.high_score_place,
+                ),
+                None => ui::draw_game_over(self.player.score, self.current_level + 1),
+            },
             GameState::Victory => {
-                ui::draw_victory(self.player.score, self.levels.len());
+                ui::draw_victory(self.player.score, self.levels.len(), &self.ranks);
+            }
+            GameState::Settings => ui::draw_settings(&self.save.settings, self.settings_row),
+            GameState::Achievements => ui::draw_achievements(&self.save.achievements),
+            GameState::Leaderboard => {
+                let name = &self.levels[self.leaderboard_level].data.name;
+                ui::draw_leaderboard(
+                    name,
+                    self.leaderboard_level,
+                    self.levels.len(),
+                    self.save
+                        .leaderboard_cache
+                        .boards
+                        .get(&leaderboard::level_id(name)),
+                    &self.save.leaderboard.player_name,
+                    &self.leaderboard_status,
+                );
             }
         }
     }
 
     fn draw_gameplay(&self) {
         // Draw background with parallax
-        self.background.draw(self.camera.position.x);
+        let view_x = match &self.photo {
+            Some(photo) => photo.view().x,
+            None => self.camera.position.x,
+        };
+        self.background.draw(view_x);
 
         // Apply camera
-        self.camera.apply();
+        match &self.photo {
+            Some(photo) => set_camera(&photo.camera2d()),
+            None => self.camera.apply(),
+        }
 
         // Draw level
-        let level = &self.levels[self.current_level];
-        level.draw();
+        let level = self.current_level();
+        let palette = self.save.settings.palette();
+        level.draw(palette);
+        if let Some(run) = &self.survival {
+            run.pool.draw(palette);
+        }
 
         // Draw player
         self.player.draw();
@@ -1300,17 +2080,57 @@ impl Game {
         // Draw particles
         self.particles.draw();
 
+        if self.debug_overlay {
+            let spawned: Vec<&Enemy> = match &self.survival {
+                Some(run) => run.pool.iter().collect(),
+                None => Vec::new(),
+            };
+            debug::draw_world_overlay(&DebugView {
+                player: &self.player,
+                level,
+                spawned: &spawned,
+                camera: &self.camera,
+            });
+        }
+
         // Reset camera for UI
         self.camera.reset();
 
+        if self.debug_overlay {
+            debug::draw_screen_overlay(&self.frame_times, self.photo.as_ref());
+        }
+        if let Some(photo) = &self.photo {
+            if !self.debug_overlay {
+                debug::draw_photo_hint(photo);
+            }
+            return;
+        }
+
         // Draw HUD
+        let title = match &self.survival {
+            Some(run) => format!("Wave {}", run.wave.max(1)),
+            None => format!("Level {}", self.current_level + 1),
+        };
         self.hud.draw(
             self.player.health,
             self.player.lives,
             self.player.score,
             self.player.coins,
-            self.current_level + 1,
+            &title,
         );
+        if let Some(run) = &self.survival {
+            self.hud.draw_wave_status(run.remaining(), run.breather_left());
+        }
+        self.hud.draw_combo(&self.combo);
+        self.hud.draw_toast();
+
+        if let Some(cutscene) = &self.cutscene {
+            if let Some((speaker, text)) = cutscene.dialogue() {
+                ui::draw_dialogue_box(speaker, text);
+            }
+            ui::draw_skip_hint();
+            return;
+        }
 
         // Draw controls hint at bottom
         draw_text(
@@ -1323,6 +2143,80 @@ impl Game {
     }
 }
 
+/// Game state an enemy collision can change
+struct EnemyContact<'a> {
+    player: &'a mut Player,
+    combo: &'a mut ComboState,
+    statistics: &'a mut RunStatistics,
+    particles: &'a mut ParticleSystem,
+    audio: &'a AudioSystem,
+}
+
+impl EnemyContact<'_> {
+    /// Stomp the enemy if the player comes down on it, otherwise take a hit
+    fn touch(&mut self, player_aabb: &AABB, enemy: &mut Enemy) {
+        if !enemy.alive {
+            return;
+        }
+
+        let enemy_aabb = enemy.aabb();
+        if !player_aabb.intersects(&enemy_aabb) {
+            return;
+        }
+
+        // Check if player is stomping enemy (from above)
+        if self.player.body.velocity.y > 0.0 && player_aabb.center().y < enemy_aabb.center().y {
+            // Stomp enemy
+            enemy.kill();
+            self.player.body.velocity.y = -250.0; // Bounce
+            let (combo, points) = scoring::stomp(*self.combo, STOMP_POINTS);
+            *self.combo = combo;
+            self.statistics.record_stomp(combo.stomp_chain);
+            self.player.add_score(points);
+            self.particles.emit_enemy_death(
+                enemy.body.position.x + enemy.body.size.x / 2.0,
+                enemy.body.position.y + enemy.body.size.y / 2.0,
+            );
+            self.audio.play_enemy_death();
+        } else {
+            // Take damage
+            let health_before = self.player.health;
+            self.player.take_damage(1);
+            if self.player.health < health_before {
+                *self.combo = scoring::take_damage(*self.combo);
+                self.statistics.record_damage();
+            }
+            self.particles.emit_damage(
+                self.player.body.position.x + self.player.body.size.x / 2.0,
+                self.player.body.position.y + self.player.body.size.y / 2.0,
+            );
+            self.audio.play_damage();
+        }
+    }
+}
+
+/// Seed for survival waves: `--seed N` replays a run, otherwise the clock
+fn survival_seed() -> u64 {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2)
+        .find(|pair| pair[0] == "--seed")
+        .and_then(|pair| pair[1].parse().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0)
+        })
+}
+
+/// Music for a built-in level: `music/<name>.ogg` with an `_intense` stem
+fn level_music(name: &str) -> MusicData {
+    MusicData {
+        track: format!("music/{}.ogg", name),
+        intensity_stem: Some(format!("music/{}_intense.ogg", name)),
+    }
+}
+
 fn window_conf() -> Conf {
     Conf {
         window_title: "Rust Platformer - Coding Challenge".to_string(),
@@ -1,6 +1,24 @@
+use macroquad::models::{draw_mesh, Mesh, Vertex};
 use macroquad::prelude::*;
+use std::cell::RefCell;
 
-#[derive(Debug, Clone)]
+/// Default global particle budget (live particles across all effects)
+pub const DEFAULT_PARTICLE_BUDGET: usize = 4096;
+
+/// Sides of the polygon used to draw a particle
+const PARTICLE_SIDES: usize = 6;
+const VERTICES_PER_PARTICLE: usize = PARTICLE_SIDES + 1;
+const INDICES_PER_PARTICLE: usize = PARTICLE_SIDES * 3;
+
+/// Particles per mesh; macroquad's default draw call holds 10000 vertices
+/// and 5000 indices
+const PARTICLES_PER_BATCH: usize = 5000 / INDICES_PER_PARTICLE;
+
+/// Marks the end of the emission-order list
+const NONE: usize = usize::MAX;
+
+/// A single particle, used to describe emissions and to inspect the pool
+#[derive(Debug, Clone, Copy)]
 pub struct Particle {
     pub position: Vec2,
     pub velocity: Vec2,
@@ -29,37 +47,130 @@ impl Particle {
         }
     }
 
-    pub fn update(&mut self, delta_time: f32) {
-        self.position += self.velocity * delta_time;
-        self.velocity.y += 300.0 * delta_time; // Gravity
-        self.lifetime += delta_time;
-    }
-
+    #[cfg(test)]
     pub fn is_dead(&self) -> bool {
         self.lifetime >= self.max_lifetime
     }
-
-    pub fn draw(&self) {
-        let alpha = 1.0 - (self.lifetime / self.max_lifetime);
-        let color = Color::new(self.color.r, self.color.g, self.color.b, alpha);
-        draw_circle(self.position.x, self.position.y, self.size, color);
-    }
 }
 
+/// Fixed-capacity particle pool
+///
+/// Particle data is stored as parallel arrays and never reallocated after
+/// construction. Dead slots go on a freelist for reuse; when the budget is
+/// exhausted, new emissions replace the oldest live particles.
 pub struct ParticleSystem {
-    pub particles: Vec<Particle>,
+    positions: Vec<Vec2>,
+    velocities: Vec<Vec2>,
+    lifetimes: Vec<f32>,
+    max_lifetimes: Vec<f32>,
+    sizes: Vec<f32>,
+    colors: Vec<Color>,
+    alive: Vec<bool>,
+
+    /// Slots that have never been used or whose particle has died
+    free: Vec<usize>,
+
+    /// Live slots in emission order (intrusive doubly-linked list)
+    prev: Vec<usize>,
+    next: Vec<usize>,
+    oldest: usize,
+    newest: usize,
+
+    /// Number of live particles
+    count: usize,
+
+    /// Reused vertex and index buffers for batched drawing
+    batch: RefCell<Mesh>,
 }
 
 impl ParticleSystem {
     pub fn new() -> Self {
+        Self::with_budget(DEFAULT_PARTICLE_BUDGET)
+    }
+
+    /// Create a pool holding at most `budget` live particles
+    pub fn with_budget(budget: usize) -> Self {
+        let batch_particles = budget.min(PARTICLES_PER_BATCH);
+
         Self {
-            particles: Vec::new(),
+            positions: vec![Vec2::ZERO; budget],
+            velocities: vec![Vec2::ZERO; budget],
+            lifetimes: vec![0.0; budget],
+            max_lifetimes: vec![0.0; budget],
+            sizes: vec![0.0; budget],
+            colors: vec![WHITE; budget],
+            alive: vec![false; budget],
+            // Reversed so slots are handed out from the front
+            free: (0..budget).rev().collect(),
+            prev: vec![NONE; budget],
+            next: vec![NONE; budget],
+            oldest: NONE,
+            newest: NONE,
+            count: 0,
+            batch: RefCell::new(Mesh {
+                vertices: Vec::with_capacity(batch_particles * VERTICES_PER_PARTICLE),
+                indices: Vec::with_capacity(batch_particles * INDICES_PER_PARTICLE),
+                texture: None,
+            }),
         }
     }
 
+    /// Add a particle, replacing the oldest one if the budget is used up
+    pub fn emit(&mut self, particle: Particle) {
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None if self.oldest != NONE => {
+                let oldest = self.oldest;
+                self.kill(oldest);
+                self.free.pop().expect("killed slot is free")
+            }
+            // Zero budget: nothing can be emitted
+            None => return,
+        };
+
+        self.positions[slot] = particle.position;
+        self.velocities[slot] = particle.velocity;
+        self.lifetimes[slot] = particle.lifetime;
+        self.max_lifetimes[slot] = particle.max_lifetime;
+        self.sizes[slot] = particle.size;
+        self.colors[slot] = particle.color;
+        self.alive[slot] = true;
+
+        // Append to the emission-order list
+        self.prev[slot] = self.newest;
+        self.next[slot] = NONE;
+        if self.newest != NONE {
+            self.next[self.newest] = slot;
+        } else {
+            self.oldest = slot;
+        }
+        self.newest = slot;
+        self.count += 1;
+    }
+
+    /// Return a slot to the freelist
+    fn kill(&mut self, slot: usize) {
+        let (prev, next) = (self.prev[slot], self.next[slot]);
+
+        if prev != NONE {
+            self.next[prev] = next;
+        } else {
+            self.oldest = next;
+        }
+        if next != NONE {
+            self.prev[next] = prev;
+        } else {
+            self.newest = prev;
+        }
+
+        self.alive[slot] = false;
+        self.free.push(slot);
+        self.count -= 1;
+    }
+
     pub fn emit_jump(&mut self, x: f32, y: f32) {
         for _ in 0..8 {
-            self.particles.push(Particle::new(x, y, WHITE));
+            self.emit(Particle::new(x, y, WHITE));
         }
     }
 
@@ -67,44 +178,147 @@ impl ParticleSystem {
         for _ in 0..10 {
             let mut particle = Particle::new(x, y, BROWN);
             particle.velocity.y = particle.velocity.y.abs(); // Only downward
-            self.particles.push(particle);
+            self.emit(particle);
         }
     }
 
     pub fn emit_collect(&mut self, x: f32, y: f32, color: Color) {
         for _ in 0..15 {
-            self.particles.push(Particle::new(x, y, color));
+            self.emit(Particle::new(x, y, color));
         }
     }
 
     pub fn emit_enemy_death(&mut self, x: f32, y: f32) {
         for _ in 0..20 {
-            self.particles.push(Particle::new(x, y, RED));
+            self.emit(Particle::new(x, y, RED));
         }
     }
 
     pub fn emit_damage(&mut self, x: f32, y: f32) {
         for _ in 0..12 {
-            self.particles.push(Particle::new(x, y, RED));
+            self.emit(Particle::new(x, y, RED));
         }
     }
 
     pub fn update(&mut self, delta_time: f32) {
-        for particle in &mut self.particles {
-            particle.update(delta_time);
+        // Integrate every slot; dead ones are ignored and cheap to touch
+        for (position, velocity) in self.positions.iter_mut().zip(self.velocities.iter_mut()) {
+            *position += *velocity * delta_time;
+            velocity.y += 300.0 * delta_time; // Gravity
+        }
+
+        for lifetime in &mut self.lifetimes {
+            *lifetime += delta_time;
         }
 
-        self.particles.retain(|p| !p.is_dead());
+        for slot in 0..self.alive.len() {
+            if self.alive[slot] && self.lifetimes[slot] >= self.max_lifetimes[slot] {
+                self.kill(slot);
+            }
+        }
     }
 
+    /// Draw all particles, one mesh per batch of particles
     pub fn draw(&self) {
-        for particle in &self.particles {
-            particle.draw();
+        let mut batch = self.batch.borrow_mut();
+        let mut in_batch = 0;
+
+        for slot in (0..self.alive.len()).filter(|&slot| self.alive[slot]) {
+            let alpha = 1.0 - (self.lifetimes[slot] / self.max_lifetimes[slot]);
+            let color = self.colors[slot];
+            let color = Color::new(color.r, color.g, color.b, alpha);
+
+            Self::push_polygon(&mut batch, self.positions[slot], self.sizes[slot], color);
+            in_batch += 1;
+
+            if in_batch == PARTICLES_PER_BATCH {
+                Self::flush(&mut batch);
+                in_batch = 0;
+            }
+        }
+
+        Self::flush(&mut batch);
+    }
+
+    /// Append a filled polygon approximating a circle
+    fn push_polygon(batch: &mut Mesh, center: Vec2, radius: f32, color: Color) {
+        let base = batch.vertices.len() as u16;
+
+        batch.vertices.push(Vertex::new(center.x, center.y, 0.0, 0.0, 0.0, color));
+        for side in 0..PARTICLE_SIDES {
+            let angle = side as f32 / PARTICLE_SIDES as f32 * std::f32::consts::TAU;
+            batch.vertices.push(Vertex::new(
+                center.x + angle.cos() * radius,
+                center.y + angle.sin() * radius,
+                0.0,
+                0.0,
+                0.0,
+                color,
+            ));
+        }
+
+        for side in 0..PARTICLE_SIDES as u16 {
+            let next = (side + 1) % PARTICLE_SIDES as u16;
+            batch.indices.extend_from_slice(&[base, base + 1 + side, base + 1 + next]);
         }
     }
 
+    /// Submit the batch as a single draw call and reset it
+    fn flush(batch: &mut Mesh) {
+        if !batch.indices.is_empty() {
+            draw_mesh(batch);
+        }
+
+        batch.vertices.clear();
+        batch.indices.clear();
+    }
+
     pub fn clear(&mut self) {
-        self.particles.clear();
+        for slot in 0..self.alive.len() {
+            if self.alive[slot] {
+                self.kill(slot);
+            }
+        }
+    }
+
+    /// Number of live particles
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Global particle budget
+    #[cfg(test)]
+    pub fn capacity(&self) -> usize {
+        self.alive.len()
+    }
+
+    /// Live particles, oldest first
+    #[cfg(test)]
+    pub fn iter(&self) -> impl Iterator<Item = Particle> + '_ {
+        let mut slot = self.oldest;
+
+        std::iter::from_fn(move || {
+            if slot == NONE {
+                return None;
+            }
+
+            let particle = Particle {
+                position: self.positions[slot],
+                velocity: self.velocities[slot],
+                color: self.colors[slot],
+                lifetime: self.lifetimes[slot],
+                max_lifetime: self.max_lifetimes[slot],
+                size: self.sizes[slot],
+            };
+            slot = self.next[slot];
+            Some(particle)
+        })
     }
 }
 
@@ -113,3 +327,143 @@ impl Default for ParticleSystem {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// Counts allocations made on the current thread, so tests running in
+    /// parallel don't see each other's allocations
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn count_allocation() {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count_allocation();
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count_allocation();
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(|count| count.get())
+    }
+
+    fn particle(color: Color) -> Particle {
+        Particle {
+            position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            color,
+            lifetime: 0.0,
+            max_lifetime: 1.0,
+            size: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_stress_no_allocation_after_warmup() {
+        let mut system = ParticleSystem::with_budget(10_000);
+
+        // Warm up: fill the pool once and let it drain
+        for _ in 0..500 {
+            system.emit_enemy_death(100.0, 100.0);
+        }
+        assert_eq!(system.len(), 10_000);
+        system.update(1.0);
+        assert!(system.is_empty());
+
+        let before = allocations();
+
+        // 10k particles emitted over a few frames, then overflowing the budget
+        for frame in 0..60 {
+            for _ in 0..200 {
+                system.emit_enemy_death(frame as f32, 100.0);
+            }
+            system.update(0.016);
+        }
+
+        assert_eq!(allocations(), before, "particle system allocated after warmup");
+        assert!(system.len() <= system.capacity());
+    }
+
+    #[test]
+    fn test_dead_particles_are_recycled() {
+        let mut system = ParticleSystem::with_budget(100);
+
+        for _ in 0..100 {
+            system.emit(particle(WHITE));
+        }
+        assert_eq!(system.len(), 100);
+
+        system.update(2.0);
+        assert!(system.is_empty());
+
+        // The freed slots take the next emissions without evicting anything
+        for _ in 0..60 {
+            system.emit(particle(BLUE));
+        }
+        assert_eq!(system.len(), 60);
+        assert_eq!(system.capacity(), 100);
+        assert!(system.iter().all(|p| p.color == BLUE && !p.is_dead()));
+    }
+
+    #[test]
+    fn test_budget_evicts_oldest_first() {
+        let mut system = ParticleSystem::with_budget(10);
+
+        for _ in 0..10 {
+            system.emit(particle(WHITE));
+        }
+        for _ in 0..4 {
+            system.emit(particle(RED));
+        }
+
+        assert_eq!(system.len(), 10);
+        let colors: Vec<Color> = system.iter().map(|p| p.color).collect();
+        assert_eq!(colors[..6], [WHITE; 6]);
+        assert_eq!(colors[6..], [RED; 4]);
+    }
+
+    #[test]
+    fn test_update_moves_and_expires_particles() {
+        let mut system = ParticleSystem::with_budget(4);
+
+        let mut short = particle(WHITE);
+        short.max_lifetime = 0.1;
+        let mut long = particle(RED);
+        long.velocity = Vec2::new(10.0, 0.0);
+
+        system.emit(short);
+        system.emit(long);
+        system.update(0.5);
+
+        let remaining: Vec<Particle> = system.iter().collect();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].color, RED);
+        assert_eq!(remaining[0].position.x, 5.0);
+        assert!(remaining[0].velocity.y > 0.0); // Gravity
+
+        system.clear();
+        assert!(system.is_empty());
+    }
+}
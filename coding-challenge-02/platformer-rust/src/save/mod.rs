@@ -0,0 +1,79 @@
+use crate::achievements::Unlocked;
+use crate::leaderboard::{LeaderboardCache, LeaderboardConfig};
+use crate::settings::Settings;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Save file location, relative to the working directory like `levels/`
+pub const SAVE_PATH: &str = "save.json";
+
+/// Entries kept in the survival high-score table
+pub const HIGH_SCORE_SLOTS: usize = 5;
+
+/// A finished survival run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: i32,
+    /// Wave the run ended on
+    pub wave: u32,
+    /// Seed the waves were planned from, so the run can be replayed
+    pub seed: u64,
+}
+
+/// Everything kept between sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    /// Best survival runs, highest score first
+    #[serde(default)]
+    pub survival_scores: Vec<HighScore>,
+    /// Accessibility options
+    #[serde(default)]
+    pub settings: Settings,
+    /// Unlocked achievements and when each was unlocked
+    #[serde(default)]
+    pub achievements: Unlocked,
+    /// Player name and server for the online leaderboard
+    #[serde(default)]
+    pub leaderboard: LeaderboardConfig,
+    /// Last boards fetched and times still waiting to be sent
+    #[serde(default)]
+    pub leaderboard_cache: LeaderboardCache,
+}
+
+impl SaveData {
+    pub fn load_from_file(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read save file: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse save file: {}", e))
+    }
+
+    /// The saved data, or an empty save if there is none yet
+    pub fn load_or_default(path: &str) -> Self {
+        Self::load_from_file(path).unwrap_or_default()
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize save data: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write save file: {}", e))
+    }
+
+    /// Add a survival run to the high-score table
+    ///
+    /// Returns its place (0 is the best) if it made the table. Ties go
+    /// below the runs already recorded.
+    pub fn record_survival(&mut self, entry: HighScore) -> Option<usize> {
+        let place = self
+            .survival_scores
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(self.survival_scores.len());
+        if place >= HIGH_SCORE_SLOTS {
+            return None;
+        }
+
+        self.survival_scores.insert(place, entry);
+        self.survival_scores.truncate(HIGH_SCORE_SLOTS);
+        Some(place)
+    }
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Points for stomping an enemy, before the combo multiplier
+pub const STOMP_POINTS: i32 = 200;
+
+/// Seconds after a coin during which the next coin extends the streak
+pub const COIN_CHAIN_WINDOW: f32 = 1.5;
+
+/// Bonus per coin already in the streak when another is collected
+pub const COIN_STREAK_BONUS: i32 = 50;
+
+/// Running combo state for the current life
+///
+/// All transitions are pure: they take the current state and return the
+/// next one along with any points earned, so the game loop only has to
+/// apply the result.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ComboState {
+    /// Enemies stomped since the player last touched the ground
+    pub stomp_chain: u32,
+    /// Coins collected in the current streak
+    pub coin_streak: u32,
+    /// Seconds left before the coin streak lapses
+    pub coin_timer: f32,
+}
+
+impl ComboState {
+    /// Multiplier applied to the most recent stomp
+    pub fn stomp_multiplier(&self) -> u32 {
+        self.stomp_chain.max(1)
+    }
+
+    /// Whether there is anything worth showing in the HUD
+    pub fn is_active(&self) -> bool {
+        self.stomp_chain > 1 || self.coin_streak > 1
+    }
+
+    /// Fraction of the coin streak window left, for the HUD timer bar
+    pub fn coin_timer_fraction(&self) -> f32 {
+        (self.coin_timer / COIN_CHAIN_WINDOW).clamp(0.0, 1.0)
+    }
+}
+
+/// Stomp an enemy: each stomp in the same airborne chain scores one
+/// multiple higher (x1, x2, x3, ...)
+pub fn stomp(state: ComboState, base_points: i32) -> (ComboState, i32) {
+    let next = ComboState {
+        stomp_chain: state.stomp_chain + 1,
+        ..state
+    };
+    (next, base_points * next.stomp_multiplier() as i32)
+}
+
+/// Collect a coin: returns the streak bonus on top of the coin's own value
+pub fn collect_coin(state: ComboState) -> (ComboState, i32) {
+    let streak = if state.coin_timer > 0.0 {
+        state.coin_streak + 1
+    } else {
+        1
+    };
+    let next = ComboState {
+        coin_streak: streak,
+        coin_timer: COIN_CHAIN_WINDOW,
+        ..state
+    };
+    (next, COIN_STREAK_BONUS * (streak as i32 - 1))
+}
+
+/// Touch the ground: ends the stomp chain but leaves the coin streak running
+pub fn land(state: ComboState) -> ComboState {
+    ComboState {
+        stomp_chain: 0,
+        ..state
+    }
+}
+
+/// Take damage (or die): every combo is lost
+pub fn take_damage(_state: ComboState) -> ComboState {
+    ComboState::default()
+}
+
+/// Advance the coin streak timer
+pub fn tick(state: ComboState, delta_time: f32) -> ComboState {
+    if state.coin_timer <= 0.0 {
+        return state;
+    }
+
+    let coin_timer = state.coin_timer - delta_time;
+    if coin_timer > 0.0 {
+        ComboState {
+            coin_timer,
+            ..state
+        }
+    } else {
+        ComboState {
+            coin_streak: 0,
+            coin_timer: 0.0,
+            ..state
+        }
+    }
+}
+
+/// Letter rank awarded at the end of a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    S,
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Rank::S => "S",
+            Rank::A => "A",
+            Rank::B => "B",
+            Rank::C => "C",
+        };
+        write!(f, "{}", letter)
+    }
+}
+
+/// Everything a rank requires; all conditions must hold
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RankRequirement {
+    pub min_score: i32,
+    /// Seconds
+    pub max_time: f32,
+    pub max_deaths: u32,
+    /// Percentage of the level's collectibles, 0 to 100
+    pub min_collection: f32,
+}
+
+/// Per-level rank thresholds; anything below B is a C
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RankThresholds {
+    pub s: RankRequirement,
+    pub a: RankRequirement,
+    pub b: RankRequirement,
+}
+
+impl RankThresholds {
+    /// Thresholds scaled from a par score and par time: S needs par score
+    /// inside par time with no deaths and everything collected, and each
+    /// lower rank relaxes all four
+    pub fn from_par(par_score: i32, par_time: f32) -> Self {
+        Self {
+            s: RankRequirement {
+                min_score: par_score,
+                max_time: par_time,
+                max_deaths: 0,
+                min_collection: 100.0,
+            },
+            a: RankRequirement {
+                min_score: par_score * 3 / 4,
+                max_time: par_time * 1.5,
+                max_deaths: 1,
+                min_collection: 75.0,
+            },
+            b: RankRequirement {
+                min_score: par_score / 2,
+                max_time: par_time * 2.5,
+                max_deaths: 3,
+                min_collection: 50.0,
+            },
+        }
+    }
+}
+
+impl Default for RankThresholds {
+    fn default() -> Self {
+        Self::from_par(1500, 60.0)
+    }
+}
+
+/// How a level was played, as far as ranking is concerned
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunStats {
+    pub score: i32,
+    /// Seconds from level start to reaching the goal
+    pub time: f32,
+    pub deaths: u32,
+    pub collected: usize,
+    pub total_collectibles: usize,
+}
+
+impl RunStats {
+    /// Percentage of collectibles picked up; a level with none counts as 100
+    pub fn collection_percent(&self) -> f32 {
+        if self.total_collectibles == 0 {
+            return 100.0;
+        }
+        self.collected as f32 / self.total_collectibles as f32 * 100.0
+    }
+
+    fn meets(&self, requirement: &RankRequirement) -> bool {
+        self.score >= requirement.min_score
+            && self.time <= requirement.max_time
+            && self.deaths <= requirement.max_deaths
+            && self.collection_percent() >= requirement.min_collection
+    }
+}
+
+/// Best rank whose requirements the run meets
+pub fn compute_rank(stats: &RunStats, thresholds: &RankThresholds) -> Rank {
+    if stats.meets(&thresholds.s) {
+        Rank::S
+    } else if stats.meets(&thresholds.a) {
+        Rank::A
+    } else if stats.meets(&thresholds.b) {
+        Rank::B
+    } else {
+        Rank::C
+    }
+}
@@ -0,0 +1,123 @@
+pub mod palette;
+
+pub use palette::Palette;
+
+use crate::entities::JumpTiming;
+use serde::{Deserialize, Serialize};
+
+/// Slowest gameplay speed the settings screen allows
+pub const MIN_GAME_SPEED: f32 = 0.5;
+
+/// Change in game speed per key press on the settings screen
+pub const GAME_SPEED_STEP: f32 = 0.1;
+
+/// Accessibility options, kept in the save file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Multiplier on gameplay delta time (0.5 to 1.0); menus always run at full speed
+    #[serde(default = "default_game_speed")]
+    pub game_speed: f32,
+    /// Draw platforms, enemies and collectibles with the high-contrast palette
+    #[serde(default)]
+    pub high_contrast: bool,
+    /// Longer jump buffer and coyote time
+    #[serde(default)]
+    pub extended_jump_assist: bool,
+    #[serde(default = "default_screen_shake")]
+    pub screen_shake: bool,
+}
+
+fn default_game_speed() -> f32 {
+    1.0
+}
+
+fn default_screen_shake() -> bool {
+    true
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            game_speed: default_game_speed(),
+            high_contrast: false,
+            extended_jump_assist: false,
+            screen_shake: default_screen_shake(),
+        }
+    }
+}
+
+impl Settings {
+    /// Gameplay delta time for a frame that took `delta_time`
+    pub fn scale_delta(&self, delta_time: f32) -> f32 {
+        delta_time * self.game_speed.clamp(MIN_GAME_SPEED, 1.0)
+    }
+
+    pub fn palette(&self) -> &'static Palette {
+        if self.high_contrast {
+            &Palette::HIGH_CONTRAST
+        } else {
+            &Palette::STANDARD
+        }
+    }
+
+    pub fn jump_timing(&self) -> JumpTiming {
+        if self.extended_jump_assist {
+            JumpTiming::EXTENDED
+        } else {
+            JumpTiming::STANDARD
+        }
+    }
+
+    /// Change an option: `step` of -1 or +1 moves the speed down or up, any step flips a toggle
+    pub fn adjust(&mut self, option: SettingsOption, step: i32) {
+        match option {
+            SettingsOption::GameSpeed => {
+                // Whole tenths, so repeated steps don't drift
+                let tenths = (self.game_speed * 10.0).round() + step as f32 * GAME_SPEED_STEP * 10.0;
+                self.game_speed = (tenths / 10.0).clamp(MIN_GAME_SPEED, 1.0);
+            }
+            SettingsOption::HighContrast => self.high_contrast = !self.high_contrast,
+            SettingsOption::JumpAssist => self.extended_jump_assist = !self.extended_jump_assist,
+            SettingsOption::ScreenShake => self.screen_shake = !self.screen_shake,
+        }
+    }
+}
+
+/// A row on the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsOption {
+    GameSpeed,
+    HighContrast,
+    JumpAssist,
+    ScreenShake,
+}
+
+impl SettingsOption {
+    /// Rows in screen order
+    pub const ALL: [SettingsOption; 4] = [
+        SettingsOption::GameSpeed,
+        SettingsOption::HighContrast,
+        SettingsOption::JumpAssist,
+        SettingsOption::ScreenShake,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsOption::GameSpeed => "Game Speed",
+            SettingsOption::HighContrast => "High Contrast",
+            SettingsOption::JumpAssist => "Extended Jump Assist",
+            SettingsOption::ScreenShake => "Screen Shake",
+        }
+    }
+
+    /// Current value as shown on the settings screen
+    pub fn value(&self, settings: &Settings) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+        match self {
+            SettingsOption::GameSpeed => format!("{:.1}x", settings.game_speed),
+            SettingsOption::HighContrast => on_off(settings.high_contrast),
+            SettingsOption::JumpAssist => on_off(settings.extended_jump_assist),
+            SettingsOption::ScreenShake => on_off(settings.screen_shake),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::entities::{CollectibleType, EnemyType, PlatformType};
+use macroquad::prelude::*;
+
+/// Main colors for platforms, enemies and collectibles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub solid_platform: Color,
+    pub moving_platform: Color,
+    pub disappearing_platform: Color,
+    pub walker: Color,
+    pub flyer: Color,
+    pub patroller: Color,
+    pub coin: Color,
+    pub gem: Color,
+    pub health_pack: Color,
+    pub extra_life: Color,
+    pub double_jump: Color,
+}
+
+impl Palette {
+    /// The game's usual colors
+    pub const STANDARD: Palette = Palette {
+        solid_platform: DARKBROWN,
+        moving_platform: ORANGE,
+        disappearing_platform: PURPLE,
+        walker: RED,
+        flyer: PINK,
+        patroller: MAROON,
+        coin: GOLD,
+        gem: BLUE,
+        health_pack: RED,
+        extra_life: PINK,
+        double_jump: DARKGREEN,
+    };
+
+    /// Saturated colors that stand out from the sky and hills and from each other
+    pub const HIGH_CONTRAST: Palette = Palette {
+        solid_platform: BLACK,
+        moving_platform: Color::new(0.0, 0.0, 0.45, 1.0),
+        disappearing_platform: MAGENTA,
+        walker: Color::new(1.0, 0.0, 0.0, 1.0),
+        flyer: Color::new(1.0, 0.35, 0.0, 1.0),
+        patroller: Color::new(0.55, 0.0, 0.0, 1.0),
+        coin: YELLOW,
+        gem: WHITE,
+        health_pack: Color::new(0.0, 0.9, 0.0, 1.0),
+        extra_life: MAGENTA,
+        double_jump: Color::new(0.0, 0.35, 0.0, 1.0),
+    };
+
+    pub fn platform(&self, platform_type: PlatformType) -> Color {
+        match platform_type {
+            PlatformType::Solid => self.solid_platform,
+            PlatformType::Moving => self.moving_platform,
+            PlatformType::Disappearing => self.disappearing_platform,
+        }
+    }
+
+    pub fn enemy(&self, enemy_type: EnemyType) -> Color {
+        match enemy_type {
+            EnemyType::Walker => self.walker,
+            EnemyType::Flyer => self.flyer,
+            EnemyType::Patroller => self.patroller,
+        }
+    }
+
+    pub fn collectible(&self, collectible_type: CollectibleType) -> Color {
+        match collectible_type {
+            CollectibleType::Coin => self.coin,
+            CollectibleType::Gem => self.gem,
+            CollectibleType::HealthPack => self.health_pack,
+            CollectibleType::ExtraLife => self.extra_life,
+            CollectibleType::DoubleJump => self.double_jump,
+        }
+    }
+}
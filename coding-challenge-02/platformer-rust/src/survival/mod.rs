@@ -0,0 +1,264 @@
+use crate::entities::{Enemy, EnemyType};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+mod pool;
+mod waves;
+
+pub use pool::*;
+pub use waves::*;
+
+/// Points for clearing a wave, multiplied by the wave number
+pub const WAVE_CLEAR_POINTS: i32 = 500;
+
+/// Survival arena setup, stored in the level file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalConfig {
+    pub spawners: Vec<SpawnerData>,
+    pub waves: WaveTable,
+    /// Most enemies alive at once; later spawns wait for a free slot
+    pub max_active: usize,
+    /// Seconds of quiet before each wave
+    pub breather: f32,
+}
+
+/// A point enemies enter the arena from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnerData {
+    /// Walkers and patrollers stand on this point; flyers circle it
+    pub x: f32,
+    pub y: f32,
+    /// Enemy types this spawner produces; empty means any
+    #[serde(default)]
+    pub enemy_types: Vec<EnemyType>,
+    /// Patrol distance either side for ground enemies, orbit radius for flyers
+    #[serde(default = "default_spawner_range")]
+    pub range: f32,
+}
+
+fn default_spawner_range() -> f32 {
+    120.0
+}
+
+impl SpawnerData {
+    pub fn accepts(&self, enemy_type: EnemyType) -> bool {
+        self.enemy_types.is_empty() || self.enemy_types.contains(&enemy_type)
+    }
+
+    /// A fresh enemy of the given type at this spawner
+    pub fn spawn(&self, enemy_type: EnemyType) -> Enemy {
+        let (x, y, range) = (self.x, self.y, self.range);
+        let mut enemy = match enemy_type {
+            EnemyType::Walker => Enemy::new_walker(x, y, x - range, x + range),
+            EnemyType::Patroller => Enemy::new_patroller(x, y, x - range, x + range),
+            EnemyType::Flyer => return Enemy::new_flyer(x, y, range),
+        };
+        // Ground enemies don't fall, so put their feet on the spawn point
+        enemy.body.position.y -= enemy.body.size.y;
+        enemy
+    }
+}
+
+/// Bonus for clearing a wave
+pub fn wave_bonus(wave: u32) -> i32 {
+    WAVE_CLEAR_POINTS * wave as i32
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WavePhase {
+    /// Waiting before the next wave, with seconds left
+    Breather(f32),
+    /// Enemies are still spawning or alive
+    Fighting,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurvivalEvent {
+    WaveStarted(u32),
+    WaveCleared { wave: u32, bonus: i32 },
+}
+
+/// Wave progression for one survival run
+///
+/// Waves are planned from the seed, so a run can be replayed exactly; the
+/// live enemies are kept in a fixed-size pool.
+pub struct SurvivalRun {
+    pub config: SurvivalConfig,
+    pub seed: u64,
+    /// Current wave, counting from 1; 0 until the first wave starts
+    pub wave: u32,
+    pub phase: WavePhase,
+    pub pool: EnemyPool,
+    pending: VecDeque<SpawnOrder>,
+    spawn_interval: f32,
+    spawn_timer: f32,
+}
+
+impl SurvivalRun {
+    pub fn new(config: SurvivalConfig, seed: u64) -> Self {
+        Self {
+            phase: WavePhase::Breather(config.breather),
+            pool: EnemyPool::new(config.max_active),
+            config,
+            seed,
+            wave: 0,
+            pending: VecDeque::new(),
+            spawn_interval: 0.0,
+            spawn_timer: 0.0,
+        }
+    }
+
+    /// Enemies left in the current wave, spawned or not
+    pub fn remaining(&self) -> usize {
+        self.pending.len() + self.pool.active_count()
+    }
+
+    /// Seconds until the next wave, while between waves
+    pub fn breather_left(&self) -> Option<f32> {
+        match self.phase {
+            WavePhase::Breather(left) => Some(left),
+            WavePhase::Fighting => None,
+        }
+    }
+
+    /// Advance the run: count down the breather, spawn due enemies and
+    /// notice when a wave has been cleared
+    pub fn update(&mut self, delta_time: f32) -> Option<SurvivalEvent> {
+        self.pool.update(delta_time);
+
+        match self.phase {
+            WavePhase::Breather(left) => {
+                let left = left - delta_time;
+                if left > 0.0 {
+                    self.phase = WavePhase::Breather(left);
+                    return None;
+                }
+
+                self.wave += 1;
+                let plan = plan_wave(&self.config, self.seed, self.wave);
+                self.pending = plan.spawns.into();
+                self.spawn_interval = plan.spawn_interval;
+                self.spawn_timer = 0.0;
+                self.phase = WavePhase::Fighting;
+                Some(SurvivalEvent::WaveStarted(self.wave))
+            }
+            WavePhase::Fighting => {
+                self.spawn_timer -= delta_time;
+                while self.spawn_timer <= 0.0 {
+                    let Some(order) = self.pending.front() else {
+                        break;
+                    };
+                    let enemy = self.config.spawners[order.spawner].spawn(order.enemy_type);
+                    if !self.pool.spawn(enemy) {
+                        // At the cap: spawn as soon as a slot frees up
+                        self.spawn_timer = 0.0;
+                        break;
+                    }
+                    self.pending.pop_front();
+                    self.spawn_timer += self.spawn_interval;
+                }
+
+                if self.pending.is_empty() && self.pool.active_count() == 0 {
+                    self.phase = WavePhase::Breather(self.config.breather);
+                    return Some(SurvivalEvent::WaveCleared {
+                        wave: self.wave,
+                        bonus: wave_bonus(self.wave),
+                    });
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn spawner(x: f32, enemy_types: Vec<EnemyType>) -> SpawnerData {
+        SpawnerData {
+            x,
+            y: 500.0,
+            enemy_types,
+            range: 100.0,
+        }
+    }
+
+    fn config(max_active: usize) -> SurvivalConfig {
+        SurvivalConfig {
+            spawners: vec![
+                spawner(100.0, vec![EnemyType::Walker, EnemyType::Patroller]),
+                spawner(900.0, vec![EnemyType::Walker, EnemyType::Patroller]),
+                spawner(500.0, vec![EnemyType::Flyer]),
+            ],
+            waves: WaveTable {
+                base: vec![
+                    WaveEntry {
+                        enemy_type: EnemyType::Walker,
+                        count: 4,
+                    },
+                    WaveEntry {
+                        enemy_type: EnemyType::Flyer,
+                        count: 2,
+                    },
+                ],
+                spawn_interval: 1.0,
+                difficulty_step: 0.5,
+                min_spawn_interval: 0.3,
+            },
+            max_active,
+            breather: 2.0,
+        }
+    }
+
+    /// Run until the next event, failing if it doesn't come within a minute
+    fn run_until_event(run: &mut SurvivalRun) -> SurvivalEvent {
+        for _ in 0..60 * 60 {
+            if let Some(event) = run.update(DT) {
+                return event;
+            }
+        }
+        panic!("no survival event within a minute");
+    }
+
+    #[test]
+    fn test_pool_caps_and_reuses_slots() {
+        let spawner = spawner(300.0, vec![]);
+        let mut pool = EnemyPool::new(3);
+
+        for _ in 0..3 {
+            assert!(pool.spawn(spawner.spawn(EnemyType::Walker)));
+        }
+        assert!(!pool.spawn(spawner.spawn(EnemyType::Walker)));
+        assert_eq!(pool.active_count(), 3);
+
+        pool.iter_mut().next().unwrap().kill();
+        assert_eq!(pool.active_count(), 2);
+
+        assert!(pool.spawn(spawner.spawn(EnemyType::Flyer)));
+        assert_eq!(pool.active_count(), 3);
+        assert_eq!(pool.slots_used(), 3);
+    }
+
+    #[test]
+    fn test_spawning_holds_at_active_cap() {
+        let mut run = SurvivalRun::new(config(2), 1);
+        run_until_event(&mut run);
+
+        for _ in 0..60 * 10 {
+            run.update(DT);
+            assert!(run.pool.active_count() <= 2);
+        }
+        assert_eq!(run.pool.active_count(), 2);
+        assert_eq!(run.remaining(), 6);
+
+        // Freeing a slot lets the next enemy in on the following update
+        run.pool.iter_mut().next().unwrap().kill();
+        run.update(DT);
+        assert_eq!(run.pool.active_count(), 2);
+        assert_eq!(run.remaining(), 5);
+        assert_eq!(run.pool.slots_used(), 2);
+    }
+}
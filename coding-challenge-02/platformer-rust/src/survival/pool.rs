@@ -0,0 +1,64 @@
+use crate::entities::Enemy;
+use crate::settings::Palette;
+
+/// Fixed-capacity store for spawned enemies
+///
+/// Dead enemies keep their slot and are overwritten by the next spawn, so
+/// the backing vector is allocated once and never grows past `capacity`.
+pub struct EnemyPool {
+    slots: Vec<Enemy>,
+    capacity: usize,
+}
+
+impl EnemyPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Slots handed out so far, alive or dead
+    #[cfg(test)]
+    pub fn slots_used(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.slots.iter().filter(|enemy| enemy.alive).count()
+    }
+
+    /// Put an enemy in a free slot; returns false when every slot holds a
+    /// live enemy
+    pub fn spawn(&mut self, enemy: Enemy) -> bool {
+        if let Some(slot) = self.slots.iter_mut().find(|slot| !slot.alive) {
+            *slot = enemy;
+            return true;
+        }
+        if self.slots.len() < self.capacity {
+            self.slots.push(enemy);
+            return true;
+        }
+        false
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Enemy> {
+        self.slots.iter().filter(|enemy| enemy.alive)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Enemy> {
+        self.slots.iter_mut().filter(|enemy| enemy.alive)
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for enemy in self.iter_mut() {
+            enemy.update(delta_time);
+        }
+    }
+
+    pub fn draw(&self, palette: &Palette) {
+        for enemy in &self.slots {
+            enemy.draw(palette);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Enemies of one type in the first wave
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WaveEntry {
+    pub enemy_type: EnemyType,
+    pub count: u32,
+}
+
+/// What each wave contains and how quickly waves get harder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveTable {
+    /// Composition of wave 1
+    pub base: Vec<WaveEntry>,
+    /// Seconds between spawns in wave 1
+    pub spawn_interval: f32,
+    /// Difficulty added per wave: wave n is `1 + difficulty_step * (n - 1)`
+    /// times wave 1, in enemy count and spawn rate
+    pub difficulty_step: f32,
+    /// Shortest spawn interval at any difficulty
+    pub min_spawn_interval: f32,
+}
+
+impl WaveTable {
+    /// Difficulty multiplier for a wave
+    pub fn difficulty(&self, wave: u32) -> f32 {
+        1.0 + self.difficulty_step * wave.saturating_sub(1) as f32
+    }
+
+    /// Enemies of each type in a wave, in table order
+    pub fn counts(&self, wave: u32) -> Vec<WaveEntry> {
+        let difficulty = self.difficulty(wave);
+        self.base
+            .iter()
+            .map(|entry| WaveEntry {
+                count: (entry.count as f32 * difficulty).round() as u32,
+                ..*entry
+            })
+            .collect()
+    }
+
+    /// Seconds between spawns in a wave
+    pub fn spawn_interval(&self, wave: u32) -> f32 {
+        (self.spawn_interval / self.difficulty(wave)).max(self.min_spawn_interval)
+    }
+}
+
+/// One enemy to spawn during a wave
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnOrder {
+    pub enemy_type: EnemyType,
+    /// Index into the config's spawners
+    pub spawner: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavePlan {
+    pub wave: u32,
+    /// Spawns in the order they happen
+    pub spawns: Vec<SpawnOrder>,
+    pub spawn_interval: f32,
+}
+
+/// Compose a wave from the table: its enemies in a shuffled order, each
+/// sent to a random spawner that accepts its type
+///
+/// The plan depends only on the config, seed and wave number. Enemy types
+/// no spawner accepts are left out.
+pub fn plan_wave(config: &SurvivalConfig, seed: u64, wave: u32) -> WavePlan {
+    let mut rng = StdRng::seed_from_u64(seed ^ (wave as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+    let mut spawns = Vec::new();
+    for entry in config.waves.counts(wave) {
+        let spawners: Vec<usize> = (0..config.spawners.len())
+            .filter(|&i| config.spawners[i].accepts(entry.enemy_type))
+            .collect();
+        if spawners.is_empty() {
+            continue;
+        }
+
+        for _ in 0..entry.count {
+            spawns.push(SpawnOrder {
+                enemy_type: entry.enemy_type,
+                spawner: spawners[rng.gen_range(0..spawners.len())],
+            });
+        }
+    }
+    spawns.shuffle(&mut rng);
+
+    WavePlan {
+        wave,
+        spawns,
+        spawn_interval: config.waves.spawn_interval(wave),
+    }
+}
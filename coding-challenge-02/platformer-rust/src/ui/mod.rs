@@ -1,4 +1,13 @@
+use crate::achievements::{self, Unlocked, ACHIEVEMENTS};
+use crate::leaderboard::{self, BoardStatus, Entry, LevelBoard, TOP_ENTRIES};
+use crate::save::HighScore;
+use crate::scoring::{ComboState, Rank};
+use crate::settings::{Settings, SettingsOption};
 use macroquad::prelude::*;
+use std::collections::VecDeque;
+
+/// Seconds each achievement toast stays on screen
+pub const TOAST_SECONDS: f32 = 3.0;
 
 pub struct Background {
     layers: Vec<BackgroundLayer>,
@@ -85,6 +94,12 @@ impl Default for Background {
 pub struct HUD {
     pub flash_timer: f32,
     pub flash_message: String,
+    /// Level number and rank of the last completed level
+    pub last_rank: Option<(usize, Rank)>,
+    /// Names of unlocked achievements waiting for a toast; the first is showing
+    pub toasts: VecDeque<String>,
+    /// Seconds left on the toast showing now
+    pub toast_timer: f32,
 }
 
 impl HUD {
@@ -92,6 +107,9 @@ impl HUD {
         Self {
             flash_timer: 0.0,
             flash_message: String::new(),
+            last_rank: None,
+            toasts: VecDeque::new(),
+            toast_timer: 0.0,
         }
     }
 
@@ -100,13 +118,30 @@ impl HUD {
         self.flash_timer = duration;
     }
 
+    /// Queue a toast for an unlocked achievement
+    pub fn show_achievement(&mut self, name: &str) {
+        if self.toasts.is_empty() {
+            self.toast_timer = TOAST_SECONDS;
+        }
+        self.toasts.push_back(name.to_string());
+    }
+
     pub fn update(&mut self, delta_time: f32) {
         if self.flash_timer > 0.0 {
             self.flash_timer -= delta_time;
         }
+
+        if !self.toasts.is_empty() {
+            self.toast_timer -= delta_time;
+            if self.toast_timer <= 0.0 {
+                self.toasts.pop_front();
+                self.toast_timer = if self.toasts.is_empty() { 0.0 } else { TOAST_SECONDS };
+            }
+        }
     }
 
-    pub fn draw(&self, health: i32, lives: i32, score: i32, coins: i32, level_num: usize) {
+    /// Draw the stats panel, with `title` ("Level 2", "Wave 5") in the top right
+    pub fn draw(&self, health: i32, lives: i32, score: i32, coins: i32, title: &str) {
         let margin = 20.0;
         let y_start = 20.0;
 
@@ -146,17 +181,29 @@ impl HUD {
             YELLOW,
         );
 
-        // Draw level number
-        let level_text = format!("Level {}", level_num);
-        let text_width = measure_text(&level_text, None, 30, 1.0).width;
+        // Draw level or wave number
+        let text_width = measure_text(title, None, 30, 1.0).width;
         draw_text(
-            &level_text,
+            title,
             screen_width() - text_width - margin,
             y_start + 10.0,
             30.0,
             WHITE,
         );
 
+        // Draw rank of the previous level
+        if let Some((level, rank)) = self.last_rank {
+            let rank_text = format!("Level {} Rank: {}", level, rank);
+            let text_width = measure_text(&rank_text, None, 20, 1.0).width;
+            draw_text(
+                &rank_text,
+                screen_width() - text_width - margin,
+                y_start + 35.0,
+                20.0,
+                rank_color(rank),
+            );
+        }
+
         // Draw flash message
         if self.flash_timer > 0.0 {
             let alpha = (self.flash_timer * 3.0).sin().abs();
@@ -172,6 +219,98 @@ impl HUD {
     }
 }
 
+impl HUD {
+    /// Draw the current achievement toast in the bottom right corner
+    pub fn draw_toast(&self) {
+        let Some(name) = self.toasts.front() else {
+            return;
+        };
+
+        // Fade in over the first quarter second and out over the last
+        let shown = TOAST_SECONDS - self.toast_timer;
+        let alpha = (shown / 0.25).min(self.toast_timer / 0.25).clamp(0.0, 1.0);
+
+        let width = measure_text(name, None, 24, 1.0).width.max(220.0) + 30.0;
+        let x = screen_width() - width - 20.0;
+        let y = screen_height() - 100.0;
+        draw_rectangle(x, y, width, 60.0, Color::new(0.0, 0.0, 0.0, 0.7 * alpha));
+        draw_rectangle_lines(x, y, width, 60.0, 2.0, Color::new(GOLD.r, GOLD.g, GOLD.b, alpha));
+        draw_text("ACHIEVEMENT UNLOCKED", x + 15.0, y + 22.0, 18.0, Color::new(GOLD.r, GOLD.g, GOLD.b, alpha));
+        draw_text(name, x + 15.0, y + 47.0, 24.0, Color::new(1.0, 1.0, 1.0, alpha));
+    }
+}
+
+impl HUD {
+    /// Draw the active combo multiplier and coin streak timer bar
+    pub fn draw_combo(&self, combo: &ComboState) {
+        if !combo.is_active() {
+            return;
+        }
+
+        let center_x = screen_width() / 2.0;
+        let mut y = 30.0;
+
+        if combo.stomp_chain > 1 {
+            let text = format!("STOMP x{}", combo.stomp_multiplier());
+            let text_width = measure_text(&text, None, 30, 1.0).width;
+            draw_text(&text, center_x - text_width / 2.0, y, 30.0, ORANGE);
+            y += 30.0;
+        }
+
+        if combo.coin_streak > 1 {
+            let text = format!("COIN STREAK {}", combo.coin_streak);
+            let text_width = measure_text(&text, None, 24, 1.0).width;
+            draw_text(&text, center_x - text_width / 2.0, y, 24.0, GOLD);
+
+            // Timer bar drains until the streak lapses
+            let bar_width = 120.0;
+            let bar_y = y + 8.0;
+            draw_rectangle(
+                center_x - bar_width / 2.0,
+                bar_y,
+                bar_width,
+                6.0,
+                Color::new(0.0, 0.0, 0.0, 0.5),
+            );
+            draw_rectangle(
+                center_x - bar_width / 2.0,
+                bar_y,
+                bar_width * combo.coin_timer_fraction(),
+                6.0,
+                GOLD,
+            );
+        }
+    }
+}
+
+impl HUD {
+    /// Draw the survival countdown to the next wave, or the enemies left in
+    /// the current one
+    pub fn draw_wave_status(&self, remaining: usize, breather: Option<f32>) {
+        let text = match breather {
+            Some(left) => format!("Next wave in {}", left.ceil() as u32),
+            None => format!("Enemies left: {}", remaining),
+        };
+        let text_width = measure_text(&text, None, 20, 1.0).width;
+        draw_text(
+            &text,
+            screen_width() - text_width - 20.0,
+            55.0,
+            20.0,
+            if breather.is_some() { GREEN } else { ORANGE },
+        );
+    }
+}
+
+fn rank_color(rank: Rank) -> Color {
+    match rank {
+        Rank::S => GOLD,
+        Rank::A => GREEN,
+        Rank::B => SKYBLUE,
+        Rank::C => LIGHTGRAY,
+    }
+}
+
 impl Default for HUD {
     fn default() -> Self {
         Self::new()
@@ -255,6 +394,92 @@ impl Menu {
     }
 }
 
+/// Break `text` into lines no wider than `max_width`
+///
+/// `measure` gives the rendered width of a string. Words are never split, so
+/// a single word wider than `max_width` gets a line to itself.
+pub fn wrap_text(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if line.is_empty() {
+            line.push_str(word);
+            continue;
+        }
+
+        let candidate = format!("{} {}", line, word);
+        if measure(&candidate) <= max_width {
+            line = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Draw a cutscene dialogue box along the bottom of the screen
+pub fn draw_dialogue_box(speaker: Option<&str>, text: &str) {
+    let margin = 30.0;
+    let padding = 20.0;
+    let font_size = 24.0;
+    let line_height = 30.0;
+    let box_width = screen_width() - margin * 2.0;
+    let box_height = 150.0;
+    let box_x = margin;
+    let box_y = screen_height() - box_height - margin;
+
+    draw_rectangle(
+        box_x,
+        box_y,
+        box_width,
+        box_height,
+        Color::new(0.05, 0.05, 0.15, 0.9),
+    );
+    draw_rectangle_lines(box_x, box_y, box_width, box_height, 3.0, GOLD);
+
+    let mut y = box_y + padding + font_size * 0.75;
+    if let Some(speaker) = speaker {
+        draw_text(speaker, box_x + padding, y, font_size, GOLD);
+        y += line_height;
+    }
+
+    let lines = wrap_text(text, box_width - padding * 2.0, |s| {
+        measure_text(s, None, font_size as u16, 1.0).width
+    });
+    for line in &lines {
+        draw_text(line, box_x + padding, y, font_size, WHITE);
+        y += line_height;
+    }
+
+    let hint = "ENTER to continue";
+    let hint_width = measure_text(hint, None, 16, 1.0).width;
+    draw_text(
+        hint,
+        box_x + box_width - hint_width - padding,
+        box_y + box_height - padding / 2.0,
+        16.0,
+        GRAY,
+    );
+}
+
+/// Draw the hint for skipping a cutscene
+pub fn draw_skip_hint() {
+    let hint = "TAB to skip";
+    let hint_width = measure_text(hint, None, 20, 1.0).width;
+    draw_text(
+        hint,
+        screen_width() - hint_width - 20.0,
+        screen_height() - 10.0,
+        20.0,
+        Color::new(1.0, 1.0, 1.0, 0.7),
+    );
+}
+
 pub fn draw_game_over(score: i32, final_level: usize) {
     clear_background(BLACK);
 
@@ -299,7 +524,7 @@ pub fn draw_game_over(score: i32, final_level: usize) {
     );
 }
 
-pub fn draw_victory(score: i32, total_levels: usize) {
+pub fn draw_victory(score: i32, total_levels: usize, ranks: &[Rank]) {
     clear_background(Color::new(0.1, 0.1, 0.3, 1.0));
 
     // Draw stars
@@ -339,6 +564,23 @@ pub fn draw_victory(score: i32, total_levels: usize) {
         YELLOW,
     );
 
+    // One letter per level
+    if !ranks.is_empty() {
+        let label = "Ranks:";
+        let spacing = 40.0;
+        let label_width = measure_text(label, None, 30, 1.0).width;
+        let total_width = label_width + spacing * ranks.len() as f32;
+        let mut x = screen_width() / 2.0 - total_width / 2.0;
+        let y = screen_height() / 2.0 + 110.0;
+
+        draw_text(label, x, y, 30.0, WHITE);
+        x += label_width + spacing / 2.0;
+        for &rank in ranks {
+            draw_text(&rank.to_string(), x, y, 30.0, rank_color(rank));
+            x += spacing;
+        }
+    }
+
     let restart_text = "Press ENTER to return to menu";
     let restart_width = measure_text(restart_text, None, 20, 1.0).width;
     draw_text(
@@ -349,3 +591,276 @@ pub fn draw_victory(score: i32, total_levels: usize) {
         GRAY,
     );
 }
+
+/// Game over screen for survival mode, with the high-score table
+///
+/// `new_place` is the row this run took in `high_scores`, if any.
+pub fn draw_survival_over(score: i32, wave: u32, high_scores: &[HighScore], new_place: Option<usize>) {
+    clear_background(BLACK);
+
+    let title = "SURVIVAL OVER";
+    let title_width = measure_text(title, None, 60, 1.0).width;
+    draw_text(
+        title,
+        screen_width() / 2.0 - title_width / 2.0,
+        120.0,
+        60.0,
+        RED,
+    );
+
+    let summary = format!("Score: {}   Wave: {}", score, wave);
+    let summary_width = measure_text(&summary, None, 30, 1.0).width;
+    draw_text(
+        &summary,
+        screen_width() / 2.0 - summary_width / 2.0,
+        180.0,
+        30.0,
+        WHITE,
+    );
+
+    let heading = "HIGH SCORES";
+    let heading_width = measure_text(heading, None, 30, 1.0).width;
+    draw_text(
+        heading,
+        screen_width() / 2.0 - heading_width / 2.0,
+        250.0,
+        30.0,
+        GOLD,
+    );
+
+    for (i, entry) in high_scores.iter().enumerate() {
+        let row = format!("{}. {:>7}   wave {}", i + 1, entry.score, entry.wave);
+        let row_width = measure_text(&row, None, 24, 1.0).width;
+        let color = if new_place == Some(i) { YELLOW } else { LIGHTGRAY };
+        draw_text(
+            &row,
+            screen_width() / 2.0 - row_width / 2.0,
+            290.0 + i as f32 * 30.0,
+            24.0,
+            color,
+        );
+    }
+
+    let restart_text = "Press ENTER to return to menu";
+    let restart_width = measure_text(restart_text, None, 20, 1.0).width;
+    draw_text(
+        restart_text,
+        screen_width() / 2.0 - restart_width / 2.0,
+        screen_height() - 60.0,
+        20.0,
+        GRAY,
+    );
+}
+
+/// Draw the accessibility settings screen with row `selected` highlighted
+pub fn draw_settings(settings: &Settings, selected: usize) {
+    clear_background(BLACK);
+
+    let title = "SETTINGS";
+    let title_width = measure_text(title, None, 60, 1.0).width;
+    draw_text(
+        title,
+        screen_width() / 2.0 - title_width / 2.0,
+        screen_height() / 4.0,
+        60.0,
+        GOLD,
+    );
+
+    let label_x = screen_width() / 2.0 - 220.0;
+    let value_x = screen_width() / 2.0 + 140.0;
+    for (i, option) in SettingsOption::ALL.iter().enumerate() {
+        let y = 250.0 + i as f32 * 50.0;
+        let color = if i == selected { YELLOW } else { WHITE };
+        let prefix = if i == selected { "> " } else { "  " };
+        draw_text(&format!("{}{}", prefix, option.label()), label_x, y, 30.0, color);
+        draw_text(&option.value(settings), value_x, y, 30.0, color);
+    }
+
+    // Swatches so the palette choice can be judged before playing
+    let palette = settings.palette();
+    let swatches = [
+        palette.solid_platform,
+        palette.moving_platform,
+        palette.walker,
+        palette.flyer,
+        palette.coin,
+        palette.gem,
+    ];
+    let swatch_x = screen_width() / 2.0 - swatches.len() as f32 * 20.0;
+    draw_rectangle(swatch_x - 10.0, 450.0, swatches.len() as f32 * 40.0 + 20.0, 40.0, SKYBLUE);
+    for (i, color) in swatches.iter().enumerate() {
+        draw_rectangle(swatch_x + i as f32 * 40.0 + 5.0, 455.0, 30.0, 30.0, *color);
+    }
+
+    let hint = "UP/DOWN to choose, LEFT/RIGHT or ENTER to change, ESC to save and return";
+    let hint_width = measure_text(hint, None, 20, 1.0).width;
+    draw_text(
+        hint,
+        screen_width() / 2.0 - hint_width / 2.0,
+        screen_height() - 60.0,
+        20.0,
+        GRAY,
+    );
+}
+
+/// Draw the achievements screen: every achievement, locked or not, with
+/// hidden ones masked until unlocked
+pub fn draw_achievements(unlocked: &Unlocked) {
+    clear_background(BLACK);
+
+    let title = "ACHIEVEMENTS";
+    let title_width = measure_text(title, None, 60, 1.0).width;
+    draw_text(
+        title,
+        screen_width() / 2.0 - title_width / 2.0,
+        100.0,
+        60.0,
+        GOLD,
+    );
+
+    let summary = format!("{} of {} unlocked", unlocked.len(), ACHIEVEMENTS.len());
+    let summary_width = measure_text(&summary, None, 20, 1.0).width;
+    draw_text(
+        &summary,
+        screen_width() / 2.0 - summary_width / 2.0,
+        135.0,
+        20.0,
+        LIGHTGRAY,
+    );
+
+    let x = 80.0;
+    for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+        let y = 185.0 + i as f32 * 52.0;
+        match unlocked.get(achievement.id) {
+            Some(&timestamp) => {
+                draw_text(achievement.name, x, y, 26.0, YELLOW);
+                let date = achievements::format_date(timestamp);
+                let date_width = measure_text(&date, None, 18, 1.0).width;
+                draw_text(&date, screen_width() - x - date_width, y, 18.0, LIGHTGRAY);
+                draw_text(achievement.description, x, y + 20.0, 18.0, WHITE);
+            }
+            None if achievement.hidden => {
+                draw_text("???", x, y, 26.0, DARKGRAY);
+                draw_text("Hidden achievement", x, y + 20.0, 18.0, DARKGRAY);
+            }
+            None => {
+                draw_text(achievement.name, x, y, 26.0, GRAY);
+                draw_text(achievement.description, x, y + 20.0, 18.0, DARKGRAY);
+            }
+        }
+    }
+
+    let hint = "Press ESC or ENTER to return to menu";
+    let hint_width = measure_text(hint, None, 20, 1.0).width;
+    draw_text(
+        hint,
+        screen_width() / 2.0 - hint_width / 2.0,
+        screen_height() - 30.0,
+        20.0,
+        GRAY,
+    );
+}
+
+/// Draw the leaderboard for one level: the top 10 times with the player's
+/// row highlighted, or their place below the table if they are further down
+///
+/// `board` is the last board received for the level, possibly from an
+/// earlier session when the server can't be reached.
+pub fn draw_leaderboard(
+    level_name: &str,
+    level_index: usize,
+    level_count: usize,
+    board: Option<&LevelBoard>,
+    player: &str,
+    status: &BoardStatus,
+) {
+    clear_background(BLACK);
+
+    let title = "LEADERBOARD";
+    let title_width = measure_text(title, None, 60, 1.0).width;
+    draw_text(
+        title,
+        screen_width() / 2.0 - title_width / 2.0,
+        90.0,
+        60.0,
+        GOLD,
+    );
+
+    let heading = format!("< Level {} of {}: {} >", level_index + 1, level_count, level_name);
+    let heading_width = measure_text(&heading, None, 26, 1.0).width;
+    draw_text(
+        &heading,
+        screen_width() / 2.0 - heading_width / 2.0,
+        130.0,
+        26.0,
+        WHITE,
+    );
+
+    let note = match (status, board) {
+        (BoardStatus::Disabled, _) => {
+            "Offline: set leaderboard.endpoint in save.json to compare times".to_string()
+        }
+        (BoardStatus::Loading, _) => "Loading...".to_string(),
+        (BoardStatus::Live, _) => String::new(),
+        (BoardStatus::Offline(_), Some(board)) => format!(
+            "Offline: showing results from {}",
+            achievements::format_date(board.fetched_at)
+        ),
+        (BoardStatus::Offline(_), None) => "Offline: no results saved for this level".to_string(),
+    };
+    let note_width = measure_text(&note, None, 18, 1.0).width;
+    draw_text(
+        &note,
+        screen_width() / 2.0 - note_width / 2.0,
+        158.0,
+        18.0,
+        LIGHTGRAY,
+    );
+
+    let columns = [80.0, 140.0, 420.0, 540.0, 660.0];
+    for (x, label) in columns.iter().zip(["#", "PLAYER", "TIME", "SCORE", "ITEMS"]) {
+        draw_text(label, *x, 195.0, 20.0, GRAY);
+    }
+
+    let draw_row = |rank: usize, entry: &Entry, y: f32| {
+        let color = if entry.player == player { YELLOW } else { WHITE };
+        let cells = [
+            rank.to_string(),
+            entry.player.clone(),
+            leaderboard::format_time(entry.time_ms),
+            entry.score.to_string(),
+            format!("{}%", entry.collection),
+        ];
+        for (x, cell) in columns.iter().zip(&cells) {
+            draw_text(cell, *x, y, 24.0, color);
+        }
+    };
+
+    match board {
+        Some(board) if !board.top.is_empty() => {
+            for (i, entry) in board.top.iter().take(TOP_ENTRIES).enumerate() {
+                draw_row(i + 1, entry, 228.0 + i as f32 * 28.0);
+            }
+            if let (Some(rank), Some(entry)) = (board.player_rank, &board.player_entry) {
+                if rank > TOP_ENTRIES {
+                    draw_text("...", columns[0], 228.0 + TOP_ENTRIES as f32 * 28.0, 24.0, GRAY);
+                    draw_row(rank, entry, 256.0 + TOP_ENTRIES as f32 * 28.0);
+                }
+            }
+        }
+        Some(_) => {
+            draw_text("No times yet: be the first!", columns[1], 228.0, 24.0, GRAY);
+        }
+        None => {}
+    }
+
+    let hint = "LEFT/RIGHT to change level, R to refresh, ESC or ENTER to return";
+    let hint_width = measure_text(hint, None, 20, 1.0).width;
+    draw_text(
+        hint,
+        screen_width() / 2.0 - hint_width / 2.0,
+        screen_height() - 20.0,
+        20.0,
+        GRAY,
+    );
+}
@@ -0,0 +1,171 @@
+use platformer_rust::entities::*;
+use platformer_rust::physics::JUMP_VELOCITY;
+use platformer_rust::save::*;
+use platformer_rust::settings::*;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Player with no double jump, so only ground jumps (real or assisted) count
+fn player(timing: JumpTiming) -> Player {
+    let mut player = Player::new(0.0, 0.0);
+    player.jump_timing = timing;
+    player.can_double_jump = false;
+    player
+}
+
+/// Run one frame of input and report whether it started a ground jump
+fn frame(player: &mut Player, on_ground: bool, jump_pressed: bool) -> bool {
+    player.body.on_ground = on_ground;
+    player.body.velocity.y = if on_ground { 0.0 } else { 100.0 };
+    player.apply_input(
+        PlayerInput {
+            move_x: 0.0,
+            jump_pressed,
+        },
+        DT,
+    );
+    player.body.velocity.y == JUMP_VELOCITY
+}
+
+/// Press jump in the air, land `frames_early` frames later
+fn buffered_jump_fires(timing: JumpTiming, frames_early: usize) -> bool {
+    let mut player = player(timing);
+    assert!(!frame(&mut player, false, true));
+    for _ in 1..frames_early {
+        assert!(!frame(&mut player, false, false));
+    }
+    frame(&mut player, true, false)
+}
+
+/// Walk off a ledge and press jump `frames_late` frames after leaving it
+fn coyote_jump_fires(timing: JumpTiming, frames_late: usize) -> bool {
+    let mut player = player(timing);
+    frame(&mut player, true, false);
+    for _ in 1..frames_late {
+        frame(&mut player, false, false);
+    }
+    frame(&mut player, false, true)
+}
+
+#[test]
+fn test_jump_on_ground_is_immediate() {
+    let mut player = player(JumpTiming::STANDARD);
+    assert!(frame(&mut player, true, true));
+}
+
+#[test]
+fn test_jump_buffer_window() {
+    // 0.1s is 6 frames at 60 FPS
+    assert!(buffered_jump_fires(JumpTiming::STANDARD, 1));
+    assert!(buffered_jump_fires(JumpTiming::STANDARD, 5));
+    assert!(!buffered_jump_fires(JumpTiming::STANDARD, 7));
+
+    // 0.25s is 15 frames
+    assert!(buffered_jump_fires(JumpTiming::EXTENDED, 14));
+    assert!(!buffered_jump_fires(JumpTiming::EXTENDED, 16));
+}
+
+#[test]
+fn test_coyote_time_window() {
+    // 0.08s is 4.8 frames at 60 FPS
+    assert!(coyote_jump_fires(JumpTiming::STANDARD, 1));
+    assert!(coyote_jump_fires(JumpTiming::STANDARD, 4));
+    assert!(!coyote_jump_fires(JumpTiming::STANDARD, 5));
+
+    // 0.2s is 12 frames
+    assert!(coyote_jump_fires(JumpTiming::EXTENDED, 11));
+    assert!(!coyote_jump_fires(JumpTiming::EXTENDED, 13));
+}
+
+#[test]
+fn test_buffered_jump_fires_once() {
+    let mut player = player(JumpTiming::EXTENDED);
+    frame(&mut player, false, true);
+    assert!(frame(&mut player, true, false));
+    // Still grounded next frame: the press was used up
+    assert!(!frame(&mut player, true, false));
+}
+
+#[test]
+fn test_coyote_jump_is_not_repeated() {
+    let mut player = player(JumpTiming::EXTENDED);
+    frame(&mut player, true, false);
+    assert!(frame(&mut player, false, true));
+    assert!(!frame(&mut player, false, true));
+}
+
+#[test]
+fn test_air_press_uses_double_jump_before_buffering() {
+    let mut player = player(JumpTiming::EXTENDED);
+    player.can_double_jump = true;
+
+    assert!(!frame(&mut player, false, true));
+    assert!(player.has_double_jumped);
+    // The press went into the double jump, so landing doesn't jump again
+    assert!(!frame(&mut player, true, false));
+}
+
+#[test]
+fn test_respawn_clears_jump_windows() {
+    let mut player = player(JumpTiming::EXTENDED);
+    frame(&mut player, false, true);
+    player.respawn(0.0, 0.0);
+    assert!(!frame(&mut player, true, false));
+}
+
+#[test]
+fn test_game_speed_steps_and_clamps() {
+    let mut settings = Settings::default();
+    assert_eq!(settings.scale_delta(DT), DT);
+
+    settings.adjust(SettingsOption::GameSpeed, 1);
+    assert_eq!(settings.game_speed, 1.0);
+    for _ in 0..3 {
+        settings.adjust(SettingsOption::GameSpeed, -1);
+    }
+    assert_eq!(settings.game_speed, 0.7);
+    assert_eq!(SettingsOption::GameSpeed.value(&settings), "0.7x");
+    for _ in 0..10 {
+        settings.adjust(SettingsOption::GameSpeed, -1);
+    }
+    assert_eq!(settings.game_speed, MIN_GAME_SPEED);
+    assert_eq!(settings.scale_delta(0.02), 0.01);
+}
+
+#[test]
+fn test_toggles_pick_palette_and_jump_timing() {
+    let mut settings = Settings::default();
+    assert_eq!(settings.palette(), &Palette::STANDARD);
+    assert_eq!(settings.jump_timing(), JumpTiming::STANDARD);
+    assert!(settings.screen_shake);
+
+    settings.adjust(SettingsOption::HighContrast, 1);
+    settings.adjust(SettingsOption::JumpAssist, -1);
+    settings.adjust(SettingsOption::ScreenShake, 1);
+    assert_eq!(settings.palette(), &Palette::HIGH_CONTRAST);
+    assert_eq!(settings.jump_timing(), JumpTiming::EXTENDED);
+    assert!(!settings.screen_shake);
+    assert_ne!(
+        Palette::HIGH_CONTRAST.enemy(EnemyType::Walker),
+        Palette::HIGH_CONTRAST.platform(PlatformType::Solid)
+    );
+}
+
+#[test]
+fn test_settings_persist_in_save_file() {
+    let path = std::env::temp_dir().join("platformer_settings_save_test.json");
+    let path = path.to_str().unwrap();
+
+    let mut save = SaveData::default();
+    save.settings.game_speed = 0.6;
+    save.settings.high_contrast = true;
+    save.save_to_file(path).unwrap();
+    assert_eq!(SaveData::load_from_file(path).unwrap(), save);
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_old_saves_get_default_settings() {
+    let save: SaveData = serde_json::from_str(r#"{"survival_scores": []}"#).unwrap();
+    assert_eq!(save.settings, Settings::default());
+}
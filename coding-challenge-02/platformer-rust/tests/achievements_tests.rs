@@ -0,0 +1,207 @@
+use platformer_rust::achievements::*;
+use platformer_rust::save::SaveData;
+use platformer_rust::scoring::Rank;
+use platformer_rust::ui::{HUD, TOAST_SECONDS};
+
+/// A three-level campaign with two gems per level, finished cleanly with
+/// every gem and an S on each level
+fn perfect_run() -> RunStatistics {
+    let mut stats = RunStatistics::new(3, 6);
+    for number in 1..=3 {
+        stats.begin_level();
+        stats.finish_level(number, 2, 2, Rank::S);
+    }
+    stats
+}
+
+fn unlocked_ids(hook: Hook, stats: &RunStatistics) -> Vec<&'static str> {
+    evaluate(hook, stats, &Unlocked::new())
+        .iter()
+        .map(|achievement| achievement.id)
+        .collect()
+}
+
+#[test]
+fn test_first_steps_needs_level_one_finished() {
+    let mut stats = RunStatistics::new(3, 0);
+    assert!(!first_steps(&stats));
+
+    stats.finish_level(1, 0, 0, Rank::C);
+    assert!(first_steps(&stats));
+}
+
+#[test]
+fn test_untouchable_needs_a_clean_level_two() {
+    let mut stats = RunStatistics::new(3, 0);
+    stats.record_damage();
+    stats.finish_level(1, 0, 0, Rank::B);
+    assert!(!untouchable(&stats), "damage on level 1 doesn't matter, but level 2 isn't done");
+
+    stats.begin_level();
+    stats.finish_level(2, 0, 0, Rank::A);
+    assert!(untouchable(&stats));
+
+    let mut hit = RunStatistics::new(3, 0);
+    hit.begin_level();
+    hit.record_damage();
+    hit.finish_level(2, 0, 0, Rank::A);
+    assert!(!untouchable(&hit));
+
+    let mut fell = RunStatistics::new(3, 0);
+    fell.begin_level();
+    fell.record_death();
+    fell.finish_level(2, 0, 0, Rank::A);
+    assert!(!untouchable(&fell), "falling off the map counts too");
+}
+
+#[test]
+fn test_gem_hoarder_needs_every_gem_in_the_game() {
+    assert!(gem_hoarder(&perfect_run()));
+
+    let mut missed_one = RunStatistics::new(3, 6);
+    missed_one.finish_level(1, 2, 2, Rank::S);
+    missed_one.finish_level(2, 1, 2, Rank::S);
+    missed_one.finish_level(3, 2, 2, Rank::S);
+    assert!(!gem_hoarder(&missed_one));
+
+    let mut unfinished = RunStatistics::new(3, 4);
+    unfinished.finish_level(1, 2, 2, Rank::S);
+    unfinished.finish_level(2, 2, 2, Rank::S);
+    assert!(!gem_hoarder(&unfinished), "level 3 has gems too");
+
+    assert!(!gem_hoarder(&RunStatistics::new(3, 0)));
+}
+
+#[test]
+fn test_sky_stomper_needs_three_stomps_in_the_air() {
+    let mut stats = RunStatistics::default();
+    stats.record_stomp(1);
+    stats.record_stomp(2);
+    assert!(!sky_stomper(&stats));
+
+    stats.record_landing();
+    stats.record_stomp(1);
+    assert!(!sky_stomper(&stats));
+    assert_eq!(stats.best_stomp_chain, 2);
+
+    stats.record_stomp(2);
+    stats.record_stomp(SKY_STOMPER_CHAIN);
+    assert!(sky_stomper(&stats));
+    assert_eq!(stats.enemies_stomped, 5);
+}
+
+#[test]
+fn test_flawless_needs_the_game_finished_without_dying() {
+    assert!(flawless(&perfect_run()));
+
+    let mut died = perfect_run();
+    died.record_death();
+    assert!(!flawless(&died));
+
+    let mut hit = perfect_run();
+    hit.record_damage();
+    assert!(flawless(&hit), "losing health isn't losing a life");
+
+    let mut partway = RunStatistics::new(3, 0);
+    partway.finish_level(1, 0, 0, Rank::S);
+    assert!(!flawless(&partway));
+}
+
+#[test]
+fn test_perfectionist_needs_s_on_every_level() {
+    assert!(perfectionist(&perfect_run()));
+
+    let mut slipped = RunStatistics::new(3, 0);
+    slipped.finish_level(1, 0, 0, Rank::S);
+    slipped.finish_level(2, 0, 0, Rank::A);
+    slipped.finish_level(3, 0, 0, Rank::S);
+    assert!(!perfectionist(&slipped));
+}
+
+#[test]
+fn test_punching_bag_needs_ten_hits() {
+    let mut stats = RunStatistics::default();
+    for _ in 1..PUNCHING_BAG_HITS {
+        stats.record_damage();
+    }
+    assert!(!punching_bag(&stats));
+
+    stats.record_damage();
+    assert!(punching_bag(&stats));
+}
+
+#[test]
+fn test_evaluate_only_checks_the_hook() {
+    let mut stats = perfect_run();
+    stats.record_stomp(3);
+    for _ in 0..PUNCHING_BAG_HITS {
+        stats.record_damage();
+    }
+
+    assert_eq!(
+        unlocked_ids(Hook::LevelEnd, &stats),
+        vec!["first_steps", "untouchable", "gem_hoarder", "flawless", "perfectionist"]
+    );
+    assert_eq!(unlocked_ids(Hook::DamageTaken, &stats), vec!["punching_bag"]);
+    assert!(unlocked_ids(Hook::StompCombo, &stats).is_empty(), "damage broke the chain");
+}
+
+#[test]
+fn test_unlock_stamps_each_achievement_once() {
+    let stats = perfect_run();
+    let mut unlocked = Unlocked::new();
+
+    let earned = unlock(Hook::LevelEnd, &stats, &mut unlocked, 1_000);
+    assert_eq!(earned.len(), 5);
+    assert_eq!(unlocked.get("first_steps"), Some(&1_000));
+
+    assert!(unlock(Hook::LevelEnd, &stats, &mut unlocked, 2_000).is_empty());
+    assert_eq!(unlocked.get("first_steps"), Some(&1_000));
+}
+
+#[test]
+fn test_achievement_ids_are_unique() {
+    let mut ids: Vec<_> = ACHIEVEMENTS.iter().map(|achievement| achievement.id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), ACHIEVEMENTS.len());
+}
+
+#[test]
+fn test_format_date() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_763_078_399), "2025-11-13");
+}
+
+#[test]
+fn test_unlocks_persist_in_save_file() {
+    let path = std::env::temp_dir().join("platformer_achievements_save_test.json");
+    let path = path.to_str().unwrap();
+
+    let mut save = SaveData::default();
+    unlock(Hook::LevelEnd, &perfect_run(), &mut save.achievements, 1_763_000_000);
+    save.save_to_file(path).unwrap();
+    assert_eq!(SaveData::load_from_file(path).unwrap(), save);
+    std::fs::remove_file(path).ok();
+
+    let old: SaveData = serde_json::from_str(r#"{"survival_scores": []}"#).unwrap();
+    assert!(old.achievements.is_empty());
+}
+
+#[test]
+fn test_toasts_queue_one_after_another() {
+    let mut hud = HUD::new();
+    hud.show_achievement("First Steps");
+    hud.show_achievement("Flawless");
+
+    hud.update(TOAST_SECONDS - 0.1);
+    assert_eq!(hud.toasts.front().map(String::as_str), Some("First Steps"));
+
+    hud.update(0.2);
+    assert_eq!(hud.toasts.front().map(String::as_str), Some("Flawless"));
+    assert_eq!(hud.toast_timer, TOAST_SECONDS);
+
+    hud.update(TOAST_SECONDS);
+    assert!(hud.toasts.is_empty());
+}
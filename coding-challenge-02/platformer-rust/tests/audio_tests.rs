@@ -0,0 +1,49 @@
+use macroquad::prelude::*;
+use platformer_rust::audio::*;
+
+const TRIGGER: IntensityTrigger = IntensityTrigger {
+    engage_distance: 200.0,
+    release_distance: 320.0,
+};
+
+#[test]
+fn test_intensity_engages_inside_threshold() {
+    assert!(!intensity_engaged(false, None, TRIGGER));
+    assert!(!intensity_engaged(false, Some(250.0), TRIGGER));
+    assert!(intensity_engaged(false, Some(200.0), TRIGGER));
+    assert!(intensity_engaged(false, Some(50.0), TRIGGER));
+}
+
+#[test]
+fn test_intensity_holds_until_release_distance() {
+    // Between the thresholds the previous state wins
+    assert!(intensity_engaged(true, Some(250.0), TRIGGER));
+    assert!(intensity_engaged(true, Some(319.0), TRIGGER));
+    assert!(!intensity_engaged(true, Some(320.0), TRIGGER));
+    // No enemies left at all releases straight away
+    assert!(!intensity_engaged(true, None, TRIGGER));
+}
+
+#[test]
+fn test_enemy_pacing_across_threshold_does_not_flicker() {
+    let mut engaged = false;
+    let mut changes = 0;
+    for frame in 0..600 {
+        // Walks between 150 and 270 pixels away
+        let distance = 210.0 + 60.0 * (frame as f32 * 0.05).sin();
+        let next = intensity_engaged(engaged, Some(distance), TRIGGER);
+        if next != engaged {
+            changes += 1;
+        }
+        engaged = next;
+    }
+    assert_eq!(changes, 1);
+}
+
+#[test]
+fn test_nearest_distance() {
+    let player = vec2(100.0, 100.0);
+    assert_eq!(nearest_distance(player, []), None);
+    let nearest = nearest_distance(player, [vec2(400.0, 100.0), vec2(100.0, 130.0)]);
+    assert_eq!(nearest, Some(30.0));
+}
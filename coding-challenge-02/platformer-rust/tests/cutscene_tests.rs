@@ -0,0 +1,256 @@
+use macroquad::prelude::Vec2;
+use platformer_rust::cutscene::*;
+use platformer_rust::level::LevelData;
+use platformer_rust::ui::wrap_text;
+
+const NONE: CutsceneInput = CutsceneInput {
+    advance: false,
+    skip: false,
+};
+const ADVANCE: CutsceneInput = CutsceneInput {
+    advance: true,
+    skip: false,
+};
+const SKIP: CutsceneInput = CutsceneInput {
+    advance: false,
+    skip: true,
+};
+
+fn player(commands: Vec<CutsceneCommand>) -> CutscenePlayer {
+    CutscenePlayer::new(&CutsceneScript::new(commands), Vec2::ZERO)
+}
+
+fn wait(duration: f32) -> CutsceneCommand {
+    CutsceneCommand::Wait { duration }
+}
+
+fn say(text: &str) -> CutsceneCommand {
+    CutsceneCommand::Dialogue {
+        speaker: None,
+        text: text.to_string(),
+    }
+}
+
+fn sparkle(x: f32) -> CutsceneCommand {
+    CutsceneCommand::SpawnParticles {
+        x,
+        y: 0.0,
+        effect: ParticleEffect::Sparkle,
+    }
+}
+
+fn spawned_at(events: &[CutsceneEvent]) -> Vec<f32> {
+    events
+        .iter()
+        .map(|event| match *event {
+            CutsceneEvent::SpawnParticles { x, .. } => x,
+        })
+        .collect()
+}
+
+#[test]
+fn test_wait_holds_until_duration_elapses() {
+    let mut cutscene = player(vec![wait(1.0), sparkle(1.0)]);
+
+    assert!(cutscene.update(0.5, NONE).is_empty());
+    assert!(cutscene.update(0.49, NONE).is_empty());
+    assert!(!cutscene.is_finished());
+
+    assert_eq!(spawned_at(&cutscene.update(0.02, NONE)), vec![1.0]);
+    assert!(cutscene.is_finished());
+}
+
+#[test]
+fn test_leftover_time_carries_into_next_command() {
+    let mut cutscene = player(vec![wait(0.25), sparkle(1.0), wait(0.25), sparkle(2.0)]);
+
+    // One long frame runs through the first wait and into the second
+    assert_eq!(spawned_at(&cutscene.update(0.375, NONE)), vec![1.0]);
+    assert!(cutscene.update(0.0625, NONE).is_empty());
+    assert_eq!(spawned_at(&cutscene.update(0.0625, NONE)), vec![2.0]);
+    assert!(cutscene.is_finished());
+}
+
+#[test]
+fn test_instant_commands_run_in_the_same_frame() {
+    let mut cutscene = player(vec![
+        CutsceneCommand::LockInput { locked: true },
+        sparkle(1.0),
+        sparkle(2.0),
+        wait(1.0),
+    ]);
+
+    assert_eq!(spawned_at(&cutscene.update(0.0, NONE)), vec![1.0, 2.0]);
+    assert!(cutscene.input_locked());
+    assert!(!cutscene.is_finished());
+}
+
+#[test]
+fn test_camera_pans_from_start_focus() {
+    let mut cutscene = CutscenePlayer::new(
+        &CutsceneScript::new(vec![
+            CutsceneCommand::MoveCamera {
+                x: 200.0,
+                y: 100.0,
+                duration: 1.0,
+            },
+            wait(1.0),
+        ]),
+        Vec2::new(100.0, 100.0),
+    );
+    assert_eq!(cutscene.camera_focus(), None);
+
+    cutscene.update(0.5, NONE);
+    let halfway = cutscene.camera_focus().unwrap();
+    assert!((halfway.x - 150.0).abs() < 1e-3);
+    assert_eq!(halfway.y, 100.0);
+
+    // Eased, so past the midpoint it is ahead of a linear pan
+    cutscene.update(0.25, NONE);
+    assert!(cutscene.camera_focus().unwrap().x > 175.0);
+
+    cutscene.update(0.5, NONE);
+    assert_eq!(cutscene.camera_focus(), Some(Vec2::new(200.0, 100.0)));
+
+    // The camera is released when the script ends
+    cutscene.update(1.0, NONE);
+    assert!(cutscene.is_finished());
+    assert_eq!(cutscene.camera_focus(), None);
+}
+
+#[test]
+fn test_dialogue_waits_for_advance() {
+    let mut cutscene = player(vec![say("first"), say("second"), sparkle(1.0)]);
+
+    cutscene.update(10.0, NONE);
+    assert_eq!(cutscene.dialogue(), Some((None, "first")));
+
+    // One press closes one dialogue
+    cutscene.update(0.016, ADVANCE);
+    assert_eq!(cutscene.dialogue(), Some((None, "second")));
+
+    assert_eq!(spawned_at(&cutscene.update(0.016, ADVANCE)), vec![1.0]);
+    assert!(cutscene.is_finished());
+    assert_eq!(cutscene.dialogue(), None);
+}
+
+#[test]
+fn test_advance_before_dialogue_is_ignored() {
+    let mut cutscene = player(vec![wait(0.5), say("hello")]);
+
+    // The press lands during the wait, which ends this frame
+    cutscene.update(1.0, ADVANCE);
+    assert_eq!(cutscene.dialogue(), Some((None, "hello")));
+}
+
+#[test]
+fn test_end_of_script_unlocks_input() {
+    let mut cutscene = player(vec![CutsceneCommand::LockInput { locked: true }, wait(1.0)]);
+
+    cutscene.update(0.5, NONE);
+    assert!(cutscene.input_locked());
+
+    cutscene.update(0.5, NONE);
+    assert!(cutscene.is_finished());
+    assert!(!cutscene.input_locked());
+}
+
+#[test]
+fn test_skip_ends_script_immediately() {
+    let mut cutscene = player(vec![
+        CutsceneCommand::LockInput { locked: true },
+        CutsceneCommand::MoveCamera {
+            x: 500.0,
+            y: 0.0,
+            duration: 2.0,
+        },
+        say("never finished"),
+        sparkle(1.0),
+    ]);
+
+    cutscene.update(1.0, NONE);
+    assert!(cutscene.camera_focus().is_some());
+
+    // Skipping drops the remaining commands, including their effects
+    assert!(cutscene.update(0.016, SKIP).is_empty());
+    assert!(cutscene.is_finished());
+    assert!(cutscene.was_skipped());
+    assert!(!cutscene.input_locked());
+    assert_eq!(cutscene.camera_focus(), None);
+    assert_eq!(cutscene.dialogue(), None);
+
+    assert!(cutscene.update(1.0, ADVANCE).is_empty());
+}
+
+#[test]
+fn test_skip_takes_priority_over_advance() {
+    let mut cutscene = player(vec![say("one"), say("two")]);
+    cutscene.update(
+        0.0,
+        CutsceneInput {
+            advance: true,
+            skip: true,
+        },
+    );
+    assert!(cutscene.was_skipped());
+}
+
+#[test]
+fn test_empty_script_finishes_on_first_update() {
+    let mut cutscene = player(vec![]);
+    assert!(!cutscene.is_finished());
+    cutscene.update(0.0, NONE);
+    assert!(cutscene.is_finished());
+    assert!(!cutscene.was_skipped());
+}
+
+#[test]
+fn test_scripts_load_from_level_json() {
+    let json = r#"{
+        "name": "Scripted",
+        "width": 800.0, "height": 600.0,
+        "spawn_x": 0.0, "spawn_y": 0.0,
+        "platforms": [], "enemies": [], "collectibles": [], "checkpoints": [],
+        "goal_x": 700.0, "goal_y": 500.0,
+        "intro": [
+            {"command": "lock_input", "locked": true},
+            {"command": "move_camera", "x": 700.0, "y": 500.0, "duration": 1.5},
+            {"command": "dialogue", "speaker": "Guide", "text": "Over there!"},
+            {"command": "spawn_particles", "x": 700.0, "y": 500.0, "effect": "burst"},
+            {"command": "wait", "duration": 0.5}
+        ]
+    }"#;
+
+    let data: LevelData = serde_json::from_str(json).unwrap();
+    assert_eq!(data.outro, None);
+
+    let intro = data.intro.unwrap();
+    assert_eq!(intro.commands.len(), 5);
+    assert_eq!(
+        intro.commands[2],
+        CutsceneCommand::Dialogue {
+            speaker: Some("Guide".to_string()),
+            text: "Over there!".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_wrap_text_fits_width() {
+    // One unit per character
+    let measure = |s: &str| s.chars().count() as f32;
+
+    let lines = wrap_text("the quick brown fox jumps over the lazy dog", 15.0, measure);
+    assert_eq!(
+        lines,
+        vec!["the quick brown", "fox jumps over", "the lazy dog"]
+    );
+    assert!(lines.iter().all(|line| measure(line) <= 15.0));
+
+    // Words are never split
+    assert_eq!(
+        wrap_text("tiny enormousword", 5.0, measure),
+        vec!["tiny", "enormousword"]
+    );
+    assert!(wrap_text("   ", 10.0, measure).is_empty());
+}
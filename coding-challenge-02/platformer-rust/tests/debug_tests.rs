@@ -0,0 +1,57 @@
+use macroquad::camera::Camera as _;
+use macroquad::prelude::*;
+use platformer_rust::camera::Camera;
+use platformer_rust::debug::*;
+
+const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+fn assert_near(a: Vec2, b: Vec2) {
+    assert!((a - b).length() < 1e-3, "{:?} != {:?}", a, b);
+}
+
+fn camera_at(x: f32, y: f32) -> Camera {
+    let mut camera = Camera::new(SCREEN.x, SCREEN.y);
+    camera.position = Vec2::new(x, y);
+    camera
+}
+
+#[test]
+fn test_photo_camera2d_matches_gameplay_at_start() {
+    let camera = camera_at(300.0, 40.0);
+    let photo = PhotoCamera::from_camera(&camera);
+    assert_eq!(photo.camera2d().matrix(), camera.camera2d().matrix());
+}
+
+#[test]
+fn test_zoom_is_clamped() {
+    let mut photo = PhotoCamera::from_camera(&camera_at(0.0, 0.0));
+    photo.zoom_at(SCREEN / 2.0, 1000.0);
+    assert_eq!(photo.zoom, MAX_ZOOM);
+    photo.zoom_at(SCREEN / 2.0, 0.0001);
+    assert_eq!(photo.zoom, MIN_ZOOM);
+}
+
+#[test]
+fn test_pan_moves_by_screen_pixels() {
+    let mut photo = PhotoCamera::from_camera(&camera_at(0.0, 0.0));
+    photo.zoom_at(SCREEN / 2.0, 4.0);
+    let center = photo.center;
+
+    // 100 screen pixels is 25 world pixels at 4x
+    photo.pan(Vec2::new(100.0, -40.0));
+    assert_near(photo.center, center + Vec2::new(25.0, -10.0));
+}
+
+#[test]
+fn test_frame_times_keep_recent_history() {
+    let mut frames = FrameTimes::default();
+    assert_eq!(frames.average(), 0.0);
+
+    for i in 0..FRAME_HISTORY + 10 {
+        frames.push(if i == 0 { 0.5 } else { 0.02 });
+    }
+    assert_eq!(frames.iter().count(), FRAME_HISTORY);
+    // The slow first frame has scrolled out
+    assert_eq!(frames.max(), 0.02);
+    assert!((frames.average() - 0.02).abs() < 1e-6);
+}
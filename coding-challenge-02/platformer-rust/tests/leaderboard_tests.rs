@@ -0,0 +1,310 @@
+use platformer_rust::leaderboard::*;
+use platformer_rust::save::SaveData;
+use platformer_rust::scoring::RunStats;
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// The reference server binary on a free port, killed when dropped
+struct Server {
+    child: Child,
+    url: String,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_leaderboard-server"))
+            .args(["--addr", "127.0.0.1:0"])
+            .env_remove(KEY_ENV)
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start leaderboard-server");
+
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let url = line
+            .trim()
+            .strip_prefix("Listening on ")
+            .unwrap_or_else(|| panic!("unexpected server output: {}", line))
+            .to_string();
+        Self { child, url }
+    }
+
+    fn client(&self) -> Client {
+        Client::new(&self.url)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn stats(time: f32, score: i32, collected: usize) -> RunStats {
+    RunStats {
+        score,
+        time,
+        deaths: 0,
+        collected,
+        total_collectibles: 8,
+    }
+}
+
+fn signed(player: &str, level: &str, time: f32, score: i32) -> SignedSubmission {
+    Submission::new(player, level, &stats(time, score, 6)).sign(DEFAULT_KEY.as_bytes())
+}
+
+/// An address nothing is listening on
+fn closed_endpoint() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}", addr)
+}
+
+/// Poll `service` until `count` replies arrive or five seconds pass
+fn wait_for_replies(service: &LeaderboardService, count: usize) -> Vec<Reply> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut replies = Vec::new();
+    while replies.len() < count && Instant::now() < deadline {
+        replies.extend(service.poll());
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    replies
+}
+
+#[test]
+fn test_submission_from_run_stats() {
+    let submission = Submission::new("Ada", "tutorial-valley", &stats(42.3456, 1800, 6));
+    assert_eq!(submission.time_ms, 42346);
+    assert_eq!(submission.score, 1800);
+    assert_eq!(submission.collection, 75);
+    assert_eq!(
+        submission.signing_message(),
+        "Ada\ntutorial-valley\n42346\n1800\n75"
+    );
+}
+
+#[test]
+fn test_signature_covers_every_field() {
+    let key = DEFAULT_KEY.as_bytes();
+    let original = Submission::new("Ada", "tutorial-valley", &stats(42.0, 1800, 6));
+    let signature = original.signature(key);
+    assert_eq!(signature.len(), 64);
+    assert_eq!(original.signature(key), signature);
+
+    let faster = Submission {
+        time_ms: 1000,
+        ..original.clone()
+    };
+    let renamed = Submission {
+        player: "Eve".to_string(),
+        ..original.clone()
+    };
+    assert_ne!(faster.signature(key), signature);
+    assert_ne!(renamed.signature(key), signature);
+    assert_ne!(original.signature(b"another key"), signature);
+}
+
+#[test]
+fn test_level_ids_and_times() {
+    assert_eq!(level_id("Tutorial Valley"), "tutorial-valley");
+    assert_eq!(level_id("  The Final Ascent!  "), "the-final-ascent");
+    assert_eq!(format_time(42_346), "0:42.34");
+    assert_eq!(format_time(125_070), "2:05.07");
+}
+
+#[test]
+fn test_server_ranks_top_ten_and_the_player_below() {
+    let server = Server::start();
+    let client = server.client();
+
+    // Twelve players, each a second slower than the last
+    for i in 0..12 {
+        let rank = client
+            .submit(&signed(
+                &format!("P{:02}", i),
+                "sky-fortress",
+                30.0 + i as f32,
+                1000,
+            ))
+            .unwrap();
+        assert_eq!(rank, i + 1);
+    }
+
+    let board = client.fetch("sky-fortress", "P11").unwrap();
+    assert_eq!(board.level, "sky-fortress");
+    assert_eq!(board.top.len(), TOP_ENTRIES);
+    assert_eq!(board.top[0].player, "P00");
+    assert_eq!(board.top[9].player, "P09");
+    assert_eq!(board.player_rank, Some(12));
+    assert_eq!(board.player_entry.as_ref().unwrap().time_ms, 41_000);
+    assert!(board.fetched_at > 0);
+
+    // Other levels are separate, and a player without a time has no rank
+    let empty = client.fetch("tutorial-valley", "P00").unwrap();
+    assert!(empty.top.is_empty());
+    assert_eq!(empty.player_rank, None);
+}
+
+#[test]
+fn test_server_keeps_each_players_best_time() {
+    let server = Server::start();
+    let client = server.client();
+
+    client
+        .submit(&signed("Ada", "lava-caves", 50.0, 900))
+        .unwrap();
+    assert_eq!(
+        client
+            .submit(&signed("Bob", "lava-caves", 45.0, 700))
+            .unwrap(),
+        1
+    );
+
+    // A slower run does not replace Ada's best; a faster one does
+    assert_eq!(
+        client
+            .submit(&signed("Ada", "lava-caves", 60.0, 2000))
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        client
+            .submit(&signed("Ada", "lava-caves", 40.0, 800))
+            .unwrap(),
+        1
+    );
+
+    // Same time as Ada with more points goes ahead of her
+    assert_eq!(
+        client
+            .submit(&signed("Cy", "lava-caves", 40.0, 1200))
+            .unwrap(),
+        1
+    );
+
+    let board = client.fetch("lava-caves", "Ada").unwrap();
+    let order: Vec<_> = board
+        .top
+        .iter()
+        .map(|entry| entry.player.as_str())
+        .collect();
+    assert_eq!(order, ["Cy", "Ada", "Bob"]);
+    assert_eq!(board.top[1].time_ms, 40_000);
+    assert_eq!(board.player_rank, Some(2));
+}
+
+#[test]
+fn test_server_rejects_forged_and_invalid_submissions() {
+    let server = Server::start();
+    let client = server.client();
+
+    let mut forged = signed("Eve", "lava-caves", 90.0, 100);
+    forged.submission.time_ms = 1;
+    let error = client.submit(&forged).unwrap_err();
+    assert!(error.contains("401"), "{}", error);
+
+    let wrong_key = Submission::new("Eve", "lava-caves", &stats(9.0, 100, 8)).sign(b"guessed");
+    assert!(client.submit(&wrong_key).unwrap_err().contains("401"));
+
+    let newline = signed("Eve\nAda", "lava-caves", 9.0, 100);
+    assert!(client.submit(&newline).unwrap_err().contains("400"));
+
+    assert!(client.fetch("lava-caves", "Eve").unwrap().top.is_empty());
+}
+
+#[test]
+fn test_service_round_trip_in_the_background() {
+    let server = Server::start();
+    let service = LeaderboardService::new(server.client());
+
+    let submission = signed("Ada", "tutorial-valley", 33.0, 1500);
+    service.submit(submission.clone());
+    service.fetch("tutorial-valley", "Ada");
+
+    let replies = wait_for_replies(&service, 2);
+    assert_eq!(replies.len(), 2);
+    assert_eq!(
+        replies[0],
+        Reply::Submitted {
+            submission,
+            rank: 1
+        }
+    );
+    match &replies[1] {
+        Reply::Fetched(board) => {
+            assert_eq!(board.top.len(), 1);
+            assert_eq!(board.player_rank, Some(1));
+        }
+        other => panic!("expected a board, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_offline_requests_fail_without_blocking() {
+    let service = LeaderboardService::new(Client::new(&closed_endpoint()));
+    let submission = signed("Ada", "tutorial-valley", 33.0, 1500);
+    service.submit(submission.clone());
+    service.fetch("tutorial-valley", "Ada");
+
+    let replies = wait_for_replies(&service, 2);
+    match &replies[..] {
+        [Reply::SubmitFailed {
+            submission: failed,
+            error,
+        }, Reply::FetchFailed { level, .. }] => {
+            assert_eq!(failed, &submission);
+            assert!(error.contains("unreachable"), "{}", error);
+            assert_eq!(level, "tutorial-valley");
+        }
+        other => panic!("expected two failures, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cache_survives_in_the_save_file() {
+    let server = Server::start();
+    let client = server.client();
+    let accepted = signed("Ada", "tutorial-valley", 33.0, 1500);
+    let waiting = signed("Ada", "sky-fortress", 61.0, 900);
+    client.submit(&accepted).unwrap();
+
+    let mut save = SaveData::default();
+    save.leaderboard.endpoint = Some(server.url.clone());
+    save.leaderboard_cache.pending = vec![accepted.clone(), waiting.clone()];
+    save.leaderboard_cache.accepted(&accepted);
+    assert_eq!(save.leaderboard_cache.pending, vec![waiting]);
+    save.leaderboard_cache.boards.insert(
+        "tutorial-valley".to_string(),
+        client.fetch("tutorial-valley", "Ada").unwrap(),
+    );
+
+    let path = std::env::temp_dir().join(format!(
+        "platformer-leaderboard-{}.json",
+        std::process::id()
+    ));
+    let path = path.to_str().unwrap();
+    save.save_to_file(path).unwrap();
+    drop(server);
+
+    // With the server gone the saved board and pending time are still there
+    let loaded = SaveData::load_from_file(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(loaded, save);
+    assert_eq!(
+        loaded.leaderboard_cache.boards["tutorial-valley"].top[0].player,
+        "Ada"
+    );
+
+    // Older saves have no leaderboard section
+    let old: SaveData = serde_json::from_str(r#"{"survival_scores": []}"#).unwrap();
+    assert_eq!(old.leaderboard, LeaderboardConfig::default());
+    assert_eq!(old.leaderboard.player_name, "Player");
+    assert!(old.leaderboard_cache.pending.is_empty());
+}
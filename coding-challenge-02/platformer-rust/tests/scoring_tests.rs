@@ -0,0 +1,146 @@
+use platformer_rust::scoring::*;
+
+fn run(score: i32, time: f32, deaths: u32, collected: usize) -> RunStats {
+    RunStats {
+        score,
+        time,
+        deaths,
+        collected,
+        total_collectibles: 4,
+    }
+}
+
+#[test]
+fn test_stomp_chain_multiplies_points() {
+    let (state, first) = stomp(ComboState::default(), STOMP_POINTS);
+    let (state, second) = stomp(state, STOMP_POINTS);
+    let (state, third) = stomp(state, STOMP_POINTS);
+
+    assert_eq!(first, STOMP_POINTS);
+    assert_eq!(second, STOMP_POINTS * 2);
+    assert_eq!(third, STOMP_POINTS * 3);
+    assert_eq!(state.stomp_multiplier(), 3);
+}
+
+#[test]
+fn test_landing_ends_stomp_chain_only() {
+    let (state, _) = stomp(ComboState::default(), STOMP_POINTS);
+    let (state, _) = stomp(state, STOMP_POINTS);
+    let (state, _) = collect_coin(state);
+    let (state, _) = collect_coin(state);
+
+    let landed = land(state);
+    assert_eq!(landed.stomp_chain, 0);
+    assert_eq!(landed.coin_streak, 2);
+
+    let (_, points) = stomp(landed, STOMP_POINTS);
+    assert_eq!(points, STOMP_POINTS);
+}
+
+#[test]
+fn test_coin_streak_bonus_within_window() {
+    let (state, first) = collect_coin(ComboState::default());
+    let state = tick(state, COIN_CHAIN_WINDOW * 0.5);
+    let (state, second) = collect_coin(state);
+    let state = tick(state, COIN_CHAIN_WINDOW * 0.9);
+    let (state, third) = collect_coin(state);
+
+    assert_eq!(first, 0);
+    assert_eq!(second, COIN_STREAK_BONUS);
+    assert_eq!(third, COIN_STREAK_BONUS * 2);
+    assert_eq!(state.coin_streak, 3);
+    assert_eq!(state.coin_timer_fraction(), 1.0);
+}
+
+#[test]
+fn test_coin_streak_lapses_after_window() {
+    let (state, _) = collect_coin(ComboState::default());
+    let (state, _) = collect_coin(state);
+
+    let state = tick(state, COIN_CHAIN_WINDOW / 2.0);
+    assert!((state.coin_timer_fraction() - 0.5).abs() < 1e-6);
+
+    let state = tick(state, COIN_CHAIN_WINDOW);
+    assert_eq!(state.coin_streak, 0);
+    assert_eq!(state.coin_timer, 0.0);
+
+    let (state, bonus) = collect_coin(state);
+    assert_eq!(bonus, 0);
+    assert_eq!(state.coin_streak, 1);
+}
+
+#[test]
+fn test_damage_resets_every_combo() {
+    let (state, _) = stomp(ComboState::default(), STOMP_POINTS);
+    let (state, _) = stomp(state, STOMP_POINTS);
+    let (state, _) = collect_coin(state);
+    let (state, _) = collect_coin(state);
+    assert!(state.is_active());
+
+    let state = take_damage(state);
+    assert_eq!(state, ComboState::default());
+    assert!(!state.is_active());
+}
+
+#[test]
+fn test_single_stomp_or_coin_is_not_a_combo() {
+    let (state, _) = stomp(ComboState::default(), STOMP_POINTS);
+    let (state, _) = collect_coin(state);
+    assert!(!state.is_active());
+}
+
+#[test]
+fn test_rank_tiers() {
+    let thresholds = RankThresholds::from_par(1000, 60.0);
+
+    assert_eq!(compute_rank(&run(1000, 60.0, 0, 4), &thresholds), Rank::S);
+    assert_eq!(compute_rank(&run(800, 80.0, 1, 3), &thresholds), Rank::A);
+    assert_eq!(compute_rank(&run(500, 150.0, 3, 2), &thresholds), Rank::B);
+    assert_eq!(compute_rank(&run(499, 150.0, 3, 2), &thresholds), Rank::C);
+}
+
+#[test]
+fn test_rank_requires_every_condition() {
+    let thresholds = RankThresholds::from_par(1000, 60.0);
+
+    // Perfect except for one thing drops to the rank that allows it
+    assert_eq!(compute_rank(&run(5000, 30.0, 1, 4), &thresholds), Rank::A);
+    assert_eq!(compute_rank(&run(5000, 61.0, 0, 4), &thresholds), Rank::A);
+    assert_eq!(compute_rank(&run(5000, 30.0, 0, 3), &thresholds), Rank::A);
+    assert_eq!(compute_rank(&run(5000, 30.0, 0, 1), &thresholds), Rank::C);
+    assert_eq!(compute_rank(&run(5000, 30.0, 4, 4), &thresholds), Rank::C);
+}
+
+#[test]
+fn test_rank_thresholds_are_per_level() {
+    let stats = run(1200, 45.0, 0, 4);
+
+    assert_eq!(
+        compute_rank(&stats, &RankThresholds::from_par(1000, 60.0)),
+        Rank::S
+    );
+    assert_eq!(
+        compute_rank(&stats, &RankThresholds::from_par(2000, 30.0)),
+        Rank::B
+    );
+}
+
+#[test]
+fn test_collection_percent() {
+    assert_eq!(run(0, 0.0, 0, 3).collection_percent(), 75.0);
+
+    let empty = RunStats::default();
+    assert_eq!(empty.collection_percent(), 100.0);
+}
+
+#[test]
+fn test_rank_thresholds_default_when_missing_from_json() {
+    let json = r#"{
+        "name": "Old Level", "width": 1000.0, "height": 600.0,
+        "spawn_x": 0.0, "spawn_y": 0.0,
+        "platforms": [], "enemies": [], "collectibles": [], "checkpoints": [],
+        "goal_x": 900.0, "goal_y": 450.0
+    }"#;
+    let data: platformer_rust::level::LevelData = serde_json::from_str(json).unwrap();
+    assert_eq!(data.rank_thresholds, RankThresholds::default());
+}
@@ -0,0 +1,204 @@
+use platformer_rust::entities::*;
+use platformer_rust::save::*;
+use platformer_rust::survival::*;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn spawner(x: f32, enemy_types: Vec<EnemyType>) -> SpawnerData {
+    SpawnerData {
+        x,
+        y: 500.0,
+        enemy_types,
+        range: 100.0,
+    }
+}
+
+fn config(max_active: usize) -> SurvivalConfig {
+    SurvivalConfig {
+        spawners: vec![
+            spawner(100.0, vec![EnemyType::Walker, EnemyType::Patroller]),
+            spawner(900.0, vec![EnemyType::Walker, EnemyType::Patroller]),
+            spawner(500.0, vec![EnemyType::Flyer]),
+        ],
+        waves: WaveTable {
+            base: vec![
+                WaveEntry {
+                    enemy_type: EnemyType::Walker,
+                    count: 4,
+                },
+                WaveEntry {
+                    enemy_type: EnemyType::Flyer,
+                    count: 2,
+                },
+            ],
+            spawn_interval: 1.0,
+            difficulty_step: 0.5,
+            min_spawn_interval: 0.3,
+        },
+        max_active,
+        breather: 2.0,
+    }
+}
+
+/// Run until the next event, failing if it doesn't come within a minute
+fn run_until_event(run: &mut SurvivalRun) -> SurvivalEvent {
+    for _ in 0..60 * 60 {
+        if let Some(event) = run.update(DT) {
+            return event;
+        }
+    }
+    panic!("no survival event within a minute");
+}
+
+fn kill_all(run: &mut SurvivalRun) {
+    for enemy in run.pool.iter_mut() {
+        enemy.kill();
+    }
+}
+
+#[test]
+fn test_wave_plans_are_deterministic_per_seed() {
+    let config = config(8);
+
+    for wave in 1..=5 {
+        assert_eq!(plan_wave(&config, 42, wave), plan_wave(&config, 42, wave));
+    }
+
+    let differs = (1..=5).any(|wave| plan_wave(&config, 42, wave) != plan_wave(&config, 43, wave));
+    assert!(differs, "different seeds should shuffle waves differently");
+}
+
+#[test]
+fn test_difficulty_scales_counts_and_interval() {
+    let table = config(8).waves;
+
+    assert_eq!(table.difficulty(1), 1.0);
+    assert_eq!(table.difficulty(3), 2.0);
+
+    let counts: Vec<u32> = table.counts(3).iter().map(|entry| entry.count).collect();
+    assert_eq!(counts, vec![8, 4]);
+
+    assert_eq!(table.spawn_interval(1), 1.0);
+    assert_eq!(table.spawn_interval(3), 0.5);
+    // Never faster than the floor
+    assert_eq!(table.spawn_interval(50), 0.3);
+}
+
+#[test]
+fn test_plan_uses_only_accepting_spawners() {
+    let mut config = config(8);
+    config.waves.base.push(WaveEntry {
+        enemy_type: EnemyType::Patroller,
+        count: 3,
+    });
+    config.spawners[0].enemy_types = vec![EnemyType::Walker];
+
+    let plan = plan_wave(&config, 7, 2);
+    assert_eq!(plan.spawns.len(), 6 + 3 + 5);
+    for order in &plan.spawns {
+        assert!(config.spawners[order.spawner].accepts(order.enemy_type));
+    }
+
+    // A type nobody spawns is dropped from the wave
+    config.spawners[2].enemy_types = vec![EnemyType::Walker];
+    let plan = plan_wave(&config, 7, 1);
+    assert!(plan.spawns.iter().all(|order| order.enemy_type != EnemyType::Flyer));
+}
+
+#[test]
+fn test_ground_enemies_stand_on_spawner() {
+    let spawner = spawner(300.0, vec![]);
+
+    let walker = spawner.spawn(EnemyType::Walker);
+    assert_eq!(walker.body.position.y + walker.body.size.y, 500.0);
+    assert_eq!((walker.patrol_start, walker.patrol_end), (200.0, 400.0));
+
+    let flyer = spawner.spawn(EnemyType::Flyer);
+    assert_eq!(flyer.fly_center.y, 500.0);
+    assert_eq!(flyer.fly_radius, 100.0);
+}
+
+#[test]
+fn test_waves_start_after_breather_and_pay_out_when_cleared() {
+    let mut run = SurvivalRun::new(config(8), 1);
+    assert_eq!(run.wave, 0);
+    assert!(run.breather_left().is_some());
+
+    assert_eq!(run_until_event(&mut run), SurvivalEvent::WaveStarted(1));
+    assert_eq!(run.remaining(), 6);
+
+    // Let every enemy spawn, then clear them
+    while run.remaining() > run.pool.active_count() {
+        assert_eq!(run.update(DT), None);
+    }
+    kill_all(&mut run);
+
+    assert_eq!(
+        run.update(DT),
+        Some(SurvivalEvent::WaveCleared {
+            wave: 1,
+            bonus: WAVE_CLEAR_POINTS
+        })
+    );
+    assert_eq!(run.phase, WavePhase::Breather(2.0));
+
+    assert_eq!(run_until_event(&mut run), SurvivalEvent::WaveStarted(2));
+    assert_eq!(wave_bonus(2), WAVE_CLEAR_POINTS * 2);
+}
+
+#[test]
+fn test_same_seed_replays_same_run() {
+    let positions = |seed| {
+        let mut run = SurvivalRun::new(config(8), seed);
+        for _ in 0..60 * 8 {
+            run.update(DT);
+        }
+        run.pool
+            .iter_mut()
+            .map(|enemy| (enemy.enemy_type, enemy.body.position.x))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(positions(99), positions(99));
+}
+
+#[test]
+fn test_high_score_table_keeps_best_runs() {
+    let mut save = SaveData::default();
+    let run = |score| HighScore {
+        score,
+        wave: 1,
+        seed: 0,
+    };
+
+    assert_eq!(save.record_survival(run(500)), Some(0));
+    assert_eq!(save.record_survival(run(900)), Some(0));
+    assert_eq!(save.record_survival(run(500)), Some(2));
+    for score in [100, 200, 300] {
+        save.record_survival(run(score));
+    }
+
+    let scores: Vec<i32> = save.survival_scores.iter().map(|entry| entry.score).collect();
+    assert_eq!(scores, vec![900, 500, 500, 300, 200]);
+    assert_eq!(save.record_survival(run(50)), None);
+    assert_eq!(save.survival_scores.len(), HIGH_SCORE_SLOTS);
+}
+
+#[test]
+fn test_save_round_trip() {
+    let path = std::env::temp_dir().join("platformer_survival_save_test.json");
+    let path = path.to_str().unwrap();
+
+    let mut save = SaveData::default();
+    save.record_survival(HighScore {
+        score: 4200,
+        wave: 7,
+        seed: 12345,
+    });
+    save.save_to_file(path).unwrap();
+
+    assert_eq!(SaveData::load_from_file(path).unwrap(), save);
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(SaveData::load_or_default(path), SaveData::default());
+}
@@ -0,0 +1,240 @@
+use crate::chess::{Board, PieceType, Position};
+
+/// When the AI gives up a lost game or offers to split a dead-drawn one
+///
+/// Scores are in centipawns from the AI's side, as the search reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdjudicationConfig {
+    /// Whether the AI resigns, offers draws and accepts the player's offers
+    pub enabled: bool,
+    /// Score at or below which the AI considers the game lost
+    pub resign_threshold: i32,
+    /// Consecutive lost scores before the AI resigns
+    pub resign_moves: u32,
+    /// Shallower searches are too unreliable to resign or offer a draw on
+    pub min_depth: u8,
+    /// Scores this close to zero count as level
+    pub draw_window: i32,
+    /// Consecutive level scores in a simplified position before the AI offers a draw
+    pub draw_moves: u32,
+    /// Most material (knights, bishops, rooks and queens of both sides) a
+    /// position may hold and still count as simplified
+    pub simplified_material: i32,
+    /// The AI accepts a draw offer unless it is ahead by more than this
+    pub accept_window: i32,
+}
+
+impl AdjudicationConfig {
+    /// Tournament play: every game is played out to the end
+    pub fn tournament() -> Self {
+        AdjudicationConfig { enabled: false, ..Self::default() }
+    }
+}
+
+impl Default for AdjudicationConfig {
+    fn default() -> Self {
+        AdjudicationConfig {
+            enabled: true,
+            resign_threshold: -900,
+            resign_moves: 4,
+            min_depth: 3,
+            draw_window: 25,
+            draw_moves: 6,
+            simplified_material: 2000,
+            accept_window: 50,
+        }
+    }
+}
+
+/// What the AI wants to do besides playing its move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjudication {
+    /// Resign instead of moving
+    Resign,
+    /// Play the move, then offer a draw
+    OfferDraw,
+}
+
+/// Tracks the AI's scores move by move to decide when to resign or offer a draw
+///
+/// A decision needs the same verdict several moves running, so one bad
+/// score from a horizon effect doesn't end the game: any score that breaks
+/// the run starts the count again.
+#[derive(Debug, Clone)]
+pub struct Adjudicator {
+    config: AdjudicationConfig,
+    /// Consecutive scores at or below the resign threshold
+    lost: u32,
+    /// Consecutive level scores in simplified positions
+    level: u32,
+    /// Latest score at any depth, for answering draw offers
+    last_score: Option<i32>,
+}
+
+impl Adjudicator {
+    pub fn new(config: AdjudicationConfig) -> Self {
+        Adjudicator { config, lost: 0, level: 0, last_score: None }
+    }
+
+    pub fn config(&self) -> &AdjudicationConfig {
+        &self.config
+    }
+
+    /// Record the score of the AI's latest search, `depth` plies deep, in a
+    /// position holding `material` (see [`piece_material`])
+    ///
+    /// Searches shallower than the configured minimum neither count towards
+    /// nor break a run. After a draw offer the run starts again, so a
+    /// declined offer isn't repeated every move.
+    pub fn observe(&mut self, score: i32, depth: u8, material: i32) -> Option<Adjudication> {
+        self.last_score = Some(score);
+        if !self.config.enabled || depth < self.config.min_depth {
+            return None;
+        }
+
+        self.lost = if score <= self.config.resign_threshold { self.lost + 1 } else { 0 };
+        let level = score.abs() <= self.config.draw_window && material <= self.config.simplified_material;
+        self.level = if level { self.level + 1 } else { 0 };
+
+        if self.lost >= self.config.resign_moves {
+            Some(Adjudication::Resign)
+        } else if self.level >= self.config.draw_moves {
+            self.level = 0;
+            Some(Adjudication::OfferDraw)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the AI takes the player's draw offer
+    ///
+    /// It does unless its latest score puts it ahead by more than the accept
+    /// window; with no score yet, or in tournament play, it plays on.
+    pub fn accepts_draw(&self) -> bool {
+        self.config.enabled && self.last_score.is_some_and(|score| score <= self.config.accept_window)
+    }
+
+    /// Forget the scores seen so far, e.g. after moves are taken back
+    pub fn reset(&mut self) {
+        self.lost = 0;
+        self.level = 0;
+        self.last_score = None;
+    }
+}
+
+/// Material of both sides, pawns and kings left out
+pub fn piece_material(board: &Board) -> i32 {
+    (0..8)
+        .flat_map(|row| (0..8).filter_map(move |col| Position::new(row, col)))
+        .filter_map(|pos| board.get_piece(pos))
+        .filter(|piece| !matches!(piece.piece_type, PieceType::Pawn | PieceType::King))
+        .map(|piece| piece.value())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simplified and unsimplified material
+    const ENDGAME: i32 = 1150;
+    const MIDDLEGAME: i32 = 6000;
+
+    /// Feed full-depth scores, returning each decision
+    fn replay(adjudicator: &mut Adjudicator, scores: &[i32], material: i32) -> Vec<Option<Adjudication>> {
+        scores.iter().map(|&score| adjudicator.observe(score, 4, material)).collect()
+    }
+
+    #[test]
+    fn test_resigns_after_consecutive_lost_scores() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        let decisions = replay(&mut adjudicator, &[-950, -1200, -1500, -2000], MIDDLEGAME);
+        assert_eq!(decisions, vec![None, None, None, Some(Adjudication::Resign)]);
+    }
+
+    #[test]
+    fn test_single_bad_score_does_not_resign() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        let decisions = replay(&mut adjudicator, &[-50, -2500, -40, -60, -30], MIDDLEGAME);
+        assert!(decisions.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_recovery_restarts_the_count() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        let decisions = replay(&mut adjudicator, &[-1000, -1000, -1000, -850, -1000, -1000, -1000], MIDDLEGAME);
+        assert!(decisions.iter().all(Option::is_none));
+        assert_eq!(adjudicator.observe(-1000, 4, MIDDLEGAME), Some(Adjudication::Resign));
+    }
+
+    #[test]
+    fn test_shallow_searches_are_ignored() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        replay(&mut adjudicator, &[-1000, -1000, -1000], MIDDLEGAME);
+
+        // A shallow spike in either direction neither resigns nor breaks the run
+        assert_eq!(adjudicator.observe(-5000, 1, MIDDLEGAME), None);
+        assert_eq!(adjudicator.observe(300, 2, MIDDLEGAME), None);
+        assert_eq!(adjudicator.observe(-1000, 3, MIDDLEGAME), Some(Adjudication::Resign));
+    }
+
+    #[test]
+    fn test_mated_scores_count_as_lost() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig { resign_moves: 2, ..Default::default() });
+        let decisions = replay(&mut adjudicator, &[-100000 + 9, -100000 + 7], MIDDLEGAME);
+        assert_eq!(decisions, vec![None, Some(Adjudication::Resign)]);
+    }
+
+    #[test]
+    fn test_offers_draw_in_level_simplified_positions() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+
+        // Level scores with plenty of material left never lead to an offer
+        assert!(replay(&mut adjudicator, &[0; 10], MIDDLEGAME).iter().all(Option::is_none));
+
+        let decisions = replay(&mut adjudicator, &[10, -20, 0, 25, 5, -10], ENDGAME);
+        assert_eq!(decisions.last(), Some(&Some(Adjudication::OfferDraw)));
+        assert!(decisions[..5].iter().all(Option::is_none));
+
+        // A declined offer is only repeated after another full run
+        assert!(replay(&mut adjudicator, &[0; 5], ENDGAME).iter().all(Option::is_none));
+        assert_eq!(adjudicator.observe(0, 4, ENDGAME), Some(Adjudication::OfferDraw));
+    }
+
+    #[test]
+    fn test_unbalanced_score_breaks_draw_run() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        let decisions = replay(&mut adjudicator, &[0, 0, 0, 0, 0, 80, 0, 0, 0, 0, 0], ENDGAME);
+        assert!(decisions.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_answers_draw_offers_by_score() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::default());
+        assert!(!adjudicator.accepts_draw());
+
+        // Shallow scores still count when answering an offer
+        adjudicator.observe(30, 1, MIDDLEGAME);
+        assert!(adjudicator.accepts_draw());
+        adjudicator.observe(-400, 4, MIDDLEGAME);
+        assert!(adjudicator.accepts_draw());
+        adjudicator.observe(120, 4, MIDDLEGAME);
+        assert!(!adjudicator.accepts_draw());
+
+        adjudicator.reset();
+        assert!(!adjudicator.accepts_draw());
+    }
+
+    #[test]
+    fn test_tournament_mode_plays_on() {
+        let mut adjudicator = Adjudicator::new(AdjudicationConfig::tournament());
+        assert!(replay(&mut adjudicator, &[-3000; 10], MIDDLEGAME).iter().all(Option::is_none));
+        assert!(replay(&mut adjudicator, &[0; 10], ENDGAME).iter().all(Option::is_none));
+        assert!(!adjudicator.accepts_draw());
+    }
+
+    #[test]
+    fn test_piece_material() {
+        assert_eq!(piece_material(&Board::new()), 2 * (2 * 320 + 2 * 330 + 2 * 500 + 900));
+    }
+}
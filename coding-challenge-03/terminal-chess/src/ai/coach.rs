@@ -0,0 +1,165 @@
+use super::engine::ChessAI;
+use crate::chess::{Board, Move, PieceType};
+
+/// Search depth for the blunder check (reduced so it answers instantly)
+const COACH_DEPTH: u8 = 2;
+
+/// Net material loss (centipawns) that triggers a warning
+const HANG_THRESHOLD: i32 = 200;
+
+/// Warning about a move that loses material to a simple tactic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoachWarning {
+    /// Piece the opponent wins
+    pub piece: PieceType,
+    /// Net material lost in centipawns
+    pub material_lost: i32,
+    /// Opponent reply that wins the material
+    pub reply: Move,
+}
+
+impl CoachWarning {
+    pub fn message(&self) -> String {
+        format!(
+            "Are you sure? This hangs your {} ({} wins it)",
+            self.piece.name(),
+            self.reply.to_algebraic()
+        )
+    }
+}
+
+/// Beginner coach that flags moves hanging material over two plies
+pub struct Coach {
+    engine: ChessAI,
+}
+
+impl Coach {
+    pub fn new() -> Self {
+        Coach {
+            engine: ChessAI::new(COACH_DEPTH),
+        }
+    }
+
+    /// Check a (legal) move before it is played
+    ///
+    /// Asks the engine for the opponent's best reply at reduced depth; if that
+    /// reply captures and the exchange (allowing one recapture on the same
+    /// square) leaves the mover down material, a warning is returned.
+    pub fn check_move(&self, board: &Board, mov: &Move) -> Option<CoachWarning> {
+        let color = mov.piece.color;
+
+        let mut after_move = board.clone();
+        after_move.make_move(mov);
+
+        let reply = self.engine.find_best_move(&after_move, color.opposite())?;
+        let lost = reply.captured?;
+
+        let mut after_reply = after_move.clone();
+        after_reply.make_move(&reply);
+
+        let recapture = after_reply
+            .generate_legal_moves(color)
+            .iter()
+            .filter(|m| m.to == reply.to)
+            .filter_map(|m| m.captured.map(|p| p.value()))
+            .max()
+            .unwrap_or(0);
+
+        let gained = mov.captured.map_or(0, |p| p.value());
+        let net = gained - lost.value() + recapture;
+
+        if net <= -HANG_THRESHOLD {
+            Some(CoachWarning {
+                piece: lost.piece_type,
+                material_lost: -net,
+                reply,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Coach {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Position;
+
+    fn pos(s: &str) -> Position {
+        Position::from_algebraic(s).unwrap()
+    }
+
+    fn find_move(board: &Board, from: &str, to: &str) -> Move {
+        board
+            .generate_legal_moves(board.get_piece(pos(from)).unwrap().color)
+            .into_iter()
+            .find(|m| m.from == pos(from) && m.to == pos(to))
+            .unwrap()
+    }
+
+    fn play(moves: &[(&str, &str)]) -> Board {
+        let mut board = Board::new();
+        for (from, to) in moves {
+            let mov = find_move(&board, from, to);
+            board.make_move(&mov);
+        }
+        board
+    }
+
+    #[test]
+    fn test_warns_when_queen_hangs() {
+        // 1. e4 e5 2. Qh5 Nc6 with the d7 pawn gone: Qg4 walks into the c8 bishop
+        let mut board = play(&[("e2", "e4"), ("e7", "e5"), ("d1", "h5"), ("b8", "c6")]);
+        board.set_piece(pos("d7"), None);
+
+        let mov = find_move(&board, "h5", "g4");
+        let warning = Coach::new().check_move(&board, &mov).unwrap();
+
+        assert_eq!(warning.piece, PieceType::Queen);
+        assert!(warning.material_lost >= 800);
+        assert!(warning.message().contains("hangs your queen"));
+    }
+
+    #[test]
+    fn test_warns_on_losing_exchange() {
+        // 1. e4 e6 2. Nc3 a6 3. Nd5?? exd5 exd5 trades a knight for a pawn
+        let board = play(&[("e2", "e4"), ("e7", "e6"), ("b1", "c3"), ("a7", "a6")]);
+
+        let mov = find_move(&board, "c3", "d5");
+        let warning = Coach::new().check_move(&board, &mov).unwrap();
+
+        assert_eq!(warning.piece, PieceType::Knight);
+        assert_eq!(warning.material_lost, 220);
+    }
+
+    #[test]
+    fn test_no_warning_for_defended_pawn() {
+        // 1. e4 e5 2. Nf3 Nc6 3. d4: exd4 Nxd4 keeps material level
+        let board = play(&[("e2", "e4"), ("e7", "e5"), ("g1", "f3"), ("b8", "c6")]);
+
+        let mov = find_move(&board, "d2", "d4");
+        assert!(Coach::new().check_move(&board, &mov).is_none());
+    }
+
+    #[test]
+    fn test_no_warning_for_even_trade() {
+        // 1. e4 d5 2. exd5 Qxd5 is an even pawn trade
+        let board = play(&[("e2", "e4"), ("d7", "d5")]);
+
+        let mov = find_move(&board, "e4", "d5");
+        assert!(Coach::new().check_move(&board, &mov).is_none());
+    }
+
+    #[test]
+    fn test_no_warning_for_quiet_opening_move() {
+        let board = Board::new();
+        let mov = find_move(&board, "e2", "e4");
+        assert!(Coach::new().check_move(&board, &mov).is_none());
+    }
+}
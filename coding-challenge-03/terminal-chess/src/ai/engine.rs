@@ -1,17 +1,176 @@
-use crate::chess::{Board, Color, Move};
+use super::progress::SearchProgress;
+use super::skill::{blunder_probability, pick_move_index, SkillRng};
+use super::smp::{self, SearchResult};
+use super::stats::SearchStats;
+use super::tablebase::{self, RootProbe, Tablebase};
+use crate::chess::{Board, CheckCount, Color, Move, VariantKind};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct ChessAI {
     depth: u8,
+    variant: VariantKind,
+    /// Checks delivered before the search starts
+    checks: CheckCount,
+    /// Search threads (1 = the classic single-threaded search)
+    threads: usize,
+    node_limit: Option<u64>,
+    time_limit: Option<Duration>,
+    /// Where the search reports its progress, if anyone is watching
+    progress: Option<Sender<SearchProgress>>,
+    /// Endgame tables consulted in standard chess once few enough pieces are left
+    tablebase: Option<Arc<dyn Tablebase>>,
 }
 
 impl ChessAI {
     pub fn new(depth: u8) -> Self {
-        ChessAI { depth }
+        ChessAI::with_variant(depth, VariantKind::Standard, CheckCount::default())
+    }
+
+    /// Create an AI that plays towards a variant's objective
+    pub fn with_variant(depth: u8, variant: VariantKind, checks: CheckCount) -> Self {
+        ChessAI {
+            depth,
+            variant,
+            checks,
+            threads: 1,
+            node_limit: None,
+            time_limit: None,
+            progress: None,
+            tablebase: None,
+        }
+    }
+
+    /// Search with `threads` Lazy SMP threads sharing one transposition table
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Stop searching once all threads together have visited `nodes` nodes
+    pub fn with_node_limit(mut self, nodes: u64) -> Self {
+        self.node_limit = Some(nodes);
+        self
+    }
+
+    /// Stop searching after `limit`, playing the best move found so far
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Report [`SearchProgress`] after every iteration and every ~250ms in between
+    ///
+    /// Reporting goes through [`ChessAI::search`], so the fixed-depth minimax is not used.
+    pub fn with_progress(mut self, sender: Sender<SearchProgress>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Play from `tablebase` at the root and score table positions in the search
+    ///
+    /// Only used in standard chess; variants change the result of an ending.
+    pub fn with_tablebase(mut self, tablebase: Arc<dyn Tablebase>) -> Self {
+        self.tablebase = Some(tablebase);
+        self
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub fn node_limit(&self) -> Option<u64> {
+        self.node_limit
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub(super) fn progress(&self) -> Option<&Sender<SearchProgress>> {
+        self.progress.as_ref()
+    }
+
+    /// Legal moves for `color` under the variant's rules
+    pub(super) fn legal_moves(&self, board: &Board, color: Color) -> Vec<Move> {
+        self.variant.rules().filter_moves(board, color, board.generate_legal_moves(color))
+    }
+
+    /// Check count after `mover` has played into `board`
+    pub(super) fn checks_after(&self, board: &Board, mover: Color, checks: CheckCount) -> CheckCount {
+        let mut checks = checks;
+        self.variant.rules().after_move(board, mover, &mut checks);
+        checks
+    }
+
+    /// Checks delivered before the search starts
+    pub(super) fn start_checks(&self) -> CheckCount {
+        self.checks
+    }
+
+    /// Score for a reached variant objective, relative to `color`
+    pub(super) fn objective_score(&self, board: &Board, checks: &CheckCount, color: Color, ply: i32) -> Option<i32> {
+        self.variant.objective_score(board, checks, color, ply)
+    }
+
+    /// Tablebase, if one applies to this game
+    fn tablebase(&self) -> Option<&dyn Tablebase> {
+        if self.variant != VariantKind::Standard {
+            return None;
+        }
+        self.tablebase.as_deref()
+    }
+
+    /// Tablebase score of a position reached `ply` plies into the search
+    pub(super) fn probe_wdl(&self, board: &Board, color: Color, ply: i32) -> Option<i32> {
+        let tablebase = self.tablebase()?;
+        if !tablebase::in_tables(tablebase, board) {
+            return None;
+        }
+        tablebase.probe_wdl(board, color).map(|wdl| wdl.score(ply))
+    }
+
+    /// Move to play straight from the tables, if the root position is in them
+    pub(super) fn probe_root(&self, board: &Board, color: Color) -> Option<RootProbe> {
+        let tablebase = self.tablebase()?;
+        tablebase::probe_root(tablebase, board, color, &self.legal_moves(board, color))
+    }
+
+    /// Iterative-deepening search using the configured threads and limits
+    ///
+    /// Runs Lazy SMP: every thread searches the full tree and they share work
+    /// through a transposition table. Returns `None` if there are no legal moves.
+    pub fn search(&self, board: &Board, color: Color) -> Option<SearchResult> {
+        smp::search(self, board, color)
+    }
+
+    /// Run [`ChessAI::search`] while collecting [`SearchStats`]
+    pub fn search_with_stats(&self, board: &Board, color: Color) -> Option<(SearchResult, SearchStats)> {
+        smp::search_with_stats(self, board, color)
+    }
+
+    /// Whether moves come from `search` rather than the fixed-depth minimax
+    fn uses_smp(&self) -> bool {
+        self.threads > 1 || self.node_limit.is_some() || self.time_limit.is_some() || self.progress.is_some()
     }
 
     /// Find the best move using minimax with alpha-beta pruning
+    ///
+    /// With more than one thread, a node/time limit or a progress channel this runs [`ChessAI::search`].
     pub fn find_best_move(&self, board: &Board, color: Color) -> Option<Move> {
-        let legal_moves = board.generate_legal_moves(color);
+        if self.uses_smp() {
+            return self.search(board, color).map(|result| result.best_move);
+        }
+        if let Some(probe) = self.probe_root(board, color) {
+            return Some(probe.best_move);
+        }
+
+        let legal_moves = self.legal_moves(board, color);
 
         if legal_moves.is_empty() {
             return None;
@@ -19,14 +178,16 @@ impl ChessAI {
 
         let mut best_move = legal_moves[0];
         let mut best_score = i32::MIN;
-        let mut alpha = i32::MIN;
+        // -i32::MAX rather than i32::MIN so the bound can be negated safely
+        let mut alpha = -i32::MAX;
         let beta = i32::MAX;
 
         for mov in legal_moves {
             let mut new_board = board.clone();
             new_board.make_move(&mov);
+            let checks = self.checks_after(&new_board, color, self.checks);
 
-            let score = -self.minimax(&new_board, self.depth - 1, -beta, -alpha, color.opposite());
+            let score = -self.minimax(&new_board, checks, self.depth - 1, -beta, -alpha, color.opposite());
 
             if score > best_score {
                 best_score = score;
@@ -39,14 +200,86 @@ impl ChessAI {
         Some(best_move)
     }
 
+    /// Score every legal move with a full-window search, best first
+    pub fn rank_moves(&self, board: &Board, color: Color) -> Vec<(Move, i32)> {
+        let mut ranked: Vec<(Move, i32)> = self
+            .legal_moves(board, color)
+            .into_iter()
+            .map(|mov| {
+                let mut new_board = board.clone();
+                new_board.make_move(&mov);
+                let checks = self.checks_after(&new_board, color, self.checks);
+                let score =
+                    -self.minimax(&new_board, checks, self.depth - 1, -i32::MAX, i32::MAX, color.opposite());
+                (mov, score)
+            })
+            .collect();
+
+        // Stable sort keeps generation order among equal scores
+        ranked.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        ranked
+    }
+
+    /// Choose a move at the given skill level (1-10)
+    ///
+    /// Full skill plays the best move; lower levels occasionally play the 2nd or
+    /// 3rd best move. The choice is deterministic for a given `rng` seed.
+    pub fn choose_move(&self, board: &Board, color: Color, skill: u8, rng: &mut SkillRng) -> Option<Move> {
+        // Only the best move is ever played, so let the parallel search find it
+        if self.uses_smp() && blunder_probability(skill) == 0.0 {
+            return self.find_best_move(board, color);
+        }
+        if blunder_probability(skill) == 0.0 {
+            if let Some(probe) = self.probe_root(board, color) {
+                return Some(probe.best_move);
+            }
+        }
+
+        let ranked = self.rank_moves(board, color);
+        if ranked.is_empty() {
+            return None;
+        }
+
+        let idx = pick_move_index(skill, ranked.len(), rng);
+        Some(ranked[idx].0)
+    }
+
+    /// Like [`ChessAI::choose_move`], also returning search statistics
+    ///
+    /// Statistics come from [`ChessAI::search_with_stats`], so they are only
+    /// available at full skill; weaker levels pick from the ranking as usual.
+    pub fn choose_move_with_stats(
+        &self,
+        board: &Board,
+        color: Color,
+        skill: u8,
+        rng: &mut SkillRng,
+    ) -> Option<(Move, Option<SearchStats>)> {
+        if blunder_probability(skill) == 0.0 {
+            return self.search_with_stats(board, color).map(|(result, stats)| (result.best_move, Some(stats)));
+        }
+        self.choose_move(board, color, skill, rng).map(|mov| (mov, None))
+    }
+
     /// Minimax algorithm with alpha-beta pruning
-    fn minimax(&self, board: &Board, depth: u8, mut alpha: i32, beta: i32, color: Color) -> i32 {
+    fn minimax(&self, board: &Board, checks: CheckCount, depth: u8, mut alpha: i32, beta: i32, color: Color) -> i32 {
+        // Variant objective reached by the previous move
+        let ply = (self.depth - depth) as i32;
+        if let Some(score) = self.variant.objective_score(board, &checks, color, ply) {
+            return score;
+        }
+
+        // Position in the endgame tables
+        if let Some(score) = self.probe_wdl(board, color, ply) {
+            return score;
+        }
+
         // Base case: depth 0 or game over
         if depth == 0 {
-            return self.evaluate_position(board, color);
+            return self.evaluate_position(board, &checks, color);
         }
 
-        let legal_moves = board.generate_legal_moves(color);
+        let legal_moves = self.legal_moves(board, color);
 
         // Checkmate or stalemate
         if legal_moves.is_empty() {
@@ -64,8 +297,9 @@ impl ChessAI {
         for mov in legal_moves {
             let mut new_board = board.clone();
             new_board.make_move(&mov);
+            let child_checks = self.checks_after(&new_board, color, checks);
 
-            let score = -self.minimax(&new_board, depth - 1, -beta, -alpha, color.opposite());
+            let score = -self.minimax(&new_board, child_checks, depth - 1, -beta, -alpha, color.opposite());
 
             max_score = max_score.max(score);
             alpha = alpha.max(score);
@@ -80,7 +314,7 @@ impl ChessAI {
     }
 
     /// Evaluate the board position from the perspective of the given color
-    fn evaluate_position(&self, board: &Board, color: Color) -> i32 {
+    pub(super) fn evaluate_position(&self, board: &Board, checks: &CheckCount, color: Color) -> i32 {
         let base_eval = board.evaluate();
 
         // Adjust based on whose turn it is
@@ -94,7 +328,10 @@ impl ChessAI {
         let opponent_mobility = board.generate_legal_moves(color.opposite()).len() as i32;
         let mobility_score = (mobility - opponent_mobility) * 10;
 
-        eval + mobility_score
+        // Progress towards the variant objective (zero for standard chess)
+        let objective = self.variant.rules().evaluate(board, checks, color);
+
+        eval + mobility_score + objective
     }
 }
 
@@ -107,4 +344,109 @@ mod tests {
         let ai = ChessAI::new(3);
         assert_eq!(ai.depth, 3);
     }
+
+    #[test]
+    fn test_find_best_move_from_start() {
+        let ai = ChessAI::new(1);
+        let board = Board::new();
+        let mov = ai.find_best_move(&board, Color::White).unwrap();
+        assert!(board.generate_legal_moves(Color::White).contains(&mov));
+    }
+
+    #[test]
+    fn test_rank_moves_best_first() {
+        let ai = ChessAI::new(1);
+        let ranked = ai.rank_moves(&Board::new(), Color::White);
+
+        assert_eq!(ranked.len(), 20);
+        assert!(ranked.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    fn position(pieces: &[(&str, crate::chess::PieceType, Color)]) -> Board {
+        use crate::chess::{Piece, Position};
+
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position::new(row, col).unwrap(), None);
+            }
+        }
+        board.white_can_castle_kingside = false;
+        board.white_can_castle_queenside = false;
+        board.black_can_castle_kingside = false;
+        board.black_can_castle_queenside = false;
+
+        for &(square, piece_type, color) in pieces {
+            board.set_piece(Position::from_algebraic(square).unwrap(), Some(Piece::new(piece_type, color)));
+        }
+        board
+    }
+
+    #[test]
+    fn test_king_of_the_hill_ai_steps_onto_hill() {
+        use crate::chess::{PieceType, Position};
+
+        // White is a queen down, but one king step from the hill
+        let board = position(&[
+            ("e3", PieceType::King, Color::White),
+            ("h8", PieceType::King, Color::Black),
+            ("a6", PieceType::Queen, Color::Black),
+        ]);
+
+        let ai = ChessAI::with_variant(2, VariantKind::KingOfTheHill, CheckCount::default());
+        let mov = ai.find_best_move(&board, Color::White).unwrap();
+        assert!(crate::chess::KingOfTheHill::is_hill(mov.to));
+        assert_eq!(mov.from, Position::from_algebraic("e3").unwrap());
+    }
+
+    #[test]
+    fn test_three_check_ai_delivers_third_check() {
+        use crate::chess::PieceType;
+
+        let board = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("e8", PieceType::King, Color::Black),
+            ("a1", PieceType::Rook, Color::White),
+            ("h5", PieceType::Rook, Color::Black),
+        ]);
+        let checks = CheckCount { white: 2, black: 0 };
+
+        let ai = ChessAI::with_variant(2, VariantKind::ThreeCheck, checks);
+        let mov = ai.find_best_move(&board, Color::White).unwrap();
+
+        let mut after = board.clone();
+        after.make_move(&mov);
+        assert!(after.is_in_check(Color::Black));
+
+        // A standard AI has no reason to prefer the check
+        let standard = ChessAI::new(2).rank_moves(&board, Color::White);
+        let three_check = ai.rank_moves(&board, Color::White);
+        assert!(three_check[0].1 > standard[0].1);
+    }
+
+    #[test]
+    fn test_choose_move_seeded_deterministic() {
+        use crate::chess::MAX_SKILL;
+
+        let ai = ChessAI::new(1);
+        let board = Board::new();
+        let ranked = ai.rank_moves(&board, Color::White);
+
+        let play = |seed| {
+            let mut rng = SkillRng::new(seed);
+            (0..20)
+                .map(|_| ai.choose_move(&board, Color::White, 1, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let first = play(42);
+        assert_eq!(first, play(42));
+
+        // Weak play only ever strays to the 2nd/3rd best move
+        let top3: Vec<Move> = ranked.iter().take(3).map(|(m, _)| *m).collect();
+        assert!(first.iter().all(|m| top3.contains(m)));
+        assert!(first.iter().any(|m| *m != ranked[0].0));
+
+        let mut rng = SkillRng::new(42);
+        assert_eq!(ai.choose_move(&board, Color::White, MAX_SKILL, &mut rng), Some(ranked[0].0));
+    }
 }
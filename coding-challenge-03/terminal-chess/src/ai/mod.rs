@@ -1,3 +1,20 @@
+pub mod adjudicator;
+pub mod coach;
 pub mod engine;
+pub mod progress;
+pub mod skill;
+pub mod smp;
+pub mod stats;
+#[cfg(feature = "syzygy")]
+pub mod syzygy;
+pub mod tablebase;
+pub mod tt;
 
+pub use adjudicator::{piece_material, Adjudication, AdjudicationConfig, Adjudicator};
+pub use coach::{Coach, CoachWarning};
 pub use engine::ChessAI;
+pub use progress::{Evaluation, SearchProgress};
+pub use skill::SkillRng;
+pub use smp::SearchResult;
+pub use stats::SearchStats;
+pub use tablebase::{Tablebase, Wdl};
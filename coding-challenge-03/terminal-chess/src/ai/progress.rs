@@ -0,0 +1,112 @@
+use super::tt::MATE_BOUND;
+use crate::chess::{Color, Move};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+/// Minimum time between progress reports sent while an iteration is running
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Snapshot of a running search, sent over the channel given to [`ChessAI::with_progress`]
+///
+/// [`ChessAI::with_progress`]: super::ChessAI::with_progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchProgress {
+    /// Iteration being searched, or just finished if `completed`
+    pub depth: u8,
+    /// Best move of the last finished iteration
+    pub best_move: Move,
+    /// Score of `best_move`, relative to the side searching
+    pub score: i32,
+    /// Nodes searched by all threads so far
+    pub nodes: u64,
+    pub elapsed: Duration,
+    /// Whether iteration `depth` has just finished
+    pub completed: bool,
+}
+
+impl SearchProgress {
+    /// Evaluation from `viewer`'s side, for a search run for `searcher`
+    pub fn evaluation_for(&self, searcher: Color, viewer: Color) -> Evaluation {
+        let score = if searcher == viewer { self.score } else { -self.score };
+        Evaluation::from_score(score)
+    }
+}
+
+/// Human-readable position assessment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Evaluation {
+    /// Material-style advantage in pawns (positive is good)
+    Pawns(f64),
+    /// Forced win (positive) or loss (negative) in this many moves
+    Mate(i32),
+}
+
+impl Evaluation {
+    /// Convert a search score, where mates are `100000 - ply`
+    pub fn from_score(score: i32) -> Self {
+        if score.abs() >= MATE_BOUND {
+            let plies = 100000 - score.abs();
+            let moves = (plies + 1) / 2;
+            Evaluation::Mate(if score > 0 { moves } else { -moves })
+        } else {
+            Evaluation::Pawns(score as f64 / 100.0)
+        }
+    }
+
+    /// Short text such as `+0.35`, `M3` or `-M2`
+    pub fn label(&self) -> String {
+        match *self {
+            Evaluation::Pawns(pawns) => format!("{:+.2}", pawns),
+            Evaluation::Mate(moves) if moves < 0 => format!("-M{}", -moves),
+            Evaluation::Mate(moves) => format!("M{}", moves),
+        }
+    }
+}
+
+/// Sends [`SearchProgress`] from the main search thread, rate-limiting periodic reports
+pub(super) struct ProgressReporter<'a> {
+    sender: &'a Sender<SearchProgress>,
+    start: Instant,
+    next_report: Instant,
+}
+
+impl<'a> ProgressReporter<'a> {
+    pub(super) fn new(sender: &'a Sender<SearchProgress>, start: Instant) -> Self {
+        ProgressReporter { sender, start, next_report: start + PROGRESS_INTERVAL }
+    }
+
+    /// Whether a periodic report is due
+    pub(super) fn due(&self) -> bool {
+        Instant::now() >= self.next_report
+    }
+
+    /// Send a report; a dropped receiver just means nobody is watching
+    pub(super) fn send(&mut self, depth: u8, best_move: Move, score: i32, nodes: u64, completed: bool) {
+        let now = Instant::now();
+        let _ = self.sender.send(SearchProgress { depth, best_move, score, nodes, elapsed: now - self.start, completed });
+        self.next_report = now + PROGRESS_INTERVAL;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluation_from_score() {
+        assert_eq!(Evaluation::from_score(35), Evaluation::Pawns(0.35));
+        assert_eq!(Evaluation::from_score(-250), Evaluation::Pawns(-2.5));
+        // Mate on the next ply is mate in 1; mated after 4 plies is mated in 2
+        assert_eq!(Evaluation::from_score(100000 - 1), Evaluation::Mate(1));
+        assert_eq!(Evaluation::from_score(-100000 + 4), Evaluation::Mate(-2));
+        assert_eq!(Evaluation::from_score(100000 - 5), Evaluation::Mate(3));
+    }
+
+    #[test]
+    fn test_evaluation_labels() {
+        assert_eq!(Evaluation::Pawns(0.35).label(), "+0.35");
+        assert_eq!(Evaluation::Pawns(-1.0).label(), "-1.00");
+        assert_eq!(Evaluation::Mate(3).label(), "M3");
+        assert_eq!(Evaluation::Mate(-2).label(), "-M2");
+    }
+}
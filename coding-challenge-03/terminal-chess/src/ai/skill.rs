@@ -0,0 +1,126 @@
+use crate::chess::MAX_SKILL;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small seedable PRNG (xorshift64*) so skill-level move choice is reproducible
+#[derive(Debug, Clone)]
+pub struct SkillRng {
+    state: u64,
+}
+
+impl SkillRng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift must never have an all-zero state
+        SkillRng {
+            state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed },
+        }
+    }
+
+    /// Seed from the system clock (for interactive play)
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        SkillRng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform float in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Probability that the AI deliberately plays a weaker move at a given skill level
+///
+/// Skill 10 never blunders; each level below adds roughly 7%, up to 60% at skill 1.
+pub fn blunder_probability(skill: u8) -> f64 {
+    let skill = skill.clamp(1, MAX_SKILL);
+    (MAX_SKILL - skill) as f64 / 15.0
+}
+
+/// Pick an index into a best-first list of `candidates` moves
+///
+/// Usually 0 (the best move); with probability [`blunder_probability`] the
+/// 2nd or 3rd best move is chosen instead, when available.
+pub fn pick_move_index(skill: u8, candidates: usize, rng: &mut SkillRng) -> usize {
+    if candidates <= 1 {
+        return 0;
+    }
+
+    if rng.next_f64() >= blunder_probability(skill) {
+        return 0;
+    }
+
+    let weaker = (candidates - 1).min(2);
+    1 + (rng.next_u64() % weaker as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blunder_probability_scales_with_skill() {
+        assert_eq!(blunder_probability(10), 0.0);
+        assert!((blunder_probability(1) - 0.6).abs() < 1e-9);
+        assert!(blunder_probability(3) > blunder_probability(7));
+        // Out-of-range levels are clamped
+        assert_eq!(blunder_probability(0), blunder_probability(1));
+        assert_eq!(blunder_probability(42), 0.0);
+    }
+
+    #[test]
+    fn test_full_skill_always_picks_best() {
+        let mut rng = SkillRng::new(7);
+        for _ in 0..1000 {
+            assert_eq!(pick_move_index(MAX_SKILL, 20, &mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn test_pick_is_seeded_deterministic() {
+        let picks = |seed| {
+            let mut rng = SkillRng::new(seed);
+            (0..50)
+                .map(|_| pick_move_index(2, 20, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(picks(1234), picks(1234));
+        assert_ne!(picks(1234), picks(4321));
+    }
+
+    #[test]
+    fn test_low_skill_blunder_rate_and_range() {
+        let mut rng = SkillRng::new(99);
+        let trials = 10_000;
+        let mut blunders = 0;
+
+        for _ in 0..trials {
+            let idx = pick_move_index(1, 20, &mut rng);
+            assert!(idx <= 2);
+            if idx > 0 {
+                blunders += 1;
+            }
+        }
+
+        let rate = blunders as f64 / trials as f64;
+        assert!((rate - 0.6).abs() < 0.03, "blunder rate {}", rate);
+    }
+
+    #[test]
+    fn test_weaker_move_limited_by_candidates() {
+        let mut rng = SkillRng::new(5);
+        for _ in 0..200 {
+            assert!(pick_move_index(1, 2, &mut rng) <= 1);
+            assert_eq!(pick_move_index(1, 1, &mut rng), 0);
+        }
+    }
+}
@@ -0,0 +1,659 @@
+use super::engine::ChessAI;
+use super::progress::ProgressReporter;
+use super::stats::SearchStats;
+use super::tablebase::RootProbe;
+use super::tt::{pack_move, position_hash, score_from_tt, score_to_tt, Bound, TranspositionTable, TtEntry, DEFAULT_TT_ENTRIES};
+use crate::chess::{Board, CheckCount, Color, Move};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Deepest iteration a helper thread will start
+const MAX_DEPTH: u8 = 64;
+
+/// Nodes a worker searches between checks of the node and time limits
+///
+/// Nodes are expensive here (full legal move generation plus a mobility
+/// evaluation), so this is kept small to stop close to the deadline.
+const LIMIT_CHECK_INTERVAL: u64 = 64;
+
+/// Outcome of a (possibly multi-threaded) search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Move,
+    pub score: i32,
+    /// Depth of the iteration the move came from
+    pub depth: u8,
+    /// Nodes searched by all threads together
+    pub nodes: u64,
+    pub elapsed: Duration,
+}
+
+impl SearchResult {
+    /// Nodes per second across all threads
+    pub fn nps(&self) -> u64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            (self.nodes as f64 / secs) as u64
+        } else {
+            self.nodes
+        }
+    }
+}
+
+/// State shared by every search thread
+struct Shared {
+    tt: TranspositionTable,
+    stop: AtomicBool,
+    nodes: AtomicU64,
+    node_limit: Option<u64>,
+    deadline: Option<Instant>,
+}
+
+/// Last iteration a worker finished
+#[derive(Debug, Clone, Copy)]
+struct Completed {
+    depth: u8,
+    best_move: Move,
+    score: i32,
+}
+
+/// One Lazy SMP search thread
+///
+/// Every worker searches the whole tree from the root. They only cooperate
+/// through the shared transposition table; helpers start at staggered depths
+/// and order quiet moves differently so they fill in different parts of it.
+///
+/// With `STATS` false every statistics update is compiled out.
+struct Worker<'a, const STATS: bool> {
+    ai: &'a ChessAI,
+    shared: &'a Shared,
+    id: usize,
+    /// Nodes counted but not yet added to the shared total
+    unpublished: u64,
+    completed: Option<Completed>,
+    stats: SearchStats,
+    /// Only the main thread reports progress
+    reporter: Option<ProgressReporter<'a>>,
+    /// Iteration currently being searched
+    searching: u8,
+}
+
+impl<'a, const STATS: bool> Worker<'a, STATS> {
+    fn new(ai: &'a ChessAI, shared: &'a Shared, id: usize) -> Self {
+        Worker { ai, shared, id, unpublished: 0, completed: None, stats: SearchStats::default(), reporter: None, searching: 0 }
+    }
+
+    fn is_main(&self) -> bool {
+        self.id == 0
+    }
+
+    /// Whether the current iteration should be abandoned
+    ///
+    /// The main thread always finishes depth 1 so there is a move to play.
+    fn stopped(&self) -> bool {
+        if self.is_main() && self.completed.is_none() {
+            return false;
+        }
+        self.shared.stop.load(Ordering::Relaxed)
+    }
+
+    /// Count a node, publishing the count and enforcing limits periodically
+    fn count_node(&mut self) {
+        self.unpublished += 1;
+        if STATS {
+            self.stats.nodes += 1;
+        }
+        if self.unpublished >= LIMIT_CHECK_INTERVAL {
+            self.publish_nodes();
+            self.check_limits();
+            if self.reporter.as_ref().is_some_and(|reporter| reporter.due()) {
+                self.report(false);
+            }
+        }
+    }
+
+    /// Send the best move so far to the progress channel, if there is one
+    fn report(&mut self, completed: bool) {
+        let (Some(reporter), Some(best)) = (self.reporter.as_mut(), self.completed) else {
+            return;
+        };
+        let depth = if completed { best.depth } else { self.searching };
+        // Counted nodes are left unpublished so the limit checks happen exactly as without reporting
+        let nodes = self.shared.nodes.load(Ordering::Relaxed) + self.unpublished;
+        reporter.send(depth, best.best_move, best.score, nodes, completed);
+    }
+
+    fn publish_nodes(&mut self) {
+        self.shared.nodes.fetch_add(self.unpublished, Ordering::Relaxed);
+        self.unpublished = 0;
+    }
+
+    fn check_limits(&self) {
+        let shared = self.shared;
+        let out_of_nodes = shared.node_limit.is_some_and(|limit| shared.nodes.load(Ordering::Relaxed) >= limit);
+        let out_of_time = shared.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if out_of_nodes || out_of_time {
+            shared.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Iterative deepening until the depth is reached or the search is stopped
+    fn run(&mut self, board: &Board, color: Color, max_depth: u8) {
+        let first = if self.is_main() { 1 } else { 1 + (self.id % 2) as u8 };
+        let last = if self.is_main() { max_depth } else { MAX_DEPTH };
+
+        for depth in first..=last {
+            self.searching = depth;
+            match self.search_root(board, color, depth) {
+                Some((best_move, score)) => self.completed = Some(Completed { depth, best_move, score }),
+                None => break,
+            }
+            self.report(true);
+            if self.stopped() {
+                break;
+            }
+        }
+    }
+
+    fn search_root(&mut self, board: &Board, color: Color, depth: u8) -> Option<(Move, i32)> {
+        let checks = self.ai.start_checks();
+        let hash = position_hash(board, color, &checks);
+        let tt_move = self.shared.tt.probe(hash).and_then(|entry| entry.best);
+
+        let mut moves = self.ai.legal_moves(board, color);
+        order_moves(&mut moves, tt_move, self.id);
+
+        let mut best: Option<(Move, i32)> = None;
+        let mut alpha = -i32::MAX;
+        let beta = i32::MAX;
+
+        for mov in moves {
+            let mut new_board = board.clone();
+            new_board.make_move(&mov);
+            let child_checks = self.ai.checks_after(&new_board, color, checks);
+
+            let score = -self.negamax(&new_board, child_checks, depth - 1, 1, -beta, -alpha, color.opposite());
+            if self.stopped() {
+                return None;
+            }
+
+            let improves = match best {
+                Some((_, best_score)) => score > best_score,
+                None => true,
+            };
+            if improves {
+                best = Some((mov, score));
+            }
+            alpha = alpha.max(score);
+        }
+
+        let (best_move, score) = best?;
+        self.shared.tt.store(hash, TtEntry { score: score_to_tt(score, 0), depth, bound: Bound::Exact, best: Some(pack_move(&best_move)) });
+        Some((best_move, score))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(&mut self, board: &Board, checks: CheckCount, depth: u8, ply: i32, mut alpha: i32, beta: i32, color: Color) -> i32 {
+        self.count_node();
+        if self.stopped() {
+            return 0;
+        }
+
+        if let Some(score) = self.ai.objective_score(board, &checks, color, ply) {
+            return score;
+        }
+
+        if let Some(score) = self.ai.probe_wdl(board, color, ply) {
+            if STATS {
+                self.stats.tb_hits += 1;
+            }
+            return score;
+        }
+
+        if depth == 0 {
+            if STATS {
+                self.stats.qnodes += 1;
+            }
+            return self.ai.evaluate_position(board, &checks, color);
+        }
+
+        let hash = position_hash(board, color, &checks);
+        let mut tt_move = None;
+        let probe = self.shared.tt.probe(hash);
+        if STATS {
+            self.stats.tt_probes += 1;
+            self.stats.tt_hits += probe.is_some() as u64;
+        }
+        if let Some(entry) = probe {
+            tt_move = entry.best;
+            if entry.depth >= depth {
+                let score = score_from_tt(entry.score, ply);
+                let cutoff = match entry.bound {
+                    Bound::Exact => true,
+                    Bound::Lower => score >= beta,
+                    Bound::Upper => score <= alpha,
+                };
+                if cutoff {
+                    return score;
+                }
+            }
+        }
+
+        let mut moves = self.ai.legal_moves(board, color);
+        if moves.is_empty() {
+            return if board.is_in_check(color) { -100000 + ply } else { 0 };
+        }
+        order_moves(&mut moves, tt_move, self.id);
+
+        let original_alpha = alpha;
+        let mut max_score = i32::MIN;
+        let mut best_move = moves[0];
+
+        for (index, mov) in moves.into_iter().enumerate() {
+            let mut new_board = board.clone();
+            new_board.make_move(&mov);
+            let child_checks = self.ai.checks_after(&new_board, color, checks);
+
+            let score = -self.negamax(&new_board, child_checks, depth - 1, ply + 1, -beta, -alpha, color.opposite());
+            if self.stopped() {
+                return 0;
+            }
+
+            if score > max_score {
+                max_score = score;
+                best_move = mov;
+            }
+            alpha = alpha.max(score);
+
+            if alpha >= beta {
+                if STATS {
+                    self.stats.record_cutoff(index);
+                }
+                break;
+            }
+        }
+
+        let bound = if max_score >= beta {
+            Bound::Lower
+        } else if max_score <= original_alpha {
+            Bound::Upper
+        } else {
+            Bound::Exact
+        };
+        self.shared.tt.store(hash, TtEntry { score: score_to_tt(max_score, ply), depth, bound, best: Some(pack_move(&best_move)) });
+
+        max_score
+    }
+}
+
+/// Order moves: hash move, then captures (most valuable victim first), then quiet moves
+///
+/// Helper threads rotate the quiet moves by their id so each explores a
+/// slightly different tree.
+fn order_moves(moves: &mut [Move], tt_move: Option<u16>, jitter: usize) {
+    moves.sort_by_key(|mov| {
+        if Some(pack_move(mov)) == tt_move {
+            (0, 0)
+        } else if let Some(captured) = mov.captured {
+            (1, -captured.value())
+        } else {
+            (2, 0)
+        }
+    });
+
+    let quiet_start = moves.iter().position(|m| m.captured.is_none() && Some(pack_move(m)) != tt_move).unwrap_or(moves.len());
+    let quiet = &mut moves[quiet_start..];
+    if jitter > 0 && !quiet.is_empty() {
+        let len = quiet.len();
+        quiet.rotate_left(jitter % len);
+    }
+}
+
+/// Follow best moves through the table from `best_move`, checking each is legal
+fn principal_variation(ai: &ChessAI, tt: &TranspositionTable, board: &Board, color: Color, best_move: Move, depth: u8) -> Vec<Move> {
+    let mut pv = vec![best_move];
+    let mut board = board.clone();
+    board.make_move(&best_move);
+    let mut checks = ai.checks_after(&board, color, ai.start_checks());
+    let mut color = color.opposite();
+
+    while pv.len() < depth as usize {
+        let Some(packed) = tt.probe(position_hash(&board, color, &checks)).and_then(|entry| entry.best) else {
+            break;
+        };
+        let Some(mov) = ai.legal_moves(&board, color).into_iter().find(|m| pack_move(m) == packed) else {
+            break;
+        };
+
+        board.make_move(&mov);
+        checks = ai.checks_after(&board, color, checks);
+        color = color.opposite();
+        pv.push(mov);
+    }
+
+    pv
+}
+
+/// Run a Lazy SMP search with the AI's thread count and limits
+pub(super) fn search(ai: &ChessAI, board: &Board, color: Color) -> Option<SearchResult> {
+    run::<false>(ai, board, color).map(|(result, _)| result)
+}
+
+/// Like [`search`], also collecting [`SearchStats`] from every thread
+pub(super) fn search_with_stats(ai: &ChessAI, board: &Board, color: Color) -> Option<(SearchResult, SearchStats)> {
+    run::<true>(ai, board, color)
+}
+
+fn run<const STATS: bool>(ai: &ChessAI, board: &Board, color: Color) -> Option<(SearchResult, SearchStats)> {
+    if ai.legal_moves(board, color).is_empty() {
+        return None;
+    }
+
+    let start = Instant::now();
+    if let Some(probe) = ai.probe_root(board, color) {
+        return Some(tablebase_result(ai, probe, start));
+    }
+
+    let shared = Shared {
+        tt: TranspositionTable::new(DEFAULT_TT_ENTRIES),
+        stop: AtomicBool::new(false),
+        nodes: AtomicU64::new(0),
+        node_limit: ai.node_limit(),
+        deadline: ai.time_limit().map(|limit| start + limit),
+    };
+    let max_depth = ai.depth().max(1);
+
+    let mut stats = SearchStats::default();
+    let results: Vec<Option<Completed>> = thread::scope(|scope| {
+        let helpers: Vec<_> = (1..ai.threads())
+            .map(|id| {
+                let shared = &shared;
+                scope.spawn(move || {
+                    let mut worker = Worker::<STATS>::new(ai, shared, id);
+                    worker.run(board, color, max_depth);
+                    worker.publish_nodes();
+                    (worker.completed, worker.stats)
+                })
+            })
+            .collect();
+
+        let mut main = Worker::<STATS>::new(ai, &shared, 0);
+        main.reporter = ai.progress().map(|sender| ProgressReporter::new(sender, start));
+        main.run(board, color, max_depth);
+        main.publish_nodes();
+
+        // The main thread decides when the search is over
+        shared.stop.store(true, Ordering::Relaxed);
+
+        stats.merge(&main.stats);
+        let mut results = vec![main.completed];
+        for helper in helpers {
+            let (completed, helper_stats) = helper.join().expect("search thread panicked");
+            stats.merge(&helper_stats);
+            results.push(completed);
+        }
+        results
+    });
+
+    // Deepest finished iteration wins; ties go to the main thread
+    let best = results.into_iter().flatten().fold(None::<Completed>, |best, c| match best {
+        Some(b) if b.depth >= c.depth => Some(b),
+        _ => Some(c),
+    })?;
+
+    let result = SearchResult {
+        best_move: best.best_move,
+        score: best.score,
+        depth: best.depth,
+        nodes: shared.nodes.load(Ordering::Relaxed),
+        elapsed: start.elapsed(),
+    };
+    if STATS {
+        stats.depth = result.depth;
+        stats.elapsed = result.elapsed;
+        stats.pv = principal_variation(ai, &shared.tt, board, color, result.best_move, result.depth);
+    }
+
+    Some((result, stats))
+}
+
+/// Result for a root position answered by the tablebase, without searching
+fn tablebase_result(ai: &ChessAI, probe: RootProbe, start: Instant) -> (SearchResult, SearchStats) {
+    let result = SearchResult { best_move: probe.best_move, score: probe.score(), depth: 0, nodes: 0, elapsed: start.elapsed() };
+    if let Some(sender) = ai.progress() {
+        ProgressReporter::new(sender, start).send(0, result.best_move, result.score, 0, true);
+    }
+
+    let stats = SearchStats { tb_hits: 1, pv: vec![result.best_move], elapsed: result.elapsed, ..SearchStats::default() };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{Piece, PieceType, Position};
+
+    fn position(pieces: &[(&str, PieceType, Color)]) -> Board {
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position::new(row, col).unwrap(), None);
+            }
+        }
+        board.white_can_castle_kingside = false;
+        board.white_can_castle_queenside = false;
+        board.black_can_castle_kingside = false;
+        board.black_can_castle_queenside = false;
+
+        for &(square, piece_type, color) in pieces {
+            board.set_piece(Position::from_algebraic(square).unwrap(), Some(Piece::new(piece_type, color)));
+        }
+        board
+    }
+
+    /// White can win Black's undefended queen with the knight
+    fn hanging_queen() -> Board {
+        position(&[
+            ("g1", PieceType::King, Color::White),
+            ("f3", PieceType::Knight, Color::White),
+            ("a2", PieceType::Pawn, Color::White),
+            ("g8", PieceType::King, Color::Black),
+            ("d4", PieceType::Queen, Color::Black),
+            ("a7", PieceType::Pawn, Color::Black),
+        ])
+    }
+
+    fn takes_queen(mov: &Move) -> bool {
+        mov.captured.map(|p| p.piece_type) == Some(PieceType::Queen)
+    }
+
+    #[test]
+    fn test_single_thread_matches_minimax() {
+        let board = Board::new();
+        let ai = ChessAI::new(2);
+        let result = ai.search(&board, Color::White).unwrap();
+
+        assert_eq!(result.depth, 2);
+        assert_eq!(result.score, ai.rank_moves(&board, Color::White)[0].1);
+        assert!(result.nodes > 0);
+    }
+
+    #[test]
+    fn test_parallel_move_is_legal() {
+        let board = Board::new();
+        let result = ChessAI::new(3).with_threads(4).search(&board, Color::White).unwrap();
+
+        assert!(board.generate_legal_moves(Color::White).contains(&result.best_move));
+        assert_eq!(result.depth, 3);
+    }
+
+    #[test]
+    fn test_no_legal_moves() {
+        // Black is checkmated
+        let board = position(&[
+            ("a1", PieceType::King, Color::White),
+            ("g7", PieceType::Queen, Color::White),
+            ("f6", PieceType::King, Color::White),
+            ("h8", PieceType::King, Color::Black),
+        ]);
+        assert_eq!(ChessAI::new(3).with_threads(2).search(&board, Color::Black), None);
+    }
+
+    #[test]
+    fn test_fixed_nodes_single_thread_is_deterministic() {
+        let board = Board::new();
+        let ai = ChessAI::new(MAX_DEPTH).with_node_limit(3_000);
+
+        let first = ai.search(&board, Color::White).unwrap();
+        let second = ai.search(&board, Color::White).unwrap();
+        assert_eq!((first.best_move, first.score, first.depth), (second.best_move, second.score, second.depth));
+        assert!(first.nodes >= 3_000);
+        assert!(first.nodes < 3_000 + 2 * LIMIT_CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn test_fixed_nodes_parallel_is_legal_and_stable() {
+        let board = hanging_queen();
+        let legal = board.generate_legal_moves(Color::White);
+
+        for _ in 0..3 {
+            let result = ChessAI::new(MAX_DEPTH).with_threads(4).with_node_limit(3_000).search(&board, Color::White).unwrap();
+            assert!(legal.contains(&result.best_move));
+            assert!(takes_queen(&result.best_move), "played {}", result.best_move.to_algebraic());
+        }
+    }
+
+    #[test]
+    fn test_tiny_node_limit_still_returns_a_move() {
+        let board = Board::new();
+        let result = ChessAI::new(MAX_DEPTH).with_threads(3).with_node_limit(1).search(&board, Color::White).unwrap();
+        assert!(board.generate_legal_moves(Color::White).contains(&result.best_move));
+        assert!(result.depth >= 1);
+    }
+
+    #[test]
+    fn test_time_limit_stops_search() {
+        let board = Board::new();
+        let limit = Duration::from_millis(200);
+        let result = ChessAI::new(MAX_DEPTH).with_threads(2).with_time_limit(limit).search(&board, Color::White).unwrap();
+
+        assert!(result.elapsed < limit + Duration::from_secs(1));
+        assert!(result.depth < MAX_DEPTH);
+    }
+
+    #[test]
+    fn test_find_best_move_uses_parallel_search() {
+        let board = hanging_queen();
+        let mov = ChessAI::new(2).with_threads(3).find_best_move(&board, Color::White).unwrap();
+        assert!(takes_queen(&mov));
+    }
+
+    #[test]
+    fn test_progress_reported_for_every_iteration() {
+        let board = hanging_queen();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let result = ChessAI::new(4).with_threads(2).with_progress(sender).search(&board, Color::White).unwrap();
+
+        let reports: Vec<_> = receiver.try_iter().collect();
+        let completed: Vec<_> = reports.iter().filter(|p| p.completed).collect();
+        assert_eq!(completed.iter().map(|p| p.depth).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(completed.windows(2).all(|w| w[0].nodes <= w[1].nodes));
+
+        let last = completed.last().unwrap();
+        assert_eq!((last.best_move, last.score), (result.best_move, result.score));
+        assert!(reports.iter().all(|p| p.nodes <= result.nodes));
+    }
+
+    #[test]
+    fn test_progress_does_not_change_search() {
+        let board = Board::new();
+        let ai = ChessAI::new(MAX_DEPTH).with_node_limit(3_000);
+        let (sender, _receiver) = std::sync::mpsc::channel();
+        let watched = ChessAI::new(MAX_DEPTH).with_node_limit(3_000).with_progress(sender);
+
+        let plain = ai.search(&board, Color::White).unwrap();
+        let reported = watched.search(&board, Color::White).unwrap();
+        assert_eq!((plain.best_move, plain.score, plain.depth), (reported.best_move, reported.score, reported.depth));
+    }
+
+    /// Replay `pv` from `board`, checking every move is legal
+    fn assert_pv_legal(board: &Board, color: Color, pv: &[Move]) {
+        let mut board = board.clone();
+        let mut color = color;
+        for mov in pv {
+            assert!(board.generate_legal_moves(color).contains(mov), "illegal pv move {}", mov.to_algebraic());
+            board.make_move(mov);
+            color = color.opposite();
+        }
+    }
+
+    fn assert_stats_consistent(board: &Board, color: Color, result: &SearchResult, stats: &SearchStats) {
+        assert_eq!(stats.nodes, result.nodes);
+        assert_eq!(stats.depth, result.depth);
+        assert!(stats.total_cutoffs() <= stats.nodes);
+        assert!(stats.qnodes <= stats.nodes);
+        assert!(stats.tt_hits <= stats.tt_probes);
+        assert!(stats.tt_probes <= stats.nodes);
+
+        assert!(!stats.pv.is_empty());
+        assert!(stats.pv.len() <= result.depth as usize);
+        assert_eq!(stats.pv[0], result.best_move);
+        assert_pv_legal(board, color, &stats.pv);
+    }
+
+    #[test]
+    fn test_stats_are_consistent() {
+        let board = Board::new();
+        let ai = ChessAI::new(3);
+        let (result, stats) = ai.search_with_stats(&board, Color::White).unwrap();
+
+        assert_stats_consistent(&board, Color::White, &result, &stats);
+        assert!(stats.qnodes > 0);
+        assert!(stats.total_cutoffs() > 0);
+        assert_eq!(stats.pv.len(), 3);
+
+        // Collecting stats does not change the search
+        assert_eq!(ai.search(&board, Color::White).map(|r| (r.best_move, r.score, r.nodes)), Some((result.best_move, result.score, result.nodes)));
+    }
+
+    #[test]
+    fn test_parallel_stats_are_consistent() {
+        let board = hanging_queen();
+        let (result, stats) =
+            ChessAI::new(MAX_DEPTH).with_threads(3).with_node_limit(3_000).search_with_stats(&board, Color::White).unwrap();
+
+        assert_stats_consistent(&board, Color::White, &result, &stats);
+        assert!(stats.tt_hits > 0);
+    }
+
+    #[test]
+    fn test_nodes_per_second_scale_with_threads() {
+        let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        if cores < 2 {
+            eprintln!("skipping thread scaling check: only {} core available", cores);
+            return;
+        }
+        let threads = cores.min(4);
+
+        let board = Board::new();
+        let run = |threads| {
+            ChessAI::new(MAX_DEPTH)
+                .with_threads(threads)
+                .with_time_limit(Duration::from_millis(800))
+                .search(&board, Color::White)
+                .unwrap()
+                .nps()
+        };
+
+        let single = run(1);
+        let parallel = run(threads);
+        assert!(
+            parallel as f64 > single as f64 * 1.3,
+            "{} threads: {} nps vs {} nps single-threaded",
+            threads,
+            parallel,
+            single
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::chess::Move;
+use std::fmt;
+use std::time::Duration;
+
+/// Move-ordering slots tracked for beta cutoffs: 1st, 2nd, 3rd and any later move
+pub const CUTOFF_SLOTS: usize = 4;
+
+/// Counters collected during a search for tuning
+///
+/// Only searches started with [`ChessAI::search_with_stats`](super::ChessAI::search_with_stats)
+/// fill these in; the plain search compiles the counting out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Depth of the iteration the move came from
+    pub depth: u8,
+    /// Nodes searched by all threads together
+    pub nodes: u64,
+    /// Nodes at the search horizon, where the position is evaluated statically
+    pub qnodes: u64,
+    /// Transposition table lookups
+    pub tt_probes: u64,
+    /// Lookups that found an entry for the position
+    pub tt_hits: u64,
+    /// Positions scored from the endgame tablebase instead of searched
+    pub tb_hits: u64,
+    /// Beta cutoffs, by the index of the move that caused them
+    pub cutoffs: [u64; CUTOFF_SLOTS],
+    /// Principal variation, starting with the move to play
+    pub pv: Vec<Move>,
+    pub elapsed: Duration,
+}
+
+impl SearchStats {
+    pub(super) fn record_cutoff(&mut self, move_index: usize) {
+        self.cutoffs[move_index.min(CUTOFF_SLOTS - 1)] += 1;
+    }
+
+    /// Add another thread's counters to these
+    pub(super) fn merge(&mut self, other: &SearchStats) {
+        self.nodes += other.nodes;
+        self.qnodes += other.qnodes;
+        self.tt_probes += other.tt_probes;
+        self.tt_hits += other.tt_hits;
+        self.tb_hits += other.tb_hits;
+        for (total, count) in self.cutoffs.iter_mut().zip(other.cutoffs) {
+            *total += count;
+        }
+    }
+
+    /// Total beta cutoffs
+    pub fn total_cutoffs(&self) -> u64 {
+        self.cutoffs.iter().sum()
+    }
+
+    /// Nodes per second across all threads
+    pub fn nps(&self) -> u64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            (self.nodes as f64 / secs) as u64
+        } else {
+            self.nodes
+        }
+    }
+
+    /// Fraction of table lookups that found an entry
+    pub fn tt_hit_rate(&self) -> f64 {
+        ratio(self.tt_hits, self.tt_probes)
+    }
+
+    /// Fraction of nodes that ended in a beta cutoff
+    pub fn cutoff_rate(&self) -> f64 {
+        ratio(self.total_cutoffs(), self.nodes)
+    }
+
+    /// Fraction of cutoffs caused by the move in each ordering slot
+    pub fn cutoff_share(&self) -> [f64; CUTOFF_SLOTS] {
+        let total = self.total_cutoffs();
+        self.cutoffs.map(|count| ratio(count, total))
+    }
+
+    /// Fraction of nodes searched at the horizon
+    pub fn qnode_share(&self) -> f64 {
+        ratio(self.qnodes, self.nodes)
+    }
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+impl fmt::Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "depth {}  nodes {}  nps {}  time {:.2}s",
+            self.depth,
+            self.nodes,
+            self.nps(),
+            self.elapsed.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "tt hits {:.1}% ({}/{})  qnodes {:.1}%  tb hits {}",
+            self.tt_hit_rate() * 100.0,
+            self.tt_hits,
+            self.tt_probes,
+            self.qnode_share() * 100.0,
+            self.tb_hits
+        )?;
+
+        write!(f, "beta cutoffs {:.1}% of nodes:", self.cutoff_rate() * 100.0)?;
+        let labels = ["1st", "2nd", "3rd", "later"];
+        for (label, share) in labels.iter().zip(self.cutoff_share()) {
+            write!(f, "  {} {:.1}%", label, share * 100.0)?;
+        }
+        writeln!(f)?;
+
+        let pv: Vec<String> = self.pv.iter().map(Move::to_algebraic).collect();
+        write!(f, "pv {}", pv.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_handle_empty_stats() {
+        let stats = SearchStats::default();
+        assert_eq!(stats.tt_hit_rate(), 0.0);
+        assert_eq!(stats.cutoff_rate(), 0.0);
+        assert_eq!(stats.cutoff_share(), [0.0; CUTOFF_SLOTS]);
+        assert_eq!(stats.qnode_share(), 0.0);
+    }
+
+    #[test]
+    fn test_merge_and_cutoff_slots() {
+        let mut stats = SearchStats { nodes: 10, qnodes: 4, tt_probes: 6, tt_hits: 3, ..SearchStats::default() };
+        stats.record_cutoff(0);
+        stats.record_cutoff(7);
+
+        let mut other = SearchStats { nodes: 10, ..SearchStats::default() };
+        other.record_cutoff(0);
+        other.record_cutoff(1);
+        stats.merge(&other);
+
+        assert_eq!(stats.nodes, 20);
+        assert_eq!(stats.cutoffs, [2, 1, 0, 1]);
+        assert_eq!(stats.cutoff_rate(), 0.2);
+        assert_eq!(stats.cutoff_share()[0], 0.5);
+        assert_eq!(stats.tt_hit_rate(), 0.5);
+        assert_eq!(stats.qnode_share(), 0.2);
+    }
+}
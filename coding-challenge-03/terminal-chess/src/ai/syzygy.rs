@@ -0,0 +1,187 @@
+use crate::chess::{Board, Color, PieceType, Position};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Extension of win/draw/loss tables
+pub const WDL_EXTENSION: &str = "rtbw";
+/// Extension of distance-to-zeroing tables
+pub const DTZ_EXTENSION: &str = "rtbz";
+
+const WDL_MAGIC: [u8; 4] = [0x71, 0xe8, 0x23, 0x5d];
+const DTZ_MAGIC: [u8; 4] = [0xd7, 0x66, 0x0c, 0xa5];
+
+/// Files present for one material signature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableFiles {
+    pub wdl: bool,
+    pub dtz: bool,
+}
+
+/// Syzygy table files found in a directory, by material such as `KQvK`
+///
+/// Files are recognised by name and checked for the Syzygy magic bytes;
+/// anything else in the directory is skipped. Reading the compressed table
+/// data is not part of this index, so it does not implement
+/// [`Tablebase`](super::Tablebase) itself.
+#[derive(Debug, Clone, Default)]
+pub struct SyzygyTables {
+    dir: PathBuf,
+    tables: BTreeMap<String, TableFiles>,
+}
+
+impl SyzygyTables {
+    /// Index the table files in `dir`
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let mut tables: BTreeMap<String, TableFiles> = BTreeMap::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let (Some(stem), Some(extension)) = (path.file_stem().and_then(|s| s.to_str()), path.extension()) else {
+                continue;
+            };
+            let magic = if extension == WDL_EXTENSION {
+                WDL_MAGIC
+            } else if extension == DTZ_EXTENSION {
+                DTZ_MAGIC
+            } else {
+                continue;
+            };
+            if material_pieces(stem).is_none() || !has_magic(&path, magic) {
+                continue;
+            }
+
+            let files = tables.entry(stem.to_string()).or_default();
+            if magic == WDL_MAGIC {
+                files.wdl = true;
+            } else {
+                files.dtz = true;
+            }
+        }
+
+        Ok(SyzygyTables { dir: dir.to_path_buf(), tables })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of material signatures with at least one file
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Most pieces, kings included, in any table with a WDL file
+    pub fn max_pieces(&self) -> usize {
+        self.tables
+            .iter()
+            .filter(|(_, files)| files.wdl)
+            .filter_map(|(material, _)| material_pieces(material))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Files available for the material on `board`, whichever side has it
+    pub fn files_for(&self, board: &Board) -> Option<TableFiles> {
+        let white = side_material(board, Color::White);
+        let black = side_material(board, Color::Black);
+        self.tables
+            .get(&format!("{}v{}", white, black))
+            .or_else(|| self.tables.get(&format!("{}v{}", black, white)))
+            .copied()
+    }
+}
+
+/// Pieces in a material signature such as `KRPvKR`, if it is one
+fn material_pieces(material: &str) -> Option<usize> {
+    let (white, black) = material.split_once('v')?;
+    let valid = |side: &str| side.starts_with('K') && side.chars().all(|c| "KQRBNP".contains(c));
+    (valid(white) && valid(black)).then(|| white.len() + black.len())
+}
+
+/// One side's pieces in Syzygy order, e.g. `KRP`
+fn side_material(board: &Board, color: Color) -> String {
+    let order = [
+        (PieceType::King, 'K'),
+        (PieceType::Queen, 'Q'),
+        (PieceType::Rook, 'R'),
+        (PieceType::Bishop, 'B'),
+        (PieceType::Knight, 'N'),
+        (PieceType::Pawn, 'P'),
+    ];
+    let mut material = String::new();
+    for (piece_type, letter) in order {
+        for row in 0..8 {
+            for col in 0..8 {
+                if board
+                    .get_piece(Position { row, col })
+                    .is_some_and(|p| p.color == color && p.piece_type == piece_type)
+                {
+                    material.push(letter);
+                }
+            }
+        }
+    }
+    material
+}
+
+fn has_magic(path: &Path, magic: [u8; 4]) -> bool {
+    let mut header = [0u8; 4];
+    File::open(path).and_then(|mut file| file.read_exact(&mut header)).is_ok() && header == magic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Piece;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("terminal-chess-syzygy-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_indexes_valid_tables_only() {
+        let dir = temp_dir("index");
+        fs::write(dir.join("KQvK.rtbw"), WDL_MAGIC).unwrap();
+        fs::write(dir.join("KQvK.rtbz"), DTZ_MAGIC).unwrap();
+        fs::write(dir.join("KRPvKR.rtbw"), WDL_MAGIC).unwrap();
+        // Wrong magic, not a material signature, not a table
+        fs::write(dir.join("KPvK.rtbw"), DTZ_MAGIC).unwrap();
+        fs::write(dir.join("notes.rtbw"), WDL_MAGIC).unwrap();
+        fs::write(dir.join("README.txt"), "tables").unwrap();
+
+        let tables = SyzygyTables::open(&dir).unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables.max_pieces(), 5);
+
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position { row, col }, None);
+            }
+        }
+        board.set_piece(Position::from_algebraic("e1").unwrap(), Some(Piece::new(PieceType::King, Color::White)));
+        board.set_piece(Position::from_algebraic("e8").unwrap(), Some(Piece::new(PieceType::King, Color::Black)));
+        board.set_piece(Position::from_algebraic("d8").unwrap(), Some(Piece::new(PieceType::Queen, Color::Black)));
+        assert_eq!(tables.files_for(&board), Some(TableFiles { wdl: true, dtz: true }));
+
+        board.set_piece(Position::from_algebraic("d8").unwrap(), Some(Piece::new(PieceType::Pawn, Color::Black)));
+        assert_eq!(tables.files_for(&board), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_directory_is_an_error() {
+        assert!(SyzygyTables::open("/nonexistent/syzygy").is_err());
+    }
+}
@@ -0,0 +1,447 @@
+use crate::chess::{Board, Color, Move, MoveType, PieceType, Position};
+
+/// Score for a tablebase win, less the ply it is reached at
+///
+/// Well above any material evaluation but below the mate scores, so a mate
+/// the search can see is still preferred.
+pub const TB_WIN_SCORE: i32 = 20000;
+
+/// Win/draw/loss for the side to move, as stored in Syzygy WDL tables
+///
+/// Cursed wins and blessed losses are wins and losses that the 50-move rule
+/// turns into draws. Ordered from worst to best for the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Wdl {
+    Loss,
+    BlessedLoss,
+    Draw,
+    CursedWin,
+    Win,
+}
+
+impl Wdl {
+    /// The same result seen from the other side
+    pub fn opposite(self) -> Wdl {
+        match self {
+            Wdl::Loss => Wdl::Win,
+            Wdl::BlessedLoss => Wdl::CursedWin,
+            Wdl::Draw => Wdl::Draw,
+            Wdl::CursedWin => Wdl::BlessedLoss,
+            Wdl::Win => Wdl::Loss,
+        }
+    }
+
+    /// Search score for this result, reached `ply` plies from the root
+    pub fn score(self, ply: i32) -> i32 {
+        match self {
+            Wdl::Win => TB_WIN_SCORE - ply,
+            Wdl::Loss => -(TB_WIN_SCORE - ply),
+            // Drawn under the 50-move rule
+            Wdl::CursedWin | Wdl::BlessedLoss | Wdl::Draw => 0,
+        }
+    }
+
+    fn is_win(self) -> bool {
+        matches!(self, Wdl::Win | Wdl::CursedWin)
+    }
+
+    fn is_loss(self) -> bool {
+        matches!(self, Wdl::Loss | Wdl::BlessedLoss)
+    }
+}
+
+/// Endgame tablebase the engine can consult instead of searching
+///
+/// Probes return `None` when the position is not covered (too many pieces,
+/// or the table for its material is missing); the engine then searches as usual.
+pub trait Tablebase: Send + Sync {
+    /// Most pieces, kings included, in any available table
+    fn max_pieces(&self) -> usize;
+
+    /// Win/draw/loss for `color` to move
+    fn probe_wdl(&self, board: &Board, color: Color) -> Option<Wdl>;
+
+    /// Distance to zeroing the 50-move counter, in plies, for `color` to move
+    ///
+    /// Positive when `color` wins, negative when it loses and 0 for draws.
+    fn probe_dtz(&self, board: &Board, color: Color) -> Option<i32>;
+}
+
+/// Move chosen from the tables at the root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootProbe {
+    pub best_move: Move,
+    /// Result after playing `best_move`, for the side that played it
+    pub wdl: Wdl,
+    /// Plies until the 50-move counter is reset (or mate) after `best_move`
+    pub distance: i32,
+}
+
+impl RootProbe {
+    /// Search score of the root position
+    pub fn score(&self) -> i32 {
+        self.wdl.score(self.distance)
+    }
+}
+
+/// Pieces on the board, kings included
+pub fn piece_count(board: &Board) -> usize {
+    (0..8)
+        .flat_map(|row| (0..8).map(move |col| Position { row, col }))
+        .filter(|&pos| board.get_piece(pos).is_some())
+        .count()
+}
+
+/// Whether `board` is small enough for the tables and can be looked up in them
+///
+/// Tables don't cover castling rights.
+pub fn in_tables(tablebase: &dyn Tablebase, board: &Board) -> bool {
+    let castling = board.white_can_castle_kingside
+        || board.white_can_castle_queenside
+        || board.black_can_castle_kingside
+        || board.black_can_castle_queenside;
+    !castling && piece_count(board) <= tablebase.max_pieces()
+}
+
+/// Pick a move among `moves` using DTZ, as a tablebase-aware engine does at the root
+///
+/// The best result wins; among wins the move that reaches a zeroing move (or
+/// mate) soonest, among losses the one that holds out longest. Returns `None`
+/// if any move leads to a position the tables can't answer.
+pub fn probe_root(tablebase: &dyn Tablebase, board: &Board, color: Color, moves: &[Move]) -> Option<RootProbe> {
+    if moves.is_empty() || !in_tables(tablebase, board) {
+        return None;
+    }
+
+    let mut best: Option<(RootProbe, (Wdl, i32))> = None;
+    for &mov in moves {
+        let mut child = board.clone();
+        child.make_move(&mov);
+        let opponent = color.opposite();
+
+        let (wdl, distance) = if child.generate_legal_moves(opponent).is_empty() {
+            if child.is_in_check(opponent) {
+                (Wdl::Win, 1)
+            } else {
+                (Wdl::Draw, 0)
+            }
+        } else {
+            let wdl = tablebase.probe_wdl(&child, opponent)?.opposite();
+            let distance = if wdl == Wdl::Draw {
+                0
+            } else if is_zeroing(&mov) {
+                1
+            } else {
+                1 + tablebase.probe_dtz(&child, opponent)?.abs()
+            };
+            (wdl, distance)
+        };
+
+        // Quick wins and slow losses sort highest
+        let tiebreak = if wdl.is_win() {
+            -distance
+        } else if wdl.is_loss() {
+            distance
+        } else {
+            0
+        };
+        let key = (wdl, tiebreak);
+        if best.as_ref().is_none_or(|(_, best_key)| key > *best_key) {
+            best = Some((RootProbe { best_move: mov, wdl, distance }, key));
+        }
+    }
+
+    best.map(|(probe, _)| probe)
+}
+
+/// Captures and pawn moves reset the 50-move counter
+fn is_zeroing(mov: &Move) -> bool {
+    mov.captured.is_some() || mov.piece.piece_type == PieceType::Pawn || mov.move_type == MoveType::EnPassant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChessAI;
+    use crate::chess::Piece;
+    use std::sync::Arc;
+
+    /// Tablebase answering from a function of the position
+    struct MockTablebase<F> {
+        max_pieces: usize,
+        probe: F,
+    }
+
+    impl<F> Tablebase for MockTablebase<F>
+    where
+        F: Fn(&Board, Color) -> Option<(Wdl, i32)> + Send + Sync,
+    {
+        fn max_pieces(&self) -> usize {
+            self.max_pieces
+        }
+
+        fn probe_wdl(&self, board: &Board, color: Color) -> Option<Wdl> {
+            (self.probe)(board, color).map(|(wdl, _)| wdl)
+        }
+
+        fn probe_dtz(&self, board: &Board, color: Color) -> Option<i32> {
+            (self.probe)(board, color).map(|(_, dtz)| dtz)
+        }
+    }
+
+    fn position(pieces: &[(&str, PieceType, Color)]) -> Board {
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position::new(row, col).unwrap(), None);
+            }
+        }
+        board.white_can_castle_kingside = false;
+        board.white_can_castle_queenside = false;
+        board.black_can_castle_kingside = false;
+        board.black_can_castle_queenside = false;
+
+        for &(square, piece_type, color) in pieces {
+            board.set_piece(Position::from_algebraic(square).unwrap(), Some(Piece::new(piece_type, color)));
+        }
+        board
+    }
+
+    fn pieces(board: &Board) -> Vec<(Position, Piece)> {
+        (0..8)
+            .flat_map(|row| (0..8).map(move |col| Position { row, col }))
+            .filter_map(|pos| board.get_piece(pos).map(|piece| (pos, piece)))
+            .collect()
+    }
+
+    fn distance(a: Position, b: Position) -> i32 {
+        (a.row - b.row).abs().max((a.col - b.col).abs()) as i32
+    }
+
+    /// Result already settled for `color` to move: no legal moves, or the lone king takes the last piece
+    fn decided(board: &Board, color: Color) -> Option<(Wdl, i32)> {
+        let moves = board.generate_legal_moves(color);
+        if moves.is_empty() {
+            return Some(if board.is_in_check(color) { (Wdl::Loss, 0) } else { (Wdl::Draw, 0) });
+        }
+        // The lone king takes the last piece
+        if moves.iter().any(|m| m.captured.is_some()) {
+            return Some((Wdl::Draw, 0));
+        }
+        None
+    }
+
+    /// Stand-in for the KQvK (and bare kings) tables, with a made-up DTZ that
+    /// shrinks as the lone king is driven to the edge
+    fn kqk_tables() -> Arc<dyn Tablebase> {
+        Arc::new(MockTablebase {
+            max_pieces: 3,
+            probe: |board: &Board, color: Color| {
+                let pieces = pieces(board);
+                if pieces.len() == 2 {
+                    return Some((Wdl::Draw, 0));
+                }
+                let (_, queen) = pieces.iter().find(|(_, p)| p.piece_type != PieceType::King)?;
+                if pieces.len() != 3 || queen.piece_type != PieceType::Queen {
+                    return None;
+                }
+                if let Some(result) = decided(board, color) {
+                    return Some(result);
+                }
+
+                let lone = board.find_king(queen.color.opposite())?;
+                let edge = lone.row.min(7 - lone.row).min(lone.col).min(7 - lone.col) as i32;
+                let dtz = 2 * (distance(lone, board.find_king(queen.color)?) + edge) + 1;
+                Some(if color == queen.color { (Wdl::Win, dtz) } else { (Wdl::Loss, -dtz) })
+            },
+        })
+    }
+
+    /// Stand-in for the KPvK table: a rook pawn is a draw once the defending
+    /// king is in front of it, anything else wins
+    fn kpk_tables() -> Arc<dyn Tablebase> {
+        Arc::new(MockTablebase {
+            max_pieces: 3,
+            probe: |board: &Board, color: Color| {
+                let pieces = pieces(board);
+                let &(square, pawn) = pieces.iter().find(|(_, p)| p.piece_type == PieceType::Pawn)?;
+                if pieces.len() != 3 {
+                    return None;
+                }
+                if let Some(result) = decided(board, color) {
+                    return Some(result);
+                }
+
+                let defender = board.find_king(pawn.color.opposite())?;
+                let ahead = if pawn.color == Color::White { defender.row > square.row } else { defender.row < square.row };
+                let rook_pawn = square.col == 0 || square.col == 7;
+                if rook_pawn && ahead && (defender.col - square.col).abs() <= 1 {
+                    return Some((Wdl::Draw, 0));
+                }
+                let dtz = 2 * distance(square, defender) + 1;
+                Some(if color == pawn.color { (Wdl::Win, dtz) } else { (Wdl::Loss, -dtz) })
+            },
+        })
+    }
+
+    fn is_checkmate(board: &Board, mov: &Move, mated: Color) -> bool {
+        let mut after = board.clone();
+        after.make_move(mov);
+        after.is_in_check(mated) && after.generate_legal_moves(mated).is_empty()
+    }
+
+    #[test]
+    fn test_engine_converts_kq_vs_k_instantly() {
+        let board = position(&[
+            ("g6", PieceType::King, Color::White),
+            ("a7", PieceType::Queen, Color::White),
+            ("h8", PieceType::King, Color::Black),
+        ]);
+
+        // A depth no search could finish: the move has to come from the tables
+        let ai = ChessAI::new(30).with_tablebase(kqk_tables());
+        let mov = ai.find_best_move(&board, Color::White).unwrap();
+        assert!(is_checkmate(&board, &mov, Color::Black));
+
+        let (result, stats) = ai.with_node_limit(1_000_000).search_with_stats(&board, Color::White).unwrap();
+        assert!(is_checkmate(&board, &result.best_move, Color::Black));
+        assert_eq!(result.nodes, 0);
+        assert_eq!(result.score, TB_WIN_SCORE - 1);
+        assert_eq!(stats.tb_hits, 1);
+        assert_eq!(stats.pv, vec![result.best_move]);
+    }
+
+    #[test]
+    fn test_engine_keeps_the_queen_in_kq_vs_k() {
+        // The black king attacks the queen; every move must keep the win
+        let board = position(&[
+            ("a1", PieceType::King, Color::White),
+            ("d7", PieceType::Queen, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        let tables = kqk_tables();
+        let ai = ChessAI::new(30).with_tablebase(tables.clone());
+
+        let mut rng = crate::ai::SkillRng::new(7);
+        let mov = ai.choose_move(&board, Color::White, crate::chess::MAX_SKILL, &mut rng).unwrap();
+        let mut after = board.clone();
+        after.make_move(&mov);
+        assert_eq!(tables.probe_wdl(&after, Color::Black), Some(Wdl::Loss));
+    }
+
+    #[test]
+    fn test_kp_vs_k_fortress_is_a_draw() {
+        let board = position(&[
+            ("c5", PieceType::King, Color::White),
+            ("a5", PieceType::Pawn, Color::White),
+            ("a8", PieceType::King, Color::Black),
+        ]);
+        let ai = ChessAI::new(3).with_tablebase(kpk_tables());
+
+        let probe = ai.probe_root(&board, Color::White).unwrap();
+        assert_eq!(probe.wdl, Wdl::Draw);
+        assert_eq!(ai.search(&board, Color::White).unwrap().score, 0);
+
+        // Without tables the extra pawn looks like a win
+        assert!(ChessAI::new(3).search(&board, Color::White).unwrap().score > 0);
+
+        // With the defending king cut off the same pawn wins
+        let cut_off = position(&[
+            ("c5", PieceType::King, Color::White),
+            ("a5", PieceType::Pawn, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        assert_eq!(ai.probe_root(&cut_off, Color::White).unwrap().wdl, Wdl::Win);
+    }
+
+    #[test]
+    fn test_search_probes_once_pieces_drop_to_table_limit() {
+        // Four pieces is beyond the tables; taking the rook gets into them
+        let board = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("d1", PieceType::Queen, Color::White),
+            ("e8", PieceType::King, Color::Black),
+            ("a4", PieceType::Rook, Color::Black),
+        ]);
+        let ai = ChessAI::new(2).with_tablebase(kqk_tables());
+        assert!(ai.probe_root(&board, Color::White).is_none());
+
+        let (result, stats) = ai.search_with_stats(&board, Color::White).unwrap();
+        assert_eq!(result.best_move.to, Position::from_algebraic("a4").unwrap());
+        assert_eq!(result.score, TB_WIN_SCORE - 1);
+        assert!(result.nodes > 0);
+        assert!(stats.tb_hits > 0);
+    }
+
+    #[test]
+    fn test_variants_ignore_tables() {
+        let board = position(&[
+            ("g6", PieceType::King, Color::White),
+            ("a7", PieceType::Queen, Color::White),
+            ("h8", PieceType::King, Color::Black),
+        ]);
+        let ai = ChessAI::with_variant(2, crate::chess::VariantKind::ThreeCheck, Default::default())
+            .with_tablebase(kqk_tables());
+        assert!(ai.probe_root(&board, Color::White).is_none());
+    }
+
+    #[test]
+    fn test_wdl_opposite_and_order() {
+        assert_eq!(Wdl::Win.opposite(), Wdl::Loss);
+        assert_eq!(Wdl::CursedWin.opposite(), Wdl::BlessedLoss);
+        assert_eq!(Wdl::Draw.opposite(), Wdl::Draw);
+        assert!(Wdl::Win > Wdl::CursedWin && Wdl::CursedWin > Wdl::Draw && Wdl::Draw > Wdl::BlessedLoss);
+        assert_eq!(Wdl::CursedWin.score(3), 0);
+        assert!(Wdl::Win.score(3) > Wdl::Win.score(5));
+    }
+
+    #[test]
+    fn test_in_tables_counts_pieces() {
+        let tablebase = MockTablebase { max_pieces: 3, probe: |_: &Board, _: Color| None };
+        let kpk = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("e2", PieceType::Pawn, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        assert_eq!(piece_count(&kpk), 3);
+        assert!(in_tables(&tablebase, &kpk));
+        assert!(!in_tables(&tablebase, &Board::new()));
+    }
+
+    #[test]
+    fn test_probe_root_prefers_shortest_win() {
+        // Every move wins; the tables say the king move to d2 gets there soonest
+        let board = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("h1", PieceType::Queen, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        let tablebase = MockTablebase {
+            max_pieces: 3,
+            probe: |board: &Board, _: Color| {
+                let king = board.find_king(Color::White)?;
+                let dtz = if king == Position::from_algebraic("d2").unwrap() { -3 } else { -9 };
+                Some((Wdl::Loss, dtz))
+            },
+        };
+
+        let moves = board.generate_legal_moves(Color::White);
+        let probe = probe_root(&tablebase, &board, Color::White, &moves).unwrap();
+        assert_eq!(probe.best_move.to, Position::from_algebraic("d2").unwrap());
+        assert_eq!(probe.wdl, Wdl::Win);
+        assert_eq!(probe.distance, 4);
+        assert_eq!(probe.score(), TB_WIN_SCORE - 4);
+    }
+
+    #[test]
+    fn test_probe_root_falls_back_when_tables_missing() {
+        let board = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("h1", PieceType::Queen, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        let tablebase = MockTablebase { max_pieces: 3, probe: |_: &Board, _: Color| None };
+        let moves = board.generate_legal_moves(Color::White);
+        assert_eq!(probe_root(&tablebase, &board, Color::White, &moves), None);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::chess::{Board, CheckCount, Color, Move, MoveType, PieceType, Position};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default number of transposition table entries (16 bytes each)
+pub const DEFAULT_TT_ENTRIES: usize = 1 << 18;
+
+/// Scores at least this far from zero are mate (or variant win) scores
+pub(super) const MATE_BOUND: i32 = 90000;
+
+/// How a stored score relates to the true value of the position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    /// The search failed high: true score >= stored score
+    Lower,
+    /// The search failed low: true score <= stored score
+    Upper,
+}
+
+/// A decoded transposition table entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtEntry {
+    pub score: i32,
+    pub depth: u8,
+    pub bound: Bound,
+    /// Best move found, packed by [`pack_move`]
+    pub best: Option<u16>,
+}
+
+/// One table slot. The key is stored xor'd with the data so a torn write
+/// (key from one store, data from another) fails validation instead of
+/// returning the wrong entry.
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Lock-free transposition table shared between search threads
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    mask: u64,
+}
+
+impl TranspositionTable {
+    /// Create a table with at least `entries` slots (rounded up to a power of two)
+    pub fn new(entries: usize) -> Self {
+        let size = entries.max(1).next_power_of_two();
+        TranspositionTable {
+            slots: (0..size).map(|_| Slot::default()).collect(),
+            mask: size as u64 - 1,
+        }
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<TtEntry> {
+        let slot = &self.slots[(hash & self.mask) as usize];
+        let data = slot.data.load(Ordering::Relaxed);
+        let key = slot.key.load(Ordering::Relaxed);
+        if key ^ data != hash {
+            return None;
+        }
+        decode(data)
+    }
+
+    pub fn store(&self, hash: u64, entry: TtEntry) {
+        let slot = &self.slots[(hash & self.mask) as usize];
+        let data = encode(entry);
+        slot.key.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+}
+
+// Data layout: score (32 bits) | depth (8) | bound (2, 0 = empty) | move (16)
+fn encode(entry: TtEntry) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 1u64,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+    let best = entry.best.map(|m| m as u64 | 1 << 15).unwrap_or(0);
+    (entry.score as u32 as u64) | (entry.depth as u64) << 32 | bound << 40 | best << 42
+}
+
+fn decode(data: u64) -> Option<TtEntry> {
+    let bound = match (data >> 40) & 0b11 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+    let best = (data >> 42) & 0xFFFF;
+    Some(TtEntry {
+        score: data as u32 as i32,
+        depth: (data >> 32) as u8,
+        bound,
+        best: (best & 1 << 15 != 0).then_some((best & 0x7FFF) as u16),
+    })
+}
+
+/// Make a mate score relative to the node at `ply` before storing it
+pub fn score_to_tt(score: i32, ply: i32) -> i32 {
+    if score >= MATE_BOUND {
+        score + ply
+    } else if score <= -MATE_BOUND {
+        score - ply
+    } else {
+        score
+    }
+}
+
+/// Turn a stored mate score back into one relative to the root
+pub fn score_from_tt(score: i32, ply: i32) -> i32 {
+    if score >= MATE_BOUND {
+        score - ply
+    } else if score <= -MATE_BOUND {
+        score + ply
+    } else {
+        score
+    }
+}
+
+fn square_index(pos: Position) -> u16 {
+    (pos.row as u16) * 8 + pos.col as u16
+}
+
+/// Pack a move's from/to squares and promotion piece into 15 bits
+pub fn pack_move(mov: &Move) -> u16 {
+    let promotion = match mov.move_type {
+        MoveType::Promotion(PieceType::Queen) => 1,
+        MoveType::Promotion(PieceType::Rook) => 2,
+        MoveType::Promotion(PieceType::Bishop) => 3,
+        MoveType::Promotion(PieceType::Knight) => 4,
+        _ => 0,
+    };
+    square_index(mov.from) | square_index(mov.to) << 6 | promotion << 12
+}
+
+// Zobrist keys come from splitmix64 over a fixed index, so hashes are the
+// same in every run and on every thread without a shared key table.
+const SIDE_KEY: u64 = 768;
+const CASTLING_KEY: u64 = 769;
+const EN_PASSANT_KEY: u64 = 773;
+const CHECKS_KEY: u64 = 781;
+
+fn zobrist(index: u64) -> u64 {
+    let mut z = index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn piece_index(piece_type: PieceType, color: Color) -> u64 {
+    let kind = match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    };
+    match color {
+        Color::White => kind,
+        Color::Black => kind + 6,
+    }
+}
+
+/// Zobrist hash of a position with `color` to move
+///
+/// Includes castling rights, the en passant file and the variant check count,
+/// since all of them change the value of the position.
+pub fn position_hash(board: &Board, color: Color, checks: &CheckCount) -> u64 {
+    let mut hash = 0;
+
+    for row in 0..8 {
+        for col in 0..8 {
+            let pos = Position::new(row, col).unwrap();
+            if let Some(piece) = board.get_piece(pos) {
+                hash ^= zobrist(piece_index(piece.piece_type, piece.color) * 64 + square_index(pos) as u64);
+            }
+        }
+    }
+
+    if color == Color::Black {
+        hash ^= zobrist(SIDE_KEY);
+    }
+
+    let castling = [
+        board.white_can_castle_kingside,
+        board.white_can_castle_queenside,
+        board.black_can_castle_kingside,
+        board.black_can_castle_queenside,
+    ];
+    for (i, _) in castling.iter().enumerate().filter(|(_, &allowed)| allowed) {
+        hash ^= zobrist(CASTLING_KEY + i as u64);
+    }
+
+    if let Some(target) = board.en_passant_target {
+        hash ^= zobrist(EN_PASSANT_KEY + target.col as u64);
+    }
+
+    if checks.white > 0 || checks.black > 0 {
+        hash ^= zobrist(CHECKS_KEY + checks.white.min(7) as u64 * 8 + checks.black.min(7) as u64);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let tt = TranspositionTable::new(1024);
+        let entry = TtEntry { score: -1234, depth: 7, bound: Bound::Lower, best: Some(0x1ABC & 0x7FFF) };
+        tt.store(42, entry);
+
+        assert_eq!(tt.probe(42), Some(entry));
+        // Same slot, different key
+        assert_eq!(tt.probe(42 + 1024), None);
+        assert_eq!(tt.probe(7), None);
+    }
+
+    #[test]
+    fn test_empty_table_misses_zero_hash() {
+        let tt = TranspositionTable::new(16);
+        assert_eq!(tt.probe(0), None);
+    }
+
+    #[test]
+    fn test_mate_scores_are_ply_relative() {
+        // Mated 5 plies from the root, stored at ply 2, read back at ply 4
+        let stored = score_to_tt(-100000 + 5, 2);
+        assert_eq!(score_from_tt(stored, 4), -100000 + 7);
+        assert_eq!(score_from_tt(score_to_tt(250, 3), 9), 250);
+    }
+
+    #[test]
+    fn test_hash_distinguishes_side_and_checks() {
+        let board = Board::new();
+        let none = CheckCount::default();
+        let white = position_hash(&board, Color::White, &none);
+
+        assert_eq!(white, position_hash(&board.clone(), Color::White, &none));
+        assert_ne!(white, position_hash(&board, Color::Black, &none));
+        assert_ne!(white, position_hash(&board, Color::White, &CheckCount { white: 1, black: 0 }));
+    }
+
+    #[test]
+    fn test_hash_matches_transposition() {
+        let board = Board::new();
+        let play = |moves: &[(&str, &str)]| {
+            let mut board = board.clone();
+            for &(from, to) in moves {
+                let from = Position::from_algebraic(from).unwrap();
+                let to = Position::from_algebraic(to).unwrap();
+                let color = board.get_piece(from).unwrap().color;
+                let mov = board
+                    .generate_legal_moves(color)
+                    .into_iter()
+                    .find(|m| m.from == from && m.to == to)
+                    .unwrap();
+                board.make_move(&mov);
+            }
+            position_hash(&board, Color::White, &CheckCount::default())
+        };
+
+        let a = play(&[("g1", "f3"), ("g8", "f6"), ("b1", "c3"), ("b8", "c6")]);
+        let b = play(&[("b1", "c3"), ("b8", "c6"), ("g1", "f3"), ("g8", "f6")]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_concurrent_access_never_returns_torn_entries() {
+        // Every hash maps to one expected entry; any entry a reader sees must
+        // be the one written for that hash, however stores interleave.
+        let tt = Arc::new(TranspositionTable::new(64));
+        let expected = |hash: u64| TtEntry {
+            score: (hash % 20000) as i32 - 10000,
+            depth: (hash % 64) as u8,
+            bound: Bound::Exact,
+            best: Some((hash % 0x7FFF) as u16),
+        };
+
+        let handles: Vec<_> = (0..8u64)
+            .map(|t| {
+                let tt = Arc::clone(&tt);
+                thread::spawn(move || {
+                    for i in 0..20_000u64 {
+                        let hash = zobrist(t * 1_000_003 + i % 512);
+                        if i % 2 == 0 {
+                            tt.store(hash, expected(hash));
+                        } else if let Some(entry) = tt.probe(hash) {
+                            assert_eq!(entry, expected(hash));
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -229,6 +229,24 @@ impl Board {
         false
     }
 
+    /// Enemy pieces the piece on `from` currently attacks
+    pub fn attacked_pieces(&self, from: Position) -> Vec<(Position, Piece)> {
+        let Some(piece) = self.get_piece(from) else {
+            return Vec::new();
+        };
+
+        let mut attacked: Vec<(Position, Piece)> = self
+            .generate_pseudo_legal_moves(from, piece)
+            .into_iter()
+            .filter(|m| m.move_type != MoveType::EnPassant)
+            .filter_map(|m| m.captured.map(|captured| (m.to, captured)))
+            .collect();
+
+        // Capture-promotions list the same square once per promotion piece
+        attacked.dedup_by_key(|(pos, _)| *pos);
+        attacked
+    }
+
     /// Check if the given color is in check
     pub fn is_in_check(&self, color: Color) -> bool {
         if let Some(king_pos) = self.find_king(color) {
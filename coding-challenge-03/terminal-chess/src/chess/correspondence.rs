@@ -0,0 +1,767 @@
+use super::{parse_san, Color, Game, GameMode, GameState, Move, Position, VariantKind};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File extension for correspondence games
+pub const EXTENSION: &str = "chessgame";
+
+/// First line of the state sidecar; bumped if the format changes
+const SIDECAR_HEADER: &str = "% terminal-chess correspondence 1";
+
+/// Hex digits kept from each hash, short enough that mail clients don't wrap the lines
+const HASH_HEX_LEN: usize = 32;
+
+/// Why a correspondence file could not be read or a move could not be played
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorrError {
+    /// The file could not be read or written
+    Io(String),
+    /// Not a correspondence game file
+    Format(String),
+    /// The file was changed outside terminal-chess; `ply` is the first bad move, if known
+    Tampered { ply: Option<usize>, reason: String },
+    /// The token matches neither player's seat
+    NotAPlayer { white: String, black: String },
+    /// The token belongs to the player who is not on move
+    NotYourTurn { waiting_for: String },
+    /// The game has already ended
+    GameOver(String),
+    /// The move is not legal, or not valid SAN
+    InvalidMove(String),
+    /// Player names and tokens must be usable in the file
+    InvalidInput(String),
+}
+
+impl fmt::Display for CorrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorrError::Io(message) | CorrError::Format(message) => write!(f, "{}", message),
+            CorrError::Tampered { ply: Some(ply), reason } => {
+                write!(f, "The game file has been tampered with at ply {}: {}", ply, reason)
+            }
+            CorrError::Tampered { ply: None, reason } => write!(f, "The game file has been tampered with: {}", reason),
+            CorrError::NotAPlayer { white, black } => write!(
+                f,
+                "Your token matches neither {} (White) nor {} (Black); if you are one of them, the file has been altered",
+                white, black
+            ),
+            CorrError::NotYourTurn { waiting_for } => write!(f, "It's not your turn: waiting for {}", waiting_for),
+            CorrError::GameOver(result) => write!(f, "The game is over: {}", result),
+            CorrError::InvalidMove(message) => write!(f, "Invalid move: {}", message),
+            CorrError::InvalidInput(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CorrError {}
+
+/// A signed move in the record
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CorrMove {
+    san: String,
+    /// Unix time the move was played
+    time: u64,
+    /// HMAC of the move and everything before it, keyed by the mover's token
+    signature: String,
+}
+
+/// A game played by exchanging a `.chessgame` file
+///
+/// The file is a PGN game followed by a sidecar of `%` lines, which PGN
+/// readers skip. The sidecar is the signed record: players, clock, and each
+/// move with the time it was played and a signature. Every signature covers
+/// the one before it, so a player who checks their own signatures knows
+/// nothing up to their last move has been changed. A seat is claimed by the
+/// first token that moves for it (the creator's seat is claimed at once), and
+/// only a hash of each token is stored.
+#[derive(Debug, Clone)]
+pub struct CorrGame {
+    white: String,
+    black: String,
+    variant: VariantKind,
+    days_per_move: Option<u32>,
+    created: u64,
+    white_key: Option<String>,
+    black_key: Option<String>,
+    moves: Vec<CorrMove>,
+    /// The record replayed
+    game: Game,
+}
+
+impl CorrGame {
+    /// Start a game, with the creator's token claiming `creator`'s seat
+    pub fn new(
+        white: &str,
+        black: &str,
+        variant: VariantKind,
+        days_per_move: Option<u32>,
+        creator: Color,
+        token: &str,
+        now: u64,
+    ) -> Result<Self, CorrError> {
+        let white = check_name(white)?;
+        let black = check_name(black)?;
+        if white == black {
+            return Err(CorrError::InvalidInput("White and Black need different names".to_string()));
+        }
+        if days_per_move == Some(0) {
+            return Err(CorrError::InvalidInput("Days per move must be at least 1".to_string()));
+        }
+        let key = Some(token_key(check_token(token)?));
+
+        Ok(CorrGame {
+            white,
+            black,
+            variant,
+            days_per_move,
+            created: now,
+            white_key: if creator == Color::White { key.clone() } else { None },
+            black_key: if creator == Color::Black { key } else { None },
+            moves: Vec::new(),
+            game: Game::new(GameMode::PlayerVsPlayer, 0).with_variant(variant),
+        })
+    }
+
+    /// Read a correspondence file, replaying its moves
+    ///
+    /// Checks the file's structure, that every move is legal and that the PGN
+    /// matches the record. Signatures need a token; see [`CorrGame::verify`].
+    pub fn parse(text: &str) -> Result<Self, CorrError> {
+        let (pgn, sidecar): (Vec<&str>, Vec<&str>) = text.lines().map(str::trim).partition(|line| !line.starts_with('%'));
+
+        let mut lines = sidecar.into_iter();
+        if lines.next() != Some(SIDECAR_HEADER) {
+            return Err(CorrError::Format("Not a terminal-chess correspondence game".to_string()));
+        }
+
+        let mut fields = SidecarFields::default();
+        for line in lines {
+            fields.read(line)?;
+        }
+        let variant = fields.variant.unwrap_or_default();
+
+        let mut game = CorrGame {
+            white: fields.white.ok_or_else(|| missing("white"))?,
+            black: fields.black.ok_or_else(|| missing("black"))?,
+            variant,
+            days_per_move: fields.days_per_move,
+            created: fields.created.ok_or_else(|| missing("created"))?,
+            white_key: fields.white_key,
+            black_key: fields.black_key,
+            moves: Vec::new(),
+            game: Game::new(GameMode::PlayerVsPlayer, 0).with_variant(variant),
+        };
+
+        for (index, mov) in fields.moves.into_iter().enumerate() {
+            let ply = index + 1;
+            if game.game.is_game_over() {
+                return Err(CorrError::Tampered {
+                    ply: Some(ply),
+                    reason: "moves were added after the game ended".to_string(),
+                });
+            }
+            let legal = parse_san(&mov.san, &game.game.get_legal_moves()).map_err(|e| CorrError::Tampered {
+                ply: Some(ply),
+                reason: format!("'{}' is not playable here ({})", mov.san, e),
+            })?;
+            game.game.make_move(legal).map_err(|e| CorrError::Tampered { ply: Some(ply), reason: e })?;
+            game.moves.push(mov);
+        }
+
+        if collapse_whitespace(&pgn.join("\n")) != collapse_whitespace(&game.pgn()) {
+            return Err(CorrError::Tampered {
+                ply: None,
+                reason: "the PGN does not match the signed move record".to_string(),
+            });
+        }
+
+        Ok(game)
+    }
+
+    /// Load a correspondence file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CorrError> {
+        let text = fs::read_to_string(path).map_err(|e| CorrError::Io(format!("Failed to read file: {}", e)))?;
+        Self::parse(&text)
+    }
+
+    /// Write the game back to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CorrError> {
+        fs::write(path, self.to_file_string()).map_err(|e| CorrError::Io(format!("Failed to write file: {}", e)))
+    }
+
+    /// File contents: the PGN, then the signed sidecar
+    pub fn to_file_string(&self) -> String {
+        let mut text = self.pgn();
+        text.push('\n');
+        text.push_str(SIDECAR_HEADER);
+        text.push('\n');
+
+        let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        for (field, value) in [
+            ("white", self.white.clone()),
+            ("black", self.black.clone()),
+            ("variant", self.variant.rules().name().to_string()),
+            ("created", self.created.to_string()),
+            ("days-per-move", optional(&self.days_per_move.map(|days| days.to_string()))),
+            ("white-key", optional(&self.white_key)),
+            ("black-key", optional(&self.black_key)),
+        ] {
+            text.push_str(&format!("% {} {}\n", field, value));
+        }
+        for (index, mov) in self.moves.iter().enumerate() {
+            text.push_str(&format!("% move {} {} {} {}\n", index + 1, mov.san, mov.time, mov.signature));
+        }
+        text
+    }
+
+    /// Play `san` for the holder of `token`
+    ///
+    /// The token must belong to the player on move, or claim that player's
+    /// empty seat, and every move it signed earlier must still verify.
+    pub fn play(&mut self, san: &str, token: &str, now: u64) -> Result<Move, CorrError> {
+        let token = check_token(token)?;
+        let to_move = self.game.current_player;
+
+        match self.verify(token)? {
+            Some(seat) if seat != to_move => {
+                return Err(CorrError::NotYourTurn {
+                    waiting_for: self.player(to_move).to_string(),
+                })
+            }
+            Some(_) => {}
+            None if self.key(to_move).is_some() => {
+                return Err(if self.key(to_move.opposite()).is_some() {
+                    CorrError::NotAPlayer {
+                        white: self.white.clone(),
+                        black: self.black.clone(),
+                    }
+                } else {
+                    CorrError::NotYourTurn {
+                        waiting_for: self.player(to_move).to_string(),
+                    }
+                });
+            }
+            None => {}
+        }
+
+        if self.game.is_game_over() {
+            return Err(CorrError::GameOver(self.status()));
+        }
+
+        let mov = parse_san(san, &self.game.get_legal_moves()).map_err(|e| CorrError::InvalidMove(e.to_string()))?;
+        let san = self.game.to_san(&mov);
+        self.game.make_move(mov).map_err(CorrError::InvalidMove)?;
+
+        // Never earlier than the move before, so the clock can't run backwards
+        let time = now.max(self.last_move_time());
+        let ply = self.moves.len() + 1;
+        let signature = sign(token, &self.chain_hash(), ply, &san, time);
+        self.moves.push(CorrMove { san, time, signature });
+
+        let key = self.key_mut(to_move);
+        if key.is_none() {
+            *key = Some(token_key(token));
+        }
+        Ok(mov)
+    }
+
+    /// Check the signatures on every move made with `token`
+    ///
+    /// Returns the token's seat, or `None` if it has no seat yet.
+    pub fn verify(&self, token: &str) -> Result<Option<Color>, CorrError> {
+        let key = token_key(token);
+        let seat = [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| self.key(color) == Some(key.as_str()));
+
+        if let Some(seat) = seat {
+            let mut previous = self.header_hash();
+            for (index, mov) in self.moves.iter().enumerate() {
+                let ply = index + 1;
+                let mover = if ply % 2 == 1 { Color::White } else { Color::Black };
+                if mover == seat && sign(token, &previous, ply, &mov.san, mov.time) != mov.signature {
+                    return Err(CorrError::Tampered {
+                        ply: Some(ply),
+                        reason: format!("the record no longer matches what {} signed", self.player(seat)),
+                    });
+                }
+                previous = mov.signature.clone();
+            }
+        }
+        Ok(seat)
+    }
+
+    /// The game replayed from the record
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Name of the player with `color`
+    pub fn player(&self, color: Color) -> &str {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    /// Number of moves (plies) played
+    pub fn ply_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Suggested file name, e.g. `alice-vs-bob.chessgame`
+    pub fn default_filename(&self) -> String {
+        let slug = |name: &str| -> String {
+            name.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+                .collect()
+        };
+        format!("{}-vs-{}.{}", slug(&self.white), slug(&self.black), EXTENSION)
+    }
+
+    /// One-line game status, e.g. "Black (bob) to move" or "Checkmate, alice wins"
+    pub fn status(&self) -> String {
+        let named = |color: Color| format!("{} ({})", color_name(color), self.player(color));
+        match self.game.state {
+            GameState::Playing => format!("{} to move", named(self.game.current_player)),
+            GameState::Check => format!("{} to move, in check", named(self.game.current_player)),
+            GameState::Checkmate(winner) => format!("Checkmate, {} wins", named(winner)),
+            GameState::VariantWin(winner) => format!("{}, {} wins", self.variant.rules().name(), named(winner)),
+            GameState::Stalemate => "Stalemate, draw".to_string(),
+            GameState::Draw => "Draw by the 50-move rule".to_string(),
+            GameState::Resignation(winner) => format!("{} resigned, {} wins", named(winner.opposite()), named(winner)),
+            GameState::DrawAgreed => "Draw agreed".to_string(),
+        }
+    }
+
+    /// Time left for the player on move, if the game has a move limit
+    pub fn clock_status(&self, now: u64) -> Option<String> {
+        let days = self.days_per_move?;
+        if self.game.is_game_over() {
+            return None;
+        }
+
+        let deadline = self.last_move_time() + u64::from(days) * 86_400;
+        let name = self.player(self.game.current_player);
+        Some(if now <= deadline {
+            format!("{} has {} left ({} per move)", name, format_duration(deadline - now), plural_days(days))
+        } else {
+            format!("{} is {} over time ({} per move)", name, format_duration(now - deadline), plural_days(days))
+        })
+    }
+
+    /// The current position, move list and status as plain text
+    pub fn render(&self, now: u64) -> String {
+        let mut text = format!("  {} (White) vs {} (Black)", self.white, self.black);
+        if self.variant != VariantKind::Standard {
+            text.push_str(&format!(", {}", self.variant.rules().name()));
+        }
+        text.push_str("\n\n");
+
+        for row in (0..8).rev() {
+            text.push_str(&format!("  {} ", row + 1));
+            for col in 0..8 {
+                let square = Position::new(row, col).unwrap();
+                let symbol = self.game.board.get_piece(square).map_or('.', |piece| piece.to_unicode());
+                text.push_str(&format!(" {}", symbol));
+            }
+            text.push('\n');
+        }
+        text.push_str("     a b c d e f g h\n\n");
+
+        let movetext = self.movetext();
+        if !movetext.is_empty() {
+            text.push_str(&format!("  {}\n", movetext));
+        }
+        text.push_str(&format!("  {}\n", self.status()));
+        if let Some(status) = self.variant.rules().status(&self.game.checks) {
+            text.push_str(&format!("  {}\n", status));
+        }
+        if let Some(clock) = self.clock_status(now) {
+            text.push_str(&format!("  {}\n", clock));
+        }
+        text
+    }
+
+    /// Numbered SAN moves, e.g. "1. e4 e5 2. Nf3"
+    fn movetext(&self) -> String {
+        let mut tokens = Vec::new();
+        for (index, mov) in self.moves.iter().enumerate() {
+            if index % 2 == 0 {
+                tokens.push(format!("{}.", index / 2 + 1));
+            }
+            tokens.push(mov.san.clone());
+        }
+        tokens.join(" ")
+    }
+
+    fn pgn(&self) -> String {
+        self.game.to_pgn_between(&self.white, &self.black)
+    }
+
+    fn key(&self, color: Color) -> Option<&str> {
+        match color {
+            Color::White => self.white_key.as_deref(),
+            Color::Black => self.black_key.as_deref(),
+        }
+    }
+
+    fn key_mut(&mut self, color: Color) -> &mut Option<String> {
+        match color {
+            Color::White => &mut self.white_key,
+            Color::Black => &mut self.black_key,
+        }
+    }
+
+    fn last_move_time(&self) -> u64 {
+        self.moves.last().map_or(self.created, |mov| mov.time)
+    }
+
+    /// What the first signature chains from: the game's fixed settings
+    fn header_hash(&self) -> String {
+        let header = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            SIDECAR_HEADER,
+            self.white,
+            self.black,
+            self.variant.rules().name(),
+            self.created,
+            self.days_per_move.unwrap_or(0)
+        );
+        hex(&Sha256::digest(header.as_bytes()))
+    }
+
+    /// What the next signature chains from
+    fn chain_hash(&self) -> String {
+        self.moves.last().map_or_else(|| self.header_hash(), |mov| mov.signature.clone())
+    }
+}
+
+/// Sidecar values as read, before the game is replayed
+#[derive(Default)]
+struct SidecarFields {
+    white: Option<String>,
+    black: Option<String>,
+    variant: Option<VariantKind>,
+    created: Option<u64>,
+    days_per_move: Option<u32>,
+    white_key: Option<String>,
+    black_key: Option<String>,
+    moves: Vec<CorrMove>,
+}
+
+impl SidecarFields {
+    fn read(&mut self, line: &str) -> Result<(), CorrError> {
+        let line = line.trim_start_matches('%').trim();
+        let (field, value) = line.split_once(' ').unwrap_or((line, ""));
+        let value = value.trim();
+        let bad = || CorrError::Format(format!("Malformed sidecar line: % {}", line));
+        let optional = |value: &str| (value != "-").then(|| value.to_string());
+
+        match field {
+            "white" => self.white = Some(check_name(value)?),
+            "black" => self.black = Some(check_name(value)?),
+            "variant" => self.variant = Some(VariantKind::from_name(value).ok_or_else(bad)?),
+            "created" => self.created = Some(value.parse().map_err(|_| bad())?),
+            "days-per-move" => {
+                self.days_per_move = optional(value).map(|days| days.parse()).transpose().map_err(|_| bad())?
+            }
+            "white-key" => self.white_key = optional(value),
+            "black-key" => self.black_key = optional(value),
+            "move" => {
+                let parts: Vec<&str> = value.split_whitespace().collect();
+                let [ply, san, time, signature] = parts[..] else {
+                    return Err(bad());
+                };
+                if ply.parse::<usize>().ok() != Some(self.moves.len() + 1) {
+                    return Err(CorrError::Tampered {
+                        ply: Some(self.moves.len() + 1),
+                        reason: format!("moves are out of order (found ply {})", ply),
+                    });
+                }
+                self.moves.push(CorrMove {
+                    san: san.to_string(),
+                    time: time.parse().map_err(|_| bad())?,
+                    signature: signature.to_string(),
+                });
+            }
+            _ => return Err(bad()),
+        }
+        Ok(())
+    }
+}
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// A fresh random token for a player who doesn't have one
+pub fn generate_token() -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+    let local = 0u8;
+    let seed = format!("{}:{}:{:p}", nanos, std::process::id(), &local);
+    hex(&Sha256::digest(seed.as_bytes()))
+}
+
+fn check_name(name: &str) -> Result<String, CorrError> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['"', '\\', '\n', '\r']) {
+        return Err(CorrError::InvalidInput(format!(
+            "Invalid player name '{}': it must not be empty or contain quotes or backslashes",
+            name
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn check_token(token: &str) -> Result<&str, CorrError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(CorrError::InvalidInput("A player token must not be empty".to_string()));
+    }
+    Ok(token)
+}
+
+/// What is stored to recognise a token's seat
+fn token_key(token: &str) -> String {
+    let digest = Sha256::digest(format!("terminal-chess corr key\n{}", token).as_bytes());
+    hex(&digest)
+}
+
+fn sign(token: &str, previous: &str, ply: usize, san: &str, time: u64) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(token.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}\n{}\n{}\n{}", previous, ply, san, time).as_bytes());
+    hex(&mac.finalize().into_bytes())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut text: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    text.truncate(HASH_HEX_LEN);
+    text
+}
+
+fn missing(field: &str) -> CorrError {
+    CorrError::Format(format!("The sidecar has no '{}' line", field))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+fn plural_days(days: u32) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
+
+/// "2d 4h", "3h 5m" or "12m"
+fn format_duration(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3_600 % 24, seconds / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "alice-secret";
+    const BOB: &str = "bob-secret";
+    const START: u64 = 1_700_000_000;
+
+    fn new_game() -> CorrGame {
+        CorrGame::new("alice", "bob", VariantKind::Standard, Some(3), Color::White, ALICE, START).unwrap()
+    }
+
+    /// Play `moves` alternately as Alice and Bob, emailing the file after each one
+    fn exchange(mut file: String, moves: &[&str]) -> String {
+        for (index, san) in moves.iter().enumerate() {
+            let mut game = CorrGame::parse(&file).unwrap();
+            let token = if game.game().current_player == Color::White { ALICE } else { BOB };
+            game.play(san, token, START + 3_600 * (index as u64 + 1)).unwrap();
+            file = game.to_file_string();
+        }
+        file
+    }
+
+    #[test]
+    fn test_new_game_file_round_trips() {
+        let game = new_game();
+        let file = game.to_file_string();
+
+        assert!(file.starts_with("[Event \"Terminal Chess\"]\n"));
+        assert!(file.contains("[White \"alice\"]\n[Black \"bob\"]\n"));
+        assert!(file.contains("% days-per-move 3\n"));
+        assert!(file.contains("% black-key -\n"));
+        assert_eq!(game.default_filename(), "alice-vs-bob.chessgame");
+
+        let loaded = CorrGame::parse(&file).unwrap();
+        assert_eq!(loaded.to_file_string(), file);
+        assert_eq!(loaded.verify(ALICE), Ok(Some(Color::White)));
+        assert_eq!(loaded.verify(BOB), Ok(None));
+    }
+
+    #[test]
+    fn test_resumes_across_many_exchanges() {
+        // Scholar's mate, one email per move
+        let file = exchange(new_game().to_file_string(), &["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6"]);
+        let file = exchange(file, &["Qxf7#"]);
+
+        let mut game = CorrGame::parse(&file).unwrap();
+        assert_eq!(game.ply_count(), 7);
+        assert_eq!(game.game().state, GameState::Checkmate(Color::White));
+        assert!(file.contains("1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0"));
+        assert_eq!(game.verify(BOB), Ok(Some(Color::Black)));
+        assert!(matches!(game.play("Ke7", BOB, START), Err(CorrError::GameOver(_))));
+
+        // A longer game keeps verifying at every step
+        let moves = [
+            "d4", "d5", "c4", "e6", "Nc3", "Nf6", "Bg5", "Be7", "e3", "O-O", "Nf3", "Nbd7", "Rc1", "c6", "Bd3",
+            "dxc4", "Bxc4", "Nd5", "Bxe7", "Qxe7", "O-O", "Nxc3", "Rxc3", "e5",
+        ];
+        let file = exchange(new_game().to_file_string(), &moves);
+        let game = CorrGame::parse(&file).unwrap();
+        assert_eq!(game.ply_count(), moves.len());
+        assert_eq!(game.verify(ALICE), Ok(Some(Color::White)));
+        assert_eq!(game.verify(BOB), Ok(Some(Color::Black)));
+        assert!(file.contains("5. e3 O-O"));
+    }
+
+    #[test]
+    fn test_turn_enforcement() {
+        let mut game = new_game();
+
+        // Bob can't claim White's seat, which Alice holds
+        assert_eq!(
+            game.play("e4", BOB, START),
+            Err(CorrError::NotYourTurn { waiting_for: "alice".to_string() })
+        );
+        game.play("e4", ALICE, START).unwrap();
+
+        // Alice can't also take Black's empty seat
+        assert_eq!(
+            game.play("e5", ALICE, START),
+            Err(CorrError::NotYourTurn { waiting_for: "bob".to_string() })
+        );
+        game.play("e5", BOB, START).unwrap();
+        assert_eq!(
+            game.play("Nf6", BOB, START),
+            Err(CorrError::NotYourTurn { waiting_for: "alice".to_string() })
+        );
+
+        // Once both seats are taken, anyone else is turned away
+        assert!(matches!(game.play("Nf3", "mallory", START), Err(CorrError::NotAPlayer { .. })));
+
+        // Illegal moves are rejected without using up the turn
+        assert!(matches!(game.play("Ke3", ALICE, START), Err(CorrError::InvalidMove(_))));
+        game.play("Nf3", ALICE, START).unwrap();
+        assert_eq!(game.ply_count(), 3);
+    }
+
+    #[test]
+    fn test_detects_modified_history() {
+        let file = exchange(new_game().to_file_string(), &["e4", "e5", "Nf3", "Nc6"]);
+
+        // Bob rewrites Alice's second move (and the PGN to match) and replies
+        let forged = file.replace("% move 3 Nf3", "% move 3 Bc4").replace("2. Nf3 Nc6", "2. Bc4 Nc6");
+        let mut game = CorrGame::parse(&forged).unwrap();
+        assert!(matches!(
+            game.play("Bc4", ALICE, START),
+            Err(CorrError::Tampered { ply: Some(3), .. })
+        ));
+
+        // Changing a move's timestamp breaks the signature too
+        let line = file.lines().find(|line| line.starts_with("% move 1 ")).unwrap();
+        let retimed = line.replace(&(START + 3_600).to_string(), &START.to_string());
+        let game = CorrGame::parse(&file.replace(line, &retimed)).unwrap();
+        assert!(matches!(game.verify(ALICE), Err(CorrError::Tampered { ply: Some(1), .. })));
+
+        // So does renaming the players, which the first signature chains from
+        let game = CorrGame::parse(&file.replace("alice", "alicia")).unwrap();
+        assert!(matches!(game.verify(ALICE), Err(CorrError::Tampered { ply: Some(1), .. })));
+    }
+
+    #[test]
+    fn test_detects_inconsistent_files() {
+        let file = exchange(new_game().to_file_string(), &["e4", "e5"]);
+
+        // The PGN edited without the record
+        assert!(matches!(
+            CorrGame::parse(&file.replace("1. e4 e5", "1. d4 e5")),
+            Err(CorrError::Tampered { ply: None, .. })
+        ));
+
+        // An illegal move in the record
+        assert!(matches!(
+            CorrGame::parse(&file.replace("% move 2 e5", "% move 2 e4")),
+            Err(CorrError::Tampered { ply: Some(2), .. })
+        ));
+
+        // A dropped move
+        let line = file.lines().find(|line| line.starts_with("% move 1 ")).unwrap();
+        assert!(matches!(
+            CorrGame::parse(&file.replace(&format!("{}\n", line), "")),
+            Err(CorrError::Tampered { ply: Some(1), .. })
+        ));
+
+        assert!(matches!(CorrGame::parse("[Event \"?\"]\n\n*\n"), Err(CorrError::Format(_))));
+    }
+
+    #[test]
+    fn test_survives_mail_rewrapping() {
+        let file = exchange(new_game().to_file_string(), &["e4", "e5", "Nf3", "Nc6"]);
+        let wrapped = file.replace("2. Nf3", "\r\n2. Nf3").replace('\n', "\r\n");
+
+        let mut game = CorrGame::parse(&wrapped).unwrap();
+        game.play("Bb5", ALICE, START).unwrap();
+        assert_eq!(game.ply_count(), 5);
+    }
+
+    #[test]
+    fn test_clock_and_render() {
+        let mut game = new_game();
+        assert_eq!(
+            game.clock_status(START + 3_600),
+            Some("alice has 2d 23h left (3 days per move)".to_string())
+        );
+
+        game.play("e4", ALICE, START + 86_400).unwrap();
+        assert_eq!(
+            game.clock_status(START + 5 * 86_400 + 120),
+            Some("bob is 1d 0h over time (3 days per move)".to_string())
+        );
+
+        let text = game.render(START + 86_400);
+        assert!(text.contains("alice (White) vs bob (Black)"));
+        assert!(text.contains("  4  . . . . ♙ . . .\n"));
+        assert!(text.contains("  1. e4\n"));
+        assert!(text.contains("  Black (bob) to move\n"));
+    }
+
+    #[test]
+    fn test_rejects_unusable_names() {
+        let result = CorrGame::new("al\"ice", "bob", VariantKind::Standard, None, Color::White, ALICE, START);
+        assert!(matches!(result, Err(CorrError::InvalidInput(_))));
+        let result = CorrGame::new("bob", "bob", VariantKind::Standard, None, Color::White, ALICE, START);
+        assert!(matches!(result, Err(CorrError::InvalidInput(_))));
+    }
+}
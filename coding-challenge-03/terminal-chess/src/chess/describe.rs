@@ -0,0 +1,394 @@
+use super::{Board, Color, Move, MoveType, PieceType, Position};
+use std::cmp::Reverse;
+
+/// Long-form English description of `mov`, played from `board`
+///
+/// e.g. "White knight from g1 to f3, attacking black pawn on e5." Covers
+/// captures, en passant, castling, promotion, and check or checkmate in the
+/// resulting position.
+pub fn describe_move(board: &Board, mov: &Move) -> String {
+    let side = capitalize(color_name(mov.piece.color));
+    let opponent = mov.piece.color.opposite();
+
+    let mut text = match (mov.move_type, mov.captured) {
+        (MoveType::Castle, _) => format!(
+            "{} castles {}",
+            side,
+            if mov.to.col > mov.from.col { "kingside" } else { "queenside" }
+        ),
+        (MoveType::EnPassant, _) => format!(
+            "{} pawn from {} takes {} pawn en passant, landing on {}",
+            side,
+            mov.from,
+            color_name(opponent),
+            mov.to
+        ),
+        (_, Some(captured)) => format!(
+            "{} {} from {} takes {} on {}",
+            side,
+            mov.piece.piece_type.name(),
+            mov.from,
+            piece_name(captured.color, captured.piece_type),
+            mov.to
+        ),
+        (_, None) => format!("{} {} from {} to {}", side, mov.piece.piece_type.name(), mov.from, mov.to),
+    };
+
+    if let MoveType::Promotion(promote_to) = mov.move_type {
+        text.push_str(&format!(", promotes to {}", promote_to.name()));
+    }
+
+    let mut after = board.clone();
+    after.make_move(mov);
+
+    if mov.move_type != MoveType::Castle {
+        let mut attacked: Vec<_> = after
+            .attacked_pieces(mov.to)
+            .into_iter()
+            .filter(|(_, piece)| piece.piece_type != PieceType::King)
+            .collect();
+        attacked.sort_by_key(|(_, piece)| Reverse(piece.value()));
+
+        if !attacked.is_empty() {
+            let targets: Vec<String> = attacked
+                .iter()
+                .map(|(pos, piece)| format!("{} on {}", piece_name(piece.color, piece.piece_type), pos))
+                .collect();
+            text.push_str(&format!(", attacking {}", join_list(&targets)));
+        }
+    }
+
+    if after.is_in_check(opponent) {
+        if after.generate_legal_moves(opponent).is_empty() {
+            text.push_str(", checkmate");
+        } else {
+            text.push_str(", check");
+        }
+    }
+
+    text.push('.');
+    text
+}
+
+/// What stands on one square, e.g. "e4: white pawn."
+pub fn describe_square(board: &Board, pos: Position) -> String {
+    match board.get_piece(pos) {
+        Some(piece) => format!("{}: {}.", pos, piece_name(piece.color, piece.piece_type)),
+        None => format!("{}: empty.", pos),
+    }
+}
+
+/// Contents of a rank from the a-file to the h-file (`row` 0-7)
+pub fn describe_rank(board: &Board, row: i8) -> String {
+    let squares: Vec<Position> = (0..8).filter_map(|col| Position::new(row, col)).collect();
+    format!("Rank {}: {}.", row + 1, describe_line(board, &squares))
+}
+
+/// Contents of a file from the first rank to the eighth (`col` 0-7)
+pub fn describe_file(board: &Board, col: i8) -> String {
+    let squares: Vec<Position> = (0..8).filter_map(|row| Position::new(row, col)).collect();
+    format!("File {}: {}.", file_letter(col), describe_line(board, &squares))
+}
+
+/// Every piece one side has, king first, e.g. "White: king on e1, rooks on a1 and h1, ..."
+pub fn describe_pieces(board: &Board, color: Color) -> String {
+    let mut groups = Vec::new();
+
+    for piece_type in [
+        PieceType::King,
+        PieceType::Queen,
+        PieceType::Rook,
+        PieceType::Bishop,
+        PieceType::Knight,
+        PieceType::Pawn,
+    ] {
+        let squares: Vec<String> = all_squares()
+            .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.color == color && p.piece_type == piece_type))
+            .map(|pos| pos.to_algebraic())
+            .collect();
+
+        if !squares.is_empty() {
+            let name = if squares.len() == 1 {
+                piece_type.name().to_string()
+            } else {
+                format!("{}s", piece_type.name())
+            };
+            groups.push(format!("{} on {}", name, join_list(&squares)));
+        }
+    }
+
+    if groups.is_empty() {
+        format!("{} has no pieces.", capitalize(color_name(color)))
+    } else {
+        format!("{}: {}.", capitalize(color_name(color)), groups.join(", "))
+    }
+}
+
+/// A board question typed in place of a move, e.g. "read rank 4"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardQuery {
+    Rank(i8),
+    File(i8),
+    Square(Position),
+    Pieces(Color),
+}
+
+impl BoardQuery {
+    /// Commands listed in the accessible help line
+    pub const HELP: &'static str = "read rank 1-8, read file a-h, read <square>, read white, read black";
+
+    /// Parse a `read ...` command
+    ///
+    /// Returns `None` when the input is not a query at all, and an error
+    /// message when it is one but cannot be understood.
+    pub fn parse(input: &str) -> Option<Result<BoardQuery, String>> {
+        let mut words = input.split_whitespace();
+        if words.next()? != "read" {
+            return None;
+        }
+
+        let words: Vec<&str> = words.collect();
+        let query = match words.as_slice() {
+            ["rank", rank] => rank
+                .parse::<i8>()
+                .ok()
+                .filter(|r| (1..=8).contains(r))
+                .map(|r| BoardQuery::Rank(r - 1)),
+            ["file", file] => match file.as_bytes() {
+                [letter @ b'a'..=b'h'] => Some(BoardQuery::File((letter - b'a') as i8)),
+                _ => None,
+            },
+            ["white"] => Some(BoardQuery::Pieces(Color::White)),
+            ["black"] => Some(BoardQuery::Pieces(Color::Black)),
+            [square] => Position::from_algebraic(square).map(BoardQuery::Square),
+            _ => None,
+        };
+
+        Some(query.ok_or_else(|| format!("Unknown query. Try: {}", Self::HELP)))
+    }
+
+    /// Answer the query for `board`
+    pub fn answer(&self, board: &Board) -> String {
+        match *self {
+            BoardQuery::Rank(row) => describe_rank(board, row),
+            BoardQuery::File(col) => describe_file(board, col),
+            BoardQuery::Square(pos) => describe_square(board, pos),
+            BoardQuery::Pieces(color) => describe_pieces(board, color),
+        }
+    }
+}
+
+/// Describe a line of squares, collapsing runs of empty squares
+fn describe_line(board: &Board, squares: &[Position]) -> String {
+    let mut parts = Vec::new();
+    let mut empty_run: Vec<Position> = Vec::new();
+
+    let flush = |run: &mut Vec<Position>, parts: &mut Vec<String>| {
+        match run.as_slice() {
+            [] => {}
+            [single] => parts.push(format!("{} empty", single)),
+            [first, .., last] => parts.push(format!("{} to {} empty", first, last)),
+        }
+        run.clear();
+    };
+
+    for &pos in squares {
+        match board.get_piece(pos) {
+            Some(piece) => {
+                flush(&mut empty_run, &mut parts);
+                parts.push(format!("{} {}", pos, piece_name(piece.color, piece.piece_type)));
+            }
+            None => empty_run.push(pos),
+        }
+    }
+    flush(&mut empty_run, &mut parts);
+
+    parts.join(", ")
+}
+
+fn all_squares() -> impl Iterator<Item = Position> {
+    (0..8).flat_map(|row| (0..8).filter_map(move |col| Position::new(row, col)))
+}
+
+fn piece_name(color: Color, piece_type: PieceType) -> String {
+    format!("{} {}", color_name(color), piece_type.name())
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn file_letter(col: i8) -> char {
+    (b'a' + col as u8) as char
+}
+
+/// "a", "a and b", "a, b and c"
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Piece;
+
+    fn pos(s: &str) -> Position {
+        Position::from_algebraic(s).unwrap()
+    }
+
+    fn find_move(board: &Board, from: &str, to: &str) -> Move {
+        board
+            .generate_legal_moves(board.get_piece(pos(from)).unwrap().color)
+            .into_iter()
+            .find(|m| m.from == pos(from) && m.to == pos(to))
+            .unwrap()
+    }
+
+    fn play(moves: &[(&str, &str)]) -> Board {
+        let mut board = Board::new();
+        for (from, to) in moves {
+            let mov = find_move(&board, from, to);
+            board.make_move(&mov);
+        }
+        board
+    }
+
+    fn describe(board: &Board, from: &str, to: &str) -> String {
+        describe_move(board, &find_move(board, from, to))
+    }
+
+    #[test]
+    fn test_describe_quiet_move() {
+        let board = Board::new();
+        assert_eq!(describe(&board, "e2", "e4"), "White pawn from e2 to e4.");
+        assert_eq!(describe(&board, "g1", "f3"), "White knight from g1 to f3.");
+    }
+
+    #[test]
+    fn test_describe_move_with_attack() {
+        let board = play(&[("e2", "e4"), ("e7", "e5")]);
+        assert_eq!(
+            describe(&board, "g1", "f3"),
+            "White knight from g1 to f3, attacking black pawn on e5."
+        );
+    }
+
+    #[test]
+    fn test_describe_capture() {
+        let board = play(&[("e2", "e4"), ("d7", "d5")]);
+        assert_eq!(
+            describe(&board, "e4", "d5"),
+            "White pawn from e4 takes black pawn on d5."
+        );
+    }
+
+    #[test]
+    fn test_describe_en_passant() {
+        let board = play(&[("e2", "e4"), ("a7", "a6"), ("e4", "e5"), ("d7", "d5")]);
+        assert_eq!(
+            describe(&board, "e5", "d6"),
+            "White pawn from e5 takes black pawn en passant, landing on d6, attacking black pawn on c7 and black pawn on e7."
+        );
+    }
+
+    #[test]
+    fn test_describe_castling() {
+        let mut board = Board::new();
+        for square in ["f1", "g1", "b8", "c8", "d8"] {
+            board.set_piece(pos(square), None);
+        }
+        assert_eq!(describe(&board, "e1", "g1"), "White castles kingside.");
+        assert_eq!(describe(&board, "e8", "c8"), "Black castles queenside.");
+    }
+
+    #[test]
+    fn test_describe_promotion() {
+        let mut board = Board::new();
+        board.set_piece(pos("b7"), Some(Piece::new(PieceType::Pawn, Color::White)));
+        board.set_piece(pos("b8"), None);
+
+        let promote = |to: &str| {
+            board
+                .generate_legal_moves(Color::White)
+                .into_iter()
+                .find(|m| m.from == pos("b7") && m.to == pos(to) && m.move_type == MoveType::Promotion(PieceType::Queen))
+                .unwrap()
+        };
+
+        assert_eq!(
+            describe_move(&board, &promote("b8")),
+            "White pawn from b7 to b8, promotes to queen, attacking black rook on a8, black bishop on c8, \
+             black pawn on c7 and black pawn on a7."
+        );
+        assert_eq!(
+            describe_move(&board, &promote("a8")),
+            "White pawn from b7 takes black rook on a8, promotes to queen, attacking black bishop on c8 and black pawn on a7."
+        );
+    }
+
+    #[test]
+    fn test_describe_check() {
+        let board = play(&[("e2", "e4"), ("f7", "f6")]);
+        assert_eq!(
+            describe(&board, "d1", "h5"),
+            "White queen from d1 to h5, attacking black pawn on h7, check."
+        );
+    }
+
+    #[test]
+    fn test_describe_checkmate() {
+        let board = play(&[("f2", "f3"), ("e7", "e5"), ("g2", "g4")]);
+        assert_eq!(
+            describe(&board, "d8", "h4"),
+            "Black queen from d8 to h4, attacking white pawn on h2 and white pawn on g4, checkmate."
+        );
+    }
+
+    #[test]
+    fn test_describe_rank_and_file() {
+        let board = play(&[("e2", "e4")]);
+        assert_eq!(describe_rank(&board, 3), "Rank 4: a4 to d4 empty, e4 white pawn, f4 to h4 empty.");
+        assert_eq!(
+            describe_file(&board, 4),
+            "File e: e1 white king, e2 to e3 empty, e4 white pawn, e5 to e6 empty, e7 black pawn, e8 black king."
+        );
+        assert_eq!(describe_square(&board, pos("e2")), "e2: empty.");
+    }
+
+    #[test]
+    fn test_describe_pieces() {
+        let mut board = Board::new();
+        for col in 0..8 {
+            board.set_piece(Position::new(1, col).unwrap(), None);
+        }
+        assert_eq!(
+            describe_pieces(&board, Color::White),
+            "White: king on e1, queen on d1, rooks on a1 and h1, bishops on c1 and f1, knights on b1 and g1."
+        );
+    }
+
+    #[test]
+    fn test_parse_queries() {
+        assert_eq!(BoardQuery::parse("read rank 4"), Some(Ok(BoardQuery::Rank(3))));
+        assert_eq!(BoardQuery::parse("read file e"), Some(Ok(BoardQuery::File(4))));
+        assert_eq!(BoardQuery::parse("read e4"), Some(Ok(BoardQuery::Square(pos("e4")))));
+        assert_eq!(BoardQuery::parse("read black"), Some(Ok(BoardQuery::Pieces(Color::Black))));
+        assert!(matches!(BoardQuery::parse("read rank 9"), Some(Err(_))));
+        assert_eq!(BoardQuery::parse("e2e4"), None);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Board, Color, Move, Position};
+use super::{pgn, Board, CheckCount, Color, Handicap, Move, Position, VariantKind};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -14,8 +14,11 @@ pub enum GameState {
     Playing,
     Check,
     Checkmate(Color), // Winner
+    VariantWin(Color), // Winner by the variant's own objective
     Stalemate,
     Draw,
+    Resignation(Color), // Winner, after the other side resigned
+    DrawAgreed,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +27,8 @@ struct HistoryEntry {
     en_passant: Option<Position>,
     castling: (bool, bool, bool, bool),
     halfmove: u32,
+    #[serde(default)]
+    checks: CheckCount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,13 +38,20 @@ pub struct Game {
     pub state: GameState,
     pub mode: GameMode,
     pub ai_difficulty: u8,
+    #[serde(default)]
+    pub handicap: Handicap,
+    #[serde(default)]
+    pub variant: VariantKind,
+    /// Checks delivered so far (only counted by variants that use them)
+    #[serde(default)]
+    pub checks: CheckCount,
     history: Vec<HistoryEntry>,
 }
 
 impl Game {
     pub fn new(mode: GameMode, ai_difficulty: u8) -> Self {
         let board = Board::new();
-        let state = Self::determine_state(&board, Color::White);
+        let state = Self::determine_state(&board, Color::White, VariantKind::Standard, &CheckCount::default());
 
         Game {
             board,
@@ -47,13 +59,58 @@ impl Game {
             state,
             mode,
             ai_difficulty,
+            handicap: Handicap::default(),
+            variant: VariantKind::Standard,
+            checks: CheckCount::default(),
             history: Vec::new(),
         }
     }
 
+    /// Create a game against the AI with handicap settings (the AI plays Black)
+    pub fn with_handicap(mode: GameMode, ai_difficulty: u8, handicap: Handicap) -> Self {
+        let mut game = Game::new(mode, ai_difficulty);
+        handicap.odds.apply(&mut game.board, Color::Black);
+        game.handicap = handicap;
+        game.refresh_state();
+        game
+    }
+
+    /// Play this game under a variant's rules (set before the first move)
+    pub fn with_variant(mut self, variant: VariantKind) -> Self {
+        self.variant = variant;
+        self.refresh_state();
+        self
+    }
+
     /// Get all legal moves for the current player
     pub fn get_legal_moves(&self) -> Vec<Move> {
-        self.board.generate_legal_moves(self.current_player)
+        let moves = self.board.generate_legal_moves(self.current_player);
+        self.variant.rules().filter_moves(&self.board, self.current_player, moves)
+    }
+
+    /// Standard Algebraic Notation for a legal move, with its check or mate mark
+    pub fn to_san(&self, mov: &Move) -> String {
+        let mut san = pgn::to_san(&self.board, mov);
+
+        let mut after = self.clone();
+        if after.make_move(*mov).is_ok() {
+            match after.state {
+                GameState::Checkmate(_) => san.push('#'),
+                GameState::Check => san.push('+'),
+                // Variant wins still show a check that was delivered
+                _ if after.board.is_in_check(after.current_player) => san.push('+'),
+                _ => {}
+            }
+        }
+
+        san
+    }
+
+    /// Every legal move for the current player in SAN, sorted
+    pub fn legal_moves_san(&self) -> Vec<String> {
+        let mut moves: Vec<String> = self.get_legal_moves().iter().map(|mov| self.to_san(mov)).collect();
+        moves.sort();
+        moves
     }
 
     /// Make a move if it's legal
@@ -75,11 +132,13 @@ impl Game {
                 self.board.black_can_castle_queenside,
             ),
             halfmove: self.board.halfmove_clock,
+            checks: self.checks,
         };
         self.history.push(entry);
 
         // Make the move
         self.board.make_move(&mov);
+        self.variant.rules().after_move(&self.board, self.current_player, &mut self.checks);
 
         // Update fullmove number
         if self.current_player == Color::Black {
@@ -90,7 +149,7 @@ impl Game {
         self.current_player = self.current_player.opposite();
 
         // Update game state
-        self.state = Self::determine_state(&self.board, self.current_player);
+        self.refresh_state();
 
         Ok(())
     }
@@ -99,6 +158,7 @@ impl Game {
     pub fn undo_move(&mut self) -> Result<(), String> {
         if let Some(entry) = self.history.pop() {
             self.board.unmake_move(&entry.mov, entry.en_passant, entry.castling, entry.halfmove);
+            self.checks = entry.checks;
 
             // Switch player back
             self.current_player = self.current_player.opposite();
@@ -109,7 +169,7 @@ impl Game {
             }
 
             // Update game state
-            self.state = Self::determine_state(&self.board, self.current_player);
+            self.refresh_state();
 
             Ok(())
         } else {
@@ -117,9 +177,21 @@ impl Game {
         }
     }
 
+    /// Recompute the game state after the board or variant changes
+    fn refresh_state(&mut self) {
+        self.state = Self::determine_state(&self.board, self.current_player, self.variant, &self.checks);
+    }
+
     /// Determine the current game state
-    fn determine_state(board: &Board, current_player: Color) -> GameState {
-        let legal_moves = board.generate_legal_moves(current_player);
+    fn determine_state(board: &Board, current_player: Color, variant: VariantKind, checks: &CheckCount) -> GameState {
+        let rules = variant.rules();
+
+        // A variant objective ends the game before anything else is considered
+        if let Some(winner) = rules.winner(board, checks) {
+            return GameState::VariantWin(winner);
+        }
+
+        let legal_moves = rules.filter_moves(board, current_player, board.generate_legal_moves(current_player));
         let in_check = board.is_in_check(current_player);
 
         if legal_moves.is_empty() {
@@ -140,7 +212,25 @@ impl Game {
 
     /// Check if the game is over
     pub fn is_game_over(&self) -> bool {
-        matches!(self.state, GameState::Checkmate(_) | GameState::Stalemate | GameState::Draw)
+        matches!(
+            self.state,
+            GameState::Checkmate(_)
+                | GameState::VariantWin(_)
+                | GameState::Stalemate
+                | GameState::Draw
+                | GameState::Resignation(_)
+                | GameState::DrawAgreed
+        )
+    }
+
+    /// End the game with `color` resigning
+    pub fn resign(&mut self, color: Color) {
+        self.state = GameState::Resignation(color.opposite());
+    }
+
+    /// End the game as a draw both sides agreed to
+    pub fn agree_draw(&mut self) {
+        self.state = GameState::DrawAgreed;
     }
 
     /// Get the move history
@@ -199,7 +289,9 @@ impl Game {
                 white_captured.push(Piece::new(piece_type, Color::White));
             }
 
-            let black_missing = starting_counts[idx] - black_pieces[idx];
+            // Pieces given as odds were never on the board, so they aren't captures
+            let odds = if self.handicap.odds.removed_piece() == Some(piece_type) { 1 } else { 0 };
+            let black_missing = starting_counts[idx] - odds - black_pieces[idx];
             for _ in 0..black_missing {
                 black_captured.push(Piece::new(piece_type, Color::Black));
             }
@@ -229,6 +321,81 @@ impl Game {
 
         Ok(game)
     }
+
+    /// Export the game as PGN
+    pub fn to_pgn(&self) -> String {
+        let (white, black) = match self.mode {
+            GameMode::PlayerVsAI => ("Player", "Computer"),
+            GameMode::PlayerVsPlayer => ("Player 1", "Player 2"),
+        };
+        self.to_pgn_between(white, black)
+    }
+
+    /// Export the game as PGN with the given player names
+    pub fn to_pgn_between(&self, white: &str, black: &str) -> String {
+        // Walk back to the starting position, which may differ from the
+        // standard one when material odds were given
+        let mut start = self.clone();
+        while start.undo_move().is_ok() {}
+
+        let result = match self.state {
+            GameState::Checkmate(Color::White)
+            | GameState::VariantWin(Color::White)
+            | GameState::Resignation(Color::White) => "1-0",
+            GameState::Checkmate(Color::Black)
+            | GameState::VariantWin(Color::Black)
+            | GameState::Resignation(Color::Black) => "0-1",
+            GameState::Stalemate | GameState::Draw | GameState::DrawAgreed => "1/2-1/2",
+            GameState::Playing | GameState::Check => "*",
+        };
+
+        let mut pgn = String::new();
+        for (tag, value) in [
+            ("Event", "Terminal Chess"),
+            ("Site", "?"),
+            ("Date", "????.??.??"),
+            ("Round", "-"),
+            ("White", white),
+            ("Black", black),
+            ("Result", result),
+            ("Variant", self.variant.rules().name()),
+        ] {
+            pgn.push_str(&format!("[{} \"{}\"]\n", tag, value));
+        }
+
+        let start_fen = pgn::to_fen(&start.board, start.current_player);
+        if start_fen != pgn::to_fen(&Board::new(), Color::White) {
+            pgn.push_str("[SetUp \"1\"]\n");
+            pgn.push_str(&format!("[FEN \"{}\"]\n", start_fen));
+        }
+        pgn.push('\n');
+
+        let mut tokens = Vec::new();
+        for mov in self.get_history() {
+            if start.current_player == Color::White {
+                tokens.push(format!("{}.", start.board.fullmove_number));
+            } else if tokens.is_empty() {
+                tokens.push(format!("{}...", start.board.fullmove_number));
+            }
+
+            let san = start.to_san(&mov);
+            start.make_move(mov).expect("history replays legally");
+            tokens.push(san);
+        }
+        tokens.push(result.to_string());
+
+        pgn.push_str(&tokens.join(" "));
+        pgn.push('\n');
+        pgn
+    }
+
+    /// Save game to a PGN file
+    pub fn save_pgn<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        fs::write(path, self.to_pgn())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -271,6 +438,183 @@ mod tests {
         assert!(game.board.get_piece(to).is_none());
     }
 
+    #[test]
+    fn test_handicap_game_setup() {
+        use crate::chess::{MaterialOdds, PieceType};
+
+        let handicap = Handicap {
+            odds: MaterialOdds::Rook,
+            skill: 3,
+            coaching: true,
+        };
+        let game = Game::with_handicap(GameMode::PlayerVsAI, 2, handicap);
+
+        assert!(game.board.get_piece(Position::from_algebraic("a8").unwrap()).is_none());
+        assert_eq!(game.handicap.skill, 3);
+        assert_eq!(game.state, GameState::Playing);
+
+        // The missing rook is odds, not a capture
+        let (_, black_captured) = game.get_captured_pieces();
+        assert!(!black_captured.iter().any(|p| p.piece_type == PieceType::Rook));
+    }
+
+    #[test]
+    fn test_old_saves_load_without_handicap() {
+        let game = Game::new(GameMode::PlayerVsAI, 2);
+        let mut json: serde_json::Value = serde_json::to_value(&game).unwrap();
+        json.as_object_mut().unwrap().remove("handicap");
+
+        let loaded: Game = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.handicap, Handicap::default());
+    }
+
+    fn play(game: &mut Game, moves: &[(&str, &str)]) {
+        for (from_str, to_str) in moves.iter() {
+            let from = Position::from_algebraic(from_str).unwrap();
+            let to = Position::from_algebraic(to_str).unwrap();
+            let mov = *game
+                .get_legal_moves()
+                .iter()
+                .find(|m| m.from == from && m.to == to)
+                .unwrap();
+            game.make_move(mov).unwrap();
+        }
+    }
+
+    // 1. e4 d5 2. Ke2 dxe4 3. Ke3 a6 4. Kxe4
+    const KING_WALK: [(&str, &str); 7] = [
+        ("e2", "e4"),
+        ("d7", "d5"),
+        ("e1", "e2"),
+        ("d5", "e4"),
+        ("e2", "e3"),
+        ("a7", "a6"),
+        ("e3", "e4"),
+    ];
+
+    // 1. e4 d6 2. Bb5+ c6 3. Bxc6+ Nxc6 4. Qh5 a6 5. Qxf7+
+    const THREE_CHECKS: [(&str, &str); 9] = [
+        ("e2", "e4"),
+        ("d7", "d6"),
+        ("f1", "b5"),
+        ("c7", "c6"),
+        ("b5", "c6"),
+        ("b8", "c6"),
+        ("d1", "h5"),
+        ("a7", "a6"),
+        ("h5", "f7"),
+    ];
+
+    #[test]
+    fn test_king_of_the_hill_win() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3).with_variant(VariantKind::KingOfTheHill);
+        play(&mut game, &KING_WALK[..6]);
+        assert_eq!(game.state, GameState::Playing);
+
+        // Kxe4 puts the white king on the hill
+        play(&mut game, &KING_WALK[6..]);
+        assert_eq!(game.state, GameState::VariantWin(Color::White));
+        assert!(game.is_game_over());
+
+        game.undo_move().unwrap();
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_three_check_win() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3).with_variant(VariantKind::ThreeCheck);
+        play(&mut game, &THREE_CHECKS[..8]);
+        assert_eq!(game.checks, CheckCount { white: 2, black: 0 });
+        assert_eq!(game.state, GameState::Playing);
+
+        play(&mut game, &THREE_CHECKS[8..]);
+        assert_eq!(game.checks.white, 3);
+        assert_eq!(game.state, GameState::VariantWin(Color::White));
+
+        // Undo restores the count
+        game.undo_move().unwrap();
+        assert_eq!(game.checks.white, 2);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_standard_ignores_variant_objectives() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3);
+        play(&mut game, &KING_WALK);
+        assert_eq!(game.state, GameState::Playing);
+
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3);
+        play(&mut game, &THREE_CHECKS);
+        assert_eq!(game.state, GameState::Check);
+        assert_eq!(game.checks, CheckCount::default());
+        assert_eq!(game.get_legal_moves(), game.board.generate_legal_moves(Color::Black));
+    }
+
+    #[test]
+    fn test_pgn_export() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3).with_variant(VariantKind::ThreeCheck);
+        play(&mut game, &THREE_CHECKS);
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[Variant \"Three-check\"]\n"));
+        assert!(pgn.contains("[Result \"1-0\"]\n"));
+        assert!(!pgn.contains("[FEN"));
+        assert!(pgn.ends_with("1. e4 d6 2. Bb5+ c6 3. Bxc6+ Nxc6 4. Qh5 a6 5. Qxf7+ 1-0\n"));
+
+        let standard = Game::new(GameMode::PlayerVsPlayer, 3).to_pgn();
+        assert!(standard.contains("[Variant \"Standard\"]\n"));
+        assert!(standard.ends_with("\n*\n"));
+    }
+
+    #[test]
+    fn test_resignation_and_agreed_draw_end_the_game() {
+        let mut game = Game::new(GameMode::PlayerVsAI, 3);
+        play(&mut game, &KING_WALK[..2]);
+
+        game.resign(Color::Black);
+        assert_eq!(game.state, GameState::Resignation(Color::White));
+        assert!(game.is_game_over());
+        assert!(game.to_pgn().ends_with("1. e4 d5 1-0\n"));
+
+        game.agree_draw();
+        assert!(game.is_game_over());
+        assert!(game.to_pgn().contains("[Result \"1/2-1/2\"]\n"));
+    }
+
+    #[test]
+    fn test_pgn_export_with_odds_includes_fen() {
+        use crate::chess::MaterialOdds;
+
+        let handicap = Handicap {
+            odds: MaterialOdds::Queen,
+            ..Handicap::default()
+        };
+        let game = Game::with_handicap(GameMode::PlayerVsAI, 2, handicap);
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[SetUp \"1\"]\n"));
+        assert!(pgn.contains("[FEN \"rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\"]\n"));
+    }
+
+    #[test]
+    fn test_old_saves_load_without_variant() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3);
+        play(&mut game, &KING_WALK[..2]);
+
+        let mut json: serde_json::Value = serde_json::to_value(&game).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("variant");
+        object.remove("checks");
+        for entry in object["history"].as_array_mut().unwrap() {
+            entry.as_object_mut().unwrap().remove("checks");
+        }
+
+        let mut loaded: Game = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.variant, VariantKind::Standard);
+        assert_eq!(loaded.checks, CheckCount::default());
+        assert!(loaded.undo_move().is_ok());
+    }
+
     #[test]
     fn test_checkmate_detection() {
         let mut game = Game::new(GameMode::PlayerVsPlayer, 3);
@@ -294,4 +638,19 @@ mod tests {
 
         assert!(matches!(game.state, GameState::Checkmate(_)));
     }
+
+    #[test]
+    fn test_move_san_marks_check_and_mate() {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 3);
+        assert_eq!(game.legal_moves_san().len(), 20);
+        assert!(game.legal_moves_san().contains(&"Nf3".to_string()));
+
+        play(&mut game, &[("f2", "f3"), ("e7", "e6"), ("g2", "g4")]);
+        let mate = game
+            .get_legal_moves()
+            .into_iter()
+            .find(|m| m.to == Position::from_algebraic("h4").unwrap())
+            .unwrap();
+        assert_eq!(game.to_san(&mate), "Qh4#");
+    }
 }
@@ -0,0 +1,117 @@
+use super::{Board, Color, PieceType, Position};
+use serde::{Deserialize, Serialize};
+
+/// Full-strength skill level (the AI never deliberately picks a weaker move)
+pub const MAX_SKILL: u8 = 10;
+
+/// Material the AI gives up before the game starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MaterialOdds {
+    #[default]
+    None,
+    Knight,
+    Rook,
+    Queen,
+}
+
+impl MaterialOdds {
+    /// Human-readable label for menus
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaterialOdds::None => "None",
+            MaterialOdds::Knight => "Knight odds",
+            MaterialOdds::Rook => "Rook odds",
+            MaterialOdds::Queen => "Queen odds",
+        }
+    }
+
+    /// Piece removed from the starting position, if any
+    pub fn removed_piece(&self) -> Option<PieceType> {
+        match self {
+            MaterialOdds::None => None,
+            MaterialOdds::Knight => Some(PieceType::Knight),
+            MaterialOdds::Rook => Some(PieceType::Rook),
+            MaterialOdds::Queen => Some(PieceType::Queen),
+        }
+    }
+
+    /// Remove the odds material for `color` from a starting board
+    ///
+    /// Odds are always taken from the queenside (b-knight, a-rook), as is
+    /// traditional; giving rook odds also forfeits queenside castling.
+    pub fn apply(&self, board: &mut Board, color: Color) {
+        let back_rank = if color == Color::White { 0 } else { 7 };
+
+        let col = match self {
+            MaterialOdds::None => return,
+            MaterialOdds::Knight => 1,
+            MaterialOdds::Rook => 0,
+            MaterialOdds::Queen => 3,
+        };
+
+        board.set_piece(Position::new(back_rank, col).unwrap(), None);
+
+        if *self == MaterialOdds::Rook {
+            match color {
+                Color::White => board.white_can_castle_queenside = false,
+                Color::Black => board.black_can_castle_queenside = false,
+            }
+        }
+    }
+}
+
+/// Handicap settings for games against the AI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handicap {
+    /// Material the AI starts without
+    pub odds: MaterialOdds,
+    /// AI skill level (1-10); lower levels occasionally play the 2nd/3rd best move
+    pub skill: u8,
+    /// Warn before confirming a move that hangs material
+    pub coaching: bool,
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Handicap {
+            odds: MaterialOdds::None,
+            skill: MAX_SKILL,
+            coaching: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knight_odds_removes_queenside_knight() {
+        let mut board = Board::new();
+        MaterialOdds::Knight.apply(&mut board, Color::Black);
+
+        assert!(board.get_piece(Position::from_algebraic("b8").unwrap()).is_none());
+        assert!(board.get_piece(Position::from_algebraic("g8").unwrap()).is_some());
+        assert!(board.get_piece(Position::from_algebraic("b1").unwrap()).is_some());
+        assert!(board.evaluate() > 0);
+    }
+
+    #[test]
+    fn test_rook_odds_forfeits_queenside_castling() {
+        let mut board = Board::new();
+        MaterialOdds::Rook.apply(&mut board, Color::Black);
+
+        assert!(board.get_piece(Position::from_algebraic("a8").unwrap()).is_none());
+        assert!(!board.black_can_castle_queenside);
+        assert!(board.black_can_castle_kingside);
+        assert!(board.white_can_castle_queenside);
+    }
+
+    #[test]
+    fn test_no_odds_leaves_board_unchanged() {
+        let mut board = Board::new();
+        MaterialOdds::None.apply(&mut board, Color::Black);
+        assert_eq!(board.evaluate(), Board::new().evaluate());
+        assert_eq!(board.generate_legal_moves(Color::Black).len(), 20);
+    }
+}
@@ -3,9 +3,20 @@ pub mod position;
 pub mod board;
 pub mod moves;
 pub mod game;
+pub mod handicap;
+pub mod pgn;
+pub mod san;
+pub mod describe;
+pub mod variant;
+pub mod correspondence;
 
 pub use piece::{Piece, PieceType, Color};
 pub use position::Position;
 pub use board::Board;
 pub use moves::{Move, MoveType};
 pub use game::{Game, GameState, GameMode};
+pub use handicap::{Handicap, MaterialOdds, MAX_SKILL};
+pub use san::{parse_san, SanError};
+pub use describe::{describe_move, BoardQuery};
+pub use correspondence::{CorrError, CorrGame};
+pub use variant::{CheckCount, KingOfTheHill, Standard, ThreeCheck, Variant, VariantKind, CHECKS_TO_WIN};
@@ -0,0 +1,173 @@
+use super::{Board, Color, Move, MoveType, PieceType, Position};
+
+/// Standard Algebraic Notation for `mov`, played from `board`
+///
+/// Check and mate suffixes are left to the caller, which knows the
+/// position after the move.
+pub fn to_san(board: &Board, mov: &Move) -> String {
+    let is_capture = mov.captured.is_some() || mov.move_type == MoveType::EnPassant;
+
+    if mov.move_type == MoveType::Castle {
+        return if mov.to.col > mov.from.col { "O-O" } else { "O-O-O" }.to_string();
+    }
+
+    let mut san = String::new();
+
+    if mov.piece.piece_type == PieceType::Pawn {
+        if is_capture {
+            san.push(file_char(mov.from));
+            san.push('x');
+        }
+        san.push_str(&mov.to.to_algebraic());
+
+        if let MoveType::Promotion(promote_to) = mov.move_type {
+            san.push('=');
+            san.push(piece_letter(promote_to));
+        }
+
+        return san;
+    }
+
+    san.push(piece_letter(mov.piece.piece_type));
+
+    // Disambiguate when another piece of the same type can reach the square
+    let rivals: Vec<Position> = board
+        .generate_legal_moves(mov.piece.color)
+        .into_iter()
+        .filter(|m| m.piece == mov.piece && m.to == mov.to && m.from != mov.from)
+        .map(|m| m.from)
+        .collect();
+
+    if !rivals.is_empty() {
+        if rivals.iter().all(|p| p.col != mov.from.col) {
+            san.push(file_char(mov.from));
+        } else if rivals.iter().all(|p| p.row != mov.from.row) {
+            san.push(rank_char(mov.from));
+        } else {
+            san.push_str(&mov.from.to_algebraic());
+        }
+    }
+
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&mov.to.to_algebraic());
+
+    san
+}
+
+/// Forsyth-Edwards Notation for `board` with `to_move` on move
+pub fn to_fen(board: &Board, to_move: Color) -> String {
+    let mut ranks = Vec::new();
+
+    for row in (0..8).rev() {
+        let mut rank = String::new();
+        let mut empty = 0;
+
+        for col in 0..8 {
+            match board.get_piece(Position::new(row, col).unwrap()) {
+                Some(piece) => {
+                    if empty > 0 {
+                        rank.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    let letter = piece_letter(piece.piece_type);
+                    rank.push(if piece.color == Color::White { letter } else { letter.to_ascii_lowercase() });
+                }
+                None => empty += 1,
+            }
+        }
+
+        if empty > 0 {
+            rank.push_str(&empty.to_string());
+        }
+        ranks.push(rank);
+    }
+
+    let mut castling = String::new();
+    for (allowed, flag) in [
+        (board.white_can_castle_kingside, 'K'),
+        (board.white_can_castle_queenside, 'Q'),
+        (board.black_can_castle_kingside, 'k'),
+        (board.black_can_castle_queenside, 'q'),
+    ] {
+        if allowed {
+            castling.push(flag);
+        }
+    }
+    if castling.is_empty() {
+        castling.push('-');
+    }
+
+    format!(
+        "{} {} {} {} {} {}",
+        ranks.join("/"),
+        if to_move == Color::White { 'w' } else { 'b' },
+        castling,
+        board.en_passant_target.map_or("-".to_string(), |pos| pos.to_algebraic()),
+        board.halfmove_clock,
+        board.fullmove_number
+    )
+}
+
+fn piece_letter(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Pawn => 'P',
+        PieceType::Knight => 'N',
+        PieceType::Bishop => 'B',
+        PieceType::Rook => 'R',
+        PieceType::Queen => 'Q',
+        PieceType::King => 'K',
+    }
+}
+
+fn file_char(pos: Position) -> char {
+    (b'a' + pos.col as u8) as char
+}
+
+fn rank_char(pos: Position) -> char {
+    (b'1' + pos.row as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Piece;
+
+    fn legal_move(board: &Board, color: Color, from: &str, to: &str) -> Move {
+        let from = Position::from_algebraic(from).unwrap();
+        let to = Position::from_algebraic(to).unwrap();
+        board
+            .generate_legal_moves(color)
+            .into_iter()
+            .find(|m| m.from == from && m.to == to)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_san_basic_moves() {
+        let board = Board::new();
+        assert_eq!(to_san(&board, &legal_move(&board, Color::White, "e2", "e4")), "e4");
+        assert_eq!(to_san(&board, &legal_move(&board, Color::White, "g1", "f3")), "Nf3");
+    }
+
+    #[test]
+    fn test_san_disambiguates_by_file() {
+        let mut board = Board::new();
+        // Clear the path so both knights can reach d2
+        board.set_piece(Position::from_algebraic("d2").unwrap(), None);
+        board.set_piece(Position::from_algebraic("f3").unwrap(), Some(Piece::new(PieceType::Knight, Color::White)));
+        board.set_piece(Position::from_algebraic("g1").unwrap(), None);
+
+        assert_eq!(to_san(&board, &legal_move(&board, Color::White, "b1", "d2")), "Nbd2");
+        assert_eq!(to_san(&board, &legal_move(&board, Color::White, "f3", "d2")), "Nfd2");
+    }
+
+    #[test]
+    fn test_start_position_fen() {
+        assert_eq!(
+            to_fen(&Board::new(), Color::White),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use super::{Move, MoveType, PieceType, Position};
+use std::fmt;
+
+/// Why typed SAN could not be turned into a single legal move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SanError {
+    /// Not well-formed SAN, or no legal move matches it
+    Invalid(String),
+    /// Several legal moves match; the player has to pick one
+    Ambiguous(Vec<Move>),
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::Invalid(message) => write!(f, "{}", message),
+            SanError::Ambiguous(candidates) => write!(f, "Ambiguous move: {} legal moves match", candidates.len()),
+        }
+    }
+}
+
+/// The parts of a non-castling SAN move, e.g. "Nbxd2" or "exd8=Q"
+struct SanParts {
+    piece_type: PieceType,
+    from_col: Option<i8>,
+    from_row: Option<i8>,
+    capture: bool,
+    to: Position,
+    promotion: Option<PieceType>,
+}
+
+/// Find the legal move written as `input` in Standard Algebraic Notation
+///
+/// Accepts piece moves ("Nf3", "Rae1", "Qh4xe1"), pawn moves ("e4", "exd5",
+/// "e8=Q") and castling ("O-O", "O-O-O"), with an optional "+" or "#"
+/// suffix. Capture marks and promotions must match the move actually
+/// played, so "Nxf3" onto an empty square is rejected rather than guessed.
+pub fn parse_san(input: &str, legal_moves: &[Move]) -> Result<Move, SanError> {
+    let san = input.trim();
+    let body = san.strip_suffix(['+', '#']).unwrap_or(san);
+
+    if body.is_empty() {
+        return Err(invalid("Empty move"));
+    }
+    if body.ends_with(['+', '#']) {
+        return Err(invalid(format!("'{}' has more than one check mark", san)));
+    }
+
+    if let Some(kingside) = castle_side(body) {
+        return legal_moves
+            .iter()
+            .find(|m| m.move_type == MoveType::Castle && (m.to.col > m.from.col) == kingside)
+            .copied()
+            .ok_or_else(|| {
+                invalid(format!(
+                    "Castling {} is not legal here",
+                    if kingside { "kingside" } else { "queenside" }
+                ))
+            });
+    }
+
+    let parts = split(body)?;
+
+    let matches: Vec<Move> = legal_moves
+        .iter()
+        .filter(|m| {
+            m.piece.piece_type == parts.piece_type
+                && m.move_type != MoveType::Castle
+                && m.to == parts.to
+                && parts.from_col.is_none_or(|col| m.from.col == col)
+                && parts.from_row.is_none_or(|row| m.from.row == row)
+                && promotion_of(m) == parts.promotion
+        })
+        .copied()
+        .collect();
+
+    let target = parts.to.to_algebraic();
+    let name = parts.piece_type.name();
+
+    match matches.as_slice() {
+        [] => Err(invalid(no_match_message(&parts, legal_moves, &target))),
+        [mov] => {
+            let captures = mov.captured.is_some() || mov.move_type == MoveType::EnPassant;
+            if captures && !parts.capture {
+                Err(invalid(format!("The {} on {} captures; mark it with 'x'", name, target)))
+            } else if !captures && parts.capture {
+                Err(invalid(format!("There is nothing to capture on {}", target)))
+            } else {
+                Ok(*mov)
+            }
+        }
+        _ => Err(SanError::Ambiguous(matches)),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> SanError {
+    SanError::Invalid(message.into())
+}
+
+/// `Some(true)` for kingside castling, `Some(false)` for queenside
+fn castle_side(body: &str) -> Option<bool> {
+    match body {
+        "O-O" | "0-0" => Some(true),
+        "O-O-O" | "0-0-0" => Some(false),
+        _ => None,
+    }
+}
+
+fn split(body: &str) -> Result<SanParts, SanError> {
+    let chars: Vec<char> = body.chars().collect();
+
+    let (piece_type, rest) = match chars[0] {
+        'N' => (PieceType::Knight, &chars[1..]),
+        'B' => (PieceType::Bishop, &chars[1..]),
+        'R' => (PieceType::Rook, &chars[1..]),
+        'Q' => (PieceType::Queen, &chars[1..]),
+        'K' => (PieceType::King, &chars[1..]),
+        'P' => return Err(invalid(format!("Pawn moves have no letter: write '{}'", &body[1..]))),
+        'a'..='h' => (PieceType::Pawn, &chars[..]),
+        c @ ('n' | 'r' | 'q' | 'k') => {
+            return Err(invalid(format!(
+                "Piece letters are upper case: write '{}{}'",
+                c.to_ascii_uppercase(),
+                &body[1..]
+            )))
+        }
+        c => return Err(invalid(format!("'{}' does not start a move; expected a piece letter or a file", c))),
+    };
+
+    // Promotion suffix, e.g. "=Q"
+    let (rest, promotion) = match rest {
+        [head @ .., '=', letter] => {
+            let promote_to = match letter {
+                'Q' => PieceType::Queen,
+                'R' => PieceType::Rook,
+                'B' => PieceType::Bishop,
+                'N' => PieceType::Knight,
+                _ => return Err(invalid(format!("Cannot promote to '{}'; use Q, R, B or N", letter))),
+            };
+            (head, Some(promote_to))
+        }
+        _ => (rest, None),
+    };
+
+    // Destination square
+    let (rest, to) = match rest {
+        [head @ .., file @ 'a'..='h', rank @ '1'..='8'] => (head, square(*file, *rank)),
+        _ => return Err(invalid(format!("'{}' does not end in a square such as e4", body))),
+    };
+
+    // Capture mark
+    let (rest, capture) = match rest {
+        [head @ .., 'x'] => (head, true),
+        _ => (rest, false),
+    };
+
+    // Disambiguation: file, rank, or both
+    let (from_col, from_row) = match rest {
+        [] => (None, None),
+        [file @ 'a'..='h'] => (Some(*file as i8 - 'a' as i8), None),
+        [rank @ '1'..='8'] => (None, Some(*rank as i8 - '1' as i8)),
+        [file @ 'a'..='h', rank @ '1'..='8'] => (Some(*file as i8 - 'a' as i8), Some(*rank as i8 - '1' as i8)),
+        _ => return Err(invalid(format!("Cannot read '{}' as a move", body))),
+    };
+
+    if piece_type == PieceType::Pawn {
+        // For pawns `from_col` is the pawn's own file, which SAN only writes on captures
+        if capture && from_col.is_none() {
+            return Err(invalid(format!("Pawn captures name the pawn's file, e.g. 'ex{}'", to)));
+        }
+        if !capture && (from_col.is_some() || from_row.is_some()) {
+            if chars[0] == 'b' {
+                return Err(invalid(format!("Bishop moves use an upper-case B: write 'B{}'", &body[1..])));
+            }
+            return Err(invalid(format!(
+                "Pawn moves are written as just the square ('{}'), captures with the pawn's file ('{}x{}')",
+                to, chars[0], to
+            )));
+        }
+        if from_row.is_some() {
+            return Err(invalid(format!("Cannot read '{}' as a move", body)));
+        }
+
+        let last_rank = to.row == 0 || to.row == 7;
+        if last_rank && promotion.is_none() {
+            return Err(invalid(format!("Promotion needs a piece, e.g. '{}=Q'", body)));
+        }
+        if !last_rank && promotion.is_some() {
+            return Err(invalid("Pawns only promote on the last rank"));
+        }
+    } else if promotion.is_some() {
+        return Err(invalid("Only pawns can promote"));
+    }
+
+    Ok(SanParts {
+        piece_type,
+        from_col,
+        from_row,
+        capture,
+        to,
+        promotion,
+    })
+}
+
+fn square(file: char, rank: char) -> Position {
+    Position::new(rank as i8 - '1' as i8, file as i8 - 'a' as i8).expect("file and rank are in range")
+}
+
+fn promotion_of(mov: &Move) -> Option<PieceType> {
+    match mov.move_type {
+        MoveType::Promotion(piece_type) => Some(piece_type),
+        _ => None,
+    }
+}
+
+/// Explain why nothing matched, as specifically as the legal moves allow
+fn no_match_message(parts: &SanParts, legal_moves: &[Move], target: &str) -> String {
+    let name = parts.piece_type.name();
+    let reaches_target = legal_moves
+        .iter()
+        .any(|m| m.piece.piece_type == parts.piece_type && m.to == parts.to);
+
+    if reaches_target {
+        let from = match (parts.from_col, parts.from_row) {
+            (Some(col), Some(row)) => Position::new(row, col).map(|p| format!(" on {}", p)),
+            (Some(col), None) => Some(format!(" on the {}-file", (b'a' + col as u8) as char)),
+            (None, Some(row)) => Some(format!(" on rank {}", row + 1)),
+            (None, None) => None,
+        };
+        match from {
+            Some(from) => format!("No {}{} can move to {}", name, from, target),
+            None => format!("No legal {} move to {} matches", name, target),
+        }
+    } else {
+        format!("No {} can legally move to {}", name, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{pgn, Board, Color, Game, GameMode, Piece};
+
+    fn pos(s: &str) -> Position {
+        Position::from_algebraic(s).unwrap()
+    }
+
+    fn play(moves: &[&str]) -> Game {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 0);
+        for san in moves {
+            let mov = parse_san(san, &game.get_legal_moves()).unwrap();
+            game.make_move(mov).unwrap();
+        }
+        game
+    }
+
+    fn board_with(pieces: &[(&str, PieceType, Color)]) -> Board {
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position::new(row, col).unwrap(), None);
+            }
+        }
+        board.white_can_castle_kingside = false;
+        board.white_can_castle_queenside = false;
+        board.black_can_castle_kingside = false;
+        board.black_can_castle_queenside = false;
+        for &(square, piece_type, color) in pieces {
+            board.set_piece(pos(square), Some(Piece::new(piece_type, color)));
+        }
+        board
+    }
+
+    fn err(input: &str, legal_moves: &[Move]) -> String {
+        match parse_san(input, legal_moves) {
+            Err(SanError::Invalid(message)) => message,
+            other => panic!("Expected an error for '{}', got {:?}", input, other),
+        }
+    }
+
+    /// Every legal move's SAN must parse back to exactly that move
+    fn assert_round_trip(board: &Board, color: Color) {
+        let legal = board.generate_legal_moves(color);
+        assert!(!legal.is_empty());
+
+        for mov in &legal {
+            let san = pgn::to_san(board, mov);
+            assert_eq!(parse_san(&san, &legal), Ok(*mov), "round trip of {}", san);
+            assert_eq!(parse_san(&format!("{}+", san), &legal), Ok(*mov), "round trip of {}+", san);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_opening_positions() {
+        assert_round_trip(&Board::new(), Color::White);
+
+        let game = play(&["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+        assert_round_trip(&game.board, Color::White);
+
+        let game = play(&["d4", "d5", "c4", "dxc4", "e4"]);
+        assert_round_trip(&game.board, Color::Black);
+    }
+
+    #[test]
+    fn test_round_trip_castling_and_en_passant() {
+        let game = play(&["e4", "a6", "e5", "a5", "Nf3", "a4", "Bc4", "d5"]);
+        assert_round_trip(&game.board, Color::White);
+        let legal = game.get_legal_moves();
+        assert_eq!(parse_san("exd6", &legal).unwrap().move_type, MoveType::EnPassant);
+        assert_eq!(parse_san("O-O", &legal).unwrap().move_type, MoveType::Castle);
+        assert_eq!(parse_san("0-0", &legal), parse_san("O-O", &legal));
+        assert!(err("O-O-O", &legal).contains("queenside"));
+    }
+
+    #[test]
+    fn test_round_trip_promotions_and_disambiguation() {
+        // Pushes and captures into promotion; three queens and two rooks share targets
+        let board = board_with(&[
+            ("e1", PieceType::King, Color::White),
+            ("g7", PieceType::Pawn, Color::White),
+            ("a1", PieceType::Queen, Color::White),
+            ("a3", PieceType::Queen, Color::White),
+            ("c1", PieceType::Queen, Color::White),
+            ("d4", PieceType::Rook, Color::White),
+            ("d6", PieceType::Rook, Color::White),
+            ("h8", PieceType::Rook, Color::Black),
+            ("h5", PieceType::King, Color::Black),
+        ]);
+        assert_round_trip(&board, Color::White);
+
+        let legal = board.generate_legal_moves(Color::White);
+        assert_eq!(pgn::to_san(&board, &parse_san("Qa1b2", &legal).unwrap()), "Qa1b2");
+        assert_eq!(parse_san("gxh8=N", &legal).unwrap().move_type, MoveType::Promotion(PieceType::Knight));
+        assert_eq!(parse_san("R6d5", &legal).unwrap().from, pos("d6"));
+    }
+
+    #[test]
+    fn test_ambiguous_move_lists_candidates() {
+        let board = board_with(&[
+            ("e1", PieceType::King, Color::White),
+            ("b1", PieceType::Knight, Color::White),
+            ("f3", PieceType::Knight, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        let legal = board.generate_legal_moves(Color::White);
+
+        match parse_san("Nd2", &legal) {
+            Err(SanError::Ambiguous(candidates)) => {
+                let mut sans: Vec<String> = candidates.iter().map(|m| pgn::to_san(&board, m)).collect();
+                sans.sort();
+                assert_eq!(sans, ["Nbd2", "Nfd2"]);
+            }
+            other => panic!("Expected ambiguity, got {:?}", other),
+        }
+        assert_eq!(parse_san("Nfd2", &legal).unwrap().from, pos("f3"));
+    }
+
+    #[test]
+    fn test_rejects_ill_formed_input() {
+        let legal = Board::new().generate_legal_moves(Color::White);
+
+        assert_eq!(err("", &legal), "Empty move");
+        assert!(err("Nf3++", &legal).contains("more than one check mark"));
+        assert!(err("nf3", &legal).contains("write 'Nf3'"));
+        assert!(err("Pe4", &legal).contains("write 'e4'"));
+        assert!(err("Nf", &legal).contains("does not end in a square"));
+        assert!(err("Zf3", &legal).contains("does not start a move"));
+        assert!(err("e2e4x", &legal).contains("does not end in a square"));
+        assert!(err("ee4", &legal).contains("('e4')"));
+        assert!(err("bc4", &legal).contains("write 'Bc4'"));
+        assert!(err("e4=Q", &legal).contains("last rank"));
+        assert!(err("Nf3=Q", &legal).contains("Only pawns"));
+        assert!(err("N11f3", &legal).contains("Cannot read"));
+    }
+
+    #[test]
+    fn test_rejects_illegal_moves_with_reasons() {
+        let legal = Board::new().generate_legal_moves(Color::White);
+
+        assert_eq!(err("e5", &legal), "No pawn can legally move to e5");
+        assert_eq!(err("Nd4", &legal), "No knight can legally move to d4");
+        assert_eq!(err("Ndf3", &legal), "No knight on the d-file can move to f3");
+        assert_eq!(err("Nxf3", &legal), "There is nothing to capture on f3");
+        assert_eq!(err("O-O", &legal), "Castling kingside is not legal here");
+
+        let game = play(&["e4", "d5"]);
+        assert_eq!(
+            err("ed5", &game.get_legal_moves()),
+            "Pawn moves are written as just the square ('d5'), captures with the pawn's file ('exd5')"
+        );
+        assert_eq!(err("xd5", &game.get_legal_moves()), "'x' does not start a move; expected a piece letter or a file");
+        assert_eq!(
+            parse_san("exd5", &game.get_legal_moves()).unwrap().captured,
+            Some(Piece::new(PieceType::Pawn, Color::Black))
+        );
+
+        let board = board_with(&[
+            ("e1", PieceType::King, Color::White),
+            ("b7", PieceType::Pawn, Color::White),
+            ("e8", PieceType::King, Color::Black),
+        ]);
+        assert!(err("b8", &board.generate_legal_moves(Color::White)).contains("b8=Q"));
+    }
+}
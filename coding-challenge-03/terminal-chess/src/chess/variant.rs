@@ -0,0 +1,286 @@
+use super::{Board, Color, Move, Position};
+use serde::{Deserialize, Serialize};
+
+/// Checks needed to win Three-check
+pub const CHECKS_TO_WIN: u8 = 3;
+
+/// Score for reaching a variant objective, on the same scale as checkmate
+const OBJECTIVE_SCORE: i32 = 100000;
+
+/// Checks delivered by each side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CheckCount {
+    pub white: u8,
+    pub black: u8,
+}
+
+impl CheckCount {
+    /// Checks delivered by `color`
+    pub fn by(&self, color: Color) -> u8 {
+        match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        }
+    }
+
+    /// Count a check delivered by `color`
+    pub fn record(&mut self, color: Color) {
+        match color {
+            Color::White => self.white += 1,
+            Color::Black => self.black += 1,
+        }
+    }
+}
+
+/// Rule hooks layered on top of standard chess
+///
+/// Every hook defaults to standard behaviour, so a variant only overrides
+/// what it changes.
+pub trait Variant {
+    /// Name used in menus and the PGN `Variant` tag
+    fn name(&self) -> &'static str;
+
+    /// Adjust the legal moves for `color` (standard chess keeps them all)
+    fn filter_moves(&self, _board: &Board, _color: Color, moves: Vec<Move>) -> Vec<Move> {
+        moves
+    }
+
+    /// Update variant state after `mover` has moved
+    fn after_move(&self, _board: &Board, _mover: Color, _checks: &mut CheckCount) {}
+
+    /// Winner by a variant objective, if either side has reached it
+    fn winner(&self, _board: &Board, _checks: &CheckCount) -> Option<Color> {
+        None
+    }
+
+    /// Evaluation bonus for progress towards the objective, from `color`'s side
+    fn evaluate(&self, _board: &Board, _checks: &CheckCount, _color: Color) -> i32 {
+        0
+    }
+
+    /// Extra status line for the game display
+    fn status(&self, _checks: &CheckCount) -> Option<String> {
+        None
+    }
+}
+
+/// Standard chess: no extra rules
+pub struct Standard;
+
+impl Variant for Standard {
+    fn name(&self) -> &'static str {
+        "Standard"
+    }
+}
+
+/// King of the Hill: moving your king to a center square also wins
+pub struct KingOfTheHill;
+
+impl KingOfTheHill {
+    /// Moves the king needs to reach the nearest center square
+    fn distance_to_hill(board: &Board, color: Color) -> i32 {
+        board
+            .find_king(color)
+            .map(|king| {
+                let rank = (3 - king.row).max(king.row - 4).max(0);
+                let file = (3 - king.col).max(king.col - 4).max(0);
+                rank.max(file) as i32
+            })
+            .unwrap_or(i32::MAX)
+    }
+
+    /// Whether a square is one of d4, e4, d5, e5
+    pub fn is_hill(pos: Position) -> bool {
+        (3..=4).contains(&pos.row) && (3..=4).contains(&pos.col)
+    }
+}
+
+impl Variant for KingOfTheHill {
+    fn name(&self) -> &'static str {
+        "King of the Hill"
+    }
+
+    fn winner(&self, board: &Board, _checks: &CheckCount) -> Option<Color> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| board.find_king(color).is_some_and(Self::is_hill))
+    }
+
+    fn evaluate(&self, board: &Board, _checks: &CheckCount, color: Color) -> i32 {
+        // Worth more than a queen per step once the king is close
+        let progress = |c| match Self::distance_to_hill(board, c) {
+            1 => 1500,
+            2 => 400,
+            3 => 100,
+            _ => 0,
+        };
+
+        progress(color) - progress(color.opposite())
+    }
+
+    fn status(&self, _checks: &CheckCount) -> Option<String> {
+        Some("Objective: king to d4, e4, d5 or e5".to_string())
+    }
+}
+
+/// Three-check: delivering a third check also wins
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck {
+    fn name(&self) -> &'static str {
+        "Three-check"
+    }
+
+    fn after_move(&self, board: &Board, mover: Color, checks: &mut CheckCount) {
+        if board.is_in_check(mover.opposite()) {
+            checks.record(mover);
+        }
+    }
+
+    fn winner(&self, _board: &Board, checks: &CheckCount) -> Option<Color> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| checks.by(color) >= CHECKS_TO_WIN)
+    }
+
+    fn evaluate(&self, _board: &Board, checks: &CheckCount, color: Color) -> i32 {
+        let progress = |c| match checks.by(c) {
+            0 => 0,
+            1 => 400,
+            _ => 1500,
+        };
+
+        progress(color) - progress(color.opposite())
+    }
+
+    fn status(&self, checks: &CheckCount) -> Option<String> {
+        Some(format!(
+            "Checks: White {}/{}, Black {}/{}",
+            checks.white, CHECKS_TO_WIN, checks.black, CHECKS_TO_WIN
+        ))
+    }
+}
+
+/// Selectable variant (stored in saved games)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VariantKind {
+    #[default]
+    Standard,
+    KingOfTheHill,
+    ThreeCheck,
+}
+
+impl VariantKind {
+    /// Rules for this variant
+    pub fn rules(&self) -> &'static dyn Variant {
+        match self {
+            VariantKind::Standard => &Standard,
+            VariantKind::KingOfTheHill => &KingOfTheHill,
+            VariantKind::ThreeCheck => &ThreeCheck,
+        }
+    }
+
+    /// Parse a command-line name such as `koth` or `three-check`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "standard" | "chess" => Some(VariantKind::Standard),
+            "koth" | "kingofthehill" => Some(VariantKind::KingOfTheHill),
+            "3check" | "threecheck" => Some(VariantKind::ThreeCheck),
+            _ => None,
+        }
+    }
+
+    /// Score for a decided variant objective, from `color`'s side
+    ///
+    /// `ply` is the search depth reached so far, so quicker wins score higher.
+    pub fn objective_score(&self, board: &Board, checks: &CheckCount, color: Color, ply: i32) -> Option<i32> {
+        self.rules().winner(board, checks).map(|winner| {
+            if winner == color {
+                OBJECTIVE_SCORE - ply
+            } else {
+                -OBJECTIVE_SCORE + ply
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{Piece, PieceType};
+
+    fn kings_only(white_king: &str, black_king: &str) -> Board {
+        let mut board = Board::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                board.set_piece(Position::new(row, col).unwrap(), None);
+            }
+        }
+        board.set_piece(Position::from_algebraic(white_king).unwrap(), Some(Piece::new(PieceType::King, Color::White)));
+        board.set_piece(Position::from_algebraic(black_king).unwrap(), Some(Piece::new(PieceType::King, Color::Black)));
+        board
+    }
+
+    #[test]
+    fn test_variant_names_parse() {
+        assert_eq!(VariantKind::from_name("koth"), Some(VariantKind::KingOfTheHill));
+        assert_eq!(VariantKind::from_name("Three-Check"), Some(VariantKind::ThreeCheck));
+        assert_eq!(VariantKind::from_name("3check"), Some(VariantKind::ThreeCheck));
+        assert_eq!(VariantKind::from_name("standard"), Some(VariantKind::Standard));
+        assert_eq!(VariantKind::from_name("atomic"), None);
+    }
+
+    #[test]
+    fn test_king_of_the_hill_winner() {
+        let board = kings_only("e4", "e8");
+        assert_eq!(KingOfTheHill.winner(&board, &CheckCount::default()), Some(Color::White));
+
+        let board = kings_only("e3", "d5");
+        assert_eq!(KingOfTheHill.winner(&board, &CheckCount::default()), Some(Color::Black));
+
+        assert_eq!(KingOfTheHill.winner(&Board::new(), &CheckCount::default()), None);
+    }
+
+    #[test]
+    fn test_king_of_the_hill_prefers_nearer_king() {
+        let checks = CheckCount::default();
+        let near = kings_only("e3", "e8");
+        let far = kings_only("e1", "e8");
+
+        assert!(KingOfTheHill.evaluate(&near, &checks, Color::White) > KingOfTheHill.evaluate(&far, &checks, Color::White));
+        assert!(KingOfTheHill.evaluate(&near, &checks, Color::Black) < 0);
+    }
+
+    #[test]
+    fn test_three_check_counts_and_wins() {
+        let board = kings_only("e1", "e8");
+        let mut checks = CheckCount::default();
+
+        // No check on the board: nothing recorded
+        ThreeCheck.after_move(&board, Color::White, &mut checks);
+        assert_eq!(checks, CheckCount::default());
+
+        let mut board = board;
+        board.set_piece(Position::from_algebraic("e5").unwrap(), Some(Piece::new(PieceType::Rook, Color::White)));
+        for _ in 0..2 {
+            ThreeCheck.after_move(&board, Color::White, &mut checks);
+        }
+        assert_eq!(checks.white, 2);
+        assert_eq!(ThreeCheck.winner(&board, &checks), None);
+
+        ThreeCheck.after_move(&board, Color::White, &mut checks);
+        assert_eq!(ThreeCheck.winner(&board, &checks), Some(Color::White));
+        assert_eq!(ThreeCheck.status(&checks).unwrap(), "Checks: White 3/3, Black 0/3");
+    }
+
+    #[test]
+    fn test_standard_has_no_objective() {
+        let board = kings_only("e4", "d5");
+        let checks = CheckCount { white: 5, black: 5 };
+
+        assert_eq!(Standard.winner(&board, &checks), None);
+        assert_eq!(Standard.evaluate(&board, &checks, Color::White), 0);
+        assert_eq!(VariantKind::Standard.objective_score(&board, &checks, Color::White, 0), None);
+        assert_eq!(Standard.filter_moves(&Board::new(), Color::White, Board::new().generate_legal_moves(Color::White)).len(), 20);
+    }
+}
@@ -2,6 +2,8 @@ pub mod chess;
 pub mod ai;
 pub mod ui;
 
-pub use chess::{Board, Color, Game, GameMode, GameState, Move, Piece, PieceType, Position};
+pub use chess::{
+    Board, Color, Game, GameMode, GameState, Handicap, MaterialOdds, Move, Piece, PieceType, Position, VariantKind,
+};
 pub use ai::ChessAI;
 pub use ui::TerminalUI;
@@ -1,14 +1,76 @@
-use terminal_chess::{ChessAI, Color, Game, GameMode, GameState, TerminalUI};
+use terminal_chess::ai::{piece_material, Adjudication, AdjudicationConfig, Adjudicator, Coach, Evaluation, SearchStats, SkillRng};
+use terminal_chess::chess::correspondence::{self, CorrGame};
+use terminal_chess::chess::{describe_move, BoardQuery};
+use terminal_chess::{ChessAI, Color, Game, GameMode, GameState, TerminalUI, VariantKind};
 use terminal_chess::ui::terminal::MenuChoice;
+use terminal_chess::ui::Settings;
 use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+/// Command-line options
+struct Options {
+    variant: VariantKind,
+    accessible: bool,
+    /// AI search threads
+    threads: usize,
+    /// Directory of Syzygy endgame tables
+    syzygy_path: Option<PathBuf>,
+    /// When the AI resigns or agrees to a draw
+    adjudication: AdjudicationConfig,
+}
+
+/// Environment variable holding the correspondence player token
+const TOKEN_ENV: &str = "TERMINAL_CHESS_TOKEN";
+
+const CORR_USAGE: &str = "Usage: terminal-chess corr new --white NAME --black NAME [--as white|black] [--days N] [--variant NAME] [--out FILE]
+       terminal-chess corr move FILE MOVE
+       terminal-chess corr show FILE
+       (new and move take --token TOKEN, else $TERMINAL_CHESS_TOKEN, else ~/.terminal-chess-token)";
 
 fn main() -> io::Result<()> {
-    let ui = TerminalUI::new();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "corr") {
+        if let Err(e) = run_corr(&args[1..]) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let options = match parse_args(args.into_iter()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: terminal-chess [--variant standard|koth|three-check] [--a11y] [--threads N] [--syzygy-path DIR]");
+            eprintln!("                     [--tournament] [--resign-threshold PAWNS] [--resign-moves N] [--draw-window PAWNS]");
+            eprintln!("       terminal-chess corr new|move|show ...");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(path) = &options.syzygy_path {
+        report_syzygy(path);
+    }
+
+    let settings_path = Settings::default_path();
+    let (settings, warnings) = match &settings_path {
+        Some(path) => Settings::load(path),
+        None => (Settings::default(), Vec::new()),
+    };
+
+    let ui = TerminalUI::new().with_accessibility(options.accessible).with_settings(settings, settings_path);
 
     // Enable raw mode for better terminal control
     ui.enable_raw_mode()?;
 
-    let result = run_game(&ui);
+    // Shown before the menu clears the screen
+    if !warnings.is_empty() {
+        ui.show_message(&format!("Warning: {}", warnings.join("\n  Warning: ")))?;
+    }
+
+    let result = run_game(&ui, options.variant, options.threads, options.adjudication);
 
     // Disable raw mode before exiting
     ui.disable_raw_mode()?;
@@ -16,7 +78,244 @@ fn main() -> io::Result<()> {
     result
 }
 
-fn run_game(ui: &TerminalUI) -> io::Result<()> {
+/// Run a `corr` subcommand: correspondence games played by exchanging a file
+fn run_corr(args: &[String]) -> Result<(), String> {
+    let (command, args) = args.split_first().ok_or(CORR_USAGE)?;
+    let (positional, flags) = split_flags(args)?;
+    let flag = |name: &str| flags.iter().find(|(flag, _)| flag == name).map(|(_, value)| value.as_str());
+    let allow_flags = |allowed: &[&str]| match flags.iter().find(|(flag, _)| !allowed.contains(&flag.as_str())) {
+        Some((flag, _)) => Err(format!("Unknown option for corr {}: --{}\n{}", command, flag, CORR_USAGE)),
+        None => Ok(()),
+    };
+    let now = correspondence::unix_now();
+
+    match (command.as_str(), positional.as_slice()) {
+        ("new", []) => {
+            allow_flags(&["white", "black", "as", "days", "variant", "out", "token"])?;
+            let white = flag("white").ok_or("corr new needs --white NAME")?;
+            let black = flag("black").ok_or("corr new needs --black NAME")?;
+            let creator = match flag("as").unwrap_or("white").to_lowercase().as_str() {
+                "white" => Color::White,
+                "black" => Color::Black,
+                other => return Err(format!("--as must be white or black, not {}", other)),
+            };
+            let days = flag("days")
+                .map(|days| days.parse::<u32>().map_err(|_| format!("Invalid number of days: {}", days)))
+                .transpose()?;
+            let variant = match flag("variant") {
+                Some(name) => VariantKind::from_name(name).ok_or_else(|| format!("Unknown variant: {}", name))?,
+                None => VariantKind::Standard,
+            };
+
+            let token = player_token(flag("token"), true)?.ok_or("No player token")?;
+            let game = CorrGame::new(white, black, variant, days, creator, &token, now).map_err(|e| e.to_string())?;
+            let path = flag("out").map_or_else(|| PathBuf::from(game.default_filename()), PathBuf::from);
+            if path.exists() {
+                return Err(format!("{} already exists", path.display()));
+            }
+
+            game.save(&path).map_err(|e| e.to_string())?;
+            let first = game.player(Color::White);
+            eprintln!("Created {}; {} moves first", path.display(), first);
+            print!("{}", game.to_file_string());
+        }
+        ("move", [file, san]) => {
+            allow_flags(&["token"])?;
+            let token = player_token(flag("token"), true)?.ok_or("No player token")?;
+            let mut game = CorrGame::load(file).map_err(|e| e.to_string())?;
+            game.play(san, &token, now).map_err(|e| e.to_string())?;
+            game.save(file).map_err(|e| e.to_string())?;
+
+            eprint!("{}", game.render(now));
+            if !game.game().is_game_over() {
+                eprintln!("\n  Send {} to {}", file, game.player(game.game().current_player));
+            }
+            print!("{}", game.to_file_string());
+        }
+        ("show", [file]) => {
+            allow_flags(&["token"])?;
+            let game = CorrGame::load(file).map_err(|e| e.to_string())?;
+            print!("{}", game.render(now));
+
+            // Only check signatures with a token the player already has
+            if let Some(token) = player_token(flag("token"), false)? {
+                match game.verify(&token).map_err(|e| e.to_string())? {
+                    Some(color) => println!("  Your moves as {} verify", game.player(color)),
+                    None => println!("  Your token has no seat in this game"),
+                }
+            }
+        }
+        _ => return Err(CORR_USAGE.to_string()),
+    }
+
+    Ok(())
+}
+
+/// `--name value` options as (name, value)
+type Flags = Vec<(String, String)>;
+
+/// Split `--name value` and `--name=value` options from positional arguments
+fn split_flags(args: &[String]) -> Result<(Vec<String>, Flags), String> {
+    let mut positional = Vec::new();
+    let mut flags = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => {
+                let (name, value) = match flag.split_once('=') {
+                    Some((name, value)) => (name.to_string(), value.to_string()),
+                    None => (
+                        flag.to_string(),
+                        args.next().ok_or_else(|| format!("--{} needs a value", flag))?.clone(),
+                    ),
+                };
+                flags.push((name, value));
+            }
+            None => positional.push(arg.clone()),
+        }
+    }
+    Ok((positional, flags))
+}
+
+/// The player's correspondence token: `--token`, else the environment, else
+/// the token file in the home directory, created if `create` is set
+fn player_token(flag: Option<&str>, create: bool) -> Result<Option<String>, String> {
+    if let Some(token) = flag.map(str::to_string).or_else(|| std::env::var(TOKEN_ENV).ok()) {
+        return Ok(Some(token));
+    }
+
+    let home = std::env::var_os("HOME").ok_or("No --token given and no home directory for a token file")?;
+    let path = PathBuf::from(home).join(".terminal-chess-token");
+    match std::fs::read_to_string(&path) {
+        Ok(token) => Ok(Some(token.trim().to_string())),
+        Err(_) if create => {
+            let token = correspondence::generate_token();
+            std::fs::write(&path, format!("{}\n", token)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            eprintln!("Created your player token in {}; keep it private", path.display());
+            Ok(Some(token))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Say which Syzygy tables were found; the game goes on without them either way
+#[cfg(feature = "syzygy")]
+fn report_syzygy(path: &std::path::Path) {
+    use terminal_chess::ai::syzygy::SyzygyTables;
+
+    match SyzygyTables::open(path) {
+        Ok(tables) if tables.is_empty() => {
+            eprintln!("No Syzygy tables in {}; endgames will be searched", path.display())
+        }
+        Ok(tables) => eprintln!(
+            "Found {} Syzygy tables (up to {} pieces) in {}, but this build has no table decoder; endgames will be searched",
+            tables.len(),
+            tables.max_pieces(),
+            path.display()
+        ),
+        Err(e) => eprintln!("Cannot read Syzygy tables in {}: {}; endgames will be searched", path.display(), e),
+    }
+}
+
+#[cfg(not(feature = "syzygy"))]
+fn report_syzygy(path: &std::path::Path) {
+    eprintln!("Ignoring --syzygy-path {}: built without the syzygy feature", path.display());
+}
+
+/// Parse `--variant <name>` (or `--variant=<name>`), `--threads <n>`, `--syzygy-path <dir>`, `--a11y`
+/// and the resignation and draw settings from the command line
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut variant = VariantKind::Standard;
+    let mut accessible = false;
+    let mut threads = 1;
+    let mut syzygy_path = None;
+    let mut adjudication = AdjudicationConfig::default();
+    let mut tournament = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--a11y" {
+            accessible = true;
+            continue;
+        }
+        if arg == "--tournament" {
+            tournament = true;
+            continue;
+        }
+
+        if let Some(pawns) = flag_value(&arg, "--resign-threshold", &mut args)? {
+            adjudication.resign_threshold = parse_pawns(&pawns)?;
+            continue;
+        }
+        if let Some(pawns) = flag_value(&arg, "--draw-window", &mut args)? {
+            adjudication.draw_window = parse_pawns(&pawns)?.abs();
+            continue;
+        }
+        if let Some(count) = flag_value(&arg, "--resign-moves", &mut args)? {
+            adjudication.resign_moves = match count.parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(format!("Invalid number of moves: {}", count)),
+            };
+            continue;
+        }
+
+        let thread_count = if arg == "--threads" {
+            Some(args.next().ok_or("--threads needs a number")?)
+        } else {
+            arg.strip_prefix("--threads=").map(str::to_string)
+        };
+        if let Some(count) = thread_count {
+            threads = match count.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(format!("Invalid thread count: {}", count)),
+            };
+            continue;
+        }
+
+        if arg == "--syzygy-path" {
+            syzygy_path = Some(PathBuf::from(args.next().ok_or("--syzygy-path needs a directory")?));
+            continue;
+        }
+        if let Some(path) = arg.strip_prefix("--syzygy-path=") {
+            syzygy_path = Some(PathBuf::from(path));
+            continue;
+        }
+
+        let name = if arg == "--variant" {
+            args.next().ok_or("--variant needs a name")?
+        } else if let Some(name) = arg.strip_prefix("--variant=") {
+            name.to_string()
+        } else {
+            return Err(format!("Unknown argument: {}", arg));
+        };
+
+        variant = VariantKind::from_name(&name).ok_or_else(|| format!("Unknown variant: {}", name))?;
+    }
+
+    if tournament {
+        adjudication.enabled = false;
+    }
+
+    Ok(Options { variant, accessible, threads, syzygy_path, adjudication })
+}
+
+/// The value of `--name <value>` or `--name=<value>` if `arg` is that option
+fn flag_value(arg: &str, name: &str, args: &mut impl Iterator<Item = String>) -> Result<Option<String>, String> {
+    if arg == name {
+        return args.next().map(Some).ok_or_else(|| format!("{} needs a value", name));
+    }
+    Ok(arg.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')).map(str::to_string))
+}
+
+/// Parse a score in pawns, such as `-9` or `0.25`, into centipawns
+fn parse_pawns(text: &str) -> Result<i32, String> {
+    match text.parse::<f64>() {
+        Ok(pawns) if pawns.is_finite() => Ok((pawns * 100.0).round() as i32),
+        _ => Err(format!("Invalid number of pawns: {}", text)),
+    }
+}
+
+fn run_game(ui: &TerminalUI, variant: VariantKind, threads: usize, adjudication: AdjudicationConfig) -> io::Result<()> {
     loop {
         let choice = ui.show_menu()?;
 
@@ -26,20 +325,21 @@ fn run_game(ui: &TerminalUI) -> io::Result<()> {
                 break;
             }
             MenuChoice::PlayerVsPlayer => {
-                let mut game = Game::new(GameMode::PlayerVsPlayer, 0);
-                play_game(ui, &mut game)?;
+                let mut game = Game::new(GameMode::PlayerVsPlayer, 0).with_variant(variant);
+                play_game(ui, &mut game, threads, adjudication)?;
             }
             MenuChoice::PlayerVsAI => {
                 let difficulty = ui.get_difficulty()?;
-                let mut game = Game::new(GameMode::PlayerVsAI, difficulty);
-                play_game(ui, &mut game)?;
+                let handicap = ui.get_handicap()?;
+                let mut game = Game::with_handicap(GameMode::PlayerVsAI, difficulty, handicap).with_variant(variant);
+                play_game(ui, &mut game, threads, adjudication)?;
             }
             MenuChoice::LoadGame => {
                 let filename = ui.get_filename("Enter filename to load")?;
                 match Game::load_from_file(&filename) {
                     Ok(mut game) => {
                         ui.show_message(&format!("Game loaded from {}", filename))?;
-                        play_game(ui, &mut game)?;
+                        play_game(ui, &mut game, threads, adjudication)?;
                     }
                     Err(e) => {
                         ui.show_message(&format!("Failed to load game: {}", e))?;
@@ -52,12 +352,21 @@ fn run_game(ui: &TerminalUI) -> io::Result<()> {
     Ok(())
 }
 
-fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
+fn play_game(ui: &TerminalUI, game: &mut Game, threads: usize, adjudication: AdjudicationConfig) -> io::Result<()> {
     let mut last_move = None;
+    let mut rng = SkillRng::from_time();
+    let coach = Coach::new();
+    // Search statistics are collected and shown after each engine move while on
+    let mut debug = false;
+    let mut last_stats: Option<SearchStats> = None;
+    // The engine's latest assessment from the player's (White's) side
+    let mut evaluation: Option<Evaluation> = None;
+    // Decides when the AI resigns or offers a draw
+    let mut adjudicator = Adjudicator::new(adjudication);
 
     loop {
         // Display the board
-        ui.display_game(game, last_move)?;
+        ui.display_game(game, last_move, evaluation)?;
 
         // Check if game is over
         if game.is_game_over() {
@@ -68,30 +377,121 @@ fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
                         if winner == Color::White { "White" } else { "Black" }
                     ))?;
                 }
+                GameState::VariantWin(winner) => {
+                    ui.show_message(&format!(
+                        "{}! {} wins!",
+                        game.variant.rules().name(),
+                        if winner == Color::White { "White" } else { "Black" }
+                    ))?;
+                }
                 GameState::Stalemate => {
                     ui.show_message("Stalemate! The game is a draw.")?;
                 }
                 GameState::Draw => {
                     ui.show_message("Draw by 50-move rule.")?;
                 }
+                GameState::Resignation(winner) => {
+                    let (winner, loser) = if winner == Color::White { ("White", "Black") } else { ("Black", "White") };
+                    ui.show_message(&format!("{} resigns. {} wins!", loser, winner))?;
+                }
+                GameState::DrawAgreed => {
+                    ui.show_message("Draw agreed.")?;
+                }
                 _ => {}
             }
             break;
         }
 
+        // Whether the AI offers a draw once its move is on the board
+        let mut ai_offers_draw = false;
+
         // Get move based on game mode
         let mov_option = if game.mode == GameMode::PlayerVsAI && game.current_player == Color::Black {
             // AI's turn
             ui.show_thinking()?;
-            let ai = ChessAI::new(game.ai_difficulty);
-            ai.find_best_move(&game.board, Color::Black)
+            let (sender, receiver) = mpsc::channel();
+            let ai = ChessAI::with_variant(game.ai_difficulty, game.variant, game.checks)
+                .with_threads(threads)
+                .with_progress(sender);
+            evaluation = None;
+            // Score and depth of the deepest finished iteration, from the AI's side
+            let mut searched = None;
+
+            // Search in the background while this thread keeps the thinking line up to date
+            let (board, skill, rng) = (&game.board, game.handicap.skill, &mut rng);
+            let chosen = thread::scope(|scope| -> io::Result<_> {
+                let search = scope.spawn(move || {
+                    if debug {
+                        ai.choose_move_with_stats(board, Color::Black, skill, rng)
+                    } else {
+                        ai.choose_move(board, Color::Black, skill, rng).map(|mov| (mov, None))
+                    }
+                });
+
+                // The channel closes when the search drops the AI; skip straight to the newest report
+                while let Ok(first) = receiver.recv() {
+                    let mut progress = first;
+                    for report in std::iter::once(first).chain(receiver.try_iter()) {
+                        if report.completed {
+                            searched = Some((report.score, report.depth));
+                        }
+                        progress = report;
+                    }
+                    let assessment = progress.evaluation_for(Color::Black, Color::White);
+                    evaluation = Some(assessment);
+                    ui.show_search_progress(game, &progress, assessment)?;
+                }
+                Ok(search.join().expect("search thread panicked"))
+            })?;
+
+            if debug {
+                last_stats = chosen.as_ref().and_then(|(_, stats)| stats.clone());
+                match &last_stats {
+                    Some(stats) => ui.show_message(&format_stats(stats))?,
+                    None => ui.show_message("No search statistics: the AI only keeps them at full skill")?,
+                }
+            }
+
+            let decision = searched.and_then(|(score, depth)| adjudicator.observe(score, depth, piece_material(&game.board)));
+            match decision {
+                Some(Adjudication::Resign) => {
+                    game.resign(Color::Black);
+                    None
+                }
+                Some(Adjudication::OfferDraw) => {
+                    ai_offers_draw = true;
+                    chosen.map(|(mov, _)| mov)
+                }
+                None => chosen.map(|(mov, _)| mov),
+            }
         } else {
             // Human's turn
             loop {
-                let input = ui.get_move("Enter move")?;
+                let input = ui.get_move("Enter move", &game.legal_moves_san())?;
+
+                if let Some(query) = BoardQuery::parse(&input.to_lowercase()) {
+                    match query {
+                        Ok(query) => ui.announce(&query.answer(&game.board))?,
+                        Err(e) => ui.announce(&e)?,
+                    }
+                    continue;
+                }
 
                 match ui.parse_move(&input, game) {
-                    Ok(mov) => break Some(mov),
+                    Ok(mov) => {
+                        // Coaching: warn before confirming a move that hangs material
+                        if game.mode == GameMode::PlayerVsAI
+                            && game.handicap.coaching
+                            && game.get_legal_moves().contains(&mov)
+                        {
+                            if let Some(warning) = coach.check_move(&game.board, &mov) {
+                                if !ui.confirm(&warning.message())? {
+                                    continue;
+                                }
+                            }
+                        }
+                        break Some(mov);
+                    }
                     Err(e) => {
                         if e == "UNDO" {
                             // Undo move
@@ -110,6 +510,8 @@ fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
                                 }
                             }
                             last_move = None;
+                            evaluation = None;
+                            adjudicator.reset();
                             break None;
                         } else if e == "SAVE" {
                             // Save game
@@ -119,6 +521,62 @@ fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
                                 Err(e) => ui.show_message(&format!("Failed to save: {}", e))?,
                             }
                             continue;
+                        } else if e == "PGN" {
+                            let filename = ui.get_filename("Enter filename for PGN export")?;
+                            match game.save_pgn(&filename) {
+                                Ok(_) => ui.show_message(&format!("PGN exported to {}", filename))?,
+                                Err(e) => ui.show_message(&format!("Failed to export: {}", e))?,
+                            }
+                            continue;
+                        } else if e == "COACH" && game.mode == GameMode::PlayerVsAI {
+                            game.handicap.coaching = !game.handicap.coaching;
+                            ui.show_message(&format!(
+                                "Coaching {}",
+                                if game.handicap.coaching { "enabled" } else { "disabled" }
+                            ))?;
+                            continue;
+                        } else if e == "DEBUG ON" || e == "DEBUG OFF" {
+                            debug = e == "DEBUG ON";
+                            ui.show_message(&format!("Search debug output {}", if debug { "on" } else { "off" }))?;
+                            continue;
+                        } else if e == "STATS" {
+                            match &last_stats {
+                                Some(stats) => ui.show_message(&format_stats(stats))?,
+                                None => ui.show_message("No search statistics yet; enable them with `debug on`")?,
+                            }
+                            continue;
+                        } else if e == "RESIGN" {
+                            if ui.confirm("Resign the game?")? {
+                                game.resign(game.current_player);
+                                break None;
+                            }
+                            continue;
+                        } else if e == "DRAW" {
+                            let accepted = if game.mode == GameMode::PlayerVsAI {
+                                adjudicator.accepts_draw()
+                            } else {
+                                let (from, to) = match game.current_player {
+                                    Color::White => ("White", "Black"),
+                                    Color::Black => ("Black", "White"),
+                                };
+                                ui.confirm(&format!("{} offers a draw. {}, do you accept?", from, to))?
+                            };
+                            if accepted {
+                                game.agree_draw();
+                                break None;
+                            }
+                            if game.mode == GameMode::PlayerVsAI {
+                                ui.show_message("The AI declines the draw offer.")?;
+                            }
+                            continue;
+                        } else if e == "SETTINGS" {
+                            let saved = ui.edit_settings(game, last_move)?;
+                            ui.show_message(&saved)?;
+                            // Redraw the board in the new look
+                            break None;
+                        } else if e == "MOVES" {
+                            ui.show_message(&format!("Legal moves: {}", game.legal_moves_san().join(" ")))?;
+                            continue;
                         } else if e == "QUIT" {
                             return Ok(());
                         } else {
@@ -130,12 +588,24 @@ fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
             }
         };
 
-        // If undo was performed, skip move execution
+        // If undo was performed or the game was resigned, skip move execution
         if let Some(mov) = mov_option {
+            // Describe against the position before the move
+            let announcement = ui.is_accessible().then(|| describe_move(&game.board, &mov));
+
             // Make the move
             match game.make_move(mov) {
                 Ok(_) => {
                     last_move = Some(mov);
+                    if let Some(text) = announcement {
+                        ui.announce(&text)?;
+                    }
+                    if ai_offers_draw && !game.is_game_over() {
+                        ui.display_game(game, last_move, evaluation)?;
+                        if ui.confirm("The AI offers a draw. Do you accept?")? {
+                            game.agree_draw();
+                        }
+                    }
                 }
                 Err(e) => {
                     ui.show_message(&format!("Invalid move: {}", e))?;
@@ -146,3 +616,8 @@ fn play_game(ui: &TerminalUI, game: &mut Game) -> io::Result<()> {
 
     Ok(())
 }
+
+/// Indent multi-line search statistics to line up with `show_message`
+fn format_stats(stats: &SearchStats) -> String {
+    stats.to_string().replace('\n', "\n  ")
+}
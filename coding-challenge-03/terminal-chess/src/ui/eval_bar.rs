@@ -0,0 +1,59 @@
+use crate::ai::Evaluation;
+
+/// Rows in the evaluation bar, one per board rank
+pub const BAR_HEIGHT: usize = 8;
+
+/// Advantage (in pawns) at which the bar stops growing
+const SATURATION_PAWNS: f64 = 5.0;
+
+/// Rows of the bar filled for the player, counted from the bottom
+///
+/// An even position fills half the bar. Only a forced mate fills it completely
+/// or empties it, so any other advantage leaves at least one row either way.
+pub fn bar_fill(evaluation: Evaluation, height: usize) -> usize {
+    match evaluation {
+        Evaluation::Mate(moves) if moves > 0 => height,
+        Evaluation::Mate(_) => 0,
+        Evaluation::Pawns(pawns) => {
+            let share = 0.5 + pawns.clamp(-SATURATION_PAWNS, SATURATION_PAWNS) / (2.0 * SATURATION_PAWNS);
+            let fill = (share * height as f64).round() as usize;
+            fill.clamp(1.min(height), height.saturating_sub(1))
+        }
+    }
+}
+
+/// The bar as ASCII rows from top to bottom, filled from the bottom
+pub fn render_eval_bar(evaluation: Evaluation, height: usize) -> Vec<String> {
+    let fill = bar_fill(evaluation, height);
+    (0..height).map(|row| if height - row <= fill { "|#|".to_string() } else { "| |".to_string() }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_fill_from_pawns() {
+        assert_eq!(bar_fill(Evaluation::Pawns(0.0), BAR_HEIGHT), 4);
+        assert_eq!(bar_fill(Evaluation::Pawns(1.25), BAR_HEIGHT), 5);
+        assert_eq!(bar_fill(Evaluation::Pawns(-2.5), BAR_HEIGHT), 2);
+        // Big but non-mating advantages saturate one row short of the ends
+        assert_eq!(bar_fill(Evaluation::Pawns(40.0), BAR_HEIGHT), 7);
+        assert_eq!(bar_fill(Evaluation::Pawns(-40.0), BAR_HEIGHT), 1);
+    }
+
+    #[test]
+    fn test_bar_fill_from_mate_scores() {
+        assert_eq!(bar_fill(Evaluation::from_score(100000 - 3), BAR_HEIGHT), BAR_HEIGHT);
+        assert_eq!(bar_fill(Evaluation::from_score(-100000 + 2), BAR_HEIGHT), 0);
+        assert_eq!(bar_fill(Evaluation::Mate(12), BAR_HEIGHT), BAR_HEIGHT);
+    }
+
+    #[test]
+    fn test_render_fills_from_the_bottom() {
+        let bar = render_eval_bar(Evaluation::Pawns(-2.5), 4);
+        assert_eq!(bar, vec!["| |", "| |", "| |", "|#|"]);
+        assert!(render_eval_bar(Evaluation::Mate(-1), BAR_HEIGHT).iter().all(|row| row == "| |"));
+        assert!(render_eval_bar(Evaluation::Mate(1), BAR_HEIGHT).iter().all(|row| row == "|#|"));
+    }
+}
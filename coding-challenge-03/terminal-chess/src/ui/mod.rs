@@ -1,3 +1,6 @@
+pub mod eval_bar;
+pub mod settings;
 pub mod terminal;
 
+pub use settings::Settings;
 pub use terminal::TerminalUI;
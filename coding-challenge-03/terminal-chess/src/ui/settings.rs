@@ -0,0 +1,561 @@
+use crate::chess::{Color, Piece, PieceType};
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Directory under the user config directory holding the settings file
+const SETTINGS_DIR: &str = "terminal-chess";
+
+/// Name of the settings file
+const SETTINGS_FILE: &str = "settings.toml";
+
+/// Names of the eight basic ANSI colors, in palette order
+const BASIC_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Channel level above which a true color counts as lit in the 8-color palette
+///
+/// Set above the midpoint so tan light squares and brown dark squares end up
+/// as different basic colors.
+const BASIC_THRESHOLD: u8 = 160;
+
+/// A color from the settings file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeColor {
+    /// One of the eight basic ANSI colors (0-7)
+    Basic(u8),
+    /// An entry of the 256-color palette
+    Indexed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl ThemeColor {
+    /// Parse a color name ("blue"), a 256-color index ("94") or "#rrggbb"
+    pub fn parse(text: &str) -> Option<ThemeColor> {
+        let text = text.trim();
+        if let Some(hex) = text.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            return Some(ThemeColor::Rgb(channel(0)?, channel(2)?, channel(4)?));
+        }
+        if let Ok(index) = text.parse::<u8>() {
+            return Some(ThemeColor::Indexed(index));
+        }
+        let name = text.to_lowercase();
+        BASIC_NAMES.iter().position(|basic| *basic == name).map(|i| ThemeColor::Basic(i as u8))
+    }
+
+    /// The color as written to the settings file
+    pub fn to_value(self) -> Value {
+        match self {
+            ThemeColor::Basic(n) => Value::String(BASIC_NAMES[n as usize & 7].to_string()),
+            ThemeColor::Indexed(index) => Value::Integer(index.into()),
+            ThemeColor::Rgb(r, g, b) => Value::String(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        }
+    }
+
+    /// The nearest color the terminal can show
+    pub fn for_terminal(self, support: ColorSupport) -> ThemeColor {
+        match (support, self) {
+            (ColorSupport::TrueColor, color) | (_, color @ ThemeColor::Basic(_)) => color,
+            (ColorSupport::Ansi256, ThemeColor::Rgb(r, g, b)) => ThemeColor::Indexed(cube_index(r, g, b)),
+            (ColorSupport::Ansi256, color) => color,
+            (ColorSupport::Basic, ThemeColor::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                ThemeColor::Basic(basic_index(r, g, b))
+            }
+            (ColorSupport::Basic, ThemeColor::Rgb(r, g, b)) => ThemeColor::Basic(basic_index(r, g, b)),
+        }
+    }
+
+    /// SGR parameters setting this as the background color
+    pub fn background_sgr(self) -> String {
+        match self {
+            ThemeColor::Basic(n) => format!("{}", 40 + n),
+            ThemeColor::Indexed(index) => format!("48;5;{}", index),
+            ThemeColor::Rgb(r, g, b) => format!("48;2;{};{};{}", r, g, b),
+        }
+    }
+
+    /// SGR parameters setting this as the foreground color
+    pub fn foreground_sgr(self) -> String {
+        match self {
+            ThemeColor::Basic(n) => format!("{}", 30 + n),
+            ThemeColor::Indexed(index) => format!("38;5;{}", index),
+            ThemeColor::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+        }
+    }
+}
+
+/// Nearest entry of the 6x6x6 color cube in the 256-color palette
+fn cube_index(r: u8, g: u8, b: u8) -> u8 {
+    let level = |channel: u8| if channel < 48 { 0 } else if channel < 115 { 1 } else { (channel - 35) / 40 };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Approximate RGB value of a 256-color palette entry
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match index {
+        0..=15 => {
+            let bright = if index >= 8 { 255 } else { 205 };
+            let lit = |bit: u8| if index & bit != 0 { bright } else { 0 };
+            (lit(1), lit(2), lit(4))
+        }
+        16..=231 => {
+            let cube = index - 16;
+            (LEVELS[(cube / 36) as usize], LEVELS[(cube / 6 % 6) as usize], LEVELS[(cube % 6) as usize])
+        }
+        _ => {
+            let gray = 8 + 10 * (index - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Basic color with the same channels lit
+fn basic_index(r: u8, g: u8, b: u8) -> u8 {
+    let lit = |channel: u8, bit: u8| if channel > BASIC_THRESHOLD { bit } else { 0 };
+    lit(r, 1) | lit(g, 2) | lit(b, 4)
+}
+
+/// Which colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// Only the eight basic ANSI colors
+    Basic,
+    /// The 256-color palette
+    Ansi256,
+    /// 24-bit color
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect color support from `COLORTERM` and `TERM`
+    pub fn detect() -> ColorSupport {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        ColorSupport::from_env(colorterm.as_deref(), term.as_deref())
+    }
+
+    /// Color support given the values of `COLORTERM` and `TERM`
+    ///
+    /// Windows consoles set neither but have shown 24-bit color since Windows 10.
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> ColorSupport {
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+            return ColorSupport::TrueColor;
+        }
+        match term {
+            Some(term) if term.contains("direct") => ColorSupport::TrueColor,
+            Some(term) if term.contains("256color") => ColorSupport::Ansi256,
+            None if cfg!(windows) => ColorSupport::TrueColor,
+            _ => ColorSupport::Basic,
+        }
+    }
+}
+
+/// How pieces are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceSet {
+    /// Solid Unicode glyphs for both sides, told apart by color
+    UnicodeFilled,
+    /// Hollow Unicode glyphs for both sides, told apart by color
+    UnicodeOutline,
+    /// Letters: upper case for White, lower case for Black
+    Ascii,
+}
+
+impl PieceSet {
+    const ALL: [PieceSet; 3] = [PieceSet::UnicodeFilled, PieceSet::UnicodeOutline, PieceSet::Ascii];
+
+    /// Name used in the settings file
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceSet::UnicodeFilled => "filled",
+            PieceSet::UnicodeOutline => "outline",
+            PieceSet::Ascii => "ascii",
+        }
+    }
+
+    /// Description shown on the settings screen
+    pub fn label(self) -> &'static str {
+        match self {
+            PieceSet::UnicodeFilled => "Unicode filled",
+            PieceSet::UnicodeOutline => "Unicode outline",
+            PieceSet::Ascii => "ASCII letters",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PieceSet> {
+        PieceSet::ALL.into_iter().find(|set| set.name() == name.trim().to_lowercase())
+    }
+
+    /// The set after this one, wrapping around
+    pub fn next(self) -> PieceSet {
+        let index = PieceSet::ALL.iter().position(|set| *set == self).unwrap_or(0);
+        PieceSet::ALL[(index + 1) % PieceSet::ALL.len()]
+    }
+
+    /// The character drawn for `piece`
+    pub fn glyph(self, piece: Piece) -> char {
+        match self {
+            PieceSet::UnicodeFilled => Piece::new(piece.piece_type, Color::Black).to_unicode(),
+            PieceSet::UnicodeOutline => Piece::new(piece.piece_type, Color::White).to_unicode(),
+            PieceSet::Ascii => {
+                let letter = match piece.piece_type {
+                    PieceType::Pawn => 'p',
+                    PieceType::Knight => 'n',
+                    PieceType::Bishop => 'b',
+                    PieceType::Rook => 'r',
+                    PieceType::Queen => 'q',
+                    PieceType::King => 'k',
+                };
+                if piece.color == Color::White {
+                    letter.to_ascii_uppercase()
+                } else {
+                    letter
+                }
+            }
+        }
+    }
+}
+
+/// A named set of square and highlight colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub light_square: ThemeColor,
+    pub dark_square: ThemeColor,
+    pub last_move: ThemeColor,
+    pub check: ThemeColor,
+}
+
+/// Themes the `settings` command cycles through; the last suits 8-color terminals
+pub const THEMES: [Theme; 4] = [
+    Theme {
+        name: "classic",
+        light_square: ThemeColor::Rgb(240, 217, 181),
+        dark_square: ThemeColor::Rgb(181, 136, 99),
+        last_move: ThemeColor::Rgb(170, 162, 58),
+        check: ThemeColor::Rgb(208, 64, 64),
+    },
+    Theme {
+        name: "green",
+        light_square: ThemeColor::Rgb(238, 238, 210),
+        dark_square: ThemeColor::Rgb(118, 150, 86),
+        last_move: ThemeColor::Rgb(186, 202, 68),
+        check: ThemeColor::Rgb(208, 64, 64),
+    },
+    Theme {
+        name: "blue",
+        light_square: ThemeColor::Indexed(153),
+        dark_square: ThemeColor::Indexed(67),
+        last_move: ThemeColor::Indexed(186),
+        check: ThemeColor::Indexed(167),
+    },
+    Theme {
+        name: "ansi",
+        light_square: ThemeColor::Basic(6),
+        dark_square: ThemeColor::Basic(4),
+        last_move: ThemeColor::Basic(3),
+        check: ThemeColor::Basic(1),
+    },
+];
+
+/// How the board looks, as kept in the settings file
+///
+/// ```toml
+/// [board]
+/// light = "#f0d9b5"      # color name, 256-color index or #rrggbb
+/// dark = "#b58863"
+/// pieces = "filled"      # filled, outline or ascii
+/// coordinates = true
+///
+/// [highlight]
+/// last_move = "#aaa23a"
+/// check = "#d04040"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub light_square: ThemeColor,
+    pub dark_square: ThemeColor,
+    /// Squares the last move left and reached
+    pub last_move: ThemeColor,
+    /// Square of a king in check
+    pub check: ThemeColor,
+    pub pieces: PieceSet,
+    /// Whether file and rank labels are drawn around the board
+    pub coordinates: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings::with_theme(&THEMES[0])
+    }
+}
+
+impl Settings {
+    /// Default settings with the colors of `theme`
+    pub fn with_theme(theme: &Theme) -> Self {
+        Settings {
+            light_square: theme.light_square,
+            dark_square: theme.dark_square,
+            last_move: theme.last_move,
+            check: theme.check,
+            pieces: PieceSet::UnicodeFilled,
+            coordinates: true,
+        }
+    }
+
+    /// The preset these colors come from, if any
+    pub fn theme(&self) -> Option<&'static Theme> {
+        THEMES.iter().find(|theme| {
+            (theme.light_square, theme.dark_square, theme.last_move, theme.check)
+                == (self.light_square, self.dark_square, self.last_move, self.check)
+        })
+    }
+
+    /// Switch to the theme after the current one (the first if the colors are custom)
+    pub fn next_theme(&mut self) {
+        let next = match self.theme() {
+            Some(current) => THEMES.iter().position(|theme| theme == current).map_or(0, |i| (i + 1) % THEMES.len()),
+            None => 0,
+        };
+        let theme = &THEMES[next];
+        self.light_square = theme.light_square;
+        self.dark_square = theme.dark_square;
+        self.last_move = theme.last_move;
+        self.check = theme.check;
+    }
+
+    /// Where the settings file lives: `$XDG_CONFIG_HOME`, else `~/.config`
+    /// (`%APPDATA%` on Windows), then `terminal-chess/settings.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let config = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_absolute())
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+        config.map(|dir| dir.join(SETTINGS_DIR).join(SETTINGS_FILE))
+    }
+
+    /// Load settings from `path`, with a warning for anything that had to be skipped
+    ///
+    /// A missing file gives the defaults without a warning.
+    pub fn load(path: &Path) -> (Settings, Vec<String>) {
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                let (settings, warnings) = Settings::parse(&text);
+                let warnings = warnings.into_iter().map(|warning| format!("{}: {}", path.display(), warning)).collect();
+                (settings, warnings)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Settings::default(), Vec::new()),
+            Err(e) => (Settings::default(), vec![format!("Cannot read {}: {}; using the default look", path.display(), e)]),
+        }
+    }
+
+    /// Parse a settings file, keeping the default for each missing or invalid key
+    pub fn parse(text: &str) -> (Settings, Vec<String>) {
+        let mut settings = Settings::default();
+        let mut warnings = Vec::new();
+
+        let table = match text.parse::<Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                warnings.push(format!("not valid TOML ({}); using the default look", e.message()));
+                return (settings, warnings);
+            }
+        };
+
+        let empty = Table::new();
+        let section = |name: &str, warnings: &mut Vec<String>| match table.get(name) {
+            Some(Value::Table(section)) => section,
+            Some(_) => {
+                warnings.push(format!("[{}] should be a table; ignoring it", name));
+                &empty
+            }
+            None => &empty,
+        };
+
+        let board = section("board", &mut warnings);
+        read_color(board, "board", "light", &mut settings.light_square, &mut warnings);
+        read_color(board, "board", "dark", &mut settings.dark_square, &mut warnings);
+        if let Some(value) = board.get("pieces") {
+            match value.as_str().and_then(PieceSet::from_name) {
+                Some(pieces) => settings.pieces = pieces,
+                None => warnings.push(format!(
+                    "board.pieces = {} is not filled, outline or ascii; using {}",
+                    value,
+                    settings.pieces.name()
+                )),
+            }
+        }
+        if let Some(value) = board.get("coordinates") {
+            match value.as_bool() {
+                Some(coordinates) => settings.coordinates = coordinates,
+                None => warnings.push(format!("board.coordinates = {} is not true or false; showing them", value)),
+            }
+        }
+
+        let highlight = section("highlight", &mut warnings);
+        read_color(highlight, "highlight", "last_move", &mut settings.last_move, &mut warnings);
+        read_color(highlight, "highlight", "check", &mut settings.check, &mut warnings);
+
+        (settings, warnings)
+    }
+
+    /// The settings file contents
+    pub fn to_toml(&self) -> String {
+        let mut board = Table::new();
+        board.insert("light".to_string(), self.light_square.to_value());
+        board.insert("dark".to_string(), self.dark_square.to_value());
+        board.insert("pieces".to_string(), Value::String(self.pieces.name().to_string()));
+        board.insert("coordinates".to_string(), Value::Boolean(self.coordinates));
+
+        let mut highlight = Table::new();
+        highlight.insert("last_move".to_string(), self.last_move.to_value());
+        highlight.insert("check".to_string(), self.check.to_value());
+
+        let mut table = Table::new();
+        table.insert("board".to_string(), Value::Table(board));
+        table.insert("highlight".to_string(), Value::Table(highlight));
+        table.to_string()
+    }
+
+    /// Write the settings file, creating its directory if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_toml())
+    }
+}
+
+/// Read the color at `section.key` into `color`, warning and leaving it alone if invalid
+fn read_color(table: &Table, section: &str, key: &str, color: &mut ThemeColor, warnings: &mut Vec<String>) {
+    let Some(value) = table.get(key) else {
+        return;
+    };
+    let parsed = match value {
+        Value::String(text) => ThemeColor::parse(text),
+        Value::Integer(index) => u8::try_from(*index).ok().map(ThemeColor::Indexed),
+        _ => None,
+    };
+    match parsed {
+        Some(parsed) => *color = parsed,
+        None => warnings.push(format!(
+            "{}.{} = {} is not a color name, 256-color index or #rrggbb; using the default",
+            section, key, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_colors() {
+        assert_eq!(ThemeColor::parse("Blue"), Some(ThemeColor::Basic(4)));
+        assert_eq!(ThemeColor::parse("94"), Some(ThemeColor::Indexed(94)));
+        assert_eq!(ThemeColor::parse("#F0d9b5"), Some(ThemeColor::Rgb(240, 217, 181)));
+        assert_eq!(ThemeColor::parse("#f0d9b"), None);
+        assert_eq!(ThemeColor::parse("256"), None);
+        assert_eq!(ThemeColor::parse("beige"), None);
+    }
+
+    #[test]
+    fn test_colors_fall_back_to_what_the_terminal_shows() {
+        let tan = ThemeColor::Rgb(240, 217, 181);
+        assert_eq!(tan.for_terminal(ColorSupport::TrueColor), tan);
+        assert_eq!(tan.for_terminal(ColorSupport::Ansi256), ThemeColor::Indexed(223));
+        assert_eq!(tan.for_terminal(ColorSupport::Basic), ThemeColor::Basic(7));
+        assert_eq!(ThemeColor::Indexed(67).for_terminal(ColorSupport::Basic), ThemeColor::Basic(4));
+        assert_eq!(ThemeColor::Indexed(12).for_terminal(ColorSupport::Basic), ThemeColor::Basic(4));
+        assert_eq!(ThemeColor::Basic(3).for_terminal(ColorSupport::Ansi256), ThemeColor::Basic(3));
+
+        // The classic squares and highlight stay apart in 8 colors
+        let classic = &THEMES[0];
+        let basic: Vec<_> = [classic.light_square, classic.dark_square, classic.last_move]
+            .iter()
+            .map(|color| color.for_terminal(ColorSupport::Basic))
+            .collect();
+        assert_eq!(basic, vec![ThemeColor::Basic(7), ThemeColor::Basic(1), ThemeColor::Basic(3)]);
+    }
+
+    #[test]
+    fn test_detect_color_support() {
+        assert_eq!(ColorSupport::from_env(Some("truecolor"), Some("xterm")), ColorSupport::TrueColor);
+        assert_eq!(ColorSupport::from_env(None, Some("xterm-256color")), ColorSupport::Ansi256);
+        assert_eq!(ColorSupport::from_env(None, Some("xterm")), ColorSupport::Basic);
+        assert_eq!(ColorSupport::from_env(None, Some("linux")), ColorSupport::Basic);
+    }
+
+    #[test]
+    fn test_missing_keys_keep_defaults() {
+        let (settings, warnings) = Settings::parse("[board]\npieces = \"ascii\"\n");
+        assert!(warnings.is_empty());
+        assert_eq!(settings, Settings { pieces: PieceSet::Ascii, ..Settings::default() });
+        assert_eq!(Settings::parse(""), (Settings::default(), Vec::new()));
+    }
+
+    #[test]
+    fn test_bad_values_warn_and_keep_defaults() {
+        let text = "[board]\nlight = \"#zzzzzz\"\ndark = 300\npieces = \"fancy\"\ncoordinates = \"no\"\n\n[highlight]\ncheck = \"red\"\n";
+        let (settings, warnings) = Settings::parse(text);
+        assert_eq!(settings, Settings { check: ThemeColor::Basic(1), ..Settings::default() });
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].contains("board.light = \"#zzzzzz\""), "{}", warnings[0]);
+        assert!(warnings[1].contains("board.dark = 300"), "{}", warnings[1]);
+
+        let (settings, warnings) = Settings::parse("board = [");
+        assert_eq!(settings, Settings::default());
+        assert!(warnings[0].starts_with("not valid TOML"), "{}", warnings[0]);
+    }
+
+    #[test]
+    fn test_settings_round_trip_through_the_file() {
+        let mut settings = Settings { pieces: PieceSet::UnicodeOutline, coordinates: false, ..Settings::default() };
+        settings.next_theme();
+        settings.next_theme();
+        assert_eq!(settings.theme().map(|theme| theme.name), Some("blue"));
+
+        let path = std::env::temp_dir()
+            .join(format!("terminal-chess-settings-{}", std::process::id()))
+            .join(SETTINGS_FILE);
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path), (settings, Vec::new()));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let missing = Settings::load(Path::new("/nonexistent/terminal-chess/settings.toml"));
+        assert_eq!(missing, (Settings::default(), Vec::new()));
+    }
+
+    #[test]
+    fn test_cycling_wraps_around() {
+        let mut settings = Settings { light_square: ThemeColor::Basic(2), ..Settings::default() };
+        assert!(settings.theme().is_none());
+        settings.next_theme();
+        assert_eq!(settings.theme(), Some(&THEMES[0]));
+        for _ in 0..THEMES.len() {
+            settings.next_theme();
+        }
+        assert_eq!(settings.theme(), Some(&THEMES[0]));
+        assert_eq!(PieceSet::Ascii.next(), PieceSet::UnicodeFilled);
+    }
+
+    #[test]
+    fn test_piece_glyphs() {
+        let black_knight = Piece::new(PieceType::Knight, Color::Black);
+        let white_knight = Piece::new(PieceType::Knight, Color::White);
+        assert_eq!(PieceSet::UnicodeFilled.glyph(white_knight), '♞');
+        assert_eq!(PieceSet::UnicodeOutline.glyph(black_knight), '♘');
+        assert_eq!(PieceSet::Ascii.glyph(white_knight), 'N');
+        assert_eq!(PieceSet::Ascii.glyph(black_knight), 'n');
+    }
+}
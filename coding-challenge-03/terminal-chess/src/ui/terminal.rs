@@ -1,27 +1,75 @@
-use crate::chess::{Board, Color, Game, GameState, Move, PieceType, Position};
+use super::eval_bar::{render_eval_bar, BAR_HEIGHT};
+use super::settings::{ColorSupport, Settings, ThemeColor};
+use crate::ai::{Evaluation, SearchProgress};
+use crate::chess::{
+    parse_san, BoardQuery, Board, Color, Game, GameMode, GameState, Handicap, MaterialOdds, Move, Piece, PieceType,
+    Position, SanError, VariantKind, MAX_SKILL,
+};
 use crossterm::{
     cursor,
     event::{self, Event, KeyCode},
-    execute,
-    style::{Color as CColor, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    execute, queue,
+    style::Print,
     terminal::{self, Clear, ClearType},
 };
+use std::cell::Cell;
+use std::fmt::Write as _;
 use std::io::{self, Write};
-
-pub struct TerminalUI;
+use std::path::PathBuf;
+
+/// Color of White's pieces, before falling back to what the terminal shows
+const WHITE_PIECE: ThemeColor = ThemeColor::Indexed(15);
+
+/// Color of Black's pieces
+const BLACK_PIECE: ThemeColor = ThemeColor::Indexed(0);
+
+pub struct TerminalUI {
+    /// Screen-reader friendly mode: plain line-based text, no board drawing
+    accessible: bool,
+    /// How the board looks; the `settings` command changes it mid-game
+    settings: Cell<Settings>,
+    /// Where `settings` are saved, if there is a config directory
+    settings_path: Option<PathBuf>,
+    /// Colors the terminal can show
+    color_support: ColorSupport,
+}
 
 impl TerminalUI {
     pub fn new() -> Self {
-        TerminalUI
+        TerminalUI {
+            accessible: false,
+            settings: Cell::new(Settings::default()),
+            settings_path: None,
+            color_support: ColorSupport::detect(),
+        }
+    }
+
+    /// Use `settings` for the board, saving changes to `path`
+    pub fn with_settings(mut self, settings: Settings, path: Option<PathBuf>) -> Self {
+        self.settings = Cell::new(settings);
+        self.settings_path = path;
+        self
+    }
+
+    /// Switch accessibility mode on or off
+    ///
+    /// In accessibility mode input is read a line at a time, nothing is drawn
+    /// at cursor positions, and the board is described in words instead of
+    /// being drawn in color.
+    pub fn with_accessibility(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        self
+    }
+
+    pub fn is_accessible(&self) -> bool {
+        self.accessible
     }
 
     /// Display the main menu and get user choice
     pub fn show_menu(&self) -> io::Result<MenuChoice> {
         self.clear_screen()?;
 
-        println!("\n╔════════════════════════════════════════╗");
-        println!("║        TERMINAL CHESS GAME             ║");
-        println!("╚════════════════════════════════════════╝\n");
+        self.print_title("TERMINAL CHESS GAME");
         println!("  1. Player vs Player");
         println!("  2. Player vs AI");
         println!("  3. Load Game");
@@ -30,8 +78,8 @@ impl TerminalUI {
         io::stdout().flush()?;
 
         loop {
-            if let Event::Key(key_event) = event::read()? {
-                match key_event.code {
+            if let Some(code) = self.next_key()? {
+                match code {
                     KeyCode::Char('1') => return Ok(MenuChoice::PlayerVsPlayer),
                     KeyCode::Char('2') => return Ok(MenuChoice::PlayerVsAI),
                     KeyCode::Char('3') => return Ok(MenuChoice::LoadGame),
@@ -46,9 +94,7 @@ impl TerminalUI {
     pub fn get_difficulty(&self) -> io::Result<u8> {
         self.clear_screen()?;
 
-        println!("\n╔════════════════════════════════════════╗");
-        println!("║        SELECT AI DIFFICULTY            ║");
-        println!("╚════════════════════════════════════════╝\n");
+        self.print_title("SELECT AI DIFFICULTY");
         println!("  1. Easy (Depth 1)");
         println!("  2. Medium (Depth 2)");
         println!("  3. Hard (Depth 3)");
@@ -57,8 +103,8 @@ impl TerminalUI {
         io::stdout().flush()?;
 
         loop {
-            if let Event::Key(key_event) = event::read()? {
-                match key_event.code {
+            if let Some(code) = self.next_key()? {
+                match code {
                     KeyCode::Char('1') => return Ok(1),
                     KeyCode::Char('2') => return Ok(2),
                     KeyCode::Char('3') => return Ok(3),
@@ -69,31 +115,107 @@ impl TerminalUI {
         }
     }
 
+    /// Get handicap settings for a game against the AI
+    pub fn get_handicap(&self) -> io::Result<Handicap> {
+        self.clear_screen()?;
+
+        self.print_title("HANDICAP: MATERIAL ODDS");
+        println!("  1. None");
+        println!("  2. AI starts without a knight");
+        println!("  3. AI starts without a rook");
+        println!("  4. AI starts without its queen\n");
+        print!("Enter odds (1-4): ");
+        io::stdout().flush()?;
+
+        let odds = loop {
+            if let Some(code) = self.next_key()? {
+                match code {
+                    KeyCode::Char('1') => break MaterialOdds::None,
+                    KeyCode::Char('2') => break MaterialOdds::Knight,
+                    KeyCode::Char('3') => break MaterialOdds::Rook,
+                    KeyCode::Char('4') => break MaterialOdds::Queen,
+                    _ => {}
+                }
+            }
+        };
+
+        self.clear_screen()?;
+
+        self.print_title("HANDICAP: AI SKILL LEVEL");
+        println!("  1-9. Lower levels sometimes play a weaker move");
+        println!("  0.   Full strength (10)\n");
+        print!("Enter skill (1-9, 0 for 10): ");
+        io::stdout().flush()?;
+
+        let skill = loop {
+            if let Some(code) = self.next_key()? {
+                match code {
+                    KeyCode::Char('0') => break MAX_SKILL,
+                    KeyCode::Char(c @ '1'..='9') => break c as u8 - b'0',
+                    _ => {}
+                }
+            }
+        };
+
+        let coaching = self.confirm("Enable coaching (warn before moves that hang material)?")?;
+
+        Ok(Handicap { odds, skill, coaching })
+    }
+
+    /// Ask a yes/no question
+    pub fn confirm(&self, question: &str) -> io::Result<bool> {
+        print!("\n  {} [y/n]: ", question);
+        io::stdout().flush()?;
+
+        loop {
+            if let Some(code) = self.next_key()? {
+                match code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        println!("y");
+                        return Ok(true);
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        println!("n");
+                        return Ok(false);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
     /// Display the game board
-    pub fn display_game(&self, game: &Game, last_move: Option<Move>) -> io::Result<()> {
+    ///
+    /// `evaluation` is the last engine assessment from White's side, drawn as a bar beside the board.
+    pub fn display_game(&self, game: &Game, last_move: Option<Move>, evaluation: Option<Evaluation>) -> io::Result<()> {
         self.clear_screen()?;
 
-        println!("\n╔════════════════════════════════════════╗");
-        println!("║        TERMINAL CHESS GAME             ║");
-        println!("╚════════════════════════════════════════╝\n");
+        self.print_title("TERMINAL CHESS GAME");
 
         // Display captured pieces
         let (white_captured, black_captured) = game.get_captured_pieces();
+        let pieces = self.settings.get().pieces;
 
-        print!("  Black captured: ");
-        for piece in &white_captured {
-            print!("{} ", piece.to_unicode());
-        }
-        println!("\n");
+        if self.accessible {
+            // The board is read on request instead of drawn
+            println!("  Black captured: {}", describe_captured(&white_captured));
+            println!("  White captured: {}", describe_captured(&black_captured));
+        } else {
+            print!("  Black captured: ");
+            for piece in &white_captured {
+                print!("{} ", pieces.glyph(*piece));
+            }
+            println!("\n");
 
-        // Display board
-        self.display_board(&game.board, last_move)?;
+            // Display board
+            self.display_board(game, last_move, evaluation)?;
 
-        print!("\n  White captured: ");
-        for piece in &black_captured {
-            print!("{} ", piece.to_unicode());
+            print!("\n  White captured: ");
+            for piece in &black_captured {
+                print!("{} ", pieces.glyph(*piece));
+            }
+            println!();
         }
-        println!();
 
         // Display game state
         println!("\n  Current player: {}", if game.current_player == Color::White { "White" } else { "Black" });
@@ -103,110 +225,155 @@ impl TerminalUI {
             GameState::Checkmate(winner) => {
                 println!("  Status: CHECKMATE! {} wins!", if winner == Color::White { "White" } else { "Black" });
             }
+            GameState::VariantWin(winner) => {
+                println!(
+                    "  Status: {}! {} wins!",
+                    game.variant.rules().name().to_uppercase(),
+                    if winner == Color::White { "White" } else { "Black" }
+                );
+            }
             GameState::Stalemate => println!("  Status: STALEMATE! Game is a draw."),
             GameState::Draw => println!("  Status: DRAW (50-move rule)"),
+            GameState::Resignation(winner) => {
+                println!("  Status: RESIGNATION! {} wins!", if winner == Color::White { "White" } else { "Black" });
+            }
+            GameState::DrawAgreed => println!("  Status: DRAW (agreed)"),
             GameState::Playing => println!("  Status: Playing"),
         }
 
-        println!("\n  Commands: [move] e2e4, [u]ndo, [s]ave, [q]uit");
-
-        Ok(())
-    }
-
-    /// Display the chess board with colors
-    fn display_board(&self, board: &Board, last_move: Option<Move>) -> io::Result<()> {
-        let light_square = CColor::Rgb { r: 240, g: 217, b: 181 };
-        let dark_square = CColor::Rgb { r: 181, g: 136, b: 99 };
-        let highlight_square = CColor::Rgb { r: 170, g: 162, b: 58 };
-        let white_piece_color = CColor::White;
-        let black_piece_color = CColor::Black;
+        if let Some(evaluation) = evaluation {
+            println!("  Evaluation: {} for White", evaluation.label());
+        }
 
-        // Print column labels
-        print!("     ");
-        for col in 0..8 {
-            print!(" {} ", (b'a' + col as u8) as char);
+        if game.variant != VariantKind::Standard {
+            let rules = game.variant.rules();
+            println!("  Variant: {}", rules.name());
+            if let Some(status) = rules.status(&game.checks) {
+                println!("  {}", status);
+            }
         }
-        println!();
 
-        // Print top border
-        println!("    ┌────────────────────────┐");
+        if game.mode == GameMode::PlayerVsAI {
+            let handicap = &game.handicap;
+            println!(
+                "  Handicap: {}, AI skill {}/{}, coaching {}",
+                handicap.odds.label(),
+                handicap.skill,
+                MAX_SKILL,
+                if handicap.coaching { "on" } else { "off" }
+            );
+            println!("\n  Commands: [move] e2e4 or Nf3 (Tab completes), moves, [u]ndo, [s]ave, [p]gn export, [t]oggle coaching, offer draw, resign, debug on/off, stats, settings, [q]uit");
+        } else {
+            println!("\n  Commands: [move] e2e4 or Nf3 (Tab completes), moves, [u]ndo, [s]ave, [p]gn export, offer draw, resign, settings, [q]uit");
+        }
 
-        // Print board rows (from top to bottom, which is row 7 to 0)
-        for row in (0..8).rev() {
-            print!("  {} │", row + 1);
+        if self.accessible {
+            println!("  Board: {}", BoardQuery::HELP);
+        }
 
-            for col in 0..8 {
-                let pos = Position::new(row, col).unwrap();
-                let is_light = (row + col) % 2 == 0;
+        Ok(())
+    }
 
-                // Check if this square is part of the last move
-                let is_highlighted = last_move.is_some_and(|m| m.from == pos || m.to == pos);
+    /// Display the chess board in the current settings
+    fn display_board(&self, game: &Game, last_move: Option<Move>, evaluation: Option<Evaluation>) -> io::Result<()> {
+        let board = render_board(
+            &game.board,
+            last_move,
+            checked_king(game),
+            evaluation,
+            &self.settings.get(),
+            self.color_support,
+        );
+        print!("{}", board);
+        io::stdout().flush()
+    }
 
-                let bg_color = if is_highlighted {
-                    highlight_square
-                } else if is_light {
-                    light_square
-                } else {
-                    dark_square
-                };
-
-                execute!(io::stdout(), SetBackgroundColor(bg_color))?;
-
-                if let Some(piece) = board.get_piece(pos) {
-                    let fg_color = if piece.color == Color::White {
-                        white_piece_color
-                    } else {
-                        black_piece_color
-                    };
-                    execute!(io::stdout(), SetForegroundColor(fg_color))?;
-                    print!(" {} ", piece.to_unicode());
-                } else {
-                    print!("   ");
-                }
+    /// Cycle the board settings with a live preview, then save them
+    ///
+    /// Returns a message saying where the settings went.
+    pub fn edit_settings(&self, game: &Game, last_move: Option<Move>) -> io::Result<String> {
+        let mut settings = self.settings.get();
 
-                execute!(io::stdout(), ResetColor)?;
+        loop {
+            self.clear_screen()?;
+            self.print_title("BOARD SETTINGS");
+            if !self.accessible {
+                self.display_board(game, last_move, None)?;
             }
 
-            println!("│ {}", row + 1);
-        }
-
-        // Print bottom border
-        println!("    └────────────────────────┘");
+            println!("  1. Theme: {}", settings.theme().map_or("custom", |theme| theme.name));
+            println!("  2. Pieces: {}", settings.pieces.label());
+            println!("  3. Coordinates: {}", if settings.coordinates { "shown" } else { "hidden" });
+            print!("\n  Press 1-3 to change, Enter to keep: ");
+            io::stdout().flush()?;
 
-        // Print column labels again
-        print!("     ");
-        for col in 0..8 {
-            print!(" {} ", (b'a' + col as u8) as char);
+            match self.next_key()? {
+                Some(KeyCode::Char('1')) => settings.next_theme(),
+                Some(KeyCode::Char('2')) => settings.pieces = settings.pieces.next(),
+                Some(KeyCode::Char('3')) => settings.coordinates = !settings.coordinates,
+                Some(KeyCode::Enter) | Some(KeyCode::Esc) => break,
+                _ => continue,
+            }
+            self.settings.set(settings);
         }
-        println!("\n");
+        println!();
 
-        Ok(())
+        Ok(match &self.settings_path {
+            Some(path) => match settings.save(path) {
+                Ok(()) => format!("Settings saved to {}", path.display()),
+                Err(e) => format!("Settings apply to this session only; cannot write {}: {}", path.display(), e),
+            },
+            None => "Settings apply to this session only: no config directory to save them in".to_string(),
+        })
     }
 
     /// Get a move from the user
-    pub fn get_move(&self, prompt: &str) -> io::Result<String> {
+    ///
+    /// Input keeps its case, since SAN tells a bishop ("Bc4") from the
+    /// b-file ("bxc4") that way. Tab completes the input against
+    /// `completions`: a unique match is filled in, several matches are
+    /// extended to their common prefix and listed.
+    pub fn get_move(&self, prompt: &str, completions: &[String]) -> io::Result<String> {
         print!("  {}: ", prompt);
         io::stdout().flush()?;
 
         let mut input = String::new();
 
+        if self.accessible {
+            // Let the terminal (and screen reader) handle line editing
+            io::stdin().read_line(&mut input)?;
+            return Ok(input.trim().to_string());
+        }
+
         loop {
-            if let Event::Key(key_event) = event::read()? {
-                match key_event.code {
+            if let Some(code) = self.next_key()? {
+                match code {
                     KeyCode::Char(c) => {
                         input.push(c);
                         print!("{}", c);
                         io::stdout().flush()?;
                     }
-                    KeyCode::Backspace => {
-                        if !input.is_empty() {
-                            input.pop();
-                            execute!(io::stdout(), cursor::MoveLeft(1), Print(' '), cursor::MoveLeft(1))?;
+                    KeyCode::Backspace if !input.is_empty() => {
+                        input.pop();
+                        execute!(io::stdout(), cursor::MoveLeft(1), Print(' '), cursor::MoveLeft(1))?;
+                    }
+                    KeyCode::Tab => {
+                        let matches: Vec<&String> = completions.iter().filter(|c| c.starts_with(&input)).collect();
+                        let prefix = common_prefix(&matches);
+
+                        if prefix.len() > input.len() {
+                            print!("{}", &prefix[input.len()..]);
+                            input = prefix;
+                        } else if matches.len() > 1 {
+                            // Nothing more to fill in: show the choices and redraw the prompt
+                            let choices: Vec<&str> = matches.iter().map(|m| m.as_str()).collect();
+                            print!("\r\n  {}\r\n  {}: {}", choices.join(" "), prompt, input);
                         }
+                        io::stdout().flush()?;
                     }
                     KeyCode::Enter => {
                         println!();
-                        return Ok(input.trim().to_lowercase());
+                        return Ok(input.trim().to_string());
                     }
                     _ => {}
                 }
@@ -216,68 +383,209 @@ impl TerminalUI {
 
     /// Get filename for save/load
     pub fn get_filename(&self, prompt: &str) -> io::Result<String> {
-        self.get_move(prompt)
+        self.get_move(prompt, &[])
+    }
+
+    /// Ask which of several moves matching an ambiguous SAN input was meant
+    ///
+    /// Returns `None` if the player backs out.
+    pub fn choose_move(&self, game: &Game, candidates: &[Move]) -> io::Result<Option<Move>> {
+        println!("\n  Ambiguous move. Which did you mean?");
+        for (i, mov) in candidates.iter().enumerate().take(9) {
+            println!("  {}. {}", i + 1, game.to_san(mov));
+        }
+        print!("  Enter choice (1-{}, Esc to cancel): ", candidates.len().min(9));
+        io::stdout().flush()?;
+
+        loop {
+            if let Some(code) = self.next_key()? {
+                match code {
+                    KeyCode::Char(c @ '1'..='9') => {
+                        if let Some(mov) = candidates.get((c as u8 - b'1') as usize) {
+                            println!("{}", c);
+                            return Ok(Some(*mov));
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Enter => {
+                        println!();
+                        return Ok(None);
+                    }
+                    _ => {}
+                }
+            }
+        }
     }
 
     /// Display a message
     pub fn show_message(&self, message: &str) -> io::Result<()> {
         println!("\n  {}", message);
-        println!("  Press any key to continue...");
-        io::stdout().flush()?;
 
-        event::read()?;
+        if self.accessible {
+            println!("  Press Enter to continue.");
+            io::stdout().flush()?;
+            io::stdin().read_line(&mut String::new())?;
+        } else {
+            println!("  Press any key to continue...");
+            io::stdout().flush()?;
+            event::read()?;
+        }
+
         Ok(())
     }
 
+    /// Announce a move or answer a query as a single line of text
+    pub fn announce(&self, text: &str) -> io::Result<()> {
+        println!("\n  {}", text);
+        io::stdout().flush()
+    }
+
     /// Display a thinking message
     pub fn show_thinking(&self) -> io::Result<()> {
-        print!("\n  AI is thinking");
-        io::stdout().flush()?;
-
-        for _ in 0..3 {
-            std::thread::sleep(std::time::Duration::from_millis(300));
-            print!(".");
-            io::stdout().flush()?;
+        if self.accessible {
+            println!("\n  AI is thinking.");
+            return Ok(());
         }
-        println!();
 
-        Ok(())
+        println!("\n  AI is thinking...");
+        io::stdout().flush()
     }
 
-    /// Clear the screen
+    /// Show what the search has found so far
+    ///
+    /// The line is built up front and written in one go so the search thread
+    /// isn't held up by the terminal. Accessible mode only reports finished
+    /// iterations, each on its own line, to keep the screen reader quiet.
+    pub fn show_search_progress(&self, game: &Game, progress: &SearchProgress, evaluation: Evaluation) -> io::Result<()> {
+        let summary = format!(
+            "depth {}  best {}  eval {}  nodes {}",
+            progress.depth,
+            game.to_san(&progress.best_move),
+            evaluation.label(),
+            progress.nodes
+        );
+
+        let mut stdout = io::stdout().lock();
+        if self.accessible {
+            if progress.completed {
+                writeln!(stdout, "  {}", summary)?;
+            }
+        } else {
+            queue!(stdout, Print("\r"), Clear(ClearType::CurrentLine), Print(format!("  {}", summary)))?;
+        }
+        stdout.flush()
+    }
+
+    /// Clear the screen (accessible mode keeps the scrollback and just starts a new block)
     fn clear_screen(&self) -> io::Result<()> {
+        if self.accessible {
+            println!();
+            return Ok(());
+        }
+
         execute!(io::stdout(), Clear(ClearType::All), cursor::MoveTo(0, 0))?;
         Ok(())
     }
 
-    /// Enable raw mode for terminal
+    /// Print a screen title, boxed unless in accessible mode
+    fn print_title(&self, title: &str) {
+        if self.accessible {
+            println!("\n  {}\n", title);
+        } else {
+            println!("\n╔════════════════════════════════════════╗");
+            println!("║        {:<32}║", title);
+            println!("╚════════════════════════════════════════╝\n");
+        }
+    }
+
+    /// Read the next key press
+    ///
+    /// Accessible mode reads a whole line instead and returns its first
+    /// character (or Enter for an empty line).
+    fn next_key(&self) -> io::Result<Option<KeyCode>> {
+        if self.accessible {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+            return Ok(Some(line.trim().chars().next().map_or(KeyCode::Enter, KeyCode::Char)));
+        }
+
+        match event::read()? {
+            Event::Key(key_event) => Ok(Some(key_event.code)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Enable raw mode for terminal (left off in accessible mode)
     pub fn enable_raw_mode(&self) -> io::Result<()> {
+        if self.accessible {
+            return Ok(());
+        }
         terminal::enable_raw_mode()
     }
 
     /// Disable raw mode for terminal
     pub fn disable_raw_mode(&self) -> io::Result<()> {
+        if self.accessible {
+            return Ok(());
+        }
         terminal::disable_raw_mode()
     }
 
-    /// Parse a move from algebraic notation
+    /// Parse a move in coordinate notation (e2e4) or SAN (Nf3)
+    ///
+    /// Commands are recognised in lower case only, so "q" quits while
+    /// "Qd4" moves the queen. SAN that several legal moves match is
+    /// resolved by asking the player.
     pub fn parse_move(&self, input: &str, game: &Game) -> Result<Move, String> {
         // Handle special commands
         if input.starts_with('u') {
             return Err("UNDO".to_string());
         }
+        if input == "stats" {
+            return Err("STATS".to_string());
+        }
+        if input == "moves" {
+            return Err("MOVES".to_string());
+        }
+        match input {
+            "settings" => return Err("SETTINGS".to_string()),
+            "debug on" => return Err("DEBUG ON".to_string()),
+            "debug off" => return Err("DEBUG OFF".to_string()),
+            "resign" => return Err("RESIGN".to_string()),
+            "offer draw" | "draw" => return Err("DRAW".to_string()),
+            _ => {}
+        }
         if input.starts_with('s') {
             return Err("SAVE".to_string());
         }
         if input.starts_with('q') {
             return Err("QUIT".to_string());
         }
+        if input.starts_with('t') {
+            return Err("COACH".to_string());
+        }
+        if input.starts_with('p') {
+            return Err("PGN".to_string());
+        }
 
-        // Parse algebraic notation (e.g., "e2e4" or "e7e8q" for promotion)
-        if input.len() < 4 {
-            return Err("Invalid move format. Use algebraic notation (e.g., e2e4)".to_string());
+        let coordinates = input.to_lowercase();
+        let is_coordinate = coordinates.len() >= 4
+            && coordinates.is_char_boundary(4)
+            && Position::from_algebraic(&coordinates[0..2]).is_some()
+            && Position::from_algebraic(&coordinates[2..4]).is_some();
+        if !is_coordinate {
+            return match parse_san(input, &game.get_legal_moves()) {
+                Ok(mov) => Ok(mov),
+                Err(SanError::Invalid(message)) => Err(message),
+                Err(SanError::Ambiguous(candidates)) => match self.choose_move(game, &candidates) {
+                    Ok(Some(mov)) => Ok(mov),
+                    Ok(None) => Err("Move cancelled".to_string()),
+                    Err(e) => Err(format!("Could not read choice: {}", e)),
+                },
+            };
         }
+        let input = coordinates.as_str();
 
+        // Parse coordinate notation (e.g., "e2e4" or "e7e8q" for promotion)
         let from = Position::from_algebraic(&input[0..2])
             .ok_or_else(|| "Invalid source position".to_string())?;
 
@@ -323,6 +631,128 @@ impl TerminalUI {
     }
 }
 
+/// Square of the side to move's king when it is in check
+fn checked_king(game: &Game) -> Option<Position> {
+    match game.state {
+        GameState::Check | GameState::Checkmate(_) => game.board.find_king(game.current_player),
+        _ => None,
+    }
+}
+
+/// Draw the board as text with SGR color codes
+///
+/// `check` is the square of a king in check. Colors are reduced to what
+/// `support` says the terminal can show, and the evaluation bar is drawn
+/// beside the ranks.
+pub fn render_board(
+    board: &Board,
+    last_move: Option<Move>,
+    check: Option<Position>,
+    evaluation: Option<Evaluation>,
+    settings: &Settings,
+    support: ColorSupport,
+) -> String {
+    let mut out = String::new();
+    let files: String = (b'a'..=b'h').map(|file| format!(" {} ", file as char)).collect();
+    let bar = evaluation.map(|evaluation| render_eval_bar(evaluation, BAR_HEIGHT));
+
+    if settings.coordinates {
+        out.push_str(&format!("     {}\n", files));
+    }
+    out.push_str("    ┌────────────────────────┐\n");
+
+    // From top to bottom, which is row 7 to 0
+    for row in (0..8).rev() {
+        if settings.coordinates {
+            let _ = write!(out, "  {} │", row + 1);
+        } else {
+            out.push_str("    │");
+        }
+
+        for col in 0..8 {
+            let pos = Position::new(row, col).unwrap();
+            let square = if check == Some(pos) {
+                settings.check
+            } else if last_move.is_some_and(|m| m.from == pos || m.to == pos) {
+                settings.last_move
+            } else if (row + col) % 2 == 0 {
+                settings.light_square
+            } else {
+                settings.dark_square
+            };
+            let _ = write!(out, "\x1b[{}m", square.for_terminal(support).background_sgr());
+
+            match board.get_piece(pos) {
+                Some(piece) => {
+                    let color = if piece.color == Color::White { WHITE_PIECE } else { BLACK_PIECE };
+                    let _ = write!(
+                        out,
+                        "\x1b[{}m {} ",
+                        color.for_terminal(support).foreground_sgr(),
+                        settings.pieces.glyph(piece)
+                    );
+                }
+                None => out.push_str("   "),
+            }
+            out.push_str("\x1b[0m");
+        }
+
+        let label = if settings.coordinates { format!(" {}", row + 1) } else { "  ".to_string() };
+        match &bar {
+            Some(bar) => {
+                let _ = writeln!(out, "│{}   {}", label, bar[7 - row as usize]);
+            }
+            None => {
+                let _ = writeln!(out, "│{}", label.trim_end());
+            }
+        }
+    }
+
+    out.push_str("    └────────────────────────┘\n");
+    if settings.coordinates {
+        let _ = writeln!(out, "     {}", files);
+    }
+    out.push('\n');
+    out
+}
+
+/// Longest prefix shared by every completion (empty when there are none)
+fn common_prefix(completions: &[&String]) -> String {
+    let Some((first, rest)) = completions.split_first() else {
+        return String::new();
+    };
+
+    let mut len = first.len();
+    for other in rest {
+        len = first
+            .char_indices()
+            .zip(other.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(len);
+    }
+
+    first[..len].to_string()
+}
+
+/// Captured pieces in words, e.g. "white pawn, white knight"
+fn describe_captured(pieces: &[Piece]) -> String {
+    if pieces.is_empty() {
+        return "nothing".to_string();
+    }
+
+    pieces
+        .iter()
+        .map(|piece| {
+            let color = if piece.color == Color::White { "white" } else { "black" };
+            format!("{} {}", color, piece.piece_type.name())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuChoice {
     PlayerVsPlayer,
@@ -336,3 +766,86 @@ impl Default for TerminalUI {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::settings::{PieceSet, THEMES};
+    use std::path::Path;
+
+    /// 1. e4 f5 2. Qh5+: a last move to highlight and a king in check
+    fn checked_game() -> (Game, Move) {
+        let mut game = Game::new(GameMode::PlayerVsPlayer, 0);
+        let mut last = None;
+        for san in ["e4", "f5", "Qh5+"] {
+            let mov = parse_san(san, &game.get_legal_moves()).unwrap();
+            game.make_move(mov).unwrap();
+            last = Some(mov);
+        }
+        (game, last.unwrap())
+    }
+
+    /// Compare a rendering with `src/ui/snapshots/<name>.txt`, with escape
+    /// characters written as `\e` to keep the files readable
+    ///
+    /// Set `UPDATE_SNAPSHOTS=1` to rewrite the files instead.
+    fn assert_snapshot(name: &str, rendered: &str) {
+        let rendered = rendered.replace('\x1b', "\\e");
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/ui/snapshots").join(format!("{}.txt", name));
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &rendered).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {} (run with UPDATE_SNAPSHOTS=1)", path.display(), e));
+        assert_eq!(rendered, expected, "board differs from {}", path.display());
+    }
+
+    fn render(settings: &Settings, support: ColorSupport, evaluation: Option<Evaluation>) -> String {
+        let (game, last_move) = checked_game();
+        render_board(&game.board, Some(last_move), checked_king(&game), evaluation, settings, support)
+    }
+
+    #[test]
+    fn test_board_snapshot_per_theme() {
+        for theme in &THEMES {
+            let rendered = render(&Settings::with_theme(theme), ColorSupport::TrueColor, None);
+            assert_snapshot(&format!("board_{}", theme.name), &rendered);
+        }
+    }
+
+    #[test]
+    fn test_board_snapshot_color_fallback() {
+        let settings = Settings::default();
+        assert_snapshot("board_classic_256", &render(&settings, ColorSupport::Ansi256, None));
+        assert_snapshot("board_classic_8", &render(&settings, ColorSupport::Basic, None));
+    }
+
+    #[test]
+    fn test_board_snapshot_ascii_without_coordinates() {
+        let settings = Settings { pieces: PieceSet::Ascii, coordinates: false, ..Settings::default() };
+        let rendered = render(&settings, ColorSupport::TrueColor, Some(Evaluation::Pawns(1.25)));
+        assert_snapshot("board_ascii_plain", &rendered);
+    }
+
+    #[test]
+    fn test_check_and_last_move_highlights() {
+        let (game, last_move) = checked_game();
+        assert_eq!(checked_king(&game), Position::from_algebraic("e8"));
+
+        let settings = Settings::default();
+        let rendered = render_board(&game.board, Some(last_move), None, None, &settings, ColorSupport::TrueColor);
+        let last_move_bg = format!("\x1b[{}m", settings.last_move.background_sgr());
+        assert_eq!(rendered.matches(&last_move_bg).count(), 2);
+        assert!(!rendered.contains(&settings.check.background_sgr()));
+    }
+
+    #[test]
+    fn test_settings_command() {
+        let ui = TerminalUI::new();
+        let game = Game::new(GameMode::PlayerVsPlayer, 0);
+        assert_eq!(ui.parse_move("settings", &game), Err("SETTINGS".to_string()));
+        assert_eq!(ui.parse_move("save", &game), Err("SAVE".to_string()));
+    }
+}
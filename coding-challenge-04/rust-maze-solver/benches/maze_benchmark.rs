@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use rust_maze_solver::{
-    algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm},
+    algorithms::{GeneratorAlgorithm, Hand, MazeGenerator, MazeSolver, PathfindingAlgorithm},
 };
 
 fn benchmark_generation(c: &mut Criterion) {
@@ -44,6 +44,14 @@ fn benchmark_solving(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("Dijkstra", size), &maze, |b, maze| {
             b.iter(|| MazeSolver::solve(black_box(maze), PathfindingAlgorithm::Dijkstra));
         });
+
+        group.bench_with_input(BenchmarkId::new("WallFollower", size), &maze, |b, maze| {
+            b.iter(|| MazeSolver::solve(black_box(maze), PathfindingAlgorithm::WallFollower(Hand::Right)));
+        });
+
+        group.bench_with_input(BenchmarkId::new("DeadEndFilling", size), &maze, |b, maze| {
+            b.iter(|| MazeSolver::solve(black_box(maze), PathfindingAlgorithm::DeadEndFilling));
+        });
     }
 
     group.finish();
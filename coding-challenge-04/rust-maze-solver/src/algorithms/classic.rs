@@ -0,0 +1,276 @@
+//! Classic strategies a person could carry out by hand. Neither promises a
+//! shortest path; they are here to compare against the graph searches.
+
+use super::solver::{SolutionResult, SolveError};
+use crate::maze::Maze;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Which hand stays on the wall while following it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Wall follower - Walks with one hand on the wall until it reaches the exit
+///
+/// Only guaranteed on simply-connected (perfect) mazes. Around a loop the
+/// walk can come back to a cell facing the same way it did before, and from
+/// there it would repeat forever; that is reported as
+/// [`SolveError::Looped`]. There are at most as many such states as cell
+/// sides, so detection always happens within that many steps.
+///
+/// The trace is the full walk, dead ends included; the path is the walk with
+/// every detour that came back on itself cut out.
+pub(crate) fn wall_follower(maze: &Maze, hand: Hand) -> Result<SolutionResult, SolveError> {
+    let goal = maze.end;
+    let mut current = maze.start;
+    let mut trace = vec![current];
+    let mut path = vec![current];
+    let mut path_index: HashMap<(usize, usize), usize> = HashMap::from([(current, 0)]);
+    let mut seen_states = HashSet::new();
+
+    // Start with the hand on the first wall found, as a person would
+    let sides = maze.sides(current.0, current.1);
+    let mut entry = (0..sides.len())
+        .find(|&i| !is_open(maze, current, sides[i]))
+        .unwrap_or(0);
+
+    while current != goal {
+        if !seen_states.insert((current, entry)) {
+            return Err(SolveError::Looped { steps: trace.len() - 1 });
+        }
+
+        let sides = maze.sides(current.0, current.1);
+        let count = sides.len();
+        // Sides run clockwise, so the right hand turns the other way first
+        let next = (1..=count)
+            .map(|turn| match hand {
+                Hand::Left => (entry + turn) % count,
+                Hand::Right => (entry + count - turn) % count,
+            })
+            .find_map(|i| sides[i].filter(|_| is_open(maze, current, sides[i])));
+        let Some(next) = next else {
+            // Walled in on every side
+            return Err(SolveError::NoPath);
+        };
+
+        let next_sides = maze.sides(next.0, next.1);
+        entry = next_sides
+            .iter()
+            .position(|&side| side == Some(current))
+            .expect("adjacency is symmetric");
+        current = next;
+        trace.push(current);
+
+        if let Some(&index) = path_index.get(&current) {
+            for cell in path.drain(index + 1..) {
+                path_index.remove(&cell);
+            }
+        } else {
+            path_index.insert(current, path.len());
+            path.push(current);
+        }
+    }
+
+    let visited: HashSet<_> = trace.iter().copied().collect();
+    Ok(SolutionResult {
+        path_length: path.len(),
+        nodes_explored: visited.len(),
+        path,
+        visited,
+        trace,
+    })
+}
+
+/// Dead-end filling - Seals dead ends until only the solution corridor is left
+///
+/// Any cell other than the start and exit with at most one unsealed open
+/// neighbor is a dead end. Sealing it can turn its neighbor into one, so
+/// filling runs until no dead ends remain; in a perfect maze that leaves
+/// exactly the solution. Loops are never filled, so in a braided maze the
+/// path is the shortest one through what is left.
+///
+/// The trace lists the cells in the order they were sealed.
+pub(crate) fn dead_end_filling(maze: &Maze) -> Option<SolutionResult> {
+    let start = maze.start;
+    let goal = maze.end;
+    let fixed = |cell: (usize, usize)| cell == start || cell == goal;
+
+    let mut open_count: HashMap<(usize, usize), usize> = maze
+        .positions()
+        .into_iter()
+        .filter(|&cell| is_walkable(maze, cell))
+        .map(|cell| (cell, open_neighbors(maze, cell).count()))
+        .collect();
+    let mut dead_ends: VecDeque<_> = open_count
+        .iter()
+        .filter(|&(&cell, &count)| count <= 1 && !fixed(cell))
+        .map(|(&cell, _)| cell)
+        .collect();
+    // Seal in a stable order so the animation doesn't change between runs
+    dead_ends.make_contiguous().sort();
+
+    let mut sealed = HashSet::new();
+    let mut trace = Vec::new();
+    while let Some(cell) = dead_ends.pop_front() {
+        if !sealed.insert(cell) {
+            continue;
+        }
+        trace.push(cell);
+
+        for neighbor in open_neighbors(maze, cell) {
+            if sealed.contains(&neighbor) {
+                continue;
+            }
+            if let Some(count) = open_count.get_mut(&neighbor) {
+                *count -= 1;
+                if *count <= 1 && !fixed(neighbor) {
+                    dead_ends.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    // Read the path off what is left
+    let mut came_from = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    let mut reached = HashSet::from([start]);
+    while let Some(current) = queue.pop_front() {
+        if current == goal {
+            let mut path = vec![current];
+            let mut cell = current;
+            while let Some(&prev) = came_from.get(&cell) {
+                path.push(prev);
+                cell = prev;
+            }
+            path.reverse();
+
+            let visited: HashSet<_> = sealed.union(&reached).copied().collect();
+            return Some(SolutionResult {
+                path_length: path.len(),
+                nodes_explored: visited.len(),
+                path,
+                visited,
+                trace,
+            });
+        }
+
+        for neighbor in open_neighbors(maze, current) {
+            if !sealed.contains(&neighbor) && reached.insert(neighbor) {
+                came_from.insert(neighbor, current);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    None
+}
+
+/// The same walkability rule the graph searches use: the exit always counts
+fn is_walkable(maze: &Maze, cell: (usize, usize)) -> bool {
+    cell == maze.end || maze.get(cell.0, cell.1).is_some_and(|c| c.is_walkable())
+}
+
+fn is_open(maze: &Maze, from: (usize, usize), side: Option<(usize, usize)>) -> bool {
+    side.is_some_and(|to| maze.is_linked(from, to) && is_walkable(maze, to))
+}
+
+fn open_neighbors(maze: &Maze, cell: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+    maze.open_neighbors(cell.0, cell.1)
+        .into_iter()
+        .filter(move |&next| is_walkable(maze, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm};
+    use crate::maze::{CellType, Topology};
+
+    /// A maze with no inner walls at all, so every cell sits on a loop
+    fn open_room(size: usize) -> Maze {
+        let mut maze = Maze::new(size, size);
+        for (row, col) in maze.positions() {
+            maze.set_cell_type(row, col, CellType::Path);
+        }
+        for (row, col) in maze.positions() {
+            for next in maze.neighbors(row, col) {
+                maze.link((row, col), next);
+            }
+        }
+        maze
+    }
+
+    #[test]
+    fn test_wall_follower_solves_perfect_mazes() {
+        for topology in [Topology::Square, Topology::Hex, Topology::Theta] {
+            let maze = MazeGenerator::generate_with_topology(
+                12,
+                10,
+                GeneratorAlgorithm::RecursiveBacktracker,
+                topology,
+            );
+            let bfs = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+
+            for hand in [Hand::Left, Hand::Right] {
+                let result = wall_follower(&maze, hand).unwrap();
+                assert_eq!(result.path.first(), Some(&maze.start));
+                assert_eq!(result.path.last(), Some(&maze.end));
+                for step in result.trace.windows(2) {
+                    assert!(maze.is_linked(step[0], step[1]), "{:?} {:?} crossed a wall", topology, hand);
+                }
+                // Dead ends are walked but cut from the path
+                assert_eq!(result.path_length, bfs.path_length, "{:?} {:?}", topology, hand);
+                assert!(result.trace.len() >= result.path.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wall_follower_detects_loops() {
+        let mut maze = open_room(4);
+        maze.start = (1, 1);
+        maze.end = (3, 3);
+
+        for hand in [Hand::Left, Hand::Right] {
+            match wall_follower(&maze, hand) {
+                // Four sides per cell bound the states it can be in
+                Err(SolveError::Looped { steps }) => assert!(steps <= 4 * maze.cell_count(), "{}", steps),
+                other => panic!("{:?}: expected a loop, got {:?}", hand, other.map(|r| r.path)),
+            }
+        }
+
+        // Starting against the outer wall, the same room is solved
+        maze.start = (0, 0);
+        assert!(wall_follower(&maze, Hand::Right).is_ok());
+    }
+
+    #[test]
+    fn test_dead_end_filling_matches_bfs_on_perfect_mazes() {
+        for algorithm in [
+            GeneratorAlgorithm::RecursiveBacktracker,
+            GeneratorAlgorithm::Prims,
+            GeneratorAlgorithm::Kruskals,
+        ] {
+            for topology in [Topology::Square, Topology::Hex, Topology::Theta] {
+                let maze = MazeGenerator::generate_with_topology(14, 9, algorithm, topology);
+                let bfs = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+                let result = dead_end_filling(&maze).unwrap();
+
+                assert_eq!(result.path, bfs.path, "{:?} {:?}", algorithm, topology);
+                // Everything off the solution corridor gets sealed
+                assert_eq!(result.trace.len() + result.path_length, maze.cell_count());
+            }
+        }
+    }
+
+    #[test]
+    fn test_dead_end_filling_leaves_loops_alone() {
+        let maze = open_room(4);
+        let result = dead_end_filling(&maze).unwrap();
+
+        assert!(result.trace.is_empty());
+        assert_eq!(result.path_length, 7);
+    }
+}
@@ -1,6 +1,7 @@
-use crate::maze::{CellType, Maze};
+use crate::maze::{CellType, Maze, Topology};
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,29 +20,60 @@ impl MazeGenerator {
         height: usize,
         algorithm: GeneratorAlgorithm,
     ) -> Maze {
+        Self::generate_with_topology(width, height, algorithm, Topology::Square)
+    }
+
+    /// Generate a maze over any topology. Every algorithm only walks the
+    /// maze's neighbor function, so each one works on every topology.
+    pub fn generate_with_topology(
+        width: usize,
+        height: usize,
+        algorithm: GeneratorAlgorithm,
+        topology: Topology,
+    ) -> Maze {
+        Self::generate_with_rng(width, height, algorithm, topology, &mut rand::thread_rng())
+    }
+
+    /// Generate a maze from a seed; the same seed always gives the same maze
+    pub fn generate_seeded(
+        width: usize,
+        height: usize,
+        algorithm: GeneratorAlgorithm,
+        topology: Topology,
+        seed: u64,
+    ) -> Maze {
+        Self::generate_with_rng(width, height, algorithm, topology, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Generate a maze drawing every random choice from `rng`
+    pub fn generate_with_rng<R: Rng>(
+        width: usize,
+        height: usize,
+        algorithm: GeneratorAlgorithm,
+        topology: Topology,
+        rng: &mut R,
+    ) -> Maze {
+        let maze = Maze::with_topology(width, height, topology);
         match algorithm {
-            GeneratorAlgorithm::RecursiveBacktracker => {
-                Self::recursive_backtracker(width, height)
-            }
-            GeneratorAlgorithm::Prims => Self::prims(width, height),
-            GeneratorAlgorithm::Kruskals => Self::kruskals(width, height),
-            GeneratorAlgorithm::AldousBroder => Self::aldous_broder(width, height),
+            GeneratorAlgorithm::RecursiveBacktracker => Self::recursive_backtracker(maze, rng),
+            GeneratorAlgorithm::Prims => Self::prims(maze, rng),
+            GeneratorAlgorithm::Kruskals => Self::kruskals(maze, rng),
+            GeneratorAlgorithm::AldousBroder => Self::aldous_broder(maze, rng),
         }
     }
 
     /// Recursive Backtracker (DFS-based) - Creates perfect mazes with long corridors
-    fn recursive_backtracker(width: usize, height: usize) -> Maze {
-        let mut maze = Maze::new(width, height);
-        let mut rng = rand::thread_rng();
+    fn recursive_backtracker<R: Rng>(mut maze: Maze, rng: &mut R) -> Maze {
         let mut stack = Vec::new();
         let mut visited = HashSet::new();
 
         // Start from random position
-        let start_row = rng.gen_range(0..height);
-        let start_col = rng.gen_range(0..width);
+        let Some(&start) = maze.positions().choose(rng) else {
+            return maze;
+        };
 
-        stack.push((start_row, start_col));
-        visited.insert((start_row, start_col));
+        stack.push(start);
+        visited.insert(start);
 
         while let Some((row, col)) = stack.last().copied() {
             maze.set_cell_type(row, col, CellType::Path);
@@ -52,10 +84,10 @@ impl MazeGenerator {
             if neighbors.is_empty() {
                 stack.pop();
             } else {
-                let &(next_row, next_col) = neighbors.choose(&mut rng).unwrap();
-                Self::carve_path(&mut maze, row, col, next_row, next_col);
-                visited.insert((next_row, next_col));
-                stack.push((next_row, next_col));
+                let &next = neighbors.choose(rng).unwrap();
+                Self::carve_path(&mut maze, (row, col), next);
+                visited.insert(next);
+                stack.push(next);
             }
         }
 
@@ -65,38 +97,37 @@ impl MazeGenerator {
     }
 
     /// Prim's Algorithm - Creates mazes with many short dead ends
-    fn prims(width: usize, height: usize) -> Maze {
-        let mut maze = Maze::new(width, height);
-        let mut rng = rand::thread_rng();
+    fn prims<R: Rng>(mut maze: Maze, rng: &mut R) -> Maze {
         let mut walls = Vec::new();
         let mut visited = HashSet::new();
 
         // Start from random cell
-        let start_row = rng.gen_range(0..height);
-        let start_col = rng.gen_range(0..width);
+        let Some(&start) = maze.positions().choose(rng) else {
+            return maze;
+        };
 
-        visited.insert((start_row, start_col));
-        maze.set_cell_type(start_row, start_col, CellType::Path);
+        visited.insert(start);
+        maze.set_cell_type(start.0, start.1, CellType::Path);
 
         // Add walls of starting cell
-        for (nr, nc) in maze.neighbors(start_row, start_col) {
-            if !visited.contains(&(nr, nc)) {
-                walls.push((start_row, start_col, nr, nc));
+        for neighbor in maze.neighbors(start.0, start.1) {
+            if !visited.contains(&neighbor) {
+                walls.push((start, neighbor));
             }
         }
 
         while !walls.is_empty() {
             let idx = rng.gen_range(0..walls.len());
-            let (from_row, from_col, to_row, to_col) = walls.swap_remove(idx);
+            let (from, to) = walls.swap_remove(idx);
 
-            if !visited.contains(&(to_row, to_col)) {
-                visited.insert((to_row, to_col));
-                Self::carve_path(&mut maze, from_row, from_col, to_row, to_col);
+            if !visited.contains(&to) {
+                visited.insert(to);
+                Self::carve_path(&mut maze, from, to);
 
                 // Add new walls
-                for (nr, nc) in maze.neighbors(to_row, to_col) {
-                    if !visited.contains(&(nr, nc)) {
-                        walls.push((to_row, to_col, nr, nc));
+                for neighbor in maze.neighbors(to.0, to.1) {
+                    if !visited.contains(&neighbor) {
+                        walls.push((to, neighbor));
                     }
                 }
             }
@@ -108,43 +139,36 @@ impl MazeGenerator {
     }
 
     /// Kruskal's Algorithm - Creates uniform mazes
-    fn kruskals(width: usize, height: usize) -> Maze {
-        let mut maze = Maze::new(width, height);
-        let mut rng = rand::thread_rng();
+    fn kruskals<R: Rng>(mut maze: Maze, rng: &mut R) -> Maze {
 
         // Initialize all cells as paths in their own set
-        let mut parent: Vec<Vec<(usize, usize)>> = (0..height)
-            .map(|r| (0..width).map(|c| (r, c)).collect())
+        let mut parent: Vec<Vec<(usize, usize)>> = maze
+            .grid
+            .iter()
+            .enumerate()
+            .map(|(r, cells)| (0..cells.len()).map(|c| (r, c)).collect())
             .collect();
 
-        for row in 0..height {
-            for col in 0..width {
-                maze.set_cell_type(row, col, CellType::Path);
-            }
-        }
-
-        // Create list of all possible edges
+        // Create list of all possible edges, each counted once
         let mut edges = Vec::new();
-        for row in 0..height {
-            for col in 0..width {
-                if row + 1 < height {
-                    edges.push((row, col, row + 1, col));
-                }
-                if col + 1 < width {
-                    edges.push((row, col, row, col + 1));
+        for (row, col) in maze.positions() {
+            maze.set_cell_type(row, col, CellType::Path);
+            for neighbor in maze.neighbors(row, col) {
+                if (row, col) < neighbor {
+                    edges.push(((row, col), neighbor));
                 }
             }
         }
 
-        edges.shuffle(&mut rng);
+        edges.shuffle(rng);
 
         // Process edges
-        for (r1, c1, r2, c2) in edges {
-            let root1 = Self::find_root(&parent, r1, c1);
-            let root2 = Self::find_root(&parent, r2, c2);
+        for (a, b) in edges {
+            let root1 = Self::find_root(&parent, a.0, a.1);
+            let root2 = Self::find_root(&parent, b.0, b.1);
 
             if root1 != root2 {
-                Self::carve_path(&mut maze, r1, c1, r2, c2);
+                Self::carve_path(&mut maze, a, b);
                 parent[root2.0][root2.1] = root1;
             }
         }
@@ -155,30 +179,29 @@ impl MazeGenerator {
     }
 
     /// Aldous-Broder Algorithm - Random walk that creates uniform spanning trees
-    fn aldous_broder(width: usize, height: usize) -> Maze {
-        let mut maze = Maze::new(width, height);
-        let mut rng = rand::thread_rng();
+    fn aldous_broder<R: Rng>(mut maze: Maze, rng: &mut R) -> Maze {
         let mut visited = HashSet::new();
-        let total_cells = width * height;
+        let total_cells = maze.cell_count();
 
         // Start from random position
-        let mut current_row = rng.gen_range(0..height);
-        let mut current_col = rng.gen_range(0..width);
+        let Some(&start) = maze.positions().choose(rng) else {
+            return maze;
+        };
+        let mut current = start;
 
-        visited.insert((current_row, current_col));
-        maze.set_cell_type(current_row, current_col, CellType::Path);
+        visited.insert(current);
+        maze.set_cell_type(current.0, current.1, CellType::Path);
 
         while visited.len() < total_cells {
-            let neighbors = maze.neighbors(current_row, current_col);
-            let &(next_row, next_col) = neighbors.choose(&mut rng).unwrap();
+            let neighbors = maze.neighbors(current.0, current.1);
+            let &next = neighbors.choose(rng).unwrap();
 
-            if !visited.contains(&(next_row, next_col)) {
-                Self::carve_path(&mut maze, current_row, current_col, next_row, next_col);
-                visited.insert((next_row, next_col));
+            if !visited.contains(&next) {
+                Self::carve_path(&mut maze, current, next);
+                visited.insert(next);
             }
 
-            current_row = next_row;
-            current_col = next_col;
+            current = next;
         }
 
         maze.mark_start();
@@ -198,46 +221,9 @@ impl MazeGenerator {
             .collect()
     }
 
-    fn carve_path(maze: &mut Maze, from_row: usize, from_col: usize, to_row: usize, to_col: usize) {
-        maze.set_cell_type(to_row, to_col, CellType::Path);
-
-        // Remove walls between cells (for visualization purposes if needed)
-        // We need to do this in two separate steps to avoid mutable borrow issues
-        if from_row == to_row {
-            // Horizontal movement
-            if from_col < to_col {
-                if let Some(from_cell) = maze.get_mut(from_row, from_col) {
-                    from_cell.walls.east = false;
-                }
-                if let Some(to_cell) = maze.get_mut(to_row, to_col) {
-                    to_cell.walls.west = false;
-                }
-            } else {
-                if let Some(from_cell) = maze.get_mut(from_row, from_col) {
-                    from_cell.walls.west = false;
-                }
-                if let Some(to_cell) = maze.get_mut(to_row, to_col) {
-                    to_cell.walls.east = false;
-                }
-            }
-        } else {
-            // Vertical movement
-            if from_row < to_row {
-                if let Some(from_cell) = maze.get_mut(from_row, from_col) {
-                    from_cell.walls.south = false;
-                }
-                if let Some(to_cell) = maze.get_mut(to_row, to_col) {
-                    to_cell.walls.north = false;
-                }
-            } else {
-                if let Some(from_cell) = maze.get_mut(from_row, from_col) {
-                    from_cell.walls.north = false;
-                }
-                if let Some(to_cell) = maze.get_mut(to_row, to_col) {
-                    to_cell.walls.south = false;
-                }
-            }
-        }
+    fn carve_path(maze: &mut Maze, from: (usize, usize), to: (usize, usize)) {
+        maze.set_cell_type(to.0, to.1, CellType::Path);
+        maze.link(from, to);
     }
 
     fn find_root(parent: &[Vec<(usize, usize)>], row: usize, col: usize) -> (usize, usize) {
@@ -320,4 +306,57 @@ mod tests {
             assert!(path_count > 0, "Algorithm {:?} should create paths", algo);
         }
     }
+
+    /// Walk the carved passages from every cell: a perfect maze has exactly
+    /// one fewer passage than cells and reaches every cell from any other.
+    fn assert_perfect(maze: &Maze) {
+        let cells = maze.positions();
+        let passages: usize = cells
+            .iter()
+            .map(|&(row, col)| maze.open_neighbors(row, col).len())
+            .sum::<usize>()
+            / 2;
+        assert_eq!(passages, cells.len() - 1, "{:?} maze has a loop or gap", maze.topology);
+
+        let mut seen = HashSet::from([cells[0]]);
+        let mut stack = vec![cells[0]];
+        while let Some((row, col)) = stack.pop() {
+            for next in maze.open_neighbors(row, col) {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        assert_eq!(seen.len(), cells.len(), "{:?} maze is not connected", maze.topology);
+    }
+
+    #[test]
+    fn test_seeded_generation_is_repeatable() {
+        for topology in [Topology::Square, Topology::Hex] {
+            let a = MazeGenerator::generate_seeded(12, 9, GeneratorAlgorithm::Prims, topology, 42);
+            let b = MazeGenerator::generate_seeded(12, 9, GeneratorAlgorithm::Prims, topology, 42);
+            assert_eq!(serde_json::to_string(&a).unwrap(), serde_json::to_string(&b).unwrap());
+            assert_perfect(&a);
+        }
+    }
+
+    #[test]
+    fn test_every_algorithm_builds_perfect_mazes_on_every_topology() {
+        let algorithms = [
+            GeneratorAlgorithm::RecursiveBacktracker,
+            GeneratorAlgorithm::Prims,
+            GeneratorAlgorithm::Kruskals,
+            GeneratorAlgorithm::AldousBroder,
+        ];
+
+        for topology in [Topology::Square, Topology::Hex, Topology::Theta] {
+            for algo in algorithms {
+                let maze = MazeGenerator::generate_with_topology(9, 7, algo, topology);
+                assert_eq!(maze.topology, topology);
+                assert_perfect(&maze);
+                assert_eq!(maze.count_cells(CellType::Start), 1);
+                assert_eq!(maze.count_cells(CellType::End), 1);
+            }
+        }
+    }
 }
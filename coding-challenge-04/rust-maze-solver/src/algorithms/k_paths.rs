@@ -0,0 +1,331 @@
+use crate::maze::Maze;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Node expansions allowed by default before enumeration gives up
+pub const DEFAULT_MAX_EXPANSIONS: usize = 1_000_000;
+
+type Pos = (usize, usize);
+
+/// One path found by [`k_shortest_paths`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedPath {
+    pub path: Vec<Pos>,
+    /// Steps taken, i.e. cells visited minus one
+    pub cost: usize,
+}
+
+impl RankedPath {
+    fn new(path: Vec<Pos>) -> Self {
+        Self {
+            cost: path.len().saturating_sub(1),
+            path,
+        }
+    }
+
+    /// Cells on the path, counting start and end (the solvers' `path_length`)
+    pub fn length(&self) -> usize {
+        self.path.len()
+    }
+
+    fn edges(&self) -> HashSet<(Pos, Pos)> {
+        self.path.windows(2).map(|step| (step[0].min(step[1]), step[0].max(step[1]))).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KPathsResult {
+    /// Paths in order of increasing cost
+    pub paths: Vec<RankedPath>,
+    /// Cells dequeued across every search Yen's algorithm ran
+    pub nodes_expanded: usize,
+    /// The expansion cap was hit, so `paths` may be missing some
+    pub truncated: bool,
+}
+
+impl KPathsResult {
+    /// Percentage of the shorter path's steps that the two paths share
+    pub fn overlap(&self, a: usize, b: usize) -> f64 {
+        let (a, b) = (&self.paths[a], &self.paths[b]);
+        let shorter = a.cost.min(b.cost);
+        if shorter == 0 {
+            return 100.0;
+        }
+        let shared = a.edges().intersection(&b.edges()).count();
+        shared as f64 / shorter as f64 * 100.0
+    }
+}
+
+/// Enumerate up to `k` shortest simple paths from start to end with Yen's
+/// algorithm, treating the maze as an unweighted graph of open passages.
+///
+/// A perfect maze has exactly one simple path, so it yields one result
+/// whatever `k` is. Each spur search counts its dequeued cells against
+/// `max_expansions`; once that runs out the paths found so far are returned
+/// with `truncated` set.
+pub fn k_shortest_paths(maze: &Maze, k: usize, max_expansions: usize) -> KPathsResult {
+    let mut search = SpurSearch {
+        maze,
+        budget: max_expansions,
+        expanded: 0,
+    };
+    let mut result = KPathsResult {
+        paths: Vec::new(),
+        nodes_expanded: 0,
+        truncated: false,
+    };
+
+    if k == 0 {
+        return result;
+    }
+
+    match search.shortest(maze.start, &HashSet::new(), &HashSet::new()) {
+        Ok(Some(path)) => result.paths.push(RankedPath::new(path)),
+        Ok(None) => {}
+        Err(Exhausted) => result.truncated = true,
+    }
+
+    // Candidates ordered by cost, then by cells for a stable tie-break
+    let mut candidates: BTreeSet<(usize, Vec<Pos>)> = BTreeSet::new();
+
+    'outer: while !result.paths.is_empty() && result.paths.len() < k {
+        let previous = result.paths.last().unwrap().path.clone();
+
+        for i in 0..previous.len() - 1 {
+            let spur = previous[i];
+            let root = &previous[..=i];
+
+            // Block the next step of every known path sharing this root
+            let blocked_edges: HashSet<(Pos, Pos)> = result
+                .paths
+                .iter()
+                .filter(|p| p.path.len() > i + 1 && p.path[..=i] == *root)
+                .map(|p| (p.path[i], p.path[i + 1]))
+                .collect();
+            // Keep the spur path simple by avoiding the root's cells
+            let blocked_cells: HashSet<Pos> = root[..i].iter().copied().collect();
+
+            match search.shortest(spur, &blocked_cells, &blocked_edges) {
+                Ok(Some(spur_path)) => {
+                    let mut total = root[..i].to_vec();
+                    total.extend(spur_path);
+                    let known = result.paths.iter().any(|p| p.path == total);
+                    if !known {
+                        candidates.insert((total.len() - 1, total));
+                    }
+                }
+                Ok(None) => {}
+                Err(Exhausted) => {
+                    result.truncated = true;
+                    break 'outer;
+                }
+            }
+        }
+
+        match candidates.pop_first() {
+            Some((_, path)) => result.paths.push(RankedPath::new(path)),
+            None => break,
+        }
+    }
+
+    result.nodes_expanded = search.expanded;
+    result
+}
+
+/// The expansion budget ran out mid-search
+struct Exhausted;
+
+struct SpurSearch<'a> {
+    maze: &'a Maze,
+    budget: usize,
+    expanded: usize,
+}
+
+impl SpurSearch<'_> {
+    /// Breadth-first shortest path from `from` to the maze end, avoiding
+    /// `blocked_cells` and the directed steps in `blocked_edges`
+    fn shortest(
+        &mut self,
+        from: Pos,
+        blocked_cells: &HashSet<Pos>,
+        blocked_edges: &HashSet<(Pos, Pos)>,
+    ) -> Result<Option<Vec<Pos>>, Exhausted> {
+        let goal = self.maze.end;
+        let mut queue = VecDeque::from([from]);
+        let mut came_from: HashMap<Pos, Pos> = HashMap::new();
+        let mut visited = HashSet::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if self.expanded == self.budget {
+                return Err(Exhausted);
+            }
+            self.expanded += 1;
+
+            if current == goal {
+                let mut path = vec![current];
+                let mut at = current;
+                while let Some(&prev) = came_from.get(&at) {
+                    path.push(prev);
+                    at = prev;
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+
+            for neighbor in self.maze.open_neighbors(current.0, current.1) {
+                let walkable = self
+                    .maze
+                    .get(neighbor.0, neighbor.1)
+                    .is_some_and(|cell| cell.is_walkable() || neighbor == goal);
+                if !walkable
+                    || visited.contains(&neighbor)
+                    || blocked_cells.contains(&neighbor)
+                    || blocked_edges.contains(&(current, neighbor))
+                {
+                    continue;
+                }
+
+                visited.insert(neighbor);
+                came_from.insert(neighbor, current);
+                queue.push_back(neighbor);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm};
+    use crate::maze::CellType;
+
+    /// A square maze with only the given passages open
+    fn graph(width: usize, height: usize, start: Pos, end: Pos, links: &[(Pos, Pos)]) -> Maze {
+        let mut maze = Maze::new(width, height);
+        for (row, col) in maze.positions() {
+            maze.set_cell_type(row, col, CellType::Path);
+        }
+        for &(a, b) in links {
+            maze.link(a, b);
+        }
+        maze.start = start;
+        maze.end = end;
+        maze
+    }
+
+    /// Start and end in the middle row, joined directly (2 steps), over the
+    /// top (4 steps) and around the bottom (6 steps)
+    fn three_routes() -> Maze {
+        graph(
+            3,
+            4,
+            (1, 0),
+            (1, 2),
+            &[
+                ((1, 0), (1, 1)),
+                ((1, 1), (1, 2)),
+                ((1, 0), (0, 0)),
+                ((0, 0), (0, 1)),
+                ((0, 1), (0, 2)),
+                ((0, 2), (1, 2)),
+                ((1, 0), (2, 0)),
+                ((2, 0), (3, 0)),
+                ((3, 0), (3, 1)),
+                ((3, 1), (3, 2)),
+                ((3, 2), (2, 2)),
+                ((2, 2), (1, 2)),
+            ],
+        )
+    }
+
+    fn costs(result: &KPathsResult) -> Vec<usize> {
+        result.paths.iter().map(|p| p.cost).collect()
+    }
+
+    #[test]
+    fn test_finds_second_and_third_shortest() {
+        let result = k_shortest_paths(&three_routes(), 3, DEFAULT_MAX_EXPANSIONS);
+
+        assert_eq!(costs(&result), vec![2, 4, 6]);
+        assert_eq!(result.paths[0].path, vec![(1, 0), (1, 1), (1, 2)]);
+        assert_eq!(result.paths[1].path, vec![(1, 0), (0, 0), (0, 1), (0, 2), (1, 2)]);
+        assert_eq!(result.paths[2].length(), 7);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_stops_when_graph_runs_out_of_paths() {
+        let result = k_shortest_paths(&three_routes(), 10, DEFAULT_MAX_EXPANSIONS);
+
+        assert_eq!(costs(&result), vec![2, 4, 6]);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_open_grid_orders_tied_paths_before_longer_ones() {
+        // A 3x3 room: six 4-step corner-to-corner paths, then 6-step detours
+        let mut links = Vec::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                if col < 2 {
+                    links.push(((row, col), (row, col + 1)));
+                }
+                if row < 2 {
+                    links.push(((row, col), (row + 1, col)));
+                }
+            }
+        }
+        let maze = graph(3, 3, (0, 0), (2, 2), &links);
+        let result = k_shortest_paths(&maze, 7, DEFAULT_MAX_EXPANSIONS);
+
+        assert_eq!(costs(&result), vec![4, 4, 4, 4, 4, 4, 6]);
+        let distinct: HashSet<&Vec<Pos>> = result.paths.iter().map(|p| &p.path).collect();
+        assert_eq!(distinct.len(), 7);
+        for path in &result.paths {
+            let cells: HashSet<&Pos> = path.path.iter().collect();
+            assert_eq!(cells.len(), path.path.len(), "path revisits a cell");
+        }
+    }
+
+    #[test]
+    fn test_perfect_maze_has_one_path() {
+        let maze = MazeGenerator::generate(12, 12, GeneratorAlgorithm::RecursiveBacktracker);
+        let bfs = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+        let result = k_shortest_paths(&maze, 3, DEFAULT_MAX_EXPANSIONS);
+
+        assert_eq!(result.paths.len(), 1);
+        assert_eq!(result.paths[0].path, bfs.path);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_expansion_cap_returns_partial_results() {
+        let maze = three_routes();
+        let first_only = k_shortest_paths(&maze, 1, DEFAULT_MAX_EXPANSIONS);
+
+        let capped = k_shortest_paths(&maze, 3, first_only.nodes_expanded);
+        assert_eq!(costs(&capped), vec![2]);
+        assert!(capped.truncated);
+        assert_eq!(capped.nodes_expanded, first_only.nodes_expanded);
+
+        let starved = k_shortest_paths(&maze, 3, 1);
+        assert!(starved.paths.is_empty());
+        assert!(starved.truncated);
+    }
+
+    #[test]
+    fn test_overlap_percentage() {
+        let mut maze = three_routes();
+        // Extend the end by one step so every route shares its last edge
+        maze.link((1, 2), (2, 2));
+        maze.end = (2, 2);
+        let result = k_shortest_paths(&maze, 2, DEFAULT_MAX_EXPANSIONS);
+
+        assert_eq!(costs(&result), vec![3, 5]);
+        assert_eq!(result.overlap(0, 0), 100.0);
+        // Only the final (1,2)-(2,2) step is shared out of the shorter path's three
+        assert!((result.overlap(0, 1) - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(result.overlap(0, 1), result.overlap(1, 0));
+    }
+}
@@ -1,5 +1,9 @@
+pub mod classic;
 pub mod generator;
+pub mod k_paths;
 pub mod solver;
 
+pub use classic::Hand;
 pub use generator::{GeneratorAlgorithm, MazeGenerator};
-pub use solver::{PathfindingAlgorithm, MazeSolver, SolutionResult};
+pub use k_paths::{k_shortest_paths, KPathsResult, RankedPath, DEFAULT_MAX_EXPANSIONS};
+pub use solver::{PathfindingAlgorithm, MazeSolver, SolutionResult, SolveError};
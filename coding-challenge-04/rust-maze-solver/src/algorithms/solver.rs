@@ -1,6 +1,9 @@
+use super::classic::{self, Hand};
+use crate::maze::topology::{self, Topology};
 use crate::maze::Maze;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PathfindingAlgorithm {
@@ -8,6 +11,9 @@ pub enum PathfindingAlgorithm {
     BFS,
     DFS,
     Dijkstra,
+    /// Keeps one hand on the wall; perfect mazes only
+    WallFollower(Hand),
+    DeadEndFilling,
 }
 
 #[derive(Debug, Clone)]
@@ -16,18 +22,54 @@ pub struct SolutionResult {
     pub visited: HashSet<(usize, usize)>,
     pub path_length: usize,
     pub nodes_explored: usize,
+    /// Cells in the order the algorithm reached them, for animation.
+    /// A wall follower's walk lists a cell again each time it passes.
+    pub trace: Vec<(usize, usize)>,
 }
 
+/// Why a solver gave up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The exit can't be reached from the start
+    NoPath,
+    /// The wall follower came back to a place it had already been, facing
+    /// the same way, without reaching the exit
+    Looped { steps: usize },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NoPath => write!(f, "No solution found!"),
+            SolveError::Looped { steps } => write!(
+                f,
+                "Wall follower is going round a loop after {} steps; it only works on mazes without loops",
+                steps
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
 pub struct MazeSolver;
 
 impl MazeSolver {
     pub fn solve(maze: &Maze, algorithm: PathfindingAlgorithm) -> Option<SolutionResult> {
-        match algorithm {
+        Self::try_solve(maze, algorithm).ok()
+    }
+
+    /// Like [`MazeSolver::solve`], but says why no solution was found
+    pub fn try_solve(maze: &Maze, algorithm: PathfindingAlgorithm) -> Result<SolutionResult, SolveError> {
+        let result = match algorithm {
             PathfindingAlgorithm::AStar => Self::a_star(maze),
             PathfindingAlgorithm::BFS => Self::bfs(maze),
             PathfindingAlgorithm::DFS => Self::dfs(maze),
             PathfindingAlgorithm::Dijkstra => Self::dijkstra(maze),
-        }
+            PathfindingAlgorithm::WallFollower(hand) => return classic::wall_follower(maze, hand),
+            PathfindingAlgorithm::DeadEndFilling => classic::dead_end_filling(maze),
+        };
+        result.ok_or(SolveError::NoPath)
     }
 
     /// A* Algorithm - Optimal pathfinding with heuristic
@@ -38,15 +80,18 @@ impl MazeSolver {
         let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
         let mut g_score: HashMap<(usize, usize), f64> = HashMap::new();
         let mut visited = HashSet::new();
+        let mut trace = Vec::new();
 
         g_score.insert(start, 0.0);
         open_set.push(AStarNode {
             position: start,
-            f_score: Self::heuristic(start, goal),
+            f_score: Self::heuristic(maze.topology, start, goal),
         });
 
         while let Some(AStarNode { position: current, .. }) = open_set.pop() {
-            visited.insert(current);
+            if visited.insert(current) {
+                trace.push(current);
+            }
 
             if current == goal {
                 let path = Self::reconstruct_path(&came_from, current);
@@ -55,10 +100,11 @@ impl MazeSolver {
                     nodes_explored: visited.len(),
                     path,
                     visited,
+                    trace,
                 });
             }
 
-            for neighbor in maze.neighbors(current.0, current.1) {
+            for neighbor in maze.open_neighbors(current.0, current.1) {
                 if let Some(cell) = maze.get(neighbor.0, neighbor.1) {
                     if !cell.is_walkable() && neighbor != goal {
                         continue;
@@ -69,7 +115,7 @@ impl MazeSolver {
                     if tentative_g_score < *g_score.get(&neighbor).unwrap_or(&f64::MAX) {
                         came_from.insert(neighbor, current);
                         g_score.insert(neighbor, tentative_g_score);
-                        let f_score = tentative_g_score + Self::heuristic(neighbor, goal);
+                        let f_score = tentative_g_score + Self::heuristic(maze.topology, neighbor, goal);
                         open_set.push(AStarNode {
                             position: neighbor,
                             f_score,
@@ -88,10 +134,12 @@ impl MazeSolver {
         let goal = maze.end;
         let mut queue = VecDeque::new();
         let mut visited = HashSet::new();
+        let mut trace = Vec::new();
         let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
 
         queue.push_back(start);
         visited.insert(start);
+        trace.push(start);
 
         while let Some(current) = queue.pop_front() {
             if current == goal {
@@ -101,16 +149,18 @@ impl MazeSolver {
                     nodes_explored: visited.len(),
                     path,
                     visited,
+                    trace,
                 });
             }
 
-            for neighbor in maze.neighbors(current.0, current.1) {
+            for neighbor in maze.open_neighbors(current.0, current.1) {
                 if let Some(cell) = maze.get(neighbor.0, neighbor.1) {
                     if (!cell.is_walkable() && neighbor != goal) || visited.contains(&neighbor) {
                         continue;
                     }
 
                     visited.insert(neighbor);
+                    trace.push(neighbor);
                     came_from.insert(neighbor, current);
                     queue.push_back(neighbor);
                 }
@@ -126,6 +176,7 @@ impl MazeSolver {
         let goal = maze.end;
         let mut stack = Vec::new();
         let mut visited = HashSet::new();
+        let mut trace = Vec::new();
         let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
 
         stack.push(start);
@@ -136,6 +187,7 @@ impl MazeSolver {
             }
 
             visited.insert(current);
+            trace.push(current);
 
             if current == goal {
                 let path = Self::reconstruct_path(&came_from, current);
@@ -144,10 +196,11 @@ impl MazeSolver {
                     nodes_explored: visited.len(),
                     path,
                     visited,
+                    trace,
                 });
             }
 
-            for neighbor in maze.neighbors(current.0, current.1) {
+            for neighbor in maze.open_neighbors(current.0, current.1) {
                 if let Some(cell) = maze.get(neighbor.0, neighbor.1) {
                     if (!cell.is_walkable() && neighbor != goal) || visited.contains(&neighbor) {
                         continue;
@@ -172,6 +225,7 @@ impl MazeSolver {
         let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
         let mut distances: HashMap<(usize, usize), f64> = HashMap::new();
         let mut visited = HashSet::new();
+        let mut trace = Vec::new();
 
         distances.insert(start, 0.0);
         open_set.push(DijkstraNode {
@@ -185,6 +239,7 @@ impl MazeSolver {
             }
 
             visited.insert(current);
+            trace.push(current);
 
             if current == goal {
                 let path = Self::reconstruct_path(&came_from, current);
@@ -193,10 +248,11 @@ impl MazeSolver {
                     nodes_explored: visited.len(),
                     path,
                     visited,
+                    trace,
                 });
             }
 
-            for neighbor in maze.neighbors(current.0, current.1) {
+            for neighbor in maze.open_neighbors(current.0, current.1) {
                 if let Some(cell) = maze.get(neighbor.0, neighbor.1) {
                     if !cell.is_walkable() && neighbor != goal {
                         continue;
@@ -219,11 +275,18 @@ impl MazeSolver {
         None
     }
 
-    fn heuristic(from: (usize, usize), to: (usize, usize)) -> f64 {
-        // Manhattan distance
-        let dx = (from.0 as i32 - to.0 as i32).abs() as f64;
-        let dy = (from.1 as i32 - to.1 as i32).abs() as f64;
-        dx + dy
+    fn heuristic(topology: Topology, from: (usize, usize), to: (usize, usize)) -> f64 {
+        match topology {
+            Topology::Square => {
+                // Manhattan distance
+                let dx = (from.0 as i32 - to.0 as i32).abs() as f64;
+                let dy = (from.1 as i32 - to.1 as i32).abs() as f64;
+                dx + dy
+            }
+            Topology::Hex => topology::hex_distance(from, to) as f64,
+            // Each step moves at most one ring in or out
+            Topology::Theta => from.0.abs_diff(to.0) as f64,
+        }
     }
 
     fn reconstruct_path(
@@ -346,8 +409,10 @@ mod tests {
 
     #[test]
     fn test_heuristic_calculation() {
-        assert_eq!(MazeSolver::heuristic((0, 0), (3, 4)), 7.0);
-        assert_eq!(MazeSolver::heuristic((5, 5), (5, 5)), 0.0);
+        assert_eq!(MazeSolver::heuristic(Topology::Square, (0, 0), (3, 4)), 7.0);
+        assert_eq!(MazeSolver::heuristic(Topology::Square, (5, 5), (5, 5)), 0.0);
+        assert_eq!(MazeSolver::heuristic(Topology::Hex, (0, 0), (3, 1)), 3.0);
+        assert_eq!(MazeSolver::heuristic(Topology::Theta, (4, 17), (1, 2)), 3.0);
     }
 
     #[test]
@@ -359,4 +424,28 @@ mod tests {
         assert!(result.path_length > 0);
         assert!(result.path_length <= result.nodes_explored);
     }
+
+    #[test]
+    fn test_solvers_follow_passages_on_every_topology() {
+        for topology in [Topology::Square, Topology::Hex, Topology::Theta] {
+            let maze = MazeGenerator::generate_with_topology(
+                12,
+                10,
+                GeneratorAlgorithm::RecursiveBacktracker,
+                topology,
+            );
+            let bfs = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+
+            for algo in [PathfindingAlgorithm::AStar, PathfindingAlgorithm::DFS, PathfindingAlgorithm::Dijkstra] {
+                let result = MazeSolver::solve(&maze, algo).unwrap();
+                assert_eq!(result.path.first(), Some(&maze.start));
+                assert_eq!(result.path.last(), Some(&maze.end));
+                for step in result.path.windows(2) {
+                    assert!(maze.is_linked(step[0], step[1]), "{:?} {:?} crossed a wall", topology, algo);
+                }
+                // A perfect maze has exactly one path between two cells
+                assert_eq!(result.path_length, bfs.path_length);
+            }
+        }
+    }
 }
@@ -19,7 +19,7 @@ struct MazeMetadata {
 
 pub fn save_maze(maze: &Maze, path: &Path, algorithm: Option<&str>) -> io::Result<()> {
     let maze_file = MazeFile {
-        version: "1.0".to_string(),
+        version: "1.1".to_string(),
         maze: maze.clone(),
         metadata: MazeMetadata {
             generated_algorithm: algorithm.map(|s| s.to_string()),
@@ -33,6 +33,25 @@ pub fn save_maze(maze: &Maze, path: &Path, algorithm: Option<&str>) -> io::Resul
     Ok(())
 }
 
+pub fn export_maze_as_svg(maze: &Maze, path: &Path) -> io::Result<()> {
+    use crate::visualization::MazeRenderer;
+
+    let renderer = MazeRenderer::new();
+    fs::write(path, renderer.render_svg(maze, &[]))?;
+
+    Ok(())
+}
+
+/// Write the maze as SVG with each of `paths` drawn in its own color
+pub fn export_solution_as_svg(maze: &Maze, paths: &[Vec<(usize, usize)>], path: &Path) -> io::Result<()> {
+    use crate::visualization::MazeRenderer;
+
+    let renderer = MazeRenderer::new();
+    fs::write(path, renderer.render_svg_paths(maze, paths))?;
+
+    Ok(())
+}
+
 pub fn load_maze(path: &Path) -> io::Result<Maze> {
     let contents = fs::read_to_string(path)?;
     let maze_file: MazeFile = serde_json::from_str(&contents)?;
@@ -55,6 +74,7 @@ pub fn export_maze_as_text(maze: &Maze, path: &Path) -> io::Result<()> {
 mod tests {
     use super::*;
     use crate::algorithms::{GeneratorAlgorithm, MazeGenerator};
+    use crate::maze::Topology;
     use std::fs;
 
     #[test]
@@ -85,4 +105,62 @@ mod tests {
 
         fs::remove_file(temp_path).ok();
     }
+
+    #[test]
+    fn test_topology_survives_save_and_load() {
+        let maze = MazeGenerator::generate_with_topology(
+            6,
+            5,
+            GeneratorAlgorithm::Prims,
+            Topology::Hex,
+        );
+        let temp_path = std::env::temp_dir().join("test_maze_hex.json");
+
+        save_maze(&maze, &temp_path, Some("Prims")).unwrap();
+        let contents = fs::read_to_string(&temp_path).unwrap();
+        assert!(contents.contains("\"topology\": \"Hex\""));
+
+        let loaded = load_maze(&temp_path).unwrap();
+        assert_eq!(loaded.topology, Topology::Hex);
+        assert_eq!(loaded.passages, maze.passages);
+
+        fs::remove_file(temp_path).ok();
+    }
+
+    #[test]
+    fn test_untagged_maze_loads_as_square() {
+        let json = r#"{
+            "version": "1.0",
+            "maze": { "width": 1, "height": 1, "grid": [[{
+                "cell_type": "Start",
+                "walls": { "north": true, "south": true, "east": true, "west": true }
+            }]], "start": [0, 0], "end": [0, 0] },
+            "metadata": { "generated_algorithm": null, "created_at": "2024-01-01T00:00:00Z" }
+        }"#;
+        let temp_path = std::env::temp_dir().join("test_maze_v1.json");
+        fs::write(&temp_path, json).unwrap();
+
+        let loaded = load_maze(&temp_path).unwrap();
+        assert_eq!(loaded.topology, Topology::Square);
+
+        fs::remove_file(temp_path).ok();
+    }
+
+    #[test]
+    fn test_export_as_svg() {
+        let maze = MazeGenerator::generate_with_topology(
+            0,
+            5,
+            GeneratorAlgorithm::RecursiveBacktracker,
+            Topology::Theta,
+        );
+        let temp_path = std::env::temp_dir().join("test_maze_theta.svg");
+
+        export_maze_as_svg(&maze, &temp_path).unwrap();
+        let contents = fs::read_to_string(&temp_path).unwrap();
+        assert!(contents.starts_with("<svg"));
+        assert!(contents.contains("<path"));
+
+        fs::remove_file(temp_path).ok();
+    }
 }
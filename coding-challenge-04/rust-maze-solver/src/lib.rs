@@ -1,8 +1,13 @@
 pub mod algorithms;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod io;
 pub mod maze;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod visualization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm};
-pub use maze::{Cell, CellType, Maze};
+pub use maze::{Cell, CellType, Maze, Topology};
+#[cfg(not(target_arch = "wasm32"))]
 pub use visualization::{MazeAnimator, MazeRenderer};
@@ -1,7 +1,11 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use rust_maze_solver::{
-    algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm},
+    algorithms::{
+        k_shortest_paths, GeneratorAlgorithm, Hand, MazeGenerator, MazeSolver,
+        PathfindingAlgorithm, DEFAULT_MAX_EXPANSIONS,
+    },
     io,
+    maze::Topology,
     visualization::{
         renderer::{MazeRenderer, SolutionStats},
         MazeAnimator,
@@ -36,6 +40,10 @@ enum Commands {
         #[arg(short, long, value_enum, default_value = "recursive-backtracker")]
         algorithm: GenAlgo,
 
+        /// Cell shape of the maze
+        #[arg(short, long, value_enum, default_value = "square")]
+        topology: Shape,
+
         /// Output file path (optional)
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -63,6 +71,10 @@ enum Commands {
         #[arg(short = 'g', long, value_enum, default_value = "recursive-backtracker")]
         gen_algorithm: GenAlgo,
 
+        /// Cell shape of the maze (if not loading from file)
+        #[arg(short, long, value_enum, default_value = "square")]
+        topology: Shape,
+
         /// Solving algorithm to use
         #[arg(short, long, value_enum, default_value = "a-star")]
         algorithm: SolveAlgo,
@@ -78,6 +90,18 @@ enum Commands {
         /// Don't display statistics
         #[arg(long)]
         no_stats: bool,
+
+        /// Enumerate the K shortest simple paths instead of a single solution
+        #[arg(short = 'k', long, value_name = "K")]
+        k_paths: Option<usize>,
+
+        /// Node expansions allowed before --k-paths stops with partial results
+        #[arg(long, default_value_t = DEFAULT_MAX_EXPANSIONS)]
+        max_expansions: usize,
+
+        /// Also write the maze and its solution path(s) to an SVG file
+        #[arg(long)]
+        svg: Option<PathBuf>,
     },
 
     /// Generate and solve a maze in one command
@@ -94,6 +118,10 @@ enum Commands {
         #[arg(short = 'g', long, value_enum, default_value = "recursive-backtracker")]
         gen_algorithm: GenAlgo,
 
+        /// Cell shape of the maze
+        #[arg(short, long, value_enum, default_value = "square")]
+        topology: Shape,
+
         /// Solving algorithm to use
         #[arg(short, long, value_enum, default_value = "a-star")]
         solve_algorithm: SolveAlgo,
@@ -117,7 +145,7 @@ enum Commands {
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Output text file
+        /// Output file (.svg for an SVG image, anything else for text)
         #[arg(short, long)]
         output: PathBuf,
     },
@@ -135,6 +163,16 @@ enum GenAlgo {
     AldousBroder,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum Shape {
+    /// Square cells on a rectangular grid
+    Square,
+    /// Hexagonal cells, six neighbors each
+    Hex,
+    /// Circular maze of concentric rings (height sets the ring count)
+    Theta,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum SolveAlgo {
     /// A* - Optimal pathfinding with heuristic
@@ -145,6 +183,12 @@ enum SolveAlgo {
     Dfs,
     /// Dijkstra's Algorithm - Optimal without heuristic
     Dijkstra,
+    /// Left-hand wall follower - Perfect mazes only, stops if it loops
+    WallLeft,
+    /// Right-hand wall follower - Perfect mazes only, stops if it loops
+    WallRight,
+    /// Dead-end filling - Seals dead ends until only the solution is left
+    DeadEndFilling,
 }
 
 impl From<GenAlgo> for GeneratorAlgorithm {
@@ -158,6 +202,16 @@ impl From<GenAlgo> for GeneratorAlgorithm {
     }
 }
 
+impl From<Shape> for Topology {
+    fn from(shape: Shape) -> Self {
+        match shape {
+            Shape::Square => Topology::Square,
+            Shape::Hex => Topology::Hex,
+            Shape::Theta => Topology::Theta,
+        }
+    }
+}
+
 impl From<SolveAlgo> for PathfindingAlgorithm {
     fn from(algo: SolveAlgo) -> Self {
         match algo {
@@ -165,6 +219,9 @@ impl From<SolveAlgo> for PathfindingAlgorithm {
             SolveAlgo::Bfs => PathfindingAlgorithm::BFS,
             SolveAlgo::Dfs => PathfindingAlgorithm::DFS,
             SolveAlgo::Dijkstra => PathfindingAlgorithm::Dijkstra,
+            SolveAlgo::WallLeft => PathfindingAlgorithm::WallFollower(Hand::Left),
+            SolveAlgo::WallRight => PathfindingAlgorithm::WallFollower(Hand::Right),
+            SolveAlgo::DeadEndFilling => PathfindingAlgorithm::DeadEndFilling,
         }
     }
 }
@@ -177,11 +234,12 @@ fn main() {
             width,
             height,
             algorithm,
+            topology,
             output,
             no_display,
         } => {
-            println!("Generating {}x{} maze using {:?}...", width, height, algorithm);
-            let maze = MazeGenerator::generate(width, height, algorithm.into());
+            println!("Generating {}x{} {:?} maze using {:?}...", width, height, topology, algorithm);
+            let maze = MazeGenerator::generate_with_topology(width, height, algorithm.into(), topology.into());
 
             if !no_display {
                 let renderer = MazeRenderer::new();
@@ -203,10 +261,14 @@ fn main() {
             width,
             height,
             gen_algorithm,
+            topology,
             algorithm,
             animate,
             delay,
             no_stats,
+            k_paths,
+            max_expansions,
+            svg,
         } => {
             let maze = if let Some(path) = input {
                 println!("Loading maze from {}...", path.display());
@@ -218,16 +280,39 @@ fn main() {
                     }
                 }
             } else {
-                println!("Generating {}x{} maze...", width, height);
-                MazeGenerator::generate(width, height, gen_algorithm.into())
+                println!("Generating {}x{} {:?} maze...", width, height, topology);
+                MazeGenerator::generate_with_topology(width, height, gen_algorithm.into(), topology.into())
             };
 
+            if let Some(k) = k_paths {
+                println!("Finding the {} shortest paths...", k);
+                let result = k_shortest_paths(&maze, k, max_expansions);
+                if result.paths.is_empty() && !result.truncated {
+                    eprintln!("No solution found!");
+                    return;
+                }
+
+                let paths: Vec<_> = result.paths.iter().map(|p| p.path.clone()).collect();
+                let renderer = MazeRenderer::new();
+                println!("\n{}", renderer.render_with_paths(&maze, &paths));
+                if !no_stats {
+                    println!("{}", renderer.render_k_paths_statistics(&result));
+                }
+                if let Some(path) = svg {
+                    match io::export_solution_as_svg(&maze, &paths, &path) {
+                        Ok(_) => println!("SVG saved to {}", path.display()),
+                        Err(e) => eprintln!("Error saving SVG: {}", e),
+                    }
+                }
+                return;
+            }
+
             println!("Solving maze using {:?}...", algorithm);
             let start_time = Instant::now();
-            let result = match MazeSolver::solve(&maze, algorithm.into()) {
-                Some(r) => r,
-                None => {
-                    eprintln!("No solution found!");
+            let result = match MazeSolver::try_solve(&maze, algorithm.into()) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!("{}", e);
                     return;
                 }
             };
@@ -235,7 +320,7 @@ fn main() {
 
             if animate {
                 let animator = MazeAnimator::new(delay);
-                if let Err(e) = animator.animate_solution(&maze, &result.path, &result.visited) {
+                if let Err(e) = animator.animate_solution(&maze, &result.path, &result.trace) {
                     eprintln!("Animation error: {}", e);
                 }
             } else {
@@ -254,19 +339,27 @@ fn main() {
                 };
                 println!("{}", renderer.render_statistics(&stats));
             }
+
+            if let Some(path) = svg {
+                match io::export_solution_as_svg(&maze, &[result.path], &path) {
+                    Ok(_) => println!("SVG saved to {}", path.display()),
+                    Err(e) => eprintln!("Error saving SVG: {}", e),
+                }
+            }
         }
 
         Commands::Auto {
             width,
             height,
             gen_algorithm,
+            topology,
             solve_algorithm,
             animate,
             delay,
             output,
         } => {
-            println!("Generating {}x{} maze using {:?}...", width, height, gen_algorithm);
-            let maze = MazeGenerator::generate(width, height, gen_algorithm.into());
+            println!("Generating {}x{} {:?} maze using {:?}...", width, height, topology, gen_algorithm);
+            let maze = MazeGenerator::generate_with_topology(width, height, gen_algorithm.into(), topology.into());
 
             if let Some(path) = &output {
                 if let Err(e) = io::save_maze(&maze, path, Some(&format!("{:?}", gen_algorithm))) {
@@ -276,10 +369,10 @@ fn main() {
 
             println!("Solving maze using {:?}...", solve_algorithm);
             let start_time = Instant::now();
-            let result = match MazeSolver::solve(&maze, solve_algorithm.into()) {
-                Some(r) => r,
-                None => {
-                    eprintln!("No solution found!");
+            let result = match MazeSolver::try_solve(&maze, solve_algorithm.into()) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!("{}", e);
                     return;
                 }
             };
@@ -287,7 +380,7 @@ fn main() {
 
             if animate {
                 let animator = MazeAnimator::new(delay);
-                if let Err(e) = animator.animate_solution(&maze, &result.path, &result.visited) {
+                if let Err(e) = animator.animate_solution(&maze, &result.path, &result.trace) {
                     eprintln!("Animation error: {}", e);
                 }
             } else {
@@ -322,7 +415,15 @@ fn main() {
             };
 
             println!("Exporting to {}...", output.display());
-            match io::export_maze_as_text(&maze, &output) {
+            let is_svg = output
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+            let result = if is_svg {
+                io::export_maze_as_svg(&maze, &output)
+            } else {
+                io::export_maze_as_text(&maze, &output)
+            };
+            match result {
                 Ok(_) => println!("Export complete!"),
                 Err(e) => eprintln!("Error exporting maze: {}", e),
             }
@@ -1,27 +1,56 @@
-use super::cell::{Cell, CellType};
+use super::cell::{Cell, CellType, Direction};
+use super::topology::{self, Topology, HEX_DIRECTIONS};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
+/// A maze over any [`Topology`]. Cells are addressed as `(row, col)`:
+/// grid rows for square mazes, odd-row offset rows for hex mazes and
+/// rings (row 0 is the center) for theta mazes, whose rows differ in length.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Maze {
     pub width: usize,
     pub height: usize,
+    #[serde(default)]
+    pub topology: Topology,
     pub grid: Vec<Vec<Cell>>,
     pub start: (usize, usize),
     pub end: (usize, usize),
+    /// Carved passages for non-square topologies, each stored once as
+    /// `(smaller, larger)`. Square mazes record passages in cell walls.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub passages: BTreeSet<((usize, usize), (usize, usize))>,
 }
 
 impl Maze {
     pub fn new(width: usize, height: usize) -> Self {
-        let grid = vec![vec![Cell::new(); width]; height];
+        Self::with_topology(width, height, Topology::Square)
+    }
+
+    /// Create a maze with every wall up. For theta mazes `height` is the
+    /// number of rings and `width` is derived from the outermost ring.
+    pub fn with_topology(width: usize, height: usize, topology: Topology) -> Self {
+        let (width, grid) = match topology {
+            Topology::Square | Topology::Hex => (width, vec![vec![Cell::new(); width]; height]),
+            Topology::Theta => {
+                let sizes = topology::theta_ring_sizes(height);
+                let width = sizes.last().copied().unwrap_or(0);
+                (width, sizes.into_iter().map(|n| vec![Cell::new(); n]).collect())
+            }
+        };
         let start = (0, 0);
-        let end = (height.saturating_sub(1), width.saturating_sub(1));
+        let end = match topology {
+            Topology::Square | Topology::Hex => (height.saturating_sub(1), width.saturating_sub(1)),
+            Topology::Theta => (height.saturating_sub(1), 0),
+        };
 
         Self {
             width,
             height,
+            topology,
             grid,
             start,
             end,
+            passages: BTreeSet::new(),
         }
     }
 
@@ -40,7 +69,25 @@ impl Maze {
     }
 
     pub fn is_valid_position(&self, row: i32, col: i32) -> bool {
-        row >= 0 && col >= 0 && (row as usize) < self.height && (col as usize) < self.width
+        row >= 0
+            && col >= 0
+            && self
+                .grid
+                .get(row as usize)
+                .is_some_and(|r| (col as usize) < r.len())
+    }
+
+    /// Every cell position, row by row
+    pub fn positions(&self) -> Vec<(usize, usize)> {
+        self.grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| (0..cells.len()).map(move |col| (row, col)))
+            .collect()
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.grid.iter().map(|row| row.len()).sum()
     }
 
     pub fn mark_start(&mut self) {
@@ -65,7 +112,66 @@ impl Maze {
         self.mark_end();
     }
 
+    /// Cells adjacent to `(row, col)`, whether or not a wall separates them
     pub fn neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        match self.topology {
+            Topology::Square => self.square_neighbors(row, col),
+            Topology::Hex => self.hex_neighbors(row, col),
+            Topology::Theta => self.theta_neighbors(row, col),
+        }
+    }
+
+    /// Adjacent cells reachable without crossing a wall
+    pub fn open_neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        self.neighbors(row, col)
+            .into_iter()
+            .filter(|&next| self.is_linked((row, col), next))
+            .collect()
+    }
+
+    /// Knock down the wall between two adjacent cells
+    pub fn link(&mut self, a: (usize, usize), b: (usize, usize)) {
+        match self.topology {
+            Topology::Square => {
+                if let Some(direction) = Self::square_direction(a, b) {
+                    if let Some(cell) = self.get_mut(a.0, a.1) {
+                        cell.walls.remove_wall(direction);
+                    }
+                    if let Some(cell) = self.get_mut(b.0, b.1) {
+                        cell.walls.remove_wall(direction.opposite());
+                    }
+                }
+            }
+            Topology::Hex | Topology::Theta => {
+                self.passages.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+
+    /// Whether a passage connects two adjacent cells
+    pub fn is_linked(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match self.topology {
+            Topology::Square => {
+                let (Some(direction), Some(cell)) = (Self::square_direction(a, b), self.get(a.0, a.1)) else {
+                    return false;
+                };
+                match direction {
+                    Direction::North => !cell.walls.north,
+                    Direction::South => !cell.walls.south,
+                    Direction::East => !cell.walls.east,
+                    Direction::West => !cell.walls.west,
+                }
+            }
+            Topology::Hex | Topology::Theta => self.passages.contains(&(a.min(b), a.max(b))),
+        }
+    }
+
+    fn square_direction(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+        let delta = (to.0 as i32 - from.0 as i32, to.1 as i32 - from.1 as i32);
+        Direction::all().into_iter().find(|d| d.delta() == delta)
+    }
+
+    fn square_neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
         let mut neighbors = Vec::new();
         let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
 
@@ -81,6 +187,101 @@ impl Maze {
         neighbors
     }
 
+    /// Hex neighbors in [`HEX_DIRECTIONS`] order, skipping those off the grid
+    pub fn hex_neighbor(&self, row: usize, col: usize, direction: usize) -> Option<(usize, usize)> {
+        let (q, r) = topology::offset_to_axial(row, col);
+        let (dq, dr) = HEX_DIRECTIONS[direction];
+        let (new_row, new_col) = topology::axial_to_offset(q + dq, r + dr);
+        self.is_valid_position(new_row, new_col)
+            .then_some((new_row as usize, new_col as usize))
+    }
+
+    fn hex_neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        (0..HEX_DIRECTIONS.len())
+            .filter_map(|direction| self.hex_neighbor(row, col, direction))
+            .collect()
+    }
+
+    /// The cell one ring further in that shares this cell's inner wall
+    pub fn theta_inward(&self, ring: usize, col: usize) -> Option<(usize, usize)> {
+        if ring == 0 {
+            return None;
+        }
+        let inner = self.grid[ring - 1].len();
+        Some((ring - 1, col * inner / self.grid[ring].len()))
+    }
+
+    /// The next cell clockwise around the same ring
+    pub fn theta_clockwise(&self, ring: usize, col: usize) -> Option<(usize, usize)> {
+        let size = self.grid[ring].len();
+        (size > 1).then_some((ring, (col + 1) % size))
+    }
+
+    fn theta_neighbors(&self, ring: usize, col: usize) -> Vec<(usize, usize)> {
+        let size = self.grid[ring].len();
+        let mut neighbors = Vec::new();
+
+        if let Some(clockwise) = self.theta_clockwise(ring, col) {
+            neighbors.push(clockwise);
+            let counter_clockwise = (ring, (col + size - 1) % size);
+            if counter_clockwise != clockwise {
+                neighbors.push(counter_clockwise);
+            }
+        }
+
+        neighbors.extend(self.theta_inward(ring, col));
+
+        if let Some(outer) = self.grid.get(ring + 1) {
+            let ratio = outer.len() / size;
+            neighbors.extend((0..ratio).map(|k| (ring + 1, col * ratio + k)));
+        }
+
+        neighbors
+    }
+
+    /// What lies past each side of a cell, walking clockwise around it;
+    /// `None` where the side is part of the maze's outer boundary.
+    ///
+    /// Square cells list north, east, south, west and hex cells follow
+    /// [`HEX_DIRECTIONS`]. Theta cells list their outer neighbors, then the
+    /// clockwise, inward and counter-clockwise ones; the center has only
+    /// outer neighbors.
+    pub fn sides(&self, row: usize, col: usize) -> Vec<Option<(usize, usize)>> {
+        match self.topology {
+            Topology::Square => [(-1, 0), (0, 1), (1, 0), (0, -1)]
+                .into_iter()
+                .map(|(dr, dc)| {
+                    let (new_row, new_col) = (row as i32 + dr, col as i32 + dc);
+                    self.is_valid_position(new_row, new_col)
+                        .then_some((new_row as usize, new_col as usize))
+                })
+                .collect(),
+            Topology::Hex => (0..HEX_DIRECTIONS.len())
+                .map(|direction| self.hex_neighbor(row, col, direction))
+                .collect(),
+            Topology::Theta => {
+                let size = self.grid[row].len();
+                let mut sides: Vec<_> = match self.grid.get(row + 1) {
+                    Some(outer) => {
+                        let ratio = outer.len() / size;
+                        (0..ratio).map(|k| Some((row + 1, col * ratio + k))).collect()
+                    }
+                    None => vec![None],
+                };
+                if row > 0 {
+                    let clockwise = self.theta_clockwise(row, col);
+                    let counter_clockwise = clockwise.map(|_| (row, (col + size - 1) % size));
+                    sides.push(clockwise);
+                    sides.push(self.theta_inward(row, col));
+                    if counter_clockwise != clockwise {
+                        sides.push(counter_clockwise);
+                    }
+                }
+                sides
+            }
+        }
+    }
+
     pub fn count_cells(&self, cell_type: CellType) -> usize {
         self.grid
             .iter()
@@ -138,6 +339,103 @@ mod tests {
         assert_eq!(neighbors.len(), 3);
     }
 
+    fn assert_neighbors_symmetric(maze: &Maze) {
+        for (row, col) in maze.positions() {
+            let neighbors = maze.neighbors(row, col);
+            assert!(!neighbors.contains(&(row, col)));
+            for (nr, nc) in neighbors {
+                assert!(
+                    maze.neighbors(nr, nc).contains(&(row, col)),
+                    "{:?}: ({}, {}) lists ({}, {}) but not the reverse",
+                    maze.topology,
+                    row,
+                    col,
+                    nr,
+                    nc
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_symmetry_all_topologies() {
+        assert_neighbors_symmetric(&Maze::with_topology(6, 5, Topology::Square));
+        assert_neighbors_symmetric(&Maze::with_topology(6, 5, Topology::Hex));
+        assert_neighbors_symmetric(&Maze::with_topology(0, 8, Topology::Theta));
+    }
+
+    #[test]
+    fn test_hex_neighbors() {
+        let maze = Maze::with_topology(5, 5, Topology::Hex);
+
+        // Interior cells have all six neighbors
+        assert_eq!(maze.neighbors(2, 2).len(), 6);
+        assert_eq!(maze.neighbors(1, 2).len(), 6);
+
+        // Even rows lean left, odd rows lean right
+        let even = maze.neighbors(2, 2);
+        assert!(even.contains(&(1, 1)) && even.contains(&(1, 2)));
+        let odd = maze.neighbors(1, 2);
+        assert!(odd.contains(&(0, 2)) && odd.contains(&(0, 3)));
+
+        assert_eq!(maze.neighbors(0, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_theta_shape_and_neighbors() {
+        let maze = Maze::with_topology(0, 4, Topology::Theta);
+        assert_eq!(maze.grid.iter().map(|r| r.len()).collect::<Vec<_>>(), vec![1, 6, 12, 24]);
+        assert_eq!(maze.width, 24);
+        assert_eq!(maze.cell_count(), 43);
+
+        // The center touches the whole first ring
+        assert_eq!(maze.neighbors(0, 0).len(), 6);
+
+        // Ring 1 cell: two around, one in, two out
+        let neighbors = maze.neighbors(1, 0);
+        assert_eq!(neighbors.len(), 5);
+        assert!(neighbors.contains(&(1, 5)));
+        assert!(neighbors.contains(&(2, 0)) && neighbors.contains(&(2, 1)));
+
+        assert!(maze.is_valid_position(1, 5));
+        assert!(!maze.is_valid_position(1, 6));
+    }
+
+    #[test]
+    fn test_sides_cover_every_neighbor() {
+        for maze in [
+            Maze::with_topology(6, 5, Topology::Square),
+            Maze::with_topology(6, 5, Topology::Hex),
+            Maze::with_topology(0, 5, Topology::Theta),
+        ] {
+            for (row, col) in maze.positions() {
+                let mut sides: Vec<_> = maze.sides(row, col).into_iter().flatten().collect();
+                let mut neighbors = maze.neighbors(row, col);
+                sides.sort();
+                neighbors.sort();
+                assert_eq!(sides, neighbors, "{:?} ({}, {})", maze.topology, row, col);
+            }
+        }
+
+        let square = Maze::new(3, 3);
+        assert_eq!(square.sides(0, 0), vec![None, Some((0, 1)), Some((1, 0)), None]);
+        let theta = Maze::with_topology(0, 3, Topology::Theta);
+        assert_eq!(theta.sides(2, 0), vec![None, Some((2, 1)), Some((1, 0)), Some((2, 11))]);
+    }
+
+    #[test]
+    fn test_link_and_open_neighbors() {
+        for topology in [Topology::Square, Topology::Hex] {
+            let mut maze = Maze::with_topology(4, 4, topology);
+            assert!(maze.open_neighbors(1, 1).is_empty());
+
+            maze.link((1, 1), (1, 2));
+            assert!(maze.is_linked((1, 2), (1, 1)));
+            assert_eq!(maze.open_neighbors(1, 1), vec![(1, 2)]);
+            assert_eq!(maze.open_neighbors(1, 2), vec![(1, 1)]);
+        }
+    }
+
     #[test]
     fn test_set_cell_type() {
         let mut maze = Maze::new(5, 5);
@@ -1,5 +1,7 @@
 pub mod cell;
 pub mod grid;
+pub mod topology;
 
 pub use cell::{Cell, CellType};
 pub use grid::Maze;
+pub use topology::Topology;
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// The shape of the cells that make up a maze and how they connect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Topology {
+    /// Rectangular grid, 4 neighbors per cell
+    #[default]
+    Square,
+    /// Pointy-top hexagons in odd-row offset layout, 6 neighbors per cell
+    Hex,
+    /// Concentric rings around a single center cell; outer rings are
+    /// subdivided so cells stay roughly square
+    Theta,
+}
+
+/// Axial `(dq, dr)` steps to the six hex neighbors, in the order of the
+/// hexagon's edges starting from east and turning clockwise on screen:
+/// east, south-east, south-west, west, north-west, north-east.
+pub const HEX_DIRECTIONS: [(i32, i32); 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+/// Convert an odd-row offset `(row, col)` position to axial `(q, r)`.
+pub fn offset_to_axial(row: usize, col: usize) -> (i32, i32) {
+    let r = row as i32;
+    let q = col as i32 - (r - (r & 1)) / 2;
+    (q, r)
+}
+
+/// Convert axial `(q, r)` back to odd-row offset `(row, col)`.
+/// Either component may be negative if the axial position is off the grid.
+pub fn axial_to_offset(q: i32, r: i32) -> (i32, i32) {
+    (r, q + (r - (r & 1)) / 2)
+}
+
+/// Number of steps between two hex cells.
+pub fn hex_distance(from: (usize, usize), to: (usize, usize)) -> usize {
+    let (q1, r1) = offset_to_axial(from.0, from.1);
+    let (q2, r2) = offset_to_axial(to.0, to.1);
+    let dq = q1 - q2;
+    let dr = r1 - r2;
+    ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as usize
+}
+
+/// Cells in each ring of a theta maze with `rings` rings.
+///
+/// Ring 0 is the single center cell. Each further ring keeps the cell count
+/// of the ring inside it, or multiplies it when the cells would otherwise get
+/// much wider than they are deep.
+pub fn theta_ring_sizes(rings: usize) -> Vec<usize> {
+    let mut sizes = Vec::with_capacity(rings);
+    if rings == 0 {
+        return sizes;
+    }
+    sizes.push(1);
+
+    let ring_height = 1.0 / rings as f64;
+    for ring in 1..rings {
+        let radius = ring as f64 / rings as f64;
+        let circumference = 2.0 * PI * radius;
+        let previous = sizes[ring - 1];
+        let cell_width = circumference / previous as f64;
+        let ratio = (cell_width / ring_height).round().max(1.0) as usize;
+        sizes.push(previous * ratio);
+    }
+
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axial_roundtrip() {
+        for row in 0..6 {
+            for col in 0..6 {
+                let (q, r) = offset_to_axial(row, col);
+                assert_eq!(axial_to_offset(q, r), (row as i32, col as i32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_distance() {
+        assert_eq!(hex_distance((2, 2), (2, 2)), 0);
+        assert_eq!(hex_distance((0, 0), (0, 3)), 3);
+        // Straight down-right diagonal: (0,0) -> (1,0) -> (2,1) -> (3,1)
+        assert_eq!(hex_distance((0, 0), (3, 1)), 3);
+    }
+
+    #[test]
+    fn test_theta_ring_sizes() {
+        assert_eq!(theta_ring_sizes(0), Vec::<usize>::new());
+        assert_eq!(theta_ring_sizes(1), vec![1]);
+        assert_eq!(theta_ring_sizes(6), vec![1, 6, 12, 24, 24, 24]);
+
+        // Every ring evenly subdivides the one inside it
+        let sizes = theta_ring_sizes(20);
+        for ring in 1..sizes.len() {
+            assert_eq!(sizes[ring] % sizes[ring - 1], 0);
+        }
+    }
+}
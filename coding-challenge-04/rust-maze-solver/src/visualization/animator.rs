@@ -23,11 +23,13 @@ impl MazeAnimator {
         }
     }
 
+    /// Play back a solver's trace one cell per frame, then draw its path.
+    /// Cells the trace passes more than once get a frame each time.
     pub fn animate_solution(
         &self,
         maze: &Maze,
         path: &[(usize, usize)],
-        visited: &HashSet<(usize, usize)>,
+        trace: &[(usize, usize)],
     ) -> std::io::Result<()> {
         // Clear screen and hide cursor
         let mut stdout = stdout();
@@ -36,7 +38,7 @@ impl MazeAnimator {
 
         // Animate visited cells
         let mut current_visited = HashSet::new();
-        for &cell in visited.iter() {
+        for &cell in trace {
             current_visited.insert(cell);
 
             // Clear and redraw
@@ -60,7 +62,7 @@ impl MazeAnimator {
             stdout.execute(cursor::MoveTo(0, 0))?;
             let frame = self.renderer.render_with_highlights(
                 maze,
-                visited,
+                &current_visited,
                 &current_solution,
             );
             print!("{}", frame);
@@ -1,6 +1,21 @@
-use crate::maze::{CellType, Maze};
-use colored::Colorize;
-use std::collections::HashSet;
+use crate::algorithms::KPathsResult;
+use crate::maze::topology::HEX_DIRECTIONS;
+use crate::maze::{CellType, Maze, Topology};
+use colored::{Color, Colorize};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+
+/// Side of a square cell, depth of a theta ring and width of a hex cell in SVG output
+const SVG_CELL_SIZE: f64 = 20.0;
+const SVG_MARGIN: f64 = 10.0;
+
+/// Terminal colors for the paths of a multi-path solution, shortest first
+const PATH_COLORS: [Color; 4] = [Color::BrightYellow, Color::BrightMagenta, Color::BrightCyan, Color::White];
+/// SVG stroke colors matching [`PATH_COLORS`]
+const SVG_PATH_COLORS: [&str; 4] = ["gold", "magenta", "deepskyblue", "gray"];
+
+/// Which path each cell belongs to; cells on several paths take the earliest
+type PathIndex = HashMap<(usize, usize), usize>;
 
 pub struct MazeRenderer {
     pub use_unicode: bool,
@@ -24,6 +39,34 @@ impl MazeRenderer {
         maze: &Maze,
         visited: &HashSet<(usize, usize)>,
         solution: &HashSet<(usize, usize)>,
+    ) -> String {
+        let paths = solution.iter().map(|&pos| (pos, 0)).collect();
+        self.render_layers(maze, visited, &paths)
+    }
+
+    /// Render several solution paths at once, each in its own color
+    pub fn render_with_paths(&self, maze: &Maze, paths: &[Vec<(usize, usize)>]) -> String {
+        let mut index = PathIndex::new();
+        for (i, path) in paths.iter().enumerate() {
+            for &pos in path {
+                index.entry(pos).or_insert(i);
+            }
+        }
+        self.render_layers(maze, &HashSet::new(), &index)
+    }
+
+    fn render_layers(&self, maze: &Maze, visited: &HashSet<(usize, usize)>, paths: &PathIndex) -> String {
+        match maze.topology {
+            Topology::Square => self.render_square(maze, visited, paths),
+            Topology::Hex | Topology::Theta => self.render_passage_map(maze, visited, paths),
+        }
+    }
+
+    fn render_square(
+        &self,
+        maze: &Maze,
+        visited: &HashSet<(usize, usize)>,
+        paths: &PathIndex,
     ) -> String {
         let mut output = String::new();
 
@@ -33,7 +76,7 @@ impl MazeRenderer {
 
         // Render maze rows
         for row in 0..maze.height {
-            output.push_str(&self.render_row(maze, row, visited, solution));
+            output.push_str(&self.render_row(maze, row, visited, paths));
             output.push('\n');
         }
 
@@ -49,7 +92,7 @@ impl MazeRenderer {
         maze: &Maze,
         row: usize,
         visited: &HashSet<(usize, usize)>,
-        solution: &HashSet<(usize, usize)>,
+        paths: &PathIndex,
     ) -> String {
         let mut output = String::new();
 
@@ -67,7 +110,7 @@ impl MazeRenderer {
                 cell.cell_type,
                 (row, col),
                 visited,
-                solution,
+                paths,
             );
             output.push_str(&symbol);
 
@@ -92,14 +135,11 @@ impl MazeRenderer {
         cell_type: CellType,
         position: (usize, usize),
         visited: &HashSet<(usize, usize)>,
-        solution: &HashSet<(usize, usize)>,
+        paths: &PathIndex,
     ) -> String {
-        if solution.contains(&position) {
-            return if self.use_unicode {
-                "●".bright_yellow().to_string()
-            } else {
-                "o".bright_yellow().to_string()
-            };
+        if let Some(&index) = paths.get(&position) {
+            let color = PATH_COLORS[index % PATH_COLORS.len()];
+            return if self.use_unicode { "●" } else { "o" }.color(color).to_string();
         }
 
         if visited.contains(&position) {
@@ -138,6 +178,295 @@ impl MazeRenderer {
         }
     }
 
+    /// Hex and theta cells don't line up with terminal rows, so they are drawn
+    /// as a map of cell markers joined by their open passages
+    fn render_passage_map(
+        &self,
+        maze: &Maze,
+        visited: &HashSet<(usize, usize)>,
+        paths: &PathIndex,
+    ) -> String {
+        let positions: Vec<((usize, usize), (usize, usize))> = maze
+            .positions()
+            .into_iter()
+            .map(|pos| (pos, Self::ascii_position(maze, pos)))
+            .collect();
+        let columns = positions.iter().map(|(_, (x, _))| x + 2).max().unwrap_or(0);
+        let rows = positions.iter().map(|(_, (_, y))| y + 1).max().unwrap_or(0);
+        let mut canvas = vec![vec![" ".to_string(); columns]; rows];
+
+        for &(pos, (x, y)) in &positions {
+            let cell = maze.get(pos.0, pos.1).unwrap();
+            canvas[y][x] = if cell.cell_type == CellType::Path && !visited.contains(&pos) && !paths.contains_key(&pos) {
+                if self.use_unicode { "○" } else { "+" }.to_string()
+            } else {
+                self.get_cell_symbol(cell.cell_type, pos, visited, paths)
+            };
+        }
+
+        for &(pos, (x1, y1)) in &positions {
+            for next in maze.open_neighbors(pos.0, pos.1) {
+                if next < pos {
+                    continue;
+                }
+                let (x2, y2) = Self::ascii_position(maze, next);
+                let (dx, dy) = (x2 as i32 - x1 as i32, y2 as i32 - y1 as i32);
+                let symbol = match (dx.signum(), dy.signum(), self.use_unicode) {
+                    (_, 0, true) => "─",
+                    (_, 0, false) => "-",
+                    (0, _, true) => "│",
+                    (0, _, false) => "|",
+                    (a, b, true) if a == b => "╲",
+                    (a, b, false) if a == b => "\\",
+                    (_, _, true) => "╱",
+                    (_, _, false) => "/",
+                };
+
+                // Fill the gap between the two markers, leaving other cells intact
+                let steps = dx.abs().max(dy.abs());
+                for step in 1..steps {
+                    let x = (x1 as i32 + (dx * step) / steps) as usize;
+                    let y = (y1 as i32 + (dy * step) / steps) as usize;
+                    if canvas[y][x] == " " {
+                        canvas[y][x] = symbol.to_string();
+                    }
+                }
+            }
+        }
+
+        let mut output = String::new();
+        for row in canvas {
+            output.push_str(row.concat().trim_end());
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Character column and row of a cell in the passage map
+    fn ascii_position(maze: &Maze, (row, col): (usize, usize)) -> (usize, usize) {
+        match maze.topology {
+            Topology::Square => (col * 2 + 1, row),
+            // Odd rows sit half a cell to the right
+            Topology::Hex => (col * 4 + (row % 2) * 2 + 1, row * 2),
+            Topology::Theta => {
+                let (x, y) = Self::polar_point(maze, (row, col), 4.0, 2.0);
+                let rings = maze.height as f64;
+                ((x + rings * 4.0).round() as usize + 1, (y + rings * 2.0).round() as usize)
+            }
+        }
+    }
+
+    /// Position of a theta cell's center relative to the maze center, with
+    /// one ring spanning `x_scale` horizontally and `y_scale` vertically.
+    /// Angles run clockwise on screen starting from the right.
+    fn polar_point(maze: &Maze, (ring, col): (usize, usize), x_scale: f64, y_scale: f64) -> (f64, f64) {
+        if ring == 0 {
+            return (0.0, 0.0);
+        }
+        let radius = ring as f64 + 0.5;
+        let angle = 2.0 * PI * (col as f64 + 0.5) / maze.grid[ring].len() as f64;
+        (radius * angle.cos() * x_scale, radius * angle.sin() * y_scale)
+    }
+
+    /// Render the maze's walls as an SVG image, with `solution` drawn as a
+    /// line through the centers of its cells
+    pub fn render_svg(&self, maze: &Maze, solution: &[(usize, usize)]) -> String {
+        self.render_svg_paths(maze, &[solution.to_vec()])
+    }
+
+    /// Render the maze as SVG with several solution paths, each in its own
+    /// color; earlier (shorter) paths are drawn on top
+    pub fn render_svg_paths(&self, maze: &Maze, paths: &[Vec<(usize, usize)>]) -> String {
+        let (width, height, walls) = match maze.topology {
+            Topology::Square => Self::square_svg_walls(maze),
+            Topology::Hex => Self::hex_svg_walls(maze),
+            Topology::Theta => Self::theta_svg_walls(maze),
+        };
+
+        let mut output = String::new();
+        output.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\">\n",
+            width, height, width, height
+        ));
+        output.push_str(&format!(
+            "  <rect width=\"{:.0}\" height=\"{:.0}\" fill=\"white\"/>\n",
+            width, height
+        ));
+
+        for (index, solution) in paths.iter().enumerate().rev() {
+            if solution.len() < 2 {
+                continue;
+            }
+            let points: Vec<String> = solution
+                .iter()
+                .map(|&pos| {
+                    let (x, y) = Self::svg_center(maze, pos);
+                    format!("{:.1},{:.1}", x, y)
+                })
+                .collect();
+            output.push_str(&format!(
+                "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"4\" stroke-linecap=\"round\"/>\n",
+                points.join(" "),
+                SVG_PATH_COLORS[index % SVG_PATH_COLORS.len()]
+            ));
+        }
+
+        for (pos, color) in [(maze.start, "green"), (maze.end, "red")] {
+            let (x, y) = Self::svg_center(maze, pos);
+            output.push_str(&format!(
+                "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\"/>\n",
+                x,
+                y,
+                SVG_CELL_SIZE / 4.0,
+                color
+            ));
+        }
+
+        output.push_str("  <g fill=\"none\" stroke=\"black\" stroke-width=\"2\" stroke-linecap=\"round\">\n");
+        for wall in walls {
+            output.push_str(&format!("    {}\n", wall));
+        }
+        output.push_str("  </g>\n</svg>\n");
+
+        output
+    }
+
+    fn svg_line((x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> String {
+        format!("<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>", x1, y1, x2, y2)
+    }
+
+    fn svg_center(maze: &Maze, (row, col): (usize, usize)) -> (f64, f64) {
+        match maze.topology {
+            Topology::Square => (
+                SVG_MARGIN + (col as f64 + 0.5) * SVG_CELL_SIZE,
+                SVG_MARGIN + (row as f64 + 0.5) * SVG_CELL_SIZE,
+            ),
+            Topology::Hex => {
+                let size = Self::hex_svg_size();
+                let offset = (row % 2) as f64 * 0.5;
+                (
+                    SVG_MARGIN + (col as f64 + offset + 0.5) * SVG_CELL_SIZE,
+                    SVG_MARGIN + size * (1.5 * row as f64 + 1.0),
+                )
+            }
+            Topology::Theta => {
+                let center = SVG_MARGIN + maze.height as f64 * SVG_CELL_SIZE;
+                let (x, y) = Self::polar_point(maze, (row, col), SVG_CELL_SIZE, SVG_CELL_SIZE);
+                (center + x, center + y)
+            }
+        }
+    }
+
+    fn square_svg_walls(maze: &Maze) -> (f64, f64, Vec<String>) {
+        let mut walls = Vec::new();
+        for (row, col) in maze.positions() {
+            let cell = maze.get(row, col).unwrap();
+            let x1 = SVG_MARGIN + col as f64 * SVG_CELL_SIZE;
+            let y1 = SVG_MARGIN + row as f64 * SVG_CELL_SIZE;
+            let (x2, y2) = (x1 + SVG_CELL_SIZE, y1 + SVG_CELL_SIZE);
+
+            // Each cell draws its north and west walls; the last row and
+            // column close off the south and east edges
+            if cell.walls.north {
+                walls.push(Self::svg_line((x1, y1), (x2, y1)));
+            }
+            if cell.walls.west {
+                walls.push(Self::svg_line((x1, y1), (x1, y2)));
+            }
+            if row + 1 == maze.height && cell.walls.south {
+                walls.push(Self::svg_line((x1, y2), (x2, y2)));
+            }
+            if col + 1 == maze.width && cell.walls.east {
+                walls.push(Self::svg_line((x2, y1), (x2, y2)));
+            }
+        }
+
+        let width = SVG_MARGIN * 2.0 + maze.width as f64 * SVG_CELL_SIZE;
+        let height = SVG_MARGIN * 2.0 + maze.height as f64 * SVG_CELL_SIZE;
+        (width, height, walls)
+    }
+
+    /// Distance from a hex cell's center to its corners
+    fn hex_svg_size() -> f64 {
+        SVG_CELL_SIZE / 3f64.sqrt()
+    }
+
+    fn hex_svg_walls(maze: &Maze) -> (f64, f64, Vec<String>) {
+        let size = Self::hex_svg_size();
+        let mut walls = Vec::new();
+
+        for pos in maze.positions() {
+            let (cx, cy) = Self::svg_center(maze, pos);
+            let corner = |i: usize| {
+                let angle = (60.0 * i as f64 - 30.0).to_radians();
+                (cx + size * angle.cos(), cy + size * angle.sin())
+            };
+
+            // Edge i runs between corners i and i + 1 and faces HEX_DIRECTIONS[i]
+            for direction in 0..HEX_DIRECTIONS.len() {
+                let open = match maze.hex_neighbor(pos.0, pos.1, direction) {
+                    Some(next) => next < pos || maze.is_linked(pos, next),
+                    None => false,
+                };
+                if !open {
+                    walls.push(Self::svg_line(corner(direction), corner(direction + 1)));
+                }
+            }
+        }
+
+        let width = SVG_MARGIN * 2.0 + (maze.width as f64 + 0.5) * SVG_CELL_SIZE;
+        let height = SVG_MARGIN * 2.0 + size * (1.5 * maze.height as f64 + 0.5);
+        (width, height, walls)
+    }
+
+    fn theta_svg_walls(maze: &Maze) -> (f64, f64, Vec<String>) {
+        let rings = maze.height as f64;
+        let center = SVG_MARGIN + rings * SVG_CELL_SIZE;
+        let point = |radius: f64, angle: f64| (center + radius * angle.cos(), center + radius * angle.sin());
+        let mut walls = Vec::new();
+
+        // Ring 0 is a single open disc; every other cell draws its inner arc
+        // and its clockwise side
+        for (ring, col) in maze.positions().into_iter().filter(|&(ring, _)| ring > 0) {
+            let count = maze.grid[ring].len() as f64;
+            let inner = ring as f64 * SVG_CELL_SIZE;
+            let outer = inner + SVG_CELL_SIZE;
+            let from = 2.0 * PI * col as f64 / count;
+            let to = 2.0 * PI * (col as f64 + 1.0) / count;
+
+            let inward_open = match maze.theta_inward(ring, col) {
+                Some(next) => maze.is_linked((ring, col), next),
+                None => false,
+            };
+            if !inward_open {
+                let (x1, y1) = point(inner, from);
+                let (x2, y2) = point(inner, to);
+                walls.push(format!(
+                    "<path d=\"M {:.1} {:.1} A {:.1} {:.1} 0 0 1 {:.1} {:.1}\"/>",
+                    x1, y1, inner, inner, x2, y2
+                ));
+            }
+
+            let clockwise_open = match maze.theta_clockwise(ring, col) {
+                Some(next) => maze.is_linked((ring, col), next),
+                None => false,
+            };
+            if !clockwise_open {
+                walls.push(Self::svg_line(point(inner, to), point(outer, to)));
+            }
+        }
+
+        walls.push(format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\"/>",
+            center,
+            center,
+            rings * SVG_CELL_SIZE
+        ));
+
+        let size = center * 2.0;
+        (size, size, walls)
+    }
+
     fn render_top_border(&self, width: usize) -> String {
         if self.use_unicode {
             format!("┌{}┐", "─".repeat(if self.show_grid { width * 2 - 1 } else { width }))
@@ -175,6 +504,37 @@ impl MazeRenderer {
 
         output
     }
+
+    /// Summarize a K-shortest-paths run: each path's length and cost in its
+    /// display color, then how much every pair of paths overlaps
+    pub fn render_k_paths_statistics(&self, result: &KPathsResult) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("\n{}\n", "=== K Shortest Paths ===".bright_cyan().bold()));
+        for (i, path) in result.paths.iter().enumerate() {
+            let label = format!("Path {}", i + 1).color(PATH_COLORS[i % PATH_COLORS.len()]);
+            output.push_str(&format!("  {}: length {}, cost {}\n", label, path.length(), path.cost));
+        }
+
+        if result.paths.len() > 1 {
+            output.push_str("  Overlap:\n");
+            for a in 0..result.paths.len() {
+                for b in a + 1..result.paths.len() {
+                    output.push_str(&format!("    {} & {}: {:.1}%\n", a + 1, b + 1, result.overlap(a, b)));
+                }
+            }
+        }
+
+        output.push_str(&format!("  Nodes Expanded: {}\n", result.nodes_expanded.to_string().bright_blue()));
+        if result.truncated {
+            output.push_str(&format!(
+                "  {}\n",
+                "Expansion cap reached; results are partial".bright_red()
+            ));
+        }
+
+        output
+    }
 }
 
 impl Default for MazeRenderer {
@@ -226,4 +586,109 @@ mod tests {
         assert!(output.contains("42"));
         assert!(output.contains("100"));
     }
+
+    #[test]
+    fn test_render_hex_passage_map() {
+        let mut maze = Maze::with_topology(3, 2, Topology::Hex);
+        for pos in maze.positions() {
+            maze.set_cell_type(pos.0, pos.1, CellType::Path);
+        }
+        maze.link((0, 0), (0, 1));
+        maze.link((0, 0), (1, 0));
+        maze.link((0, 1), (1, 0));
+
+        let renderer = MazeRenderer { use_unicode: false, show_grid: true };
+        let output = renderer.render(&maze);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Odd rows are shifted right; passages sit between their cells
+        assert_eq!(lines, vec![" +---+   +", "  \\ /", "   +   +   +"]);
+    }
+
+    #[test]
+    fn test_render_theta_passage_map() {
+        let maze = crate::algorithms::MazeGenerator::generate_with_topology(
+            0,
+            4,
+            crate::algorithms::GeneratorAlgorithm::RecursiveBacktracker,
+            Topology::Theta,
+        );
+        let renderer = MazeRenderer { use_unicode: false, show_grid: true };
+        let output = renderer.render(&maze);
+
+        // Every cell gets its own marker
+        assert!(output.contains('S') && output.contains('E'));
+        assert_eq!(output.matches('+').count() + 2, maze.cell_count());
+    }
+
+    #[test]
+    fn test_render_svg_for_each_topology() {
+        let renderer = MazeRenderer::new();
+        for topology in [Topology::Square, Topology::Hex, Topology::Theta] {
+            let maze = Maze::with_topology(4, 3, topology);
+            let svg = renderer.render_svg(&maze, &[maze.start, maze.end]);
+
+            assert!(svg.starts_with("<svg"));
+            assert!(svg.trim_end().ends_with("</svg>"));
+            assert!(svg.contains("<polyline"));
+            assert!(svg.contains("<line"), "{:?} maze should draw walls", topology);
+        }
+    }
+
+    #[test]
+    fn test_hex_svg_skips_open_walls() {
+        let renderer = MazeRenderer::new();
+        let mut maze = Maze::with_topology(2, 1, Topology::Hex);
+        let closed = renderer.render_svg(&maze, &[]).matches("<line").count();
+        assert_eq!(closed, 11);
+
+        maze.link((0, 0), (0, 1));
+        let open = renderer.render_svg(&maze, &[]).matches("<line").count();
+        assert_eq!(open, 10);
+    }
+
+    #[test]
+    fn test_svg_paths_get_distinct_colors() {
+        let renderer = MazeRenderer::new();
+        let maze = Maze::new(3, 3);
+        let paths = vec![vec![(0, 0), (0, 1)], vec![(0, 0), (1, 0)]];
+        let svg = renderer.render_svg_paths(&maze, &paths);
+
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        // The shortest path is drawn last so it stays on top
+        let gold = svg.find("stroke=\"gold\"").unwrap();
+        let second = svg.find("stroke=\"magenta\"").unwrap();
+        assert!(second < gold);
+    }
+
+    #[test]
+    fn test_render_with_paths_marks_every_path() {
+        let mut maze = Maze::new(3, 1);
+        for pos in maze.positions() {
+            maze.set_cell_type(pos.0, pos.1, CellType::Path);
+        }
+        let renderer = MazeRenderer { use_unicode: false, show_grid: true };
+        let output = renderer.render_with_paths(&maze, &[vec![(0, 0)], vec![(0, 0), (0, 2)]]);
+
+        assert_eq!(output.matches('o').count(), 2);
+    }
+
+    #[test]
+    fn test_k_paths_statistics_rendering() {
+        let renderer = MazeRenderer::new();
+        let result = KPathsResult {
+            paths: vec![
+                crate::algorithms::RankedPath { path: vec![(0, 0), (0, 1), (0, 2)], cost: 2 },
+                crate::algorithms::RankedPath { path: vec![(0, 0), (1, 0), (1, 1), (0, 1), (0, 2)], cost: 4 },
+            ],
+            nodes_expanded: 17,
+            truncated: true,
+        };
+
+        let output = renderer.render_k_paths_statistics(&result);
+        assert!(output.contains("length 3, cost 2"));
+        assert!(output.contains("1 & 2: 50.0%"));
+        assert!(output.contains("17"));
+        assert!(output.contains("partial"));
+    }
 }
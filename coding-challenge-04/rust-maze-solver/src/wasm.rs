@@ -0,0 +1,182 @@
+//! Browser bindings, built with the `wasm` feature
+//!
+//! Mazes are always generated from a seed and nothing here touches the
+//! terminal, the file system or threads, so the same calls work in a web
+//! page. Algorithms are named as on the command line (`prims`, `a-star`,
+//! `wall-left`, ...).
+
+use crate::algorithms::{GeneratorAlgorithm, Hand, MazeGenerator, MazeSolver, PathfindingAlgorithm, SolutionResult};
+use crate::maze::{Maze, Topology};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// A generated maze, kept on the Rust side along with its latest solution
+#[wasm_bindgen]
+pub struct MazeHandle {
+    maze: Maze,
+    solution: Option<SolutionResult>,
+}
+
+#[wasm_bindgen]
+impl MazeHandle {
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.maze.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.maze.height
+    }
+
+    /// The whole maze as a plain object, shaped like its JSON serialization
+    pub fn maze(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.maze)?)
+    }
+}
+
+/// The route a solver found
+#[wasm_bindgen]
+pub struct Path {
+    cells: Vec<Position>,
+    nodes_explored: usize,
+}
+
+#[wasm_bindgen]
+impl Path {
+    /// Number of cells on the route, start and exit included
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[wasm_bindgen(getter, js_name = nodesExplored)]
+    pub fn nodes_explored(&self) -> usize {
+        self.nodes_explored
+    }
+
+    /// The route from start to exit as `{ row, col }` objects
+    pub fn cells(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.cells)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+/// What an animation frame does to a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepKind {
+    /// The solver reached the cell
+    Visit,
+    /// The cell is on the final route
+    Path,
+}
+
+/// One frame of a solve animation, as `{ kind, row, col }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StepEvent {
+    pub kind: StepKind,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Generate a square maze; the same seed always gives the same maze
+#[wasm_bindgen]
+pub fn generate(width: usize, height: usize, algorithm: &str, seed: u32) -> Result<MazeHandle, JsError> {
+    if width == 0 || height == 0 {
+        return Err(JsError::new("Maze width and height must be at least 1"));
+    }
+    let algorithm = generator_algorithm(algorithm).map_err(|e| JsError::new(&e))?;
+    Ok(MazeHandle {
+        maze: MazeGenerator::generate_seeded(width, height, algorithm, Topology::Square, u64::from(seed)),
+        solution: None,
+    })
+}
+
+/// Solve the maze, keeping the solver's steps for [`step_events`]
+#[wasm_bindgen]
+pub fn solve(handle: &mut MazeHandle, algorithm: &str) -> Result<Path, JsError> {
+    let algorithm = pathfinding_algorithm(algorithm).map_err(|e| JsError::new(&e))?;
+    let result = MazeSolver::try_solve(&handle.maze, algorithm).map_err(|e| JsError::new(&e.to_string()))?;
+    let path = Path {
+        cells: result.path.iter().map(|&cell| cell.into()).collect(),
+        nodes_explored: result.nodes_explored,
+    };
+    handle.solution = Some(result);
+    Ok(path)
+}
+
+/// The latest solve as animation frames: every cell the solver reached, in
+/// order, then the route. Empty until the maze has been solved.
+#[wasm_bindgen(js_name = stepEvents)]
+pub fn step_events(handle: &MazeHandle) -> Result<JsValue, JsError> {
+    let events = handle.solution.as_ref().map(events).unwrap_or_default();
+    Ok(serde_wasm_bindgen::to_value(&events)?)
+}
+
+/// The frames the terminal animator plays for a solution
+pub fn events(result: &SolutionResult) -> Vec<StepEvent> {
+    let visits = result.trace.iter().map(|&(row, col)| StepEvent { kind: StepKind::Visit, row, col });
+    let route = result.path.iter().map(|&(row, col)| StepEvent { kind: StepKind::Path, row, col });
+    visits.chain(route).collect()
+}
+
+fn generator_algorithm(name: &str) -> Result<GeneratorAlgorithm, String> {
+    match name {
+        "recursive-backtracker" => Ok(GeneratorAlgorithm::RecursiveBacktracker),
+        "prims" => Ok(GeneratorAlgorithm::Prims),
+        "kruskals" => Ok(GeneratorAlgorithm::Kruskals),
+        "aldous-broder" => Ok(GeneratorAlgorithm::AldousBroder),
+        _ => Err(format!("Unknown generation algorithm '{}'", name)),
+    }
+}
+
+fn pathfinding_algorithm(name: &str) -> Result<PathfindingAlgorithm, String> {
+    match name {
+        "a-star" => Ok(PathfindingAlgorithm::AStar),
+        "bfs" => Ok(PathfindingAlgorithm::BFS),
+        "dfs" => Ok(PathfindingAlgorithm::DFS),
+        "dijkstra" => Ok(PathfindingAlgorithm::Dijkstra),
+        "wall-left" => Ok(PathfindingAlgorithm::WallFollower(Hand::Left)),
+        "wall-right" => Ok(PathfindingAlgorithm::WallFollower(Hand::Right)),
+        "dead-end-filling" => Ok(PathfindingAlgorithm::DeadEndFilling),
+        _ => Err(format!("Unknown solving algorithm '{}'", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_names_match_the_cli() {
+        assert_eq!(generator_algorithm("aldous-broder"), Ok(GeneratorAlgorithm::AldousBroder));
+        assert_eq!(pathfinding_algorithm("wall-right"), Ok(PathfindingAlgorithm::WallFollower(Hand::Right)));
+        assert!(generator_algorithm("AStar").is_err());
+        assert!(pathfinding_algorithm("").is_err());
+    }
+
+    #[test]
+    fn test_events_visit_then_trace_route() {
+        let maze = MazeGenerator::generate_seeded(8, 8, GeneratorAlgorithm::Kruskals, Topology::Square, 7);
+        let result = MazeSolver::try_solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+        let events = events(&result);
+
+        assert_eq!(events.len(), result.trace.len() + result.path.len());
+        let first_path = events.iter().position(|e| e.kind == StepKind::Path).unwrap();
+        assert_eq!(first_path, result.trace.len());
+        assert!(events[first_path..].iter().all(|e| e.kind == StepKind::Path));
+        assert_eq!((events[first_path].row, events[first_path].col), maze.start);
+    }
+}
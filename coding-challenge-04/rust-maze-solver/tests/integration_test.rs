@@ -1,6 +1,9 @@
+#![cfg(not(target_arch = "wasm32"))]
+
 use rust_maze_solver::{
     algorithms::{GeneratorAlgorithm, MazeGenerator, MazeSolver, PathfindingAlgorithm},
     io,
+    maze::{CellType, Maze, Topology},
 };
 use std::fs;
 
@@ -168,3 +171,66 @@ fn test_solution_path_is_continuous() {
         );
     }
 }
+
+#[test]
+fn test_bfs_on_hand_built_hex_maze() {
+    // 3x3 hex maze (odd rows shifted right):
+    //
+    //    S - o - o
+    //     \       \
+    //      o - o   o
+    //     /       /
+    //    o - o   E
+    //
+    // Start and end are three steps apart as the crow flies, but the
+    // walls force the route along the top and down the right side.
+    let mut maze = Maze::with_topology(3, 3, Topology::Hex);
+    for pos in maze.positions() {
+        maze.set_cell_type(pos.0, pos.1, CellType::Path);
+    }
+    let passages = [
+        ((0, 0), (0, 1)),
+        ((0, 1), (0, 2)),
+        ((0, 2), (1, 2)),
+        ((1, 2), (2, 2)),
+        ((0, 0), (1, 0)),
+        ((1, 0), (1, 1)),
+        ((1, 0), (2, 0)),
+        ((2, 0), (2, 1)),
+    ];
+    for (a, b) in passages {
+        assert!(maze.neighbors(a.0, a.1).contains(&b), "{:?} and {:?} are not adjacent", a, b);
+        maze.link(a, b);
+    }
+    maze.start = (0, 0);
+    maze.end = (2, 2);
+    maze.mark_start();
+    maze.mark_end();
+
+    let result = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+    assert_eq!(result.path, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+
+    let astar = MazeSolver::solve(&maze, PathfindingAlgorithm::AStar).unwrap();
+    assert_eq!(astar.path, result.path);
+}
+
+#[test]
+fn test_theta_maze_roundtrip_and_solve() {
+    let temp_dir = std::env::temp_dir();
+    let file_path = temp_dir.join("test_maze_theta_integration.json");
+
+    let maze = MazeGenerator::generate_with_topology(0, 6, GeneratorAlgorithm::Kruskals, Topology::Theta);
+    io::save_maze(&maze, &file_path, Some("Kruskals")).unwrap();
+    let loaded = io::load_maze(&file_path).unwrap();
+
+    assert_eq!(loaded.topology, Topology::Theta);
+    assert_eq!(loaded.cell_count(), maze.cell_count());
+
+    let original = MazeSolver::solve(&maze, PathfindingAlgorithm::BFS).unwrap();
+    let reloaded = MazeSolver::solve(&loaded, PathfindingAlgorithm::BFS).unwrap();
+    assert_eq!(original.path, reloaded.path);
+    assert_eq!(reloaded.path.first(), Some(&(0, 0)));
+    assert_eq!(reloaded.path.last().map(|&(ring, _)| ring), Some(5));
+
+    fs::remove_file(file_path).ok();
+}
@@ -0,0 +1,57 @@
+//! Runs the browser bindings under wasm-bindgen-test:
+//! `wasm-pack test --headless --firefox -- --no-default-features --features wasm`
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use rust_maze_solver::wasm::{generate, solve, step_events};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn kind(event: &JsValue) -> String {
+    js_sys::Reflect::get(event, &JsValue::from_str("kind")).unwrap().as_string().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn generate_is_seeded() {
+    let a = generate(12, 10, "prims", 42).unwrap();
+    let b = generate(12, 10, "prims", 42).unwrap();
+    assert_eq!(a.width(), 12);
+    assert_eq!(a.height(), 10);
+    assert_eq!(
+        js_sys::JSON::stringify(&a.maze().unwrap()).unwrap(),
+        js_sys::JSON::stringify(&b.maze().unwrap()).unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn solve_finds_a_route() {
+    let mut handle = generate(15, 15, "recursive-backtracker", 1).unwrap();
+    let path = solve(&mut handle, "a-star").unwrap();
+    assert!(path.length() > 1);
+    assert!(path.nodes_explored() >= path.length());
+
+    let cells = js_sys::Array::from(&path.cells().unwrap());
+    assert_eq!(cells.length() as usize, path.length());
+}
+
+#[wasm_bindgen_test]
+fn step_events_follow_the_latest_solve() {
+    let mut handle = generate(10, 10, "kruskals", 3).unwrap();
+    assert_eq!(js_sys::Array::from(&step_events(&handle).unwrap()).length(), 0);
+
+    let path = solve(&mut handle, "bfs").unwrap();
+    let events = js_sys::Array::from(&step_events(&handle).unwrap());
+    assert_eq!(kind(&events.get(0)), "visit");
+    assert_eq!(kind(&events.get(events.length() - 1)), "path");
+
+    let route = events.iter().filter(|e| kind(e) == "path").count();
+    assert_eq!(route, path.length());
+}
+
+#[wasm_bindgen_test]
+fn unknown_algorithms_are_rejected() {
+    assert!(generate(5, 5, "maze-o-matic", 0).is_err());
+    let mut handle = generate(5, 5, "prims", 0).unwrap();
+    assert!(solve(&mut handle, "teleport").is_err());
+}
@@ -0,0 +1,7 @@
+//! Alert rules engine over saved search results
+
+pub mod rules;
+pub mod scheduler;
+
+pub use rules::{evaluate, rule_matches, should_fire};
+pub use scheduler::AlertScheduler;
@@ -0,0 +1,266 @@
+//! Pure alert rule evaluation
+
+use crate::models::{AlertMatch, AlertRule, EbayListing};
+use chrono::Utc;
+use std::collections::HashMap;
+
+/// Check whether a listing satisfies every filter on a rule
+pub fn rule_matches(rule: &AlertRule, listing: &EbayListing) -> bool {
+    if let Some(price_max) = rule.price_max {
+        if listing.price.amount > price_max {
+            return false;
+        }
+    }
+
+    if rule.free_shipping_only && !listing.free_shipping {
+        return false;
+    }
+
+    if !rule.conditions.is_empty()
+        && !rule
+            .conditions
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(listing.condition.trim()))
+    {
+        return false;
+    }
+
+    let title = listing.title.to_lowercase();
+
+    if !rule
+        .must_include
+        .iter()
+        .all(|kw| title.contains(&kw.to_lowercase()))
+    {
+        return false;
+    }
+
+    if rule
+        .must_exclude
+        .iter()
+        .any(|kw| title.contains(&kw.to_lowercase()))
+    {
+        return false;
+    }
+
+    true
+}
+
+/// Decide whether a match should fire given the last alerted price for the item
+///
+/// An item fires the first time it matches, and afterwards only when its price
+/// drops below the price it last fired at.
+pub fn should_fire(last_alerted_price: Option<f64>, price: f64) -> bool {
+    match last_alerted_price {
+        Some(previous) => price < previous,
+        None => true,
+    }
+}
+
+/// Evaluate a rule against a batch of listings
+///
+/// `last_alerted` maps item IDs to the price they last fired at for this rule.
+pub fn evaluate(
+    rule: &AlertRule,
+    listings: &[EbayListing],
+    last_alerted: &HashMap<String, f64>,
+) -> Vec<AlertMatch> {
+    let now = Utc::now();
+
+    listings
+        .iter()
+        .filter(|listing| rule_matches(rule, listing))
+        .filter(|listing| {
+            should_fire(
+                last_alerted.get(&listing.item_id).copied(),
+                listing.price.amount,
+            )
+        })
+        .map(|listing| AlertMatch {
+            rule_id: rule.id.clone(),
+            item_id: listing.item_id.clone(),
+            title: listing.title.clone(),
+            price: listing.price.amount,
+            listing_url: listing.listing_url.clone(),
+            matched_at: now,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BuyingFormat, Price, SellerInfo};
+
+    fn rule() -> AlertRule {
+        AlertRule {
+            id: "rule-1".to_string(),
+            phrase_id: "vintage-cameras".to_string(),
+            price_max: None,
+            conditions: vec![],
+            free_shipping_only: false,
+            must_include: vec![],
+            must_exclude: vec![],
+            webhook_url: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn listing(item_id: &str, title: &str, price: f64) -> EbayListing {
+        EbayListing {
+            item_id: item_id.to_string(),
+            title: title.to_string(),
+            price: Price::usd(price),
+            price_max: None,
+            shipping: None,
+            condition: "Used".to_string(),
+            format: BuyingFormat::BuyItNow,
+            seller: SellerInfo::default(),
+            location: "USA".to_string(),
+            thumbnail_url: None,
+            listing_url: format!("https://ebay.com/itm/{}", item_id),
+            bids: None,
+            time_left: None,
+            free_shipping: false,
+            returns_accepted: false,
+            label: None,
+            best_offer: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_rule_matches_everything() {
+        assert!(rule_matches(&rule(), &listing("1", "Canon AE-1", 500.0)));
+    }
+
+    #[test]
+    fn test_price_ceiling_is_inclusive() {
+        let mut r = rule();
+        r.price_max = Some(150.0);
+
+        assert!(rule_matches(&r, &listing("1", "Canon AE-1", 150.0)));
+        assert!(rule_matches(&r, &listing("2", "Canon AE-1", 99.0)));
+        assert!(!rule_matches(&r, &listing("3", "Canon AE-1", 150.01)));
+    }
+
+    #[test]
+    fn test_free_shipping_filter() {
+        let mut r = rule();
+        r.free_shipping_only = true;
+
+        let mut item = listing("1", "Canon AE-1", 100.0);
+        assert!(!rule_matches(&r, &item));
+
+        item.free_shipping = true;
+        assert!(rule_matches(&r, &item));
+    }
+
+    #[test]
+    fn test_condition_filter_case_insensitive() {
+        let mut r = rule();
+        r.conditions = vec!["used".to_string(), "Refurbished".to_string()];
+
+        let mut item = listing("1", "Canon AE-1", 100.0);
+        assert!(rule_matches(&r, &item));
+
+        item.condition = "New".to_string();
+        assert!(!rule_matches(&r, &item));
+    }
+
+    #[test]
+    fn test_must_include_requires_all_keywords() {
+        let mut r = rule();
+        r.must_include = vec!["canon".to_string(), "LENS".to_string()];
+
+        assert!(rule_matches(
+            &r,
+            &listing("1", "Canon AE-1 with 50mm lens", 100.0)
+        ));
+        assert!(!rule_matches(
+            &r,
+            &listing("2", "Canon AE-1 body only", 100.0)
+        ));
+    }
+
+    #[test]
+    fn test_must_exclude_rejects_any_keyword() {
+        let mut r = rule();
+        r.must_exclude = vec!["parts".to_string(), "broken".to_string()];
+
+        assert!(rule_matches(&r, &listing("1", "Canon AE-1 working", 100.0)));
+        assert!(!rule_matches(
+            &r,
+            &listing("2", "Canon AE-1 for PARTS", 100.0)
+        ));
+        assert!(!rule_matches(&r, &listing("3", "Broken Canon AE-1", 100.0)));
+    }
+
+    #[test]
+    fn test_all_filters_combined() {
+        let mut r = rule();
+        r.price_max = Some(150.0);
+        r.free_shipping_only = true;
+        r.conditions = vec!["Used".to_string()];
+        r.must_include = vec!["canon".to_string()];
+        r.must_exclude = vec!["parts".to_string()];
+
+        let mut good = listing("1", "Canon AE-1", 120.0);
+        good.free_shipping = true;
+        assert!(rule_matches(&r, &good));
+
+        let mut too_expensive = good.clone();
+        too_expensive.price = Price::usd(200.0);
+        assert!(!rule_matches(&r, &too_expensive));
+
+        let mut paid_shipping = good.clone();
+        paid_shipping.free_shipping = false;
+        assert!(!rule_matches(&r, &paid_shipping));
+
+        let mut wrong_condition = good.clone();
+        wrong_condition.condition = "New".to_string();
+        assert!(!rule_matches(&r, &wrong_condition));
+
+        let mut missing_keyword = good.clone();
+        missing_keyword.title = "Nikon FM2".to_string();
+        assert!(!rule_matches(&r, &missing_keyword));
+
+        let mut excluded_keyword = good.clone();
+        excluded_keyword.title = "Canon AE-1 for parts".to_string();
+        assert!(!rule_matches(&r, &excluded_keyword));
+    }
+
+    #[test]
+    fn test_should_fire_first_time() {
+        assert!(should_fire(None, 100.0));
+    }
+
+    #[test]
+    fn test_should_fire_only_on_further_price_drop() {
+        assert!(!should_fire(Some(100.0), 100.0));
+        assert!(!should_fire(Some(100.0), 110.0));
+        assert!(should_fire(Some(100.0), 95.0));
+    }
+
+    #[test]
+    fn test_evaluate_skips_already_alerted_items() {
+        let mut r = rule();
+        r.price_max = Some(150.0);
+
+        let listings = vec![
+            listing("1", "Canon AE-1", 120.0),
+            listing("2", "Canon AE-1", 130.0),
+            listing("3", "Canon AE-1", 90.0),
+            listing("4", "Canon AE-1", 300.0),
+        ];
+
+        let mut last_alerted = HashMap::new();
+        last_alerted.insert("1".to_string(), 120.0);
+        last_alerted.insert("3".to_string(), 100.0);
+
+        let matches = evaluate(&r, &listings, &last_alerted);
+        let ids: Vec<&str> = matches.iter().map(|m| m.item_id.as_str()).collect();
+
+        assert_eq!(ids, vec!["2", "3"]);
+        assert!(matches.iter().all(|m| m.rule_id == "rule-1"));
+    }
+}
@@ -0,0 +1,265 @@
+//! Periodic alert evaluation and webhook delivery
+
+use crate::error::Result;
+use crate::models::{AlertMatch, AlertRule};
+use crate::search::SearchManager;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Runs saved searches that have alert rules and records new matches
+pub struct AlertScheduler {
+    search_manager: Arc<SearchManager>,
+    interval: Duration,
+    http: reqwest::Client,
+}
+
+impl AlertScheduler {
+    /// Create new alert scheduler
+    pub fn new(search_manager: Arc<SearchManager>, interval: Duration) -> Self {
+        Self {
+            search_manager,
+            interval,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Spawn the scheduler loop on the current runtime
+    pub fn spawn(self) -> JoinHandle<()> {
+        info!("Starting alert scheduler (interval: {:?})", self.interval);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+
+            loop {
+                ticker.tick().await;
+
+                match self.run_once().await {
+                    Ok(count) => debug!("Alert check complete, {} new alerts", count),
+                    Err(e) => warn!("Alert check failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Run every watched search once, returning the number of new alerts
+    pub async fn run_once(&self) -> Result<usize> {
+        let rules = self.search_manager.list_alerts().await?;
+        let phrase_ids: BTreeSet<String> = rules.into_iter().map(|r| r.phrase_id).collect();
+
+        let mut total = 0;
+
+        for phrase_id in phrase_ids {
+            let results = match self.search_manager.search_by_phrase_id(&phrase_id).await {
+                Ok(results) => results,
+                Err(e) => {
+                    warn!("Alert search for phrase {} failed: {}", phrase_id, e);
+                    continue;
+                }
+            };
+
+            for (rule, matches) in self
+                .search_manager
+                .process_alerts(&phrase_id, &results)
+                .await?
+            {
+                total += matches.len();
+
+                if let Some(url) = &rule.webhook_url {
+                    if let Err(e) = self.post_webhook(url, &rule, &matches).await {
+                        warn!("Webhook delivery for rule {} failed: {}", rule.id, e);
+                    }
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// POST newly fired alerts to the rule's webhook
+    async fn post_webhook(
+        &self,
+        url: &str,
+        rule: &AlertRule,
+        matches: &[AlertMatch],
+    ) -> Result<()> {
+        let response = self
+            .http
+            .post(url)
+            .json(&webhook_payload(rule, matches))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?;
+
+        response.error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// Build the JSON body sent to alert webhooks
+pub fn webhook_payload(rule: &AlertRule, matches: &[AlertMatch]) -> serde_json::Value {
+    json!({
+        "rule_id": rule.id,
+        "phrase_id": rule.phrase_id,
+        "alerts": matches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::BrowserPool;
+    use crate::config::ConfigManager;
+    use crate::storage::{Database, ResultCache};
+    use chrono::Utc;
+    use tempfile::NamedTempFile;
+    use tokio::sync::Mutex;
+
+    const CONFIG: &str = r#"
+[server]
+name = "test-ebay-mcp"
+version = "1.0.0"
+log_level = "info"
+
+[browser]
+pool_min_size = 0
+pool_max_size = 1
+headless = true
+page_load_timeout = 5
+element_timeout = 5
+user_agents = []
+randomize_delay = false
+delay_min_ms = 0
+delay_max_ms = 0
+
+[database]
+path = "test.db"
+auto_migrate = true
+
+[cache]
+enabled = false
+ttl_seconds = 300
+max_memory_entries = 100
+enable_disk_cache = false
+disk_cache_dir = "/tmp/cache"
+
+[logging]
+level = "info"
+file = "test.log"
+max_file_size = "10MB"
+max_backups = 3
+
+[scraper]
+base_url = "http://127.0.0.1:9"
+max_retries = 1
+screenshot_on_error = false
+screenshot_dir = "/tmp"
+"#;
+
+    const PHRASES: &str = r#"
+version = "1.0"
+
+[[phrases]]
+id = "vintage-cameras"
+name = "Vintage Cameras"
+query = "vintage camera"
+created_at = "2024-01-01T00:00:00Z"
+"#;
+
+    async fn search_manager(
+        config_file: &NamedTempFile,
+        phrases_file: &NamedTempFile,
+        db_file: &NamedTempFile,
+    ) -> Arc<SearchManager> {
+        tokio::fs::write(config_file.path(), CONFIG).await.unwrap();
+        tokio::fs::write(phrases_file.path(), PHRASES).await.unwrap();
+
+        let config_manager = ConfigManager::load(
+            config_file.path().to_path_buf(),
+            phrases_file.path().to_path_buf(),
+        )
+        .await
+        .unwrap();
+        let config = config_manager.get_config().await;
+
+        Arc::new(SearchManager::new(
+            Arc::new(config_manager),
+            Arc::new(BrowserPool::new(config.browser.into()).await.unwrap()),
+            Arc::new(ResultCache::new(false, 300, 100, None)),
+            Arc::new(Mutex::new(Database::new(db_file.path()).unwrap())),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_spawned_scheduler_runs_watched_searches() {
+        let config_file = NamedTempFile::new().unwrap();
+        let phrases_file = NamedTempFile::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+        let search_manager = search_manager(&config_file, &phrases_file, &db_file).await;
+        search_manager
+            .create_alert(AlertRule {
+                id: "rule-1".to_string(),
+                phrase_id: "vintage-cameras".to_string(),
+                price_max: Some(150.0),
+                conditions: vec![],
+                free_shipping_only: false,
+                must_include: vec![],
+                must_exclude: vec![],
+                webhook_url: None,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let handle =
+            AlertScheduler::new(search_manager.clone(), Duration::from_secs(3600)).spawn();
+
+        // The first tick fires at once and searches the watched phrase
+        let mut history = Vec::new();
+        for _ in 0..100 {
+            history = search_manager.get_history(10, 0).await.unwrap();
+            if !history.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        handle.abort();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].query, "vintage camera");
+    }
+
+    #[test]
+    fn test_webhook_payload_structure() {
+        let rule = AlertRule {
+            id: "rule-1".to_string(),
+            phrase_id: "vintage-cameras".to_string(),
+            price_max: Some(150.0),
+            conditions: vec![],
+            free_shipping_only: true,
+            must_include: vec![],
+            must_exclude: vec![],
+            webhook_url: Some("https://example.com/hook".to_string()),
+            created_at: Utc::now(),
+        };
+        let matches = vec![AlertMatch {
+            rule_id: "rule-1".to_string(),
+            item_id: "123".to_string(),
+            title: "Canon AE-1".to_string(),
+            price: 120.0,
+            listing_url: "https://ebay.com/itm/123".to_string(),
+            matched_at: Utc::now(),
+        }];
+
+        let payload = webhook_payload(&rule, &matches);
+
+        assert_eq!(payload["rule_id"], "rule-1");
+        assert_eq!(payload["phrase_id"], "vintage-cameras");
+        assert_eq!(payload["alerts"].as_array().unwrap().len(), 1);
+        assert_eq!(payload["alerts"][0]["item_id"], "123");
+    }
+}
@@ -10,6 +10,9 @@ pub struct AntiDetection {
     /// Available user agents
     user_agents: Vec<String>,
 
+    /// Available Accept-Language values
+    accept_languages: Vec<String>,
+
     /// Random delay configuration
     randomize_delay: bool,
     delay_min: Duration,
@@ -26,12 +29,19 @@ impl AntiDetection {
     ) -> Self {
         Self {
             user_agents,
+            accept_languages: Vec::new(),
             randomize_delay,
             delay_min,
             delay_max,
         }
     }
 
+    /// Set the Accept-Language values to choose from
+    pub fn with_accept_languages(mut self, accept_languages: Vec<String>) -> Self {
+        self.accept_languages = accept_languages;
+        self
+    }
+
     /// Get a random user agent
     pub fn random_user_agent(&self) -> &str {
         if self.user_agents.is_empty() {
@@ -64,6 +74,42 @@ impl AntiDetection {
         (widths[idx], heights[idx])
     }
 
+    /// Get a random Accept-Language value
+    pub fn random_accept_language(&self) -> &str {
+        if self.accept_languages.is_empty() {
+            return DEFAULT_ACCEPT_LANGUAGE;
+        }
+
+        let idx = rand::thread_rng().gen_range(0..self.accept_languages.len());
+        &self.accept_languages[idx]
+    }
+
+    /// Pick a fingerprint for a new browser context
+    pub fn random_fingerprint(&self) -> Fingerprint {
+        Fingerprint {
+            user_agent: self.random_user_agent().to_string(),
+            accept_language: self.random_accept_language().to_string(),
+            viewport: self.random_viewport(),
+        }
+    }
+
+    /// Fingerprint used when variation is disabled
+    pub fn fixed_fingerprint(&self, viewport: (u32, u32)) -> Fingerprint {
+        Fingerprint {
+            user_agent: self
+                .user_agents
+                .first()
+                .map_or(DEFAULT_USER_AGENT, String::as_str)
+                .to_string(),
+            accept_language: self
+                .accept_languages
+                .first()
+                .map_or(DEFAULT_ACCEPT_LANGUAGE, String::as_str)
+                .to_string(),
+            viewport,
+        }
+    }
+
     /// Get JavaScript to inject for anti-detection
     pub fn anti_detection_script() -> &'static str {
         r#"
@@ -116,6 +162,55 @@ impl AntiDetection {
     }
 }
 
+/// Browser identity applied to one context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub user_agent: String,
+    pub accept_language: String,
+    pub viewport: (u32, u32),
+}
+
+impl Fingerprint {
+    /// Extra HTTP headers for the context
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        vec![("Accept-Language", self.accept_language.clone())]
+    }
+
+    /// Languages from the Accept-Language value, best first
+    pub fn languages(&self) -> Vec<String> {
+        self.accept_language
+            .split(',')
+            .map(|part| part.split(';').next().unwrap_or("").trim().to_string())
+            .filter(|lang| !lang.is_empty())
+            .collect()
+    }
+
+    /// JavaScript keeping `navigator.languages` in line with the header
+    ///
+    /// Runs after [`AntiDetection::anti_detection_script`], replacing its
+    /// fixed language list.
+    pub fn languages_script(&self) -> String {
+        let languages = serde_json::to_string(&self.languages()).unwrap_or_else(|_| "[]".into());
+        format!(
+            "Object.defineProperty(navigator, 'languages', {{ get: () => {} }});",
+            languages
+        )
+    }
+}
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            accept_language: DEFAULT_ACCEPT_LANGUAGE.to_string(),
+            viewport: (1920, 1080),
+        }
+    }
+}
+
+/// Default Accept-Language if none configured
+const DEFAULT_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.9";
+
 /// Default user agent if none configured
 const DEFAULT_USER_AGENT: &str =
     "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
@@ -319,4 +414,53 @@ mod tests {
         assert_eq!(cloned.user_agents.len(), 1);
         assert_eq!(cloned.randomize_delay, true);
     }
+
+    #[test]
+    fn test_random_fingerprint_uses_configured_lists() {
+        let anti_det = AntiDetection::new(
+            vec!["UA1".to_string(), "UA2".to_string()],
+            false,
+            Duration::from_millis(0),
+            Duration::from_millis(0),
+        )
+        .with_accept_languages(vec!["de-DE,de;q=0.9".to_string()]);
+
+        let fingerprint = anti_det.random_fingerprint();
+        assert!(fingerprint.user_agent == "UA1" || fingerprint.user_agent == "UA2");
+        assert_eq!(fingerprint.accept_language, "de-DE,de;q=0.9");
+        assert!(fingerprint.viewport.0 >= 1280);
+    }
+
+    #[test]
+    fn test_fixed_fingerprint() {
+        let anti_det = AntiDetection::new(
+            vec![],
+            false,
+            Duration::from_millis(0),
+            Duration::from_millis(0),
+        );
+
+        let fingerprint = anti_det.fixed_fingerprint((1920, 1080));
+        assert_eq!(fingerprint.user_agent, DEFAULT_USER_AGENT);
+        assert_eq!(fingerprint.accept_language, DEFAULT_ACCEPT_LANGUAGE);
+        assert_eq!(fingerprint.viewport, (1920, 1080));
+    }
+
+    #[test]
+    fn test_fingerprint_languages() {
+        let fingerprint = Fingerprint {
+            user_agent: "UA".to_string(),
+            accept_language: "en-GB,en;q=0.9, de;q=0.5".to_string(),
+            viewport: (1366, 768),
+        };
+
+        assert_eq!(fingerprint.languages(), vec!["en-GB", "en", "de"]);
+        assert!(fingerprint
+            .languages_script()
+            .contains(r#"["en-GB","en","de"]"#));
+        assert_eq!(
+            fingerprint.headers(),
+            vec![("Accept-Language", "en-GB,en;q=0.9, de;q=0.5".to_string())]
+        );
+    }
 }
@@ -1,7 +1,13 @@
 //! Headless browser management module
 
 pub mod anti_detection;
+pub mod context;
 pub mod pool;
+pub mod proxy;
+pub mod session;
 
-pub use anti_detection::AntiDetection;
+pub use anti_detection::{AntiDetection, Fingerprint};
+pub use context::ContextSource;
 pub use pool::{BrowserPool, BrowserPoolConfig, PoolStats, PooledBrowser};
+pub use proxy::{Proxy, ProxyId, ProxyPool, ProxyScheme, ProxyStats};
+pub use session::{BrowserSession, CookieJar, SessionInfo, StoredCookie};
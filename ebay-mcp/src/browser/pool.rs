@@ -1,8 +1,11 @@
 //! Browser pool management for headless Chrome instances
 
+use super::anti_detection::{AntiDetection, Fingerprint};
+use super::proxy::{self, Proxy, ProxyId, ProxyPool, ProxyStats};
 use crate::error::Result;
-use crate::models::BrowserConfig;
+use crate::models::{BrowserConfig, FingerprintConfig, ProxyConfig, ProxyRotation};
 use std::collections::VecDeque;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
@@ -19,15 +22,24 @@ pub struct BrowserInstance {
     // browser: Browser,
     created_at: Instant,
     request_count: std::sync::atomic::AtomicU64,
+    proxy: Option<ProxyId>,
+    fingerprint: Fingerprint,
 }
 
 impl BrowserInstance {
     /// Create new browser instance (stub for now)
     pub fn new() -> Self {
+        Self::with_context(None, Fingerprint::default())
+    }
+
+    /// Create instance whose context uses the given proxy and fingerprint
+    pub fn with_context(proxy: Option<ProxyId>, fingerprint: Fingerprint) -> Self {
         Self {
             id: Uuid::new_v4(),
             created_at: Instant::now(),
             request_count: std::sync::atomic::AtomicU64::new(0),
+            proxy,
+            fingerprint,
         }
     }
 
@@ -36,6 +48,16 @@ impl BrowserInstance {
         self.id
     }
 
+    /// Proxy assigned to this context (None for a direct connection)
+    pub fn proxy(&self) -> Option<ProxyId> {
+        self.proxy
+    }
+
+    /// Fingerprint applied to this context
+    pub fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
+    }
+
     /// Get age of this instance
     pub fn age(&self) -> Duration {
         self.created_at.elapsed()
@@ -85,6 +107,15 @@ pub struct BrowserPoolConfig {
     pub randomize_delay: bool,
     pub delay_min: Duration,
     pub delay_max: Duration,
+
+    /// Proxies and rotation
+    pub proxy: ProxyConfig,
+
+    /// Per-context fingerprint variation
+    pub fingerprint: FingerprintConfig,
+
+    /// Chrome user data dir kept across restarts (None for a throwaway profile)
+    pub profile_dir: Option<std::path::PathBuf>,
 }
 
 impl From<BrowserConfig> for BrowserPoolConfig {
@@ -101,6 +132,12 @@ impl From<BrowserConfig> for BrowserPoolConfig {
             randomize_delay: config.randomize_delay,
             delay_min: Duration::from_millis(config.delay_min_ms),
             delay_max: Duration::from_millis(config.delay_max_ms),
+            proxy: config.proxy,
+            fingerprint: config.fingerprint,
+            profile_dir: config
+                .session
+                .enabled
+                .then(|| config.session.profile_dir.join("chrome")),
         }
     }
 }
@@ -129,6 +166,12 @@ pub struct BrowserPool {
 
     /// Pool statistics
     stats: Arc<Mutex<PoolStats>>,
+
+    /// Fingerprint source for new contexts
+    anti_detection: AntiDetection,
+
+    /// Proxies with their health
+    proxies: Arc<Mutex<ProxyPool>>,
 }
 
 impl BrowserPool {
@@ -139,11 +182,30 @@ impl BrowserPool {
             config.min_size, config.max_size
         );
 
+        let proxies = ProxyPool::from_config(&config.proxy)?;
+        if !proxies.is_empty() {
+            info!(
+                "Routing browser traffic through {} proxies ({:?} rotation)",
+                proxies.len(),
+                proxies.rotation()
+            );
+        }
+
+        let anti_detection = AntiDetection::new(
+            config.user_agents.clone(),
+            config.randomize_delay,
+            config.delay_min,
+            config.delay_max,
+        )
+        .with_accept_languages(config.fingerprint.accept_languages.clone());
+
         let pool = Self {
             config,
             available: Arc::new(Mutex::new(VecDeque::new())),
             in_use_count: Arc::new(Mutex::new(0)),
             stats: Arc::new(Mutex::new(PoolStats::default())),
+            anti_detection,
+            proxies: Arc::new(Mutex::new(proxies)),
         };
 
         // Warm up pool to min_size
@@ -192,17 +254,45 @@ impl BrowserPool {
     async fn create_instance(&self) -> Result<BrowserInstance> {
         debug!("Creating new browser instance");
 
-        // TODO: Create actual browser when we can build
+        // Per-context rotation pins the proxy when the context is created
+        let proxy = {
+            let mut proxies = self.proxies.lock().await;
+            match proxies.rotation() {
+                ProxyRotation::PerContext => proxies.select(&[], Instant::now()),
+                ProxyRotation::PerSearch => None,
+            }
+        };
+
+        let fingerprint = if self.config.fingerprint.enabled {
+            self.anti_detection.random_fingerprint()
+        } else {
+            self.anti_detection
+                .fixed_fingerprint(self.config.window_size)
+        };
+
+        // TODO: Create actual browser when we can build, launched with
+        // --user-data-dir set to profile_dir, passing the proxy server and
+        // viewport to the new context, the fingerprint headers to
+        // Network.setExtraHTTPHeaders and the session's cookies to
+        // Network.setCookies
         // For now, create a stub instance
-        let instance = BrowserInstance::new();
+        let instance = BrowserInstance::with_context(proxy, fingerprint);
 
-        debug!("Created browser instance: {}", instance.id());
+        debug!(
+            "Created browser instance: {} (proxy: {:?}, user agent: {})",
+            instance.id(),
+            proxy,
+            instance.fingerprint().user_agent
+        );
 
         Ok(instance)
     }
 
     /// Acquire browser instance from pool
-    pub async fn acquire(&self) -> Result<PooledBrowser> {
+    ///
+    /// Takes the pool's `Arc` so the returned browser can give its instance
+    /// back when dropped.
+    pub async fn acquire(self: &Arc<Self>) -> Result<PooledBrowser> {
         debug!("Acquiring browser from pool");
 
         let instance = {
@@ -242,7 +332,7 @@ impl BrowserPool {
 
         Ok(PooledBrowser {
             instance: Some(instance),
-            pool: self.clone_arc(),
+            pool: Arc::clone(self),
         })
     }
 
@@ -250,9 +340,17 @@ impl BrowserPool {
     async fn release(&self, instance: BrowserInstance) -> Result<()> {
         debug!("Releasing browser instance: {}", instance.id());
 
+        // Contexts are bound to their proxy, so one whose proxy is cooling
+        // down is replaced rather than reused
+        let proxy_unhealthy = match instance.proxy() {
+            Some(id) => !self.proxies.lock().await.is_healthy(id, Instant::now()),
+            None => false,
+        };
+
         // Check if instance is too old or has too many requests
-        let should_destroy =
-            instance.age() > self.config.max_instance_age || instance.request_count() > 1000;
+        let should_destroy = instance.age() > self.config.max_instance_age
+            || instance.request_count() > 1000
+            || proxy_unhealthy;
 
         if should_destroy {
             debug!(
@@ -300,11 +398,70 @@ impl BrowserPool {
         self.stats.lock().await.clone()
     }
 
-    /// Clone Arc reference to self (for PooledBrowser)
-    fn clone_arc(&self) -> Arc<Self> {
-        // This is a workaround - normally we'd store Arc<Self> in the struct
-        // For now, we'll use a different approach
-        unimplemented!("Need to refactor to use Arc<BrowserPool> internally")
+    /// Get proxy health statistics
+    pub async fn proxy_stats(&self) -> ProxyStats {
+        self.proxies.lock().await.stats(Instant::now())
+    }
+
+    /// Run a request through the proxies, failing over to another on error
+    ///
+    /// `assigned` is the proxy of the context making the request; it is tried
+    /// first under per-context rotation. Errors that point at the proxy
+    /// (network failures, rate limiting, CAPTCHAs) put it in cooldown and
+    /// the request is retried through the next healthy proxy, up to the
+    /// configured number of attempts. Without proxies the request runs once
+    /// over a direct connection.
+    pub async fn with_proxy<T, F, Fut>(
+        &self,
+        assigned: Option<ProxyId>,
+        mut request: F,
+    ) -> Result<T>
+    where
+        F: FnMut(Option<Proxy>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_attempts = {
+            let proxies = self.proxies.lock().await;
+            if proxies.is_empty() {
+                drop(proxies);
+                return request(None).await;
+            }
+            proxies.max_attempts() as usize
+        };
+
+        let mut tried = Vec::new();
+        let mut last_error = None;
+
+        while tried.len() < max_attempts {
+            let (id, proxy) = {
+                let mut proxies = self.proxies.lock().await;
+                let now = Instant::now();
+                match proxies.select_for_attempt(assigned, &tried, now) {
+                    Some(id) => (id, proxies.get(id).cloned()),
+                    None => {
+                        return Err(
+                            last_error.unwrap_or_else(|| proxy::no_healthy_proxy(&proxies, now))
+                        )
+                    }
+                }
+            };
+            tried.push(id);
+
+            match request(proxy).await {
+                Ok(value) => {
+                    self.proxies.lock().await.mark_succeeded(id);
+                    return Ok(value);
+                }
+                Err(e) if proxy::is_proxy_failure(&e) => {
+                    self.proxies.lock().await.mark_failed(id, Instant::now());
+                    debug!("Request through proxy {} failed: {}", id, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.expect("at least one attempt was made"))
     }
 }
 
@@ -320,6 +477,16 @@ impl PooledBrowser {
         self.instance.as_ref().unwrap().id()
     }
 
+    /// Proxy assigned to this browser's context
+    pub fn proxy(&self) -> Option<ProxyId> {
+        self.instance.as_ref().and_then(BrowserInstance::proxy)
+    }
+
+    /// Fingerprint applied to this browser's context
+    pub fn fingerprint(&self) -> &Fingerprint {
+        self.instance.as_ref().unwrap().fingerprint()
+    }
+
     /// Increment request count
     pub fn increment_requests(&self) {
         if let Some(instance) = &self.instance {
@@ -346,6 +513,8 @@ impl Drop for PooledBrowser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::EbayMcpError;
+    use crate::models::{ProxyEntry, SessionConfig};
 
     #[tokio::test]
     async fn test_browser_instance_creation() {
@@ -369,10 +538,150 @@ mod tests {
             randomize_delay: true,
             delay_min_ms: 100,
             delay_max_ms: 500,
+            proxy: ProxyConfig::default(),
+            fingerprint: FingerprintConfig::default(),
+            session: SessionConfig::default(),
         };
 
         let pool_config: BrowserPoolConfig = browser_config.into();
         assert_eq!(pool_config.min_size, 2);
         assert_eq!(pool_config.max_size, 5);
+        assert_eq!(
+            pool_config.profile_dir,
+            Some(std::path::PathBuf::from("./data/browser-profile/chrome"))
+        );
+    }
+
+    fn proxy_pool_config(min_size: usize, rotation: ProxyRotation) -> BrowserPoolConfig {
+        let proxies = (1..=3)
+            .map(|i| ProxyEntry {
+                url: format!("http://10.0.0.{}:8080", i),
+                username: None,
+                password: None,
+            })
+            .collect();
+
+        BrowserPoolConfig {
+            min_size,
+            max_size: 3,
+            browser_path: None,
+            headless: true,
+            user_agents: vec!["UA1".to_string(), "UA2".to_string()],
+            timeout: Duration::from_secs(30),
+            max_instance_age: Duration::from_secs(3600),
+            window_size: (1920, 1080),
+            randomize_delay: false,
+            delay_min: Duration::ZERO,
+            delay_max: Duration::ZERO,
+            proxy: ProxyConfig {
+                proxies,
+                rotation,
+                cooldown_seconds: 60,
+                max_attempts: 3,
+            },
+            fingerprint: FingerprintConfig::default(),
+            profile_dir: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_per_context_instances_get_proxies_in_turn() {
+        let pool = BrowserPool::new(proxy_pool_config(3, ProxyRotation::PerContext))
+            .await
+            .unwrap();
+
+        let available = pool.available.lock().await;
+        let assigned: Vec<_> = available.iter().map(|i| i.proxy()).collect();
+        assert_eq!(assigned, vec![Some(0), Some(1), Some(2)]);
+        assert!(available
+            .iter()
+            .all(|i| i.fingerprint().user_agent.starts_with("UA")));
+    }
+
+    #[tokio::test]
+    async fn test_with_proxy_fails_over_and_records_health() {
+        let pool = BrowserPool::new(proxy_pool_config(0, ProxyRotation::PerSearch))
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        let result = pool
+            .with_proxy(None, |proxy| {
+                let proxy = proxy.unwrap();
+                seen.push(proxy.host.clone());
+                async move {
+                    if proxy.host == "10.0.0.1" {
+                        Err(EbayMcpError::RateLimited)
+                    } else {
+                        Ok(proxy.host)
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result, "10.0.0.2");
+        assert_eq!(seen, vec!["10.0.0.1", "10.0.0.2"]);
+
+        let stats = pool.proxy_stats().await;
+        assert_eq!(stats.cooling_down, 1);
+        assert_eq!(stats.total_failures, 1);
+        assert_eq!(stats.total_successes, 1);
+
+        // The failed proxy is skipped by the next search
+        let next = pool
+            .with_proxy(None, |proxy| async move { Ok(proxy.unwrap().host) })
+            .await
+            .unwrap();
+        assert_eq!(next, "10.0.0.3");
+    }
+
+    #[tokio::test]
+    async fn test_with_proxy_gives_up_after_max_attempts() {
+        let mut config = proxy_pool_config(0, ProxyRotation::PerSearch);
+        config.proxy.max_attempts = 2;
+        let pool = BrowserPool::new(config).await.unwrap();
+
+        let mut attempts = 0;
+        let result: Result<()> = pool
+            .with_proxy(None, |_| {
+                attempts += 1;
+                async { Err(EbayMcpError::Network("connection reset".to_string())) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(EbayMcpError::Network(_))));
+        assert_eq!(attempts, 2);
+        assert_eq!(pool.proxy_stats().await.healthy, 1);
+    }
+
+    #[tokio::test]
+    async fn test_with_proxy_does_not_blame_proxy_for_other_errors() {
+        let pool = BrowserPool::new(proxy_pool_config(0, ProxyRotation::PerContext))
+            .await
+            .unwrap();
+
+        let result: Result<()> = pool
+            .with_proxy(Some(1), |proxy| {
+                assert_eq!(proxy.unwrap().host, "10.0.0.2");
+                async { Err(EbayMcpError::InvalidInput("bad query".to_string())) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(EbayMcpError::InvalidInput(_))));
+        assert_eq!(pool.proxy_stats().await.healthy, 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_proxy_without_proxies_goes_direct() {
+        let mut config = proxy_pool_config(0, ProxyRotation::PerSearch);
+        config.proxy.proxies.clear();
+        let pool = BrowserPool::new(config).await.unwrap();
+
+        let direct = pool
+            .with_proxy(None, |proxy| async move { Ok(proxy.is_none()) })
+            .await
+            .unwrap();
+        assert!(direct);
     }
 }
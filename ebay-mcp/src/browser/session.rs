@@ -0,0 +1,723 @@
+//! Persistent browser session: profile directory, cookies and warm-up
+//!
+//! The profile directory holds Chrome's user data dir (`chrome/`) and the
+//! cookie jar (`cookies.json`), which is saved after the warm-up and again
+//! when the session is dropped. Cookies are kept in a jar of our own rather
+//! than only in Chrome's store because pool contexts do not share the
+//! profile: each new context is seeded from the jar.
+//!
+//! A run that crashes leaves `session.lock` and Chrome's `Singleton*` files
+//! behind, and may leave a half-written cookie file. Opening the session
+//! removes locks whose process is gone and sets an unreadable cookie file
+//! aside, so the next start does not need manual cleanup.
+
+use crate::error::{EbayMcpError, Result};
+use crate::models::SessionConfig;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDateTime, Utc};
+use reqwest::header::{COOKIE, LOCATION, SET_COOKIE};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Saved cookie jar inside the profile directory
+const COOKIE_FILE: &str = "cookies.json";
+
+/// Lock naming the process that has the profile open
+const LOCK_FILE: &str = "session.lock";
+
+/// Chrome's user data dir inside the profile directory
+const CHROME_DIR: &str = "chrome";
+
+/// Files Chrome leaves in its user data dir while running
+const CHROME_LOCKS: [&str; 3] = ["SingletonLock", "SingletonSocket", "SingletonCookie"];
+
+/// Redirects followed by one fetch
+const MAX_REDIRECTS: usize = 5;
+
+/// A cookie as stored in the jar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Domain without a leading dot
+    pub domain: String,
+    /// Only sent to `domain` itself, not its subdomains
+    pub host_only: bool,
+    pub path: String,
+    /// None for a session cookie
+    pub expires: Option<DateTime<Utc>>,
+    pub secure: bool,
+    pub http_only: bool,
+    /// When the cookie was first set; kept when the server replaces it
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredCookie {
+    /// Parse a `Set-Cookie` header received from `url`
+    ///
+    /// Returns None for malformed headers and for a `Domain` the URL's host
+    /// may not set.
+    pub fn parse(url: &Url, header: &str, now: DateTime<Utc>) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Self {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            expires: None,
+            secure: false,
+            http_only: false,
+            created_at: now,
+        };
+
+        let mut max_age = None;
+        for attribute in parts {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "expires" => cookie.expires = parse_expires(value).or(cookie.expires),
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                _ => {}
+            }
+        }
+
+        // Max-Age wins over Expires
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(now + ChronoDuration::seconds(seconds));
+        }
+
+        Some(cookie)
+    }
+
+    /// Whether the cookie has expired by `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Whether the cookie goes with a request to `url`
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let host_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+
+        host_ok && path_matches(url.path(), &self.path) && (!self.secure || url.scheme() == "https")
+    }
+
+    fn same_slot(&self, other: &StoredCookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+}
+
+/// Cookies of the session, in the order they were first set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CookieJar {
+    cookies: Vec<StoredCookie>,
+}
+
+impl CookieJar {
+    /// Store a `Set-Cookie` header received from `url`
+    ///
+    /// A cookie set again keeps its creation time; one set already expired
+    /// is removed.
+    pub fn store(&mut self, url: &Url, header: &str, now: DateTime<Utc>) {
+        let Some(mut cookie) = StoredCookie::parse(url, header, now) else {
+            debug!("Ignoring cookie from {}: {}", url, header);
+            return;
+        };
+
+        let existing = self.cookies.iter().position(|c| c.same_slot(&cookie));
+        if cookie.is_expired(now) {
+            if let Some(index) = existing {
+                self.cookies.remove(index);
+            }
+            return;
+        }
+
+        match existing {
+            Some(index) => {
+                cookie.created_at = self.cookies[index].created_at;
+                self.cookies[index] = cookie;
+            }
+            None => self.cookies.push(cookie),
+        }
+    }
+
+    /// `Cookie` header value for a request to `url`, if any cookie applies
+    pub fn header_for(&self, url: &Url, now: DateTime<Utc>) -> Option<String> {
+        let mut cookies: Vec<&StoredCookie> = self
+            .cookies
+            .iter()
+            .filter(|c| !c.is_expired(now) && c.matches(url))
+            .collect();
+        if cookies.is_empty() {
+            return None;
+        }
+
+        // More specific paths first, as browsers send them
+        cookies.sort_by(|a, b| b.path.len().cmp(&a.path.len()));
+        Some(
+            cookies
+                .iter()
+                .map(|c| format!("{}={}", c.name, c.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Drop cookies that have expired by `now`
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) {
+        self.cookies.retain(|c| !c.is_expired(now));
+    }
+
+    /// Cookie by name, on any domain
+    pub fn get(&self, name: &str) -> Option<&StoredCookie> {
+        self.cookies.iter().find(|c| c.name == name)
+    }
+
+    /// All cookies in the jar
+    pub fn cookies(&self) -> &[StoredCookie] {
+        &self.cookies
+    }
+
+    /// Number of cookies in the jar
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Whether the jar is empty
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+}
+
+/// Contents of `cookies.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedSession {
+    saved_at: Option<DateTime<Utc>>,
+    signed_in: bool,
+    jar: CookieJar,
+}
+
+/// Changing state of an open session
+#[derive(Debug, Default)]
+struct SessionState {
+    jar: CookieJar,
+    signed_in: Option<bool>,
+    warmed_up_at: Option<DateTime<Utc>>,
+}
+
+/// State of the browser session, as reported by the `session_info` tool
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub profile_dir: PathBuf,
+    pub cookie_count: usize,
+    /// Age of the longest-held cookie
+    pub oldest_cookie_age_seconds: Option<i64>,
+    /// Age of the most recently created cookie
+    pub newest_cookie_age_seconds: Option<i64>,
+    /// Cookies loaded from the previous run
+    pub restored_cookie_count: usize,
+    /// When the previous run last saved its cookies
+    pub previous_saved_at: Option<DateTime<Utc>>,
+    /// Whether the previous run was signed in
+    pub signed_in_before_restart: bool,
+    /// Whether the homepage showed a signed-in account at warm-up (None before)
+    pub signed_in: Option<bool>,
+    /// Whether the previous run's sign-in is still valid (None when there was none, or before warm-up)
+    pub signed_in_survived_restart: Option<bool>,
+    pub warmed_up_at: Option<DateTime<Utc>>,
+    /// Repairs made to the profile when it was opened
+    pub repairs: Vec<String>,
+}
+
+/// Browser session persisted in a profile directory
+pub struct BrowserSession {
+    config: SessionConfig,
+    base_url: String,
+    client: reqwest::Client,
+    state: Mutex<SessionState>,
+    restored_cookie_count: usize,
+    previous_saved_at: Option<DateTime<Utc>>,
+    signed_in_before_restart: bool,
+    repairs: Vec<String>,
+}
+
+impl BrowserSession {
+    /// Open the profile directory, repairing what a crashed run left behind
+    ///
+    /// Fails if another running process has the profile open.
+    pub fn open(config: &SessionConfig, base_url: &str, user_agent: Option<&str>) -> Result<Self> {
+        let dir = &config.profile_dir;
+        fs::create_dir_all(dir.join(CHROME_DIR))?;
+
+        let mut repairs = repair_locks(dir)?;
+        let saved = load_cookies(dir, &mut repairs)?;
+        for repair in &repairs {
+            warn!("Repaired browser profile {}: {}", dir.display(), repair);
+        }
+
+        fs::write(dir.join(LOCK_FILE), std::process::id().to_string())?;
+
+        let mut jar = saved.jar;
+        jar.purge_expired(Utc::now());
+        info!(
+            "Opened browser profile {} ({} cookies restored)",
+            dir.display(),
+            jar.len()
+        );
+
+        let mut client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(Duration::from_secs(30));
+        if let Some(user_agent) = user_agent {
+            client = client.user_agent(user_agent);
+        }
+
+        Ok(Self {
+            config: config.clone(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: client.build()?,
+            restored_cookie_count: jar.len(),
+            previous_saved_at: saved.saved_at,
+            signed_in_before_restart: saved.signed_in,
+            state: Mutex::new(SessionState {
+                jar,
+                ..Default::default()
+            }),
+            repairs,
+        })
+    }
+
+    /// Chrome's user data dir inside the profile
+    pub fn chrome_profile_dir(&self) -> PathBuf {
+        self.config.profile_dir.join(CHROME_DIR)
+    }
+
+    /// Copy of the current cookie jar, for seeding new browser contexts
+    pub fn cookies(&self) -> CookieJar {
+        self.state().jar.clone()
+    }
+
+    /// Visit the homepage and run one search, then save the cookies
+    ///
+    /// This is what a returning visitor does, so the session's cookies are
+    /// refreshed before the first real search. The homepage also tells
+    /// whether the account is still signed in.
+    pub async fn warm_up(&self) -> Result<()> {
+        info!("Warming up browser session on {}", self.base_url);
+
+        let homepage = self.fetch(&format!("{}/", self.base_url)).await?;
+        let signed_in = homepage.contains(&self.config.signed_in_marker);
+
+        let search_url = format!(
+            "{}/sch/i.html?_nkw={}",
+            self.base_url,
+            urlencoding::encode(&self.config.warm_up_query)
+        );
+        self.fetch(&search_url).await?;
+
+        {
+            let mut state = self.state();
+            state.signed_in = Some(signed_in);
+            state.warmed_up_at = Some(Utc::now());
+        }
+        if self.signed_in_before_restart && !signed_in {
+            warn!("The signed-in session did not survive the restart");
+        }
+
+        self.save()
+    }
+
+    /// GET a page with the session's cookies, storing any it sets
+    pub async fn fetch(&self, url: &str) -> Result<String> {
+        let mut url = Url::parse(url)
+            .map_err(|e| EbayMcpError::InvalidInput(format!("Invalid URL '{}': {}", url, e)))?;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let cookies = self.state().jar.header_for(&url, Utc::now());
+            let mut request = self.client.get(url.clone());
+            if let Some(cookies) = cookies {
+                request = request.header(COOKIE, cookies);
+            }
+            let response = request.send().await?;
+
+            {
+                let now = Utc::now();
+                let mut state = self.state();
+                for header in response.headers().get_all(SET_COOKIE) {
+                    if let Ok(header) = header.to_str() {
+                        state.jar.store(&url, header, now);
+                    }
+                }
+            }
+
+            let status = response.status();
+            if status.is_redirection() {
+                if let Some(location) = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    debug!("{} redirected to {}", url, location);
+                    url = url.join(location).map_err(|e| {
+                        EbayMcpError::Network(format!("Bad redirect from {}: {}", url, e))
+                    })?;
+                    continue;
+                }
+            }
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                return Err(EbayMcpError::RateLimited);
+            }
+            if !status.is_success() {
+                return Err(EbayMcpError::Network(format!(
+                    "{} returned {}",
+                    url, status
+                )));
+            }
+
+            return Ok(response.text().await?);
+        }
+
+        Err(EbayMcpError::Network(format!(
+            "Too many redirects from {}",
+            url
+        )))
+    }
+
+    /// Write the cookie jar to the profile
+    pub fn save(&self) -> Result<()> {
+        let saved = {
+            let state = self.state();
+            SavedSession {
+                saved_at: Some(Utc::now()),
+                signed_in: state.signed_in.unwrap_or(self.signed_in_before_restart),
+                jar: state.jar.clone(),
+            }
+        };
+
+        // Write then rename, so a crash mid-write leaves the old file intact
+        let path = self.config.profile_dir.join(COOKIE_FILE);
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_vec_pretty(&saved)?)?;
+        fs::rename(&temp, &path)?;
+
+        debug!("Saved {} cookies to {}", saved.jar.len(), path.display());
+        Ok(())
+    }
+
+    /// Current state of the session
+    pub fn info(&self) -> SessionInfo {
+        let now = Utc::now();
+        let state = self.state();
+        let ages = state
+            .jar
+            .cookies()
+            .iter()
+            .map(|c| (now - c.created_at).num_seconds());
+
+        SessionInfo {
+            profile_dir: self.config.profile_dir.clone(),
+            cookie_count: state.jar.len(),
+            oldest_cookie_age_seconds: ages.clone().max(),
+            newest_cookie_age_seconds: ages.min(),
+            restored_cookie_count: self.restored_cookie_count,
+            previous_saved_at: self.previous_saved_at,
+            signed_in_before_restart: self.signed_in_before_restart,
+            signed_in: state.signed_in,
+            signed_in_survived_restart: state.signed_in.filter(|_| self.signed_in_before_restart),
+            warmed_up_at: state.warmed_up_at,
+            repairs: self.repairs.clone(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, SessionState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for BrowserSession {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            warn!("Failed to save browser session cookies: {}", e);
+        }
+        if let Err(e) = fs::remove_file(self.config.profile_dir.join(LOCK_FILE)) {
+            warn!("Failed to release browser profile lock: {}", e);
+        }
+    }
+}
+
+/// Remove locks left by processes that are no longer running
+fn repair_locks(dir: &Path) -> Result<Vec<String>> {
+    let mut repairs = Vec::new();
+    let in_use = |pid: u32| {
+        EbayMcpError::Browser(format!(
+            "Browser profile {} is in use by process {}",
+            dir.display(),
+            pid
+        ))
+    };
+
+    let lock = dir.join(LOCK_FILE);
+    if let Ok(contents) = fs::read_to_string(&lock) {
+        if let Some(pid) = contents
+            .trim()
+            .parse()
+            .ok()
+            .filter(|&pid| process_alive(pid))
+        {
+            return Err(in_use(pid));
+        }
+        fs::remove_file(&lock)?;
+        repairs.push(format!("removed stale {}", LOCK_FILE));
+    }
+
+    // Chrome points SingletonLock at `hostname-pid`
+    let chrome = dir.join(CHROME_DIR);
+    let owner = fs::read_link(chrome.join("SingletonLock"))
+        .ok()
+        .and_then(|target| {
+            let target = target.to_string_lossy().into_owned();
+            target.rsplit_once('-')?.1.parse::<u32>().ok()
+        });
+    if let Some(pid) = owner.filter(|&pid| process_alive(pid)) {
+        return Err(in_use(pid));
+    }
+    for name in CHROME_LOCKS {
+        let path = chrome.join(name);
+        if fs::symlink_metadata(&path).is_ok() {
+            fs::remove_file(&path)?;
+            repairs.push(format!("removed stale {}/{}", CHROME_DIR, name));
+        }
+    }
+
+    Ok(repairs)
+}
+
+/// Load the saved cookies, setting an unreadable file aside
+fn load_cookies(dir: &Path, repairs: &mut Vec<String>) -> Result<SavedSession> {
+    let path = dir.join(COOKIE_FILE);
+    let contents = match fs::read(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(SavedSession::default()),
+        Err(e) => return Err(e.into()),
+    };
+
+    match serde_json::from_slice(&contents) {
+        Ok(saved) => Ok(saved),
+        Err(e) => {
+            let aside = path.with_extension("json.corrupt");
+            fs::rename(&path, &aside)?;
+            repairs.push(format!(
+                "moved unreadable {} to {} ({})",
+                COOKIE_FILE,
+                aside.display(),
+                e
+            ));
+            Ok(SavedSession::default())
+        }
+    }
+}
+
+/// Whether a process is running, checked through /proc on Linux
+///
+/// Elsewhere only our own process counts as running, so a lock left by
+/// another process is always treated as stale.
+fn process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        false
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Whether a request path falls under a cookie path
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Cookie path for a `Set-Cookie` without a `Path` attribute
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+/// Parse an `Expires` attribute in either common date format
+fn parse_expires(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+                .ok()
+                .map(|date| date.and_utc())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_parse_set_cookie() {
+        let cookie = StoredCookie::parse(
+            &url("https://www.ebay.com/sch/i.html"),
+            "ds2=abc; Domain=.ebay.com; Path=/; Max-Age=3600; Secure; HttpOnly",
+            now(),
+        )
+        .unwrap();
+        assert_eq!(cookie.name, "ds2");
+        assert_eq!(cookie.value, "abc");
+        assert_eq!(cookie.domain, "ebay.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.expires, Some(now() + ChronoDuration::hours(1)));
+        assert!(cookie.secure && cookie.http_only);
+
+        let cookie = StoredCookie::parse(
+            &url("https://www.ebay.com/sch/i.html"),
+            "nonsession=1; Expires=Wed, 21 Oct 2026 07:28:00 GMT",
+            now(),
+        )
+        .unwrap();
+        assert!(cookie.host_only);
+        assert_eq!(cookie.path, "/sch");
+        assert_eq!(
+            cookie.expires,
+            Some(Utc.with_ymd_and_hms(2026, 10, 21, 7, 28, 0).unwrap())
+        );
+
+        // A site may not set cookies for another domain
+        assert!(StoredCookie::parse(
+            &url("https://www.ebay.com/"),
+            "x=1; Domain=example.com",
+            now()
+        )
+        .is_none());
+        assert!(StoredCookie::parse(&url("https://www.ebay.com/"), "no value", now()).is_none());
+    }
+
+    #[test]
+    fn test_jar_matches_domain_path_and_scheme() {
+        let mut jar = CookieJar::default();
+        let home = url("https://www.ebay.com/");
+        jar.store(&home, "site=1; Domain=ebay.com; Path=/", now());
+        jar.store(&home, "host=2; Path=/", now());
+        jar.store(&home, "search=3; Path=/sch", now());
+        jar.store(&home, "secure=4; Path=/; Secure", now());
+
+        assert_eq!(
+            jar.header_for(&url("https://www.ebay.com/sch/i.html"), now())
+                .unwrap(),
+            "search=3; site=1; host=2; secure=4"
+        );
+        assert_eq!(
+            jar.header_for(&url("http://signin.ebay.com/"), now())
+                .unwrap(),
+            "site=1"
+        );
+        assert_eq!(
+            jar.header_for(&url("https://www.ebay.com/schedule"), now())
+                .unwrap(),
+            "site=1; host=2; secure=4"
+        );
+        assert!(jar
+            .header_for(&url("https://example.com/"), now())
+            .is_none());
+    }
+
+    #[test]
+    fn test_jar_replaces_and_expires_cookies() {
+        let mut jar = CookieJar::default();
+        let home = url("https://www.ebay.com/");
+        jar.store(&home, "ds2=old; Max-Age=60", now());
+
+        let later = now() + ChronoDuration::seconds(30);
+        jar.store(&home, "ds2=new; Max-Age=60", later);
+        assert_eq!(jar.len(), 1);
+        let cookie = jar.get("ds2").unwrap();
+        assert_eq!(cookie.value, "new");
+        assert_eq!(cookie.created_at, now());
+        assert_eq!(cookie.expires, Some(later + ChronoDuration::seconds(60)));
+
+        assert!(jar
+            .header_for(&home, later + ChronoDuration::seconds(61))
+            .is_none());
+
+        jar.store(&home, "ds2=gone; Max-Age=0", later);
+        assert!(jar.is_empty());
+    }
+
+    #[test]
+    fn test_profile_in_use_is_not_opened_twice() {
+        let dir = TempDir::new().unwrap();
+        let config = SessionConfig {
+            profile_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let session = BrowserSession::open(&config, "http://127.0.0.1", None).unwrap();
+        let err = BrowserSession::open(&config, "http://127.0.0.1", None)
+            .err()
+            .unwrap();
+        assert!(matches!(err, EbayMcpError::Browser(_)));
+
+        drop(session);
+        assert!(!dir.path().join(LOCK_FILE).exists());
+        BrowserSession::open(&config, "http://127.0.0.1", None).unwrap();
+    }
+}
@@ -4,6 +4,14 @@ use clap::Parser;
 use ebay_mcp_server::{config::ConfigManager, utils};
 use std::path::PathBuf;
 use tracing::info;
+#[cfg(feature = "replay")]
+use {
+    ebay_mcp_server::models::{AppConfig, SnapshotQuery},
+    ebay_mcp_server::scraper::replay_snapshot,
+    ebay_mcp_server::storage::{Database, PageArchive},
+    std::sync::Arc,
+    tokio::sync::Mutex,
+};
 
 /// Command line arguments
 #[derive(Parser, Debug)]
@@ -21,6 +29,39 @@ struct Args {
     /// Log level (trace, debug, info, warn, error)
     #[arg(short, long, default_value = "info")]
     log_level: String,
+
+    /// Developer command to run instead of the server
+    #[cfg(feature = "replay")]
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Developer commands
+#[cfg(feature = "replay")]
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Re-run the current scraper against archived pages and diff the output
+    Replay {
+        /// Replay a single snapshot by ID
+        #[arg(long)]
+        id: Option<i64>,
+
+        /// Only snapshots whose search phrase contains this text
+        #[arg(long)]
+        phrase: Option<String>,
+
+        /// Only snapshots captured on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+
+        /// Only snapshots captured on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<chrono::NaiveDate>,
+
+        /// Maximum number of snapshots to replay
+        #[arg(long)]
+        limit: Option<usize>,
+    },
 }
 
 #[tokio::main]
@@ -38,6 +79,26 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Loaded configuration: {}", config.server.name);
 
+    #[cfg(feature = "replay")]
+    if let Some(Command::Replay {
+        id,
+        phrase,
+        since,
+        until,
+        limit,
+    }) = args.command
+    {
+        let query = SnapshotQuery {
+            phrase,
+            since: since.map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+            until: until.map(|d| d.and_hms_opt(23, 59, 59).unwrap().and_utc()),
+            kind: None,
+            limit,
+        };
+        let clean = replay(&config, id, &query).await?;
+        std::process::exit(if clean { 0 } else { 1 });
+    }
+
     // Initialize MCP server
     let server = ebay_mcp_server::server::EbayMcpServer::new(config_manager).await?;
 
@@ -59,3 +120,37 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Replay archived snapshots, returning whether all of them were clean
+#[cfg(feature = "replay")]
+async fn replay(
+    config: &AppConfig,
+    id: Option<i64>,
+    query: &SnapshotQuery,
+) -> anyhow::Result<bool> {
+    let database = Database::new(&config.database.path)?;
+    let archive = PageArchive::new(&config.archive, Arc::new(Mutex::new(database)))?;
+
+    let snapshots = match id {
+        Some(id) => archive.get(id).await?.into_iter().collect::<Vec<_>>(),
+        None => archive.query(query).await?,
+    };
+
+    let mut changed = 0;
+    for snapshot in &snapshots {
+        let report = replay_snapshot(&archive, snapshot).await?;
+        if !report.is_clean() {
+            changed += 1;
+        }
+        print!("{}", report);
+    }
+
+    println!(
+        "Replayed {} snapshots: {} unchanged, {} changed",
+        snapshots.len(),
+        snapshots.len() - changed,
+        changed
+    );
+
+    Ok(changed == 0)
+}
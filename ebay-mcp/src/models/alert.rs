@@ -0,0 +1,104 @@
+//! Alert rule data models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Alert rule evaluated against listings found for a saved search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Unique identifier
+    pub id: String,
+
+    /// Saved search phrase the rule watches
+    pub phrase_id: String,
+
+    /// Maximum acceptable price (inclusive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_max: Option<f64>,
+
+    /// Accepted conditions (case-insensitive); empty accepts any condition
+    #[serde(default)]
+    pub conditions: Vec<String>,
+
+    /// Only match listings with free shipping
+    #[serde(default)]
+    pub free_shipping_only: bool,
+
+    /// Keywords that must all appear in the title (case-insensitive)
+    #[serde(default)]
+    pub must_include: Vec<String>,
+
+    /// Keywords that must not appear in the title (case-insensitive)
+    #[serde(default)]
+    pub must_exclude: Vec<String>,
+
+    /// Optional webhook that receives a POST for every new match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Recorded match of a listing against an alert rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertMatch {
+    /// Rule that fired
+    pub rule_id: String,
+
+    /// Matched eBay item ID
+    pub item_id: String,
+
+    /// Listing title at match time
+    pub title: String,
+
+    /// Listing price at match time
+    pub price: f64,
+
+    /// Listing URL
+    pub listing_url: String,
+
+    /// Match timestamp
+    pub matched_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_rule_defaults_on_deserialize() {
+        let json = r#"{
+            "id": "rule-1",
+            "phrase_id": "vintage-cameras",
+            "created_at": "2025-11-13T12:00:00Z"
+        }"#;
+
+        let rule: AlertRule = serde_json::from_str(json).unwrap();
+
+        assert!(rule.price_max.is_none());
+        assert!(rule.conditions.is_empty());
+        assert!(!rule.free_shipping_only);
+        assert!(rule.must_include.is_empty());
+        assert!(rule.must_exclude.is_empty());
+        assert!(rule.webhook_url.is_none());
+    }
+
+    #[test]
+    fn test_alert_match_serialization() {
+        let alert = AlertMatch {
+            rule_id: "rule-1".to_string(),
+            item_id: "123".to_string(),
+            title: "Canon AE-1".to_string(),
+            price: 120.0,
+            listing_url: "https://ebay.com/itm/123".to_string(),
+            matched_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&alert).unwrap();
+        let deserialized: AlertMatch = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.item_id, "123");
+        assert_eq!(deserialized.price, 120.0);
+    }
+}
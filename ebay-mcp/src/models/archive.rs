@@ -0,0 +1,112 @@
+//! Page snapshot archive data models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of scraped page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageKind {
+    /// Search results page
+    Results,
+
+    /// Single listing detail page
+    Detail,
+}
+
+impl PageKind {
+    /// Name stored in the archive index
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageKind::Results => "results",
+            PageKind::Detail => "detail",
+        }
+    }
+}
+
+impl fmt::Display for PageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PageKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "results" => Ok(PageKind::Results),
+            "detail" => Ok(PageKind::Detail),
+            other => Err(format!("unknown page kind '{}'", other)),
+        }
+    }
+}
+
+/// Archived page with the extraction output recorded at capture time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageSnapshot {
+    /// Index row ID (0 until stored)
+    pub id: i64,
+
+    /// Kind of page
+    pub kind: PageKind,
+
+    /// URL the page was loaded from
+    pub url: String,
+
+    /// Search phrase that led to the page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_phrase: Option<String>,
+
+    /// SHA-256 of the raw HTML, also the archive file name
+    pub html_hash: String,
+
+    /// Size of the compressed HTML on disk
+    pub compressed_bytes: u64,
+
+    /// Capture timestamp
+    pub captured_at: DateTime<Utc>,
+
+    /// Extraction output at capture time
+    pub extracted: serde_json::Value,
+}
+
+/// Filter for querying the snapshot index
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotQuery {
+    /// Case-insensitive substring of the search phrase
+    pub phrase: Option<String>,
+
+    /// Only snapshots captured at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only snapshots captured at or before this time
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only snapshots of this kind
+    pub kind: Option<PageKind>,
+
+    /// Maximum number of snapshots, newest first
+    pub limit: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_kind_round_trip() {
+        for kind in [PageKind::Results, PageKind::Detail] {
+            assert_eq!(kind.as_str().parse::<PageKind>().unwrap(), kind);
+        }
+        assert!("listing".parse::<PageKind>().is_err());
+    }
+
+    #[test]
+    fn test_page_kind_serialization() {
+        let json = serde_json::to_string(&PageKind::Results).unwrap();
+        assert_eq!(json, "\"results\"");
+    }
+}
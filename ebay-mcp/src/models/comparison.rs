@@ -0,0 +1,130 @@
+//! Side-by-side comparison of several searches
+
+use serde::{Deserialize, Serialize};
+
+use super::listing::ListingLabel;
+use super::search::SearchFilters;
+
+/// One search taking part in a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonQuery {
+    /// Column heading (the saved search name, or the query itself)
+    pub label: String,
+
+    /// Search query
+    pub query: String,
+
+    /// Search filters
+    #[serde(default)]
+    pub filters: SearchFilters,
+
+    /// Saved search phrase the query came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phrase_id: Option<String>,
+}
+
+impl ComparisonQuery {
+    /// Inline query with no filters, labelled with the query text
+    pub fn inline(query: impl Into<String>) -> Self {
+        let query = query.into();
+        Self {
+            label: query.clone(),
+            query,
+            filters: SearchFilters::default(),
+            phrase_id: None,
+        }
+    }
+}
+
+/// Result of comparing 2-5 searches, one column per search in request order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Columns, in the order the queries were given
+    pub columns: Vec<ComparisonColumn>,
+}
+
+/// Outcome of one search in a comparison
+///
+/// A failed search keeps its column, with `error` set and no figures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonColumn {
+    /// Column heading
+    pub label: String,
+
+    /// Search query
+    pub query: String,
+
+    /// Saved search phrase the query came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phrase_id: Option<String>,
+
+    /// Why the search failed, if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Listings returned
+    pub result_count: usize,
+
+    /// Currency of the landed prices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Median landed price of comparable listings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_landed: Option<f64>,
+
+    /// Lowest landed price of comparable listings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_landed: Option<f64>,
+
+    /// Median landed price against the column to the left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_from_previous: Option<PriceDelta>,
+
+    /// Best-value listings, best first
+    #[serde(default)]
+    pub best_value: Vec<RankedListing>,
+}
+
+impl ComparisonColumn {
+    /// Whether the search behind this column succeeded
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Difference between two median landed prices
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceDelta {
+    /// This column's median minus the previous one's
+    pub amount: f64,
+
+    /// `amount` as a percentage of the previous median
+    pub percent: f64,
+}
+
+/// A listing picked by the best-value ranking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedListing {
+    /// Item ID
+    pub item_id: String,
+
+    /// Title
+    pub title: String,
+
+    /// Price plus shipping
+    pub landed_price: f64,
+
+    /// Currency of `landed_price`
+    pub currency: String,
+
+    /// Ranking score; higher is better value
+    pub score: f64,
+
+    /// Listing URL
+    pub listing_url: String,
+
+    /// Classifier label, if the listing was labelled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<ListingLabel>,
+}
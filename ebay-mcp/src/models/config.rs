@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::listing::ListingLabel;
+
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -23,6 +25,18 @@ pub struct AppConfig {
 
     /// Scraper configuration
     pub scraper: ScraperConfig,
+
+    /// Alert scheduler configuration
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+
+    /// Page snapshot archive configuration
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+
+    /// Listing classifier configuration
+    #[serde(default)]
+    pub classifier: ClassifierConfig,
 }
 
 /// Server configuration
@@ -51,6 +65,117 @@ pub struct BrowserConfig {
     pub randomize_delay: bool,
     pub delay_min_ms: u64,
     pub delay_max_ms: u64,
+
+    /// Proxies for browser traffic (direct connection when none are listed)
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+
+    /// Per-context fingerprint variation
+    #[serde(default)]
+    pub fingerprint: FingerprintConfig,
+
+    /// Persistent profile, cookies and startup warm-up
+    #[serde(default)]
+    pub session: SessionConfig,
+}
+
+/// Proxy configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// Proxies to route browser contexts through
+    #[serde(default)]
+    pub proxies: Vec<ProxyEntry>,
+    /// When a new proxy is picked
+    #[serde(default)]
+    pub rotation: ProxyRotation,
+    /// Seconds a failing proxy is left out of rotation
+    pub cooldown_seconds: u64,
+    /// Proxies to try for one search before giving up
+    pub max_attempts: u32,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            proxies: Vec::new(),
+            rotation: ProxyRotation::default(),
+            cooldown_seconds: 300,
+            max_attempts: 3,
+        }
+    }
+}
+
+/// One proxy server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyEntry {
+    /// `http://host:port` or `socks5://host:port`, optionally with `user:pass@`
+    pub url: String,
+    /// Username, overriding any in the URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Password, overriding any in the URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+/// When the browser pool switches proxies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyRotation {
+    /// Each browser context keeps the proxy it was created with
+    #[default]
+    PerContext,
+    /// Every search goes out through the next proxy in the list
+    PerSearch,
+}
+
+/// Fingerprint variation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FingerprintConfig {
+    /// Pick user agent, viewport and accept-language per context
+    pub enabled: bool,
+    /// Accept-Language values to choose from
+    pub accept_languages: Vec<String>,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            accept_languages: vec![
+                "en-US,en;q=0.9".to_string(),
+                "en-GB,en;q=0.9".to_string(),
+                "en-US,en;q=0.8,de;q=0.5".to_string(),
+            ],
+        }
+    }
+}
+
+/// Browser session persistence configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionConfig {
+    /// Keep the profile and cookies across restarts
+    pub enabled: bool,
+    /// Directory holding the browser profile and saved cookies
+    pub profile_dir: PathBuf,
+    /// Visit the homepage and run one search at startup
+    pub warm_up: bool,
+    /// Query for the warm-up search
+    pub warm_up_query: String,
+    /// Text only present on the homepage when signed in
+    pub signed_in_marker: String,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            profile_dir: PathBuf::from("./data/browser-profile"),
+            warm_up: true,
+            warm_up_query: "usb cable".to_string(),
+            signed_in_marker: "id=\"gh-ug\"".to_string(),
+        }
+    }
 }
 
 /// Database configuration
@@ -88,6 +213,106 @@ pub struct ScraperConfig {
     pub screenshot_dir: PathBuf,
 }
 
+/// Alert scheduler configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    pub enabled: bool,
+    pub check_interval_seconds: u64,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            check_interval_seconds: 900,
+        }
+    }
+}
+
+/// Page snapshot archive configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    /// Days to keep snapshots (0 keeps them forever)
+    pub retention_days: u32,
+    /// Cap on compressed archive size in megabytes (0 means no cap)
+    pub max_size_mb: u64,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: PathBuf::from("./data/archive"),
+            retention_days: 90,
+            max_size_mb: 500,
+        }
+    }
+}
+
+/// Listing classifier configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassifierConfig {
+    /// Keyword file languages to load, e.g. `["en", "de"]`
+    pub languages: Vec<String>,
+    /// Directory of `<language>.toml` keyword files replacing the built-in ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords_dir: Option<PathBuf>,
+    /// Multiplier for rules on the condition field
+    pub condition_weight: f64,
+    /// Multiplier for rules on the title
+    pub title_weight: f64,
+    /// Score a label needs to beat `working`
+    pub threshold: f64,
+    /// Extra rules on top of the keyword files
+    #[serde(default)]
+    pub keywords: Vec<KeywordRule>,
+}
+
+impl Default for ClassifierConfig {
+    fn default() -> Self {
+        Self {
+            languages: vec!["en".to_string()],
+            keywords_dir: None,
+            condition_weight: 1.0,
+            title_weight: 1.0,
+            threshold: 1.0,
+            keywords: Vec::new(),
+        }
+    }
+}
+
+/// Keywords that vote for a listing label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordRule {
+    /// Label the keywords vote for
+    pub label: ListingLabel,
+    /// Listing field the keywords are matched against
+    #[serde(default)]
+    pub field: MatchField,
+    /// Score added for each keyword found
+    #[serde(default = "default_keyword_weight")]
+    pub weight: f64,
+    /// Words or phrases, matched case-insensitively on word boundaries
+    pub keywords: Vec<String>,
+}
+
+fn default_keyword_weight() -> f64 {
+    1.0
+}
+
+/// Listing field a keyword rule looks at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    /// Listing title
+    #[default]
+    Title,
+    /// Condition as chosen by the seller
+    Condition,
+}
+
 /// Saved phrases configuration (from search_phrases.toml)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedPhrasesConfig {
@@ -139,6 +364,9 @@ mod tests {
             randomize_delay: true,
             delay_min_ms: 100,
             delay_max_ms: 500,
+            proxy: ProxyConfig::default(),
+            fingerprint: FingerprintConfig::default(),
+            session: SessionConfig::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -164,6 +392,9 @@ mod tests {
             randomize_delay: false,
             delay_min_ms: 0,
             delay_max_ms: 0,
+            proxy: ProxyConfig::default(),
+            fingerprint: FingerprintConfig::default(),
+            session: SessionConfig::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -259,6 +490,9 @@ mod tests {
                 randomize_delay: false,
                 delay_min_ms: 0,
                 delay_max_ms: 0,
+                proxy: ProxyConfig::default(),
+                fingerprint: FingerprintConfig::default(),
+                session: SessionConfig::default(),
             },
             database: DatabaseConfig {
                 path: PathBuf::from("test.db"),
@@ -283,6 +517,9 @@ mod tests {
                 screenshot_on_error: false,
                 screenshot_dir: PathBuf::from("/tmp"),
             },
+            alerts: AlertsConfig::default(),
+            archive: ArchiveConfig::default(),
+            classifier: ClassifierConfig::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -296,6 +533,135 @@ mod tests {
         assert_eq!(deserialized.scraper.max_retries, 3);
     }
 
+    #[test]
+    fn test_alerts_config_parse_and_default() {
+        let toml_str = r#"
+enabled = true
+check_interval_seconds = 60
+"#;
+        let config: AlertsConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.check_interval_seconds, 60);
+
+        let default = AlertsConfig::default();
+        assert!(!default.enabled);
+        assert_eq!(default.check_interval_seconds, 900);
+    }
+
+    #[test]
+    fn test_archive_config_parse_and_default() {
+        let toml_str = r#"
+enabled = true
+dir = "/var/lib/ebay/archive"
+retention_days = 0
+max_size_mb = 50
+"#;
+        let config: ArchiveConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.dir, PathBuf::from("/var/lib/ebay/archive"));
+        assert_eq!(config.retention_days, 0);
+        assert_eq!(config.max_size_mb, 50);
+
+        let default = ArchiveConfig::default();
+        assert!(!default.enabled);
+        assert_eq!(default.retention_days, 90);
+    }
+
+    #[test]
+    fn test_classifier_config_parse_and_default() {
+        let toml_str = r#"
+languages = ["en", "de"]
+condition_weight = 2.0
+title_weight = 1.0
+threshold = 1.5
+
+[[keywords]]
+label = "for_parts"
+weight = 3.0
+keywords = ["needs new battery"]
+
+[[keywords]]
+label = "accessory_only"
+field = "condition"
+keywords = ["accessory"]
+"#;
+        let config: ClassifierConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.languages, vec!["en", "de"]);
+        assert_eq!(config.condition_weight, 2.0);
+        assert_eq!(config.keywords.len(), 2);
+        assert_eq!(config.keywords[0].label, ListingLabel::ForParts);
+        assert_eq!(config.keywords[0].field, MatchField::Title);
+        assert_eq!(config.keywords[1].field, MatchField::Condition);
+        assert_eq!(config.keywords[1].weight, 1.0);
+
+        let default = ClassifierConfig::default();
+        assert_eq!(default.languages, vec!["en"]);
+        assert!(default.keywords.is_empty());
+    }
+
+    #[test]
+    fn test_proxy_config_parse_and_default() {
+        let toml_str = r#"
+rotation = "per_search"
+cooldown_seconds = 60
+max_attempts = 2
+
+[[proxies]]
+url = "socks5://10.0.0.2:1080"
+username = "me"
+password = "secret"
+
+[[proxies]]
+url = "http://proxy.example:8080"
+"#;
+        let config: ProxyConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.rotation, ProxyRotation::PerSearch);
+        assert_eq!(config.cooldown_seconds, 60);
+        assert_eq!(config.proxies.len(), 2);
+        assert_eq!(config.proxies[0].username.as_deref(), Some("me"));
+        assert!(config.proxies[1].password.is_none());
+
+        let default = ProxyConfig::default();
+        assert!(default.proxies.is_empty());
+        assert_eq!(default.rotation, ProxyRotation::PerContext);
+    }
+
+    #[test]
+    fn test_browser_config_without_proxy_section() {
+        let toml_str = r#"
+pool_min_size = 1
+pool_max_size = 3
+headless = true
+page_load_timeout = 30
+element_timeout = 10
+user_agents = []
+randomize_delay = false
+delay_min_ms = 0
+delay_max_ms = 0
+"#;
+        let config: BrowserConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.proxy.proxies.is_empty());
+        assert!(config.fingerprint.enabled);
+        assert!(!config.fingerprint.accept_languages.is_empty());
+        assert!(config.session.enabled);
+        assert!(config.session.warm_up);
+    }
+
+    #[test]
+    fn test_session_config_parse() {
+        let toml_str = r#"
+enabled = true
+profile_dir = "/var/lib/ebay/profile"
+warm_up = false
+warm_up_query = "pencil"
+signed_in_marker = "Sign out"
+"#;
+        let config: SessionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.profile_dir, PathBuf::from("/var/lib/ebay/profile"));
+        assert!(!config.warm_up);
+        assert_eq!(config.signed_in_marker, "Sign out");
+    }
+
     #[test]
     fn test_saved_phrases_config_default() {
         let config = SavedPhrasesConfig::default();
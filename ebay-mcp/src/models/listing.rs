@@ -11,9 +11,13 @@ pub struct EbayListing {
     /// Title
     pub title: String,
 
-    /// Current price
+    /// Current price, or the low end of a price range
     pub price: Price,
 
+    /// High end of a price range, for listings with variations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_max: Option<Price>,
+
     /// Shipping cost
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shipping: Option<Price>,
@@ -52,10 +56,99 @@ pub struct EbayListing {
     /// Returns accepted
     #[serde(default)]
     pub returns_accepted: bool,
+
+    /// What the listing actually sells (set by the listing classifier)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<ListingLabel>,
+
+    /// Best Offer status, for listings that take or took offers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_offer: Option<BestOffer>,
+}
+
+impl EbayListing {
+    /// Whether eBay hides what this listing actually sold for
+    ///
+    /// `price` is then the asking price, not the sale price.
+    pub fn has_hidden_sale_price(&self) -> bool {
+        matches!(self.best_offer, Some(BestOffer::AcceptedHidden))
+    }
+}
+
+/// How Best Offer applies to a listing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BestOffer {
+    /// Listed "or Best Offer"; `price` is the asking price
+    Open,
+    /// Sold by accepting an offer; `price` is what it sold for
+    Accepted {
+        /// Asking price, struck through on the sold listing, if readable
+        original_price: Option<Price>,
+    },
+    /// Sold by accepting an offer eBay doesn't show; `price` is the asking price
+    AcceptedHidden,
+}
+
+impl BestOffer {
+    /// Percentage taken off `original_price` by selling at `sold`
+    ///
+    /// `None` unless the offer was accepted at a shown price in the same
+    /// currency as a positive asking price.
+    pub fn discount_percent(&self, sold: &Price) -> Option<f64> {
+        match self {
+            BestOffer::Accepted {
+                original_price: Some(original),
+            } if original.currency == sold.currency && original.amount > 0.0 => {
+                Some((original.amount - sold.amount) / original.amount * 100.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a listing actually sells, as opposed to what it was found under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingLabel {
+    /// A single working item
+    Working,
+    /// Broken, locked or sold for parts/repair
+    ForParts,
+    /// Several items sold together
+    Bundle,
+    /// Only an accessory (case, charger, box) for the searched item
+    AccessoryOnly,
+}
+
+impl ListingLabel {
+    /// All labels, in display order
+    pub const ALL: [ListingLabel; 4] = [
+        ListingLabel::Working,
+        ListingLabel::ForParts,
+        ListingLabel::Bundle,
+        ListingLabel::AccessoryOnly,
+    ];
+
+    /// Name used in config, filters and JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListingLabel::Working => "working",
+            ListingLabel::ForParts => "for_parts",
+            ListingLabel::Bundle => "bundle",
+            ListingLabel::AccessoryOnly => "accessory_only",
+        }
+    }
+}
+
+impl std::fmt::Display for ListingLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Price information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Price {
     pub amount: f64,
     pub currency: String,
@@ -184,6 +277,7 @@ mod tests {
             item_id: "123456789".to_string(),
             title: "Vintage Camera".to_string(),
             price: Price::usd(199.99),
+            price_max: None,
             shipping: Some(Price::usd(15.0)),
             condition: "Used".to_string(),
             format: BuyingFormat::BuyItNow,
@@ -199,6 +293,8 @@ mod tests {
             time_left: None,
             free_shipping: false,
             returns_accepted: true,
+            label: None,
+            best_offer: None,
         };
 
         let json = serde_json::to_string(&listing).unwrap();
@@ -218,6 +314,7 @@ mod tests {
             item_id: "987654321".to_string(),
             title: "Rare Collectible".to_string(),
             price: Price::usd(50.0),
+            price_max: None,
             shipping: None,
             condition: "New".to_string(),
             format: BuyingFormat::Auction,
@@ -229,6 +326,8 @@ mod tests {
             time_left: Some("2d 5h".to_string()),
             free_shipping: true,
             returns_accepted: false,
+            label: None,
+            best_offer: None,
         };
 
         assert_eq!(listing.bids, Some(15));
@@ -242,6 +341,7 @@ mod tests {
             item_id: "MIN123".to_string(),
             title: "Basic Item".to_string(),
             price: Price::usd(10.0),
+            price_max: None,
             shipping: None,
             condition: "New".to_string(),
             format: BuyingFormat::BuyItNow,
@@ -253,6 +353,8 @@ mod tests {
             time_left: None,
             free_shipping: false,
             returns_accepted: false,
+            label: None,
+            best_offer: None,
         };
 
         let json = serde_json::to_string(&listing).unwrap();
@@ -264,4 +366,63 @@ mod tests {
         assert!(deserialized.bids.is_none());
         assert!(deserialized.time_left.is_none());
     }
+
+    #[test]
+    fn test_listing_label_serialization() {
+        for label in ListingLabel::ALL {
+            let json = serde_json::to_string(&label).unwrap();
+            assert_eq!(json, format!("\"{}\"", label));
+
+            let deserialized: ListingLabel = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, label);
+        }
+    }
+
+    #[test]
+    fn test_best_offer_discount() {
+        let accepted = BestOffer::Accepted {
+            original_price: Some(Price::usd(200.0)),
+        };
+
+        assert_eq!(accepted.discount_percent(&Price::usd(150.0)), Some(25.0));
+        assert_eq!(accepted.discount_percent(&Price::new(150.0, "EUR")), None);
+        assert_eq!(BestOffer::Open.discount_percent(&Price::usd(150.0)), None);
+        assert_eq!(
+            BestOffer::Accepted {
+                original_price: None
+            }
+            .discount_percent(&Price::usd(150.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_best_offer_serialization() {
+        let accepted = BestOffer::Accepted {
+            original_price: Some(Price::usd(200.0)),
+        };
+        let json = serde_json::to_value(&accepted).unwrap();
+        assert_eq!(json["status"], "accepted");
+        assert_eq!(json["original_price"]["amount"], 200.0);
+        assert_eq!(serde_json::from_value::<BestOffer>(json).unwrap(), accepted);
+
+        assert_eq!(
+            serde_json::to_string(&BestOffer::AcceptedHidden).unwrap(),
+            r#"{"status":"accepted_hidden"}"#
+        );
+    }
+
+    #[test]
+    fn test_unlabeled_listing_deserializes() {
+        let json = r#"{
+            "item_id": "1", "title": "Phone", "price": {"amount": 1.0, "currency": "USD"},
+            "condition": "Used", "format": "BuyItNow",
+            "seller": {"username": "s", "feedback_score": 0, "positive_percentage": 100.0},
+            "location": "US", "listing_url": "https://ebay.com/itm/1"
+        }"#;
+
+        let listing: EbayListing = serde_json::from_str(json).unwrap();
+        assert!(listing.label.is_none());
+        assert!(listing.best_offer.is_none());
+    }
 }
@@ -1,9 +1,17 @@
 //! Data models for eBay MCP Server
 
+pub mod alert;
+pub mod archive;
+pub mod comparison;
 pub mod config;
 pub mod listing;
+pub mod portfolio;
 pub mod search;
 
+pub use alert::*;
+pub use archive::*;
+pub use comparison::*;
 pub use config::*;
 pub use listing::*;
+pub use portfolio::*;
 pub use search::*;
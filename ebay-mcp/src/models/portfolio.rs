@@ -0,0 +1,201 @@
+//! Saved search runs and the portfolio summary built from them
+
+use super::search::OfferStats;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Stored outcome of running a saved search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchRun {
+    /// Saved search phrase that ran
+    pub phrase_id: String,
+
+    /// When the results were scraped
+    pub ran_at: DateTime<Utc>,
+
+    /// Listings returned
+    pub result_count: usize,
+
+    /// Currency of the landed prices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Median landed price of comparable listings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_landed: Option<f64>,
+
+    /// Cheapest comparable listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cheapest: Option<CheapestListing>,
+
+    /// Listings not seen in any earlier run of the phrase
+    #[serde(default)]
+    pub new_listings: usize,
+
+    /// Comparable listings sold by accepted Best Offer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_offers: Option<OfferStats>,
+}
+
+/// Lowest landed price among a run's comparable listings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheapestListing {
+    /// Item ID
+    pub item_id: String,
+
+    /// Title
+    pub title: String,
+
+    /// Price plus shipping
+    pub landed_price: f64,
+
+    /// Currency of `landed_price`
+    pub currency: String,
+
+    /// Listing URL
+    pub listing_url: String,
+}
+
+/// Overview of every saved search, biggest price drop first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSummary {
+    /// When the summary was built
+    pub generated_at: DateTime<Utc>,
+
+    /// One entry per saved search
+    pub searches: Vec<PortfolioEntry>,
+}
+
+/// Whether a saved search has stored results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortfolioStatus {
+    /// Summarized from its latest stored run
+    Ok,
+
+    /// No run has been stored yet
+    NeverRun,
+}
+
+/// Summary of one saved search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioEntry {
+    /// Saved search phrase ID
+    pub phrase_id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Search query
+    pub query: String,
+
+    /// Whether there is a stored run to summarize
+    pub status: PortfolioStatus,
+
+    /// When the latest stored run happened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+
+    /// Listings in the latest run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_count: Option<usize>,
+
+    /// Cheapest comparable listing in the latest run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cheapest: Option<CheapestListing>,
+
+    /// Accepted Best Offers in the latest run, with their average discount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_offers: Option<OfferStats>,
+
+    /// Direction of the median landed price over the last 30 days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend: Option<PriceTrend>,
+
+    /// Listings first seen in the last 7 days
+    pub new_this_week: usize,
+
+    /// Fired alerts not yet acknowledged
+    pub alerts_pending: usize,
+
+    /// Why refreshing the search failed, if it was asked to and did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_error: Option<String>,
+}
+
+/// Which way a price moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    /// Rose by more than the flat threshold
+    Up,
+
+    /// Fell by more than the flat threshold
+    Down,
+
+    /// Within the flat threshold either way
+    Flat,
+}
+
+/// Change of the median landed price between the first and latest run of a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceTrend {
+    /// Which way the median moved
+    pub direction: TrendDirection,
+
+    /// Median landed price of the first run in the window
+    pub from: f64,
+
+    /// Median landed price of the latest run
+    pub to: f64,
+
+    /// `to - from` as a percentage of `from`
+    pub change_percent: f64,
+
+    /// Currency of both medians
+    pub currency: String,
+
+    /// Runs the trend was drawn from
+    pub runs: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_run_roundtrip() {
+        let run = SearchRun {
+            phrase_id: "leica-m6".to_string(),
+            ran_at: Utc::now(),
+            result_count: 2,
+            currency: Some("USD".to_string()),
+            median_landed: Some(2100.0),
+            cheapest: Some(CheapestListing {
+                item_id: "1".to_string(),
+                title: "Leica M6".to_string(),
+                landed_price: 1900.0,
+                currency: "USD".to_string(),
+                listing_url: "https://ebay.com/itm/1".to_string(),
+            }),
+            new_listings: 1,
+            accepted_offers: Some(OfferStats {
+                accepted: 3,
+                hidden: 1,
+                average_discount_percent: Some(12.5),
+            }),
+        };
+
+        let json = serde_json::to_string(&run).unwrap();
+        assert_eq!(serde_json::from_str::<SearchRun>(&json).unwrap(), run);
+    }
+
+    #[test]
+    fn test_status_serialization() {
+        assert_eq!(
+            serde_json::to_value(PortfolioStatus::NeverRun).unwrap(),
+            "never_run"
+        );
+        assert_eq!(serde_json::to_value(TrendDirection::Down).unwrap(), "down");
+    }
+}
@@ -2,10 +2,10 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
-use super::listing::EbayListing;
+use super::listing::{EbayListing, ListingLabel};
 
 /// Saved search phrase
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,10 +57,14 @@ pub struct SearchFilters {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub condition: Option<Vec<String>>,
 
-    /// Buying format (auction, buy_it_now, classified_ads)
+    /// Buying format (Auction, BuyItNow, BestOffer)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub buying_format: Option<Vec<String>>,
 
+    /// Search sold listings instead of active ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sold: Option<bool>,
+
     /// Location/shipping
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
@@ -75,6 +79,10 @@ pub struct SearchFilters {
     /// Item specifics
     #[serde(skip_serializing_if = "Option::is_none")]
     pub item_specifics: Option<HashMap<String, String>>,
+
+    /// Listing labels to keep (applied after classification, not sent to eBay)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<ListingLabel>>,
 }
 
 /// Sort order options
@@ -119,6 +127,36 @@ pub struct ShippingOptions {
     pub international: bool,
 }
 
+/// Which results pages a search collects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPaging {
+    /// Page to start from when there is no cursor
+    pub page: usize,
+
+    /// Most pages to fetch in this call
+    pub pages: usize,
+
+    /// Continue from where an earlier call stopped; overrides `page`
+    pub cursor: Option<String>,
+}
+
+impl Default for SearchPaging {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            pages: 1,
+            cursor: None,
+        }
+    }
+}
+
+impl SearchPaging {
+    /// Whether this is a plain first-page search
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Search results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResults {
@@ -131,21 +169,72 @@ pub struct SearchResults {
     /// Result listings
     pub items: Vec<EbayListing>,
 
-    /// Total result count
+    /// Total result count (eBay's estimate when the page shows one)
     pub total_count: usize,
 
-    /// Current page
+    /// Current page (the first one collected, for multi-page searches)
     pub page: usize,
 
     /// Total pages
     pub total_pages: usize,
 
+    /// Pages collected into `items`
+    #[serde(default)]
+    pub pages_fetched: usize,
+
+    /// Pass back as `cursor` to continue with the following pages; absent
+    /// once the end of the results is reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+
+    /// Problems that cut the search short, such as a page failing to load
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+
     /// Search timestamp
     pub searched_at: DateTime<Utc>,
 
     /// Time taken to scrape
     #[serde(with = "duration_serde")]
     pub duration: Duration,
+
+    /// Price statistics for each listing label present in `items`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub price_stats: BTreeMap<ListingLabel, PriceStats>,
+}
+
+/// Price statistics over a group of listings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceStats {
+    /// Number of listings priced
+    pub count: usize,
+    /// Currency of the prices
+    pub currency: String,
+    /// Lowest price
+    pub min: f64,
+    /// Highest price
+    pub max: f64,
+    /// Mean price
+    pub mean: f64,
+    /// Median price
+    pub median: f64,
+    /// Sales by accepted Best Offer among the listings, if there were any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_offers: Option<OfferStats>,
+}
+
+/// How listings sold by accepted Best Offer went
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfferStats {
+    /// Sales by accepted offer at a price eBay shows
+    pub accepted: usize,
+    /// Sales by accepted offer at a price eBay hides; left out of the
+    /// price statistics, since only the asking price is known
+    pub hidden: usize,
+    /// Mean percentage taken off the asking price, over the shown sales
+    /// whose asking price could be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub average_discount_percent: Option<f64>,
 }
 
 /// Search history entry
@@ -415,6 +504,10 @@ mod tests {
             total_pages: 3,
             searched_at: Utc::now(),
             duration: Duration::from_millis(1500),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: Default::default(),
         };
 
         let json = serde_json::to_string(&results).unwrap();
@@ -435,6 +528,7 @@ mod tests {
             item_id: "123456".to_string(),
             title: "Test Item".to_string(),
             price: Price::usd(99.99),
+            price_max: None,
             shipping: Some(Price::usd(5.00)),
             condition: "New".to_string(),
             format: BuyingFormat::BuyItNow,
@@ -450,6 +544,8 @@ mod tests {
             time_left: None,
             free_shipping: false,
             returns_accepted: true,
+            label: None,
+            best_offer: None,
         };
 
         let results = SearchResults {
@@ -461,6 +557,10 @@ mod tests {
             total_pages: 1,
             searched_at: Utc::now(),
             duration: Duration::from_millis(500),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: Default::default(),
         };
 
         let json = serde_json::to_string(&results).unwrap();
@@ -470,6 +570,39 @@ mod tests {
         assert_eq!(deserialized.items[0].item_id, "123456");
     }
 
+    #[test]
+    fn test_search_results_paging_fields_default() {
+        let json = r#"{
+            "query": "test",
+            "filters": {},
+            "items": [],
+            "total_count": 0,
+            "page": 1,
+            "total_pages": 0,
+            "searched_at": "2025-11-14T00:00:00Z",
+            "duration": 10
+        }"#;
+        let results: SearchResults = serde_json::from_str(json).unwrap();
+
+        assert_eq!(results.pages_fetched, 0);
+        assert!(results.cursor.is_none());
+        assert!(results.warnings.is_empty());
+
+        let json = serde_json::to_string(&results).unwrap();
+        assert!(!json.contains("cursor"));
+        assert!(!json.contains("warnings"));
+    }
+
+    #[test]
+    fn test_search_paging_default() {
+        assert!(SearchPaging::default().is_default());
+        assert!(!SearchPaging {
+            pages: 3,
+            ..Default::default()
+        }
+        .is_default());
+    }
+
     #[test]
     fn test_duration_serialization() {
         let results = SearchResults {
@@ -481,6 +614,10 @@ mod tests {
             total_pages: 0,
             searched_at: Utc::now(),
             duration: Duration::from_millis(2500),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: Default::default(),
         };
 
         let json = serde_json::to_string(&results).unwrap();
@@ -521,6 +658,8 @@ mod tests {
             shipping: Some(ShippingOptions::default()),
             sort_by: Some(SortOrder::PriceLowest),
             item_specifics: None,
+            labels: Some(vec![ListingLabel::Working]),
+            sold: None,
         };
 
         let debug_str = format!("{:?}", filters);
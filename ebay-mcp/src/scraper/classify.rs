@@ -0,0 +1,456 @@
+//! Listing classifier: what a listing actually sells
+//!
+//! Search results for "iPhone 12" mix working phones with parts listings,
+//! multi-phone lots and cases. Each listing is scored from keyword rules on
+//! its title and condition, and labelled `working` unless another label
+//! clearly wins. Rules come from per-language keyword files plus any extra
+//! keywords in the `[classifier]` config section.
+
+use crate::error::{EbayMcpError, Result};
+use crate::models::{ClassifierConfig, EbayListing, KeywordRule, ListingLabel, MatchField};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Keyword files compiled into the binary, by language
+const BUILTIN_KEYWORDS: &[(&str, &str)] = &[
+    ("en", include_str!("../../config/keywords/en.toml")),
+    ("de", include_str!("../../config/keywords/de.toml")),
+];
+
+/// Contents of a `<language>.toml` keyword file
+#[derive(Debug, Deserialize)]
+struct KeywordFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<KeywordRule>,
+}
+
+/// A single keyword, normalized and with its final weight
+#[derive(Debug, Clone)]
+struct Keyword {
+    label: ListingLabel,
+    field: MatchField,
+    weight: f64,
+    /// Normalized phrase padded with spaces, for whole-word matching
+    pattern: String,
+}
+
+/// Labels listings from their title and condition
+#[derive(Debug, Clone)]
+pub struct ListingClassifier {
+    keywords: Vec<Keyword>,
+    threshold: f64,
+}
+
+impl ListingClassifier {
+    /// Build a classifier from config, loading its keyword files
+    ///
+    /// A language's file is read from `keywords_dir` when present there and
+    /// taken from the built-in set otherwise.
+    pub fn from_config(config: &ClassifierConfig) -> Result<Self> {
+        let mut classifier = Self {
+            keywords: Vec::new(),
+            threshold: config.threshold,
+        };
+
+        for language in &config.languages {
+            for rule in load_keyword_file(language, config.keywords_dir.as_deref())? {
+                classifier.add_rule(&rule, config)?;
+            }
+        }
+
+        for rule in &config.keywords {
+            classifier.add_rule(rule, config)?;
+        }
+
+        Ok(classifier)
+    }
+
+    fn add_rule(&mut self, rule: &KeywordRule, config: &ClassifierConfig) -> Result<()> {
+        let multiplier = match rule.field {
+            MatchField::Title => config.title_weight,
+            MatchField::Condition => config.condition_weight,
+        };
+        let weight = rule.weight * multiplier;
+
+        if !weight.is_finite() {
+            return Err(EbayMcpError::Config(format!(
+                "Keyword rule for '{}' has an invalid weight",
+                rule.label
+            )));
+        }
+
+        for keyword in &rule.keywords {
+            let normalized = normalize(keyword);
+            if normalized.trim().is_empty() {
+                return Err(EbayMcpError::Config(format!(
+                    "Keyword rule for '{}' has an empty keyword",
+                    rule.label
+                )));
+            }
+
+            self.keywords.push(Keyword {
+                label: rule.label,
+                field: rule.field,
+                weight,
+                pattern: normalized,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Score every label for a title and condition
+    pub fn scores(&self, title: &str, condition: &str) -> [(ListingLabel, f64); 4] {
+        let title = normalize(title);
+        let condition = normalize(condition);
+        let mut scores = ListingLabel::ALL.map(|label| (label, 0.0));
+
+        for keyword in &self.keywords {
+            let text = match keyword.field {
+                MatchField::Title => &title,
+                MatchField::Condition => &condition,
+            };
+
+            if text.contains(&keyword.pattern) {
+                scores[label_index(keyword.label)].1 += keyword.weight;
+            }
+        }
+
+        scores
+    }
+
+    /// Label a title and condition
+    ///
+    /// The highest-scoring label wins if it reaches the threshold and beats
+    /// the `working` score; anything less stays `working`.
+    pub fn classify(&self, title: &str, condition: &str) -> ListingLabel {
+        let scores = self.scores(title, condition);
+        let working = scores[label_index(ListingLabel::Working)].1;
+
+        scores
+            .iter()
+            .filter(|(label, score)| {
+                *label != ListingLabel::Working && *score >= self.threshold && *score > working
+            })
+            // Earlier labels win ties: parts beat bundles beat accessories
+            .fold(
+                None,
+                |best: Option<(ListingLabel, f64)>, &(label, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((label, score)),
+                },
+            )
+            .map_or(ListingLabel::Working, |(label, _)| label)
+    }
+
+    /// Set the label on each listing
+    pub fn label_listings(&self, listings: &mut [EbayListing]) {
+        for listing in listings {
+            listing.label = Some(self.classify(&listing.title, &listing.condition));
+        }
+    }
+}
+
+impl Default for ListingClassifier {
+    fn default() -> Self {
+        Self::from_config(&ClassifierConfig::default()).expect("built-in keyword files are valid")
+    }
+}
+
+/// Read the rules for a language, preferring `dir` over the built-in files
+fn load_keyword_file(language: &str, dir: Option<&Path>) -> Result<Vec<KeywordRule>> {
+    if let Some(path) = dir.map(|d| d.join(format!("{}.toml", language))) {
+        if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                EbayMcpError::Config(format!("Failed to read keyword file {:?}: {}", path, e))
+            })?;
+            return Ok(toml::from_str::<KeywordFile>(&content)?.rules);
+        }
+    }
+
+    let (_, content) = BUILTIN_KEYWORDS
+        .iter()
+        .find(|(lang, _)| *lang == language)
+        .ok_or_else(|| {
+            EbayMcpError::Config(format!("No keyword file for language '{}'", language))
+        })?;
+
+    Ok(toml::from_str::<KeywordFile>(content)?.rules)
+}
+
+/// Lower-case, turn punctuation into spaces and pad with spaces
+///
+/// Matching `" cracked "` inside `" iphone 12 cracked screen "` then only
+/// finds whole words, so "unlocked" never matches "locked".
+fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len() + 2);
+    normalized.push(' ');
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            normalized.push(c);
+        } else if !normalized.ends_with(' ') {
+            normalized.push(' ');
+        }
+    }
+
+    if !normalized.ends_with(' ') {
+        normalized.push(' ');
+    }
+    normalized
+}
+
+fn label_index(label: ListingLabel) -> usize {
+    ListingLabel::ALL
+        .iter()
+        .position(|l| *l == label)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BuyingFormat, Price, SellerInfo};
+
+    /// Labelled titles from real "iPhone 12"-style searches
+    const FIXTURE: &str = include_str!("../../tests/fixtures/labeled_titles.tsv");
+
+    /// Share of fixture titles the default rules must label correctly
+    const MIN_ACCURACY: f64 = 0.9;
+
+    fn fixture() -> Vec<(ListingLabel, &'static str, &'static str)> {
+        FIXTURE
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let label = fields.next().unwrap();
+                let condition = fields.next().unwrap();
+                let title = fields.next().unwrap();
+                let label = serde_json::from_value(serde_json::json!(label)).unwrap();
+                (label, condition, title)
+            })
+            .collect()
+    }
+
+    fn classifier(languages: &[&str]) -> ListingClassifier {
+        ListingClassifier::from_config(&ClassifierConfig {
+            languages: languages.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fixture_accuracy() {
+        let classifier = classifier(&["en", "de"]);
+        let fixture = fixture();
+        assert!(fixture.len() >= 100);
+
+        let misses: Vec<_> = fixture
+            .iter()
+            .filter_map(|(expected, condition, title)| {
+                let actual = classifier.classify(title, condition);
+                (actual != *expected).then(|| format!("{} -> {} ({})", title, actual, expected))
+            })
+            .collect();
+
+        let accuracy = 1.0 - misses.len() as f64 / fixture.len() as f64;
+        assert!(
+            accuracy >= MIN_ACCURACY,
+            "accuracy {:.2} below {:.2}:\n{}",
+            accuracy,
+            MIN_ACCURACY,
+            misses.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_fixture_covers_every_label() {
+        let fixture = fixture();
+        for label in ListingLabel::ALL {
+            assert!(
+                fixture.iter().filter(|(l, _, _)| *l == label).count() >= 10,
+                "too few '{}' titles",
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn test_condition_field_marks_parts() {
+        let classifier = classifier(&["en"]);
+        assert_eq!(
+            classifier.classify("Apple iPhone 12 64GB", "For parts or not working"),
+            ListingLabel::ForParts
+        );
+        assert_eq!(
+            classifier.classify("Apple iPhone 12 64GB", "Used"),
+            ListingLabel::Working
+        );
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let classifier = classifier(&["en"]);
+        // "locked" must not fire inside "unlocked"
+        assert_eq!(
+            classifier.classify("iPhone 12 Unlocked", "Used"),
+            ListingLabel::Working
+        );
+        assert_eq!(
+            classifier.classify("iPhone 12 Locked", "Used"),
+            ListingLabel::ForParts
+        );
+        // Punctuation separates words
+        assert_eq!(
+            classifier.classify("iPhone 12 - Parts/Repair", "Used"),
+            ListingLabel::ForParts
+        );
+    }
+
+    #[test]
+    fn test_working_evidence_outweighs_weak_signals() {
+        let classifier = classifier(&["en"]);
+        assert_eq!(
+            classifier.classify("iPhone 12 128GB with Case and Charger", "Used"),
+            ListingLabel::Working
+        );
+        assert_eq!(
+            classifier.classify("iPhone 12 not for parts, fully working", "Used"),
+            ListingLabel::Working
+        );
+    }
+
+    #[test]
+    fn test_weights_and_threshold_are_configurable() {
+        let title = "iPhone 12 Original Box Only";
+
+        let default = classifier(&["en"]);
+        assert_eq!(default.classify(title, "Used"), ListingLabel::AccessoryOnly);
+
+        let strict = ListingClassifier::from_config(&ClassifierConfig {
+            threshold: 10.0,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(strict.classify(title, "Used"), ListingLabel::Working);
+
+        let condition_only = ListingClassifier::from_config(&ClassifierConfig {
+            title_weight: 0.0,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            condition_only.classify("iPhone 12 cracked screen", "Used"),
+            ListingLabel::Working
+        );
+        assert_eq!(
+            condition_only.classify("iPhone 12", "For parts or not working"),
+            ListingLabel::ForParts
+        );
+    }
+
+    #[test]
+    fn test_custom_keywords_from_config() {
+        let title = "iPhone 12 needs new screen";
+        assert_eq!(
+            classifier(&["en"]).classify(title, "Used"),
+            ListingLabel::Working
+        );
+
+        let config: ClassifierConfig = toml::from_str(
+            r#"
+languages = ["en"]
+condition_weight = 1.0
+title_weight = 1.0
+threshold = 1.0
+
+[[keywords]]
+label = "for_parts"
+weight = 5.0
+keywords = ["needs new screen"]
+"#,
+        )
+        .unwrap();
+
+        let classifier = ListingClassifier::from_config(&config).unwrap();
+        assert_eq!(classifier.classify(title, "Used"), ListingLabel::ForParts);
+    }
+
+    #[test]
+    fn test_keywords_dir_overrides_builtin_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("en.toml"),
+            "[[rule]]\nlabel = \"bundle\"\nweight = 2.0\nkeywords = [\"multipack\"]\n",
+        )
+        .unwrap();
+
+        let classifier = ListingClassifier::from_config(&ClassifierConfig {
+            keywords_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            classifier.classify("Phone multipack", "Used"),
+            ListingLabel::Bundle
+        );
+        // The built-in English rules were replaced, not extended
+        assert_eq!(
+            classifier.classify("iPhone 12 for parts", "Used"),
+            ListingLabel::Working
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let unknown_language = ClassifierConfig {
+            languages: vec!["xx".to_string()],
+            ..Default::default()
+        };
+        assert!(ListingClassifier::from_config(&unknown_language).is_err());
+
+        let empty_keyword = ClassifierConfig {
+            keywords: vec![KeywordRule {
+                label: ListingLabel::Bundle,
+                field: MatchField::Title,
+                weight: 1.0,
+                keywords: vec![" - ".to_string()],
+            }],
+            ..Default::default()
+        };
+        assert!(ListingClassifier::from_config(&empty_keyword).is_err());
+    }
+
+    #[test]
+    fn test_label_listings() {
+        let classifier = ListingClassifier::default();
+        let listing = |title: &str| EbayListing {
+            item_id: "1".to_string(),
+            title: title.to_string(),
+            price: Price::usd(300.0),
+            price_max: None,
+            shipping: None,
+            condition: "Used".to_string(),
+            format: BuyingFormat::BuyItNow,
+            seller: SellerInfo::default(),
+            location: "USA".to_string(),
+            thumbnail_url: None,
+            listing_url: "https://ebay.com/itm/1".to_string(),
+            bids: None,
+            time_left: None,
+            free_shipping: false,
+            returns_accepted: false,
+            label: None,
+            best_offer: None,
+        };
+
+        let mut listings = vec![listing("iPhone 12 64GB"), listing("Lot of 5 iPhone 12")];
+        classifier.label_listings(&mut listings);
+
+        assert_eq!(listings[0].label, Some(ListingLabel::Working));
+        assert_eq!(listings[1].label, Some(ListingLabel::Bundle));
+    }
+}
@@ -2,10 +2,14 @@
 
 use crate::browser::AntiDetection;
 use crate::error::{EbayMcpError, Result};
-use crate::models::{BuyingFormat, EbayListing, Price, SearchFilters, SearchResults};
+use crate::models::{BuyingFormat, EbayListing, PageKind, Price, SearchFilters, SearchResults};
 #[cfg(test)]
 use crate::models::SortOrder;
+use crate::scraper::extract::{extract_page, ExtractedListing};
+use crate::scraper::parse::{self, Marketplace};
+use crate::storage::PageArchive;
 use chrono::Utc;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
@@ -25,13 +29,27 @@ pub struct ScraperConfig {
     pub screenshot_dir: Option<std::path::PathBuf>,
 }
 
+impl From<crate::models::ScraperConfig> for ScraperConfig {
+    fn from(config: crate::models::ScraperConfig) -> Self {
+        Self {
+            base_url: config.base_url,
+            max_retries: config.max_retries,
+            screenshot_on_error: config.screenshot_on_error,
+            screenshot_dir: Some(config.screenshot_dir),
+        }
+    }
+}
+
 /// eBay scraper
 pub struct EbayScraper {
     /// Scraper configuration
     config: ScraperConfig,
 
     /// Anti-detection handler
-    anti_detection: AntiDetection,
+    pub(crate) anti_detection: AntiDetection,
+
+    /// Page archive (None when archiving is disabled)
+    archive: Option<Arc<PageArchive>>,
 }
 
 impl EbayScraper {
@@ -40,7 +58,40 @@ impl EbayScraper {
         Self {
             config,
             anti_detection,
+            archive: None,
+        }
+    }
+
+    /// Archive every processed page
+    pub fn with_archive(mut self, archive: Arc<PageArchive>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Extract listings from a loaded page, archiving it if enabled
+    pub async fn process_page(
+        &self,
+        kind: PageKind,
+        url: &str,
+        query: Option<&str>,
+        html: &str,
+    ) -> Result<Vec<ExtractedListing>> {
+        let listings = extract_page(kind, url, html);
+        debug!("Extracted {} listings from {}", listings.len(), url);
+
+        if let Some(ref archive) = self.archive {
+            let extracted = serde_json::to_value(&listings)?;
+
+            // Archiving is best effort and must never fail a search
+            if let Err(e) = archive
+                .archive_page(kind, url, query, html, &extracted)
+                .await
+            {
+                warn!("Failed to archive page {}: {}", url, e);
+            }
         }
+
+        Ok(listings)
     }
 
     /// Execute search with retry logic
@@ -118,7 +169,8 @@ impl EbayScraper {
         // TODO: When we can build with headless_chrome:
         // 1. Navigate to URL
         // 2. Wait for results to load
-        // 3. Extract listings
+        // 3. Extract listings from the page source via process_page
+        //    (which also archives it when archiving is enabled)
         // 4. Handle errors and CAPTCHAs
 
         // For now, return stub results
@@ -131,6 +183,10 @@ impl EbayScraper {
             total_pages: 0,
             searched_at: Utc::now(),
             duration: start.elapsed(),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: Default::default(),
         };
 
         Ok(results)
@@ -174,6 +230,7 @@ impl EbayScraper {
                 match format.as_str() {
                     "Auction" => url.push_str("&LH_Auction=1"),
                     "BuyItNow" => url.push_str("&LH_BIN=1"),
+                    "BestOffer" => url.push_str("&LH_BO=1"),
                     _ => {}
                 }
             }
@@ -188,6 +245,10 @@ impl EbayScraper {
             }
         }
 
+        if filters.sold == Some(true) {
+            url.push_str("&LH_Sold=1&LH_Complete=1");
+        }
+
         if let Some(ref sort_order) = filters.sort_by {
             url.push_str(&format!("&_sop={}", sort_order.to_ebay_param()));
         }
@@ -256,22 +317,14 @@ fn condition_to_code(condition: &str) -> &str {
     }
 }
 
-/// Parse price from eBay text
+/// Parse price from eBay text as shown on ebay.com
+///
+/// For other marketplaces, and to find out why text didn't parse, use
+/// [`parse::parse_price`](crate::scraper::parse::parse_price).
 pub fn parse_price(price_text: &str) -> Option<Price> {
-    // Remove currency symbols and commas
-    let cleaned = price_text
-        .replace('$', "")
-        .replace(',', "")
-        .replace("USD", "")
-        .trim()
-        .to_string();
-
-    // Extract numeric part
-    if let Ok(amount) = cleaned.parse::<f64>() {
-        Some(Price::usd(amount))
-    } else {
-        None
-    }
+    parse::parse_price(price_text, Marketplace::Us)
+        .ok()
+        .map(|parsed| parsed.min)
 }
 
 /// Parse buying format from eBay listing
@@ -464,6 +517,29 @@ mod tests {
         assert!(url.contains("LH_Auction=1"));
     }
 
+    #[test]
+    fn test_build_search_url_sold_best_offers() {
+        let config = ScraperConfig {
+            base_url: "https://www.ebay.com".to_string(),
+            max_retries: 3,
+            screenshot_on_error: false,
+            screenshot_dir: None,
+        };
+
+        let anti_det = AntiDetection::new(vec![], false, Duration::from_millis(0), Duration::from_millis(0));
+        let scraper = EbayScraper::new(config, anti_det);
+
+        let mut filters = SearchFilters::default();
+        filters.buying_format = Some(vec!["BestOffer".to_string()]);
+        assert!(!scraper.build_search_url("item", &filters, 1).contains("LH_Sold"));
+
+        filters.sold = Some(true);
+        let url = scraper.build_search_url("item", &filters, 1);
+
+        assert!(url.contains("LH_BO=1"));
+        assert!(url.contains("LH_Sold=1&LH_Complete=1"));
+    }
+
     #[test]
     fn test_build_search_url_with_free_shipping() {
         let config = ScraperConfig {
@@ -1222,4 +1298,64 @@ mod tests {
         let price = parse_price("$0009.99");
         assert_eq!(price.unwrap().amount, 9.99);
     }
+
+    #[tokio::test]
+    async fn test_process_page_archives_snapshot() {
+        use crate::models::{ArchiveConfig, SnapshotQuery};
+        use crate::storage::Database;
+        use tokio::sync::Mutex;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_file = tempfile::NamedTempFile::new().unwrap();
+        let database = Arc::new(Mutex::new(Database::new(db_file.path()).unwrap()));
+        let archive_config = ArchiveConfig {
+            enabled: true,
+            dir: dir.path().to_path_buf(),
+            retention_days: 0,
+            max_size_mb: 0,
+        };
+        let archive = Arc::new(PageArchive::new(&archive_config, database).unwrap());
+
+        let config = ScraperConfig {
+            base_url: "https://www.ebay.com".to_string(),
+            max_retries: 1,
+            screenshot_on_error: false,
+            screenshot_dir: None,
+        };
+        let anti_detection = AntiDetection::new(
+            vec!["test-agent".to_string()],
+            false,
+            Duration::from_millis(0),
+            Duration::from_millis(0),
+        );
+        let scraper = EbayScraper::new(config, anti_detection).with_archive(archive.clone());
+
+        let html = r#"<li class="s-item">
+<a class="s-item__link" href="https://www.ebay.com/itm/101"><span class="s-item__title">Leica M6</span></a>
+<span class="s-item__price">$2,500.00</span></li>"#;
+        let listings = scraper
+            .process_page(
+                PageKind::Results,
+                "https://www.ebay.com/sch/i.html?_nkw=leica",
+                Some("leica"),
+                html,
+            )
+            .await
+            .unwrap();
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].price, Some(2500.0));
+
+        let snapshots = archive
+            .query(&SnapshotQuery {
+                phrase: Some("leica".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(
+            snapshots[0].extracted,
+            serde_json::to_value(&listings).unwrap()
+        );
+    }
 }
@@ -0,0 +1,577 @@
+//! Field extraction from raw eBay page HTML
+//!
+//! Works on the page source rather than the live DOM so the same code can
+//! run against pages captured in the archive.
+
+use crate::models::{BestOffer, PageKind};
+use crate::scraper::parse::{parse_price, Marketplace};
+use serde::{Deserialize, Serialize};
+
+/// Fields extracted from a single listing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedListing {
+    /// eBay item ID
+    pub item_id: String,
+
+    /// Listing title
+    pub title: String,
+
+    /// Price amount, or the low end of a price range
+    pub price: Option<f64>,
+
+    /// High end of a price range, e.g. "$20.00 to $35.00"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_max: Option<f64>,
+
+    /// Currency of the price, when it isn't US dollars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Why the price text shown could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_error: Option<String>,
+
+    /// Listing URL
+    pub listing_url: String,
+
+    /// Best Offer status noted under the price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_offer: Option<BestOffer>,
+}
+
+impl ExtractedListing {
+    /// Listing with the price read from `price_text`, if one is shown
+    fn new(
+        item_id: String,
+        title: String,
+        listing_url: String,
+        price_text: Option<&str>,
+        marketplace: Marketplace,
+    ) -> Self {
+        let mut listing = Self {
+            item_id,
+            title,
+            price: None,
+            price_max: None,
+            currency: None,
+            price_error: None,
+            listing_url,
+            best_offer: None,
+        };
+
+        match price_text.map(|text| parse_price(text, marketplace)) {
+            Some(Ok(parsed)) => {
+                listing.price = Some(parsed.min.amount);
+                listing.price_max = parsed.max.map(|max| max.amount);
+                listing.currency = (parsed.min.currency != "USD").then_some(parsed.min.currency);
+            }
+            Some(Err(e)) => listing.price_error = Some(e.to_string()),
+            None => {}
+        }
+
+        listing
+    }
+}
+
+/// Class of the divider eBay puts before "results matching fewer words";
+/// nothing after it matches the query
+const END_OF_RESULTS_CLASS: &str = "srp-river-answer--REWRITE_START";
+
+/// How marketplaces word Best Offer under a listing's price, with the word
+/// added once an offer was accepted ("or Best Offer", "Best offer accepted")
+const BEST_OFFER_PHRASES: [(&str, &str); 3] = [
+    ("best offer", "accepted"),
+    ("preisvorschlag", "angenommen"),
+    ("offre directe", "accept"),
+];
+
+/// Where a results page sits in the search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageInfo {
+    /// eBay's estimate of matching results, from the count heading
+    pub total_estimate: Option<usize>,
+
+    /// What follows this page
+    pub next: NextPage,
+}
+
+/// What follows a results page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NextPage {
+    /// The pagination control links to the next page
+    Link(String),
+
+    /// This is the last page of matching results
+    End,
+
+    /// The page has no pagination control to tell
+    Unknown,
+}
+
+/// Extract listings from a page of the given kind
+///
+/// Prices are read in the format of the marketplace `url` belongs to,
+/// falling back to ebay.com's.
+pub fn extract_page(kind: PageKind, url: &str, html: &str) -> Vec<ExtractedListing> {
+    match kind {
+        PageKind::Results => extract_results(html, Marketplace::from_url(url).unwrap_or_default()),
+        PageKind::Detail => extract_detail(url, html).into_iter().collect(),
+    }
+}
+
+/// Extract listings from a search results page
+///
+/// Listings past the end-of-results divider are left out.
+pub fn extract_results(html: &str, marketplace: Marketplace) -> Vec<ExtractedListing> {
+    let html = &html[..end_of_results(html).unwrap_or(html.len())];
+
+    split_by_class(html, "s-item")
+        .into_iter()
+        .filter_map(|block| {
+            let listing_url = attr_of_class(block, "s-item__link", "href")?;
+            let item_id = item_id_from_url(&listing_url)?;
+            let title = text_of_class(block, "s-item__title")?;
+            let price = text_of_class(block, "s-item__price");
+
+            let mut listing =
+                ExtractedListing::new(item_id, title, listing_url, price.as_deref(), marketplace);
+            listing.best_offer = best_offer(block, price.as_deref(), marketplace);
+            Some(listing)
+        })
+        .collect()
+}
+
+/// Best Offer status of a results page listing
+///
+/// Active listings taking offers say "or Best Offer". Sold ones say "Best
+/// offer accepted" and strike through the asking price next to the price
+/// sold for; when eBay hides that price, the struck-through one is the only
+/// price shown.
+fn best_offer(
+    block: &str,
+    price_text: Option<&str>,
+    marketplace: Marketplace,
+) -> Option<BestOffer> {
+    let options = text_of_class(block, "s-item__purchase-options")?.to_lowercase();
+    let (_, accepted) = BEST_OFFER_PHRASES
+        .iter()
+        .find(|(phrase, _)| options.contains(phrase))?;
+    if !options.contains(accepted) {
+        return Some(BestOffer::Open);
+    }
+
+    let struck = text_of_class(block, "STRIKETHROUGH");
+    if struck.is_none() || struck.as_deref() == price_text {
+        return Some(BestOffer::AcceptedHidden);
+    }
+
+    Some(BestOffer::Accepted {
+        original_price: struck
+            .and_then(|text| parse_price(&text, marketplace).ok())
+            .map(|parsed| parsed.min),
+    })
+}
+
+/// Extract the result count and next-page link of a search results page
+pub fn extract_page_info(html: &str) -> PageInfo {
+    let total_estimate =
+        text_of_class(html, "srp-controls__count-heading").and_then(|text| result_count(&text));
+
+    let next = if end_of_results(html).is_some() {
+        NextPage::End
+    } else if let Some(tag) = tags_with_class(html, "pagination__next").next() {
+        let disabled = attr_value(tag, "aria-disabled").as_deref() == Some("true");
+        match attr_value(tag, "href") {
+            Some(href) if !disabled => NextPage::Link(decode_entities(&href)),
+            _ => NextPage::End,
+        }
+    } else if tags_with_class(html, "pagination").next().is_some() {
+        // Pagination without a next link: this is the last page
+        NextPage::End
+    } else {
+        NextPage::Unknown
+    };
+
+    PageInfo {
+        total_estimate,
+        next,
+    }
+}
+
+/// Extract the listing from an item detail page
+pub fn extract_detail(url: &str, html: &str) -> Option<ExtractedListing> {
+    let item_id = item_id_from_url(url)?;
+    let title = text_of_class(html, "x-item-title__mainTitle")?;
+    let price = text_of_class(html, "x-price-primary");
+
+    Some(ExtractedListing::new(
+        item_id,
+        title,
+        url.to_string(),
+        price.as_deref(),
+        Marketplace::from_url(url).unwrap_or_default(),
+    ))
+}
+
+/// Leading number of a count heading such as "1,234 results for canon" or
+/// "1.234 Ergebnisse"
+fn result_count(text: &str) -> Option<usize> {
+    let digits: String = text
+        .split_whitespace()
+        .next()?
+        .chars()
+        .filter(|c| !matches!(c, ',' | '.' | '+'))
+        .collect();
+
+    digits.parse().ok()
+}
+
+/// Byte offset of the end-of-results divider, if the page has one
+fn end_of_results(html: &str) -> Option<usize> {
+    tag_starts(html).find(|&start| has_class(open_tag(&html[start..]), END_OF_RESULTS_CLASS))
+}
+
+/// Item ID from a `/itm/<id>` URL
+fn item_id_from_url(url: &str) -> Option<String> {
+    let rest = &url[url.find("/itm/")? + "/itm/".len()..];
+    let id: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+    (!id.is_empty()).then_some(id)
+}
+
+/// Split HTML into chunks, each starting at an element with exactly `class`
+fn split_by_class<'a>(html: &'a str, class: &str) -> Vec<&'a str> {
+    let starts: Vec<usize> = tag_starts(html)
+        .filter(|&start| has_class(open_tag(&html[start..]), class))
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(html.len());
+            &html[start..end]
+        })
+        .collect()
+}
+
+/// Text content of the first element with `class`
+fn text_of_class(html: &str, class: &str) -> Option<String> {
+    let start = tag_starts(html).find(|&start| has_class(open_tag(&html[start..]), class))?;
+    let tag = open_tag(&html[start..]);
+    let name: String = tag[1..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+
+    let body = &html[start + tag.len()..];
+    let end = body.find(&format!("</{}", name)).unwrap_or(body.len());
+    let text = decode_entities(&strip_tags(&body[..end]));
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Attribute value of the first element with `class`
+fn attr_of_class(html: &str, class: &str, attr: &str) -> Option<String> {
+    tags_with_class(html, class)
+        .next()
+        .and_then(|tag| attr_value(tag, attr))
+        .map(|value| decode_entities(&value))
+}
+
+/// Opening tags of every element with `class`
+fn tags_with_class<'a>(html: &'a str, class: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    tag_starts(html)
+        .map(|start| open_tag(&html[start..]))
+        .filter(move |tag| has_class(tag, class))
+}
+
+/// Byte offsets of every opening tag
+fn tag_starts(html: &str) -> impl Iterator<Item = usize> + '_ {
+    html.match_indices('<').map(|(i, _)| i).filter(move |&i| {
+        html[i + 1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+    })
+}
+
+/// Opening tag at the start of `html`, including the closing `>`
+fn open_tag(html: &str) -> &str {
+    html.find('>').map_or(html, |end| &html[..=end])
+}
+
+/// Whether an opening tag has `class` in its class list
+fn has_class(tag: &str, class: &str) -> bool {
+    attr_value(tag, "class").is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+}
+
+/// Value of a quoted attribute in an opening tag
+fn attr_value(tag: &str, attr: &str) -> Option<String> {
+    let needle = format!(" {}=", attr);
+    let rest = &tag[tag.find(&needle)? + needle.len()..];
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &rest[1..];
+
+    Some(rest[..rest.find(quote)?].to_string())
+}
+
+/// Remove nested tags, keeping their text
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Decode the handful of entities eBay uses in titles and URLs
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Price;
+
+    const RESULTS_HTML: &str = r#"
+<ul class="srp-results">
+  <li class="s-item s-item__pl-on-bottom">
+    <a class="s-item__link" href="https://www.ebay.com/itm/123456789?hash=abc&amp;var=1">
+      <div class="s-item__title"><span role="heading">Canon AE-1 Program &amp; 50mm</span></div>
+    </a>
+    <span class="s-item__price">$149.99</span>
+  </li>
+  <li class="s-item">
+    <a class="s-item__link" href="https://www.ebay.com/itm/987654321">
+      <div class="s-item__title">Canon A-1 Body</div>
+    </a>
+    <span class="s-item__price">$1,020.00</span>
+  </li>
+  <li class="s-item">
+    <div class="s-item__title">Shop on eBay</div>
+  </li>
+</ul>
+"#;
+
+    #[test]
+    fn test_extract_results() {
+        let listings = extract_results(RESULTS_HTML, Marketplace::Us);
+
+        assert_eq!(listings.len(), 2);
+        assert_eq!(listings[0].item_id, "123456789");
+        assert_eq!(listings[0].title, "Canon AE-1 Program & 50mm");
+        assert_eq!(listings[0].price, Some(149.99));
+        assert_eq!(
+            listings[0].listing_url,
+            "https://www.ebay.com/itm/123456789?hash=abc&var=1"
+        );
+        assert_eq!(listings[1].price, Some(1020.0));
+    }
+
+    #[test]
+    fn test_extract_detail() {
+        let html = r#"
+<h1 class="x-item-title__mainTitle"><span class="ux-textspans">Nikon FM2 Body</span></h1>
+<div class="x-price-primary"><span>US $325.00</span></div>
+"#;
+        let listing = extract_detail("https://www.ebay.com/itm/55512345", html).unwrap();
+
+        assert_eq!(listing.item_id, "55512345");
+        assert_eq!(listing.title, "Nikon FM2 Body");
+        assert_eq!(listing.price, Some(325.0));
+
+        assert!(extract_detail("https://www.ebay.com/sch/i.html", html).is_none());
+    }
+
+    #[test]
+    fn test_extract_localized_prices() {
+        let html = r#"
+<li class="s-item">
+  <a class="s-item__link" href="https://www.ebay.de/itm/111"></a>
+  <div class="s-item__title">Canon AE-1</div>
+  <span class="s-item__price">EUR 1.234,56</span>
+</li>
+<li class="s-item">
+  <a class="s-item__link" href="https://www.ebay.de/itm/222"></a>
+  <div class="s-item__title">Objektivdeckel</div>
+  <span class="s-item__price">EUR 20,00 bis EUR 35,00</span>
+</li>
+<li class="s-item">
+  <a class="s-item__link" href="https://www.ebay.de/itm/333"></a>
+  <div class="s-item__title">Blitz</div>
+  <span class="s-item__price">Preis auf Anfrage</span>
+</li>
+"#;
+        let listings = extract_page(
+            PageKind::Results,
+            "https://www.ebay.de/sch/i.html?_nkw=canon",
+            html,
+        );
+
+        assert_eq!(listings[0].price, Some(1234.56));
+        assert_eq!(listings[0].currency.as_deref(), Some("EUR"));
+        assert_eq!(listings[1].price, Some(20.0));
+        assert_eq!(listings[1].price_max, Some(35.0));
+        assert_eq!(listings[2].price, None);
+        assert_eq!(
+            listings[2].price_error.as_deref(),
+            Some("unexpected 'Preis' in 'Preis auf Anfrage'")
+        );
+
+        // US listings serialize as before
+        let json = serde_json::to_value(extract_results(RESULTS_HTML, Marketplace::Us)).unwrap();
+        assert!(json[0].get("currency").is_none());
+        assert!(json[0].get("price_error").is_none());
+    }
+
+    #[test]
+    fn test_extract_best_offers_from_sold_results() {
+        let html = include_str!("../../tests/fixtures/sold_results_offers.html");
+        let listings = extract_results(html, Marketplace::Us);
+        let offers: Vec<_> = listings
+            .iter()
+            .map(|l| (l.price, l.best_offer.clone()))
+            .collect();
+
+        assert_eq!(
+            offers,
+            vec![
+                (
+                    Some(160.0),
+                    Some(BestOffer::Accepted {
+                        original_price: Some(Price::usd(200.0))
+                    })
+                ),
+                (
+                    Some(135.0),
+                    Some(BestOffer::Accepted {
+                        original_price: Some(Price::usd(150.0))
+                    })
+                ),
+                // Only the struck-through asking price is shown
+                (Some(240.0), Some(BestOffer::AcceptedHidden)),
+                (Some(95.0), None),
+                (Some(120.0), Some(BestOffer::Open)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_open_best_offer() {
+        let html = r#"
+<li class="s-item">
+  <a class="s-item__link" href="https://www.ebay.de/itm/111"></a>
+  <div class="s-item__title">Canon AE-1</div>
+  <span class="s-item__price">EUR 150,00</span>
+  <span class="s-item__purchase-options">oder Preisvorschlag</span>
+</li>
+<li class="s-item">
+  <a class="s-item__link" href="https://www.ebay.de/itm/222"></a>
+  <div class="s-item__title">Canon A-1</div>
+  <span class="s-item__price"><span class="POSITIVE">EUR 90,00</span></span>
+  <span class="s-item__trending-price"><span class="STRIKETHROUGH">EUR 100,00</span></span>
+  <span class="s-item__purchase-options">Preisvorschlag angenommen</span>
+</li>
+"#;
+        let listings = extract_results(html, Marketplace::De);
+
+        assert_eq!(listings[0].best_offer, Some(BestOffer::Open));
+        assert_eq!(
+            listings[1].best_offer,
+            Some(BestOffer::Accepted {
+                original_price: Some(Price::new(100.0, "EUR"))
+            })
+        );
+        // Listings without offers serialize as before
+        let json = serde_json::to_value(extract_results(RESULTS_HTML, Marketplace::Us)).unwrap();
+        assert!(json[0].get("best_offer").is_none());
+    }
+
+    #[test]
+    fn test_class_match_is_exact() {
+        assert!(has_class(
+            r#"<li class="s-item s-item__pl-on-bottom">"#,
+            "s-item"
+        ));
+        assert!(!has_class(r#"<div class="s-item__title">"#, "s-item"));
+    }
+
+    #[test]
+    fn test_results_stop_at_end_divider() {
+        let html = format!(
+            r#"{}<li class="srp-river-answer srp-river-answer--REWRITE_START">Results matching fewer words</li>
+  <li class="s-item">
+    <a class="s-item__link" href="https://www.ebay.com/itm/111222333"></a>
+    <div class="s-item__title">Canon Lens Cap</div>
+  </li>"#,
+            RESULTS_HTML
+        );
+
+        assert_eq!(extract_results(&html, Marketplace::Us).len(), 2);
+        assert_eq!(extract_page_info(&html).next, NextPage::End);
+    }
+
+    #[test]
+    fn test_extract_page_info() {
+        let html = r#"
+<h1 class="srp-controls__count-heading"><span class="BOLD">1,234</span> results for <span class="BOLD">canon</span></h1>
+<nav class="pagination" role="navigation">
+  <a class="pagination__previous" href="https://www.ebay.com/sch/i.html?_nkw=canon&amp;_pgn=1">Previous</a>
+  <a class="pagination__next icon-link" href="https://www.ebay.com/sch/i.html?_nkw=canon&amp;_pgn=3">Next</a>
+</nav>
+"#;
+        let info = extract_page_info(html);
+
+        assert_eq!(info.total_estimate, Some(1234));
+        assert_eq!(
+            info.next,
+            NextPage::Link("https://www.ebay.com/sch/i.html?_nkw=canon&_pgn=3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_page_info_last_page() {
+        let disabled = r##"<nav class="pagination"><a class="pagination__next" aria-disabled="true" href="#">Next</a></nav>"##;
+        assert_eq!(extract_page_info(disabled).next, NextPage::End);
+
+        let missing =
+            r#"<nav class="pagination"><a class="pagination__item" href="/p1">1</a></nav>"#;
+        assert_eq!(extract_page_info(missing).next, NextPage::End);
+
+        let info = extract_page_info(RESULTS_HTML);
+        assert_eq!(info.next, NextPage::Unknown);
+        assert_eq!(info.total_estimate, None);
+    }
+
+    #[test]
+    fn test_result_count_formats() {
+        assert_eq!(result_count("1,234 results for canon"), Some(1234));
+        assert_eq!(result_count("1.234 Ergebnisse"), Some(1234));
+        assert_eq!(result_count("60+ results"), Some(60));
+        assert_eq!(result_count("No exact matches found"), None);
+    }
+
+    #[test]
+    fn test_extract_page_empty() {
+        assert!(extract_page(PageKind::Results, "", "<html></html>").is_empty());
+    }
+}
@@ -1,5 +1,15 @@
 //! Web scraping module for eBay
 
+pub mod classify;
 pub mod ebay;
+pub mod extract;
+pub mod paginate;
+pub mod parse;
+pub mod replay;
 
+pub use classify::ListingClassifier;
 pub use ebay::{parse_buying_format, parse_price, EbayScraper, ScraperConfig};
+pub use extract::{ExtractedListing, NextPage, PageInfo};
+pub use paginate::{PageSource, PagedListings, SearchCursor, MAX_PAGES_PER_CALL};
+pub use parse::{Marketplace, ParseError, ParsedPrice};
+pub use replay::{diff_extractions, replay_snapshot, FieldDiff, ReplayReport};
@@ -0,0 +1,687 @@
+//! Multi-page search collection
+//!
+//! Follows eBay's next-page links from one results page to the next until a
+//! page budget or the end of results. Listings can shift across page
+//! boundaries while paging, so results are deduplicated by item ID. Where a
+//! search stopped is captured in a [`SearchCursor`] so a later call can carry
+//! on from there.
+
+use crate::error::{EbayMcpError, Result};
+use crate::models::{
+    BuyingFormat, EbayListing, PageKind, Price, SearchFilters, SearchPaging, SearchResults,
+    SellerInfo,
+};
+use crate::scraper::ebay::EbayScraper;
+use crate::scraper::extract::{extract_page_info, ExtractedListing, NextPage};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Most pages a single call may fetch
+pub const MAX_PAGES_PER_CALL: usize = 10;
+
+/// Loads the HTML of a search results page
+pub trait PageSource {
+    /// Fetch the page at `url`
+    fn fetch(&self, url: &str) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// Where a paginated search stopped, for continuing it in a later call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    /// Fingerprint of the query and filters the cursor belongs to
+    search: String,
+
+    /// Number of the next page
+    pub page: usize,
+
+    /// URL of the next page
+    pub url: String,
+
+    /// Item IDs on the last page collected, so listings pushed onto the
+    /// next page aren't returned twice
+    seen: Vec<String>,
+
+    /// eBay's result count estimate when the cursor was made
+    total_estimate: Option<usize>,
+}
+
+impl SearchCursor {
+    /// Opaque token handed to clients
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        json.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Read a token produced by [`SearchCursor::encode`]
+    pub fn decode(token: &str) -> Result<Self> {
+        let invalid = || EbayMcpError::InvalidInput("Malformed search cursor".to_string());
+
+        if token.len() % 2 != 0 || !token.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+
+    /// Fail unless the cursor came from the same query and filters
+    fn check(&self, query: &str, filters: &SearchFilters) -> Result<()> {
+        if self.search != search_fingerprint(query, filters) {
+            return Err(EbayMcpError::InvalidInput(
+                "Cursor belongs to a different query or filters".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Short hash identifying a query and its filters
+fn search_fingerprint(query: &str, filters: &SearchFilters) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(query.as_bytes());
+
+    if let Ok(filters_json) = serde_json::to_string(filters) {
+        hasher.update(filters_json.as_bytes());
+    }
+
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Listings collected from one or more results pages
+#[derive(Debug, Clone, PartialEq)]
+pub struct PagedListings {
+    /// Unique listings in page order
+    pub listings: Vec<ExtractedListing>,
+
+    /// Number of the first page collected
+    pub first_page: usize,
+
+    /// Pages collected
+    pub pages_fetched: usize,
+
+    /// Listings on the first page collected, before deduplication
+    pub page_size: usize,
+
+    /// Listings dropped because an earlier page already had them
+    pub duplicates: usize,
+
+    /// eBay's result count estimate
+    pub total_estimate: Option<usize>,
+
+    /// Where to continue, unless the end of results was reached
+    pub cursor: Option<SearchCursor>,
+
+    /// Problems that cut collection short
+    pub warnings: Vec<String>,
+}
+
+impl PagedListings {
+    /// Number of the last page collected
+    pub fn last_page(&self) -> usize {
+        self.first_page + self.pages_fetched.saturating_sub(1)
+    }
+
+    /// Turn the collected listings into search results
+    ///
+    /// Listings without a readable price are left out, since they would skew
+    /// the price statistics. Those whose price text didn't parse are counted
+    /// in a warning.
+    pub fn into_results(
+        self,
+        query: &str,
+        filters: &SearchFilters,
+        duration: Duration,
+    ) -> SearchResults {
+        let last_page = self.last_page();
+        let total_pages = match (&self.cursor, self.total_estimate) {
+            (None, _) => last_page,
+            (Some(_), Some(total)) if self.page_size > 0 => {
+                total.div_ceil(self.page_size).max(last_page + 1)
+            }
+            (Some(_), _) => last_page + 1,
+        };
+
+        let mut unreadable: Vec<String> = Vec::new();
+        let items: Vec<EbayListing> = self
+            .listings
+            .into_iter()
+            .filter_map(|extracted| {
+                if let Some(error) = &extracted.price_error {
+                    unreadable.push(error.clone());
+                }
+                listing_from_extracted(extracted)
+            })
+            .collect();
+
+        let mut warnings = self.warnings;
+        if let Some(example) = unreadable.first() {
+            warn!(
+                "Left out {} listings with unreadable prices for '{}'",
+                unreadable.len(),
+                query
+            );
+            warnings.push(format!(
+                "{} listing(s) left out because their price could not be read (first: {})",
+                unreadable.len(),
+                example
+            ));
+        }
+
+        SearchResults {
+            query: query.to_string(),
+            filters: filters.clone(),
+            total_count: self.total_estimate.unwrap_or(items.len()),
+            items,
+            page: self.first_page,
+            total_pages,
+            searched_at: Utc::now(),
+            duration,
+            pages_fetched: self.pages_fetched,
+            cursor: self.cursor.map(|cursor| cursor.encode()),
+            warnings,
+            price_stats: Default::default(),
+        }
+    }
+}
+
+/// Full listing from the fields a results page shows
+fn listing_from_extracted(extracted: ExtractedListing) -> Option<EbayListing> {
+    let currency = extracted.currency.as_deref().unwrap_or("USD");
+
+    Some(EbayListing {
+        price: Price::new(extracted.price?, currency),
+        price_max: extracted.price_max.map(|max| Price::new(max, currency)),
+        item_id: extracted.item_id,
+        title: extracted.title,
+        shipping: None,
+        condition: String::new(),
+        // Results pages only tell Best Offer listings apart
+        format: if extracted.best_offer.is_some() {
+            BuyingFormat::BestOffer
+        } else {
+            BuyingFormat::BuyItNow
+        },
+        seller: SellerInfo::default(),
+        location: String::new(),
+        thumbnail_url: None,
+        listing_url: extracted.listing_url,
+        bids: None,
+        time_left: None,
+        free_shipping: false,
+        returns_accepted: false,
+        label: None,
+        best_offer: extracted.best_offer,
+    })
+}
+
+impl EbayScraper {
+    /// Collect listings from consecutive results pages
+    ///
+    /// Starts at `paging.page`, or where `paging.cursor` left off, and stops
+    /// after `paging.pages` pages (at most [`MAX_PAGES_PER_CALL`]) or at the
+    /// end of results. If a page fails after others succeeded, the listings
+    /// so far are returned with a warning and a cursor pointing at the
+    /// failed page; a failure on the first page is an error.
+    pub async fn collect_pages<S: PageSource>(
+        &self,
+        source: &S,
+        query: &str,
+        filters: &SearchFilters,
+        paging: &SearchPaging,
+    ) -> Result<PagedListings> {
+        let budget = paging.pages.clamp(1, MAX_PAGES_PER_CALL);
+
+        let (mut page, mut url, mut last_ids, mut total_estimate) = match &paging.cursor {
+            Some(token) => {
+                let cursor = SearchCursor::decode(token)?;
+                cursor.check(query, filters)?;
+                debug!("Continuing '{}' from page {}", query, cursor.page);
+                (cursor.page, cursor.url, cursor.seen, cursor.total_estimate)
+            }
+            None => {
+                let page = paging.page.max(1);
+                (
+                    page,
+                    self.build_search_url(query, filters, page),
+                    Vec::new(),
+                    None,
+                )
+            }
+        };
+
+        let mut seen: HashSet<String> = last_ids.iter().cloned().collect();
+        let mut collected = PagedListings {
+            listings: Vec::new(),
+            first_page: page,
+            pages_fetched: 0,
+            page_size: 0,
+            duplicates: 0,
+            total_estimate,
+            cursor: None,
+            warnings: Vec::new(),
+        };
+
+        for fetched in 0..budget {
+            if fetched > 0 {
+                self.anti_detection.random_delay().await;
+            }
+
+            let html = match source.fetch(&url).await {
+                Ok(html) => html,
+                Err(e) if collected.pages_fetched == 0 => return Err(e),
+                Err(e) => {
+                    warn!("Page {} of '{}' failed: {}", page, query, e);
+                    collected.warnings.push(format!(
+                        "Page {} failed to load ({}); returning pages {}-{}. \
+                         Continue with the cursor to retry it.",
+                        page,
+                        e,
+                        collected.first_page,
+                        collected.last_page()
+                    ));
+                    collected.cursor = Some(SearchCursor {
+                        search: search_fingerprint(query, filters),
+                        page,
+                        url,
+                        seen: last_ids,
+                        total_estimate,
+                    });
+                    return Ok(collected);
+                }
+            };
+
+            let listings = self
+                .process_page(PageKind::Results, &url, Some(query), &html)
+                .await?;
+            let info = extract_page_info(&html);
+
+            total_estimate = info.total_estimate.or(total_estimate);
+            collected.total_estimate = total_estimate;
+            if collected.pages_fetched == 0 {
+                collected.page_size = listings.len();
+            }
+            collected.pages_fetched += 1;
+
+            last_ids = listings.iter().map(|l| l.item_id.clone()).collect();
+            for listing in listings {
+                if seen.insert(listing.item_id.clone()) {
+                    collected.listings.push(listing);
+                } else {
+                    collected.duplicates += 1;
+                }
+            }
+
+            url = match info.next {
+                NextPage::Link(next) => next,
+                NextPage::Unknown if !last_ids.is_empty() => {
+                    self.build_search_url(query, filters, page + 1)
+                }
+                NextPage::End | NextPage::Unknown => {
+                    debug!(
+                        "Reached the end of results for '{}' on page {}",
+                        query, page
+                    );
+                    return Ok(collected);
+                }
+            };
+            page += 1;
+        }
+
+        info!(
+            "Collected {} listings from {} pages for '{}'",
+            collected.listings.len(),
+            collected.pages_fetched,
+            query
+        );
+
+        collected.cursor = Some(SearchCursor {
+            search: search_fingerprint(query, filters),
+            page,
+            url,
+            seen: last_ids,
+            total_estimate,
+        });
+        Ok(collected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::AntiDetection;
+    use crate::scraper::ScraperConfig;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const PAGE_1: &str = include_str!("../../tests/fixtures/search_results_p1.html");
+    const PAGE_2: &str = include_str!("../../tests/fixtures/search_results_p2.html");
+
+    const PAGE_1_URL: &str = "https://www.ebay.com/sch/i.html?_nkw=canon%20ae-1";
+    const PAGE_2_URL: &str = "https://www.ebay.com/sch/i.html?_nkw=canon%20ae-1&_pgn=2";
+
+    /// Serves fixture pages by URL and records what was requested
+    struct FixtureSource {
+        pages: HashMap<String, &'static str>,
+        requested: Mutex<Vec<String>>,
+    }
+
+    impl FixtureSource {
+        fn new(pages: &[(&str, &'static str)]) -> Self {
+            Self {
+                pages: pages
+                    .iter()
+                    .map(|(url, html)| (url.to_string(), *html))
+                    .collect(),
+                requested: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn two_pages() -> Self {
+            Self::new(&[(PAGE_1_URL, PAGE_1), (PAGE_2_URL, PAGE_2)])
+        }
+
+        fn requested(&self) -> Vec<String> {
+            self.requested.lock().unwrap().clone()
+        }
+    }
+
+    impl PageSource for FixtureSource {
+        fn fetch(&self, url: &str) -> impl Future<Output = Result<String>> + Send {
+            self.requested.lock().unwrap().push(url.to_string());
+            let page = self
+                .pages
+                .get(url)
+                .map(|html| html.to_string())
+                .ok_or_else(|| EbayMcpError::Network(format!("No fixture for {}", url)));
+            async move { page }
+        }
+    }
+
+    fn scraper() -> EbayScraper {
+        let config = ScraperConfig {
+            base_url: "https://www.ebay.com".to_string(),
+            max_retries: 1,
+            screenshot_on_error: false,
+            screenshot_dir: None,
+        };
+        let anti_detection = AntiDetection::new(
+            vec![],
+            false,
+            Duration::from_millis(0),
+            Duration::from_millis(0),
+        );
+
+        EbayScraper::new(config, anti_detection)
+    }
+
+    fn pages(pages: usize) -> SearchPaging {
+        SearchPaging {
+            pages,
+            ..Default::default()
+        }
+    }
+
+    fn ids(collected: &PagedListings) -> Vec<&str> {
+        collected
+            .listings
+            .iter()
+            .map(|l| l.item_id.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_collects_pages_until_end_of_results() {
+        let source = FixtureSource::two_pages();
+        let filters = SearchFilters::default();
+
+        let collected = scraper()
+            .collect_pages(&source, "canon ae-1", &filters, &pages(5))
+            .await
+            .unwrap();
+
+        assert_eq!(source.requested(), vec![PAGE_1_URL, PAGE_2_URL]);
+        assert_eq!(collected.pages_fetched, 2);
+        assert_eq!(
+            ids(&collected),
+            vec![
+                "204512345001",
+                "204512345002",
+                "204512345003",
+                "204512345004",
+                "204512345005"
+            ]
+        );
+        // The listing pushed onto page 2 is only returned once
+        assert_eq!(collected.duplicates, 1);
+        assert_eq!(collected.total_estimate, Some(5));
+        assert!(collected.cursor.is_none());
+        assert!(collected.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_continues_where_budget_ran_out() {
+        let source = FixtureSource::two_pages();
+        let filters = SearchFilters::default();
+        let scraper = scraper();
+
+        let first = scraper
+            .collect_pages(&source, "canon ae-1", &filters, &pages(1))
+            .await
+            .unwrap();
+        assert_eq!(ids(&first).len(), 3);
+
+        let cursor = first.cursor.clone().unwrap();
+        assert_eq!(cursor.page, 2);
+        assert_eq!(cursor.url, PAGE_2_URL);
+
+        let results = first.into_results("canon ae-1", &filters, Duration::ZERO);
+        assert_eq!(results.page, 1);
+        assert_eq!(results.total_pages, 2);
+        assert_eq!(results.total_count, 5);
+        let token = results.cursor.unwrap();
+
+        let second = scraper
+            .collect_pages(
+                &source,
+                "canon ae-1",
+                &filters,
+                &SearchPaging {
+                    cursor: Some(token),
+                    ..pages(1)
+                },
+            )
+            .await
+            .unwrap();
+
+        // Deduplication carries across calls through the cursor
+        assert_eq!(second.first_page, 2);
+        assert_eq!(ids(&second), vec!["204512345004", "204512345005"]);
+        assert_eq!(second.duplicates, 1);
+        assert!(second.cursor.is_none());
+        assert_eq!(source.requested(), vec![PAGE_1_URL, PAGE_2_URL]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_page_returns_partial_results() {
+        let source = FixtureSource::new(&[(PAGE_1_URL, PAGE_1)]);
+        let filters = SearchFilters::default();
+
+        let collected = scraper()
+            .collect_pages(&source, "canon ae-1", &filters, &pages(3))
+            .await
+            .unwrap();
+
+        assert_eq!(collected.pages_fetched, 1);
+        assert_eq!(ids(&collected).len(), 3);
+        assert_eq!(collected.warnings.len(), 1);
+        assert!(collected.warnings[0].contains("Page 2 failed"));
+
+        // The cursor retries the failed page
+        let cursor = collected.cursor.unwrap();
+        assert_eq!((cursor.page, cursor.url.as_str()), (2, PAGE_2_URL));
+    }
+
+    #[tokio::test]
+    async fn test_first_page_failure_is_an_error() {
+        let source = FixtureSource::new(&[]);
+
+        let result = scraper()
+            .collect_pages(&source, "canon ae-1", &SearchFilters::default(), &pages(3))
+            .await;
+
+        assert!(matches!(result, Err(EbayMcpError::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn test_cursor_rejected_for_other_search() {
+        let source = FixtureSource::two_pages();
+        let scraper = scraper();
+        let filters = SearchFilters::default();
+
+        let first = scraper
+            .collect_pages(&source, "canon ae-1", &filters, &pages(1))
+            .await
+            .unwrap();
+        let token = first.cursor.unwrap().encode();
+
+        let other = SearchPaging {
+            cursor: Some(token),
+            ..pages(1)
+        };
+        let result = scraper
+            .collect_pages(&source, "nikon fm2", &filters, &other)
+            .await;
+        assert!(matches!(result, Err(EbayMcpError::InvalidInput(_))));
+
+        let garbage = SearchPaging {
+            cursor: Some("not-a-cursor".to_string()),
+            ..pages(1)
+        };
+        let result = scraper
+            .collect_pages(&source, "canon ae-1", &filters, &garbage)
+            .await;
+        assert!(matches!(result, Err(EbayMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_constructs_next_url_without_pagination_control() {
+        let without_nav = &PAGE_1[..PAGE_1.find("<nav").unwrap()];
+        let source = FixtureSource::new(&[(PAGE_1_URL, without_nav), (PAGE_2_URL, PAGE_2)]);
+
+        let collected = scraper()
+            .collect_pages(&source, "canon ae-1", &SearchFilters::default(), &pages(2))
+            .await
+            .unwrap();
+
+        assert_eq!(source.requested(), vec![PAGE_1_URL, PAGE_2_URL]);
+        assert_eq!(collected.pages_fetched, 2);
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = SearchCursor {
+            search: search_fingerprint("q", &SearchFilters::default()),
+            page: 3,
+            url: "https://www.ebay.com/sch/i.html?_nkw=q&_pgn=3".to_string(),
+            seen: vec!["1".to_string(), "2".to_string()],
+            total_estimate: Some(240),
+        };
+
+        assert_eq!(SearchCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(SearchCursor::decode("abc").is_err());
+        assert!(SearchCursor::decode("zz").is_err());
+    }
+
+    #[test]
+    fn test_unpriced_listings_are_left_out_of_results() {
+        let collected = PagedListings {
+            listings: vec![
+                ExtractedListing {
+                    item_id: "1".to_string(),
+                    title: "Priced".to_string(),
+                    price: Some(10.0),
+                    price_max: None,
+                    currency: None,
+                    price_error: None,
+                    listing_url: "https://www.ebay.com/itm/1".to_string(),
+                    best_offer: None,
+                },
+                ExtractedListing {
+                    item_id: "2".to_string(),
+                    title: "See price".to_string(),
+                    price: None,
+                    price_max: None,
+                    currency: None,
+                    price_error: None,
+                    listing_url: "https://www.ebay.com/itm/2".to_string(),
+                    best_offer: None,
+                },
+            ],
+            first_page: 1,
+            pages_fetched: 1,
+            page_size: 2,
+            duplicates: 0,
+            total_estimate: None,
+            cursor: None,
+            warnings: vec![],
+        };
+
+        let results = collected.into_results("q", &SearchFilters::default(), Duration::ZERO);
+        assert_eq!(results.items.len(), 1);
+        assert_eq!(results.items[0].price.amount, 10.0);
+        assert_eq!(results.total_count, 1);
+        assert_eq!(results.total_pages, 1);
+        assert!(results.cursor.is_none());
+        assert!(results.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_prices_are_counted() {
+        let listing = |id: &str, price: Option<f64>, error: Option<&str>| ExtractedListing {
+            item_id: id.to_string(),
+            title: format!("Item {}", id),
+            price,
+            price_max: price.map(|p| p * 2.0),
+            currency: Some("EUR".to_string()),
+            price_error: error.map(str::to_string),
+            listing_url: format!("https://www.ebay.de/itm/{}", id),
+            best_offer: None,
+        };
+        let collected = PagedListings {
+            listings: vec![
+                listing("1", Some(10.0), None),
+                listing("2", None, Some("malformed amount 'EUR 1.23,45'")),
+                listing("3", None, Some("no amount in 'EUR'")),
+            ],
+            first_page: 1,
+            pages_fetched: 1,
+            page_size: 3,
+            duplicates: 0,
+            total_estimate: None,
+            cursor: None,
+            warnings: vec![],
+        };
+
+        let results = collected.into_results("q", &SearchFilters::default(), Duration::ZERO);
+        assert_eq!(results.items.len(), 1);
+        assert_eq!(results.items[0].price.currency, "EUR");
+        assert_eq!(results.items[0].price_max.as_ref().unwrap().amount, 20.0);
+        assert_eq!(
+            results.warnings,
+            vec![
+                "2 listing(s) left out because their price could not be read \
+                 (first: malformed amount 'EUR 1.23,45')"
+            ]
+        );
+    }
+}
@@ -0,0 +1,1107 @@
+//! Locale-aware parsing of the prices, dates and times eBay shows
+//!
+//! Every marketplace formats numbers and dates its own way: ebay.de writes
+//! "EUR 1.234,56" and "12. Mai 2024" where ebay.com writes "US $1,234.56"
+//! and "May 12, 2024". The parsers take the marketplace a page came from and
+//! return an error instead of a guess, so callers can count and report what
+//! they could not read rather than carry on with a made-up value.
+
+use crate::models::Price;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// An eBay site with its own language and number format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Marketplace {
+    /// ebay.com
+    #[default]
+    Us,
+    /// ebay.co.uk
+    Uk,
+    /// ebay.de
+    De,
+    /// ebay.fr
+    Fr,
+}
+
+impl Marketplace {
+    /// All supported marketplaces
+    pub const ALL: [Marketplace; 4] = [
+        Marketplace::Us,
+        Marketplace::Uk,
+        Marketplace::De,
+        Marketplace::Fr,
+    ];
+
+    /// Marketplace a page URL belongs to, if it is a supported eBay site
+    pub fn from_url(url: &str) -> Option<Self> {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host = rest
+            .split(['/', '?', '#'])
+            .next()?
+            .split(':')
+            .next()?
+            .to_ascii_lowercase();
+
+        Self::ALL.into_iter().find(|marketplace| {
+            let domain = marketplace.domain();
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+    }
+
+    /// Domain of the marketplace's site
+    pub fn domain(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "ebay.com",
+            Marketplace::Uk => "ebay.co.uk",
+            Marketplace::De => "ebay.de",
+            Marketplace::Fr => "ebay.fr",
+        }
+    }
+
+    /// Currency prices are in when the text names none
+    pub fn currency(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "USD",
+            Marketplace::Uk => "GBP",
+            Marketplace::De | Marketplace::Fr => "EUR",
+        }
+    }
+
+    /// Character separating whole units from cents
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Marketplace::Us | Marketplace::Uk => '.',
+            Marketplace::De | Marketplace::Fr => ',',
+        }
+    }
+
+    /// Month names and abbreviations, lowercase and without trailing dots
+    fn months(&self) -> &'static [(&'static str, u32)] {
+        match self {
+            Marketplace::Us | Marketplace::Uk => ENGLISH_MONTHS,
+            Marketplace::De => GERMAN_MONTHS,
+            Marketplace::Fr => FRENCH_MONTHS,
+        }
+    }
+
+    /// Time units and their length in seconds
+    fn time_units(&self) -> &'static [(&'static str, i64)] {
+        match self {
+            Marketplace::Us | Marketplace::Uk => ENGLISH_UNITS,
+            Marketplace::De => GERMAN_UNITS,
+            Marketplace::Fr => FRENCH_UNITS,
+        }
+    }
+
+    /// Words around a time left that carry no value, e.g. "left"
+    fn time_fillers(&self) -> &'static [&'static str] {
+        match self {
+            Marketplace::Us | Marketplace::Uk => {
+                &["left", "time", "remaining", "ends", "ending", "in"]
+            }
+            Marketplace::De => &["noch", "restzeit", "endet", "in"],
+            Marketplace::Fr => &["il", "reste", "temps", "restant", "fin", "dans"],
+        }
+    }
+}
+
+const ENGLISH_MONTHS: &[(&str, u32)] = &[
+    ("january", 1),
+    ("jan", 1),
+    ("february", 2),
+    ("feb", 2),
+    ("march", 3),
+    ("mar", 3),
+    ("april", 4),
+    ("apr", 4),
+    ("may", 5),
+    ("june", 6),
+    ("jun", 6),
+    ("july", 7),
+    ("jul", 7),
+    ("august", 8),
+    ("aug", 8),
+    ("september", 9),
+    ("sept", 9),
+    ("sep", 9),
+    ("october", 10),
+    ("oct", 10),
+    ("november", 11),
+    ("nov", 11),
+    ("december", 12),
+    ("dec", 12),
+];
+
+const GERMAN_MONTHS: &[(&str, u32)] = &[
+    ("januar", 1),
+    ("jänner", 1),
+    ("jan", 1),
+    ("februar", 2),
+    ("feb", 2),
+    ("märz", 3),
+    ("maerz", 3),
+    ("mär", 3),
+    ("mrz", 3),
+    ("april", 4),
+    ("apr", 4),
+    ("mai", 5),
+    ("juni", 6),
+    ("jun", 6),
+    ("juli", 7),
+    ("jul", 7),
+    ("august", 8),
+    ("aug", 8),
+    ("september", 9),
+    ("sept", 9),
+    ("sep", 9),
+    ("oktober", 10),
+    ("okt", 10),
+    ("november", 11),
+    ("nov", 11),
+    ("dezember", 12),
+    ("dez", 12),
+];
+
+// "mar." is mardi (Tuesday), so March is only recognized as "mars"
+const FRENCH_MONTHS: &[(&str, u32)] = &[
+    ("janvier", 1),
+    ("janv", 1),
+    ("février", 2),
+    ("fevrier", 2),
+    ("févr", 2),
+    ("fevr", 2),
+    ("mars", 3),
+    ("avril", 4),
+    ("avr", 4),
+    ("mai", 5),
+    ("juin", 6),
+    ("juillet", 7),
+    ("juil", 7),
+    ("août", 8),
+    ("aout", 8),
+    ("septembre", 9),
+    ("sept", 9),
+    ("octobre", 10),
+    ("oct", 10),
+    ("novembre", 11),
+    ("nov", 11),
+    ("décembre", 12),
+    ("decembre", 12),
+    ("déc", 12),
+    ("dec", 12),
+];
+
+const DAY: i64 = 24 * 60 * 60;
+const HOUR: i64 = 60 * 60;
+const MINUTE: i64 = 60;
+
+const ENGLISH_UNITS: &[(&str, i64)] = &[
+    ("d", DAY),
+    ("day", DAY),
+    ("days", DAY),
+    ("h", HOUR),
+    ("hr", HOUR),
+    ("hrs", HOUR),
+    ("hour", HOUR),
+    ("hours", HOUR),
+    ("m", MINUTE),
+    ("min", MINUTE),
+    ("mins", MINUTE),
+    ("minute", MINUTE),
+    ("minutes", MINUTE),
+    ("s", 1),
+    ("sec", 1),
+    ("secs", 1),
+    ("second", 1),
+    ("seconds", 1),
+];
+
+const GERMAN_UNITS: &[(&str, i64)] = &[
+    ("t", DAY),
+    ("tag", DAY),
+    ("tage", DAY),
+    ("tagen", DAY),
+    ("std", HOUR),
+    ("h", HOUR),
+    ("stunde", HOUR),
+    ("stunden", HOUR),
+    ("m", MINUTE),
+    ("min", MINUTE),
+    ("minute", MINUTE),
+    ("minuten", MINUTE),
+    ("s", 1),
+    ("sek", 1),
+    ("sekunde", 1),
+    ("sekunden", 1),
+];
+
+const FRENCH_UNITS: &[(&str, i64)] = &[
+    ("j", DAY),
+    ("jour", DAY),
+    ("jours", DAY),
+    ("h", HOUR),
+    ("heure", HOUR),
+    ("heures", HOUR),
+    ("m", MINUTE),
+    ("mn", MINUTE),
+    ("min", MINUTE),
+    ("minute", MINUTE),
+    ("minutes", MINUTE),
+    ("s", 1),
+    ("sec", 1),
+    ("seconde", 1),
+    ("secondes", 1),
+];
+
+/// Currency markers, longest first so "US $" wins over "$"
+const CURRENCY_MARKERS: &[(&str, &str)] = &[
+    ("US $", "USD"),
+    ("US$", "USD"),
+    ("AU $", "AUD"),
+    ("AU$", "AUD"),
+    ("C $", "CAD"),
+    ("C$", "CAD"),
+    ("USD", "USD"),
+    ("GBP", "GBP"),
+    ("EUR", "EUR"),
+    ("CHF", "CHF"),
+    ("AUD", "AUD"),
+    ("CAD", "CAD"),
+    ("£", "GBP"),
+    ("€", "EUR"),
+    ("$", "USD"),
+];
+
+/// Words joining the two ends of a price range
+const RANGE_SEPARATORS: &[&str] = &[" to ", " bis ", " à ", " - ", " – ", " — "];
+
+/// Why a piece of page text could not be read
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Nothing but whitespace
+    #[error("empty text")]
+    Empty,
+
+    /// No digits where an amount was expected
+    #[error("no amount in '{0}'")]
+    NoAmount(String),
+
+    /// Digits that don't form a number in the marketplace's format
+    #[error("malformed amount '{0}'")]
+    MalformedAmount(String),
+
+    /// Text that has no place in what was being read
+    #[error("unexpected '{token}' in '{text}'")]
+    Unexpected {
+        /// The offending word
+        token: String,
+        /// The whole text
+        text: String,
+    },
+
+    /// The two ends of a price range are in different currencies
+    #[error("mixed currencies in '{0}'")]
+    MixedCurrencies(String),
+
+    /// A price range whose upper end is below its lower end
+    #[error("range ends below its start in '{0}'")]
+    InvertedRange(String),
+
+    /// No time units in a time left
+    #[error("no duration in '{0}'")]
+    NoDuration(String),
+
+    /// No recognizable day, month and year
+    #[error("no date in '{0}'")]
+    NoDate(String),
+
+    /// Day, month and year that don't make a calendar date
+    #[error("invalid date '{0}'")]
+    InvalidDate(String),
+}
+
+/// A price, or the two ends of a price range such as "$20.00 to $35.00"
+#[derive(Debug, Clone)]
+pub struct ParsedPrice {
+    /// The price, or the low end of a range
+    pub min: Price,
+
+    /// The high end of a range
+    pub max: Option<Price>,
+}
+
+/// Parse price text such as "US $1,234.56", "EUR 1.234,56" or
+/// "£20.00 to £35.00"
+///
+/// Without a currency marker the marketplace's currency is assumed. A
+/// parenthesized conversion such as "(approx. US $15.20)" is ignored.
+pub fn parse_price(text: &str, marketplace: Marketplace) -> Result<ParsedPrice, ParseError> {
+    let shown = text.split('(').next().unwrap_or_default().trim();
+    if shown.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let split = RANGE_SEPARATORS
+        .iter()
+        .filter_map(|separator| shown.find(separator).map(|at| (at, separator.len())))
+        .min();
+    let (low, high) = match split {
+        Some((at, len)) => (&shown[..at], Some(&shown[at + len..])),
+        None => (shown, None),
+    };
+
+    let (min, low_currency) = parse_amount(low, text, marketplace)?;
+    let Some(high) = high else {
+        let currency = low_currency.unwrap_or(marketplace.currency());
+        return Ok(ParsedPrice {
+            min: Price::new(min, currency),
+            max: None,
+        });
+    };
+
+    let (max, high_currency) = parse_amount(high, text, marketplace)?;
+    let currency = match (low_currency, high_currency) {
+        (Some(low), Some(high)) if low != high => {
+            return Err(ParseError::MixedCurrencies(text.to_string()))
+        }
+        (low, high) => low.or(high).unwrap_or(marketplace.currency()),
+    };
+    if max < min {
+        return Err(ParseError::InvertedRange(text.to_string()));
+    }
+
+    Ok(ParsedPrice {
+        min: Price::new(min, currency),
+        max: Some(Price::new(max, currency)),
+    })
+}
+
+/// One amount with its currency marker, if it has one
+fn parse_amount(
+    part: &str,
+    text: &str,
+    marketplace: Marketplace,
+) -> Result<(f64, Option<&'static str>), ParseError> {
+    let mut rest = part.to_string();
+    let mut currency = None;
+
+    for (marker, code) in CURRENCY_MARKERS {
+        if rest.contains(marker) {
+            if currency.is_some_and(|found| found != *code) {
+                return Err(ParseError::MixedCurrencies(text.to_string()));
+            }
+            currency = Some(*code);
+            rest = rest.replace(marker, " ");
+        }
+    }
+
+    if let Some(token) = rest
+        .split_whitespace()
+        .find(|token| token.chars().any(|c| c.is_alphabetic()))
+    {
+        return Err(ParseError::Unexpected {
+            token: token.to_string(),
+            text: text.to_string(),
+        });
+    }
+
+    // Spaces, including the no-break kinds, only ever group thousands
+    let number = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !number.chars().any(|c| c.is_ascii_digit()) {
+        return Err(ParseError::NoAmount(text.to_string()));
+    }
+
+    parse_number(&number, marketplace)
+        .map(|amount| (amount, currency))
+        .ok_or_else(|| ParseError::MalformedAmount(text.to_string()))
+}
+
+/// Number made of digits, a decimal separator and grouping separators
+///
+/// Spaces group thousands on every marketplace.
+/// When both "." and "," appear the last one is the decimal separator. When
+/// only one kind appears the marketplace decides, unless a grouping
+/// separator isn't followed by three digits and so can only be a decimal
+/// one: "12.50" reads as 12.5 even on ebay.de.
+fn parse_number(number: &str, marketplace: Marketplace) -> Option<f64> {
+    if !number
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | ' '))
+    {
+        return None;
+    }
+
+    let decimal = match (number.rfind('.'), number.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) | (None, Some(_)) => {
+            let separator = if number.contains('.') { '.' } else { ',' };
+            let count = number.matches(separator).count();
+            let after = number.len() - number.rfind(separator)? - 1;
+
+            if separator == marketplace.decimal_separator() {
+                (count == 1).then_some(separator)
+            } else if count == 1 && after != 3 {
+                Some(separator)
+            } else {
+                None
+            }
+        }
+        (None, None) => None,
+    };
+
+    let (whole, fraction) = match decimal {
+        Some(decimal) => {
+            let (whole, fraction) = number.rsplit_once(decimal)?;
+            if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            (whole, fraction)
+        }
+        None => (number, "0"),
+    };
+
+    let mut groups = whole.split(['.', ',', ' ']);
+    let first = groups.next()?;
+    let grouped = whole.contains(['.', ',', ' ']);
+    if grouped && !(1..=3).contains(&first.len()) {
+        return None;
+    }
+    if groups.any(|group| group.len() != 3) {
+        return None;
+    }
+
+    let digits: String = whole.chars().filter(|c| c.is_ascii_digit()).collect();
+    format!(
+        "{}.{}",
+        if digits.is_empty() { "0" } else { &digits },
+        fraction
+    )
+    .parse()
+    .ok()
+}
+
+/// Parse a time left such as "6d 3h left", "Noch 6T 3Std." or "6j 3h"
+pub fn parse_time_left(text: &str, marketplace: Marketplace) -> Result<Duration, ParseError> {
+    if text.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let lower = text.to_lowercase();
+    let tokens = split_digits_from_words(&lower);
+    let mut seconds = 0;
+    let mut parts = 0;
+    let mut pending: Option<i64> = None;
+
+    for token in tokens {
+        let word = token.trim_end_matches(['.', ':']);
+        if word.is_empty() {
+            continue;
+        }
+        if let Ok(value) = word.parse::<i64>() {
+            if pending.is_some() {
+                return Err(ParseError::Unexpected {
+                    token: token.to_string(),
+                    text: text.to_string(),
+                });
+            }
+            pending = Some(value);
+            continue;
+        }
+
+        match (pending.take(), unit_seconds(word, marketplace)) {
+            (Some(value), Some(unit)) => {
+                seconds += value * unit;
+                parts += 1;
+            }
+            (None, _) if marketplace.time_fillers().contains(&word) => {}
+            _ => {
+                return Err(ParseError::Unexpected {
+                    token: token.to_string(),
+                    text: text.to_string(),
+                })
+            }
+        }
+    }
+
+    if let Some(value) = pending {
+        return Err(ParseError::Unexpected {
+            token: value.to_string(),
+            text: text.to_string(),
+        });
+    }
+    if parts == 0 {
+        return Err(ParseError::NoDuration(text.to_string()));
+    }
+
+    Ok(Duration::seconds(seconds))
+}
+
+fn unit_seconds(word: &str, marketplace: Marketplace) -> Option<i64> {
+    marketplace
+        .time_units()
+        .iter()
+        .find(|(unit, _)| *unit == word)
+        .map(|(_, seconds)| *seconds)
+}
+
+/// Words with runs of digits split off, so "6d3h" reads as "6 d 3 h"
+fn split_digits_from_words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+
+    for word in text.split(|c: char| c.is_whitespace() || c == ',') {
+        let mut start = 0;
+        for (i, c) in word.char_indices().skip(1) {
+            let previous = word[..i].chars().next_back().unwrap_or(c);
+            if previous.is_ascii_digit() != c.is_ascii_digit() {
+                tokens.push(&word[start..i]);
+                start = i;
+            }
+        }
+        if start < word.len() {
+            tokens.push(&word[start..]);
+        }
+    }
+
+    tokens
+}
+
+/// Parse a date such as "Ended: 12. Mai 2024", "Sold Mar 3, 2024",
+/// "Vendu le 12 mai 2024" or "12.05.2024"
+///
+/// Labels, weekday names and times of day around the date are skipped.
+/// Numeric dates are month first on ebay.com and day first elsewhere.
+pub fn parse_date(text: &str, marketplace: Marketplace) -> Result<NaiveDate, ParseError> {
+    if text.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let lower = text.to_lowercase();
+    let mut numbers = Vec::new();
+    let mut months = Vec::new();
+    let mut numeric_dates = Vec::new();
+
+    for token in lower.split(|c: char| c.is_whitespace() || c == ',') {
+        let token = token
+            .trim_end_matches([':', '.', ')'])
+            .trim_start_matches('(');
+        if token.is_empty() || is_time_of_day(token) {
+            continue;
+        }
+
+        if token.chars().all(|c| c.is_ascii_digit()) {
+            numbers.push(token);
+        } else if token.chars().all(|c| c.is_alphabetic()) {
+            if let Some((_, month)) = marketplace.months().iter().find(|(name, _)| *name == token) {
+                months.push(*month);
+            }
+        } else if let Some(date) = numeric_date(token, marketplace) {
+            numeric_dates.push(date);
+        }
+    }
+
+    let invalid = || ParseError::InvalidDate(text.to_string());
+    match (months.as_slice(), numeric_dates.as_slice()) {
+        ([month], []) => {
+            let (years, days): (Vec<&str>, Vec<&str>) =
+                numbers.into_iter().partition(|number| number.len() == 4);
+            match (years.as_slice(), days.as_slice()) {
+                ([year], [day]) if day.len() <= 2 => NaiveDate::from_ymd_opt(
+                    year.parse().map_err(|_| invalid())?,
+                    *month,
+                    day.parse().map_err(|_| invalid())?,
+                )
+                .ok_or_else(invalid),
+                _ => Err(ParseError::NoDate(text.to_string())),
+            }
+        }
+        ([], [date]) if numbers.is_empty() => (*date).ok_or_else(invalid),
+        ([], []) => Err(ParseError::NoDate(text.to_string())),
+        _ => Err(invalid()),
+    }
+}
+
+/// "14:30", "14:30:05" or the French "14h30"
+fn is_time_of_day(token: &str) -> bool {
+    let separator = if token.contains(':') { ':' } else { 'h' };
+    let parts: Vec<&str> = token.split(separator).collect();
+
+    (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|part| (1..=2).contains(&part.len()) && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// A date written in digits, e.g. "12.05.2024", "05/12/24" or "2024-05-12"
+///
+/// `None` when the token isn't shaped like a date; `Some(None)` when it is
+/// but names no real day.
+fn numeric_date(token: &str, marketplace: Marketplace) -> Option<Option<NaiveDate>> {
+    let separator = ['.', '/', '-'].into_iter().find(|s| token.contains(*s))?;
+    let parts: Vec<&str> = token.split(separator).collect();
+    if parts.len() != 3
+        || parts
+            .iter()
+            .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    let (year, month, day) = if parts[0].len() == 4 {
+        (numbers[0], numbers[1], numbers[2])
+    } else if marketplace == Marketplace::Us {
+        (numbers[2], numbers[0], numbers[1])
+    } else {
+        (numbers[2], numbers[1], numbers[0])
+    };
+    let year = match parts[if parts[0].len() == 4 { 0 } else { 2 }].len() {
+        2 => 2000 + year,
+        4 => year,
+        _ => return None,
+    };
+
+    Some(NaiveDate::from_ymd_opt(year as i32, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expected reading of one price text: low end, high end, currency
+    type PriceCase = (&'static str, f64, Option<f64>, &'static str);
+
+    fn check_prices(marketplace: Marketplace, cases: &[PriceCase]) {
+        for (text, min, max, currency) in cases {
+            let parsed = parse_price(text, marketplace)
+                .unwrap_or_else(|e| panic!("{:?} '{}': {}", marketplace, text, e));
+
+            assert_eq!(parsed.min.amount, *min, "{:?} '{}'", marketplace, text);
+            assert_eq!(
+                parsed.max.as_ref().map(|p| p.amount),
+                *max,
+                "{:?} '{}'",
+                marketplace,
+                text
+            );
+            assert_eq!(
+                parsed.min.currency, *currency,
+                "{:?} '{}'",
+                marketplace, text
+            );
+        }
+    }
+
+    fn check_dates(marketplace: Marketplace, cases: &[(&str, (i32, u32, u32))]) {
+        for (text, (year, month, day)) in cases {
+            let parsed = parse_date(text, marketplace)
+                .unwrap_or_else(|e| panic!("{:?} '{}': {}", marketplace, text, e));
+            assert_eq!(
+                parsed,
+                NaiveDate::from_ymd_opt(*year, *month, *day).unwrap(),
+                "{:?} '{}'",
+                marketplace,
+                text
+            );
+        }
+    }
+
+    fn check_times(marketplace: Marketplace, cases: &[(&str, i64)]) {
+        for (text, seconds) in cases {
+            let parsed = parse_time_left(text, marketplace)
+                .unwrap_or_else(|e| panic!("{:?} '{}': {}", marketplace, text, e));
+            assert_eq!(
+                parsed.num_seconds(),
+                *seconds,
+                "{:?} '{}'",
+                marketplace,
+                text
+            );
+        }
+    }
+
+    fn check_failures(marketplace: Marketplace, prices: &[&str], dates: &[&str], times: &[&str]) {
+        for text in prices {
+            assert!(
+                parse_price(text, marketplace).is_err(),
+                "{:?} price '{}' should not parse",
+                marketplace,
+                text
+            );
+        }
+        for text in dates {
+            assert!(
+                parse_date(text, marketplace).is_err(),
+                "{:?} date '{}' should not parse",
+                marketplace,
+                text
+            );
+        }
+        for text in times {
+            assert!(
+                parse_time_left(text, marketplace).is_err(),
+                "{:?} time '{}' should not parse",
+                marketplace,
+                text
+            );
+        }
+    }
+
+    const D: i64 = DAY;
+    const H: i64 = HOUR;
+    const M: i64 = MINUTE;
+
+    #[test]
+    fn test_us_marketplace() {
+        check_prices(
+            Marketplace::Us,
+            &[
+                ("$149.99", 149.99, None, "USD"),
+                ("$1,020.00", 1020.0, None, "USD"),
+                ("US $325.00", 325.0, None, "USD"),
+                ("US $1,234.56", 1234.56, None, "USD"),
+                ("US$12.00", 12.0, None, "USD"),
+                ("$0.99", 0.99, None, "USD"),
+                ("$.50", 0.5, None, "USD"),
+                ("$10,000,000.00", 10_000_000.0, None, "USD"),
+                ("$1234", 1234.0, None, "USD"),
+                ("1000", 1000.0, None, "USD"),
+                ("123.45 USD", 123.45, None, "USD"),
+                ("USD 50.00", 50.0, None, "USD"),
+                ("  $75.25  ", 75.25, None, "USD"),
+                ("$20.00 to $35.00", 20.0, Some(35.0), "USD"),
+                ("$5.99 to $12.49", 5.99, Some(12.49), "USD"),
+                ("$1,099.00 to $1,499.00", 1099.0, Some(1499.0), "USD"),
+                ("$20 - $35", 20.0, Some(35.0), "USD"),
+                ("$9.99 – $19.99", 9.99, Some(19.99), "USD"),
+                ("EUR 12.34", 12.34, None, "EUR"),
+                ("GBP 8.50", 8.5, None, "GBP"),
+                ("C $45.00", 45.0, None, "CAD"),
+                ("AU $89.95", 89.95, None, "AUD"),
+                ("$29.99 (approx. EUR 27.50)", 29.99, None, "USD"),
+                ("$1,234", 1234.0, None, "USD"),
+                ("$1.5", 1.5, None, "USD"),
+                ("$4,99", 4.99, None, "USD"),
+            ],
+        );
+
+        check_dates(
+            Marketplace::Us,
+            &[
+                ("Sold Mar 3, 2024", (2024, 3, 3)),
+                ("Sold  Jun 14, 2024", (2024, 6, 14)),
+                ("Ended: May 12, 2024", (2024, 5, 12)),
+                ("May 12, 2024 10:15:32 PDT", (2024, 5, 12)),
+                ("Ended: Sep 30, 2023 18:02:11 PDT", (2023, 9, 30)),
+                ("Sept 1, 2024", (2024, 9, 1)),
+                ("December 25, 2023", (2023, 12, 25)),
+                ("Sat, Feb 29, 2024", (2024, 2, 29)),
+                ("05/12/2024", (2024, 5, 12)),
+                ("Ended: 12/31/23", (2023, 12, 31)),
+                ("2024-05-12", (2024, 5, 12)),
+            ],
+        );
+
+        check_times(
+            Marketplace::Us,
+            &[
+                ("6d 3h left", 6 * D + 3 * H),
+                ("6d 3h", 6 * D + 3 * H),
+                ("2h 15m left", 2 * H + 15 * M),
+                ("45m 10s left", 45 * M + 10),
+                ("1d left", D),
+                ("Time left: 3d 22h", 3 * D + 22 * H),
+                ("Ends in 12m 5s", 12 * M + 5),
+                ("6d3h", 6 * D + 3 * H),
+                ("2 days 4 hours", 2 * D + 4 * H),
+                ("1 hour 1 minute", H + M),
+                ("30 secs left", 30),
+            ],
+        );
+
+        check_failures(
+            Marketplace::Us,
+            &[
+                "",
+                "   ",
+                "abc",
+                "$$$",
+                "See price",
+                "$1,23.00",
+                "$35.00 to $20.00",
+            ],
+            &["Ended", "Mar 2024", "Feb 30, 2024", "13/13/2024"],
+            &["Ended", "6x 3h", "6d 3", "left"],
+        );
+    }
+
+    #[test]
+    fn test_uk_marketplace() {
+        check_prices(
+            Marketplace::Uk,
+            &[
+                ("£12.34", 12.34, None, "GBP"),
+                ("£1,234.56", 1234.56, None, "GBP"),
+                ("£0.99", 0.99, None, "GBP"),
+                ("£250", 250.0, None, "GBP"),
+                ("£2,500.00", 2500.0, None, "GBP"),
+                ("GBP 45.00", 45.0, None, "GBP"),
+                ("45.00 GBP", 45.0, None, "GBP"),
+                ("£5.99 to £9.99", 5.99, Some(9.99), "GBP"),
+                ("£1,000.00 to £1,250.00", 1000.0, Some(1250.0), "GBP"),
+                ("£12.34 (approx. US $15.20)", 12.34, None, "GBP"),
+                ("US $15.20", 15.2, None, "USD"),
+                ("EUR 14.10", 14.1, None, "EUR"),
+                ("19.99", 19.99, None, "GBP"),
+                ("£19", 19.0, None, "GBP"),
+                ("£3.50 - £7.00", 3.5, Some(7.0), "GBP"),
+                ("£0.01", 0.01, None, "GBP"),
+                ("£1,000,000.00", 1_000_000.0, None, "GBP"),
+                ("£ 64.00", 64.0, None, "GBP"),
+                ("AU $120.00", 120.0, None, "AUD"),
+                ("£9.99 – £14.99", 9.99, Some(14.99), "GBP"),
+            ],
+        );
+
+        check_dates(
+            Marketplace::Uk,
+            &[
+                ("Sold 3 Mar 2024", (2024, 3, 3)),
+                ("Ended: 12 May 2024", (2024, 5, 12)),
+                ("Sold  14 Jun 2024", (2024, 6, 14)),
+                ("12 May, 2024 18:15:02 BST", (2024, 5, 12)),
+                ("Sat, 1 Feb 2025", (2025, 2, 1)),
+                ("1 January 2024", (2024, 1, 1)),
+                ("Ended 30 Nov 2023 21:45:00 GMT", (2023, 11, 30)),
+                ("12/05/2024", (2024, 5, 12)),
+                ("31/12/23", (2023, 12, 31)),
+                ("Sold 9 Sept 2024", (2024, 9, 9)),
+            ],
+        );
+
+        check_times(
+            Marketplace::Uk,
+            &[
+                ("6d 3h left", 6 * D + 3 * H),
+                ("1d 0h", D),
+                ("23h 59m", 23 * H + 59 * M),
+                ("5m 30s left", 5 * M + 30),
+                ("Time remaining: 2d 1h", 2 * D + H),
+                ("3 days", 3 * D),
+                ("10 mins left", 10 * M),
+                ("7d", 7 * D),
+                ("1h 2m 3s", H + 2 * M + 3),
+            ],
+        );
+
+        check_failures(
+            Marketplace::Uk,
+            &["£", "£12.34.56", "Free postage", "£9.99 to US $12.00"],
+            &["Sold", "3 Mar", "32 Jan 2024", "Jan Feb 2024"],
+            &["", "Ending soon", "3 weeks"],
+        );
+    }
+
+    #[test]
+    fn test_de_marketplace() {
+        check_prices(
+            Marketplace::De,
+            &[
+                ("EUR 1.234,56", 1234.56, None, "EUR"),
+                ("EUR 12,34", 12.34, None, "EUR"),
+                ("EUR 0,99", 0.99, None, "EUR"),
+                ("EUR 1.000,00", 1000.0, None, "EUR"),
+                ("EUR 10.000", 10_000.0, None, "EUR"),
+                ("EUR 1.234", 1234.0, None, "EUR"),
+                ("1.234,56 €", 1234.56, None, "EUR"),
+                ("12,34 €", 12.34, None, "EUR"),
+                ("€ 5,00", 5.0, None, "EUR"),
+                ("12,34", 12.34, None, "EUR"),
+                ("EUR 20,00 bis EUR 35,00", 20.0, Some(35.0), "EUR"),
+                ("EUR 1.099,00 bis EUR 1.499,00", 1099.0, Some(1499.0), "EUR"),
+                ("20,00 € - 35,00 €", 20.0, Some(35.0), "EUR"),
+                ("US $1.234,56", 1234.56, None, "USD"),
+                ("GBP 8,50", 8.5, None, "GBP"),
+                ("CHF 99,00", 99.0, None, "CHF"),
+                ("EUR 12,34 (inkl. MwSt.)", 12.34, None, "EUR"),
+                ("EUR 12.50", 12.5, None, "EUR"),
+                ("EUR 1,234.56", 1234.56, None, "EUR"),
+                ("EUR 250", 250.0, None, "EUR"),
+            ],
+        );
+
+        check_dates(
+            Marketplace::De,
+            &[
+                ("Ended: 12. Mai 2024", (2024, 5, 12)),
+                ("Beendet: 12. Mai 2024", (2024, 5, 12)),
+                ("Verkauft 3. Mär 2024", (2024, 3, 3)),
+                ("Verkauft  14. Jun. 2024", (2024, 6, 14)),
+                ("12. März 2024", (2024, 3, 12)),
+                ("1. Okt. 2023 14:30:00 MESZ", (2023, 10, 1)),
+                ("Sa, 24. Dez. 2022", (2022, 12, 24)),
+                ("12.05.2024", (2024, 5, 12)),
+                ("Beendet: 12.05.24 14:30", (2024, 5, 12)),
+                ("31. Dezember 2023", (2023, 12, 31)),
+                ("5. Februar 2025", (2025, 2, 5)),
+                ("Verkauft 9. Juli 2024", (2024, 7, 9)),
+            ],
+        );
+
+        check_times(
+            Marketplace::De,
+            &[
+                ("6T 3Std.", 6 * D + 3 * H),
+                ("Noch 6T 3Std.", 6 * D + 3 * H),
+                ("2Std. 15Min.", 2 * H + 15 * M),
+                ("45Min. 10Sek.", 45 * M + 10),
+                ("1T", D),
+                ("Restzeit: 3T 22Std.", 3 * D + 22 * H),
+                ("2 Tage 4 Stunden", 2 * D + 4 * H),
+                ("1 Stunde 1 Minute", H + M),
+                ("30 Sekunden", 30),
+                ("6T 3h", 6 * D + 3 * H),
+            ],
+        );
+
+        check_failures(
+            Marketplace::De,
+            &[
+                "EUR",
+                "Preisvorschlag",
+                "EUR 1.23,45",
+                "EUR 35,00 bis EUR 20,00",
+            ],
+            &["Beendet", "12. Mai", "30. Februar 2024", "12. May 2024"],
+            &["Beendet", "6d 3h", "6T 3"],
+        );
+    }
+
+    #[test]
+    fn test_fr_marketplace() {
+        check_prices(
+            Marketplace::Fr,
+            &[
+                ("1 234,56 EUR", 1234.56, None, "EUR"),
+                ("1\u{a0}234,56 EUR", 1234.56, None, "EUR"),
+                ("1\u{202f}234,56 €", 1234.56, None, "EUR"),
+                ("12,34 EUR", 12.34, None, "EUR"),
+                ("0,99 EUR", 0.99, None, "EUR"),
+                ("EUR 12,34", 12.34, None, "EUR"),
+                ("12,34 €", 12.34, None, "EUR"),
+                ("1.234,56 EUR", 1234.56, None, "EUR"),
+                ("10 000,00 EUR", 10_000.0, None, "EUR"),
+                ("250 EUR", 250.0, None, "EUR"),
+                ("20,00 EUR à 35,00 EUR", 20.0, Some(35.0), "EUR"),
+                ("20,00 à 35,00 €", 20.0, Some(35.0), "EUR"),
+                ("1 099,00 EUR à 1 499,00 EUR", 1099.0, Some(1499.0), "EUR"),
+                ("12,34 EUR (environ 13,40 USD)", 12.34, None, "EUR"),
+                ("15,20 USD", 15.2, None, "USD"),
+                ("8,50 GBP", 8.5, None, "GBP"),
+                ("99,99 €", 99.99, None, "EUR"),
+                ("1 000 000,00 EUR", 1_000_000.0, None, "EUR"),
+                ("EUR 5,00 à EUR 7,50", 5.0, Some(7.5), "EUR"),
+                ("20 CHF", 20.0, None, "CHF"),
+            ],
+        );
+
+        check_dates(
+            Marketplace::Fr,
+            &[
+                ("Vendu le 12 mai 2024", (2024, 5, 12)),
+                ("Terminé : 12 mai 2024", (2024, 5, 12)),
+                ("Vendu le 3 mars 2024", (2024, 3, 3)),
+                ("mar. 12 mars 2024", (2024, 3, 12)),
+                ("1 févr. 2025", (2025, 2, 1)),
+                ("15 août 2023 14h30", (2023, 8, 15)),
+                ("31 déc. 2023 23:59:00", (2023, 12, 31)),
+                ("sam. 1 juin 2024", (2024, 6, 1)),
+                ("12/05/2024", (2024, 5, 12)),
+                ("Vendu le 9 juil. 2024", (2024, 7, 9)),
+                ("22 septembre 2024", (2024, 9, 22)),
+                ("1 janvier 2024", (2024, 1, 1)),
+            ],
+        );
+
+        check_times(
+            Marketplace::Fr,
+            &[
+                ("6j 3h", 6 * D + 3 * H),
+                ("Il reste 6j 3h", 6 * D + 3 * H),
+                ("2h 15min", 2 * H + 15 * M),
+                ("45min 10s", 45 * M + 10),
+                ("1j", D),
+                ("Temps restant : 3j 22h", 3 * D + 22 * H),
+                ("2 jours 4 heures", 2 * D + 4 * H),
+                ("1 heure 1 minute", H + M),
+                ("30 secondes", 30),
+                ("5mn", 5 * M),
+            ],
+        );
+
+        check_failures(
+            Marketplace::Fr,
+            &[
+                "EUR",
+                "Faire une offre",
+                "12,34 EUR à 8,50 GBP",
+                "1 23,45 EUR",
+            ],
+            &["Terminé", "12 mai", "12 May 2024", "31 avr. 2024"],
+            &["Terminé", "6T 3Std.", "3 semaines"],
+        );
+    }
+
+    #[test]
+    fn test_marketplace_from_url() {
+        assert_eq!(
+            Marketplace::from_url("https://www.ebay.com/itm/123"),
+            Some(Marketplace::Us)
+        );
+        assert_eq!(
+            Marketplace::from_url("https://www.ebay.co.uk/sch/i.html?_nkw=x"),
+            Some(Marketplace::Uk)
+        );
+        assert_eq!(
+            Marketplace::from_url("https://www.ebay.de"),
+            Some(Marketplace::De)
+        );
+        assert_eq!(
+            Marketplace::from_url("http://ebay.fr/itm/1"),
+            Some(Marketplace::Fr)
+        );
+        assert_eq!(Marketplace::from_url("https://www.ebay.com.au/itm/1"), None);
+        assert_eq!(Marketplace::from_url("https://notebay.com/itm/1"), None);
+    }
+
+    #[test]
+    fn test_errors_say_what_was_wrong() {
+        assert_eq!(
+            parse_price("  ", Marketplace::Us).unwrap_err(),
+            ParseError::Empty
+        );
+        assert_eq!(
+            parse_price("See price", Marketplace::Us)
+                .unwrap_err()
+                .to_string(),
+            "unexpected 'See' in 'See price'"
+        );
+        assert!(matches!(
+            parse_price("$35 to $20", Marketplace::Us),
+            Err(ParseError::InvertedRange(_))
+        ));
+        assert!(matches!(
+            parse_date("30. Februar 2024", Marketplace::De),
+            Err(ParseError::InvalidDate(_))
+        ));
+        assert!(matches!(
+            parse_time_left("Ending soon", Marketplace::Uk),
+            Err(ParseError::Unexpected { .. })
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+//! Replay archived pages through the current extractor
+//!
+//! Each archived page carries the extraction output recorded at capture
+//! time. Re-running the extractor and diffing the two shows exactly which
+//! fields a scraper change affects.
+
+use crate::error::{EbayMcpError, Result};
+use crate::models::PageSnapshot;
+use crate::scraper::extract::extract_page;
+use crate::storage::PageArchive;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::fmt;
+
+/// A field whose value differs between capture time and now
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// JSON path of the field, e.g. `[0].price`
+    pub path: String,
+
+    /// Value stored at capture time (None if the field is new)
+    pub stored: Option<Value>,
+
+    /// Value extracted now (None if the field disappeared)
+    pub current: Option<Value>,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "<missing>".to_string(),
+        };
+
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            show(&self.stored),
+            show(&self.current)
+        )
+    }
+}
+
+/// Outcome of replaying one snapshot
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// Snapshot ID
+    pub snapshot_id: i64,
+
+    /// Page URL
+    pub url: String,
+
+    /// Capture timestamp
+    pub captured_at: DateTime<Utc>,
+
+    /// Field differences (empty when extraction is unchanged)
+    pub diffs: Vec<FieldDiff>,
+}
+
+impl ReplayReport {
+    /// Whether the current extractor reproduces the stored output
+    pub fn is_clean(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_clean() {
+            "OK".to_string()
+        } else {
+            format!("{} changed", self.diffs.len())
+        };
+
+        writeln!(
+            f,
+            "#{} {} ({}) {}",
+            self.snapshot_id,
+            self.url,
+            self.captured_at.format("%Y-%m-%d %H:%M"),
+            status
+        )?;
+
+        for diff in &self.diffs {
+            writeln!(f, "  {}", diff)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Re-run the current extractor against an archived page
+pub async fn replay_snapshot(
+    archive: &PageArchive,
+    snapshot: &PageSnapshot,
+) -> Result<ReplayReport> {
+    let html = archive.load_html(&snapshot.html_hash).map_err(|e| {
+        EbayMcpError::ScrapingFailed(format!(
+            "archived page {} unreadable: {}",
+            snapshot.html_hash, e
+        ))
+    })?;
+
+    let current = serde_json::to_value(extract_page(snapshot.kind, &snapshot.url, &html))?;
+
+    Ok(ReplayReport {
+        snapshot_id: snapshot.id,
+        url: snapshot.url.clone(),
+        captured_at: snapshot.captured_at,
+        diffs: diff_extractions(&snapshot.extracted, &current),
+    })
+}
+
+/// Field-level differences between two extraction outputs
+pub fn diff_extractions(stored: &Value, current: &Value) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    diff_values("", Some(stored), Some(current), &mut diffs);
+    diffs
+}
+
+fn diff_values(
+    path: &str,
+    stored: Option<&Value>,
+    current: Option<&Value>,
+    diffs: &mut Vec<FieldDiff>,
+) {
+    match (stored, current) {
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(&format!("{}[{}]", path, i), a.get(i), b.get(i), diffs);
+            }
+        }
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(&child, a.get(key), b.get(key), diffs);
+            }
+        }
+        (a, b) if a != b => diffs.push(FieldDiff {
+            path: if path.is_empty() {
+                "$".to_string()
+            } else {
+                path.to_string()
+            },
+            stored: a.cloned(),
+            current: b.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ArchiveConfig, PageKind};
+    use crate::storage::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::{NamedTempFile, TempDir};
+    use tokio::sync::Mutex;
+
+    #[test]
+    fn test_diff_identical() {
+        let value = json!([{"item_id": "1", "price": 10.0}]);
+        assert!(diff_extractions(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn test_diff_changed_added_removed() {
+        let stored = json!([
+            {"item_id": "1", "title": "Canon AE-1", "price": 10.0},
+            {"item_id": "2", "title": "Nikon FM2", "price": 20.0}
+        ]);
+        let current = json!([
+            {"item_id": "1", "title": "Canon AE-1", "price": null, "shipping": 5.0}
+        ]);
+
+        let diffs = diff_extractions(&stored, &current);
+        let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+
+        assert_eq!(paths, vec!["[0].price", "[0].shipping", "[1]"]);
+        assert_eq!(diffs[0].stored, Some(json!(10.0)));
+        assert_eq!(diffs[0].current, Some(Value::Null));
+        assert_eq!(diffs[1].stored, None);
+        assert_eq!(diffs[2].current, None);
+        assert_eq!(diffs[0].to_string(), "[0].price: 10.0 -> null");
+    }
+
+    #[tokio::test]
+    async fn test_replay_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+        let database = Arc::new(Mutex::new(Database::new(db_file.path()).unwrap()));
+        let config = ArchiveConfig {
+            enabled: true,
+            dir: dir.path().to_path_buf(),
+            retention_days: 0,
+            max_size_mb: 0,
+        };
+        let archive = PageArchive::new(&config, database).unwrap();
+
+        let url = "https://www.ebay.com/itm/42";
+        let html = r#"<h1 class="x-item-title__mainTitle">Pentax K1000</h1>
+<div class="x-price-primary">$80.00</div>"#;
+        let stored = json!([{
+            "item_id": "42",
+            "title": "Pentax K1000",
+            "price": 75.0,
+            "listing_url": url
+        }]);
+
+        let id = archive
+            .archive_page(PageKind::Detail, url, None, html, &stored)
+            .await
+            .unwrap();
+        let snapshot = archive.get(id).await.unwrap().unwrap();
+
+        let report = replay_snapshot(&archive, &snapshot).await.unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.diffs.len(), 1);
+        assert_eq!(report.diffs[0].path, "[0].price");
+        assert_eq!(report.diffs[0].current, Some(json!(80.0)));
+    }
+}
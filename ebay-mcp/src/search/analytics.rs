@@ -0,0 +1,245 @@
+//! Per-label price analytics and filtering over search results
+
+use crate::models::{BestOffer, EbayListing, ListingLabel, OfferStats, PriceStats};
+use std::collections::BTreeMap;
+
+/// Price statistics over a set of listings
+///
+/// Only listings in the currency of the first one are counted, so a stray
+/// foreign-currency listing cannot skew the numbers. Listings sold by an
+/// accepted offer at a hidden price only show their asking price, so they
+/// are counted in `accepted_offers` but not priced. Returns `None` when
+/// there is nothing to count.
+pub fn price_stats<'a>(listings: impl IntoIterator<Item = &'a EbayListing>) -> Option<PriceStats> {
+    let mut listings = listings.into_iter().peekable();
+    let currency = listings.peek()?.price.currency.clone();
+    let listings: Vec<&EbayListing> = listings.filter(|l| l.price.currency == currency).collect();
+
+    let mut prices: Vec<f64> = listings
+        .iter()
+        .filter(|l| !l.has_hidden_sale_price())
+        .map(|l| l.price.amount)
+        .collect();
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_by(|a, b| a.total_cmp(b));
+
+    let count = prices.len();
+    // The two middle prices, which are the same one for an odd count
+    let median = (prices[(count - 1) / 2] + prices[count / 2]) / 2.0;
+
+    Some(PriceStats {
+        count,
+        currency,
+        min: prices[0],
+        max: prices[count - 1],
+        mean: prices.iter().sum::<f64>() / count as f64,
+        median,
+        accepted_offers: offer_stats(listings),
+    })
+}
+
+/// Accepted Best Offers among a set of listings
+///
+/// Discounts are percentages of each listing's own asking price, so
+/// listings in any currency count. Returns `None` when none sold by
+/// accepted offer.
+pub fn offer_stats<'a>(listings: impl IntoIterator<Item = &'a EbayListing>) -> Option<OfferStats> {
+    let mut accepted = 0;
+    let mut hidden = 0;
+    let mut discounts = Vec::new();
+
+    for listing in listings {
+        match &listing.best_offer {
+            Some(offer @ BestOffer::Accepted { .. }) => {
+                accepted += 1;
+                discounts.extend(offer.discount_percent(&listing.price));
+            }
+            Some(BestOffer::AcceptedHidden) => hidden += 1,
+            Some(BestOffer::Open) | None => {}
+        }
+    }
+
+    if accepted + hidden == 0 {
+        return None;
+    }
+
+    Some(OfferStats {
+        accepted,
+        hidden,
+        average_discount_percent: (!discounts.is_empty())
+            .then(|| discounts.iter().sum::<f64>() / discounts.len() as f64),
+    })
+}
+
+/// Price statistics for each label present among classified listings
+///
+/// Unlabelled listings are left out.
+pub fn price_stats_by_label(listings: &[EbayListing]) -> BTreeMap<ListingLabel, PriceStats> {
+    ListingLabel::ALL
+        .iter()
+        .filter_map(|&label| {
+            price_stats(listings.iter().filter(|l| l.label == Some(label)))
+                .map(|stats| (label, stats))
+        })
+        .collect()
+}
+
+/// Keep only listings carrying one of `labels`
+///
+/// An empty list keeps everything.
+pub fn retain_labels(listings: &mut Vec<EbayListing>, labels: &[ListingLabel]) {
+    if labels.is_empty() {
+        return;
+    }
+
+    listings.retain(|l| l.label.is_some_and(|label| labels.contains(&label)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BuyingFormat, Price, SellerInfo};
+
+    fn sold(price: f64, asking: Option<f64>) -> EbayListing {
+        let mut listing = listing(price, None);
+        listing.best_offer = Some(match asking {
+            Some(asking) => BestOffer::Accepted {
+                original_price: Some(Price::usd(asking)),
+            },
+            None => BestOffer::AcceptedHidden,
+        });
+        listing
+    }
+
+    fn listing(price: f64, label: Option<ListingLabel>) -> EbayListing {
+        EbayListing {
+            item_id: format!("{}", price),
+            title: "iPhone 12".to_string(),
+            price: Price::usd(price),
+            price_max: None,
+            shipping: None,
+            condition: "Used".to_string(),
+            format: BuyingFormat::BuyItNow,
+            seller: SellerInfo::default(),
+            location: "USA".to_string(),
+            thumbnail_url: None,
+            listing_url: "https://ebay.com/itm/1".to_string(),
+            bids: None,
+            time_left: None,
+            free_shipping: false,
+            returns_accepted: false,
+            label,
+            best_offer: None,
+        }
+    }
+
+    #[test]
+    fn test_price_stats() {
+        let listings = vec![
+            listing(300.0, None),
+            listing(100.0, None),
+            listing(200.0, None),
+            listing(400.0, None),
+        ];
+
+        let stats = price_stats(&listings).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.currency, "USD");
+        assert_eq!(stats.min, 100.0);
+        assert_eq!(stats.max, 400.0);
+        assert_eq!(stats.mean, 250.0);
+        assert_eq!(stats.median, 250.0);
+
+        let stats = price_stats(&listings[..3]).unwrap();
+        assert_eq!(stats.median, 200.0);
+
+        assert!(price_stats(&[]).is_none());
+    }
+
+    #[test]
+    fn test_price_stats_ignores_other_currencies() {
+        let mut euro = listing(9999.0, None);
+        euro.price = Price::new(9999.0, "EUR");
+
+        let stats = price_stats(&[listing(100.0, None), euro]).unwrap();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.max, 100.0);
+    }
+
+    #[test]
+    fn test_price_stats_by_label_separates_parts() {
+        let listings = vec![
+            listing(400.0, Some(ListingLabel::Working)),
+            listing(420.0, Some(ListingLabel::Working)),
+            listing(60.0, Some(ListingLabel::ForParts)),
+            listing(15.0, Some(ListingLabel::AccessoryOnly)),
+            listing(1.0, None),
+        ];
+
+        let stats = price_stats_by_label(&listings);
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[&ListingLabel::Working].mean, 410.0);
+        assert_eq!(stats[&ListingLabel::ForParts].count, 1);
+        assert!(!stats.contains_key(&ListingLabel::Bundle));
+    }
+
+    #[test]
+    fn test_accepted_offer_discount() {
+        let listings = vec![
+            sold(80.0, Some(100.0)),
+            sold(135.0, Some(150.0)),
+            sold(190.0, Some(200.0)),
+            // Hidden prices are the asking price, so never priced
+            sold(500.0, None),
+            listing(120.0, None),
+        ];
+
+        let stats = price_stats(&listings).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.max, 190.0);
+
+        let offers = stats.accepted_offers.unwrap();
+        assert_eq!((offers.accepted, offers.hidden), (3, 1));
+        // (20% + 10% + 5%) / 3
+        let discount = offers.average_discount_percent.unwrap();
+        assert!((discount - 35.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_offer_stats_without_accepted_offers() {
+        let mut open = listing(100.0, None);
+        open.best_offer = Some(BestOffer::Open);
+        assert!(offer_stats(&[open, listing(90.0, None)]).is_none());
+        assert!(price_stats(&[listing(90.0, None)])
+            .unwrap()
+            .accepted_offers
+            .is_none());
+
+        // Hidden prices alone are counted, with no discount to average
+        let offers = offer_stats(&[sold(300.0, None)]).unwrap();
+        assert_eq!((offers.accepted, offers.hidden), (0, 1));
+        assert_eq!(offers.average_discount_percent, None);
+        assert!(price_stats(&[sold(300.0, None)]).is_none());
+    }
+
+    #[test]
+    fn test_retain_labels() {
+        let mut listings = vec![
+            listing(400.0, Some(ListingLabel::Working)),
+            listing(60.0, Some(ListingLabel::ForParts)),
+            listing(1.0, None),
+        ];
+
+        retain_labels(&mut listings, &[]);
+        assert_eq!(listings.len(), 3);
+
+        retain_labels(
+            &mut listings,
+            &[ListingLabel::Working, ListingLabel::Bundle],
+        );
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].label, Some(ListingLabel::Working));
+    }
+}
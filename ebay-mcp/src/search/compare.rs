@@ -0,0 +1,324 @@
+//! Building side-by-side comparisons from several search results
+
+use crate::error::{EbayMcpError, Result};
+use crate::models::{Comparison, ComparisonColumn, ComparisonQuery, PriceDelta, SearchResults};
+use crate::search::ranking;
+use std::fmt::Write;
+
+/// Fewest queries a comparison accepts
+pub const MIN_QUERIES: usize = 2;
+
+/// Most queries a comparison accepts
+pub const MAX_QUERIES: usize = 5;
+
+/// Best-value listings shown per column
+pub const TOP_LISTINGS: usize = 3;
+
+/// Reject comparisons with too few or too many queries
+pub fn validate_query_count(count: usize) -> Result<()> {
+    if (MIN_QUERIES..=MAX_QUERIES).contains(&count) {
+        Ok(())
+    } else {
+        Err(EbayMcpError::InvalidInput(format!(
+            "compare_searches takes {} to {} queries, got {}",
+            MIN_QUERIES, MAX_QUERIES, count
+        )))
+    }
+}
+
+/// Assemble a comparison from each query's search outcome
+///
+/// Failed searches become columns carrying the error, so one bot challenge
+/// does not sink the rest. Deltas are only given between neighbouring
+/// columns that both succeeded and share a currency.
+pub fn build_comparison(outcomes: Vec<(ComparisonQuery, Result<SearchResults>)>) -> Comparison {
+    let mut columns: Vec<ComparisonColumn> = outcomes
+        .into_iter()
+        .map(|(query, outcome)| build_column(query, outcome))
+        .collect();
+
+    for i in 1..columns.len() {
+        let (previous, current) = (&columns[i - 1], &columns[i]);
+        let delta = match (previous.median_landed, current.median_landed) {
+            (Some(before), Some(after)) if previous.currency == current.currency => {
+                Some(PriceDelta {
+                    amount: after - before,
+                    percent: if before != 0.0 {
+                        (after - before) / before * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+            }
+            _ => None,
+        };
+        columns[i].delta_from_previous = delta;
+    }
+
+    Comparison { columns }
+}
+
+fn build_column(query: ComparisonQuery, outcome: Result<SearchResults>) -> ComparisonColumn {
+    let mut column = ComparisonColumn {
+        label: query.label,
+        query: query.query,
+        phrase_id: query.phrase_id,
+        error: None,
+        result_count: 0,
+        currency: None,
+        median_landed: None,
+        min_landed: None,
+        delta_from_previous: None,
+        best_value: Vec::new(),
+    };
+
+    let results = match outcome {
+        Ok(results) => results,
+        Err(e) => {
+            column.error = Some(e.user_message());
+            return column;
+        }
+    };
+
+    column.result_count = results.items.len();
+    if let Some((currency, prices)) = ranking::comparable_landed_prices(&results.items) {
+        column.currency = Some(currency);
+        column.median_landed = ranking::median(&prices);
+        column.min_landed = prices.first().copied();
+    }
+    column.best_value = ranking::best_value(&results.items, TOP_LISTINGS);
+
+    column
+}
+
+impl Comparison {
+    /// Render as a markdown table with one column per query
+    ///
+    /// Best-value listings follow as a numbered list per query, since titles
+    /// are too long for table cells.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let cells = |f: &dyn Fn(&ComparisonColumn) -> String| -> String {
+            self.columns
+                .iter()
+                .map(|c| format!(" {} |", escape(&f(c))))
+                .collect()
+        };
+
+        let _ = writeln!(out, "| |{}", cells(&|c| c.label.clone()));
+        let _ = writeln!(out, "|---|{}", "---|".repeat(self.columns.len()));
+
+        let rows: [(&str, &dyn Fn(&ComparisonColumn) -> String); 5] = [
+            ("Status", &|c| match &c.error {
+                Some(e) => format!("failed: {}", e),
+                None => "ok".to_string(),
+            }),
+            ("Results", &|c| {
+                if c.is_ok() {
+                    c.result_count.to_string()
+                } else {
+                    "-".to_string()
+                }
+            }),
+            ("Median landed", &|c| {
+                money(c.median_landed, c.currency.as_deref())
+            }),
+            ("Min landed", &|c| {
+                money(c.min_landed, c.currency.as_deref())
+            }),
+            ("Delta vs previous", &|c| match c.delta_from_previous {
+                Some(delta) => format!(
+                    "{}{} ({:+.1}%)",
+                    if delta.amount < 0.0 { "-" } else { "+" },
+                    money(Some(delta.amount.abs()), c.currency.as_deref()),
+                    delta.percent
+                ),
+                None => "-".to_string(),
+            }),
+        ];
+        for (name, value) in rows {
+            let _ = writeln!(out, "| {} |{}", name, cells(value));
+        }
+
+        for column in self.columns.iter().filter(|c| c.is_ok()) {
+            let _ = writeln!(out, "\n**Best value: {}**", column.label);
+            if column.best_value.is_empty() {
+                let _ = writeln!(out, "\nNo comparable listings.");
+                continue;
+            }
+            out.push('\n');
+            for (rank, listing) in column.best_value.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "{}. [{}]({}) - {} (score {:.2})",
+                    rank + 1,
+                    listing.title,
+                    listing.listing_url,
+                    money(Some(listing.landed_price), Some(&listing.currency)),
+                    listing.score
+                );
+            }
+        }
+
+        out
+    }
+}
+
+fn money(amount: Option<f64>, currency: Option<&str>) -> String {
+    match (amount, currency) {
+        (Some(amount), Some(currency)) => format!("{:.2} {}", amount, currency),
+        (Some(amount), None) => format!("{:.2}", amount),
+        (None, _) => "-".to_string(),
+    }
+}
+
+/// Keep cell text from breaking the table
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Price, SearchFilters};
+    use crate::search::ranking::tests::listing;
+    use chrono::Utc;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn results(prices: &[f64]) -> Result<SearchResults> {
+        Ok(SearchResults {
+            query: "q".to_string(),
+            filters: SearchFilters::default(),
+            items: prices
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| listing(&format!("{}-{}", p, i), p, None))
+                .collect(),
+            total_count: prices.len(),
+            page: 1,
+            total_pages: 1,
+            searched_at: Utc::now(),
+            duration: Duration::from_millis(5),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: BTreeMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_query_count_limits() {
+        assert!(validate_query_count(1).is_err());
+        assert!(validate_query_count(2).is_ok());
+        assert!(validate_query_count(5).is_ok());
+        assert!(validate_query_count(6).is_err());
+    }
+
+    #[test]
+    fn test_columns_carry_stats_and_adjacent_deltas() {
+        let comparison = build_comparison(vec![
+            (
+                ComparisonQuery::inline("iPhone 12 64GB"),
+                results(&[200.0, 220.0, 260.0]),
+            ),
+            (
+                ComparisonQuery::inline("iPhone 12 128GB"),
+                results(&[250.0, 275.0]),
+            ),
+            (ComparisonQuery::inline("iPhone 13 64GB"), results(&[])),
+        ]);
+
+        let [a, b, c] = &comparison.columns[..] else {
+            panic!("expected three columns")
+        };
+        assert_eq!(
+            (a.result_count, a.median_landed, a.min_landed),
+            (3, Some(220.0), Some(200.0))
+        );
+        assert_eq!(a.delta_from_previous, None);
+        assert_eq!(a.best_value.len(), 3);
+        assert_eq!(a.best_value[0].landed_price, 200.0);
+
+        let delta = b.delta_from_previous.unwrap();
+        assert_eq!(delta.amount, 42.5);
+        assert!((delta.percent - 19.318).abs() < 0.001);
+
+        // No listings: ok, but nothing to compare against
+        assert!(c.is_ok());
+        assert_eq!(
+            (c.result_count, c.median_landed, c.delta_from_previous),
+            (0, None, None)
+        );
+    }
+
+    #[test]
+    fn test_failed_query_keeps_its_column() {
+        let comparison = build_comparison(vec![
+            (ComparisonQuery::inline("a"), results(&[100.0])),
+            (
+                ComparisonQuery::inline("b"),
+                Err(EbayMcpError::CaptchaDetected),
+            ),
+            (ComparisonQuery::inline("c"), results(&[120.0])),
+        ]);
+
+        let failed = &comparison.columns[1];
+        assert!(!failed.is_ok());
+        assert_eq!(
+            failed.error.as_deref(),
+            Some("eBay requires manual verification. Please try again later.")
+        );
+        // Neither neighbour of the failure gets a delta
+        assert_eq!(comparison.columns[2].delta_from_previous, None);
+        assert!(comparison.columns[0].is_ok());
+        assert_eq!(comparison.columns[2].median_landed, Some(120.0));
+    }
+
+    #[test]
+    fn test_no_delta_across_currencies() {
+        let mut pounds = results(&[100.0]).unwrap();
+        pounds.items[0].price = Price::new(100.0, "GBP");
+
+        let comparison = build_comparison(vec![
+            (ComparisonQuery::inline("us"), results(&[100.0])),
+            (ComparisonQuery::inline("uk"), Ok(pounds)),
+        ]);
+        assert_eq!(comparison.columns[1].currency.as_deref(), Some("GBP"));
+        assert_eq!(comparison.columns[1].delta_from_previous, None);
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let comparison = build_comparison(vec![
+            (
+                ComparisonQuery::inline("iPhone 12 64GB"),
+                results(&[200.0, 300.0]),
+            ),
+            (ComparisonQuery::inline("iPhone | 13"), results(&[275.0])),
+            (
+                ComparisonQuery::inline("iPhone 14"),
+                Err(EbayMcpError::RateLimited),
+            ),
+        ]);
+
+        let markdown = comparison.to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "| | iPhone 12 64GB | iPhone \\| 13 | iPhone 14 |");
+        assert_eq!(lines[1], "|---|---|---|---|");
+        assert_eq!(
+            lines[2],
+            "| Status | ok | ok | failed: Too many requests to eBay. Please wait a moment and try again. |"
+        );
+        assert_eq!(lines[3], "| Results | 2 | 1 | - |");
+        assert_eq!(lines[4], "| Median landed | 250.00 USD | 275.00 USD | - |");
+        assert_eq!(lines[5], "| Min landed | 200.00 USD | 275.00 USD | - |");
+        assert_eq!(
+            lines[6],
+            "| Delta vs previous | - | +25.00 USD (+10.0%) | - |"
+        );
+        assert!(markdown.contains("**Best value: iPhone 12 64GB**"));
+        assert!(markdown.contains("1. [iPhone 12 200-0](https://ebay.com/itm/200-0) - 200.00 USD"));
+        assert!(!markdown.contains("**Best value: iPhone 14**"));
+    }
+}
@@ -1,14 +1,22 @@
 //! Search manager - orchestrates search operations
 
-use crate::browser::BrowserPool;
+use crate::browser::{AntiDetection, BrowserPool, ContextSource};
 use crate::config::ConfigManager;
 use crate::error::{EbayMcpError, Result};
-use crate::models::{SavedSearchPhrase, SearchFilters, SearchHistoryEntry, SearchResults};
-use crate::storage::{Database, ResultCache};
+use crate::alerts;
+use crate::models::{
+    AlertMatch, AlertRule, Comparison, ComparisonQuery, PortfolioSummary, SavedSearchPhrase,
+    SearchFilters, SearchHistoryEntry, SearchPaging, SearchResults, SearchRun,
+};
+use crate::scraper::{EbayScraper, ListingClassifier};
+use crate::search::{analytics, compare, portfolio};
+use crate::storage::{Database, PageArchive, ResultCache};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, info, warn};
 
 /// Search manager for orchestrating eBay searches
 pub struct SearchManager {
@@ -22,7 +30,16 @@ pub struct SearchManager {
     cache: Arc<ResultCache>,
 
     /// Database
-    database: Arc<RwLock<Database>>,
+    database: Arc<Mutex<Database>>,
+
+    /// Page archive (None when archiving is disabled)
+    archive: Option<Arc<PageArchive>>,
+
+    /// Labels each result (working, for parts, bundle, accessory)
+    classifier: Arc<ListingClassifier>,
+
+    /// Fires when a saved search is added, removed or renamed
+    phrase_changes: broadcast::Sender<()>,
 }
 
 impl SearchManager {
@@ -31,30 +48,67 @@ impl SearchManager {
         config_manager: Arc<ConfigManager>,
         browser_pool: Arc<BrowserPool>,
         cache: Arc<ResultCache>,
-        database: Arc<RwLock<Database>>,
+        database: Arc<Mutex<Database>>,
     ) -> Self {
         Self {
             config_manager,
             browser_pool,
             cache,
             database,
+            archive: None,
+            classifier: Arc::new(ListingClassifier::default()),
+            phrase_changes: broadcast::channel(16).0,
         }
     }
 
+    /// Label results with a configured classifier instead of the default one
+    pub fn with_classifier(mut self, classifier: Arc<ListingClassifier>) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
+    /// Archive scraped pages for replay
+    pub fn with_archive(mut self, archive: Arc<PageArchive>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Page archive, if enabled
+    pub fn archive(&self) -> Option<&Arc<PageArchive>> {
+        self.archive.as_ref()
+    }
+
     /// Execute search by query
     pub async fn search(
         &self,
         query: &str,
         filters: Option<SearchFilters>,
+    ) -> Result<SearchResults> {
+        self.search_paged(query, filters, SearchPaging::default())
+            .await
+    }
+
+    /// Execute search by query, collecting one or more results pages
+    ///
+    /// Only plain first-page searches are cached; continuing with a cursor
+    /// always goes to eBay.
+    pub async fn search_paged(
+        &self,
+        query: &str,
+        filters: Option<SearchFilters>,
+        paging: SearchPaging,
     ) -> Result<SearchResults> {
         let filters = filters.unwrap_or_default();
+        let cacheable = paging.is_default();
 
-        info!("Executing search for query: '{}'", query);
+        info!("Executing search for query: '{}' ({:?})", query, paging);
 
         // Check cache first
-        if let Some(cached) = self.cache.get(query, &filters).await {
-            info!("Cache hit for query: '{}'", query);
-            return Ok(cached);
+        if cacheable {
+            if let Some(cached) = self.cache.get(query, &filters).await {
+                info!("Cache hit for query: '{}'", query);
+                return Ok(cached);
+            }
         }
 
         debug!("Cache miss, performing search");
@@ -62,7 +116,10 @@ impl SearchManager {
         let start = Instant::now();
 
         // Execute search
-        let results = self.execute_search(query, &filters).await;
+        let results = self
+            .execute_search(query, &filters, &paging)
+            .await
+            .map(|results| self.label_results(results, &filters));
 
         let duration_ms = start.elapsed().as_millis() as i64;
 
@@ -70,13 +127,15 @@ impl SearchManager {
         match &results {
             Ok(search_results) => {
                 // Store in cache
-                let _ = self
-                    .cache
-                    .set(query.to_string(), filters.clone(), search_results.clone())
-                    .await;
+                if cacheable {
+                    let _ = self
+                        .cache
+                        .set(query.to_string(), filters.clone(), search_results.clone())
+                        .await;
+                }
 
                 // Add to history
-                let db = self.database.write().await;
+                let db = self.database.lock().await;
                 let filters_json = serde_json::to_string(&filters).ok();
 
                 let _ = db.add_search_history(
@@ -90,7 +149,7 @@ impl SearchManager {
             }
             Err(e) => {
                 // Record failed search
-                let db = self.database.write().await;
+                let db = self.database.lock().await;
                 let _ =
                     db.add_search_history(query, None, 0, duration_ms, false, Some(&e.to_string()));
             }
@@ -107,12 +166,140 @@ impl SearchManager {
         let phrase = self.config_manager.get_phrase(phrase_id).await?;
 
         // Update usage statistics
-        let db = self.database.write().await;
+        let db = self.database.lock().await;
         let _ = db.update_phrase_usage(phrase_id);
         drop(db);
 
         // Execute search with phrase parameters
-        self.search(&phrase.query, Some(phrase.filters)).await
+        let results = self.search(&phrase.query, Some(phrase.filters)).await?;
+
+        if let Err(e) = self.record_run(phrase_id, &results).await {
+            warn!("Failed to store run of phrase {}: {}", phrase_id, e);
+        }
+
+        Ok(results)
+    }
+
+    /// Store a saved search run for the portfolio summary
+    async fn record_run(&self, phrase_id: &str, results: &SearchResults) -> Result<()> {
+        let mut run = portfolio::search_run(phrase_id, results);
+
+        let db = self.database.lock().await;
+        run.new_listings = db.mark_listings_seen(
+            phrase_id,
+            results.items.iter().map(|l| l.item_id.as_str()),
+            run.ran_at,
+        )?;
+        db.add_search_run(&run)?;
+
+        Ok(())
+    }
+
+    /// Summarize every saved search from its stored runs
+    ///
+    /// With `refresh`, every saved search runs first; one that fails keeps
+    /// its stored figures and reports the error. Searches that have never
+    /// run are listed as such rather than left out.
+    pub async fn portfolio_summary(&self, refresh: bool) -> Result<PortfolioSummary> {
+        let phrases = self.config_manager.get_phrases().await;
+
+        info!(
+            "Building portfolio summary of {} saved searches (refresh: {})",
+            phrases.len(),
+            refresh
+        );
+
+        let mut refresh_errors = HashMap::new();
+        if refresh {
+            let outcomes =
+                futures::future::join_all(phrases.iter().map(|p| self.search_by_phrase_id(&p.id)))
+                    .await;
+            for (phrase, outcome) in phrases.iter().zip(outcomes) {
+                if let Err(e) = outcome {
+                    refresh_errors.insert(phrase.id.clone(), e.user_message());
+                }
+            }
+        }
+
+        let now = Utc::now();
+        let since = now - Duration::days(portfolio::TREND_WINDOW_DAYS);
+        let db = self.database.lock().await;
+
+        let rule_phrases: HashMap<String, String> = db
+            .get_alert_rules()?
+            .into_iter()
+            .map(|r| (r.id, r.phrase_id))
+            .collect();
+        let mut pending: HashMap<&str, usize> = HashMap::new();
+        for alert in db.get_pending_alerts()? {
+            if let Some(phrase_id) = rule_phrases.get(&alert.rule_id) {
+                *pending.entry(phrase_id.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut entries = Vec::with_capacity(phrases.len());
+        for phrase in &phrases {
+            let mut entry = portfolio::build_entry(
+                phrase,
+                db.get_latest_search_run(&phrase.id)?,
+                &db.get_search_runs_since(&phrase.id, since)?,
+                pending.get(phrase.id.as_str()).copied().unwrap_or(0),
+                now,
+            );
+            entry.refresh_error = refresh_errors.remove(&phrase.id);
+            entries.push(entry);
+        }
+
+        Ok(portfolio::build_summary(entries, now))
+    }
+
+    /// Run 2-5 searches concurrently and compare them side by side
+    ///
+    /// Searches share the browser pool, so they run as far in parallel as the
+    /// pool allows. A failed search is reported in its own column instead of
+    /// failing the comparison.
+    pub async fn compare(&self, queries: Vec<ComparisonQuery>) -> Result<Comparison> {
+        compare::validate_query_count(queries.len())?;
+
+        info!("Comparing {} searches", queries.len());
+
+        let outcomes = futures::future::join_all(
+            queries
+                .iter()
+                .map(|q| self.search(&q.query, Some(q.filters.clone()))),
+        )
+        .await;
+
+        let db = self.database.lock().await;
+        for query in &queries {
+            if let Some(phrase_id) = &query.phrase_id {
+                let _ = db.update_phrase_usage(phrase_id);
+            }
+        }
+        drop(db);
+
+        Ok(compare::build_comparison(
+            queries.into_iter().zip(outcomes).collect(),
+        ))
+    }
+
+    /// Comparison query for a saved phrase, looked up by ID or name
+    ///
+    /// Names match case-insensitively; an ID match wins over a name.
+    pub async fn comparison_query_for_phrase(&self, id_or_name: &str) -> Result<ComparisonQuery> {
+        let phrases = self.config_manager.get_phrases().await;
+        let phrase = phrases
+            .iter()
+            .find(|p| p.id == id_or_name)
+            .or_else(|| phrases.iter().find(|p| p.name.eq_ignore_ascii_case(id_or_name)))
+            .ok_or_else(|| EbayMcpError::PhraseNotFound(id_or_name.to_string()))?;
+
+        Ok(ComparisonQuery {
+            label: phrase.name.clone(),
+            query: phrase.query.clone(),
+            filters: phrase.filters.clone(),
+            phrase_id: Some(phrase.id.clone()),
+        })
     }
 
     /// Save a new search phrase
@@ -122,6 +309,7 @@ impl SearchManager {
         let phrase_id = phrase.id.clone();
 
         self.config_manager.save_phrase(phrase).await?;
+        let _ = self.phrase_changes.send(());
 
         info!("Saved search phrase with ID: {}", phrase_id);
 
@@ -132,7 +320,11 @@ impl SearchManager {
     pub async fn update_phrase(&self, id: &str, phrase: SavedSearchPhrase) -> Result<()> {
         info!("Updating search phrase: {}", id);
 
+        let renamed = self.config_manager.get_phrase(id).await?.name != phrase.name;
         self.config_manager.update_phrase(id, phrase).await?;
+        if renamed {
+            let _ = self.phrase_changes.send(());
+        }
 
         Ok(())
     }
@@ -142,6 +334,7 @@ impl SearchManager {
         info!("Deleting search phrase: {}", id);
 
         self.config_manager.delete_phrase(id).await?;
+        let _ = self.phrase_changes.send(());
 
         Ok(())
     }
@@ -168,13 +361,54 @@ impl SearchManager {
         self.config_manager.get_phrase(id).await
     }
 
+    /// Get phrase by name
+    ///
+    /// An exact match wins over a case-insensitive one; of several phrases
+    /// with the same name, the first saved wins.
+    pub async fn get_phrase_by_name(&self, name: &str) -> Result<SavedSearchPhrase> {
+        let phrases = self.config_manager.get_phrases().await;
+        let lowercase = name.to_lowercase();
+        phrases
+            .iter()
+            .find(|p| p.name == name)
+            .or_else(|| phrases.iter().find(|p| p.name.to_lowercase() == lowercase))
+            .cloned()
+            .ok_or_else(|| EbayMcpError::PhraseNotFound(name.to_string()))
+    }
+
+    /// Subscribe to changes of the saved search list
+    ///
+    /// A message arrives after every phrase that is saved or deleted, and
+    /// after an update that renames one.
+    pub fn subscribe_phrase_changes(&self) -> broadcast::Receiver<()> {
+        self.phrase_changes.subscribe()
+    }
+
+    /// Most recent stored run of a saved search
+    pub async fn latest_run(&self, phrase_id: &str) -> Result<Option<SearchRun>> {
+        let db = self.database.lock().await;
+        db.get_latest_search_run(phrase_id)
+    }
+
+    /// Every stored run of a saved search, oldest first
+    pub async fn run_history(&self, phrase_id: &str) -> Result<Vec<SearchRun>> {
+        let db = self.database.lock().await;
+        db.get_search_runs(phrase_id)
+    }
+
+    /// Results of a saved search still in the result cache, without
+    /// searching again
+    pub async fn cached_results(&self, phrase: &SavedSearchPhrase) -> Option<SearchResults> {
+        self.cache.get(&phrase.query, &phrase.filters).await
+    }
+
     /// Get search history
     pub async fn get_history(
         &self,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<SearchHistoryEntry>> {
-        let db = self.database.read().await;
+        let db = self.database.lock().await;
         db.get_search_history(limit, offset)
     }
 
@@ -184,20 +418,143 @@ impl SearchManager {
         self.cache.clear().await
     }
 
-    /// Execute actual search (stub for now)
-    async fn execute_search(&self, query: &str, filters: &SearchFilters) -> Result<SearchResults> {
-        debug!("Executing search: query='{}', filters={:?}", query, filters);
+    /// Create an alert rule for an existing saved search phrase
+    pub async fn create_alert(&self, rule: AlertRule) -> Result<String> {
+        // Ensure the referenced phrase exists
+        self.config_manager.get_phrase(&rule.phrase_id).await?;
 
-        // TODO: When we can build:
-        // 1. Acquire browser from pool
-        // 2. Create scraper with anti-detection
-        // 3. Execute search
-        // 4. Return browser to pool
+        info!("Creating alert rule {} for phrase {}", rule.id, rule.phrase_id);
 
-        // For now, return stub
-        Err(EbayMcpError::NotImplemented(
-            "Browser-based search execution".to_string(),
-        ))
+        let db = self.database.lock().await;
+        db.save_alert_rule(&rule)?;
+
+        Ok(rule.id)
+    }
+
+    /// List all alert rules
+    pub async fn list_alerts(&self) -> Result<Vec<AlertRule>> {
+        let db = self.database.lock().await;
+        db.get_alert_rules()
+    }
+
+    /// Delete an alert rule
+    pub async fn delete_alert(&self, rule_id: &str) -> Result<()> {
+        let db = self.database.lock().await;
+        if !db.delete_alert_rule(rule_id)? {
+            return Err(EbayMcpError::InvalidInput(format!(
+                "Alert rule '{}' not found",
+                rule_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Get fired alerts not yet acknowledged, optionally acknowledging them
+    pub async fn pending_alerts(&self, acknowledge: bool) -> Result<Vec<AlertMatch>> {
+        let db = self.database.lock().await;
+        let pending = db.get_pending_alerts()?;
+
+        if acknowledge {
+            db.acknowledge_alerts()?;
+        }
+
+        Ok(pending)
+    }
+
+    /// Evaluate the alert rules of a phrase against fresh results
+    ///
+    /// New matches are recorded in storage and returned grouped by rule.
+    pub async fn process_alerts(
+        &self,
+        phrase_id: &str,
+        results: &SearchResults,
+    ) -> Result<Vec<(AlertRule, Vec<AlertMatch>)>> {
+        let db = self.database.lock().await;
+        let mut fired = Vec::new();
+
+        for rule in db
+            .get_alert_rules()?
+            .into_iter()
+            .filter(|r| r.phrase_id == phrase_id)
+        {
+            let alerted = db.get_alerted_prices(&rule.id)?;
+            let matches = alerts::evaluate(&rule, &results.items, &alerted);
+
+            if matches.is_empty() {
+                continue;
+            }
+
+            for alert in &matches {
+                db.record_alert_match(alert)?;
+            }
+
+            info!("Alert rule {} fired for {} listings", rule.id, matches.len());
+            fired.push((rule, matches));
+        }
+
+        Ok(fired)
+    }
+
+    /// Classify listings, apply label filters and summarize prices per label
+    fn label_results(&self, mut results: SearchResults, filters: &SearchFilters) -> SearchResults {
+        self.classifier.label_listings(&mut results.items);
+
+        if let Some(ref labels) = filters.labels {
+            analytics::retain_labels(&mut results.items, labels);
+        }
+
+        results.price_stats = analytics::price_stats_by_label(&results.items);
+        results
+    }
+
+    /// Execute actual search
+    ///
+    /// Pages are loaded by a pooled browser context. A proxy that fails the
+    /// first page is cooled down and the search retried through another;
+    /// the browser goes back to the pool when dropped.
+    async fn execute_search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        paging: &SearchPaging,
+    ) -> Result<SearchResults> {
+        debug!(
+            "Executing search: query='{}', filters={:?}, paging={:?}",
+            query, filters, paging
+        );
+
+        let start = Instant::now();
+        let config = self.config_manager.get_config().await;
+        let timeout = std::time::Duration::from_secs(config.browser.page_load_timeout);
+
+        let anti_detection = AntiDetection::new(
+            config.browser.user_agents.clone(),
+            config.browser.randomize_delay,
+            std::time::Duration::from_millis(config.browser.delay_min_ms),
+            std::time::Duration::from_millis(config.browser.delay_max_ms),
+        );
+        let mut scraper = EbayScraper::new(config.scraper.into(), anti_detection);
+        if let Some(archive) = &self.archive {
+            scraper = scraper.with_archive(archive.clone());
+        }
+
+        let browser = self.browser_pool.acquire().await?;
+        let collected = self
+            .browser_pool
+            .with_proxy(browser.proxy(), |proxy| {
+                let source = ContextSource::new(browser.fingerprint(), proxy.as_ref(), timeout);
+                let scraper = &scraper;
+                async move {
+                    scraper
+                        .collect_pages(&source?, query, filters, paging)
+                        .await
+                }
+            })
+            .await?;
+        browser.increment_requests();
+
+        Ok(collected.into_results(query, filters, start.elapsed()))
     }
 
     /// Get search manager statistics
@@ -317,7 +674,6 @@ mod tests {
     // - get_phrase()
     // - get_history()
     // - clear_cache()
-    // - execute_search() (currently returns NotImplemented)
     // - stats()
     //
     // For now, these integration tests are deferred due to complexity
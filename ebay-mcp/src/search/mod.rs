@@ -1,5 +1,11 @@
 //! Search management module
 
+pub mod analytics;
+pub mod compare;
 pub mod manager;
+pub mod portfolio;
+pub mod ranking;
 
+pub use analytics::{offer_stats, price_stats, price_stats_by_label, retain_labels};
+pub use compare::build_comparison;
 pub use manager::{SearchManager, SearchManagerStats};
@@ -0,0 +1,482 @@
+//! Portfolio summary across every saved search
+//!
+//! Each saved search is summarized from its stored runs: the latest run gives
+//! the cheapest listing and the typical discount of accepted Best Offers, the
+//! runs of the last 30 days give the direction of the median landed price,
+//! and the runs of the last 7 days count the listings that first appeared in
+//! them.
+
+use crate::models::{
+    CheapestListing, PortfolioEntry, PortfolioStatus, PortfolioSummary, PriceTrend,
+    SavedSearchPhrase, SearchResults, SearchRun, TrendDirection,
+};
+use crate::search::{analytics, ranking};
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+/// Days of runs the price trend is drawn from
+pub const TREND_WINDOW_DAYS: i64 = 30;
+
+/// Days of runs whose new listings are counted
+pub const NEW_LISTINGS_WINDOW_DAYS: i64 = 7;
+
+/// Median changes smaller than this percentage count as flat
+pub const FLAT_THRESHOLD_PERCENT: f64 = 1.0;
+
+/// Summarize a saved search's results as a run to store
+///
+/// `new_listings` is left at zero; storage knows which listings are new.
+pub fn search_run(phrase_id: &str, results: &SearchResults) -> SearchRun {
+    let mut run = SearchRun {
+        phrase_id: phrase_id.to_string(),
+        ran_at: results.searched_at,
+        result_count: results.items.len(),
+        currency: None,
+        median_landed: None,
+        cheapest: None,
+        new_listings: 0,
+        accepted_offers: analytics::offer_stats(
+            results.items.iter().filter(|l| ranking::is_comparable(l)),
+        ),
+    };
+
+    if let Some((currency, prices)) = ranking::comparable_landed_prices(&results.items) {
+        run.median_landed = ranking::median(&prices);
+        run.cheapest = results
+            .items
+            .iter()
+            .filter(|l| ranking::is_comparable(l) && l.price.currency == currency)
+            .min_by(|a, b| ranking::landed_price(a).total_cmp(&ranking::landed_price(b)))
+            .map(|l| CheapestListing {
+                item_id: l.item_id.clone(),
+                title: l.title.clone(),
+                landed_price: ranking::landed_price(l),
+                currency: currency.clone(),
+                listing_url: l.listing_url.clone(),
+            });
+        run.currency = Some(currency);
+    }
+
+    run
+}
+
+/// Movement of the median landed price across runs, oldest first
+///
+/// Only runs in the currency of the latest priced run count. Needs at least
+/// two of them.
+pub fn price_trend(runs: &[SearchRun]) -> Option<PriceTrend> {
+    let currency = runs
+        .iter()
+        .rev()
+        .find(|r| r.median_landed.is_some())?
+        .currency
+        .clone()?;
+
+    let medians: Vec<f64> = runs
+        .iter()
+        .filter(|r| r.currency.as_deref() == Some(currency.as_str()))
+        .filter_map(|r| r.median_landed)
+        .collect();
+
+    if medians.len() < 2 {
+        return None;
+    }
+    let (from, to) = (medians[0], medians[medians.len() - 1]);
+
+    let change_percent = if from != 0.0 {
+        (to - from) / from * 100.0
+    } else {
+        0.0
+    };
+    let direction = if change_percent.abs() < FLAT_THRESHOLD_PERCENT {
+        TrendDirection::Flat
+    } else if change_percent < 0.0 {
+        TrendDirection::Down
+    } else {
+        TrendDirection::Up
+    };
+
+    Some(PriceTrend {
+        direction,
+        from,
+        to,
+        change_percent,
+        currency,
+        runs: medians.len(),
+    })
+}
+
+/// Summarize one saved search
+///
+/// `latest` is its most recent stored run and `history` its runs of the last
+/// [`TREND_WINDOW_DAYS`], oldest first.
+pub fn build_entry(
+    phrase: &SavedSearchPhrase,
+    latest: Option<SearchRun>,
+    history: &[SearchRun],
+    alerts_pending: usize,
+    now: DateTime<Utc>,
+) -> PortfolioEntry {
+    let week_ago = now - Duration::days(NEW_LISTINGS_WINDOW_DAYS);
+
+    PortfolioEntry {
+        phrase_id: phrase.id.clone(),
+        name: phrase.name.clone(),
+        query: phrase.query.clone(),
+        status: if latest.is_some() {
+            PortfolioStatus::Ok
+        } else {
+            PortfolioStatus::NeverRun
+        },
+        last_run_at: latest.as_ref().map(|r| r.ran_at),
+        result_count: latest.as_ref().map(|r| r.result_count),
+        accepted_offers: latest.as_ref().and_then(|r| r.accepted_offers.clone()),
+        cheapest: latest.and_then(|r| r.cheapest),
+        trend: price_trend(history),
+        new_this_week: history
+            .iter()
+            .filter(|r| r.ran_at >= week_ago)
+            .map(|r| r.new_listings)
+            .sum(),
+        alerts_pending,
+        refresh_error: None,
+    }
+}
+
+/// Collect entries into a summary, biggest price drop first
+///
+/// Searches without a trend follow, then those never run, each by name.
+pub fn build_summary(mut searches: Vec<PortfolioEntry>, now: DateTime<Utc>) -> PortfolioSummary {
+    let rank = |entry: &PortfolioEntry| match (&entry.status, &entry.trend) {
+        (PortfolioStatus::Ok, Some(trend)) => (0, trend.change_percent),
+        (PortfolioStatus::Ok, None) => (1, 0.0),
+        (PortfolioStatus::NeverRun, _) => (2, 0.0),
+    };
+
+    searches.sort_by(|a, b| {
+        let ((group_a, change_a), (group_b, change_b)) = (rank(a), rank(b));
+        group_a
+            .cmp(&group_b)
+            .then(change_a.partial_cmp(&change_b).unwrap_or(Ordering::Equal))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    PortfolioSummary {
+        generated_at: now,
+        searches,
+    }
+}
+
+impl PortfolioSummary {
+    /// Render as a markdown table with one row per saved search
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "| Search | Last run | Cheapest | {}-day median | Offer discount | New this week | Alerts pending |",
+            TREND_WINDOW_DAYS
+        );
+        let _ = writeln!(out, "|---|---|---|---|---|---|---|");
+
+        for entry in &self.searches {
+            let mut last_run = match entry.last_run_at {
+                Some(ran_at) => ran_at.format("%Y-%m-%d %H:%M").to_string(),
+                None => "never run".to_string(),
+            };
+            if let Some(error) = &entry.refresh_error {
+                let _ = write!(last_run, " (refresh failed: {})", error);
+            }
+
+            let cheapest = match &entry.cheapest {
+                Some(c) => format!("[{:.2} {}]({})", c.landed_price, c.currency, c.listing_url),
+                None => "-".to_string(),
+            };
+
+            let trend = match &entry.trend {
+                Some(t) => format!(
+                    "{} {:+.1}% ({:.2} -> {:.2} {})",
+                    match t.direction {
+                        TrendDirection::Up => "up",
+                        TrendDirection::Down => "down",
+                        TrendDirection::Flat => "flat",
+                    },
+                    t.change_percent,
+                    t.from,
+                    t.to,
+                    t.currency
+                ),
+                None => "-".to_string(),
+            };
+
+            let offers = match &entry.accepted_offers {
+                Some(offers) => {
+                    let mut cell = match offers.average_discount_percent {
+                        Some(discount) => format!("-{:.1}% ({} sold)", discount, offers.accepted),
+                        None => format!("{} sold", offers.accepted),
+                    };
+                    if offers.hidden > 0 {
+                        let _ = write!(cell, ", {} hidden", offers.hidden);
+                    }
+                    cell
+                }
+                None => "-".to_string(),
+            };
+
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                escape(&entry.name),
+                escape(&last_run),
+                cheapest,
+                trend,
+                offers,
+                entry.new_this_week,
+                entry.alerts_pending
+            );
+        }
+
+        out
+    }
+}
+
+/// Keep cell text from breaking the table
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BestOffer, ListingLabel, OfferStats, Price, SearchFilters};
+    use crate::search::ranking::tests::listing;
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 11, 20, 12, 0, 0).unwrap()
+    }
+
+    fn phrase(id: &str, name: &str) -> SavedSearchPhrase {
+        SavedSearchPhrase {
+            id: id.to_string(),
+            name: name.to_string(),
+            query: name.to_lowercase(),
+            filters: SearchFilters::default(),
+            tags: vec![],
+            created_at: now(),
+            last_used: None,
+            usage_count: 0,
+        }
+    }
+
+    fn run(days_ago: i64, median: f64, new_listings: usize) -> SearchRun {
+        SearchRun {
+            phrase_id: "p".to_string(),
+            ran_at: now() - Duration::days(days_ago),
+            result_count: 12,
+            currency: Some("USD".to_string()),
+            median_landed: Some(median),
+            cheapest: None,
+            new_listings,
+            accepted_offers: None,
+        }
+    }
+
+    fn entry(name: &str, history: &[SearchRun]) -> PortfolioEntry {
+        build_entry(
+            &phrase(name, name),
+            history.last().cloned(),
+            history,
+            0,
+            now(),
+        )
+    }
+
+    #[test]
+    fn test_search_run_from_results() {
+        let mut parts = listing("parts", 20.0, None);
+        parts.label = Some(ListingLabel::ForParts);
+        let results = SearchResults {
+            query: "q".to_string(),
+            filters: SearchFilters::default(),
+            items: vec![
+                parts,
+                listing("a", 200.0, Some(30.0)),
+                listing("b", 220.0, None),
+                listing("c", 260.0, None),
+            ],
+            total_count: 4,
+            page: 1,
+            total_pages: 1,
+            searched_at: now(),
+            duration: std::time::Duration::from_millis(5),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: BTreeMap::new(),
+        };
+
+        let run = search_run("p", &results);
+        assert_eq!(run.result_count, 4);
+        assert_eq!(run.median_landed, Some(230.0));
+        let cheapest = run.cheapest.unwrap();
+        // Parts listings never count as cheapest
+        assert_eq!(
+            (cheapest.item_id.as_str(), cheapest.landed_price),
+            ("b", 220.0)
+        );
+    }
+
+    #[test]
+    fn test_search_run_offer_discount() {
+        let sold = |id: &str, price: f64, asking: f64, label: Option<ListingLabel>| {
+            let mut listing = listing(id, price, None);
+            listing.label = label;
+            listing.best_offer = Some(BestOffer::Accepted {
+                original_price: Some(Price::usd(asking)),
+            });
+            listing
+        };
+        let mut hidden = listing("hidden", 300.0, None);
+        hidden.best_offer = Some(BestOffer::AcceptedHidden);
+        let results = SearchResults {
+            query: "q".to_string(),
+            filters: SearchFilters::default(),
+            items: vec![
+                sold("a", 160.0, 200.0, None),
+                sold("b", 135.0, 150.0, Some(ListingLabel::Working)),
+                // Parts sell at steeper discounts and would skew the average
+                sold("parts", 20.0, 80.0, Some(ListingLabel::ForParts)),
+                hidden,
+                listing("c", 180.0, None),
+            ],
+            total_count: 5,
+            page: 1,
+            total_pages: 1,
+            searched_at: now(),
+            duration: std::time::Duration::from_millis(5),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: BTreeMap::new(),
+        };
+
+        let run = search_run("p", &results);
+        assert_eq!(
+            run.accepted_offers,
+            Some(OfferStats {
+                accepted: 2,
+                hidden: 1,
+                average_discount_percent: Some(15.0),
+            })
+        );
+
+        let entry = entry("Canon AE-1", &[run]);
+        assert_eq!(entry.accepted_offers.unwrap().hidden, 1);
+    }
+
+    #[test]
+    fn test_price_trend_direction() {
+        let trend = price_trend(&[run(20, 200.0, 0), run(10, 190.0, 0), run(1, 170.0, 0)]).unwrap();
+        assert_eq!(trend.direction, TrendDirection::Down);
+        assert_eq!((trend.from, trend.to, trend.runs), (200.0, 170.0, 3));
+        assert!((trend.change_percent + 15.0).abs() < 1e-9);
+
+        let up = price_trend(&[run(5, 100.0, 0), run(1, 110.0, 0)]).unwrap();
+        assert_eq!(up.direction, TrendDirection::Up);
+        let flat = price_trend(&[run(5, 100.0, 0), run(1, 100.5, 0)]).unwrap();
+        assert_eq!(flat.direction, TrendDirection::Flat);
+
+        assert!(price_trend(&[run(1, 100.0, 0)]).is_none());
+        assert!(price_trend(&[]).is_none());
+    }
+
+    #[test]
+    fn test_price_trend_ignores_other_currencies() {
+        let mut pounds = run(10, 50.0, 0);
+        pounds.currency = Some("GBP".to_string());
+        let trend = price_trend(&[run(20, 200.0, 0), pounds, run(1, 180.0, 0)]).unwrap();
+        assert_eq!((trend.from, trend.runs), (200.0, 2));
+    }
+
+    #[test]
+    fn test_entry_counts_new_listings_this_week() {
+        let entry = entry(
+            "Leica M6",
+            &[run(20, 200.0, 5), run(6, 200.0, 2), run(1, 200.0, 3)],
+        );
+        assert_eq!(entry.status, PortfolioStatus::Ok);
+        assert_eq!(entry.new_this_week, 5);
+        assert_eq!(entry.result_count, Some(12));
+        assert_eq!(entry.last_run_at, Some(now() - Duration::days(1)));
+    }
+
+    #[test]
+    fn test_never_run_searches_are_kept() {
+        let entry = build_entry(&phrase("p", "Rolleiflex"), None, &[], 2, now());
+        assert_eq!(entry.status, PortfolioStatus::NeverRun);
+        assert_eq!(entry.alerts_pending, 2);
+        assert!(entry.trend.is_none() && entry.cheapest.is_none());
+        assert_eq!(serde_json::to_value(&entry).unwrap()["status"], "never_run");
+    }
+
+    #[test]
+    fn test_summary_sorted_by_biggest_drop() {
+        let summary = build_summary(
+            vec![
+                entry("Never", &[]),
+                entry("Rising", &[run(9, 100.0, 0), run(1, 120.0, 0)]),
+                entry("Single run", &[run(1, 100.0, 0)]),
+                entry("Small drop", &[run(9, 100.0, 0), run(1, 95.0, 0)]),
+                entry("Big drop", &[run(9, 100.0, 0), run(1, 70.0, 0)]),
+            ],
+            now(),
+        );
+
+        let names: Vec<_> = summary.searches.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Big drop", "Small drop", "Rising", "Single run", "Never"]
+        );
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let mut latest = run(1, 180.0, 1);
+        latest.cheapest = Some(CheapestListing {
+            item_id: "1".to_string(),
+            title: "Leica M6".to_string(),
+            landed_price: 150.0,
+            currency: "USD".to_string(),
+            listing_url: "https://ebay.com/itm/1".to_string(),
+        });
+        latest.accepted_offers = Some(OfferStats {
+            accepted: 3,
+            hidden: 1,
+            average_discount_percent: Some(12.5),
+        });
+        let mut never = entry("Rollei | 35", &[]);
+        never.refresh_error = Some("Too many requests".to_string());
+
+        let summary = build_summary(
+            vec![never, entry("Leica M6", &[run(20, 200.0, 0), latest])],
+            now(),
+        );
+
+        let markdown = summary.to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(
+            lines[0],
+            "| Search | Last run | Cheapest | 30-day median | Offer discount | New this week | Alerts pending |"
+        );
+        assert_eq!(
+            lines[2],
+            "| Leica M6 | 2025-11-19 12:00 | [150.00 USD](https://ebay.com/itm/1) | down -10.0% (200.00 -> 180.00 USD) | -12.5% (3 sold), 1 hidden | 1 | 0 |"
+        );
+        assert_eq!(
+            lines[3],
+            "| Rollei \\| 35 | never run (refresh failed: Too many requests) | - | - | - | 0 | 0 |"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Best-value ranking over search results
+//!
+//! Listings are scored by how far their landed price (price plus shipping)
+//! sits below the median of comparable listings, with small bonuses for
+//! well-reviewed sellers and accepted returns. Listings the classifier marked
+//! as for parts, accessories or bundles are not comparable and never rank.
+
+use crate::models::{EbayListing, ListingLabel, RankedListing};
+
+/// Bonus for a seller with enough feedback, scaled by positive percentage
+pub const SELLER_WEIGHT: f64 = 0.05;
+
+/// Feedback score a seller needs before their rating counts
+pub const MIN_SELLER_FEEDBACK: u32 = 10;
+
+/// Bonus for listings that accept returns
+pub const RETURNS_BONUS: f64 = 0.02;
+
+/// Price plus shipping
+///
+/// Shipping in a different currency is left out rather than converted.
+pub fn landed_price(listing: &EbayListing) -> f64 {
+    let shipping = match &listing.shipping {
+        Some(shipping) if !listing.free_shipping && shipping.currency == listing.price.currency => {
+            shipping.amount
+        }
+        _ => 0.0,
+    };
+    listing.price.amount + shipping
+}
+
+/// Whether a listing sells the searched item on its own
+///
+/// Unlabelled listings count, since classification may be turned off.
+pub fn is_comparable(listing: &EbayListing) -> bool {
+    matches!(listing.label, None | Some(ListingLabel::Working))
+}
+
+/// Currency and sorted landed prices of the comparable listings
+///
+/// Only listings in the currency of the first comparable one are kept, as in
+/// [`price_stats`](super::price_stats). Returns `None` when none are comparable.
+pub fn comparable_landed_prices(listings: &[EbayListing]) -> Option<(String, Vec<f64>)> {
+    let currency = listings
+        .iter()
+        .find(|l| is_comparable(l))?
+        .price
+        .currency
+        .clone();
+
+    let mut prices: Vec<f64> = listings
+        .iter()
+        .filter(|l| is_comparable(l) && l.price.currency == currency)
+        .map(landed_price)
+        .collect();
+    prices.sort_by(|a, b| a.total_cmp(b));
+
+    Some((currency, prices))
+}
+
+/// Median of already sorted values
+pub fn median(sorted: &[f64]) -> Option<f64> {
+    let count = sorted.len();
+    if count == 0 {
+        return None;
+    }
+    Some((sorted[(count - 1) / 2] + sorted[count / 2]) / 2.0)
+}
+
+/// Value score of a listing against the median landed price
+pub fn value_score(listing: &EbayListing, median_landed: f64) -> f64 {
+    let discount = if median_landed > 0.0 {
+        (median_landed - landed_price(listing)) / median_landed
+    } else {
+        0.0
+    };
+
+    let seller = if listing.seller.feedback_score >= MIN_SELLER_FEEDBACK {
+        SELLER_WEIGHT * listing.seller.positive_percentage / 100.0
+    } else {
+        0.0
+    };
+
+    let returns = if listing.returns_accepted {
+        RETURNS_BONUS
+    } else {
+        0.0
+    };
+
+    discount + seller + returns
+}
+
+/// The `count` best-value comparable listings, best first
+pub fn best_value(listings: &[EbayListing], count: usize) -> Vec<RankedListing> {
+    let Some((currency, prices)) = comparable_landed_prices(listings) else {
+        return Vec::new();
+    };
+    let median_landed = median(&prices).unwrap_or_default();
+
+    let mut ranked: Vec<RankedListing> = listings
+        .iter()
+        .filter(|l| is_comparable(l) && l.price.currency == currency)
+        .map(|l| RankedListing {
+            item_id: l.item_id.clone(),
+            title: l.title.clone(),
+            landed_price: landed_price(l),
+            currency: currency.clone(),
+            score: value_score(l, median_landed),
+            listing_url: l.listing_url.clone(),
+            label: l.label,
+        })
+        .collect();
+
+    // Cheaper first on equal scores, so the order is stable across runs
+    ranked.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.landed_price.total_cmp(&b.landed_price))
+    });
+    ranked.truncate(count);
+    ranked
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::models::{BuyingFormat, Price, SellerInfo};
+
+    pub(crate) fn listing(id: &str, price: f64, shipping: Option<f64>) -> EbayListing {
+        EbayListing {
+            item_id: id.to_string(),
+            title: format!("iPhone 12 {}", id),
+            price: Price::usd(price),
+            price_max: None,
+            shipping: shipping.map(Price::usd),
+            condition: "Used".to_string(),
+            format: BuyingFormat::BuyItNow,
+            seller: SellerInfo::default(),
+            location: "USA".to_string(),
+            thumbnail_url: None,
+            listing_url: format!("https://ebay.com/itm/{}", id),
+            bids: None,
+            time_left: None,
+            free_shipping: false,
+            returns_accepted: false,
+            label: None,
+            best_offer: None,
+        }
+    }
+
+    #[test]
+    fn test_landed_price_adds_matching_shipping() {
+        assert_eq!(landed_price(&listing("a", 100.0, Some(15.0))), 115.0);
+
+        let mut free = listing("b", 100.0, Some(15.0));
+        free.free_shipping = true;
+        assert_eq!(landed_price(&free), 100.0);
+
+        let mut foreign = listing("c", 100.0, None);
+        foreign.shipping = Some(Price::new(10.0, "GBP"));
+        assert_eq!(landed_price(&foreign), 100.0);
+    }
+
+    #[test]
+    fn test_median_of_sorted_values() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[3.0]), Some(3.0));
+        assert_eq!(median(&[1.0, 2.0, 4.0, 10.0]), Some(3.0));
+    }
+
+    #[test]
+    fn test_best_value_prefers_cheap_landed_price() {
+        let listings = vec![
+            listing("dear", 300.0, None),
+            // Cheap item price, but shipping makes it the most expensive
+            listing("shipping", 150.0, Some(200.0)),
+            listing("cheap", 200.0, Some(10.0)),
+            listing("mid", 250.0, None),
+        ];
+
+        let ids: Vec<_> = best_value(&listings, 3)
+            .into_iter()
+            .map(|r| r.item_id)
+            .collect();
+        assert_eq!(ids, vec!["cheap", "mid", "dear"]);
+    }
+
+    #[test]
+    fn test_best_value_skips_incomparable_labels() {
+        let mut parts = listing("parts", 20.0, None);
+        parts.label = Some(ListingLabel::ForParts);
+        let mut case = listing("case", 10.0, None);
+        case.label = Some(ListingLabel::AccessoryOnly);
+        let mut working = listing("working", 250.0, None);
+        working.label = Some(ListingLabel::Working);
+
+        let ranked = best_value(&[parts, case, working, listing("plain", 240.0, None)], 3);
+        let ids: Vec<_> = ranked.iter().map(|r| r.item_id.as_str()).collect();
+        assert_eq!(ids, vec!["plain", "working"]);
+    }
+
+    #[test]
+    fn test_seller_and_returns_break_price_ties() {
+        let mut trusted = listing("trusted", 200.0, None);
+        trusted.seller = SellerInfo {
+            username: "shop".to_string(),
+            feedback_score: 500,
+            positive_percentage: 99.5,
+        };
+        let mut returns = listing("returns", 200.0, None);
+        returns.returns_accepted = true;
+
+        let ranked = best_value(&[listing("plain", 200.0, None), returns, trusted], 3);
+        let ids: Vec<_> = ranked.iter().map(|r| r.item_id.as_str()).collect();
+        assert_eq!(ids, vec!["trusted", "returns", "plain"]);
+    }
+}
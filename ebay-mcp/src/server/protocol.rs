@@ -78,6 +78,9 @@ pub struct ToolCapability {}
 pub struct ResourceCapability {
     #[serde(default)]
     pub subscribe: bool,
+    /// Whether `notifications/resources/list_changed` is sent
+    #[serde(default, rename = "listChanged")]
+    pub list_changed: bool,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -180,10 +183,21 @@ pub struct ResourceContents {
     pub text: Option<String>,
 }
 
+/// List resources parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListResourcesParams {
+    /// Opaque cursor from a previous page's `nextCursor`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
 /// List resources result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListResourcesResult {
     pub resources: Vec<Resource>,
+    /// Cursor for the next page, absent on the last one
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Resource definition
@@ -240,6 +254,22 @@ pub struct PromptArgument {
     pub required: bool,
 }
 
+impl JsonRpcNotification {
+    /// Create notification
+    pub fn new(method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    /// Tell the client the resource list changed
+    pub fn resource_list_changed() -> Self {
+        Self::new("notifications/resources/list_changed", None)
+    }
+}
+
 impl JsonRpcResponse {
     /// Create success response
     pub fn success(id: RequestId, result: Value) -> Self {
@@ -411,7 +441,10 @@ mod tests {
             protocol_version: "2024-11-05".to_string(),
             capabilities: ServerCapabilities {
                 tools: ToolCapability {},
-                resources: ResourceCapability { subscribe: false },
+                resources: ResourceCapability {
+                    subscribe: false,
+                    list_changed: false,
+                },
                 prompts: PromptCapability {},
             },
             server_info: ServerInfo {
@@ -691,10 +724,16 @@ mod tests {
 
     #[test]
     fn test_resource_capability_with_subscribe() {
-        let capability = ResourceCapability { subscribe: true };
+        let capability = ResourceCapability {
+            subscribe: true,
+            list_changed: false,
+        };
         assert_eq!(capability.subscribe, true);
 
-        let capability2 = ResourceCapability { subscribe: false };
+        let capability2 = ResourceCapability {
+            subscribe: false,
+            list_changed: false,
+        };
         assert_eq!(capability2.subscribe, false);
     }
 
@@ -742,6 +781,50 @@ mod tests {
         let cap: ResourceCapability = serde_json::from_str(json).unwrap();
         // Default should have subscribe: false (default bool value)
         assert_eq!(cap.subscribe, false);
+        assert_eq!(cap.list_changed, false);
+    }
+
+    #[test]
+    fn test_resource_capability_list_changed_wire_name() {
+        let capability = ResourceCapability {
+            subscribe: false,
+            list_changed: true,
+        };
+        let json = serde_json::to_value(&capability).unwrap();
+        assert_eq!(json, json!({"subscribe": false, "listChanged": true}));
+    }
+
+    #[test]
+    fn test_list_resources_pagination_fields() {
+        let params: ListResourcesParams = serde_json::from_str(r#"{"cursor":"50"}"#).unwrap();
+        assert_eq!(params.cursor.as_deref(), Some("50"));
+        let params: ListResourcesParams = serde_json::from_str("{}").unwrap();
+        assert!(params.cursor.is_none());
+
+        let last_page = ListResourcesResult {
+            resources: vec![],
+            next_cursor: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&last_page).unwrap(),
+            r#"{"resources":[]}"#
+        );
+        let page = ListResourcesResult {
+            resources: vec![],
+            next_cursor: Some("50".to_string()),
+        };
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(json["nextCursor"], "50");
+    }
+
+    #[test]
+    fn test_resource_list_changed_notification() {
+        let notification = JsonRpcNotification::resource_list_changed();
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(
+            json,
+            json!({"jsonrpc": "2.0", "method": "notifications/resources/list_changed"})
+        );
     }
 
     #[test]
@@ -769,7 +852,10 @@ mod tests {
             protocol_version: "2024-11-05".to_string(),
             capabilities: ServerCapabilities {
                 tools: ToolCapability {},
-                resources: ResourceCapability { subscribe: false },
+                resources: ResourceCapability {
+                    subscribe: false,
+                    list_changed: false,
+                },
                 prompts: PromptCapability {},
             },
             server_info: ServerInfo {
@@ -1,14 +1,79 @@
 //! MCP resource definitions and handlers
+//!
+//! Besides the fixed resources, every saved search is a resource at
+//! `ebay://search/{name}` (its definition and latest results) with its price
+//! history at `ebay://search/{name}/history`. Names are percent-encoded in
+//! the URI, so spaces, unicode and slashes in them survive the round trip.
 
-use crate::error::Result;
+use crate::error::{EbayMcpError, Result};
+use crate::models::SavedSearchPhrase;
 use crate::search::SearchManager;
 use crate::server::protocol::{
-    ListResourcesResult, ReadResourceParams, ReadResourceResult, Resource, ResourceContents,
+    ListResourcesParams, ListResourcesResult, ReadResourceParams, ReadResourceResult, Resource,
+    ResourceContents,
 };
 use serde_json::json;
 use std::sync::Arc;
 use tracing::debug;
 
+/// Scheme and path every saved search resource starts with
+pub const SEARCH_URI_PREFIX: &str = "ebay://search/";
+
+/// Suffix of a saved search's price history resource
+const HISTORY_SUFFIX: &str = "/history";
+
+/// Resources returned per `resources/list` page
+pub const RESOURCES_PAGE_SIZE: usize = 50;
+
+/// A saved search resource, by the search's name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchResource {
+    /// `ebay://search/{name}`: definition and latest results
+    Search(String),
+
+    /// `ebay://search/{name}/history`: stored price history
+    History(String),
+}
+
+impl SearchResource {
+    /// Parse a saved search URI, `None` for any other URI
+    ///
+    /// Unencoded spaces and unicode are accepted as well as their
+    /// percent-encoded forms; a slash in a name must be encoded.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix(SEARCH_URI_PREFIX)?;
+        let (name, history) = match path.strip_suffix(HISTORY_SUFFIX) {
+            Some(name) => (name, true),
+            None => (path, false),
+        };
+        if name.is_empty() || name.contains('/') {
+            return None;
+        }
+
+        let name = urlencoding::decode(name).ok()?.into_owned();
+        Some(if history {
+            SearchResource::History(name)
+        } else {
+            SearchResource::Search(name)
+        })
+    }
+
+    /// URI of the resource
+    pub fn uri(&self) -> String {
+        match self {
+            SearchResource::Search(name) => {
+                format!("{}{}", SEARCH_URI_PREFIX, urlencoding::encode(name))
+            }
+            SearchResource::History(name) => format!(
+                "{}{}{}",
+                SEARCH_URI_PREFIX,
+                urlencoding::encode(name),
+                HISTORY_SUFFIX
+            ),
+        }
+    }
+}
+
 /// Resource handler for the MCP server
 pub struct ResourceHandler {
     search_manager: Arc<SearchManager>,
@@ -20,36 +85,47 @@ impl ResourceHandler {
         Self { search_manager }
     }
 
-    /// List all available resources
-    pub fn list_resources(&self) -> ListResourcesResult {
-        ListResourcesResult {
-            resources: vec![
-                Resource {
-                    uri: "ebay://config".to_string(),
-                    name: "Server Configuration".to_string(),
-                    description: "Server configuration and status".to_string(),
-                    mime_type: "application/json".to_string(),
-                },
-                Resource {
-                    uri: "ebay://phrases".to_string(),
-                    name: "Saved Search Phrases".to_string(),
-                    description: "All saved search phrases".to_string(),
-                    mime_type: "application/json".to_string(),
-                },
-                Resource {
-                    uri: "ebay://history".to_string(),
-                    name: "Search History".to_string(),
-                    description: "Recent search history".to_string(),
-                    mime_type: "application/json".to_string(),
-                },
-                Resource {
-                    uri: "ebay://stats".to_string(),
-                    name: "Server Statistics".to_string(),
-                    description: "Server statistics and metrics".to_string(),
-                    mime_type: "application/json".to_string(),
-                },
-            ],
+    /// List available resources, a page at a time
+    ///
+    /// The fixed resources come first, then two per saved search in the
+    /// order they were saved.
+    pub async fn list_resources(&self, params: ListResourcesParams) -> Result<ListResourcesResult> {
+        let mut resources = Self::fixed_resources();
+        for phrase in self.search_manager.list_phrases(None).await? {
+            resources.extend(search_resources(&phrase));
         }
+
+        paginate(resources, params.cursor.as_deref(), RESOURCES_PAGE_SIZE)
+    }
+
+    /// Resources that exist whatever searches are saved
+    fn fixed_resources() -> Vec<Resource> {
+        vec![
+            Resource {
+                uri: "ebay://config".to_string(),
+                name: "Server Configuration".to_string(),
+                description: "Server configuration and status".to_string(),
+                mime_type: "application/json".to_string(),
+            },
+            Resource {
+                uri: "ebay://phrases".to_string(),
+                name: "Saved Search Phrases".to_string(),
+                description: "All saved search phrases".to_string(),
+                mime_type: "application/json".to_string(),
+            },
+            Resource {
+                uri: "ebay://history".to_string(),
+                name: "Search History".to_string(),
+                description: "Recent search history".to_string(),
+                mime_type: "application/json".to_string(),
+            },
+            Resource {
+                uri: "ebay://stats".to_string(),
+                name: "Server Statistics".to_string(),
+                description: "Server statistics and metrics".to_string(),
+                mime_type: "application/json".to_string(),
+            },
+        ]
     }
 
     /// Read a resource
@@ -65,8 +141,18 @@ impl ResourceHandler {
                 let phrase_id = uri.strip_prefix("ebay://phrases/").unwrap();
                 self.get_phrase(phrase_id).await?
             }
+            uri if uri.starts_with(SEARCH_URI_PREFIX) => match SearchResource::parse(uri) {
+                Some(SearchResource::Search(name)) => self.get_search(&name).await?,
+                Some(SearchResource::History(name)) => self.get_search_history(&name).await?,
+                None => {
+                    return Err(EbayMcpError::InvalidInput(format!(
+                        "Malformed saved search URI: {}",
+                        params.uri
+                    )))
+                }
+            },
             _ => {
-                return Err(crate::error::EbayMcpError::InvalidInput(format!(
+                return Err(EbayMcpError::InvalidInput(format!(
                     "Unknown resource: {}",
                     params.uri
                 )))
@@ -114,6 +200,47 @@ impl ResourceHandler {
         Ok(serde_json::to_string_pretty(&phrase)?)
     }
 
+    async fn get_search(&self, name: &str) -> Result<String> {
+        let phrase = self.search_manager.get_phrase_by_name(name).await?;
+        let latest_run = self.search_manager.latest_run(&phrase.id).await?;
+        let results = self.search_manager.cached_results(&phrase).await;
+
+        let search = json!({
+            "search": phrase,
+            "latest_run": latest_run,
+            "results": results,
+        });
+
+        Ok(serde_json::to_string_pretty(&search)?)
+    }
+
+    async fn get_search_history(&self, name: &str) -> Result<String> {
+        let phrase = self.search_manager.get_phrase_by_name(name).await?;
+        let runs = self.search_manager.run_history(&phrase.id).await?;
+
+        let points: Vec<_> = runs
+            .iter()
+            .map(|run| {
+                json!({
+                    "ran_at": run.ran_at,
+                    "result_count": run.result_count,
+                    "currency": run.currency,
+                    "median_landed": run.median_landed,
+                    "cheapest_landed": run.cheapest.as_ref().map(|c| c.landed_price),
+                })
+            })
+            .collect();
+
+        let history = json!({
+            "phrase_id": phrase.id,
+            "name": phrase.name,
+            "query": phrase.query,
+            "points": points,
+        });
+
+        Ok(serde_json::to_string_pretty(&history)?)
+    }
+
     async fn get_history(&self) -> Result<String> {
         let history = self.search_manager.get_history(20, 0).await?;
         Ok(serde_json::to_string_pretty(&history)?)
@@ -137,6 +264,53 @@ impl ResourceHandler {
     }
 }
 
+/// The two resources of a saved search
+fn search_resources(phrase: &SavedSearchPhrase) -> [Resource; 2] {
+    [
+        Resource {
+            uri: SearchResource::Search(phrase.name.clone()).uri(),
+            name: phrase.name.clone(),
+            description: format!(
+                "Saved search \"{}\" and its most recent results",
+                phrase.query
+            ),
+            mime_type: "application/json".to_string(),
+        },
+        Resource {
+            uri: SearchResource::History(phrase.name.clone()).uri(),
+            name: format!("{} (price history)", phrase.name),
+            description: format!("Price history of saved search \"{}\"", phrase.query),
+            mime_type: "application/json".to_string(),
+        },
+    ]
+}
+
+/// The page of `resources` starting at `cursor`
+///
+/// Cursors are the offset of the page's first resource. One past the end,
+/// or not a number, is rejected as invalid params.
+fn paginate(
+    resources: Vec<Resource>,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Result<ListResourcesResult> {
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .ok()
+            .filter(|start| *start < resources.len())
+            .ok_or_else(|| EbayMcpError::InvalidInput(format!("Invalid cursor: {}", cursor)))?,
+    };
+    let end = (start + page_size).min(resources.len());
+    let next_cursor = (end < resources.len()).then(|| end.to_string());
+
+    Ok(ListResourcesResult {
+        resources: resources.into_iter().skip(start).take(page_size).collect(),
+        next_cursor,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +344,117 @@ mod tests {
         let phrase_id = test_uri.strip_prefix("ebay://phrases/").unwrap();
         assert_eq!(phrase_id, "test-id-123");
     }
+
+    #[test]
+    fn test_search_uri_round_trip() {
+        for name in [
+            "leica m6",
+            "Café — crème",
+            "カメラ",
+            "50/50 lens",
+            "100% cotton",
+            "a+b",
+        ] {
+            for resource in [
+                SearchResource::Search(name.to_string()),
+                SearchResource::History(name.to_string()),
+            ] {
+                let uri = resource.uri();
+                assert!(uri.is_ascii(), "{} is encoded", uri);
+                assert_eq!(SearchResource::parse(&uri), Some(resource));
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_uri_encoding() {
+        assert_eq!(
+            SearchResource::Search("leica m6".to_string()).uri(),
+            "ebay://search/leica%20m6"
+        );
+        assert_eq!(
+            SearchResource::History("café".to_string()).uri(),
+            "ebay://search/caf%C3%A9/history"
+        );
+        // A name ending in "/history" keeps its slash encoded
+        assert_eq!(
+            SearchResource::Search("old/history".to_string()).uri(),
+            "ebay://search/old%2Fhistory"
+        );
+    }
+
+    #[test]
+    fn test_search_uri_parsing() {
+        let search = |name: &str| Some(SearchResource::Search(name.to_string()));
+        let history = |name: &str| Some(SearchResource::History(name.to_string()));
+
+        // Clients may send names unencoded
+        assert_eq!(
+            SearchResource::parse("ebay://search/leica m6"),
+            search("leica m6")
+        );
+        assert_eq!(
+            SearchResource::parse("ebay://search/カメラ/history"),
+            history("カメラ")
+        );
+        assert_eq!(
+            SearchResource::parse("ebay://search/history"),
+            search("history")
+        );
+        assert_eq!(
+            SearchResource::parse("ebay://search/history/history"),
+            history("history")
+        );
+
+        assert_eq!(SearchResource::parse("ebay://search/"), None);
+        assert_eq!(SearchResource::parse("ebay://search//history"), None);
+        assert_eq!(SearchResource::parse("ebay://search/a/b"), None);
+        assert_eq!(
+            SearchResource::parse("ebay://search/a/history/history"),
+            None
+        );
+        assert_eq!(
+            SearchResource::parse("ebay://search/%FF"),
+            None,
+            "not UTF-8"
+        );
+        assert_eq!(SearchResource::parse("ebay://phrases/leica"), None);
+    }
+
+    fn resources(count: usize) -> Vec<Resource> {
+        (0..count)
+            .map(|i| Resource {
+                uri: format!("ebay://search/{}", i),
+                name: i.to_string(),
+                description: String::new(),
+                mime_type: "application/json".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_paginate() {
+        let first = paginate(resources(120), None, 50).unwrap();
+        assert_eq!(first.resources.len(), 50);
+        assert_eq!(first.next_cursor.as_deref(), Some("50"));
+
+        let last = paginate(resources(120), Some("100"), 50).unwrap();
+        assert_eq!(last.resources.len(), 20);
+        assert_eq!(last.resources[0].name, "100");
+        assert_eq!(last.next_cursor, None);
+
+        let exact = paginate(resources(50), None, 50).unwrap();
+        assert_eq!(exact.next_cursor, None);
+
+        let empty = paginate(vec![], None, 50).unwrap();
+        assert!(empty.resources.is_empty() && empty.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_paginate_rejects_bad_cursors() {
+        for cursor in ["120", "abc", "-1", ""] {
+            let err = paginate(resources(120), Some(cursor), 50).unwrap_err();
+            assert!(matches!(err, EbayMcpError::InvalidInput(_)), "{}", cursor);
+        }
+    }
 }
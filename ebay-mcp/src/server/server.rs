@@ -1,17 +1,21 @@
 //! Main MCP server implementation
 
-use crate::browser::{BrowserPool, BrowserPoolConfig};
+use crate::alerts::AlertScheduler;
+use crate::browser::{BrowserPool, BrowserPoolConfig, BrowserSession};
 use crate::config::ConfigManager;
 use crate::error::{EbayMcpError, Result};
+use crate::scraper::ListingClassifier;
 use crate::search::SearchManager;
 use crate::server::protocol::*;
 use crate::server::{ResourceHandler, ToolHandler};
-use crate::storage::{Database, ResultCache};
+use crate::storage::{Database, PageArchive, ResultCache};
+use serde::Serialize;
 use serde_json::Value;
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, error, info, warn};
 
 /// MCP server state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -27,8 +31,8 @@ pub struct EbayMcpServer {
     /// Configuration manager (kept for future use)
     _config_manager: Arc<ConfigManager>,
 
-    /// Search manager (used by handlers)
-    _search_manager: Arc<SearchManager>,
+    /// Search manager (used by handlers, and for saved search changes)
+    search_manager: Arc<SearchManager>,
 
     /// Tool handler
     tool_handler: Arc<ToolHandler>,
@@ -50,7 +54,7 @@ impl EbayMcpServer {
         // Initialize database
         info!("Initializing database: {:?}", config.database.path);
         let database = Database::new(&config.database.path)?;
-        let database = Arc::new(RwLock::new(database));
+        let database = Arc::new(Mutex::new(database));
 
         // Initialize cache
         info!("Initializing cache");
@@ -66,32 +70,84 @@ impl EbayMcpServer {
         );
         let cache = Arc::new(cache);
 
+        // Open the persistent browser session and warm it up
+        let session = if config.browser.session.enabled {
+            info!(
+                "Opening browser profile: {:?}",
+                config.browser.session.profile_dir
+            );
+            let session = BrowserSession::open(
+                &config.browser.session,
+                &config.scraper.base_url,
+                config.browser.user_agents.first().map(String::as_str),
+            )?;
+
+            // A failed warm-up only means the first search starts cold
+            if config.browser.session.warm_up {
+                if let Err(e) = session.warm_up().await {
+                    warn!("Browser session warm-up failed: {}", e);
+                }
+            }
+            Some(Arc::new(session))
+        } else {
+            None
+        };
+
         // Initialize browser pool
         info!("Initializing browser pool");
         let browser_pool_config: BrowserPoolConfig = config.browser.clone().into();
         let browser_pool = BrowserPool::new(browser_pool_config).await?;
         let browser_pool = Arc::new(browser_pool);
 
+        // Initialize listing classifier
+        info!(
+            "Loading listing classifier keywords for {:?}",
+            config.classifier.languages
+        );
+        let classifier = Arc::new(ListingClassifier::from_config(&config.classifier)?);
+
         // Initialize search manager
         info!("Initializing search manager");
         let config_manager = Arc::new(config_manager);
-        let search_manager = SearchManager::new(
+        let mut search_manager = SearchManager::new(
             config_manager.clone(),
             browser_pool.clone(),
             cache.clone(),
             database.clone(),
-        );
+        )
+        .with_classifier(classifier);
+
+        // Initialize page archive
+        if config.archive.enabled {
+            let archive = PageArchive::new(&config.archive, database.clone())?;
+            archive.enforce_retention().await?;
+            search_manager = search_manager.with_archive(Arc::new(archive));
+        }
+
         let search_manager = Arc::new(search_manager);
 
+        // Start alert scheduler
+        if config.alerts.enabled {
+            AlertScheduler::new(
+                search_manager.clone(),
+                std::time::Duration::from_secs(config.alerts.check_interval_seconds),
+            )
+            .spawn();
+        }
+
         // Initialize handlers
-        let tool_handler = Arc::new(ToolHandler::new(search_manager.clone()));
+        let mut tool_handler = ToolHandler::new(search_manager.clone());
+        if let Some(session) = session {
+            tool_handler = tool_handler.with_session(session);
+        }
+        let tool_handler = Arc::new(tool_handler);
         let resource_handler = Arc::new(ResourceHandler::new(search_manager.clone()));
 
         info!("Server initialization complete");
 
         Ok(Self {
             _config_manager: config_manager,
-            _search_manager: search_manager,
+            search_manager,
             tool_handler,
             resource_handler,
             state: Arc::new(RwLock::new(ServerState::Initializing)),
@@ -108,10 +164,29 @@ impl EbayMcpServer {
         let mut stdout = tokio::io::stdout();
         let reader = BufReader::new(stdin);
         let mut lines = reader.lines();
+        let mut phrase_changes = self.search_manager.subscribe_phrase_changes();
+        let mut watching_phrases = true;
 
         info!("Server ready, listening for requests");
 
-        while let Some(line) = lines.next_line().await? {
+        loop {
+            let line = tokio::select! {
+                line = lines.next_line() => line?,
+                change = phrase_changes.recv(), if watching_phrases => {
+                    match change {
+                        // A lagged receiver missed changes, which still means the list changed
+                        Ok(()) | Err(RecvError::Lagged(_)) => {
+                            debug!("Saved searches changed, notifying client");
+                            write_message(&mut stdout, &JsonRpcNotification::resource_list_changed())
+                                .await?;
+                        }
+                        Err(RecvError::Closed) => watching_phrases = false,
+                    }
+                    continue;
+                }
+            };
+            let Some(line) = line else { break };
+
             if *self.state.read().await == ServerState::ShuttingDown {
                 break;
             }
@@ -124,12 +199,7 @@ impl EbayMcpServer {
             debug!("Received request: {}", line);
 
             let response = self.handle_request(line).await;
-
-            // Write response to stdout
-            let response_json = serde_json::to_string(&response)?;
-            stdout.write_all(response_json.as_bytes()).await?;
-            stdout.write_all(b"\n").await?;
-            stdout.flush().await?;
+            write_message(&mut stdout, &response).await?;
 
             debug!("Sent response");
         }
@@ -167,7 +237,7 @@ impl EbayMcpServer {
             }
             "tools/list" => self.handle_tools_list().await,
             "tools/call" => self.handle_tools_call(request.params).await,
-            "resources/list" => self.handle_resources_list().await,
+            "resources/list" => self.handle_resources_list(request.params).await,
             "resources/read" => self.handle_resources_read(request.params).await,
             "prompts/list" => self.handle_prompts_list().await,
             "ping" => Ok(Value::Null),
@@ -205,7 +275,10 @@ impl EbayMcpServer {
             protocol_version: "2025-03-26".to_string(),
             capabilities: ServerCapabilities {
                 tools: ToolCapability {},
-                resources: ResourceCapability { subscribe: false },
+                resources: ResourceCapability {
+                    subscribe: false,
+                    list_changed: true,
+                },
                 prompts: PromptCapability {},
             },
             server_info: ServerInfo {
@@ -237,8 +310,14 @@ impl EbayMcpServer {
         Ok(serde_json::to_value(result)?)
     }
 
-    async fn handle_resources_list(&self) -> Result<Value> {
-        let result = self.resource_handler.list_resources();
+    async fn handle_resources_list(&self, params: Option<Value>) -> Result<Value> {
+        let params: ListResourcesParams = match params {
+            Some(v) => serde_json::from_value(v)
+                .map_err(|e| EbayMcpError::Protocol(format!("Invalid params: {}", e)))?,
+            None => ListResourcesParams::default(),
+        };
+
+        let result = self.resource_handler.list_resources(params).await?;
         Ok(serde_json::to_value(result)?)
     }
 
@@ -274,6 +353,19 @@ impl EbayMcpServer {
     }
 }
 
+/// Write one JSON-RPC message to `out` as a line
+async fn write_message<W, T>(out: &mut W, message: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let json = serde_json::to_string(message)?;
+    out.write_all(json.as_bytes()).await?;
+    out.write_all(b"\n").await?;
+    out.flush().await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,7 +486,10 @@ mod tests {
             protocol_version: "2025-03-26".to_string(),
             capabilities: ServerCapabilities {
                 tools: ToolCapability {},
-                resources: ResourceCapability { subscribe: false },
+                resources: ResourceCapability {
+                    subscribe: false,
+                    list_changed: false,
+                },
                 prompts: PromptCapability {},
             },
             server_info: ServerInfo {
@@ -467,7 +562,10 @@ mod tests {
     fn test_server_capabilities_structure() {
         let capabilities = ServerCapabilities {
             tools: ToolCapability {},
-            resources: ResourceCapability { subscribe: false },
+            resources: ResourceCapability {
+                subscribe: false,
+                list_changed: false,
+            },
             prompts: PromptCapability {},
         };
         assert_eq!(capabilities.resources.subscribe, false);
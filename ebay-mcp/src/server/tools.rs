@@ -1,7 +1,8 @@
 //! MCP tool definitions and handlers
 
+use crate::browser::BrowserSession;
 use crate::error::Result;
-use crate::models::{SavedSearchPhrase, SearchFilters};
+use crate::models::{AlertRule, ComparisonQuery, SavedSearchPhrase, SearchFilters, SearchPaging};
 use crate::search::SearchManager;
 use crate::server::protocol::{CallToolParams, CallToolResult, Content, ListToolsResult, Tool};
 use chrono::Utc;
@@ -13,12 +14,24 @@ use uuid::Uuid;
 /// Tool handler for the MCP server
 pub struct ToolHandler {
     search_manager: Arc<SearchManager>,
+
+    /// Persistent browser session (None when persistence is disabled)
+    session: Option<Arc<BrowserSession>>,
 }
 
 impl ToolHandler {
     /// Create new tool handler
     pub fn new(search_manager: Arc<SearchManager>) -> Self {
-        Self { search_manager }
+        Self {
+            search_manager,
+            session: None,
+        }
+    }
+
+    /// Report on the persistent browser session
+    pub fn with_session(mut self, session: Arc<BrowserSession>) -> Self {
+        self.session = Some(session);
+        self
     }
 
     /// List all available tools
@@ -27,12 +40,19 @@ impl ToolHandler {
             tools: vec![
                 self.search_ebay_tool(),
                 self.search_by_phrase_tool(),
+                self.compare_searches_tool(),
                 self.save_phrase_tool(),
                 self.list_phrases_tool(),
                 self.update_phrase_tool(),
                 self.delete_phrase_tool(),
                 self.get_history_tool(),
                 self.clear_cache_tool(),
+                self.create_alert_tool(),
+                self.list_alerts_tool(),
+                self.delete_alert_tool(),
+                self.pending_alerts_tool(),
+                self.portfolio_summary_tool(),
+                self.session_info_tool(),
             ],
         }
     }
@@ -44,12 +64,19 @@ impl ToolHandler {
         let result = match params.name.as_str() {
             "search_ebay" => self.handle_search_ebay(params.arguments).await,
             "search_by_phrase" => self.handle_search_by_phrase(params.arguments).await,
+            "compare_searches" => self.handle_compare_searches(params.arguments).await,
             "save_search_phrase" => self.handle_save_phrase(params.arguments).await,
             "list_search_phrases" => self.handle_list_phrases(params.arguments).await,
             "update_search_phrase" => self.handle_update_phrase(params.arguments).await,
             "delete_search_phrase" => self.handle_delete_phrase(params.arguments).await,
             "get_search_history" => self.handle_get_history(params.arguments).await,
             "clear_cache" => self.handle_clear_cache(params.arguments).await,
+            "create_alert" => self.handle_create_alert(params.arguments).await,
+            "list_alerts" => self.handle_list_alerts(params.arguments).await,
+            "delete_alert" => self.handle_delete_alert(params.arguments).await,
+            "pending_alerts" => self.handle_pending_alerts(params.arguments).await,
+            "portfolio_summary" => self.handle_portfolio_summary(params.arguments).await,
+            "session_info" => self.handle_session_info(params.arguments).await,
             _ => Err(crate::error::EbayMcpError::Protocol(format!(
                 "Unknown tool: {}",
                 params.name
@@ -78,7 +105,9 @@ impl ToolHandler {
     fn search_ebay_tool(&self) -> Tool {
         Tool {
             name: "search_ebay".to_string(),
-            description: "Search eBay with custom query and optional filters".to_string(),
+            description: "Search eBay with custom query and optional filters. Each result is \
+                labelled working, for_parts, bundle or accessory_only, with price stats per label"
+                .to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -102,13 +131,38 @@ impl ToolHandler {
                                 "items": {"type": "string"}
                             },
                             "sort_by": {"type": "string"},
-                            "free_shipping": {"type": "boolean"}
+                            "free_shipping": {"type": "boolean"},
+                            "sold": {
+                                "type": "boolean",
+                                "description": "Search sold listings instead of active ones"
+                            },
+                            "labels": {
+                                "type": "array",
+                                "description": "Keep only listings with these labels",
+                                "items": {
+                                    "type": "string",
+                                    "enum": ["working", "for_parts", "bundle", "accessory_only"]
+                                }
+                            }
                         }
                     },
                     "page": {
                         "type": "integer",
-                        "description": "Page number (default: 1)",
+                        "description": "Page number to start from (default: 1)",
+                        "default": 1
+                    },
+                    "pages": {
+                        "type": "integer",
+                        "description": "Results pages to collect, about 60 listings each; \
+                            duplicates across pages are dropped (default: 1)",
+                        "minimum": 1,
+                        "maximum": crate::scraper::MAX_PAGES_PER_CALL,
                         "default": 1
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Cursor from a previous call's results, to continue \
+                            where it stopped; the query and filters must match"
                     }
                 },
                 "required": ["query"]
@@ -138,6 +192,59 @@ impl ToolHandler {
         }
     }
 
+    fn compare_searches_tool(&self) -> Tool {
+        Tool {
+            name: "compare_searches".to_string(),
+            description: "Run 2-5 searches side by side and compare result counts, median and \
+                minimum landed price (price plus shipping), the price change between neighbouring \
+                queries and the top 3 best-value listings of each. Returns JSON with a markdown \
+                table; a query that fails is reported in its own column"
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "queries": {
+                        "type": "array",
+                        "description": "Searches to compare, in column order",
+                        "minItems": 2,
+                        "maxItems": 5,
+                        "items": {
+                            "oneOf": [
+                                {
+                                    "type": "string",
+                                    "description": "Inline search query"
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "query": {"type": "string"},
+                                        "filters": {"type": "object"},
+                                        "label": {
+                                            "type": "string",
+                                            "description": "Column heading (default: the query)"
+                                        }
+                                    },
+                                    "required": ["query"]
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "saved_search": {
+                                            "type": "string",
+                                            "description": "Saved search phrase ID or name"
+                                        }
+                                    },
+                                    "required": ["saved_search"]
+                                }
+                            ]
+                        }
+                    }
+                },
+                "required": ["queries"]
+            }),
+        }
+    }
+
     fn save_phrase_tool(&self) -> Tool {
         Tool {
             name: "save_search_phrase".to_string(),
@@ -259,6 +366,134 @@ impl ToolHandler {
         }
     }
 
+    fn create_alert_tool(&self) -> Tool {
+        Tool {
+            name: "create_alert".to_string(),
+            description: "Create an alert rule that fires when new listings for a saved search match"
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "phrase_id": {
+                        "type": "string",
+                        "description": "ID of saved search phrase to watch"
+                    },
+                    "price_max": {
+                        "type": "number",
+                        "description": "Maximum price (inclusive)"
+                    },
+                    "conditions": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Accepted item conditions"
+                    },
+                    "free_shipping_only": {
+                        "type": "boolean",
+                        "description": "Only match listings with free shipping",
+                        "default": false
+                    },
+                    "must_include": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Keywords that must all appear in the title"
+                    },
+                    "must_exclude": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Keywords that must not appear in the title"
+                    },
+                    "webhook_url": {
+                        "type": "string",
+                        "description": "Optional URL that receives a POST for new matches"
+                    }
+                },
+                "required": ["phrase_id"]
+            }),
+        }
+    }
+
+    fn list_alerts_tool(&self) -> Tool {
+        Tool {
+            name: "list_alerts".to_string(),
+            description: "List all alert rules".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    fn delete_alert_tool(&self) -> Tool {
+        Tool {
+            name: "delete_alert".to_string(),
+            description: "Delete an alert rule".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "alert_id": {
+                        "type": "string",
+                        "description": "ID of alert rule to delete"
+                    }
+                },
+                "required": ["alert_id"]
+            }),
+        }
+    }
+
+    fn pending_alerts_tool(&self) -> Tool {
+        Tool {
+            name: "pending_alerts".to_string(),
+            description: "Retrieve fired alerts that have not been acknowledged".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "acknowledge": {
+                        "type": "boolean",
+                        "description": "Mark returned alerts as acknowledged (default: true)",
+                        "default": true
+                    }
+                }
+            }),
+        }
+    }
+
+    fn portfolio_summary_tool(&self) -> Tool {
+        Tool {
+            name: "portfolio_summary".to_string(),
+            description: "Overview of every saved search from its latest stored run: the \
+                cheapest listing, whether the median landed price rose or fell over 30 days, \
+                the average discount of accepted Best Offers, listings new this week and \
+                pending alerts. Returns JSON with a markdown table \
+                sorted by biggest price drop; searches never run are listed as such"
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "refresh": {
+                        "type": "boolean",
+                        "description": "Run every saved search first instead of only reading stored runs (default: false)",
+                        "default": false
+                    }
+                }
+            }),
+        }
+    }
+
+    fn session_info_tool(&self) -> Tool {
+        Tool {
+            name: "session_info".to_string(),
+            description: "State of the persistent browser session: how many cookies it holds \
+                and how old they are, whether they were restored from the previous run, \
+                whether the signed-in state survived the restart, and any repairs made to \
+                the profile after a crash"
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
     // Tool handlers
 
     async fn handle_search_ebay(&self, args: Value) -> Result<String> {
@@ -274,7 +509,28 @@ impl ToolHandler {
             .get("filters")
             .and_then(|v| serde_json::from_value(v.clone()).ok());
 
-        let results = self.search_manager.search(&query, filters).await?;
+        let mut paging = SearchPaging::default();
+        if let Some(page) = args.get("page").and_then(|v| v.as_u64()) {
+            paging.page = (page as usize).max(1);
+        }
+        if let Some(pages) = args.get("pages").and_then(|v| v.as_u64()) {
+            if pages == 0 || pages as usize > crate::scraper::MAX_PAGES_PER_CALL {
+                return Err(crate::error::EbayMcpError::InvalidInput(format!(
+                    "pages must be between 1 and {}",
+                    crate::scraper::MAX_PAGES_PER_CALL
+                )));
+            }
+            paging.pages = pages as usize;
+        }
+        paging.cursor = args
+            .get("cursor")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let results = self
+            .search_manager
+            .search_paged(&query, filters, paging)
+            .await?;
 
         Ok(serde_json::to_string_pretty(&results)?)
     }
@@ -293,6 +549,47 @@ impl ToolHandler {
         Ok(serde_json::to_string_pretty(&results)?)
     }
 
+    async fn handle_compare_searches(&self, args: Value) -> Result<String> {
+        let items = args
+            .get("queries")
+            .and_then(|v| v.as_array())
+            .ok_or(crate::error::EbayMcpError::InvalidInput(
+                "Missing queries".to_string(),
+            ))?;
+        crate::search::compare::validate_query_count(items.len())?;
+
+        let mut queries = Vec::with_capacity(items.len());
+        for item in items {
+            let query = if let Some(query) = item.as_str() {
+                ComparisonQuery::inline(query)
+            } else if let Some(saved) = item.get("saved_search").and_then(|v| v.as_str()) {
+                self.search_manager.comparison_query_for_phrase(saved).await?
+            } else if let Some(query) = item.get("query").and_then(|v| v.as_str()) {
+                let mut query = ComparisonQuery::inline(query);
+                if let Some(label) = item.get("label").and_then(|v| v.as_str()) {
+                    query.label = label.to_string();
+                }
+                query.filters = item
+                    .get("filters")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default();
+                query
+            } else {
+                return Err(crate::error::EbayMcpError::InvalidInput(
+                    "Each query must be a string, {query} or {saved_search}".to_string(),
+                ));
+            };
+            queries.push(query);
+        }
+
+        let comparison = self.search_manager.compare(queries).await?;
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "columns": comparison.columns,
+            "markdown": comparison.to_markdown(),
+        }))?)
+    }
+
     async fn handle_save_phrase(&self, args: Value) -> Result<String> {
         let name: String = args
             .get("name")
@@ -427,6 +724,107 @@ impl ToolHandler {
         })
         .to_string())
     }
+
+    async fn handle_create_alert(&self, args: Value) -> Result<String> {
+        let phrase_id: String = args
+            .get("phrase_id")
+            .and_then(|v| v.as_str())
+            .ok_or(crate::error::EbayMcpError::InvalidInput(
+                "Missing phrase_id".to_string(),
+            ))?
+            .to_string();
+
+        let string_list = |key: &str| -> Vec<String> {
+            args.get(key)
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default()
+        };
+
+        let rule = AlertRule {
+            id: Uuid::new_v4().to_string(),
+            phrase_id,
+            price_max: args.get("price_max").and_then(|v| v.as_f64()),
+            conditions: string_list("conditions"),
+            free_shipping_only: args
+                .get("free_shipping_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            must_include: string_list("must_include"),
+            must_exclude: string_list("must_exclude"),
+            webhook_url: args
+                .get("webhook_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            created_at: Utc::now(),
+        };
+
+        let alert_id = self.search_manager.create_alert(rule).await?;
+
+        Ok(json!({
+            "alert_id": alert_id,
+            "message": "Alert rule created successfully"
+        })
+        .to_string())
+    }
+
+    async fn handle_list_alerts(&self, _args: Value) -> Result<String> {
+        let rules = self.search_manager.list_alerts().await?;
+
+        Ok(serde_json::to_string_pretty(&rules)?)
+    }
+
+    async fn handle_delete_alert(&self, args: Value) -> Result<String> {
+        let alert_id: String = args
+            .get("alert_id")
+            .and_then(|v| v.as_str())
+            .ok_or(crate::error::EbayMcpError::InvalidInput(
+                "Missing alert_id".to_string(),
+            ))?
+            .to_string();
+
+        self.search_manager.delete_alert(&alert_id).await?;
+
+        Ok(json!({
+            "message": "Alert rule deleted successfully"
+        })
+        .to_string())
+    }
+
+    async fn handle_pending_alerts(&self, args: Value) -> Result<String> {
+        let acknowledge = args
+            .get("acknowledge")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let alerts = self.search_manager.pending_alerts(acknowledge).await?;
+
+        Ok(serde_json::to_string_pretty(&alerts)?)
+    }
+
+    async fn handle_portfolio_summary(&self, args: Value) -> Result<String> {
+        let refresh = args
+            .get("refresh")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let summary = self.search_manager.portfolio_summary(refresh).await?;
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "generated_at": summary.generated_at,
+            "searches": summary.searches,
+            "markdown": summary.to_markdown(),
+        }))?)
+    }
+
+    async fn handle_session_info(&self, _args: Value) -> Result<String> {
+        let session = self.session.as_ref().ok_or_else(|| {
+            crate::error::EbayMcpError::Config(
+                "Browser session persistence is disabled (browser.session.enabled)".to_string(),
+            )
+        })?;
+
+        Ok(serde_json::to_string_pretty(&session.info())?)
+    }
 }
 
 #[cfg(test)]
@@ -911,12 +1309,19 @@ mod tests {
         let expected_tools = vec![
             "search_ebay",
             "search_by_phrase",
+            "compare_searches",
             "save_search_phrase",
             "list_search_phrases",
             "update_search_phrase",
             "delete_search_phrase",
             "get_search_history",
             "clear_cache",
+            "create_alert",
+            "list_alerts",
+            "delete_alert",
+            "pending_alerts",
+            "portfolio_summary",
+            "session_info",
         ];
 
         for tool_name in expected_tools {
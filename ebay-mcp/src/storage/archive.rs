@@ -0,0 +1,308 @@
+//! Raw page archive for building a scraper regression corpus
+//!
+//! Pages are stored gzip-compressed on disk, named by the SHA-256 of their
+//! HTML, so identical pages are only written once. The index (URL, search
+//! phrase, capture time, extraction output) lives in the database.
+
+use crate::error::Result;
+use crate::models::{ArchiveConfig, PageKind, PageSnapshot, SnapshotQuery};
+use crate::storage::Database;
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Compressed page archive with a database-backed index
+pub struct PageArchive {
+    /// Directory holding `<hash>.html.gz` files
+    dir: PathBuf,
+
+    /// Maximum snapshot age (None keeps snapshots forever)
+    retention: Option<chrono::Duration>,
+
+    /// Maximum total compressed size (None means no cap)
+    max_bytes: Option<u64>,
+
+    /// Snapshot index
+    database: Arc<Mutex<Database>>,
+}
+
+impl PageArchive {
+    /// Create archive, creating the archive directory if needed
+    pub fn new(config: &ArchiveConfig, database: Arc<Mutex<Database>>) -> Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+
+        info!(
+            "Initializing page archive at {} (retention: {} days, max size: {} MB)",
+            config.dir.display(),
+            config.retention_days,
+            config.max_size_mb
+        );
+
+        Ok(Self {
+            dir: config.dir.clone(),
+            retention: (config.retention_days > 0)
+                .then(|| chrono::Duration::days(config.retention_days as i64)),
+            max_bytes: (config.max_size_mb > 0).then(|| config.max_size_mb * 1024 * 1024),
+            database,
+        })
+    }
+
+    /// Archive a page and its extraction output, returning the snapshot ID
+    pub async fn archive_page(
+        &self,
+        kind: PageKind,
+        url: &str,
+        search_phrase: Option<&str>,
+        html: &str,
+        extracted: &serde_json::Value,
+    ) -> Result<i64> {
+        let html_hash = Self::hash_html(html);
+        let path = self.path_for(&html_hash);
+
+        let compressed_bytes = if path.exists() {
+            debug!("Page {} already archived, reusing file", html_hash);
+            std::fs::metadata(&path)?.len()
+        } else {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(html.as_bytes())?;
+            let compressed = encoder.finish()?;
+            std::fs::write(&path, &compressed)?;
+            compressed.len() as u64
+        };
+
+        let snapshot = PageSnapshot {
+            id: 0,
+            kind,
+            url: url.to_string(),
+            search_phrase: search_phrase.map(str::to_string),
+            html_hash,
+            compressed_bytes,
+            captured_at: Utc::now(),
+            extracted: extracted.clone(),
+        };
+
+        let id = self.database.lock().await.add_page_snapshot(&snapshot)?;
+        self.enforce_retention().await?;
+
+        Ok(id)
+    }
+
+    /// Load and decompress the archived HTML for a hash
+    pub fn load_html(&self, html_hash: &str) -> Result<String> {
+        let compressed = std::fs::read(self.path_for(html_hash))?;
+
+        let mut html = String::new();
+        GzDecoder::new(compressed.as_slice()).read_to_string(&mut html)?;
+
+        Ok(html)
+    }
+
+    /// Get a snapshot by ID
+    pub async fn get(&self, id: i64) -> Result<Option<PageSnapshot>> {
+        self.database.lock().await.get_page_snapshot(id)
+    }
+
+    /// Query snapshots by date and search phrase
+    pub async fn query(&self, query: &SnapshotQuery) -> Result<Vec<PageSnapshot>> {
+        self.database.lock().await.query_page_snapshots(query)
+    }
+
+    /// Drop snapshots past the retention window, then the oldest ones until
+    /// the archive fits the size cap. Returns the number of files removed.
+    pub async fn enforce_retention(&self) -> Result<usize> {
+        let db = self.database.lock().await;
+        let mut released = Vec::new();
+
+        if let Some(retention) = self.retention {
+            released.extend(db.delete_page_snapshots_before(Utc::now() - retention)?);
+        }
+
+        if let Some(max_bytes) = self.max_bytes {
+            while db.page_snapshot_bytes()? > max_bytes {
+                match db.delete_oldest_page_snapshot()? {
+                    Some(hash) => released.push(hash),
+                    None => break,
+                }
+            }
+        }
+
+        released.sort();
+        released.dedup();
+
+        let mut removed = 0;
+        for hash in released {
+            if db.page_snapshot_hash_in_use(&hash)? {
+                continue;
+            }
+
+            match std::fs::remove_file(self.path_for(&hash)) {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to remove archived page {}: {}", hash, e),
+            }
+        }
+
+        if removed > 0 {
+            info!("Removed {} archived pages", removed);
+        }
+
+        Ok(removed)
+    }
+
+    /// Archive directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Hex SHA-256 of the raw HTML
+    fn hash_html(html: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(html.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// On-disk path for a hash
+    fn path_for(&self, html_hash: &str) -> PathBuf {
+        self.dir.join(format!("{}.html.gz", html_hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::{NamedTempFile, TempDir};
+
+    fn test_archive(
+        retention_days: u32,
+        max_size_mb: u64,
+    ) -> (PageArchive, TempDir, NamedTempFile) {
+        let dir = TempDir::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+        let database = Arc::new(Mutex::new(Database::new(db_file.path()).unwrap()));
+
+        let config = ArchiveConfig {
+            enabled: true,
+            dir: dir.path().join("archive"),
+            retention_days,
+            max_size_mb,
+        };
+
+        (PageArchive::new(&config, database).unwrap(), dir, db_file)
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_load_page() {
+        let (archive, _dir, _db) = test_archive(0, 0);
+        let html = "<html><body>Canon AE-1</body></html>";
+        let extracted = serde_json::json!([{"title": "Canon AE-1"}]);
+
+        let id = archive
+            .archive_page(
+                PageKind::Results,
+                "https://ebay.com/sch",
+                Some("canon"),
+                html,
+                &extracted,
+            )
+            .await
+            .unwrap();
+
+        let snapshot = archive.get(id).await.unwrap().unwrap();
+        assert_eq!(snapshot.search_phrase.as_deref(), Some("canon"));
+        assert_eq!(snapshot.extracted, extracted);
+        assert_eq!(archive.load_html(&snapshot.html_hash).unwrap(), html);
+        assert!(archive
+            .dir()
+            .join(format!("{}.html.gz", snapshot.html_hash))
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_identical_pages_share_file() {
+        let (archive, _dir, _db) = test_archive(0, 0);
+        let extracted = serde_json::json!([]);
+
+        let first = archive
+            .archive_page(
+                PageKind::Results,
+                "https://ebay.com/a",
+                None,
+                "<p>same</p>",
+                &extracted,
+            )
+            .await
+            .unwrap();
+        let second = archive
+            .archive_page(
+                PageKind::Results,
+                "https://ebay.com/b",
+                None,
+                "<p>same</p>",
+                &extracted,
+            )
+            .await
+            .unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_dir(archive.dir()).unwrap().count(), 1);
+        assert_eq!(
+            archive
+                .query(&SnapshotQuery::default())
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_size_cap_evicts_oldest() {
+        let (mut archive, _dir, _db) = test_archive(0, 0);
+        // Cap small enough that only one incompressible page fits
+        archive.max_bytes = Some(3000);
+
+        let page = |mut seed: u64| {
+            (0..4000)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    char::from(b'a' + (seed % 26) as u8)
+                })
+                .collect::<String>()
+        };
+        let extracted = serde_json::json!([]);
+
+        archive
+            .archive_page(
+                PageKind::Detail,
+                "https://ebay.com/itm/1",
+                None,
+                &page(1),
+                &extracted,
+            )
+            .await
+            .unwrap();
+        let newest = archive
+            .archive_page(
+                PageKind::Detail,
+                "https://ebay.com/itm/2",
+                None,
+                &page(2),
+                &extracted,
+            )
+            .await
+            .unwrap();
+
+        let remaining = archive.query(&SnapshotQuery::default()).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, newest);
+        assert_eq!(std::fs::read_dir(archive.dir()).unwrap().count(), 1);
+    }
+}
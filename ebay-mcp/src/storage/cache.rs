@@ -196,6 +196,7 @@ mod tests {
                 item_id: format!("ITEM{}", i),
                 title: format!("Test Item {}", i),
                 price: Price::usd(10.0 + i as f64),
+                price_max: None,
                 shipping: None,
                 condition: "New".to_string(),
                 format: BuyingFormat::BuyItNow,
@@ -211,6 +212,8 @@ mod tests {
                 time_left: None,
                 free_shipping: false,
                 returns_accepted: true,
+                label: None,
+                best_offer: None,
             })
             .collect();
 
@@ -223,6 +226,10 @@ mod tests {
             total_pages: 1,
             searched_at: Utc::now(),
             duration: StdDuration::from_millis(100),
+            pages_fetched: 1,
+            cursor: None,
+            warnings: vec![],
+            price_stats: Default::default(),
         }
     }
 
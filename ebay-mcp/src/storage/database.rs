@@ -1,9 +1,12 @@
 //! Database management and operations
 
 use crate::error::Result;
-use crate::models::SearchHistoryEntry;
+use crate::models::{
+    AlertMatch, AlertRule, PageSnapshot, SearchHistoryEntry, SearchRun, SnapshotQuery,
+};
 use chrono::{DateTime, Utc};
 use rusqlite::Connection;
+use std::collections::HashMap;
 use std::path::Path;
 use tracing::{debug, info};
 
@@ -80,12 +83,82 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_metrics_name
                 ON metrics(metric_name, recorded_at DESC);
 
+            -- Alert rules
+            CREATE TABLE IF NOT EXISTS alert_rules (
+                id TEXT PRIMARY KEY,
+                phrase_id TEXT NOT NULL,
+                rule_json TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+
+            -- Fired alerts
+            CREATE TABLE IF NOT EXISTS alert_matches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                rule_id TEXT NOT NULL,
+                item_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                price REAL NOT NULL,
+                listing_url TEXT NOT NULL,
+                matched_at TEXT NOT NULL,
+                acknowledged BOOLEAN DEFAULT FALSE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_alert_matches_item
+                ON alert_matches(rule_id, item_id);
+
+            -- Archived page snapshots (compressed HTML lives on disk, named by hash)
+            CREATE TABLE IF NOT EXISTS page_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                url TEXT NOT NULL,
+                search_phrase TEXT,
+                html_hash TEXT NOT NULL,
+                compressed_bytes INTEGER NOT NULL,
+                captured_at TEXT NOT NULL,
+                extracted_json TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_page_snapshots_date
+                ON page_snapshots(captured_at DESC);
+            CREATE INDEX IF NOT EXISTS idx_page_snapshots_phrase
+                ON page_snapshots(search_phrase);
+            CREATE INDEX IF NOT EXISTS idx_page_snapshots_hash
+                ON page_snapshots(html_hash);
+
+            -- Stored runs of saved searches (the price history)
+            CREATE TABLE IF NOT EXISTS search_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                phrase_id TEXT NOT NULL,
+                ran_at TEXT NOT NULL,
+                result_count INTEGER NOT NULL,
+                currency TEXT,
+                median_landed REAL,
+                cheapest_json TEXT,
+                new_listings INTEGER NOT NULL DEFAULT 0,
+                offers_json TEXT,
+                UNIQUE (phrase_id, ran_at)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_search_runs_phrase
+                ON search_runs(phrase_id, ran_at DESC);
+
+            -- Listings each saved search has returned, with when they first appeared
+            CREATE TABLE IF NOT EXISTS seen_listings (
+                phrase_id TEXT NOT NULL,
+                item_id TEXT NOT NULL,
+                first_seen TEXT NOT NULL,
+                PRIMARY KEY (phrase_id, item_id)
+            );
+
             -- Enable WAL mode for better concurrency
             PRAGMA journal_mode=WAL;
             PRAGMA cache_size=-64000;  -- 64MB cache
             "#,
         )?;
 
+        // Columns added since the tables were first created
+        self.add_column_if_missing("search_runs", "offers_json", "TEXT")?;
+
         info!("Database schema initialized");
 
         Ok(())
@@ -203,11 +276,462 @@ impl Database {
 
         Ok(deleted)
     }
+
+    /// Store an alert rule
+    pub fn save_alert_rule(&self, rule: &AlertRule) -> Result<()> {
+        let rule_json = serde_json::to_string(rule)?;
+
+        self.conn.execute(
+            "INSERT INTO alert_rules (id, phrase_id, rule_json) VALUES (?1, ?2, ?3)",
+            (&rule.id, &rule.phrase_id, rule_json),
+        )?;
+
+        debug!("Saved alert rule {} for phrase {}", rule.id, rule.phrase_id);
+
+        Ok(())
+    }
+
+    /// Get all alert rules
+    pub fn get_alert_rules(&self) -> Result<Vec<AlertRule>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT rule_json FROM alert_rules ORDER BY created_at")?;
+
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        rows.iter()
+            .map(|json| serde_json::from_str(json).map_err(Into::into))
+            .collect()
+    }
+
+    /// Delete an alert rule and its recorded matches
+    pub fn delete_alert_rule(&self, rule_id: &str) -> Result<bool> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM alert_rules WHERE id = ?1", [rule_id])?;
+        self.conn
+            .execute("DELETE FROM alert_matches WHERE rule_id = ?1", [rule_id])?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Get the lowest price each item has already fired at for a rule
+    pub fn get_alerted_prices(&self, rule_id: &str) -> Result<HashMap<String, f64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT item_id, MIN(price) FROM alert_matches WHERE rule_id = ?1 GROUP BY item_id",
+        )?;
+
+        let prices = stmt
+            .query_map([rule_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+
+        Ok(prices)
+    }
+
+    /// Record a fired alert
+    pub fn record_alert_match(&self, alert: &AlertMatch) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO alert_matches (rule_id, item_id, title, price, listing_url, matched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                &alert.rule_id,
+                &alert.item_id,
+                &alert.title,
+                alert.price,
+                &alert.listing_url,
+                alert.matched_at.to_rfc3339(),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    /// Get alerts that have not been acknowledged yet, oldest first
+    pub fn get_pending_alerts(&self) -> Result<Vec<AlertMatch>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rule_id, item_id, title, price, listing_url, matched_at
+             FROM alert_matches
+             WHERE acknowledged = FALSE
+             ORDER BY id",
+        )?;
+
+        let alerts = stmt
+            .query_map([], |row| {
+                let matched_at: String = row.get(5)?;
+                let matched_at = DateTime::parse_from_rfc3339(&matched_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            5,
+                            rusqlite::types::Type::Text,
+                            Box::new(e),
+                        )
+                    })?;
+
+                Ok(AlertMatch {
+                    rule_id: row.get(0)?,
+                    item_id: row.get(1)?,
+                    title: row.get(2)?,
+                    price: row.get(3)?,
+                    listing_url: row.get(4)?,
+                    matched_at,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(alerts)
+    }
+
+    /// Mark all pending alerts as acknowledged
+    pub fn acknowledge_alerts(&self) -> Result<usize> {
+        let updated = self.conn.execute(
+            "UPDATE alert_matches SET acknowledged = TRUE WHERE acknowledged = FALSE",
+            [],
+        )?;
+
+        Ok(updated)
+    }
+
+    /// Record listings returned by a saved search, returning how many are new
+    ///
+    /// The first results stored for a phrase are its baseline and count as
+    /// none new.
+    pub fn mark_listings_seen<'a>(
+        &self,
+        phrase_id: &str,
+        item_ids: impl IntoIterator<Item = &'a str>,
+        seen_at: DateTime<Utc>,
+    ) -> Result<usize> {
+        let known: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM seen_listings WHERE phrase_id = ?1",
+            [phrase_id],
+            |row| row.get(0),
+        )?;
+
+        let seen_at = seen_at.to_rfc3339();
+        let mut stmt = self.conn.prepare(
+            "INSERT OR IGNORE INTO seen_listings (phrase_id, item_id, first_seen)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        let mut inserted = 0;
+        for item_id in item_ids {
+            inserted += stmt.execute((phrase_id, item_id, &seen_at))?;
+        }
+
+        Ok(if known == 0 { 0 } else { inserted })
+    }
+
+    /// Add a column to a table created by an older version
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let exists = self
+            .conn
+            .prepare(&format!(
+                "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1",
+                table
+            ))?
+            .exists([column])?;
+        if !exists {
+            self.conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Store a saved search run
+    ///
+    /// A run with the same phrase and time as a stored one (cached results
+    /// served again) is ignored. Returns whether the run was stored.
+    pub fn add_search_run(&self, run: &SearchRun) -> Result<bool> {
+        let cheapest_json = run
+            .cheapest
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let offers_json = run
+            .accepted_offers
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO search_runs
+                (phrase_id, ran_at, result_count, currency, median_landed, cheapest_json, new_listings,
+                 offers_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            (
+                &run.phrase_id,
+                run.ran_at.to_rfc3339(),
+                run.result_count as i64,
+                &run.currency,
+                run.median_landed,
+                cheapest_json,
+                run.new_listings as i64,
+                offers_json,
+            ),
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Most recent stored run of a saved search
+    pub fn get_latest_search_run(&self, phrase_id: &str) -> Result<Option<SearchRun>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT phrase_id, ran_at, result_count, currency, median_landed, cheapest_json, new_listings,
+                    offers_json
+             FROM search_runs
+             WHERE phrase_id = ?1
+             ORDER BY ran_at DESC
+             LIMIT 1",
+        )?;
+
+        let mut rows = stmt.query_map([phrase_id], row_to_search_run)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Stored runs of a saved search since `since`, oldest first
+    pub fn get_search_runs_since(
+        &self,
+        phrase_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SearchRun>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT phrase_id, ran_at, result_count, currency, median_landed, cheapest_json, new_listings,
+                    offers_json
+             FROM search_runs
+             WHERE phrase_id = ?1 AND ran_at >= ?2
+             ORDER BY ran_at",
+        )?;
+
+        let runs = stmt
+            .query_map((phrase_id, since.to_rfc3339()), row_to_search_run)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(runs)
+    }
+
+    /// Every stored run of a saved search, oldest first
+    pub fn get_search_runs(&self, phrase_id: &str) -> Result<Vec<SearchRun>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT phrase_id, ran_at, result_count, currency, median_landed, cheapest_json, new_listings,
+                    offers_json
+             FROM search_runs
+             WHERE phrase_id = ?1
+             ORDER BY ran_at",
+        )?;
+
+        let runs = stmt
+            .query_map([phrase_id], row_to_search_run)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(runs)
+    }
+
+    /// Add a page snapshot to the archive index, returning its ID
+    pub fn add_page_snapshot(&self, snapshot: &PageSnapshot) -> Result<i64> {
+        let extracted_json = serde_json::to_string(&snapshot.extracted)?;
+
+        self.conn.execute(
+            "INSERT INTO page_snapshots
+                (kind, url, search_phrase, html_hash, compressed_bytes, captured_at, extracted_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                snapshot.kind.as_str(),
+                &snapshot.url,
+                &snapshot.search_phrase,
+                &snapshot.html_hash,
+                snapshot.compressed_bytes as i64,
+                snapshot.captured_at.to_rfc3339(),
+                extracted_json,
+            ),
+        )?;
+
+        let id = self.conn.last_insert_rowid();
+        debug!("Indexed page snapshot {} ({})", id, snapshot.html_hash);
+
+        Ok(id)
+    }
+
+    /// Get a page snapshot by ID
+    pub fn get_page_snapshot(&self, id: i64) -> Result<Option<PageSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, url, search_phrase, html_hash, compressed_bytes, captured_at, extracted_json
+             FROM page_snapshots
+             WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map([id], row_to_page_snapshot)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Query page snapshots by capture date, search phrase, and kind, newest first
+    pub fn query_page_snapshots(&self, query: &SnapshotQuery) -> Result<Vec<PageSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, url, search_phrase, html_hash, compressed_bytes, captured_at, extracted_json
+             FROM page_snapshots
+             WHERE (?1 IS NULL OR search_phrase LIKE '%' || ?1 || '%')
+               AND (?2 IS NULL OR captured_at >= ?2)
+               AND (?3 IS NULL OR captured_at <= ?3)
+               AND (?4 IS NULL OR kind = ?4)
+             ORDER BY captured_at DESC, id DESC
+             LIMIT ?5",
+        )?;
+
+        let limit = query.limit.map_or(-1, |l| l as i64);
+        let snapshots = stmt
+            .query_map(
+                (
+                    &query.phrase,
+                    query.since.map(|d| d.to_rfc3339()),
+                    query.until.map(|d| d.to_rfc3339()),
+                    query.kind.map(|k| k.as_str()),
+                    limit,
+                ),
+                row_to_page_snapshot,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(snapshots)
+    }
+
+    /// Delete snapshots captured before `cutoff`, returning their HTML hashes
+    pub fn delete_page_snapshots_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>> {
+        let cutoff = cutoff.to_rfc3339();
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT html_hash FROM page_snapshots WHERE captured_at < ?1")?;
+        let hashes = stmt
+            .query_map([&cutoff], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        self.conn.execute(
+            "DELETE FROM page_snapshots WHERE captured_at < ?1",
+            [&cutoff],
+        )?;
+
+        Ok(hashes)
+    }
+
+    /// Delete the oldest snapshot, returning its HTML hash
+    pub fn delete_oldest_page_snapshot(&self) -> Result<Option<String>> {
+        let oldest = self.conn.query_row(
+            "SELECT id, html_hash FROM page_snapshots ORDER BY captured_at, id LIMIT 1",
+            [],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        );
+
+        let (id, hash) = match oldest {
+            Ok(row) => row,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        self.conn
+            .execute("DELETE FROM page_snapshots WHERE id = ?1", [id])?;
+
+        Ok(Some(hash))
+    }
+
+    /// Total compressed size of archived pages (each file counted once)
+    pub fn page_snapshot_bytes(&self) -> Result<u64> {
+        let bytes: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(compressed_bytes), 0)
+             FROM (SELECT DISTINCT html_hash, compressed_bytes FROM page_snapshots)",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(bytes as u64)
+    }
+
+    /// Whether any snapshot still references the given HTML hash
+    pub fn page_snapshot_hash_in_use(&self, html_hash: &str) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM page_snapshots WHERE html_hash = ?1",
+            [html_hash],
+            |row| row.get(0),
+        )?;
+
+        Ok(count > 0)
+    }
+}
+
+/// Map a `search_runs` row to a run
+fn row_to_search_run(row: &rusqlite::Row<'_>) -> rusqlite::Result<SearchRun> {
+    let conversion_error = |idx: usize, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, e)
+    };
+
+    let ran_at: String = row.get(1)?;
+    let ran_at = DateTime::parse_from_rfc3339(&ran_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| conversion_error(1, Box::new(e)))?;
+
+    let cheapest = row
+        .get::<_, Option<String>>(5)?
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| conversion_error(5, Box::new(e)))?;
+
+    let accepted_offers = row
+        .get::<_, Option<String>>(7)?
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| conversion_error(7, Box::new(e)))?;
+
+    Ok(SearchRun {
+        phrase_id: row.get(0)?,
+        ran_at,
+        result_count: row.get::<_, i64>(2)? as usize,
+        currency: row.get(3)?,
+        median_landed: row.get(4)?,
+        cheapest,
+        new_listings: row.get::<_, i64>(6)? as usize,
+        accepted_offers,
+    })
+}
+
+/// Map a `page_snapshots` row to a snapshot
+fn row_to_page_snapshot(row: &rusqlite::Row<'_>) -> rusqlite::Result<PageSnapshot> {
+    let conversion_error = |idx: usize, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, e)
+    };
+
+    let kind: String = row.get(1)?;
+    let kind = kind
+        .parse()
+        .map_err(|e: String| conversion_error(1, e.into()))?;
+
+    let captured_at: String = row.get(6)?;
+    let captured_at = DateTime::parse_from_rfc3339(&captured_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| conversion_error(6, Box::new(e)))?;
+
+    let extracted: String = row.get(7)?;
+    let extracted =
+        serde_json::from_str(&extracted).map_err(|e| conversion_error(7, Box::new(e)))?;
+
+    Ok(PageSnapshot {
+        id: row.get(0)?,
+        kind,
+        url: row.get(2)?,
+        search_phrase: row.get(3)?,
+        html_hash: row.get(4)?,
+        compressed_bytes: row.get::<_, i64>(5)? as u64,
+        captured_at,
+        extracted,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{OfferStats, PageKind};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -758,4 +1282,303 @@ mod tests {
         let result = Database::new(path);
         assert!(result.is_ok());
     }
+
+    fn test_alert_rule(id: &str) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            phrase_id: "vintage-cameras".to_string(),
+            price_max: Some(150.0),
+            conditions: vec![],
+            free_shipping_only: true,
+            must_include: vec!["canon".to_string()],
+            must_exclude: vec![],
+            webhook_url: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn test_alert_match(rule_id: &str, item_id: &str, price: f64) -> AlertMatch {
+        AlertMatch {
+            rule_id: rule_id.to_string(),
+            item_id: item_id.to_string(),
+            title: "Canon AE-1".to_string(),
+            price,
+            listing_url: format!("https://ebay.com/itm/{}", item_id),
+            matched_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_alert_rule_roundtrip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        db.save_alert_rule(&test_alert_rule("rule-1")).unwrap();
+
+        let rules = db.get_alert_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].price_max, Some(150.0));
+        assert!(rules[0].free_shipping_only);
+
+        assert!(db.delete_alert_rule("rule-1").unwrap());
+        assert!(!db.delete_alert_rule("rule-1").unwrap());
+        assert!(db.get_alert_rules().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_alerted_prices_track_lowest_price() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        db.record_alert_match(&test_alert_match("rule-1", "item-1", 140.0))
+            .unwrap();
+        db.record_alert_match(&test_alert_match("rule-1", "item-1", 120.0))
+            .unwrap();
+        db.record_alert_match(&test_alert_match("rule-2", "item-1", 100.0))
+            .unwrap();
+
+        let prices = db.get_alerted_prices("rule-1").unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices["item-1"], 120.0);
+    }
+
+    #[test]
+    fn test_pending_alerts_acknowledge() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        db.record_alert_match(&test_alert_match("rule-1", "item-1", 140.0))
+            .unwrap();
+        db.record_alert_match(&test_alert_match("rule-1", "item-2", 90.0))
+            .unwrap();
+
+        let pending = db.get_pending_alerts().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].item_id, "item-1");
+
+        assert_eq!(db.acknowledge_alerts().unwrap(), 2);
+        assert!(db.get_pending_alerts().unwrap().is_empty());
+
+        // Acknowledged alerts still suppress re-firing
+        assert_eq!(db.get_alerted_prices("rule-1").unwrap().len(), 2);
+    }
+
+    fn test_search_run(phrase_id: &str, days_ago: i64, median: f64) -> SearchRun {
+        SearchRun {
+            phrase_id: phrase_id.to_string(),
+            ran_at: Utc::now() - chrono::Duration::days(days_ago),
+            result_count: 10,
+            currency: Some("USD".to_string()),
+            median_landed: Some(median),
+            cheapest: None,
+            new_listings: 0,
+            accepted_offers: None,
+        }
+    }
+
+    #[test]
+    fn test_search_runs_latest_and_since() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        assert!(db.get_latest_search_run("leica").unwrap().is_none());
+
+        let old = test_search_run("leica", 40, 2400.0);
+        assert!(db.add_search_run(&old).unwrap());
+        // Cached results served again are not a new run
+        assert!(!db.add_search_run(&old).unwrap());
+        db.add_search_run(&test_search_run("leica", 10, 2200.0))
+            .unwrap();
+        db.add_search_run(&test_search_run("leica", 1, 2000.0))
+            .unwrap();
+        db.add_search_run(&test_search_run("hasselblad", 0, 900.0))
+            .unwrap();
+
+        let latest = db.get_latest_search_run("leica").unwrap().unwrap();
+        assert_eq!(latest.median_landed, Some(2000.0));
+
+        let since = Utc::now() - chrono::Duration::days(30);
+        let medians: Vec<_> = db
+            .get_search_runs_since("leica", since)
+            .unwrap()
+            .iter()
+            .map(|r| r.median_landed.unwrap())
+            .collect();
+        assert_eq!(medians, vec![2200.0, 2000.0]);
+
+        let medians: Vec<_> = db
+            .get_search_runs("leica")
+            .unwrap()
+            .iter()
+            .map(|r| r.median_landed.unwrap())
+            .collect();
+        assert_eq!(medians, vec![2400.0, 2200.0, 2000.0]);
+        assert!(db.get_search_runs("nikon").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_run_offers_roundtrip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        let mut run = test_search_run("leica", 1, 2000.0);
+        run.accepted_offers = Some(OfferStats {
+            accepted: 4,
+            hidden: 2,
+            average_discount_percent: Some(11.5),
+        });
+        db.add_search_run(&run).unwrap();
+
+        let latest = db.get_latest_search_run("leica").unwrap().unwrap();
+        assert_eq!(latest.accepted_offers, run.accepted_offers);
+    }
+
+    #[test]
+    fn test_offers_column_added_to_older_databases() {
+        let temp_file = NamedTempFile::new().unwrap();
+        Connection::open(temp_file.path())
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE search_runs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    phrase_id TEXT NOT NULL,
+                    ran_at TEXT NOT NULL,
+                    result_count INTEGER NOT NULL,
+                    currency TEXT,
+                    median_landed REAL,
+                    cheapest_json TEXT,
+                    new_listings INTEGER NOT NULL DEFAULT 0,
+                    UNIQUE (phrase_id, ran_at)
+                );",
+            )
+            .unwrap();
+
+        let db = Database::new(temp_file.path()).unwrap();
+        db.add_search_run(&test_search_run("leica", 1, 2000.0))
+            .unwrap();
+        assert!(db
+            .get_latest_search_run("leica")
+            .unwrap()
+            .unwrap()
+            .accepted_offers
+            .is_none());
+
+        // Opening again finds the column already there
+        Database::new(temp_file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_mark_listings_seen_counts_new_after_baseline() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+        let now = Utc::now();
+
+        assert_eq!(db.mark_listings_seen("leica", ["1", "2"], now).unwrap(), 0);
+        let new = db.mark_listings_seen("leica", ["2", "3", "4"], now).unwrap();
+        assert_eq!(new, 2);
+        assert_eq!(db.mark_listings_seen("leica", ["3"], now).unwrap(), 0);
+        // Each phrase has its own baseline
+        assert_eq!(db.mark_listings_seen("nikon", ["1"], now).unwrap(), 0);
+    }
+
+    fn test_snapshot(phrase: &str, hash: &str, days_ago: i64) -> PageSnapshot {
+        PageSnapshot {
+            id: 0,
+            kind: PageKind::Results,
+            url: format!("https://www.ebay.com/sch/i.html?_nkw={}", phrase),
+            search_phrase: Some(phrase.to_string()),
+            html_hash: hash.to_string(),
+            compressed_bytes: 100,
+            captured_at: Utc::now() - chrono::Duration::days(days_ago),
+            extracted: serde_json::json!([{"item_id": "1", "price": 10.0}]),
+        }
+    }
+
+    #[test]
+    fn test_page_snapshot_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        let id = db
+            .add_page_snapshot(&test_snapshot("canon ae-1", "abc", 0))
+            .unwrap();
+
+        let snapshot = db.get_page_snapshot(id).unwrap().unwrap();
+        assert_eq!(snapshot.id, id);
+        assert_eq!(snapshot.kind, PageKind::Results);
+        assert_eq!(snapshot.html_hash, "abc");
+        assert_eq!(snapshot.extracted[0]["price"], 10.0);
+
+        assert!(db.get_page_snapshot(id + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_query_page_snapshots() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        db.add_page_snapshot(&test_snapshot("canon ae-1", "a", 10))
+            .unwrap();
+        db.add_page_snapshot(&test_snapshot("canon ae-1", "b", 1))
+            .unwrap();
+        db.add_page_snapshot(&test_snapshot("nikon fm2", "c", 1))
+            .unwrap();
+
+        let canon = db
+            .query_page_snapshots(&SnapshotQuery {
+                phrase: Some("canon".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(canon.len(), 2);
+        assert_eq!(canon[0].html_hash, "b"); // newest first
+
+        let recent = db
+            .query_page_snapshots(&SnapshotQuery {
+                since: Some(Utc::now() - chrono::Duration::days(5)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(recent.len(), 2);
+
+        let limited = db
+            .query_page_snapshots(&SnapshotQuery {
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_page_snapshot_retention() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path()).unwrap();
+
+        db.add_page_snapshot(&test_snapshot("canon", "old", 30))
+            .unwrap();
+        db.add_page_snapshot(&test_snapshot("canon", "shared", 20))
+            .unwrap();
+        db.add_page_snapshot(&test_snapshot("canon", "shared", 1))
+            .unwrap();
+
+        // Duplicate hashes are only counted once
+        assert_eq!(db.page_snapshot_bytes().unwrap(), 200);
+
+        let removed = db
+            .delete_page_snapshots_before(Utc::now() - chrono::Duration::days(25))
+            .unwrap();
+        assert_eq!(removed, vec!["old".to_string()]);
+        assert!(!db.page_snapshot_hash_in_use("old").unwrap());
+
+        assert_eq!(
+            db.delete_oldest_page_snapshot().unwrap(),
+            Some("shared".to_string())
+        );
+        assert!(db.page_snapshot_hash_in_use("shared").unwrap());
+
+        db.delete_oldest_page_snapshot().unwrap();
+        assert_eq!(db.delete_oldest_page_snapshot().unwrap(), None);
+        assert_eq!(db.page_snapshot_bytes().unwrap(), 0);
+    }
 }
@@ -1,7 +1,9 @@
 //! Data storage and persistence module
 
+pub mod archive;
 pub mod cache;
 pub mod database;
 
+pub use archive::PageArchive;
 pub use cache::ResultCache;
 pub use database::Database;
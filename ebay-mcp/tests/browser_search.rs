@@ -0,0 +1,319 @@
+//! Integration tests for browser-backed search
+//!
+//! A local fixture server stands in for eBay, serving the two-page results
+//! fixture with its links pointing back at the server. Searches go through
+//! the search manager, browser pool and scraper as they would in the server.
+//! The same server can act as an HTTP proxy for a marketplace host that
+//! doesn't resolve, so a search only succeeds if it went through a proxy.
+
+use ebay_mcp_server::browser::BrowserPool;
+use ebay_mcp_server::config::ConfigManager;
+use ebay_mcp_server::models::SearchPaging;
+use ebay_mcp_server::search::SearchManager;
+use ebay_mcp_server::storage::{Database, ResultCache};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const PAGE_1: &str = include_str!("fixtures/search_results_p1.html");
+const PAGE_2: &str = include_str!("fixtures/search_results_p2.html");
+
+/// Where the fixture pages link to
+const FIXTURE_ORIGIN: &str = "https://www.ebay.com";
+
+const USER_AGENT: &str = "FixtureBrowser/1.0";
+
+/// Request target and headers the fixture server received
+#[derive(Debug, Clone)]
+struct Seen {
+    target: String,
+    user_agent: Option<String>,
+    accept_language: Option<String>,
+}
+
+/// Marketplace host only reachable through the fixture proxies
+const PROXIED_ORIGIN: &str = "http://ebay.fixture";
+
+/// What a fixture server does with requests
+enum Role {
+    /// Serve the fixture pages, linking back to itself
+    Marketplace,
+    /// Serve the fixture pages for `PROXIED_ORIGIN`, as a proxy would
+    Proxy,
+    /// Refuse everything, like a proxy eBay has banned
+    BannedProxy,
+}
+
+/// Fixture marketplace or proxy on a local port
+struct FixtureServer {
+    base_url: String,
+    seen: Arc<Mutex<Vec<Seen>>>,
+}
+
+impl FixtureServer {
+    async fn start() -> Self {
+        Self::with_role(Role::Marketplace).await
+    }
+
+    async fn with_role(role: Role) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let log = seen.clone();
+        let origin = match role {
+            Role::Marketplace => Some(base_url.clone()),
+            Role::Proxy => Some(PROXIED_ORIGIN.to_string()),
+            Role::BannedProxy => None,
+        };
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let log = log.clone();
+                let origin = origin.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request).into_owned();
+                    let target = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let header = |wanted: &str| {
+                        request.lines().find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case(wanted)
+                                .then(|| value.trim().to_string())
+                        })
+                    };
+                    log.lock().unwrap().push(Seen {
+                        target: target.clone(),
+                        user_agent: header("user-agent"),
+                        accept_language: header("accept-language"),
+                    });
+
+                    let (status, body) = match &origin {
+                        Some(origin) => respond(&target, origin),
+                        None => ("429 Too Many Requests", "<html>Blocked</html>".to_string()),
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { base_url, seen }
+    }
+
+    fn seen(&self) -> Vec<Seen> {
+        self.seen.lock().unwrap().clone()
+    }
+}
+
+/// Status line and body for a request target
+fn respond(target: &str, origin: &str) -> (&'static str, String) {
+    let page = if target.contains("_pgn=2") {
+        PAGE_2
+    } else if target.contains("/sch/i.html?_nkw=canon%20ae-1") {
+        PAGE_1
+    } else {
+        return ("404 Not Found", "<html>Not found</html>".to_string());
+    };
+
+    ("200 OK", page.replace(FIXTURE_ORIGIN, origin))
+}
+
+fn config(base_url: &str) -> String {
+    format!(
+        r#"
+[server]
+name = "test-ebay-mcp"
+version = "1.0.0"
+log_level = "info"
+
+[browser]
+pool_min_size = 0
+pool_max_size = 1
+headless = true
+page_load_timeout = 5
+element_timeout = 5
+user_agents = ["{user_agent}"]
+randomize_delay = false
+delay_min_ms = 0
+delay_max_ms = 0
+
+[browser.fingerprint]
+enabled = true
+accept_languages = ["de-DE,de;q=0.9"]
+
+[database]
+path = "test.db"
+auto_migrate = true
+
+[cache]
+enabled = false
+ttl_seconds = 300
+max_memory_entries = 100
+enable_disk_cache = false
+disk_cache_dir = "/tmp/cache"
+
+[logging]
+level = "info"
+file = "test.log"
+max_file_size = "10MB"
+max_backups = 3
+
+[scraper]
+base_url = "{base_url}"
+max_retries = 1
+screenshot_on_error = false
+screenshot_dir = "/tmp"
+"#,
+        user_agent = USER_AGENT,
+        base_url = base_url,
+    )
+}
+
+/// `config` routing contexts through `proxies`, a new one for each search
+fn proxied_config(proxies: &[&FixtureServer]) -> String {
+    let entries: Vec<String> = proxies
+        .iter()
+        .map(|proxy| format!("{{ url = \"{}\" }}", proxy.base_url))
+        .collect();
+
+    format!(
+        r#"{}
+[browser.proxy]
+proxies = [{}]
+rotation = "per_search"
+cooldown_seconds = 60
+max_attempts = 3
+"#,
+        config(PROXIED_ORIGIN),
+        entries.join(", ")
+    )
+}
+
+async fn search_manager(dir: &TempDir, config: &str) -> SearchManager {
+    let config_path = dir.path().join("config.toml");
+    let phrases_path = dir.path().join("phrases.toml");
+    tokio::fs::write(&config_path, config).await.unwrap();
+    tokio::fs::write(&phrases_path, "version = \"1.0\"\nphrases = []\n")
+        .await
+        .unwrap();
+
+    let config_manager = ConfigManager::load(config_path, phrases_path).await.unwrap();
+    let config = config_manager.get_config().await;
+
+    SearchManager::new(
+        Arc::new(config_manager),
+        Arc::new(BrowserPool::new(config.browser.into()).await.unwrap()),
+        Arc::new(ResultCache::new(false, 300, 100, None)),
+        Arc::new(tokio::sync::Mutex::new(
+            Database::new(dir.path().join("test.db")).unwrap(),
+        )),
+    )
+}
+
+fn paging(pages: usize) -> SearchPaging {
+    SearchPaging {
+        pages,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_search_collects_fixture_pages() {
+    let server = FixtureServer::start().await;
+    let dir = TempDir::new().unwrap();
+    let manager = search_manager(&dir, &config(&server.base_url)).await;
+
+    let results = manager
+        .search_paged("canon ae-1", None, paging(5))
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = results.items.iter().map(|i| i.item_id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "204512345001",
+            "204512345002",
+            "204512345003",
+            "204512345004",
+            "204512345005"
+        ]
+    );
+    assert_eq!(results.pages_fetched, 2);
+    assert_eq!(results.total_count, 5);
+    assert!(results.cursor.is_none());
+
+    // Both pages were loaded with the context's fingerprint
+    let seen = server.seen();
+    assert_eq!(seen.len(), 2);
+    assert!(seen[1].target.contains("_pgn=2"));
+    for request in &seen {
+        assert_eq!(request.user_agent.as_deref(), Some(USER_AGENT));
+        assert_eq!(request.accept_language.as_deref(), Some("de-DE,de;q=0.9"));
+    }
+
+    let history = manager.get_history(10, 0).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert!(history[0].success);
+}
+
+#[tokio::test]
+async fn test_failed_first_page_is_an_error() {
+    let server = FixtureServer::start().await;
+    let dir = TempDir::new().unwrap();
+    let manager = search_manager(&dir, &config(&server.base_url)).await;
+
+    let err = manager
+        .search_paged("nikon f3", None, paging(1))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("404"), "unexpected error: {}", err);
+    let history = manager.get_history(10, 0).await.unwrap();
+    assert!(!history[0].success);
+}
+
+#[tokio::test]
+async fn test_search_goes_through_proxy_and_skips_banned_one() {
+    let banned = FixtureServer::with_role(Role::BannedProxy).await;
+    let proxy = FixtureServer::with_role(Role::Proxy).await;
+    let dir = TempDir::new().unwrap();
+    let manager = search_manager(&dir, &proxied_config(&[&banned, &proxy])).await;
+
+    // The first proxy is refused, so the search fails over to the second
+    let results = manager
+        .search_paged("canon ae-1", None, paging(5))
+        .await
+        .unwrap();
+    assert_eq!(results.items.len(), 5);
+    assert_eq!(banned.seen().len(), 1);
+
+    // Requests reached the marketplace through the proxy, in absolute form
+    let targets: Vec<String> = proxy.seen().into_iter().map(|s| s.target).collect();
+    assert_eq!(targets.len(), 2);
+    assert!(targets[0].starts_with("http://ebay.fixture/sch/i.html?_nkw=canon%20ae-1"));
+    assert!(targets[1].contains("_pgn=2"));
+
+    // The banned proxy is cooling down, so the next search skips it
+    manager
+        .search_paged("canon ae-1", None, paging(1))
+        .await
+        .unwrap();
+    assert_eq!(banned.seen().len(), 1);
+    assert_eq!(proxy.seen().len(), 3);
+}
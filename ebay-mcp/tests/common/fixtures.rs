@@ -78,6 +78,7 @@ impl EbayListingBuilder {
                 item_id: "123456789".to_string(),
                 title: "Test Item".to_string(),
                 price: Price::usd(100.0),
+                price_max: None,
                 shipping_cost: None,
                 condition: "New".to_string(),
                 seller_name: "test_seller".to_string(),
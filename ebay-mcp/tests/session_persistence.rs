@@ -0,0 +1,258 @@
+//! Integration tests for browser session persistence
+//!
+//! A local fixture server stands in for the marketplace: signing in sets a
+//! cookie, and the homepage greets the account while that cookie comes
+//! back. Each "run" opens the session on the same profile directory and
+//! drops it again, as a restart would.
+
+use ebay_mcp_server::browser::BrowserSession;
+use ebay_mcp_server::models::SessionConfig;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Path and `Cookie` header of a request the fixture server received
+#[derive(Debug, Clone)]
+struct Seen {
+    path: String,
+    cookie: Option<String>,
+}
+
+/// Fixture marketplace on a local port
+struct FixtureServer {
+    base_url: String,
+    seen: Arc<Mutex<Vec<Seen>>>,
+}
+
+impl FixtureServer {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let log = seen.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request).into_owned();
+                    let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let cookie = request.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("cookie")
+                            .then(|| value.trim().to_string())
+                    });
+                    log.lock().unwrap().push(Seen {
+                        path: path.clone(),
+                        cookie: cookie.clone(),
+                    });
+
+                    let (headers, body) = respond(&path, cookie.as_deref());
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+                        body.len(),
+                        headers,
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { base_url, seen }
+    }
+
+    fn seen(&self, path_prefix: &str) -> Vec<Seen> {
+        self.seen
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.path.starts_with(path_prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Extra headers and body for a request
+fn respond(path: &str, cookie: Option<&str>) -> (String, String) {
+    let signed_in = cookie.is_some_and(|c| c.split("; ").any(|c| c == "ds2=account"));
+
+    if path.starts_with("/signin") {
+        (
+            "Set-Cookie: ds2=account; Path=/; Max-Age=86400; HttpOnly\r\n".to_string(),
+            "<html>Signed in</html>".to_string(),
+        )
+    } else if path.starts_with("/sch/") {
+        (
+            "Set-Cookie: ebay=%5Esbf%3D1; Path=/; Max-Age=3600\r\n".to_string(),
+            "<html><ul class=\"srp-results\"></ul></html>".to_string(),
+        )
+    } else if signed_in {
+        (
+            String::new(),
+            "<html><span id=\"gh-ug\">Hi tester!</span></html>".to_string(),
+        )
+    } else {
+        (
+            "Set-Cookie: dp1=visitor; Path=/; Max-Age=86400\r\nSet-Cookie: s=session; Path=/\r\n"
+                .to_string(),
+            "<html><a href=\"/signin\">Sign in</a></html>".to_string(),
+        )
+    }
+}
+
+fn config(dir: &Path) -> SessionConfig {
+    SessionConfig {
+        profile_dir: dir.to_path_buf(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_cookies_set_in_one_run_are_replayed_in_the_next() {
+    let server = FixtureServer::start().await;
+    let profile = TempDir::new().unwrap();
+
+    // First run: a visitor signs in and the session warms up
+    {
+        let session =
+            BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+        assert_eq!(session.info().restored_cookie_count, 0);
+
+        session
+            .fetch(&format!("{}/signin", server.base_url))
+            .await
+            .unwrap();
+        session.warm_up().await.unwrap();
+
+        let info = session.info();
+        assert_eq!(info.signed_in, Some(true));
+        assert_eq!(info.signed_in_survived_restart, None);
+        assert_eq!(info.cookie_count, 2);
+    }
+
+    let search = server.seen("/sch/");
+    assert_eq!(search.len(), 1);
+    assert!(search[0].path.contains("_nkw=usb%20cable"));
+    assert_eq!(search[0].cookie.as_deref(), Some("ds2=account"));
+
+    // Second run: the saved cookies go out with the very first request
+    let session = BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+    let info = session.info();
+    assert_eq!(info.restored_cookie_count, 2);
+    assert!(info.signed_in_before_restart);
+    assert!(info.previous_saved_at.is_some());
+    assert!(info.repairs.is_empty());
+
+    session.warm_up().await.unwrap();
+
+    let homepage = server.seen("/");
+    let replayed = homepage.iter().rev().find(|s| s.path == "/").unwrap();
+    let cookie = replayed.cookie.as_deref().unwrap();
+    assert!(cookie.contains("ds2=account"), "{}", cookie);
+    assert!(cookie.contains("ebay=%5Esbf%3D1"), "{}", cookie);
+
+    let info = session.info();
+    assert_eq!(info.signed_in, Some(true));
+    assert_eq!(info.signed_in_survived_restart, Some(true));
+    assert!(info.oldest_cookie_age_seconds.is_some());
+    assert!(info.warmed_up_at.is_some());
+}
+
+#[tokio::test]
+async fn test_lost_sign_in_is_reported() {
+    let server = FixtureServer::start().await;
+    let profile = TempDir::new().unwrap();
+
+    {
+        let session =
+            BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+        session
+            .fetch(&format!("{}/signin", server.base_url))
+            .await
+            .unwrap();
+        session.warm_up().await.unwrap();
+    }
+
+    // The sign-in cookie is gone by the next run
+    let cookies = profile.path().join("cookies.json");
+    let mut saved: serde_json::Value =
+        serde_json::from_slice(&fs::read(&cookies).unwrap()).unwrap();
+    saved["jar"]["cookies"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|c| c["name"] != "ds2");
+    fs::write(&cookies, saved.to_string()).unwrap();
+
+    let session = BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+    session.warm_up().await.unwrap();
+
+    let info = session.info();
+    assert!(info.signed_in_before_restart);
+    assert_eq!(info.signed_in, Some(false));
+    assert_eq!(info.signed_in_survived_restart, Some(false));
+    // The visitor cookies set on this homepage visit are now in the jar
+    assert!(info.cookie_count >= 3);
+}
+
+#[tokio::test]
+async fn test_profile_left_by_a_crashed_run_is_repaired() {
+    let server = FixtureServer::start().await;
+    let profile = TempDir::new().unwrap();
+
+    {
+        let session =
+            BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+        session.warm_up().await.unwrap();
+    }
+
+    // What a crash leaves: our lock, Chrome's singleton files and a
+    // half-written cookie file, all from a process that is gone
+    let dead_pid = "999999999";
+    fs::write(profile.path().join("session.lock"), dead_pid).unwrap();
+    let chrome = profile.path().join("chrome");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        format!("buildhost-{}", dead_pid),
+        chrome.join("SingletonLock"),
+    )
+    .unwrap();
+    #[cfg(not(unix))]
+    fs::write(chrome.join("SingletonLock"), "").unwrap();
+    fs::write(chrome.join("SingletonCookie"), "").unwrap();
+    let cookies = profile.path().join("cookies.json");
+    let contents = fs::read_to_string(&cookies).unwrap();
+    fs::write(&cookies, &contents[..contents.len() / 2]).unwrap();
+
+    let session = BrowserSession::open(&config(profile.path()), &server.base_url, None).unwrap();
+    let info = session.info();
+    assert_eq!(info.repairs.len(), 4, "{:?}", info.repairs);
+    assert!(info.repairs[0].contains("session.lock"));
+    assert!(info.repairs[3].contains("cookies.json"));
+    assert_eq!(info.restored_cookie_count, 0);
+
+    assert!(fs::symlink_metadata(chrome.join("SingletonLock")).is_err());
+    assert!(!chrome.join("SingletonCookie").exists());
+    assert!(profile.path().join("cookies.json.corrupt").exists());
+    assert_eq!(
+        fs::read_to_string(profile.path().join("session.lock")).unwrap(),
+        std::process::id().to_string()
+    );
+
+    // The repaired profile works as usual
+    session.warm_up().await.unwrap();
+    assert!(session.info().cookie_count > 0);
+}
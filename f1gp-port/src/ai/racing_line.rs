@@ -5,6 +5,36 @@
 use crate::data::track::Track;
 use glam::{Vec2, Vec3};
 
+/// Target speed on straights (m/s)
+const MAX_TARGET_SPEED: f32 = 50.0;
+
+/// Slowest target speed through any corner (m/s)
+const MIN_TARGET_SPEED: f32 = 10.0;
+
+/// Tyre friction coefficient assumed when planning corner and braking speeds
+const PLANNING_GRIP: f32 = 1.5;
+
+/// Gravitational acceleration (m/s²)
+const GRAVITY: f32 = 9.81;
+
+/// Distance needed to slow from `from_speed` to `to_speed` (m/s) on a
+/// `grade` (rise over run, negative = downhill)
+///
+/// Braking downhill fights gravity, so the same speed drop takes longer.
+pub fn braking_distance(from_speed: f32, to_speed: f32, grade: f32) -> f32 {
+    if from_speed <= to_speed {
+        return 0.0;
+    }
+
+    (from_speed * from_speed - to_speed * to_speed) / (2.0 * braking_deceleration(grade))
+}
+
+/// Peak braking deceleration on a grade (m/s²)
+fn braking_deceleration(grade: f32) -> f32 {
+    let slope = grade.atan();
+    (GRAVITY * (PLANNING_GRIP * slope.cos() + slope.sin())).max(0.1)
+}
+
 /// Racing line follower for AI cars
 pub struct RacingLineFollower {
     /// Racing line points (2D positions)
@@ -29,8 +59,15 @@ impl RacingLineFollower {
         if !track.sections.is_empty() {
             for section in &track.sections {
                 line_points.push(Vec2::new(section.position.x, section.position.z));
-                target_speeds.push(50.0); // Default speed (will be calculated from curvature)
             }
+
+            let grades: Vec<f32> = (0..track.sections.len())
+                .map(|i| track.section_grade(i))
+                .collect();
+            let cambers: Vec<f32> = (0..track.sections.len())
+                .map(|i| track.section_camber(i))
+                .collect();
+            target_speeds = Self::plan_speeds(&line_points, &grades, &cambers);
         }
 
         // If still no racing line data, create a simple circular path for testing
@@ -40,7 +77,7 @@ impl RacingLineFollower {
             for i in 0..num_points {
                 let angle = (i as f32 / num_points as f32) * std::f32::consts::TAU;
                 line_points.push(Vec2::new(radius * angle.cos(), radius * angle.sin()));
-                target_speeds.push(50.0);
+                target_speeds.push(MAX_TARGET_SPEED);
             }
         }
 
@@ -51,6 +88,54 @@ impl RacingLineFollower {
         }
     }
 
+    /// Plan target speeds around a closed line: cornering limits from
+    /// curvature and camber, then a backward pass so each corner's speed
+    /// is reachable under braking on the preceding slope
+    fn plan_speeds(points: &[Vec2], grades: &[f32], cambers: &[f32]) -> Vec<f32> {
+        let count = points.len();
+        if count < 3 {
+            return vec![MAX_TARGET_SPEED; count];
+        }
+
+        let mut speeds: Vec<f32> = (0..count)
+            .map(|i| {
+                let prev = points[(i + count - 1) % count];
+                let next = points[(i + 1) % count];
+                let incoming = points[i] - prev;
+                let outgoing = next - points[i];
+
+                let turn = incoming.angle_to(outgoing);
+                if !turn.is_finite() || turn.abs() < 1e-3 {
+                    return MAX_TARGET_SPEED;
+                }
+
+                // + = turning right, matching the sign of positive camber
+                let right = Vec2::new(-incoming.y, incoming.x);
+                let camber_into_turn = cambers[i] * outgoing.dot(right).signum();
+                let grip = (PLANNING_GRIP + camber_into_turn.tan()).max(0.1);
+
+                let radius = (incoming.length() + outgoing.length()) * 0.5 / turn.abs();
+                (grip * GRAVITY * radius)
+                    .sqrt()
+                    .clamp(MIN_TARGET_SPEED, MAX_TARGET_SPEED)
+            })
+            .collect();
+
+        // Propagate braking limits backwards; two laps covers the wrap-around
+        for step in (0..count * 2).rev() {
+            let i = step % count;
+            let next = (i + 1) % count;
+            let distance = (points[next] - points[i]).length();
+            let reachable = (speeds[next] * speeds[next]
+                + 2.0 * braking_deceleration(grades[i]) * distance)
+                .sqrt();
+
+            speeds[i] = speeds[i].min(reachable);
+        }
+
+        speeds
+    }
+
     /// Get the target point to steer towards (Pure Pursuit algorithm)
     pub fn get_target_point(&self, car_position: Vec3) -> Vec2 {
         let car_pos_2d = Vec2::new(car_position.x, car_position.z);
@@ -189,10 +274,12 @@ mod tests {
                     rear_wing: 10,
                     gear_ratios: [1, 2, 3, 4, 5, 6],
                     brake_balance: 50,
+                    tire_compound: crate::game::TireCompound::C,
                 },
             },
             pit_lane: Vec::new(),
             cameras: Vec::new(),
+            annotations: Default::default(),
             checksum: 0,
         }
     }
@@ -230,6 +317,55 @@ mod tests {
         assert!(speed < 100.0); // Reasonable range
     }
 
+    /// Long straight into a tight hairpin, dropping `fall` meters per section
+    fn create_straight_track(fall: f32) -> Track {
+        let mut points: Vec<Vec3> = (0..10)
+            .map(|i| Vec3::new(i as f32 * 50.0, -(i as f32) * fall, 0.0))
+            .collect();
+        let bottom = -9.0 * fall;
+        points.push(Vec3::new(450.0, bottom, 20.0));
+        points.push(Vec3::new(0.0, bottom, 20.0));
+
+        let mut track = create_test_track();
+        track.sections = points
+            .into_iter()
+            .map(|position| TrackSection {
+                position,
+                elevation: position.y,
+                width: 15.0,
+                length: 50.0,
+                surface: SurfaceType::Track,
+                ..TrackSection::default()
+            })
+            .collect();
+        track
+    }
+
+    #[test]
+    fn test_downhill_braking_distance_is_longer() {
+        let flat = braking_distance(50.0, 20.0, 0.0);
+        let downhill = braking_distance(50.0, 20.0, -0.1);
+        let uphill = braking_distance(50.0, 20.0, 0.1);
+
+        assert!(downhill > flat);
+        assert!(uphill < flat);
+        assert_eq!(braking_distance(20.0, 50.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_downhill_approach_brakes_earlier() {
+        let flat = RacingLineFollower::new(&create_straight_track(0.0), 20.0);
+        let downhill = RacingLineFollower::new(&create_straight_track(5.0), 20.0);
+
+        // Hairpin at index 9 is just as slow either way
+        assert!((flat.target_speeds[9] - downhill.target_speeds[9]).abs() < 1e-3);
+        assert!(flat.target_speeds[9] < flat.target_speeds[8]);
+
+        // One section before it, the downhill car must already be slower
+        assert!(downhill.target_speeds[8] < flat.target_speeds[8]);
+        assert_eq!(flat.target_speeds[3], MAX_TARGET_SPEED);
+    }
+
     #[test]
     fn test_calculate_steering() {
         let track = create_test_track();
@@ -6,8 +6,11 @@
 //! - Real-time engine sound synthesis (RPM-based)
 //! - Gear shift sounds
 //! - Menu sound effects
+//! - Original sound bank samples mapped to game events
 //! - Volume control and muting
 
+pub mod samples;
 pub mod sound_engine;
 
+pub use samples::{Fallback, GameSound, SampleMap};
 pub use sound_engine::SoundEngine;
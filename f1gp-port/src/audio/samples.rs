@@ -0,0 +1,137 @@
+//! Mapping from game events to original sound bank samples
+//!
+//! Events with no usable sample fall back to the synthesized sounds in
+//! [`super::sound_engine`], or to silence where there is nothing to synthesize.
+
+use crate::data::{BankSample, SoundBank};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Game events that can be voiced by a bank sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSound {
+    GearShift,
+    Collision,
+    Crowd,
+    MenuBeep,
+}
+
+impl GameSound {
+    /// All mappable events
+    pub const ALL: [GameSound; 4] = [
+        GameSound::GearShift,
+        GameSound::Collision,
+        GameSound::Crowd,
+        GameSound::MenuBeep,
+    ];
+
+    /// What to play when the bank has no sample for this event
+    pub fn fallback(self) -> Fallback {
+        match self {
+            GameSound::GearShift | GameSound::Collision | GameSound::MenuBeep => Fallback::Synth,
+            GameSound::Crowd => Fallback::Silent,
+        }
+    }
+}
+
+/// Behaviour for an event whose sample is missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    /// Use the built-in synthesized sound
+    Synth,
+    /// Play nothing
+    Silent,
+}
+
+/// Which bank entry voices each event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleMap {
+    entries: HashMap<GameSound, usize>,
+}
+
+impl Default for SampleMap {
+    /// Entry order of the bank shipped with the game
+    fn default() -> Self {
+        Self::new()
+            .with(GameSound::GearShift, 0)
+            .with(GameSound::Collision, 1)
+            .with(GameSound::Crowd, 2)
+            .with(GameSound::MenuBeep, 3)
+    }
+}
+
+impl SampleMap {
+    /// An empty map; every event falls back
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Map `sound` to bank entry `index`
+    pub fn with(mut self, sound: GameSound, index: usize) -> Self {
+        self.entries.insert(sound, index);
+        self
+    }
+
+    /// Bank entry mapped to `sound`, if any
+    pub fn index(&self, sound: GameSound) -> Option<usize> {
+        self.entries.get(&sound).copied()
+    }
+
+    /// Decoded sample for `sound`, or `None` if unmapped or absent from the bank
+    pub fn resolve(&self, bank: &SoundBank, sound: GameSound) -> Option<Arc<BankSample>> {
+        self.index(sound).and_then(|index| bank.sample(index))
+    }
+
+    /// Events this map cannot voice from `bank`
+    pub fn missing(&self, bank: &SoundBank) -> Vec<GameSound> {
+        GameSound::ALL
+            .into_iter()
+            .filter(|&sound| self.index(sound).is_none_or(|index| index >= bank.len()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::sound_bank::tests::synthetic_bank;
+    use crate::data::BankVersion;
+
+    fn two_sample_bank() -> SoundBank {
+        let bank = synthetic_bank(
+            BankVersion::SamV2,
+            &[(11025, false, vec![255; 4]), (22050, false, vec![0; 8])],
+        );
+        SoundBank::parse(bank, "TWO.SAM").unwrap()
+    }
+
+    #[test]
+    fn test_default_map_resolves_present_entries() {
+        let bank = two_sample_bank();
+        let map = SampleMap::default();
+
+        let shift = map.resolve(&bank, GameSound::GearShift).unwrap();
+        assert_eq!(shift.data.len(), 4);
+        let crash = map.resolve(&bank, GameSound::Collision).unwrap();
+        assert_eq!(crash.sample_rate, 22050);
+
+        assert!(map.resolve(&bank, GameSound::Crowd).is_none());
+        assert_eq!(
+            map.missing(&bank),
+            vec![GameSound::Crowd, GameSound::MenuBeep]
+        );
+    }
+
+    #[test]
+    fn test_custom_map_and_fallbacks() {
+        let bank = two_sample_bank();
+        let map = SampleMap::new().with(GameSound::Crowd, 1);
+
+        assert!(map.resolve(&bank, GameSound::Crowd).is_some());
+        assert!(map.resolve(&bank, GameSound::GearShift).is_none());
+        assert_eq!(GameSound::GearShift.fallback(), Fallback::Synth);
+        assert_eq!(GameSound::Crowd.fallback(), Fallback::Silent);
+    }
+}
@@ -5,11 +5,18 @@
 //! - Gear shifts
 //! - Menu sounds
 //! - Effects
+//! - Samples from the original sound bank, when one is loaded
 
+use super::samples::{Fallback, GameSound, SampleMap};
+use crate::data::{BankSample, BankVersion, SoundBank};
 use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired};
 use std::f32::consts::PI;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+/// Most bank samples mixed at once; older voices are dropped first
+const MAX_SAMPLE_VOICES: usize = 8;
+
 /// Audio engine state (shared between game thread and audio callback)
 #[derive(Debug, Clone)]
 pub struct AudioState {
@@ -35,6 +42,8 @@ pub struct AudioState {
     pub collision_samples: u32,
     /// Rain intensity (0.0 = no rain, 1.0 = heavy rain)
     pub rain_intensity: f32,
+    /// Bank samples queued to start on the next callback
+    pub sample_triggers: Vec<Arc<BankSample>>,
     /// Muted flag
     pub muted: bool,
 }
@@ -53,6 +62,7 @@ impl Default for AudioState {
             collision_trigger: false,
             collision_samples: 0,
             rain_intensity: 0.0,
+            sample_triggers: Vec::new(),
             muted: false,
         }
     }
@@ -65,6 +75,38 @@ struct EngineAudioCallback {
     sample_rate: f32,
     gear_shift_phase: f32,
     menu_beep_phase: f32,
+    voices: Vec<SampleVoice>,
+}
+
+/// A bank sample being played back
+struct SampleVoice {
+    sample: Arc<BankSample>,
+    /// Read position in source samples
+    position: f32,
+    /// Source samples advanced per output sample
+    step: f32,
+}
+
+impl SampleVoice {
+    fn new(sample: Arc<BankSample>, output_rate: f32) -> Self {
+        let step = sample.sample_rate as f32 / output_rate;
+        Self {
+            sample,
+            position: 0.0,
+            step,
+        }
+    }
+
+    /// Next output sample (linear interpolation), or `None` once finished
+    fn next(&mut self) -> Option<f32> {
+        let data = &self.sample.data;
+        let index = self.position as usize;
+        let current = *data.get(index)?;
+        let following = data.get(index + 1).copied().unwrap_or(current);
+        let frac = self.position - index as f32;
+        self.position += self.step;
+        Some(current + (following - current) * frac)
+    }
 }
 
 impl AudioCallback for EngineAudioCallback {
@@ -113,6 +155,14 @@ impl AudioCallback for EngineAudioCallback {
                 state.collision_samples = (self.sample_rate * 0.15) as u32; // 150ms
             }
 
+            // Start any queued bank samples
+            for sample in state.sample_triggers.drain(..) {
+                if self.voices.len() == MAX_SAMPLE_VOICES {
+                    self.voices.remove(0);
+                }
+                self.voices.push(SampleVoice::new(sample, self.sample_rate));
+            }
+
             // Copy values we need
             (
                 state.rpm,
@@ -168,6 +218,15 @@ impl AudioCallback for EngineAudioCallback {
                 sample += rain_sample * rain_intensity * 0.2;
             }
 
+            // 7. Original bank samples (one-shot)
+            self.voices.retain_mut(|voice| match voice.next() {
+                Some(value) => {
+                    sample += value * 0.6;
+                    true
+                }
+                None => false,
+            });
+
             // Apply master volume and output
             *x = (sample * volume).clamp(-1.0, 1.0);
         }
@@ -315,6 +374,8 @@ impl EngineAudioCallback {
 pub struct SoundEngine {
     _device: AudioDevice<EngineAudioCallback>,
     state: Arc<Mutex<AudioState>>,
+    bank: Option<SoundBank>,
+    sample_map: SampleMap,
 }
 
 impl SoundEngine {
@@ -342,6 +403,7 @@ impl SoundEngine {
                 sample_rate: spec.freq as f32,
                 gear_shift_phase: 0.0,
                 menu_beep_phase: 0.0,
+                voices: Vec::with_capacity(MAX_SAMPLE_VOICES),
             }
         })?;
 
@@ -353,9 +415,51 @@ impl SoundEngine {
         Ok(Self {
             _device: device,
             state,
+            bank: None,
+            sample_map: SampleMap::default(),
         })
     }
 
+    /// Use samples from an original sound bank, mapped by `sample_map`
+    ///
+    /// Events the map cannot voice from this bank keep their fallback.
+    pub fn load_sound_bank(&mut self, bank: SoundBank, sample_map: SampleMap) {
+        for sound in sample_map.missing(&bank) {
+            log::warn!(
+                "Sound bank has no sample for {:?}, using {:?} fallback",
+                sound,
+                sound.fallback()
+            );
+        }
+        self.bank = Some(bank);
+        self.sample_map = sample_map;
+    }
+
+    /// Layout and location of the loaded sound bank, if any
+    pub fn sound_bank_info(&self) -> Option<(BankVersion, &Path)> {
+        self.bank
+            .as_ref()
+            .map(|bank| (bank.version(), bank.source()))
+    }
+
+    /// Queue the bank sample for `sound`; returns the fallback if there is none
+    fn play_sample(&self, sound: GameSound) -> Option<Fallback> {
+        let sample = self
+            .bank
+            .as_ref()
+            .and_then(|bank| self.sample_map.resolve(bank, sound));
+
+        match sample {
+            Some(sample) => {
+                if let Ok(mut state) = self.state.lock() {
+                    state.sample_triggers.push(sample);
+                }
+                None
+            }
+            None => Some(sound.fallback()),
+        }
+    }
+
     /// Update engine RPM
     pub fn set_rpm(&self, rpm: f32) {
         if let Ok(mut state) = self.state.lock() {
@@ -365,6 +469,9 @@ impl SoundEngine {
 
     /// Trigger gear shift sound
     pub fn play_gear_shift(&self) {
+        if self.play_sample(GameSound::GearShift) != Some(Fallback::Synth) {
+            return;
+        }
         if let Ok(mut state) = self.state.lock() {
             state.gear_shift_trigger = true;
         }
@@ -372,6 +479,9 @@ impl SoundEngine {
 
     /// Trigger menu beep sound
     pub fn play_menu_beep(&self) {
+        if self.play_sample(GameSound::MenuBeep) != Some(Fallback::Synth) {
+            return;
+        }
         if let Ok(mut state) = self.state.lock() {
             state.menu_beep_trigger = true;
         }
@@ -386,11 +496,19 @@ impl SoundEngine {
 
     /// Trigger collision sound
     pub fn play_collision(&self) {
+        if self.play_sample(GameSound::Collision) != Some(Fallback::Synth) {
+            return;
+        }
         if let Ok(mut state) = self.state.lock() {
             state.collision_trigger = true;
         }
     }
 
+    /// Play crowd noise (only available from an original sound bank)
+    pub fn play_crowd(&self) {
+        self.play_sample(GameSound::Crowd);
+    }
+
     /// Set rain ambient sound intensity (0.0 = no rain, 1.0 = heavy rain)
     pub fn set_rain_intensity(&self, intensity: f32) {
         if let Ok(mut state) = self.state.lock() {
@@ -0,0 +1,543 @@
+//! Named track annotations
+//!
+//! Corners (and other landmarks) named at a distance along the lap, measured
+//! from the start/finish line in the driving direction. The 16 circuits of the
+//! 1991 season come with the corner names from the original circuit guides;
+//! the distances are approximate positions on the 1991 layouts and are scaled
+//! to the length of the parsed track.
+
+use serde::{Deserialize, Serialize};
+
+/// A named point on the lap, e.g. a corner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackAnnotation {
+    /// Corner name (e.g., "Sainte Devote")
+    pub name: String,
+
+    /// Distance along the lap from the start/finish line (meters)
+    pub distance: f32,
+
+    /// Short driving hint, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TrackAnnotation {
+    /// Create an annotation without a note
+    pub fn new(name: impl Into<String>, distance: f32) -> Self {
+        Self {
+            name: name.into(),
+            distance,
+            note: None,
+        }
+    }
+
+    /// Attach a driving hint
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+}
+
+/// A track's annotations, kept sorted by distance for O(log n) lookups
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<TrackAnnotation>", into = "Vec<TrackAnnotation>")]
+pub struct TrackAnnotations {
+    annotations: Vec<TrackAnnotation>,
+}
+
+impl From<Vec<TrackAnnotation>> for TrackAnnotations {
+    fn from(mut annotations: Vec<TrackAnnotation>) -> Self {
+        annotations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Self { annotations }
+    }
+}
+
+impl From<TrackAnnotations> for Vec<TrackAnnotation> {
+    fn from(annotations: TrackAnnotations) -> Self {
+        annotations.annotations
+    }
+}
+
+impl TrackAnnotations {
+    /// Annotations of a circuit from the original circuit guides, scaled to a
+    /// lap of `track_length` meters (unscaled if the length is unknown)
+    ///
+    /// `circuit` is a circuit name ("Monaco", "Spa-Francorchamps") or its
+    /// track file ("F1CT04.DAT"); unknown circuits get no annotations.
+    pub fn for_circuit(circuit: &str, track_length: f32) -> Self {
+        let Some(guide) = CIRCUIT_GUIDES.iter().find(|guide| guide.matches(circuit)) else {
+            return Self::default();
+        };
+
+        let scale = if track_length > 0.0 {
+            track_length / guide.lap_length
+        } else {
+            1.0
+        };
+        guide
+            .corners
+            .iter()
+            .map(|&(name, distance, note)| TrackAnnotation {
+                name: name.to_string(),
+                distance: distance * scale,
+                note: note.map(str::to_string),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Number of annotations
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    /// Whether the track has no annotations
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    /// Annotations in lap order
+    pub fn iter(&self) -> impl Iterator<Item = &TrackAnnotation> {
+        self.annotations.iter()
+    }
+
+    /// Annotation by index in lap order
+    pub fn get(&self, index: usize) -> Option<&TrackAnnotation> {
+        self.annotations.get(index)
+    }
+
+    /// Index of the first annotation beyond `distance`, wrapping to the first
+    /// one of the next lap
+    pub fn upcoming_index(&self, distance: f32) -> Option<usize> {
+        if self.annotations.is_empty() {
+            return None;
+        }
+        let passed = self.annotations.partition_point(|a| a.distance <= distance);
+        Some(passed % self.annotations.len())
+    }
+
+    /// The next annotation after `distance`
+    pub fn upcoming(&self, distance: f32) -> Option<&TrackAnnotation> {
+        self.upcoming_index(distance)
+            .map(|index| &self.annotations[index])
+    }
+
+    /// The last annotation at or before `distance`, wrapping to the last one
+    /// of the previous lap
+    pub fn at(&self, distance: f32) -> Option<&TrackAnnotation> {
+        let passed = self.annotations.partition_point(|a| a.distance <= distance);
+        passed
+            .checked_sub(1)
+            .or_else(|| self.annotations.len().checked_sub(1))
+            .map(|index| &self.annotations[index])
+    }
+}
+
+/// Corner name, distance on the documented lap (meters) and optional note
+type Corner = (&'static str, f32, Option<&'static str>);
+
+/// Corners of one circuit as given in the original circuit guides
+struct CircuitGuide {
+    /// Track file
+    file: &'static str,
+
+    /// Names the circuit goes by
+    names: &'static [&'static str],
+
+    /// Lap length of the 1991 layout (meters)
+    lap_length: f32,
+
+    /// Corners in lap order
+    corners: &'static [Corner],
+}
+
+impl CircuitGuide {
+    fn matches(&self, circuit: &str) -> bool {
+        let file_stem = self.file.trim_end_matches(".DAT");
+        circuit.eq_ignore_ascii_case(self.file)
+            || circuit.eq_ignore_ascii_case(file_stem)
+            || self
+                .names
+                .iter()
+                .any(|name| circuit.eq_ignore_ascii_case(name))
+    }
+}
+
+const CIRCUIT_GUIDES: [CircuitGuide; 16] = [
+    CircuitGuide {
+        file: "F1CT01.DAT",
+        names: &["Phoenix"],
+        lap_length: 3798.0,
+        corners: &[
+            ("Turn 1", 420.0, Some("Square right, brake early")),
+            ("Turn 3", 900.0, None),
+            ("Turn 4", 1250.0, Some("Left onto the back straight")),
+            ("Turn 6", 1900.0, None),
+            ("Turn 8", 2400.0, Some("Tight right, brake early")),
+            ("Turn 10", 2950.0, None),
+            ("Turn 12", 3350.0, None),
+            ("Turn 14", 3650.0, Some("Last corner onto the pit straight")),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT02.DAT",
+        names: &["Interlagos"],
+        lap_length: 4325.0,
+        corners: &[
+            ("S do Senna", 250.0, Some("Downhill left-right, brake hard")),
+            ("Curva do Sol", 550.0, None),
+            ("Descida do Lago", 1300.0, None),
+            ("Ferradura", 1800.0, Some("Long double-apex right")),
+            ("Laranjinha", 2250.0, None),
+            ("Pinheirinho", 2600.0, None),
+            ("Bico de Pato", 2900.0, Some("Tight hairpin")),
+            ("Mergulho", 3200.0, None),
+            ("Juncao", 3450.0, Some("Exit speed carries up the hill")),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT03.DAT",
+        names: &["Imola"],
+        lap_length: 5040.0,
+        corners: &[
+            ("Tamburello", 600.0, Some("Flat-out left")),
+            ("Villeneuve", 1200.0, None),
+            ("Tosa", 1500.0, Some("Hairpin, heavy braking")),
+            ("Piratella", 2100.0, Some("Blind crest")),
+            ("Acque Minerali", 2600.0, None),
+            ("Variante Alta", 3250.0, Some("Chicane, use the kerbs")),
+            ("Rivazza", 4100.0, None),
+            ("Variante Bassa", 4700.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT04.DAT",
+        names: &["Monaco"],
+        lap_length: 3328.0,
+        corners: &[
+            ("Sainte Devote", 250.0, Some("Tight right, first gear")),
+            ("Beau Rivage", 500.0, None),
+            ("Massenet", 800.0, None),
+            ("Casino", 950.0, None),
+            ("Mirabeau", 1150.0, None),
+            ("Loews", 1300.0, Some("Slowest hairpin of the season")),
+            (
+                "Portier",
+                1500.0,
+                Some("Flat out through the tunnel after it"),
+            ),
+            ("Tunnel", 1700.0, None),
+            ("Nouvelle Chicane", 2050.0, None),
+            ("Tabac", 2300.0, None),
+            ("Piscine", 2500.0, None),
+            ("La Rascasse", 3050.0, None),
+            ("Anthony Noghes", 3200.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT05.DAT",
+        names: &["Montreal"],
+        lap_length: 4430.0,
+        corners: &[
+            ("Virage 1", 250.0, None),
+            ("Virage 2", 400.0, Some("Slow right, hugs the wall")),
+            ("Virage 3-4", 950.0, None),
+            ("Virage 6-7", 1650.0, None),
+            ("Virage 8-9", 2150.0, None),
+            ("L'Epingle", 2750.0, Some("Hairpin, heavy braking")),
+            (
+                "Casino Chicane",
+                4100.0,
+                Some("Last chicane before the pits"),
+            ),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT06.DAT",
+        names: &["Mexico City", "Mexico"],
+        lap_length: 4421.0,
+        corners: &[
+            ("Curva 1", 1050.0, Some("End of the long straight")),
+            ("Curvas 2-3", 1300.0, None),
+            ("Ese del Lago", 1900.0, Some("Fast esses")),
+            ("Horquilla", 2700.0, Some("Hairpin")),
+            ("Curvas 12-13", 3300.0, None),
+            (
+                "Peraltada",
+                3900.0,
+                Some("Long banked right onto the straight"),
+            ),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT07.DAT",
+        names: &["Magny-Cours"],
+        lap_length: 4271.0,
+        corners: &[
+            ("Grande Courbe", 300.0, None),
+            ("Estoril", 600.0, None),
+            ("Golf", 1100.0, None),
+            ("Adelaide", 1700.0, Some("Hairpin, heavy braking")),
+            ("Nurburgring", 2200.0, None),
+            ("180", 2550.0, None),
+            ("Imola", 3200.0, None),
+            ("Chateau d'Eau", 3500.0, None),
+            ("Lycee", 4050.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT08.DAT",
+        names: &["Silverstone"],
+        lap_length: 5226.0,
+        corners: &[
+            ("Copse", 350.0, Some("Fast right")),
+            ("Maggotts", 900.0, None),
+            ("Becketts", 1100.0, None),
+            ("Chapel", 1350.0, None),
+            ("Stowe", 2250.0, None),
+            ("Vale", 2700.0, None),
+            ("Club", 3000.0, None),
+            ("Abbey", 3700.0, None),
+            ("Bridge", 4250.0, None),
+            ("Priory", 4450.0, None),
+            ("Luffield", 4700.0, None),
+            ("Woodcote", 5000.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT09.DAT",
+        names: &["Hockenheim"],
+        lap_length: 6802.0,
+        corners: &[
+            ("Nordkurve", 300.0, None),
+            (
+                "Jim Clark Chicane",
+                1500.0,
+                Some("First chicane in the forest"),
+            ),
+            ("Ostkurve", 3000.0, None),
+            ("Ostkurve Chicane", 4100.0, None),
+            ("Agip Kurve", 5400.0, Some("Braking into the stadium")),
+            ("Sachskurve", 5800.0, None),
+            ("Sudkurve", 6500.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT10.DAT",
+        names: &["Hungaroring"],
+        lap_length: 3968.0,
+        corners: &[
+            ("Turn 1", 500.0, Some("Downhill hairpin")),
+            ("Turn 2", 900.0, None),
+            ("Turn 3", 1100.0, None),
+            ("Turn 4", 1450.0, Some("Blind crest")),
+            ("Turn 5", 1700.0, None),
+            ("Turns 6-7", 2000.0, Some("Chicane")),
+            ("Turns 8-9", 2300.0, None),
+            ("Turn 11", 2800.0, None),
+            ("Turn 12", 3100.0, None),
+            ("Turns 13-14", 3500.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT11.DAT",
+        names: &["Spa-Francorchamps", "Spa"],
+        lap_length: 6940.0,
+        corners: &[
+            ("La Source", 200.0, Some("Hairpin")),
+            ("Eau Rouge", 650.0, Some("Flat if you dare")),
+            ("Raidillon", 750.0, None),
+            ("Les Combes", 1900.0, None),
+            ("Malmedy", 2200.0, None),
+            ("Rivage", 2600.0, None),
+            ("Pouhon", 3300.0, Some("Fast double-apex left")),
+            ("Les Fagnes", 3900.0, None),
+            ("Stavelot", 4600.0, None),
+            ("Blanchimont", 5800.0, None),
+            ("Bus Stop", 6600.0, Some("Last chicane before the pits")),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT12.DAT",
+        names: &["Monza"],
+        lap_length: 5800.0,
+        corners: &[
+            (
+                "Variante Goodyear",
+                700.0,
+                Some("Heavy braking from top speed"),
+            ),
+            ("Curva Grande", 1300.0, None),
+            ("Variante della Roggia", 2100.0, None),
+            ("Lesmo 1", 2600.0, None),
+            ("Lesmo 2", 2800.0, None),
+            ("Variante Ascari", 3900.0, None),
+            ("Parabolica", 5100.0, Some("Exit speed for the straight")),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT13.DAT",
+        names: &["Estoril"],
+        lap_length: 4350.0,
+        corners: &[
+            ("Curva 1", 650.0, None),
+            ("Curva 2", 950.0, None),
+            ("Curva 3", 1350.0, None),
+            ("Curva 4", 1600.0, None),
+            ("Orelhinha", 2200.0, None),
+            ("Curva do Tanque", 2650.0, None),
+            ("Esses", 3100.0, None),
+            (
+                "Parabolica",
+                3900.0,
+                Some("Long right onto the pit straight"),
+            ),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT14.DAT",
+        names: &["Barcelona"],
+        lap_length: 4747.0,
+        corners: &[
+            ("Elf", 800.0, Some("End of the pit straight")),
+            ("Renault", 1100.0, None),
+            ("Repsol", 1600.0, None),
+            ("Seat", 2000.0, None),
+            ("Wurth", 2300.0, None),
+            ("Campsa", 2800.0, Some("Blind crest")),
+            ("La Caixa", 3300.0, None),
+            ("Banc de Sabadell", 3700.0, None),
+            ("New Holland", 4300.0, None),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT15.DAT",
+        names: &["Suzuka"],
+        lap_length: 5864.0,
+        corners: &[
+            ("First Curve", 500.0, None),
+            ("S Curves", 900.0, Some("Rhythm matters more than speed")),
+            ("Dunlop", 1500.0, None),
+            ("Degner", 2100.0, None),
+            ("Hairpin", 2600.0, None),
+            ("Spoon", 3700.0, None),
+            ("130R", 4700.0, Some("Flat-out left")),
+            ("Casio Triangle", 5300.0, Some("Chicane")),
+        ],
+    },
+    CircuitGuide {
+        file: "F1CT16.DAT",
+        names: &["Adelaide"],
+        lap_length: 3780.0,
+        corners: &[
+            ("Turn 1 Chicane", 350.0, None),
+            ("Wakefield Street", 900.0, None),
+            ("Flinders Street", 1200.0, None),
+            ("East Terrace", 1500.0, None),
+            ("Victoria Park", 1900.0, None),
+            ("Brewery Bend", 2300.0, None),
+            ("Hairpin", 3350.0, Some("End of the Brabham Straight")),
+            ("Last Corner", 3650.0, None),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::championship::SEASON_CALENDAR;
+
+    fn annotations() -> TrackAnnotations {
+        vec![
+            TrackAnnotation::new("C", 800.0),
+            TrackAnnotation::new("A", 200.0).with_note("Hairpin"),
+            TrackAnnotation::new("B", 500.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_annotations_are_sorted() {
+        let names: Vec<_> = annotations().iter().map(|a| a.name.clone()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_upcoming_and_current_wrap_around_the_lap() {
+        let annotations = annotations();
+
+        assert_eq!(annotations.upcoming(0.0).unwrap().name, "A");
+        assert_eq!(annotations.upcoming(200.0).unwrap().name, "B");
+        assert_eq!(annotations.upcoming(650.0).unwrap().name, "C");
+        assert_eq!(annotations.upcoming(900.0).unwrap().name, "A");
+        assert_eq!(annotations.upcoming_index(900.0), Some(0));
+
+        assert_eq!(annotations.at(100.0).unwrap().name, "C");
+        assert_eq!(annotations.at(200.0).unwrap().name, "A");
+        assert_eq!(annotations.at(799.0).unwrap().name, "B");
+        assert_eq!(annotations.at(950.0).unwrap().name, "C");
+
+        let empty = TrackAnnotations::default();
+        assert!(empty.upcoming(10.0).is_none());
+        assert!(empty.at(10.0).is_none());
+    }
+
+    #[test]
+    fn test_every_circuit_has_a_guide() {
+        for circuit in SEASON_CALENDAR {
+            let annotations = TrackAnnotations::for_circuit(circuit, 0.0);
+            assert!(annotations.len() >= 6, "{} has no corners", circuit);
+        }
+
+        // Names used by the track menu and bare track files resolve too
+        for circuit in ["Spa-Francorchamps", "Mexico", "F1CT04.DAT", "f1ct04"] {
+            assert!(!TrackAnnotations::for_circuit(circuit, 0.0).is_empty());
+        }
+        assert!(TrackAnnotations::for_circuit("Test Track", 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_guides_are_in_lap_order() {
+        for guide in &CIRCUIT_GUIDES {
+            let mut previous = 0.0;
+            for &(name, distance, _) in guide.corners {
+                assert!(
+                    distance > previous && distance < guide.lap_length,
+                    "{}: {} is out of order",
+                    guide.file,
+                    name
+                );
+                previous = distance;
+            }
+        }
+    }
+
+    #[test]
+    fn test_distances_scale_to_the_parsed_length() {
+        let documented = TrackAnnotations::for_circuit("Monaco", 0.0);
+        let parsed = TrackAnnotations::for_circuit("Monaco", 3328.0 / 2.0);
+
+        assert_eq!(documented.get(0).unwrap().name, "Sainte Devote");
+        assert_eq!(documented.get(0).unwrap().distance, 250.0);
+        assert_eq!(parsed.get(0).unwrap().distance, 125.0);
+        assert_eq!(
+            parsed.get(5).unwrap().note.as_deref(),
+            Some("Slowest hairpin of the season")
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip_sorts() {
+        let json =
+            r#"[{"name":"B","distance":500.0},{"name":"A","distance":200.0,"note":"Hairpin"}]"#;
+        let annotations: TrackAnnotations = serde_json::from_str(json).unwrap();
+        assert_eq!(annotations.get(0).unwrap().name, "A");
+
+        let written = serde_json::to_string(&annotations).unwrap();
+        assert_eq!(
+            written,
+            r#"[{"name":"A","distance":200.0,"note":"Hairpin"},{"name":"B","distance":500.0}]"#
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::annotations::TrackAnnotations;
 use super::objects::ObjectShape;
 use super::track::{
     AIBehavior, Camera, RacingLine, Track, TrackOffsets, TrackSection, TrackSectionHeader,
@@ -36,11 +37,14 @@ pub struct TrackAsset {
 }
 
 impl TrackAsset {
-    /// Convert this asset into the runtime `Track` representation.
+    /// Convert this asset into the runtime `Track` representation, with the
+    /// circuit's named corners if it is one of the original circuits.
     pub fn into_track(self) -> Track {
+        let length = self.sections.iter().map(|s| s.length).sum();
         Track {
+            annotations: TrackAnnotations::for_circuit(&self.name, length),
             name: self.name,
-            length: self.sections.iter().map(|s| s.length).sum(),
+            length,
             object_shapes: self.object_shapes,
             sections: self.sections,
             racing_line: self.racing_line,
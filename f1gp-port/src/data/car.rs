@@ -103,6 +103,55 @@ pub struct Driver {
 
     /// Skill ratings (0-100)
     pub skills: DriverSkills,
+
+    /// Personal livery; falls back to the team colors when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub livery: Option<Livery>,
+}
+
+/// Car livery (primary body color and secondary accent color, RGB)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Livery {
+    /// Main body color
+    pub primary: (u8, u8, u8),
+
+    /// Cockpit / accent color
+    pub secondary: (u8, u8, u8),
+}
+
+impl Livery {
+    /// Neutral livery used when no roster or team colors are available
+    pub const DEFAULT: Livery = Livery {
+        primary: (200, 200, 200),
+        secondary: (40, 40, 40),
+    };
+
+    /// Build a livery from a color list (first two entries), if non-empty
+    pub fn from_colors(colors: &[(u8, u8, u8)]) -> Option<Self> {
+        let primary = *colors.first()?;
+        let secondary = colors.get(1).copied().unwrap_or(primary);
+        Some(Self { primary, secondary })
+    }
+
+    /// Primary color as normalized RGBA
+    pub fn primary_rgba(&self) -> [f32; 4] {
+        rgb_to_rgba(self.primary)
+    }
+
+    /// Secondary color as normalized RGBA
+    pub fn secondary_rgba(&self) -> [f32; 4] {
+        rgb_to_rgba(self.secondary)
+    }
+}
+
+impl Default for Livery {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+fn rgb_to_rgba((r, g, b): (u8, u8, u8)) -> [f32; 4] {
+    [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0]
 }
 
 /// Driver skill ratings
@@ -190,6 +239,15 @@ impl CarDatabase {
         self.drivers.get(name)
     }
 
+    /// Resolve the livery for a driver: personal livery, then team colors
+    pub fn livery_for_driver(&self, name: &str) -> Option<Livery> {
+        let driver = self.get_driver(name)?;
+        driver.livery.or_else(|| {
+            self.get_team(&driver.team)
+                .and_then(|team| Livery::from_colors(&team.colors))
+        })
+    }
+
     /// Get all cars
     pub fn cars(&self) -> impl Iterator<Item = &CarSpec> {
         self.cars.values()
@@ -301,6 +359,7 @@ impl CarDatabase {
                     overtaking,
                     defending,
                 },
+                livery: None,
             });
         }
 
@@ -465,6 +524,34 @@ mod tests {
         assert_eq!(db.team_count(), 3);
     }
 
+    #[test]
+    fn test_livery_falls_back_to_team_colors() {
+        let mut db = CarDatabase::create_sample();
+        let senna = db.livery_for_driver("Ayrton Senna").unwrap();
+        assert_eq!(senna.primary, (255, 255, 255));
+        assert_eq!(senna.secondary, (255, 0, 0));
+
+        let mut alesi = db.get_driver("Jean Alesi").unwrap().clone();
+        alesi.livery = Some(Livery {
+            primary: (255, 0, 0),
+            secondary: (0, 0, 0),
+        });
+        db.add_driver(alesi);
+        assert_eq!(
+            db.livery_for_driver("Jean Alesi").unwrap().secondary,
+            (0, 0, 0)
+        );
+
+        assert!(db.livery_for_driver("Unknown Driver").is_none());
+    }
+
+    #[test]
+    fn test_sample_json_defines_driver_liveries() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/samples/driver_db.json");
+        let db = CarDatabase::from_json_file(&path).unwrap();
+        assert!(db.drivers().all(|driver| driver.livery.is_some()));
+    }
+
     #[test]
     fn test_load_from_disk_prefers_env_path() {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -37,6 +37,7 @@ pub fn synthetic_track(section_count: usize, length_meters: f32) -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
@@ -2,18 +2,24 @@
 //!
 //! Contains data structures and loaders for F1GP game files
 
+pub mod annotations;
 pub mod asset;
 pub mod car;
 pub mod fixtures;
 pub mod loader;
 pub mod objects;
 pub mod parser;
+pub mod setup;
+pub mod sound_bank;
 pub mod track;
 
+pub use annotations::*;
 pub use asset::*;
 pub use car::*;
 pub use fixtures::*;
 pub use loader::*;
 pub use objects::*;
 pub use parser::*;
+pub use setup::*;
+pub use sound_bank::*;
 pub use track::*;
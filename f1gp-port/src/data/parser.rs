@@ -797,6 +797,31 @@ mod tests {
         assert!(track.length > 2500.0);
     }
 
+    #[test]
+    fn test_section_positions_follow_height() {
+        // Eau Rouge style profile: a short dip into a steep climb
+        let mut sections: Vec<TrackSection> = [-2000, -2000, 3000, 4000, 4000, 0]
+            .into_iter()
+            .map(|height| TrackSection {
+                length: 50.0,
+                height,
+                ..TrackSection::default()
+            })
+            .collect();
+
+        calculate_section_positions(&mut sections);
+
+        let elevations: Vec<f32> = sections.iter().map(|s| s.elevation).collect();
+        let expected = [0.0, -2.0, -4.0, -1.0, 3.0, 7.0];
+        for (elevation, expected) in elevations.iter().zip(expected) {
+            assert!((elevation - expected).abs() < 1e-4);
+        }
+
+        for section in &sections {
+            assert_eq!(section.position.y, section.elevation);
+        }
+    }
+
     #[test]
     fn test_parse_track_asset_basic() {
         let data = synthetic_track_bytes(DEFAULT_SECTION_COUNT, DEFAULT_TRACK_DATA_OFFSET);
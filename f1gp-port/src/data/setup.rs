@@ -0,0 +1,285 @@
+//! Original F1GP car setup files
+//!
+//! Reads and writes the single-setup files the original game saved from its
+//! car setup screen, so the thousands still hosted on community sites can be
+//! driven in the port. A file holds two 16-byte blocks, the qualifying setup
+//! followed by the race setup:
+//!
+//! | Offset | Field                                           |
+//! |--------|-------------------------------------------------|
+//! | 0      | Front wing (1-20)                               |
+//! | 1      | Rear wing (1-20)                                |
+//! | 2-7    | Gears 1-6 (1-64, higher = taller)               |
+//! | 8      | Brake balance (front share, 0-100)              |
+//! | 9      | Tyre compound (0-3 = A-D, 4 = qualifying)       |
+//! | 10-15  | Padding                                         |
+//!
+//! Padding is ignored on import and written as zeros on export.
+
+use super::track::CarSetup;
+use crate::game::TireCompound;
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Size of a setup file in bytes
+pub const SETUP_FILE_LEN: usize = 2 * SETUP_BLOCK_LEN;
+
+/// Size of one setup block (qualifying or race) in bytes
+pub const SETUP_BLOCK_LEN: usize = 16;
+
+/// Bytes of a block holding settings; the rest is padding
+pub const SETUP_DATA_LEN: usize = 10;
+
+/// Valid wing settings
+pub const WING_RANGE: RangeInclusive<u8> = 1..=20;
+
+/// Valid gear settings
+pub const GEAR_RANGE: RangeInclusive<u8> = 1..=64;
+
+/// Valid brake balance settings (percent of braking on the front axle)
+pub const BRAKE_BALANCE_RANGE: RangeInclusive<u8> = 0..=100;
+
+/// Gear setting giving a 1:1 drivetrain ratio (40 gives the stock 0.9 top
+/// gear)
+pub const GEAR_RATIO_SCALE: f32 = 36.0;
+
+/// Environment variable overriding where the setup menu exports to
+pub const SETUP_EXPORT_ENV: &str = "F1GP_SETUP_PATH";
+
+/// Default relative location for exported setups
+pub const DEFAULT_SETUP_EXPORT: &str = "settings/player.set";
+
+const GEAR_FIELDS: [&str; 6] = ["gear 1", "gear 2", "gear 3", "gear 4", "gear 5", "gear 6"];
+
+/// Why a setup file could not be read
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SetupFileError {
+    #[error("Setup file is {found} bytes, expected {expected}")]
+    WrongLength { found: usize, expected: usize },
+
+    #[error("{block} setup: {field} is {value}, outside {min}-{max}")]
+    OutOfRange {
+        block: &'static str,
+        field: &'static str,
+        value: u8,
+        min: u8,
+        max: u8,
+    },
+
+    #[error("{block} setup: unknown tyre compound {value}")]
+    UnknownCompound { block: &'static str, value: u8 },
+}
+
+/// Qualifying and race setups from one setup file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetupFile {
+    /// Setup used in qualifying
+    pub qualifying: CarSetup,
+
+    /// Setup used in the race
+    pub race: CarSetup,
+}
+
+impl SetupFile {
+    /// A file using `setup` for both qualifying and the race
+    pub fn new(setup: CarSetup) -> Self {
+        Self {
+            qualifying: setup.clone(),
+            race: setup,
+        }
+    }
+
+    /// Parse the bytes of a setup file
+    pub fn parse(bytes: &[u8]) -> Result<Self, SetupFileError> {
+        if bytes.len() != SETUP_FILE_LEN {
+            return Err(SetupFileError::WrongLength {
+                found: bytes.len(),
+                expected: SETUP_FILE_LEN,
+            });
+        }
+
+        let (qualifying, race) = bytes.split_at(SETUP_BLOCK_LEN);
+        Ok(Self {
+            qualifying: parse_block(qualifying, "qualifying")?,
+            race: parse_block(race, "race")?,
+        })
+    }
+
+    /// Bytes of this setup file, with zeroed padding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SETUP_FILE_LEN);
+        for setup in [&self.qualifying, &self.race] {
+            bytes.push(setup.front_wing);
+            bytes.push(setup.rear_wing);
+            bytes.extend_from_slice(&setup.gear_ratios);
+            bytes.push(setup.brake_balance);
+            bytes.push(compound_code(setup.tire_compound));
+            bytes.resize(bytes.len() + SETUP_BLOCK_LEN - SETUP_DATA_LEN, 0);
+        }
+        bytes
+    }
+
+    /// Load a setup file from disk
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path_ref = path.as_ref();
+        let bytes = fs::read(path_ref)
+            .with_context(|| format!("Failed to read setup file {}", path_ref.display()))?;
+        Self::parse(&bytes).with_context(|| format!("Invalid setup file {}", path_ref.display()))
+    }
+
+    /// Write this setup file to disk, creating parent directories as needed
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path_ref = path.as_ref();
+        if let Some(parent) = path_ref.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create setup directory {}", parent.display())
+                })?;
+            }
+        }
+        fs::write(path_ref, self.to_bytes())
+            .with_context(|| format!("Failed to write setup file {}", path_ref.display()))
+    }
+
+    /// Where the setup menu exports to (environment override or default)
+    pub fn export_path() -> PathBuf {
+        env::var_os(SETUP_EXPORT_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SETUP_EXPORT))
+    }
+}
+
+impl CarSetup {
+    /// Drivetrain ratios for gears 1-6: taller gear settings give lower
+    /// ratios, so more top speed and less acceleration in that gear
+    pub fn physics_gear_ratios(&self) -> [f32; 6] {
+        self.gear_ratios
+            .map(|setting| GEAR_RATIO_SCALE / f32::from(setting.max(1)))
+    }
+}
+
+/// Parse one 16-byte setup block
+fn parse_block(block: &[u8], name: &'static str) -> Result<CarSetup, SetupFileError> {
+    let in_range = |field: &'static str, value: u8, range: &RangeInclusive<u8>| {
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(SetupFileError::OutOfRange {
+                block: name,
+                field,
+                value,
+                min: *range.start(),
+                max: *range.end(),
+            })
+        }
+    };
+
+    let mut gear_ratios = [0u8; 6];
+    for (i, gear) in gear_ratios.iter_mut().enumerate() {
+        *gear = in_range(GEAR_FIELDS[i], block[2 + i], &GEAR_RANGE)?;
+    }
+
+    Ok(CarSetup {
+        front_wing: in_range("front wing", block[0], &WING_RANGE)?,
+        rear_wing: in_range("rear wing", block[1], &WING_RANGE)?,
+        gear_ratios,
+        brake_balance: in_range("brake balance", block[8], &BRAKE_BALANCE_RANGE)?,
+        tire_compound: compound_from_code(block[9]).ok_or(SetupFileError::UnknownCompound {
+            block: name,
+            value: block[9],
+        })?,
+    })
+}
+
+fn compound_code(compound: TireCompound) -> u8 {
+    match compound {
+        TireCompound::A => 0,
+        TireCompound::B => 1,
+        TireCompound::C => 2,
+        TireCompound::D => 3,
+        TireCompound::Qualifying => 4,
+    }
+}
+
+fn compound_from_code(code: u8) -> Option<TireCompound> {
+    match code {
+        0 => Some(TireCompound::A),
+        1 => Some(TireCompound::B),
+        2 => Some(TireCompound::C),
+        3 => Some(TireCompound::D),
+        4 => Some(TireCompound::Qualifying),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::DEFAULT_GEAR_RATIOS;
+
+    fn block(compound: u8) -> [u8; SETUP_BLOCK_LEN] {
+        [5, 7, 12, 17, 22, 28, 34, 40, 55, compound, 0, 0, 0, 0, 0, 0]
+    }
+
+    fn file_bytes() -> Vec<u8> {
+        [block(4), block(2)].concat()
+    }
+
+    #[test]
+    fn parses_both_blocks() {
+        let file = SetupFile::parse(&file_bytes()).unwrap();
+        assert_eq!(file.qualifying.tire_compound, TireCompound::Qualifying);
+        assert_eq!(file.race.tire_compound, TireCompound::C);
+        assert_eq!(file.race.front_wing, 5);
+        assert_eq!(file.race.rear_wing, 7);
+        assert_eq!(file.race.gear_ratios, [12, 17, 22, 28, 34, 40]);
+        assert_eq!(file.race.brake_balance, 55);
+        assert_eq!(file.to_bytes(), file_bytes());
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let err = SetupFile::parse(&file_bytes()[..20]).unwrap_err();
+        assert_eq!(
+            err,
+            SetupFileError::WrongLength {
+                found: 20,
+                expected: SETUP_FILE_LEN
+            }
+        );
+        assert_eq!(err.to_string(), "Setup file is 20 bytes, expected 32");
+    }
+
+    #[test]
+    fn rejects_out_of_range_settings() {
+        let mut bytes = file_bytes();
+        bytes[SETUP_BLOCK_LEN + 4] = 90;
+        let err = SetupFile::parse(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), "race setup: gear 3 is 90, outside 1-64");
+
+        let mut bytes = file_bytes();
+        bytes[0] = 0;
+        let err = SetupFile::parse(&bytes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "qualifying setup: front wing is 0, outside 1-20"
+        );
+
+        let mut bytes = file_bytes();
+        bytes[SETUP_BLOCK_LEN + 9] = 7;
+        let err = SetupFile::parse(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), "race setup: unknown tyre compound 7");
+    }
+
+    #[test]
+    fn default_setup_matches_stock_gearing() {
+        let ratios = CarSetup::default().physics_gear_ratios();
+        for (ratio, stock) in ratios.iter().zip(DEFAULT_GEAR_RATIOS) {
+            assert!((ratio - stock).abs() < 0.15, "{} vs {}", ratio, stock);
+        }
+    }
+}
@@ -0,0 +1,491 @@
+//! Original sound bank loader
+//!
+//! F1GP ships its digitised effects as 8-bit unsigned mono PCM, either packed
+//! into a single bank file (`.SAM`) or as loose `.PCM`/`.SND` files in a
+//! `SAMPLES` directory. Two bank layouts are recognised:
+//!
+//! | Layout | Header | Directory entry (little-endian) |
+//! |--------|--------|---------------------------------|
+//! | [`BankVersion::SamV1`] | `u16` entry count | `u32` offset, `u32` length (11025 Hz implied) |
+//! | [`BankVersion::SamV2`] | `u16` entry count | `u32` offset, `u32` length, `u16` rate, `u16` flags |
+//!
+//! Offsets are absolute and must point past the directory. Bit 0 of the V2
+//! flags marks a looping sample. A loose directory is reported as
+//! [`BankVersion::PcmDirectory`]; its files are ordered by name and all play
+//! at 11025 Hz.
+//!
+//! Samples are decoded to `f32` on first use and cached for the lifetime of
+//! the bank.
+
+use anyhow::{bail, Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// Environment variable pointing at an extracted copy of the original game
+pub const ORIGINAL_DATA_ENV: &str = "F1GP_ORIGINAL_DATA";
+
+/// Sample rate of the original effects when the bank does not say otherwise
+pub const DEFAULT_SAMPLE_RATE: u32 = 11025;
+
+/// Largest entry count accepted in a bank header
+const MAX_ENTRIES: usize = 256;
+
+/// Sample rates accepted in a V2 directory entry
+const SAMPLE_RATE_RANGE: std::ops::RangeInclusive<u32> = 4000..=48000;
+
+/// V2 flag bit marking a looping sample
+const FLAG_LOOP: u16 = 0x0001;
+
+/// File names probed, in order, under the original data directory
+const BANK_CANDIDATES: &[&str] = &[
+    "SAMPLES.SAM",
+    "HARDDISK/SAMPLES.SAM",
+    "HARDDISK/SAMPLES",
+    "SAMPLES",
+];
+
+/// Which layout a bank was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankVersion {
+    /// Bank file with offset/length directory entries
+    SamV1,
+    /// Bank file with offset/length/rate/flags directory entries
+    SamV2,
+    /// Directory of loose headerless PCM files
+    PcmDirectory,
+}
+
+impl BankVersion {
+    /// Size in bytes of one directory entry, if the layout has a directory
+    fn entry_size(self) -> Option<usize> {
+        match self {
+            BankVersion::SamV1 => Some(8),
+            BankVersion::SamV2 => Some(12),
+            BankVersion::PcmDirectory => None,
+        }
+    }
+}
+
+impl fmt::Display for BankVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankVersion::SamV1 => write!(f, "SAM v1"),
+            BankVersion::SamV2 => write!(f, "SAM v2"),
+            BankVersion::PcmDirectory => write!(f, "PCM directory"),
+        }
+    }
+}
+
+/// Directory entry describing one sample in a bank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankEntry {
+    /// Entry name (file stem for loose files, index for bank files)
+    pub name: String,
+    /// Byte offset of the sample data within the bank
+    pub offset: usize,
+    /// Sample data length in bytes (one byte per sample)
+    pub length: usize,
+    /// Playback rate in Hz
+    pub sample_rate: u32,
+    /// Whether the sample is meant to loop
+    pub looped: bool,
+}
+
+/// A decoded sample ready for mixing
+#[derive(Debug, Clone, PartialEq)]
+pub struct BankSample {
+    /// Playback rate in Hz
+    pub sample_rate: u32,
+    /// Whether the sample is meant to loop
+    pub looped: bool,
+    /// Mono samples in the range -1.0..1.0
+    pub data: Vec<f32>,
+}
+
+impl BankSample {
+    /// Playback length in seconds
+    pub fn duration(&self) -> f32 {
+        self.data.len() as f32 / self.sample_rate as f32
+    }
+}
+
+/// An original sound bank held in memory
+#[derive(Debug)]
+pub struct SoundBank {
+    version: BankVersion,
+    source: PathBuf,
+    entries: Vec<BankEntry>,
+    data: Vec<u8>,
+    decoded: Vec<OnceLock<Arc<BankSample>>>,
+}
+
+impl SoundBank {
+    /// Parse a `.SAM` bank from memory, detecting its layout
+    pub fn parse(data: Vec<u8>, source: impl Into<PathBuf>) -> Result<Self> {
+        let source = source.into();
+        let version = detect_version(&data)
+            .with_context(|| format!("Unrecognised sound bank layout: {}", source.display()))?;
+        let entries = read_directory(&data, version)?;
+        Ok(Self::from_parts(version, source, entries, data))
+    }
+
+    /// Load a `.SAM` bank file or a directory of loose PCM files
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            Self::load_dir(path)
+        } else {
+            let data = fs::read(path)
+                .with_context(|| format!("Failed to read sound bank: {}", path.display()))?;
+            Self::parse(data, path)
+        }
+    }
+
+    /// Load every `.PCM`/`.SND` file in a directory as one bank
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read sample directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| is_pcm_file(path))
+            .collect();
+        files.sort();
+
+        if files.is_empty() {
+            bail!("No PCM samples found in {}", dir.display());
+        }
+
+        let mut data = Vec::new();
+        let mut entries = Vec::with_capacity(files.len());
+        for path in files {
+            let bytes = fs::read(&path)
+                .with_context(|| format!("Failed to read sample: {}", path.display()))?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_uppercase())
+                .unwrap_or_default();
+            entries.push(BankEntry {
+                name,
+                offset: data.len(),
+                length: bytes.len(),
+                sample_rate: DEFAULT_SAMPLE_RATE,
+                looped: false,
+            });
+            data.extend_from_slice(&bytes);
+        }
+
+        Ok(Self::from_parts(
+            BankVersion::PcmDirectory,
+            dir.to_path_buf(),
+            entries,
+            data,
+        ))
+    }
+
+    /// Find and load the sound bank inside an original data directory
+    pub fn from_original_data<P: AsRef<Path>>(root: P) -> Result<Self> {
+        let root = root.as_ref();
+        let path = BANK_CANDIDATES
+            .iter()
+            .map(|candidate| root.join(candidate))
+            .find(|path| path.exists())
+            .with_context(|| {
+                format!(
+                    "No sound bank found under {} (looked for {})",
+                    root.display(),
+                    BANK_CANDIDATES.join(", ")
+                )
+            })?;
+
+        let bank = Self::load(&path)?;
+        log::info!(
+            "Loaded {} sound bank from {} ({} samples)",
+            bank.version,
+            bank.source.display(),
+            bank.len()
+        );
+        Ok(bank)
+    }
+
+    fn from_parts(
+        version: BankVersion,
+        source: PathBuf,
+        entries: Vec<BankEntry>,
+        data: Vec<u8>,
+    ) -> Self {
+        let decoded = entries.iter().map(|_| OnceLock::new()).collect();
+        Self {
+            version,
+            source,
+            entries,
+            data,
+            decoded,
+        }
+    }
+
+    /// Layout the bank was detected as
+    pub fn version(&self) -> BankVersion {
+        self.version
+    }
+
+    /// File or directory the bank was loaded from
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Directory entries in bank order
+    pub fn entries(&self) -> &[BankEntry] {
+        &self.entries
+    }
+
+    /// Number of samples in the bank
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the bank has no samples
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Decoded sample at `index`, decoding it on first access
+    pub fn sample(&self, index: usize) -> Option<Arc<BankSample>> {
+        let entry = self.entries.get(index)?;
+        let sample = self.decoded[index].get_or_init(|| {
+            let raw = &self.data[entry.offset..entry.offset + entry.length];
+            Arc::new(BankSample {
+                sample_rate: entry.sample_rate,
+                looped: entry.looped,
+                data: decode_pcm_u8(raw),
+            })
+        });
+        Some(Arc::clone(sample))
+    }
+
+    /// Number of samples decoded so far
+    pub fn decoded_count(&self) -> usize {
+        self.decoded
+            .iter()
+            .filter(|cell| cell.get().is_some())
+            .count()
+    }
+}
+
+/// Convert 8-bit unsigned PCM to `f32` samples centred on zero
+pub fn decode_pcm_u8(raw: &[u8]) -> Vec<f32> {
+    raw.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect()
+}
+
+fn is_pcm_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("pcm") || ext.eq_ignore_ascii_case("snd"))
+            .unwrap_or(false)
+}
+
+/// Pick the first layout whose directory is self-consistent
+fn detect_version(data: &[u8]) -> Result<BankVersion> {
+    [BankVersion::SamV2, BankVersion::SamV1]
+        .into_iter()
+        .find(|&version| read_directory(data, version).is_ok())
+        .context("Directory does not match any known bank layout")
+}
+
+fn read_directory(data: &[u8], version: BankVersion) -> Result<Vec<BankEntry>> {
+    let Some(entry_size) = version.entry_size() else {
+        bail!("{} has no directory", version);
+    };
+    if data.len() < 2 {
+        bail!("Sound bank too short for a header");
+    }
+
+    let count = u16::from_le_bytes([data[0], data[1]]) as usize;
+    if count == 0 || count > MAX_ENTRIES {
+        bail!("Invalid sound bank entry count: {}", count);
+    }
+
+    let directory_end = 2 + count * entry_size;
+    if data.len() < directory_end {
+        bail!("Sound bank directory truncated");
+    }
+
+    let read_u16 = |pos: usize| u16::from_le_bytes([data[pos], data[pos + 1]]);
+    let read_u32 =
+        |pos: usize| u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let pos = 2 + index * entry_size;
+        let offset = read_u32(pos) as usize;
+        let length = read_u32(pos + 4) as usize;
+        let (sample_rate, looped) = match version {
+            BankVersion::SamV2 => (
+                read_u16(pos + 8) as u32,
+                read_u16(pos + 10) & FLAG_LOOP != 0,
+            ),
+            _ => (DEFAULT_SAMPLE_RATE, false),
+        };
+
+        if offset < directory_end || offset.saturating_add(length) > data.len() {
+            bail!("Sample {} lies outside the bank data", index);
+        }
+        if !SAMPLE_RATE_RANGE.contains(&sample_rate) {
+            bail!("Sample {} has implausible rate {} Hz", index, sample_rate);
+        }
+
+        entries.push(BankEntry {
+            name: format!("{:02}", index),
+            offset,
+            length,
+            sample_rate,
+            looped,
+        });
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Build a bank with the given layout holding `samples`
+    /// as `(rate, looped, pcm)` triples
+    pub(crate) fn synthetic_bank(
+        version: BankVersion,
+        samples: &[(u16, bool, Vec<u8>)],
+    ) -> Vec<u8> {
+        let entry_size = version.entry_size().unwrap();
+        let mut offset = 2 + samples.len() * entry_size;
+
+        let mut bank = (samples.len() as u16).to_le_bytes().to_vec();
+        for (rate, looped, pcm) in samples {
+            bank.extend_from_slice(&(offset as u32).to_le_bytes());
+            bank.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+            if version == BankVersion::SamV2 {
+                bank.extend_from_slice(&rate.to_le_bytes());
+                bank.extend_from_slice(&(if *looped { FLAG_LOOP } else { 0 }).to_le_bytes());
+            }
+            offset += pcm.len();
+        }
+        for (_, _, pcm) in samples {
+            bank.extend_from_slice(pcm);
+        }
+        bank
+    }
+
+    fn ramp(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 255 / (len - 1)) as u8).collect()
+    }
+
+    #[test]
+    fn test_parse_v2_bank() {
+        let bank = synthetic_bank(
+            BankVersion::SamV2,
+            &[
+                (11025, false, ramp(64)),
+                (22050, true, vec![128; 32]),
+                (8000, false, vec![0, 255]),
+            ],
+        );
+        let bank = SoundBank::parse(bank, "TEST.SAM").unwrap();
+
+        assert_eq!(bank.version(), BankVersion::SamV2);
+        assert_eq!(bank.source(), Path::new("TEST.SAM"));
+        assert_eq!(bank.len(), 3);
+        assert_eq!(bank.entries()[1].sample_rate, 22050);
+        assert!(bank.entries()[1].looped);
+
+        let first = bank.sample(0).unwrap();
+        assert_eq!(first.data.len(), 64);
+        assert_eq!(first.data[0], -1.0);
+        assert!(first.data[63] > 0.99);
+
+        let silence = bank.sample(1).unwrap();
+        assert!(silence.data.iter().all(|&s| s == 0.0));
+        assert!((silence.duration() - 32.0 / 22050.0).abs() < f32::EPSILON);
+
+        assert_eq!(bank.sample(2).unwrap().data, vec![-1.0, 127.0 / 128.0]);
+        assert!(bank.sample(3).is_none());
+    }
+
+    #[test]
+    fn test_parse_v1_bank() {
+        let bank = synthetic_bank(
+            BankVersion::SamV1,
+            &[(0, false, ramp(100)), (0, false, ramp(50))],
+        );
+        let bank = SoundBank::parse(bank, "OLD.SAM").unwrap();
+
+        assert_eq!(bank.version(), BankVersion::SamV1);
+        assert_eq!(bank.len(), 2);
+        assert!(bank
+            .entries()
+            .iter()
+            .all(|e| e.sample_rate == DEFAULT_SAMPLE_RATE && !e.looped));
+        assert_eq!(bank.sample(1).unwrap().data.len(), 50);
+    }
+
+    #[test]
+    fn test_rejects_malformed_banks() {
+        assert!(SoundBank::parse(vec![], "EMPTY.SAM").is_err());
+        assert!(SoundBank::parse(vec![0, 0], "ZERO.SAM").is_err());
+
+        let mut truncated = synthetic_bank(BankVersion::SamV2, &[(11025, false, ramp(64))]);
+        truncated.truncate(40);
+        assert!(SoundBank::parse(truncated, "SHORT.SAM").is_err());
+
+        let mut bad_offset = synthetic_bank(BankVersion::SamV1, &[(0, false, ramp(16))]);
+        bad_offset[2..6].copy_from_slice(&0u32.to_le_bytes());
+        assert!(SoundBank::parse(bad_offset, "OVERLAP.SAM").is_err());
+    }
+
+    #[test]
+    fn test_samples_are_decoded_once() {
+        let bank = synthetic_bank(
+            BankVersion::SamV2,
+            &[(11025, false, ramp(16)), (11025, false, ramp(16))],
+        );
+        let bank = SoundBank::parse(bank, "CACHE.SAM").unwrap();
+        assert_eq!(bank.decoded_count(), 0);
+
+        let first = bank.sample(0).unwrap();
+        let again = bank.sample(0).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(bank.decoded_count(), 1);
+    }
+
+    #[test]
+    fn test_load_pcm_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("crowd.pcm"), ramp(30)).unwrap();
+        fs::write(dir.path().join("CRASH.SND"), vec![200; 10]).unwrap();
+        fs::write(dir.path().join("README.TXT"), b"not audio").unwrap();
+
+        let bank = SoundBank::load(dir.path()).unwrap();
+        assert_eq!(bank.version(), BankVersion::PcmDirectory);
+        let names: Vec<_> = bank.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["CRASH", "CROWD"]);
+        assert_eq!(bank.sample(0).unwrap().data, vec![72.0 / 128.0; 10]);
+        assert_eq!(bank.sample(1).unwrap().data.len(), 30);
+    }
+
+    #[test]
+    fn test_from_original_data() {
+        let root = TempDir::new().unwrap();
+        assert!(SoundBank::from_original_data(root.path()).is_err());
+
+        fs::create_dir_all(root.path().join("HARDDISK")).unwrap();
+        let bank = synthetic_bank(BankVersion::SamV2, &[(11025, false, ramp(8))]);
+        fs::write(root.path().join("HARDDISK/SAMPLES.SAM"), bank).unwrap();
+
+        let bank = SoundBank::from_original_data(root.path()).unwrap();
+        assert_eq!(bank.version(), BankVersion::SamV2);
+        assert_eq!(bank.source(), root.path().join("HARDDISK/SAMPLES.SAM"));
+    }
+}
@@ -3,8 +3,10 @@
 //! This module contains data structures representing the F1GP track file format.
 //! Based on reverse engineering and ArgDocs documentation.
 
+use super::annotations::TrackAnnotations;
 use super::objects::ObjectShape;
-use glam::Vec3;
+use crate::game::TireCompound;
+use glam::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 /// Offsets into the track file
@@ -70,6 +72,10 @@ pub struct Track {
     /// Camera definitions
     pub cameras: Vec<Camera>,
 
+    /// Named corners along the lap
+    #[serde(default)]
+    pub annotations: TrackAnnotations,
+
     /// File checksum (last 4 bytes)
     pub checksum: u32,
 }
@@ -129,10 +135,10 @@ pub struct TrackSection {
     /// Track width at this section (meters, from track header)
     pub width: f32,
 
-    /// Banking angle (calculated from flags, radians)
+    /// Camber/banking angle (radians, + = road leans right, i.e. banked for a right-hander)
     pub banking: f32,
 
-    /// Elevation in world space (calculated)
+    /// Elevation of the section start in world space (meters, calculated from `height`)
     pub elevation: f32,
 }
 
@@ -225,7 +231,7 @@ pub struct AIBehavior {
 }
 
 /// Car setup parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CarSetup {
     /// Front wing setting (1-20)
     pub front_wing: u8,
@@ -233,11 +239,15 @@ pub struct CarSetup {
     /// Rear wing setting (1-20)
     pub rear_wing: u8,
 
-    /// Gear ratios (6 gears)
+    /// Gear settings (6 gears, 1-64, higher = taller)
     pub gear_ratios: [u8; 6],
 
     /// Brake balance (front/rear, 0-100)
     pub brake_balance: u8,
+
+    /// Tyre compound fitted at the start
+    #[serde(default)]
+    pub tire_compound: TireCompound,
 }
 
 /// Camera definition
@@ -268,6 +278,7 @@ impl Track {
             ai_behavior: AIBehavior::default(),
             pit_lane: Vec::new(),
             cameras: Vec::new(),
+            annotations: TrackAnnotations::default(),
             checksum: 0,
         }
     }
@@ -284,6 +295,36 @@ impl Track {
 
         Ok(())
     }
+
+    /// Gradient of a section as rise over run in the driving direction
+    /// (0.1 = 10% uphill, negative = downhill)
+    pub fn section_grade(&self, index: usize) -> f32 {
+        let count = self.sections.len();
+        if count < 2 || index >= count {
+            return 0.0;
+        }
+
+        let start = &self.sections[index];
+        let end = &self.sections[(index + 1) % count];
+        let rise = end.elevation - start.elevation;
+        let run = Vec2::new(
+            end.position.x - start.position.x,
+            end.position.z - start.position.z,
+        )
+        .length()
+        .max(start.length);
+
+        if run > 0.0 {
+            rise / run
+        } else {
+            0.0
+        }
+    }
+
+    /// Camber of a section (radians, + = banked for a right-hander)
+    pub fn section_camber(&self, index: usize) -> f32 {
+        self.sections.get(index).map_or(0.0, |s| s.banking)
+    }
 }
 
 impl Default for AIBehavior {
@@ -301,8 +342,9 @@ impl Default for CarSetup {
         Self {
             front_wing: 10,
             rear_wing: 10,
-            gear_ratios: [4, 8, 12, 16, 20, 24],
+            gear_ratios: [10, 14, 20, 26, 33, 40],
             brake_balance: 50,
+            tire_compound: TireCompound::C,
         }
     }
 }
@@ -370,6 +412,28 @@ mod tests {
         assert!(track.validate().is_ok());
     }
 
+    #[test]
+    fn test_section_grade_and_camber() {
+        let mut track = Track::new("Hill".to_string());
+        for (i, elevation) in [0.0, 10.0, 5.0].iter().enumerate() {
+            track.sections.push(TrackSection {
+                position: Vec3::new(i as f32 * 100.0, *elevation, 0.0),
+                elevation: *elevation,
+                banking: 0.05 * i as f32,
+                ..TrackSection::straight(100.0)
+            });
+        }
+
+        assert!((track.section_grade(0) - 0.1).abs() < 1e-6);
+        assert!((track.section_grade(1) + 0.05).abs() < 1e-6);
+        // Last section closes the loop back to the start
+        assert!(track.section_grade(2) < 0.0);
+        assert_eq!(track.section_grade(10), 0.0);
+
+        assert_eq!(track.section_camber(2), 0.1);
+        assert_eq!(track.section_camber(10), 0.0);
+    }
+
     #[test]
     fn test_surface_types() {
         let track_surface = SurfaceType::Track;
@@ -0,0 +1,85 @@
+//! Camera settings
+//!
+//! Last-used 3D camera mode and rear-view mirror options, persisted to a
+//! small JSON settings file between sessions.
+
+use crate::game::settings_file::SettingsFile;
+use crate::render3d::{CameraMode, MirrorSettings};
+use serde::{Deserialize, Serialize};
+
+/// Environment variable overriding the camera settings file location
+pub const CAMERA_SETTINGS_ENV: &str = "F1GP_CAMERA_PATH";
+
+/// Default relative location for the camera settings file
+pub const DEFAULT_CAMERA_SETTINGS: &str = "settings/camera.json";
+
+/// Persisted camera configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    /// Camera mode the player last switched to
+    pub mode: CameraMode,
+
+    /// Rear-view mirrors in cockpit view
+    pub mirrors: MirrorSettings,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            mode: CameraMode::Chase,
+            mirrors: MirrorSettings::default(),
+        }
+    }
+}
+
+impl SettingsFile for CameraSettings {
+    const PATH_ENV: &'static str = CAMERA_SETTINGS_ENV;
+    const DEFAULT_PATH: &'static str = DEFAULT_CAMERA_SETTINGS;
+    const WHAT: &'static str = "camera settings";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings() {
+        let settings = CameraSettings::default();
+        assert_eq!(settings.mode, CameraMode::Chase);
+        assert!(settings.mirrors.enabled);
+        assert_eq!(settings.mirrors.update_interval, 2);
+    }
+
+    #[test]
+    fn test_partial_json_uses_defaults() {
+        let settings: CameraSettings =
+            serde_json::from_str(r#"{ "mode": "Cockpit", "mirrors": { "update_interval": 1 } }"#)
+                .unwrap();
+        assert_eq!(settings.mode, CameraMode::Cockpit);
+        assert_eq!(settings.mirrors.update_interval, 1);
+        assert!(settings.mirrors.enabled);
+        assert_eq!(
+            settings.mirrors.draw_distance,
+            MirrorSettings::default().draw_distance
+        );
+    }
+
+    #[test]
+    fn test_json_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("camera.json");
+
+        let settings = CameraSettings {
+            mode: CameraMode::Cockpit,
+            mirrors: MirrorSettings {
+                enabled: false,
+                ..MirrorSettings::default()
+            },
+        };
+        settings.to_json_file(&path).unwrap();
+
+        let loaded = CameraSettings::from_json_file(&path).unwrap();
+        assert_eq!(loaded, settings);
+    }
+}
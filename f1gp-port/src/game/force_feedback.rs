@@ -0,0 +1,97 @@
+//! Force feedback settings
+//!
+//! Overall strength, smoothing and per-effect toggles for steering wheel
+//! force feedback, read from a small JSON settings file.
+
+use crate::game::settings_file::SettingsFile;
+use crate::physics::FfbEffects;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable overriding the force feedback settings file location
+pub const FFB_SETTINGS_ENV: &str = "F1GP_FFB_PATH";
+
+/// Default relative location for the force feedback settings file
+pub const DEFAULT_FFB_SETTINGS: &str = "settings/force_feedback.json";
+
+/// Persisted force feedback configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForceFeedbackSettings {
+    /// Drive the wheel at all
+    pub enabled: bool,
+
+    /// Overall gain (1.0 = full-scale wheel torque at the model's peak)
+    pub strength: f32,
+
+    /// Output smoothing time constant (milliseconds, 0 = none)
+    pub smoothing_ms: f32,
+
+    /// Individual effect toggles
+    pub effects: FfbEffects,
+}
+
+impl Default for ForceFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            strength: 0.8,
+            smoothing_ms: 15.0,
+            effects: FfbEffects::default(),
+        }
+    }
+}
+
+impl ForceFeedbackSettings {
+    /// Smoothing time constant in seconds
+    pub fn smoothing_secs(&self) -> f32 {
+        self.smoothing_ms.max(0.0) / 1000.0
+    }
+}
+
+impl SettingsFile for ForceFeedbackSettings {
+    const PATH_ENV: &'static str = FFB_SETTINGS_ENV;
+    const DEFAULT_PATH: &'static str = DEFAULT_FFB_SETTINGS;
+    const WHAT: &'static str = "force feedback settings";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings() {
+        let settings = ForceFeedbackSettings::default();
+        assert!(settings.enabled);
+        assert!(settings.effects.self_aligning);
+        assert!((settings.smoothing_secs() - 0.015).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_partial_json_uses_defaults() {
+        let settings: ForceFeedbackSettings =
+            serde_json::from_str(r#"{ "strength": 1.5, "effects": { "kerbs": false } }"#).unwrap();
+        assert_eq!(settings.strength, 1.5);
+        assert!(settings.enabled);
+        assert!(!settings.effects.kerbs);
+        assert!(settings.effects.collisions);
+    }
+
+    #[test]
+    fn test_json_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("force_feedback.json");
+
+        let settings = ForceFeedbackSettings {
+            strength: 1.2,
+            effects: FfbEffects {
+                off_track: false,
+                ..FfbEffects::default()
+            },
+            ..ForceFeedbackSettings::default()
+        };
+        settings.to_json_file(&path).unwrap();
+
+        let loaded = ForceFeedbackSettings::from_json_file(&path).unwrap();
+        assert_eq!(loaded, settings);
+    }
+}
@@ -0,0 +1,175 @@
+//! Player livery selection
+//!
+//! Preset liveries the player can cycle through from the pause menu. The
+//! selection is persisted to a small JSON settings file between sessions.
+
+use crate::data::car::Livery;
+use crate::game::settings_file::SettingsFile;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable overriding the livery settings file location
+pub const LIVERY_SETTINGS_ENV: &str = "F1GP_LIVERY_PATH";
+
+/// Default relative location for the livery settings file
+pub const DEFAULT_LIVERY_SETTINGS: &str = "settings/player_livery.json";
+
+/// Car number shown above the player's car
+pub const DEFAULT_PLAYER_NUMBER: u8 = 40;
+
+/// Preset liveries available to the player (name, colors)
+pub const LIVERY_PRESETS: [(&str, Livery); 6] = [
+    (
+        "MCLAREN",
+        Livery {
+            primary: (255, 255, 255),
+            secondary: (255, 0, 0),
+        },
+    ),
+    (
+        "WILLIAMS",
+        Livery {
+            primary: (0, 0, 255),
+            secondary: (255, 255, 0),
+        },
+    ),
+    (
+        "FERRARI",
+        Livery {
+            primary: (255, 0, 0),
+            secondary: (255, 255, 255),
+        },
+    ),
+    (
+        "BENETTON",
+        Livery {
+            primary: (0, 160, 80),
+            secondary: (255, 220, 0),
+        },
+    ),
+    (
+        "JORDAN",
+        Livery {
+            primary: (0, 120, 60),
+            secondary: (0, 60, 160),
+        },
+    ),
+    (
+        "TYRRELL",
+        Livery {
+            primary: (0, 40, 120),
+            secondary: (255, 255, 255),
+        },
+    ),
+];
+
+/// Persisted player livery selection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiverySettings {
+    /// Name of the selected preset (see [`LIVERY_PRESETS`])
+    pub preset: String,
+
+    /// Player car number
+    #[serde(default = "default_player_number")]
+    pub number: u8,
+}
+
+fn default_player_number() -> u8 {
+    DEFAULT_PLAYER_NUMBER
+}
+
+impl Default for LiverySettings {
+    fn default() -> Self {
+        Self {
+            preset: LIVERY_PRESETS[0].0.to_string(),
+            number: DEFAULT_PLAYER_NUMBER,
+        }
+    }
+}
+
+impl LiverySettings {
+    /// Index of the selected preset (unknown names fall back to the first preset)
+    pub fn preset_index(&self) -> usize {
+        LIVERY_PRESETS
+            .iter()
+            .position(|(name, _)| *name == self.preset)
+            .unwrap_or(0)
+    }
+
+    /// Display name of the selected preset
+    pub fn preset_name(&self) -> &'static str {
+        LIVERY_PRESETS[self.preset_index()].0
+    }
+
+    /// Colors of the selected preset
+    pub fn livery(&self) -> Livery {
+        LIVERY_PRESETS[self.preset_index()].1
+    }
+
+    /// Select the next preset (wraps around)
+    pub fn next_preset(&mut self) {
+        let index = (self.preset_index() + 1) % LIVERY_PRESETS.len();
+        self.preset = LIVERY_PRESETS[index].0.to_string();
+    }
+
+    /// Select the previous preset (wraps around)
+    pub fn previous_preset(&mut self) {
+        let index = (self.preset_index() + LIVERY_PRESETS.len() - 1) % LIVERY_PRESETS.len();
+        self.preset = LIVERY_PRESETS[index].0.to_string();
+    }
+}
+
+impl SettingsFile for LiverySettings {
+    const PATH_ENV: &'static str = LIVERY_SETTINGS_ENV;
+    const DEFAULT_PATH: &'static str = DEFAULT_LIVERY_SETTINGS;
+    const WHAT: &'static str = "livery settings";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings() {
+        let settings = LiverySettings::default();
+        assert_eq!(settings.preset_index(), 0);
+        assert_eq!(settings.preset_name(), "MCLAREN");
+        assert_eq!(settings.number, DEFAULT_PLAYER_NUMBER);
+    }
+
+    #[test]
+    fn test_cycle_presets_wraps() {
+        let mut settings = LiverySettings::default();
+        settings.previous_preset();
+        assert_eq!(settings.preset_index(), LIVERY_PRESETS.len() - 1);
+
+        settings.next_preset();
+        assert_eq!(settings.preset_index(), 0);
+        settings.next_preset();
+        assert_eq!(settings.preset_name(), "WILLIAMS");
+        assert_eq!(settings.livery(), LIVERY_PRESETS[1].1);
+    }
+
+    #[test]
+    fn test_unknown_preset_falls_back_to_first() {
+        let settings = LiverySettings {
+            preset: "MARCH".to_string(),
+            number: 16,
+        };
+        assert_eq!(settings.preset_index(), 0);
+    }
+
+    #[test]
+    fn test_json_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("player_livery.json");
+
+        let mut settings = LiverySettings::default();
+        settings.next_preset();
+        settings.next_preset();
+        settings.to_json_file(&path).unwrap();
+
+        let loaded = LiverySettings::from_json_file(&path).unwrap();
+        assert_eq!(loaded, settings);
+        assert_eq!(loaded.preset_name(), "FERRARI");
+    }
+}
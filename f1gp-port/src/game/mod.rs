@@ -2,24 +2,38 @@
 //!
 //! Manages game state, input handling, and game loop integration.
 
+pub mod camera_settings;
 pub mod championship;
 pub mod damage;
+pub mod force_feedback;
 pub mod input;
+pub mod livery;
 pub mod pitstop;
+pub mod practice;
 pub mod qualifying;
 pub mod session;
+pub mod settings_file;
 pub mod state;
+pub mod telemetry_broadcast;
+pub mod time_scale;
 pub mod weather;
 pub mod weekend;
 
+pub use camera_settings::CameraSettings;
 pub use championship::{create_1991_season, Championship, DriverStanding, RaceResult};
 pub use damage::{CarComponent, CollisionType, DamageLevel, DamageState, FailureType};
+pub use force_feedback::ForceFeedbackSettings;
 pub use input::{CarInput, InputManager};
+pub use livery::{LiverySettings, LIVERY_PRESETS};
 pub use pitstop::{PitStopManager, PitStopRequest, RaceStrategy, TireCompound, TireSet};
+pub use practice::{CornerCallout, MicroSectorTimer};
 pub use qualifying::{
     create_1991_qualifying, QualifyingResult, QualifyingSession, QualifyingState,
 };
 pub use session::{DriverResult, RaceFlag, RaceSession, RaceState};
+pub use settings_file::SettingsFile;
 pub use state::{GameMode, GameState};
+pub use telemetry_broadcast::TelemetryBroadcastSettings;
+pub use time_scale::{SimClock, TimeScale, FAST_FORWARD_TICKS_PER_FRAME};
 pub use weather::{WeatherCondition, WeatherSystem};
 pub use weekend::{create_weekend, RaceWeekend, WeekendEntry, WeekendSession, WeekendState};
@@ -3,14 +3,17 @@
 //! Handles pit stops, tire changes, fuel loads, and race strategy.
 //! Based on 1991 F1 regulations with refueling banned (fuel load fixed at start).
 
+use serde::{Deserialize, Serialize};
+
 /// Tire compound types available in F1GP
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TireCompound {
     /// Type A - Hardest compound, longest life, least grip
     A,
     /// Type B - Medium-hard compound
     B,
     /// Type C - Medium compound (default)
+    #[default]
     C,
     /// Type D - Medium-soft compound
     D,
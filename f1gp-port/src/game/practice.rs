@@ -0,0 +1,491 @@
+//! Practice driving aids
+//!
+//! Helps with learning a track: the name of the corner coming up is called
+//! out briefly, and the lap is split into micro-sectors between consecutive
+//! corner annotations, each timed against the best time through it this
+//! session.
+
+use crate::data::annotations::TrackAnnotations;
+
+/// How far ahead of a corner its name is called out (meters)
+pub const CALLOUT_LOOKAHEAD: f32 = 250.0;
+
+/// How long a corner name stays on screen (seconds)
+pub const CALLOUT_DURATION: f32 = 2.5;
+
+/// Calls out the upcoming corner as the car approaches it
+#[derive(Debug, Clone, Default)]
+pub struct CornerCallout {
+    /// Corner being shown
+    shown: Option<usize>,
+
+    /// Corner already called out on this approach
+    announced: Option<usize>,
+
+    /// Time left on screen (seconds)
+    remaining: f32,
+}
+
+impl CornerCallout {
+    /// Create a callout with nothing shown
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow the car to `distance` along a lap of `lap_length` meters
+    pub fn update(
+        &mut self,
+        annotations: &TrackAnnotations,
+        distance: f32,
+        lap_length: f32,
+        delta_time: f32,
+    ) {
+        self.remaining -= delta_time;
+        if self.remaining <= 0.0 {
+            self.shown = None;
+        }
+
+        let Some(index) = annotations.upcoming_index(distance) else {
+            return;
+        };
+        let corner = annotations.get(index).map_or(0.0, |a| a.distance);
+        let gap = if lap_length > 0.0 {
+            (corner - distance).rem_euclid(lap_length)
+        } else {
+            corner - distance
+        };
+
+        if gap > CALLOUT_LOOKAHEAD {
+            self.announced = None;
+        } else if self.announced != Some(index) {
+            self.announced = Some(index);
+            self.shown = Some(index);
+            self.remaining = CALLOUT_DURATION;
+        }
+    }
+
+    /// Index of the corner to show, if any
+    pub fn shown(&self) -> Option<usize> {
+        self.shown
+    }
+
+    /// Forget the current approach, e.g. after the car was reset
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// A stretch of the lap between two annotations
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicroSector {
+    /// Name of the corner the sector runs to ("Line" for the last one)
+    pub label: String,
+
+    /// Lap distance where the sector starts (meters)
+    pub start: f32,
+
+    /// Lap distance where the sector ends (meters)
+    pub end: f32,
+}
+
+/// Time through a micro-sector on one lap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectorSplit {
+    /// Lap the split was set on (counted by the timer, from 1)
+    pub lap: u32,
+
+    /// Sector time (seconds)
+    pub time: f32,
+
+    /// Difference to the session best before this lap (negative = faster)
+    pub delta: Option<f32>,
+}
+
+/// One line of the micro-sector overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicroSectorRow<'a> {
+    /// Sector label
+    pub label: &'a str,
+
+    /// Latest split, if the sector has been driven
+    pub split: Option<SectorSplit>,
+
+    /// Whether the split is from the lap in progress
+    pub current_lap: bool,
+
+    /// Session best (seconds)
+    pub best: Option<f32>,
+}
+
+/// Times micro-sectors between consecutive annotations
+///
+/// Feed it the car's lap distance and lap time every tick with
+/// [`MicroSectorTimer::record`] and close each lap at the line with
+/// [`MicroSectorTimer::complete_lap`]. A sector only counts when the car
+/// was seen inside it before crossing its end, so starting mid-lap or being
+/// reset never produces a bogus split.
+#[derive(Debug, Clone, Default)]
+pub struct MicroSectorTimer {
+    sectors: Vec<MicroSector>,
+
+    /// Latest split per sector
+    splits: Vec<Option<SectorSplit>>,
+
+    /// Session best per sector (seconds)
+    best: Vec<Option<f32>>,
+
+    /// Lap in progress (from 1)
+    lap: u32,
+
+    /// Sector the car is expected in next
+    next: usize,
+
+    /// Whether the car has been seen inside sector `next`
+    armed: bool,
+
+    /// Lap time when sector `next` started (seconds)
+    sector_start_time: f32,
+
+    /// Previous sample (lap distance, lap time)
+    last_sample: Option<(f32, f32)>,
+}
+
+impl MicroSectorTimer {
+    /// Micro-sectors from the start line to each annotation and on to the line
+    ///
+    /// A track without annotations has no micro-sectors.
+    pub fn new(annotations: &TrackAnnotations, lap_length: f32) -> Self {
+        let mut sectors = Vec::new();
+        if !annotations.is_empty() {
+            let mut start = 0.0;
+            for annotation in annotations.iter() {
+                if annotation.distance > start && annotation.distance < lap_length {
+                    sectors.push(MicroSector {
+                        label: annotation.name.clone(),
+                        start,
+                        end: annotation.distance,
+                    });
+                    start = annotation.distance;
+                }
+            }
+            sectors.push(MicroSector {
+                label: "Line".to_string(),
+                start,
+                end: lap_length,
+            });
+        }
+
+        Self {
+            splits: vec![None; sectors.len()],
+            best: vec![None; sectors.len()],
+            sectors,
+            lap: 1,
+            ..Self::default()
+        }
+    }
+
+    /// The micro-sectors in lap order
+    pub fn sectors(&self) -> &[MicroSector] {
+        &self.sectors
+    }
+
+    /// Whether there is anything to time
+    pub fn is_empty(&self) -> bool {
+        self.sectors.is_empty()
+    }
+
+    /// Record the car at `distance` along the lap at `lap_time`
+    pub fn record(&mut self, distance: f32, lap_time: f32) {
+        let previous = self.last_sample.replace((distance, lap_time));
+        let last = self.sectors.len().saturating_sub(1);
+
+        // The last sector ends at the line, which complete_lap handles
+        while self.next < last {
+            let sector = &self.sectors[self.next];
+            if !self.armed {
+                self.armed = distance >= sector.start && distance < sector.end;
+                return;
+            }
+            if distance < sector.end {
+                return;
+            }
+
+            // Interpolate the moment the car crossed the sector end
+            let crossed_at = match previous {
+                Some((d0, t0)) if distance > d0 && d0 <= sector.end => {
+                    t0 + (lap_time - t0) * (sector.end - d0) / (distance - d0)
+                }
+                _ => lap_time,
+            };
+            self.finish_sector(crossed_at);
+        }
+
+        if self.next == last && !self.armed {
+            if let Some(sector) = self.sectors.get(last) {
+                self.armed = distance >= sector.start && distance < sector.end;
+            }
+        }
+    }
+
+    /// Close the lap as the car crosses the line at `lap_time`
+    pub fn complete_lap(&mut self, lap_time: f32) {
+        if self.armed && self.next + 1 == self.sectors.len() {
+            self.finish_sector(lap_time);
+        }
+        self.lap += 1;
+        self.restart_lap();
+    }
+
+    /// Start timing again from the line, e.g. after the car was reset
+    ///
+    /// Splits already set on the abandoned lap are kept.
+    pub fn restart_lap(&mut self) {
+        self.next = 0;
+        self.armed = false;
+        self.sector_start_time = 0.0;
+        self.last_sample = None;
+    }
+
+    /// Latest split of each sector
+    pub fn splits(&self) -> &[Option<SectorSplit>] {
+        &self.splits
+    }
+
+    /// Session best of each sector (seconds)
+    pub fn best(&self) -> &[Option<f32>] {
+        &self.best
+    }
+
+    /// Overlay lines in lap order
+    pub fn rows(&self) -> Vec<MicroSectorRow<'_>> {
+        self.sectors
+            .iter()
+            .zip(&self.splits)
+            .zip(&self.best)
+            .map(|((sector, split), best)| MicroSectorRow {
+                label: &sector.label,
+                split: *split,
+                current_lap: split.is_some_and(|s| s.lap == self.lap),
+                best: *best,
+            })
+            .collect()
+    }
+
+    fn finish_sector(&mut self, end_time: f32) {
+        let index = self.next;
+        let time = end_time - self.sector_start_time;
+        let best = self.best[index];
+
+        self.splits[index] = Some(SectorSplit {
+            lap: self.lap,
+            time,
+            delta: best.map(|best| time - best),
+        });
+        self.best[index] = Some(best.map_or(time, |best| best.min(time)));
+
+        self.next += 1;
+        self.armed = false;
+        self.sector_start_time = end_time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::annotations::TrackAnnotation;
+
+    const LAP_LENGTH: f32 = 1000.0;
+    const TICK: f32 = 0.1;
+
+    /// Sectors 0-200, 200-500, 500-800 and 800-1000
+    fn timer() -> MicroSectorTimer {
+        let annotations: TrackAnnotations = vec![
+            TrackAnnotation::new("Hairpin", 200.0),
+            TrackAnnotation::new("Esses", 500.0),
+            TrackAnnotation::new("Chicane", 800.0),
+        ]
+        .into();
+        MicroSectorTimer::new(&annotations, LAP_LENGTH)
+    }
+
+    /// Drive a lap at a constant speed (m/s) through each sector, sampling
+    /// every tick, and return the lap time
+    fn drive_lap(timer: &mut MicroSectorTimer, speeds: [f32; 4]) -> f32 {
+        let bounds = [0.0, 200.0, 500.0, 800.0, LAP_LENGTH];
+        let durations: Vec<f32> = (0..4)
+            .map(|i| (bounds[i + 1] - bounds[i]) / speeds[i])
+            .collect();
+        let lap_time: f32 = durations.iter().sum();
+
+        let position = |time: f32| {
+            let mut sector_start = 0.0;
+            for (i, duration) in durations.iter().enumerate() {
+                if time <= sector_start + duration {
+                    return bounds[i] + (time - sector_start) * speeds[i];
+                }
+                sector_start += duration;
+            }
+            LAP_LENGTH
+        };
+
+        let mut tick = 0;
+        while tick as f32 * TICK < lap_time - 1e-4 {
+            let time = tick as f32 * TICK;
+            timer.record(position(time), time);
+            tick += 1;
+        }
+        timer.complete_lap(lap_time);
+        lap_time
+    }
+
+    fn times(timer: &MicroSectorTimer) -> Vec<f32> {
+        timer.splits().iter().map(|s| s.unwrap().time).collect()
+    }
+
+    fn deltas(timer: &MicroSectorTimer) -> Vec<Option<f32>> {
+        timer.splits().iter().map(|s| s.unwrap().delta).collect()
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-3, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_sectors_run_between_annotations() {
+        let timer = timer();
+        let sectors: Vec<_> = timer
+            .sectors()
+            .iter()
+            .map(|s| (s.label.as_str(), s.start, s.end))
+            .collect();
+        assert_eq!(
+            sectors,
+            [
+                ("Hairpin", 0.0, 200.0),
+                ("Esses", 200.0, 500.0),
+                ("Chicane", 500.0, 800.0),
+                ("Line", 800.0, 1000.0),
+            ]
+        );
+        assert!(MicroSectorTimer::new(&TrackAnnotations::default(), LAP_LENGTH).is_empty());
+    }
+
+    #[test]
+    fn test_first_lap_sets_the_session_best() {
+        let mut timer = timer();
+        let lap_time = drive_lap(&mut timer, [50.0; 4]);
+
+        assert!((lap_time - 20.0).abs() < 1e-3);
+        assert_close(&times(&timer), &[4.0, 6.0, 6.0, 4.0]);
+        assert_eq!(deltas(&timer), [None; 4]);
+        assert_close(
+            &timer.best().iter().map(|b| b.unwrap()).collect::<Vec<_>>(),
+            &[4.0, 6.0, 6.0, 4.0],
+        );
+    }
+
+    #[test]
+    fn test_deltas_compare_with_the_session_best() {
+        let mut timer = timer();
+        drive_lap(&mut timer, [50.0; 4]);
+
+        // Faster into the hairpin, slower through the esses
+        drive_lap(&mut timer, [100.0, 40.0, 50.0, 80.0]);
+        assert_close(&times(&timer), &[2.0, 7.5, 6.0, 2.5]);
+        let lap2: Vec<f32> = deltas(&timer).into_iter().map(Option::unwrap).collect();
+        assert_close(&lap2, &[-2.0, 1.5, 0.0, -1.5]);
+
+        // The best is per sector, so lap 3 is compared with a best lap
+        // nobody drove: 2.0, 6.0, 6.0 and 2.5 seconds
+        drive_lap(&mut timer, [50.0, 60.0, 75.0, 50.0]);
+        assert_close(&times(&timer), &[4.0, 5.0, 4.0, 4.0]);
+        let lap3: Vec<f32> = deltas(&timer).into_iter().map(Option::unwrap).collect();
+        assert_close(&lap3, &[2.0, -1.0, -2.0, 1.5]);
+        assert_close(
+            &timer.best().iter().map(|b| b.unwrap()).collect::<Vec<_>>(),
+            &[2.0, 5.0, 4.0, 2.5],
+        );
+    }
+
+    #[test]
+    fn test_rows_mark_splits_from_the_lap_in_progress() {
+        let mut timer = timer();
+        drive_lap(&mut timer, [50.0; 4]);
+
+        // Into the second sector of the next lap
+        for tick in 1..=50 {
+            timer.record(tick as f32 * 5.0, tick as f32 * TICK);
+        }
+        let rows = timer.rows();
+        assert_eq!(rows[0].label, "Hairpin");
+        assert!(rows[0].current_lap);
+        assert!((rows[0].split.unwrap().time - 4.0).abs() < 1e-3);
+        assert!(!rows[1].current_lap);
+        assert_eq!(rows[3].label, "Line");
+        assert!(rows[3].best.is_some());
+    }
+
+    #[test]
+    fn test_starting_mid_lap_times_nothing_until_the_line() {
+        let mut timer = timer();
+
+        // Spawned in the esses: the sectors already under way are skipped
+        for tick in 0..200 {
+            timer.record(300.0 + tick as f32 * 3.5, tick as f32 * TICK);
+        }
+        assert!(timer.splits().iter().all(Option::is_none));
+
+        timer.complete_lap(20.0);
+        assert!(timer.splits().iter().all(Option::is_none));
+
+        drive_lap(&mut timer, [50.0; 4]);
+        assert_close(&times(&timer), &[4.0, 6.0, 6.0, 4.0]);
+    }
+
+    #[test]
+    fn test_restarted_lap_keeps_finished_splits() {
+        let mut timer = timer();
+        for tick in 0..=60 {
+            timer.record(tick as f32 * 5.0, tick as f32 * TICK);
+        }
+        timer.restart_lap();
+        timer.record(420.0, 0.0);
+        timer.record(520.0, 1.0);
+
+        assert!((timer.splits()[0].unwrap().time - 4.0).abs() < 1e-3);
+        assert!(timer.splits()[1].is_none());
+    }
+
+    #[test]
+    fn test_callout_shows_each_corner_once_per_approach() {
+        let annotations: TrackAnnotations = vec![
+            TrackAnnotation::new("Hairpin", 500.0),
+            TrackAnnotation::new("Chicane", 900.0),
+        ]
+        .into();
+        let mut callout = CornerCallout::new();
+
+        callout.update(&annotations, 100.0, LAP_LENGTH, TICK);
+        assert_eq!(callout.shown(), None);
+
+        callout.update(&annotations, 300.0, LAP_LENGTH, TICK);
+        assert_eq!(callout.shown(), Some(0));
+
+        // Gone after a while even if the car sits before the corner
+        for _ in 0..30 {
+            callout.update(&annotations, 300.0, LAP_LENGTH, TICK);
+        }
+        assert_eq!(callout.shown(), None);
+
+        // The next corner, then the first again after the line
+        callout.update(&annotations, 700.0, LAP_LENGTH, TICK);
+        assert_eq!(callout.shown(), Some(1));
+        callout.update(&annotations, 950.0, LAP_LENGTH, 3.0);
+        assert_eq!(callout.shown(), None);
+        callout.update(&annotations, 300.0, LAP_LENGTH, TICK);
+        assert_eq!(callout.shown(), Some(0));
+    }
+}
@@ -243,6 +243,25 @@ impl RaceSession {
         self.flag
     }
 
+    /// Fraction of the race distance the leader has covered (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        match self.state {
+            RaceState::Finished | RaceState::Aborted => 1.0,
+            RaceState::GridFormation | RaceState::Countdown => 0.0,
+            RaceState::Racing => {
+                let leader_laps = self.driver_laps.iter().max().copied().unwrap_or(0);
+                (leader_laps as f32 / self.total_laps.max(1) as f32).min(1.0)
+            }
+        }
+    }
+
+    /// End the race now, classifying drivers by the laps they have completed
+    pub fn force_finish(&mut self, driver_names: &[String]) {
+        if self.state != RaceState::Finished {
+            self.finish_race(driver_names);
+        }
+    }
+
     /// Finish the race and calculate results
     fn finish_race(&mut self, driver_names: &[String]) {
         self.state = RaceState::Finished;
@@ -385,6 +404,26 @@ mod tests {
         assert_eq!(flag, RaceFlag::Green);
     }
 
+    #[test]
+    fn test_progress_and_force_finish() {
+        let mut session = RaceSession::new(2, 4);
+        assert_eq!(session.progress(), 0.0);
+
+        session.state = RaceState::Racing;
+        session.driver_laps = vec![1, 2];
+        session.driver_lap_times = vec![vec![95.0], vec![90.0, 91.0]];
+        assert_eq!(session.progress(), 0.5);
+
+        let names = vec!["Driver 1".to_string(), "Driver 2".to_string()];
+        session.force_finish(&names);
+
+        assert_eq!(session.state, RaceState::Finished);
+        assert_eq!(session.progress(), 1.0);
+        assert_eq!(session.results[0].name, "Driver 2");
+        assert_eq!(session.results[0].laps_completed, 2);
+        assert!(!session.results[0].finished);
+    }
+
     #[test]
     fn test_race_finish() {
         let mut session = RaceSession::new(2, 3);
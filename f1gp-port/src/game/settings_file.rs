@@ -0,0 +1,140 @@
+//! Settings files
+//!
+//! Small JSON files holding player preferences between sessions. Each lives
+//! at a default relative path that an environment variable can override; a
+//! missing or invalid file means the defaults.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `env_var` if set and non-empty, else `default_path`
+pub fn settings_path(env_var: &str, default_path: &str) -> PathBuf {
+    match env::var(env_var) {
+        Ok(path) if !path.is_empty() => PathBuf::from(path),
+        _ => PathBuf::from(default_path),
+    }
+}
+
+/// Load settings from a JSON file; `what` names them in errors
+pub fn load<T: DeserializeOwned>(path: &Path, what: &str) -> Result<T> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {} {}", what, path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {} {}", what, path.display()))
+}
+
+/// Write settings to a JSON file, creating parent directories as needed
+pub fn save<T: Serialize>(settings: &T, path: &Path, what: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create settings directory {}", parent.display())
+            })?;
+        }
+    }
+    let json = serde_json::to_string_pretty(settings)?;
+    fs::write(path, json).with_context(|| format!("Failed to write {} {}", what, path.display()))
+}
+
+/// Load settings from the configured location, falling back to defaults
+pub fn load_or_default<T: DeserializeOwned + Default>(
+    env_var: &str,
+    default_path: &str,
+    what: &str,
+) -> T {
+    load_from_or_default(&settings_path(env_var, default_path), what)
+}
+
+/// Settings kept in one of these files
+///
+/// Implementors only name their file; loading and saving come with the trait.
+pub trait SettingsFile: Serialize + DeserializeOwned + Default {
+    /// Environment variable overriding the file location
+    const PATH_ENV: &'static str;
+
+    /// Default relative location for the file
+    const DEFAULT_PATH: &'static str;
+
+    /// What the settings are called in errors and warnings
+    const WHAT: &'static str;
+
+    /// Load settings from a JSON file
+    fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        load(path.as_ref(), Self::WHAT)
+    }
+
+    /// Write settings to a JSON file, creating parent directories as needed
+    fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save(self, path.as_ref(), Self::WHAT)
+    }
+
+    /// Load settings from the configured location, falling back to defaults
+    fn load_or_default() -> Self {
+        load_or_default(Self::PATH_ENV, Self::DEFAULT_PATH, Self::WHAT)
+    }
+
+    /// Save settings to the configured location
+    fn save(&self) -> Result<()> {
+        self.to_json_file(settings_path(Self::PATH_ENV, Self::DEFAULT_PATH))
+    }
+}
+
+fn load_from_or_default<T: DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    if !path.exists() {
+        return T::default();
+    }
+    match load(path, what) {
+        Ok(settings) => settings,
+        Err(err) => {
+            log::warn!("Ignoring invalid {}: {}", what, err);
+            T::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        volume: u8,
+    }
+
+    #[test]
+    fn test_save_creates_directories_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("example.json");
+
+        save(&Example { volume: 7 }, &path, "example settings").unwrap();
+        let loaded: Example = load(&path, "example settings").unwrap();
+        assert_eq!(loaded, Example { volume: 7 });
+    }
+
+    #[test]
+    fn test_missing_or_invalid_file_gives_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("example.json");
+
+        let missing: Example = load_from_or_default(&path, "example settings");
+        assert_eq!(missing, Example::default());
+
+        fs::write(&path, "{ not json").unwrap();
+        let invalid: Example = load_from_or_default(&path, "example settings");
+        assert_eq!(invalid, Example::default());
+
+        let err = load::<Example>(&path, "example settings").unwrap_err();
+        assert!(err.to_string().contains("Failed to parse example settings"));
+    }
+
+    #[test]
+    fn test_unset_variable_uses_default_path() {
+        let path = settings_path("F1GP_SETTINGS_FILE_TEST_UNSET", "settings/example.json");
+        assert_eq!(path, PathBuf::from("settings/example.json"));
+    }
+}
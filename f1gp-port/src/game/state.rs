@@ -3,12 +3,23 @@
 //! Manages the overall game state, integrating physics, rendering, and input.
 
 use crate::ai::{AIDriver, DriverPersonality, NearbyCarInfo, RacingLineFollower};
-use crate::data::car::CarDatabase;
-use crate::data::track::Track;
+use crate::data::car::{CarDatabase, Livery};
+use crate::data::setup::SetupFile;
+use crate::data::track::{CarSetup, SurfaceType, Track};
+use crate::game::camera_settings::CameraSettings;
+use crate::game::force_feedback::ForceFeedbackSettings;
 use crate::game::input::{CarInput, InputManager};
-use crate::game::session::RaceSession;
+use crate::game::livery::LiverySettings;
+use crate::game::practice::{CornerCallout, MicroSectorTimer};
+use crate::game::session::{RaceSession, RaceState};
+use crate::game::settings_file::SettingsFile;
+use crate::game::time_scale::{SimClock, TimeScale, FAST_FORWARD_TICK_LIMIT};
 use crate::game::weather::{WeatherCondition, WeatherSystem};
-use crate::physics::{BodyId, CarPhysics, TrackCollision};
+use crate::net::broadcast::{sector_of, TelemetryPacket};
+use crate::physics::{
+    BodyId, CarPhysics, CollisionResult, FfbStatus, ForceFeedbackModel, TrackCollision,
+    PHYSICS_TIMESTEP,
+};
 use crate::platform::{Color, Renderer};
 use crate::render::{
     Camera, CarRenderer, CarState, Hud, ParticleSystem, SpriteAtlas, SpriteSheet, Telemetry,
@@ -29,6 +40,12 @@ const TELEMETRY_DIR: &str = "telemetry";
 const TELEMETRY_SAMPLE_INTERVAL_MS: u64 = 100; // 10 Hz sampling
 const TELEMETRY_MAX_SAMPLES: usize = 100_000;
 
+/// Height of a car's body origin above the road surface (matches spawn height)
+const CAR_RIDE_HEIGHT: f32 = 1.0;
+
+/// Index of the livery selector in the pause menu
+const PAUSE_LIVERY_ITEM: usize = 1;
+
 fn telemetry_is_enabled() -> bool {
     match env::var(TELEMETRY_ENV_VAR) {
         Ok(value) => {
@@ -80,6 +97,15 @@ impl std::fmt::Display for GameMode {
     }
 }
 
+/// A session being simulated to its end without rendering
+struct FastForward {
+    /// Drives the player's car while the session is skipped
+    stand_in: AIDriver,
+
+    /// Ticks simulated since the skip started
+    ticks: u64,
+}
+
 /// Main game state
 pub struct GameState {
     /// Player car physics
@@ -154,6 +180,15 @@ pub struct GameState {
     /// Number of AI opponents for race setup
     num_opponents: usize,
 
+    /// Player livery selection (persisted)
+    player_livery: LiverySettings,
+
+    /// Player car setup; the race setup drives the physics
+    car_setup: SetupFile,
+
+    /// Last-used 3D camera mode and mirror options (persisted)
+    camera_settings: CameraSettings,
+
     /// Viewport dimensions
     viewport_width: u32,
     viewport_height: u32,
@@ -175,6 +210,30 @@ pub struct GameState {
 
     /// Next timestamp (ms) when a telemetry sample should be recorded
     telemetry_next_sample_ms: u64,
+
+    /// Steering force feedback model for the player car
+    force_feedback: ForceFeedbackModel,
+
+    /// Steering torque computed on the last physics tick
+    force_feedback_torque: f32,
+
+    /// Wheel output state shown in the HUD (None without a wheel)
+    force_feedback_status: Option<FfbStatus>,
+
+    /// Converts frame time into fixed physics ticks at the chosen speed
+    sim_clock: SimClock,
+
+    /// Physics ticks simulated since the game state was created
+    sim_ticks: u64,
+
+    /// Skip-to-end in progress, if any
+    fast_forward: Option<FastForward>,
+
+    /// Upcoming corner name shown in practice
+    corner_callout: CornerCallout,
+
+    /// Practice micro-sector times between the track's annotations
+    micro_sectors: MicroSectorTimer,
 }
 
 impl GameState {
@@ -252,6 +311,9 @@ impl GameState {
             screen: GameScreen::MainMenu,
             menu: Some(menu),
             num_opponents: 5,
+            player_livery: LiverySettings::load_or_default(),
+            car_setup: SetupFile::new(CarSetup::default()),
+            camera_settings: CameraSettings::load_or_default(),
             viewport_width,
             viewport_height,
             weather: WeatherSystem::default(),
@@ -260,6 +322,14 @@ impl GameState {
             telemetry_recording: None,
             telemetry_output_path: None,
             telemetry_next_sample_ms: 0,
+            force_feedback: ForceFeedbackModel::default(),
+            force_feedback_torque: 0.0,
+            force_feedback_status: None,
+            sim_clock: SimClock::new(),
+            sim_ticks: 0,
+            fast_forward: None,
+            corner_callout: CornerCallout::new(),
+            micro_sectors: MicroSectorTimer::default(),
         }
     }
 
@@ -286,6 +356,10 @@ impl GameState {
         // Create track collision detector
         let track_collision = TrackCollision::new(track.clone());
 
+        // Practice aids time the lap between the track's named corners
+        self.corner_callout.clear();
+        self.micro_sectors = MicroSectorTimer::new(&track.annotations, track.length);
+
         self.track_renderer = Some(track_renderer);
         self.track_collision = Some(track_collision);
         self.track = Some(track);
@@ -364,6 +438,13 @@ impl GameState {
         }
     }
 
+    /// Leave the menus and go straight to the track, as the demos and
+    /// headless runs do
+    pub fn enter_race(&mut self) {
+        self.screen = GameScreen::InGame;
+        self.menu = None;
+    }
+
     /// Get race session (if active)
     pub fn race_session(&self) -> Option<&RaceSession> {
         self.race_session.as_ref()
@@ -418,6 +499,23 @@ impl GameState {
                 self.menu = Some(Menu::main_menu(self.viewport_width, self.viewport_height));
             }
 
+            MenuAction::ExportSetup => {
+                let path = SetupFile::export_path();
+                let status = match self.car_setup.save(&path) {
+                    Ok(()) => {
+                        log::info!("Exported car setup to {}", path.display());
+                        "SETUP EXPORTED"
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to export car setup: {:#}", err);
+                        "EXPORT FAILED"
+                    }
+                };
+                if let Some(ref mut menu) = self.menu {
+                    menu.update_item_text(menu.get_selected_index(), status.to_string());
+                }
+            }
+
             MenuAction::Exit => {
                 log::info!("Exit requested");
                 // TODO: Signal to main loop to exit
@@ -450,8 +548,11 @@ impl GameState {
                             // Pause game
                             self.screen = GameScreen::Paused;
                             self.paused = true;
-                            self.menu =
-                                Some(Menu::pause_menu(self.viewport_width, self.viewport_height));
+                            self.menu = Some(Menu::pause_menu(
+                                self.viewport_width,
+                                self.viewport_height,
+                                self.player_livery.preset_name(),
+                            ));
                         }
                         GameScreen::Paused => {
                             self.handle_menu_action(MenuAction::Resume);
@@ -492,6 +593,8 @@ impl GameState {
                                 menu.update_item_text(1, format!("WEATHER: {}", weather_text));
                             }
                         }
+                    } else if self.screen == GameScreen::Paused {
+                        self.cycle_player_livery(false);
                     }
                 }
                 sdl2::keyboard::Keycode::Right => {
@@ -525,6 +628,8 @@ impl GameState {
                                 menu.update_item_text(1, format!("WEATHER: {}", weather_text));
                             }
                         }
+                    } else if self.screen == GameScreen::Paused {
+                        self.cycle_player_livery(true);
                     }
                 }
                 _ => {}
@@ -532,10 +637,38 @@ impl GameState {
         }
     }
 
-    /// Update game state
-    pub fn update(&mut self, delta_time: f32) {
+    /// Cycle the player's livery from the pause menu and persist the choice
+    fn cycle_player_livery(&mut self, forward: bool) {
+        let Some(ref mut menu) = self.menu else {
+            return;
+        };
+        if menu.get_selected_index() != PAUSE_LIVERY_ITEM {
+            return;
+        }
+
+        if forward {
+            self.player_livery.next_preset();
+        } else {
+            self.player_livery.previous_preset();
+        }
+        menu.update_item_text(
+            PAUSE_LIVERY_ITEM,
+            format!("LIVERY: {}", self.player_livery.preset_name()),
+        );
+
+        if let Err(err) = self.player_livery.save() {
+            log::warn!("Failed to save livery settings: {}", err);
+        }
+    }
+
+    /// Update game state for a rendered frame that took `frame_time` seconds
+    ///
+    /// Runs as many fixed [`PHYSICS_TIMESTEP`] ticks as the frame time and the
+    /// active [`TimeScale`] call for; speeding up never changes the timestep.
+    pub fn update(&mut self, frame_time: f32) {
         // Only update game logic when in-game and not paused
-        if self.screen != GameScreen::InGame || self.paused {
+        if self.screen != GameScreen::InGame || self.paused || self.fast_forward.is_some() {
+            self.sim_clock.reset();
             return;
         }
 
@@ -545,55 +678,197 @@ impl GameState {
         // Get player input
         let input = self.input_manager.get_car_input();
 
-        // Apply input to player car
-        self.apply_input(&input);
+        let ticks = self.sim_clock.ticks_for_frame(frame_time);
+        for tick in 0..ticks {
+            // A gear change is one key press, not one per tick
+            let mut tick_input = input.clone();
+            if tick > 0 {
+                tick_input.shift_up = false;
+                tick_input.shift_down = false;
+            }
+
+            // Apply input to player car
+            self.apply_input(&tick_input);
+            self.step(PHYSICS_TIMESTEP);
 
+            if self.screen != GameScreen::InGame {
+                break;
+            }
+        }
+
+        // Update camera
+        self.update_camera(frame_time);
+
+        // Update particle system based on weather
+        self.particle_system
+            .update(frame_time, self.weather.condition);
+    }
+
+    /// Advance the simulation by one fixed tick
+    fn step(&mut self, delta_time: f32) {
         // Update AI drivers
         self.update_ai(delta_time);
 
         // Update physics
         self.update_physics(delta_time);
 
-        // Update camera
-        self.update_camera(delta_time);
-
         // Update race session
+        let driver_names = self.driver_names();
         if let Some(ref mut session) = self.race_session {
-            // Build driver names (player + AI)
-            let mut driver_names = vec!["Player".to_string()];
-            for ai_driver in &self.ai_drivers {
-                driver_names.push(ai_driver.name.clone());
-            }
             session.update(delta_time, &driver_names);
 
             // Check if race is finished
-            if session.state == crate::game::session::RaceState::Finished {
-                self.screen = GameScreen::Results;
-                self.menu = Some(Menu::results_menu(
-                    self.viewport_width,
-                    self.viewport_height,
-                ));
-                log::info!("Race finished!");
-                if let Err(err) = self.flush_telemetry_to_disk() {
-                    log::warn!("Failed to write telemetry after race: {}", err);
-                }
+            if session.state == RaceState::Finished {
+                self.finish_session();
             }
         }
 
         // Update weather system
         self.weather.update(delta_time);
 
-        // Update particle system based on weather
-        self.particle_system
-            .update(delta_time, self.weather.condition);
-
         // Update timers
         self.total_time += delta_time;
         self.lap_time += delta_time;
+        self.sim_ticks += 1;
 
         self.capture_telemetry_frame();
     }
 
+    /// Driver names for the race session, player first
+    fn driver_names(&self) -> Vec<String> {
+        let mut driver_names = vec!["Player".to_string()];
+        for ai_driver in &self.ai_drivers {
+            driver_names.push(ai_driver.name.clone());
+        }
+        driver_names
+    }
+
+    /// Show the results once the race session has finished
+    fn finish_session(&mut self) {
+        self.fast_forward = None;
+        self.screen = GameScreen::Results;
+        self.menu = Some(Menu::results_menu(
+            self.viewport_width,
+            self.viewport_height,
+        ));
+        log::info!("Race finished!");
+        if let Err(err) = self.flush_telemetry_to_disk() {
+            log::warn!("Failed to write telemetry after race: {}", err);
+        }
+    }
+
+    /// Change the simulation speed
+    pub fn set_time_scale(&mut self, scale: TimeScale) {
+        if scale != self.sim_clock.scale() {
+            log::info!("Simulation speed set to {}", scale.label());
+        }
+        self.sim_clock.set_scale(scale);
+    }
+
+    /// Active simulation speed
+    pub fn time_scale(&self) -> TimeScale {
+        self.sim_clock.scale()
+    }
+
+    /// Physics ticks simulated so far, independent of the time scale
+    pub fn sim_ticks(&self) -> u64 {
+        self.sim_ticks
+    }
+
+    /// Start simulating the rest of the session without rendering it
+    ///
+    /// An AI stand-in drives the player's car, so the final classification is
+    /// the one the session would have produced had it been watched. Drive the
+    /// skip with [`GameState::run_fast_forward`] once per frame.
+    pub fn skip_to_session_end(&mut self) {
+        if self.fast_forward.is_some() || self.screen != GameScreen::InGame {
+            return;
+        }
+        let Some(track) = &self.track else {
+            log::warn!("Cannot skip to session end without a loaded track");
+            return;
+        };
+        if self.race_session.is_none() {
+            log::info!("No timed session to skip to the end of");
+            return;
+        }
+
+        let mut stand_in = AIDriver::new("Player".to_string(), DriverPersonality::average());
+        stand_in.set_racing_line(RacingLineFollower::new(track, 20.0));
+
+        self.input_manager.clear();
+        self.fast_forward = Some(FastForward { stand_in, ticks: 0 });
+        log::info!("Skipping to the end of the session");
+    }
+
+    /// Whether a skip to the end of the session is running
+    pub fn is_fast_forwarding(&self) -> bool {
+        self.fast_forward.is_some()
+    }
+
+    /// Simulate up to `max_ticks` ticks of a skip, AI only
+    ///
+    /// Returns how far through the session the skip is (0.0 to 1.0). The
+    /// results screen is shown when the session ends; a session still running
+    /// after [`FAST_FORWARD_TICK_LIMIT`] ticks is classified as it stands.
+    pub fn run_fast_forward(&mut self, max_ticks: u32) -> f32 {
+        let Some(mut fast_forward) = self.fast_forward.take() else {
+            return 1.0;
+        };
+
+        for _ in 0..max_ticks {
+            let nearby_cars = self.cars_near_player();
+            let input =
+                fast_forward
+                    .stand_in
+                    .update(&self.player_car, &nearby_cars, PHYSICS_TIMESTEP);
+            self.apply_input(&input);
+            self.step(PHYSICS_TIMESTEP);
+            fast_forward.ticks += 1;
+
+            if self.screen != GameScreen::InGame {
+                return 1.0;
+            }
+
+            if fast_forward.ticks >= FAST_FORWARD_TICK_LIMIT {
+                log::warn!("Session still running after skip limit; classifying as it stands");
+                let driver_names = self.driver_names();
+                if let Some(ref mut session) = self.race_session {
+                    session.force_finish(&driver_names);
+                }
+                self.finish_session();
+                return 1.0;
+            }
+        }
+
+        let progress = self
+            .race_session
+            .as_ref()
+            .map(|session| session.progress())
+            .unwrap_or(1.0);
+        self.fast_forward = Some(fast_forward);
+        progress
+    }
+
+    /// AI cars close enough to the player's car to matter to its driver
+    fn cars_near_player(&self) -> Vec<NearbyCarInfo> {
+        let position = self.player_car.body.position;
+        let forward = self.player_car.body.orientation * glam::Vec3::X;
+
+        self.ai_cars
+            .iter()
+            .filter_map(|other_car| {
+                let distance = (position - other_car.body.position).length();
+                (distance < 100.0).then(|| NearbyCarInfo {
+                    position: other_car.body.position,
+                    velocity: other_car.body.velocity,
+                    distance,
+                    is_ahead: (other_car.body.position - position).dot(forward) > 0.0,
+                })
+            })
+            .collect()
+    }
+
     /// Apply input to player car
     fn apply_input(&mut self, input: &CarInput) {
         self.player_car.set_throttle(input.throttle);
@@ -683,6 +958,7 @@ impl GameState {
 
                 self.ai_cars[i].apply_surface_grip(total_grip);
                 self.ai_cars[i].on_track = collision_result.on_track;
+                Self::follow_road_surface(&mut self.ai_cars[i], &collision_result);
 
                 // Check for lap crossing (AI driver index = i + 1, since player is 0)
                 if collision_detector
@@ -701,6 +977,10 @@ impl GameState {
 
     /// Update physics simulation
     fn update_physics(&mut self, delta_time: f32) {
+        let mut surface = SurfaceType::Track;
+        let mut lap_distance = None;
+        let mut completed_lap = None;
+
         // Check collision and apply surface physics
         if let Some(collision_detector) = &self.track_collision {
             let collision_result =
@@ -713,6 +993,9 @@ impl GameState {
             // Apply combined grip to car
             self.player_car.apply_surface_grip(total_grip);
             self.player_car.on_track = collision_result.on_track;
+            Self::follow_road_surface(&mut self.player_car, &collision_result);
+            surface = collision_result.surface;
+            lap_distance = Some(collision_result.lap_distance);
 
             // Check for lap crossing
             if collision_detector
@@ -720,6 +1003,7 @@ impl GameState {
             {
                 // Record lap time
                 if self.lap_time > 1.0 {
+                    completed_lap = Some(self.lap_time);
                     self.set_best_lap(self.lap_time);
                     log::info!("Lap {} completed: {:.2}s", self.current_lap, self.lap_time);
                     self.lap_time = 0.0;
@@ -735,8 +1019,43 @@ impl GameState {
             self.prev_section = collision_result.nearest_section;
         }
 
+        if self.mode == GameMode::Practice {
+            if let Some(distance) = lap_distance {
+                self.update_practice_aids(distance, completed_lap, delta_time);
+            }
+        }
+
         // Update player car physics
         self.player_car.update(delta_time);
+
+        // Steering torque for the wheel
+        self.force_feedback_torque =
+            self.force_feedback
+                .update(&self.player_car, surface, delta_time);
+    }
+
+    /// Follow the player around the lap for the corner callout and the
+    /// micro-sector times
+    fn update_practice_aids(&mut self, distance: f32, completed_lap: Option<f32>, delta_time: f32) {
+        let Some(track) = &self.track else {
+            return;
+        };
+
+        if let Some(lap_time) = completed_lap {
+            self.micro_sectors.complete_lap(lap_time);
+        }
+        self.micro_sectors.record(distance, self.lap_time);
+        self.corner_callout
+            .update(&track.annotations, distance, track.length, delta_time);
+    }
+
+    /// Keep a car on the road surface and feed it the local gradient and camber
+    fn follow_road_surface(car: &mut CarPhysics, collision_result: &CollisionResult) {
+        let forward = car.body.orientation * glam::Vec3::X;
+        let grade = collision_result.grade_along(glam::Vec2::new(forward.x, forward.z));
+
+        car.set_track_slope(grade, collision_result.camber);
+        car.body.position.y = collision_result.elevation + CAR_RIDE_HEIGHT;
     }
 
     /// Update camera to follow player car
@@ -762,6 +1081,16 @@ impl GameState {
                 }
             }
 
+            GameScreen::InGame if self.fast_forward.is_some() => {
+                // Skipping runs headless; only show how far along it is
+                let progress = self
+                    .race_session
+                    .as_ref()
+                    .map(|session| session.progress())
+                    .unwrap_or(0.0);
+                self.hud.render_fast_forward(renderer, progress)?;
+            }
+
             GameScreen::InGame | GameScreen::Paused => {
                 // Render game world
                 // Render track if loaded
@@ -823,10 +1152,29 @@ impl GameState {
                     delta_time: None, // TODO: Calculate delta vs best lap
                     on_track: self.player_car.on_track,
                     weather_condition: self.weather.condition,
+                    force_feedback: self.force_feedback_status,
+                    time_scale: self.sim_clock.scale(),
                 };
 
                 self.hud.render(renderer, &telemetry)?;
 
+                // Practice aids: the upcoming corner and micro-sector times
+                if self.mode == GameMode::Practice {
+                    if let Some(track) = &self.track {
+                        if let Some(corner) = self
+                            .corner_callout
+                            .shown()
+                            .and_then(|index| track.annotations.get(index))
+                        {
+                            self.hud.render_corner_callout(renderer, corner)?;
+                        }
+                    }
+                    if !self.micro_sectors.is_empty() {
+                        self.hud
+                            .render_micro_sectors(renderer, &self.micro_sectors.rows())?;
+                    }
+                }
+
                 // Render pause menu overlay
                 if self.screen == GameScreen::Paused {
                     if let Some(ref menu) = self.menu {
@@ -922,9 +1270,15 @@ impl GameState {
         }
 
         // In-game controls
+        if let Some(scale) = TimeScale::from_keycode(keycode) {
+            self.set_time_scale(scale);
+            return;
+        }
         match keycode {
             Keycode::P | Keycode::Escape => self.toggle_pause(),
             Keycode::R => self.reset(),
+            Keycode::C => self.cycle_3d_camera(),
+            Keycode::F => self.skip_to_session_end(),
             _ => self.input_manager.key_down(keycode),
         }
     }
@@ -947,9 +1301,12 @@ impl GameState {
         self.player_car.body.angular_velocity = Vec3::ZERO;
         self.player_car.engine_rpm = 1000.0;
         self.player_car.gear = 1;
+        self.force_feedback.reset();
 
         // Reset timers
         self.lap_time = 0.0;
+        self.micro_sectors.restart_lap();
+        self.corner_callout.clear();
 
         // Clear input
         self.input_manager.clear();
@@ -1012,11 +1369,33 @@ impl GameState {
         }
     }
 
-    /// Set 3D renderer
-    pub fn set_renderer_3d(&mut self, renderer_3d: Renderer3D) {
+    /// Set 3D renderer, restoring the last-used camera mode
+    ///
+    /// Mirror options need the device; apply `camera_settings().mirrors` with
+    /// `Renderer3D::set_mirror_settings` when creating the renderer.
+    pub fn set_renderer_3d(&mut self, mut renderer_3d: Renderer3D) {
+        renderer_3d.camera.mode = self.camera_settings.mode;
         self.renderer_3d = Some(renderer_3d);
     }
 
+    /// Persisted camera settings
+    pub fn camera_settings(&self) -> &CameraSettings {
+        &self.camera_settings
+    }
+
+    /// Switch the 3D camera to the next mode and persist the choice
+    pub fn cycle_3d_camera(&mut self) {
+        let Some(renderer_3d) = &mut self.renderer_3d else {
+            return;
+        };
+        renderer_3d.camera.next_mode();
+        self.camera_settings.mode = renderer_3d.camera.mode;
+
+        if let Err(err) = self.camera_settings.save() {
+            log::warn!("Failed to save camera settings: {}", err);
+        }
+    }
+
     /// Get mutable reference to 3D renderer
     pub fn renderer_3d_mut(&mut self) -> Option<&mut Renderer3D> {
         self.renderer_3d.as_mut()
@@ -1037,6 +1416,30 @@ impl GameState {
         }
     }
 
+    /// Player livery selection
+    pub fn player_livery(&self) -> &LiverySettings {
+        &self.player_livery
+    }
+
+    /// Livery and car number for every car, player first, then AI in `ai_cars` order
+    pub fn car_liveries(&self) -> Vec<(Livery, u8)> {
+        let mut liveries = Vec::with_capacity(1 + self.ai_cars.len());
+        liveries.push((self.player_livery.livery(), self.player_livery.number));
+
+        for (i, car) in self.ai_cars.iter().enumerate() {
+            let driver_name = self.ai_drivers.get(i).map(|d| d.name.as_str());
+            let roster = driver_name.and_then(|name| self.car_database.get_driver(name));
+            let livery = driver_name
+                .and_then(|name| self.car_database.livery_for_driver(name))
+                .or_else(|| Livery::from_colors(&car.spec.livery_colors))
+                .unwrap_or_default();
+            let number = roster.map(|d| d.number).unwrap_or((i + 1) as u8);
+            liveries.push((livery, number));
+        }
+
+        liveries
+    }
+
     /// Get AI cars for 3D rendering
     pub fn ai_cars(&self) -> &[CarPhysics] {
         &self.ai_cars
@@ -1057,6 +1460,72 @@ impl GameState {
         self.player_car.gear as i32
     }
 
+    /// Player car state for the live telemetry broadcast
+    pub fn telemetry_packet(&self) -> TelemetryPacket {
+        let car = &self.player_car;
+        let position = car.body.position;
+
+        TelemetryPacket {
+            tick: self.sim_ticks,
+            speed: car.speed,
+            rpm: car.engine_rpm,
+            gear: car.gear,
+            throttle: car.throttle,
+            brake: car.brake,
+            steering: car.steering,
+            position: [position.x, position.y, position.z],
+            lap: self.current_lap.min(u16::MAX as u32) as u16,
+            total_laps: self
+                .race_session
+                .as_ref()
+                .map_or(0, |session| session.total_laps.min(u16::MAX as u32) as u16),
+            sector: self.track_collision.as_ref().map_or(0, |collision| {
+                sector_of(self.prev_section, collision.section_count())
+            }),
+            lap_time: self.lap_time,
+            best_lap_time: self.best_lap,
+            tyre_temps: car.tire_temps,
+            on_track: car.on_track,
+        }
+    }
+
+    /// Turn telemetry capture on or off, overriding the environment
+    pub fn set_telemetry_enabled(&mut self, enabled: bool) {
+        self.telemetry_enabled = enabled;
+        if !enabled {
+            self.telemetry_recording = None;
+            self.telemetry_output_path = None;
+        }
+    }
+
+    /// Use an imported setup for the player car (its race setup sets the
+    /// gearing) and as the one the setup menu exports
+    pub fn set_car_setup(&mut self, setup: SetupFile) {
+        self.player_car.apply_setup(&setup.race);
+        self.car_setup = setup;
+    }
+
+    /// Player car setup
+    pub fn car_setup(&self) -> &SetupFile {
+        &self.car_setup
+    }
+
+    /// Apply force feedback strength and effect toggles
+    pub fn configure_force_feedback(&mut self, settings: &ForceFeedbackSettings) {
+        self.force_feedback
+            .configure(settings.effects, settings.strength);
+    }
+
+    /// Steering torque from the last physics tick (1.0 = full-scale, may exceed it)
+    pub fn get_force_feedback_torque(&self) -> f32 {
+        self.force_feedback_torque
+    }
+
+    /// Report the wheel output state for the HUD
+    pub fn set_force_feedback_status(&mut self, status: Option<FfbStatus>) {
+        self.force_feedback_status = status;
+    }
+
     /// Get tire squeal intensity (0.0 to 1.0) based on car sliding
     pub fn get_tire_squeal_intensity(&self) -> f32 {
         // Calculate sliding by comparing velocity direction with car heading
@@ -1278,6 +1747,24 @@ mod tests {
         assert_eq!(game.total_time, 0.0);
     }
 
+    #[test]
+    fn test_car_liveries_start_with_player() {
+        let mut game = GameState::new(1920, 1080);
+        let car_spec = game.car_database.cars().next().unwrap().clone();
+        game.ai_cars
+            .push(CarPhysics::new(BodyId(1), car_spec, Vec3::ZERO));
+        game.ai_drivers.push(AIDriver::new(
+            "Ayrton Senna".to_string(),
+            DriverPersonality::senna(),
+        ));
+
+        let liveries = game.car_liveries();
+        assert_eq!(liveries.len(), 2);
+        assert_eq!(liveries[0].0, game.player_livery().livery());
+        assert_eq!(liveries[0].1, game.player_livery().number);
+        assert_eq!(liveries[1].1, 1);
+    }
+
     #[test]
     fn test_pause_toggle() {
         let mut game = GameState::new(1920, 1080);
@@ -1308,6 +1795,147 @@ mod tests {
         assert_eq!(game.get_speed_kmh(), 180.0); // Should be 180 km/h
     }
 
+    /// Flat circular circuit, big enough for the AI to lap
+    fn circuit() -> Track {
+        use crate::data::track::{AIBehavior, RacingLine, TrackSection};
+
+        let radius = 200.0;
+        let sections = (0..16)
+            .map(|i| {
+                let angle = (i as f32 / 16.0) * std::f32::consts::TAU;
+                TrackSection {
+                    position: Vec3::new(radius * angle.cos(), 0.0, radius * angle.sin()),
+                    width: 15.0,
+                    length: radius * std::f32::consts::TAU / 16.0,
+                    surface: SurfaceType::Track,
+                    ..TrackSection::default()
+                }
+            })
+            .collect();
+
+        Track {
+            name: "Circuit".to_string(),
+            length: radius * std::f32::consts::TAU,
+            object_shapes: Vec::new(),
+            sections,
+            racing_line: RacingLine {
+                displacement: 0,
+                segments: Vec::new(),
+            },
+            ai_behavior: AIBehavior::default(),
+            pit_lane: Vec::new(),
+            cameras: Vec::new(),
+            annotations: Default::default(),
+            checksum: 0,
+        }
+    }
+
+    /// A race with three AI opponents, started, throttle held, nothing saved
+    fn race(scale: TimeScale) -> GameState {
+        fastrand::seed(1991);
+        let mut game = GameState::new(800, 600);
+        game.telemetry_enabled = true;
+        game.load_track(circuit());
+        game.telemetry_output_path = None;
+        game.spawn_ai_opponents(3);
+        game.start_race();
+        game.screen = GameScreen::InGame;
+        game.menu = None;
+        game.set_time_scale(scale);
+        game.input_manager.key_down(sdl2::keyboard::Keycode::Up);
+        game
+    }
+
+    #[test]
+    fn test_time_scale_is_deterministic() {
+        // 20 simulated seconds: countdown, then racing
+        let mut normal = race(TimeScale::Normal);
+        for _ in 0..1200 {
+            normal.update(PHYSICS_TIMESTEP);
+        }
+        let mut fast = race(TimeScale::Quadruple);
+        for _ in 0..300 {
+            fast.update(PHYSICS_TIMESTEP);
+        }
+
+        assert_eq!(normal.sim_ticks(), 1200);
+        assert_eq!(fast.sim_ticks(), 1200);
+        assert_eq!(fast.total_time, normal.total_time);
+
+        let cars = |game: &GameState| -> Vec<(Vec3, Vec3, f32, i8)> {
+            std::iter::once(&game.player_car)
+                .chain(game.ai_cars.iter())
+                .map(|car| {
+                    (
+                        car.body.position,
+                        car.body.velocity,
+                        car.engine_rpm,
+                        car.gear,
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(cars(&fast), cars(&normal));
+        assert_eq!(
+            fast.race_session().unwrap().state,
+            normal.race_session().unwrap().state
+        );
+
+        // Telemetry is sampled on simulation time, not wall time
+        let samples = |game: &GameState| -> Vec<([f32; 3], f32)> {
+            game.telemetry_recording
+                .as_ref()
+                .unwrap()
+                .samples
+                .iter()
+                .map(|sample| (sample.position, sample.speed))
+                .collect()
+        };
+        assert_eq!(samples(&fast).len(), samples(&normal).len());
+        assert_eq!(samples(&fast), samples(&normal));
+    }
+
+    #[test]
+    fn test_skip_to_session_end() {
+        let mut game = race(TimeScale::Normal);
+        game.skip_to_session_end();
+        assert!(game.is_fast_forwarding());
+
+        // Frame updates hold off while the skip runs
+        game.update(PHYSICS_TIMESTEP);
+        assert_eq!(game.sim_ticks(), 0);
+
+        let progress = game.run_fast_forward(120);
+        assert_eq!(game.sim_ticks(), 120);
+        assert_eq!(progress, 0.0);
+
+        // A zero-lap race ends as soon as the lights go out
+        game.race_session.as_mut().unwrap().total_laps = 0;
+        assert_eq!(game.run_fast_forward(1_000), 1.0);
+        assert!(!game.is_fast_forwarding());
+        assert_eq!(game.screen, GameScreen::Results);
+        assert_eq!(game.race_session().unwrap().results.len(), 4);
+    }
+
+    #[test]
+    fn test_telemetry_packet_follows_the_race() {
+        let mut game = race(TimeScale::Normal);
+        for _ in 0..600 {
+            game.update(PHYSICS_TIMESTEP);
+        }
+
+        let packet = game.telemetry_packet();
+        assert_eq!(packet.tick, game.sim_ticks());
+        assert_eq!(packet.speed, game.player_car.speed);
+        assert_eq!(packet.lap, 1);
+        assert_eq!(
+            packet.total_laps as u32,
+            game.race_session().unwrap().total_laps
+        );
+        assert!((1..=3).contains(&packet.sector));
+        assert_eq!(packet.tyre_temps, game.player_car.tire_temps);
+    }
+
     #[test]
     fn telemetry_rate_limiting() {
         let mut game = GameState::new(800, 600);
@@ -0,0 +1,81 @@
+//! Telemetry broadcast settings
+//!
+//! Whether live telemetry is streamed to an external dashboard, where to and
+//! how often, read from a small JSON settings file.
+
+use crate::game::settings_file::SettingsFile;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable overriding the broadcast settings file location
+pub const BROADCAST_SETTINGS_ENV: &str = "F1GP_BROADCAST_PATH";
+
+/// Default relative location for the broadcast settings file
+pub const DEFAULT_BROADCAST_SETTINGS: &str = "settings/telemetry_broadcast.json";
+
+/// Persisted telemetry broadcast configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryBroadcastSettings {
+    /// Stream telemetry at all
+    pub enabled: bool,
+
+    /// Host and UDP port the dashboard listens on
+    pub address: String,
+
+    /// Packets per second
+    pub rate_hz: u32,
+}
+
+impl Default for TelemetryBroadcastSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:20777".to_string(),
+            rate_hz: 60,
+        }
+    }
+}
+
+impl SettingsFile for TelemetryBroadcastSettings {
+    const PATH_ENV: &'static str = BROADCAST_SETTINGS_ENV;
+    const DEFAULT_PATH: &'static str = DEFAULT_BROADCAST_SETTINGS;
+    const WHAT: &'static str = "broadcast settings";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings() {
+        let settings = TelemetryBroadcastSettings::default();
+        assert!(!settings.enabled);
+        assert_eq!(settings.rate_hz, 60);
+    }
+
+    #[test]
+    fn test_partial_json_uses_defaults() {
+        let settings: TelemetryBroadcastSettings =
+            serde_json::from_str(r#"{ "enabled": true, "address": "192.168.1.50:20777" }"#)
+                .unwrap();
+        assert!(settings.enabled);
+        assert_eq!(settings.address, "192.168.1.50:20777");
+        assert_eq!(settings.rate_hz, 60);
+    }
+
+    #[test]
+    fn test_json_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("telemetry_broadcast.json");
+
+        let settings = TelemetryBroadcastSettings {
+            enabled: true,
+            rate_hz: 30,
+            ..TelemetryBroadcastSettings::default()
+        };
+        settings.to_json_file(&path).unwrap();
+
+        let loaded = TelemetryBroadcastSettings::from_json_file(&path).unwrap();
+        assert_eq!(loaded, settings);
+    }
+}
@@ -0,0 +1,156 @@
+//! Simulation time acceleration
+//!
+//! Speeds the game up by running more fixed physics ticks per rendered frame.
+//! The per-tick timestep is always [`PHYSICS_TIMESTEP`], so a session run at
+//! 4x steps through exactly the same states as one watched at 1x.
+
+use crate::physics::PHYSICS_TIMESTEP;
+
+/// Most ticks of real-time backlog worked off in one frame; anything beyond
+/// is dropped so a stalled frame can't snowball into a longer one
+const MAX_CATCH_UP_TICKS: u32 = 8;
+
+/// Ticks simulated per rendered frame while skipping to the end of a session
+pub const FAST_FORWARD_TICKS_PER_FRAME: u32 = 1_200;
+
+/// Simulated time after which a skipped session is called (3 hours)
+pub const FAST_FORWARD_TICK_LIMIT: u64 = 3 * 60 * 60 * 60;
+
+/// Simulation speed multiplier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeScale {
+    /// Real time
+    #[default]
+    Normal,
+    /// Twice real time
+    Double,
+    /// Four times real time
+    Quadruple,
+}
+
+impl TimeScale {
+    /// All scales, slowest first
+    pub const ALL: [TimeScale; 3] = [TimeScale::Normal, TimeScale::Double, TimeScale::Quadruple];
+
+    /// Physics ticks run for every tick of real time
+    pub fn multiplier(&self) -> u32 {
+        match self {
+            TimeScale::Normal => 1,
+            TimeScale::Double => 2,
+            TimeScale::Quadruple => 4,
+        }
+    }
+
+    /// Short label for the HUD
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeScale::Normal => "1x",
+            TimeScale::Double => "2x",
+            TimeScale::Quadruple => "4x",
+        }
+    }
+
+    /// Scale selected by a number key (1, 2 or 4)
+    pub fn from_keycode(keycode: sdl2::keyboard::Keycode) -> Option<Self> {
+        use sdl2::keyboard::Keycode;
+
+        match keycode {
+            Keycode::Num1 | Keycode::Kp1 => Some(TimeScale::Normal),
+            Keycode::Num2 | Keycode::Kp2 => Some(TimeScale::Double),
+            Keycode::Num4 | Keycode::Kp4 => Some(TimeScale::Quadruple),
+            _ => None,
+        }
+    }
+}
+
+/// Turns rendered frame times into a whole number of fixed physics ticks
+#[derive(Debug, Clone, Default)]
+pub struct SimClock {
+    /// Real time not yet simulated (seconds)
+    accumulator: f32,
+
+    /// Active speed multiplier
+    scale: TimeScale,
+}
+
+impl SimClock {
+    /// Create a real-time clock
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Active speed multiplier
+    pub fn scale(&self) -> TimeScale {
+        self.scale
+    }
+
+    /// Change the speed multiplier
+    pub fn set_scale(&mut self, scale: TimeScale) {
+        self.scale = scale;
+    }
+
+    /// Physics ticks to run for a frame that took `frame_time` seconds
+    pub fn ticks_for_frame(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.max(0.0);
+
+        let mut ticks = 0;
+        while self.accumulator >= PHYSICS_TIMESTEP && ticks < MAX_CATCH_UP_TICKS {
+            self.accumulator -= PHYSICS_TIMESTEP;
+            ticks += 1;
+        }
+        if ticks == MAX_CATCH_UP_TICKS {
+            self.accumulator = self.accumulator.min(PHYSICS_TIMESTEP);
+        }
+
+        ticks * self.scale.multiplier()
+    }
+
+    /// Forget any unsimulated time, e.g. after a pause
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_multiplies_ticks() {
+        for scale in TimeScale::ALL {
+            let mut clock = SimClock::new();
+            clock.set_scale(scale);
+            let ticks: u32 = (0..60)
+                .map(|_| clock.ticks_for_frame(PHYSICS_TIMESTEP))
+                .sum();
+            assert_eq!(ticks, 60 * scale.multiplier());
+        }
+    }
+
+    #[test]
+    fn test_partial_frames_accumulate() {
+        let mut clock = SimClock::new();
+        assert_eq!(clock.ticks_for_frame(PHYSICS_TIMESTEP * 0.6), 0);
+        assert_eq!(clock.ticks_for_frame(PHYSICS_TIMESTEP * 0.6), 1);
+    }
+
+    #[test]
+    fn test_long_frame_backlog_is_capped() {
+        let mut clock = SimClock::new();
+        clock.set_scale(TimeScale::Double);
+        assert_eq!(clock.ticks_for_frame(1.0), MAX_CATCH_UP_TICKS * 2);
+        assert!(clock.ticks_for_frame(0.0) <= 2);
+    }
+
+    #[test]
+    fn test_number_keys_select_scale() {
+        use sdl2::keyboard::Keycode;
+
+        assert_eq!(
+            TimeScale::from_keycode(Keycode::Num4),
+            Some(TimeScale::Quadruple)
+        );
+        assert_eq!(TimeScale::from_keycode(Keycode::Num3), None);
+        assert_eq!(TimeScale::Double.label(), "2x");
+    }
+}
@@ -11,6 +11,7 @@ pub mod ai;
 pub mod audio;
 pub mod data;
 pub mod game;
+pub mod net; // Experimental: lockstep multiplayer
 pub mod physics;
 pub mod platform;
 pub mod render;
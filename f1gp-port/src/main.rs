@@ -3,15 +3,21 @@
 //! A modern reimplementation of Formula 1 Grand Prix (1991) by Geoff Crammond
 
 use anyhow::Result;
-use f1gp_port::audio::SoundEngine;
-use f1gp_port::data::Track;
-use f1gp_port::game::GameState;
+use clap::Parser;
+use f1gp_port::audio::{SampleMap, SoundEngine};
+use f1gp_port::data::{SetupFile, SoundBank, Track, ORIGINAL_DATA_ENV};
+use f1gp_port::game::{
+    ForceFeedbackSettings, GameState, SettingsFile, TelemetryBroadcastSettings,
+    FAST_FORWARD_TICKS_PER_FRAME,
+};
+use f1gp_port::net::TelemetryBroadcaster;
 use f1gp_port::parse_track;
-use f1gp_port::platform::{Color, Renderer, SdlRenderer};
+use f1gp_port::platform::{Color, ForceFeedbackOutput, Renderer, SdlHapticDevice, SdlRenderer};
 use glam::Vec2;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 use std::fs;
+use std::path::PathBuf;
 use std::time::Instant;
 
 const WINDOW_WIDTH: u32 = 1280;
@@ -19,6 +25,19 @@ const WINDOW_HEIGHT: u32 = 720;
 const TARGET_FPS: u64 = 60;
 const FRAME_TIME: std::time::Duration = std::time::Duration::from_micros(1_000_000 / TARGET_FPS);
 
+/// Command-line options
+#[derive(Parser, Debug)]
+#[command(author, version, about = "F1GP Modern Port")]
+struct Cli {
+    /// Drive with the car setup from an original F1GP setup file
+    #[arg(long, value_name = "FILE")]
+    import_setup: Option<PathBuf>,
+
+    /// Drive alone in a practice session, with corner names and micro-sector times
+    #[arg(long)]
+    practice: bool,
+}
+
 /// Track metadata
 struct TrackInfo {
     filename: &'static str,
@@ -119,6 +138,17 @@ impl App {
 
 fn main() -> Result<()> {
     env_logger::init();
+    let cli = Cli::parse();
+
+    // Read the setup before opening a window, so a bad file fails fast
+    let imported_setup = cli
+        .import_setup
+        .as_deref()
+        .map(SetupFile::load)
+        .transpose()?;
+    if let Some(ref path) = cli.import_setup {
+        log::info!("Imported car setup from {}", path.display());
+    }
 
     // Print banner
     println!("╔══════════════════════════════════════════════════════════╗");
@@ -153,8 +183,9 @@ fn main() -> Result<()> {
         .audio()
         .map_err(|e| anyhow::anyhow!("Failed to initialize audio subsystem: {}", e))?;
     let sound_engine = match SoundEngine::new(&audio_subsystem) {
-        Ok(engine) => {
+        Ok(mut engine) => {
             log::info!("Audio system initialized");
+            load_original_sounds(&mut engine);
             Some(engine)
         }
         Err(e) => {
@@ -164,6 +195,60 @@ fn main() -> Result<()> {
         }
     };
 
+    // Initialize steering wheel force feedback (the haptic subsystem must
+    // outlive the output thread)
+    let ffb_settings = ForceFeedbackSettings::load_or_default();
+    let haptic_subsystem = if ffb_settings.enabled {
+        sdl_context
+            .haptic()
+            .map_err(|e| log::warn!("Failed to initialize haptic subsystem: {}", e))
+            .ok()
+    } else {
+        None
+    };
+    let force_feedback = if haptic_subsystem.is_some() {
+        match open_force_feedback(&ffb_settings) {
+            Ok(output) => {
+                log::info!("Force feedback initialized");
+                Some(output)
+            }
+            Err(e) => {
+                log::info!("Force feedback unavailable: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Stream live telemetry to an external dashboard, if configured
+    let broadcast_settings = TelemetryBroadcastSettings::load_or_default();
+    let mut telemetry_broadcast = if broadcast_settings.enabled {
+        match TelemetryBroadcaster::new(
+            broadcast_settings.address.as_str(),
+            broadcast_settings.rate_hz,
+        ) {
+            Ok(broadcaster) => {
+                log::info!(
+                    "Broadcasting telemetry to {} at {} Hz",
+                    broadcaster.target(),
+                    broadcast_settings.rate_hz
+                );
+                Some(broadcaster)
+            }
+            Err(e) => {
+                log::warn!(
+                    "Telemetry broadcast to {} unavailable: {}",
+                    broadcast_settings.address,
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Create application state
     let mut app = App::new();
     let mut game: Option<GameState> = None;
@@ -251,14 +336,24 @@ fn main() -> Result<()> {
                     let mut new_game = GameState::new(WINDOW_WIDTH, WINDOW_HEIGHT);
                     new_game.load_track(track);
                     new_game.set_camera_zoom(0.5);
+                    new_game.configure_force_feedback(&ffb_settings);
+                    if let Some(ref setup) = imported_setup {
+                        new_game.set_car_setup(setup.clone());
+                    }
 
-                    // Spawn AI opponents
-                    new_game.spawn_ai_opponents(3);
-                    new_game.start_race();
+                    if cli.practice {
+                        // Practice: no opponents, straight onto the track
+                        new_game.enter_race();
+                        log::info!("Practice session started");
+                    } else {
+                        // Spawn AI opponents
+                        new_game.spawn_ai_opponents(3);
+                        new_game.start_race();
+                        log::info!("Race started!");
+                    }
 
                     game = Some(new_game);
                     app.screen = Screen::Racing;
-                    log::info!("Race started!");
                 }
                 Err(e) => {
                     log::error!("Failed to load track: {}", e);
@@ -270,36 +365,71 @@ fn main() -> Result<()> {
         // Update game if racing
         if let Screen::Racing = app.screen {
             if let Some(ref mut g) = game {
-                g.update(delta_time);
-
-                // Update audio with engine RPM
-                if let Some(ref audio) = sound_engine {
-                    let rpm = g.get_player_rpm();
-                    audio.set_rpm(rpm);
-
-                    // Detect gear shifts
-                    let current_gear = g.get_player_gear();
-                    if current_gear != last_gear {
-                        audio.play_gear_shift();
-                        last_gear = current_gear;
+                if g.is_fast_forwarding() {
+                    // Skipping to the session end: simulate a chunk, no audio
+                    g.run_fast_forward(FAST_FORWARD_TICKS_PER_FRAME);
+                    if let Some(ref output) = force_feedback {
+                        output.submit(0.0);
+                    }
+                    if let Some(ref audio) = sound_engine {
+                        audio.set_rpm(0.0);
+                        audio.set_tire_squeal(0.0);
+                    }
+                } else {
+                    g.update(delta_time);
+
+                    // Stream telemetry to the dashboard when a packet is due
+                    if let Some(ref mut broadcaster) = telemetry_broadcast {
+                        let now = Instant::now();
+                        if broadcaster.is_due(now) {
+                            if let Err(e) = broadcaster.send(now, &g.telemetry_packet()) {
+                                log::debug!("Telemetry broadcast failed: {}", e);
+                            }
+                        }
                     }
 
-                    // Update tire squeal based on sliding
-                    let squeal_intensity = g.get_tire_squeal_intensity();
-                    audio.set_tire_squeal(squeal_intensity);
-
-                    // Update rain ambient sound based on weather
-                    use f1gp_port::game::weather::WeatherCondition;
-                    let rain_intensity = match g.get_weather_condition() {
-                        WeatherCondition::Dry => 0.0,
-                        WeatherCondition::LightRain => 0.5,
-                        WeatherCondition::HeavyRain => 1.0,
-                    };
-                    audio.set_rain_intensity(rain_intensity);
+                    // Feed the wheel and report its state back to the HUD
+                    if let Some(ref output) = force_feedback {
+                        output.submit(g.get_force_feedback_torque());
+                        g.set_force_feedback_status(Some(output.status()));
+                    }
+
+                    // Update audio with engine RPM
+                    if let Some(ref audio) = sound_engine {
+                        let rpm = g.get_player_rpm();
+                        audio.set_rpm(rpm);
+
+                        // Detect gear shifts
+                        let current_gear = g.get_player_gear();
+                        if current_gear != last_gear {
+                            audio.play_gear_shift();
+                            last_gear = current_gear;
+                        }
+
+                        // Update tire squeal based on sliding
+                        let squeal_intensity = g.get_tire_squeal_intensity();
+                        audio.set_tire_squeal(squeal_intensity);
+
+                        // Update rain ambient sound based on weather
+                        use f1gp_port::game::weather::WeatherCondition;
+                        let rain_intensity = match g.get_weather_condition() {
+                            WeatherCondition::Dry => 0.0,
+                            WeatherCondition::LightRain => 0.5,
+                            WeatherCondition::HeavyRain => 1.0,
+                        };
+                        audio.set_rain_intensity(rain_intensity);
+                    }
                 }
             }
         }
 
+        // Release the wheel outside of racing
+        if !matches!(app.screen, Screen::Racing) {
+            if let Some(ref output) = force_feedback {
+                output.submit(0.0);
+            }
+        }
+
         // Render current screen
         renderer.clear(Color::BLACK);
 
@@ -358,6 +488,12 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Open the first force feedback wheel and start its output thread
+fn open_force_feedback(settings: &ForceFeedbackSettings) -> Result<ForceFeedbackOutput> {
+    let device = SdlHapticDevice::open_first()?;
+    ForceFeedbackOutput::spawn(Box::new(device), settings.smoothing_secs())
+}
+
 /// Handle main menu input
 fn handle_main_menu(app: &mut App, keycode: Keycode, audio: Option<&SoundEngine>) {
     match keycode {
@@ -392,6 +528,28 @@ fn handle_main_menu(app: &mut App, keycode: Keycode, audio: Option<&SoundEngine>
     }
 }
 
+/// Load the original sound bank if a data path is configured
+///
+/// Without one, or if loading fails, the synthesized sounds are used.
+fn load_original_sounds(engine: &mut SoundEngine) {
+    let Some(root) = std::env::var_os(ORIGINAL_DATA_ENV) else {
+        return;
+    };
+
+    match SoundBank::from_original_data(&root) {
+        Ok(bank) => {
+            engine.load_sound_bank(bank, SampleMap::default());
+            if let Some((version, source)) = engine.sound_bank_info() {
+                log::info!("Using {} sound bank: {}", version, source.display());
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to load original sounds: {:#}", e);
+            log::warn!("Using synthesized sounds");
+        }
+    }
+}
+
 /// Handle track selection input
 fn handle_track_select(app: &mut App, keycode: Keycode, audio: Option<&SoundEngine>) {
     match keycode {
@@ -417,6 +575,9 @@ fn handle_track_select(app: &mut App, keycode: Keycode, audio: Option<&SoundEngi
                 audio.play_menu_beep();
             }
             log::info!("Loading track: {}", TRACKS[app.selected_track].name);
+            if let Some(audio) = audio {
+                audio.play_crowd();
+            }
             app.load_track_request = Some(app.selected_track);
         }
         _ => {}
@@ -0,0 +1,375 @@
+//! Live telemetry broadcast for external dashboards
+//!
+//! The player car's state goes out as one fixed-size UDP datagram per
+//! broadcast, at a configurable rate. Every field is little-endian:
+//!
+//! | Offset | Type       | Field                                          |
+//! |--------|------------|------------------------------------------------|
+//! | 0      | `[u8; 4]`  | Magic `F1GT`                                   |
+//! | 4      | `u16`      | Layout version ([`BROADCAST_VERSION`])         |
+//! | 6      | `u16`      | Packet length in bytes ([`PACKET_LEN`])        |
+//! | 8      | `u64`      | Simulation tick                                |
+//! | 16     | `f32`      | Speed (m/s)                                    |
+//! | 20     | `f32`      | Engine RPM                                     |
+//! | 24     | `f32`      | Throttle (0 to 1)                              |
+//! | 28     | `f32`      | Brake (0 to 1)                                 |
+//! | 32     | `f32`      | Steering (-1 to 1, negative = left)            |
+//! | 36     | `[f32; 3]` | World position X, Y, Z (m)                     |
+//! | 48     | `[f32; 4]` | Tyre temperatures FL, FR, RL, RR (°C)          |
+//! | 64     | `f32`      | Current lap time (s)                           |
+//! | 68     | `f32`      | Best lap time (s), 0 when there is none        |
+//! | 72     | `u16`      | Current lap, from 1                            |
+//! | 74     | `u16`      | Race distance in laps, 0 outside a race        |
+//! | 76     | `i8`       | Gear (-1 reverse, 0 neutral)                   |
+//! | 77     | `u8`       | Sector (1 to 3), 0 when unknown                |
+//! | 78     | `u8`       | Flags ([`FLAG_ON_TRACK`])                      |
+//! | 79     | `u8`       | Reserved, 0                                    |
+//!
+//! Listeners should drop datagrams whose magic or version they don't know.
+
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+/// First four bytes of every packet
+pub const BROADCAST_MAGIC: [u8; 4] = *b"F1GT";
+
+/// Bumped whenever the packet layout changes
+pub const BROADCAST_VERSION: u16 = 1;
+
+/// Size of every packet in bytes
+pub const PACKET_LEN: usize = 80;
+
+/// Sectors a lap is split into
+pub const SECTOR_COUNT: usize = 3;
+
+/// Flag bit: the car is on the racing surface
+pub const FLAG_ON_TRACK: u8 = 0b1;
+
+/// Player car state sent in one broadcast
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TelemetryPacket {
+    /// Simulation tick the state was taken after
+    pub tick: u64,
+
+    /// Speed (m/s)
+    pub speed: f32,
+
+    /// Engine RPM
+    pub rpm: f32,
+
+    /// Gear (-1 reverse, 0 neutral)
+    pub gear: i8,
+
+    /// Throttle (0 to 1)
+    pub throttle: f32,
+
+    /// Brake (0 to 1)
+    pub brake: f32,
+
+    /// Steering (-1 to 1, negative = left)
+    pub steering: f32,
+
+    /// World position (m)
+    pub position: [f32; 3],
+
+    /// Current lap, from 1
+    pub lap: u16,
+
+    /// Race distance in laps, 0 outside a race
+    pub total_laps: u16,
+
+    /// Sector (1 to 3), 0 when unknown
+    pub sector: u8,
+
+    /// Current lap time (s)
+    pub lap_time: f32,
+
+    /// Best lap time (s)
+    pub best_lap_time: Option<f32>,
+
+    /// Tyre temperatures FL, FR, RL, RR (°C)
+    pub tyre_temps: [f32; 4],
+
+    /// Whether the car is on the racing surface
+    pub on_track: bool,
+}
+
+impl TelemetryPacket {
+    /// Write the packet into `buf`
+    pub fn encode(&self, buf: &mut [u8; PACKET_LEN]) {
+        let mut w = Writer { buf, pos: 0 };
+        w.bytes(&BROADCAST_MAGIC);
+        w.bytes(&BROADCAST_VERSION.to_le_bytes());
+        w.bytes(&(PACKET_LEN as u16).to_le_bytes());
+        w.bytes(&self.tick.to_le_bytes());
+        for value in [
+            self.speed,
+            self.rpm,
+            self.throttle,
+            self.brake,
+            self.steering,
+        ] {
+            w.bytes(&value.to_le_bytes());
+        }
+        for value in self.position.into_iter().chain(self.tyre_temps) {
+            w.bytes(&value.to_le_bytes());
+        }
+        w.bytes(&self.lap_time.to_le_bytes());
+        w.bytes(&self.best_lap_time.unwrap_or(0.0).to_le_bytes());
+        w.bytes(&self.lap.to_le_bytes());
+        w.bytes(&self.total_laps.to_le_bytes());
+        w.bytes(&self.gear.to_le_bytes());
+        w.bytes(&[
+            self.sector,
+            if self.on_track { FLAG_ON_TRACK } else { 0 },
+            0,
+        ]);
+        debug_assert_eq!(w.pos, PACKET_LEN);
+    }
+
+    /// Read a received datagram, `None` if it is not a packet of this version
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < PACKET_LEN || bytes[..4] != BROADCAST_MAGIC {
+            return None;
+        }
+
+        let mut r = Reader { bytes, pos: 4 };
+        if r.u16() != BROADCAST_VERSION || r.u16() as usize != PACKET_LEN {
+            return None;
+        }
+
+        let tick = r.u64();
+        let [speed, rpm, throttle, brake, steering] = [r.f32(), r.f32(), r.f32(), r.f32(), r.f32()];
+        let position = [r.f32(), r.f32(), r.f32()];
+        let tyre_temps = [r.f32(), r.f32(), r.f32(), r.f32()];
+        let lap_time = r.f32();
+        let best_lap_time = Some(r.f32()).filter(|&t| t > 0.0);
+        let lap = r.u16();
+        let total_laps = r.u16();
+        let [gear, sector, flags] = [r.u8(), r.u8(), r.u8()];
+
+        Some(Self {
+            tick,
+            speed,
+            rpm,
+            gear: gear as i8,
+            throttle,
+            brake,
+            steering,
+            position,
+            lap,
+            total_laps,
+            sector,
+            lap_time,
+            best_lap_time,
+            tyre_temps,
+            on_track: flags & FLAG_ON_TRACK != 0,
+        })
+    }
+}
+
+/// Sector (1 to [`SECTOR_COUNT`]) of a track section, 0 for an unknown track
+pub fn sector_of(section: usize, section_count: usize) -> u8 {
+    if section_count == 0 {
+        return 0;
+    }
+    (section.min(section_count - 1) * SECTOR_COUNT / section_count + 1) as u8
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8; PACKET_LEN],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut out = [0; N];
+        out.copy_from_slice(&self.bytes[self.pos..self.pos + N]);
+        self.pos += N;
+        out
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take())
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take())
+    }
+
+    fn f32(&mut self) -> f32 {
+        f32::from_le_bytes(self.take())
+    }
+}
+
+/// Sends telemetry packets to one address at a fixed rate
+///
+/// Packets are encoded into a buffer owned by the broadcaster, so sending
+/// never allocates.
+#[derive(Debug)]
+pub struct TelemetryBroadcaster {
+    socket: UdpSocket,
+    target: SocketAddr,
+    interval: Duration,
+    next_due: Option<Instant>,
+    buffer: [u8; PACKET_LEN],
+}
+
+impl TelemetryBroadcaster {
+    /// Broadcast to `target` at `rate_hz` packets per second
+    pub fn new(target: impl ToSocketAddrs, rate_hz: u32) -> io::Result<Self> {
+        let target = target.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no address to broadcast to")
+        })?;
+        let bind: SocketAddr = if target.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(bind)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            socket,
+            target,
+            interval: Duration::from_secs(1) / rate_hz.max(1),
+            next_due: None,
+            buffer: [0; PACKET_LEN],
+        })
+    }
+
+    /// Address packets go to
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Whether a packet should go out at `now`
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_due.is_none_or(|due| now >= due)
+    }
+
+    /// Send `packet` if one is due at `now`, returning whether it was sent
+    ///
+    /// A dashboard that isn't listening is not an error.
+    pub fn send(&mut self, now: Instant, packet: &TelemetryPacket) -> io::Result<bool> {
+        if !self.is_due(now) {
+            return Ok(false);
+        }
+        // Catch up after a stall instead of bursting
+        self.next_due = Some(match self.next_due {
+            Some(due) if now < due + self.interval => due + self.interval,
+            _ => now + self.interval,
+        });
+
+        packet.encode(&mut self.buffer);
+        match self.socket.send_to(&self.buffer, self.target) {
+            Ok(_) => Ok(true),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::ConnectionReset
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_offsets() {
+        let packet = TelemetryPacket {
+            tick: 0x0102_0304_0506_0708,
+            speed: 1.0,
+            gear: -1,
+            position: [0.0, 2.0, 0.0],
+            lap: 3,
+            sector: 2,
+            best_lap_time: None,
+            tyre_temps: [0.0, 0.0, 0.0, 95.5],
+            on_track: true,
+            ..TelemetryPacket::default()
+        };
+        let mut buf = [0xAA; PACKET_LEN];
+        packet.encode(&mut buf);
+
+        assert_eq!(&buf[0..4], b"F1GT");
+        assert_eq!(&buf[4..8], &[1, 0, PACKET_LEN as u8, 0]);
+        assert_eq!(&buf[8..16], &[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(&buf[16..20], &1.0f32.to_le_bytes());
+        assert_eq!(&buf[40..44], &2.0f32.to_le_bytes());
+        assert_eq!(&buf[60..64], &95.5f32.to_le_bytes());
+        assert_eq!(&buf[68..72], &[0; 4]);
+        assert_eq!(&buf[72..74], &[3, 0]);
+        assert_eq!(&buf[76..80], &[0xFF, 2, FLAG_ON_TRACK, 0]);
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_datagrams() {
+        let mut buf = [0; PACKET_LEN];
+        TelemetryPacket::default().encode(&mut buf);
+        assert!(TelemetryPacket::decode(&buf).is_some());
+
+        assert!(TelemetryPacket::decode(&buf[..PACKET_LEN - 1]).is_none());
+
+        let mut wrong_magic = buf;
+        wrong_magic[0] = b'X';
+        assert!(TelemetryPacket::decode(&wrong_magic).is_none());
+
+        let mut wrong_version = buf;
+        wrong_version[4] = 2;
+        assert!(TelemetryPacket::decode(&wrong_version).is_none());
+    }
+
+    #[test]
+    fn test_sector_of() {
+        assert_eq!(sector_of(0, 0), 0);
+        assert_eq!(sector_of(0, 30), 1);
+        assert_eq!(sector_of(9, 30), 1);
+        assert_eq!(sector_of(10, 30), 2);
+        assert_eq!(sector_of(29, 30), 3);
+        assert_eq!(sector_of(40, 30), 3);
+    }
+
+    #[test]
+    fn test_send_is_rate_limited() {
+        let mut broadcaster = TelemetryBroadcaster::new("127.0.0.1:9", 50).unwrap();
+        let start = Instant::now();
+        assert!(broadcaster.is_due(start));
+
+        let _ = broadcaster
+            .send(start, &TelemetryPacket::default())
+            .unwrap();
+        assert!(!broadcaster.is_due(start + Duration::from_millis(19)));
+        assert!(broadcaster.is_due(start + Duration::from_millis(20)));
+
+        // After a long stall the next packet is one interval away, not a burst
+        let late = start + Duration::from_secs(1);
+        let _ = broadcaster.send(late, &TelemetryPacket::default()).unwrap();
+        assert!(!broadcaster.is_due(late + Duration::from_millis(19)));
+    }
+}
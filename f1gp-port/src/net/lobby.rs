@@ -0,0 +1,216 @@
+//! Host/join handshake over UDP
+//!
+//! The host binds a known address and waits; the client sends hellos to it
+//! until the host answers with the session settings. After that both sides
+//! hold a [`UdpTransport`] to each other and start a lockstep session.
+
+use super::packet::{Message, PROTOCOL_VERSION};
+use super::transport::{UdpTransport, MAX_DATAGRAM};
+use super::{NetError, CLIENT_PLAYER, HOST_PLAYER};
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+/// Gap between the client's hello retries
+const HELLO_INTERVAL: Duration = Duration::from_millis(200);
+
+/// An agreed session, ready to start
+#[derive(Debug)]
+pub struct Connection {
+    /// Link to the other peer
+    pub transport: UdpTransport,
+
+    /// Player slot this side controls
+    pub local_player: usize,
+
+    /// Input delay both sides use, in ticks
+    pub input_delay: u32,
+}
+
+/// Host waiting for a client
+#[derive(Debug)]
+pub struct Host {
+    socket: UdpSocket,
+    input_delay: u32,
+}
+
+impl Host {
+    /// Bind the address clients will join
+    pub fn bind(addr: SocketAddr, input_delay: u32) -> Result<Self, NetError> {
+        let socket = UdpSocket::bind(addr)?;
+        Ok(Self {
+            socket,
+            input_delay,
+        })
+    }
+
+    /// Address the host is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr, NetError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Wait for a client with a matching protocol version
+    ///
+    /// Clients on another version are told so and the wait goes on.
+    pub fn accept(self, timeout: Duration) -> Result<Connection, NetError> {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = vec![0; MAX_DATAGRAM];
+
+        loop {
+            let Some((len, from)) = recv_until(&self.socket, &mut buffer, deadline)? else {
+                return Err(NetError::Timeout(timeout));
+            };
+            let Some(Message::Hello { version }) = Message::decode(&buffer[..len]) else {
+                continue;
+            };
+
+            if version != PROTOCOL_VERSION {
+                log::warn!(
+                    "Turning away {}: protocol {} (ours is {})",
+                    from,
+                    version,
+                    PROTOCOL_VERSION
+                );
+                let reject = Message::Reject {
+                    version: PROTOCOL_VERSION,
+                };
+                self.socket.send_to(&reject.encode(), from)?;
+                continue;
+            }
+
+            let welcome = Message::Welcome {
+                version: PROTOCOL_VERSION,
+                input_delay: self.input_delay,
+            };
+            self.socket.send_to(&welcome.encode(), from)?;
+            log::info!("Player joined from {}", from);
+
+            return Ok(Connection {
+                transport: UdpTransport::new(self.socket, from)?,
+                local_player: HOST_PLAYER,
+                input_delay: self.input_delay,
+            });
+        }
+    }
+}
+
+/// Join the host at `host_addr`
+pub fn join(host_addr: SocketAddr, timeout: Duration) -> Result<Connection, NetError> {
+    let bind_addr: SocketAddr = if host_addr.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(bind_addr)?;
+
+    let deadline = Instant::now() + timeout;
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION,
+    }
+    .encode();
+    let mut buffer = vec![0; MAX_DATAGRAM];
+
+    loop {
+        socket.send_to(&hello, host_addr)?;
+
+        let retry_at = (Instant::now() + HELLO_INTERVAL).min(deadline);
+        let Some((len, from)) = recv_until(&socket, &mut buffer, retry_at)? else {
+            if Instant::now() < deadline {
+                continue;
+            }
+            return Err(NetError::Timeout(timeout));
+        };
+        if from != host_addr {
+            continue;
+        }
+
+        match Message::decode(&buffer[..len]) {
+            Some(Message::Welcome {
+                version,
+                input_delay,
+            }) if version == PROTOCOL_VERSION => {
+                log::info!("Joined {} with input delay {}", host_addr, input_delay);
+                return Ok(Connection {
+                    transport: UdpTransport::new(socket, host_addr)?,
+                    local_player: CLIENT_PLAYER,
+                    input_delay,
+                });
+            }
+            Some(Message::Welcome { version, .. }) | Some(Message::Reject { version }) => {
+                return Err(NetError::VersionMismatch {
+                    ours: PROTOCOL_VERSION,
+                    theirs: version,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Blocking receive, `None` once `deadline` passes
+fn recv_until(
+    socket: &UdpSocket,
+    buffer: &mut [u8],
+    deadline: Instant,
+) -> Result<Option<(usize, SocketAddr)>, NetError> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(None);
+        }
+        socket.set_read_timeout(Some(remaining))?;
+
+        match socket.recv_from(buffer) {
+            Ok(received) => return Ok(Some(received)),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::ConnectionReset
+                ) =>
+            {
+                continue
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::transport::Transport;
+
+    #[test]
+    fn test_join_local_host() {
+        let host = Host::bind("127.0.0.1:0".parse().unwrap(), 4).unwrap();
+        let addr = host.local_addr().unwrap();
+        let accepted = std::thread::spawn(move || host.accept(Duration::from_secs(5)));
+
+        let mut client = join(addr, Duration::from_secs(5)).unwrap();
+        let mut host = accepted.join().unwrap().unwrap();
+
+        assert_eq!(
+            (host.local_player, client.local_player),
+            (HOST_PLAYER, CLIENT_PLAYER)
+        );
+        assert_eq!((host.input_delay, client.input_delay), (4, 4));
+        assert_eq!(client.transport.peer(), addr);
+
+        host.transport.send(b"go").unwrap();
+        let received = (0..1000).find_map(|_| {
+            std::thread::sleep(Duration::from_millis(1));
+            client.transport.recv().unwrap()
+        });
+        assert_eq!(received, Some(b"go".to_vec()));
+    }
+
+    #[test]
+    fn test_join_gives_up_without_host() {
+        // Bound but never answering
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let result = join(silent.local_addr().unwrap(), Duration::from_millis(300));
+        assert!(matches!(result, Err(NetError::Timeout(_))));
+    }
+}
@@ -0,0 +1,450 @@
+//! Lockstep session between two peers
+//!
+//! Each peer schedules its local input `input_delay` ticks ahead and sends it
+//! to the other side. A tick is only simulated once both players' inputs for
+//! it are in, so the simulations never guess and never roll back. Every
+//! [`CHECKSUM_INTERVAL`] ticks the peers swap state digests; a mismatch ends
+//! the session instead of letting the two races drift apart.
+
+use super::packet::{
+    DisconnectReason, InputPacket, Message, NetInput, StateChecksum, PROTOCOL_VERSION,
+};
+use super::transport::Transport;
+use super::{NetError, HOST_PLAYER, PLAYER_COUNT};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Ticks between local input and the tick it applies to
+pub const DEFAULT_INPUT_DELAY: u32 = 3;
+
+/// Ticks between state checksum exchanges
+pub const CHECKSUM_INTERVAL: u32 = 30;
+
+/// Silence from the peer after which the session ends
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Most inputs carried by one packet
+const MAX_INPUTS_PER_PACKET: usize = 64;
+
+/// Checksum ticks kept for comparison with late peer digests
+const CHECKSUM_HISTORY: u32 = 8 * CHECKSUM_INTERVAL;
+
+/// One side of a two-player lockstep session
+#[derive(Debug)]
+pub struct LockstepSession<T: Transport> {
+    transport: T,
+    local_player: usize,
+    input_delay: u32,
+
+    /// Next tick to simulate
+    tick: u32,
+
+    /// Tick the next local input is scheduled for
+    next_local_tick: u32,
+
+    /// Local inputs the peer may still need, by tick
+    local_inputs: BTreeMap<u32, NetInput>,
+
+    /// Peer inputs not simulated yet, by tick
+    remote_inputs: BTreeMap<u32, NetInput>,
+
+    /// Peer inputs are all in below this tick
+    remote_received: u32,
+
+    /// Peer holds all our inputs below this tick
+    peer_ack: u32,
+
+    send_sequence: u32,
+    last_remote_sequence: Option<u32>,
+
+    local_checksums: BTreeMap<u32, u64>,
+    remote_checksums: BTreeMap<u32, u64>,
+    latest_checksum: Option<StateChecksum>,
+
+    last_heard: Instant,
+    timeout: Duration,
+}
+
+impl<T: Transport> LockstepSession<T> {
+    /// Start a session at tick 0
+    ///
+    /// The first `input_delay` ticks run on neutral inputs for both players,
+    /// since no one could have pressed anything for them yet.
+    pub fn new(transport: T, local_player: usize, input_delay: u32) -> Self {
+        assert!(
+            local_player < PLAYER_COUNT,
+            "player {} out of range",
+            local_player
+        );
+
+        let neutral: BTreeMap<u32, NetInput> = (0..input_delay)
+            .map(|tick| (tick, NetInput::default()))
+            .collect();
+
+        Self {
+            transport,
+            local_player,
+            input_delay,
+            tick: 0,
+            next_local_tick: input_delay,
+            local_inputs: BTreeMap::new(),
+            remote_inputs: neutral,
+            remote_received: input_delay,
+            peer_ack: input_delay,
+            send_sequence: 0,
+            last_remote_sequence: None,
+            local_checksums: BTreeMap::new(),
+            remote_checksums: BTreeMap::new(),
+            latest_checksum: None,
+            last_heard: Instant::now(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Override how long the peer may stay silent
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Player slot this side controls
+    pub fn local_player(&self) -> usize {
+        self.local_player
+    }
+
+    /// Input delay in ticks
+    pub fn input_delay(&self) -> u32 {
+        self.input_delay
+    }
+
+    /// Next tick to simulate
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Whether the next local input is due
+    ///
+    /// False while waiting on the peer, so a stalled peer does not make the
+    /// local lead (and so the felt delay) grow.
+    pub fn needs_local_input(&self) -> bool {
+        self.next_local_tick <= self.tick + self.input_delay
+    }
+
+    /// Schedule the local input for tick `tick() + input_delay()`
+    ///
+    /// Returns false, dropping the input, when none is due.
+    pub fn add_local_input(&mut self, input: NetInput) -> bool {
+        if !self.needs_local_input() {
+            return false;
+        }
+        self.local_inputs.insert(self.next_local_tick, input);
+        self.next_local_tick += 1;
+        true
+    }
+
+    /// Send every local input the peer has not acknowledged
+    pub fn send(&mut self) -> Result<(), NetError> {
+        let inputs: Vec<NetInput> = self
+            .local_inputs
+            .range(self.peer_ack..)
+            .take(MAX_INPUTS_PER_PACKET)
+            .map(|(_, &input)| input)
+            .collect();
+
+        let packet = InputPacket {
+            sequence: self.send_sequence,
+            ack: self.remote_received,
+            first_tick: self.peer_ack,
+            inputs,
+            checksum: self.latest_checksum,
+        };
+        self.send_sequence = self.send_sequence.wrapping_add(1);
+
+        self.transport.send(&Message::Inputs(packet).encode())?;
+        Ok(())
+    }
+
+    /// Take in everything the peer has sent
+    pub fn poll(&mut self) -> Result<(), NetError> {
+        while let Some(bytes) = self.transport.recv()? {
+            let Some(message) = Message::decode(&bytes) else {
+                log::debug!("Dropping malformed datagram ({} bytes)", bytes.len());
+                continue;
+            };
+            self.last_heard = Instant::now();
+
+            match message {
+                Message::Inputs(packet) => self.receive_inputs(packet)?,
+                // The client repeats its hello until a welcome gets through
+                Message::Hello { .. } if self.local_player == HOST_PLAYER => {
+                    let welcome = Message::Welcome {
+                        version: PROTOCOL_VERSION,
+                        input_delay: self.input_delay,
+                    };
+                    self.transport.send(&welcome.encode())?;
+                }
+                Message::Disconnect(DisconnectReason::Quit) => return Err(NetError::PeerLeft),
+                Message::Disconnect(DisconnectReason::Desync { tick, digest }) => {
+                    log::error!(
+                        "Peer reported desync at tick {}: peer digest {:016x}, local digest {}",
+                        tick,
+                        digest,
+                        self.local_checksums
+                            .get(&tick)
+                            .map_or("not reached".to_string(), |d| format!("{:016x}", d))
+                    );
+                    return Err(NetError::PeerDesync { tick, digest });
+                }
+                _ => {}
+            }
+        }
+
+        if self.last_heard.elapsed() > self.timeout {
+            return Err(NetError::Timeout(self.timeout));
+        }
+        Ok(())
+    }
+
+    /// Both players' inputs for the next tick, if they are all in
+    ///
+    /// Moves the session on a tick; the caller must step its simulation
+    /// with the returned inputs, ordered by player slot.
+    pub fn next_inputs(&mut self) -> Option<[NetInput; PLAYER_COUNT]> {
+        let tick = self.tick;
+        let local = if tick < self.input_delay {
+            NetInput::default()
+        } else {
+            *self.local_inputs.get(&tick)?
+        };
+        let remote = *self.remote_inputs.get(&tick)?;
+
+        self.remote_inputs.remove(&tick);
+        self.tick += 1;
+        self.prune_local_inputs();
+
+        let mut inputs = [remote; PLAYER_COUNT];
+        inputs[self.local_player] = local;
+        Some(inputs)
+    }
+
+    /// Record the simulation digest after `tick` ticks
+    ///
+    /// Only ticks on the checksum interval are kept and exchanged. Fails on
+    /// a mismatch with the peer's digest for the same tick, after logging it
+    /// and telling the peer.
+    pub fn record_checksum(&mut self, tick: u32, digest: u64) -> Result<(), NetError> {
+        if !tick.is_multiple_of(CHECKSUM_INTERVAL) {
+            return Ok(());
+        }
+
+        self.local_checksums.insert(tick, digest);
+        self.latest_checksum = Some(StateChecksum { tick, digest });
+        self.local_checksums = self
+            .local_checksums
+            .split_off(&tick.saturating_sub(CHECKSUM_HISTORY));
+
+        match self.remote_checksums.remove(&tick) {
+            Some(remote) => self.compare_checksums(tick, digest, remote),
+            None => Ok(()),
+        }
+    }
+
+    /// Tell the peer we are leaving
+    pub fn quit(&mut self) {
+        let message = Message::Disconnect(DisconnectReason::Quit);
+        if let Err(e) = self.transport.send(&message.encode()) {
+            log::debug!("Could not send quit: {}", e);
+        }
+    }
+
+    fn receive_inputs(&mut self, packet: InputPacket) -> Result<(), NetError> {
+        // Stale or reordered; a newer packet already carried the same inputs
+        if self
+            .last_remote_sequence
+            .is_some_and(|last| packet.sequence.wrapping_sub(last) as i32 <= 0)
+        {
+            return Ok(());
+        }
+        self.last_remote_sequence = Some(packet.sequence);
+
+        self.peer_ack = self.peer_ack.max(packet.ack);
+        self.prune_local_inputs();
+
+        for (tick, input) in (packet.first_tick..).zip(packet.inputs) {
+            if tick >= self.tick {
+                self.remote_inputs.entry(tick).or_insert(input);
+            }
+        }
+        while self.remote_inputs.contains_key(&self.remote_received) {
+            self.remote_received += 1;
+        }
+
+        if let Some(StateChecksum { tick, digest }) = packet.checksum {
+            match self.local_checksums.get(&tick) {
+                Some(&local) => self.compare_checksums(tick, local, digest)?,
+                None if tick >= self.tick => {
+                    self.remote_checksums.insert(tick, digest);
+                }
+                // Already compared, or too old to matter
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn compare_checksums(&mut self, tick: u32, local: u64, remote: u64) -> Result<(), NetError> {
+        if local == remote {
+            return Ok(());
+        }
+
+        log::error!(
+            "Desync at tick {}: local digest {:016x}, peer digest {:016x}",
+            tick,
+            local,
+            remote
+        );
+        let message = Message::Disconnect(DisconnectReason::Desync {
+            tick,
+            digest: local,
+        });
+        if let Err(e) = self.transport.send(&message.encode()) {
+            log::debug!("Could not report desync to peer: {}", e);
+        }
+
+        Err(NetError::Desync {
+            tick,
+            local,
+            remote,
+        })
+    }
+
+    /// Forget local inputs both sides are done with
+    fn prune_local_inputs(&mut self) {
+        let keep_from = self.peer_ack.min(self.tick);
+        self.local_inputs = self.local_inputs.split_off(&keep_from);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::transport::ChannelTransport;
+
+    fn pair(
+        delay: u32,
+    ) -> (
+        LockstepSession<ChannelTransport>,
+        LockstepSession<ChannelTransport>,
+    ) {
+        let (a, b) = ChannelTransport::pair();
+        (
+            LockstepSession::new(a, 0, delay),
+            LockstepSession::new(b, 1, delay),
+        )
+    }
+
+    fn throttle(value: u8) -> NetInput {
+        NetInput {
+            throttle: value,
+            ..NetInput::default()
+        }
+    }
+
+    #[test]
+    fn test_delay_ticks_run_on_neutral_inputs() {
+        let (mut host, _client) = pair(2);
+
+        assert_eq!(host.next_inputs(), Some([NetInput::default(); 2]));
+        assert_eq!(host.next_inputs(), Some([NetInput::default(); 2]));
+        // Tick 2 needs real inputs from both sides
+        assert_eq!(host.next_inputs(), None);
+        assert_eq!(host.tick(), 2);
+    }
+
+    #[test]
+    fn test_local_input_waits_for_peer() {
+        let (mut host, mut client) = pair(1);
+
+        assert!(host.add_local_input(throttle(10)));
+        // Only one tick of lead is allowed
+        assert!(!host.add_local_input(throttle(20)));
+        host.send().unwrap();
+
+        assert_eq!(host.next_inputs(), Some([NetInput::default(); 2]));
+        assert_eq!(host.next_inputs(), None);
+
+        client.poll().unwrap();
+        client.next_inputs().unwrap();
+        client.add_local_input(throttle(99));
+        client.send().unwrap();
+        assert_eq!(client.next_inputs(), Some([throttle(10), throttle(99)]));
+
+        host.poll().unwrap();
+        assert_eq!(host.next_inputs(), Some([throttle(10), throttle(99)]));
+    }
+
+    #[test]
+    fn test_stale_packets_are_ignored() {
+        let (mut host, mut client) = pair(0);
+
+        host.add_local_input(throttle(1));
+        host.send().unwrap();
+        let stale = InputPacket {
+            sequence: 0,
+            ack: 0,
+            first_tick: 0,
+            inputs: vec![throttle(200)],
+            checksum: None,
+        };
+        host.transport
+            .send(&Message::Inputs(stale).encode())
+            .unwrap();
+
+        client.add_local_input(throttle(2));
+        client.poll().unwrap();
+        assert_eq!(client.next_inputs(), Some([throttle(1), throttle(2)]));
+    }
+
+    #[test]
+    fn test_checksum_mismatch_ends_both_sides() {
+        let (mut host, mut client) = pair(0);
+
+        client.record_checksum(CHECKSUM_INTERVAL, 0xbad).unwrap();
+        client.send().unwrap();
+        host.record_checksum(CHECKSUM_INTERVAL, 0x600d).unwrap();
+
+        let err = host.poll().unwrap_err();
+        assert!(matches!(
+            err,
+            NetError::Desync {
+                tick: CHECKSUM_INTERVAL,
+                local: 0x600d,
+                remote: 0xbad
+            }
+        ));
+
+        let err = client.poll().unwrap_err();
+        assert!(matches!(
+            err,
+            NetError::PeerDesync {
+                tick: CHECKSUM_INTERVAL,
+                digest: 0x600d
+            }
+        ));
+    }
+
+    #[test]
+    fn test_peer_quit_ends_session() {
+        let (mut host, mut client) = pair(0);
+        client.quit();
+        assert!(matches!(host.poll(), Err(NetError::PeerLeft)));
+    }
+
+    #[test]
+    fn test_silent_peer_times_out() {
+        let (a, _b) = ChannelTransport::pair();
+        let mut session = LockstepSession::new(a, 0, 0).with_timeout(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(matches!(session.poll(), Err(NetError::Timeout(_))));
+    }
+}
@@ -0,0 +1,53 @@
+//! Network multiplayer module (experimental)
+//!
+//! Two-player lockstep over UDP: peers swap per-tick inputs, step identical
+//! fixed-timestep simulations and compare state checksums to catch desyncs.
+//! [`broadcast`] streams live telemetry to external dashboards.
+
+pub mod broadcast;
+pub mod lobby;
+pub mod lockstep;
+pub mod packet;
+pub mod sim;
+pub mod transport;
+
+pub use broadcast::{TelemetryBroadcaster, TelemetryPacket};
+pub use lobby::{join, Connection, Host};
+pub use lockstep::{LockstepSession, CHECKSUM_INTERVAL, DEFAULT_INPUT_DELAY};
+pub use packet::{NetInput, PROTOCOL_VERSION};
+pub use sim::LockstepSim;
+pub use transport::{ChannelTransport, Transport, UdpTransport};
+
+use std::time::Duration;
+use thiserror::Error;
+
+/// Players in a session
+pub const PLAYER_COUNT: usize = 2;
+
+/// Player slot of the hosting peer
+pub const HOST_PLAYER: usize = 0;
+
+/// Player slot of the joining peer
+pub const CLIENT_PLAYER: usize = 1;
+
+/// Ways a network session can end early
+#[derive(Debug, Error)]
+pub enum NetError {
+    #[error("Network error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Peer did not respond within {0:?}")]
+    Timeout(Duration),
+
+    #[error("Peer left the session")]
+    PeerLeft,
+
+    #[error("Protocol version mismatch (ours {ours}, peer {theirs})")]
+    VersionMismatch { ours: u16, theirs: u16 },
+
+    #[error("Desync at tick {tick}: local digest {local:016x}, peer digest {remote:016x}")]
+    Desync { tick: u32, local: u64, remote: u64 },
+
+    #[error("Peer detected a desync at tick {tick} (peer digest {digest:016x})")]
+    PeerDesync { tick: u32, digest: u64 },
+}
@@ -0,0 +1,196 @@
+//! Wire format for lockstep sessions
+//!
+//! Every datagram is one bincode-encoded [`Message`]. Inputs are quantized
+//! before they go on the wire so both peers feed the simulation exactly the
+//! same floats, whatever the local input device produced.
+
+use crate::game::CarInput;
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever the wire format or the simulation changes incompatibly
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Shift-up bit in [`NetInput::buttons`]
+pub const BUTTON_SHIFT_UP: u8 = 0b01;
+
+/// Shift-down bit in [`NetInput::buttons`]
+pub const BUTTON_SHIFT_DOWN: u8 = 0b10;
+
+/// One player's input for one tick, quantized for the wire
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetInput {
+    /// Throttle (0-255 maps to 0.0-1.0)
+    pub throttle: u8,
+
+    /// Brake (0-255 maps to 0.0-1.0)
+    pub brake: u8,
+
+    /// Steering (-127 to 127 maps to -1.0 to 1.0)
+    pub steering: i8,
+
+    /// Shift requests (`BUTTON_*` bits)
+    pub buttons: u8,
+}
+
+impl NetInput {
+    /// Quantize a local car input
+    pub fn from_car_input(input: &CarInput) -> Self {
+        let mut buttons = 0;
+        if input.shift_up {
+            buttons |= BUTTON_SHIFT_UP;
+        }
+        if input.shift_down {
+            buttons |= BUTTON_SHIFT_DOWN;
+        }
+
+        Self {
+            throttle: (input.throttle.clamp(0.0, 1.0) * 255.0).round() as u8,
+            brake: (input.brake.clamp(0.0, 1.0) * 255.0).round() as u8,
+            steering: (input.steering.clamp(-1.0, 1.0) * 127.0).round() as i8,
+            buttons,
+        }
+    }
+
+    /// Expand back into the car input the simulation applies
+    pub fn to_car_input(self) -> CarInput {
+        CarInput {
+            throttle: self.throttle as f32 / 255.0,
+            brake: self.brake as f32 / 255.0,
+            steering: self.steering as f32 / 127.0,
+            shift_up: self.buttons & BUTTON_SHIFT_UP != 0,
+            shift_down: self.buttons & BUTTON_SHIFT_DOWN != 0,
+        }
+    }
+}
+
+/// Digest of the simulation state after a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChecksum {
+    /// Tick the digest was taken after
+    pub tick: u32,
+
+    /// State digest
+    pub digest: u64,
+}
+
+/// Inputs for a run of consecutive ticks
+///
+/// Senders repeat every input the peer has not acknowledged yet, so a lost
+/// datagram is covered by the next one instead of needing a resend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputPacket {
+    /// Per-sender packet counter, used to drop stale or reordered packets
+    pub sequence: u32,
+
+    /// Ticks of the receiver's inputs the sender holds, all ticks below it
+    pub ack: u32,
+
+    /// Tick of the first entry in `inputs`
+    pub first_tick: u32,
+
+    /// Sender's inputs for `first_tick`, `first_tick + 1`, ...
+    pub inputs: Vec<NetInput>,
+
+    /// Sender's most recent state checksum
+    pub checksum: Option<StateChecksum>,
+}
+
+/// Why a peer is leaving the session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisconnectReason {
+    /// Player quit
+    Quit,
+
+    /// Sender's state diverged at `tick`; `digest` is the sender's digest
+    Desync { tick: u32, digest: u64 },
+}
+
+/// Everything that can travel between peers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Message {
+    /// Joining client asks to play
+    Hello { version: u16 },
+
+    /// Host accepts the client and fixes the session settings
+    Welcome { version: u16, input_delay: u32 },
+
+    /// Host turns the client away
+    Reject { version: u16 },
+
+    /// Per-tick inputs
+    Inputs(InputPacket),
+
+    /// Peer is ending the session
+    Disconnect(DisconnectReason),
+}
+
+impl Message {
+    /// Encode for sending
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("net messages always serialize")
+    }
+
+    /// Decode a received datagram, `None` if it is not a message
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        bincode::deserialize(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_quantization_round_trip() {
+        let input = CarInput {
+            throttle: 1.0,
+            brake: 0.0,
+            steering: -1.0,
+            shift_up: true,
+            shift_down: false,
+        };
+
+        let net = NetInput::from_car_input(&input);
+        assert_eq!(net.throttle, 255);
+        assert_eq!(net.steering, -127);
+        assert_eq!(net.buttons, BUTTON_SHIFT_UP);
+
+        let back = net.to_car_input();
+        assert_eq!(back.throttle, 1.0);
+        assert_eq!(back.steering, -1.0);
+        assert!(back.shift_up && !back.shift_down);
+
+        // Quantizing again is stable
+        assert_eq!(NetInput::from_car_input(&back), net);
+    }
+
+    #[test]
+    fn test_out_of_range_inputs_clamp() {
+        let input = CarInput {
+            throttle: 3.0,
+            brake: -1.0,
+            steering: 2.0,
+            ..CarInput::default()
+        };
+
+        let net = NetInput::from_car_input(&input);
+        assert_eq!((net.throttle, net.brake, net.steering), (255, 0, 127));
+    }
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = Message::Inputs(InputPacket {
+            sequence: 7,
+            ack: 40,
+            first_tick: 38,
+            inputs: vec![NetInput::default(); 3],
+            checksum: Some(StateChecksum {
+                tick: 30,
+                digest: 0xdead_beef,
+            }),
+        });
+
+        assert_eq!(Message::decode(&message.encode()), Some(message));
+        assert_eq!(Message::decode(&[0xff; 3]), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Deterministic two-car simulation for lockstep play
+//!
+//! Both peers run this with identical inputs at [`PHYSICS_TIMESTEP`], so it
+//! must never read the clock, the frame time or a random source. The cars run
+//! on a flat plane: track collision is left to the full game for now.
+
+use super::packet::NetInput;
+use super::PLAYER_COUNT;
+use crate::data::car::CarSpec;
+use crate::physics::{BodyId, CarPhysics, PHYSICS_TIMESTEP};
+use glam::Vec3;
+
+/// Gap between the two grid slots (m)
+const GRID_SPACING: f32 = 8.0;
+
+/// FNV-1a offset basis
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Two cars stepped together, one per player
+#[derive(Debug, Clone)]
+pub struct LockstepSim {
+    cars: [CarPhysics; PLAYER_COUNT],
+    tick: u32,
+}
+
+impl LockstepSim {
+    /// Both cars side by side on the grid, at rest
+    pub fn new(spec: CarSpec) -> Self {
+        let car = |slot: usize| {
+            let offset = Vec3::new(0.0, 0.0, slot as f32 * GRID_SPACING);
+            CarPhysics::new(BodyId(slot), spec.clone(), offset)
+        };
+
+        Self {
+            cars: [car(0), car(1)],
+            tick: 0,
+        }
+    }
+
+    /// Ticks simulated so far
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Car driven by `player`
+    pub fn car(&self, player: usize) -> &CarPhysics {
+        &self.cars[player]
+    }
+
+    /// Mutable car access, e.g. to inject a fault in tests
+    pub fn car_mut(&mut self, player: usize) -> &mut CarPhysics {
+        &mut self.cars[player]
+    }
+
+    /// Advance one tick with each player's input
+    pub fn step(&mut self, inputs: &[NetInput; PLAYER_COUNT]) {
+        let dt = PHYSICS_TIMESTEP;
+
+        for (car, input) in self.cars.iter_mut().zip(inputs) {
+            let input = input.to_car_input();
+            car.set_throttle(input.throttle);
+            car.set_brake(input.brake);
+            car.set_steering(input.steering);
+            if input.shift_up {
+                car.shift_up();
+            }
+            if input.shift_down {
+                car.shift_down();
+            }
+
+            car.update(dt);
+
+            // Semi-implicit Euler, as in `PhysicsWorld`, with the plane
+            // holding the car up
+            let body = &mut car.body;
+            body.velocity += body.force_accumulator * body.inv_mass * dt;
+            body.velocity.y = 0.0;
+            body.velocity *= body.linear_damping;
+            body.position += body.velocity * dt;
+
+            body.angular_velocity += body.torque_accumulator * body.inv_moment_of_inertia * dt;
+            body.angular_velocity *= body.angular_damping;
+            if body.angular_velocity.length() > 0.0001 {
+                let delta = glam::Quat::from_scaled_axis(body.angular_velocity * dt);
+                body.orientation = (delta * body.orientation).normalize();
+            }
+
+            body.clear_accumulators();
+        }
+
+        self.tick += 1;
+    }
+
+    /// Digest of everything that feeds the next tick
+    ///
+    /// Floats are hashed by bit pattern, so any divergence at all shows up.
+    pub fn digest(&self) -> u64 {
+        let mut hash = FNV_OFFSET;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        feed(&self.tick.to_le_bytes());
+        for car in &self.cars {
+            let body = &car.body;
+            let floats = [
+                body.position.to_array(),
+                body.velocity.to_array(),
+                body.angular_velocity.to_array(),
+            ];
+            for value in floats.iter().flatten() {
+                feed(&value.to_bits().to_le_bytes());
+            }
+            for value in body.orientation.to_array() {
+                feed(&value.to_bits().to_le_bytes());
+            }
+            for value in [car.engine_rpm, car.speed] {
+                feed(&value.to_bits().to_le_bytes());
+            }
+            for value in car.tire_temps {
+                feed(&value.to_bits().to_le_bytes());
+            }
+            feed(&car.gear.to_le_bytes());
+        }
+
+        hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::car::CarDatabase;
+
+    fn sim() -> LockstepSim {
+        let db = CarDatabase::create_sample();
+        LockstepSim::new(db.get_car("McLaren").unwrap().clone())
+    }
+
+    fn flat_out() -> NetInput {
+        NetInput {
+            throttle: 255,
+            ..NetInput::default()
+        }
+    }
+
+    #[test]
+    fn test_same_inputs_same_digest() {
+        let (mut a, mut b) = (sim(), sim());
+        for tick in 0..600 {
+            let steer = NetInput {
+                steering: if tick % 120 < 60 { 60 } else { -60 },
+                ..flat_out()
+            };
+            a.step(&[flat_out(), steer]);
+            b.step(&[flat_out(), steer]);
+        }
+
+        assert_eq!(a.tick(), 600);
+        assert_eq!(a.digest(), b.digest());
+        assert!(a.car(0).speed > 0.0);
+    }
+
+    #[test]
+    fn test_any_difference_changes_digest() {
+        let (mut a, mut b) = (sim(), sim());
+        a.step(&[flat_out(), NetInput::default()]);
+        b.step(&[flat_out(), NetInput::default()]);
+        assert_eq!(a.digest(), b.digest());
+
+        b.car_mut(1).body.position.x += 0.001;
+        assert_ne!(a.digest(), b.digest());
+    }
+}
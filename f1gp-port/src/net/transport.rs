@@ -0,0 +1,138 @@
+//! Datagram transports between two peers
+//!
+//! Lockstep only needs unreliable, unordered datagrams: sequence numbers and
+//! input redundancy in the packets cover loss and reordering.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+/// Largest datagram the session sends or accepts
+pub const MAX_DATAGRAM: usize = 1200;
+
+/// Unreliable datagram link to the other peer
+pub trait Transport {
+    /// Send one datagram
+    fn send(&mut self, payload: &[u8]) -> io::Result<()>;
+
+    /// Next received datagram, without blocking
+    fn recv(&mut self) -> io::Result<Option<Vec<u8>>>;
+}
+
+/// UDP socket connected to a single peer
+#[derive(Debug)]
+pub struct UdpTransport {
+    socket: UdpSocket,
+    peer: SocketAddr,
+    buffer: Vec<u8>,
+}
+
+impl UdpTransport {
+    /// Wrap a bound socket, talking only to `peer`
+    pub fn new(socket: UdpSocket, peer: SocketAddr) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            peer,
+            buffer: vec![0; MAX_DATAGRAM],
+        })
+    }
+
+    /// Address of the other peer
+    pub fn peer(&self) -> SocketAddr {
+        self.peer
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send(&mut self, payload: &[u8]) -> io::Result<()> {
+        self.socket.send_to(payload, self.peer)?;
+        Ok(())
+    }
+
+    fn recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            match self.socket.recv_from(&mut self.buffer) {
+                // Anyone else on the port is ignored
+                Ok((len, from)) if from == self.peer => {
+                    return Ok(Some(self.buffer[..len].to_vec()))
+                }
+                Ok(_) => continue,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                // Windows reports an ICMP port unreachable from an earlier send here
+                Err(e) if e.kind() == io::ErrorKind::ConnectionReset => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// In-process transport over channels, for tests and local play
+#[derive(Debug)]
+pub struct ChannelTransport {
+    tx: Sender<Vec<u8>>,
+    rx: Receiver<Vec<u8>>,
+}
+
+impl ChannelTransport {
+    /// Two transports wired to each other
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, b_rx) = mpsc::channel();
+        let (b_tx, a_rx) = mpsc::channel();
+        (Self { tx: a_tx, rx: a_rx }, Self { tx: b_tx, rx: b_rx })
+    }
+}
+
+impl Transport for ChannelTransport {
+    fn send(&mut self, payload: &[u8]) -> io::Result<()> {
+        self.tx
+            .send(payload.to_vec())
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "peer transport dropped"))
+    }
+
+    fn recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        match self.rx.try_recv() {
+            Ok(payload) => Ok(Some(payload)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "peer transport dropped",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_pair_delivers_both_ways() {
+        let (mut a, mut b) = ChannelTransport::pair();
+        a.send(b"ping").unwrap();
+        b.send(b"pong").unwrap();
+
+        assert_eq!(b.recv().unwrap(), Some(b"ping".to_vec()));
+        assert_eq!(a.recv().unwrap(), Some(b"pong".to_vec()));
+        assert_eq!(a.recv().unwrap(), None);
+    }
+
+    #[test]
+    fn test_udp_transport_loopback() {
+        let a_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let b_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let (a_addr, b_addr) = (
+            a_socket.local_addr().unwrap(),
+            b_socket.local_addr().unwrap(),
+        );
+        let mut a = UdpTransport::new(a_socket, b_addr).unwrap();
+        let mut b = UdpTransport::new(b_socket, a_addr).unwrap();
+
+        a.send(b"lights out").unwrap();
+        let received = (0..1000).find_map(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            b.recv().unwrap()
+        });
+        assert_eq!(received, Some(b"lights out".to_vec()));
+    }
+}
@@ -4,8 +4,18 @@
 
 use super::engine::{BodyId, PhysicsBody};
 use crate::data::car::CarSpec;
+use crate::data::track::CarSetup;
 use glam::Vec3;
 
+/// Gravitational acceleration (m/s²)
+const GRAVITY: f32 = 9.81;
+
+/// Base tire friction coefficient
+const BASE_FRICTION: f32 = 0.8;
+
+/// Drivetrain ratios for gears 1-6 before a setup is applied
+pub const DEFAULT_GEAR_RATIOS: [f32; 6] = [3.5, 2.5, 1.8, 1.4, 1.1, 0.9];
+
 /// Tire grip levels
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TireGrip {
@@ -61,6 +71,9 @@ pub struct CarPhysics {
     /// Current gear (0 = reverse, 1-6 = forward gears)
     pub gear: i8,
 
+    /// Drivetrain ratios for gears 1-6
+    gear_ratios: [f32; 6],
+
     /// Throttle input (0.0-1.0)
     pub throttle: f32,
 
@@ -82,6 +95,12 @@ pub struct CarPhysics {
     /// Surface grip multiplier applied this frame (track/weather)
     surface_grip_multiplier: f32,
 
+    /// Road gradient along the car's heading (rise over run, + = uphill)
+    pub track_grade: f32,
+
+    /// Road camber under the car (radians, + = banked for a right-hander)
+    pub track_camber: f32,
+
     /// Is car on track surface?
     pub on_track: bool,
 
@@ -99,6 +118,7 @@ impl CarPhysics {
             spec,
             engine_rpm: 1000.0, // Idle RPM
             gear: 1,            // Start in first gear
+            gear_ratios: DEFAULT_GEAR_RATIOS,
             throttle: 0.0,
             brake: 0.0,
             steering: 0.0,
@@ -106,6 +126,8 @@ impl CarPhysics {
             tire_temps: [80.0; 4], // Optimal temp around 80°C
             tire_grip: TireGrip::perfect(),
             surface_grip_multiplier: 1.0,
+            track_grade: 0.0,
+            track_camber: 0.0,
             on_track: true,
             speed: 0.0,
         }
@@ -146,6 +168,9 @@ impl CarPhysics {
         // Apply aerodynamic forces
         self.apply_aerodynamic_forces();
 
+        // Apply gravity along the road slope
+        self.apply_slope_force();
+
         // Apply tire friction
         self.apply_tire_friction();
 
@@ -165,15 +190,7 @@ impl CarPhysics {
 
         // Simplified: RPM proportional to rear wheel speed
         let avg_rear_wheel = (self.wheel_speeds[2] + self.wheel_speeds[3]) / 2.0;
-        let gear_ratio = match self.gear {
-            1 => 3.5,
-            2 => 2.5,
-            3 => 1.8,
-            4 => 1.4,
-            5 => 1.1,
-            6 => 0.9,
-            _ => 1.0,
-        };
+        let gear_ratio = self.gear_ratio(self.gear);
 
         let wheel_rpm =
             (avg_rear_wheel * gear_ratio * 60.0 / (2.0 * std::f32::consts::PI)).max(0.0);
@@ -254,23 +271,56 @@ impl CarPhysics {
         self.body.add_force(Vec3::new(0.0, -downforce, 0.0));
     }
 
+    /// Apply the component of gravity acting along the road
+    fn apply_slope_force(&mut self) {
+        if self.track_grade == 0.0 {
+            return;
+        }
+
+        // Uphill pulls the car back, downhill pushes it forward
+        let slope_angle = self.track_grade.atan();
+        let forward = self.body.orientation * Vec3::X;
+        self.body
+            .add_force(-forward * self.spec.mass * GRAVITY * slope_angle.sin());
+    }
+
     /// Apply tire friction
     fn apply_tire_friction(&mut self) {
         // Simplified tire friction
-        let friction_coefficient =
-            0.8 * self.tire_grip.average() * self.surface_grip_multiplier.clamp(0.0, 1.5);
+        let friction_coefficient = BASE_FRICTION
+            * self.tire_grip.average()
+            * self.surface_grip_multiplier.clamp(0.0, 1.5)
+            * self.camber_grip_factor();
 
         // Lateral friction (perpendicular to forward direction)
         let forward = self.body.orientation * Vec3::X;
         let lateral_velocity = self.body.velocity - forward * self.body.velocity.dot(forward);
 
         if lateral_velocity.length() > 0.1 {
-            let lateral_friction =
-                -lateral_velocity.normalize() * friction_coefficient * self.spec.mass * 9.81 * 2.0;
+            let lateral_friction = -lateral_velocity.normalize()
+                * friction_coefficient
+                * self.spec.mass
+                * GRAVITY
+                * 2.0;
             self.body.add_force(lateral_friction);
         }
     }
 
+    /// Lateral grip multiplier from road camber
+    ///
+    /// Camber leaning into the turn loads the tires and lets the road carry part
+    /// of the cornering force; adverse camber unloads them and costs grip.
+    pub fn camber_grip_factor(&self) -> f32 {
+        let turn_direction = if self.steering.abs() > 0.01 {
+            self.steering.signum()
+        } else {
+            0.0
+        };
+        let camber_into_turn = self.track_camber * turn_direction;
+
+        (self.track_camber.cos() + camber_into_turn.sin() / BASE_FRICTION).clamp(0.5, 1.5)
+    }
+
     /// Update tire temperatures based on usage
     fn update_tire_temps(&mut self, dt: f32) {
         // Simplified: tire temp increases with speed and friction
@@ -343,6 +393,19 @@ impl CarPhysics {
         self.steering = steering.clamp(-1.0, 1.0);
     }
 
+    /// Use the gearing of `setup` from now on
+    pub fn apply_setup(&mut self, setup: &CarSetup) {
+        self.gear_ratios = setup.physics_gear_ratios();
+    }
+
+    /// Drivetrain ratio of a forward gear (1.0 outside 1-6)
+    pub fn gear_ratio(&self, gear: i8) -> f32 {
+        match gear {
+            1..=6 => self.gear_ratios[gear as usize - 1],
+            _ => 1.0,
+        }
+    }
+
     /// Shift to higher gear
     pub fn shift_up(&mut self) {
         if self.gear < 6 {
@@ -357,6 +420,12 @@ impl CarPhysics {
         }
     }
 
+    /// Set road gradient (along the car's heading) and camber under the car
+    pub fn set_track_slope(&mut self, grade: f32, camber: f32) {
+        self.track_grade = grade;
+        self.track_camber = camber;
+    }
+
     /// Apply surface-based grip multiplier to all tires
     /// This modifies the tire grip based on the surface type (track, grass, gravel, etc.)
     pub fn apply_surface_grip(&mut self, surface_multiplier: f32) {
@@ -461,6 +530,65 @@ mod tests {
         assert!(car.engine_rpm > 1000.0);
     }
 
+    /// Drive flat out in a straight line, integrating the car body directly
+    fn speed_after(grade: f32, seconds: f32) -> f32 {
+        let spec = create_test_car_spec();
+        let mut car = CarPhysics::new(BodyId(3), spec, Vec3::ZERO);
+        car.body.velocity = Vec3::new(20.0, 0.0, 0.0);
+        car.set_track_slope(grade, 0.0);
+        car.set_throttle(1.0);
+
+        let dt = crate::physics::PHYSICS_TIMESTEP;
+        for _ in 0..(seconds / dt) as usize {
+            car.update(dt);
+            car.body.velocity += car.body.force_accumulator * car.body.inv_mass * dt;
+            car.body.velocity.y = 0.0; // Road surface supports the car
+            car.body.position += car.body.velocity * dt;
+            car.body.clear_accumulators();
+        }
+
+        car.body.velocity.x
+    }
+
+    #[test]
+    fn grade_changes_speed_for_same_throttle() {
+        let flat = speed_after(0.0, 5.0);
+        let uphill = speed_after(0.1, 5.0);
+        let downhill = speed_after(-0.1, 5.0);
+
+        assert!(
+            uphill < flat,
+            "uphill {} should be slower than flat {}",
+            uphill,
+            flat
+        );
+        assert!(
+            downhill > flat,
+            "downhill {} should be faster than flat {}",
+            downhill,
+            flat
+        );
+    }
+
+    #[test]
+    fn camber_into_turn_adds_grip() {
+        let spec = create_test_car_spec();
+        let mut car = CarPhysics::new(BodyId(4), spec, Vec3::ZERO);
+        assert_eq!(car.camber_grip_factor(), 1.0);
+
+        // Right-hand turn on a road banked for a right-hander
+        car.set_steering(0.5);
+        car.set_track_slope(0.0, 0.1);
+        let banked = car.camber_grip_factor();
+
+        // Same turn with adverse camber
+        car.set_track_slope(0.0, -0.1);
+        let adverse = car.camber_grip_factor();
+
+        assert!(banked > 1.0);
+        assert!(adverse < 1.0);
+    }
+
     #[test]
     fn surface_grip_does_not_compound() {
         let spec = create_test_car_spec();
@@ -22,6 +22,28 @@ pub struct CollisionResult {
 
     /// Closest track section index
     pub nearest_section: usize,
+
+    /// Road elevation under the car (meters, interpolated along the section)
+    pub elevation: f32,
+
+    /// Section gradient in the track's driving direction (rise over run)
+    pub grade: f32,
+
+    /// Section camber (radians, + = banked for a right-hander)
+    pub camber: f32,
+
+    /// Unit track direction in the XZ plane
+    pub direction: Vec2,
+
+    /// Distance along the lap from the start of the first section (meters)
+    pub lap_distance: f32,
+}
+
+impl CollisionResult {
+    /// Gradient along a heading (XZ plane), e.g. the car's forward vector
+    pub fn grade_along(&self, heading: Vec2) -> f32 {
+        self.grade * heading.normalize_or_zero().dot(self.direction)
+    }
 }
 
 /// Track collision detector
@@ -31,6 +53,9 @@ pub struct TrackCollision {
 
     /// Cached track positions for faster lookup
     track_positions: Vec<Vec2>,
+
+    /// Lap distance at the start of each section (meters)
+    section_starts: Vec<f32>,
 }
 
 impl TrackCollision {
@@ -42,10 +67,20 @@ impl TrackCollision {
             .iter()
             .map(|s| Vec2::new(s.position.x, s.position.z))
             .collect();
+        let section_starts = track
+            .sections
+            .iter()
+            .scan(0.0, |start, section| {
+                let section_start = *start;
+                *start += section.length;
+                Some(section_start)
+            })
+            .collect();
 
         Self {
             track,
             track_positions,
+            section_starts,
         }
     }
 
@@ -57,7 +92,20 @@ impl TrackCollision {
         let nearest_section = self.find_nearest_section(car_pos_2d);
         let section = &self.track.sections[nearest_section];
 
-        let (distance_from_center, _) = self.project_onto_section(nearest_section, car_pos_2d);
+        let (distance_from_center, closest) =
+            self.project_onto_section(nearest_section, car_pos_2d);
+        let next_section = &self.track.sections[(nearest_section + 1) % self.track.sections.len()];
+        let next_pos = self.track_positions[(nearest_section + 1) % self.track_positions.len()];
+        let section_pos = self.track_positions[nearest_section];
+
+        // Interpolate elevation along the section
+        let span = next_pos - section_pos;
+        let t = if span.length_squared() > 1e-6 {
+            ((closest - section_pos).dot(span) / span.length_squared()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let elevation = section.elevation + (next_section.elevation - section.elevation) * t;
 
         let half_width = section.width / 2.0;
         let on_track = distance_from_center <= half_width;
@@ -93,6 +141,11 @@ impl TrackCollision {
             on_track,
             grip_multiplier,
             nearest_section,
+            elevation,
+            grade: self.track.section_grade(nearest_section),
+            camber: self.track.section_camber(nearest_section),
+            direction: span.normalize_or_zero(),
+            lap_distance: self.section_starts[nearest_section] + section.length * t,
         }
     }
 
@@ -217,6 +270,7 @@ mod tests {
             ai_behavior: AIBehavior::default(),
             pit_lane: vec![],
             cameras: vec![],
+            annotations: Default::default(),
             checksum: 0,
         }
     }
@@ -264,6 +318,34 @@ mod tests {
         assert!(!collision.check_lap_crossing(0, 1));
     }
 
+    #[test]
+    fn test_collision_reports_slope() {
+        let mut track = create_test_track();
+        track.sections[1].position.y = 10.0;
+        track.sections[1].elevation = 10.0;
+        track.sections[0].banking = 0.1;
+        let collision = TrackCollision::new(track);
+
+        let result = collision.check_collision(Vec3::new(40.0, 0.0, 0.0));
+        assert_eq!(result.nearest_section, 0);
+        assert!((result.grade - 0.1).abs() < 1e-6);
+        assert_eq!(result.camber, 0.1);
+        assert!((result.elevation - 4.0).abs() < 1e-4);
+
+        // Driving the wrong way turns the climb into a descent
+        assert!(result.grade_along(Vec2::new(-1.0, 0.0)) < 0.0);
+        assert!((result.grade_along(Vec2::new(1.0, 0.0)) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_collision_reports_lap_distance() {
+        let collision = TrackCollision::new(create_test_track());
+
+        assert_eq!(collision.check_collision(Vec3::ZERO).lap_distance, 0.0);
+        let result = collision.check_collision(Vec3::new(40.0, 0.0, 3.0));
+        assert!((result.lap_distance - 40.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_nearest_section() {
         let track = create_test_track();
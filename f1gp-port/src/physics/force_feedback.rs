@@ -0,0 +1,475 @@
+//! Steering force feedback
+//!
+//! Derives a steering wheel torque signal from the car physics each tick:
+//! self-aligning torque from the front tyre slip angle, vibration over kerbs
+//! and off-track surfaces, and jolts from sudden impacts. The signal is
+//! normalized so that 1.0 is the wheel's full-scale torque; anything beyond
+//! that is clipped by the device and flagged by [`ClippingDetector`].
+
+use crate::data::track::SurfaceType;
+use crate::physics::CarPhysics;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// Pacejka stiffness factor for the front tyre lateral force curve
+const TYRE_STIFFNESS: f32 = 10.0;
+
+/// Pacejka shape factor for the front tyre lateral force curve
+const TYRE_SHAPE: f32 = 1.9;
+
+/// Maximum steering angle of the front wheels (radians, matches car physics)
+const MAX_STEERING_ANGLE: f32 = 30.0 * std::f32::consts::PI / 180.0;
+
+/// Speed (m/s) at which the self-aligning torque reaches full weight
+const ALIGNING_FULL_SPEED: f32 = 30.0;
+
+/// Length of one kerb stripe (meters); sets the kerb vibration frequency
+const KERB_STRIPE_LENGTH: f32 = 1.5;
+
+/// Peak torque of the kerb vibration
+const KERB_AMPLITUDE: f32 = 0.25;
+
+/// Peak torque of the grass rumble
+const GRASS_AMPLITUDE: f32 = 0.12;
+
+/// Peak torque of the gravel rumble
+const GRAVEL_AMPLITUDE: f32 = 0.3;
+
+/// Speed (m/s) at which surface vibration reaches full amplitude
+const VIBRATION_FULL_SPEED: f32 = 20.0;
+
+/// Per-tick acceleration (m/s²) above which a velocity change counts as an impact
+const JOLT_THRESHOLD: f32 = 60.0;
+
+/// Acceleration above the threshold that produces a full-scale jolt
+const JOLT_FULL_SCALE: f32 = 120.0;
+
+/// Time constant (seconds) over which a collision jolt dies away
+const JOLT_DECAY: f32 = 0.15;
+
+/// Signal level above which the wheel output is clipped
+pub const CLIP_LEVEL: f32 = 1.0;
+
+/// How long (seconds) the clipping indicator stays lit after the last clipped sample
+pub const CLIP_HOLD: f32 = 0.5;
+
+/// Slip angle (radians) at which the front tyre lateral force peaks
+pub fn peak_slip_angle() -> f32 {
+    (std::f32::consts::FRAC_PI_2 / TYRE_SHAPE).tan() / TYRE_STIFFNESS
+}
+
+/// Normalized front tyre lateral force at a slip angle (Pacejka, peak = 1.0)
+pub fn lateral_force(slip_angle: f32) -> f32 {
+    (TYRE_SHAPE * (TYRE_STIFFNESS * slip_angle).atan()).sin()
+}
+
+/// Normalized pneumatic trail at a slip angle
+///
+/// The trail shrinks as the contact patch starts to slide and passes zero at
+/// the force peak, which is what makes the steering go light past the limit.
+pub fn pneumatic_trail(slip_angle: f32) -> f32 {
+    1.0 - slip_angle.abs() / peak_slip_angle()
+}
+
+/// Self-aligning torque at a slip angle
+///
+/// Positive torque pulls the wheel back towards the direction of travel. It
+/// builds with slip, fades as the tyre nears its peak and reverses beyond it.
+pub fn aligning_torque(slip_angle: f32) -> f32 {
+    lateral_force(slip_angle) * pneumatic_trail(slip_angle)
+}
+
+/// Slip angle of the front tyres (radians)
+///
+/// The difference between where the front wheels point and where the car is
+/// actually travelling, in the same sense as positive steering input.
+pub fn front_slip_angle(car: &CarPhysics) -> f32 {
+    let forward = car.body.orientation * Vec3::X;
+    let left = car.body.orientation * Vec3::NEG_Z;
+    let longitudinal = car.body.velocity.dot(forward);
+    let lateral = car.body.velocity.dot(left);
+
+    if longitudinal.abs() < 0.5 {
+        return 0.0;
+    }
+
+    car.steering * MAX_STEERING_ANGLE - lateral.atan2(longitudinal.abs())
+}
+
+/// Which force feedback effects are enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FfbEffects {
+    /// Self-aligning torque from the front tyres
+    pub self_aligning: bool,
+    /// Vibration over kerbs
+    pub kerbs: bool,
+    /// Rumble on grass and gravel
+    pub off_track: bool,
+    /// Jolts from impacts
+    pub collisions: bool,
+}
+
+impl Default for FfbEffects {
+    fn default() -> Self {
+        Self {
+            self_aligning: true,
+            kerbs: true,
+            off_track: true,
+            collisions: true,
+        }
+    }
+}
+
+/// Force feedback state as shown in the HUD
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FfbStatus {
+    /// Torque currently sent to the wheel (-1.0 to 1.0)
+    pub level: f32,
+    /// Whether the requested torque recently exceeded the wheel's range
+    pub clipping: bool,
+}
+
+/// Steering torque model, stepped once per physics tick
+#[derive(Debug, Clone)]
+pub struct ForceFeedbackModel {
+    /// Enabled effects
+    effects: FfbEffects,
+    /// Overall gain applied to the summed effects
+    strength: f32,
+    /// Kerb vibration phase (0.0 to 1.0)
+    kerb_phase: f32,
+    /// Off-track noise generator state
+    noise_state: u32,
+    /// Velocity at the previous tick, for impact detection
+    prev_velocity: Option<Vec3>,
+    /// Current collision jolt torque (decays towards zero)
+    jolt: f32,
+}
+
+impl Default for ForceFeedbackModel {
+    fn default() -> Self {
+        Self::new(FfbEffects::default(), 1.0)
+    }
+}
+
+impl ForceFeedbackModel {
+    /// Create a model with the given effects and strength
+    pub fn new(effects: FfbEffects, strength: f32) -> Self {
+        Self {
+            effects,
+            strength: strength.max(0.0),
+            kerb_phase: 0.0,
+            noise_state: 0x9E37_79B9,
+            prev_velocity: None,
+            jolt: 0.0,
+        }
+    }
+
+    /// Change effects and strength, keeping the running state
+    pub fn configure(&mut self, effects: FfbEffects, strength: f32) {
+        self.effects = effects;
+        self.strength = strength.max(0.0);
+    }
+
+    /// Forget the previous tick (call after teleporting the car)
+    pub fn reset(&mut self) {
+        self.kerb_phase = 0.0;
+        self.prev_velocity = None;
+        self.jolt = 0.0;
+    }
+
+    /// Compute the steering torque for this tick
+    ///
+    /// The result is not clamped, so a strong enough signal can exceed
+    /// [`CLIP_LEVEL`].
+    pub fn update(&mut self, car: &CarPhysics, surface: SurfaceType, dt: f32) -> f32 {
+        let mut torque = 0.0;
+
+        if self.effects.self_aligning {
+            let grip = car.tire_grip.average();
+            let speed_factor = (car.speed / ALIGNING_FULL_SPEED).min(1.0);
+            torque += aligning_torque(front_slip_angle(car)) * grip * speed_factor;
+        }
+
+        torque += self.surface_vibration(car.speed, surface, dt);
+        torque += self.collision_jolt(car, dt);
+
+        torque * self.strength
+    }
+
+    /// Kerb and off-track vibration
+    fn surface_vibration(&mut self, speed: f32, surface: SurfaceType, dt: f32) -> f32 {
+        let speed_factor = (speed / VIBRATION_FULL_SPEED).min(1.0);
+
+        match surface {
+            SurfaceType::Kerb if self.effects.kerbs => {
+                self.kerb_phase = (self.kerb_phase + speed * dt / KERB_STRIPE_LENGTH).fract();
+                (self.kerb_phase * std::f32::consts::TAU).sin() * KERB_AMPLITUDE * speed_factor
+            }
+            SurfaceType::Grass if self.effects.off_track => {
+                self.next_noise() * GRASS_AMPLITUDE * speed_factor
+            }
+            SurfaceType::Gravel if self.effects.off_track => {
+                self.next_noise() * GRAVEL_AMPLITUDE * speed_factor
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Decaying kick from an abrupt change in velocity
+    fn collision_jolt(&mut self, car: &CarPhysics, dt: f32) -> f32 {
+        let velocity = car.body.velocity;
+        let prev_velocity = self.prev_velocity.replace(velocity);
+
+        if dt <= 0.0 {
+            return self.jolt;
+        }
+
+        self.jolt *= (-dt / JOLT_DECAY).exp();
+
+        if let Some(prev) = prev_velocity {
+            let change = velocity - prev;
+            let acceleration = change.length() / dt;
+            if self.effects.collisions && acceleration > JOLT_THRESHOLD {
+                // Kick the wheel towards the side the impact pushed the car
+                let left = car.body.orientation * Vec3::NEG_Z;
+                let side = if change.dot(left) >= 0.0 { 1.0 } else { -1.0 };
+                let magnitude = ((acceleration - JOLT_THRESHOLD) / JOLT_FULL_SCALE).min(1.0);
+                self.jolt = side * magnitude;
+            }
+        }
+
+        self.jolt
+    }
+
+    /// Next sample of the off-track noise (-1.0 to 1.0)
+    fn next_noise(&mut self) -> f32 {
+        // xorshift32: cheap and deterministic, which is all a rumble needs
+        self.noise_state ^= self.noise_state << 13;
+        self.noise_state ^= self.noise_state >> 17;
+        self.noise_state ^= self.noise_state << 5;
+        (self.noise_state as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+/// First-order low-pass filter for the output signal
+#[derive(Debug, Clone)]
+pub struct FfbSmoother {
+    /// Filter time constant (seconds); zero passes the input straight through
+    time_constant: f32,
+    /// Current filtered value
+    value: f32,
+}
+
+impl FfbSmoother {
+    /// Create a smoother with the given time constant in seconds
+    pub fn new(time_constant: f32) -> Self {
+        Self {
+            time_constant: time_constant.max(0.0),
+            value: 0.0,
+        }
+    }
+
+    /// Filter one sample
+    pub fn step(&mut self, target: f32, dt: f32) -> f32 {
+        if self.time_constant <= 0.0 {
+            self.value = target;
+        } else {
+            let alpha = 1.0 - (-dt.max(0.0) / self.time_constant).exp();
+            self.value += (target - self.value) * alpha;
+        }
+        self.value
+    }
+
+    /// Current filtered value
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+/// Detects when the requested torque exceeds the wheel's range
+///
+/// The indicator is held for [`CLIP_HOLD`] after the last clipped sample so a
+/// brief spike is still visible in the HUD.
+#[derive(Debug, Clone, Default)]
+pub struct ClippingDetector {
+    /// Time left before the indicator turns off
+    hold_remaining: f32,
+}
+
+impl ClippingDetector {
+    /// Create a detector with the indicator off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one sample and return whether the indicator is lit
+    pub fn update(&mut self, signal: f32, dt: f32) -> bool {
+        if signal.abs() > CLIP_LEVEL {
+            self.hold_remaining = CLIP_HOLD;
+        } else {
+            self.hold_remaining = (self.hold_remaining - dt).max(0.0);
+        }
+        self.is_clipping()
+    }
+
+    /// Whether the indicator is lit
+    pub fn is_clipping(&self) -> bool {
+        self.hold_remaining > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::car::CarDatabase;
+    use crate::physics::BodyId;
+    use glam::Quat;
+
+    fn test_car(velocity: Vec3, steering: f32) -> CarPhysics {
+        let spec = CarDatabase::create_sample().cars().next().unwrap().clone();
+        let mut car = CarPhysics::new(BodyId(0), spec, Vec3::ZERO);
+        car.body.velocity = velocity;
+        car.speed = velocity.length();
+        car.steering = steering;
+        car
+    }
+
+    #[test]
+    fn test_aligning_torque_sign_flips_across_peak() {
+        let peak = peak_slip_angle();
+        assert!(peak > 0.0);
+
+        // Below the peak the wheel pulls back towards centre
+        assert!(aligning_torque(peak * 0.5) > 0.0);
+        assert!(aligning_torque(-peak * 0.5) < 0.0);
+
+        // Past the peak it reverses and goes light
+        assert!(aligning_torque(peak * 1.5) < 0.0);
+        assert!(aligning_torque(-peak * 1.5) > 0.0);
+
+        // The lateral force itself is still positive past the peak
+        assert!(lateral_force(peak * 1.5) > 0.0);
+        assert!(lateral_force(peak) >= lateral_force(peak * 1.5));
+    }
+
+    #[test]
+    fn test_model_torque_flips_with_steering_past_peak() {
+        let peak = peak_slip_angle();
+        let velocity = Vec3::new(ALIGNING_FULL_SPEED, 0.0, 0.0);
+        let mut model = ForceFeedbackModel::default();
+
+        let below = test_car(velocity, peak * 0.5 / MAX_STEERING_ANGLE);
+        assert!(model.update(&below, SurfaceType::Track, 1.0 / 60.0) > 0.0);
+
+        let above = test_car(velocity, peak * 1.5 / MAX_STEERING_ANGLE);
+        assert!(model.update(&above, SurfaceType::Track, 1.0 / 60.0) < 0.0);
+    }
+
+    #[test]
+    fn test_slip_angle_follows_heading() {
+        // Car yawed left of its direction of travel with the wheels straight
+        let mut car = test_car(Vec3::new(30.0, 0.0, 0.0), 0.0);
+        car.body.orientation = Quat::from_rotation_y(0.1);
+        assert!((front_slip_angle(&car) - 0.1).abs() < 1e-4);
+
+        car.body.velocity = Vec3::ZERO;
+        assert_eq!(front_slip_angle(&car), 0.0);
+    }
+
+    #[test]
+    fn test_disabled_effects_are_silent() {
+        let effects = FfbEffects {
+            self_aligning: false,
+            kerbs: false,
+            off_track: false,
+            collisions: false,
+        };
+        let mut model = ForceFeedbackModel::new(effects, 1.0);
+        let car = test_car(Vec3::new(40.0, 0.0, 0.0), 0.3);
+
+        for surface in [SurfaceType::Kerb, SurfaceType::Gravel] {
+            assert_eq!(model.update(&car, surface, 1.0 / 60.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_kerb_vibration() {
+        let effects = FfbEffects {
+            self_aligning: false,
+            ..FfbEffects::default()
+        };
+        let mut model = ForceFeedbackModel::new(effects, 1.0);
+        let car = test_car(Vec3::new(VIBRATION_FULL_SPEED, 0.0, 0.0), 0.0);
+
+        let samples: Vec<f32> = (0..30)
+            .map(|_| model.update(&car, SurfaceType::Kerb, 1.0 / 60.0))
+            .collect();
+        assert!(samples.iter().any(|&s| s > 0.1));
+        assert!(samples.iter().any(|&s| s < -0.1));
+        assert!(samples.iter().all(|s| s.abs() <= KERB_AMPLITUDE));
+    }
+
+    #[test]
+    fn test_collision_jolt_decays() {
+        let effects = FfbEffects {
+            self_aligning: false,
+            ..FfbEffects::default()
+        };
+        let mut model = ForceFeedbackModel::new(effects, 1.0);
+        let dt = 1.0 / 60.0;
+
+        let mut car = test_car(Vec3::new(40.0, 0.0, 0.0), 0.0);
+        assert_eq!(model.update(&car, SurfaceType::Track, dt), 0.0);
+
+        // Knocked sideways and nearly stopped in one tick
+        car.body.velocity = Vec3::new(5.0, 0.0, -5.0);
+        let jolt = model.update(&car, SurfaceType::Track, dt);
+        assert!(jolt > 0.5);
+
+        let later = model.update(&car, SurfaceType::Track, dt);
+        assert!(later > 0.0 && later < jolt);
+
+        model.reset();
+        car.body.velocity = Vec3::ZERO;
+        assert_eq!(model.update(&car, SurfaceType::Track, dt), 0.0);
+    }
+
+    #[test]
+    fn test_smoother() {
+        let mut smoother = FfbSmoother::new(0.05);
+        let first = smoother.step(1.0, 0.01);
+        assert!(first > 0.0 && first < 1.0);
+        for _ in 0..100 {
+            smoother.step(1.0, 0.01);
+        }
+        assert!((smoother.value() - 1.0).abs() < 1e-3);
+
+        let mut passthrough = FfbSmoother::new(0.0);
+        assert_eq!(passthrough.step(0.7, 0.01), 0.7);
+    }
+
+    #[test]
+    fn test_clipping_detector_triggers_on_over_strength_signal() {
+        let mut detector = ClippingDetector::new();
+        let dt = 1.0 / 200.0;
+
+        assert!(!detector.update(0.8, dt));
+        assert!(!detector.update(-CLIP_LEVEL, dt));
+
+        // A strong effect pushed past the wheel's range
+        let mut model = ForceFeedbackModel::new(FfbEffects::default(), 3.0);
+        let peak = peak_slip_angle();
+        let car = test_car(
+            Vec3::new(ALIGNING_FULL_SPEED, 0.0, 0.0),
+            peak * 0.4 / MAX_STEERING_ANGLE,
+        );
+        let signal = model.update(&car, SurfaceType::Track, 1.0 / 60.0);
+        assert!(detector.update(signal, dt));
+
+        // Held briefly, then released
+        assert!(detector.update(0.0, CLIP_HOLD * 0.5));
+        assert!(!detector.update(0.0, CLIP_HOLD));
+    }
+}
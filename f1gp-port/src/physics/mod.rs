@@ -5,7 +5,11 @@
 pub mod car;
 pub mod collision;
 pub mod engine;
+pub mod force_feedback;
 
-pub use car::{CarPhysics, TireGrip};
+pub use car::{CarPhysics, TireGrip, DEFAULT_GEAR_RATIOS};
 pub use collision::{CollisionResult, SurfacePhysics, TrackCollision};
 pub use engine::{BodyId, PhysicsBody, PhysicsWorld, PHYSICS_TIMESTEP};
+pub use force_feedback::{
+    ClippingDetector, FfbEffects, FfbSmoother, FfbStatus, ForceFeedbackModel, CLIP_LEVEL,
+};
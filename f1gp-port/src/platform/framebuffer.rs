@@ -0,0 +1,404 @@
+//! Offscreen software renderer
+//!
+//! Draws into an RGBA pixel buffer in memory instead of a window, so frames
+//! can be rendered headlessly and hashed in regression tests.
+
+use super::graphics::{get_char_pattern, Color, Rect, Renderer};
+use anyhow::Result;
+use glam::Vec2;
+
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// In-memory RGBA framebuffer implementing [`Renderer`]
+#[derive(Debug, Clone)]
+pub struct FrameBuffer {
+    width: u32,
+    height: u32,
+
+    /// RGBA8 pixels, rows top to bottom
+    pixels: Vec<u8>,
+}
+
+impl FrameBuffer {
+    /// Create a black framebuffer
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// RGBA8 pixels, rows top to bottom
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Color of one pixel, `None` outside the buffer
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let p = &self.pixels[i..i + 4];
+        Some(Color::rgba(p[0], p[1], p[2], p[3]))
+    }
+
+    /// Change the size, clearing the buffer to black
+    pub fn resize(&mut self, width: u32, height: u32) {
+        *self = Self::new(width, height);
+    }
+
+    /// FNV-1a hash of the size and pixels, for spotting rendering changes
+    pub fn checksum(&self) -> u64 {
+        let size = [self.width.to_le_bytes(), self.height.to_le_bytes()];
+        size.iter()
+            .flatten()
+            .chain(&self.pixels)
+            .fold(FNV_OFFSET, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Blend one pixel over the buffer, ignoring pixels outside it
+    fn blend(&mut self, x: i32, y: i32, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 || color.a == 0 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let dst = &mut self.pixels[i..i + 4];
+        if color.a == u8::MAX {
+            dst.copy_from_slice(&[color.r, color.g, color.b, u8::MAX]);
+            return;
+        }
+
+        let alpha = color.a as u32;
+        for (channel, src) in dst.iter_mut().zip([color.r, color.g, color.b]) {
+            *channel = ((src as u32 * alpha + *channel as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+        dst[3] = (alpha + dst[3] as u32 * (255 - alpha) / 255) as u8;
+    }
+
+    /// Blend the pixels from `x0` to `x1` inclusive on row `y`
+    fn fill_span(&mut self, x0: i32, x1: i32, y: i32, color: Color) {
+        if y < 0 || y >= self.height as i32 {
+            return;
+        }
+        for x in x0.max(0)..=x1.min(self.width as i32 - 1) {
+            self.blend(x, y, color);
+        }
+    }
+
+    /// Rows of the buffer from `y0` to `y1` inclusive
+    fn rows(&self, y0: i32, y1: i32) -> std::ops::RangeInclusive<i32> {
+        y0.max(0)..=y1.min(self.height as i32 - 1)
+    }
+
+    /// Clip a segment to the buffer (Liang-Barsky), `None` when it misses
+    fn clip_line(&self, start: Vec2, end: Vec2) -> Option<(Vec2, Vec2)> {
+        if !start.is_finite() || !end.is_finite() {
+            return None;
+        }
+
+        let min = Vec2::splat(-1.0);
+        let max = Vec2::new(self.width as f32, self.height as f32);
+        let delta = end - start;
+        let (mut t0, mut t1) = (0.0f32, 1.0f32);
+        for (p, q) in [
+            (-delta.x, start.x - min.x),
+            (delta.x, max.x - start.x),
+            (-delta.y, start.y - min.y),
+            (delta.y, max.y - start.y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < 0.0 {
+                if t > t1 {
+                    return None;
+                }
+                t0 = t0.max(t);
+            } else {
+                if t < t0 {
+                    return None;
+                }
+                t1 = t1.min(t);
+            }
+        }
+
+        Some((start + delta * t0, start + delta * t1))
+    }
+}
+
+impl Renderer for FrameBuffer {
+    fn clear(&mut self, color: Color) {
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn present(&mut self) {}
+
+    fn draw_line(&mut self, start: Vec2, end: Vec2, color: Color) -> Result<()> {
+        let Some((start, end)) = self.clip_line(start, end) else {
+            return Ok(());
+        };
+        let (mut x, mut y) = (start.x as i32, start.y as i32);
+        let (x1, y1) = (end.x as i32, end.y as i32);
+        if y == y1 {
+            self.fill_span(x.min(x1), x.max(x1), y, color);
+            return Ok(());
+        }
+
+        // Bresenham
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let step_x = if x < x1 { 1 } else { -1 };
+        let step_y = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.blend(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_rect(&mut self, rect: Rect, color: Color) -> Result<()> {
+        let (x0, y0) = (rect.x as i32, rect.y as i32);
+        let x1 = x0.saturating_add(rect.width as i32) - 1;
+        let y1 = y0.saturating_add(rect.height as i32) - 1;
+        if x1 < x0 || y1 < y0 {
+            return Ok(());
+        }
+
+        self.fill_span(x0, x1, y0, color);
+        if y1 > y0 {
+            self.fill_span(x0, x1, y1, color);
+        }
+        for y in self.rows(y0.saturating_add(1), y1 - 1) {
+            self.blend(x0, y, color);
+            if x1 > x0 {
+                self.blend(x1, y, color);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_filled_rect(&mut self, rect: Rect, color: Color) -> Result<()> {
+        let (x0, y0) = (rect.x as i32, rect.y as i32);
+        let x1 = x0.saturating_add(rect.width as i32) - 1;
+        let y1 = y0.saturating_add(rect.height as i32) - 1;
+        for y in self.rows(y0, y1) {
+            self.fill_span(x0, x1, y, color);
+        }
+        Ok(())
+    }
+
+    fn draw_circle(&mut self, center: Vec2, radius: f32, color: Color) -> Result<()> {
+        let (cx, cy, r) = (center.x as i32, center.y as i32, radius as i32);
+        if r <= 0 {
+            self.blend(cx, cy, color);
+            return Ok(());
+        }
+
+        // Midpoint circle, one octant mirrored eight ways
+        let (mut x, mut y, mut err) = (r, 0, 1 - r);
+        while x >= y {
+            for (px, py) in [
+                (x, y),
+                (y, x),
+                (-y, x),
+                (-x, y),
+                (-x, -y),
+                (-y, -x),
+                (y, -x),
+                (x, -y),
+            ] {
+                self.blend(cx + px, cy + py, color);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_filled_circle(&mut self, center: Vec2, radius: f32, color: Color) -> Result<()> {
+        let (cx, cy, r) = (center.x as i32, center.y as i32, radius.max(0.0) as i32);
+        for y in self.rows(cy - r, cy + r) {
+            let dy = y - cy;
+            let half_width = ((r * r - dy * dy) as f32).sqrt() as i32;
+            self.fill_span(cx - half_width, cx + half_width, y, color);
+        }
+        Ok(())
+    }
+
+    fn draw_text(&mut self, text: &str, position: Vec2, size: f32, color: Color) -> Result<()> {
+        // Same 5x7 glyphs and spacing as the SDL renderer
+        let pixel_size = size / 7.0;
+        let advance = size * 0.8;
+
+        for (i, ch) in text.chars().enumerate() {
+            let origin = Vec2::new(position.x + i as f32 * advance, position.y);
+            for (y, row) in get_char_pattern(ch).iter().enumerate() {
+                for (x, &pixel) in row.iter().enumerate() {
+                    if pixel {
+                        let rect = Rect::new(
+                            (origin.x + x as f32 * pixel_size).trunc(),
+                            (origin.y + y as f32 * pixel_size).trunc(),
+                            pixel_size.ceil(),
+                            pixel_size.ceil(),
+                        );
+                        self.draw_filled_rect(rect, color)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn viewport_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn draw_rgba_region(
+        &mut self,
+        _cache_key: &str,
+        pixels: &[u8],
+        image_width: u32,
+        image_height: u32,
+        src: Rect,
+        dst: Rect,
+        angle_deg: f32,
+    ) -> Result<()> {
+        if dst.width <= 0.0 || dst.height <= 0.0 || src.width <= 0.0 || src.height <= 0.0 {
+            return Ok(());
+        }
+
+        // Rotated clockwise about the destination centre, as SDL does
+        let center = dst.center();
+        let half = Vec2::new(dst.width, dst.height) / 2.0;
+        let (sin, cos) = angle_deg.to_radians().sin_cos();
+        let extent = Vec2::new(
+            half.x * cos.abs() + half.y * sin.abs(),
+            half.x * sin.abs() + half.y * cos.abs(),
+        );
+        let (min, max) = (center - extent, center + extent);
+        let columns = min.x.floor().max(0.0) as i32..max.x.ceil().min(self.width as f32) as i32;
+
+        for y in self.rows(min.y.floor() as i32, max.y.ceil() as i32 - 1) {
+            for x in columns.clone() {
+                // Nearest source texel under this pixel's centre
+                let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - center;
+                let local = Vec2::new(
+                    offset.x * cos + offset.y * sin,
+                    offset.y * cos - offset.x * sin,
+                ) + half;
+                if local.x < 0.0 || local.y < 0.0 || local.x >= dst.width || local.y >= dst.height {
+                    continue;
+                }
+                let texel_x = (src.x + local.x * src.width / dst.width) as u32;
+                let texel_y = (src.y + local.y * src.height / dst.height) as u32;
+                if texel_x >= image_width || texel_y >= image_height {
+                    continue;
+                }
+                let i = (texel_y as usize * image_width as usize + texel_x as usize) * 4;
+                if let Some(texel) = pixels.get(i..i + 4) {
+                    self.blend(x, y, Color::rgba(texel[0], texel[1], texel[2], texel[3]));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filled_triangle_covers_interior() {
+        let mut buffer = FrameBuffer::new(16, 16);
+        buffer.clear(Color::BLACK);
+        buffer
+            .draw_filled_triangle(
+                [
+                    Vec2::new(2.0, 2.0),
+                    Vec2::new(13.0, 2.0),
+                    Vec2::new(2.0, 13.0),
+                ],
+                Color::RED,
+            )
+            .unwrap();
+
+        assert_eq!(buffer.pixel(4, 4), Some(Color::RED));
+        assert_eq!(buffer.pixel(12, 12), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn test_lines_are_clipped_to_the_buffer() {
+        let mut buffer = FrameBuffer::new(16, 16);
+        buffer.clear(Color::BLACK);
+        buffer
+            .draw_line(Vec2::splat(-1.0e5), Vec2::splat(1.0e5), Color::WHITE)
+            .unwrap();
+
+        assert_eq!(buffer.pixel(5, 5), Some(Color::WHITE));
+        assert_eq!(buffer.pixel(5, 6), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn test_alpha_blending() {
+        let mut buffer = FrameBuffer::new(1, 1);
+        buffer.clear(Color::BLACK);
+        buffer
+            .draw_filled_rect(
+                Rect::new(0.0, 0.0, 1.0, 1.0),
+                Color::rgba(255, 255, 255, 128),
+            )
+            .unwrap();
+
+        assert_eq!(buffer.pixel(0, 0), Some(Color::rgb(128, 128, 128)));
+    }
+
+    #[test]
+    fn test_checksum_follows_pixels() {
+        let mut buffer = FrameBuffer::new(32, 16);
+        let blank = buffer.checksum();
+        assert_eq!(blank, FrameBuffer::new(32, 16).checksum());
+        assert_ne!(blank, FrameBuffer::new(16, 32).checksum());
+
+        buffer
+            .draw_text("LAP 1", Vec2::new(1.0, 1.0), 7.0, Color::WHITE)
+            .unwrap();
+        assert_ne!(buffer.checksum(), blank);
+    }
+}
@@ -130,6 +130,43 @@ pub trait Renderer {
         dst: Rect,
         angle_deg: f32,
     ) -> Result<()>;
+
+    /// Draw a filled triangle, one horizontal line per pixel row
+    fn draw_filled_triangle(&mut self, points: [Vec2; 3], color: Color) -> Result<()> {
+        let mut points = points;
+        points.sort_by(|a, b| a.y.total_cmp(&b.y));
+        let [top, middle, bottom] = points;
+
+        let (_, height) = self.viewport_size();
+        let first_row = top.y.ceil().max(0.0) as i32;
+        let last_row = bottom.y.floor().min(height as f32 - 1.0) as i32;
+
+        for row in first_row..=last_row {
+            let y = row as f32;
+            let long_x = edge_x(top, bottom, y);
+            let short_x = if y < middle.y {
+                edge_x(top, middle, y)
+            } else {
+                edge_x(middle, bottom, y)
+            };
+            self.draw_line(
+                Vec2::new(long_x.min(short_x), y),
+                Vec2::new(long_x.max(short_x), y),
+                color,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// X coordinate where the edge from `a` to `b` crosses row `y`
+fn edge_x(a: Vec2, b: Vec2, y: f32) -> f32 {
+    if (b.y - a.y).abs() < f32::EPSILON {
+        a.x
+    } else {
+        a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y)
+    }
 }
 
 /// SDL2-based renderer implementation
@@ -458,7 +495,7 @@ fn draw_char(
 
 /// Get 5x7 bitmap pattern for a character
 /// Returns a 7-row array, each row has 5 pixels
-fn get_char_pattern(ch: char) -> [[bool; 5]; 7] {
+pub(crate) fn get_char_pattern(ch: char) -> [[bool; 5]; 7] {
     match ch {
         '0' => [
             [true, true, true, true, false],
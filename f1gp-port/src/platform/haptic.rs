@@ -0,0 +1,277 @@
+//! Steering wheel force feedback output
+//!
+//! The game submits a target torque each physics tick; a dedicated thread
+//! smooths it, watches for clipping and drives the wheel at a fixed rate so
+//! device latency never stalls the frame loop.
+
+use crate::physics::{ClippingDetector, FfbSmoother, FfbStatus, CLIP_LEVEL};
+use anyhow::{anyhow, Result};
+use sdl2::sys;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Rate at which the output thread updates the wheel
+const OUTPUT_RATE_HZ: u64 = 200;
+
+/// A device that can apply a constant steering torque
+pub trait ForceFeedbackDevice: Send {
+    /// Apply a torque (-1.0 to 1.0, positive pulls the wheel left)
+    fn set_force(&mut self, level: f32) -> Result<()>;
+
+    /// Release the wheel
+    fn stop(&mut self);
+}
+
+/// Target and status shared between the game and output thread
+#[derive(Debug, Default)]
+struct FfbShared {
+    /// Latest torque requested by the physics
+    target: f32,
+    /// Latest output state
+    status: FfbStatus,
+}
+
+/// Background force feedback output
+pub struct ForceFeedbackOutput {
+    shared: Arc<Mutex<FfbShared>>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ForceFeedbackOutput {
+    /// Start the output thread for a device
+    pub fn spawn(mut device: Box<dyn ForceFeedbackDevice>, smoothing_secs: f32) -> Result<Self> {
+        let shared = Arc::new(Mutex::new(FfbShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_shared = Arc::clone(&shared);
+        let thread_running = Arc::clone(&running);
+        let handle = thread::Builder::new()
+            .name("force-feedback".to_string())
+            .spawn(move || {
+                let period = Duration::from_micros(1_000_000 / OUTPUT_RATE_HZ);
+                let mut smoother = FfbSmoother::new(smoothing_secs);
+                let mut clipping = ClippingDetector::new();
+                let mut last_tick = Instant::now();
+                let mut reported_error = false;
+
+                while thread_running.load(Ordering::Acquire) {
+                    let dt = last_tick.elapsed().as_secs_f32();
+                    last_tick = Instant::now();
+
+                    let target = thread_shared.lock().unwrap().target;
+                    let smoothed = smoother.step(target, dt);
+                    let clipped = clipping.update(smoothed, dt);
+                    let level = smoothed.clamp(-CLIP_LEVEL, CLIP_LEVEL);
+
+                    if let Err(err) = device.set_force(level) {
+                        if !reported_error {
+                            log::warn!("Force feedback output failed: {}", err);
+                            reported_error = true;
+                        }
+                    }
+
+                    thread_shared.lock().unwrap().status = FfbStatus {
+                        level,
+                        clipping: clipped,
+                    };
+
+                    thread::sleep(period);
+                }
+
+                device.stop();
+            })?;
+
+        Ok(Self {
+            shared,
+            running,
+            handle: Some(handle),
+        })
+    }
+
+    /// Set the torque the wheel should move towards
+    pub fn submit(&self, torque: f32) {
+        self.shared.lock().unwrap().target = torque;
+    }
+
+    /// Latest output level and clipping state
+    pub fn status(&self) -> FfbStatus {
+        self.shared.lock().unwrap().status
+    }
+}
+
+impl Drop for ForceFeedbackOutput {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Wheel driven through an SDL haptic constant-force effect
+///
+/// The safe `sdl2` haptic wrapper only exposes rumble, so this talks to the
+/// SDL haptic API directly. The haptic subsystem must stay initialized for
+/// the lifetime of the device.
+pub struct SdlHapticDevice {
+    haptic: *mut sys::SDL_Haptic,
+    effect_id: i32,
+    effect: sys::SDL_HapticEffect,
+    running: bool,
+}
+
+// SAFETY: the device is owned by exactly one thread at a time (the output
+// thread after `spawn`), and SDL's haptic calls are not tied to the thread
+// that opened the device.
+unsafe impl Send for SdlHapticDevice {}
+
+impl SdlHapticDevice {
+    /// Open the first haptic device that supports a constant force
+    pub fn open_first() -> Result<Self> {
+        // SAFETY: plain SDL calls; every pointer returned is checked before use
+        // and the device is closed again on any failure after opening.
+        unsafe {
+            let count = sys::SDL_NumHaptics();
+            for index in 0..count.max(0) {
+                let haptic = sys::SDL_HapticOpen(index);
+                if haptic.is_null() {
+                    continue;
+                }
+
+                if sys::SDL_HapticQuery(haptic) & sys::SDL_HAPTIC_CONSTANT == 0 {
+                    sys::SDL_HapticClose(haptic);
+                    continue;
+                }
+
+                let mut effect: sys::SDL_HapticEffect = std::mem::zeroed();
+                effect.constant.type_ = sys::SDL_HAPTIC_CONSTANT as u16;
+                effect.constant.direction.type_ = sys::SDL_HAPTIC_CARTESIAN as u8;
+                effect.constant.direction.dir[0] = 1;
+                effect.constant.length = sys::SDL_HAPTIC_INFINITY;
+
+                let effect_id = sys::SDL_HapticNewEffect(haptic, &mut effect);
+                if effect_id < 0 {
+                    sys::SDL_HapticClose(haptic);
+                    continue;
+                }
+
+                let name = sys::SDL_HapticName(index);
+                if !name.is_null() {
+                    log::info!(
+                        "Force feedback device: {}",
+                        CStr::from_ptr(name).to_string_lossy()
+                    );
+                }
+
+                return Ok(Self {
+                    haptic,
+                    effect_id,
+                    effect,
+                    running: false,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "No force feedback device with constant force support"
+        ))
+    }
+}
+
+impl ForceFeedbackDevice for SdlHapticDevice {
+    fn set_force(&mut self, level: f32) -> Result<()> {
+        // SAFETY: `haptic` and `effect_id` stay valid until `drop`, and the
+        // effect was created as a constant force so `constant` is the active
+        // union member.
+        unsafe {
+            self.effect.constant.level = (level.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            if sys::SDL_HapticUpdateEffect(self.haptic, self.effect_id, &mut self.effect) < 0 {
+                return Err(anyhow!(
+                    "SDL_HapticUpdateEffect failed: {}",
+                    CStr::from_ptr(sys::SDL_GetError()).to_string_lossy()
+                ));
+            }
+            if !self.running {
+                if sys::SDL_HapticRunEffect(self.haptic, self.effect_id, 1) < 0 {
+                    return Err(anyhow!(
+                        "SDL_HapticRunEffect failed: {}",
+                        CStr::from_ptr(sys::SDL_GetError()).to_string_lossy()
+                    ));
+                }
+                self.running = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if self.running {
+            // SAFETY: the device is still open (closed only in `drop`)
+            unsafe {
+                sys::SDL_HapticStopEffect(self.haptic, self.effect_id);
+            }
+            self.running = false;
+        }
+    }
+}
+
+impl Drop for SdlHapticDevice {
+    fn drop(&mut self) {
+        self.stop();
+        // SAFETY: the effect and device were created in `open_first` and are
+        // released exactly once here.
+        unsafe {
+            sys::SDL_HapticDestroyEffect(self.haptic, self.effect_id);
+            sys::SDL_HapticClose(self.haptic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Device that records the last level it was given
+    struct RecordingDevice {
+        last: Arc<Mutex<Option<f32>>>,
+    }
+
+    impl ForceFeedbackDevice for RecordingDevice {
+        fn set_force(&mut self, level: f32) -> Result<()> {
+            *self.last.lock().unwrap() = Some(level);
+            Ok(())
+        }
+
+        fn stop(&mut self) {
+            *self.last.lock().unwrap() = None;
+        }
+    }
+
+    #[test]
+    fn test_output_thread_clamps_and_reports_clipping() {
+        let last = Arc::new(Mutex::new(None));
+        let device = RecordingDevice {
+            last: Arc::clone(&last),
+        };
+        let output = ForceFeedbackOutput::spawn(Box::new(device), 0.0).unwrap();
+        output.submit(2.5);
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !output.status().clipping && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let status = output.status();
+        assert!(status.clipping);
+        assert_eq!(status.level, CLIP_LEVEL);
+        assert_eq!(*last.lock().unwrap(), Some(CLIP_LEVEL));
+
+        // Dropping the output releases the wheel
+        drop(output);
+        assert_eq!(*last.lock().unwrap(), None);
+    }
+}
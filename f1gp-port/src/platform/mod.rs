@@ -1,7 +1,11 @@
 //! Platform module
 //!
-//! Platform-specific abstractions for graphics, input, and windowing.
+//! Platform-specific abstractions for graphics, input, windowing, and force feedback.
 
+pub mod framebuffer;
 pub mod graphics;
+pub mod haptic;
 
+pub use framebuffer::FrameBuffer;
 pub use graphics::{Color, Rect, Renderer, SdlRenderer};
+pub use haptic::{ForceFeedbackDevice, ForceFeedbackOutput, SdlHapticDevice};
@@ -2,7 +2,11 @@
 //!
 //! Renders race information overlay including lap times, speed, gear, RPM, etc.
 
+use crate::data::annotations::TrackAnnotation;
+use crate::game::practice::MicroSectorRow;
+use crate::game::time_scale::TimeScale;
 use crate::game::weather::WeatherCondition;
+use crate::physics::FfbStatus;
 use crate::platform::{Color, Rect, Renderer};
 use anyhow::Result;
 use glam::Vec2;
@@ -34,6 +38,10 @@ pub struct Telemetry {
     pub on_track: bool,
     /// Current weather condition
     pub weather_condition: WeatherCondition,
+    /// Steering wheel force feedback output, if a wheel is connected
+    pub force_feedback: Option<FfbStatus>,
+    /// Active simulation speed
+    pub time_scale: TimeScale,
 }
 
 impl Hud {
@@ -65,6 +73,11 @@ impl Hud {
         // Draw status indicators
         self.draw_status_indicators(renderer, telemetry)?;
 
+        // Draw force feedback meter
+        if let Some(status) = telemetry.force_feedback {
+            self.draw_force_feedback_meter(renderer, status)?;
+        }
+
         Ok(())
     }
 
@@ -264,6 +277,28 @@ impl Hud {
             weather_color,
         )?;
 
+        // Simulation speed, highlighted when running faster than real time
+        let scale_y = weather_y + weather_height + 5.0;
+        let scale_color = if telemetry.time_scale == TimeScale::Normal {
+            Color::rgb(200, 200, 200)
+        } else {
+            Color::rgb(255, 255, 0)
+        };
+        renderer.draw_filled_rect(
+            Rect::new(weather_x, scale_y, weather_width, 30.0),
+            Color::rgba(0, 0, 0, 180),
+        )?;
+        renderer.draw_rect(
+            Rect::new(weather_x, scale_y, weather_width, 30.0),
+            scale_color,
+        )?;
+        renderer.draw_text(
+            &format!("SPEED {}", telemetry.time_scale.label()),
+            Vec2::new(weather_x + 10.0, scale_y + 5.0),
+            18.0,
+            scale_color,
+        )?;
+
         // Off-track warning
         if !telemetry.on_track {
             let warning_x = self.screen_width as f32 / 2.0 - 80.0;
@@ -284,6 +319,201 @@ impl Hud {
 
         Ok(())
     }
+
+    /// Draw the progress bar shown while a session is simulated to its end
+    pub fn render_fast_forward(&self, renderer: &mut dyn Renderer, progress: f32) -> Result<()> {
+        let bar_width = 400.0;
+        let bar_height = 24.0;
+        let bar_x = self.screen_width as f32 / 2.0 - bar_width / 2.0;
+        let bar_y = self.screen_height as f32 / 2.0;
+        let progress = progress.clamp(0.0, 1.0);
+
+        renderer.draw_text(
+            "SIMULATING TO SESSION END",
+            Vec2::new(bar_x, bar_y - 40.0),
+            24.0,
+            Color::rgb(255, 255, 255),
+        )?;
+
+        renderer.draw_filled_rect(
+            Rect::new(bar_x, bar_y, bar_width, bar_height),
+            Color::rgba(0, 0, 0, 180),
+        )?;
+        renderer.draw_filled_rect(
+            Rect::new(bar_x, bar_y, bar_width * progress, bar_height),
+            Color::rgb(0, 200, 255),
+        )?;
+        renderer.draw_rect(
+            Rect::new(bar_x, bar_y, bar_width, bar_height),
+            Color::rgb(255, 255, 255),
+        )?;
+
+        renderer.draw_text(
+            &format!("{:.0}%", progress * 100.0),
+            Vec2::new(bar_x + bar_width + 10.0, bar_y),
+            20.0,
+            Color::rgb(255, 255, 255),
+        )?;
+
+        Ok(())
+    }
+
+    /// Draw the name of the corner coming up, centred below the top edge
+    pub fn render_corner_callout(
+        &self,
+        renderer: &mut dyn Renderer,
+        corner: &TrackAnnotation,
+    ) -> Result<()> {
+        let name = corner.name.to_uppercase();
+        let note = corner.note.as_deref().map(str::to_uppercase);
+        let name_size = 28.0;
+        let note_size = 16.0;
+        let width = text_width(&name, name_size)
+            .max(note.as_deref().map_or(0.0, |n| text_width(n, note_size)))
+            + 30.0;
+        let height = if note.is_some() { 70.0 } else { 45.0 };
+        let x = self.screen_width as f32 / 2.0 - width / 2.0;
+        let y = 40.0;
+
+        renderer.draw_filled_rect(Rect::new(x, y, width, height), Color::rgba(0, 0, 0, 180))?;
+        renderer.draw_rect(Rect::new(x, y, width, height), Color::rgb(255, 255, 0))?;
+        renderer.draw_text(
+            &name,
+            Vec2::new(
+                self.screen_width as f32 / 2.0 - text_width(&name, name_size) / 2.0,
+                y + 8.0,
+            ),
+            name_size,
+            Color::rgb(255, 255, 0),
+        )?;
+        if let Some(note) = note {
+            renderer.draw_text(
+                &note,
+                Vec2::new(
+                    self.screen_width as f32 / 2.0 - text_width(&note, note_size) / 2.0,
+                    y + 45.0,
+                ),
+                note_size,
+                Color::rgb(200, 200, 200),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Draw the practice micro-sector table: time of each sector on the lap
+    /// in progress (greyed out until driven again) against the session best
+    pub fn render_micro_sectors(
+        &self,
+        renderer: &mut dyn Renderer,
+        rows: &[MicroSectorRow],
+    ) -> Result<()> {
+        let panel_x = 10.0;
+        let panel_y = 95.0;
+        let panel_width = 330.0;
+        let row_height = 20.0;
+        let panel_height = 30.0 + rows.len() as f32 * row_height;
+
+        let bg_rect = Rect::new(panel_x, panel_y, panel_width, panel_height);
+        renderer.draw_filled_rect(bg_rect, Color::rgba(0, 0, 0, 180))?;
+        renderer.draw_rect(bg_rect, Color::rgb(200, 200, 200))?;
+        renderer.draw_text(
+            "MICRO SECTORS",
+            Vec2::new(panel_x + 10.0, panel_y + 6.0),
+            16.0,
+            Color::rgb(255, 255, 0),
+        )?;
+
+        for (i, row) in rows.iter().enumerate() {
+            let y = panel_y + 30.0 + i as f32 * row_height;
+            let text_color = if row.current_lap {
+                Color::rgb(255, 255, 255)
+            } else {
+                Color::rgb(130, 130, 130)
+            };
+
+            let label: String = row.label.to_uppercase().chars().take(14).collect();
+            renderer.draw_text(&label, Vec2::new(panel_x + 10.0, y), 14.0, text_color)?;
+
+            let time = row.split.map_or_else(
+                || "--.---".to_string(),
+                |split| format!("{:.3}", split.time),
+            );
+            renderer.draw_text(&time, Vec2::new(panel_x + 175.0, y), 14.0, text_color)?;
+
+            // Delta to the session best, only for the lap in progress
+            if let Some(delta) = row.split.filter(|_| row.current_lap).and_then(|s| s.delta) {
+                let (delta_str, delta_color) = if delta > 0.0 {
+                    (format!("+{:.3}", delta), Color::rgb(255, 0, 0)) // Slower (red)
+                } else {
+                    (format!("{:.3}", delta), Color::rgb(0, 255, 0)) // Faster (green)
+                };
+                renderer.draw_text(&delta_str, Vec2::new(panel_x + 255.0, y), 14.0, delta_color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draw force feedback level meter with clipping warning
+    fn draw_force_feedback_meter(
+        &self,
+        renderer: &mut dyn Renderer,
+        status: FfbStatus,
+    ) -> Result<()> {
+        let meter_width = 160.0;
+        let meter_height = 12.0;
+        let meter_x = self.screen_width as f32 / 2.0 - meter_width / 2.0;
+        let meter_y = 10.0;
+
+        renderer.draw_filled_rect(
+            Rect::new(meter_x, meter_y, meter_width, meter_height),
+            Color::rgba(0, 0, 0, 180),
+        )?;
+
+        // Bar grows left or right from centre with the torque direction
+        let centre_x = meter_x + meter_width / 2.0;
+        let bar_width = status.level.clamp(-1.0, 1.0).abs() * meter_width / 2.0;
+        let bar_x = if status.level < 0.0 {
+            centre_x - bar_width
+        } else {
+            centre_x
+        };
+        let bar_color = if status.clipping {
+            Color::rgb(255, 0, 0)
+        } else {
+            Color::rgb(0, 200, 255)
+        };
+        renderer.draw_filled_rect(
+            Rect::new(bar_x, meter_y, bar_width, meter_height),
+            bar_color,
+        )?;
+
+        let border_color = if status.clipping {
+            Color::rgb(255, 0, 0)
+        } else {
+            Color::rgb(200, 200, 200)
+        };
+        renderer.draw_rect(
+            Rect::new(meter_x, meter_y, meter_width, meter_height),
+            border_color,
+        )?;
+
+        let label = if status.clipping { "FFB CLIP" } else { "FFB" };
+        renderer.draw_text(
+            label,
+            Vec2::new(meter_x + meter_width + 8.0, meter_y - 2.0),
+            16.0,
+            border_color,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Width of `text` as drawn by the renderer's 5x7 font at `size`
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.8
 }
 
 /// Format time in MM:SS.mmm format
@@ -319,6 +549,12 @@ mod tests {
         assert_eq!(format_time(125.456), "02:05.456");
     }
 
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width("", 20.0), 0.0);
+        assert_eq!(text_width("TABAC", 20.0), 80.0);
+    }
+
     #[test]
     fn test_telemetry_creation() {
         let telemetry = Telemetry {
@@ -331,6 +567,8 @@ mod tests {
             delta_time: Some(0.623),
             on_track: true,
             weather_condition: WeatherCondition::Dry,
+            force_feedback: None,
+            time_scale: TimeScale::Double,
         };
         assert_eq!(telemetry.speed, 250.0);
         assert_eq!(telemetry.gear, 5);
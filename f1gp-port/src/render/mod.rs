@@ -7,6 +7,7 @@ pub mod car_renderer;
 pub mod hud;
 pub mod particles;
 pub mod sprite_atlas;
+pub mod topdown;
 pub mod track_renderer;
 
 pub use camera::{Camera, CameraMode};
@@ -14,4 +15,5 @@ pub use car_renderer::{CarRenderer, CarState};
 pub use hud::{Hud, Telemetry};
 pub use particles::ParticleSystem;
 pub use sprite_atlas::{SpriteAtlas, SpriteFrame, SpriteSheet};
+pub use topdown::{TopDownRenderer, TopDownView};
 pub use track_renderer::TrackRenderer;
@@ -0,0 +1,329 @@
+//! Top-down 2D renderer
+//!
+//! Draws the race seen from above with plain 2D primitives: the track
+//! outline, each car as a triangle pointing where it faces, and the HUD
+//! text. It only needs a [`Renderer`], so machines without a GPU adapter can
+//! still play through SDL2, and tests can render into a
+//! [`FrameBuffer`](crate::platform::FrameBuffer).
+
+use crate::data::car::Livery;
+use crate::data::track::Track;
+use crate::game::GameState;
+use crate::physics::CarPhysics;
+use crate::platform::{Color, Rect, Renderer};
+use crate::render::{Camera, CameraMode, TrackRenderer};
+use crate::render3d::HudLine;
+use anyhow::Result;
+use glam::{Vec2, Vec3};
+
+/// Pixels per meter when following the player
+const FOLLOW_ZOOM: f32 = 4.0;
+
+/// Car body length (meters)
+const CAR_LENGTH: f32 = 4.5;
+
+/// Car body width (meters)
+const CAR_WIDTH: f32 = 2.0;
+
+/// Shortest a car is drawn (pixels), so cars stay visible zoomed out
+const MIN_CAR_LENGTH: f32 = 8.0;
+
+/// Height of HUD text at scale 1.0 (pixels)
+const HUD_TEXT_SIZE: f32 = 12.0;
+
+/// Height of the pause banner text (pixels)
+const PAUSED_TEXT_SIZE: f32 = 32.0;
+
+const GRASS: Color = Color::rgb(20, 80, 20);
+const ASPHALT: Color = Color::rgb(60, 60, 70);
+const CENTERLINE: Color = Color::rgb(110, 110, 120);
+
+/// What the top-down camera shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopDownView {
+    /// Centered on the player car
+    Follow,
+    /// The whole track
+    Overview,
+}
+
+impl TopDownView {
+    /// Name shown in the HUD
+    pub fn label(self) -> &'static str {
+        match self {
+            TopDownView::Follow => "Follow",
+            TopDownView::Overview => "Overview",
+        }
+    }
+}
+
+/// Renders a [`GameState`] from above onto any [`Renderer`]
+pub struct TopDownRenderer {
+    camera: Camera,
+    track: Option<TrackRenderer>,
+    view: TopDownView,
+    follow_zoom: f32,
+}
+
+impl TopDownRenderer {
+    /// Create a renderer following the player
+    pub fn new(viewport_width: u32, viewport_height: u32) -> Self {
+        let mut camera = Camera::new(viewport_width, viewport_height);
+        camera.mode = CameraMode::TopDown;
+
+        Self {
+            camera,
+            track: None,
+            view: TopDownView::Follow,
+            follow_zoom: FOLLOW_ZOOM,
+        }
+    }
+
+    /// Build the track outline to draw
+    pub fn load_track(&mut self, track: &Track) {
+        self.track = Some(TrackRenderer::new(track));
+    }
+
+    /// Current camera view
+    pub fn view(&self) -> TopDownView {
+        self.view
+    }
+
+    /// Switch between following the player and the whole track
+    pub fn cycle_view(&mut self) {
+        self.view = match self.view {
+            TopDownView::Follow => TopDownView::Overview,
+            TopDownView::Overview => TopDownView::Follow,
+        };
+    }
+
+    /// Follow the player again at the default zoom
+    pub fn reset_view(&mut self) {
+        self.view = TopDownView::Follow;
+        self.follow_zoom = FOLLOW_ZOOM;
+    }
+
+    /// Zoom the follow view by a factor
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.follow_zoom = (self.follow_zoom * factor).clamp(0.1, 10.0);
+    }
+
+    /// Draw one frame: track, cars, then the HUD lines on top
+    pub fn render(
+        &mut self,
+        target: &mut impl Renderer,
+        game: &GameState,
+        hud: &[HudLine],
+    ) -> Result<()> {
+        let (width, height) = target.viewport_size();
+        self.camera.set_viewport_size(width, height);
+        self.place_camera(game);
+
+        target.clear(GRASS);
+        self.draw_track(target)?;
+        self.draw_cars(target, game)?;
+        self.draw_hud(target, game, hud)?;
+
+        Ok(())
+    }
+
+    fn place_camera(&mut self, game: &GameState) {
+        match self.view {
+            TopDownView::Follow => {
+                let position = game.player_car().body.position;
+                self.camera.set_zoom(self.follow_zoom);
+                self.camera
+                    .center_on(Vec3::new(position.x, position.z, 0.0));
+            }
+            TopDownView::Overview => {
+                if let Some(track) = &self.track {
+                    self.camera.fit_bounds(track.bounds);
+                }
+            }
+        }
+    }
+
+    /// Screen position of a ground-plane (x, z) point
+    fn to_screen(&self, ground: Vec2) -> Vec2 {
+        self.camera
+            .world_to_screen(Vec3::new(ground.x, ground.y, 0.0))
+    }
+
+    fn draw_track(&self, target: &mut impl Renderer) -> Result<()> {
+        let Some(track) = &self.track else {
+            return Ok(());
+        };
+        let points: Vec<(Vec2, Vec2, Vec2)> = track
+            .outline()
+            .map(|(left, center, right)| {
+                (
+                    self.to_screen(left),
+                    self.to_screen(center),
+                    self.to_screen(right),
+                )
+            })
+            .collect();
+        if points.len() < 2 {
+            return Ok(());
+        }
+
+        let (width, height) = target.viewport_size();
+        let viewport = Rect::new(0.0, 0.0, width as f32, height as f32);
+        let visible: Vec<usize> = (0..points.len())
+            .filter(|&i| {
+                let (l1, _, r1) = points[i];
+                let (l2, _, r2) = points[(i + 1) % points.len()];
+                overlaps(&[l1, r1, l2, r2], viewport)
+            })
+            .collect();
+
+        // Road surface first, so no segment paints over a neighbour's lines
+        for &i in &visible {
+            let (l1, _, r1) = points[i];
+            let (l2, _, r2) = points[(i + 1) % points.len()];
+            target.draw_filled_triangle([l1, r1, l2], ASPHALT)?;
+            target.draw_filled_triangle([r1, r2, l2], ASPHALT)?;
+        }
+
+        for &i in &visible {
+            let (l1, c1, r1) = points[i];
+            let (l2, c2, r2) = points[(i + 1) % points.len()];
+            target.draw_line(l1, l2, Color::WHITE)?;
+            target.draw_line(r1, r2, Color::WHITE)?;
+            if i % 2 == 0 {
+                target.draw_line(c1, c2, CENTERLINE)?;
+            }
+        }
+
+        // Start/finish line across the first section
+        let (left, _, right) = points[0];
+        target.draw_line(left, right, Color::WHITE)?;
+
+        Ok(())
+    }
+
+    fn draw_cars(&self, target: &mut impl Renderer, game: &GameState) -> Result<()> {
+        let liveries = game.car_liveries();
+        let livery = |i: usize| {
+            liveries
+                .get(i)
+                .map(|(livery, _)| *livery)
+                .unwrap_or_default()
+        };
+
+        // Player last, so it is never hidden under an opponent
+        for (i, car) in game.ai_cars().iter().enumerate() {
+            self.draw_car(target, car, livery(i + 1))?;
+        }
+        self.draw_car(target, game.player_car(), livery(0))
+    }
+
+    /// A triangle pointing the way the car faces, in its livery colors
+    fn draw_car(&self, target: &mut impl Renderer, car: &CarPhysics, livery: Livery) -> Result<()> {
+        let position = car.body.position;
+        let forward = car.body.orientation * Vec3::X;
+
+        let center = self.to_screen(Vec2::new(position.x, position.z));
+        let ahead = self.to_screen(Vec2::new(position.x + forward.x, position.z + forward.z));
+        let heading = (ahead - center).try_normalize().unwrap_or(Vec2::X);
+        let side = heading.perp();
+
+        let length = (CAR_LENGTH * self.camera.zoom).max(MIN_CAR_LENGTH);
+        let half_width = length * CAR_WIDTH / CAR_LENGTH / 2.0;
+        let nose = center + heading * length / 2.0;
+        let tail = center - heading * length / 2.0;
+        let points = [nose, tail + side * half_width, tail - side * half_width];
+
+        let (r, g, b) = livery.primary;
+        target.draw_filled_triangle(points, Color::rgb(r, g, b))?;
+        let (r, g, b) = livery.secondary;
+        for (i, point) in points.iter().enumerate() {
+            let next = points[(i + 1) % points.len()];
+            target.draw_line(*point, next, Color::rgb(r, g, b))?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_hud(
+        &self,
+        target: &mut impl Renderer,
+        game: &GameState,
+        hud: &[HudLine],
+    ) -> Result<()> {
+        // The pixel font only has capitals
+        for (text, x, y, scale, color) in hud {
+            target.draw_text(
+                &text.to_uppercase(),
+                Vec2::new(*x, *y),
+                HUD_TEXT_SIZE * scale,
+                hud_color(*color),
+            )?;
+        }
+
+        if game.is_paused() {
+            let (width, height) = target.viewport_size();
+            let text = "PAUSED";
+            let text_width = text.len() as f32 * PAUSED_TEXT_SIZE * 0.8;
+            let position = Vec2::new(
+                (width as f32 - text_width) / 2.0,
+                (height as f32 - PAUSED_TEXT_SIZE) / 2.0,
+            );
+            target.draw_text(text, position, PAUSED_TEXT_SIZE, Color::YELLOW)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the bounding box of `points` overlaps `rect`
+fn overlaps(points: &[Vec2], rect: Rect) -> bool {
+    let min = points.iter().fold(Vec2::INFINITY, |min, p| min.min(*p));
+    let max = points.iter().fold(Vec2::NEG_INFINITY, |max, p| max.max(*p));
+    max.x >= rect.x
+        && max.y >= rect.y
+        && min.x <= rect.x + rect.width
+        && min.y <= rect.y + rect.height
+}
+
+/// HUD line color (0.0-1.0 RGBA) as a platform color
+fn hud_color(color: [f32; 4]) -> Color {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::rgba(
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2]),
+        channel(color[3]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_cycle_and_reset() {
+        let mut renderer = TopDownRenderer::new(800, 600);
+        assert_eq!(renderer.view(), TopDownView::Follow);
+
+        renderer.cycle_view();
+        assert_eq!(renderer.view(), TopDownView::Overview);
+        renderer.zoom_by(2.0);
+
+        renderer.reset_view();
+        assert_eq!(renderer.view(), TopDownView::Follow);
+        assert_eq!(renderer.follow_zoom, FOLLOW_ZOOM);
+    }
+
+    #[test]
+    fn test_hud_color() {
+        assert_eq!(
+            hud_color([1.0, 0.5, 0.0, 1.0]),
+            Color::rgba(255, 128, 0, 255)
+        );
+        assert_eq!(
+            hud_color([2.0, -1.0, 0.0, 1.0]),
+            Color::rgba(255, 0, 0, 255)
+        );
+    }
+}
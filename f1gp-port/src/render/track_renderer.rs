@@ -46,6 +46,14 @@ impl TrackRenderer {
         }
     }
 
+    /// Left edge, centerline and right edge of each section, in ground (x, z)
+    /// coordinates
+    pub(crate) fn outline(&self) -> impl Iterator<Item = (Vec2, Vec2, Vec2)> + '_ {
+        self.edge_points
+            .iter()
+            .map(|point| (point.left, point.center, point.right))
+    }
+
     /// Generate edge points (left, center, right) for each track section
     fn generate_edge_points(sections: &[TrackSection]) -> Vec<EdgePoint> {
         if sections.is_empty() {
@@ -392,6 +400,7 @@ mod tests {
             ai_behavior: AIBehavior::default(),
             pit_lane: vec![],
             cameras: vec![],
+            annotations: Default::default(),
             checksum: 0,
         }
     }
@@ -1,11 +1,13 @@
 // Camera3D - 3D camera system for different view modes
 // Stage 6.1 & 6.4 - Enhanced with smooth transitions, shake, interpolation
 
+use super::cockpit;
 use crate::physics::CarPhysics;
 use glam::{Mat4, Vec3};
+use serde::{Deserialize, Serialize};
 
 /// Camera viewing modes
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CameraMode {
     /// First-person from driver's eyes
     Cockpit,
@@ -95,6 +97,9 @@ pub struct Camera3D {
     // Camera shake
     shake: CameraShake,
 
+    // Chase camera up vector, eased towards the road normal
+    road_up: Vec3,
+
     // TV Camera state
     tv_camera_index: usize,
     tv_camera_positions: Vec<Vec3>,
@@ -134,6 +139,7 @@ impl Camera3D {
             position_smoothing: 0.9, // Smooth position transitions
             target_smoothing: 0.85,  // Smooth target transitions
             shake: CameraShake::new(),
+            road_up: Vec3::Y,
             tv_camera_index: 0,
             tv_camera_positions,
             free_yaw: 0.0,
@@ -174,9 +180,8 @@ impl Camera3D {
         // Calculate desired camera position based on mode
         match self.mode {
             CameraMode::Cockpit => {
-                // Position at driver head (0.5m above car center)
-                let offset = Vec3::new(0.0, 0.5, 0.0);
-                self.desired_position = car.body.position + offset;
+                // Position at the driver's eyes inside the monocoque
+                self.desired_position = cockpit::eye_position(car);
 
                 // Look forward from car's orientation
                 let forward = car.body.orientation * Vec3::NEG_Z;
@@ -192,11 +197,28 @@ impl Camera3D {
                 let car_forward = car.body.orientation * Vec3::NEG_Z;
                 let car_right = car.body.orientation * Vec3::X;
 
+                // Sit behind the car along the slope so the camera climbs
+                // and drops with the road
+                let slope = car.track_grade.atan();
+                let flat_forward = Vec3::new(car_forward.x, 0.0, car_forward.z).normalize_or_zero();
+                let road_forward = flat_forward * slope.cos() + Vec3::Y * slope.sin();
+
                 // Add slight offset to the right for better view
-                let offset = -car_forward * 6.0 + Vec3::Y * 2.5 + car_right * 0.5;
+                let offset = -road_forward * 6.0 + Vec3::Y * 2.5 + car_right * 0.5;
                 self.desired_position = car.body.position + offset;
                 self.desired_target = car.body.position + Vec3::Y * 0.8;
-                self.up = Vec3::Y;
+
+                // Bank with the camber (positive leans the road right)
+                let camber = car.track_camber;
+                let desired_up = (Vec3::Y * camber.cos() + car_right * camber.sin()).normalize();
+                let up_lerp_factor = 1.0 - 0.9_f32.powf(delta_time * 60.0);
+                self.road_up =
+                    Self::lerp_vec3(self.road_up, desired_up, up_lerp_factor).normalize_or_zero();
+                self.up = if self.road_up == Vec3::ZERO {
+                    Vec3::Y
+                } else {
+                    self.road_up
+                };
 
                 // Chase camera has moderate smoothing
                 self.position_smoothing = 0.9;
@@ -470,6 +492,40 @@ mod tests {
         assert!(distance_moved < 50.0); // Didn't jump all the way
     }
 
+    #[test]
+    fn test_chase_camera_follows_slope() {
+        use crate::data::CarDatabase;
+
+        let car_database = CarDatabase::create_sample();
+        let car_spec = car_database.cars().next().unwrap().clone();
+        let mut car = CarPhysics::new(BodyId(0), car_spec, Vec3::new(0.0, 1.0, 0.0));
+
+        let mut flat = Camera3D::new(16.0 / 9.0);
+        let mut banked = Camera3D::new(16.0 / 9.0);
+
+        for _ in 0..300 {
+            flat.update_from_car(&car, 0.016);
+        }
+
+        // Downhill with the road leaning right
+        car.set_track_slope(-0.1, 0.1);
+        banked.update_from_car(&car, 0.016);
+
+        // Banking eases in rather than snapping
+        assert!(banked.up.x > 0.0);
+        assert!(banked.up.x < 0.1_f32.sin() * 0.5);
+
+        for _ in 0..300 {
+            banked.update_from_car(&car, 0.016);
+        }
+
+        assert!((banked.up.x - 0.1_f32.sin()).abs() < 1e-3);
+        assert_eq!(flat.up, Vec3::Y);
+
+        // Behind a car heading downhill the camera sits higher
+        assert!(banked.position().y > flat.position().y + 0.5);
+    }
+
     #[test]
     fn test_camera_mode_cycling() {
         let mut camera = Camera3D::new(16.0 / 9.0);
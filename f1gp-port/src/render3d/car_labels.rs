@@ -0,0 +1,150 @@
+// Car Number Labels
+// Projects car numbers above nearby cars as HUD text, faded by distance
+
+use crate::data::car::Livery;
+use glam::{Mat4, Vec3};
+
+/// Labels are fully opaque within this distance (meters)
+pub const LABEL_FADE_START: f32 = 20.0;
+
+/// Labels are hidden beyond this distance (meters)
+pub const LABEL_MAX_DISTANCE: f32 = 80.0;
+
+/// Height of the label anchor above the car origin (meters)
+pub const LABEL_HEIGHT: f32 = 2.0;
+
+/// HUD text scale used for labels
+const LABEL_SCALE: f32 = 1.5;
+
+/// Glyph size at scale 1.0 (matches the HUD bitmap font)
+const GLYPH_WIDTH: f32 = 8.0;
+const GLYPH_HEIGHT: f32 = 16.0;
+
+/// HUD text line: (text, x, y, scale, color)
+pub type HudLine = (String, f32, f32, f32, [f32; 4]);
+
+/// Label opacity for a car at the given distance from the camera
+pub fn label_alpha(distance: f32) -> f32 {
+    if distance <= LABEL_FADE_START {
+        1.0
+    } else if distance >= LABEL_MAX_DISTANCE {
+        0.0
+    } else {
+        1.0 - (distance - LABEL_FADE_START) / (LABEL_MAX_DISTANCE - LABEL_FADE_START)
+    }
+}
+
+/// Project a world position to screen pixels (None if behind the camera or off-screen)
+pub fn project_to_screen(
+    view_proj: Mat4,
+    world: Vec3,
+    screen_width: f32,
+    screen_height: f32,
+) -> Option<(f32, f32)> {
+    let clip = view_proj * world.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+
+    let ndc = clip.truncate() / clip.w;
+    if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 || ndc.z < 0.0 || ndc.z > 1.0 {
+        return None;
+    }
+
+    let x = (ndc.x + 1.0) * 0.5 * screen_width;
+    let y = (1.0 - ndc.y) * 0.5 * screen_height;
+    Some((x, y))
+}
+
+/// Build HUD text lines for car numbers above nearby cars
+///
+/// `cars` holds (position, number, livery); labels are centered above each car
+/// and colored with the car's primary livery color.
+pub fn car_number_labels(
+    view_proj: Mat4,
+    camera_pos: Vec3,
+    cars: &[(Vec3, u8, Livery)],
+    screen_width: f32,
+    screen_height: f32,
+) -> Vec<HudLine> {
+    let mut labels = Vec::new();
+
+    for (position, number, livery) in cars {
+        let alpha = label_alpha(position.distance(camera_pos));
+        if alpha <= 0.0 {
+            continue;
+        }
+
+        let anchor = *position + Vec3::Y * LABEL_HEIGHT;
+        let Some((x, y)) = project_to_screen(view_proj, anchor, screen_width, screen_height) else {
+            continue;
+        };
+
+        let text = number.to_string();
+        let text_width = text.len() as f32 * GLYPH_WIDTH * LABEL_SCALE;
+        let mut color = livery.primary_rgba();
+        color[3] = alpha;
+
+        labels.push((
+            text,
+            x - text_width * 0.5,
+            y - GLYPH_HEIGHT * LABEL_SCALE,
+            LABEL_SCALE,
+            color,
+        ));
+    }
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_view_proj() -> Mat4 {
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 2.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(60.0_f32.to_radians(), 16.0 / 9.0, 0.1, 1000.0);
+        proj * view
+    }
+
+    #[test]
+    fn test_label_alpha_fades_with_distance() {
+        assert_eq!(label_alpha(5.0), 1.0);
+        assert_eq!(label_alpha(LABEL_FADE_START), 1.0);
+        assert!((label_alpha(50.0) - 0.5).abs() < 1e-5);
+        assert_eq!(label_alpha(LABEL_MAX_DISTANCE), 0.0);
+        assert_eq!(label_alpha(500.0), 0.0);
+    }
+
+    #[test]
+    fn test_project_behind_camera_is_hidden() {
+        let view_proj = test_view_proj();
+        assert!(project_to_screen(view_proj, Vec3::new(0.0, 0.0, 20.0), 1280.0, 720.0).is_none());
+
+        let (x, _) = project_to_screen(view_proj, Vec3::ZERO, 1280.0, 720.0).unwrap();
+        assert!((x - 640.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_car_number_labels() {
+        let view_proj = test_view_proj();
+        let camera_pos = Vec3::new(0.0, 2.0, 10.0);
+        let livery = Livery {
+            primary: (255, 0, 0),
+            secondary: (255, 255, 255),
+        };
+        let cars = [
+            (Vec3::ZERO, 27, livery),
+            (Vec3::new(0.0, 0.0, -200.0), 5, livery),
+        ];
+
+        let labels = car_number_labels(view_proj, camera_pos, &cars, 1280.0, 720.0);
+
+        // Distant car is culled, nearby car is fully opaque and centered
+        assert_eq!(labels.len(), 1);
+        let (text, x, _, _, color) = &labels[0];
+        assert_eq!(text, "27");
+        assert!((x + GLYPH_WIDTH * LABEL_SCALE - 640.0).abs() < 1.0);
+        assert_eq!(*color, [1.0, 0.0, 0.0, 1.0]);
+    }
+}
@@ -1,10 +1,19 @@
 // Car Model Generator - Stage 6.3
 // Creates 3D car models for rendering
 
+use crate::data::car::Livery;
 use crate::physics::CarPhysics;
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 
+/// Maximum number of cars drawn in a single instanced draw call
+pub const MAX_CAR_INSTANCES: usize = 32;
+
+/// Livery slots: which color a car vertex is painted with
+pub const LIVERY_SLOT_FIXED: f32 = 0.0; // Vertex color (wings, wheels)
+pub const LIVERY_SLOT_PRIMARY: f32 = 1.0; // Instance primary color (body)
+pub const LIVERY_SLOT_SECONDARY: f32 = 2.0; // Instance secondary color (cockpit)
+
 /// Vertex for car rendering
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -12,11 +21,16 @@ pub struct CarVertex {
     pub position: [f32; 3],
     pub normal: [f32; 3],
     pub color: [f32; 4],
+    pub livery_slot: f32,
 }
 
 impl CarVertex {
-    pub const ATTRIBS: [wgpu::VertexAttribute; 3] =
-        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x4];
+    pub const ATTRIBS: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x3,
+        2 => Float32x4,
+        3 => Float32
+    ];
 
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
@@ -32,8 +46,64 @@ impl CarVertex {
             position: position.to_array(),
             normal: normal.to_array(),
             color,
+            livery_slot: LIVERY_SLOT_FIXED,
         }
     }
+
+    /// Paint this vertex with an instance livery color instead of its own color
+    pub fn with_livery_slot(mut self, livery_slot: f32) -> Self {
+        self.livery_slot = livery_slot;
+        self
+    }
+}
+
+/// Per-instance car data (model transform and livery colors)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct CarInstance {
+    pub model: [[f32; 4]; 4],
+    pub primary: [f32; 4],
+    pub secondary: [f32; 4],
+}
+
+impl CarInstance {
+    /// Locations follow on from `CarVertex::ATTRIBS`
+    pub const ATTRIBS: [wgpu::VertexAttribute; 6] = wgpu::vertex_attr_array![
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Float32x4,
+        8 => Float32x4,
+        9 => Float32x4
+    ];
+
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<CarInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+
+    /// Create instance data for a car with the given livery
+    pub fn new(car: &CarPhysics, livery: &Livery) -> Self {
+        Self {
+            model: CarModel::get_transform_matrix(car).to_cols_array_2d(),
+            primary: livery.primary_rgba(),
+            secondary: livery.secondary_rgba(),
+        }
+    }
+}
+
+/// Build the instance buffer contents for a set of cars (capped at `MAX_CAR_INSTANCES`)
+pub fn build_car_instances<'a, I>(cars: I) -> Vec<CarInstance>
+where
+    I: IntoIterator<Item = (&'a CarPhysics, Livery)>,
+{
+    cars.into_iter()
+        .take(MAX_CAR_INSTANCES)
+        .map(|(car, livery)| CarInstance::new(car, &livery))
+        .collect()
 }
 
 /// Simple box-based car model (like original F1GP)
@@ -44,6 +114,9 @@ pub struct CarModel {
 
 impl CarModel {
     /// Create a simple box-based F1 car model
+    ///
+    /// The body and cockpit are tagged with livery slots so instanced drawing can
+    /// repaint them per car; `team_color` is kept as the vertex color fallback.
     pub fn create_f1_car(team_color: [f32; 4]) -> Self {
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
@@ -62,6 +135,7 @@ impl CarModel {
             Vec3::new(0.0, height * 0.5, 0.0),
             Vec3::new(width, height, length),
             team_color,
+            LIVERY_SLOT_PRIMARY,
         );
 
         // Front wing (low, wide)
@@ -71,6 +145,7 @@ impl CarModel {
             Vec3::new(0.0, 0.1, -half_len - 0.3),
             Vec3::new(width * 1.2, 0.1, 0.5),
             [0.1, 0.1, 0.1, 1.0], // Black
+            LIVERY_SLOT_FIXED,
         );
 
         // Rear wing (high, wide)
@@ -80,6 +155,7 @@ impl CarModel {
             Vec3::new(0.0, height + 0.3, half_len - 0.2),
             Vec3::new(width * 1.1, 0.1, 0.4),
             [0.1, 0.1, 0.1, 1.0], // Black
+            LIVERY_SLOT_FIXED,
         );
 
         // Wheels (4 cylinders approximated as boxes)
@@ -98,6 +174,7 @@ impl CarModel {
             ),
             Vec3::new(wheel_width, wheel_radius * 2.0, wheel_radius * 2.0),
             wheel_color,
+            LIVERY_SLOT_FIXED,
         );
 
         // Front right wheel
@@ -111,6 +188,7 @@ impl CarModel {
             ),
             Vec3::new(wheel_width, wheel_radius * 2.0, wheel_radius * 2.0),
             wheel_color,
+            LIVERY_SLOT_FIXED,
         );
 
         // Rear left wheel
@@ -124,6 +202,7 @@ impl CarModel {
             ),
             Vec3::new(wheel_width, wheel_radius * 2.0, wheel_radius * 2.0),
             wheel_color,
+            LIVERY_SLOT_FIXED,
         );
 
         // Rear right wheel
@@ -133,6 +212,7 @@ impl CarModel {
             Vec3::new(half_width + wheel_width * 0.5, wheel_radius, half_len - 0.8),
             Vec3::new(wheel_width, wheel_radius * 2.0, wheel_radius * 2.0),
             wheel_color,
+            LIVERY_SLOT_FIXED,
         );
 
         // Cockpit (darker color on top)
@@ -147,6 +227,7 @@ impl CarModel {
             Vec3::new(0.0, height + 0.2, 0.0),
             Vec3::new(width * 0.6, 0.4, length * 0.3),
             cockpit_color,
+            LIVERY_SLOT_SECONDARY,
         );
 
         Self { vertices, indices }
@@ -159,6 +240,7 @@ impl CarModel {
         center: Vec3,
         size: Vec3,
         color: [f32; 4],
+        livery_slot: f32,
     ) {
         let _base_index = vertices.len() as u32;
         let half_size = size * 0.5;
@@ -196,7 +278,9 @@ impl CarModel {
 
             // Add 4 vertices for this face
             for &idx in face_indices {
-                vertices.push(CarVertex::new(positions[idx], *normal, color));
+                vertices.push(
+                    CarVertex::new(positions[idx], *normal, color).with_livery_slot(livery_slot),
+                );
             }
 
             // Add 2 triangles for this face
@@ -289,4 +373,72 @@ mod tests {
         let origin = transform.transform_point3(Vec3::ZERO);
         assert!((origin - car.body.position).length() < 0.01);
     }
+
+    #[test]
+    fn test_instance_layout_follows_vertex_layout() {
+        // 4x4 model matrix + two RGBA colors, no padding
+        assert_eq!(std::mem::size_of::<CarInstance>(), 96);
+        assert_eq!(
+            CarInstance::desc().array_stride,
+            std::mem::size_of::<CarInstance>() as u64
+        );
+        assert_eq!(
+            CarInstance::desc().step_mode,
+            wgpu::VertexStepMode::Instance
+        );
+
+        let vertex_locations: Vec<u32> = CarVertex::ATTRIBS
+            .iter()
+            .map(|a| a.shader_location)
+            .collect();
+        for attr in CarInstance::ATTRIBS.iter() {
+            assert!(!vertex_locations.contains(&attr.shader_location));
+        }
+    }
+
+    #[test]
+    fn test_car_model_uses_livery_slots() {
+        let model = CarModel::create_f1_car([1.0, 0.0, 0.0, 1.0]);
+        let slots: Vec<f32> = model.vertices.iter().map(|v| v.livery_slot).collect();
+
+        assert!(slots.contains(&LIVERY_SLOT_FIXED));
+        assert!(slots.contains(&LIVERY_SLOT_PRIMARY));
+        assert!(slots.contains(&LIVERY_SLOT_SECONDARY));
+    }
+
+    #[test]
+    fn test_build_car_instances() {
+        use crate::data::CarDatabase;
+        use crate::physics::BodyId;
+
+        let car_database = CarDatabase::create_sample();
+        let car_spec = car_database.cars().next().unwrap().clone();
+        let cars: Vec<CarPhysics> = (0..MAX_CAR_INSTANCES + 4)
+            .map(|i| {
+                CarPhysics::new(
+                    BodyId(i),
+                    car_spec.clone(),
+                    Vec3::new(i as f32 * 5.0, 1.0, 0.0),
+                )
+            })
+            .collect();
+        let livery = Livery {
+            primary: (255, 0, 0),
+            secondary: (0, 0, 255),
+        };
+
+        let instances = build_car_instances(cars.iter().map(|car| (car, livery)));
+
+        // One instance per car, capped to the buffer size
+        assert_eq!(instances.len(), MAX_CAR_INSTANCES);
+        assert_eq!(instances[0].primary, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(instances[0].secondary, [0.0, 0.0, 1.0, 1.0]);
+
+        let model = Mat4::from_cols_array_2d(&instances[3].model);
+        let origin = model.transform_point3(Vec3::ZERO);
+        assert!((origin - cars[3].body.position).length() < 0.01);
+
+        let bytes: &[u8] = bytemuck::cast_slice(&instances);
+        assert_eq!(bytes.len(), MAX_CAR_INSTANCES * 96);
+    }
 }
@@ -0,0 +1,526 @@
+// Cockpit View
+// Driver's-eye view from the monocoque: steering wheel, dashboard and rear-view mirrors
+
+use super::car_labels::{project_to_screen, HudLine};
+use super::renderer::Vertex;
+use crate::physics::CarPhysics;
+use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Driver eye height above the car origin (meters)
+pub const DRIVER_EYE_HEIGHT: f32 = 0.75;
+
+/// Driver eye offset behind the car origin (meters)
+pub const DRIVER_EYE_SETBACK: f32 = 0.3;
+
+/// Steering wheel rotation at full lock (radians)
+pub const STEERING_WHEEL_LOCK: f32 = std::f32::consts::FRAC_PI_2;
+
+/// Steering wheel center relative to the driver's eye, in car space (meters)
+const WHEEL_OFFSET: Vec3 = Vec3::new(0.0, -0.28, -0.5);
+
+/// Steering wheel rim radius and thickness (meters)
+const WHEEL_RADIUS: f32 = 0.15;
+const WHEEL_RIM_WIDTH: f32 = 0.025;
+
+/// Rim segments (quads) around the full circle
+const WHEEL_SEGMENTS: usize = 24;
+
+/// Rectangular center body of the wheel, where the display sits (meters)
+const WHEEL_BODY_WIDTH: f32 = 0.2;
+const WHEEL_BODY_HEIGHT: f32 = 0.09;
+
+const WHEEL_RIM_COLOR: [f32; 4] = [0.08, 0.08, 0.08, 1.0];
+const WHEEL_BODY_COLOR: [f32; 4] = [0.2, 0.2, 0.22, 1.0];
+const WHEEL_MARKER_COLOR: [f32; 4] = [0.9, 0.75, 0.1, 1.0];
+
+/// Number of RPM shift lights across the top of the wheel
+pub const RPM_LED_COUNT: usize = 10;
+
+/// Fraction of max RPM at which the first LED lights
+pub const RPM_LED_START: f32 = 0.6;
+
+/// Screen spacing between LEDs at scale 1.0 (pixels)
+const LED_SPACING: f32 = 10.0;
+
+const DASH_SCALE: f32 = 1.5;
+
+/// Glyph size at scale 1.0 (matches the HUD bitmap font)
+const GLYPH_WIDTH: f32 = 8.0;
+const GLYPH_HEIGHT: f32 = 16.0;
+
+const LED_OFF_COLOR: [f32; 4] = [0.15, 0.15, 0.15, 1.0];
+const GEAR_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+/// Mirror width as a fraction of the screen width
+const MIRROR_SCREEN_WIDTH: f32 = 0.22;
+
+/// Mirror width / height
+const MIRROR_ASPECT: f32 = 2.6;
+
+/// Gap between a mirror and the screen edge (pixels)
+const MIRROR_MARGIN: f32 = 12.0;
+
+/// Mirror field of view (radians)
+const MIRROR_FOV: f32 = 0.7;
+
+/// Mirror near plane (meters)
+const MIRROR_NEAR: f32 = 0.1;
+
+/// Mirror position relative to the car origin, for the left mirror (meters)
+const MIRROR_OFFSET: Vec3 = Vec3::new(-0.45, 0.7, -0.6);
+
+/// How far mirrors angle outwards from straight back
+const MIRROR_TOE_OUT: f32 = 0.2;
+
+/// Which rear-view mirror
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorSide {
+    Left,
+    Right,
+}
+
+impl MirrorSide {
+    /// Both mirrors, left first
+    pub const ALL: [MirrorSide; 2] = [MirrorSide::Left, MirrorSide::Right];
+
+    fn sign(self) -> f32 {
+        match self {
+            MirrorSide::Left => -1.0,
+            MirrorSide::Right => 1.0,
+        }
+    }
+}
+
+/// Rear-view mirror rendering options
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MirrorSettings {
+    /// Draw mirrors in cockpit view
+    pub enabled: bool,
+
+    /// Re-render mirrors every N frames (1 = every frame)
+    pub update_interval: u32,
+
+    /// Mirror texture resolution relative to its on-screen size
+    pub resolution_scale: f32,
+
+    /// Far plane for the mirror views (meters)
+    pub draw_distance: f32,
+}
+
+impl Default for MirrorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            update_interval: 2,
+            resolution_scale: 0.5,
+            draw_distance: 250.0,
+        }
+    }
+}
+
+/// Decides which frames re-render the mirrors
+#[derive(Debug, Clone)]
+pub struct MirrorSchedule {
+    frames_since_update: u32,
+    stale: bool,
+}
+
+impl MirrorSchedule {
+    /// Create a schedule that renders on the first frame
+    pub fn new() -> Self {
+        Self {
+            frames_since_update: 0,
+            stale: true,
+        }
+    }
+
+    /// Advance one frame; returns true if the mirrors should be re-rendered
+    pub fn tick(&mut self, settings: &MirrorSettings) -> bool {
+        let interval = settings.update_interval.max(1);
+        let due = self.stale || self.frames_since_update + 1 >= interval;
+        if due {
+            self.frames_since_update = 0;
+        } else {
+            self.frames_since_update += 1;
+        }
+        self.stale = false;
+        due
+    }
+
+    /// Force a re-render next frame (mirror contents are out of date)
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+}
+
+impl Default for MirrorSchedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Screen-space rectangle (pixels, origin top-left)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl MirrorRect {
+    /// Offscreen texture size for this mirror at the given resolution scale
+    pub fn texture_size(&self, resolution_scale: f32) -> (u32, u32) {
+        let scale = resolution_scale.clamp(0.1, 1.0);
+        (
+            ((self.width * scale) as u32).max(1),
+            ((self.height * scale) as u32).max(1),
+        )
+    }
+}
+
+/// Where a mirror is drawn on screen (top corners)
+pub fn mirror_rect(side: MirrorSide, screen_width: f32, screen_height: f32) -> MirrorRect {
+    let width = screen_width * MIRROR_SCREEN_WIDTH;
+    let height = (width / MIRROR_ASPECT).min(screen_height * 0.5);
+    let x = match side {
+        MirrorSide::Left => MIRROR_MARGIN,
+        MirrorSide::Right => screen_width - MIRROR_MARGIN - width,
+    };
+    MirrorRect {
+        x: x.max(0.0),
+        y: MIRROR_MARGIN.min(screen_height - height).max(0.0),
+        width,
+        height,
+    }
+}
+
+/// Driver eye position in world space
+pub fn eye_position(car: &CarPhysics) -> Vec3 {
+    car.body.position + car.body.orientation * Vec3::new(0.0, DRIVER_EYE_HEIGHT, DRIVER_EYE_SETBACK)
+}
+
+/// Steering wheel rotation for a steering input (-1.0 = full left)
+///
+/// Positive angles turn the wheel anticlockwise as seen by the driver.
+pub fn steering_wheel_angle(steering: f32) -> f32 {
+    -steering.clamp(-1.0, 1.0) * STEERING_WHEEL_LOCK
+}
+
+/// Steering wheel center in world space
+pub fn steering_wheel_center(car: &CarPhysics) -> Vec3 {
+    eye_position(car) + car.body.orientation * WHEEL_OFFSET
+}
+
+/// Steering wheel triangles in car space, centered on the wheel hub
+pub fn steering_wheel_mesh(steering: f32) -> Vec<Vertex> {
+    let rotation = Quat::from_rotation_z(steering_wheel_angle(steering));
+    let mut vertices = Vec::with_capacity(steering_wheel_vertex_count());
+
+    let mut quad = |corners: [Vec3; 4], color: [f32; 4]| {
+        for index in [0, 1, 2, 0, 2, 3] {
+            vertices.push(Vertex {
+                position: (rotation * corners[index]).to_array(),
+                color,
+            });
+        }
+    };
+
+    // Rim
+    let inner = WHEEL_RADIUS - WHEEL_RIM_WIDTH;
+    for i in 0..WHEEL_SEGMENTS {
+        let a0 = i as f32 / WHEEL_SEGMENTS as f32 * std::f32::consts::TAU;
+        let a1 = (i + 1) as f32 / WHEEL_SEGMENTS as f32 * std::f32::consts::TAU;
+        let (s0, c0) = a0.sin_cos();
+        let (s1, c1) = a1.sin_cos();
+        quad(
+            [
+                Vec3::new(c0 * inner, s0 * inner, 0.0),
+                Vec3::new(c0 * WHEEL_RADIUS, s0 * WHEEL_RADIUS, 0.0),
+                Vec3::new(c1 * WHEEL_RADIUS, s1 * WHEEL_RADIUS, 0.0),
+                Vec3::new(c1 * inner, s1 * inner, 0.0),
+            ],
+            WHEEL_RIM_COLOR,
+        );
+    }
+
+    // Center body
+    let (w, h) = (WHEEL_BODY_WIDTH * 0.5, WHEEL_BODY_HEIGHT * 0.5);
+    quad(
+        [
+            Vec3::new(-w, -h, 0.0),
+            Vec3::new(w, -h, 0.0),
+            Vec3::new(w, h, 0.0),
+            Vec3::new(-w, h, 0.0),
+        ],
+        WHEEL_BODY_COLOR,
+    );
+
+    // Straight-ahead marker at 12 o'clock
+    let m = WHEEL_RIM_WIDTH * 0.5;
+    quad(
+        [
+            Vec3::new(-m, inner, 0.001),
+            Vec3::new(m, inner, 0.001),
+            Vec3::new(m, WHEEL_RADIUS, 0.001),
+            Vec3::new(-m, WHEEL_RADIUS, 0.001),
+        ],
+        WHEEL_MARKER_COLOR,
+    );
+
+    vertices
+}
+
+/// Number of vertices produced by `steering_wheel_mesh`
+pub const fn steering_wheel_vertex_count() -> usize {
+    (WHEEL_SEGMENTS + 2) * 6
+}
+
+/// Steering wheel triangles in world space for the car's current steering input
+pub fn steering_wheel_vertices(car: &CarPhysics) -> Vec<Vertex> {
+    let transform =
+        Mat4::from_rotation_translation(car.body.orientation, steering_wheel_center(car));
+    let mut vertices = steering_wheel_mesh(car.steering);
+    for vertex in &mut vertices {
+        vertex.position = transform
+            .transform_point3(Vec3::from_array(vertex.position))
+            .to_array();
+    }
+    vertices
+}
+
+/// Number of shift lights lit at this RPM
+pub fn rpm_leds_lit(rpm: f32, max_rpm: f32) -> usize {
+    if max_rpm <= 0.0 {
+        return 0;
+    }
+    let start = max_rpm * RPM_LED_START;
+    let fraction = ((rpm - start) / (max_rpm - start)).clamp(0.0, 1.0);
+    (fraction * RPM_LED_COUNT as f32).ceil() as usize
+}
+
+/// Color of a lit shift light (green, then red, then blue at the limiter)
+fn led_color(index: usize) -> [f32; 4] {
+    match index * 10 / RPM_LED_COUNT {
+        0..=3 => [0.1, 1.0, 0.2, 1.0],
+        4..=7 => [1.0, 0.15, 0.1, 1.0],
+        _ => [0.2, 0.4, 1.0, 1.0],
+    }
+}
+
+/// Dashboard gear label (gear 0 is reverse)
+pub fn gear_label(gear: i8) -> &'static str {
+    match gear {
+        0 => "R",
+        1 => "1",
+        2 => "2",
+        3 => "3",
+        4 => "4",
+        5 => "5",
+        6 => "6",
+        _ => "N",
+    }
+}
+
+/// HUD lines for the wheel display: shift lights above the gear indicator
+///
+/// Anchored to the wheel body in 3D so the display turns with the car; nothing
+/// is drawn if the wheel is off-screen.
+pub fn dashboard_lines(
+    view_proj: Mat4,
+    car: &CarPhysics,
+    screen_width: f32,
+    screen_height: f32,
+) -> Vec<HudLine> {
+    let anchor = steering_wheel_center(car);
+    let Some((x, y)) = project_to_screen(view_proj, anchor, screen_width, screen_height) else {
+        return Vec::new();
+    };
+
+    let mut lines = Vec::with_capacity(RPM_LED_COUNT + 1);
+
+    let lit = rpm_leds_lit(car.engine_rpm, car.spec.engine.max_rpm);
+    let row_width = (RPM_LED_COUNT - 1) as f32 * LED_SPACING * DASH_SCALE;
+    let led_y = y - GLYPH_HEIGHT * DASH_SCALE * 1.5;
+    for i in 0..RPM_LED_COUNT {
+        let color = if i < lit { led_color(i) } else { LED_OFF_COLOR };
+        let led_x = x - row_width * 0.5 + i as f32 * LED_SPACING * DASH_SCALE;
+        lines.push(("*".to_string(), led_x, led_y, DASH_SCALE, color));
+    }
+
+    let gear = gear_label(car.gear);
+    let gear_scale = DASH_SCALE * 1.5;
+    lines.push((
+        gear.to_string(),
+        x - GLYPH_WIDTH * gear_scale * 0.5,
+        y - GLYPH_HEIGHT * gear_scale * 0.5,
+        gear_scale,
+        GEAR_COLOR,
+    ));
+
+    lines
+}
+
+/// Mirror camera position in world space
+pub fn mirror_position(car: &CarPhysics, side: MirrorSide) -> Vec3 {
+    let offset = Vec3::new(
+        MIRROR_OFFSET.x.abs() * side.sign(),
+        MIRROR_OFFSET.y,
+        MIRROR_OFFSET.z,
+    );
+    car.body.position + car.body.orientation * offset
+}
+
+/// View matrix for a mirror, looking back and slightly outwards
+///
+/// The image is not flipped here; the compositor mirrors it horizontally.
+pub fn mirror_view_matrix(car: &CarPhysics, side: MirrorSide) -> Mat4 {
+    let position = mirror_position(car, side);
+    let backward = Vec3::new(side.sign() * MIRROR_TOE_OUT, 0.0, 1.0).normalize();
+    let direction = car.body.orientation * backward;
+    let up = car.body.orientation * Vec3::Y;
+    Mat4::look_at_rh(position, position + direction, up)
+}
+
+/// Projection matrix for a mirror, clipped at the reduced draw distance
+pub fn mirror_projection_matrix(rect: &MirrorRect, settings: &MirrorSettings) -> Mat4 {
+    let aspect = rect.width / rect.height.max(1.0);
+    let far = settings.draw_distance.max(MIRROR_NEAR * 10.0);
+    Mat4::perspective_rh(MIRROR_FOV, aspect, MIRROR_NEAR, far)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::CarDatabase;
+    use crate::physics::BodyId;
+
+    fn test_car() -> CarPhysics {
+        let spec = CarDatabase::create_sample().cars().next().unwrap().clone();
+        CarPhysics::new(BodyId(0), spec, Vec3::ZERO)
+    }
+
+    #[test]
+    fn test_steering_wheel_angle() {
+        assert_eq!(steering_wheel_angle(0.0), 0.0);
+        assert_eq!(steering_wheel_angle(-1.0), STEERING_WHEEL_LOCK);
+        assert_eq!(steering_wheel_angle(2.0), -STEERING_WHEEL_LOCK);
+    }
+
+    #[test]
+    fn test_wheel_marker_turns_with_input() {
+        let marker_x = |steering: f32| {
+            let mesh = steering_wheel_mesh(steering);
+            mesh[mesh.len() - 1].position[0]
+        };
+
+        assert!(marker_x(0.0).abs() < 0.02);
+        // Steering left turns the top of the wheel to the left
+        assert!(marker_x(-1.0) < -0.1);
+        assert!(marker_x(1.0) > 0.1);
+    }
+
+    #[test]
+    fn test_wheel_mesh_size() {
+        assert_eq!(
+            steering_wheel_mesh(0.3).len(),
+            steering_wheel_vertex_count()
+        );
+    }
+
+    #[test]
+    fn test_eye_above_car() {
+        let car = test_car();
+        let eye = eye_position(&car);
+        assert!((eye.y - DRIVER_EYE_HEIGHT).abs() < 1e-5);
+        assert!(steering_wheel_center(&car).y < eye.y);
+    }
+
+    #[test]
+    fn test_rpm_leds() {
+        assert_eq!(rpm_leds_lit(1000.0, 13000.0), 0);
+        assert_eq!(rpm_leds_lit(13000.0, 13000.0), RPM_LED_COUNT);
+        let mid = rpm_leds_lit(13000.0 * 0.8, 13000.0);
+        assert!(mid > 0 && mid < RPM_LED_COUNT);
+        assert_eq!(rpm_leds_lit(5000.0, 0.0), 0);
+    }
+
+    #[test]
+    fn test_gear_label() {
+        assert_eq!(gear_label(0), "R");
+        assert_eq!(gear_label(3), "3");
+        assert_eq!(gear_label(-1), "N");
+    }
+
+    #[test]
+    fn test_dashboard_projects_in_front_of_driver() {
+        let car = test_car();
+        let eye = eye_position(&car);
+        let view = Mat4::look_at_rh(eye, eye + Vec3::NEG_Z, Vec3::Y);
+        let proj = Mat4::perspective_rh(60.0_f32.to_radians(), 16.0 / 9.0, 0.1, 1000.0);
+
+        let lines = dashboard_lines(proj * view, &car, 1280.0, 720.0);
+        assert_eq!(lines.len(), RPM_LED_COUNT + 1);
+        let (gear, x, y, _, _) = &lines[RPM_LED_COUNT];
+        assert_eq!(gear, "1");
+        assert!((*x - 640.0).abs() < 40.0);
+        assert!(*y > 360.0, "wheel sits below the horizon");
+
+        // Looking backwards hides the display
+        let view = Mat4::look_at_rh(eye, eye + Vec3::Z, Vec3::Y);
+        assert!(dashboard_lines(proj * view, &car, 1280.0, 720.0).is_empty());
+    }
+
+    #[test]
+    fn test_mirror_schedule_every_other_frame() {
+        let settings = MirrorSettings::default();
+        let mut schedule = MirrorSchedule::new();
+        let frames: Vec<bool> = (0..6).map(|_| schedule.tick(&settings)).collect();
+        assert_eq!(frames, vec![true, false, true, false, true, false]);
+
+        schedule.invalidate();
+        assert!(schedule.tick(&settings));
+        assert!(!schedule.tick(&settings));
+    }
+
+    #[test]
+    fn test_mirror_schedule_every_frame() {
+        let settings = MirrorSettings {
+            update_interval: 0,
+            ..MirrorSettings::default()
+        };
+        let mut schedule = MirrorSchedule::new();
+        assert!((0..4).all(|_| schedule.tick(&settings)));
+    }
+
+    #[test]
+    fn test_mirror_rects_in_top_corners() {
+        let left = mirror_rect(MirrorSide::Left, 1280.0, 720.0);
+        let right = mirror_rect(MirrorSide::Right, 1280.0, 720.0);
+        assert_eq!(left.y, right.y);
+        assert!(left.x < 50.0);
+        assert!(right.x + right.width > 1230.0);
+        assert!(left.x + left.width < right.x);
+
+        let (w, h) = left.texture_size(0.5);
+        assert_eq!(w, (left.width * 0.5) as u32);
+        assert_eq!(h, (left.height * 0.5) as u32);
+    }
+
+    #[test]
+    fn test_mirrors_look_backwards() {
+        let car = test_car();
+        for side in MirrorSide::ALL {
+            let view = mirror_view_matrix(&car, side);
+            // A point behind the car is in front of the mirror camera (negative view-space z)
+            let behind = view.transform_point3(Vec3::new(0.0, 0.5, 20.0));
+            assert!(behind.z < 0.0);
+        }
+
+        let left = mirror_position(&car, MirrorSide::Left);
+        let right = mirror_position(&car, MirrorSide::Right);
+        assert!(left.x < 0.0 && right.x > 0.0);
+    }
+}
@@ -1,9 +1,14 @@
 // HUD Renderer - Custom bitmap font overlay system
 // Custom implementation to avoid egui/wgpu version conflicts
 
+use super::car_labels::HudLine;
+use super::hud_batch::{GlyphCache, HudBatch, HudStats};
 use anyhow::Result;
 use bytemuck::{Pod, Zeroable};
-use wgpu::util::DeviceExt;
+use std::time::Instant;
+
+/// Glyphs the HUD buffers hold before they first have to grow
+const INITIAL_GLYPH_CAPACITY: usize = 1024;
 
 /// Vertex for HUD text rendering
 #[repr(C)]
@@ -116,16 +121,40 @@ fn get_char_pixel(char_code: u8, x: usize, y: usize) -> bool {
     }
 }
 
+/// Vertex and index buffers sized for `capacity` glyphs
+fn create_glyph_buffers(device: &wgpu::Device, capacity: usize) -> (wgpu::Buffer, wgpu::Buffer) {
+    let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("HUD Vertex Buffer"),
+        size: (capacity * 4 * std::mem::size_of::<HudVertex>()) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("HUD Index Buffer"),
+        size: (capacity * 6 * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    (vertex_buffer, index_buffer)
+}
+
 /// HUD Renderer
+///
+/// Text is laid out through a glyph cache and batched into one vertex/index
+/// upload and one draw call per frame (the HUD has a single font atlas).
 pub struct HudRenderer {
     render_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
+    /// Glyphs the current buffers can hold
+    glyph_capacity: usize,
     _font_texture: wgpu::Texture,
     _font_view: wgpu::TextureView,
     _font_sampler: wgpu::Sampler,
     bind_group: wgpu::BindGroup,
-    num_indices: u32,
+    glyph_cache: GlyphCache,
+    batch: HudBatch,
+    stats: HudStats,
     screen_width: f32,
     screen_height: f32,
 }
@@ -266,31 +295,21 @@ impl HudRenderer {
             cache: None,
         });
 
-        // Create initial buffers (will be updated each frame)
-        let vertices: Vec<HudVertex> = vec![];
-        let indices: Vec<u16> = vec![];
-
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("HUD Vertex Buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("HUD Index Buffer"),
-            contents: bytemuck::cast_slice(&indices),
-            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-        });
+        // Persistent buffers, rewritten each frame and grown when too small
+        let (vertex_buffer, index_buffer) = create_glyph_buffers(device, INITIAL_GLYPH_CAPACITY);
 
         Ok(Self {
             render_pipeline,
             vertex_buffer,
             index_buffer,
+            glyph_capacity: INITIAL_GLYPH_CAPACITY,
             _font_texture: font_texture,
             _font_view: font_view,
             _font_sampler: font_sampler,
             bind_group,
-            num_indices: 0,
+            glyph_cache: GlyphCache::new(),
+            batch: HudBatch::new(),
+            stats: HudStats::default(),
             screen_width: config.width as f32,
             screen_height: config.height as f32,
         })
@@ -302,131 +321,67 @@ impl HudRenderer {
         self.screen_height = height as f32;
     }
 
-    /// Build text geometry for rendering
-    /// Returns (vertices, indices) for the text
-    fn build_text_geometry(
-        &self,
-        text: &str,
-        x: f32,
-        y: f32,
-        scale: f32,
-        color: [f32; 4],
-    ) -> (Vec<HudVertex>, Vec<u16>) {
-        const CHAR_WIDTH: f32 = 8.0;
-        const CHAR_HEIGHT: f32 = 16.0;
-        const _CHARS_PER_ROW: f32 = 16.0;
-        const ATLAS_WIDTH: f32 = 128.0;
-        const ATLAS_HEIGHT: f32 = 96.0;
-
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-
-        let char_screen_width = CHAR_WIDTH * scale;
-        let char_screen_height = CHAR_HEIGHT * scale;
-
-        for (i, ch) in text.chars().enumerate() {
-            let char_code = ch as u8;
-            if !(32..128).contains(&char_code) {
-                continue; // Skip non-printable characters
-            }
-
-            let char_idx = (char_code - 32) as usize;
-            let tex_col = (char_idx % 16) as f32;
-            let tex_row = (char_idx / 16) as f32;
-
-            // Texture coordinates in atlas
-            let tex_x = tex_col * CHAR_WIDTH / ATLAS_WIDTH;
-            let tex_y = tex_row * CHAR_HEIGHT / ATLAS_HEIGHT;
-            let tex_w = CHAR_WIDTH / ATLAS_WIDTH;
-            let tex_h = CHAR_HEIGHT / ATLAS_HEIGHT;
-
-            // Screen position (normalized to -1..1)
-            let screen_x = (x + i as f32 * char_screen_width) / self.screen_width * 2.0 - 1.0;
-            let screen_y = 1.0 - (y / self.screen_height * 2.0);
-            let screen_w = char_screen_width / self.screen_width * 2.0;
-            let screen_h = char_screen_height / self.screen_height * 2.0;
-
-            let base_index = vertices.len() as u16;
-
-            // Four vertices for this character quad
-            vertices.push(HudVertex {
-                position: [screen_x, screen_y],
-                tex_coords: [tex_x, tex_y],
-                color,
-            });
-            vertices.push(HudVertex {
-                position: [screen_x + screen_w, screen_y],
-                tex_coords: [tex_x + tex_w, tex_y],
-                color,
-            });
-            vertices.push(HudVertex {
-                position: [screen_x + screen_w, screen_y - screen_h],
-                tex_coords: [tex_x + tex_w, tex_y + tex_h],
-                color,
-            });
-            vertices.push(HudVertex {
-                position: [screen_x, screen_y - screen_h],
-                tex_coords: [tex_x, tex_y + tex_h],
-                color,
-            });
-
-            // Two triangles
-            indices.extend_from_slice(&[
-                base_index,
-                base_index + 1,
-                base_index + 2,
-                base_index,
-                base_index + 2,
-                base_index + 3,
-            ]);
-        }
-
-        (vertices, indices)
+    /// Timing and batching figures for the HUD pass
+    pub fn stats(&self) -> &HudStats {
+        &self.stats
     }
 
     /// Render HUD text overlay
     pub fn render(
         &mut self,
         device: &wgpu::Device,
-        _queue: &wgpu::Queue,
+        queue: &wgpu::Queue,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
-        lines: &[(String, f32, f32, f32, [f32; 4])], // (text, x, y, scale, color)
+        lines: &[HudLine],
     ) {
-        // Build geometry for all text lines
-        let mut all_vertices = Vec::new();
-        let mut all_indices = Vec::new();
-
-        for (text, x, y, scale, color) in lines {
-            let (mut vertices, indices) = self.build_text_geometry(text, *x, *y, *scale, *color);
+        let started = Instant::now();
+        let drawn = self.encode(device, queue, encoder, view, lines);
+
+        let (hits, misses) = self.glyph_cache.end_frame();
+        self.stats.cache_hits = hits;
+        self.stats.cache_misses = misses;
+        self.stats.glyphs = self.batch.glyph_count();
+        self.stats.draw_calls = usize::from(drawn);
+        self.stats.record(started.elapsed());
+    }
 
-            // Offset indices by current vertex count
-            let base_index = all_vertices.len() as u16;
-            all_indices.extend(indices.iter().map(|i| i + base_index));
-            all_vertices.append(&mut vertices);
+    /// Batch, upload and draw the lines; false if there was nothing to draw
+    fn encode(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        lines: &[HudLine],
+    ) -> bool {
+        self.batch.build(
+            &mut self.glyph_cache,
+            lines,
+            (self.screen_width, self.screen_height),
+        );
+        if self.batch.is_empty() {
+            return false; // Nothing to render
         }
 
-        if all_indices.is_empty() {
-            return; // Nothing to render
+        let glyphs = self.batch.glyph_count();
+        if glyphs > self.glyph_capacity {
+            self.glyph_capacity = glyphs.next_power_of_two();
+            (self.vertex_buffer, self.index_buffer) =
+                create_glyph_buffers(device, self.glyph_capacity);
         }
 
-        // Update buffers
-        self.num_indices = all_indices.len() as u32;
-
-        // Recreate buffers if size changed
-        if !all_vertices.is_empty() {
-            self.vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("HUD Vertex Buffer"),
-                contents: bytemuck::cast_slice(&all_vertices),
-                usage: wgpu::BufferUsages::VERTEX,
-            });
-
-            self.index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("HUD Index Buffer"),
-                contents: bytemuck::cast_slice(&all_indices),
-                usage: wgpu::BufferUsages::INDEX,
-            });
-        }
+        // One upload per buffer for the whole HUD
+        queue.write_buffer(
+            &self.vertex_buffer,
+            0,
+            bytemuck::cast_slice(&self.batch.vertices),
+        );
+        queue.write_buffer(
+            &self.index_buffer,
+            0,
+            bytemuck::cast_slice(&self.batch.indices),
+        );
 
         // Render pass
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -448,7 +403,9 @@ impl HudRenderer {
         render_pass.set_pipeline(&self.render_pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-        render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.batch.indices.len() as u32, 0, 0..1);
+
+        true
     }
 }
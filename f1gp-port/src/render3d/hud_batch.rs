@@ -0,0 +1,415 @@
+// HUD Text Batching
+// Glyph quad cache, per-frame batch assembly and HUD pass timing
+
+use super::car_labels::HudLine;
+use super::hud::HudVertex;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Glyph cell size in the font atlas (pixels)
+pub const GLYPH_WIDTH: f32 = 8.0;
+pub const GLYPH_HEIGHT: f32 = 16.0;
+
+/// Font atlas size (16 x 6 glyph cells)
+pub const ATLAS_WIDTH: f32 = 128.0;
+pub const ATLAS_HEIGHT: f32 = 96.0;
+
+/// Frames averaged by the HUD pass timer
+const TIMING_WINDOW: usize = 60;
+
+/// How a text run is drawn; part of the glyph cache key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub scale: f32,
+    pub color: [f32; 4],
+}
+
+impl TextStyle {
+    pub fn new(scale: f32, color: [f32; 4]) -> Self {
+        Self { scale, color }
+    }
+
+    /// Bit pattern used for exact comparison (NaN-safe, unlike `==`)
+    fn bits(&self) -> [u32; 5] {
+        [
+            self.scale.to_bits(),
+            self.color[0].to_bits(),
+            self.color[1].to_bits(),
+            self.color[2].to_bits(),
+            self.color[3].to_bits(),
+        ]
+    }
+}
+
+/// Lay out a text run as glyph quads relative to its top-left corner
+///
+/// Positions are in pixels (y down) so a run stays valid when it moves or the
+/// window is resized. Characters outside printable ASCII take up a cell but
+/// draw nothing.
+pub fn layout_text(text: &str, style: TextStyle) -> Vec<HudVertex> {
+    let cell_w = GLYPH_WIDTH * style.scale;
+    let cell_h = GLYPH_HEIGHT * style.scale;
+    let tex_w = GLYPH_WIDTH / ATLAS_WIDTH;
+    let tex_h = GLYPH_HEIGHT / ATLAS_HEIGHT;
+    let color = style.color;
+
+    let mut quads = Vec::with_capacity(text.len() * 4);
+    for (i, ch) in text.chars().enumerate() {
+        let code = ch as u32;
+        if !(32..128).contains(&code) {
+            continue;
+        }
+
+        let glyph = (code - 32) as usize;
+        let tex_x = (glyph % 16) as f32 * tex_w;
+        let tex_y = (glyph / 16) as f32 * tex_h;
+        let x = i as f32 * cell_w;
+
+        quads.extend_from_slice(&[
+            HudVertex {
+                position: [x, 0.0],
+                tex_coords: [tex_x, tex_y],
+                color,
+            },
+            HudVertex {
+                position: [x + cell_w, 0.0],
+                tex_coords: [tex_x + tex_w, tex_y],
+                color,
+            },
+            HudVertex {
+                position: [x + cell_w, cell_h],
+                tex_coords: [tex_x + tex_w, tex_y + tex_h],
+                color,
+            },
+            HudVertex {
+                position: [x, cell_h],
+                tex_coords: [tex_x, tex_y + tex_h],
+                color,
+            },
+        ]);
+    }
+    quads
+}
+
+/// A laid-out run and the last frame it was drawn in
+#[derive(Debug)]
+struct CachedRun {
+    style: [u32; 5],
+    quads: Vec<HudVertex>,
+    last_used: u64,
+}
+
+/// Glyph quads for recently drawn strings, keyed by content and style
+///
+/// Labels and control hints are laid out once and reused every frame.
+/// Strings with changing values (speed, lap times) miss and are laid out
+/// again; runs not drawn during a frame are dropped at `end_frame`, so stale
+/// values don't accumulate.
+#[derive(Debug, Default)]
+pub struct GlyphCache {
+    runs: HashMap<String, Vec<CachedRun>>,
+    frame: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl GlyphCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Quads for `text` in `style`, laying it out on a miss
+    pub fn get(&mut self, text: &str, style: TextStyle) -> &[HudVertex] {
+        let bits = style.bits();
+        let frame = self.frame;
+
+        // Only allocate a key for strings not seen yet
+        if !self.runs.contains_key(text) {
+            self.runs.insert(text.to_string(), Vec::new());
+        }
+        let runs = self.runs.get_mut(text).expect("inserted above");
+
+        let index = match runs.iter().position(|run| run.style == bits) {
+            Some(index) => {
+                self.hits += 1;
+                index
+            }
+            None => {
+                self.misses += 1;
+                runs.push(CachedRun {
+                    style: bits,
+                    quads: layout_text(text, style),
+                    last_used: frame,
+                });
+                runs.len() - 1
+            }
+        };
+
+        let run = &mut runs[index];
+        run.last_used = frame;
+        &run.quads
+    }
+
+    /// Drop runs not drawn this frame and return the frame's hit/miss counts
+    pub fn end_frame(&mut self) -> (usize, usize) {
+        let frame = self.frame;
+        self.runs.retain(|_, runs| {
+            runs.retain(|run| run.last_used == frame);
+            !runs.is_empty()
+        });
+
+        self.frame += 1;
+        let counts = (self.hits, self.misses);
+        self.hits = 0;
+        self.misses = 0;
+        counts
+    }
+
+    /// Number of cached runs
+    pub fn len(&self) -> usize {
+        self.runs.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+}
+
+/// All HUD text for one frame, ready for a single upload and draw
+///
+/// Vertices are in normalized device coordinates. The vectors are reused
+/// across frames so steady-state batching doesn't allocate.
+#[derive(Debug, Default)]
+pub struct HudBatch {
+    pub vertices: Vec<HudVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl HudBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Append a laid-out run at pixel position (x, y)
+    pub fn push_run(&mut self, quads: &[HudVertex], x: f32, y: f32, screen: (f32, f32)) {
+        let (width, height) = screen;
+
+        for quad in quads.chunks_exact(4) {
+            let base = self.vertices.len() as u32;
+            self.vertices.extend(quad.iter().map(|v| HudVertex {
+                position: [
+                    (x + v.position[0]) / width * 2.0 - 1.0,
+                    1.0 - (y + v.position[1]) / height * 2.0,
+                ],
+                ..*v
+            }));
+            self.indices
+                .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+    }
+
+    /// Rebuild the batch from HUD lines, laying out only uncached strings
+    pub fn build(&mut self, cache: &mut GlyphCache, lines: &[HudLine], screen: (f32, f32)) {
+        self.clear();
+        for (text, x, y, scale, color) in lines {
+            let quads = cache.get(text, TextStyle::new(*scale, *color));
+            self.push_run(quads, *x, *y, screen);
+        }
+    }
+
+    /// Number of glyph quads in the batch
+    pub fn glyph_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// Rolling CPU time of the HUD pass (batching, upload and draw encoding)
+#[derive(Debug, Clone, Default)]
+pub struct HudStats {
+    samples: VecDeque<Duration>,
+    /// Glyph quads drawn last frame
+    pub glyphs: usize,
+    /// Glyph cache hits and misses last frame
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    /// Draw calls issued last frame (one per font atlas)
+    pub draw_calls: usize,
+}
+
+impl HudStats {
+    /// Record one frame's HUD pass time
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.samples.len() == TIMING_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+    }
+
+    /// Mean pass time over the last `TIMING_WINDOW` frames
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Slowest pass time over the last `TIMING_WINDOW` frames
+    pub fn max(&self) -> Duration {
+        self.samples.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Short summary for the stats line
+    pub fn summary(&self) -> String {
+        format!(
+            "HUD: {:.2} ms (max {:.2}) | {} glyphs | cache {}/{} | {} draw",
+            self.average().as_secs_f64() * 1000.0,
+            self.max().as_secs_f64() * 1000.0,
+            self.glyphs,
+            self.cache_hits,
+            self.cache_hits + self.cache_misses,
+            self.draw_calls
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+    fn line(text: &str, x: f32, y: f32, scale: f32, color: [f32; 4]) -> HudLine {
+        (text.to_string(), x, y, scale, color)
+    }
+
+    #[test]
+    fn test_layout_skips_unprintable_but_advances() {
+        let quads = layout_text("A\u{2713}B", TextStyle::new(2.0, WHITE));
+        assert_eq!(quads.len(), 8);
+
+        // 'B' sits in the third cell, 16 pixels wide at scale 2
+        assert_eq!(quads[4].position, [32.0, 0.0]);
+        assert_eq!(quads[6].position, [48.0, 32.0]);
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_strings() {
+        let mut cache = GlyphCache::new();
+        let style = TextStyle::new(1.0, WHITE);
+
+        let first = cache.get("Controls:", style).to_vec();
+        assert_eq!(cache.end_frame(), (0, 1));
+
+        let second = cache.get("Controls:", style).to_vec();
+        assert_eq!(cache.end_frame(), (1, 0));
+        assert_eq!(first.len(), second.len());
+        assert_eq!(first[5].position, second[5].position);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_content_change_invalidates() {
+        let mut cache = GlyphCache::new();
+        let style = TextStyle::new(1.5, WHITE);
+
+        cache.get("Speed: 120 km/h", style);
+        cache.end_frame();
+
+        cache.get("Speed: 121 km/h", style);
+        assert_eq!(cache.end_frame(), (0, 1));
+
+        // The old value was not drawn last frame, so it is gone
+        assert_eq!(cache.len(), 1);
+        cache.get("Speed: 120 km/h", style);
+        assert_eq!(cache.end_frame(), (0, 1));
+    }
+
+    #[test]
+    fn test_style_change_invalidates() {
+        let mut cache = GlyphCache::new();
+
+        cache.get("Track: ON", TextStyle::new(1.5, WHITE));
+        cache.end_frame();
+
+        let red = cache.get("Track: ON", TextStyle::new(1.5, RED)).to_vec();
+        assert_eq!(cache.end_frame(), (0, 1));
+        assert!(red.iter().all(|v| v.color == RED));
+
+        let bigger = cache.get("Track: ON", TextStyle::new(2.0, RED)).to_vec();
+        assert_eq!(cache.end_frame(), (0, 1));
+        assert_eq!(bigger[1].position[0], 16.0);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_same_text_in_two_styles_coexists() {
+        let mut cache = GlyphCache::new();
+        for _ in 0..3 {
+            cache.get("P1", TextStyle::new(1.0, WHITE));
+            cache.get("P1", TextStyle::new(1.0, RED));
+            cache.end_frame();
+        }
+        assert_eq!(cache.len(), 2);
+
+        cache.get("P1", TextStyle::new(1.0, RED));
+        assert_eq!(cache.end_frame(), (1, 0));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_batch_places_runs_in_ndc() {
+        let mut cache = GlyphCache::new();
+        let mut batch = HudBatch::new();
+        let lines = vec![
+            line("AB", 0.0, 0.0, 1.0, WHITE),
+            line("C", 400.0, 300.0, 1.0, RED),
+        ];
+
+        batch.build(&mut cache, &lines, (800.0, 600.0));
+        assert_eq!(batch.glyph_count(), 3);
+        assert_eq!(batch.indices.len(), 18);
+        assert_eq!(batch.vertices[0].position, [-1.0, 1.0]);
+        assert_eq!(batch.vertices[8].position, [0.0, 0.0]);
+        assert_eq!(&batch.indices[12..], &[8, 9, 10, 8, 10, 11]);
+
+        // Moving a line or resizing the window reuses the cached quads
+        cache.end_frame();
+        let moved = vec![line("AB", 8.0, 0.0, 1.0, WHITE)];
+        batch.build(&mut cache, &moved, (1600.0, 1200.0));
+        assert_eq!(cache.end_frame(), (1, 0));
+        assert_eq!(batch.glyph_count(), 2);
+        assert!((batch.vertices[0].position[0] + 0.99).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stats_window() {
+        let mut stats = HudStats::default();
+        assert_eq!(stats.average(), Duration::ZERO);
+
+        stats.record(Duration::from_millis(4));
+        for _ in 0..TIMING_WINDOW {
+            stats.record(Duration::from_millis(1));
+        }
+        assert_eq!(stats.average(), Duration::from_millis(1));
+        assert_eq!(stats.max(), Duration::from_millis(1));
+
+        stats.glyphs = 120;
+        stats.cache_hits = 14;
+        stats.cache_misses = 1;
+        stats.draw_calls = 1;
+        assert_eq!(
+            stats.summary(),
+            "HUD: 1.00 ms (max 1.00) | 120 glyphs | cache 14/15 | 1 draw"
+        );
+    }
+}
@@ -2,13 +2,19 @@
 // Phase 6: 3D Graphics Implementation
 
 pub mod camera3d;
+pub mod car_labels;
 pub mod car_model;
+pub mod cockpit;
 pub mod hud;
+pub mod hud_batch;
 pub mod renderer;
 pub mod track_mesh;
 
 pub use camera3d::{Camera3D, CameraMode};
-pub use car_model::{CarModel, CarVertex, LODLevel};
+pub use car_labels::{car_number_labels, label_alpha, HudLine};
+pub use car_model::{build_car_instances, CarInstance, CarModel, CarVertex, LODLevel};
+pub use cockpit::{MirrorSchedule, MirrorSettings, MirrorSide};
 pub use hud::{HudRenderer, HudVertex};
+pub use hud_batch::{GlyphCache, HudBatch, HudStats, TextStyle};
 pub use renderer::Renderer3D;
 pub use track_mesh::{TrackMesh, TrackVertex};
@@ -1,14 +1,20 @@
 // Renderer3D - wgpu-based 3D renderer
 // Stage 6.1: Basic 3D Setup
 // Stage 6.2: Track Rendering Integration
+// Cockpit view: steering wheel and rear-view mirrors
 
 use anyhow::Result;
 use bytemuck::{Pod, Zeroable};
-use glam::Mat4;
+use glam::{Mat4, Vec3};
 use wgpu::util::DeviceExt;
 
-use super::camera3d::Camera3D;
-use super::car_model::{CarModel, CarVertex};
+use super::camera3d::{Camera3D, CameraMode};
+use super::car_labels::{car_number_labels, HudLine};
+use super::car_model::{build_car_instances, CarInstance, CarModel, CarVertex, MAX_CAR_INSTANCES};
+use super::cockpit::{
+    self, mirror_projection_matrix, mirror_rect, mirror_view_matrix, MirrorRect, MirrorSchedule,
+    MirrorSettings, MirrorSide,
+};
 use super::track_mesh::{TrackMesh, TrackVertex};
 use crate::data::Track;
 use crate::game::GameState;
@@ -54,8 +60,12 @@ impl CameraUniforms {
 
     fn update(&mut self, camera: &Camera3D) {
         let view_proj = camera.projection_matrix() * camera.view_matrix();
+        self.set(view_proj, camera.position());
+    }
+
+    fn set(&mut self, view_proj: Mat4, camera_pos: Vec3) {
         self.view_proj = view_proj.to_cols_array_2d();
-        self.camera_pos = camera.position().to_array();
+        self.camera_pos = camera_pos.to_array();
     }
 }
 
@@ -80,25 +90,145 @@ impl LightUniforms {
     }
 }
 
-/// Uniforms for model transformation (per-car)
-#[repr(C)]
-#[derive(Copy, Clone, Debug, Pod, Zeroable)]
-struct ModelUniforms {
-    model: [[f32; 4]; 4],
+/// Offscreen target and camera for one rear-view mirror
+struct MirrorTarget {
+    side: MirrorSide,
+    rect: MirrorRect,
+    _color_texture: wgpu::Texture,
+    color_view: wgpu::TextureView,
+    _depth_texture: wgpu::Texture,
+    depth_view: wgpu::TextureView,
+    camera_uniforms: CameraUniforms,
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    composite_bind_group: wgpu::BindGroup,
 }
 
-impl ModelUniforms {
-    fn new() -> Self {
+/// Shared state needed to (re)create mirror targets
+struct MirrorResources<'a> {
+    format: wgpu::TextureFormat,
+    camera_layout: &'a wgpu::BindGroupLayout,
+    composite_layout: &'a wgpu::BindGroupLayout,
+    sampler: &'a wgpu::Sampler,
+}
+
+impl MirrorTarget {
+    fn new(
+        device: &wgpu::Device,
+        resources: &MirrorResources,
+        side: MirrorSide,
+        screen_width: u32,
+        screen_height: u32,
+        settings: &MirrorSettings,
+    ) -> Self {
+        let rect = mirror_rect(side, screen_width as f32, screen_height as f32);
+        let (width, height) = rect.texture_size(settings.resolution_scale);
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let color_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mirror Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: resources.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let color_view = color_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mirror Depth Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let camera_uniforms = CameraUniforms::new();
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mirror Camera Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniforms]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Mirror Camera Bind Group"),
+            layout: resources.camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let composite_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Mirror Composite Bind Group"),
+            layout: resources.composite_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&color_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(resources.sampler),
+                },
+            ],
+        });
+
         Self {
-            model: Mat4::IDENTITY.to_cols_array_2d(),
+            side,
+            rect,
+            _color_texture: color_texture,
+            color_view,
+            _depth_texture: depth_texture,
+            depth_view,
+            camera_uniforms,
+            camera_buffer,
+            camera_bind_group,
+            composite_bind_group,
         }
     }
+}
 
-    fn update(&mut self, transform: Mat4) {
-        self.model = transform.to_cols_array_2d();
-    }
+/// Create the offscreen targets for both mirrors
+fn create_mirrors(
+    device: &wgpu::Device,
+    resources: &MirrorResources,
+    screen_width: u32,
+    screen_height: u32,
+    settings: &MirrorSettings,
+) -> Vec<MirrorTarget> {
+    MirrorSide::ALL
+        .iter()
+        .map(|&side| {
+            MirrorTarget::new(
+                device,
+                resources,
+                side,
+                screen_width,
+                screen_height,
+                settings,
+            )
+        })
+        .collect()
 }
 
+/// Sky color behind the skybox
+const SKY_CLEAR_COLOR: wgpu::Color = wgpu::Color {
+    r: 0.53,
+    g: 0.81,
+    b: 0.92,
+    a: 1.0,
+};
+
 /// 3D Renderer using wgpu
 pub struct Renderer3D {
     pub camera: Camera3D,
@@ -132,13 +262,26 @@ pub struct Renderer3D {
     car_vertex_buffer: wgpu::Buffer,
     car_index_buffer: wgpu::Buffer,
     car_index_count: u32,
-    model_buffer: wgpu::Buffer,
-    model_bind_group_layout: wgpu::BindGroupLayout,
+    car_instance_buffer: wgpu::Buffer,
 
     // Skybox rendering (Stage 6.5)
     skybox_pipeline: wgpu::RenderPipeline,
     skybox_vertex_buffer: wgpu::Buffer,
     skybox_vertex_count: u32,
+
+    // Cockpit view: steering wheel and rear-view mirrors
+    cockpit_pipeline: wgpu::RenderPipeline,
+    wheel_vertex_buffer: wgpu::Buffer,
+    mirror_pipeline: wgpu::RenderPipeline,
+    mirror_camera_layout: wgpu::BindGroupLayout,
+    mirror_composite_layout: wgpu::BindGroupLayout,
+    mirror_sampler: wgpu::Sampler,
+    mirror_settings: MirrorSettings,
+    mirror_schedule: MirrorSchedule,
+    mirrors: Vec<MirrorTarget>,
+    surface_format: wgpu::TextureFormat,
+    screen_width: u32,
+    screen_height: u32,
 }
 
 impl Renderer3D {
@@ -365,28 +508,12 @@ impl Renderer3D {
             usage: wgpu::BufferUsages::VERTEX,
         });
 
-        // Create model bind group layout (Group 2 for car shader)
-        let model_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Model Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            });
-
-        // Create model uniform buffer
-        let model_uniforms = ModelUniforms::new();
-        let model_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Model Buffer"),
-            contents: bytemuck::cast_slice(&[model_uniforms]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        // Create car instance buffer (per-car transform + livery, one draw call)
+        let car_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Car Instance Buffer"),
+            size: (MAX_CAR_INSTANCES * std::mem::size_of::<CarInstance>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
         });
 
         // Load car shader
@@ -398,11 +525,7 @@ impl Renderer3D {
         // Create car render pipeline
         let car_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Car Pipeline Layout"),
-            bind_group_layouts: &[
-                &camera_bind_group_layout,
-                &light_bind_group_layout,
-                &model_bind_group_layout,
-            ],
+            bind_group_layouts: &[&camera_bind_group_layout, &light_bind_group_layout],
             push_constant_ranges: &[],
         });
 
@@ -412,7 +535,7 @@ impl Renderer3D {
             vertex: wgpu::VertexState {
                 module: &car_shader,
                 entry_point: Some("vs_main"),
-                buffers: &[CarVertex::desc()],
+                buffers: &[CarVertex::desc(), CarInstance::desc()],
                 compilation_options: Default::default(),
             },
             fragment: Some(wgpu::FragmentState {
@@ -450,7 +573,7 @@ impl Renderer3D {
             cache: None,
         });
 
-        // Create default car model (red for now, will be replaced per-team)
+        // Create shared car model (livery colors come from the instance buffer)
         let car_model = CarModel::create_f1_car([1.0, 0.0, 0.0, 1.0]);
 
         // Create car vertex buffer
@@ -584,6 +707,149 @@ impl Renderer3D {
 
         let skybox_vertex_count = skybox_vertices.len() as u32;
 
+        // Cockpit steering wheel: drawn over the scene, both sides visible
+        let cockpit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Cockpit Pipeline"),
+            layout: Some(&basic_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &basic_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[Vertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &basic_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let wheel_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Steering Wheel Vertex Buffer"),
+            size: (cockpit::steering_wheel_vertex_count() * std::mem::size_of::<Vertex>())
+                as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Rear-view mirrors: offscreen scene renders composited into the top corners
+        let mirror_composite_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Mirror Composite Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        let mirror_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Mirror Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let mirror_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mirror Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/mirror.wgsl").into()),
+        });
+
+        let mirror_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Mirror Composite Pipeline"),
+            layout: Some(
+                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Mirror Composite Pipeline Layout"),
+                    bind_group_layouts: &[&mirror_composite_layout],
+                    push_constant_ranges: &[],
+                }),
+            ),
+            vertex: wgpu::VertexState {
+                module: &mirror_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &mirror_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let mirror_settings = MirrorSettings::default();
+        let mirrors = create_mirrors(
+            device,
+            &MirrorResources {
+                format: config.format,
+                camera_layout: &camera_bind_group_layout,
+                composite_layout: &mirror_composite_layout,
+                sampler: &mirror_sampler,
+            },
+            config.width,
+            config.height,
+            &mirror_settings,
+        );
+
         Ok(Self {
             camera,
             camera_uniforms,
@@ -606,11 +872,22 @@ impl Renderer3D {
             car_vertex_buffer,
             car_index_buffer,
             car_index_count,
-            model_buffer,
-            model_bind_group_layout,
+            car_instance_buffer,
             skybox_pipeline,
             skybox_vertex_buffer,
             skybox_vertex_count,
+            cockpit_pipeline,
+            wheel_vertex_buffer,
+            mirror_pipeline,
+            mirror_camera_layout: camera_bind_group_layout,
+            mirror_composite_layout,
+            mirror_sampler,
+            mirror_settings,
+            mirror_schedule: MirrorSchedule::new(),
+            mirrors,
+            surface_format: config.format,
+            screen_width: config.width,
+            screen_height: config.height,
         })
     }
 
@@ -660,12 +937,7 @@ impl Renderer3D {
                 view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: 0.53,
-                        g: 0.81,
-                        b: 0.92,
-                        a: 1.0,
-                    }),
+                    load: wgpu::LoadOp::Clear(SKY_CLEAR_COLOR),
                     store: wgpu::StoreOp::Store,
                 },
                 depth_slice: None,
@@ -705,24 +977,25 @@ impl Renderer3D {
         Ok(())
     }
 
-    /// Render cars from game state
+    /// Render all cars (player + AI) with a single instanced draw call
     pub fn render_cars(
         &self,
-        device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
         game_state: &GameState,
         queue: &wgpu::Queue,
     ) -> Result<()> {
-        // Create model bind group for rendering
-        let model_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Model Bind Group"),
-            layout: &self.model_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: self.model_buffer.as_entire_binding(),
-            }],
-        });
+        let cars = std::iter::once(game_state.player_car()).chain(game_state.ai_cars());
+        let liveries = game_state.car_liveries();
+        let instances = build_car_instances(cars.zip(liveries.iter().map(|(livery, _)| *livery)));
+        if instances.is_empty() {
+            return Ok(());
+        }
+        queue.write_buffer(
+            &self.car_instance_buffer,
+            0,
+            bytemuck::cast_slice(&instances),
+        );
 
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Car Render Pass"),
@@ -750,35 +1023,262 @@ impl Renderer3D {
         render_pass.set_pipeline(&self.car_pipeline);
         render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
         render_pass.set_bind_group(1, &self.light_bind_group, &[]);
-        render_pass.set_bind_group(2, &model_bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.car_vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.car_instance_buffer.slice(..));
         render_pass.set_index_buffer(self.car_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.car_index_count, 0, 0..instances.len() as u32);
 
-        // Render player car
-        let player_car = game_state.player_car();
-        let transform = CarModel::get_transform_matrix(player_car);
-        let mut model_uniforms = ModelUniforms::new();
-        model_uniforms.update(transform);
-        queue.write_buffer(
-            &self.model_buffer,
-            0,
-            bytemuck::cast_slice(&[model_uniforms]),
+        drop(render_pass);
+
+        Ok(())
+    }
+
+    /// HUD lines with car numbers above nearby opponents (distance-faded)
+    pub fn car_label_lines(
+        &self,
+        game_state: &GameState,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Vec<HudLine> {
+        let view_proj = self.camera.projection_matrix() * self.camera.view_matrix();
+        let cars: Vec<_> = game_state
+            .ai_cars()
+            .iter()
+            .zip(game_state.car_liveries().into_iter().skip(1))
+            .map(|(car, (livery, number))| (car.body.position, number, livery))
+            .collect();
+
+        car_number_labels(
+            view_proj,
+            self.camera.position(),
+            &cars,
+            screen_width,
+            screen_height,
+        )
+    }
+
+    /// HUD lines for the steering wheel display (cockpit view only)
+    pub fn dashboard_lines(
+        &self,
+        game_state: &GameState,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Vec<HudLine> {
+        if self.camera.mode != CameraMode::Cockpit {
+            return Vec::new();
+        }
+        let view_proj = self.camera.projection_matrix() * self.camera.view_matrix();
+        cockpit::dashboard_lines(
+            view_proj,
+            game_state.player_car(),
+            screen_width,
+            screen_height,
+        )
+    }
+
+    /// Rear-view mirror options
+    pub fn mirror_settings(&self) -> &MirrorSettings {
+        &self.mirror_settings
+    }
+
+    /// Change rear-view mirror options, recreating the mirror targets
+    pub fn set_mirror_settings(&mut self, device: &wgpu::Device, settings: MirrorSettings) {
+        self.mirror_settings = settings;
+        self.rebuild_mirrors(device);
+    }
+
+    fn rebuild_mirrors(&mut self, device: &wgpu::Device) {
+        self.mirrors = create_mirrors(
+            device,
+            &MirrorResources {
+                format: self.surface_format,
+                camera_layout: &self.mirror_camera_layout,
+                composite_layout: &self.mirror_composite_layout,
+                sampler: &self.mirror_sampler,
+            },
+            self.screen_width,
+            self.screen_height,
+            &self.mirror_settings,
         );
-        render_pass.draw_indexed(0..self.car_index_count, 0, 0..1);
+        self.mirror_schedule.invalidate();
+    }
+
+    /// Render the cockpit overlay: steering wheel and rear-view mirrors
+    ///
+    /// Does nothing outside cockpit view. Call after `render_cars`.
+    pub fn render_cockpit(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        game_state: &GameState,
+        queue: &wgpu::Queue,
+    ) -> Result<()> {
+        if self.camera.mode != CameraMode::Cockpit {
+            // Mirror contents are out of date by the time we come back
+            self.mirror_schedule.invalidate();
+            return Ok(());
+        }
 
-        // TODO: Render AI cars when we have access to them
+        let wheel = cockpit::steering_wheel_vertices(game_state.player_car());
+        queue.write_buffer(&self.wheel_vertex_buffer, 0, bytemuck::cast_slice(&wheel));
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Cockpit Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.cockpit_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.wheel_vertex_buffer.slice(..));
+        render_pass.draw(0..wheel.len() as u32, 0..1);
 
         drop(render_pass);
 
+        if self.mirror_settings.enabled {
+            if self.mirror_schedule.tick(&self.mirror_settings) {
+                self.render_mirror_scenes(encoder, game_state, queue);
+            }
+            self.composite_mirrors(encoder, view);
+        }
+
         Ok(())
     }
 
+    /// Render the scene behind the player into each mirror texture
+    ///
+    /// Mirrors use a shorter far plane and leave out the player's own car.
+    fn render_mirror_scenes(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        game_state: &GameState,
+        queue: &wgpu::Queue,
+    ) {
+        let player = game_state.player_car();
+        for mirror in &mut self.mirrors {
+            let rect = mirror.rect;
+            let view_proj = mirror_projection_matrix(&rect, &self.mirror_settings)
+                * mirror_view_matrix(player, mirror.side);
+            mirror
+                .camera_uniforms
+                .set(view_proj, cockpit::mirror_position(player, mirror.side));
+            queue.write_buffer(
+                &mirror.camera_buffer,
+                0,
+                bytemuck::cast_slice(&[mirror.camera_uniforms]),
+            );
+        }
+
+        let cars = std::iter::once(player).chain(game_state.ai_cars());
+        let liveries = game_state.car_liveries();
+        let instances = build_car_instances(cars.zip(liveries.iter().map(|(livery, _)| *livery)));
+        queue.write_buffer(
+            &self.car_instance_buffer,
+            0,
+            bytemuck::cast_slice(&instances),
+        );
+
+        for mirror in &self.mirrors {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Mirror Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &mirror.color_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(SKY_CLEAR_COLOR),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &mirror.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.skybox_pipeline);
+            render_pass.set_bind_group(0, &mirror.camera_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.skybox_vertex_buffer.slice(..));
+            render_pass.draw(0..self.skybox_vertex_count, 0..1);
+
+            if let (Some(vertex_buffer), Some(index_buffer)) =
+                (&self.track_vertex_buffer, &self.track_index_buffer)
+            {
+                render_pass.set_pipeline(&self.track_pipeline);
+                render_pass.set_bind_group(0, &mirror.camera_bind_group, &[]);
+                render_pass.set_bind_group(1, &self.light_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                render_pass.draw_indexed(0..self.track_index_count, 0, 0..1);
+            }
+
+            // Instance 0 is the player's car, which the mirror camera sits inside
+            if instances.len() > 1 {
+                render_pass.set_pipeline(&self.car_pipeline);
+                render_pass.set_bind_group(0, &mirror.camera_bind_group, &[]);
+                render_pass.set_bind_group(1, &self.light_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, self.car_vertex_buffer.slice(..));
+                render_pass.set_vertex_buffer(1, self.car_instance_buffer.slice(..));
+                render_pass
+                    .set_index_buffer(self.car_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                render_pass.draw_indexed(0..self.car_index_count, 0, 1..instances.len() as u32);
+            }
+        }
+    }
+
+    /// Draw the mirror textures into their screen corners
+    fn composite_mirrors(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Mirror Composite Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.mirror_pipeline);
+        for mirror in &self.mirrors {
+            let rect = mirror.rect;
+            render_pass.set_viewport(rect.x, rect.y, rect.width, rect.height, 0.0, 1.0);
+            render_pass.set_bind_group(0, &mirror.composite_bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+
     /// Handle window resize
     pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
         if width > 0 && height > 0 {
             let aspect_ratio = width as f32 / height as f32;
             self.camera.set_aspect_ratio(aspect_ratio);
 
+            self.screen_width = width;
+            self.screen_height = height;
+            self.rebuild_mirrors(device);
+
             // Recreate depth texture
             self.depth_texture = device.create_texture(&wgpu::TextureDescriptor {
                 label: Some("Depth Texture"),
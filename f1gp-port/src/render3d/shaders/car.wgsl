@@ -1,5 +1,6 @@
 // Car Rendering Shader - Stage 6.3 & 6.5
 // Renders 3D car models with lighting, specular highlights, and fog
+// All cars are drawn in one instanced call; transform and livery are per-instance
 
 struct CameraUniforms {
     view_proj: mat4x4<f32>,
@@ -14,23 +15,26 @@ struct LightUniforms {
     ambient: f32,
 }
 
-struct ModelUniforms {
-    model: mat4x4<f32>,
-}
-
 @group(0) @binding(0)
 var<uniform> camera: CameraUniforms;
 
 @group(1) @binding(0)
 var<uniform> light: LightUniforms;
 
-@group(2) @binding(0)
-var<uniform> model: ModelUniforms;
-
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) color: vec4<f32>,
+    @location(3) livery_slot: f32,
+}
+
+struct InstanceInput {
+    @location(4) model_0: vec4<f32>,
+    @location(5) model_1: vec4<f32>,
+    @location(6) model_2: vec4<f32>,
+    @location(7) model_3: vec4<f32>,
+    @location(8) primary: vec4<f32>,
+    @location(9) secondary: vec4<f32>,
 }
 
 struct VertexOutput {
@@ -41,20 +45,34 @@ struct VertexOutput {
 }
 
 @vertex
-fn vs_main(in: VertexInput) -> VertexOutput {
+fn vs_main(in: VertexInput, instance: InstanceInput) -> VertexOutput {
     var out: VertexOutput;
 
+    let model = mat4x4<f32>(
+        instance.model_0,
+        instance.model_1,
+        instance.model_2,
+        instance.model_3,
+    );
+
     // Transform position by model matrix, then by view-projection
-    let world_position = model.model * vec4<f32>(in.position, 1.0);
+    let world_position = model * vec4<f32>(in.position, 1.0);
     out.clip_position = camera.view_proj * world_position;
     out.world_position = world_position.xyz;
 
     // Transform normal by model matrix (should use inverse transpose for non-uniform scaling)
     // For now, simple rotation is fine since we don't have scaling
-    let world_normal = (model.model * vec4<f32>(in.normal, 0.0)).xyz;
+    let world_normal = (model * vec4<f32>(in.normal, 0.0)).xyz;
     out.world_normal = normalize(world_normal);
 
-    out.color = in.color;
+    // Livery slot: 0 = fixed vertex color, 1 = primary, 2 = secondary
+    if (in.livery_slot > 1.5) {
+        out.color = instance.secondary;
+    } else if (in.livery_slot > 0.5) {
+        out.color = instance.primary;
+    } else {
+        out.color = in.color;
+    }
 
     return out;
 }
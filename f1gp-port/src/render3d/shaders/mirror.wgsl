@@ -0,0 +1,35 @@
+// Mirror Composite Shader
+// Draws a rear-view mirror texture into its screen-corner viewport, flipped horizontally
+
+@group(0) @binding(0)
+var mirror_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var mirror_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // One triangle covering the whole viewport
+    var out: VertexOutput;
+    let x = f32((index << 1u) & 2u);
+    let y = f32(index & 2u);
+    out.clip_position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, y);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Dark housing around the glass
+    let edge = min(min(in.uv.x, 1.0 - in.uv.x), min(in.uv.y, 1.0 - in.uv.y));
+    if (edge < 0.03) {
+        return vec4<f32>(0.05, 0.05, 0.05, 1.0);
+    }
+
+    // Mirrors show the scene left-right reversed
+    return textureSample(mirror_texture, mirror_sampler, vec2<f32>(1.0 - in.uv.x, in.uv.y));
+}
@@ -56,7 +56,6 @@ impl TrackMesh {
         let mut bounds_max = Vec3::new(f32::MIN, f32::MIN, f32::MIN);
 
         // Generate mesh for each section
-        let mut current_height = 0.0;
         for (i, section) in track.sections.iter().enumerate() {
             let next_section = track.sections.get(i + 1).or_else(|| track.sections.first());
 
@@ -64,7 +63,6 @@ impl TrackMesh {
                 Self::generate_section_mesh(
                     section,
                     next,
-                    &mut current_height,
                     &mut vertices,
                     &mut indices,
                     &mut bounds_min,
@@ -88,7 +86,6 @@ impl TrackMesh {
     fn generate_section_mesh(
         section: &TrackSection,
         next_section: &TrackSection,
-        current_height: &mut f32,
         vertices: &mut Vec<TrackVertex>,
         indices: &mut Vec<u32>,
         bounds_min: &mut Vec3,
@@ -96,30 +93,27 @@ impl TrackMesh {
     ) {
         let base_index = vertices.len() as u32;
 
-        // Update cumulative height
-        *current_height += section.elevation;
-
-        // Section positions
-        let pos1 = section.position + Vec3::Y * *current_height;
-        let pos2 = next_section.position + Vec3::Y * (*current_height + next_section.elevation);
+        // Section positions already carry elevation, so this is the 3D centerline
+        let pos1 = section.position;
+        let pos2 = next_section.position;
 
         // Calculate perpendicular direction for track width
         let forward = (pos2 - pos1).normalize();
         let right = Vec3::new(-forward.z, 0.0, forward.x).normalize();
 
+        // Tilt the cross-section by each end's camber
+        let right_dir1 = Self::banked_right(right, section.banking);
+        let right_dir2 = Self::banked_right(right, next_section.banking);
+
         // Half widths
         let half_width1 = section.width * 0.5;
         let half_width2 = next_section.width * 0.5;
 
-        // Banking rotation (simplified - rotate around forward axis)
-        let banking_offset1 = right * section.banking.tan() * half_width1;
-        let banking_offset2 = right * next_section.banking.tan() * half_width2;
-
         // Generate vertices for road surface
-        let left1 = pos1 - right * half_width1 + banking_offset1;
-        let right1 = pos1 + right * half_width1 + banking_offset1;
-        let left2 = pos2 - right * half_width2 + banking_offset2;
-        let right2 = pos2 + right * half_width2 + banking_offset2;
+        let left1 = pos1 - right_dir1 * half_width1;
+        let right1 = pos1 + right_dir1 * half_width1;
+        let left2 = pos2 - right_dir2 * half_width2;
+        let right2 = pos2 + right_dir2 * half_width2;
 
         // Surface color based on type
         let color = Self::surface_color(section.surface);
@@ -159,24 +153,28 @@ impl TrackMesh {
                 next_section,
                 pos1,
                 pos2,
-                forward,
-                right,
-                *current_height,
+                right_dir1,
+                right_dir2,
                 vertices,
                 indices,
             );
         }
     }
 
+    /// Rotate the horizontal right vector about the direction of travel.
+    /// Positive banking leans the road right, lowering the right edge.
+    fn banked_right(right: Vec3, banking: f32) -> Vec3 {
+        right * banking.cos() - Vec3::Y * banking.sin()
+    }
+
     /// Generate kerb geometry at track edges
     fn generate_kerbs(
         section: &TrackSection,
         next_section: &TrackSection,
         pos1: Vec3,
         pos2: Vec3,
-        _forward: Vec3,
-        right: Vec3,
-        _height: f32,
+        right1: Vec3,
+        right2: Vec3,
         vertices: &mut Vec<TrackVertex>,
         indices: &mut Vec<u32>,
     ) {
@@ -190,10 +188,10 @@ impl TrackMesh {
         let kerb_color = [1.0, 0.2, 0.2, 1.0]; // Red
 
         // Left kerb
-        let left_inner1 = pos1 - right * half_width1;
-        let left_outer1 = pos1 - right * (half_width1 + kerb_width);
-        let left_inner2 = pos2 - right * half_width2;
-        let left_outer2 = pos2 - right * (half_width2 + kerb_width);
+        let left_inner1 = pos1 - right1 * half_width1;
+        let left_outer1 = pos1 - right1 * (half_width1 + kerb_width);
+        let left_inner2 = pos2 - right2 * half_width2;
+        let left_outer2 = pos2 - right2 * (half_width2 + kerb_width);
 
         let base_index = vertices.len() as u32;
 
@@ -234,10 +232,10 @@ impl TrackMesh {
         ]);
 
         // Right kerb (similar)
-        let right_inner1 = pos1 + right * half_width1;
-        let right_outer1 = pos1 + right * (half_width1 + kerb_width);
-        let right_inner2 = pos2 + right * half_width2;
-        let right_outer2 = pos2 + right * (half_width2 + kerb_width);
+        let right_inner1 = pos1 + right1 * half_width1;
+        let right_outer1 = pos1 + right1 * (half_width1 + kerb_width);
+        let right_inner2 = pos2 + right2 * half_width2;
+        let right_outer2 = pos2 + right2 * (half_width2 + kerb_width);
 
         let base_index = vertices.len() as u32;
 
@@ -372,14 +370,12 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_track_mesh_creation() {
-        // Create simple test track
-        let track = Track {
+    fn make_track(sections: Vec<TrackSection>) -> Track {
+        Track {
             name: "Test Track".to_string(),
             length: 100.0,
             object_shapes: vec![],
-            sections: vec![make_section(0.0), make_section(10.0)],
+            sections,
             racing_line: crate::data::RacingLine {
                 displacement: 0,
                 segments: vec![],
@@ -392,12 +388,20 @@ mod tests {
                     rear_wing: 10,
                     gear_ratios: [5, 8, 11, 14, 17, 20],
                     brake_balance: 50,
+                    tire_compound: crate::game::TireCompound::C,
                 },
             },
             pit_lane: vec![],
             cameras: vec![],
+            annotations: Default::default(),
             checksum: 0,
-        };
+        }
+    }
+
+    #[test]
+    fn test_track_mesh_creation() {
+        // Create simple test track
+        let track = make_track(vec![make_section(0.0), make_section(10.0)]);
 
         let mesh = TrackMesh::from_track(&track);
 
@@ -411,6 +415,49 @@ mod tests {
         assert!(mesh.bounds_min.z < mesh.bounds_max.z);
     }
 
+    #[test]
+    fn test_mesh_follows_elevation() {
+        let mut start = make_section(0.0);
+        start.surface = SurfaceType::Grass;
+        let mut crest = make_section(10.0);
+        crest.position.y = 1.0;
+        crest.elevation = 1.0;
+
+        let mesh = TrackMesh::from_track(&make_track(vec![start, crest]));
+
+        // First quad climbs from the start section to the crest
+        let y: Vec<f32> = mesh.vertices[..4].iter().map(|v| v.position[1]).collect();
+        assert_eq!(y[0], 0.0);
+        assert_eq!(y[1], 0.0);
+        assert!((y[2] - 1.0).abs() < 1e-5);
+        assert!((y[3] - 1.0).abs() < 1e-5);
+        assert!((mesh.bounds_max.y - 1.0).abs() < 1e-5);
+
+        // Normals tilt back against the climb
+        assert!(mesh.vertices[0].normal[2] < 0.0);
+    }
+
+    #[test]
+    fn test_mesh_applies_banking() {
+        let mut start = make_section(0.0);
+        start.surface = SurfaceType::Grass;
+        start.banking = 0.1;
+        let mut end = make_section(10.0);
+        end.banking = 0.1;
+
+        let mesh = TrackMesh::from_track(&make_track(vec![start, end]));
+
+        let left = mesh.vertices[0].position;
+        let right = mesh.vertices[1].position;
+        let expected = 12.0 * 0.1f32.sin();
+
+        // Banked right: left edge above the centerline, right edge below
+        assert!(left[1] > 0.0);
+        assert!(right[1] < 0.0);
+        assert!((left[1] - right[1] - expected).abs() < 1e-4);
+        assert!(((Vec3::from(left) - Vec3::from(right)).length() - 12.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_surface_colors() {
         use crate::data::SurfaceType;
@@ -28,6 +28,9 @@ pub enum MenuAction {
     /// Return to main menu
     MainMenu,
 
+    /// Write the player's car setup to a setup file
+    ExportSetup,
+
     /// Show options
     Options,
 
@@ -140,6 +143,7 @@ impl Menu {
             MenuItem::new(format!("OPPONENTS: {}", num_opponents), MenuAction::None),
             MenuItem::new(format!("WEATHER: {}", weather_text), MenuAction::None),
             MenuItem::new("START RACE", MenuAction::StartRace),
+            MenuItem::new("EXPORT SETUP", MenuAction::ExportSetup),
             MenuItem::new("BACK", MenuAction::MainMenu),
         ];
 
@@ -154,9 +158,10 @@ impl Menu {
     }
 
     /// Create pause menu
-    pub fn pause_menu(screen_width: u32, screen_height: u32) -> Self {
+    pub fn pause_menu(screen_width: u32, screen_height: u32, livery_name: &str) -> Self {
         let items = vec![
             MenuItem::new("RESUME", MenuAction::Resume),
+            MenuItem::new(format!("LIVERY: {}", livery_name), MenuAction::None),
             MenuItem::new("RESTART", MenuAction::Restart),
             MenuItem::new("MAIN MENU", MenuAction::MainMenu),
         ];
@@ -372,9 +377,20 @@ mod tests {
 
     #[test]
     fn test_pause_menu() {
-        let menu = Menu::pause_menu(800, 600);
+        let menu = Menu::pause_menu(800, 600, "MCLAREN");
         assert_eq!(menu.menu_type, MenuType::Pause);
         assert_eq!(menu.get_selected_action(), MenuAction::Resume);
+        assert_eq!(menu.items[1].text, "LIVERY: MCLAREN");
+    }
+
+    #[test]
+    fn test_race_setup_menu() {
+        let mut menu = Menu::race_setup_menu(800, 600, 3, WeatherCondition::Dry);
+        assert_eq!(menu.get_selected_action(), MenuAction::StartRace);
+        menu.move_down();
+        assert_eq!(menu.get_selected_action(), MenuAction::ExportSetup);
+        menu.move_down();
+        assert_eq!(menu.get_selected_action(), MenuAction::MainMenu);
     }
 
     #[test]
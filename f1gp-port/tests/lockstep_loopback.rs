@@ -0,0 +1,204 @@
+//! Loopback tests for lockstep multiplayer
+//!
+//! Two sessions run in one thread over channel transports, each driving its
+//! own simulation, exactly as two machines would over UDP.
+
+use f1gp_port::data::car::CarDatabase;
+use f1gp_port::net::packet::{BUTTON_SHIFT_DOWN, BUTTON_SHIFT_UP};
+use f1gp_port::net::{
+    ChannelTransport, LockstepSession, LockstepSim, NetError, NetInput, Transport,
+    CHECKSUM_INTERVAL, CLIENT_PLAYER, DEFAULT_INPUT_DELAY, HOST_PLAYER,
+};
+use std::io;
+
+const TICKS: u32 = 5_000;
+
+/// Transport that loses every `nth` datagram it sends
+struct Lossy<T> {
+    inner: T,
+    nth: u32,
+    sent: u32,
+}
+
+impl<T: Transport> Transport for Lossy<T> {
+    fn send(&mut self, payload: &[u8]) -> io::Result<()> {
+        self.sent += 1;
+        if self.sent.is_multiple_of(self.nth) {
+            return Ok(());
+        }
+        self.inner.send(payload)
+    }
+
+    fn recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self.inner.recv()
+    }
+}
+
+struct Peer<T: Transport> {
+    session: LockstepSession<T>,
+    sim: LockstepSim,
+    inputs_sent: u32,
+    checksums: Vec<(u32, u64)>,
+}
+
+impl<T: Transport> Peer<T> {
+    fn new(transport: T, player: usize) -> Self {
+        let spec = CarDatabase::create_sample()
+            .get_car("McLaren")
+            .unwrap()
+            .clone();
+        Self {
+            session: LockstepSession::new(transport, player, DEFAULT_INPUT_DELAY),
+            sim: LockstepSim::new(spec),
+            inputs_sent: 0,
+            checksums: Vec::new(),
+        }
+    }
+
+    /// One frame of the game loop: sample input, talk to the peer, simulate
+    fn frame(&mut self, target: u32) -> Result<(), NetError> {
+        if self.session.needs_local_input() {
+            let input = scripted_input(self.session.local_player(), self.inputs_sent);
+            self.session.add_local_input(input);
+            self.inputs_sent += 1;
+        }
+        self.session.send()?;
+        self.session.poll()?;
+
+        while self.sim.tick() < target {
+            let Some(inputs) = self.session.next_inputs() else {
+                break;
+            };
+            self.sim.step(&inputs);
+
+            let (tick, digest) = (self.sim.tick(), self.sim.digest());
+            self.session.record_checksum(tick, digest)?;
+            if tick.is_multiple_of(CHECKSUM_INTERVAL) {
+                self.checksums.push((tick, digest));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Different, busy driving for each player: throttle lifts, weaving and shifts
+fn scripted_input(player: usize, n: u32) -> NetInput {
+    let phase = n + player as u32 * 97;
+    NetInput {
+        throttle: if phase % 400 < 300 { 255 } else { 90 },
+        brake: if phase % 400 >= 360 { 200 } else { 0 },
+        steering: ((phase % 180) as i32 - 90).clamp(-127, 127) as i8,
+        buttons: match phase % 250 {
+            0 => BUTTON_SHIFT_UP,
+            125 if player == 1 => BUTTON_SHIFT_DOWN,
+            _ => 0,
+        },
+    }
+}
+
+/// Pump both peers until they reach `target`, or `fault` has them give up
+fn run<T: Transport>(
+    host: &mut Peer<T>,
+    client: &mut Peer<T>,
+    target: u32,
+    mut fault: impl FnMut(&mut Peer<T>),
+) -> (Result<(), NetError>, Result<(), NetError>) {
+    let (mut host_result, mut client_result) = (Ok(()), Ok(()));
+
+    for _ in 0..target * 10 {
+        if host_result.is_ok() {
+            host_result = host.frame(target);
+        }
+        if client_result.is_ok() {
+            client_result = client.frame(target);
+            fault(client);
+        }
+
+        let done = |peer: &Peer<T>, result: &Result<(), NetError>| {
+            result.is_err() || peer.sim.tick() == target
+        };
+        if done(host, &host_result) && done(client, &client_result) {
+            break;
+        }
+    }
+
+    (host_result, client_result)
+}
+
+#[test]
+fn test_loopback_checksums_match_for_5000_ticks() {
+    let (a, b) = ChannelTransport::pair();
+    let mut host = Peer::new(a, HOST_PLAYER);
+    let mut client = Peer::new(b, CLIENT_PLAYER);
+
+    let (host_result, client_result) = run(&mut host, &mut client, TICKS, |_| {});
+    host_result.unwrap();
+    client_result.unwrap();
+
+    assert_eq!(host.sim.tick(), TICKS);
+    assert_eq!(client.sim.tick(), TICKS);
+    assert_eq!(host.checksums.len(), (TICKS / CHECKSUM_INTERVAL) as usize);
+    assert_eq!(host.checksums, client.checksums);
+    assert_eq!(host.sim.digest(), client.sim.digest());
+
+    // The run actually exercised the physics, with the players apart
+    let (car0, car1) = (host.sim.car(HOST_PLAYER), host.sim.car(CLIENT_PLAYER));
+    assert!(car0.speed > 0.0 && car1.speed > 0.0);
+    assert_ne!(car0.body.position, car1.body.position);
+}
+
+#[test]
+fn test_loopback_survives_packet_loss() {
+    let (a, b) = ChannelTransport::pair();
+    let lossy = |inner, nth| Lossy {
+        inner,
+        nth,
+        sent: 0,
+    };
+    let mut host = Peer::new(lossy(a, 5), HOST_PLAYER);
+    let mut client = Peer::new(lossy(b, 7), CLIENT_PLAYER);
+
+    let (host_result, client_result) = run(&mut host, &mut client, 1_500, |_| {});
+    host_result.unwrap();
+    client_result.unwrap();
+
+    assert_eq!(host.sim.tick(), 1_500);
+    assert_eq!(host.checksums, client.checksums);
+}
+
+#[test]
+fn test_desync_ends_both_sessions_at_next_checksum() {
+    let (a, b) = ChannelTransport::pair();
+    let mut host = Peer::new(a, HOST_PLAYER);
+    let mut client = Peer::new(b, CLIENT_PLAYER);
+
+    // Nudge one car on the client only, once
+    let mut nudged_at = None;
+    let (host_result, client_result) = run(&mut host, &mut client, TICKS, |client| {
+        if nudged_at.is_none() && client.sim.tick() >= 1_000 {
+            client.sim.car_mut(HOST_PLAYER).body.velocity.x += 0.01;
+            nudged_at = Some(client.sim.tick());
+        }
+    });
+
+    // The digest for the nudged tick itself was already taken
+    let first_check = (nudged_at.unwrap() / CHECKSUM_INTERVAL + 1) * CHECKSUM_INTERVAL;
+    for result in [host_result, client_result] {
+        match result {
+            Err(NetError::Desync {
+                tick,
+                local,
+                remote,
+            }) => {
+                assert_eq!(tick, first_check);
+                assert_ne!(local, remote);
+            }
+            Err(NetError::PeerDesync { tick, .. }) => assert_eq!(tick, first_check),
+            other => panic!("expected a desync, got {:?}", other),
+        }
+    }
+
+    // Neither side ran on far past the mismatch
+    assert!(host.sim.tick() < first_check + CHECKSUM_INTERVAL);
+    assert!(client.sim.tick() < first_check + CHECKSUM_INTERVAL);
+}
@@ -0,0 +1,88 @@
+//! Integration tests for original F1GP setup file import and export
+
+use f1gp_port::data::{CarDatabase, SetupFile, SETUP_BLOCK_LEN, SETUP_DATA_LEN, SETUP_FILE_LEN};
+use f1gp_port::game::TireCompound;
+use f1gp_port::physics::{BodyId, CarPhysics};
+use glam::Vec3;
+
+/// Low-downforce Monza setup: minimal wings and long gearing, on qualifying
+/// tyres for qualifying and B compounds for the race
+#[rustfmt::skip]
+const MONZA_LOW_DOWNFORCE: [u8; SETUP_FILE_LEN] = [
+    // Qualifying: wings, gears 1-6, brake balance, compound, padding
+    2, 1, 16, 22, 28, 34, 40, 46, 54, 4, 0x4D, 0x4F, 0x4E, 0x5A, 0x41, 0x00,
+    // Race
+    2, 1, 16, 22, 28, 34, 40, 46, 54, 1, 0x4D, 0x4F, 0x4E, 0x5A, 0x41, 0x00,
+];
+
+/// Zero the padding of each block, which export does not preserve
+fn without_padding(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    for block in bytes.chunks_mut(SETUP_BLOCK_LEN) {
+        block[SETUP_DATA_LEN..].fill(0);
+    }
+    bytes
+}
+
+#[test]
+fn import_then_export_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("MONZA.SET");
+    let exported = dir.path().join("export").join("MONZA.SET");
+    std::fs::write(&original, MONZA_LOW_DOWNFORCE).unwrap();
+
+    let setup = SetupFile::load(&original).unwrap();
+    setup.save(&exported).unwrap();
+
+    let bytes = std::fs::read(&exported).unwrap();
+    assert_eq!(bytes.len(), SETUP_FILE_LEN);
+    assert_eq!(bytes, without_padding(&MONZA_LOW_DOWNFORCE));
+    assert_eq!(SetupFile::load(&exported).unwrap(), setup);
+}
+
+#[test]
+fn monza_low_downforce_sets_physics_gearing() {
+    let setup = SetupFile::parse(&MONZA_LOW_DOWNFORCE).unwrap();
+    assert_eq!(setup.race.front_wing, 2);
+    assert_eq!(setup.race.rear_wing, 1);
+    assert_eq!(setup.race.brake_balance, 54);
+    assert_eq!(setup.race.tire_compound, TireCompound::B);
+    assert_eq!(setup.qualifying.tire_compound, TireCompound::Qualifying);
+
+    let spec = CarDatabase::create_sample().cars().next().unwrap().clone();
+    let stock = CarPhysics::new(BodyId(0), spec.clone(), Vec3::ZERO);
+    let mut car = CarPhysics::new(BodyId(0), spec, Vec3::ZERO);
+    car.apply_setup(&setup.race);
+
+    let expected = [2.25, 1.636, 1.286, 1.059, 0.9, 0.783];
+    for (gear, ratio) in (1..=6).zip(expected) {
+        assert!(
+            (car.gear_ratio(gear) - ratio).abs() < 0.001,
+            "gear {}: {} vs {}",
+            gear,
+            car.gear_ratio(gear),
+            ratio
+        );
+    }
+
+    // Longer than stock at both ends of the box
+    assert!(car.gear_ratio(1) < stock.gear_ratio(1));
+    assert!(car.gear_ratio(6) < stock.gear_ratio(6));
+}
+
+#[test]
+fn corrupted_files_fail_with_the_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("BROKEN.SET");
+    let mut bytes = MONZA_LOW_DOWNFORCE;
+    bytes[SETUP_BLOCK_LEN + 1] = 200;
+    std::fs::write(&path, bytes).unwrap();
+
+    let message = format!("{:#}", SetupFile::load(&path).unwrap_err());
+    assert!(message.contains("BROKEN.SET"), "{}", message);
+    assert!(
+        message.contains("race setup: rear wing is 200, outside 1-20"),
+        "{}",
+        message
+    );
+}
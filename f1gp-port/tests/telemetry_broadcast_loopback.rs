@@ -0,0 +1,100 @@
+//! Loopback test for the live telemetry broadcast
+//!
+//! A broadcaster sends to a socket bound on localhost, and every field must
+//! come back out of the datagram exactly as it went in.
+
+use f1gp_port::net::broadcast::PACKET_LEN;
+use f1gp_port::net::{TelemetryBroadcaster, TelemetryPacket};
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+fn listener() -> UdpSocket {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+    socket
+}
+
+#[test]
+fn test_every_field_survives_the_round_trip() {
+    let socket = listener();
+    let mut broadcaster = TelemetryBroadcaster::new(socket.local_addr().unwrap(), 60).unwrap();
+
+    // Distinct, non-default values so a swapped or dropped field shows up
+    let sent = TelemetryPacket {
+        tick: 1_234_567_890_123,
+        speed: 81.25,
+        rpm: 12_345.5,
+        gear: 5,
+        throttle: 0.75,
+        brake: 0.125,
+        steering: -0.5,
+        position: [-102.5, 3.25, 2048.75],
+        lap: 12,
+        total_laps: 58,
+        sector: 2,
+        lap_time: 47.125,
+        best_lap_time: Some(81.5),
+        tyre_temps: [88.5, 91.25, 79.0, 102.75],
+        on_track: true,
+    };
+    assert!(broadcaster.send(Instant::now(), &sent).unwrap());
+
+    let mut buf = [0u8; 2 * PACKET_LEN];
+    let (len, _) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(len, PACKET_LEN);
+
+    let received = TelemetryPacket::decode(&buf[..len]).unwrap();
+    assert_eq!(received.tick, sent.tick);
+    assert_eq!(received.speed, sent.speed);
+    assert_eq!(received.rpm, sent.rpm);
+    assert_eq!(received.gear, sent.gear);
+    assert_eq!(received.throttle, sent.throttle);
+    assert_eq!(received.brake, sent.brake);
+    assert_eq!(received.steering, sent.steering);
+    assert_eq!(received.position, sent.position);
+    assert_eq!(received.lap, sent.lap);
+    assert_eq!(received.total_laps, sent.total_laps);
+    assert_eq!(received.sector, sent.sector);
+    assert_eq!(received.lap_time, sent.lap_time);
+    assert_eq!(received.best_lap_time, sent.best_lap_time);
+    assert_eq!(received.tyre_temps, sent.tyre_temps);
+    assert_eq!(received.on_track, sent.on_track);
+    assert_eq!(received, sent);
+}
+
+#[test]
+fn test_reverse_off_track_without_best_lap() {
+    let socket = listener();
+    let mut broadcaster = TelemetryBroadcaster::new(socket.local_addr().unwrap(), 60).unwrap();
+
+    let sent = TelemetryPacket {
+        gear: -1,
+        lap: 1,
+        best_lap_time: None,
+        on_track: false,
+        ..TelemetryPacket::default()
+    };
+    broadcaster.send(Instant::now(), &sent).unwrap();
+
+    let mut buf = [0u8; 2 * PACKET_LEN];
+    let (len, _) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(TelemetryPacket::decode(&buf[..len]), Some(sent));
+}
+
+#[test]
+fn test_rate_limits_packets() {
+    let socket = listener();
+    let mut broadcaster = TelemetryBroadcaster::new(socket.local_addr().unwrap(), 60).unwrap();
+
+    let start = Instant::now();
+    let packet = TelemetryPacket::default();
+    assert!(broadcaster.send(start, &packet).unwrap());
+    assert!(!broadcaster
+        .send(start + Duration::from_millis(5), &packet)
+        .unwrap());
+    assert!(broadcaster
+        .send(start + Duration::from_millis(17), &packet)
+        .unwrap());
+}
@@ -0,0 +1,71 @@
+//! Smoke test for the top-down 2D renderer
+//!
+//! Runs a short race headlessly, rendering every frame into an offscreen
+//! framebuffer and hashing it, so a change in what the 2D fallback draws
+//! shows up as a different hash. After an intended rendering change, update
+//! `EXPECTED_HASH` to the hash the failing assertion reports.
+
+use f1gp_port::data::fixtures::synthetic_track;
+use f1gp_port::game::GameState;
+use f1gp_port::platform::FrameBuffer;
+use f1gp_port::render::TopDownRenderer;
+use sdl2::keyboard::Keycode;
+use std::collections::HashSet;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const FRAMES: usize = 100;
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Hash of the reference race
+const EXPECTED_HASH: u64 = 0xbb24981a5c70fbca;
+
+/// Hash of every frame of a race with the throttle held, and the last frame
+fn render_race() -> (u64, FrameBuffer) {
+    fastrand::seed(1991);
+
+    let track = synthetic_track(64, 2000.0);
+    let mut renderer = TopDownRenderer::new(WIDTH, HEIGHT);
+    renderer.load_track(&track);
+
+    let mut game = GameState::new(WIDTH, HEIGHT);
+    game.set_telemetry_enabled(false);
+    game.load_track(track);
+    game.spawn_ai_opponents(3);
+    game.start_race();
+    game.enter_race();
+    game.handle_key_down(Keycode::Up);
+
+    let mut buffer = FrameBuffer::new(WIDTH, HEIGHT);
+    let mut hash = 0u64;
+    for _ in 0..FRAMES {
+        game.update(FRAME_TIME);
+        let hud = vec![(
+            format!("Speed: {:.0} km/h", game.get_speed_kmh()),
+            8.0,
+            8.0,
+            1.0,
+            [1.0, 1.0, 1.0, 1.0],
+        )];
+        renderer.render(&mut buffer, &game, &hud).unwrap();
+        hash = hash.rotate_left(7) ^ buffer.checksum();
+    }
+
+    (hash, buffer)
+}
+
+#[test]
+fn test_topdown_frames_match_the_reference_hash() {
+    let (first, buffer) = render_race();
+    let (second, _) = render_race();
+    assert_eq!(first, second, "identical races rendered differently");
+    assert_eq!(
+        first, EXPECTED_HASH,
+        "top-down rendering changed: hash {:#018x}",
+        first
+    );
+
+    // Grass, asphalt, track lines, cars and HUD text all made it to screen
+    let colors: HashSet<&[u8]> = buffer.pixels().chunks_exact(4).collect();
+    assert!(colors.len() >= 5, "only {} colors drawn", colors.len());
+}
@@ -0,0 +1,306 @@
+//! Demo control flow shared by both renderers
+//!
+//! The demo owns the game and turns player commands into `GameState` calls;
+//! renderers only draw the result. Physics, AI and lap timing therefore
+//! behave identically in 3D and 2D.
+
+use crate::renderer::Renderer;
+use crate::track_loader;
+use anyhow::{anyhow, Result};
+use f1gp_port::game::GameState;
+use f1gp_port::render3d::HudLine;
+use sdl2::keyboard::Keycode;
+use std::time::Instant;
+
+/// Number of AI opponents in the demo race
+const AI_OPPONENTS: usize = 3;
+
+/// What the player asked for, whichever window system reported it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Switch to the next camera
+    CycleCamera,
+    /// Toggle the free-flying camera
+    ToggleFreeCamera,
+    /// Back to the default camera
+    ResetCamera,
+    /// Zoom by mouse wheel steps
+    Zoom(f32),
+    /// Pause or resume
+    TogglePause,
+    /// Put the player car back on the grid
+    Reset,
+    /// Load the track at this index
+    SelectTrack(usize),
+    /// The window changed size
+    Resize(u32, u32),
+    /// Driving key pressed
+    KeyDown(Keycode),
+    /// Driving key released
+    KeyUp(Keycode),
+}
+
+/// Game state and frame timing for one run of the demo
+pub struct Demo {
+    game: GameState,
+    last_frame: Instant,
+    frame_count: u64,
+    last_fps_print: Instant,
+    fps: f64,
+}
+
+impl Demo {
+    /// Start a race with AI opponents on the track at `track_index`
+    pub fn new(width: u32, height: u32, track_index: usize) -> Result<Self> {
+        let mut game = GameState::new(width, height);
+        log::info!("Game state created");
+
+        let track = track_loader::get_track(track_index)
+            .ok_or_else(|| anyhow!("Track {} could not be loaded", track_index))?;
+        log::info!("Loading track: {}", track.name);
+        game.load_track(track);
+
+        game.spawn_ai_opponents(AI_OPPONENTS);
+        game.start_race();
+        game.enter_race();
+        log::info!("AI opponents spawned");
+
+        Ok(Self {
+            game,
+            last_frame: Instant::now(),
+            frame_count: 0,
+            last_fps_print: Instant::now(),
+            fps: 0.0,
+        })
+    }
+
+    /// Give the current track to a renderer
+    pub fn load_track_into(&self, renderer: &mut dyn Renderer) {
+        if let Some(track) = self.game.track() {
+            renderer.load_track(track);
+            log::info!("Track loaded into {} renderer", renderer.name());
+        }
+    }
+
+    /// Apply one player command
+    pub fn handle(&mut self, command: Command, renderer: &mut dyn Renderer) {
+        match command {
+            Command::CycleCamera => {
+                renderer.cycle_camera();
+                log::info!("Camera mode: {}", renderer.camera_label());
+            }
+            Command::ToggleFreeCamera => {
+                renderer.toggle_free_camera();
+                log::info!("Camera mode: {}", renderer.camera_label());
+            }
+            Command::ResetCamera => {
+                renderer.reset_camera();
+                log::info!("Camera reset: {}", renderer.camera_label());
+            }
+            Command::Zoom(delta) => renderer.zoom(delta),
+            Command::TogglePause => {
+                self.game.toggle_pause();
+                log::info!("Pause toggled");
+            }
+            Command::Reset => {
+                self.game.reset();
+                log::info!("Game reset");
+            }
+            Command::SelectTrack(index) => self.switch_track(index, renderer),
+            Command::Resize(width, height) => {
+                self.game.set_viewport_size(width, height);
+                renderer.resize(width, height);
+            }
+            Command::KeyDown(key) => self.game.handle_key_down(key),
+            Command::KeyUp(key) => self.game.handle_key_up(key),
+        }
+    }
+
+    fn switch_track(&mut self, track_index: usize, renderer: &mut dyn Renderer) {
+        if track_index >= track_loader::get_track_count() {
+            log::warn!("Invalid track index: {}", track_index);
+            return;
+        }
+
+        if let Some(track) = track_loader::get_track(track_index) {
+            log::info!("Switching to track: {}", track.name);
+            self.game.load_track(track);
+            self.load_track_into(renderer);
+            self.game.reset();
+        }
+    }
+
+    /// Seconds since the previous call
+    pub fn frame_time(&mut self) -> f32 {
+        let delta_time = self.last_frame.elapsed().as_secs_f32();
+        self.last_frame = Instant::now();
+        delta_time
+    }
+
+    /// Advance the game by `delta_time` and draw it
+    pub fn frame(&mut self, renderer: &mut dyn Renderer, delta_time: f32) -> Result<()> {
+        self.game.update(delta_time);
+        self.game.update_3d_camera(delta_time);
+
+        let mut hud_lines = self.hud_lines(renderer);
+        hud_lines.extend(renderer.overlay_lines(&self.game));
+        renderer.render(&self.game, &hud_lines)?;
+
+        self.log_fps(renderer);
+        Ok(())
+    }
+
+    /// Telemetry on the left, controls on the right: (text, x, y, scale, color)
+    fn hud_lines(&self, renderer: &dyn Renderer) -> Vec<HudLine> {
+        let game = &self.game;
+        let player_car = game.player_car();
+        let on_track_icon = if player_car.on_track { "ON" } else { "OFF" };
+
+        vec![
+            // Top-left: Telemetry
+            (
+                format!("FPS: {:.1}", self.fps),
+                10.0,
+                10.0,
+                1.5,
+                [0.0, 1.0, 0.0, 1.0], // Green
+            ),
+            (
+                format!("Speed: {:.0} km/h", game.get_speed_kmh()),
+                10.0,
+                34.0,
+                1.5,
+                [1.0, 1.0, 1.0, 1.0], // White
+            ),
+            (
+                format!("Gear: {}", game.get_gear()),
+                10.0,
+                58.0,
+                1.5,
+                [1.0, 1.0, 1.0, 1.0],
+            ),
+            (
+                format!("RPM: {:.0}", game.get_rpm()),
+                10.0,
+                82.0,
+                1.5,
+                [1.0, 1.0, 1.0, 1.0],
+            ),
+            (
+                format!("Track: {}", on_track_icon),
+                10.0,
+                106.0,
+                1.5,
+                if player_car.on_track {
+                    [0.0, 1.0, 0.0, 1.0] // Green
+                } else {
+                    [1.0, 0.0, 0.0, 1.0] // Red
+                },
+            ),
+            (
+                format!("Camera: {}", renderer.camera_label()),
+                10.0,
+                130.0,
+                1.5,
+                [0.5, 0.5, 1.0, 1.0], // Light blue
+            ),
+            (
+                format!("AI Opponents: {}", game.ai_cars().len()),
+                10.0,
+                154.0,
+                1.5,
+                [1.0, 1.0, 0.0, 1.0], // Yellow
+            ),
+            (
+                format!(
+                    "Track: {}",
+                    game.track().map(|t| t.name.as_str()).unwrap_or("Unknown")
+                ),
+                10.0,
+                178.0,
+                1.5,
+                [0.0, 1.0, 1.0, 1.0], // Cyan
+            ),
+            // Top-right: Controls
+            (
+                "Controls:".to_string(),
+                920.0,
+                10.0,
+                1.5,
+                [1.0, 1.0, 1.0, 1.0],
+            ),
+            (
+                "Arrows/WASD: Drive".to_string(),
+                920.0,
+                34.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+            (
+                "C: Camera Mode".to_string(),
+                920.0,
+                50.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+            (
+                "P: Pause".to_string(),
+                920.0,
+                66.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+            (
+                "R: Reset".to_string(),
+                920.0,
+                82.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+            (
+                "1-5: Change Track".to_string(),
+                920.0,
+                98.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+            (
+                "ESC: Exit".to_string(),
+                920.0,
+                114.0,
+                1.0,
+                [0.8, 0.8, 0.8, 1.0],
+            ),
+        ]
+    }
+
+    /// Log frame rate and driving stats once a second
+    fn log_fps(&mut self, renderer: &dyn Renderer) {
+        self.frame_count += 1;
+        if self.last_fps_print.elapsed().as_secs() < 1 {
+            return;
+        }
+
+        self.fps = self.frame_count as f64 / self.last_fps_print.elapsed().as_secs_f64();
+        let game = &self.game;
+        let on_track = if game.player_car().on_track {
+            "✓"
+        } else {
+            "✗"
+        };
+
+        log::info!(
+            "FPS: {:.1} | Speed: {:.0} km/h | Gear: {} | RPM: {:.0} | Track: {} | Camera: {} | AI: {} | {}",
+            self.fps,
+            game.get_speed_kmh(),
+            game.get_gear(),
+            game.get_rpm(),
+            on_track,
+            renderer.camera_label(),
+            game.ai_cars().len(),
+            renderer.stats()
+        );
+        self.last_fps_print = Instant::now();
+        self.frame_count = 0;
+    }
+}
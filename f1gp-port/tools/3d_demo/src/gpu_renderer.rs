@@ -0,0 +1,219 @@
+//! wgpu 3D renderer for the demo
+
+use crate::renderer::Renderer;
+use anyhow::{anyhow, Result};
+use f1gp_port::data::Track;
+use f1gp_port::game::GameState;
+use f1gp_port::render3d::{CameraMode, HudLine, HudRenderer, Renderer3D};
+use std::collections::HashSet;
+use std::sync::Arc;
+use winit::keyboard::KeyCode;
+use winit::window::Window;
+
+/// 3D scene and HUD drawn with wgpu into a winit window
+pub struct GpuRenderer {
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+    renderer_3d: Renderer3D,
+    hud: HudRenderer,
+}
+
+impl GpuRenderer {
+    /// Set up wgpu for `window`; fails when no GPU adapter is available
+    pub fn new(window: Arc<Window>) -> Result<Self> {
+        let size = window.inner_size();
+
+        // Initialize wgpu
+        let instance = wgpu::Instance::default();
+
+        let surface = instance.create_surface(window)?;
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: Some(&surface),
+            force_fallback_adapter: false,
+        }))
+        .map_err(|e| anyhow!("No GPU adapter available: {}", e))?;
+
+        log::info!("Adapter: {:?}", adapter.get_info());
+
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default()))?;
+
+        log::info!("Device and queue created");
+
+        // Configure surface
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = surface_caps
+            .formats
+            .iter()
+            .find(|f| f.is_srgb())
+            .copied()
+            .unwrap_or(surface_caps.formats[0]);
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(&device, &config);
+        log::info!("Surface configured");
+
+        // Create 3D renderer
+        let renderer_3d = Renderer3D::new(&device, &config)?;
+        log::info!("3D renderer created");
+
+        // Create HUD renderer
+        let hud = HudRenderer::new(&device, &queue, &config)?;
+        log::info!("HUD renderer created");
+
+        Ok(Self {
+            surface,
+            device,
+            queue,
+            config,
+            renderer_3d,
+            hud,
+        })
+    }
+
+    /// Fly the free camera with the held keys: WASD/arrows to move, Space
+    /// and Ctrl for up and down, Shift to go faster
+    pub fn move_free_camera(&mut self, pressed_keys: &HashSet<KeyCode>, delta_time: f32) {
+        if self.renderer_3d.camera.mode != CameraMode::Free {
+            return;
+        }
+
+        let held = |keys: &[KeyCode]| keys.iter().any(|key| pressed_keys.contains(key));
+        let axis = |positive: &[KeyCode], negative: &[KeyCode]| {
+            held(positive) as i32 as f32 - held(negative) as i32 as f32
+        };
+
+        let forward = axis(
+            &[KeyCode::KeyW, KeyCode::ArrowUp],
+            &[KeyCode::KeyS, KeyCode::ArrowDown],
+        );
+        let right = axis(
+            &[KeyCode::KeyD, KeyCode::ArrowRight],
+            &[KeyCode::KeyA, KeyCode::ArrowLeft],
+        );
+        let up = axis(
+            &[KeyCode::Space],
+            &[KeyCode::ControlLeft, KeyCode::ControlRight],
+        );
+        let speed_mult = if held(&[KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            3.0
+        } else {
+            1.0
+        };
+
+        self.renderer_3d.camera.move_free_camera(
+            forward * speed_mult,
+            right * speed_mult,
+            up * speed_mult,
+            delta_time,
+        );
+    }
+
+    /// Look around with raw mouse motion (free camera)
+    pub fn handle_mouse_motion(&mut self, delta_x: f64, delta_y: f64) {
+        self.renderer_3d
+            .camera
+            .handle_mouse_motion(delta_x, delta_y);
+    }
+}
+
+impl Renderer for GpuRenderer {
+    fn name(&self) -> &'static str {
+        "3D"
+    }
+
+    fn load_track(&mut self, track: &Track) {
+        self.renderer_3d.load_track(&self.device, track);
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+
+        // Resize renderer depth buffer
+        self.renderer_3d.resize(&self.device, width, height);
+
+        // Resize HUD
+        self.hud.resize(width, height);
+    }
+
+    fn render(&mut self, game: &GameState, hud: &[HudLine]) -> Result<()> {
+        // Update renderer from game state
+        self.renderer_3d.update(game, &self.queue);
+
+        // Get surface texture
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Create command encoder
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        // Render 3D scene (skybox + track)
+        self.renderer_3d.render(&mut encoder, &view)?;
+
+        // Render cars
+        self.renderer_3d
+            .render_cars(&mut encoder, &view, game, &self.queue)?;
+
+        // Render HUD overlay
+        self.hud
+            .render(&self.device, &self.queue, &mut encoder, &view, hud);
+
+        // Submit commands and present
+        self.queue.submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        Ok(())
+    }
+
+    fn camera_label(&self) -> String {
+        format!("{:?}", self.renderer_3d.camera.mode)
+    }
+
+    fn cycle_camera(&mut self) {
+        self.renderer_3d.camera.next_mode();
+    }
+
+    fn toggle_free_camera(&mut self) {
+        self.renderer_3d.camera.toggle_free_mode();
+    }
+
+    fn reset_camera(&mut self) {
+        self.renderer_3d.camera.reset();
+    }
+
+    fn zoom(&mut self, delta: f32) {
+        self.renderer_3d.camera.handle_zoom(delta);
+    }
+
+    fn overlay_lines(&self, game: &GameState) -> Vec<HudLine> {
+        // Car numbers above nearby opponents
+        self.renderer_3d
+            .car_label_lines(game, self.config.width as f32, self.config.height as f32)
+    }
+
+    fn stats(&self) -> String {
+        self.hud.stats().summary()
+    }
+}
@@ -0,0 +1,123 @@
+//! Renderer abstraction for the demo
+//!
+//! The demo drives the game the same way whichever renderer draws it: the
+//! wgpu 3D renderer when a GPU adapter is available, or the SDL2 top-down
+//! 2D renderer otherwise.
+
+use anyhow::{anyhow, bail, Result};
+use f1gp_port::data::Track;
+use f1gp_port::game::GameState;
+use f1gp_port::render3d::HudLine;
+use std::str::FromStr;
+
+/// Something that can draw the demo
+pub trait Renderer {
+    /// Short name for logs ("3D" or "2D")
+    fn name(&self) -> &'static str;
+
+    /// Prepare to draw a newly loaded track
+    fn load_track(&mut self, track: &Track);
+
+    /// Follow a window resize
+    fn resize(&mut self, width: u32, height: u32);
+
+    /// Draw the game with the HUD lines on top, and present the frame
+    fn render(&mut self, game: &GameState, hud: &[HudLine]) -> Result<()>;
+
+    /// Current camera, as shown in the HUD
+    fn camera_label(&self) -> String;
+
+    /// Switch to the next camera
+    fn cycle_camera(&mut self);
+
+    /// Toggle the free-flying camera, where there is one
+    fn toggle_free_camera(&mut self) {}
+
+    /// Back to the default camera
+    fn reset_camera(&mut self);
+
+    /// Zoom by mouse wheel steps (positive zooms in)
+    fn zoom(&mut self, delta: f32);
+
+    /// Extra HUD lines for this renderer, such as car numbers
+    fn overlay_lines(&self, _game: &GameState) -> Vec<HudLine> {
+        Vec::new()
+    }
+
+    /// One-line drawing statistics for the FPS log
+    fn stats(&self) -> String;
+}
+
+/// Which renderer to use (`--renderer`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RendererChoice {
+    /// 3D, falling back to 2D when no GPU adapter is available
+    Auto,
+    /// 3D only
+    ThreeD,
+    /// 2D only
+    TwoD,
+}
+
+impl RendererChoice {
+    /// Read `--renderer <auto|3d|2d>` (or `--renderer=...`) from the
+    /// command line arguments, defaulting to auto
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        let mut choice = Self::Auto;
+
+        while let Some(arg) = args.next() {
+            let value = match arg.strip_prefix("--renderer=") {
+                Some(value) => value.to_string(),
+                None if arg == "--renderer" => args
+                    .next()
+                    .ok_or_else(|| anyhow!("--renderer needs a value: auto, 3d or 2d"))?,
+                None => bail!("Unknown argument: {} (usage: --renderer auto|3d|2d)", arg),
+            };
+            choice = value.parse()?;
+        }
+
+        Ok(choice)
+    }
+}
+
+impl FromStr for RendererChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "3d" => Ok(Self::ThreeD),
+            "2d" => Ok(Self::TwoD),
+            other => bail!("Unknown renderer '{}': expected auto, 3d or 2d", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_renderer_choice_from_args() {
+        assert_eq!(
+            RendererChoice::from_args(args(&[])).unwrap(),
+            RendererChoice::Auto
+        );
+        assert_eq!(
+            RendererChoice::from_args(args(&["--renderer", "2d"])).unwrap(),
+            RendererChoice::TwoD
+        );
+        assert_eq!(
+            RendererChoice::from_args(args(&["--renderer=3D"])).unwrap(),
+            RendererChoice::ThreeD
+        );
+        assert!(RendererChoice::from_args(args(&["--renderer"])).is_err());
+        assert!(RendererChoice::from_args(args(&["--renderer", "vulkan"])).is_err());
+        assert!(RendererChoice::from_args(args(&["--fullscreen"])).is_err());
+    }
+}
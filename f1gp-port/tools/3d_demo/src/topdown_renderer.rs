@@ -0,0 +1,128 @@
+//! SDL2 top-down 2D renderer for the demo
+//!
+//! Needs no GPU adapter: the library's top-down renderer draws through an
+//! SDL2 canvas, which falls back to software rendering when it has to.
+
+use crate::demo::Command;
+use crate::renderer::Renderer;
+use anyhow::Result;
+use f1gp_port::data::Track;
+use f1gp_port::game::GameState;
+use f1gp_port::platform::{Renderer as _, SdlRenderer};
+use f1gp_port::render::TopDownRenderer;
+use f1gp_port::render3d::HudLine;
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::Keycode;
+
+/// Zoom factor per mouse wheel step
+const ZOOM_STEP: f32 = 1.1;
+
+/// Top-down view of the race in an SDL2 window
+pub struct TopDown2dRenderer {
+    canvas: SdlRenderer,
+    topdown: TopDownRenderer,
+}
+
+impl TopDown2dRenderer {
+    /// Open the window
+    pub fn new(title: &str, width: u32, height: u32) -> Result<Self> {
+        Ok(Self {
+            canvas: SdlRenderer::new(title, width, height)?,
+            topdown: TopDownRenderer::new(width, height),
+        })
+    }
+
+    /// Collect the commands from pending window events; false once the
+    /// window is closed or Escape is pressed
+    pub fn poll_commands(&mut self, commands: &mut Vec<Command>) -> bool {
+        for event in self.canvas.event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => return false,
+                Event::KeyDown {
+                    keycode: Some(key),
+                    repeat: false,
+                    ..
+                } => commands.push(key_command(key)),
+                Event::KeyUp {
+                    keycode: Some(key), ..
+                } => commands.push(Command::KeyUp(key)),
+                Event::MouseWheel { y, .. } => commands.push(Command::Zoom(y as f32)),
+                Event::Window {
+                    win_event: WindowEvent::SizeChanged(width, height),
+                    ..
+                } => commands.push(Command::Resize(width.max(1) as u32, height.max(1) as u32)),
+                _ => {}
+            }
+        }
+        true
+    }
+}
+
+/// Demo command for a key press: the same keys as the 3D window
+fn key_command(key: Keycode) -> Command {
+    match key {
+        Keycode::C => Command::CycleCamera,
+        Keycode::F => Command::ToggleFreeCamera,
+        Keycode::Home => Command::ResetCamera,
+        Keycode::P => Command::TogglePause,
+        Keycode::R => Command::Reset,
+        Keycode::Num1 => Command::SelectTrack(0),
+        Keycode::Num2 => Command::SelectTrack(1),
+        Keycode::Num3 => Command::SelectTrack(2),
+        Keycode::Num4 => Command::SelectTrack(3),
+        Keycode::Num5 => Command::SelectTrack(4),
+        Keycode::Num6 => Command::SelectTrack(5),
+        Keycode::Num7 => Command::SelectTrack(6),
+        Keycode::Num8 => Command::SelectTrack(7),
+        Keycode::Num9 => Command::SelectTrack(8),
+        Keycode::Num0 => Command::SelectTrack(9),
+        Keycode::Minus => Command::SelectTrack(10),
+        Keycode::Equals => Command::SelectTrack(11),
+        _ => Command::KeyDown(key),
+    }
+}
+
+impl Renderer for TopDown2dRenderer {
+    fn name(&self) -> &'static str {
+        "2D"
+    }
+
+    fn load_track(&mut self, track: &Track) {
+        self.topdown.load_track(track);
+    }
+
+    fn resize(&mut self, _width: u32, _height: u32) {
+        // The canvas reports its new size, which the top-down renderer reads
+        // every frame
+    }
+
+    fn render(&mut self, game: &GameState, hud: &[HudLine]) -> Result<()> {
+        self.topdown.render(&mut self.canvas, game, hud)?;
+        self.canvas.present();
+        Ok(())
+    }
+
+    fn camera_label(&self) -> String {
+        self.topdown.view().label().to_string()
+    }
+
+    fn cycle_camera(&mut self) {
+        self.topdown.cycle_view();
+    }
+
+    fn reset_camera(&mut self) {
+        self.topdown.reset_view();
+    }
+
+    fn zoom(&mut self, delta: f32) {
+        self.topdown.zoom_by(ZOOM_STEP.powf(delta));
+    }
+
+    fn stats(&self) -> String {
+        "HUD: SDL2 canvas".to_string()
+    }
+}
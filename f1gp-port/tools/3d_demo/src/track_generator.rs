@@ -63,6 +63,7 @@ pub fn generate_test_track() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
@@ -171,6 +172,7 @@ pub fn generate_monaco() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
@@ -286,6 +288,7 @@ pub fn generate_spa() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
@@ -386,6 +389,7 @@ pub fn generate_monza() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
@@ -494,6 +498,7 @@ pub fn generate_silverstone() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: Vec::new(),
         cameras: Vec::new(),
+        annotations: Default::default(),
         checksum: 0,
     }
 }
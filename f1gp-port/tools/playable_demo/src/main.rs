@@ -10,6 +10,8 @@ use glam::Vec3;
 use sdl2::event::Event;
 use std::time::Instant;
 
+mod netplay;
+
 const WINDOW_WIDTH: u32 = 1280;
 const WINDOW_HEIGHT: u32 = 720;
 const TARGET_FPS: u64 = 60;
@@ -18,6 +20,10 @@ const FRAME_TIME: std::time::Duration = std::time::Duration::from_micros(1_000_0
 fn main() -> Result<()> {
     env_logger::init();
 
+    if let Some(role) = netplay::parse_args(std::env::args().skip(1))? {
+        return netplay::run(role);
+    }
+
     log::info!("F1GP Playable Demo Starting");
     log::info!("Window size: {}x{}", WINDOW_WIDTH, WINDOW_HEIGHT);
     log::info!("Target FPS: {}", TARGET_FPS);
@@ -152,6 +158,7 @@ fn create_test_track() -> Track {
         ai_behavior: AIBehavior::default(),
         pit_lane: vec![],
         cameras: vec![],
+        annotations: Default::default(),
         checksum: 0,
     }
 }
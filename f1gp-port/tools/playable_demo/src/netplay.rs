@@ -0,0 +1,262 @@
+//! Experimental two-player mode
+//!
+//! `--host <addr>` waits for a second player on `addr`, `--join <addr>`
+//! joins a host. Both cars run in a lockstep simulation on an open plane;
+//! the session ends on quit, timeout or desync.
+
+use anyhow::{anyhow, bail, Context, Result};
+use f1gp_port::data::car::CarDatabase;
+use f1gp_port::game::InputManager;
+use f1gp_port::net::{
+    self, Host, LockstepSession, LockstepSim, NetInput, UdpTransport, DEFAULT_INPUT_DELAY,
+    PLAYER_COUNT,
+};
+use f1gp_port::platform::{Color, Renderer, SdlRenderer};
+use glam::{Vec2, Vec3};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+const WINDOW_WIDTH: u32 = 1280;
+const WINDOW_HEIGHT: u32 = 720;
+const FRAME_TIME: Duration = Duration::from_micros(1_000_000 / 60);
+
+/// How long the lobby waits for the other player
+const LOBBY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Most ticks simulated in one frame when catching up
+const MAX_TICKS_PER_FRAME: usize = 4;
+
+/// Pixels per metre
+const SCALE: f32 = 4.0;
+
+/// Spacing of the ground grid (m)
+const GRID_SPACING: f32 = 20.0;
+
+const PLAYER_COLORS: [Color; PLAYER_COUNT] = [Color::rgb(220, 40, 40), Color::rgb(40, 90, 220)];
+
+/// Which side of the session this process is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetRole {
+    Host(SocketAddr),
+    Join(SocketAddr),
+}
+
+/// Parse `--host <addr>` or `--join <addr>`; `None` for single-player
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<NetRole>> {
+    let Some(flag) = args.next() else {
+        return Ok(None);
+    };
+
+    let addr = |value: Option<String>| -> Result<SocketAddr> {
+        let value = value.ok_or_else(|| anyhow!("{} needs an address, e.g. 0.0.0.0:7777", flag))?;
+        value
+            .parse()
+            .with_context(|| format!("Invalid address '{}'", value))
+    };
+
+    match flag.as_str() {
+        "--host" => Ok(Some(NetRole::Host(addr(args.next())?))),
+        "--join" => Ok(Some(NetRole::Join(addr(args.next())?))),
+        other => bail!("Unknown argument '{}' (expected --host or --join)", other),
+    }
+}
+
+/// Connect to the other player and race until either side leaves
+pub fn run(role: NetRole) -> Result<()> {
+    let connection = match role {
+        NetRole::Host(addr) => {
+            let host = Host::bind(addr, DEFAULT_INPUT_DELAY)?;
+            log::info!("Hosting on {}, waiting for a player...", host.local_addr()?);
+            host.accept(LOBBY_TIMEOUT)?
+        }
+        NetRole::Join(addr) => {
+            log::info!("Joining {}...", addr);
+            net::join(addr, LOBBY_TIMEOUT)?
+        }
+    };
+    log::info!(
+        "Connected to {} as player {} (input delay {} ticks)",
+        connection.transport.peer(),
+        connection.local_player + 1,
+        connection.input_delay
+    );
+
+    let mut session = LockstepSession::new(
+        connection.transport,
+        connection.local_player,
+        connection.input_delay,
+    );
+    let spec = CarDatabase::create_sample()
+        .get_car("McLaren")
+        .cloned()
+        .ok_or_else(|| anyhow!("Sample car database has no McLaren"))?;
+    let mut sim = LockstepSim::new(spec);
+
+    let mut renderer =
+        SdlRenderer::new("F1GP Playable Demo - 2 Player", WINDOW_WIDTH, WINDOW_HEIGHT)?;
+    let mut input_manager = InputManager::new();
+
+    while renderer.poll_events() {
+        let frame_start = Instant::now();
+
+        for event in renderer.event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => {
+                    session.quit();
+                    return Ok(());
+                }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } => input_manager.key_down(keycode),
+                Event::KeyUp {
+                    keycode: Some(keycode),
+                    ..
+                } => input_manager.key_up(keycode),
+                _ => {}
+            }
+        }
+
+        if let Err(e) = step_session(&mut session, &mut sim, &mut input_manager) {
+            log::error!("Session ended at tick {}: {}", sim.tick(), e);
+            log::info!("Final state digest {:016x}", sim.digest());
+            break;
+        }
+
+        let waiting = !session.needs_local_input();
+        render(&mut renderer, &sim, session.local_player(), waiting)?;
+        renderer.present();
+
+        let elapsed = frame_start.elapsed();
+        if elapsed < FRAME_TIME {
+            renderer.delay(FRAME_TIME - elapsed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sample local input, swap packets and run every tick that is ready
+fn step_session(
+    session: &mut LockstepSession<UdpTransport>,
+    sim: &mut LockstepSim,
+    input_manager: &mut InputManager,
+) -> Result<(), net::NetError> {
+    if session.needs_local_input() {
+        session.add_local_input(NetInput::from_car_input(&input_manager.get_car_input()));
+        // Shift presses are edges, so only clear them once they were sampled
+        input_manager.update();
+    }
+    session.send()?;
+    session.poll()?;
+
+    for _ in 0..MAX_TICKS_PER_FRAME {
+        let Some(inputs) = session.next_inputs() else {
+            break;
+        };
+        sim.step(&inputs);
+        session.record_checksum(sim.tick(), sim.digest())?;
+    }
+    Ok(())
+}
+
+/// Top-down view centred on the local car
+fn render(
+    renderer: &mut SdlRenderer,
+    sim: &LockstepSim,
+    local_player: usize,
+    waiting: bool,
+) -> Result<()> {
+    renderer.clear(Color::rgb(30, 110, 40));
+
+    let (width, height) = renderer.viewport_size();
+    let screen_center = Vec2::new(width as f32, height as f32) / 2.0;
+    let camera = sim.car(local_player).body.position;
+    let to_screen = |p: Vec3| screen_center + Vec2::new(p.x - camera.x, p.z - camera.z) * SCALE;
+
+    // Ground grid, so motion shows on the open plane
+    let half_extent = Vec2::new(width as f32, height as f32) / (2.0 * SCALE);
+    let grid_color = Color::rgb(45, 130, 55);
+    let first_x = ((camera.x - half_extent.x) / GRID_SPACING).floor() as i32;
+    let last_x = ((camera.x + half_extent.x) / GRID_SPACING).ceil() as i32;
+    for i in first_x..=last_x {
+        let x = to_screen(Vec3::new(i as f32 * GRID_SPACING, 0.0, camera.z)).x;
+        renderer.draw_line(Vec2::new(x, 0.0), Vec2::new(x, height as f32), grid_color)?;
+    }
+    let first_z = ((camera.z - half_extent.y) / GRID_SPACING).floor() as i32;
+    let last_z = ((camera.z + half_extent.y) / GRID_SPACING).ceil() as i32;
+    for i in first_z..=last_z {
+        let y = to_screen(Vec3::new(camera.x, 0.0, i as f32 * GRID_SPACING)).y;
+        renderer.draw_line(Vec2::new(0.0, y), Vec2::new(width as f32, y), grid_color)?;
+    }
+
+    for (player, color) in PLAYER_COLORS.iter().enumerate() {
+        let car = sim.car(player);
+        let position = to_screen(car.body.position);
+        let heading = car.body.orientation * Vec3::X;
+        let nose = position + Vec2::new(heading.x, heading.z) * 3.0 * SCALE;
+
+        renderer.draw_filled_circle(position, 1.5 * SCALE, *color)?;
+        renderer.draw_line(position, nose, Color::rgb(255, 255, 255))?;
+    }
+
+    let car = sim.car(local_player);
+    let status = format!(
+        "P{} | TICK {} | {:.0} KM/H | GEAR {}",
+        local_player + 1,
+        sim.tick(),
+        car.speed * 3.6,
+        car.gear
+    );
+    renderer.draw_text(
+        &status,
+        Vec2::new(20.0, 20.0),
+        20.0,
+        Color::rgb(255, 255, 255),
+    )?;
+    if waiting {
+        renderer.draw_text(
+            "WAITING FOR PEER",
+            Vec2::new(20.0, 50.0),
+            20.0,
+            Color::rgb(255, 220, 0),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<NetRole>> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_roles() {
+        assert_eq!(parse(&[]).unwrap(), None);
+        assert_eq!(
+            parse(&["--host", "0.0.0.0:7777"]).unwrap(),
+            Some(NetRole::Host("0.0.0.0:7777".parse().unwrap()))
+        );
+        assert_eq!(
+            parse(&["--join", "192.168.1.20:7777"]).unwrap(),
+            Some(NetRole::Join("192.168.1.20:7777".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(parse(&["--join"]).is_err());
+        assert!(parse(&["--join", "nowhere"]).is_err());
+        assert!(parse(&["--race"]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Print live telemetry broadcast by the game
+//!
+//! A minimal dashboard: binds the broadcast port and prints one line per
+//! decoded packet. Enable the broadcast in `settings/telemetry_broadcast.json`.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use f1gp_port::net::broadcast::{TelemetryPacket, PACKET_LEN};
+use std::net::UdpSocket;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Print live F1GP telemetry packets")]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:20777")]
+    bind: String,
+
+    /// Stop after this many packets
+    #[arg(long)]
+    count: Option<u64>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let socket =
+        UdpSocket::bind(&cli.bind).with_context(|| format!("Failed to bind {}", cli.bind))?;
+    println!("Listening for telemetry on {}", socket.local_addr()?);
+
+    let mut buf = [0u8; PACKET_LEN * 2];
+    let mut received = 0;
+    while cli.count.is_none_or(|count| received < count) {
+        let (len, from) = socket.recv_from(&mut buf)?;
+        match TelemetryPacket::decode(&buf[..len]) {
+            Some(packet) => {
+                println!("{}", format_packet(&packet));
+                received += 1;
+            }
+            None => eprintln!("Ignoring {} byte datagram from {}", len, from),
+        }
+    }
+
+    Ok(())
+}
+
+fn format_packet(p: &TelemetryPacket) -> String {
+    let laps = if p.total_laps > 0 {
+        format!("{}/{}", p.lap, p.total_laps)
+    } else {
+        p.lap.to_string()
+    };
+    let best = p
+        .best_lap_time
+        .map_or_else(|| "-".to_string(), |t| format!("{:.3}", t));
+    let gear = match p.gear {
+        -1 => "R".to_string(),
+        0 => "N".to_string(),
+        g => g.to_string(),
+    };
+
+    format!(
+        "tick {:>7} | {:>5.1} km/h {:>5.0} rpm gear {} | thr {:.2} brk {:.2} str {:+.2} | \
+         pos ({:.1}, {:.1}, {:.1}) | lap {} S{} {:.3}s best {} | tyres {:.0}/{:.0}/{:.0}/{:.0} C{}",
+        p.tick,
+        p.speed * 3.6,
+        p.rpm,
+        gear,
+        p.throttle,
+        p.brake,
+        p.steering,
+        p.position[0],
+        p.position[1],
+        p.position[2],
+        laps,
+        p.sector,
+        p.lap_time,
+        best,
+        p.tyre_temps[0],
+        p.tyre_temps[1],
+        p.tyre_temps[2],
+        p.tyre_temps[3],
+        if p.on_track { "" } else { " | off track" },
+    )
+}
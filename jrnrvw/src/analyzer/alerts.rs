@@ -0,0 +1,170 @@
+//! Overdue and stale tasks
+//!
+//! A task is overdue once the due date of its newest entry that sets one
+//! has passed, and stale once it has gone more than `stale_after_days`
+//! days without an entry. Finished tasks are neither; tasks without any
+//! status count as unfinished.
+//!
+//! Overdue is judged against today, since due dates are about the
+//! calendar. Stale is judged against the report's newest entry, so an old
+//! journal tree reads the same whenever it is reported on.
+
+use chrono::NaiveDate;
+
+use crate::models::{OverdueTask, Repository, StaleTask, Task, TaskStatus};
+
+/// Days without an entry after which `[tasks] stale_after_days` reports an
+/// unfinished task as stale, unless configured otherwise
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 30;
+
+/// Unfinished tasks due before `today`, longest overdue first
+pub fn overdue_tasks(repositories: &[Repository], today: NaiveDate) -> Vec<OverdueTask> {
+    let mut overdue: Vec<OverdueTask> = unfinished(repositories)
+        .filter_map(|(repository, task)| {
+            let due = task.due_date().filter(|due| *due < today)?;
+            Some(OverdueTask {
+                repository: repository.name.clone(),
+                task: task.name.clone(),
+                due,
+                days_overdue: (today - due).num_days(),
+            })
+        })
+        .collect();
+
+    overdue.sort_by(|a, b| {
+        b.days_overdue
+            .cmp(&a.days_overdue)
+            .then_with(|| (&a.repository, &a.task).cmp(&(&b.repository, &b.task)))
+    });
+    overdue
+}
+
+/// Unfinished tasks with no entry in the `stale_after_days` days up to
+/// `as_of`, longest open first
+pub fn stale_tasks(repositories: &[Repository], as_of: NaiveDate, stale_after_days: u32) -> Vec<StaleTask> {
+    let mut stale: Vec<StaleTask> = unfinished(repositories)
+        .filter_map(|(repository, task)| {
+            let (opened, last_entry) = task.date_range()?;
+            if (as_of - last_entry).num_days() <= i64::from(stale_after_days) {
+                return None;
+            }
+            Some(StaleTask {
+                repository: repository.name.clone(),
+                task: task.name.clone(),
+                opened,
+                last_entry,
+                days_open: (as_of - opened).num_days(),
+            })
+        })
+        .collect();
+
+    stale.sort_by(|a, b| {
+        b.days_open
+            .cmp(&a.days_open)
+            .then_with(|| (&a.repository, &a.task).cmp(&(&b.repository, &b.task)))
+    });
+    stale
+}
+
+/// Tasks not marked done, with their repository
+fn unfinished(repositories: &[Repository]) -> impl Iterator<Item = (&Repository, &Task)> {
+    repositories
+        .iter()
+        .flat_map(|repository| repository.tasks.iter().map(move |task| (repository, task)))
+        .filter(|(_, task)| task.status() != Some(TaskStatus::Done))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::JournalEntry;
+    use std::path::PathBuf;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(day: &str, due: Option<&str>, status: Option<&str>) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", day)), date(day));
+        entry.due = due.map(date);
+        entry.status = status.map(str::to_string);
+        entry
+    }
+
+    fn repository(name: &str, tasks: Vec<(&str, Vec<JournalEntry>)>) -> Repository {
+        let mut repository = Repository::new(name.to_string(), None);
+        for (name, entries) in tasks {
+            let mut task = Task::new(name.to_string());
+            for entry in entries {
+                task.add_entry(entry);
+            }
+            repository.add_task(task);
+        }
+        repository
+    }
+
+    #[test]
+    fn test_overdue_longest_first() {
+        let repositories = vec![
+            repository(
+                "api",
+                vec![
+                    ("auth", vec![entry("2025-11-01", Some("2025-11-10"), None)]),
+                    ("deploy", vec![entry("2025-11-01", Some("2025-11-03"), Some("open"))]),
+                    ("docs", vec![entry("2025-11-01", Some("2025-11-20"), None)]),
+                    ("undated", vec![entry("2025-11-01", None, None)]),
+                ],
+            ),
+            repository("web", vec![("css", vec![entry("2025-11-01", Some("2025-11-03"), Some("done"))])]),
+        ];
+
+        let overdue = overdue_tasks(&repositories, date("2025-11-20"));
+        let rows: Vec<(&str, i64)> = overdue.iter().map(|t| (t.task.as_str(), t.days_overdue)).collect();
+        assert_eq!(rows, vec![("deploy", 17), ("auth", 10)], "due today isn't overdue, done never is");
+        assert_eq!(overdue[0].due, date("2025-11-03"));
+    }
+
+    #[test]
+    fn test_newest_due_date_counts() {
+        // The deadline moved out in a later entry
+        let repositories = vec![repository(
+            "api",
+            vec![(
+                "auth",
+                vec![entry("2025-11-01", Some("2025-11-05"), None), entry("2025-11-04", Some("2025-12-01"), None)],
+            )],
+        )];
+        assert!(overdue_tasks(&repositories, date("2025-11-20")).is_empty());
+        assert_eq!(overdue_tasks(&repositories, date("2025-12-02"))[0].due, date("2025-12-01"));
+    }
+
+    #[test]
+    fn test_stale_boundary() {
+        let repositories = vec![repository(
+            "api",
+            vec![
+                ("auth", vec![entry("2025-09-01", None, None), entry("2025-10-01", None, None)]),
+                ("deploy", vec![entry("2025-09-15", None, None), entry("2025-10-02", None, None)]),
+                ("css", vec![entry("2025-08-01", None, Some("done"))]),
+                ("docs", vec![entry("2025-11-01", None, None)]),
+            ],
+        )];
+
+        // 2025-11-01 is 31 days after 2025-10-01 and 30 after 2025-10-02
+        let stale = stale_tasks(&repositories, date("2025-11-01"), 30);
+        assert_eq!(
+            stale,
+            vec![StaleTask {
+                repository: "api".to_string(),
+                task: "auth".to_string(),
+                opened: date("2025-09-01"),
+                last_entry: date("2025-10-01"),
+                days_open: 61,
+            }]
+        );
+
+        let stale = stale_tasks(&repositories, date("2025-11-01"), 10);
+        let tasks: Vec<&str> = stale.iter().map(|t| t.task.as_str()).collect();
+        assert_eq!(tasks, vec!["auth", "deploy"], "longest open first");
+    }
+}
@@ -0,0 +1,321 @@
+//! Author attribution for journal trees shared by a team
+//!
+//! An entry's author comes from the first of these that applies:
+//! 1. `author:` in the journal's front matter (`#+AUTHOR:` in org journals)
+//! 2. the directory at `authors.path_component` under the journal root, so
+//!    with `path_component = 1` `<root>/alice/2025.11.20 - JRN - x.md` is alice's
+//! 3. with `authors.git_blame` set, whoever committed most of the file's lines
+//! 4. with `--git-metadata`, the author of the last commit touching the file
+//!
+//! The sources are never merged: a journal in `alice/` whose front matter
+//! says `author: bob` is bob's, which is how a journal written up for a
+//! colleague gets credited to them. Names are kept as written, so `Alice`
+//! and `alice` are two authors.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use crate::config::settings::AuthorsConfig;
+use crate::error::Result;
+use crate::models::{AuthorSummary, JournalEntry, Repository, TaskStatus};
+
+/// Name used for entries without an author
+pub const UNATTRIBUTED: &str = "Unattributed";
+
+/// What git blame calls lines that have not been committed
+const NOT_COMMITTED: &str = "Not Committed Yet";
+
+/// Where an entry's author came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorSource {
+    FrontMatter,
+    Path,
+    GitBlame,
+    GitLog,
+}
+
+/// Assigns authors to journal entries
+#[derive(Debug, Default)]
+pub struct AuthorResolver {
+    root: PathBuf,
+    path_component: Option<usize>,
+    git_blame: bool,
+    excluded: Vec<String>,
+}
+
+impl AuthorResolver {
+    /// Resolver for journals found under `root`
+    pub fn new(config: &AuthorsConfig, root: &Path) -> Result<Self> {
+        config.validate()?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            path_component: config.path_component,
+            git_blame: config.git_blame,
+            excluded: config.exclude_from_llm.iter().map(|name| name.trim().to_string()).collect(),
+        })
+    }
+
+    /// Author from the journal's location alone
+    pub fn path_author(&self, entry: &JournalEntry) -> Option<String> {
+        let index = self.path_component?.checked_sub(1)?;
+        let relative = entry.filepath.strip_prefix(&self.root).ok().map(Path::to_path_buf).or_else(|| {
+            // Scans from a relative root may give paths in another form
+            let filepath = entry.filepath.canonicalize().ok()?;
+            let root = self.root.canonicalize().ok()?;
+            filepath.strip_prefix(root).ok().map(Path::to_path_buf)
+        })?;
+
+        let name = relative
+            .parent()?
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .nth(index)?
+            .trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Author and where it came from, following the documented precedence
+    pub fn resolve(&self, entry: &JournalEntry) -> Option<(String, AuthorSource)> {
+        if let Some(author) = entry.author.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+            return Some((author.to_string(), AuthorSource::FrontMatter));
+        }
+        if let Some(author) = self.path_author(entry) {
+            return Some((author, AuthorSource::Path));
+        }
+        if self.git_blame {
+            if let Some(author) = blame_author(&entry.filepath) {
+                return Some((author, AuthorSource::GitBlame));
+            }
+        }
+        entry
+            .git
+            .as_ref()
+            .map(|git| git.author.trim())
+            .filter(|author| !author.is_empty())
+            .map(|author| (author.to_string(), AuthorSource::GitLog))
+    }
+
+    /// Whether `author` is kept out of LLM prompts, ignoring case
+    pub fn is_excluded(&self, author: &str) -> bool {
+        self.excluded.iter().any(|name| name.eq_ignore_ascii_case(author))
+    }
+
+    /// Set an entry's author and mark it private if the author asked to be
+    pub fn attribute_entry(&self, entry: &mut JournalEntry) {
+        entry.author = self.resolve(entry).map(|(author, _)| author);
+        entry.private = entry.author.as_deref().is_some_and(|author| self.is_excluded(author));
+    }
+
+    /// Attribute every entry
+    pub fn attribute_entries(&self, entries: &mut [JournalEntry]) {
+        for entry in entries {
+            self.attribute_entry(entry);
+        }
+    }
+}
+
+/// Whoever committed most of the lines of `path`
+///
+/// `None` when git is missing, the file is not committed, or it is outside
+/// a repository. Ties go to the name that sorts first.
+pub fn blame_author(path: &Path) -> Option<String> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(path.file_name()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    most_frequent_author(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Most frequent `author` in `git blame --line-porcelain` output
+fn most_frequent_author(porcelain: &str) -> Option<String> {
+    let mut lines: HashMap<&str, usize> = HashMap::new();
+    // Content lines start with a tab, so they cannot be mistaken for headers
+    for author in porcelain.lines().filter_map(|line| line.strip_prefix("author ")) {
+        if author != NOT_COMMITTED {
+            *lines.entry(author).or_default() += 1;
+        }
+    }
+
+    lines
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(author, _)| author.to_string())
+}
+
+/// Entries and completed tasks per author, unattributed last
+///
+/// A finished task is credited to the author of its newest entry. Empty
+/// when no entry has an author, so single-user reports are unchanged.
+pub fn team_summary(entries: &[JournalEntry], repositories: &[Repository]) -> Vec<AuthorSummary> {
+    if entries.iter().all(|entry| entry.author.is_none()) {
+        return Vec::new();
+    }
+
+    // Keyed so unattributed sorts after every name
+    let mut rows: BTreeMap<(bool, &str), AuthorSummary> = BTreeMap::new();
+
+    for entry in entries {
+        summary_row(&mut rows, entry.author.as_deref()).entries += 1;
+    }
+    for task in repositories.iter().flat_map(|repo| &repo.tasks) {
+        if task.status() == Some(TaskStatus::Done) {
+            if let Some(latest) = task.latest_entry() {
+                summary_row(&mut rows, latest.author.as_deref()).completed_tasks += 1;
+            }
+        }
+    }
+
+    rows.into_values().collect()
+}
+
+fn summary_row<'r, 'a>(
+    rows: &'r mut BTreeMap<(bool, &'a str), AuthorSummary>,
+    author: Option<&'a str>,
+) -> &'r mut AuthorSummary {
+    let name = author.unwrap_or(UNATTRIBUTED);
+    rows.entry((author.is_none(), name)).or_insert_with(|| AuthorSummary {
+        author: name.to_string(),
+        entries: 0,
+        completed_tasks: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{GitMetadata, Task};
+    use chrono::NaiveDate;
+
+    fn entry(path: &str, author: Option<&str>) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(path), NaiveDate::from_ymd_opt(2025, 11, 20).unwrap());
+        entry.author = author.map(str::to_string);
+        entry
+    }
+
+    fn resolver(path_component: Option<usize>, exclude: &[&str]) -> AuthorResolver {
+        let config = AuthorsConfig {
+            path_component,
+            git_blame: false,
+            exclude_from_llm: exclude.iter().map(|name| name.to_string()).collect(),
+        };
+        AuthorResolver::new(&config, Path::new("/team/journals")).unwrap()
+    }
+
+    #[test]
+    fn test_path_component() {
+        let first = resolver(Some(1), &[]);
+        let second = resolver(Some(2), &[]);
+        let nested = entry("/team/journals/alice/2025/2025.11.20 - JRN - x.md", None);
+
+        assert_eq!(first.path_author(&nested).as_deref(), Some("alice"));
+        assert_eq!(second.path_author(&nested).as_deref(), Some("2025"));
+        // Files directly under the root, or not under it at all, have no path author
+        assert_eq!(first.path_author(&entry("/team/journals/2025.11.20 - JRN - x.md", None)), None);
+        assert_eq!(first.path_author(&entry("/elsewhere/bob/x.md", None)), None);
+        assert_eq!(resolver(None, &[]).path_author(&nested), None);
+    }
+
+    #[test]
+    fn test_front_matter_beats_path() {
+        let resolver = resolver(Some(1), &[]);
+
+        let written_for_bob = entry("/team/journals/alice/x.md", Some("bob"));
+        assert_eq!(resolver.resolve(&written_for_bob), Some(("bob".to_string(), AuthorSource::FrontMatter)));
+
+        let alices = entry("/team/journals/alice/x.md", Some("  "));
+        assert_eq!(resolver.resolve(&alices), Some(("alice".to_string(), AuthorSource::Path)));
+    }
+
+    #[test]
+    fn test_last_commit_author_comes_last() {
+        let resolver = resolver(Some(1), &[]);
+        let committed = |path: &str| {
+            let mut entry = entry(path, None);
+            entry.git = Some(GitMetadata {
+                commit: "0123456789abcdef".to_string(),
+                author: "carol".to_string(),
+                date: chrono::DateTime::parse_from_rfc3339("2025-11-20T09:30:00+00:00").unwrap(),
+            });
+            entry
+        };
+
+        assert_eq!(resolver.resolve(&committed("/team/journals/x.md")), Some(("carol".to_string(), AuthorSource::GitLog)));
+        assert_eq!(resolver.resolve(&committed("/team/journals/alice/x.md")), Some(("alice".to_string(), AuthorSource::Path)));
+    }
+
+    #[test]
+    fn test_excluded_authors_are_private() {
+        let resolver = resolver(Some(1), &["Bob"]);
+        let mut entries = vec![
+            entry("/team/journals/alice/x.md", None),
+            entry("/team/journals/bob/y.md", None),
+            entry("/team/journals/z.md", None),
+        ];
+        resolver.attribute_entries(&mut entries);
+
+        let authors: Vec<_> = entries.iter().map(|e| e.author.as_deref()).collect();
+        assert_eq!(authors, vec![Some("alice"), Some("bob"), None]);
+        let private: Vec<_> = entries.iter().map(|e| e.private).collect();
+        assert_eq!(private, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_most_frequent_author() {
+        let porcelain = "\
+abc123 1 1 2
+author bob
+author-mail <bob@example.com>
+\tauthor alice
+abc124 2 2
+author alice
+\tline
+abc125 3 3
+author bob
+\tline
+000000 4 4
+author Not Committed Yet
+\tline
+000000 5 5
+author Not Committed Yet
+\tline
+";
+        assert_eq!(most_frequent_author(porcelain).as_deref(), Some("bob"));
+        // Ties go to the name that sorts first
+        assert_eq!(most_frequent_author("author zoe\nauthor ann\n").as_deref(), Some("ann"));
+        assert_eq!(most_frequent_author("author Not Committed Yet\n"), None);
+    }
+
+    #[test]
+    fn test_team_summary() {
+        let alice = entry("a.md", Some("alice"));
+        let mut alice_done = entry("b.md", Some("alice"));
+        alice_done.date = NaiveDate::from_ymd_opt(2025, 11, 21).unwrap();
+        alice_done.status = Some("done".to_string());
+        let bob = entry("c.md", Some("bob"));
+        let nobody = entry("d.md", None);
+
+        let mut shipped = Task::new("ship".to_string());
+        shipped.add_entry(bob.clone());
+        shipped.add_entry(alice_done.clone());
+        let mut repo = Repository::new("web".to_string(), None);
+        repo.add_task(shipped);
+
+        let team = team_summary(&[alice, alice_done, bob, nobody], &[repo]);
+        let rows: Vec<_> = team.iter().map(|r| (r.author.as_str(), r.entries, r.completed_tasks)).collect();
+        assert_eq!(rows, vec![("alice", 2, 1), ("bob", 1, 0), (UNATTRIBUTED, 1, 0)]);
+
+        assert!(team_summary(&[entry("a.md", None)], &[]).is_empty());
+    }
+}
@@ -0,0 +1,328 @@
+//! On-disk index of parsed journals
+//!
+//! Each analysed root gets an index file holding every journal's mtime, size,
+//! content hash, content, and parse result. A journal whose size and mtime
+//! are unchanged is taken from the index without being read; one whose mtime
+//! moved but whose content hash is the same is read but not parsed again.
+//!
+//! The index is rewritten after every run with only the journals discovered
+//! in that run, so deleted files and repositories that are no longer under
+//! the root drop out of it. An index written by another jrnrvw version is
+//! ignored, since the parser may have changed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::Result;
+use crate::models::ParseFailure;
+use super::pipeline::ParsedJournal;
+
+/// Bumped whenever the index layout changes
+const FORMAT_VERSION: u32 = 8;
+
+/// Environment variable overriding where indexes are kept
+pub const CACHE_DIR_ENV: &str = "JRNRVW_CACHE_DIR";
+
+/// Where the index cache lives and whether to trust it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheSettings {
+    /// Directory holding one index file per root
+    pub dir: PathBuf,
+
+    /// Reuse indexed parse results; when false every journal is parsed and
+    /// the index is rebuilt from scratch
+    pub reuse: bool,
+}
+
+impl CacheSettings {
+    /// `$JRNRVW_CACHE_DIR`, else `$XDG_CACHE_HOME/jrnrvw`, else
+    /// `~/.cache/jrnrvw`; `None` if none of these can be determined
+    pub fn default_dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir));
+        }
+        if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir).join("jrnrvw"));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("jrnrvw"))
+    }
+
+    /// Index file for `root`
+    pub fn index_path(&self, root: &Path) -> PathBuf {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let key = content_hash(root.to_string_lossy().as_bytes());
+        self.dir.join(format!("index-{}.json", key))
+    }
+}
+
+/// A journal as it was when last parsed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct IndexedFile {
+    /// Modification time, if the filesystem reports one
+    pub modified: Option<SystemTime>,
+
+    /// Length in bytes
+    pub size: u64,
+
+    /// FNV-1a hash of the content, as hex
+    pub hash: String,
+
+    /// File content, kept for the report's raw views
+    pub content: String,
+
+    /// What the parser extracted; `None` if the content did not parse
+    pub parsed: Option<ParsedJournal>,
+
+    /// Why the content did not parse
+    #[serde(default)]
+    pub error: Option<ParseFailure>,
+}
+
+impl IndexedFile {
+    /// Whether the file's metadata still matches, so its content need not be
+    /// read again
+    pub fn is_fresh(&self, modified: Option<SystemTime>, size: u64) -> bool {
+        self.modified.is_some() && self.modified == modified && self.size == size
+    }
+}
+
+/// Parse results of every journal under one root
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JournalIndex {
+    /// Index format and jrnrvw version that wrote the index
+    version: String,
+
+    /// Indexed journals by path
+    files: BTreeMap<PathBuf, IndexedFile>,
+}
+
+impl JournalIndex {
+    /// Index built from this run's journals
+    pub(crate) fn from_files(files: impl IntoIterator<Item = (PathBuf, IndexedFile)>) -> Self {
+        Self {
+            version: current_version(),
+            files: files.into_iter().collect(),
+        }
+    }
+
+    /// Index stored for `root`, or an empty one if there is none or it is
+    /// unreadable or from another version
+    pub fn load(settings: &CacheSettings, root: &Path) -> Self {
+        let index: Option<Self> = fs::read_to_string(settings.index_path(root))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+
+        index
+            .filter(|index| index.version == current_version())
+            .unwrap_or_default()
+    }
+
+    /// Write the index for `root`, replacing the previous one atomically
+    pub fn save(&self, settings: &CacheSettings, root: &Path) -> Result<()> {
+        fs::create_dir_all(&settings.dir)?;
+        let path = settings.index_path(root);
+        let partial = path.with_extension("json.tmp");
+
+        fs::write(&partial, serde_json::to_string(self)?)?;
+        fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    /// Indexed state of the journal at `path`
+    pub(crate) fn get(&self, path: &Path) -> Option<&IndexedFile> {
+        self.files.get(path)
+    }
+
+    /// Whether the journal at `path` is indexed
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Number of indexed journals
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no journals are indexed
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Delete every index in `dir`, returning how many were removed
+pub fn clear(dir: &Path) -> Result<usize> {
+    clear_cache_dir(dir, "index-")
+}
+
+/// Delete the `<prefix>*.json` files in `dir`, returning how many were
+/// removed; a missing directory has nothing to remove
+pub(crate) fn clear_cache_dir(dir: &Path, prefix: &str) -> Result<usize> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        let is_cached = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".json"));
+        if is_cached {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// 64-bit FNV-1a hash as hex; stable across runs and toolchains, unlike
+/// `std`'s default hasher
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn current_version() -> String {
+    format!("{}:{}", FORMAT_VERSION, env!("CARGO_PKG_VERSION"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{load_entries, AuthorResolver, ProjectTagger};
+    use crate::discovery::DiscoverySettings;
+    use tempfile::TempDir;
+
+    const JOURNAL: &str = "2025.03.01 - JRN - cache.md";
+
+    fn settings(dir: &TempDir, reuse: bool) -> CacheSettings {
+        CacheSettings {
+            dir: dir.path().join("cache"),
+            reuse,
+        }
+    }
+
+    fn load(root: &Path, settings: &CacheSettings) -> Vec<crate::JournalEntry> {
+        load_entries(root, &ProjectTagger::default(), &AuthorResolver::default(), false, Some(settings), &DiscoverySettings::default(), false).unwrap()
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(b""), "cbf29ce484222325");
+        assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(content_hash(b"## Task\nA"), content_hash(b"## Task\nB"));
+    }
+
+    #[test]
+    fn test_unchanged_files_are_not_parsed_again() {
+        let root = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let path = root.path().join(JOURNAL);
+        fs::write(&path, "## Task\nOriginal\n").unwrap();
+
+        let first = load(root.path(), &settings(&cache, true));
+        assert_eq!(first[0].task.as_deref(), Some("Original"));
+
+        // Doctor the index: a reused entry shows the doctored task
+        let mut index = JournalIndex::load(&settings(&cache, true), root.path());
+        let file = index.files.get_mut(&path).unwrap();
+        file.parsed.as_mut().unwrap().task = Some("From index".to_string());
+        index.save(&settings(&cache, true), root.path()).unwrap();
+
+        let cached = load(root.path(), &settings(&cache, true));
+        assert_eq!(cached[0].task.as_deref(), Some("From index"));
+        assert_eq!(cached[0].raw_content, "## Task\nOriginal\n");
+
+        // --no-cache parses again and repairs the index
+        let rescanned = load(root.path(), &settings(&cache, false));
+        assert_eq!(rescanned[0].task.as_deref(), Some("Original"));
+        let repaired = load(root.path(), &settings(&cache, true));
+        assert_eq!(repaired[0].task.as_deref(), Some("Original"));
+    }
+
+    #[test]
+    fn test_touched_file_with_same_content_reuses_parse() {
+        let root = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let path = root.path().join(JOURNAL);
+        fs::write(&path, "## Task\nOriginal\n").unwrap();
+        load(root.path(), &settings(&cache, true));
+
+        let mut index = JournalIndex::load(&settings(&cache, true), root.path());
+        let file = index.files.get_mut(&path).unwrap();
+        file.modified = Some(SystemTime::UNIX_EPOCH);
+        file.parsed.as_mut().unwrap().task = Some("From index".to_string());
+        index.save(&settings(&cache, true), root.path()).unwrap();
+
+        let entries = load(root.path(), &settings(&cache, true));
+        assert_eq!(entries[0].task.as_deref(), Some("From index"));
+
+        // The new mtime is recorded
+        let index = JournalIndex::load(&settings(&cache, true), root.path());
+        assert_ne!(index.get(&path).unwrap().modified, Some(SystemTime::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn test_changed_and_deleted_files_invalidate() {
+        let root = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let kept = root.path().join("api").join(JOURNAL);
+        let removed = root.path().join("web").join("2025.03.02 - JRN - gone.md");
+        for path in [&kept, &removed] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "## Task\nOriginal\n").unwrap();
+        }
+        load(root.path(), &settings(&cache, true));
+        assert_eq!(JournalIndex::load(&settings(&cache, true), root.path()).len(), 2);
+
+        // A different length always shows up, whatever the mtime resolution
+        fs::write(&kept, "## Task\nRewritten task\n").unwrap();
+        fs::remove_dir_all(root.path().join("web")).unwrap();
+
+        let entries = load(root.path(), &settings(&cache, true));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].task.as_deref(), Some("Rewritten task"));
+
+        let index = JournalIndex::load(&settings(&cache, true), root.path());
+        assert!(index.contains(&kept));
+        assert!(!index.contains(&removed));
+    }
+
+    #[test]
+    fn test_index_from_another_version_is_ignored() {
+        let root = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        fs::write(root.path().join(JOURNAL), "## Task\nOriginal\n").unwrap();
+        load(root.path(), &settings(&cache, true));
+
+        let path = settings(&cache, true).index_path(root.path());
+        let stale = fs::read_to_string(&path).unwrap().replace(&current_version(), "0:0.0.0");
+        fs::write(&path, stale).unwrap();
+        assert!(JournalIndex::load(&settings(&cache, true), root.path()).is_empty());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(JournalIndex::load(&settings(&cache, true), root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_clear_removes_only_indexes() {
+        let cache = TempDir::new().unwrap();
+        assert_eq!(clear(&cache.path().join("missing")).unwrap(), 0);
+
+        fs::write(cache.path().join("index-0123456789abcdef.json"), "{}").unwrap();
+        fs::write(cache.path().join("index-fedcba9876543210.json"), "{}").unwrap();
+        fs::write(cache.path().join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(clear(cache.path()).unwrap(), 2);
+        assert!(cache.path().join("notes.txt").exists());
+    }
+}
@@ -0,0 +1,303 @@
+//! Journal hygiene rules for `jrnrvw check`
+//!
+//! Rules compare the journals as they are now against a committed
+//! [`Baseline`]. Evaluation is pure: the current state, the days with
+//! entries and the day the check runs as of are all passed in.
+//!
+//! Workdays are Monday to Friday. A weekend day without an entry neither
+//! counts towards nor breaks a run of missing workdays; an entry on any day
+//! breaks it.
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::config::settings::{CheckConfig, CheckRuleConfig};
+use crate::models::{Baseline, CheckRule, OpenTask, Report, RuleSeverity, Violation};
+
+/// Rules `current` breaks, compared with `baseline`
+///
+/// `active_days` are the days with at least one entry. Rules of severity
+/// `off` are skipped.
+pub fn evaluate(
+    config: &CheckConfig,
+    baseline: &Baseline,
+    current: &Baseline,
+    active_days: &BTreeSet<NaiveDate>,
+) -> Vec<Violation> {
+    let rules = [
+        (CheckRule::OpenTaskGrowth, config.open_task_growth),
+        (CheckRule::TaskAge, config.task_age),
+        (CheckRule::MissingEntries, config.missing_entries),
+    ];
+
+    rules
+        .into_iter()
+        .filter(|(_, rule)| rule.severity != RuleSeverity::Off)
+        .filter_map(|(kind, rule)| match kind {
+            CheckRule::OpenTaskGrowth => open_task_growth(baseline, current, rule),
+            CheckRule::TaskAge => task_age(current, rule),
+            CheckRule::MissingEntries => missing_entries(baseline.as_of, current.as_of, active_days, rule),
+        })
+        .collect()
+}
+
+/// Days with at least one dated entry in the report
+pub fn active_days(report: &Report) -> BTreeSet<NaiveDate> {
+    report
+        .repositories
+        .iter()
+        .flat_map(|repo| &repo.tasks)
+        .flat_map(|task| &task.entries)
+        .filter(|entry| !entry.undated)
+        .map(|entry| entry.date)
+        .collect()
+}
+
+/// More open tasks than the baseline had, by more than `max`
+///
+/// Lists the tasks the baseline didn't have open.
+fn open_task_growth(baseline: &Baseline, current: &Baseline, rule: CheckRuleConfig) -> Option<Violation> {
+    let growth = current.open_tasks.len() as i64 - baseline.open_tasks.len() as i64;
+    if growth <= rule.max as i64 {
+        return None;
+    }
+
+    let known: BTreeSet<(&str, &str)> = baseline
+        .open_tasks
+        .iter()
+        .map(|t| (t.repository.as_str(), t.task.as_str()))
+        .collect();
+    let tasks: Vec<OpenTask> = current
+        .open_tasks
+        .iter()
+        .filter(|t| !known.contains(&(t.repository.as_str(), t.task.as_str())))
+        .cloned()
+        .collect();
+
+    Some(Violation {
+        rule: CheckRule::OpenTaskGrowth,
+        severity: rule.severity,
+        message: format!(
+            "{} open tasks, {} more than the baseline's {} (at most {} more allowed)",
+            current.open_tasks.len(),
+            growth,
+            baseline.open_tasks.len(),
+            rule.max
+        ),
+        tasks,
+        dates: Vec::new(),
+    })
+}
+
+/// Tasks open for more than `max` days
+fn task_age(current: &Baseline, rule: CheckRuleConfig) -> Option<Violation> {
+    let tasks: Vec<OpenTask> = current
+        .open_tasks
+        .iter()
+        .filter(|t| (current.as_of - t.opened).num_days() > rule.max as i64)
+        .cloned()
+        .collect();
+    if tasks.is_empty() {
+        return None;
+    }
+
+    Some(Violation {
+        rule: CheckRule::TaskAge,
+        severity: rule.severity,
+        message: format!("{} open for more than {} days", plural(tasks.len(), "task"), rule.max),
+        tasks,
+        dates: Vec::new(),
+    })
+}
+
+/// Runs of more than `max` workdays without an entry, after `since` and up
+/// to `as_of`
+///
+/// Lists every workday of those runs.
+fn missing_entries(
+    since: NaiveDate,
+    as_of: NaiveDate,
+    active_days: &BTreeSet<NaiveDate>,
+    rule: CheckRuleConfig,
+) -> Option<Violation> {
+    let mut runs: Vec<Vec<NaiveDate>> = vec![Vec::new()];
+    let mut day = since + Duration::days(1);
+    while day <= as_of {
+        if active_days.contains(&day) {
+            runs.push(Vec::new());
+        } else if is_workday(day) {
+            runs.last_mut().expect("there is always a current run").push(day);
+        }
+        day += Duration::days(1);
+    }
+
+    let long: Vec<Vec<NaiveDate>> = runs.into_iter().filter(|run| run.len() > rule.max as usize).collect();
+    let longest = long.iter().map(Vec::len).max()?;
+
+    Some(Violation {
+        rule: CheckRule::MissingEntries,
+        severity: rule.severity,
+        message: format!(
+            "{} of more than {} workdays without an entry, the longest {} workdays",
+            plural(long.len(), "run"),
+            rule.max,
+            longest
+        ),
+        tasks: Vec::new(),
+        dates: long.into_iter().flatten().collect(),
+    })
+}
+
+fn is_workday(day: NaiveDate) -> bool {
+    !matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn open(repository: &str, task: &str, opened: &str) -> OpenTask {
+        OpenTask { repository: repository.to_string(), task: task.to_string(), opened: date(opened) }
+    }
+
+    fn baseline(as_of: &str, open_tasks: Vec<OpenTask>) -> Baseline {
+        Baseline { as_of: date(as_of), open_tasks }
+    }
+
+    fn rule(max: u32) -> CheckRuleConfig {
+        CheckRuleConfig { max, severity: RuleSeverity::Fail }
+    }
+
+    fn days(days: &[&str]) -> BTreeSet<NaiveDate> {
+        days.iter().map(|d| date(d)).collect()
+    }
+
+    #[test]
+    fn test_open_task_growth_boundary() {
+        let before = baseline("2025-11-03", vec![open("api", "auth", "2025-11-01")]);
+        let now = baseline(
+            "2025-11-10",
+            vec![
+                open("api", "auth", "2025-11-01"),
+                open("api", "deploy", "2025-11-05"),
+                open("web", "css", "2025-11-07"),
+            ],
+        );
+
+        assert_eq!(open_task_growth(&before, &now, rule(2)), None, "growing by exactly max is allowed");
+
+        let violation = open_task_growth(&before, &now, rule(1)).unwrap();
+        assert_eq!(violation.message, "3 open tasks, 2 more than the baseline's 1 (at most 1 more allowed)");
+        assert_eq!(violation.tasks, vec![open("api", "deploy", "2025-11-05"), open("web", "css", "2025-11-07")]);
+
+        // Fewer open tasks is never a regression, even with a limit of 0
+        assert_eq!(open_task_growth(&now, &before, rule(0)), None);
+    }
+
+    #[test]
+    fn test_open_task_growth_replaced_tasks() {
+        // Finishing one task and opening another is no growth
+        let before = baseline("2025-11-03", vec![open("api", "auth", "2025-11-01")]);
+        let now = baseline("2025-11-10", vec![open("api", "deploy", "2025-11-05")]);
+        assert_eq!(open_task_growth(&before, &now, rule(0)), None);
+    }
+
+    #[test]
+    fn test_task_age_boundary() {
+        let now = baseline(
+            "2025-11-30",
+            vec![open("api", "auth", "2025-10-31"), open("api", "deploy", "2025-10-30"), open("web", "css", "2025-11-29")],
+        );
+
+        let violation = task_age(&now, rule(30)).unwrap();
+        assert_eq!(violation.message, "1 task open for more than 30 days");
+        assert_eq!(violation.tasks, vec![open("api", "deploy", "2025-10-30")], "30 days open is allowed");
+
+        assert_eq!(task_age(&now, rule(31)), None);
+        assert_eq!(task_age(&now, rule(0)).unwrap().tasks.len(), 3);
+        assert_eq!(task_age(&baseline("2025-11-30", vec![]), rule(0)), None);
+    }
+
+    #[test]
+    fn test_missing_entries_boundary() {
+        // Mon 2025-11-03 to Fri 2025-11-07, entries Monday and Friday
+        let active = days(&["2025-11-03", "2025-11-07"]);
+        let since = date("2025-11-02");
+        let as_of = date("2025-11-07");
+
+        assert_eq!(missing_entries(since, as_of, &active, rule(3)), None, "3 missing workdays are allowed");
+
+        let violation = missing_entries(since, as_of, &active, rule(2)).unwrap();
+        assert_eq!(violation.message, "1 run of more than 2 workdays without an entry, the longest 3 workdays");
+        assert_eq!(violation.dates, vec![date("2025-11-04"), date("2025-11-05"), date("2025-11-06")]);
+    }
+
+    #[test]
+    fn test_missing_entries_across_weekends() {
+        let since = date("2025-11-06");
+        let as_of = date("2025-11-11");
+
+        // Fri, (Sat, Sun skipped), Mon, Tue: three workdays in a row
+        assert_eq!(missing_entries(since, as_of, &BTreeSet::new(), rule(3)), None);
+
+        let violation = missing_entries(since, as_of, &BTreeSet::new(), rule(2)).unwrap();
+        assert_eq!(violation.dates, vec![date("2025-11-07"), date("2025-11-10"), date("2025-11-11")]);
+    }
+
+    #[test]
+    fn test_missing_entries_only_after_the_baseline() {
+        let active = days(&["2025-11-03"]);
+
+        // Nothing to check on the baseline's own day, or before it
+        assert_eq!(missing_entries(date("2025-11-07"), date("2025-11-07"), &active, rule(0)), None);
+        assert_eq!(missing_entries(date("2025-11-10"), date("2025-11-07"), &active, rule(0)), None);
+
+        // The baseline day itself isn't counted
+        let violation = missing_entries(date("2025-11-03"), date("2025-11-05"), &active, rule(1)).unwrap();
+        assert_eq!(violation.dates, vec![date("2025-11-04"), date("2025-11-05")]);
+    }
+
+    #[test]
+    fn test_weekend_entry_breaks_a_run() {
+        let since = date("2025-11-05");
+        let as_of = date("2025-11-11");
+        assert!(missing_entries(since, as_of, &BTreeSet::new(), rule(3)).is_some());
+        assert_eq!(missing_entries(since, as_of, &days(&["2025-11-08"]), rule(3)), None);
+    }
+
+    #[test]
+    fn test_evaluate_skips_rules_turned_off() {
+        let before = baseline("2025-11-03", vec![]);
+        let now = baseline("2025-11-10", vec![open("api", "auth", "2025-09-01")]);
+        let config = CheckConfig {
+            open_task_growth: rule(0),
+            task_age: CheckRuleConfig { max: 30, severity: RuleSeverity::Warn },
+            missing_entries: CheckRuleConfig { max: 0, severity: RuleSeverity::Off },
+        };
+
+        let violations = evaluate(&config, &before, &now, &BTreeSet::new());
+        let rules: Vec<(CheckRule, RuleSeverity)> = violations.iter().map(|v| (v.rule, v.severity)).collect();
+        assert_eq!(
+            rules,
+            vec![(CheckRule::OpenTaskGrowth, RuleSeverity::Fail), (CheckRule::TaskAge, RuleSeverity::Warn)]
+        );
+
+        // Against itself only the old task is left, and it only warns
+        let violations = evaluate(&CheckConfig::default(), &now, &now, &BTreeSet::new());
+        let rules: Vec<(CheckRule, RuleSeverity)> = violations.iter().map(|v| (v.rule, v.severity)).collect();
+        assert_eq!(rules, vec![(CheckRule::TaskAge, RuleSeverity::Warn)]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Diagnostics for `--ci`: parse errors and rule violations, each pinned to
+//! a journal file and line
+//!
+//! Parse failures and undated entries are reported on the entry's own file.
+//! An overdue task is reported on the newest entry that sets its due date,
+//! at the first line naming that date.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::models::{CiRule, Diagnostic, DiagnosticLevel, JournalEntry, Report};
+
+/// Every problem the `rules` find in the report's entries, sorted by file
+/// and line
+pub fn diagnostics(report: &Report, rules: &[CiRule]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // Grouping can list an entry more than once; report each file once
+    let mut seen = BTreeSet::new();
+    let entries = report
+        .repositories
+        .iter()
+        .flat_map(|repo| repo.tasks.iter())
+        .flat_map(|task| task.entries.iter())
+        .filter(|entry| seen.insert(entry.filepath.as_path()));
+
+    for entry in entries {
+        if rules.contains(&CiRule::Parse) {
+            if let Some(ref error) = entry.parse_error {
+                diagnostics.push(diagnostic(
+                    &entry.filepath,
+                    error.line,
+                    DiagnosticLevel::Error,
+                    CiRule::Parse,
+                    format!("journal does not parse: {}", error.reason),
+                ));
+            }
+            for warning in &entry.directive_warnings {
+                diagnostics.push(diagnostic(
+                    &entry.filepath,
+                    warning.line,
+                    DiagnosticLevel::Warning,
+                    CiRule::Parse,
+                    warning.message.clone(),
+                ));
+            }
+        }
+
+        if rules.contains(&CiRule::Undated) && entry.undated {
+            diagnostics.push(diagnostic(
+                &entry.filepath,
+                1,
+                DiagnosticLevel::Error,
+                CiRule::Undated,
+                "no date in the filename or front matter".to_string(),
+            ));
+        }
+    }
+
+    if rules.contains(&CiRule::Overdue) {
+        for overdue in &report.overdue {
+            let task = report
+                .repositories
+                .iter()
+                .filter(|repo| repo.name == overdue.repository)
+                .flat_map(|repo| repo.tasks.iter())
+                .find(|task| task.name == overdue.task);
+            let Some(entry) = task.and_then(|task| {
+                task.entries
+                    .iter()
+                    .filter(|entry| entry.due == Some(overdue.due))
+                    .max_by_key(|entry| entry.date)
+            }) else {
+                continue;
+            };
+
+            diagnostics.push(diagnostic(
+                &entry.filepath,
+                due_line(entry),
+                DiagnosticLevel::Error,
+                CiRule::Overdue,
+                format!(
+                    "task '{}' was due {} ({} day(s) overdue)",
+                    overdue.task, overdue.due, overdue.days_overdue
+                ),
+            ));
+        }
+    }
+
+    diagnostics.sort_by(|a, b| (&a.path, a.line, a.level).cmp(&(&b.path, b.line, b.level)));
+    diagnostics
+}
+
+fn diagnostic(path: &Path, line: usize, level: DiagnosticLevel, rule: CiRule, message: String) -> Diagnostic {
+    Diagnostic {
+        path: path.to_path_buf(),
+        line,
+        level,
+        rule,
+        message,
+    }
+}
+
+/// First line (from 1) naming the entry's due date, else 1
+fn due_line(entry: &JournalEntry) -> usize {
+    let Some(due) = entry.due else {
+        return 1;
+    };
+    let date = due.format("%Y-%m-%d").to_string();
+    entry
+        .raw_content
+        .lines()
+        .position(|line| line.contains(&date))
+        .map_or(1, |index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ReportBuilder;
+    use crate::models::{DirectiveWarning, ParseFailure};
+    use chrono::{Local, NaiveDate};
+    use std::path::PathBuf;
+
+    fn entry(name: &str, task: &str, content: &str) -> JournalEntry {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 3).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from(name), date);
+        entry.repository = Some("ops".to_string());
+        entry.task = Some(task.to_string());
+        entry.raw_content = content.to_string();
+        entry
+    }
+
+    fn problems() -> Report {
+        let mut broken = entry("broken.md", "Broken", "---\ndate: 2025-13-40\n---\n");
+        broken.parse_error = Some(ParseFailure {
+            line: 2,
+            reason: "invalid date `2025-13-40`".to_string(),
+        });
+        broken.directive_warnings.push(DirectiveWarning {
+            line: 3,
+            message: "unknown directive `jrnrvw:skip`".to_string(),
+        });
+
+        let mut undated = entry("notes.md", "Notes", "## Task\nNotes\n");
+        undated.undated = true;
+
+        let mut release = entry("release.md", "Release", "## Task\nRelease\n\ndue: 2025-11-10\n");
+        release.due = NaiveDate::from_ymd_opt(2025, 11, 10);
+
+        let mut later = entry("later.md", "Later", "## Task\nLater\n");
+        later.due = Some(Local::now().date_naive() + chrono::Duration::days(30));
+
+        ReportBuilder::new(vec![broken, undated, release, later]).build().unwrap()
+    }
+
+    fn lines(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_every_rule() {
+        let diagnostics = diagnostics(&problems(), &CiRule::ALL);
+        let days = (Local::now().date_naive() - NaiveDate::from_ymd_opt(2025, 11, 10).unwrap()).num_days();
+        assert_eq!(
+            lines(&diagnostics),
+            [
+                "broken.md:2: error: journal does not parse: invalid date `2025-13-40`".to_string(),
+                "broken.md:3: warning: unknown directive `jrnrvw:skip`".to_string(),
+                "notes.md:1: error: no date in the filename or front matter".to_string(),
+                format!("release.md:4: error: task 'Release' was due 2025-11-10 ({} day(s) overdue)", days),
+            ]
+        );
+        let rules: Vec<CiRule> = diagnostics.iter().map(|d| d.rule).collect();
+        assert_eq!(rules, [CiRule::Parse, CiRule::Parse, CiRule::Undated, CiRule::Overdue]);
+    }
+
+    #[test]
+    fn test_selected_rules_only() {
+        let report = problems();
+        let overdue = diagnostics(&report, &[CiRule::Overdue]);
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].path, PathBuf::from("release.md"));
+
+        assert!(diagnostics(&report, &[]).is_empty());
+    }
+}
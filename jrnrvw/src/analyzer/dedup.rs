@@ -0,0 +1,295 @@
+//! Duplicate and near-duplicate task detection
+//!
+//! Unfinished tasks copied forward from day to day drift in small ways: a
+//! capital letter, a trailing "(cont.)", a fixed typo. Within a repository,
+//! task names that are equal once case and punctuation are dropped, or
+//! that are at least as similar as the configured threshold, are folded
+//! into one task. Names with different numbers in them ("PR 112", "PR 113")
+//! are never near-duplicates, as the numbers usually tell tasks apart.
+//! Similarity links chain, so A ~ B and B ~ C puts all three together. The
+//! folded task takes the name of its newest entry.
+
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::config::settings::{DedupConfig, Similarity};
+use crate::models::{JournalEntry, TaskCluster};
+
+/// A task name as seen in one repository's entries
+struct Name {
+    name: String,
+    normalized: String,
+    first_seen: NaiveDate,
+    /// Date and file of the newest entry naming it
+    newest: (NaiveDate, String),
+    mentions: usize,
+}
+
+/// Rename every entry's task to the task it duplicates, returning the
+/// clusters of names that were folded together
+///
+/// Does nothing when the pass is disabled.
+pub fn dedup_tasks(entries: &mut [JournalEntry], config: &DedupConfig) -> Vec<TaskCluster> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    // Task names per repository, by name
+    let mut repositories: BTreeMap<String, BTreeMap<String, Name>> = BTreeMap::new();
+    for entry in entries.iter() {
+        let Some(task) = entry.task.as_ref() else { continue };
+        let repository = entry.repository.clone().unwrap_or_else(|| "Unknown".to_string());
+        let newest = (entry.date, entry.filename.clone());
+        repositories
+            .entry(repository)
+            .or_default()
+            .entry(task.clone())
+            .and_modify(|name| {
+                name.first_seen = name.first_seen.min(entry.date);
+                name.newest = name.newest.clone().max(newest.clone());
+                name.mentions += 1;
+            })
+            .or_insert_with(|| Name {
+                name: task.clone(),
+                normalized: normalize_title(task),
+                first_seen: entry.date,
+                newest,
+                mentions: 1,
+            });
+    }
+
+    let mut clusters = Vec::new();
+    let mut renames: HashMap<(String, String), String> = HashMap::new();
+    for (repository, names) in repositories {
+        let names: Vec<Name> = names.into_values().collect();
+        for group in group_duplicates(&names, config) {
+            if group.len() < 2 {
+                continue;
+            }
+
+            let mut members: Vec<&Name> = group.iter().map(|&i| &names[i]).collect();
+            let task = members.iter().max_by(|a, b| a.newest.cmp(&b.newest)).expect("cluster has members").name.clone();
+            members.sort_by(|a, b| (a.first_seen, &a.name).cmp(&(b.first_seen, &b.name)));
+
+            for member in &members {
+                renames.insert((repository.clone(), member.name.clone()), task.clone());
+            }
+            clusters.push(TaskCluster {
+                repository: repository.clone(),
+                task,
+                members: members.iter().map(|m| m.name.clone()).collect(),
+                first_seen: members.iter().map(|m| m.first_seen).min().expect("cluster has members"),
+                last_seen: members.iter().map(|m| m.newest.0).max().expect("cluster has members"),
+                mentions: members.iter().map(|m| m.mentions).sum(),
+            });
+        }
+    }
+
+    for entry in entries.iter_mut() {
+        let Some(task) = entry.task.as_ref() else { continue };
+        let repository = entry.repository.clone().unwrap_or_else(|| "Unknown".to_string());
+        if let Some(renamed) = renames.get(&(repository, task.clone())) {
+            entry.task = Some(renamed.clone());
+        }
+    }
+
+    clusters.sort_by(|a, b| (&a.repository, &a.task).cmp(&(&b.repository, &b.task)));
+    clusters
+}
+
+/// Indices of `names` grouped into duplicates
+fn group_duplicates(names: &[Name], config: &DedupConfig) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..names.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for a in 0..names.len() {
+        for b in a + 1..names.len() {
+            let (x, y) = (&names[a].normalized, &names[b].normalized);
+            // Names of nothing but punctuation only match themselves
+            let duplicate = x == y
+                || (!x.is_empty()
+                    && !y.is_empty()
+                    && numbers(x) == numbers(y)
+                    && similarity(x, y, config.method) >= config.threshold);
+            if duplicate {
+                let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                parent[rb] = ra;
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..names.len() {
+        let r = root(&mut parent, i);
+        groups.entry(r).or_default().push(i);
+    }
+    groups.into_values().collect()
+}
+
+/// Lower-cased words of a task name, punctuation dropped
+pub fn normalize_title(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Words of a normalized name that are numbers
+fn numbers(normalized: &str) -> BTreeSet<&str> {
+    normalized.split(' ').filter(|w| w.chars().all(|c| c.is_ascii_digit())).collect()
+}
+
+/// How alike two normalized names are, from 0 to 1
+pub fn similarity(a: &str, b: &str, method: Similarity) -> f64 {
+    match method {
+        Similarity::Levenshtein => {
+            let longest = a.chars().count().max(b.chars().count());
+            if longest == 0 {
+                return 1.0;
+            }
+            1.0 - levenshtein(a, b) as f64 / longest as f64
+        }
+        Similarity::Tokens => {
+            let a: BTreeSet<&str> = a.split(' ').filter(|w| !w.is_empty()).collect();
+            let b: BTreeSet<&str> = b.split(' ').filter(|w| !w.is_empty()).collect();
+            let either = a.union(&b).count();
+            if either == 0 {
+                return 1.0;
+            }
+            a.intersection(&b).count() as f64 / either as f64
+        }
+    }
+}
+
+/// Edits (insertions, deletions, substitutions) turning `a` into `b`
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(repo: &str, task: &str, day: u32) -> JournalEntry {
+        let date = NaiveDate::from_ymd_opt(2025, 11, day).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from(format!("2025.11.{:02} - JRN - {}.md", day, task)), date);
+        entry.filename = format!("2025.11.{:02} - JRN - {}.md", day, task);
+        entry.repository = Some(repo.to_string());
+        entry.task = Some(task.to_string());
+        entry
+    }
+
+    fn tasks(entries: &[JournalEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.task.as_deref().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(normalize_title("Fix login-bug (cont.)!"), "fix login bug cont");
+        assert_eq!(normalize_title("  Ship   v2  "), "ship v2");
+        assert_eq!(normalize_title("???"), "");
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert!((similarity("fix login bug", "fix login bugs", Similarity::Levenshtein) - 13.0 / 14.0).abs() < 1e-9);
+        assert_eq!(similarity("deploy api", "api deploy", Similarity::Tokens), 1.0);
+        assert_eq!(similarity("fix login", "fix logout", Similarity::Tokens), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_exact_duplicates_fold_after_normalizing() {
+        let mut entries = vec![
+            entry("web", "Fix login bug", 10),
+            entry("web", "fix login bug.", 11),
+            entry("web", "FIX LOGIN BUG", 12),
+            entry("web", "Write docs", 12),
+            entry("api", "fix login bug", 12),
+        ];
+        let clusters = dedup_tasks(&mut entries, &DedupConfig::default());
+
+        assert_eq!(clusters.len(), 1);
+        let cluster = &clusters[0];
+        assert_eq!(cluster.repository, "web");
+        assert_eq!(cluster.task, "FIX LOGIN BUG");
+        assert_eq!(cluster.members, vec!["Fix login bug", "fix login bug.", "FIX LOGIN BUG"]);
+        assert_eq!(cluster.first_seen, NaiveDate::from_ymd_opt(2025, 11, 10).unwrap());
+        assert_eq!(cluster.last_seen, NaiveDate::from_ymd_opt(2025, 11, 12).unwrap());
+        assert_eq!(cluster.mentions, 3);
+
+        // Other repositories keep their own tasks
+        assert_eq!(
+            tasks(&entries),
+            vec!["FIX LOGIN BUG", "FIX LOGIN BUG", "FIX LOGIN BUG", "Write docs", "fix login bug"]
+        );
+    }
+
+    #[test]
+    fn test_near_duplicates_by_threshold() {
+        let entries = vec![
+            entry("web", "migrate sessions table", 10),
+            entry("web", "migrate session table", 11),
+            entry("web", "migrate session tables", 12),
+            entry("web", "deploy", 12),
+        ];
+
+        let mut folded = entries.clone();
+        let clusters = dedup_tasks(&mut folded, &DedupConfig::default());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members.len(), 3);
+        assert_eq!(clusters[0].task, "migrate session tables");
+
+        let strict = DedupConfig { threshold: 1.0, ..DedupConfig::default() };
+        let mut kept = entries.clone();
+        assert!(dedup_tasks(&mut kept, &strict).is_empty());
+        assert_eq!(tasks(&kept), tasks(&entries));
+
+        let by_words = DedupConfig { method: Similarity::Tokens, threshold: 0.5, ..DedupConfig::default() };
+        let mut folded = entries.clone();
+        let clusters = dedup_tasks(&mut folded, &by_words);
+        assert_eq!(clusters[0].members, vec!["migrate sessions table", "migrate session table", "migrate session tables"]);
+    }
+
+    #[test]
+    fn test_numbers_tell_tasks_apart() {
+        let mut entries = vec![
+            entry("web", "Review PR 112", 10),
+            entry("web", "Review PR 113", 11),
+            entry("web", "review pr 113!", 12),
+        ];
+        let clusters = dedup_tasks(&mut entries, &DedupConfig::default());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members, vec!["Review PR 113", "review pr 113!"]);
+        assert_eq!(tasks(&entries), vec!["Review PR 112", "review pr 113!", "review pr 113!"]);
+    }
+
+    #[test]
+    fn test_disabled_leaves_entries_alone() {
+        let mut entries = vec![entry("web", "Fix login bug", 10), entry("web", "fix login bug", 11)];
+        let config = DedupConfig { enabled: false, ..DedupConfig::default() };
+        assert!(dedup_tasks(&mut entries, &config).is_empty());
+        assert_eq!(tasks(&entries), vec!["Fix login bug", "fix login bug"]);
+    }
+}
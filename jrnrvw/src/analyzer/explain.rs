@@ -0,0 +1,329 @@
+//! Provenance for `--explain` reports
+//!
+//! The normal pipeline never pays for this: entries are re-parsed with line
+//! tracking only when an explanation is asked for, and clusters are filled in
+//! after grouping by walking the finished report.
+
+use std::collections::HashSet;
+
+use crate::models::{
+    Cluster, EntryExplanation, GroupBy, JournalEntry, MatchRule, Provenance, RejectReason,
+    RejectedLine, Report, StatusMarker,
+};
+use crate::parser::metadata::activity_from_line;
+use crate::parser::org::is_org;
+use crate::parser::JournalParser;
+use crate::profile;
+use super::grouper::entry_tags;
+use super::signals::checkbox_state;
+
+/// Attach an explanation to every entry that has content
+pub fn explain_entries(entries: &mut [JournalEntry]) {
+    let _span = profile::span("explain");
+    for entry in entries {
+        entry.explanation = explain_entry(entry).map(Box::new);
+    }
+}
+
+/// Work out where each of `entry`'s extracted values came from
+///
+/// Returns `None` when the entry has no content or it cannot be parsed, as
+/// then only filename metadata was extracted, and for org journals, whose
+/// parser keeps no line numbers.
+pub fn explain_entry(entry: &JournalEntry) -> Option<EntryExplanation> {
+    if entry.raw_content.is_empty() || is_org(&entry.filepath) {
+        return None;
+    }
+
+    let parsed = JournalParser::new(entry.raw_content.clone())
+        .with_line_tracking()
+        .parse()
+        .ok()?;
+    let lines = parsed.source_lines.as_ref()?;
+    let raw_lines: Vec<&str> = entry.raw_content.lines().collect();
+    let raw = |line: usize| raw_lines.get(line - 1).map_or("", |l| l.trim()).to_string();
+
+    let section_start = |name: &str, rule: MatchRule| {
+        let line = *lines.sections.get(name)?.first()?;
+        Some(Provenance { line, raw: raw(line), rule, status: None })
+    };
+
+    let mut explanation = EntryExplanation {
+        task: section_start("Task", MatchRule::TaskSection),
+        repository: section_start("Repository", MatchRule::RepositorySection),
+        ..EntryExplanation::default()
+    };
+
+    if parsed.project().is_some() {
+        explanation.project = parsed
+            .front_matter
+            .key_lines
+            .get("project")
+            .map(|&line| Provenance { line, raw: raw(line), rule: MatchRule::FrontMatter, status: None });
+    }
+
+    let mut accounted: HashSet<usize> = explanation.task.iter().map(|p| p.line).collect();
+
+    if let (Some(content), Some(starts)) = (parsed.sections.get("Activities"), lines.sections.get("Activities")) {
+        let content_lines = content.lines().filter(|l| !l.trim().is_empty());
+        for (text, &line) in content_lines.zip(starts) {
+            accounted.insert(line);
+            match activity_from_line(text) {
+                Some(activity) => explanation.activities.push(Provenance {
+                    line,
+                    raw: raw(line),
+                    rule: rule_for(&raw(line)),
+                    status: checkbox_state(&activity).map(|done| {
+                        if done { StatusMarker::Done } else { StatusMarker::Open }
+                    }),
+                }),
+                None => explanation.rejected.push(RejectedLine {
+                    line,
+                    raw: raw(line),
+                    reason: RejectReason::NumberWithoutDelimiter,
+                }),
+            }
+        }
+    }
+
+    for line in (parsed.front_matter.lines + 1)..=raw_lines.len() {
+        if accounted.contains(&line) || !looks_like_task(&raw(line)) {
+            continue;
+        }
+
+        let reason = if let Some(directive) = lines.ignored_by(line) {
+            RejectReason::Ignored { directive: directive.kind, directive_line: directive.line }
+        } else {
+            match lines.section_at(line) {
+                None => RejectReason::BeforeFirstSection,
+                // Continuation of an extracted activity
+                Some("Activities") => continue,
+                Some(section) => RejectReason::OutsideActivities { section: section.to_string() },
+            }
+        };
+        explanation.rejected.push(RejectedLine { line, raw: raw(line), reason });
+    }
+    explanation.rejected.sort_by_key(|rejected| rejected.line);
+
+    Some(explanation)
+}
+
+/// Record which report group each explained entry joined, and why
+pub fn annotate_clusters(report: &mut Report, group_by: GroupBy) {
+    for repo in &mut report.repositories {
+        for task in &mut repo.tasks {
+            let size = task.entries.len();
+            for entry in &mut task.entries {
+                let Some(explanation) = entry.explanation.as_deref() else {
+                    continue;
+                };
+                let cluster = Cluster {
+                    group: repo.name.clone(),
+                    task: task.name.clone(),
+                    size,
+                    reason: cluster_reason(entry, explanation, group_by, &task.name, size),
+                };
+                if let Some(explanation) = entry.explanation.as_deref_mut() {
+                    explanation.cluster = Some(cluster);
+                }
+            }
+        }
+    }
+}
+
+/// Plain-language account of the keys an entry was grouped by
+fn cluster_reason(
+    entry: &JournalEntry,
+    explanation: &EntryExplanation,
+    group_by: GroupBy,
+    task_name: &str,
+    size: usize,
+) -> String {
+    let task = match &explanation.task {
+        Some(p) => format!("task from {} on line {}", p.rule, p.line),
+        None => format!("no ## Task section, so filed under '{}'", task_name),
+    };
+
+    let key = match group_by {
+        GroupBy::Repository => {
+            let repo = match (&explanation.repository, &entry.repository) {
+                (Some(p), _) => format!("repository from {} on line {}", p.rule, p.line),
+                (None, Some(_)) => "repository detected from the journal's location".to_string(),
+                (None, None) => "no repository found".to_string(),
+            };
+            format!("{}; {}", repo, task)
+        }
+        GroupBy::Project => {
+            let project = match (&explanation.project, &entry.project) {
+                (Some(p), _) => format!("project from {} on line {}", p.rule, p.line),
+                (None, Some(_)) => "project from tagging rules".to_string(),
+                (None, None) => "no project tag".to_string(),
+            };
+            format!("{}; {}", project, task)
+        }
+        GroupBy::Tag => {
+            let tags = entry_tags(entry);
+            let tags = if tags.is_empty() {
+                "no tags or project".to_string()
+            } else {
+                format!("tagged {}", tags.into_iter().collect::<Vec<_>>().join(", "))
+            };
+            format!("{}; {}", tags, task)
+        }
+        GroupBy::Author => {
+            let author = match &entry.author {
+                Some(author) => format!("written by {}", author),
+                None => "no author found".to_string(),
+            };
+            format!("{}; {}", author, task)
+        }
+        GroupBy::Task => task,
+        GroupBy::Date | GroupBy::Week | GroupBy::Month => {
+            format!("filename date {}", entry.date)
+        }
+    };
+
+    match size {
+        1 => format!("{}; no other entries share it", key),
+        2 => format!("{}; shared with 1 other entry", key),
+        _ => format!("{}; shared with {} other entries", key, size - 1),
+    }
+}
+
+/// Extraction rule for an Activities line, judged from the line as written
+fn rule_for(raw: &str) -> MatchRule {
+    if raw.starts_with(['-', '*', '+']) {
+        MatchRule::BulletItem
+    } else if raw.starts_with(|c: char| c.is_ascii_digit()) && raw.contains(['.', ')']) {
+        MatchRule::NumberedItem
+    } else {
+        MatchRule::PlainLine
+    }
+}
+
+/// Whether a line is written like a task: a list item or a checkbox
+fn looks_like_task(raw: &str) -> bool {
+    let list_item = ["- ", "* ", "+ "].iter().any(|marker| raw.starts_with(marker));
+    let numbered = raw
+        .find(|c: char| !c.is_ascii_digit())
+        .is_some_and(|idx| idx > 0 && raw[idx..].starts_with(['.', ')']));
+    let checkbox = ["[ ]", "[x]", "[X]"].iter().any(|marker| raw.starts_with(marker));
+    list_item || numbered || checkbox
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ReportBuilder;
+    use crate::models::DirectiveKind;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn entry(content: &str) -> JournalEntry {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        JournalEntry::new(PathBuf::from("2025.11.13 - JRN - test.md"), date)
+            .with_content(content.to_string())
+    }
+
+    #[test]
+    fn test_activities_carry_line_rule_and_status() {
+        let explanation = explain_entry(&entry(
+            "## Task\nShip it\n\n## Activities\n- [x] Wrote tests\n2. [ ] Review\n\nPlain note\n",
+        ))
+        .unwrap();
+
+        let task = explanation.task.unwrap();
+        assert_eq!((task.line, task.raw.as_str(), task.rule), (2, "Ship it", MatchRule::TaskSection));
+
+        let activities: Vec<_> = explanation
+            .activities
+            .iter()
+            .map(|p| (p.line, p.rule, p.status))
+            .collect();
+        assert_eq!(
+            activities,
+            vec![
+                (5, MatchRule::BulletItem, Some(StatusMarker::Done)),
+                (6, MatchRule::NumberedItem, Some(StatusMarker::Open)),
+                (8, MatchRule::PlainLine, None),
+            ]
+        );
+        assert!(explanation.rejected.is_empty());
+    }
+
+    #[test]
+    fn test_rejected_lines_explain_why() {
+        let content = "- [ ] stray\n\n## Notes\n- [ ] follow up\n\n## Activities\n- kept\n\n2025 was busy\n\n<!-- jrnrvw:ignore-section -->\n- hidden\n\n## Time Spent\n1h\n<!-- jrnrvw:ignore -->\n- dropped\n";
+        let explanation = explain_entry(&entry(content)).unwrap();
+
+        assert_eq!(explanation.activities.len(), 1);
+        let rejected: Vec<_> = explanation
+            .rejected
+            .iter()
+            .map(|r| (r.line, r.reason.clone()))
+            .collect();
+        assert_eq!(
+            rejected,
+            vec![
+                (1, RejectReason::BeforeFirstSection),
+                (4, RejectReason::OutsideActivities { section: "Notes".to_string() }),
+                (9, RejectReason::NumberWithoutDelimiter),
+                (12, RejectReason::Ignored { directive: DirectiveKind::IgnoreSection, directive_line: 11 }),
+                (17, RejectReason::Ignored { directive: DirectiveKind::Ignore, directive_line: 16 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_front_matter_project_line() {
+        let explanation = explain_entry(&entry("---\ntitle: x\nproject: infra\n---\n## Task\nT\n")).unwrap();
+
+        let project = explanation.project.unwrap();
+        assert_eq!((project.line, project.raw.as_str()), (3, "project: infra"));
+        assert_eq!(explanation.task.unwrap().line, 6);
+
+        let explanation = explain_entry(&entry("+++\nproject = \"infra\"\n+++\n## Task\nT\n")).unwrap();
+        let project = explanation.project.unwrap();
+        assert_eq!((project.line, project.raw.as_str()), (2, "project = \"infra\""));
+    }
+
+    #[test]
+    fn test_entries_without_content_are_not_explained() {
+        assert!(explain_entry(&entry("")).is_none());
+    }
+
+    #[test]
+    fn test_clusters_record_group_and_size() {
+        let mut entries = vec![
+            entry("## Task\nShared\n\n## Repository\nweb\n"),
+            entry("## Task\nShared\n\n## Repository\nweb\n"),
+            entry("## Notes\nno task\n"),
+        ];
+        for e in &mut entries {
+            e.task = e.raw_content.contains("Shared").then(|| "Shared".to_string());
+            e.repository = Some("web".to_string());
+        }
+        explain_entries(&mut entries);
+
+        let mut report = ReportBuilder::new(entries).build().unwrap();
+        annotate_clusters(&mut report, GroupBy::Repository);
+
+        let clusters: Vec<Cluster> = report.repositories[0]
+            .tasks
+            .iter()
+            .flat_map(|t| &t.entries)
+            .filter_map(|e| e.explanation.as_ref()?.cluster.clone())
+            .collect();
+        let shared = clusters.iter().find(|c| c.task == "Shared").unwrap();
+        assert_eq!((shared.group.as_str(), shared.size), ("web", 2));
+        assert_eq!(
+            shared.reason,
+            "repository from ## Repository section on line 5; task from ## Task section on line 2; shared with 1 other entry"
+        );
+        let general = clusters.iter().find(|c| c.task == "General").unwrap();
+        assert_eq!(
+            general.reason,
+            "repository detected from the journal's location; no ## Task section, so filed under 'General'; no other entries share it"
+        );
+    }
+}
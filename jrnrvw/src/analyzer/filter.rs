@@ -1,6 +1,6 @@
 //! Filtering journal entries by date and other criteria
 
-use chrono::{NaiveDate, Datelike, Duration, Local};
+use chrono::{NaiveDate, Datelike, Duration, Local, Months};
 use crate::models::JournalEntry;
 use crate::error::Result;
 
@@ -32,6 +32,39 @@ pub enum TimeRange {
     Before(NaiveDate),
 }
 
+impl TimeRange {
+    /// First and last day of the range as of `today`, either open-ended
+    /// when the range has no bound on that side
+    pub fn bounds(&self, today: NaiveDate) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        match self {
+            TimeRange::LastWeek => (Some(today - Duration::days(7)), Some(today)),
+
+            TimeRange::LastMonth => (Some(today - Duration::days(30)), Some(today)),
+
+            TimeRange::ThisWeek => {
+                // Find the Monday of this week
+                let weekday = today.weekday().num_days_from_monday();
+                let monday = today - Duration::days(weekday as i64);
+                (Some(monday), Some(monday + Duration::days(6)))
+            }
+
+            TimeRange::ThisMonth => {
+                let first = today.with_day(1).expect("every month has a first day");
+                let next = first + Months::new(1);
+                (Some(first), Some(next - Duration::days(1)))
+            }
+
+            TimeRange::ActivityDays => (None, None),
+
+            TimeRange::Custom(from, to) => (Some(*from), Some(*to)),
+
+            TimeRange::Since(since) => (Some(*since), None),
+
+            TimeRange::Before(before) => (None, Some(*before)),
+        }
+    }
+}
+
 /// Filter for journal entries with builder pattern
 #[derive(Debug, Clone)]
 pub struct EntryFilter {
@@ -44,8 +77,20 @@ pub struct EntryFilter {
     /// Task name filter
     task: Option<String>,
 
+    /// Project tag filter
+    project: Option<String>,
+
+    /// Author filter, ignoring case
+    author: Option<String>,
+
+    /// Tag filter, ignoring case; the project counts as a tag
+    tag: Option<String>,
+
     /// Only include entries with activities
     has_activities: bool,
+
+    /// Drop entries whose filename date did not parse
+    strict_dates: bool,
 }
 
 impl EntryFilter {
@@ -55,7 +100,11 @@ impl EntryFilter {
             time_range: None,
             repository: None,
             task: None,
+            project: None,
+            author: None,
+            tag: None,
             has_activities: false,
+            strict_dates: false,
         }
     }
 
@@ -65,6 +114,11 @@ impl EntryFilter {
         self
     }
 
+    /// The time range filter, if any
+    pub fn time_range(&self) -> Option<&TimeRange> {
+        self.time_range.as_ref()
+    }
+
     /// Filter by repository name
     pub fn with_repository(mut self, repository: impl Into<String>) -> Self {
         self.repository = Some(repository.into());
@@ -77,16 +131,44 @@ impl EntryFilter {
         self
     }
 
+    /// Filter by project tag
+    pub fn with_project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// Filter by author, ignoring case
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Filter by tag or project, ignoring case
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
     /// Only include entries with activities
     pub fn with_activities_only(mut self) -> Self {
         self.has_activities = true;
         self
     }
 
+    /// Drop undated entries, which time ranges otherwise keep
+    pub fn with_strict_dates(mut self) -> Self {
+        self.strict_dates = true;
+        self
+    }
+
     /// Apply the filter to a vector of journal entries
     pub fn apply(&self, entries: Vec<JournalEntry>) -> Result<Vec<JournalEntry>> {
         let mut filtered = entries;
 
+        if self.strict_dates {
+            filtered.retain(|e| !e.undated);
+        }
+
         // Apply time range filter
         if let Some(ref time_range) = self.time_range {
             filtered = self.filter_by_time_range(filtered, time_range)?;
@@ -108,6 +190,21 @@ impl EntryFilter {
                 .collect();
         }
 
+        // Apply project filter
+        if let Some(ref project) = self.project {
+            filtered.retain(|e| e.has_project(project));
+        }
+
+        // Apply author filter
+        if let Some(ref author) = self.author {
+            filtered.retain(|e| e.has_author(author));
+        }
+
+        // Apply tag filter
+        if let Some(ref tag) = self.tag {
+            filtered.retain(|e| e.has_tag(tag));
+        }
+
         // Apply activities filter
         if self.has_activities {
             filtered = filtered
@@ -120,6 +217,9 @@ impl EntryFilter {
     }
 
     /// Filter entries by time range
+    ///
+    /// Undated entries have no real date to compare, so every date-based
+    /// range keeps them; `with_strict_dates` drops them instead.
     fn filter_by_time_range(
         &self,
         entries: Vec<JournalEntry>,
@@ -127,78 +227,36 @@ impl EntryFilter {
     ) -> Result<Vec<JournalEntry>> {
         let today = Local::now().date_naive();
 
-        let filtered = match time_range {
-            TimeRange::LastWeek => {
-                let start_date = today - Duration::days(7);
-                entries
-                    .into_iter()
-                    .filter(|e| e.date >= start_date && e.date <= today)
-                    .collect()
-            }
-
-            TimeRange::LastMonth => {
-                let start_date = today - Duration::days(30);
-                entries
-                    .into_iter()
-                    .filter(|e| e.date >= start_date && e.date <= today)
-                    .collect()
-            }
-
-            TimeRange::ThisWeek => {
-                // Find the Monday of this week
-                let weekday = today.weekday().num_days_from_monday();
-                let monday = today - Duration::days(weekday as i64);
-                let sunday = monday + Duration::days(6);
-
-                entries
-                    .into_iter()
-                    .filter(|e| e.date >= monday && e.date <= sunday)
-                    .collect()
-            }
-
-            TimeRange::ThisMonth => {
-                let year = today.year();
-                let month = today.month();
-
-                entries
-                    .into_iter()
-                    .filter(|e| e.date.year() == year && e.date.month() == month)
-                    .collect()
-            }
-
-            TimeRange::ActivityDays => {
-                entries
-                    .into_iter()
-                    .filter(|e| !e.activities.is_empty())
-                    .collect()
-            }
-
-            TimeRange::Custom(from, to) => {
-                entries
-                    .into_iter()
-                    .filter(|e| e.date >= *from && e.date <= *to)
-                    .collect()
-            }
+        let (from, to) = time_range.bounds(today);
+        let in_range = |date: NaiveDate| from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to);
 
-            TimeRange::Since(date) => {
-                entries
-                    .into_iter()
-                    .filter(|e| e.date >= *date)
-                    .collect()
-            }
-
-            TimeRange::Before(date) => {
-                entries
-                    .into_iter()
-                    .filter(|e| e.date <= *date)
-                    .collect()
-            }
-        };
+        let filtered = entries
+            .into_iter()
+            .filter(|e| match time_range {
+                TimeRange::ActivityDays => !e.activities.is_empty(),
+                _ => e.undated || in_range(e.date),
+            })
+            .collect();
 
         Ok(filtered)
     }
 }
 
+/// Resolve a relative date such as `7d` (seven days ago) or `2w` (two weeks
+/// ago) against `today`; `None` if `spec` is not one
+pub fn parse_relative_date(spec: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let spec = spec.trim();
+    let unit = spec.chars().last()?;
+    let count: i64 = spec[..spec.len() - unit.len_utf8()].parse().ok()?;
+
+    let days = match unit.to_ascii_lowercase() {
+        'd' => count,
+        'w' => count.checked_mul(7)?,
+        _ => return None,
+    };
+    today.checked_sub_signed(Duration::try_days(days)?)
+}
+
 impl Default for EntryFilter {
     fn default() -> Self {
         Self::new()
@@ -239,6 +297,55 @@ mod tests {
         assert_eq!(filtered.len(), 2);
     }
 
+    #[test]
+    fn test_filter_by_project() {
+        let mut entries = vec![
+            create_test_entry("dns", Some("task1"), "2025-11-13"),
+            create_test_entry("web", Some("task2"), "2025-11-14"),
+            create_test_entry("terraform", Some("task3"), "2025-11-15"),
+        ];
+        entries[0].project = Some("infra".to_string());
+        entries[2].project = Some("infra".to_string());
+
+        let filter = EntryFilter::new().with_project("infra");
+        let result = filter.apply(entries).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|e| e.has_project("infra")));
+    }
+
+    #[test]
+    fn test_filter_by_author() {
+        let mut entries = vec![
+            create_test_entry("dns", Some("task1"), "2025-11-13"),
+            create_test_entry("web", Some("task2"), "2025-11-14"),
+            create_test_entry("web", Some("task3"), "2025-11-15"),
+        ];
+        entries[0].author = Some("Alice".to_string());
+        entries[1].author = Some("bob".to_string());
+
+        let result = EntryFilter::new().with_author("alice").apply(entries).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].author.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn test_filter_by_tag() {
+        let mut entries = vec![
+            create_test_entry("dns", Some("task1"), "2025-11-13"),
+            create_test_entry("web", Some("task2"), "2025-11-14"),
+            create_test_entry("web", Some("task3"), "2025-11-15"),
+        ];
+        entries[0].tags = vec!["Database".to_string()];
+        entries[1].project = Some("database".to_string());
+
+        let result = EntryFilter::new().with_tag("database").apply(entries).unwrap();
+
+        let tasks: Vec<_> = result.iter().map(|e| e.task.as_deref().unwrap()).collect();
+        assert_eq!(tasks, ["task1", "task2"]);
+    }
+
     #[test]
     fn test_filter_with_activities() {
         let mut entry1 = create_test_entry("repo1", None, "2025-11-13");
@@ -598,6 +705,46 @@ mod tests {
         assert_eq!(filtered[0].date.day(), 31);
     }
 
+    #[test]
+    fn test_undated_entries_pass_time_ranges_unless_strict() {
+        let mut undated = create_test_entry("repo1", None, "2020-01-01");
+        undated.undated = true;
+        let entries = vec![
+            create_test_entry("repo1", None, "2025-11-10"),
+            create_test_entry("repo1", None, "2025-11-14"),
+            undated,
+        ];
+        let range = TimeRange::Since(NaiveDate::from_ymd_opt(2025, 11, 12).unwrap());
+
+        let filter = EntryFilter::new().with_time_range(range.clone());
+        let filtered = filter.apply(entries.clone()).unwrap();
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().any(|e| e.undated));
+
+        let filter = EntryFilter::new().with_time_range(range).with_strict_dates();
+        let filtered = filter.apply(entries.clone()).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].date.day(), 14);
+
+        // Strict dates apply without a time range too
+        let filter = EntryFilter::new().with_strict_dates();
+        assert_eq!(filter.apply(entries).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_relative_date() {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 15).unwrap();
+
+        assert_eq!(parse_relative_date("7d", today), NaiveDate::from_ymd_opt(2025, 11, 8));
+        assert_eq!(parse_relative_date("2w", today), NaiveDate::from_ymd_opt(2025, 11, 1));
+        assert_eq!(parse_relative_date("0d", today), Some(today));
+        assert_eq!(parse_relative_date("3W", today), NaiveDate::from_ymd_opt(2025, 10, 25));
+
+        for spec in ["", "d", "7", "7m", "-", "2025-11-01", "1.5w", "99999999999999w"] {
+            assert_eq!(parse_relative_date(spec, today), None, "{}", spec);
+        }
+    }
+
     fn create_test_entry(repo: &str, task: Option<&str>, date_str: &str) -> JournalEntry {
         let date_parts: Vec<&str> = date_str.split('-').collect();
         let date = NaiveDate::from_ymd_opt(
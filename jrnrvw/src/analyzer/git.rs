@@ -0,0 +1,119 @@
+//! Git metadata for journal entries
+//!
+//! With `--git-metadata` (or `discovery.git_metadata`), each entry records
+//! the last commit touching its file: hash, author and date, as given by
+//! `git log -1`. Journals outside a repository, files never committed, and
+//! machines without git simply get no metadata.
+
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+use chrono::DateTime;
+
+use crate::models::{GitMetadata, JournalEntry};
+use crate::profile::{self, Context};
+
+/// Separates fields in the `git log` format; never appears in names
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// `git log` format: hash, author name, strict ISO 8601 author date
+const LOG_FORMAT: &str = "--format=%H%x1f%an%x1f%aI";
+
+/// Last commit touching `path`
+///
+/// `None` when git is missing, the file is not committed, or it is outside
+/// a repository.
+pub fn last_commit(path: &Path) -> Option<GitMetadata> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "-1", LOG_FORMAT, "--"])
+        .arg(path.file_name()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_log_line(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Metadata from one line of [`LOG_FORMAT`] output
+fn parse_log_line(line: &str) -> Option<GitMetadata> {
+    let mut fields = line.trim_end().splitn(3, FIELD_SEPARATOR);
+    let commit = fields.next().filter(|hash| !hash.is_empty())?;
+    let author = fields.next()?;
+    let date = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+
+    Some(GitMetadata {
+        commit: commit.to_string(),
+        author: author.to_string(),
+        date,
+    })
+}
+
+/// Record the last commit of every entry's file, one git call per file
+/// spread across the available cores
+pub fn enrich_entries(entries: &mut [JournalEntry]) {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(entries.len());
+    if workers <= 1 {
+        for entry in entries {
+            entry.git = last_commit(&entry.filepath);
+        }
+        return;
+    }
+
+    let chunk_size = entries.len().div_ceil(workers);
+    let context = profile::context();
+    thread::scope(|scope| {
+        for chunk in entries.chunks_mut(chunk_size) {
+            let context = context.clone();
+            scope.spawn(move || {
+                let _attached = context.as_ref().map(Context::attach);
+                for entry in chunk {
+                    entry.git = last_commit(&entry.filepath);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let git = parse_log_line(
+            "0123456789abcdef0123456789abcdef01234567\u{1f}Carol Danvers\u{1f}2025-11-20T09:30:00+01:00\n",
+        )
+        .unwrap();
+
+        assert_eq!(git.commit, "0123456789abcdef0123456789abcdef01234567");
+        assert_eq!(git.short_commit(), "0123456");
+        assert_eq!(git.author, "Carol Danvers");
+        assert_eq!(git.date.to_rfc3339(), "2025-11-20T09:30:00+01:00");
+    }
+
+    #[test]
+    fn test_parse_log_line_rejects_incomplete_output() {
+        // Nothing committed: git log prints nothing
+        assert_eq!(parse_log_line(""), None);
+        assert_eq!(parse_log_line("abc123\u{1f}Carol"), None);
+        assert_eq!(parse_log_line("abc123\u{1f}Carol\u{1f}yesterday"), None);
+    }
+
+    #[test]
+    fn test_outside_a_repository() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("2025.11.20 - JRN - x.md");
+        std::fs::write(&path, "## Task\nx\n").unwrap();
+
+        let mut entries = vec![JournalEntry::new(path, chrono::NaiveDate::from_ymd_opt(2025, 11, 20).unwrap())];
+        enrich_entries(&mut entries);
+        assert_eq!(entries[0].git, None);
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::models::{JournalEntry, Repository, Task, GroupBy, SortBy};
 use crate::error::Result;
-use std::collections::HashMap;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use super::authors::UNATTRIBUTED;
 
 /// Groups journal entries into repositories and tasks
 #[derive(Debug)]
@@ -12,12 +14,21 @@ pub struct Grouper {
 
     /// How to sort entries
     sort_by: SortBy,
+
+    /// First day of the week when grouping by week
+    week_start: Weekday,
 }
 
 impl Grouper {
     /// Create a new grouper
     pub fn new(group_by: GroupBy, sort_by: SortBy) -> Self {
-        Self { group_by, sort_by }
+        Self { group_by, sort_by, week_start: Weekday::Mon }
+    }
+
+    /// Set the first day of the week (Monday by default)
+    pub fn with_week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
     }
 
     /// Group entries into repositories
@@ -27,6 +38,9 @@ impl Grouper {
 
         match self.group_by {
             GroupBy::Repository => self.group_by_repository(entries),
+            GroupBy::Project => self.group_by_project(entries),
+            GroupBy::Tag => self.group_by_tag(entries),
+            GroupBy::Author => self.group_by_author(entries),
             GroupBy::Task => self.group_by_task(entries),
             GroupBy::Date => self.group_by_date(entries),
             GroupBy::Week => self.group_by_week(entries),
@@ -72,34 +86,7 @@ impl Grouper {
 
         let mut repositories: Vec<Repository> = repo_map
             .into_iter()
-            .map(|(name, entries)| {
-                let mut repo = Repository::new(name.clone(), None);
-
-                // Group entries by task within the repository
-                let mut task_map: HashMap<String, Vec<JournalEntry>> = HashMap::new();
-
-                for entry in entries {
-                    let task_name = entry
-                        .task
-                        .clone()
-                        .unwrap_or_else(|| "General".to_string());
-
-                    task_map
-                        .entry(task_name)
-                        .or_insert_with(Vec::new)
-                        .push(entry);
-                }
-
-                for (task_name, task_entries) in task_map {
-                    let mut task = Task::new(task_name);
-                    for entry in task_entries {
-                        task.add_entry(entry);
-                    }
-                    repo.add_task(task);
-                }
-
-                repo
-            })
+            .map(|(name, entries)| Self::build_repository(name, entries))
             .collect();
 
         // Sort repositories by name
@@ -108,6 +95,113 @@ impl Grouper {
         Ok(repositories)
     }
 
+    /// Group entries by project tag, with untagged entries last
+    fn group_by_project(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
+        let mut project_map: HashMap<Option<String>, Vec<JournalEntry>> = HashMap::new();
+
+        for entry in entries {
+            project_map.entry(entry.project.clone()).or_default().push(entry);
+        }
+
+        let mut projects: Vec<(Option<String>, Vec<JournalEntry>)> = project_map.into_iter().collect();
+        // None sorts first, so flip it to the end
+        projects.sort_by(|a, b| (a.0.is_none(), &a.0).cmp(&(b.0.is_none(), &b.0)));
+
+        Ok(projects
+            .into_iter()
+            .map(|(project, entries)| {
+                let name = project.unwrap_or_else(|| "Untagged".to_string());
+                Self::build_repository(name, entries)
+            })
+            .collect())
+    }
+
+    /// Group entries by tag, with untagged entries last
+    ///
+    /// An entry is listed under each of its tags and its project.
+    fn group_by_tag(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
+        let mut tag_map: BTreeMap<String, Vec<JournalEntry>> = BTreeMap::new();
+        let mut untagged = Vec::new();
+
+        for entry in entries {
+            let tags = entry_tags(&entry);
+            if tags.is_empty() {
+                untagged.push(entry);
+                continue;
+            }
+            for tag in tags {
+                tag_map.entry(tag.to_string()).or_default().push(entry.clone());
+            }
+        }
+
+        let mut repositories: Vec<Repository> = tag_map
+            .into_iter()
+            .map(|(tag, entries)| Self::build_repository(tag, entries))
+            .collect();
+        if !untagged.is_empty() {
+            repositories.push(Self::build_repository("Untagged".to_string(), untagged));
+        }
+
+        Ok(repositories)
+    }
+
+    /// Group entries by author, with unattributed entries last
+    fn group_by_author(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
+        let mut author_map: HashMap<Option<String>, Vec<JournalEntry>> = HashMap::new();
+
+        for entry in entries {
+            author_map.entry(entry.author.clone()).or_default().push(entry);
+        }
+
+        let mut authors: Vec<(Option<String>, Vec<JournalEntry>)> = author_map.into_iter().collect();
+        authors.sort_by(|a, b| (a.0.is_none(), &a.0).cmp(&(b.0.is_none(), &b.0)));
+
+        Ok(authors
+            .into_iter()
+            .map(|(author, entries)| {
+                let name = author.unwrap_or_else(|| UNATTRIBUTED.to_string());
+                Self::build_repository(name, entries)
+            })
+            .collect())
+    }
+
+    /// Build a repository with entries grouped by task
+    ///
+    /// The repository takes the entries' project tag when they all share one.
+    fn build_repository(name: String, entries: Vec<JournalEntry>) -> Repository {
+        let mut repo = Repository::new(name, None);
+
+        let project = entries.first().and_then(|e| e.project.clone());
+        if entries.iter().all(|e| e.project == project) {
+            repo.project = project;
+        }
+
+        // Group entries by task within the repository
+        let mut task_map: HashMap<String, Vec<JournalEntry>> = HashMap::new();
+
+        for entry in entries {
+            let task_name = entry
+                .task
+                .clone()
+                .unwrap_or_else(|| "General".to_string());
+
+            task_map
+                .entry(task_name)
+                .or_insert_with(Vec::new)
+                .push(entry);
+        }
+
+        for (task_name, task_entries) in task_map {
+            let mut task = Task::new(task_name);
+            for entry in task_entries {
+                task.add_entry(entry);
+            }
+            repo.add_task(task);
+        }
+
+        repo
+    }
+
     /// Group entries by task
     fn group_by_task(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
         // When grouping by task, we create a single "Tasks" repository
@@ -158,19 +252,19 @@ impl Grouper {
         Ok(vec![repo])
     }
 
-    /// Group entries by week
+    /// Group entries by week, oldest first
     fn group_by_week(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
-        let mut week_map: HashMap<String, Vec<JournalEntry>> = HashMap::new();
+        let mut week_map: BTreeMap<NaiveDate, Vec<JournalEntry>> = BTreeMap::new();
 
         for entry in entries {
-            let week_str = entry.date.format("Week %W, %Y").to_string();
-            week_map.entry(week_str).or_insert_with(Vec::new).push(entry);
+            let week = week_of(entry.date, self.week_start);
+            week_map.entry(week).or_default().push(entry);
         }
 
         let mut repo = Repository::new("By Week".to_string(), None);
 
-        for (week_str, week_entries) in week_map {
-            let mut task = Task::new(week_str);
+        for (week, week_entries) in week_map {
+            let mut task = Task::new(week_label(week));
             for entry in week_entries {
                 task.add_entry(entry);
             }
@@ -180,22 +274,21 @@ impl Grouper {
         Ok(vec![repo])
     }
 
-    /// Group entries by month
+    /// Group entries by month, oldest first
     fn group_by_month(&self, entries: Vec<JournalEntry>) -> Result<Vec<Repository>> {
-        let mut month_map: HashMap<String, Vec<JournalEntry>> = HashMap::new();
+        let mut month_map: BTreeMap<NaiveDate, Vec<JournalEntry>> = BTreeMap::new();
 
         for entry in entries {
-            let month_str = entry.date.format("%B %Y").to_string();
             month_map
-                .entry(month_str)
-                .or_insert_with(Vec::new)
+                .entry(month_of(entry.date))
+                .or_default()
                 .push(entry);
         }
 
         let mut repo = Repository::new("By Month".to_string(), None);
 
-        for (month_str, month_entries) in month_map {
-            let mut task = Task::new(month_str);
+        for (month, month_entries) in month_map {
+            let mut task = Task::new(month_label(month));
             for entry in month_entries {
                 task.add_entry(entry);
             }
@@ -206,6 +299,35 @@ impl Grouper {
     }
 }
 
+/// First day of the week holding `date`
+pub fn week_of(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    let days_in = (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    date - Duration::days(days_in as i64)
+}
+
+/// `Week 45, 2025` for the week starting on `start`
+///
+/// Weeks are numbered by their first day, like `%W` numbers Monday weeks,
+/// so a week starting in late December keeps that year's number.
+pub fn week_label(start: NaiveDate) -> String {
+    format!("Week {:02}, {}", start.ordinal0() / 7 + 1, start.year())
+}
+
+/// First day of the month holding `date`
+pub fn month_of(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+/// `November 2025` for the month starting on `start`
+pub fn month_label(start: NaiveDate) -> String {
+    start.format("%B %Y").to_string()
+}
+
+/// An entry's tags and project, each once
+pub fn entry_tags(entry: &JournalEntry) -> BTreeSet<&str> {
+    entry.tags.iter().map(String::as_str).chain(entry.project.as_deref()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +350,68 @@ mod tests {
         assert!(repos.iter().any(|r| r.name == "repo2"));
     }
 
+    #[test]
+    fn test_group_by_project() {
+        let mut entries = vec![
+            create_test_entry("dns", "rotate", "2025-11-13"),
+            create_test_entry("web", "deploy", "2025-11-14"),
+            create_test_entry("scratch", "notes", "2025-11-15"),
+            create_test_entry("terraform", "rotate", "2025-11-16"),
+        ];
+        entries[0].project = Some("infra".to_string());
+        entries[1].project = Some("frontend".to_string());
+        entries[3].project = Some("infra".to_string());
+
+        let grouper = Grouper::new(GroupBy::Project, SortBy::Date);
+        let repos = grouper.group_entries(entries).unwrap();
+
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["frontend", "infra", "Untagged"]);
+        assert_eq!(repos[1].project.as_deref(), Some("infra"));
+        assert_eq!(repos[1].entry_count(), 2);
+        assert_eq!(repos[1].find_task("rotate").unwrap().entry_count(), 2);
+        assert!(repos[2].project.is_none());
+    }
+
+    #[test]
+    fn test_group_by_author() {
+        let mut entries = vec![
+            create_test_entry("dns", "rotate", "2025-11-13"),
+            create_test_entry("web", "deploy", "2025-11-14"),
+            create_test_entry("dns", "rotate", "2025-11-15"),
+        ];
+        entries[0].author = Some("bob".to_string());
+        entries[2].author = Some("alice".to_string());
+
+        let grouper = Grouper::new(GroupBy::Author, SortBy::Date);
+        let repos = grouper.group_entries(entries).unwrap();
+
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "bob", "Unattributed"]);
+        assert_eq!(repos[2].find_task("deploy").unwrap().entry_count(), 1);
+    }
+
+    #[test]
+    fn test_group_by_repository_sets_shared_project() {
+        let mut entries = vec![
+            create_test_entry("dns", "task1", "2025-11-13"),
+            create_test_entry("dns", "task2", "2025-11-14"),
+            create_test_entry("web", "task1", "2025-11-15"),
+            create_test_entry("web", "task2", "2025-11-16"),
+        ];
+        entries[0].project = Some("infra".to_string());
+        entries[1].project = Some("infra".to_string());
+        entries[2].project = Some("frontend".to_string());
+
+        let grouper = Grouper::new(GroupBy::Repository, SortBy::Date);
+        let repos = grouper.group_entries(entries).unwrap();
+
+        assert_eq!(repos[0].name, "dns");
+        assert_eq!(repos[0].project.as_deref(), Some("infra"));
+        // Mixed tags: no single project for the repository
+        assert!(repos[1].project.is_none());
+    }
+
     #[test]
     fn test_group_by_task() {
         let entries = vec![
@@ -635,6 +819,57 @@ mod tests {
         assert_eq!(repos[0].tasks[0].entries.len(), 1);
     }
 
+    #[test]
+    fn test_group_by_tag() {
+        let mut entries = vec![
+            create_test_entry("dns", "rotate", "2025-11-13"),
+            create_test_entry("web", "deploy", "2025-11-14"),
+            create_test_entry("scratch", "notes", "2025-11-15"),
+        ];
+        entries[0].project = Some("infra".to_string());
+        entries[0].tags = vec!["security".to_string()];
+        entries[1].tags = vec!["infra".to_string(), "release".to_string()];
+
+        let grouper = Grouper::new(GroupBy::Tag, SortBy::Date);
+        let repos = grouper.group_entries(entries).unwrap();
+
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["infra", "release", "security", "Untagged"]);
+        assert_eq!(repos[0].entry_count(), 2);
+        assert_eq!(repos[3].find_task("notes").unwrap().entry_count(), 1);
+    }
+
+    #[test]
+    fn test_group_by_week_with_week_start() {
+        let entries = vec![
+            create_test_entry("repo1", "task1", "2025-11-08"), // Saturday
+            create_test_entry("repo1", "task2", "2025-11-09"), // Sunday
+            create_test_entry("repo1", "task3", "2025-11-10"), // Monday
+        ];
+
+        let monday = Grouper::new(GroupBy::Week, SortBy::Date).group_entries(entries.clone()).unwrap();
+        let weeks: Vec<(&str, usize)> = monday[0].tasks.iter().map(|t| (t.name.as_str(), t.entry_count())).collect();
+        assert_eq!(weeks, vec![("Week 44, 2025", 2), ("Week 45, 2025", 1)]);
+
+        let sunday = Grouper::new(GroupBy::Week, SortBy::Date)
+            .with_week_start(Weekday::Sun)
+            .group_entries(entries)
+            .unwrap();
+        let weeks: Vec<(&str, usize)> = sunday[0].tasks.iter().map(|t| (t.name.as_str(), t.entry_count())).collect();
+        assert_eq!(weeks, vec![("Week 44, 2025", 1), ("Week 45, 2025", 2)]);
+    }
+
+    #[test]
+    fn test_week_of() {
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(week_of(date("2025-11-13"), Weekday::Mon), date("2025-11-10"));
+        assert_eq!(week_of(date("2025-11-10"), Weekday::Mon), date("2025-11-10"));
+        assert_eq!(week_of(date("2025-11-13"), Weekday::Sun), date("2025-11-09"));
+        assert_eq!(week_of(date("2025-11-13"), Weekday::Fri), date("2025-11-07"));
+        assert_eq!(week_label(date("2024-12-30")), "Week 53, 2024");
+        assert_eq!(month_of(date("2025-11-13")), date("2025-11-01"));
+    }
+
     fn create_test_entry(repo: &str, task: &str, date_str: &str) -> JournalEntry {
         let date_parts: Vec<&str> = date_str.split('-').collect();
         let date = NaiveDate::from_ymd_opt(
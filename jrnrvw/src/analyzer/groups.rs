@@ -0,0 +1,250 @@
+//! Entries written and tasks opened and finished per `--group-by` group
+//!
+//! Every entry counts towards its group; grouped by tag, it counts once
+//! towards each of its tags and its project, as when grouping. A task is
+//! opened in the group of its first entry and completed in the group of
+//! the entry that finished it (see [`Task::completed_on`]). Weeks, months
+//! and days without activity inside the report's range are listed with
+//! zero counts, so charts drawn from them have no gaps.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Months, NaiveDate, Weekday};
+
+use crate::models::{DateRange, GroupBy, GroupSummary, JournalEntry, Task};
+use super::authors::UNATTRIBUTED;
+use super::grouper::{entry_tags, month_label, month_of, week_label, week_of};
+
+/// What an entry is counted under
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    /// A day, week or month, by its first day
+    Period(NaiveDate),
+
+    /// A named group; fallbacks such as `Untagged` sort after every name
+    Name { fallback: bool, name: String },
+}
+
+impl Key {
+    fn name(name: &str) -> Self {
+        Key::Name { fallback: false, name: name.to_string() }
+    }
+
+    fn fallback(name: &str) -> Self {
+        Key::Name { fallback: true, name: name.to_string() }
+    }
+}
+
+/// Activity per group of `entries`, in the order the groups are listed
+///
+/// When grouping by day, week or month, every period overlapping `range`
+/// is listed, with or without entries.
+pub fn group_summaries(
+    entries: &[JournalEntry],
+    group_by: GroupBy,
+    week_start: Weekday,
+    range: Option<&DateRange>,
+) -> Vec<GroupSummary> {
+    let keys = |entry: &JournalEntry| entry_keys(entry, group_by, week_start);
+    let mut rows: BTreeMap<Key, GroupSummary> = BTreeMap::new();
+
+    for entry in entries {
+        for key in keys(entry) {
+            summary_row(&mut rows, group_by, key).entries += 1;
+        }
+    }
+
+    for task in tasks(entries) {
+        if let Some(first) = task.entries.iter().min_by_key(|e| e.date) {
+            for key in keys(first) {
+                summary_row(&mut rows, group_by, key).tasks_opened += 1;
+            }
+        }
+        let finished = task.completed_on().and_then(|date| task.entries.iter().find(|e| e.date == date));
+        if let Some(finished) = finished {
+            for key in keys(finished) {
+                summary_row(&mut rows, group_by, key).tasks_completed += 1;
+            }
+        }
+    }
+
+    if let Some(range) = range {
+        let mut start = period_of(group_by, week_start, range.from);
+        let last = period_of(group_by, week_start, range.to);
+        while let (Some(first), Some(last)) = (start, last) {
+            if first > last {
+                break;
+            }
+            summary_row(&mut rows, group_by, Key::Period(first));
+            start = next_period(group_by, first);
+        }
+    }
+
+    rows.into_values().collect()
+}
+
+fn summary_row(rows: &mut BTreeMap<Key, GroupSummary>, group_by: GroupBy, key: Key) -> &mut GroupSummary {
+    rows.entry(key.clone()).or_insert_with(|| match key {
+        Key::Period(start) => period_group(group_by, start),
+        Key::Name { name, .. } => GroupSummary::empty(name, None),
+    })
+}
+
+/// Groups `entry` counts towards
+fn entry_keys(entry: &JournalEntry, group_by: GroupBy, week_start: Weekday) -> Vec<Key> {
+    match group_by {
+        GroupBy::Repository => vec![Key::name(entry.repository.as_deref().unwrap_or("Unknown"))],
+        GroupBy::Project => vec![entry.project.as_deref().map_or_else(|| Key::fallback("Untagged"), Key::name)],
+        GroupBy::Tag => {
+            let tags = entry_tags(entry);
+            if tags.is_empty() {
+                vec![Key::fallback("Untagged")]
+            } else {
+                tags.into_iter().map(Key::name).collect()
+            }
+        }
+        GroupBy::Author => vec![entry.author.as_deref().map_or_else(|| Key::fallback(UNATTRIBUTED), Key::name)],
+        GroupBy::Task => vec![Key::name(entry.task.as_deref().unwrap_or("Untitled"))],
+        GroupBy::Date | GroupBy::Week | GroupBy::Month => {
+            period_of(group_by, week_start, entry.date).map(Key::Period).into_iter().collect()
+        }
+    }
+}
+
+/// First day of the period holding `date`, `None` unless grouping by period
+fn period_of(group_by: GroupBy, week_start: Weekday, date: NaiveDate) -> Option<NaiveDate> {
+    match group_by {
+        GroupBy::Date => Some(date),
+        GroupBy::Week => Some(week_of(date, week_start)),
+        GroupBy::Month => Some(month_of(date)),
+        _ => None,
+    }
+}
+
+/// First day of the period after the one starting on `start`
+fn next_period(group_by: GroupBy, start: NaiveDate) -> Option<NaiveDate> {
+    match group_by {
+        GroupBy::Date => start.checked_add_signed(Duration::days(1)),
+        GroupBy::Week => start.checked_add_signed(Duration::days(7)),
+        GroupBy::Month => start.checked_add_months(Months::new(1)),
+        _ => None,
+    }
+}
+
+/// An empty group for the period starting on `start`
+fn period_group(group_by: GroupBy, start: NaiveDate) -> GroupSummary {
+    let (name, last) = match group_by {
+        GroupBy::Week => (week_label(start), start + Duration::days(6)),
+        GroupBy::Month => (month_label(start), start + Months::new(1) - Duration::days(1)),
+        _ => (start.to_string(), start),
+    };
+    GroupSummary::empty(name, Some(DateRange::new(start, last)))
+}
+
+/// Named tasks of `entries`, per repository
+fn tasks(entries: &[JournalEntry]) -> Vec<Task> {
+    let mut tasks: BTreeMap<(&str, &str), Task> = BTreeMap::new();
+    for entry in entries {
+        let Some(name) = entry.task.as_deref() else { continue };
+        let repository = entry.repository.as_deref().unwrap_or("Unknown");
+        tasks
+            .entry((repository, name))
+            .or_insert_with(|| Task::new(name.to_string()))
+            .add_entry(entry.clone());
+    }
+    tasks.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(day: &str, repository: &str, task: &str, status: Option<&str>) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", day)), date(day));
+        entry.repository = Some(repository.to_string());
+        entry.task = Some(task.to_string());
+        entry.status = status.map(str::to_string);
+        entry
+    }
+
+    fn counts(groups: &[GroupSummary]) -> Vec<(&str, usize, usize, usize)> {
+        groups
+            .iter()
+            .map(|g| (g.name.as_str(), g.entries, g.tasks_opened, g.tasks_completed))
+            .collect()
+    }
+
+    fn entries() -> Vec<JournalEntry> {
+        vec![
+            entry("2025-11-03", "api", "auth", None),
+            entry("2025-11-05", "api", "auth", None),
+            entry("2025-11-12", "web", "deploy", None),
+            entry("2025-11-19", "api", "auth", Some("done")),
+        ]
+    }
+
+    #[test]
+    fn test_weeks_count_entries_and_tasks() {
+        let groups = group_summaries(&entries(), GroupBy::Week, Weekday::Mon, None);
+        assert_eq!(
+            counts(&groups),
+            vec![
+                ("Week 44, 2025", 2, 1, 0),
+                ("Week 45, 2025", 1, 1, 0),
+                ("Week 46, 2025", 1, 0, 1),
+            ]
+        );
+        assert_eq!(groups[0].period, Some(DateRange::new(date("2025-11-03"), date("2025-11-09"))));
+    }
+
+    #[test]
+    fn test_empty_periods_fill_the_range() {
+        let range = DateRange::new(date("2025-10-20"), date("2025-11-30"));
+        let groups = group_summaries(&entries()[..2], GroupBy::Week, Weekday::Mon, Some(&range));
+        assert_eq!(
+            counts(&groups),
+            vec![
+                ("Week 42, 2025", 0, 0, 0),
+                ("Week 43, 2025", 0, 0, 0),
+                ("Week 44, 2025", 2, 1, 0),
+                ("Week 45, 2025", 0, 0, 0),
+                ("Week 46, 2025", 0, 0, 0),
+                ("Week 47, 2025", 0, 0, 0),
+            ]
+        );
+
+        let groups = group_summaries(&entries(), GroupBy::Month, Weekday::Mon, Some(&range));
+        assert_eq!(counts(&groups), vec![("October 2025", 0, 0, 0), ("November 2025", 4, 2, 1)]);
+        assert_eq!(groups[1].period, Some(DateRange::new(date("2025-11-01"), date("2025-11-30"))));
+    }
+
+    #[test]
+    fn test_week_start() {
+        // 2025-11-09 is a Sunday
+        let entries = vec![entry("2025-11-08", "api", "auth", None), entry("2025-11-09", "api", "auth", None)];
+        let groups = group_summaries(&entries, GroupBy::Week, Weekday::Sun, None);
+        assert_eq!(counts(&groups), vec![("Week 44, 2025", 1, 1, 0), ("Week 45, 2025", 1, 0, 0)]);
+        assert_eq!(groups[1].period, Some(DateRange::new(date("2025-11-09"), date("2025-11-15"))));
+    }
+
+    #[test]
+    fn test_repositories_and_tags() {
+        let groups = group_summaries(&entries(), GroupBy::Repository, Weekday::Mon, None);
+        assert_eq!(counts(&groups), vec![("api", 3, 1, 1), ("web", 1, 1, 0)]);
+        assert_eq!(groups[0].period, None);
+
+        let mut tagged = entries();
+        tagged[0].tags = vec!["backend".to_string(), "security".to_string()];
+        tagged[3].project = Some("backend".to_string());
+        let groups = group_summaries(&tagged, GroupBy::Tag, Weekday::Mon, None);
+        assert_eq!(
+            counts(&groups),
+            vec![("backend", 2, 1, 1), ("security", 1, 1, 0), ("Untagged", 2, 1, 0)]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Tasks finished per week, their age when finished, and open tasks over
+//! time
+//!
+//! A task opens with its first dated entry and is finished on the day
+//! [`Task::completed_on`] gives; a task that never records one is open to
+//! the end of the series. Undated entries, which only have their file's
+//! modification time to go by, play no part, and repositories with nothing
+//! else are listed as skipped.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, Weekday};
+
+use crate::models::{Metrics, Repository, Task, WeekMetrics};
+use super::grouper::week_of;
+
+/// Throughput of the tasks in `repositories`, by weeks starting on
+/// `week_start`
+pub fn throughput(repositories: &[Repository], week_start: Weekday) -> Metrics {
+    let mut metrics = Metrics::default();
+    // (opened, finished) per task
+    let mut spans: Vec<(NaiveDate, Option<NaiveDate>)> = Vec::new();
+    let mut newest: Option<NaiveDate> = None;
+
+    for repository in repositories {
+        let tasks: Vec<Task> = repository.tasks.iter().filter_map(dated).collect();
+        if tasks.is_empty() {
+            metrics.skipped.push(repository.name.clone());
+            continue;
+        }
+
+        for task in &tasks {
+            let Some((opened, last)) = task.date_range() else { continue };
+            newest = Some(newest.map_or(last, |newest| newest.max(last)));
+            spans.push((opened, task.completed_on()));
+        }
+    }
+
+    let (Some(first), Some(newest)) = (spans.iter().map(|(opened, _)| *opened).min(), newest) else {
+        return metrics;
+    };
+
+    let mut weeks: BTreeMap<NaiveDate, WeekMetrics> = BTreeMap::new();
+    let mut week = week_of(first, week_start);
+    while week <= newest {
+        let end = week + Duration::days(6);
+        let open = spans
+            .iter()
+            .filter(|(opened, finished)| *opened <= end && finished.is_none_or(|finished| finished > end))
+            .count();
+        weeks.insert(week, WeekMetrics { week, completed: 0, open });
+        week = end + Duration::days(1);
+    }
+
+    for finished in spans.iter().filter_map(|(_, finished)| *finished) {
+        if let Some(row) = weeks.get_mut(&week_of(finished, week_start)) {
+            row.completed += 1;
+        }
+    }
+
+    let ages: Vec<i64> = spans
+        .iter()
+        .filter_map(|(opened, finished)| finished.map(|finished| (finished - *opened).num_days()))
+        .collect();
+    if !ages.is_empty() {
+        metrics.average_days_to_complete = Some(ages.iter().sum::<i64>() as f64 / ages.len() as f64);
+    }
+
+    metrics.weeks = weeks.into_values().collect();
+    metrics
+}
+
+/// `task` with only its dated entries, `None` if it has none
+fn dated(task: &Task) -> Option<Task> {
+    let mut dated = task.clone();
+    dated.entries.retain(|entry| !entry.undated);
+    (!dated.entries.is_empty()).then_some(dated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::JournalEntry;
+    use std::path::PathBuf;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(day: &str, status: Option<&str>) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", day)), date(day));
+        entry.status = status.map(str::to_string);
+        entry
+    }
+
+    fn repository(name: &str, tasks: Vec<(&str, Vec<JournalEntry>)>) -> Repository {
+        let mut repository = Repository::new(name.to_string(), None);
+        for (name, entries) in tasks {
+            let mut task = Task::new(name.to_string());
+            for entry in entries {
+                task.add_entry(entry);
+            }
+            repository.add_task(task);
+        }
+        repository
+    }
+
+    #[test]
+    fn test_weekly_series() {
+        // Weeks starting Monday 2025-11-03, 2025-11-10 and 2025-11-17
+        let repositories = vec![repository(
+            "api",
+            vec![
+                ("auth", vec![entry("2025-11-03", None), entry("2025-11-05", Some("done"))]),
+                ("deploy", vec![entry("2025-11-04", None), entry("2025-11-12", Some("open"))]),
+                ("docs", vec![entry("2025-11-11", None), entry("2025-11-19", Some("done"))]),
+                ("notes", vec![entry("2025-11-06", None)]),
+            ],
+        )];
+
+        let metrics = throughput(&repositories, Weekday::Mon);
+        let rows: Vec<(NaiveDate, usize, usize)> = metrics.weeks.iter().map(|w| (w.week, w.completed, w.open)).collect();
+        assert_eq!(
+            rows,
+            vec![(date("2025-11-03"), 1, 2), (date("2025-11-10"), 0, 3), (date("2025-11-17"), 1, 2)],
+            "tasks without a completion stay open"
+        );
+        // auth took 2 days, docs 8
+        assert_eq!(metrics.average_days_to_complete, Some(5.0));
+        assert!(metrics.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_week_start_and_empty_weeks() {
+        let repositories = vec![repository(
+            "api",
+            vec![("auth", vec![entry("2025-11-02", None), entry("2025-11-18", Some("done"))])],
+        )];
+
+        let metrics = throughput(&repositories, Weekday::Sun);
+        assert_eq!(metrics.completed_series(), vec![0, 0, 1]);
+        assert_eq!(metrics.open_series(), vec![1, 1, 0]);
+        assert_eq!(metrics.weeks[0].week, date("2025-11-02"));
+        assert_eq!(metrics.average_days_to_complete, Some(16.0));
+    }
+
+    #[test]
+    fn test_skips_repositories_without_dated_entries() {
+        let mut undated = entry("2025-11-20", Some("done"));
+        undated.undated = true;
+        let repositories = vec![
+            repository("api", vec![("auth", vec![entry("2025-11-03", None), undated.clone()])]),
+            repository("scratch", vec![("ideas", vec![undated])]),
+        ];
+
+        let metrics = throughput(&repositories, Weekday::Mon);
+        assert_eq!(metrics.skipped, vec!["scratch"]);
+        assert_eq!(metrics.open_series(), vec![1], "undated entries neither finish nor extend a task");
+        assert_eq!(metrics.average_days_to_complete, None);
+
+        let metrics = throughput(&repositories[1..], Weekday::Mon);
+        assert!(metrics.weeks.is_empty());
+        assert!(!metrics.is_empty());
+    }
+}
@@ -4,8 +4,40 @@ pub mod filter;
 pub mod grouper;
 pub mod stats;
 pub mod report_builder;
+pub mod signals;
+pub mod references;
+pub mod tagger;
+pub mod pipeline;
+pub mod cache;
+pub mod authors;
+pub mod git;
+pub mod time;
+pub mod task_graph;
+pub mod dedup;
+pub mod check;
+pub mod ci;
+pub mod groups;
+pub mod alerts;
+pub mod metrics;
+pub mod search;
+#[cfg(feature = "explain")]
+pub mod explain;
 
-pub use filter::{TimeRange, EntryFilter};
+pub use filter::{parse_relative_date, TimeRange, EntryFilter};
 pub use grouper::Grouper;
 pub use stats::StatisticsCalculator;
 pub use report_builder::ReportBuilder;
+pub use signals::SignalDetector;
+pub use references::ReferenceDetector;
+pub use tagger::ProjectTagger;
+pub use task_graph::task_graph;
+pub use dedup::dedup_tasks;
+pub use groups::group_summaries;
+pub use alerts::{overdue_tasks, stale_tasks};
+pub use metrics::throughput;
+pub use search::{search, SearchHit, SearchQuery};
+pub use authors::AuthorResolver;
+pub use pipeline::{Analysis, load_entries};
+pub use cache::{CacheSettings, JournalIndex};
+#[cfg(feature = "explain")]
+pub use explain::{annotate_clusters, explain_entries};
@@ -0,0 +1,419 @@
+//! Discovery-to-report pipeline shared by the one-shot CLI and serve mode
+
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::config::settings::{DedupConfig, ReferencesConfig, SignalsConfig};
+use crate::discovery::{discover_reporting, DiscoverySettings};
+use crate::error::{JrnrvwError, Result};
+use crate::models::{Directive, DirectiveWarning, GroupBy, JournalEntry, ParseFailure, Report, SortBy, TimeLogged};
+use crate::parser::org::is_org;
+use crate::parser::{JournalParser, MetadataExtractor, OrgParser};
+use crate::profile::{self, Context};
+use super::alerts::DEFAULT_STALE_AFTER_DAYS;
+use super::cache::{content_hash, CacheSettings, IndexedFile, JournalIndex};
+use super::{git, AuthorResolver, EntryFilter, ProjectTagger, ReportBuilder};
+
+/// Discover, parse, tag, and attribute every journal under `root`
+///
+/// `discovery` picks the files: org-mode journals are included when it
+/// sets `include_org`, and are parsed by [`OrgParser`] into the same entries
+/// Markdown gives, and its exclude patterns and `.jrnrvwignore` files prune
+/// the walk unless it sets `no_ignore`. With `follow_symlinks` it follows
+/// links, once per file, and broken links are warnings. With
+/// `git_metadata`, each entry records the last commit touching its file
+/// before authors are attributed, so the commit author can stand in for one.
+///
+/// Files that cannot be read or parsed are kept with only the metadata taken
+/// from their filename. Files are parsed on one worker thread per core.
+/// Parse errors and directive warnings are printed to stderr, in discovery
+/// order, when `print_warnings` is set. Journals whose front matter sets
+/// `jrnrvw.skip` are dropped once the index is written, so the cache still
+/// knows them.
+///
+/// With `cache`, unchanged files are taken from the root's index instead of
+/// being parsed, and the index is rewritten afterwards. Failing to write it
+/// only costs the next run its head start, so that is a warning.
+pub fn load_entries(
+    root: &Path,
+    tagger: &ProjectTagger,
+    authors: &AuthorResolver,
+    print_warnings: bool,
+    cache: Option<&CacheSettings>,
+    discovery: &DiscoverySettings,
+    git_metadata: bool,
+) -> Result<Vec<JournalEntry>> {
+    let mut entries = {
+        let _span = profile::span("discovery");
+        let mut warnings = Vec::new();
+        let entries = discover_reporting(&discovery.scanner(root), &mut warnings)?;
+        if print_warnings {
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+        }
+        entries
+    };
+
+    let index = match cache {
+        Some(settings) if settings.reuse => JournalIndex::load(settings, root),
+        _ => JournalIndex::default(),
+    };
+    let files = {
+        let _span = profile::span("parsing");
+        load_in_parallel(&mut entries, &index)
+    };
+
+    if print_warnings {
+        for entry in &entries {
+            if let Some(ref error) = entry.parse_error {
+                eprintln!(
+                    "Warning: Failed to parse journal file {}:{}: {}",
+                    entry.filepath.display(),
+                    error.line,
+                    error.reason
+                );
+            }
+        }
+    }
+
+    if let Some(settings) = cache {
+        let _span = profile::span("index");
+        let paths = entries.iter().map(|entry| entry.filepath.clone());
+        let index = JournalIndex::from_files(
+            paths.zip(files).filter_map(|(path, file)| Some((path, file?))),
+        );
+        if let Err(e) = index.save(settings, root) {
+            if print_warnings {
+                eprintln!("Warning: could not update the index cache: {}", e);
+            }
+        }
+    }
+
+    if print_warnings {
+        for entry in &entries {
+            for warning in &entry.directive_warnings {
+                eprintln!(
+                    "Warning: {}:{}: {}",
+                    entry.filepath.display(),
+                    warning.line,
+                    warning.message
+                );
+            }
+        }
+    }
+
+    entries.retain(|entry| !entry.skipped);
+
+    // Front matter tags are already set; rules fill in the rest
+    {
+        let _span = profile::span("tagging");
+        tagger.tag_entries(&mut entries);
+    }
+
+    if git_metadata {
+        let _span = profile::span("git");
+        git::enrich_entries(&mut entries);
+    }
+
+    // Front matter authors are already set; the path and git fill in the rest
+    let _span = profile::span("authors");
+    authors.attribute_entries(&mut entries);
+
+    Ok(entries)
+}
+
+/// Load every entry, splitting them evenly across the available cores
+///
+/// Returns the indexed state of each entry's file, in entry order; `None`
+/// where the file could not be read.
+fn load_in_parallel(entries: &mut [JournalEntry], index: &JournalIndex) -> Vec<Option<IndexedFile>> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(entries.len());
+    if workers <= 1 {
+        return entries.iter_mut().map(|entry| load_entry(entry, index)).collect();
+    }
+
+    let chunk_size = entries.len().div_ceil(workers);
+    let context = profile::context();
+    thread::scope(|scope| {
+        let workers: Vec<_> = entries
+            .chunks_mut(chunk_size)
+            .map(|chunk| {
+                let context = context.clone();
+                scope.spawn(move || {
+                    let _attached = context.as_ref().map(Context::attach);
+                    chunk
+                        .iter_mut()
+                        .map(|entry| load_entry(entry, index))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("parser thread panicked"))
+            .collect()
+    })
+}
+
+/// Fill in an entry from its file, reusing the indexed parse if the file is
+/// unchanged
+fn load_entry(entry: &mut JournalEntry, index: &JournalIndex) -> Option<IndexedFile> {
+    let metadata = fs::metadata(&entry.filepath).ok()?;
+    let modified = metadata.modified().ok();
+    let size = metadata.len();
+    let indexed = index.get(&entry.filepath);
+
+    let file = match indexed {
+        Some(file) if file.is_fresh(modified, size) => file.clone(),
+        _ => {
+            let content = fs::read_to_string(&entry.filepath).ok()?;
+            let hash = content_hash(content.as_bytes());
+            match indexed {
+                Some(file) if file.hash == hash => IndexedFile {
+                    modified,
+                    size,
+                    ..file.clone()
+                },
+                _ => {
+                    let (parsed, error) = match parse_content(&entry.filepath, &content) {
+                        Ok(parsed) => (Some(parsed), None),
+                        Err(e) => (None, Some(parse_failure(e))),
+                    };
+                    IndexedFile {
+                        modified,
+                        size,
+                        hash,
+                        parsed,
+                        error,
+                        content,
+                    }
+                }
+            }
+        }
+    };
+
+    entry.raw_content = file.content.clone();
+    entry.parse_error = file.error.clone();
+    if let Some(parsed) = &file.parsed {
+        parsed.apply(entry);
+    }
+    Some(file)
+}
+
+/// Where and why parsing stopped
+fn parse_failure(error: JrnrvwError) -> ParseFailure {
+    match error {
+        JrnrvwError::ParseError { line, reason, .. } => ParseFailure { line, reason },
+        other => ParseFailure { line: 1, reason: other.to_string() },
+    }
+}
+
+/// Parse a journal's content
+fn parse_content(path: &Path, content: &str) -> Result<ParsedJournal> {
+    let _span = profile::span_with("parse", || path.display().to_string());
+
+    let parsed = if is_org(path) {
+        OrgParser::new(content.to_string()).with_path(path).parse()?
+    } else {
+        JournalParser::new(content.to_string()).with_path(path).parse()?
+    };
+    let extractor = MetadataExtractor::new(parsed.sections.clone());
+    let overrides = parsed.overrides().clone();
+
+    Ok(ParsedJournal {
+        archived: parsed.is_archived(),
+        title: parsed.title(),
+        date: parsed.date(),
+        tags: parsed.tags(),
+        metadata: parsed.metadata(),
+        project: parsed.project(),
+        author: parsed.author(),
+        status: parsed.status(),
+        priority: parsed.priority(),
+        task_id: parsed.task_id(),
+        due: parsed.due().or_else(|| extractor.extract_due_date()),
+        directives: parsed.directives,
+        directive_warnings: parsed.warnings,
+        task: extractor.extract_task(),
+        activities: extractor.extract_activities(),
+        notes: extractor.extract_notes(),
+        time_spent: extractor.extract_time_spent(),
+        time_logged: extractor.extract_time_logged(),
+        depends_on: extractor.extract_dependencies(),
+        repository: overrides.repository.or_else(|| extractor.extract_repository()),
+        skip: overrides.skip,
+    })
+}
+
+/// What parsing a journal's content yields, as kept in the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ParsedJournal {
+    pub archived: bool,
+    pub title: Option<String>,
+
+    /// Front matter date, overriding the filename's
+    pub date: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    pub metadata: HashMap<String, String>,
+    pub project: Option<String>,
+    pub author: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+
+    /// Explicit task id from the front matter
+    pub task_id: Option<String>,
+    pub directives: Vec<Directive>,
+    pub directive_warnings: Vec<DirectiveWarning>,
+    pub task: Option<String>,
+    pub activities: Vec<String>,
+    pub notes: Option<String>,
+    pub time_spent: Option<String>,
+    pub time_logged: Option<TimeLogged>,
+    pub depends_on: Vec<String>,
+
+    /// Due date from the front matter, else the first written in the body
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+
+    /// Repository named in the journal, overriding the detected one
+    pub repository: Option<String>,
+
+    /// Front matter asked for the journal to be left out
+    pub skip: bool,
+}
+
+impl ParsedJournal {
+    fn apply(&self, entry: &mut JournalEntry) {
+        entry.archived = self.archived;
+        entry.skipped = self.skip;
+        entry.title = self.title.clone();
+        entry.tags = self.tags.clone();
+        entry.metadata = self.metadata.clone();
+        entry.project = self.project.clone();
+        entry.author = self.author.clone();
+        entry.status = self.status.clone();
+        entry.priority = self.priority.clone();
+        entry.task_id = self.task_id.clone();
+        entry.directives = self.directives.clone();
+        entry.directive_warnings = self.directive_warnings.clone();
+        entry.task = self.task.clone();
+        entry.activities = self.activities.clone();
+        entry.notes = self.notes.clone();
+        entry.time_spent = self.time_spent.clone();
+        entry.time_logged = self.time_logged;
+        entry.depends_on = self.depends_on.clone();
+        entry.due = self.due;
+
+        if let Some(repo) = &self.repository {
+            entry.repository = Some(repo.clone());
+        }
+        if let Some(date) = self.date {
+            entry.date = date;
+            entry.undated = false;
+        }
+    }
+}
+
+/// Everything needed to turn a journal tree into a report, so the same
+/// analysis can be re-run on demand
+#[derive(Debug)]
+pub struct Analysis {
+    /// Root directory to search
+    pub root: PathBuf,
+
+    /// Project tagging rules
+    pub tagger: ProjectTagger,
+
+    /// Author attribution and LLM exclusions
+    pub authors: AuthorResolver,
+
+    /// Trend and anomaly thresholds
+    pub signals: SignalsConfig,
+
+    /// Cross-repository reference detection
+    pub references: ReferencesConfig,
+
+    /// Folding duplicate tasks together
+    pub dedup: DedupConfig,
+
+    /// Entry filter
+    pub filter: EntryFilter,
+
+    /// Grouping strategy
+    pub group_by: GroupBy,
+
+    /// Sorting strategy
+    pub sort_by: SortBy,
+
+    /// First day of the week when grouping by week
+    pub week_start: Weekday,
+
+    /// Days without an entry after which an unfinished task is stale
+    pub stale_after_days: u32,
+
+    /// Print directive warnings to stderr while parsing
+    pub print_warnings: bool,
+
+    /// Index cache to reuse and update, if any
+    pub cache: Option<CacheSettings>,
+
+    /// Which files under the root are journals
+    pub discovery: DiscoverySettings,
+
+    /// Record the last commit touching each journal
+    pub git_metadata: bool,
+}
+
+impl Analysis {
+    /// Analysis of `root` with default settings
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            tagger: ProjectTagger::default(),
+            authors: AuthorResolver::default(),
+            signals: SignalsConfig::default(),
+            references: ReferencesConfig::default(),
+            dedup: DedupConfig::default(),
+            filter: EntryFilter::new(),
+            group_by: GroupBy::Repository,
+            sort_by: SortBy::Date,
+            week_start: Weekday::Mon,
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            print_warnings: false,
+            cache: None,
+            discovery: DiscoverySettings::default(),
+            git_metadata: false,
+        }
+    }
+
+    /// Run discovery, parsing, tagging, and report building from scratch
+    pub fn run(&self) -> Result<Report> {
+        let entries = load_entries(
+            &self.root,
+            &self.tagger,
+            &self.authors,
+            self.print_warnings,
+            self.cache.as_ref(),
+            &self.discovery,
+            self.git_metadata,
+        )?;
+
+        ReportBuilder::new(entries)
+            .with_filter(self.filter.clone())
+            .with_grouping(self.group_by, self.sort_by)
+            .with_week_start(self.week_start)
+            .with_stale_after(self.stale_after_days)
+            .with_signals(self.signals.clone())
+            .with_references(self.references.clone())
+            .with_dedup(self.dedup.clone())
+            .build()
+    }
+}
@@ -0,0 +1,395 @@
+//! Cross-repository reference detection
+//!
+//! Activities and notes are scanned line by line for two kinds of reference:
+//! 1. explicit ones matching the configured pattern (`ref:infra#2024-05-02`
+//!    by default), resolved to the named repository's entry on that date
+//! 2. bare mentions of another discovered repository's name, resolved to
+//!    that repository's latest entry on or before the mentioning one
+//!
+//! A reference on a line that reads as waiting ("blocked", "waiting on",
+//! ...) marks the source task as blocked on the other repository.
+
+use chrono::NaiveDate;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::settings::ReferencesConfig;
+use crate::error::{JrnrvwError, Result};
+use crate::models::{Dangling, EntryRef, JournalEntry, Reference, ReferenceGraph, ReferenceKind};
+
+/// Phrases that make a reference a dependency (matched lower-cased)
+const BLOCKING_PHRASES: [&str; 6] = [
+    "blocked",
+    "blocker",
+    "waiting on",
+    "waiting for",
+    "depends on",
+    "dependent on",
+];
+
+/// Shortest repository name detected as a bare mention; shorter names
+/// match too much ordinary prose
+const MIN_MENTION_LEN: usize = 3;
+
+/// Builds the reference graph for a set of entries
+#[derive(Debug)]
+pub struct ReferenceDetector {
+    enabled: bool,
+    pattern: Regex,
+    detect_mentions: bool,
+}
+
+impl Default for ReferenceDetector {
+    fn default() -> Self {
+        Self::new(&ReferencesConfig::default()).expect("default reference pattern is valid")
+    }
+}
+
+impl ReferenceDetector {
+    /// Compile the configured reference pattern
+    pub fn new(config: &ReferencesConfig) -> Result<Self> {
+        config.validate()?;
+        let pattern = Regex::new(&config.pattern)
+            .map_err(|e| JrnrvwError::ConfigError(format!("references: invalid pattern: {}", e)))?;
+
+        Ok(Self {
+            enabled: config.enabled,
+            pattern,
+            detect_mentions: config.detect_mentions,
+        })
+    }
+
+    /// Find references written in `sources`, resolving them against `known`
+    ///
+    /// `known` is usually every loaded entry, so a reference from this week
+    /// to an older journal still resolves when the report is filtered.
+    /// Entries without a repository are skipped as sources.
+    pub fn detect(&self, sources: &[JournalEntry], known: &[JournalEntry]) -> ReferenceGraph {
+        if !self.enabled {
+            return ReferenceGraph::default();
+        }
+
+        let index = RepositoryIndex::new(known);
+        let mentions = if self.detect_mentions { index.mention_patterns() } else { Vec::new() };
+
+        let mut edges = Vec::new();
+        for entry in sources {
+            let Some(repository) = entry.repository.as_deref() else {
+                continue;
+            };
+            let source = entry_ref(entry, repository);
+            // Repositories this entry already references, lower-cased
+            let mut referenced = BTreeSet::new();
+
+            for line in entry_lines(entry) {
+                let blocking = is_blocking(line);
+                let mut masked = line.to_string();
+
+                for captures in self.pattern.captures_iter(line) {
+                    let (Some(whole), Some(repo)) = (captures.get(0), captures.name("repo")) else {
+                        continue;
+                    };
+                    masked.replace_range(whole.range(), &" ".repeat(whole.len()));
+                    if repo.as_str().eq_ignore_ascii_case(repository) {
+                        continue;
+                    }
+
+                    let written_date = captures.name("date").map(|d| d.as_str());
+                    let date = written_date.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+                    let (target, dangling) = match (written_date, date) {
+                        (Some(_), None) => (None, Some(Dangling::UnknownDate)),
+                        _ => index.resolve(repo.as_str(), date, entry.date),
+                    };
+
+                    referenced.insert(repo.as_str().to_lowercase());
+                    edges.push(Reference {
+                        source: source.clone(),
+                        repository: repo.as_str().to_string(),
+                        date,
+                        kind: ReferenceKind::Explicit,
+                        blocking,
+                        context: line.to_string(),
+                        target,
+                        dangling,
+                    });
+                }
+
+                for (name, mention) in &mentions {
+                    let key = name.to_lowercase();
+                    if name.eq_ignore_ascii_case(repository)
+                        || referenced.contains(&key)
+                        || !mention.is_match(&masked)
+                    {
+                        continue;
+                    }
+
+                    let (target, dangling) = index.resolve(name, None, entry.date);
+                    referenced.insert(key);
+                    edges.push(Reference {
+                        source: source.clone(),
+                        repository: name.to_string(),
+                        date: None,
+                        kind: ReferenceKind::Mention,
+                        blocking,
+                        context: line.to_string(),
+                        target,
+                        dangling,
+                    });
+                }
+            }
+        }
+
+        ReferenceGraph { edges }
+    }
+}
+
+/// Known entries by lower-cased repository name, oldest first
+struct RepositoryIndex<'a> {
+    repositories: BTreeMap<String, (&'a str, Vec<&'a JournalEntry>)>,
+}
+
+impl<'a> RepositoryIndex<'a> {
+    fn new(entries: &'a [JournalEntry]) -> Self {
+        let mut repositories: BTreeMap<String, (&str, Vec<&JournalEntry>)> = BTreeMap::new();
+        for entry in entries {
+            if let Some(name) = entry.repository.as_deref() {
+                repositories.entry(name.to_lowercase()).or_insert((name, Vec::new())).1.push(entry);
+            }
+        }
+        for (_, entries) in repositories.values_mut() {
+            entries.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.filepath.cmp(&b.filepath)));
+        }
+
+        Self { repositories }
+    }
+
+    /// Whole-word, case-insensitive matchers for each repository name
+    fn mention_patterns(&self) -> Vec<(&'a str, Regex)> {
+        self.repositories
+            .values()
+            .filter(|(name, _)| name.chars().count() >= MIN_MENTION_LEN)
+            .filter_map(|(name, _)| {
+                let pattern = format!(r"(?i)(?:^|[^\w-]){}(?:$|[^\w-])", regex::escape(name));
+                Regex::new(&pattern).ok().map(|regex| (*name, regex))
+            })
+            .collect()
+    }
+
+    /// Entry a reference points at, or why there is none
+    ///
+    /// Without a date the latest entry on or before `before` is used; having
+    /// none is not an error, the reference just has no link.
+    fn resolve(
+        &self,
+        repository: &str,
+        date: Option<NaiveDate>,
+        before: NaiveDate,
+    ) -> (Option<EntryRef>, Option<Dangling>) {
+        let Some((name, entries)) = self.repositories.get(&repository.to_lowercase()) else {
+            return (None, Some(Dangling::UnknownRepository));
+        };
+
+        match date {
+            Some(date) => match entries.iter().find(|e| e.date == date) {
+                Some(entry) => (Some(entry_ref(entry, name)), None),
+                None => (None, Some(Dangling::UnknownDate)),
+            },
+            None => (
+                entries.iter().rev().find(|e| e.date <= before).map(|e| entry_ref(e, name)),
+                None,
+            ),
+        }
+    }
+}
+
+fn entry_ref(entry: &JournalEntry, repository: &str) -> EntryRef {
+    EntryRef {
+        repository: repository.to_string(),
+        task: entry.task.clone(),
+        date: entry.date,
+        path: entry.filepath.clone(),
+    }
+}
+
+/// Activity and note lines of an entry
+fn entry_lines(entry: &JournalEntry) -> impl Iterator<Item = &str> {
+    entry
+        .activities
+        .iter()
+        .map(String::as_str)
+        .chain(entry.notes.iter().flat_map(|notes| notes.lines()))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+}
+
+fn is_blocking(line: &str) -> bool {
+    let line = line.to_lowercase();
+    BLOCKING_PHRASES.iter().any(|phrase| line.contains(phrase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    fn entry(repo: &str, day: u32, activities: &[&str]) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}/2024-05-{:02}.md", repo, day)), date(day));
+        entry.repository = Some(repo.to_string());
+        entry.task = Some(format!("{} work", repo));
+        entry.activities = activities.iter().map(|a| a.to_string()).collect();
+        entry
+    }
+
+    fn detect(entries: &[JournalEntry]) -> ReferenceGraph {
+        ReferenceDetector::default().detect(entries, entries)
+    }
+
+    #[test]
+    fn test_explicit_reference_resolves() {
+        let entries = vec![
+            entry("infra", 2, &["Provisioned the queue"]),
+            entry("infra", 3, &["Rotated keys"]),
+            entry("web", 6, &["Blocked on infra ticket, see ref:infra#2024-05-02"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 1);
+        let edge = &graph.edges[0];
+        assert_eq!(edge.kind, ReferenceKind::Explicit);
+        assert_eq!(edge.source.repository, "web");
+        assert_eq!(edge.date, Some(date(2)));
+        assert!(edge.blocking);
+        assert_eq!(edge.target.as_ref().unwrap().path, PathBuf::from("infra/2024-05-02.md"));
+        assert_eq!(edge.dangling, None);
+        assert_eq!(graph.blocking().count(), 1);
+    }
+
+    #[test]
+    fn test_dangling_references_are_flagged() {
+        let entries = vec![
+            entry("infra", 2, &[]),
+            entry("web", 6, &["see ref:infra#2024-05-09", "see ref:billing#2024-05-01", "ref:infra#2024-13-40"]),
+        ];
+
+        let graph = detect(&entries);
+        let dangling: Vec<_> = graph.edges.iter().map(|e| e.dangling).collect();
+        assert_eq!(
+            dangling,
+            vec![Some(Dangling::UnknownDate), Some(Dangling::UnknownRepository), Some(Dangling::UnknownDate)]
+        );
+        assert!(graph.edges.iter().all(|e| e.target.is_none()));
+    }
+
+    #[test]
+    fn test_mentions_resolve_to_latest_earlier_entry() {
+        let entries = vec![
+            entry("infra", 2, &[]),
+            entry("infra", 4, &[]),
+            entry("infra", 9, &[]),
+            entry("web", 6, &["Waiting on Infra to open the firewall"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 1);
+        let edge = &graph.edges[0];
+        assert_eq!(edge.kind, ReferenceKind::Mention);
+        assert_eq!(edge.repository, "infra");
+        assert!(edge.blocking);
+        assert_eq!(edge.target.as_ref().unwrap().date, date(4));
+    }
+
+    #[test]
+    fn test_mentions_need_whole_words() {
+        let entries = vec![
+            entry("infra", 2, &["Mentions myself: infra"]),
+            entry("api", 2, &[]),
+            entry("web", 6, &["Reworked the infrastructure docs", "Moved web-api calls", "the api is fine"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].context, "the api is fine");
+        assert!(!graph.edges[0].blocking);
+    }
+
+    #[test]
+    fn test_explicit_reference_is_not_also_a_mention() {
+        let entries = vec![
+            entry("infra", 2, &[]),
+            entry("web", 6, &["ref:infra#2024-05-02"]),
+            entry("web", 7, &["infra again, per ref:infra#2024-05-02"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.iter().all(|e| e.kind == ReferenceKind::Explicit));
+    }
+
+    #[test]
+    fn test_circular_references() {
+        let entries = vec![
+            entry("api", 3, &["Blocked on ref:infra#2024-05-04"]),
+            entry("infra", 4, &["Waiting for the web team, see ref:web#2024-05-05"]),
+            entry("web", 5, &["Depends on the api schema ref:api#2024-05-03"]),
+            entry("docs", 5, &["Described the web flow"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(graph.blocking().count(), 3);
+        assert_eq!(
+            graph.cycles(),
+            vec![vec!["api".to_string(), "infra".to_string(), "web".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_mutual_mentions_form_a_cycle() {
+        let entries = vec![
+            entry("api", 4, &["Asked infra about quotas"]),
+            entry("infra", 4, &["Raised quotas for api"]),
+        ];
+
+        let graph = detect(&entries);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.cycles(), vec![vec!["api".to_string(), "infra".to_string()]]);
+    }
+
+    #[test]
+    fn test_custom_pattern_and_no_mentions() {
+        let config = ReferencesConfig {
+            pattern: r"see (?P<repo>[\w-]+) journal (?P<date>\d{4}-\d{2}-\d{2})".to_string(),
+            detect_mentions: false,
+            ..ReferencesConfig::default()
+        };
+        let entries = vec![
+            entry("infra", 2, &[]),
+            entry("web", 6, &["blocked on infra ticket, see infra repo journal 2024-05-02", "see infra journal 2024-05-02"]),
+        ];
+
+        let graph = ReferenceDetector::new(&config).unwrap().detect(&entries, &entries);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].target.as_ref().unwrap().date, date(2));
+    }
+
+    #[test]
+    fn test_references_to_filtered_out_entries_resolve() {
+        let older = entry("infra", 2, &[]);
+        let newer = entry("web", 20, &["ref:infra#2024-05-02"]);
+        let known = vec![older, newer.clone()];
+
+        let graph = ReferenceDetector::default().detect(&[newer], &known);
+        assert!(graph.edges[0].target.is_some());
+    }
+
+    #[test]
+    fn test_disabled_detector_is_silent() {
+        let config = ReferencesConfig { enabled: false, ..ReferencesConfig::default() };
+        let entries = vec![entry("infra", 2, &[]), entry("web", 6, &["ref:infra#2024-05-02"])];
+        assert!(ReferenceDetector::new(&config).unwrap().detect(&entries, &entries).is_empty());
+    }
+}
@@ -1,8 +1,18 @@
 //! Report builder with filtering and grouping capabilities
 
-use crate::models::{JournalEntry, Report, DateRange, GroupBy, SortBy};
+use chrono::{Local, NaiveDate, Weekday};
+use crate::config::settings::{DedupConfig, ReferencesConfig, SignalsConfig};
+use crate::models::{JournalEntry, Report, ReportWarnings, DateRange, GroupBy, SortBy, TaskGraph};
 use crate::error::{Result, JrnrvwError};
-use super::{EntryFilter, Grouper, StatisticsCalculator};
+use crate::profile;
+use super::alerts::{overdue_tasks, stale_tasks, DEFAULT_STALE_AFTER_DAYS};
+use super::authors::team_summary;
+use super::dedup::dedup_tasks;
+use super::groups::group_summaries;
+use super::metrics::throughput;
+use super::task_graph::task_graph;
+use super::time::time_summary;
+use super::{EntryFilter, Grouper, ReferenceDetector, SignalDetector, StatisticsCalculator};
 
 /// Builder for creating reports from journal entries
 #[derive(Debug)]
@@ -18,6 +28,24 @@ pub struct ReportBuilder {
 
     /// Sorting strategy
     sort_by: SortBy,
+
+    /// First day of the week when grouping by week
+    week_start: Weekday,
+
+    /// Trend and anomaly thresholds
+    signals: SignalsConfig,
+
+    /// Cross-repository reference detection
+    references: ReferencesConfig,
+
+    /// Folding duplicate tasks together
+    dedup: DedupConfig,
+
+    /// Days without an entry after which an unfinished task is stale
+    stale_after_days: u32,
+
+    /// Day overdue tasks are judged against
+    today: NaiveDate,
 }
 
 impl ReportBuilder {
@@ -28,6 +56,12 @@ impl ReportBuilder {
             filter: None,
             group_by: GroupBy::Repository,
             sort_by: SortBy::Date,
+            week_start: Weekday::Mon,
+            signals: SignalsConfig::default(),
+            references: ReferencesConfig::default(),
+            dedup: DedupConfig::default(),
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            today: Local::now().date_naive(),
         }
     }
 
@@ -56,10 +90,62 @@ impl ReportBuilder {
         self
     }
 
+    /// Set the first day of the week (Monday by default)
+    pub fn with_week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+
+    /// Set the trend and anomaly thresholds
+    pub fn with_signals(mut self, signals: SignalsConfig) -> Self {
+        self.signals = signals;
+        self
+    }
+
+    /// Set how cross-repository references are detected
+    pub fn with_references(mut self, references: ReferencesConfig) -> Self {
+        self.references = references;
+        self
+    }
+
+    /// Set how duplicate tasks are folded together
+    pub fn with_dedup(mut self, dedup: DedupConfig) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set the days without an entry after which an unfinished task is
+    /// stale
+    pub fn with_stale_after(mut self, days: u32) -> Self {
+        self.stale_after_days = days;
+        self
+    }
+
+    /// Set the day overdue tasks are judged against (today by default)
+    pub fn with_today(mut self, today: NaiveDate) -> Self {
+        self.today = today;
+        self
+    }
+
     /// Build the report
     pub fn build(self) -> Result<Report> {
+        let _span = profile::span("analyze");
+
+        let detector = ReferenceDetector::new(&self.references)?;
+
+        // References may point at entries the filter drops, so keep them all
+        let known_entries = self.entries.clone();
+
+        // Days the filter asked for, which may reach past the entries
+        let requested = self
+            .filter
+            .as_ref()
+            .and_then(|filter| filter.time_range())
+            .map_or((None, None), |range| range.bounds(self.today));
+
         // Apply filter if present
-        let filtered_entries = if let Some(filter) = self.filter {
+        let mut filtered_entries = if let Some(filter) = self.filter {
+            let _span = profile::span("filter");
             filter.apply(self.entries)?
         } else {
             self.entries
@@ -71,23 +157,95 @@ impl ReportBuilder {
             ));
         }
 
+        // Fold copied-forward and reworded tasks into one before grouping
+        let duplicates = {
+            let _span = profile::span("dedup");
+            dedup_tasks(&mut filtered_entries, &self.dedup)
+        };
+
         // Calculate date range from filtered entries
         let date_range = calculate_date_range(&filtered_entries);
 
         // Group entries into repositories
-        let grouper = Grouper::new(self.group_by, self.sort_by);
-        let repositories = grouper.group_entries(filtered_entries.clone())?;
+        let repositories = {
+            let _span = profile::span("group");
+            let grouper = Grouper::new(self.group_by, self.sort_by).with_week_start(self.week_start);
+            grouper.group_entries(filtered_entries.clone())?
+        };
 
         // Calculate statistics
-        let stats_calculator = StatisticsCalculator::new(
-            filtered_entries.clone(),
-            repositories.clone(),
-        );
-        let statistics = stats_calculator.calculate()?;
+        let statistics = {
+            let _span = profile::span("statistics");
+            let stats_calculator = StatisticsCalculator::new(
+                filtered_entries.clone(),
+                repositories.clone(),
+            );
+            let mut statistics = stats_calculator.calculate()?;
+            if self.dedup.enabled {
+                statistics.task_mentions = Some(filtered_entries.iter().filter(|e| e.task.is_some()).count());
+            }
+            statistics
+        };
+
+        // Look for trends and anomalies
+        let signals = {
+            let _span = profile::span("signals");
+            SignalDetector::new(self.signals).detect(&filtered_entries)
+        };
+
+        // Link entries that refer to other repositories
+        let references = {
+            let _span = profile::span("references");
+            detector.detect(&filtered_entries, &known_entries)
+        };
+
+        // Tasks per repository, whatever the grouping
+        let by_repository = Grouper::new(GroupBy::Repository, SortBy::Date);
+        let tasks = if self.group_by == GroupBy::Repository {
+            repositories.clone()
+        } else {
+            by_repository.group_entries(filtered_entries.clone())?
+        };
+
+        // Order tasks by what they depend on
+        let task_graph = if filtered_entries.iter().any(|e| !e.depends_on.is_empty()) {
+            let _span = profile::span("tasks");
+            let known = by_repository.group_entries(known_entries)?;
+            task_graph(&tasks, &known)
+        } else {
+            TaskGraph::default()
+        };
+
+        // Tasks past their due date, or without a recent entry
+        let overdue = overdue_tasks(&tasks, self.today);
+        let stale = date_range
+            .as_ref()
+            .map(|period| stale_tasks(&tasks, period.to, self.stale_after_days))
+            .unwrap_or_default();
+        let metrics = throughput(&tasks, self.week_start);
+
+        let team = team_summary(&filtered_entries, &repositories);
+        let time = time_summary(&filtered_entries);
+
+        // Every period of the requested range, active or not
+        let group_range = date_range
+            .as_ref()
+            .map(|period| DateRange::new(requested.0.unwrap_or(period.from), requested.1.unwrap_or(period.to)));
+        let groups = group_summaries(&filtered_entries, self.group_by, self.week_start, group_range.as_ref());
 
         // Create the report
         let report = Report::new(repositories, date_range)
-            .with_statistics(statistics);
+            .with_statistics(statistics)
+            .with_warnings(ReportWarnings::from_entries(&filtered_entries))
+            .with_signals(signals)
+            .with_references(references)
+            .with_task_graph(task_graph)
+            .with_duplicates(duplicates)
+            .with_team(team)
+            .with_time(time)
+            .with_groups(groups)
+            .with_alerts(overdue, stale)
+            .with_metrics(metrics);
 
         Ok(report)
     }
@@ -190,6 +348,80 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_build_collects_directive_warnings() {
+        use crate::models::{Directive, DirectiveKind};
+
+        let mut archived = create_test_entry("2025-11-13", "repo1", Some("task1"));
+        archived.archived = true;
+        archived.directives = vec![Directive { kind: DirectiveKind::Archive, line: 1 }];
+
+        let mut filtered_out = create_test_entry("2025-11-14", "repo2", Some("task2"));
+        filtered_out.directives = vec![Directive { kind: DirectiveKind::Ignore, line: 5 }];
+
+        let filter = EntryFilter::new().with_repository("repo1");
+
+        let report = ReportBuilder::new(vec![archived, filtered_out])
+            .with_filter(filter)
+            .build()
+            .unwrap();
+
+        // Archived entries still count; warnings only cover entries in the report
+        assert_eq!(report.statistics.total_entries, 1);
+        assert_eq!(report.warnings.archive, 1);
+        assert_eq!(report.warnings.ignore, 0);
+    }
+
+    #[test]
+    fn test_build_includes_signals() {
+        let mut entries: Vec<_> = (13..=22)
+            .map(|day| create_test_entry(&format!("2025-11-{}", day), "repo1", None))
+            .collect();
+        for entry in &mut entries {
+            entry.raw_content = "word ".repeat(50);
+        }
+        entries[4].raw_content = "word ".repeat(2000);
+
+        let report = ReportBuilder::new(entries.clone()).build().unwrap();
+        assert_eq!(report.signals.len(), 1);
+        assert_eq!(report.signals[0].sources[0].date, entries[4].date);
+
+        let disabled = SignalsConfig { enabled: false, ..SignalsConfig::default() };
+        let report = ReportBuilder::new(entries).with_signals(disabled).build().unwrap();
+        assert!(report.signals.is_empty());
+    }
+
+    #[test]
+    fn test_build_fills_empty_weeks_of_the_filter_range() {
+        use crate::analyzer::TimeRange;
+
+        let entries = vec![
+            create_test_entry("2025-11-04", "repo1", Some("task1")),
+            create_test_entry("2025-11-19", "repo1", Some("task1")),
+        ];
+        let from = NaiveDate::from_ymd_opt(2025, 10, 27).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 30).unwrap();
+
+        let report = ReportBuilder::new(entries)
+            .with_filter(EntryFilter::new().with_time_range(TimeRange::Custom(from, to)))
+            .group_by(GroupBy::Week)
+            .build()
+            .unwrap();
+
+        let weeks: Vec<(&str, usize)> = report.groups.iter().map(|g| (g.name.as_str(), g.entries)).collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("Week 43, 2025", 0),
+                ("Week 44, 2025", 1),
+                ("Week 45, 2025", 0),
+                ("Week 46, 2025", 1),
+                ("Week 47, 2025", 0),
+            ]
+        );
+        assert_eq!(report.repositories[0].tasks.len(), 2, "repositories only list active weeks");
+    }
+
     fn create_test_entry(
         date_str: &str,
         repo: &str,
@@ -0,0 +1,425 @@
+//! Full-text search over parsed journals
+//!
+//! Each entry's content is split into lower-cased words and matched against
+//! the query in tiers: the query's words in order, as a phrase; then every
+//! word of the query anywhere in the entry; then, with fuzzy matching, every
+//! word within a typo or two. Within a tier, entries whose matched words sit
+//! closest together come first, then those with the most matches, then the
+//! newest.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use super::dedup::levenshtein;
+use crate::error::{JrnrvwError, Result};
+use crate::models::JournalEntry;
+
+/// Lines a match may cover and still be shown whole; a match spread wider
+/// is shown from its first word
+const MAX_SNIPPET_SPREAD: usize = 3;
+
+/// What to search for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Lower-cased words of the query, in order
+    terms: Vec<String>,
+
+    /// Let words match with typos
+    fuzzy: bool,
+}
+
+/// How an entry matched, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchKind {
+    /// The query's words, next to each other and in order
+    Phrase,
+    /// Every word of the query, anywhere in the entry
+    Words,
+    /// Every word of the query, allowing for typos
+    Fuzzy,
+}
+
+/// One entry matching a query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    /// Journal file
+    pub path: PathBuf,
+
+    /// Entry date
+    pub date: NaiveDate,
+
+    /// Repository of the entry, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Entry title, else task
+    pub title: String,
+
+    /// Tier the entry matched in
+    pub kind: MatchKind,
+
+    /// Words from the first matched word to the last in the closest match
+    pub span: usize,
+
+    /// Times the phrase occurs, else words matching a query word
+    pub occurrences: usize,
+
+    /// Line (from 1) where the closest match starts
+    pub line: usize,
+
+    /// The closest match with the lines around it
+    pub snippet: Vec<SnippetLine>,
+}
+
+/// A line of a search result's snippet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnippetLine {
+    /// Line number, from 1
+    pub number: usize,
+
+    /// The line as written
+    pub text: String,
+
+    /// Byte ranges of the matched words in `text`
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// A word of an entry's content
+#[derive(Debug)]
+struct Word {
+    /// Lower-cased
+    text: String,
+    /// Line, from 0
+    line: usize,
+    /// Byte range in the line
+    start: usize,
+    end: usize,
+}
+
+/// Split `content` into words: runs of letters and digits
+fn words(content: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    for (line, text) in content.lines().enumerate() {
+        let mut start = None;
+        for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            match (c.is_alphanumeric(), start) {
+                (true, None) => start = Some(i),
+                (false, Some(from)) => {
+                    words.push(Word {
+                        text: text[from..i].to_lowercase(),
+                        line,
+                        start: from,
+                        end: i,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    words
+}
+
+/// Typos a query word may have and still match: none for short words, one
+/// from four letters, two from eight
+fn typos_allowed(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+impl SearchQuery {
+    /// Query for the words of `query`; fails if it has none
+    pub fn new(query: &str, fuzzy: bool) -> Result<Self> {
+        let terms: Vec<String> = words(query).into_iter().map(|word| word.text).collect();
+        if terms.is_empty() {
+            return Err(JrnrvwError::InvalidArgument(format!(
+                "search query '{}' has no words to look for",
+                query
+            )));
+        }
+        Ok(Self { terms, fuzzy })
+    }
+
+    /// Lower-cased words of the query, in order
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Whether `word` counts as `term`: the same, or with `fuzzy` within
+    /// the typos the term allows
+    fn matches(term: &str, word: &str, fuzzy: bool) -> bool {
+        if term == word {
+            return true;
+        }
+        let allowed = typos_allowed(term);
+        fuzzy
+            && allowed > 0
+            && term.chars().count().abs_diff(word.chars().count()) <= allowed
+            && levenshtein(term, word) <= allowed
+    }
+
+    /// Word indices where the phrase starts
+    fn phrase_starts(&self, words: &[Word]) -> Vec<usize> {
+        words
+            .windows(self.terms.len())
+            .enumerate()
+            .filter(|(_, window)| window.iter().zip(&self.terms).all(|(word, term)| word.text == *term))
+            .map(|(start, _)| start)
+            .collect()
+    }
+
+    /// Closest run of words holding every distinct query word, as the
+    /// indices of its first and last word, and how many words match one
+    fn closest(&self, words: &[Word], fuzzy: bool) -> Option<((usize, usize), usize)> {
+        let mut terms: Vec<&str> = Vec::new();
+        for term in &self.terms {
+            if !terms.contains(&term.as_str()) {
+                terms.push(term);
+            }
+        }
+
+        // Every (word, term) match, in word order
+        let matches: Vec<(usize, usize)> = words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| {
+                terms
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, term)| Self::matches(term, &word.text, fuzzy))
+                    .map(move |(t, _)| (i, t))
+            })
+            .collect();
+        let matched = matches.iter().map(|&(i, _)| i).collect::<BTreeSet<_>>().len();
+
+        // Slide a window over the matches, shrinking it from the left while
+        // it still holds every term
+        let mut counts = vec![0; terms.len()];
+        let mut covered = 0;
+        let mut left = 0;
+        let mut best: Option<(usize, usize)> = None;
+        for &(last, term) in &matches {
+            if counts[term] == 0 {
+                covered += 1;
+            }
+            counts[term] += 1;
+
+            while covered == terms.len() {
+                let first = matches[left].0;
+                if best.is_none_or(|(f, l)| last - first < l - f) {
+                    best = Some((first, last));
+                }
+                let dropped = matches[left].1;
+                counts[dropped] -= 1;
+                if counts[dropped] == 0 {
+                    covered -= 1;
+                }
+                left += 1;
+            }
+        }
+        best.map(|window| (window, matched))
+    }
+
+    /// How `entry` matches, with a snippet of `context` lines either side
+    /// of the match; `None` if it does not
+    pub fn match_entry(&self, entry: &JournalEntry, context: usize) -> Option<SearchHit> {
+        let words = words(&entry.raw_content);
+        let phrases = self.phrase_starts(&words);
+
+        let (kind, (first, last), occurrences) = if let Some(&start) = phrases.first() {
+            (MatchKind::Phrase, (start, start + self.terms.len() - 1), phrases.len())
+        } else if let Some((window, matched)) = self.closest(&words, false) {
+            (MatchKind::Words, window, matched)
+        } else if self.fuzzy {
+            let (window, matched) = self.closest(&words, true)?;
+            (MatchKind::Fuzzy, window, matched)
+        } else {
+            return None;
+        };
+
+        // Phrases are highlighted where they occur, words wherever they match
+        let highlighted: BTreeSet<usize> = match kind {
+            MatchKind::Phrase => phrases
+                .iter()
+                .flat_map(|&start| start..start + self.terms.len())
+                .collect(),
+            _ => (0..words.len())
+                .filter(|&i| {
+                    self.terms
+                        .iter()
+                        .any(|term| Self::matches(term, &words[i].text, kind == MatchKind::Fuzzy))
+                })
+                .collect(),
+        };
+
+        let lines: Vec<&str> = entry.raw_content.lines().collect();
+        let (first_line, last_line) = (words[first].line, words[last].line);
+        let end_line = if last_line - first_line < MAX_SNIPPET_SPREAD {
+            last_line
+        } else {
+            first_line
+        };
+        let from = first_line.saturating_sub(context);
+        let to = (end_line + context).min(lines.len() - 1);
+        let snippet = (from..=to)
+            .map(|line| SnippetLine {
+                number: line + 1,
+                text: lines[line].to_string(),
+                highlights: highlighted
+                    .iter()
+                    .map(|&i| &words[i])
+                    .filter(|word| word.line == line)
+                    .map(|word| (word.start, word.end))
+                    .collect(),
+            })
+            .collect();
+
+        Some(SearchHit {
+            path: entry.filepath.clone(),
+            date: entry.date,
+            repository: entry.repository.clone(),
+            title: entry.description(),
+            kind,
+            span: last - first + 1,
+            occurrences,
+            line: first_line + 1,
+            snippet,
+        })
+    }
+}
+
+/// Entries matching `query`, best first, each with `context` lines either
+/// side of its match
+pub fn search(entries: &[JournalEntry], query: &SearchQuery, context: usize) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = entries
+        .iter()
+        .filter_map(|entry| query.match_entry(entry, context))
+        .collect();
+    hits.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then(a.span.cmp(&b.span))
+            .then(b.occurrences.cmp(&a.occurrences))
+            .then(b.date.cmp(&a.date))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, day: u32, content: &str) -> JournalEntry {
+        let date = NaiveDate::from_ymd_opt(2025, 9, day).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", name)), date);
+        entry.title = Some(name.to_string());
+        entry.raw_content = content.to_string();
+        entry
+    }
+
+    fn titles(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_words() {
+        let query = SearchQuery::new("Connection-pool  EXHAUSTION!", false).unwrap();
+        assert_eq!(query.terms(), ["connection", "pool", "exhaustion"]);
+        assert!(SearchQuery::new(" -- ", false).is_err());
+    }
+
+    #[test]
+    fn test_typos_allowed() {
+        assert!(SearchQuery::matches("pool", "pool", false));
+        assert!(!SearchQuery::matches("pool", "poll", false));
+        assert!(SearchQuery::matches("pool", "poll", true));
+        assert!(!SearchQuery::matches("api", "apu", true));
+        assert!(SearchQuery::matches("exhaustion", "exhuastion", true));
+        assert!(!SearchQuery::matches("exhaustion", "exhausted", true));
+    }
+
+    #[test]
+    fn test_phrase_before_words_before_fuzzy() {
+        let entries = vec![
+            entry("fuzzy", 20, "Conection pool exhuastion again\n"),
+            entry("words", 19, "The pool ran dry.\nExhaustion of every connection.\n"),
+            entry("phrase", 1, "Saw connection pool exhaustion in prod\n"),
+            entry("none", 21, "Nothing to see\n"),
+        ];
+
+        let query = SearchQuery::new("connection pool exhaustion", true).unwrap();
+        let hits = search(&entries, &query, 0);
+        assert_eq!(titles(&hits), ["phrase", "words", "fuzzy"]);
+        let kinds: Vec<MatchKind> = hits.iter().map(|hit| hit.kind).collect();
+        assert_eq!(kinds, [MatchKind::Phrase, MatchKind::Words, MatchKind::Fuzzy]);
+
+        // Without fuzzy matching the misspelt entry is not found
+        let exact = SearchQuery::new("connection pool exhaustion", false).unwrap();
+        assert_eq!(titles(&search(&entries, &exact, 0)), ["phrase", "words"]);
+    }
+
+    #[test]
+    fn test_closer_words_rank_higher() {
+        let entries = vec![
+            entry("far", 2, "retry the job\nlots of unrelated words here\nthen the queue\n"),
+            entry("near", 1, "the queue will retry\n"),
+        ];
+        let query = SearchQuery::new("retry queue", false).unwrap();
+        let hits = search(&entries, &query, 0);
+        assert_eq!(titles(&hits), ["near", "far"]);
+        assert_eq!(hits[0].span, 3);
+        assert_eq!(hits[1].span, 11);
+    }
+
+    #[test]
+    fn test_more_occurrences_then_newer_rank_higher() {
+        let entries = vec![
+            entry("once-old", 1, "deploy freeze\n"),
+            entry("once-new", 3, "deploy freeze\n"),
+            entry("twice", 2, "deploy freeze\nanother deploy freeze\n"),
+        ];
+        let query = SearchQuery::new("deploy freeze", false).unwrap();
+        let hits = search(&entries, &query, 0);
+        assert_eq!(titles(&hits), ["twice", "once-new", "once-old"]);
+        assert_eq!(hits[0].occurrences, 2);
+    }
+
+    #[test]
+    fn test_snippet_context_and_highlights() {
+        let content = "# Outage\n\nTimeouts at 09:00.\nRoot cause: connection pool exhaustion.\nRaised the limit.\nDone.\n";
+        let query = SearchQuery::new("pool exhaustion", false).unwrap();
+        let hit = query.match_entry(&entry("outage", 1, content), 1).unwrap();
+
+        assert_eq!(hit.line, 4);
+        let numbers: Vec<usize> = hit.snippet.iter().map(|line| line.number).collect();
+        assert_eq!(numbers, [3, 4, 5]);
+        let line = &hit.snippet[1];
+        assert_eq!(line.text, "Root cause: connection pool exhaustion.");
+        let marked: Vec<&str> = line.highlights.iter().map(|&(s, e)| &line.text[s..e]).collect();
+        assert_eq!(marked, ["pool", "exhaustion"]);
+        assert!(hit.snippet[0].highlights.is_empty());
+
+        // Context stops at the ends of the entry
+        let hit = query.match_entry(&entry("outage", 1, content), 10).unwrap();
+        assert_eq!(hit.snippet.len(), 6);
+    }
+
+    #[test]
+    fn test_spread_out_match_shows_its_first_line() {
+        let content = "alpha\n\n\n\n\nbeta\n";
+        let query = SearchQuery::new("alpha beta", false).unwrap();
+        let hit = query.match_entry(&entry("spread", 1, content), 0).unwrap();
+        assert_eq!(hit.snippet.len(), 1);
+        assert_eq!(hit.snippet[0].text, "alpha");
+
+        let close = query.match_entry(&entry("close", 1, "alpha\nbeta\n"), 0).unwrap();
+        assert_eq!(close.snippet.len(), 2);
+    }
+}
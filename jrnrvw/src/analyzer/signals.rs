@@ -0,0 +1,621 @@
+//! Trend and anomaly detection over per-day aggregates
+//!
+//! Entries are bucketed per day, then into 7-day windows counted back from
+//! the newest entry so the latest window is never a partial calendar week.
+//! Three detectors run over those series:
+//! 1. volume drops: a repository's latest window against the rolling mean of
+//!    the windows before it
+//! 2. completion trend: the share of checked `[x]` activities falling several
+//!    windows in a row
+//! 3. long entries: a day's word count scored against every other day
+
+use chrono::NaiveDate;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::config::settings::SignalsConfig;
+use crate::models::{JournalEntry, Severity, Signal, SignalKind, SignalSource};
+
+/// Days in one aggregation window
+const WINDOW_DAYS: i64 = 7;
+
+/// Smallest standard deviation used when scoring day lengths (words)
+///
+/// Keeps a perfectly uniform history from turning any deviation into an
+/// infinite score.
+const MIN_WORD_DEVIATION: f64 = 1.0;
+
+/// Runs the trend and anomaly pass over a set of entries
+#[derive(Debug, Default)]
+pub struct SignalDetector {
+    config: SignalsConfig,
+}
+
+impl SignalDetector {
+    /// Create a detector with the given thresholds
+    pub fn new(config: SignalsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Find all signals, most severe first
+    pub fn detect(&self, entries: &[JournalEntry]) -> Vec<Signal> {
+        if !self.config.enabled || entries.is_empty() {
+            return Vec::new();
+        }
+
+        let mut signals = self.volume_drops(entries);
+        signals.extend(self.completion_trend(entries));
+        signals.extend(self.long_entries(entries));
+
+        signals.sort_by_key(|s| Reverse(s.severity));
+        signals
+    }
+
+    /// Repositories whose latest window fell well below their recent average
+    fn volume_drops(&self, entries: &[JournalEntry]) -> Vec<Signal> {
+        let (first, end) = date_bounds(entries);
+        let weeks = self.config.baseline_weeks + 1;
+
+        // Without a full baseline every repository would look like it dropped
+        if (end - first).num_days() + 1 < weeks as i64 * WINDOW_DAYS {
+            return Vec::new();
+        }
+
+        let mut by_repo: BTreeMap<&str, Vec<&JournalEntry>> = BTreeMap::new();
+        for entry in entries {
+            if let Some(repo) = entry.repository.as_deref() {
+                by_repo.entry(repo).or_default().push(entry);
+            }
+        }
+
+        let mut signals = Vec::new();
+        for (repo, repo_entries) in by_repo {
+            let daily = daily_totals(&repo_entries, |_| 1.0);
+            let totals = window_totals(&daily, end, weeks);
+
+            let Some(Some(baseline)) = rolling_mean(&totals, self.config.baseline_weeks)
+                .last()
+                .copied()
+            else {
+                continue;
+            };
+            if baseline < self.config.min_baseline_entries {
+                continue;
+            }
+
+            let current = totals[totals.len() - 1];
+            let drop = 1.0 - current / baseline;
+            if drop < self.config.drop_threshold {
+                continue;
+            }
+
+            let severity = if drop >= self.config.critical_drop_threshold {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            };
+
+            // Point at the latest window, or the last entry before it went quiet
+            let mut sources: Vec<&JournalEntry> = repo_entries
+                .iter()
+                .copied()
+                .filter(|e| window_index(e.date, end) == 0)
+                .collect();
+            if sources.is_empty() {
+                sources.extend(repo_entries.iter().copied().max_by_key(|e| e.date));
+            }
+
+            signals.push(Signal {
+                severity,
+                kind: SignalKind::VolumeDrop,
+                message: format!(
+                    "entries for {} dropped {:.0}% versus the prior {}-week average ({} vs {:.1})",
+                    repo,
+                    drop * 100.0,
+                    self.config.baseline_weeks,
+                    current,
+                    baseline
+                ),
+                sources: to_sources(sources),
+            });
+        }
+
+        signals
+    }
+
+    /// Completion rate of checkbox activities falling several windows running
+    fn completion_trend(&self, entries: &[JournalEntry]) -> Option<Signal> {
+        let (_, end) = date_bounds(entries);
+        let weeks = self.config.trend_weeks + 1;
+
+        let mut done = vec![0.0; weeks];
+        let mut total = vec![0.0; weeks];
+        for entry in entries {
+            let index = window_index(entry.date, end);
+            if index >= weeks {
+                continue;
+            }
+            let slot = weeks - 1 - index;
+            for activity in &entry.activities {
+                match checkbox_state(activity) {
+                    Some(true) => {
+                        done[slot] += 1.0;
+                        total[slot] += 1.0;
+                    }
+                    Some(false) => total[slot] += 1.0,
+                    None => {}
+                }
+            }
+        }
+
+        // Every window needs checkbox data for a trend to mean anything
+        if total.contains(&0.0) {
+            return None;
+        }
+
+        let rates: Vec<f64> = done.iter().zip(&total).map(|(d, t)| d / t).collect();
+        if trailing_declines(&rates) < self.config.trend_weeks {
+            return None;
+        }
+
+        let sources = entries
+            .iter()
+            .filter(|e| window_index(e.date, end) == 0)
+            .filter(|e| e.activities.iter().any(|a| checkbox_state(a).is_some()))
+            .collect();
+
+        Some(Signal {
+            severity: Severity::Warning,
+            kind: SignalKind::CompletionTrend,
+            message: format!(
+                "task completion rate trending down {} weeks in a row (from {:.0}% to {:.0}%)",
+                self.config.trend_weeks,
+                rates[0] * 100.0,
+                rates[rates.len() - 1] * 100.0
+            ),
+            sources: to_sources(sources),
+        })
+    }
+
+    /// Days whose total word count is far above every other day's
+    fn long_entries(&self, entries: &[JournalEntry]) -> Vec<Signal> {
+        let refs: Vec<&JournalEntry> = entries.iter().collect();
+        let daily = daily_totals(&refs, |e| e.raw_content.split_whitespace().count() as f64);
+        if daily.len() < self.config.min_samples.max(2) {
+            return Vec::new();
+        }
+
+        let dates: Vec<NaiveDate> = daily.keys().copied().collect();
+        let words: Vec<f64> = daily.values().copied().collect();
+
+        let mut signals = Vec::new();
+        for (i, score) in leave_one_out_z_scores(&words, MIN_WORD_DEVIATION)
+            .into_iter()
+            .enumerate()
+        {
+            if score < self.config.zscore_threshold {
+                continue;
+            }
+
+            let date = dates[i];
+            signals.push(Signal {
+                severity: Severity::Info,
+                kind: SignalKind::LongEntry,
+                message: format!(
+                    "unusually long entry on {} ({} words, {:.1} standard deviations above other days)",
+                    date, words[i], score
+                ),
+                sources: to_sources(entries.iter().filter(|e| e.date == date).collect()),
+            });
+        }
+
+        signals
+    }
+}
+
+/// Arithmetic mean, or `None` for an empty slice
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Population standard deviation, or `None` for an empty slice
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    let mean = mean(values)?;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    Some(variance.sqrt())
+}
+
+/// Mean of the `window` values before each point
+///
+/// The first `window` points have no full history and map to `None`.
+pub fn rolling_mean(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            if window == 0 || i < window {
+                None
+            } else {
+                mean(&values[i - window..i])
+            }
+        })
+        .collect()
+}
+
+/// Standard z-score of every value against the whole series
+///
+/// A constant series scores 0 everywhere.
+pub fn z_scores(values: &[f64]) -> Vec<f64> {
+    let (Some(mean), Some(std)) = (mean(values), std_dev(values)) else {
+        return Vec::new();
+    };
+
+    values
+        .iter()
+        .map(|v| if std == 0.0 { 0.0 } else { (v - mean) / std })
+        .collect()
+}
+
+/// Z-score of every value against the rest of the series
+///
+/// Leaving the value out keeps a single large outlier from inflating the
+/// deviation it is measured against. The deviation is floored at
+/// `min_deviation`.
+pub fn leave_one_out_z_scores(values: &[f64], min_deviation: f64) -> Vec<f64> {
+    if values.len() < 2 {
+        return vec![0.0; values.len()];
+    }
+
+    (0..values.len())
+        .map(|i| {
+            let others: Vec<f64> = values
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, v)| *v)
+                .collect();
+            let mean = mean(&others).unwrap_or(0.0);
+            let std = std_dev(&others).unwrap_or(0.0).max(min_deviation);
+            (values[i] - mean) / std
+        })
+        .collect()
+}
+
+/// Number of consecutive strict decreases at the end of a series
+pub fn trailing_declines(values: &[f64]) -> usize {
+    values
+        .windows(2)
+        .rev()
+        .take_while(|pair| pair[1] < pair[0])
+        .count()
+}
+
+/// Whether an activity is a checked (`[x]`) or open (`[ ]`) checkbox
+pub(crate) fn checkbox_state(activity: &str) -> Option<bool> {
+    let activity = activity.trim_start();
+    if activity.starts_with("[x]") || activity.starts_with("[X]") {
+        Some(true)
+    } else if activity.starts_with("[ ]") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Oldest and newest entry dates
+fn date_bounds(entries: &[JournalEntry]) -> (NaiveDate, NaiveDate) {
+    let first = entries.iter().map(|e| e.date).min().unwrap_or_default();
+    let last = entries.iter().map(|e| e.date).max().unwrap_or_default();
+    (first, last)
+}
+
+/// Sum a per-entry value for each day
+fn daily_totals(
+    entries: &[&JournalEntry],
+    value: impl Fn(&JournalEntry) -> f64,
+) -> BTreeMap<NaiveDate, f64> {
+    let mut daily = BTreeMap::new();
+    for entry in entries {
+        *daily.entry(entry.date).or_insert(0.0) += value(entry);
+    }
+    daily
+}
+
+/// Which 7-day window counted back from `end` a date falls in (0 = latest)
+fn window_index(date: NaiveDate, end: NaiveDate) -> usize {
+    ((end - date).num_days().max(0) / WINDOW_DAYS) as usize
+}
+
+/// Totals for the `windows` windows ending at `end`, oldest first
+fn window_totals(daily: &BTreeMap<NaiveDate, f64>, end: NaiveDate, windows: usize) -> Vec<f64> {
+    let mut totals = vec![0.0; windows];
+    for (date, value) in daily {
+        let index = window_index(*date, end);
+        if index < windows {
+            totals[windows - 1 - index] += value;
+        }
+    }
+    totals
+}
+
+fn to_sources(mut entries: Vec<&JournalEntry>) -> Vec<SignalSource> {
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.filepath.cmp(&b.filepath))
+    });
+    entries
+        .into_iter()
+        .map(|e| SignalSource {
+            date: e.date,
+            path: e.filepath.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn date(offset: i64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 1).unwrap() + chrono::Duration::days(offset)
+    }
+
+    fn entry(offset: i64, repo: &str) -> JournalEntry {
+        let path = PathBuf::from(format!("{}/{}_journal.md", repo, date(offset)));
+        let mut entry = JournalEntry::new(path, date(offset));
+        entry.repository = Some(repo.to_string());
+        entry.raw_content = "word ".repeat(100);
+        entry
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_mean_and_std_dev() {
+        assert_eq!(mean(&[]), None);
+        assert_close(mean(&[2.0, 4.0, 6.0]).unwrap(), 4.0);
+        assert_close(
+            std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap(),
+            2.0,
+        );
+    }
+
+    #[test]
+    fn test_rolling_mean_uses_previous_points_only() {
+        let means = rolling_mean(&[1.0, 2.0, 3.0, 4.0, 10.0], 3);
+        assert_eq!(means[..3], [None, None, None]);
+        assert_close(means[3].unwrap(), 2.0);
+        assert_close(means[4].unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_z_scores() {
+        let scores = z_scores(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_close(scores[0], -1.5);
+        assert_close(scores[7], 2.0);
+
+        assert_eq!(z_scores(&[3.0, 3.0, 3.0]), vec![0.0, 0.0, 0.0]);
+        assert!(z_scores(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_leave_one_out_finds_single_spike() {
+        // A spike in a short series can't reach z = 3 when it is part of its own baseline
+        let series = [10.0, 12.0, 9.0, 11.0, 10.0, 60.0, 11.0];
+        assert!(z_scores(&series)[5] < 3.0);
+
+        let scores = leave_one_out_z_scores(&series, 1.0);
+        assert!(scores[5] > 3.0);
+        assert!(scores.iter().enumerate().all(|(i, s)| i == 5 || *s < 3.0));
+    }
+
+    #[test]
+    fn test_trailing_declines() {
+        assert_eq!(trailing_declines(&[0.9, 0.8, 0.7, 0.6]), 3);
+        assert_eq!(trailing_declines(&[0.5, 0.9, 0.8, 0.7]), 2);
+        assert_eq!(trailing_declines(&[0.5, 0.6]), 0);
+        assert_eq!(trailing_declines(&[0.7, 0.7]), 0);
+        assert_eq!(trailing_declines(&[]), 0);
+    }
+
+    #[test]
+    fn test_detects_volume_drop() {
+        // Five entries a week for four weeks, then one in the final week
+        let mut entries = Vec::new();
+        for week in 0..4 {
+            for day in 0..5 {
+                entries.push(entry(week * 7 + day, "alpha"));
+                entries.push(entry(week * 7 + day, "beta"));
+            }
+        }
+        entries.push(entry(28, "alpha"));
+        for day in 28..33 {
+            entries.push(entry(day, "beta"));
+        }
+        entries.push(entry(34, "beta"));
+
+        let signals = SignalDetector::default().detect(&entries);
+        let drops: Vec<_> = signals
+            .iter()
+            .filter(|s| s.kind == SignalKind::VolumeDrop)
+            .collect();
+
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].severity, Severity::Critical);
+        assert_eq!(
+            drops[0].message,
+            "entries for alpha dropped 80% versus the prior 4-week average (1 vs 5.0)"
+        );
+        assert_eq!(drops[0].sources.len(), 1);
+        assert_eq!(drops[0].sources[0].date, date(28));
+        assert_eq!(
+            drops[0].sources[0].path,
+            PathBuf::from("alpha/2024-03-29_journal.md")
+        );
+    }
+
+    #[test]
+    fn test_silent_repo_links_last_entry() {
+        let mut entries = Vec::new();
+        for day in 0..28 {
+            entries.push(entry(day, "alpha"));
+            entries.push(entry(day, "beta"));
+        }
+        for day in 28..35 {
+            entries.push(entry(day, "beta"));
+        }
+
+        let signals = SignalDetector::default().detect(&entries);
+        let drop = signals
+            .iter()
+            .find(|s| s.kind == SignalKind::VolumeDrop)
+            .unwrap();
+        assert!(drop.message.starts_with("entries for alpha dropped 100%"));
+        assert_eq!(drop.sources[0].date, date(27));
+    }
+
+    #[test]
+    fn test_short_history_has_no_volume_baseline() {
+        let mut entries: Vec<_> = (0..14).map(|day| entry(day, "alpha")).collect();
+        entries.push(entry(20, "beta"));
+
+        let signals = SignalDetector::default().detect(&entries);
+        assert!(signals.iter().all(|s| s.kind != SignalKind::VolumeDrop));
+    }
+
+    #[test]
+    fn test_drop_threshold_is_configurable() {
+        let mut entries = Vec::new();
+        for day in 0..28 {
+            entries.push(entry(day, "alpha"));
+        }
+        for day in 28..32 {
+            entries.push(entry(day, "alpha"));
+        }
+        entries.push(entry(34, "alpha"));
+
+        // 7/week down to 5: a 29% drop
+        assert!(SignalDetector::default().detect(&entries).is_empty());
+
+        let config = SignalsConfig {
+            drop_threshold: 0.25,
+            ..SignalsConfig::default()
+        };
+        let signals = SignalDetector::new(config).detect(&entries);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_detects_completion_trend() {
+        // Completion rate per week: 100%, 75%, 50%, 25%
+        let mut entries = Vec::new();
+        for (week, checked) in [4, 3, 2, 1].into_iter().enumerate() {
+            let mut e = entry(week as i64 * 7, "alpha");
+            e.activities = (0..4)
+                .map(|i| if i < checked { "[x] done" } else { "[ ] open" }.to_string())
+                .collect();
+            e.activities.push("plain activity".to_string());
+            entries.push(e);
+        }
+
+        let signals = SignalDetector::default().detect(&entries);
+        let trend = signals
+            .iter()
+            .find(|s| s.kind == SignalKind::CompletionTrend)
+            .unwrap();
+        assert_eq!(
+            trend.message,
+            "task completion rate trending down 3 weeks in a row (from 100% to 25%)"
+        );
+        assert_eq!(trend.sources.len(), 1);
+        assert_eq!(trend.sources[0].date, date(21));
+    }
+
+    #[test]
+    fn test_completion_recovery_is_not_a_trend() {
+        let mut entries = Vec::new();
+        for (week, checked) in [4, 2, 1, 3].into_iter().enumerate() {
+            let mut e = entry(week as i64 * 7, "alpha");
+            e.activities = (0..4)
+                .map(|i| if i < checked { "[X] done" } else { "[ ] open" }.to_string())
+                .collect();
+            entries.push(e);
+        }
+
+        let signals = SignalDetector::default().detect(&entries);
+        assert!(signals
+            .iter()
+            .all(|s| s.kind != SignalKind::CompletionTrend));
+    }
+
+    #[test]
+    fn test_detects_long_entry() {
+        let mut entries: Vec<_> = (0..10).map(|day| entry(day, "alpha")).collect();
+        entries[3].raw_content = "word ".repeat(90);
+        entries[6].raw_content = "word ".repeat(110);
+        entries[8].raw_content = "word ".repeat(1200);
+
+        let signals = SignalDetector::default().detect(&entries);
+        let long: Vec<_> = signals
+            .iter()
+            .filter(|s| s.kind == SignalKind::LongEntry)
+            .collect();
+
+        assert_eq!(long.len(), 1);
+        assert_eq!(long[0].severity, Severity::Info);
+        assert!(long[0]
+            .message
+            .starts_with("unusually long entry on 2024-03-09 (1200 words"));
+        assert_eq!(long[0].sources[0].date, date(8));
+    }
+
+    #[test]
+    fn test_too_few_days_for_z_scores() {
+        let mut entries: Vec<_> = (0..4).map(|day| entry(day, "alpha")).collect();
+        entries[2].raw_content = "word ".repeat(5000);
+
+        assert!(SignalDetector::default().detect(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_disabled_detector_is_silent() {
+        let mut entries: Vec<_> = (0..10).map(|day| entry(day, "alpha")).collect();
+        entries[8].raw_content = "word ".repeat(1200);
+
+        let config = SignalsConfig {
+            enabled: false,
+            ..SignalsConfig::default()
+        };
+        assert!(SignalDetector::new(config).detect(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_signals_sorted_by_severity() {
+        let mut entries = Vec::new();
+        for day in 0..28 {
+            entries.push(entry(day, "alpha"));
+            entries.push(entry(day, "beta"));
+        }
+        for day in 28..35 {
+            entries.push(entry(day, "beta"));
+        }
+        entries[10].raw_content = "word ".repeat(3000);
+
+        let signals = SignalDetector::default().detect(&entries);
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].severity, Severity::Critical);
+        assert_eq!(signals[1].severity, Severity::Info);
+    }
+}
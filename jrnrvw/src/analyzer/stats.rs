@@ -1,6 +1,6 @@
 //! Statistics calculation for journal entries and repositories
 
-use crate::models::{JournalEntry, Repository, Statistics};
+use crate::models::{JournalEntry, Repository, Statistics, TaskStatus, TimeLogged};
 use crate::error::Result;
 use chrono::NaiveDate;
 use std::collections::HashSet;
@@ -38,6 +38,9 @@ impl StatisticsCalculator {
         // Calculate unique tasks
         let unique_tasks = self.calculate_unique_tasks();
 
+        // Count open, done and stale tasks
+        let (open_tasks, done_tasks, stale_tasks) = self.calculate_task_states();
+
         // Calculate total time (if available)
         let total_time = self.calculate_total_time();
 
@@ -47,6 +50,10 @@ impl StatisticsCalculator {
             active_days,
             repositories,
             unique_tasks,
+            task_mentions: None,
+            open_tasks,
+            done_tasks,
+            stale_tasks,
             total_time,
         })
     }
@@ -87,23 +94,28 @@ impl StatisticsCalculator {
         unique_tasks.len()
     }
 
+    /// Count open, done and stale tasks as of the newest entry
+    ///
+    /// Tasks without any status markers count as open.
+    fn calculate_task_states(&self) -> (usize, usize, usize) {
+        let Some(as_of) = self.entries.iter().map(|e| e.date).max() else {
+            return (0, 0, 0);
+        };
+
+        let mut counts = (0, 0, 0);
+        for task in self.repositories.iter().flat_map(|r| &r.tasks) {
+            match task.status_as_of(as_of) {
+                Some(TaskStatus::Done) => counts.1 += 1,
+                Some(TaskStatus::Stale) => counts.2 += 1,
+                Some(TaskStatus::Open) | None => counts.0 += 1,
+            }
+        }
+        counts
+    }
+
     /// Calculate total time spent (if time information is available)
     fn calculate_total_time(&self) -> Option<String> {
-        // For now, this is a stub. In a full implementation, this would
-        // parse time_spent fields and aggregate them.
-        let time_entries: Vec<&str> = self
-            .entries
-            .iter()
-            .filter_map(|e| e.time_spent.as_deref())
-            .collect();
-
-        if time_entries.is_empty() {
-            None
-        } else {
-            // TODO: Implement proper time aggregation
-            // For now, just return a placeholder
-            Some(format!("{} entries with time data", time_entries.len()))
-        }
+        TimeLogged::total(self.entries.iter().filter_map(|e| e.time_logged)).map(|total| total.to_string())
     }
 
     /// Get statistics for a specific repository
@@ -170,6 +182,28 @@ mod tests {
         assert_eq!(stats.unique_tasks, 2);
     }
 
+    #[test]
+    fn test_task_states() {
+        use crate::models::{Task, STALE_AFTER_DAYS};
+
+        let mut old = create_test_entry("2025-10-01", "repo1", Some("old"));
+        old.activities = vec!["[ ] never finished".to_string()];
+        let mut done = create_test_entry("2025-10-01", "repo1", Some("done"));
+        done.activities = vec!["[x] shipped".to_string()];
+        let current = create_test_entry("2025-11-15", "repo1", Some("current"));
+        assert!((current.date - old.date).num_days() > STALE_AFTER_DAYS);
+
+        let mut repo = Repository::new("repo1".to_string(), None);
+        for entry in [&old, &done, &current] {
+            let mut task = Task::new(entry.task.clone().unwrap());
+            task.add_entry(entry.clone());
+            repo.add_task(task);
+        }
+
+        let stats = StatisticsCalculator::new(vec![old, done, current], vec![repo]).calculate().unwrap();
+        assert_eq!((stats.open_tasks, stats.done_tasks, stats.stale_tasks), (1, 1, 1));
+    }
+
     #[test]
     fn test_active_days_with_duplicates() {
         let entries = vec![
@@ -200,14 +234,14 @@ mod tests {
             create_test_entry("2025-11-13", "repo1", Some("task1")),
             create_test_entry("2025-11-14", "repo1", Some("task2")),
         ];
-        entries[0].time_spent = Some("2h".to_string());
-        entries[1].time_spent = Some("3h".to_string());
+        entries[0].time_logged = Some(TimeLogged::new(120, false));
+        entries[1].time_logged = Some(TimeLogged::new(195, true));
 
         let calculator = StatisticsCalculator::new(entries, vec![]);
         let stats = calculator.calculate().unwrap();
 
         assert!(stats.total_time.is_some());
-        assert_eq!(stats.total_time.unwrap(), "2 entries with time data");
+        assert_eq!(stats.total_time.unwrap(), "~5h 15m");
     }
 
     #[test]
@@ -0,0 +1,294 @@
+//! Task dependency graph
+//!
+//! Each task's `blocked by:` and `depends on` ids are resolved to tasks. An
+//! id is looked up in the task's own repository first, then among the other
+//! repositories' tasks, where it has to be unique; `<repository>#<id>` names
+//! the repository outright. Ids match case-insensitively.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::models::reference::strongly_connected;
+use crate::models::{Repository, TaskDependency, TaskGraph, TaskNode, TaskOrder, UnresolvedDependency};
+
+/// Build the dependency graph between the tasks of `repositories`
+///
+/// `known` is every loaded task, grouped by repository: a dependency on a
+/// task the report's filter left out is dropped from the graph rather than
+/// flagged unresolved.
+pub fn task_graph(repositories: &[Repository], known: &[Repository]) -> TaskGraph {
+    let nodes = task_nodes(repositories);
+    let depends_on: Vec<&[String]> = repositories
+        .iter()
+        .flat_map(|repo| repo.tasks.iter().map(|task| task.depends_on.as_slice()))
+        .collect();
+    let index = TaskIndex::new(&nodes);
+    let known_nodes = task_nodes(known);
+    let known_index = TaskIndex::new(&known_nodes);
+
+    // Task to the tasks it depends on, by position in `nodes`
+    let mut graph: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    let mut unresolved = Vec::new();
+    for (from, references) in depends_on.iter().enumerate() {
+        let repository = &nodes[from].repository;
+        for reference in references.iter() {
+            match index.resolve(reference, repository) {
+                Some(to) => {
+                    graph.entry(from).or_default().insert(to);
+                    graph.entry(to).or_default();
+                }
+                None if known_index.resolve(reference, repository).is_some() => {}
+                None => unresolved.push(UnresolvedDependency {
+                    task: nodes[from].clone(),
+                    reference: reference.clone(),
+                }),
+            }
+        }
+    }
+
+    let by_name = |a: &TaskNode, b: &TaskNode| (&a.repository, &a.name, &a.id).cmp(&(&b.repository, &b.name, &b.id));
+
+    let mut edges: Vec<TaskDependency> = graph
+        .iter()
+        .flat_map(|(&from, targets)| {
+            targets.iter().map(move |&to| (from, to))
+        })
+        .map(|(from, to)| TaskDependency {
+            task: nodes[from].clone(),
+            depends_on: nodes[to].clone(),
+        })
+        .collect();
+    edges.sort_by(|a, b| by_name(&a.task, &b.task).then_with(|| by_name(&a.depends_on, &b.depends_on)));
+    unresolved.sort_by(|a, b| by_name(&a.task, &b.task));
+
+    let mut cycles: Vec<Vec<TaskNode>> = strongly_connected(&graph)
+        .into_iter()
+        .filter(|component| component.len() > 1 || graph[&component[0]].contains(&component[0]))
+        .map(|component| {
+            let mut members: Vec<TaskNode> = component.into_iter().map(|i| nodes[i].clone()).collect();
+            members.sort_by(by_name);
+            members
+        })
+        .collect();
+    cycles.sort_by(|a, b| by_name(&a[0], &b[0]));
+
+    let involved: BTreeSet<&str> = graph
+        .keys()
+        .map(|&i| nodes[i].repository.as_str())
+        .chain(unresolved.iter().map(|u| u.task.repository.as_str()))
+        .collect();
+    let order = involved
+        .into_iter()
+        .map(|repository| {
+            let members: Vec<usize> = (0..nodes.len()).filter(|&i| nodes[i].repository == repository).collect();
+            TaskOrder {
+                repository: repository.to_string(),
+                tasks: dependency_order(&members, &graph, &nodes).into_iter().map(|i| nodes[i].clone()).collect(),
+            }
+        })
+        .collect();
+
+    TaskGraph {
+        order,
+        edges,
+        unresolved,
+        cycles,
+    }
+}
+
+fn task_nodes(repositories: &[Repository]) -> Vec<TaskNode> {
+    repositories
+        .iter()
+        .flat_map(|repo| {
+            repo.tasks.iter().map(|task| TaskNode {
+                repository: repo.name.clone(),
+                id: task.id.clone(),
+                name: task.name.clone(),
+            })
+        })
+        .collect()
+}
+
+/// `members` ordered so each comes after the members it depends on, ties
+/// broken by name; members caught in a cycle, or waiting on one, come last
+fn dependency_order(members: &[usize], graph: &BTreeMap<usize, BTreeSet<usize>>, nodes: &[TaskNode]) -> Vec<usize> {
+    let in_group: BTreeSet<usize> = members.iter().copied().collect();
+    let mut waiting: HashMap<usize, usize> = HashMap::new();
+    let mut dependents: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &member in members {
+        let targets = graph.get(&member).into_iter().flatten().filter(|t| in_group.contains(t));
+        for &target in targets {
+            *waiting.entry(member).or_default() += 1;
+            dependents.entry(target).or_default().push(member);
+        }
+    }
+
+    let key = |i: usize| (nodes[i].name.as_str(), nodes[i].id.as_str(), i);
+    let mut ready: BTreeSet<(&str, &str, usize)> = members
+        .iter()
+        .filter(|m| !waiting.contains_key(m))
+        .map(|&m| key(m))
+        .collect();
+
+    let mut order = Vec::with_capacity(members.len());
+    while let Some(next) = ready.pop_first() {
+        let task = next.2;
+        order.push(task);
+        for &dependent in dependents.get(&task).into_iter().flatten() {
+            let count = waiting.get_mut(&dependent).expect("dependent is waiting");
+            *count -= 1;
+            if *count == 0 {
+                waiting.remove(&dependent);
+                ready.insert(key(dependent));
+            }
+        }
+    }
+
+    let mut stuck: Vec<usize> = waiting.into_keys().collect();
+    stuck.sort_by_key(|&i| key(i));
+    order.extend(stuck);
+    order
+}
+
+/// Tasks by lower-cased repository and id
+struct TaskIndex {
+    by_key: HashMap<(String, String), usize>,
+    by_id: HashMap<String, Vec<usize>>,
+}
+
+impl TaskIndex {
+    fn new(nodes: &[TaskNode]) -> Self {
+        let mut by_key = HashMap::new();
+        let mut by_id: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            let id = node.id.to_lowercase();
+            by_key.entry((node.repository.to_lowercase(), id.clone())).or_insert(i);
+            by_id.entry(id).or_default().push(i);
+        }
+        Self { by_key, by_id }
+    }
+
+    /// Task `reference` names, written in a task of `repository`
+    fn resolve(&self, reference: &str, repository: &str) -> Option<usize> {
+        let reference = reference.to_lowercase();
+        if let Some((repo, id)) = reference.split_once('#') {
+            return self.by_key.get(&(repo.to_string(), id.to_string())).copied();
+        }
+        if let Some(&task) = self.by_key.get(&(repository.to_lowercase(), reference.clone())) {
+            return Some(task);
+        }
+        match self.by_id.get(&reference).map(Vec::as_slice) {
+            Some(&[only]) => Some(only),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{JournalEntry, Task};
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    /// Tasks given as (name, explicit id, dependencies)
+    fn repository(name: &str, tasks: &[(&str, &str, &[&str])]) -> Repository {
+        let mut repo = Repository::new(name.to_string(), None);
+        for (task_name, id, depends_on) in tasks {
+            let mut entry = JournalEntry::new(
+                PathBuf::from(format!("{}.md", task_name)),
+                NaiveDate::from_ymd_opt(2025, 11, 1).unwrap(),
+            );
+            entry.task_id = Some(id.to_string());
+            entry.depends_on = depends_on.iter().map(|d| d.to_string()).collect();
+            let mut task = Task::new(task_name.to_string());
+            task.add_entry(entry);
+            repo.add_task(task);
+        }
+        repo
+    }
+
+    fn names(order: &TaskOrder) -> Vec<&str> {
+        order.tasks.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_topological_order_within_repository() {
+        let repos = vec![
+            repository(
+                "web",
+                &[
+                    ("deploy", "4", &["3", "2"]),
+                    ("login", "3", &["1"]),
+                    ("schema", "1", &[]),
+                    ("api", "2", &["1"]),
+                    ("docs", "5", &[]),
+                ],
+            ),
+            repository("quiet", &[("alone", "1", &[])]),
+        ];
+        let graph = task_graph(&repos, &repos);
+
+        assert_eq!(graph.order.len(), 1);
+        assert_eq!(names(&graph.order[0]), vec!["docs", "schema", "api", "login", "deploy"]);
+        assert_eq!(graph.edges.len(), 4);
+        assert!(graph.unresolved.is_empty());
+        assert!(graph.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_cross_repository_and_unresolved() {
+        let repos = vec![
+            repository("api", &[("tokens", "tokens", &[])]),
+            repository("infra", &[("vpc", "vpc", &[])]),
+            repository(
+                "web",
+                &[("login", "login", &["TOKENS", "infra#vpc", "api#vpc", "42"])],
+            ),
+        ];
+        let graph = task_graph(&repos, &repos);
+
+        let targets: Vec<String> = graph.edges.iter().map(|e| e.depends_on.key()).collect();
+        assert_eq!(targets, vec!["api#tokens", "infra#vpc"]);
+        let unresolved: Vec<&str> = graph.unresolved.iter().map(|u| u.reference.as_str()).collect();
+        assert_eq!(unresolved, vec!["api#vpc", "42"]);
+        let repos: Vec<&str> = graph.order.iter().map(|o| o.repository.as_str()).collect();
+        assert_eq!(repos, vec!["api", "infra", "web"]);
+    }
+
+    #[test]
+    fn test_cycles_are_detected() {
+        let repos = vec![repository(
+            "web",
+            &[
+                ("a", "a", &["b"]),
+                ("b", "b", &["c"]),
+                ("c", "c", &["a"]),
+                ("d", "d", &["a"]),
+                ("e", "e", &["e"]),
+            ],
+        )];
+        let graph = task_graph(&repos, &repos);
+
+        let cycles: Vec<Vec<&str>> = graph
+            .cycles
+            .iter()
+            .map(|cycle| cycle.iter().map(|t| t.name.as_str()).collect())
+            .collect();
+        assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["e"]]);
+        assert_eq!(names(&graph.order[0]), vec!["a", "b", "c", "d", "e"]);
+
+        let errors = graph.cycle_errors();
+        assert_eq!(
+            errors[0].to_string(),
+            "Dependency cycle between tasks: a (web#a), b (web#b), c (web#c)"
+        );
+    }
+
+    #[test]
+    fn test_dependency_outside_the_report_is_not_unresolved() {
+        let known = vec![repository("web", &[("login", "3", &["1"]), ("schema", "1", &[])])];
+        let shown = vec![repository("web", &[("login", "3", &["1"])])];
+        let graph = task_graph(&shown, &known);
+        assert!(graph.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Time totals per repository, tag and day
+//!
+//! Adds up the time each entry logged (see [`crate::parser::duration`]).
+//! Entries without a repository count under `Unknown`, as when grouping;
+//! an entry counts once towards each of its tags and its project.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::models::{JournalEntry, TimeLogged, TimeRow, TimeSummary};
+
+/// Repository name for entries without one
+const UNKNOWN_REPOSITORY: &str = "Unknown";
+
+/// Time logged by `entries`, `None` when none of them logged any
+pub fn time_summary(entries: &[JournalEntry]) -> Option<TimeSummary> {
+    let logged: Vec<(&JournalEntry, TimeLogged)> = entries
+        .iter()
+        .filter_map(|entry| entry.time_logged.map(|time| (entry, time)))
+        .collect();
+    let total = TimeLogged::total(logged.iter().map(|(_, time)| *time))?;
+
+    let mut by_repository: BTreeMap<&str, TimeLogged> = BTreeMap::new();
+    let mut by_tag: BTreeMap<&str, TimeLogged> = BTreeMap::new();
+    let mut by_day: BTreeMap<String, TimeLogged> = BTreeMap::new();
+
+    for (entry, time) in &logged {
+        let repository = entry.repository.as_deref().unwrap_or(UNKNOWN_REPOSITORY);
+        *by_repository.entry(repository).or_default() += *time;
+
+        let tags: BTreeSet<&str> = entry.tags.iter().map(String::as_str).chain(entry.project.as_deref()).collect();
+        for tag in tags {
+            *by_tag.entry(tag).or_default() += *time;
+        }
+
+        *by_day.entry(entry.date.to_string()).or_default() += *time;
+    }
+
+    Some(TimeSummary {
+        total,
+        by_repository: most_time_first(by_repository),
+        by_tag: most_time_first(by_tag),
+        by_day: by_day.into_iter().map(|(name, time)| TimeRow { name, time }).collect(),
+    })
+}
+
+/// Rows sorted by time, ties by name
+fn most_time_first(totals: BTreeMap<&str, TimeLogged>) -> Vec<TimeRow> {
+    let mut rows: Vec<TimeRow> = totals
+        .into_iter()
+        .map(|(name, time)| TimeRow { name: name.to_string(), time })
+        .collect();
+    // Stable, so equal times stay in name order
+    rows.sort_by_key(|row| std::cmp::Reverse(row.time.minutes));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn entry(date: &str, repository: Option<&str>, tags: &[&str], time: Option<TimeLogged>) -> JournalEntry {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", date)), date);
+        entry.repository = repository.map(str::to_string);
+        entry.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        entry.time_logged = time;
+        entry
+    }
+
+    fn rows(rows: &[TimeRow]) -> Vec<(&str, u32)> {
+        rows.iter().map(|row| (row.name.as_str(), row.time.minutes)).collect()
+    }
+
+    #[test]
+    fn test_no_time_logged() {
+        let entries = vec![entry("2025-11-13", Some("api"), &[], None)];
+        assert_eq!(time_summary(&entries), None);
+    }
+
+    #[test]
+    fn test_totals_per_repository_tag_and_day() {
+        let mut tagged = entry("2025-11-14", Some("api"), &["backend", "infra"], Some(TimeLogged::new(60, true)));
+        tagged.project = Some("infra".to_string());
+        let entries = vec![
+            entry("2025-11-13", Some("api"), &["backend"], Some(TimeLogged::new(90, false))),
+            tagged,
+            entry("2025-11-14", None, &[], Some(TimeLogged::new(200, false))),
+            entry("2025-11-15", Some("web"), &["frontend"], None),
+        ];
+
+        let summary = time_summary(&entries).unwrap();
+
+        assert_eq!(summary.total, TimeLogged::new(350, true));
+        assert_eq!(rows(&summary.by_repository), vec![("Unknown", 200), ("api", 150)]);
+        assert!(summary.by_repository[1].time.approximate);
+        // The project is one of the tags, so it counts once
+        assert_eq!(rows(&summary.by_tag), vec![("backend", 150), ("infra", 60)]);
+        assert_eq!(rows(&summary.by_day), vec![("2025-11-13", 90), ("2025-11-14", 260)]);
+    }
+}
@@ -1,14 +1,18 @@
 //! Command-line interface definitions
 
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
 use std::path::PathBuf;
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Weekday};
 
 #[derive(Parser, Debug)]
 #[command(name = "jrnrvw")]
 #[command(about = "Journal Review Tool - Analyze task journal files", long_about = None)]
 #[command(version)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Root directory to search (default: current directory)
     #[arg(value_name = "PATH")]
     pub path: Option<PathBuf>,
@@ -46,14 +50,23 @@ pub struct Cli {
     #[arg(long, value_name = "DATE", value_parser = parse_date)]
     pub to: Option<NaiveDate>,
 
-    /// All entries since date (inclusive)
-    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    /// All entries since date (inclusive; yyyy-mm-dd, or 7d / 2w ago)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_or_relative)]
     pub since: Option<NaiveDate>,
 
+    /// All entries up to date (inclusive; yyyy-mm-dd, or 7d / 2w ago)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_or_relative, conflicts_with = "before")]
+    pub until: Option<NaiveDate>,
+
     /// All entries before date (exclusive)
     #[arg(long, value_name = "DATE", value_parser = parse_date)]
     pub before: Option<NaiveDate>,
 
+    /// Leave out journals whose filename date does not parse, which date
+    /// ranges otherwise keep
+    #[arg(long)]
+    pub strict_dates: bool,
+
     // Filtering
     /// Filter by repository name (regex)
     #[arg(long, value_name = "PATTERN")]
@@ -63,12 +76,20 @@ pub struct Cli {
     #[arg(long, value_name = "PATTERN")]
     pub task: Option<String>,
 
+    /// Filter by project tag
+    #[arg(long, value_name = "TAG")]
+    pub project: Option<String>,
+
+    /// Filter by author (case-insensitive)
+    #[arg(long, value_name = "NAME")]
+    pub author: Option<String>,
+
     /// Custom filename pattern
     #[arg(long, value_name = "PATTERN")]
     pub pattern: Option<String>,
 
     // Grouping and sorting
-    /// Group by: repo, task, date, week, month
+    /// Group by: repo, project, tag, author, task, date, week, month
     #[arg(long, value_enum, default_value = "repo")]
     pub group_by: GroupByArg,
 
@@ -80,14 +101,20 @@ pub struct Cli {
     #[arg(long)]
     pub reverse: bool,
 
+    /// First day of the week for --group-by week, e.g. monday or sun
+    /// (default: `output.week_start` from the config, else monday)
+    #[arg(long, value_name = "DAY", value_parser = parse_weekday)]
+    pub week_start: Option<Weekday>,
+
     // Output
     /// Output file (default: stdout)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
-    /// Output format: text, markdown, json, html, csv
-    #[arg(short = 'f', long, value_enum, default_value = "text")]
-    pub format: FormatArg,
+    /// Output format: text, markdown, json, html, csv, dot
+    /// (default: `general.default_format` from the config, else text)
+    #[arg(short = 'f', long, value_enum)]
+    pub format: Option<FormatArg>,
 
     /// Disable colored output
     #[arg(long)]
@@ -101,6 +128,36 @@ pub struct Cli {
     #[arg(short, long, conflicts_with = "verbose")]
     pub quiet: bool,
 
+    /// Re-parse every journal instead of reusing the index cache (the cache
+    /// is still rebuilt)
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Count every task name separately instead of folding duplicates and
+    /// near-duplicates into one task
+    #[arg(long)]
+    pub no_dedup: bool,
+
+    /// Record the last commit (hash, author, date) touching each journal
+    #[arg(long)]
+    pub git_metadata: bool,
+
+    /// Read journals excluded by `.jrnrvwignore` files or the configured
+    /// `exclude` patterns
+    #[arg(long)]
+    pub no_ignore: bool,
+
+    /// Exit non-zero after writing the report if any unfinished task is past
+    /// its due date, for cron jobs
+    #[arg(long)]
+    pub fail_on_overdue: bool,
+
+    /// CI mode: print `file:line: severity: message` diagnostics for the
+    /// config's `[ci] rules` instead of the report, without colour; exits 0
+    /// when clean, 1 on violations, 2 on any other error
+    #[arg(long, conflicts_with = "summarize")]
+    pub ci: bool,
+
     // Display options
     /// Show only summary statistics
     #[arg(long)]
@@ -122,28 +179,287 @@ pub struct Cli {
     #[arg(long)]
     pub stats: bool,
 
+    /// Cut entry bodies in markdown output to N characters (0 for no limit)
+    #[arg(long, value_name = "N", default_value = "200")]
+    pub max_entry_chars: usize,
+
+    /// Show where each task and activity came from, and which lines were skipped
+    #[cfg(feature = "explain")]
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Print how long each phase took (count, total, p95) to stderr
+    #[cfg(feature = "profile")]
+    #[arg(long)]
+    pub profile: bool,
+
+    /// Also write the timings as a Chrome trace (chrome://tracing, Perfetto)
+    #[cfg(feature = "profile")]
+    #[arg(long, value_name = "FILE", requires = "profile")]
+    pub profile_trace: Option<PathBuf>,
+
     // AI Summarization
     /// Generate AI-powered summary of journal entries
     #[arg(long)]
     pub summarize: bool,
 
-    /// LLM to use for summarization: claude, codex
-    #[arg(long, value_enum, default_value = "claude", requires = "summarize")]
-    pub llm: LlmArg,
+    /// LLM to use for summarization: claude, codex, ollama
+    /// (default: `llm.backend` from the config, else claude)
+    #[arg(long, value_enum, requires = "summarize")]
+    pub llm: Option<LlmArg>,
 
     /// Save AI summary to file
     #[arg(long, value_name = "FILE", requires = "summarize")]
     pub summary_output: Option<PathBuf>,
 
+    /// Ask the LLM again instead of reusing a cached summary of the same
+    /// journals (the cached summary is replaced)
+    #[arg(long, requires = "summarize")]
+    pub no_llm_cache: bool,
+
+    /// Print what was masked (`[redact]` in the config) before the prompt
+    /// was sent, with the placeholder each value became
+    #[arg(long, requires = "summarize")]
+    pub show_redactions: bool,
+
     // Config
     /// Load configuration from file
     #[arg(long, value_name = "FILE")]
     pub config: Option<PathBuf>,
+
+    /// Layer the config file's `[profile.NAME]` over its top-level settings
+    /// (default: its `default_profile`)
+    #[arg(long, value_name = "NAME")]
+    pub config_profile: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Serve the report over HTTP, re-running the analysis periodically
+    ///
+    /// Filtering, grouping, and config options go before `serve`.
+    Serve(ServeArgs),
+
+    /// Write the report's tasks (and optionally entries) as CSV files
+    ///
+    /// Filtering, grouping, and config options go before `export`.
+    Export(ExportArgs),
+
+    /// Manage the index cache of parsed journals
+    Cache(CacheArgs),
+
+    /// Search every journal for words or a phrase, best matches first
+    ///
+    /// Entries holding the query as a phrase come first, then those holding
+    /// all of its words, closest together first. Other filtering and config
+    /// options go before `search`; `--format json` prints the results as
+    /// JSON.
+    Search(SearchArgs),
+
+    /// Post a day's digest to a configured Slack or webhook channel
+    ///
+    /// Filtering and config options go before `notify`.
+    Notify(NotifyArgs),
+
+    /// Compare the journals with a committed baseline, failing when
+    /// journal hygiene regresses
+    ///
+    /// Rules and their severities come from the config's `[check]`
+    /// section. Filtering and config options go before `check`; `--format
+    /// json` prints the results as JSON.
+    Check(CheckArgs),
+
+    /// Inspect the LLM backend used by `--summarize`
+    Llm(LlmArgs),
+
+    /// Inspect the configuration file
+    Config(ConfigArgs),
+
+    /// Print a shell completion script
+    ///
+    /// Profile and channel names from the config file are offered as
+    /// completions; regenerate the script after adding one.
+    Completions(CompletionsArgs),
+
+    /// Print the man page (roff) to standard output
+    Man,
+}
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Root directory to search (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Address to listen on
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+    pub bind: SocketAddr,
+
+    /// Seconds between automatic refreshes (0 to refresh only via /api/refresh)
+    #[arg(long, value_name = "SECS", default_value_t = 300)]
+    pub refresh_interval: u64,
+
+    /// Require `Authorization: Bearer <TOKEN>` on every request
+    #[arg(long, value_name = "TOKEN", env = "JRNRVW_SERVE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Root directory to search (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Write one row per task to FILE
+    #[arg(long, value_name = "FILE")]
+    pub tasks_csv: PathBuf,
+
+    /// Also write one row per journal entry, next to the tasks file as
+    /// `<name>-entries.csv`
+    #[arg(long)]
+    pub include_entries: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct NotifyArgs {
+    /// Root directory to search (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Channel from the config file's `[notify.channels]` to post to
+    #[arg(long, value_name = "NAME")]
+    pub channel_config: String,
+
+    /// Day to summarize (yyyy-mm-dd; default: yesterday)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub date: Option<NaiveDate>,
+
+    /// Print the payloads instead of posting them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Words or phrase to look for (case-insensitive)
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+
+    /// Root directory to search (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only search this repository
+    #[arg(long, value_name = "NAME")]
+    pub repo: Option<String>,
+
+    /// Only search entries since date (inclusive; yyyy-mm-dd, or 7d / 2w ago)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_or_relative)]
+    pub since: Option<NaiveDate>,
+
+    /// Only search entries with this tag or project (case-insensitive)
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Also match words with a typo or two, ranked after exact matches
+    #[arg(long)]
+    pub fuzzy: bool,
+
+    /// Lines of context around each match
+    #[arg(short = 'C', long, value_name = "N", default_value_t = 1)]
+    pub context: usize,
+
+    /// Most results to show (0 for all)
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Root directory to search (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Baseline file to compare with
+    #[arg(long, value_name = "FILE")]
+    pub baseline: PathBuf,
+
+    /// Write the journals' current state to the baseline file instead of
+    /// checking them
+    #[arg(long)]
+    pub update_baseline: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub action: CacheCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Delete every cached index
+    Clear {
+        /// Delete the cached LLM summaries instead
+        #[arg(long)]
+        llm: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct LlmArgs {
+    #[command(subcommand)]
+    pub action: LlmCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LlmCommand {
+    /// Check the backend is reachable and has the configured model
+    Check {
+        /// Backend to check instead of the configured one
+        #[arg(long, value_enum)]
+        backend: Option<LlmArg>,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the effective configuration, with the profile layered in
+    Show {
+        /// Profile to layer in (default: --config-profile, else the file's
+        /// `default_profile`)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to complete for
+    #[arg(value_enum)]
+    pub shell: ShellArg,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ShellArg {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum GroupByArg {
     Repo,
+    Project,
+    Tag,
+    Author,
     Task,
     Date,
     Week,
@@ -164,15 +480,31 @@ pub enum FormatArg {
     Json,
     Html,
     Csv,
+    Dot,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum LlmArg {
     Claude,
     Codex,
+    Ollama,
 }
 
 fn parse_date(s: &str) -> Result<NaiveDate, String> {
     NaiveDate::parse_from_str(s, "%Y-%m-%d")
         .map_err(|e| format!("Invalid date format '{}': {}. Expected yyyy-mm-dd", s, e))
 }
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    s.parse().map_err(|_| format!("Invalid day '{}'. Expected a weekday such as monday or sun", s))
+}
+
+fn parse_date_or_relative(s: &str) -> Result<NaiveDate, String> {
+    let today = chrono::Local::now().date_naive();
+    if let Some(date) = crate::analyzer::parse_relative_date(s, today) {
+        return Ok(date);
+    }
+    parse_date(s).map_err(|_| {
+        format!("Invalid date '{}'. Expected yyyy-mm-dd or a relative form like 7d or 2w", s)
+    })
+}
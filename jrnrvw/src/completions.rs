@@ -0,0 +1,130 @@
+//! Shell completion scripts and the man page, generated from the clap
+//! definitions in [`crate::cli`]
+
+use crate::cli::{Cli, ShellArg};
+use crate::Result;
+use clap::builder::PossibleValuesParser;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+/// Names offered for arguments whose values come from the config file
+///
+/// Scripts are static, so these are the names defined when the script was
+/// generated; regenerate it after adding a profile or channel. The
+/// PowerShell script completes flags and subcommands only.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicValues {
+    /// `[profile.NAME]` tables, for `--config-profile` and `config show --profile`
+    pub profiles: Vec<String>,
+
+    /// `[notify.channels.NAME]` tables, for `notify --channel-config`
+    pub channels: Vec<String>,
+}
+
+/// The clap command, with the dynamic values as each argument's choices
+pub fn command(values: &DynamicValues) -> clap::Command {
+    let mut cmd = Cli::command();
+    if !values.profiles.is_empty() {
+        let profiles = PossibleValuesParser::new(values.profiles.clone());
+        cmd = cmd
+            .mut_arg("config_profile", |arg| arg.value_parser(profiles.clone()))
+            .mut_subcommand("config", |config| {
+                config.mut_subcommand("show", |show| {
+                    show.mut_arg("profile", |arg| arg.value_parser(profiles))
+                })
+            });
+    }
+    if !values.channels.is_empty() {
+        let channels = PossibleValuesParser::new(values.channels.clone());
+        cmd = cmd.mut_subcommand("notify", |notify| {
+            notify.mut_arg("channel_config", |arg| arg.value_parser(channels))
+        });
+    }
+    cmd
+}
+
+/// Write the completion script for `shell`
+pub fn write_completions(shell: ShellArg, values: &DynamicValues, out: &mut dyn Write) {
+    let shell = match shell {
+        ShellArg::Bash => Shell::Bash,
+        ShellArg::Zsh => Shell::Zsh,
+        ShellArg::Fish => Shell::Fish,
+        ShellArg::Powershell => Shell::PowerShell,
+    };
+    let mut cmd = command(values);
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
+
+/// Write the roff man page
+pub fn write_man_page(out: &mut dyn Write) -> Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(shell: ShellArg, values: &DynamicValues) -> String {
+        let mut out = Vec::new();
+        write_completions(shell, values, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_command_is_valid() {
+        command(&DynamicValues::default()).debug_assert();
+        command(&DynamicValues {
+            profiles: vec!["work".to_string()],
+            channels: vec!["team".to_string()],
+        })
+        .debug_assert();
+    }
+
+    #[test]
+    fn test_bash_lists_subcommands() {
+        let bash = script(ShellArg::Bash, &DynamicValues::default());
+        // The first non-empty word list is the top level's flags and subcommands
+        let top: Vec<&str> = bash
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("opts=\""))
+            .find(|opts| opts.len() > 1)
+            .unwrap()
+            .trim_end_matches('"')
+            .split_whitespace()
+            .collect();
+        for word in ["serve", "export", "cache", "search", "notify", "check", "llm", "config", "completions", "man", "--config-profile"] {
+            assert!(top.contains(&word), "missing {}", word);
+        }
+    }
+
+    #[test]
+    fn test_dynamic_values() {
+        let values = DynamicValues {
+            profiles: vec!["work".to_string(), "personal".to_string()],
+            channels: vec!["team-standup".to_string()],
+        };
+        // clap's PowerShell script completes names but not values
+        for shell in [ShellArg::Bash, ShellArg::Zsh, ShellArg::Fish] {
+            let script = script(shell, &values);
+            assert!(script.contains("personal"), "{:?} has no profiles", shell);
+            assert!(script.contains("team-standup"), "{:?} has no channels", shell);
+        }
+
+        let bash = script(ShellArg::Bash, &DynamicValues::default());
+        assert!(!bash.contains("personal"));
+        assert!(script(ShellArg::Powershell, &values).contains("Register-ArgumentCompleter"));
+    }
+
+    #[test]
+    fn test_man_page() {
+        let mut out = Vec::new();
+        write_man_page(&mut out).unwrap();
+        let man = String::from_utf8(out).unwrap();
+        assert!(man.contains(".TH jrnrvw 1"));
+        assert!(man.contains("completions"));
+        assert!(man.contains("\\-\\-config\\-profile"));
+    }
+}
@@ -1,12 +1,55 @@
 //! Configuration file support
 
+use chrono::Weekday;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use crate::analyzer::alerts::DEFAULT_STALE_AFTER_DAYS;
 use crate::error::{JrnrvwError, Result};
+use crate::llm::redact::{Builtin, RedactPattern};
+use crate::llm::LlmProvider;
+use crate::models::{CiRule, OutputFormat, RuleSeverity};
+use crate::output::notify::ChannelKind;
+
+/// Table holding the named profiles, `[profile.<name>]`
+const PROFILE_TABLE: &str = "profile";
+
+/// Top-level key naming the profile used when none is asked for
+const DEFAULT_PROFILE_KEY: &str = "default_profile";
 
 /// Main configuration structure
+///
+/// A config file may define named profiles, each a partial config layered
+/// over the top-level values: tables merge key by key, anything else
+/// (including lists) is replaced.
+///
+/// ```toml
+/// default_profile = "work"
+///
+/// [general]
+/// default_format = "markdown"
+///
+/// [profile.work.general]
+/// default_path = "~/work/journals"
+///
+/// [profile.personal.llm]
+/// backend = "ollama"
+/// model = "llama3.1"
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
+    /// File the configuration came from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+
+    /// Profile layered over the top-level values, if any
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+
+    /// Profiles the file defines, by name
+    #[serde(skip)]
+    pub profiles: Vec<String>,
+
     #[serde(default)]
     pub general: GeneralConfig,
 
@@ -18,59 +61,250 @@ pub struct Config {
 
     #[serde(default)]
     pub output: OutputConfig,
+
+    #[serde(default)]
+    pub tagging: TaggingConfig,
+
+    #[serde(default)]
+    pub signals: SignalsConfig,
+
+    #[serde(default)]
+    pub references: ReferencesConfig,
+
+    #[serde(default)]
+    pub dedup: DedupConfig,
+
+    #[serde(default)]
+    pub tasks: TasksConfig,
+
+    #[serde(default)]
+    pub notify: NotifyConfig,
+
+    #[serde(default)]
+    pub llm: LlmConfig,
+
+    #[serde(default)]
+    pub redact: RedactConfig,
+
+    #[serde(default)]
+    pub authors: AuthorsConfig,
+
+    #[serde(default)]
+    pub check: CheckConfig,
+
+    #[serde(default)]
+    pub ci: CiConfig,
 }
 
 impl Config {
-    /// Load configuration from a file
+    /// Load configuration from a file, with its `default_profile` if it
+    /// names one
     pub fn load_from_file(path: &Path) -> Result<Self> {
+        Self::load_profile(path, None)
+    }
+
+    /// Load configuration from a file with `profile` (else the file's
+    /// `default_profile`) layered over the top-level values
+    ///
+    /// Fails if the profile is not defined in the file.
+    pub fn load_profile(path: &Path, profile: Option<&str>) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .map_err(|e| JrnrvwError::ConfigError(
                 format!("Failed to read config file: {}", e)
             ))?;
 
-        toml::from_str(&content)
+        let mut table: toml::Table = toml::from_str(&content)
             .map_err(|e| JrnrvwError::ConfigError(
                 format!("Failed to parse config file: {}", e)
-            ))
+            ))?;
+        let profiles = match table.get(PROFILE_TABLE) {
+            Some(toml::Value::Table(profiles)) => profiles.keys().cloned().collect(),
+            _ => Vec::new(),
+        };
+        let active_profile = apply_profile(&mut table, profile)?;
+
+        let mut config: Self = table.try_into()
+            .map_err(|e| JrnrvwError::ConfigError(match active_profile {
+                Some(ref name) => format!("Failed to parse config file with profile '{}': {}", name, e),
+                None => format!("Failed to parse config file: {}", e),
+            }))?;
+        config.source = Some(path.to_path_buf());
+        config.active_profile = active_profile;
+        config.profiles = profiles;
+
+        config.general.validate()?;
+        config.tagging.validate()?;
+        config.signals.validate()?;
+        config.references.validate()?;
+        config.dedup.validate()?;
+        config.notify.validate()?;
+        config.llm.validate()?;
+        config.redact.validate()?;
+        config.authors.validate()?;
+        Ok(config)
     }
 
     /// Try to load default config (from ~/.jrnrvw.toml or ./.jrnrvw.toml)
     pub fn load_default() -> Result<Option<Self>> {
-        // Try project-level config first
-        if let Ok(config) = Self::load_from_file(Path::new(".jrnrvw.toml")) {
-            return Ok(Some(config));
-        }
+        Self::load_default_profile(None)
+    }
 
-        // Try user-level config
+    /// Try to load default config with `profile` layered over it
+    ///
+    /// A file that can't be loaded is skipped, unless a profile was asked
+    /// for: then its errors are reported, and so is finding no file at all.
+    pub fn load_default_profile(profile: Option<&str>) -> Result<Option<Self>> {
+        // Project-level config first, then user-level
+        let mut paths = vec![PathBuf::from(".jrnrvw.toml")];
         if let Some(home) = std::env::var_os("HOME") {
-            let home_path = PathBuf::from(home).join(".jrnrvw.toml");
-            if let Ok(config) = Self::load_from_file(&home_path) {
-                return Ok(Some(config));
+            paths.push(PathBuf::from(home).join(".jrnrvw.toml"));
+        }
+
+        for path in &paths {
+            match Self::load_profile(path, profile) {
+                Ok(config) => return Ok(Some(config)),
+                Err(e) if profile.is_some() && path.exists() => return Err(e),
+                Err(_) => {}
             }
         }
 
-        Ok(None)
+        match profile {
+            Some(name) => Err(JrnrvwError::ConfigError(format!(
+                "no config file to take profile '{}' from (looked for ./.jrnrvw.toml and ~/.jrnrvw.toml)",
+                name
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// The effective configuration as TOML, for `jrnrvw config show`
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| JrnrvwError::ConfigError(format!("Failed to write configuration: {}", e)))
     }
 
     /// Get default configuration
     pub fn default() -> Self {
         Self {
+            source: None,
+            active_profile: None,
+            profiles: Vec::new(),
             general: GeneralConfig::default(),
             discovery: DiscoveryConfig::default(),
             parsing: ParsingConfig::default(),
             output: OutputConfig::default(),
+            tagging: TaggingConfig::default(),
+            signals: SignalsConfig::default(),
+            references: ReferencesConfig::default(),
+            dedup: DedupConfig::default(),
+            tasks: TasksConfig::default(),
+            notify: NotifyConfig::default(),
+            llm: LlmConfig::default(),
+            redact: RedactConfig::default(),
+            authors: AuthorsConfig::default(),
+            check: CheckConfig::default(),
+            ci: CiConfig::default(),
+        }
+    }
+}
+
+/// Take the profile tables out of a config file and layer the chosen one
+/// over the rest, returning its name
+fn apply_profile(table: &mut toml::Table, requested: Option<&str>) -> Result<Option<String>> {
+    let profiles = match table.remove(PROFILE_TABLE) {
+        None => toml::Table::new(),
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(JrnrvwError::ConfigError(
+                "profile must hold named tables, as in [profile.work]".to_string(),
+            ))
+        }
+    };
+    let default = match table.remove(DEFAULT_PROFILE_KEY) {
+        None => None,
+        Some(toml::Value::String(name)) => Some(name),
+        Some(_) => {
+            return Err(JrnrvwError::ConfigError(
+                "default_profile must be the name of a profile".to_string(),
+            ))
+        }
+    };
+
+    let Some(name) = requested.map(str::to_string).or(default) else {
+        return Ok(None);
+    };
+    match profiles.get(&name) {
+        Some(toml::Value::Table(overrides)) => {
+            merge_tables(table, overrides.clone());
+            Ok(Some(name))
+        }
+        Some(_) => Err(JrnrvwError::ConfigError(format!(
+            "profile.{} must be a table of settings",
+            name
+        ))),
+        None => {
+            let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            Err(JrnrvwError::ConfigError(if known.is_empty() {
+                format!("no profile '{}': the config file defines no profiles", name)
+            } else {
+                format!("no profile '{}' (defined: {})", name, known.join(", "))
+            }))
+        }
+    }
+}
+
+/// Merge `overrides` into `base`: tables key by key, other values replaced
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(value)) => merge_tables(inner, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
 }
 
 /// General configuration
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct GeneralConfig {
+    /// Journal root when no path is given on the command line; `~/` is
+    /// the home directory
     pub default_path: String,
+
+    /// Report format when `--format` is not given
     pub default_format: String,
+
+    /// Colour the text report on a terminal; `--no-color` turns it off
     pub colored_output: bool,
 }
 
+impl GeneralConfig {
+    /// Check the default format is one jrnrvw can write
+    pub fn validate(&self) -> Result<()> {
+        self.format().map(|_| ())
+    }
+
+    /// `default_path` with `~/` expanded
+    pub fn root(&self) -> PathBuf {
+        match (self.default_path.strip_prefix("~/"), std::env::var_os("HOME")) {
+            (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+            _ => PathBuf::from(&self.default_path),
+        }
+    }
+
+    /// `default_format` as an output format
+    pub fn format(&self) -> Result<OutputFormat> {
+        self.default_format.parse().map_err(|_| {
+            JrnrvwError::ConfigError(format!(
+                "general.default_format must be text, markdown, json, html, csv or dot, not '{}'",
+                self.default_format
+            ))
+        })
+    }
+}
+
 impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
@@ -83,9 +317,24 @@ impl Default for GeneralConfig {
 
 /// Discovery configuration
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct DiscoveryConfig {
     pub exclude_dirs: Vec<String>,
     pub case_sensitive: bool,
+
+    /// Also read org-mode journals (`YYYY.MM.DD - JRN - name.org`)
+    pub include_org: bool,
+
+    /// Gitignore-style patterns, relative to the root, of paths discovery
+    /// skips along with those in `.jrnrvwignore` files (`--no-ignore`
+    /// disregards both)
+    pub exclude: Vec<String>,
+
+    /// Follow symbolic links, skipping cycles and reading each file once
+    pub follow_symlinks: bool,
+
+    /// Record the last commit touching each journal (`--git-metadata`)
+    pub git_metadata: bool,
 }
 
 impl Default for DiscoveryConfig {
@@ -99,12 +348,17 @@ impl Default for DiscoveryConfig {
                 "build".to_string(),
             ],
             case_sensitive: false,
+            include_org: false,
+            exclude: Vec::new(),
+            follow_symlinks: false,
+            git_metadata: false,
         }
     }
 }
 
 /// Parsing configuration
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ParsingConfig {
     pub extract_fields: Vec<String>,
 }
@@ -125,11 +379,15 @@ impl Default for ParsingConfig {
 
 /// Output configuration
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct OutputConfig {
     pub default_group_by: String,
     pub default_sort_by: String,
     pub include_stats: bool,
     pub date_format: String,
+
+    /// First day of the week when grouping by week
+    pub week_start: Weekday,
 }
 
 impl Default for OutputConfig {
@@ -139,7 +397,593 @@ impl Default for OutputConfig {
             default_sort_by: "date".to_string(),
             include_stats: true,
             date_format: "%Y-%m-%d".to_string(),
+            week_start: Weekday::Mon,
+        }
+    }
+}
+
+/// Project tagging rules
+///
+/// ```toml
+/// [[tagging.remote]]
+/// pattern = 'github\.com/acme/infra-'
+/// tag = "infra"
+///
+/// [[tagging.path]]
+/// pattern = "~/work/clients/**"
+/// tag = "clients"
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TaggingConfig {
+    /// Glob rules matched against journal paths, first match wins
+    #[serde(default)]
+    pub path: Vec<TagRule>,
+
+    /// Regex rules matched against repository remote URLs, first match wins
+    #[serde(default)]
+    pub remote: Vec<TagRule>,
+}
+
+impl TaggingConfig {
+    /// Check that every rule compiles and none is shadowed by a catch-all
+    pub fn validate(&self) -> Result<()> {
+        crate::analyzer::ProjectTagger::new(self).map(|_| ())
+    }
+}
+
+/// A single pattern -> project tag rule
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TagRule {
+    pub pattern: String,
+    pub tag: String,
+}
+
+/// Thresholds for the trend and anomaly pass
+///
+/// ```toml
+/// [signals]
+/// baseline_weeks = 4
+/// drop_threshold = 0.5
+/// zscore_threshold = 3.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SignalsConfig {
+    /// Run the pass at all
+    pub enabled: bool,
+
+    /// Weeks averaged as the baseline for volume drops
+    pub baseline_weeks: usize,
+
+    /// Fractional drop versus the baseline that raises a warning
+    pub drop_threshold: f64,
+
+    /// Fractional drop versus the baseline that raises a critical signal
+    pub critical_drop_threshold: f64,
+
+    /// Minimum baseline entries per week before drops are reported
+    pub min_baseline_entries: f64,
+
+    /// Consecutive weekly declines in completion rate that raise a signal
+    pub trend_weeks: usize,
+
+    /// Z-score above which a day's entry length is flagged
+    pub zscore_threshold: f64,
+
+    /// Minimum active days before z-scores are computed
+    pub min_samples: usize,
+}
+
+impl Default for SignalsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            baseline_weeks: 4,
+            drop_threshold: 0.5,
+            critical_drop_threshold: 0.8,
+            min_baseline_entries: 2.0,
+            trend_weeks: 3,
+            zscore_threshold: 3.0,
+            min_samples: 7,
+        }
+    }
+}
+
+impl SignalsConfig {
+    /// Check that the thresholds make sense together
+    pub fn validate(&self) -> Result<()> {
+        if self.baseline_weeks == 0 || self.trend_weeks == 0 {
+            return Err(JrnrvwError::ConfigError(
+                "signals: baseline_weeks and trend_weeks must be at least 1".to_string(),
+            ));
+        }
+        for (name, value) in [
+            ("drop_threshold", self.drop_threshold),
+            ("critical_drop_threshold", self.critical_drop_threshold),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(JrnrvwError::ConfigError(format!(
+                    "signals: {} must be between 0 and 1, got {}",
+                    name, value
+                )));
+            }
+        }
+        if self.critical_drop_threshold < self.drop_threshold {
+            return Err(JrnrvwError::ConfigError(
+                "signals: critical_drop_threshold must not be below drop_threshold".to_string(),
+            ));
+        }
+        if self.zscore_threshold <= 0.0 {
+            return Err(JrnrvwError::ConfigError(
+                "signals: zscore_threshold must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Cross-repository reference detection
+///
+/// `pattern` is a regex with a `repo` capture group and an optional `date`
+/// group (`yyyy-mm-dd`):
+///
+/// ```toml
+/// [references]
+/// pattern = 'see (?P<repo>[\w-]+) (?P<date>\d{4}-\d{2}-\d{2})'
+/// detect_mentions = false
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReferencesConfig {
+    /// Run the pass at all
+    pub enabled: bool,
+
+    /// Explicit reference syntax
+    pub pattern: String,
+
+    /// Also link bare mentions of other repositories' names
+    pub detect_mentions: bool,
+}
+
+impl Default for ReferencesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pattern: r"ref:(?P<repo>[\w.-]*[\w-])(?:#(?P<date>\d{4}-\d{2}-\d{2}))?".to_string(),
+            detect_mentions: true,
+        }
+    }
+}
+
+impl ReferencesConfig {
+    /// Check that the pattern compiles and captures a repository
+    pub fn validate(&self) -> Result<()> {
+        let pattern = regex::Regex::new(&self.pattern).map_err(|e| {
+            JrnrvwError::ConfigError(format!("references: invalid pattern: {}", e))
+        })?;
+        if !pattern.capture_names().any(|name| name == Some("repo")) {
+            return Err(JrnrvwError::ConfigError(
+                "references: pattern needs a `repo` capture group".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Folding duplicate and near-duplicate tasks together
+///
+/// Names always match with case and punctuation ignored; `threshold` is
+/// how similar (0 to 1) two names have to be beyond that:
+///
+/// ```toml
+/// [dedup]
+/// method = "tokens"     # shared words, or "levenshtein" for edit distance
+/// threshold = 0.8
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// Run the pass at all
+    pub enabled: bool,
+
+    /// How near-duplicate names are compared
+    pub method: Similarity,
+
+    /// Similarity from which two names count as the same task
+    pub threshold: f64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            method: Similarity::Levenshtein,
+            threshold: 0.9,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Check the threshold is a similarity
+    pub fn validate(&self) -> Result<()> {
+        if !(self.threshold > 0.0 && self.threshold <= 1.0) {
+            return Err(JrnrvwError::ConfigError(format!(
+                "dedup: threshold must be above 0 and at most 1, got {}",
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// When tasks are reported as needing attention
+///
+/// ```toml
+/// [tasks]
+/// stale_after_days = 30   # days without an entry before an unfinished task is stale
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TasksConfig {
+    /// Days without an entry, up to the newest one, after which an
+    /// unfinished task is listed as stale
+    pub stale_after_days: u32,
+}
+
+impl Default for TasksConfig {
+    fn default() -> Self {
+        Self { stale_after_days: DEFAULT_STALE_AFTER_DAYS }
+    }
+}
+
+/// Rules `jrnrvw check` holds the journals to
+///
+/// Each rule has a limit, `max`, and a severity: `fail` makes the check
+/// exit non-zero, `warn` only reports it, and `off` skips the rule.
+///
+/// ```toml
+/// # Open tasks may grow by at most 5 over the baseline
+/// [check.open_task_growth]
+/// max = 5
+/// severity = "fail"
+///
+/// # No open task older than 30 days
+/// [check.task_age]
+/// max = 30
+/// severity = "warn"
+///
+/// # No more than 3 workdays in a row without an entry since the baseline
+/// [check.missing_entries]
+/// max = 3
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CheckConfig {
+    #[serde(default = "CheckRuleConfig::open_task_growth")]
+    pub open_task_growth: CheckRuleConfig,
+
+    #[serde(default = "CheckRuleConfig::task_age")]
+    pub task_age: CheckRuleConfig,
+
+    #[serde(default = "CheckRuleConfig::missing_entries")]
+    pub missing_entries: CheckRuleConfig,
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            open_task_growth: CheckRuleConfig::open_task_growth(),
+            task_age: CheckRuleConfig::task_age(),
+            missing_entries: CheckRuleConfig::missing_entries(),
+        }
+    }
+}
+
+/// Limit and severity of one `jrnrvw check` rule
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct CheckRuleConfig {
+    /// Most the rule allows: open tasks added, days open, or workdays
+    /// without an entry
+    pub max: u32,
+
+    #[serde(default)]
+    pub severity: RuleSeverity,
+}
+
+impl CheckRuleConfig {
+    fn open_task_growth() -> Self {
+        Self { max: 5, severity: RuleSeverity::Fail }
+    }
+
+    fn task_age() -> Self {
+        Self { max: 30, severity: RuleSeverity::Warn }
+    }
+
+    fn missing_entries() -> Self {
+        Self { max: 3, severity: RuleSeverity::Fail }
+    }
+}
+
+/// Rules `--ci` enforces
+///
+/// ```toml
+/// [ci]
+/// rules = ["overdue", "undated"]   # default: all of parse, overdue, undated
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CiConfig {
+    #[serde(default = "default_ci_rules")]
+    pub rules: Vec<CiRule>,
+}
+
+fn default_ci_rules() -> Vec<CiRule> {
+    CiRule::ALL.to_vec()
+}
+
+impl Default for CiConfig {
+    fn default() -> Self {
+        Self { rules: default_ci_rules() }
+    }
+}
+
+/// How similar two task names are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Similarity {
+    /// One minus the edit distance over the longer name's length
+    #[default]
+    Levenshtein,
+
+    /// Words in both names over words in either
+    Tokens,
+}
+
+/// Destinations for `jrnrvw notify`, by name
+///
+/// Webhook URLs are secrets, so they are never read from this file: each
+/// channel's URL comes from the environment variable named by `url_env`
+/// (default `JRNRVW_NOTIFY_<NAME>_URL`), or failing that from the system
+/// keyring entry for service `jrnrvw` and the channel's name.
+///
+/// ```toml
+/// [notify.channels.work]
+/// kind = "slack"
+/// url_env = "WORK_SLACK_WEBHOOK"
+///
+/// [notify.channels.ops]
+/// kind = "webhook"
+/// max_attempts = 5
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct NotifyConfig {
+    #[serde(default)]
+    pub channels: BTreeMap<String, ChannelConfig>,
+}
+
+impl NotifyConfig {
+    /// Check that no channel has its URL written into the file
+    pub fn validate(&self) -> Result<()> {
+        for (name, channel) in &self.channels {
+            if channel.url.is_some() {
+                return Err(JrnrvwError::ConfigError(format!(
+                    "notify.channels.{}: webhook URLs are secrets and can't be set in the config file; \
+                     set ${} or store it in the keyring",
+                    name,
+                    channel.url_env(name)
+                )));
+            }
+            if channel.max_attempts == 0 {
+                return Err(JrnrvwError::ConfigError(format!(
+                    "notify.channels.{}: max_attempts must be at least 1",
+                    name
+                )));
+            }
         }
+        Ok(())
+    }
+
+    /// Settings for the channel called `name`
+    pub fn channel(&self, name: &str) -> Result<&ChannelConfig> {
+        self.channels.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.channels.keys().map(String::as_str).collect();
+            JrnrvwError::ConfigError(if known.is_empty() {
+                format!("no notify channel '{}': no channels are configured", name)
+            } else {
+                format!("no notify channel '{}' (configured: {})", name, known.join(", "))
+            })
+        })
+    }
+}
+
+/// One `jrnrvw notify` destination
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ChannelConfig {
+    /// Slack incoming webhook or generic JSON webhook
+    pub kind: ChannelKind,
+
+    /// Environment variable holding the webhook URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_env: Option<String>,
+
+    /// Attempts per message before giving up
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Only read so it can be rejected
+    #[serde(default, skip_serializing)]
+    url: Option<String>,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+impl ChannelConfig {
+    /// Environment variable the URL of channel `name` is read from
+    pub fn url_env(&self, name: &str) -> String {
+        self.url_env.clone().unwrap_or_else(|| {
+            let name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                .collect();
+            format!("JRNRVW_NOTIFY_{}_URL", name)
+        })
+    }
+
+    /// Webhook URL of channel `name`, from the environment or the keyring
+    pub fn resolve_url(&self, name: &str) -> Result<String> {
+        let var = self.url_env(name);
+        if let Some(url) = std::env::var(&var).ok().filter(|url| !url.is_empty()) {
+            return Ok(url);
+        }
+
+        let not_found = || {
+            JrnrvwError::ConfigError(format!(
+                "no webhook URL for notify channel '{}': set ${} or store it in the keyring (service 'jrnrvw', user '{}')",
+                name, var, name
+            ))
+        };
+        match keyring::Entry::new("jrnrvw", name).and_then(|entry| entry.get_password()) {
+            Ok(url) if !url.is_empty() => Ok(url),
+            Ok(_) | Err(keyring::Error::NoEntry) => Err(not_found()),
+            Err(e) => Err(JrnrvwError::ConfigError(format!("{} ({})", not_found(), e))),
+        }
+    }
+}
+
+/// Backend writing `--summarize` reports
+///
+/// `--llm` on the command line overrides `backend`. `model` is passed to
+/// the CLIs as `--model` and is required for Ollama.
+///
+/// ```toml
+/// [llm]
+/// backend = "ollama"
+/// base_url = "http://localhost:11434"
+/// model = "llama3.1"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LlmConfig {
+    /// `claude`, `codex` or `ollama`
+    #[serde(default)]
+    pub backend: LlmProvider,
+
+    /// Ollama server (default `http://localhost:11434`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+
+    /// Model to ask for; the backend's default if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl LlmConfig {
+    /// Check the settings make sense for the chosen backend
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref url) = self.base_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(JrnrvwError::ConfigError(format!(
+                    "llm.base_url must be an http:// or https:// URL, not '{}'",
+                    url
+                )));
+            }
+        }
+        if self.backend == LlmProvider::Ollama && self.model.as_deref().is_none_or(str::is_empty) {
+            return Err(JrnrvwError::ConfigError(
+                "llm.model must name a model for the ollama backend (see `ollama list`)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Masking of sensitive text in `--summarize` prompts
+///
+/// Matches become placeholders such as `[PERSON_1]` in the prompt and are
+/// put back into the summary; see [`crate::llm::redact`]. `builtin` picks
+/// the detectors that need no patterns (default: all of email, phone and
+/// secret); `patterns` adds regexes, each masked under its `label`.
+///
+/// ```toml
+/// [redact]
+/// builtin = ["email", "secret"]
+///
+/// [[redact.patterns]]
+/// label = "PERSON"
+/// regex = '\b(?:Jane Doe|John Smith)\b'
+///
+/// [[redact.patterns]]
+/// label = "CLIENT"
+/// regex = '(?i)\bacme( corp)?\b'
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    /// Mask anything at all
+    pub enabled: bool,
+
+    pub builtin: Vec<Builtin>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<RedactPattern>,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            builtin: Builtin::ALL.to_vec(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl RedactConfig {
+    /// Check that every pattern compiles under a usable label
+    pub fn validate(&self) -> Result<()> {
+        crate::llm::Redactor::new(self).map(|_| ())
+    }
+}
+
+/// Who wrote each journal, for shared team journal trees
+///
+/// An author in the front matter wins, then the path component, then git
+/// blame; see [`crate::analyzer::authors`].
+///
+/// ```toml
+/// [authors]
+/// path_component = 1        # <root>/alice/2025.11.20 - JRN - x.md
+/// git_blame = true
+/// exclude_from_llm = ["bob"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AuthorsConfig {
+    /// Directory under the journal root naming the author, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_component: Option<usize>,
+
+    /// Fall back to whoever wrote most of the file's lines in git
+    pub git_blame: bool,
+
+    /// Authors whose entries count in the report but never reach an LLM
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude_from_llm: Vec<String>,
+}
+
+impl AuthorsConfig {
+    /// Check the path component is a real directory level
+    pub fn validate(&self) -> Result<()> {
+        if self.path_component == Some(0) {
+            return Err(JrnrvwError::ConfigError(
+                "authors.path_component counts from 1 (the first directory under the journal root)".to_string(),
+            ));
+        }
+        if self.exclude_from_llm.iter().any(|name| name.trim().is_empty()) {
+            return Err(JrnrvwError::ConfigError(
+                "authors.exclude_from_llm has an empty name".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -204,5 +1048,346 @@ mod tests {
         assert_eq!(config.default_sort_by, "date");
         assert!(config.include_stats);
         assert_eq!(config.date_format, "%Y-%m-%d");
+        assert_eq!(config.week_start, Weekday::Mon);
+
+        let config: OutputConfig = toml::from_str("week_start = \"sunday\"").unwrap();
+        assert_eq!(config.week_start, Weekday::Sun);
+    }
+
+    #[test]
+    fn test_load_tagging_rules() {
+        let temp_file = std::env::temp_dir().join("tagging_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[[tagging.remote]]\npattern = 'github\\.com/acme/infra-'\ntag = \"infra\"\n\n[[tagging.path]]\npattern = \"/work/**\"\ntag = \"work\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.tagging.remote.len(), 1);
+        assert_eq!(config.tagging.remote[0].tag, "infra");
+        assert_eq!(config.tagging.path[0].pattern, "/work/**");
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_signal_thresholds() {
+        let temp_file = std::env::temp_dir().join("signals_config.toml");
+        std::fs::write(&temp_file, "[signals]\nbaseline_weeks = 6\ndrop_threshold = 0.6\n").unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.signals.baseline_weeks, 6);
+        assert_eq!(config.signals.drop_threshold, 0.6);
+        assert_eq!(config.signals.trend_weeks, 3);
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_check_rules() {
+        let temp_file = std::env::temp_dir().join("check_config.toml");
+        std::fs::write(&temp_file, "[check.task_age]\nmax = 14\nseverity = \"fail\"\n\n[check.missing_entries]\nmax = 2\n").unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.check.task_age, CheckRuleConfig { max: 14, severity: RuleSeverity::Fail });
+        assert_eq!(config.check.missing_entries, CheckRuleConfig { max: 2, severity: RuleSeverity::Fail });
+        assert_eq!(config.check.open_task_growth, CheckConfig::default().open_task_growth);
+
+        std::fs::write(&temp_file, "[check.task_age]\nmax = 14\nseverity = \"error\"\n").unwrap();
+        assert!(Config::load_from_file(&temp_file).is_err());
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_ci_rules() {
+        let temp_file = std::env::temp_dir().join("ci_config.toml");
+        std::fs::write(&temp_file, "[ci]\nrules = [\"overdue\", \"undated\"]\n").unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.ci.rules, [CiRule::Overdue, CiRule::Undated]);
+        assert_eq!(Config::default().ci.rules, CiRule::ALL);
+
+        std::fs::write(&temp_file, "[ci]\nrules = [\"lint\"]\n").unwrap();
+        assert!(Config::load_from_file(&temp_file).is_err());
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_tasks_settings() {
+        let temp_file = std::env::temp_dir().join("tasks_config.toml");
+        std::fs::write(&temp_file, "[tasks]\nstale_after_days = 45\n").unwrap();
+        assert_eq!(Config::load_from_file(&temp_file).unwrap().tasks.stale_after_days, 45);
+
+        std::fs::write(&temp_file, "[general]\n").unwrap();
+        assert_eq!(Config::load_from_file(&temp_file).unwrap().tasks.stale_after_days, 30);
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_dedup_settings() {
+        let temp_file = std::env::temp_dir().join("dedup_config.toml");
+        std::fs::write(&temp_file, "[dedup]\nmethod = \"tokens\"\nthreshold = 0.75\n").unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert!(config.dedup.enabled);
+        assert_eq!(config.dedup.method, Similarity::Tokens);
+        assert_eq!(config.dedup.threshold, 0.75);
+
+        std::fs::write(&temp_file, "[dedup]\nthreshold = 1.5\n").unwrap();
+        assert!(Config::load_from_file(&temp_file).is_err());
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_signals_validation() {
+        assert!(SignalsConfig::default().validate().is_ok());
+
+        let config = SignalsConfig { drop_threshold: 1.5, ..SignalsConfig::default() };
+        assert!(config.validate().is_err());
+
+        let config = SignalsConfig {
+            drop_threshold: 0.9,
+            critical_drop_threshold: 0.8,
+            ..SignalsConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_references_validation() {
+        assert!(ReferencesConfig::default().validate().is_ok());
+
+        let config = ReferencesConfig { pattern: "ref:(\\w+)".to_string(), ..Default::default() };
+        assert!(config.validate().unwrap_err().to_string().contains("`repo` capture group"));
+
+        let config = ReferencesConfig { pattern: "ref:(?P<repo>".to_string(), ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_load_notify_channels() {
+        let temp_file = std::env::temp_dir().join("notify_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[notify.channels.work]\nkind = \"slack\"\n\n[notify.channels.ops-team]\nkind = \"webhook\"\nurl_env = \"OPS_HOOK\"\nmax_attempts = 5\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        let work = config.notify.channel("work").unwrap();
+        assert_eq!(work.kind, ChannelKind::Slack);
+        assert_eq!(work.max_attempts, 3);
+        assert_eq!(work.url_env("work"), "JRNRVW_NOTIFY_WORK_URL");
+        assert_eq!(config.notify.channel("ops-team").unwrap().url_env("ops-team"), "OPS_HOOK");
+        assert_eq!(ChannelConfig { url_env: None, ..work.clone() }.url_env("ops-team"), "JRNRVW_NOTIFY_OPS_TEAM_URL");
+
+        let err = config.notify.channel("home").unwrap_err().to_string();
+        assert!(err.contains("configured: ops-team, work"), "{}", err);
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_notify_url_in_config_is_rejected() {
+        let temp_file = std::env::temp_dir().join("notify_url_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[notify.channels.work]\nkind = \"slack\"\nurl = \"https://hooks.slack.com/services/T/B/X\"\n",
+        )
+        .unwrap();
+
+        let err = Config::load_from_file(&temp_file).unwrap_err().to_string();
+        assert!(err.contains("webhook URLs are secrets"), "{}", err);
+        assert!(!err.contains("hooks.slack.com"));
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_rejects_shadowing_catch_all() {
+        let temp_file = std::env::temp_dir().join("tagging_catch_all.toml");
+        std::fs::write(
+            &temp_file,
+            "[[tagging.path]]\npattern = \"**\"\ntag = \"misc\"\n\n[[tagging.path]]\npattern = \"/work/**\"\ntag = \"work\"\n",
+        )
+        .unwrap();
+
+        let err = Config::load_from_file(&temp_file).unwrap_err();
+        assert!(err.to_string().starts_with("Configuration error: path rule 1"));
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_load_llm_backend() {
+        let temp_file = std::env::temp_dir().join("llm_backend_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[llm]\nbackend = \"ollama\"\nbase_url = \"http://gpu-box:11434\"\nmodel = \"llama3.1\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.llm.backend, LlmProvider::Ollama);
+        assert_eq!(config.llm.base_url.as_deref(), Some("http://gpu-box:11434"));
+        assert_eq!(config.llm.model.as_deref(), Some("llama3.1"));
+
+        std::fs::remove_file(temp_file).ok();
+        assert_eq!(Config::default().llm.backend, LlmProvider::Claude);
+    }
+
+    #[test]
+    fn test_llm_validation() {
+        let ollama = LlmConfig {
+            backend: LlmProvider::Ollama,
+            base_url: None,
+            model: Some("llama3.1".to_string()),
+        };
+        assert!(ollama.validate().is_ok());
+
+        let no_model = LlmConfig { model: None, ..ollama.clone() };
+        assert!(no_model.validate().unwrap_err().to_string().contains("llm.model"));
+
+        let bad_url = LlmConfig { base_url: Some("localhost:11434".to_string()), ..ollama };
+        assert!(bad_url.validate().unwrap_err().to_string().contains("http://"));
+        assert!(LlmConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_load_authors() {
+        let temp_file = std::env::temp_dir().join("authors_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[authors]\npath_component = 2\ngit_blame = true\nexclude_from_llm = [\"bob\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.authors.path_component, Some(2));
+        assert!(config.authors.git_blame);
+        assert_eq!(config.authors.exclude_from_llm, vec!["bob"]);
+        std::fs::remove_file(temp_file).ok();
+
+        let zero = AuthorsConfig { path_component: Some(0), ..AuthorsConfig::default() };
+        assert!(zero.validate().unwrap_err().to_string().contains("counts from 1"));
+        assert!(!Config::default().authors.git_blame);
+    }
+
+    #[test]
+    fn test_load_redact() {
+        let temp_file = std::env::temp_dir().join("redact_config.toml");
+        std::fs::write(
+            &temp_file,
+            "[redact]\nbuiltin = [\"email\"]\n\n[[redact.patterns]]\nlabel = \"PERSON\"\nregex = 'Jane Doe'\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert!(config.redact.enabled);
+        assert_eq!(config.redact.builtin, vec![Builtin::Email]);
+        assert_eq!(config.redact.patterns[0].label, "PERSON");
+        std::fs::remove_file(temp_file).ok();
+
+        let bad = RedactConfig {
+            patterns: vec![RedactPattern { label: "PERSON".to_string(), regex: "(".to_string() }],
+            ..RedactConfig::default()
+        };
+        assert!(bad.validate().unwrap_err().to_string().contains("invalid pattern for PERSON"));
+        assert_eq!(Config::default().redact.builtin, Builtin::ALL.to_vec());
+    }
+
+    const PROFILES: &str = r#"
+default_profile = "work"
+
+[general]
+default_format = "markdown"
+
+[discovery]
+include_org = true
+
+[profile.work.general]
+default_path = "/work/journals"
+
+[profile.personal.general]
+default_format = "json"
+
+[profile.personal.llm]
+backend = "ollama"
+model = "llama3.1"
+"#;
+
+    fn write_profiles(name: &str) -> PathBuf {
+        let temp_file = std::env::temp_dir().join(name);
+        std::fs::write(&temp_file, PROFILES).unwrap();
+        temp_file
+    }
+
+    #[test]
+    fn test_profile_layers_over_top_level() {
+        let temp_file = write_profiles("profiles_layered.toml");
+
+        let personal = Config::load_profile(&temp_file, Some("personal")).unwrap();
+        assert_eq!(personal.active_profile.as_deref(), Some("personal"));
+        assert_eq!(personal.general.default_format, "json");
+        // Keys the profile leaves alone keep their top-level or default values
+        assert_eq!(personal.general.default_path, ".");
+        assert!(personal.general.colored_output);
+        assert!(personal.discovery.include_org);
+        assert_eq!(personal.llm.backend, LlmProvider::Ollama);
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_default_profile() {
+        let temp_file = write_profiles("profiles_default.toml");
+
+        let config = Config::load_from_file(&temp_file).unwrap();
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        assert_eq!(config.general.root(), PathBuf::from("/work/journals"));
+        assert_eq!(config.general.format().unwrap(), OutputFormat::Markdown);
+        assert_eq!(config.llm.backend, LlmProvider::Claude);
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let temp_file = write_profiles("profiles_unknown.toml");
+
+        let err = Config::load_profile(&temp_file, Some("home")).unwrap_err();
+        assert!(err.to_string().contains("no profile 'home' (defined: personal, work)"));
+
+        std::fs::write(&temp_file, "[general]\ndefault_format = \"text\"\n").unwrap();
+        let err = Config::load_profile(&temp_file, Some("home")).unwrap_err();
+        assert!(err.to_string().contains("defines no profiles"));
+        assert!(Config::load_profile(&temp_file, None).unwrap().active_profile.is_none());
+
+        std::fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_merge_tables_replaces_lists() {
+        let mut base: toml::Table = toml::from_str(
+            "[discovery]\nexclude_dirs = [\".git\", \"target\"]\ncase_sensitive = true\n",
+        )
+        .unwrap();
+        let overrides: toml::Table = toml::from_str("[discovery]\nexclude_dirs = [\"vendor\"]\n").unwrap();
+        merge_tables(&mut base, overrides);
+
+        let discovery = base["discovery"].as_table().unwrap();
+        assert_eq!(discovery["exclude_dirs"].as_array().unwrap().len(), 1);
+        assert_eq!(discovery["case_sensitive"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_invalid_default_format() {
+        let general = GeneralConfig { default_format: "pdf".to_string(), ..GeneralConfig::default() };
+        assert!(general.validate().unwrap_err().to_string().contains("general.default_format"));
     }
 }
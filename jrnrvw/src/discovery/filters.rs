@@ -6,7 +6,8 @@ use regex::Regex;
 
 /// Parser for extracting information from journal filenames
 ///
-/// Matches filenames in the format: `YYYY.MM.DD - JRN - description.md`
+/// Matches filenames in the format: `YYYY.MM.DD - JRN - description.md`, or
+/// `.org` for org-mode journals
 pub struct FilenameParser {
     /// Compiled regex pattern for matching journal filenames
     pattern: Regex,
@@ -31,9 +32,9 @@ impl FilenameParser {
     /// let parser = FilenameParser::new().unwrap();
     /// ```
     pub fn new() -> Result<Self> {
-        // Pattern: YYYY.MM.DD - JRN - description.md
+        // Pattern: YYYY.MM.DD - JRN - description.md (or .org)
         // Captures: (year)(month)(day)(description)
-        let pattern = Regex::new(r"^(\d{4})\.(\d{2})\.(\d{2})\s*-\s*JRN\s*-\s*(.+)\.md$")?;
+        let pattern = Regex::new(r"^(\d{4})\.(\d{2})\.(\d{2})\s*-\s*JRN\s*-\s*(.+)\.(?:md|org)$")?;
         Ok(Self { pattern })
     }
 
@@ -160,6 +161,7 @@ mod tests {
         assert!(parser.matches("2024.01.15 - JRN - Daily standup.md"));
         assert!(parser.matches("2023.12.31 - JRN - Year end review.md"));
         assert!(parser.matches("2024.01.01-JRN-New year planning.md"));
+        assert!(parser.matches("2024.01.15 - JRN - Daily standup.org"));
     }
 
     #[test]
@@ -4,22 +4,42 @@ pub mod scanner;
 pub mod filters;
 pub mod repo_detector;
 
-pub use scanner::JournalScanner;
+pub use scanner::{DiscoverySettings, JournalScanner, ScanResult, IGNORE_FILENAME};
 pub use filters::FilenameParser;
 pub use repo_detector::RepositoryDetector;
 
 use crate::{JournalEntry, Result};
+use chrono::{DateTime, Local, NaiveDate};
 use std::path::Path;
 
 /// Discover all journal files in the given directory tree
+///
+/// A journal whose filename date is not a real date (such as `2025.13.40`)
+/// is kept as an undated entry, dated by its modification time; it is
+/// skipped if the filesystem reports none.
 pub fn discover_journals(
     root: &Path,
     excludes: Vec<String>
 ) -> Result<Vec<JournalEntry>> {
-    let scanner = JournalScanner::new(root.to_path_buf())
-        .with_excludes(excludes);
+    discover_with(
+        &JournalScanner::new(root.to_path_buf()).with_excludes(excludes)
+    )
+}
+
+/// Discover the journal files `scanner` finds, as [`discover_journals`] does
+pub fn discover_with(scanner: &JournalScanner) -> Result<Vec<JournalEntry>> {
+    discover_reporting(scanner, &mut Vec::new())
+}
 
-    let paths = scanner.scan()?;
+/// Discover as [`discover_with`] does, adding what the scan had to skip,
+/// such as broken symlinks, to `warnings`
+pub fn discover_reporting(
+    scanner: &JournalScanner,
+    warnings: &mut Vec<String>
+) -> Result<Vec<JournalEntry>> {
+    let scan = scanner.scan_all()?;
+    warnings.extend(scan.warnings);
+    let paths = scan.files;
 
     let parser = FilenameParser::new()?;
     let mut entries = Vec::new();
@@ -27,18 +47,33 @@ pub fn discover_journals(
     for path in paths {
         if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
             if parser.matches(filename) {
-                if let Ok(date) = parser.parse_date(filename) {
-                    let mut entry = JournalEntry::new(path.clone(), date);
+                let mut entry = match parser.parse_date(filename) {
+                    Ok(date) => JournalEntry::new(path.clone(), date),
+                    Err(_) => match modified_date(&path) {
+                        Some(date) => {
+                            let mut entry = JournalEntry::new(path.clone(), date);
+                            entry.undated = true;
+                            entry
+                        }
+                        None => continue,
+                    },
+                };
 
-                    // Detect repository
-                    let repo = RepositoryDetector::detect(&path);
-                    entry.repository = Some(repo);
+                // Detect repository
+                let repo = RepositoryDetector::detect(&path);
+                entry.repository = Some(repo);
+                entry.remote = RepositoryDetector::detect_remote(&path);
 
-                    entries.push(entry);
-                }
+                entries.push(entry);
             }
         }
     }
 
     Ok(entries)
 }
+
+/// Local date the file was last modified
+fn modified_date(path: &Path) -> Option<NaiveDate> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(DateTime::<Local>::from(modified).date_naive())
+}
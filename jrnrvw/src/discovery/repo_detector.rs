@@ -64,6 +64,62 @@ impl RepositoryDetector {
         // Ultimate fallback
         "Unknown".to_string()
     }
+
+    /// Detect the remote URL of the repository containing `path`
+    ///
+    /// Reads `.git/config` of the nearest enclosing repository and returns
+    /// the `origin` URL, or the first remote listed if there is no `origin`.
+    /// Returns `None` outside a repository or when no remote is configured.
+    pub fn detect_remote(path: &Path) -> Option<String> {
+        let mut current = if path.is_file() {
+            path.parent()
+        } else {
+            Some(path)
+        };
+
+        while let Some(dir) = current {
+            let git_dir = dir.join(".git");
+
+            if git_dir.exists() {
+                let config = std::fs::read_to_string(git_dir.join("config")).ok()?;
+                return Self::parse_remote_url(&config);
+            }
+
+            current = dir.parent();
+        }
+
+        None
+    }
+
+    /// Pick the remote URL out of a git config file
+    fn parse_remote_url(config: &str) -> Option<String> {
+        let mut section = String::new();
+        let mut first = None;
+
+        for line in config.lines() {
+            let line = line.trim();
+
+            if line.starts_with('[') {
+                section = line.trim_matches(|c| c == '[' || c == ']').to_string();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if key.trim() != "url" || !section.starts_with("remote ") {
+                continue;
+            }
+
+            let url = value.trim().to_string();
+            if section == "remote \"origin\"" {
+                return Some(url);
+            }
+            first.get_or_insert(url);
+        }
+
+        first
+    }
 }
 
 #[cfg(test)]
@@ -141,4 +197,40 @@ mod tests {
         // Note: This behavior may vary by system
         assert!(!repo_name.is_empty());
     }
+
+    #[test]
+    fn test_detect_remote_prefers_origin() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("infra-dns");
+        let git_dir = repo_dir.join(".git");
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(
+            git_dir.join("config"),
+            "[core]\n\tbare = false\n[remote \"upstream\"]\n\turl = https://github.com/other/infra-dns.git\n[remote \"origin\"]\n\turl = git@github.com:acme/infra-dns.git\n",
+        )
+        .unwrap();
+
+        let journal_file = repo_dir.join("journal.md");
+        fs::write(&journal_file, "# Journal").unwrap();
+
+        assert_eq!(
+            RepositoryDetector::detect_remote(&journal_file),
+            Some("git@github.com:acme/infra-dns.git".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detect_remote_without_remote() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("local");
+        let git_dir = repo_dir.join(".git");
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(git_dir.join("config"), "[core]\n\tbare = false\n").unwrap();
+
+        assert_eq!(RepositoryDetector::detect_remote(&repo_dir), None);
+        assert_eq!(
+            RepositoryDetector::parse_remote_url("[remote \"fork\"]\nurl = https://example.com/fork.git\n"),
+            Some("https://example.com/fork.git".to_string())
+        );
+    }
 }
@@ -1,8 +1,46 @@
 //! File system scanner for discovering journal files
 
-use crate::error::Result;
-use std::path::PathBuf;
-use walkdir::WalkDir;
+use crate::error::{JrnrvwError, Result};
+use crate::parser::org::ORG_EXTENSION;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Gitignore-style file listing paths to leave out, read in every directory
+pub const IGNORE_FILENAME: &str = ".jrnrvwignore";
+
+/// What discovery collects under a root, beyond the built-in excludes
+#[derive(Debug, Clone, Default)]
+pub struct DiscoverySettings {
+    /// Read org-mode journals as well as Markdown ones
+    pub include_org: bool,
+
+    /// Gitignore-style patterns, relative to the root, of paths to skip
+    pub exclude: Vec<String>,
+
+    /// Disregard `exclude` and every `.jrnrvwignore` (`--no-ignore`)
+    pub no_ignore: bool,
+
+    /// Follow symbolic links, reading each file once however many reach it
+    pub follow_symlinks: bool,
+}
+
+impl DiscoverySettings {
+    /// Scanner for `root` with these settings
+    pub fn scanner(&self, root: &Path) -> JournalScanner {
+        let scanner = JournalScanner::new(root.to_path_buf())
+            .with_org(self.include_org)
+            .with_symlinks(self.follow_symlinks);
+        if self.no_ignore {
+            scanner.with_ignore_files(false)
+        } else {
+            scanner.with_exclude_patterns(self.exclude.clone())
+        }
+    }
+}
 
 /// Scanner for recursively finding journal files in a directory tree
 pub struct JournalScanner {
@@ -10,6 +48,23 @@ pub struct JournalScanner {
     root: PathBuf,
     /// Directories to exclude from scanning
     excludes: Vec<String>,
+    /// Also collect org-mode (`.org`) files
+    include_org: bool,
+    /// Gitignore-style patterns, relative to the root, of paths to skip
+    exclude_patterns: Vec<String>,
+    /// Honour `.jrnrvwignore` files
+    ignore_files: bool,
+    /// Follow symbolic links
+    follow_symlinks: bool,
+}
+
+/// Files a scan found, and the problems it stepped around
+#[derive(Debug, Default)]
+pub struct ScanResult {
+    /// Journal files, each once
+    pub files: Vec<PathBuf>,
+    /// Links that could not be followed, and the like
+    pub warnings: Vec<String>,
 }
 
 impl JournalScanner {
@@ -35,6 +90,10 @@ impl JournalScanner {
                 "target".to_string(),
                 "node_modules".to_string(),
             ],
+            include_org: false,
+            exclude_patterns: Vec::new(),
+            ignore_files: true,
+            follow_symlinks: false,
         }
     }
 
@@ -58,11 +117,68 @@ impl JournalScanner {
         self
     }
 
+    /// Also collect `.org` files when scanning
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use jrnrvw::discovery::JournalScanner;
+    ///
+    /// let scanner = JournalScanner::new(PathBuf::from(".")).with_org(true);
+    /// ```
+    pub fn with_org(mut self, include_org: bool) -> Self {
+        self.include_org = include_org;
+        self
+    }
+
+    /// Skip paths matching gitignore-style `patterns`, relative to the root
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use jrnrvw::discovery::JournalScanner;
+    ///
+    /// let scanner = JournalScanner::new(PathBuf::from("."))
+    ///     .with_exclude_patterns(vec!["archive/**".to_string()]);
+    /// ```
+    pub fn with_exclude_patterns(mut self, mut patterns: Vec<String>) -> Self {
+        self.exclude_patterns.append(&mut patterns);
+        self
+    }
+
+    /// Honour (the default) or disregard `.jrnrvwignore` files
+    ///
+    /// A `.jrnrvwignore` applies to its own directory and everything below
+    /// it, with gitignore syntax; one deeper in the tree overrides its
+    /// parents, so it can exclude what they re-include with `!` and the
+    /// other way round.
+    pub fn with_ignore_files(mut self, ignore_files: bool) -> Self {
+        self.ignore_files = ignore_files;
+        self
+    }
+
+    /// Follow symbolic links to files and directories (off by default)
+    ///
+    /// Each directory is entered once, by its canonical path, so a link
+    /// back up the tree ends there instead of looping, and a file reachable
+    /// through several links is collected once, under the first path found
+    /// in name order. Broken links become warnings from
+    /// [`JournalScanner::scan_all`].
+    pub fn with_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
     /// Scan the directory tree and return all found .md files
     ///
     /// This method recursively walks the directory tree starting from the root,
     /// skipping any directories in the excludes list, and collects all files
-    /// with the .md extension.
+    /// with the .md extension, plus .org files if [`JournalScanner::with_org`]
+    /// asked for them. Paths matching the exclude patterns or a
+    /// `.jrnrvwignore` are skipped during the walk, so excluded directories
+    /// are never read.
     ///
     /// # Returns
     ///
@@ -71,6 +187,7 @@ impl JournalScanner {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - An exclude pattern is not a valid glob
     /// - The root directory cannot be accessed
     /// - Permission errors occur during directory traversal
     ///
@@ -85,45 +202,137 @@ impl JournalScanner {
     /// println!("Found {} markdown files", files.len());
     /// ```
     pub fn scan(&self) -> Result<Vec<PathBuf>> {
-        let mut md_files = Vec::new();
+        Ok(self.scan_all()?.files)
+    }
+
+    /// Scan as [`JournalScanner::scan`] does, keeping the warnings too
+    pub fn scan_all(&self) -> Result<ScanResult> {
+        let mut result = ScanResult::default();
+
+        let mut walker = WalkBuilder::new(&self.root);
+        walker.standard_filters(false);
+        if self.ignore_files {
+            walker.add_custom_ignore_filename(IGNORE_FILENAME);
+        }
+        let excludes = self.excludes.clone();
+        let patterns = self.exclude_matcher()?;
+        if self.follow_symlinks {
+            // Name order, so the same path wins each run
+            walker.follow_links(true).sort_by_file_name(|a, b| a.cmp(b));
+            let visited = Mutex::new(HashSet::new());
+            if let Ok(root) = self.root.canonicalize() {
+                visited.lock().unwrap().insert(root);
+            }
+            walker.filter_entry(move |e| {
+                should_visit(e, &excludes, &patterns) && first_visit(e, &visited)
+            });
+        } else {
+            walker.filter_entry(move |e| should_visit(e, &excludes, &patterns));
+        }
 
-        for entry in WalkDir::new(&self.root)
-            .into_iter()
-            .filter_entry(|e| self.should_visit(e))
-        {
-            let entry = entry?;
+        let mut seen = HashSet::new();
+        for entry in walker.build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) if self.follow_symlinks && is_loop(&err) => continue,
+                Err(err) if self.follow_symlinks => match broken_link(&err) {
+                    Some(link) => {
+                        result
+                            .warnings
+                            .push(format!("broken symlink {} skipped", link.display()));
+                        continue;
+                    }
+                    None => return Err(err.into()),
+                },
+                Err(err) => return Err(err.into()),
+            };
             let path = entry.path();
 
-            // Only include files (not directories) with .md extension
-            if entry.file_type().is_file() {
+            // Only include files (not directories) with a journal extension
+            if entry.file_type().is_some_and(|t| t.is_file()) {
                 if let Some(extension) = path.extension() {
-                    if extension == "md" {
-                        md_files.push(path.to_path_buf());
+                    if extension == "md" || (self.include_org && extension == ORG_EXTENSION) {
+                        if self.follow_symlinks {
+                            let target = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+                            if !seen.insert(target) {
+                                continue;
+                            }
+                        }
+                        result.files.push(path.to_path_buf());
                     }
                 }
             }
         }
 
-        Ok(md_files)
+        Ok(result)
     }
 
-    /// Determine if a directory entry should be visited during traversal
-    ///
-    /// Returns false if the entry is a directory and matches any of the
-    /// excluded directory names.
-    fn should_visit(&self, entry: &walkdir::DirEntry) -> bool {
-        // Always visit files
-        if !entry.file_type().is_dir() {
-            return true;
+    /// Matcher for the exclude patterns, rooted at the scan root
+    fn exclude_matcher(&self) -> Result<Gitignore> {
+        let mut builder = GitignoreBuilder::new(&self.root);
+        for pattern in &self.exclude_patterns {
+            builder.add_line(None, pattern).map_err(|e| {
+                JrnrvwError::ConfigError(format!("invalid exclude pattern '{}': {}", pattern, e))
+            })?;
         }
+        Ok(builder.build()?)
+    }
+}
 
-        // Check if this directory should be excluded
-        if let Some(dir_name) = entry.file_name().to_str() {
-            !self.excludes.iter().any(|exclude| dir_name == exclude)
-        } else {
-            // If we can't get the directory name, skip it
-            false
+/// Whether `entry` is a directory not yet entered under any path
+fn first_visit(entry: &ignore::DirEntry, visited: &Mutex<HashSet<PathBuf>>) -> bool {
+    if !entry.file_type().is_some_and(|t| t.is_dir()) {
+        return true;
+    }
+    match entry.path().canonicalize() {
+        Ok(target) => visited.lock().unwrap().insert(target),
+        Err(_) => true,
+    }
+}
+
+/// Whether a walk error is a link back to a directory being walked
+fn is_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithPath { err, .. } => is_loop(err),
+        _ => false,
+    }
+}
+
+/// The symlink a walk error failed to follow, if it points nowhere
+fn broken_link(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithDepth { err, .. } => broken_link(err),
+        ignore::Error::WithPath { path, err } => {
+            let missing = err.io_error().is_some_and(|e| e.kind() == ErrorKind::NotFound);
+            let is_link = path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink());
+            (missing && is_link).then_some(path.as_path())
         }
+        _ => None,
+    }
+}
+
+/// Determine if a directory entry should be visited during traversal
+///
+/// Returns false if the entry matches an exclude pattern, or is a directory
+/// matching any of the excluded directory names.
+fn should_visit(entry: &ignore::DirEntry, excludes: &[String], patterns: &Gitignore) -> bool {
+    let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+    if entry.depth() > 0 && patterns.matched(entry.path(), is_dir).is_ignore() {
+        return false;
+    }
+
+    // Always visit files
+    if !is_dir {
+        return true;
+    }
+
+    // Check if this directory should be excluded
+    if let Some(dir_name) = entry.file_name().to_str() {
+        !excludes.iter().any(|exclude| dir_name == exclude)
+    } else {
+        // If we can't get the directory name, skip it
+        false
     }
 }
 
@@ -165,6 +374,22 @@ mod tests {
         assert!(files.iter().all(|f| f.extension().unwrap() == "md"));
     }
 
+    #[test]
+    fn test_scan_finds_org_files_when_asked() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("notes.md"), "# Notes").unwrap();
+        fs::write(temp_path.join("work.org"), "* TODO Work").unwrap();
+
+        let scanner = JournalScanner::new(temp_path.to_path_buf());
+        assert_eq!(scanner.scan().unwrap().len(), 1);
+
+        let files = scanner.with_org(true).scan().unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|f| f.ends_with("work.org")));
+    }
+
     #[test]
     fn test_scan_excludes_directories() {
         let temp_dir = TempDir::new().unwrap();
@@ -185,4 +410,153 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert!(files[0].ends_with("root.md"));
     }
+
+    fn names(scanner: &JournalScanner) -> Vec<String> {
+        let mut names: Vec<String> = scanner
+            .scan()
+            .unwrap()
+            .iter()
+            .map(|f| f.strip_prefix(&scanner.root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_scan_skips_exclude_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir_all(temp_path.join("archive/2023")).unwrap();
+        fs::create_dir_all(temp_path.join("repo/drafts")).unwrap();
+        fs::write(temp_path.join("root.md"), "# Root").unwrap();
+        fs::write(temp_path.join("archive/2023/old.md"), "# Old").unwrap();
+        fs::write(temp_path.join("repo/keep.md"), "# Keep").unwrap();
+        fs::write(temp_path.join("repo/drafts/wip.md"), "# WIP").unwrap();
+
+        let scanner = JournalScanner::new(temp_path.to_path_buf())
+            .with_exclude_patterns(vec!["archive/**".to_string(), "**/drafts/**".to_string()]);
+        assert_eq!(names(&scanner), vec!["repo/keep.md", "root.md"]);
+
+        let bad = JournalScanner::new(temp_path.to_path_buf())
+            .with_exclude_patterns(vec!["{archive".to_string()]);
+        assert!(matches!(bad.scan(), Err(JrnrvwError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_nested_ignore_file_overrides_parent() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir_all(temp_path.join("repo")).unwrap();
+        fs::write(temp_path.join(IGNORE_FILENAME), "*.md\n!important.md\n").unwrap();
+        fs::write(temp_path.join("repo").join(IGNORE_FILENAME), "important.md\n!notes.md\n").unwrap();
+        for name in ["important.md", "notes.md", "repo/important.md", "repo/notes.md", "repo/other.md"] {
+            fs::write(temp_path.join(name), "# Journal").unwrap();
+        }
+
+        // The root file re-includes important.md everywhere, but the
+        // repository's own file excludes it there and re-includes notes.md
+        let scanner = JournalScanner::new(temp_path.to_path_buf());
+        assert_eq!(names(&scanner), vec!["important.md", "repo/notes.md"]);
+    }
+
+    #[test]
+    fn test_scan_without_ignore_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::create_dir_all(temp_path.join("scratch")).unwrap();
+        fs::write(temp_path.join(IGNORE_FILENAME), "scratch/\n").unwrap();
+        fs::write(temp_path.join("root.md"), "# Root").unwrap();
+        fs::write(temp_path.join("scratch/wip.md"), "# WIP").unwrap();
+
+        let scanner = JournalScanner::new(temp_path.to_path_buf());
+        assert_eq!(names(&scanner), vec!["root.md"]);
+
+        let scanner = scanner.with_ignore_files(false);
+        assert_eq!(names(&scanner), vec!["root.md", "scratch/wip.md"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_skipped_unless_followed() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path().join("vault");
+        let root = temp_dir.path().join("journals");
+        fs::create_dir_all(&vault).unwrap();
+        fs::create_dir_all(&root).unwrap();
+        fs::write(vault.join("shared.md"), "# Shared").unwrap();
+        fs::write(root.join("own.md"), "# Own").unwrap();
+        symlink(&vault, root.join("vault")).unwrap();
+
+        let scanner = JournalScanner::new(root.clone());
+        assert_eq!(names(&scanner), vec!["own.md"]);
+
+        let scanner = scanner.with_symlinks(true);
+        assert_eq!(names(&scanner), vec!["own.md", "vault/shared.md"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycles_and_duplicates() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("notes/deep")).unwrap();
+        fs::create_dir_all(root.join("vault")).unwrap();
+        fs::write(root.join("notes/deep/entry.md"), "# Entry").unwrap();
+        fs::write(root.join("vault/shared.md"), "# Shared").unwrap();
+        // A link back up the tree, two ways into the vault, and a second
+        // name for one file
+        symlink(root, root.join("notes/deep/up")).unwrap();
+        symlink(root.join("vault"), root.join("notes/a-vault")).unwrap();
+        symlink(root.join("vault"), root.join("notes/b-vault")).unwrap();
+        symlink(root.join("notes/deep/entry.md"), root.join("alias.md")).unwrap();
+
+        let scanner = JournalScanner::new(root.to_path_buf()).with_symlinks(true);
+        let result = scanner.scan_all().unwrap();
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(names(&scanner), vec!["alias.md", "notes/a-vault/shared.md"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_symlink_is_a_warning() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("root.md"), "# Root").unwrap();
+        symlink(root.join("missing"), root.join("gone")).unwrap();
+
+        let result = JournalScanner::new(root.to_path_buf())
+            .with_symlinks(true)
+            .scan_all()
+            .unwrap();
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].starts_with("broken symlink"), "{}", result.warnings[0]);
+        assert!(result.warnings[0].contains("gone"), "{}", result.warnings[0]);
+    }
+
+    #[test]
+    fn test_settings_no_ignore_drops_patterns() {
+        let settings = DiscoverySettings {
+            include_org: true,
+            exclude: vec!["archive/**".to_string()],
+            no_ignore: false,
+            follow_symlinks: false,
+        };
+        let scanner = settings.scanner(Path::new("."));
+        assert!(scanner.include_org && scanner.ignore_files);
+        assert_eq!(scanner.exclude_patterns, vec!["archive/**"]);
+
+        let scanner = DiscoverySettings { no_ignore: true, ..settings }.scanner(Path::new("."));
+        assert!(!scanner.ignore_files);
+        assert!(scanner.exclude_patterns.is_empty());
+    }
 }
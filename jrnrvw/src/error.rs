@@ -11,9 +11,10 @@ pub enum JrnrvwError {
     #[error("Invalid date format in filename: {0}")]
     InvalidDateFormat(String),
 
-    #[error("Failed to parse journal file {path}: {reason}")]
+    #[error("Failed to parse journal file {path}:{line}: {reason}")]
     ParseError {
         path: PathBuf,
+        line: usize,
         reason: String,
     },
 
@@ -31,6 +32,33 @@ pub enum JrnrvwError {
 
     #[error("Directory traversal error: {0}")]
     WalkDir(#[from] walkdir::Error),
+
+    #[error("Directory traversal error: {0}")]
+    Walk(#[from] ignore::Error),
+
+    #[error("Server error: {0}")]
+    Server(String),
+
+    #[error("Notification error: {0}")]
+    Notify(String),
+
+    #[error("LLM error: {message}")]
+    Llm { message: String, partial: String },
+
+    #[error("{0} journal check(s) failed")]
+    CheckFailed(usize),
+
+    #[error("{0} overdue task(s)")]
+    Overdue(usize),
+
+    #[error("{0} CI violation(s)")]
+    CiViolations(usize),
+
+    #[error("Dependency cycle between tasks: {}", .0.join(", "))]
+    DependencyCycle(Vec<String>),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
 }
 
 /// Result type alias for jrnrvw
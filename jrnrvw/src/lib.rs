@@ -3,6 +3,7 @@
 //! A command-line tool for finding and analyzing task journal files.
 
 pub mod cli;
+pub mod completions;
 pub mod config;
 pub mod error;
 pub mod models;
@@ -11,6 +12,8 @@ pub mod parser;
 pub mod analyzer;
 pub mod output;
 pub mod llm;
+pub mod serve;
+pub mod profile;
 
 // Re-export commonly used types
 pub use error::{JrnrvwError, Result};
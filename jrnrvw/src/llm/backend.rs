@@ -0,0 +1,51 @@
+//! Common interface of the LLM backends
+
+use crate::error::{JrnrvwError, Result};
+use crate::llm::stream::SummaryStream;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// An LLM that can turn a prompt into text
+///
+/// Summaries only go through this trait, so they don't depend on which
+/// backend is configured.
+pub trait LlmBackend: Send + Sync {
+    /// Short name for messages, e.g. `ollama`
+    fn name(&self) -> &str;
+
+    /// Where requests go: a URL or the CLI binary
+    fn endpoint(&self) -> String;
+
+    /// Model requests ask for, if one is configured
+    fn model(&self) -> Option<&str>;
+
+    /// Stream the response to `prompt` as it is generated
+    fn generate_streaming(&self, prompt: &str) -> Result<SummaryStream>;
+
+    /// Wait for the whole response to `prompt`
+    fn generate(&self, prompt: &str) -> Result<String> {
+        self.generate_streaming(prompt)?.collect_text()
+    }
+
+    /// Models the backend can use, or `None` if it has no way to list them
+    ///
+    /// Also confirms the backend is reachable.
+    fn list_models(&self) -> Result<Option<Vec<String>>>;
+}
+
+/// Run `<cli> --version` to make sure the CLI starts
+pub(crate) fn run_version(path: PathBuf, name: &str) -> Result<()> {
+    let output = Command::new(path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| JrnrvwError::ConfigError(format!("Failed to run {} CLI: {}", name, e)))?;
+    if !output.status.success() {
+        return Err(JrnrvwError::ConfigError(format!(
+            "{} CLI failed: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
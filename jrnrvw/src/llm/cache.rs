@@ -0,0 +1,313 @@
+//! On-disk cache of LLM responses
+//!
+//! Responses are stored one file per prompt, keyed on a hash of the backend,
+//! model, prompt template version and the prompt itself, which includes the
+//! journal content. Rerunning a summary over unchanged journals reads the
+//! stored response instead of asking the LLM again. Only responses that
+//! arrive complete are stored.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::analyzer::cache::{clear_cache_dir, content_hash};
+use crate::analyzer::CacheSettings;
+use crate::error::Result;
+use crate::llm::backend::LlmBackend;
+use crate::llm::prompts::PROMPT_VERSION;
+use crate::llm::stream::SummaryStream;
+
+/// `llm` under the index cache directory; `None` if there is none
+pub fn default_dir() -> Option<PathBuf> {
+    CacheSettings::default_dir().map(|dir| dir.join("llm"))
+}
+
+/// Hits and misses so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+
+    /// Rough count of the prompt and response tokens not sent again
+    pub saved_tokens: usize,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LLM cache: {} hit(s), {} miss(es), ~{} tokens saved",
+            self.hits, self.misses, self.saved_tokens
+        )
+    }
+}
+
+/// A stored response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedResponse {
+    backend: String,
+    model: Option<String>,
+    prompt_version: u32,
+    response: String,
+}
+
+/// Backend answering from the cache when it can, and asking `inner` otherwise
+///
+/// With `reuse` off in the settings every prompt goes to `inner`, and the
+/// fresh responses replace the stored ones.
+pub struct CachedBackend {
+    inner: Box<dyn LlmBackend>,
+    settings: Option<CacheSettings>,
+    stats: Arc<Mutex<CacheStats>>,
+}
+
+impl CachedBackend {
+    /// Cache `inner`'s responses as `settings` say; `None` turns caching off
+    pub fn new(inner: Box<dyn LlmBackend>, settings: Option<CacheSettings>) -> Self {
+        Self {
+            inner,
+            settings,
+            stats: Arc::default(),
+        }
+    }
+
+    /// Whether responses are stored at all
+    pub fn is_enabled(&self) -> bool {
+        self.settings.is_some()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Cache file for `prompt` sent to the inner backend
+    fn entry_path(&self, dir: &Path, prompt: &str) -> PathBuf {
+        let key = format!(
+            "{}\0{}\0{}\0{}",
+            self.inner.name(),
+            self.inner.model().unwrap_or(""),
+            PROMPT_VERSION,
+            prompt
+        );
+        dir.join(format!("summary-{}.json", content_hash(key.as_bytes())))
+    }
+
+    /// Stored response to `prompt`, if it was stored for this backend and model
+    fn lookup(&self, path: &Path) -> Option<String> {
+        let cached: CachedResponse = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+        let matches = cached.backend == self.inner.name()
+            && cached.model.as_deref() == self.inner.model()
+            && cached.prompt_version == PROMPT_VERSION;
+        matches.then_some(cached.response)
+    }
+}
+
+impl LlmBackend for CachedBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn endpoint(&self) -> String {
+        self.inner.endpoint()
+    }
+
+    fn model(&self) -> Option<&str> {
+        self.inner.model()
+    }
+
+    fn generate_streaming(&self, prompt: &str) -> Result<SummaryStream> {
+        let Some(ref settings) = self.settings else {
+            return self.inner.generate_streaming(prompt);
+        };
+        let path = self.entry_path(&settings.dir, prompt);
+
+        if settings.reuse {
+            if let Some(response) = self.lookup(&path) {
+                let mut stats = self.stats.lock().unwrap();
+                stats.hits += 1;
+                stats.saved_tokens += estimate_tokens(prompt) + estimate_tokens(&response);
+                return Ok(SummaryStream::from_text("cache", response));
+            }
+        }
+        self.stats.lock().unwrap().misses += 1;
+
+        let entry = CachedResponse {
+            backend: self.inner.name().to_string(),
+            model: self.inner.model().map(str::to_string),
+            prompt_version: PROMPT_VERSION,
+            response: String::new(),
+        };
+        let dir = settings.dir.clone();
+        let stream = self.inner.generate_streaming(prompt)?;
+        Ok(stream.on_complete(move |response| {
+            // The cache only saves work; failing to write it is not an error
+            let _ = store(&dir, &path, &CachedResponse { response: response.to_string(), ..entry });
+        }))
+    }
+
+    fn list_models(&self) -> Result<Option<Vec<String>>> {
+        self.inner.list_models()
+    }
+}
+
+/// Write `entry` to `path`, replacing any previous one atomically
+fn store(dir: &Path, path: &Path, entry: &CachedResponse) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let partial = path.with_extension("json.tmp");
+    fs::write(&partial, serde_json::to_string(entry)?)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// About four characters per token for English text
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Delete every cached response in `dir`, returning how many were removed
+pub fn clear(dir: &Path) -> Result<usize> {
+    clear_cache_dir(dir, "summary-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::JrnrvwError;
+    use crate::llm::stream::{StreamDecoder, StreamEvent};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Answers every prompt with `reply`, failing if `reply` is `boom`
+    struct Counting {
+        model: Option<String>,
+        reply: String,
+        calls: Arc<AtomicUsize>,
+    }
+
+    struct Whole;
+
+    impl StreamDecoder for Whole {
+        fn decode_line(&mut self, line: &str) -> StreamEvent {
+            match line {
+                "boom" => StreamEvent::Error("backend failed".to_string()),
+                text => StreamEvent::Text(text.to_string()),
+            }
+        }
+    }
+
+    impl LlmBackend for Counting {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn endpoint(&self) -> String {
+            "test".to_string()
+        }
+
+        fn model(&self) -> Option<&str> {
+            self.model.as_deref()
+        }
+
+        fn generate_streaming(&self, _prompt: &str) -> Result<SummaryStream> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let body = std::io::Cursor::new(self.reply.clone().into_bytes());
+            Ok(SummaryStream::from_reader("Counting", body, Box::new(Whole)))
+        }
+
+        fn list_models(&self) -> Result<Option<Vec<String>>> {
+            Ok(None)
+        }
+    }
+
+    fn cached(dir: &TempDir, model: Option<&str>, reply: &str, reuse: bool) -> (CachedBackend, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = Counting {
+            model: model.map(str::to_string),
+            reply: reply.to_string(),
+            calls: Arc::clone(&calls),
+        };
+        let settings = CacheSettings { dir: dir.path().join("llm"), reuse };
+        (CachedBackend::new(Box::new(inner), Some(settings)), calls)
+    }
+
+    #[test]
+    fn test_second_request_is_answered_from_cache() {
+        let dir = TempDir::new().unwrap();
+        let (backend, calls) = cached(&dir, None, "Summary", true);
+
+        assert_eq!(backend.generate("prompt one").unwrap(), "Summary");
+        assert_eq!(backend.generate("prompt one").unwrap(), "Summary");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let stats = backend.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        // "prompt one" and "Summary" come to 3 + 2 tokens
+        assert_eq!(stats.saved_tokens, 5);
+
+        // A different prompt (new journal content) is a miss
+        backend.generate("prompt two").unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_model_is_part_of_the_key() {
+        let dir = TempDir::new().unwrap();
+        let (first, _) = cached(&dir, Some("llama3"), "From llama3", true);
+        first.generate("prompt").unwrap();
+
+        let (second, calls) = cached(&dir, Some("mistral"), "From mistral", true);
+        assert_eq!(second.generate("prompt").unwrap(), "From mistral");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_without_reuse_the_response_is_refreshed() {
+        let dir = TempDir::new().unwrap();
+        let (old, _) = cached(&dir, None, "Old", true);
+        old.generate("prompt").unwrap();
+
+        let (fresh, calls) = cached(&dir, None, "Fresh", false);
+        assert_eq!(fresh.generate("prompt").unwrap(), "Fresh");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let (reader, calls) = cached(&dir, None, "Unused", true);
+        assert_eq!(reader.generate("prompt").unwrap(), "Fresh");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_failed_responses_are_not_stored() {
+        let dir = TempDir::new().unwrap();
+        let (failing, _) = cached(&dir, None, "Half\nboom", true);
+        assert!(matches!(failing.generate("prompt"), Err(JrnrvwError::Llm { .. })));
+
+        assert_eq!(clear(&dir.path().join("llm")).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_clear_removes_only_responses() {
+        let dir = TempDir::new().unwrap();
+        let (backend, _) = cached(&dir, None, "Summary", true);
+        backend.generate("prompt one").unwrap();
+        backend.generate("prompt two").unwrap();
+        fs::write(dir.path().join("llm").join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(clear(&dir.path().join("llm")).unwrap(), 2);
+        assert!(dir.path().join("llm").join("notes.txt").exists());
+        assert_eq!(clear(&dir.path().join("missing")).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_disabled_cache_passes_through() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = Counting { model: None, reply: "Summary".to_string(), calls: Arc::clone(&calls) };
+        let backend = CachedBackend::new(Box::new(inner), None);
+
+        backend.generate("prompt").unwrap();
+        backend.generate("prompt").unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(backend.stats(), CacheStats::default());
+    }
+}
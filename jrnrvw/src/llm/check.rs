@@ -0,0 +1,140 @@
+//! `jrnrvw llm check`: make sure the configured backend answers before a report needs it
+
+use crate::error::{JrnrvwError, Result};
+use crate::llm::backend::LlmBackend;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Outcome of a successful backend check
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckReport {
+    pub backend: String,
+    pub endpoint: String,
+    /// Configured model, if any
+    pub model: Option<String>,
+    /// Models the backend offers, if it can list them
+    pub models: Option<Vec<String>>,
+    /// Time to reach the backend and list its models
+    pub latency: Duration,
+}
+
+/// Reach `backend` and confirm its configured model is available
+///
+/// Fails if the backend can't be reached, or lists its models and the
+/// configured one is not among them.
+pub fn check(backend: &dyn LlmBackend) -> Result<CheckReport> {
+    let start = Instant::now();
+    let models = backend.list_models()?;
+    let latency = start.elapsed();
+
+    let model = backend.model().map(str::to_string);
+    if let (Some(model), Some(models)) = (&model, &models) {
+        if !models.iter().any(|name| same_model(name, model)) {
+            let available = if models.is_empty() {
+                "none".to_string()
+            } else {
+                models.join(", ")
+            };
+            return Err(JrnrvwError::ConfigError(format!(
+                "model '{}' is not available on {} at {} (available: {})",
+                model,
+                backend.name(),
+                backend.endpoint(),
+                available
+            )));
+        }
+    }
+
+    Ok(CheckReport {
+        backend: backend.name().to_string(),
+        endpoint: backend.endpoint(),
+        model,
+        models,
+        latency,
+    })
+}
+
+/// Whether a listed model is the configured one; Ollama adds an implicit `:latest` tag
+fn same_model(listed: &str, configured: &str) -> bool {
+    listed == configured || listed.strip_suffix(":latest") == Some(configured)
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Backend:  {} ({})", self.backend, self.endpoint)?;
+        match (&self.model, &self.models) {
+            (Some(model), Some(_)) => writeln!(f, "Model:    {} (available)", model)?,
+            (Some(model), None) => writeln!(f, "Model:    {} (not listed by this backend)", model)?,
+            (None, _) => writeln!(f, "Model:    backend default")?,
+        }
+        if let Some(ref models) = self.models {
+            writeln!(f, "Models:   {}", models.len())?;
+        }
+        write!(f, "Latency:  {} ms", self.latency.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::stream::SummaryStream;
+
+    struct Fake {
+        model: Option<&'static str>,
+        models: Option<Vec<String>>,
+    }
+
+    impl LlmBackend for Fake {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn endpoint(&self) -> String {
+            "http://fake".to_string()
+        }
+
+        fn model(&self) -> Option<&str> {
+            self.model
+        }
+
+        fn generate_streaming(&self, _prompt: &str) -> Result<SummaryStream> {
+            unreachable!("check never generates")
+        }
+
+        fn list_models(&self) -> Result<Option<Vec<String>>> {
+            Ok(self.models.clone())
+        }
+    }
+
+    fn listed(names: &[&str]) -> Option<Vec<String>> {
+        Some(names.iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn test_configured_model_is_found() {
+        let report = check(&Fake { model: Some("llama3.1"), models: listed(&["llama3.1:latest", "qwen2"]) }).unwrap();
+        assert_eq!(report.model.as_deref(), Some("llama3.1"));
+        assert!(report.to_string().contains("llama3.1 (available)"));
+        assert!(report.to_string().contains("Latency:"));
+    }
+
+    #[test]
+    fn test_missing_model_fails() {
+        let err = check(&Fake { model: Some("llama9"), models: listed(&["qwen2:7b"]) }).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'llama9' is not available"), "{}", message);
+        assert!(message.contains("qwen2:7b"), "{}", message);
+
+        assert!(check(&Fake { model: Some("llama3"), models: listed(&[]) })
+            .unwrap_err()
+            .to_string()
+            .contains("available: none"));
+    }
+
+    #[test]
+    fn test_unlisted_models_are_not_checked() {
+        let report = check(&Fake { model: Some("sonnet"), models: None }).unwrap();
+        assert!(report.to_string().contains("not listed by this backend"));
+        assert!(check(&Fake { model: None, models: None }).unwrap().to_string().contains("backend default"));
+    }
+}
@@ -1,55 +1,143 @@
 //! Claude CLI integration
 
 use crate::error::{JrnrvwError, Result};
+use crate::llm::backend::{run_version, LlmBackend};
+use crate::llm::stream::{StreamDecoder, StreamEvent, SummaryStream};
+use serde_json::Value;
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::io::Write;
 
-/// Call Claude CLI to generate a summary
-pub fn generate_summary(prompt: &str) -> Result<String> {
-    // Check if claude CLI is available
-    let claude_path = which::which("claude")
-        .map_err(|_| JrnrvwError::ConfigError(
-            "Claude CLI not found. Please install it: https://github.com/anthropics/claude-code".to_string()
-        ))?;
-
-    // Call claude with --print flag for non-interactive mode
-    let mut child = Command::new(claude_path)
-        .arg("-p")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| JrnrvwError::ConfigError(
-            format!("Failed to spawn Claude CLI: {}", e)
-        ))?;
-
-    // Write prompt to stdin
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(prompt.as_bytes())
+/// Backend running prompts through the Claude CLI
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeBackend {
+    /// Passed as `--model`; the CLI's default model otherwise
+    pub model: Option<String>,
+}
+
+impl ClaudeBackend {
+    pub fn new(model: Option<String>) -> Self {
+        Self { model }
+    }
+
+    fn cli_path() -> Result<PathBuf> {
+        which::which("claude")
+            .map_err(|_| JrnrvwError::ConfigError(
+                "Claude CLI not found. Please install it: https://github.com/anthropics/claude-code".to_string()
+            ))
+    }
+}
+
+impl LlmBackend for ClaudeBackend {
+    fn name(&self) -> &str {
+        "claude"
+    }
+
+    fn endpoint(&self) -> String {
+        Self::cli_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| "claude (not on PATH)".to_string())
+    }
+
+    fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    fn generate_streaming(&self, prompt: &str) -> Result<SummaryStream> {
+        let claude_path = Self::cli_path()?;
+
+        // Non-interactive mode, forwarding the API's streaming events as JSON lines
+        let mut command = Command::new(claude_path);
+        command
+            .arg("-p")
+            .args(["--output-format", "stream-json", "--verbose", "--include-partial-messages"]);
+        if let Some(ref model) = self.model {
+            command.args(["--model", model]);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .map_err(|e| JrnrvwError::ConfigError(
-                format!("Failed to write to Claude stdin: {}", e)
+                format!("Failed to spawn Claude CLI: {}", e)
             ))?;
+
+        // Write prompt to stdin; dropping it closes the pipe so Claude starts answering
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(prompt.as_bytes())
+                .map_err(|e| JrnrvwError::ConfigError(
+                    format!("Failed to write to Claude stdin: {}", e)
+                ))?;
+        }
+
+        SummaryStream::spawn("Claude", child, Box::new(ClaudeDecoder::default()))
     }
 
-    // Wait for completion and get output
-    let output = child.wait_with_output()
-        .map_err(|e| JrnrvwError::ConfigError(
-            format!("Failed to read Claude output: {}", e)
-        ))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(JrnrvwError::ConfigError(
-            format!("Claude CLI failed: {}", stderr)
-        ));
+    /// The CLI accepts any model name, so this only checks that it runs
+    fn list_models(&self) -> Result<Option<Vec<String>>> {
+        run_version(Self::cli_path()?, "Claude")?;
+        Ok(None)
     }
+}
 
-    let summary = String::from_utf8(output.stdout)
-        .map_err(|e| JrnrvwError::ConfigError(
-            format!("Invalid UTF-8 in Claude output: {}", e)
-        ))?;
+/// Decoder for `--output-format stream-json` output
+///
+/// Text arrives as `content_block_delta` stream events. The closing `result`
+/// line repeats the whole answer; it is only used when no deltas were seen
+/// (older CLIs without partial messages).
+#[derive(Debug, Default)]
+pub struct ClaudeDecoder {
+    streamed: bool,
+}
+
+impl StreamDecoder for ClaudeDecoder {
+    fn decode_line(&mut self, line: &str) -> StreamEvent {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return StreamEvent::Ignore;
+        };
 
-    Ok(summary)
+        match message["type"].as_str() {
+            Some("stream_event") => {
+                let event = &message["event"];
+                match event["type"].as_str() {
+                    Some("content_block_delta") if event["delta"]["type"] == "text_delta" => {
+                        match event["delta"]["text"].as_str() {
+                            Some(text) if !text.is_empty() => {
+                                self.streamed = true;
+                                StreamEvent::Text(text.to_string())
+                            }
+                            _ => StreamEvent::Ignore,
+                        }
+                    }
+                    Some("error") => StreamEvent::Error(
+                        event["error"]["message"]
+                            .as_str()
+                            .unwrap_or("Claude stream error")
+                            .to_string(),
+                    ),
+                    _ => StreamEvent::Ignore,
+                }
+            }
+            Some("result") => {
+                let result = message["result"].as_str().unwrap_or_default();
+                if message["is_error"].as_bool().unwrap_or(false) {
+                    let reason = if result.is_empty() {
+                        message["subtype"].as_str().unwrap_or("unknown error")
+                    } else {
+                        result
+                    };
+                    StreamEvent::Error(format!("Claude reported an error: {}", reason))
+                } else if !self.streamed && !result.is_empty() {
+                    self.streamed = true;
+                    StreamEvent::Text(result.to_string())
+                } else {
+                    StreamEvent::Ignore
+                }
+            }
+            _ => StreamEvent::Ignore,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -60,7 +148,7 @@ mod tests {
     fn test_generate_summary_with_simple_prompt() {
         // This test will only run if Claude CLI is available
         if which::which("claude").is_ok() {
-            let result = generate_summary("Say 'test successful' and nothing else.");
+            let result = ClaudeBackend::default().generate("Say 'test successful' and nothing else.");
             // Don't assert success as Claude may require configuration or permissions
             // Just ensure the function can be called without panicking
             if let Ok(summary) = result {
@@ -74,6 +162,58 @@ mod tests {
         // Test error handling when claude binary doesn't exist
         // We can't easily test this without mocking, so we'll just ensure
         // the function exists and has the right signature
-        let _result = generate_summary("test");
+        let _result = ClaudeBackend::default().generate("test");
+    }
+
+    fn delta(text: &str) -> String {
+        serde_json::json!({
+            "type": "stream_event",
+            "event": {
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": { "type": "text_delta", "text": text }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_decoder_yields_text_deltas() {
+        let mut decoder = ClaudeDecoder::default();
+        assert_eq!(
+            decoder.decode_line(r#"{"type":"system","subtype":"init"}"#),
+            StreamEvent::Ignore
+        );
+        assert_eq!(decoder.decode_line(&delta("Hello")), StreamEvent::Text("Hello".to_string()));
+        assert_eq!(decoder.decode_line(&delta(", world")), StreamEvent::Text(", world".to_string()));
+
+        // The closing result repeats what was already streamed
+        assert_eq!(
+            decoder.decode_line(r#"{"type":"result","subtype":"success","is_error":false,"result":"Hello, world"}"#),
+            StreamEvent::Ignore
+        );
+    }
+
+    #[test]
+    fn test_decoder_falls_back_to_result() {
+        let mut decoder = ClaudeDecoder::default();
+        assert_eq!(
+            decoder.decode_line(r#"{"type":"result","subtype":"success","is_error":false,"result":"All done"}"#),
+            StreamEvent::Text("All done".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decoder_reports_errors() {
+        let mut decoder = ClaudeDecoder::default();
+        assert_eq!(
+            decoder.decode_line(r#"{"type":"stream_event","event":{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}}"#),
+            StreamEvent::Error("Overloaded".to_string())
+        );
+        assert_eq!(
+            decoder.decode_line(r#"{"type":"result","subtype":"error_during_execution","is_error":true}"#),
+            StreamEvent::Error("Claude reported an error: error_during_execution".to_string())
+        );
+        assert_eq!(decoder.decode_line("not json"), StreamEvent::Ignore);
     }
 }
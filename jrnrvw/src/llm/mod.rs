@@ -1,26 +1,70 @@
 //! LLM integration for AI-powered summarization
+//!
+//! Summaries are written by an [`LlmBackend`]: the Claude or Codex CLI, or a
+//! local Ollama server. [`create_backend`] picks one from the `[llm]` config.
 
+pub mod backend;
+pub mod cache;
+pub mod check;
 pub mod claude;
 pub mod codex;
+pub mod ollama;
 pub mod prompts;
+pub mod redact;
+pub mod stream;
 
-use crate::error::Result;
+use crate::config::settings::LlmConfig;
+use crate::error::{JrnrvwError, Result};
 use crate::models::Repository;
+use crate::profile;
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+pub use backend::LlmBackend;
+pub use cache::CachedBackend;
+pub use redact::{Redactions, Redactor};
+pub use stream::SummaryStream;
 
 /// Supported LLM providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LlmProvider {
+    #[default]
     Claude,
     Codex,
+    Ollama,
+}
+
+/// Build the backend `config` describes
+pub fn create_backend(config: &LlmConfig) -> Result<Box<dyn LlmBackend>> {
+    let model = config.model.clone().filter(|m| !m.is_empty());
+    Ok(match config.backend {
+        LlmProvider::Claude => Box::new(claude::ClaudeBackend::new(model)),
+        LlmProvider::Codex => Box::new(codex::CodexBackend::new(model)),
+        LlmProvider::Ollama => {
+            let model = model.ok_or_else(|| JrnrvwError::ConfigError(
+                "llm.model must name a model for the ollama backend (see `ollama list`)".to_string()
+            ))?;
+            Box::new(ollama::OllamaBackend::new(config.base_url.as_deref(), &model))
+        }
+    })
 }
 
-/// Generate a summary using the specified LLM provider
+/// Generate a summary using the given backend
 pub fn summarize(
-    provider: LlmProvider,
+    backend: &dyn LlmBackend,
     repositories: &[Repository],
     date_range: Option<(NaiveDate, NaiveDate)>,
 ) -> Result<String> {
+    summarize_stream(backend, repositories, date_range)?.collect_text()
+}
+
+/// Stream a summary from the given backend as it is generated
+pub fn summarize_stream(
+    backend: &dyn LlmBackend,
+    repositories: &[Repository],
+    date_range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<SummaryStream> {
     // Calculate total entries
     let total_entries: usize = repositories
         .iter()
@@ -30,16 +74,14 @@ pub fn summarize(
     // Generate prompt
     let prompt = prompts::create_summary_prompt(repositories, total_entries, date_range);
 
-    // Call appropriate LLM
-    match provider {
-        LlmProvider::Claude => claude::generate_summary(&prompt),
-        LlmProvider::Codex => codex::generate_summary(&prompt),
-    }
+    // Call the backend
+    let span = profile::span_with("llm", || format!("{} summary", backend.name()));
+    backend.generate_streaming(&prompt).map(|stream| stream.within(span))
 }
 
-/// Generate a brief summary using the specified LLM provider
+/// Generate a brief summary using the given backend
 pub fn summarize_brief(
-    provider: LlmProvider,
+    backend: &dyn LlmBackend,
     repositories: &[Repository],
 ) -> Result<String> {
     let total_entries: usize = repositories
@@ -49,10 +91,8 @@ pub fn summarize_brief(
 
     let prompt = prompts::create_brief_summary_prompt(repositories, total_entries);
 
-    match provider {
-        LlmProvider::Claude => claude::generate_summary(&prompt),
-        LlmProvider::Codex => codex::generate_summary(&prompt),
-    }
+    let span = profile::span_with("llm", || format!("{} brief summary", backend.name()));
+    backend.generate_streaming(&prompt)?.within(span).collect_text()
 }
 
 #[cfg(test)]
@@ -79,7 +119,7 @@ mod tests {
         // Only run if claude is available
         if which::which("claude").is_ok() {
             let repo = create_test_repo();
-            let result = summarize(LlmProvider::Claude, &[repo], None);
+            let result = summarize(&claude::ClaudeBackend::default(), &[repo], None);
             // Don't assert success as Claude may not be configured
             // Just ensure the function can be called
             let _ = result;
@@ -91,7 +131,7 @@ mod tests {
         // Only run if codex is available
         if which::which("codex").is_ok() {
             let repo = create_test_repo();
-            let result = summarize(LlmProvider::Codex, &[repo], None);
+            let result = summarize(&codex::CodexBackend::default(), &[repo], None);
             // Don't assert success as Codex may not be configured
             // Just ensure the function can be called
             let _ = result;
@@ -102,8 +142,27 @@ mod tests {
     fn test_summarize_brief() {
         if which::which("claude").is_ok() {
             let repo = create_test_repo();
-            let result = summarize_brief(LlmProvider::Claude, &[repo]);
+            let result = summarize_brief(&claude::ClaudeBackend::default(), &[repo]);
             let _ = result;
         }
     }
+
+    #[test]
+    fn test_create_backend_from_config() {
+        let backend = create_backend(&LlmConfig::default()).unwrap();
+        assert_eq!(backend.name(), "claude");
+
+        let config = LlmConfig {
+            backend: LlmProvider::Ollama,
+            base_url: Some("http://gpu-box:11434".to_string()),
+            model: Some("llama3.1".to_string()),
+        };
+        let backend = create_backend(&config).unwrap();
+        assert_eq!(backend.name(), "ollama");
+        assert_eq!(backend.endpoint(), "http://gpu-box:11434");
+        assert_eq!(backend.model(), Some("llama3.1"));
+
+        let no_model = LlmConfig { model: None, ..config };
+        assert!(create_backend(&no_model).is_err());
+    }
 }
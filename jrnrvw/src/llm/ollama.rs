@@ -0,0 +1,192 @@
+//! Local Ollama server integration
+//!
+//! Uses the HTTP API: `POST /api/generate` with `stream: true` answers with
+//! one JSON object per line, each carrying the next piece of the response,
+//! and `GET /api/tags` lists the models that have been pulled.
+
+use crate::error::{JrnrvwError, Result};
+use crate::llm::backend::LlmBackend;
+use crate::llm::stream::{StreamDecoder, StreamEvent, SummaryStream};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Where Ollama listens unless `llm.base_url` says otherwise
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// How long to wait for the server to accept a connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest pause between streamed lines; loading a model can take a while
+const READ_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Backend sending prompts to an Ollama server
+#[derive(Debug, Clone)]
+pub struct OllamaBackend {
+    base_url: String,
+    model: String,
+    agent: ureq::Agent,
+}
+
+impl OllamaBackend {
+    /// Backend for `model` on the server at `base_url` (the local default if `None`)
+    pub fn new(base_url: Option<&str>, model: &str) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL).trim_end_matches('/').to_string();
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(CONNECT_TIMEOUT)
+            .timeout_read(READ_TIMEOUT)
+            .build();
+        Self { base_url, model: model.to_string(), agent }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Turn a failed request into an error naming the server
+    fn request_error(&self, error: ureq::Error) -> JrnrvwError {
+        let message = match error {
+            ureq::Error::Status(code, response) => {
+                // Ollama explains failures as {"error": "..."}
+                let reason = response
+                    .into_json::<Value>()
+                    .ok()
+                    .and_then(|body| body["error"].as_str().map(str::to_string))
+                    .unwrap_or_else(|| format!("HTTP {}", code));
+                format!("Ollama at {} failed: {}", self.base_url, reason)
+            }
+            ureq::Error::Transport(transport) => {
+                format!("Cannot reach Ollama at {}: {}", self.base_url, transport)
+            }
+        };
+        JrnrvwError::Llm { message, partial: String::new() }
+    }
+}
+
+#[derive(Deserialize)]
+struct Tags {
+    #[serde(default)]
+    models: Vec<TagModel>,
+}
+
+#[derive(Deserialize)]
+struct TagModel {
+    name: String,
+}
+
+impl LlmBackend for OllamaBackend {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn endpoint(&self) -> String {
+        self.base_url.clone()
+    }
+
+    fn model(&self) -> Option<&str> {
+        Some(&self.model)
+    }
+
+    fn generate_streaming(&self, prompt: &str) -> Result<SummaryStream> {
+        let response = self
+            .agent
+            .post(&self.url("/api/generate"))
+            .send_json(json!({ "model": self.model, "prompt": prompt, "stream": true }))
+            .map_err(|e| self.request_error(e))?;
+
+        Ok(SummaryStream::from_reader("Ollama", response.into_reader(), Box::new(OllamaDecoder::default())))
+    }
+
+    fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let tags: Tags = self
+            .agent
+            .get(&self.url("/api/tags"))
+            .call()
+            .map_err(|e| self.request_error(e))?
+            .into_json()
+            .map_err(|e| JrnrvwError::Llm {
+                message: format!("Unexpected model list from Ollama at {}: {}", self.base_url, e),
+                partial: String::new(),
+            })?;
+        Ok(Some(tags.models.into_iter().map(|model| model.name).collect()))
+    }
+}
+
+/// Decoder for `/api/generate` streaming lines
+///
+/// The stream has to end with a `"done": true` line; a connection that
+/// closes before it is reported as an error.
+#[derive(Debug, Default)]
+pub struct OllamaDecoder {
+    done: bool,
+}
+
+impl StreamDecoder for OllamaDecoder {
+    fn decode_line(&mut self, line: &str) -> StreamEvent {
+        if line.trim().is_empty() {
+            return StreamEvent::Ignore;
+        }
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return StreamEvent::Error(format!("Unexpected line from Ollama: {}", line));
+        };
+
+        if let Some(error) = message["error"].as_str() {
+            return StreamEvent::Error(format!("Ollama reported an error: {}", error));
+        }
+        if message["done"].as_bool().unwrap_or(false) {
+            self.done = true;
+        }
+        match message["response"].as_str() {
+            Some(text) if !text.is_empty() => StreamEvent::Text(text.to_string()),
+            _ => StreamEvent::Ignore,
+        }
+    }
+
+    fn finish(&mut self) -> StreamEvent {
+        if self.done {
+            StreamEvent::Ignore
+        } else {
+            StreamEvent::Error("Ollama closed the stream before the response was done".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_defaults_and_trims() {
+        assert_eq!(OllamaBackend::new(None, "llama3").endpoint(), DEFAULT_BASE_URL);
+        let backend = OllamaBackend::new(Some("http://gpu-box:11434/"), "llama3");
+        assert_eq!(backend.url("/api/tags"), "http://gpu-box:11434/api/tags");
+        assert_eq!(backend.model(), Some("llama3"));
+    }
+
+    #[test]
+    fn test_decoder_yields_response_pieces() {
+        let mut decoder = OllamaDecoder::default();
+        assert_eq!(
+            decoder.decode_line(r#"{"model":"llama3","response":"Hello","done":false}"#),
+            StreamEvent::Text("Hello".to_string())
+        );
+        assert_eq!(
+            decoder.decode_line(r#"{"model":"llama3","response":"","done":true,"eval_count":12}"#),
+            StreamEvent::Ignore
+        );
+        assert_eq!(decoder.finish(), StreamEvent::Ignore);
+    }
+
+    #[test]
+    fn test_decoder_reports_errors_and_truncation() {
+        let mut decoder = OllamaDecoder::default();
+        assert_eq!(
+            decoder.decode_line(r#"{"error":"model 'llama9' not found"}"#),
+            StreamEvent::Error("Ollama reported an error: model 'llama9' not found".to_string())
+        );
+
+        let mut decoder = OllamaDecoder::default();
+        decoder.decode_line(r#"{"response":"Half","done":false}"#);
+        assert!(matches!(decoder.finish(), StreamEvent::Error(_)));
+    }
+}
@@ -1,8 +1,15 @@
 //! Prompt templates for LLM summarization
 
-use crate::models::Repository;
+use crate::models::{Repository, Task};
 use chrono::NaiveDate;
 
+/// Bumped whenever the templates change, so cached summaries of the old
+/// prompts are not reused
+pub const PROMPT_VERSION: u32 = 2;
+
+/// Stands in for names that would identify an author excluded from prompts
+const PRIVATE_NAME: &str = "(private)";
+
 /// Generate a summarization prompt for journal entries
 pub fn create_summary_prompt(
     repositories: &[Repository],
@@ -35,14 +42,30 @@ pub fn create_summary_prompt(
 
     // Add repository content
     for repo in repositories {
-        prompt.push_str(&format!("### Repository: {}\n\n", repo.name));
+        prompt.push_str(&format!("### Repository: {}\n\n", repo_name(repo)));
 
         for task in &repo.tasks {
-            prompt.push_str(&format!("#### Task: {}\n\n", task.name));
+            prompt.push_str(&format!("#### Task: {}\n\n", task_name(task)));
 
             for entry in &task.entries {
                 prompt.push_str(&format!("**Date**: {}\n", entry.date));
 
+                // Archived entries count towards the totals but keep their text private
+                if entry.archived {
+                    prompt.push_str("**Archived**: content omitted\n\n");
+                    continue;
+                }
+
+                // So are those of authors excluded from prompts, name included
+                if entry.private {
+                    prompt.push_str("**Private**: author and content omitted\n\n");
+                    continue;
+                }
+
+                if let Some(ref author) = entry.author {
+                    prompt.push_str(&format!("**Author**: {}\n", author));
+                }
+
                 if let Some(ref title) = entry.title {
                     prompt.push_str(&format!("**Title**: {}\n", title));
                 }
@@ -84,9 +107,9 @@ pub fn create_brief_summary_prompt(
     prompt.push_str(&format!("Total: {} entries across {} repositories\n\n", total_entries, repositories.len()));
 
     for repo in repositories {
-        prompt.push_str(&format!("**{}**:\n", repo.name));
+        prompt.push_str(&format!("**{}**:\n", repo_name(repo)));
         for task in &repo.tasks {
-            prompt.push_str(&format!("- {}: {} entries\n", task.name, task.entries.len()));
+            prompt.push_str(&format!("- {}: {} entries\n", task_name(task), task.entries.len()));
         }
         prompt.push_str("\n");
     }
@@ -94,10 +117,29 @@ pub fn create_brief_summary_prompt(
     prompt
 }
 
+/// Repository name, withheld when only excluded authors wrote in it, as
+/// when grouping by author
+fn repo_name(repo: &Repository) -> &str {
+    if repo.entry_count() > 0 && repo.tasks.iter().flat_map(|t| &t.entries).all(|e| e.private) {
+        PRIVATE_NAME
+    } else {
+        &repo.name
+    }
+}
+
+/// Task name, withheld when only excluded authors wrote about it
+fn task_name(task: &Task) -> &str {
+    if !task.entries.is_empty() && task.entries.iter().all(|e| e.private) {
+        PRIVATE_NAME
+    } else {
+        &task.name
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Task, JournalEntry};
+    use crate::models::JournalEntry;
     use std::path::PathBuf;
 
     #[test]
@@ -124,6 +166,67 @@ mod tests {
         assert!(prompt.contains("Did something"));
     }
 
+    #[test]
+    fn test_summary_prompt_omits_archived_entries() {
+        let mut repo = Repository::new("test-repo".to_string(), None);
+        let mut task = Task::new("test-task".to_string());
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from("test.md"), date);
+        entry.title = Some("Private Entry".to_string());
+        entry.activities = vec!["Secret work".to_string()];
+        entry.notes = Some("Confidential notes".to_string());
+        entry.archived = true;
+
+        task.add_entry(entry);
+        repo.add_task(task);
+
+        let prompt = create_summary_prompt(&[repo], 1, None);
+
+        assert!(prompt.contains("Total Entries: 1"));
+        assert!(prompt.contains("**Date**: 2025-11-13"));
+        assert!(prompt.contains("content omitted"));
+        assert!(!prompt.contains("Private Entry"));
+        assert!(!prompt.contains("Secret work"));
+        assert!(!prompt.contains("Confidential notes"));
+    }
+
+    #[test]
+    fn test_summary_prompt_withholds_excluded_authors() {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut shared = Task::new("shared-task".to_string());
+        let mut alice = JournalEntry::new(PathBuf::from("a.md"), date);
+        alice.author = Some("alice".to_string());
+        alice.activities = vec!["Reviewed the plan".to_string()];
+        let mut bob = JournalEntry::new(PathBuf::from("b.md"), date);
+        bob.author = Some("bob".to_string());
+        bob.activities = vec!["Salary talks".to_string()];
+        bob.private = true;
+        shared.add_entry(alice);
+        shared.add_entry(bob.clone());
+        let mut own = Task::new("bob-only".to_string());
+        own.add_entry(bob.clone());
+
+        let mut team = Repository::new("team".to_string(), None);
+        team.add_task(shared);
+        team.add_task(own);
+        // Grouped by author, the repository name is the author's
+        let mut by_author = Repository::new("bob".to_string(), None);
+        let mut task = Task::new("bob-only".to_string());
+        task.add_entry(bob);
+        by_author.add_task(task);
+
+        let prompt = create_summary_prompt(&[team, by_author], 4, None);
+
+        assert!(prompt.contains("Total Entries: 4"));
+        assert!(prompt.contains("**Author**: alice"));
+        assert!(prompt.contains("Reviewed the plan"));
+        assert!(prompt.contains("#### Task: shared-task"));
+        assert_eq!(prompt.matches("author and content omitted").count(), 3);
+        assert!(!prompt.contains("bob"));
+        assert!(!prompt.contains("Salary talks"));
+    }
+
     #[test]
     fn test_create_brief_summary_prompt() {
         let mut repo = Repository::new("test-repo".to_string(), None);
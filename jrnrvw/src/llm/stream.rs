@@ -0,0 +1,345 @@
+//! Incremental LLM output
+//!
+//! Every backend produces its answer as lines while it is generated: the
+//! CLIs on stdout, Ollama as newline-delimited JSON over HTTP. A
+//! [`SummaryStream`] reads those lines, hands each to a backend-specific
+//! [`StreamDecoder`], and yields the text chunks as soon as they arrive.
+//! Failures part-way through are reported as [`JrnrvwError::Llm`] carrying
+//! the text received so far.
+
+use crate::error::{JrnrvwError, Result};
+use crate::profile;
+use std::io::{BufRead, BufReader, Lines, Read};
+use std::process::Child;
+use std::thread::{self, JoinHandle};
+
+/// What one line of CLI output means
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent {
+    /// Text to show the user
+    Text(String),
+
+    /// The provider reported a failure
+    Error(String),
+
+    /// Framing or metadata with nothing to show
+    Ignore,
+}
+
+/// Turns a provider's raw output lines into stream events
+pub trait StreamDecoder {
+    /// Interpret one line of output (without its line ending)
+    fn decode_line(&mut self, line: &str) -> StreamEvent;
+
+    /// Called once the output has ended: text held back until then, or an
+    /// error if the output stopped early
+    fn finish(&mut self) -> StreamEvent {
+        StreamEvent::Ignore
+    }
+}
+
+/// Receives a complete response, e.g. to store it
+type CompletionCallback = Box<dyn FnOnce(&str) + Send>;
+
+/// Text chunks of an LLM response, in the order they were generated
+pub struct SummaryStream {
+    provider: &'static str,
+    /// CLI producing the output, if it comes from a process
+    child: Option<Child>,
+    lines: Lines<BufReader<Box<dyn Read + Send>>>,
+    stderr: Option<JoinHandle<String>>,
+    decoder: Box<dyn StreamDecoder>,
+    received: String,
+    done: bool,
+    span: Option<profile::Span>,
+    /// Called with the whole response once it has arrived without errors
+    on_complete: Option<CompletionCallback>,
+}
+
+impl SummaryStream {
+    /// Stream the stdout of a spawned CLI through `decoder`
+    ///
+    /// The child must have been spawned with piped stdout and stderr.
+    pub(crate) fn spawn(
+        provider: &'static str,
+        mut child: Child,
+        decoder: Box<dyn StreamDecoder>,
+    ) -> Result<Self> {
+        let stdout = child.stdout.take().ok_or_else(|| JrnrvwError::Llm {
+            message: format!("{} CLI has no stdout", provider),
+            partial: String::new(),
+        })?;
+
+        // Drain stderr on its own thread so a chatty CLI cannot block on a full pipe
+        let stderr = child.stderr.take().map(|mut stderr| {
+            thread::spawn(move || {
+                let mut text = String::new();
+                let _ = stderr.read_to_string(&mut text);
+                text
+            })
+        });
+
+        let mut stream = Self::from_reader(provider, stdout, decoder);
+        stream.child = Some(child);
+        stream.stderr = stderr;
+        Ok(stream)
+    }
+
+    /// Stream lines read from `reader`, such as an HTTP response body, through `decoder`
+    pub(crate) fn from_reader(
+        provider: &'static str,
+        reader: impl Read + Send + 'static,
+        decoder: Box<dyn StreamDecoder>,
+    ) -> Self {
+        let reader: Box<dyn Read + Send> = Box::new(reader);
+        Self {
+            provider,
+            child: None,
+            lines: BufReader::new(reader).lines(),
+            stderr: None,
+            decoder,
+            received: String::new(),
+            done: false,
+            span: None,
+            on_complete: None,
+        }
+    }
+
+    /// Replay a response that has already been generated
+    pub(crate) fn from_text(provider: &'static str, text: String) -> Self {
+        Self::from_reader(provider, std::io::empty(), Box::new(Replay(Some(text))))
+    }
+
+    /// Keep `span` open until the response has been read
+    pub(crate) fn within(mut self, span: profile::Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Run `callback` with the whole response if it completes successfully
+    pub(crate) fn on_complete(mut self, callback: impl FnOnce(&str) + Send + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Text received so far
+    pub fn received(&self) -> &str {
+        &self.received
+    }
+
+    /// Wait for the whole response and return it as one string
+    pub fn collect_text(mut self) -> Result<String> {
+        for chunk in self.by_ref() {
+            chunk?;
+        }
+        Ok(std::mem::take(&mut self.received))
+    }
+
+    fn emit(&mut self, text: String) -> Option<Result<String>> {
+        self.received.push_str(&text);
+        Some(Ok(text))
+    }
+
+    fn fail(&mut self, message: String) -> Option<Result<String>> {
+        self.done = true;
+        self.span = None;
+        self.stop_child();
+        Some(Err(JrnrvwError::Llm {
+            message,
+            partial: std::mem::take(&mut self.received),
+        }))
+    }
+
+    fn stop_child(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    fn stderr_text(&mut self) -> String {
+        self.stderr
+            .take()
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    }
+
+    fn finish(&mut self) -> Option<Result<String>> {
+        self.done = true;
+        self.span = None;
+
+        if let Some(child) = self.child.as_mut() {
+            let status = match child.wait() {
+                Ok(status) => status,
+                Err(e) => {
+                    return self.fail(format!("Failed to wait for {} CLI: {}", self.provider, e));
+                }
+            };
+            if !status.success() {
+                let stderr = self.stderr_text();
+                return self.fail(format!("{} CLI failed: {}", self.provider, stderr.trim()));
+            }
+        }
+
+        let last = match self.decoder.finish() {
+            StreamEvent::Text(text) if !text.is_empty() => self.emit(text),
+            StreamEvent::Error(message) => return self.fail(message),
+            _ => None,
+        };
+        if let Some(callback) = self.on_complete.take() {
+            callback(&self.received);
+        }
+        last
+    }
+}
+
+/// Hands back a stored response in one piece
+struct Replay(Option<String>);
+
+impl StreamDecoder for Replay {
+    fn decode_line(&mut self, _line: &str) -> StreamEvent {
+        StreamEvent::Ignore
+    }
+
+    fn finish(&mut self) -> StreamEvent {
+        self.0.take().map_or(StreamEvent::Ignore, StreamEvent::Text)
+    }
+}
+
+impl Iterator for SummaryStream {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.lines.next() {
+                Some(Ok(line)) => match self.decoder.decode_line(&line) {
+                    StreamEvent::Text(text) => return self.emit(text),
+                    StreamEvent::Error(message) => return self.fail(message),
+                    StreamEvent::Ignore => {}
+                },
+                Some(Err(e)) => {
+                    return self.fail(format!("Failed to read {} output: {}", self.provider, e));
+                }
+                None => return self.finish(),
+            }
+        }
+        None
+    }
+}
+
+impl Drop for SummaryStream {
+    fn drop(&mut self) {
+        // Abandoned part-way: don't leave the CLI running in the background
+        if !self.done {
+            self.stop_child();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, Stdio};
+
+    /// Passes lines through, failing on a line reading `boom`
+    struct LineDecoder;
+
+    impl StreamDecoder for LineDecoder {
+        fn decode_line(&mut self, line: &str) -> StreamEvent {
+            match line {
+                "boom" => StreamEvent::Error("provider failed".to_string()),
+                "" => StreamEvent::Ignore,
+                text => StreamEvent::Text(format!("{}\n", text)),
+            }
+        }
+    }
+
+    fn shell(script: &str) -> SummaryStream {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        SummaryStream::spawn("Test", child, Box::new(LineDecoder)).unwrap()
+    }
+
+    #[test]
+    fn test_yields_chunks_in_order() {
+        let chunks: Vec<String> = shell("printf 'one\\n\\ntwo\\n'")
+            .map(|chunk| chunk.unwrap())
+            .collect();
+        assert_eq!(chunks, vec!["one\n", "two\n"]);
+    }
+
+    #[test]
+    fn test_collect_text() {
+        let text = shell("printf 'one\\ntwo\\n'").collect_text().unwrap();
+        assert_eq!(text, "one\ntwo\n");
+    }
+
+    #[test]
+    fn test_mid_stream_error_keeps_partial_text() {
+        let err = shell("printf 'one\\ntwo\\nboom\\nthree\\n'")
+            .collect_text()
+            .unwrap_err();
+        match err {
+            JrnrvwError::Llm { message, partial } => {
+                assert_eq!(message, "provider failed");
+                assert_eq!(partial, "one\ntwo\n");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_reads_from_any_reader() {
+        let body = std::io::Cursor::new(b"one\nboom\n".to_vec());
+        let mut stream = SummaryStream::from_reader("Test", body, Box::new(LineDecoder));
+        assert_eq!(stream.next().unwrap().unwrap(), "one\n");
+        assert!(matches!(stream.next(), Some(Err(JrnrvwError::Llm { .. }))));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn test_completion_callback_sees_whole_response() {
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let recorded = std::sync::Arc::clone(&seen);
+        let text = shell("printf 'one\\ntwo\\n'")
+            .on_complete(move |text| *recorded.lock().unwrap() = Some(text.to_string()))
+            .collect_text()
+            .unwrap();
+        assert_eq!(seen.lock().unwrap().as_deref(), Some(text.as_str()));
+
+        // Not called for a failed response
+        let failed = std::sync::Arc::new(std::sync::Mutex::new(false));
+        let flag = std::sync::Arc::clone(&failed);
+        let result = shell("printf 'one\\nboom\\n'")
+            .on_complete(move |_| *flag.lock().unwrap() = true)
+            .collect_text();
+        assert!(result.is_err());
+        assert!(!*failed.lock().unwrap());
+    }
+
+    #[test]
+    fn test_replays_text_unchanged() {
+        let text = "no trailing newline\n\nsecond paragraph";
+        let stream = SummaryStream::from_text("Test", text.to_string());
+        assert_eq!(stream.collect_text().unwrap(), text);
+    }
+
+    #[test]
+    fn test_failed_exit_reports_stderr() {
+        let mut stream = shell("printf 'half\\n'; echo 'rate limited' >&2; exit 3");
+        assert_eq!(stream.next().unwrap().unwrap(), "half\n");
+        match stream.next().unwrap().unwrap_err() {
+            JrnrvwError::Llm { message, partial } => {
+                assert_eq!(message, "Test CLI failed: rate limited");
+                assert_eq!(partial, "half\n");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(stream.next().is_none());
+    }
+}
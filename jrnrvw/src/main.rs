@@ -4,135 +4,343 @@
 
 use clap::Parser;
 use jrnrvw::{
-    cli::Cli,
-    discovery::discover_journals,
-    analyzer::{EntryFilter, TimeRange, ReportBuilder},
+    cli::{Cli, Command, CacheCommand, CheckArgs, CompletionsArgs, ConfigCommand, ExportArgs, LlmCommand, NotifyArgs, SearchArgs},
+    analyzer::{Analysis, CacheSettings, EntryFilter, TimeRange, ReportBuilder, ProjectTagger, AuthorResolver, load_entries},
+    discovery::DiscoverySettings,
+    llm::LlmBackend,
     output::{Formatter, OutputOptions},
-    models::{GroupBy, SortBy, OutputFormat},
+    models::{CiRule, DiagnosticLevel, GroupBy, SortBy, OutputFormat},
+    serve::{serve, ServeConfig},
     Result,
 };
 use std::env;
 use std::fs;
 use std::io::{self, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 fn main() {
-    if let Err(e) = run() {
+    let cli = Cli::parse();
+    let ci = cli.ci;
+    if let Err(e) = run(cli) {
         eprintln!("Error: {}", e);
-        std::process::exit(1);
+        // In CI mode 1 means violations, so any other failure exits 2
+        let code = match e {
+            jrnrvw::JrnrvwError::CiViolations(_) => 1,
+            _ if ci => 2,
+            _ => 1,
+        };
+        std::process::exit(code);
     }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
+fn run(cli: Cli) -> Result<()> {
+
+    #[cfg(feature = "profile")]
+    if cli.profile {
+        let trace_path = cli.profile_trace.clone();
+        let profiler = jrnrvw::profile::Profiler::start();
+        let result = {
+            let _span = jrnrvw::profile::span("run");
+            execute(cli)
+        };
+        write_profile(&profiler.finish(), trace_path)?;
+        return result;
+    }
+
+    execute(cli)
+}
+
+/// Print the `--profile` table and write the trace file, if one was asked for
+#[cfg(feature = "profile")]
+fn write_profile(profile: &jrnrvw::profile::Profile, trace_path: Option<PathBuf>) -> Result<()> {
+    eprint!("\n{}", profile.render_table());
+
+    if let Some(path) = trace_path {
+        fs::write(&path, profile.chrome_trace().to_string())?;
+        eprintln!("Chrome trace written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Analyze the journals and write the report the CLI asked for
+fn execute(cli: Cli) -> Result<()> {
+    if let Some(Command::Cache(ref args)) = cli.command {
+        return run_cache_command(&args.action);
+    }
+
+    if let Some(Command::Config(ref args)) = cli.command {
+        return run_config_command(&args.action, &cli);
+    }
 
-    // Determine root path
-    let root_path = cli.path.clone()
+    if let Some(Command::Completions(ref args)) = cli.command {
+        return run_completions(args, &cli);
+    }
+
+    if let Some(Command::Man) = cli.command {
+        return jrnrvw::completions::write_man_page(&mut io::stdout().lock());
+    }
+
+    // Load configuration (optional)
+    let config = load_config(&cli, cli.config_profile.as_deref())?;
+
+    // Determine root path: the command line's, else the config's
+    let command_path = match cli.command {
+        Some(Command::Serve(ref args)) => args.path.clone(),
+        Some(Command::Export(ref args)) => args.path.clone(),
+        Some(Command::Notify(ref args)) => args.path.clone(),
+        Some(Command::Check(ref args)) => args.path.clone(),
+        Some(Command::Search(ref args)) => args.path.clone(),
+        Some(Command::Cache(_))
+        | Some(Command::Llm(_))
+        | Some(Command::Config(_))
+        | Some(Command::Completions(_))
+        | Some(Command::Man)
+        | None => None,
+    };
+    let root_path = command_path
+        .or_else(|| cli.path.clone())
+        .or_else(|| config.as_ref().map(|c| c.general.root()))
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
     if cli.verbose {
+        if let Some(profile) = config.as_ref().and_then(|c| c.active_profile.as_deref()) {
+            eprintln!("Config profile: {}", profile);
+        }
         eprintln!("Scanning directory: {}", root_path.display());
     }
 
-    // Load configuration (optional)
-    let _config = if let Some(ref config_path) = cli.config {
-        Some(jrnrvw::config::Config::load_from_file(config_path)?)
-    } else {
-        jrnrvw::config::Config::load_default()?
+    // Command-line output settings win over the config's
+    let output_format = match cli.format {
+        Some(format) => convert_format(format),
+        None => config.as_ref().map_or(Ok(OutputFormat::Text), |c| c.general.format())?,
+    };
+    let colored = !cli.no_color
+        && !cli.ci
+        && config.as_ref().is_none_or(|c| c.general.colored_output)
+        && atty::is(atty::Stream::Stdout);
+
+    if let Some(Command::Llm(ref args)) = cli.command {
+        let LlmCommand::Check { backend } = args.action;
+        let llm_config = llm_config(config.as_ref(), backend);
+        let backend = jrnrvw::llm::create_backend(&llm_config)?;
+        let report = jrnrvw::llm::check::check(backend.as_ref())?;
+        println!("{}", report);
+        return Ok(());
+    }
+
+    let tagger = match config {
+        Some(ref config) => ProjectTagger::new(&config.tagging)?,
+        None => ProjectTagger::default(),
+    };
+    let authors = match config {
+        Some(ref config) => AuthorResolver::new(&config.authors, &root_path)?,
+        None => AuthorResolver::default(),
+    };
+    let signals = config.as_ref().map(|c| c.signals.clone()).unwrap_or_default();
+    let references = config.as_ref().map(|c| c.references.clone()).unwrap_or_default();
+    let mut dedup = config.as_ref().map(|c| c.dedup.clone()).unwrap_or_default();
+    if cli.no_dedup {
+        dedup.enabled = false;
+    }
+    let discovery = DiscoverySettings {
+        include_org: config.as_ref().is_some_and(|c| c.discovery.include_org),
+        exclude: config.as_ref().map(|c| c.discovery.exclude.clone()).unwrap_or_default(),
+        no_ignore: cli.no_ignore,
+        follow_symlinks: config.as_ref().is_some_and(|c| c.discovery.follow_symlinks),
     };
+    let git_metadata = cli.git_metadata || config.as_ref().is_some_and(|c| c.discovery.git_metadata);
+    let week_start = cli
+        .week_start
+        .or_else(|| config.as_ref().map(|c| c.output.week_start))
+        .unwrap_or(chrono::Weekday::Mon);
+    let stale_after_days = config.as_ref().map(|c| c.tasks).unwrap_or_default().stale_after_days;
+
+    let cache = CacheSettings::default_dir().map(|dir| CacheSettings {
+        dir,
+        reuse: !cli.no_cache,
+    });
+    if cli.verbose {
+        match cache {
+            Some(ref cache) => eprintln!("Index cache: {}", cache.dir.display()),
+            None => eprintln!("Index cache: disabled (no cache directory)"),
+        }
+    }
 
-    // Discover journal files
-    let mut entries = discover_journals(&root_path, vec![])?;
+    if let Some(Command::Serve(ref args)) = cli.command {
+        let analysis = Analysis {
+            root: root_path,
+            tagger,
+            authors,
+            signals,
+            references,
+            dedup,
+            filter: build_filter(&cli)?,
+            group_by: convert_group_by(cli.group_by),
+            sort_by: convert_sort_by(cli.sort_by),
+            week_start,
+            stale_after_days,
+            print_warnings: !cli.quiet,
+            cache,
+            discovery,
+            git_metadata,
+        };
+        let config = ServeConfig {
+            bind: args.bind,
+            refresh_interval: (args.refresh_interval > 0)
+                .then(|| Duration::from_secs(args.refresh_interval)),
+            token: args.token.clone().filter(|t| !t.is_empty()),
+            options: OutputOptions {
+                colored: false,
+                verbose: cli.verbose,
+                include_activities: cli.with_activities || !cli.summary,
+                include_notes: cli.with_notes,
+                include_stats: cli.stats || !cli.summary,
+                summary_only: cli.summary,
+                max_entry_chars: cli.max_entry_chars,
+            },
+            verbose: cli.verbose,
+        };
+        return serve(analysis, config);
+    }
+
+    // Discover journal files; CI mode reports parse problems as diagnostics
+    let print_warnings = !cli.quiet && !cli.ci;
+    #[cfg_attr(not(feature = "explain"), allow(unused_mut))]
+    let mut entries = load_entries(&root_path, &tagger, &authors, print_warnings, cache.as_ref(), &discovery, git_metadata)?;
 
     if cli.verbose {
         eprintln!("Found {} journal files", entries.len());
     }
 
-    if entries.is_empty() {
-        if !cli.quiet {
+    if entries.is_empty() && !matches!(cli.command, Some(Command::Notify(_))) {
+        if !cli.quiet && !cli.ci {
             println!("No journal files found in {}", root_path.display());
         }
         return Ok(());
     }
 
-    // Parse content for each entry
-    for entry in &mut entries {
-        if let Ok(content) = fs::read_to_string(&entry.filepath) {
-            entry.raw_content = content.clone();
-
-            // Parse the journal content
-            let parser = jrnrvw::parser::JournalParser::new(content);
-            if let Ok(parsed) = parser.parse() {
-                let extractor = jrnrvw::parser::MetadataExtractor::new(parsed.sections);
-
-                entry.task = extractor.extract_task();
-                entry.activities = extractor.extract_activities();
-                entry.notes = extractor.extract_notes();
-                entry.time_spent = extractor.extract_time_spent();
+    #[cfg(feature = "explain")]
+    if cli.explain {
+        jrnrvw::analyzer::explain_entries(&mut entries);
+    }
 
-                // Override repository if specified in journal
-                if let Some(repo) = extractor.extract_repository() {
-                    entry.repository = Some(repo);
-                }
-            }
-        }
+    if let Some(Command::Search(ref args)) = cli.command {
+        return search(entries, args, &cli, &root_path, output_format, colored);
     }
 
     // Build filter from CLI arguments
     let filter = build_filter(&cli)?;
 
-    // Build report with grouping
-    let group_by = convert_group_by(cli.group_by);
+    // Build report with grouping; checks look at tasks per repository
+    let group_by = match cli.command {
+        Some(Command::Check(_)) => GroupBy::Repository,
+        _ if cli.ci => GroupBy::Repository,
+        _ => convert_group_by(cli.group_by),
+    };
     let sort_by = convert_sort_by(cli.sort_by);
 
     // Build report
-    let report = ReportBuilder::new(entries)
+    #[cfg_attr(not(feature = "explain"), allow(unused_mut))]
+    let mut report = ReportBuilder::new(entries)
         .with_filter(filter)
         .with_grouping(group_by, sort_by)
+        .with_week_start(week_start)
+        .with_stale_after(stale_after_days)
+        .with_signals(signals)
+        .with_references(references)
+        .with_dedup(dedup)
         .build()?;
 
+    #[cfg(feature = "explain")]
+    if cli.explain {
+        jrnrvw::analyzer::annotate_clusters(&mut report, group_by);
+    }
+
+    if !cli.quiet && !cli.ci {
+        for error in report.task_graph.cycle_errors() {
+            eprintln!("Error: {}", error);
+        }
+        for repository in &report.metrics.skipped {
+            eprintln!("Warning: no dated entries in repository '{}', left out of the throughput metrics", repository);
+        }
+    }
+
+    if let Some(Command::Export(ref args)) = cli.command {
+        return export_csv(&report, args, cli.quiet);
+    }
+
+    if let Some(Command::Notify(ref args)) = cli.command {
+        let channels = config.map(|c| c.notify).unwrap_or_default();
+        return notify(&report, args, &channels, cli.quiet);
+    }
+
+    if let Some(Command::Check(ref args)) = cli.command {
+        let rules = config.map(|c| c.check).unwrap_or_default();
+        return check(&report, args, &rules, output_format, cli.quiet);
+    }
+
+    if cli.ci {
+        let rules = config.map(|c| c.ci.rules).unwrap_or_else(|| CiRule::ALL.to_vec());
+        return ci(&report, &rules, cli.quiet);
+    }
+
     // Check if AI summarization is requested
     if cli.summarize {
+        let llm_cache = jrnrvw::llm::cache::default_dir().map(|dir| CacheSettings {
+            dir,
+            reuse: !cli.no_llm_cache,
+        });
+        let backend = jrnrvw::llm::CachedBackend::new(
+            jrnrvw::llm::create_backend(&llm_config(config.as_ref(), cli.llm))?,
+            llm_cache,
+        );
         if cli.verbose {
-            eprintln!("Generating AI summary using {}...", format!("{:?}", cli.llm).to_lowercase());
+            eprintln!("Generating AI summary using {}...", backend.name());
         }
 
-        // Get repositories and date range from report
-        let repositories = &report.repositories;
+        // Mask sensitive text before it reaches the prompt
+        let redact_config = config.as_ref().map(|c| c.redact.clone()).unwrap_or_default();
+        let (repositories, redactions) =
+            jrnrvw::llm::Redactor::new(&redact_config)?.redact_repositories(&report.repositories);
+        if cli.show_redactions {
+            eprint!("{}", redactions);
+        }
         let date_range = report.metadata.period.as_ref().map(|dr| (dr.from, dr.to));
 
-        // Convert CLI LlmArg to LlmProvider
-        let llm_provider = match cli.llm {
-            jrnrvw::cli::LlmArg::Claude => jrnrvw::llm::LlmProvider::Claude,
-            jrnrvw::cli::LlmArg::Codex => jrnrvw::llm::LlmProvider::Codex,
-        };
-
-        // Generate summary
-        let summary = jrnrvw::llm::summarize(llm_provider, repositories, date_range)?;
-
-        // Write summary output
+        // Generate summary, printing it as it arrives unless it goes to a file;
+        // the placeholders are put back either way
+        let stream = jrnrvw::llm::summarize_stream(&backend, &repositories, date_range)?;
+        let stream = redactions.restore_stream(stream);
         if let Some(ref summary_path) = cli.summary_output {
+            let summary = stream.collect::<Result<String>>()?;
             fs::write(summary_path, &summary)?;
             if !cli.quiet {
                 eprintln!("AI summary written to {}", summary_path.display());
             }
         } else {
-            println!("{}", summary);
-            io::stdout().flush()?;
+            jrnrvw::output::stream::write_stream(stream, &mut io::stdout().lock())?;
+        }
+        if cli.verbose {
+            if backend.is_enabled() {
+                eprintln!("{}", backend.stats());
+            } else {
+                eprintln!("LLM cache: disabled (no cache directory)");
+            }
         }
 
         // If --summary-output is specified, also generate the regular report
         if cli.summary_output.is_some() && cli.output.is_some() {
             let output_options = OutputOptions {
-                colored: !cli.no_color && atty::is(atty::Stream::Stdout),
+                colored,
                 verbose: cli.verbose,
                 include_activities: cli.with_activities || !cli.summary,
                 include_notes: cli.with_notes,
                 include_stats: cli.stats || !cli.summary,
                 summary_only: cli.summary,
+                max_entry_chars: cli.max_entry_chars,
             };
 
-            let output_format = convert_format(cli.format);
             let formatted = format_report(&report, output_format, &output_options)?;
 
             if let Some(output_path) = cli.output {
@@ -148,16 +356,16 @@ fn run() -> Result<()> {
 
     // Build output options
     let output_options = OutputOptions {
-        colored: !cli.no_color && atty::is(atty::Stream::Stdout),
+        colored,
         verbose: cli.verbose,
         include_activities: cli.with_activities || !cli.summary,
         include_notes: cli.with_notes,
         include_stats: cli.stats || !cli.summary,
         summary_only: cli.summary,
+        max_entry_chars: cli.max_entry_chars,
     };
 
     // Format output
-    let output_format = convert_format(cli.format);
     let formatted = format_report(&report, output_format, &output_options)?;
 
     // Write output
@@ -171,6 +379,244 @@ fn run() -> Result<()> {
         io::stdout().flush()?;
     }
 
+    if cli.fail_on_overdue && !report.overdue.is_empty() {
+        return Err(jrnrvw::JrnrvwError::Overdue(report.overdue.len()));
+    }
+
+    Ok(())
+}
+
+/// Run a `jrnrvw cache` subcommand
+fn run_cache_command(action: &CacheCommand) -> Result<()> {
+    let CacheCommand::Clear { llm } = *action;
+    let dir = if llm {
+        jrnrvw::llm::cache::default_dir()
+    } else {
+        CacheSettings::default_dir()
+    };
+    let Some(dir) = dir else {
+        println!("No cache directory to clear");
+        return Ok(());
+    };
+
+    if llm {
+        let removed = jrnrvw::llm::cache::clear(&dir)?;
+        println!("Removed {} cached summary file(s) from {}", removed, dir.display());
+    } else {
+        let removed = jrnrvw::analyzer::cache::clear(&dir)?;
+        println!("Removed {} index file(s) from {}", removed, dir.display());
+    }
+    Ok(())
+}
+
+/// Configuration from `--config`, else the default locations, with `profile`
+/// layered in
+fn load_config(cli: &Cli, profile: Option<&str>) -> Result<Option<jrnrvw::config::Config>> {
+    match cli.config {
+        Some(ref path) => jrnrvw::config::Config::load_profile(path, profile).map(Some),
+        None => jrnrvw::config::Config::load_default_profile(profile),
+    }
+}
+
+/// Run a `jrnrvw config` subcommand
+fn run_config_command(action: &ConfigCommand, cli: &Cli) -> Result<()> {
+    let ConfigCommand::Show { ref profile } = *action;
+    let profile = profile.as_deref().or(cli.config_profile.as_deref());
+
+    match load_config(cli, profile)? {
+        Some(config) => {
+            if let Some(ref source) = config.source {
+                println!("# File: {}", source.display());
+            }
+            match config.active_profile {
+                Some(ref name) => println!("# Profile: {}", name),
+                None => println!("# Profile: none"),
+            }
+            print!("\n{}", config.to_toml()?);
+        }
+        None => {
+            println!("# No config file found; these are the defaults");
+            print!("\n{}", jrnrvw::config::Config::default().to_toml()?);
+        }
+    }
+    Ok(())
+}
+
+/// Print the completion script `jrnrvw completions` asked for
+///
+/// Profile and channel names come from the config file; one that fails to
+/// load just leaves them out.
+fn run_completions(args: &CompletionsArgs, cli: &Cli) -> Result<()> {
+    let values = match load_config(cli, None) {
+        Ok(Some(config)) => jrnrvw::completions::DynamicValues {
+            profiles: config.profiles,
+            channels: config.notify.channels.into_keys().collect(),
+        },
+        _ => Default::default(),
+    };
+    jrnrvw::completions::write_completions(args.shell, &values, &mut io::stdout().lock());
+    Ok(())
+}
+
+/// Write the files `jrnrvw export` asked for
+fn export_csv(report: &jrnrvw::Report, args: &ExportArgs, quiet: bool) -> Result<()> {
+    let _span = jrnrvw::profile::span_with("render", || "export".to_string());
+
+    fs::write(&args.tasks_csv, jrnrvw::output::csv::tasks_csv(report)?)?;
+    if !quiet {
+        eprintln!("Tasks written to {}", args.tasks_csv.display());
+    }
+
+    if args.include_entries {
+        let stem = args.tasks_csv.file_stem().and_then(|s| s.to_str()).unwrap_or("tasks");
+        let entries_path = args.tasks_csv.with_file_name(format!("{}-entries.csv", stem));
+        fs::write(&entries_path, jrnrvw::output::csv::entries_csv(report)?)?;
+        if !quiet {
+            eprintln!("Entries written to {}", entries_path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the report against the baseline, or replace the baseline
+///
+/// Fails when a rule of severity `fail` is broken, after printing every
+/// broken rule.
+fn check(
+    report: &jrnrvw::Report,
+    args: &CheckArgs,
+    rules: &jrnrvw::config::settings::CheckConfig,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
+    use jrnrvw::models::{Baseline, CheckReport};
+
+    let today = chrono::Local::now().date_naive();
+    let current = Baseline::from_report(report, today);
+
+    if args.update_baseline {
+        current.save(&args.baseline)?;
+        if !quiet {
+            eprintln!(
+                "Baseline written to {} ({} open tasks)",
+                args.baseline.display(),
+                current.open_tasks.len()
+            );
+        }
+        return Ok(());
+    }
+
+    let baseline = Baseline::load(&args.baseline)?;
+    let active_days = jrnrvw::analyzer::check::active_days(report);
+    let violations = jrnrvw::analyzer::check::evaluate(rules, &baseline, &current, &active_days);
+    let results = CheckReport::new(today, baseline.as_of, violations);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        _ => print!("{}", results),
+    }
+
+    if results.passed {
+        Ok(())
+    } else {
+        Err(jrnrvw::JrnrvwError::CheckFailed(results.failures()))
+    }
+}
+
+/// Print the results `jrnrvw search` asked for
+fn search(
+    entries: Vec<jrnrvw::JournalEntry>,
+    args: &SearchArgs,
+    cli: &Cli,
+    root: &std::path::Path,
+    format: OutputFormat,
+    colored: bool,
+) -> Result<()> {
+    let query = jrnrvw::analyzer::SearchQuery::new(&args.query, args.fuzzy)?;
+
+    // The search's own filters win over the ones before `search`
+    let mut filter = build_filter(cli)?;
+    if let Some(ref repo) = args.repo {
+        filter = filter.with_repository(repo.clone());
+    }
+    if let Some(since) = args.since {
+        filter = filter.with_time_range(TimeRange::Since(since));
+    }
+    if let Some(ref tag) = args.tag {
+        filter = filter.with_tag(tag.clone());
+    }
+    let entries = filter.apply(entries)?;
+
+    let mut hits = jrnrvw::analyzer::search(&entries, &query, args.context);
+    if cli.verbose {
+        eprintln!("Searched {} entries, {} matched", entries.len(), hits.len());
+    }
+    if args.limit > 0 {
+        hits.truncate(args.limit);
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&hits)?),
+        _ => print!("{}", jrnrvw::output::search::format_results(&hits, &args.query, root, colored)),
+    }
+    Ok(())
+}
+
+/// Print the `--ci` diagnostics, failing if any is an error
+fn ci(report: &jrnrvw::Report, rules: &[CiRule], quiet: bool) -> Result<()> {
+    let diagnostics = jrnrvw::analyzer::ci::diagnostics(report, rules);
+    let mut stdout = io::stdout().lock();
+    for diagnostic in &diagnostics {
+        writeln!(stdout, "{}", diagnostic)?;
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Error).count();
+    if !quiet {
+        eprintln!("{} error(s), {} warning(s)", errors, diagnostics.len() - errors);
+    }
+    match errors {
+        0 => Ok(()),
+        errors => Err(jrnrvw::JrnrvwError::CiViolations(errors)),
+    }
+}
+
+/// Post the digest `jrnrvw notify` asked for
+fn notify(
+    report: &jrnrvw::Report,
+    args: &NotifyArgs,
+    channels: &jrnrvw::config::settings::NotifyConfig,
+    quiet: bool,
+) -> Result<()> {
+    use jrnrvw::output::notify::{Digest, Notifier};
+
+    let channel = channels.channel(&args.channel_config)?;
+    let date = args
+        .date
+        .unwrap_or_else(|| chrono::Local::now().date_naive() - chrono::Duration::days(1));
+    let digest = Digest::from_report(report, date);
+    let payloads = digest.payloads(channel.kind)?;
+
+    if args.dry_run {
+        for payload in &payloads {
+            println!("{}", serde_json::to_string_pretty(payload)?);
+        }
+        return Ok(());
+    }
+
+    let notifier = Notifier {
+        max_attempts: channel.max_attempts,
+        ..Notifier::new(channel.resolve_url(&args.channel_config)?)
+    };
+    notifier.send(&payloads)?;
+    if !quiet {
+        eprintln!(
+            "Posted {} message(s) to '{}': {}",
+            payloads.len(),
+            args.channel_config,
+            digest.summary()
+        );
+    }
     Ok(())
 }
 
@@ -191,9 +637,11 @@ fn build_filter(cli: &Cli) -> Result<EntryFilter> {
         Some(TimeRange::ActivityDays)
     } else if let (Some(from), Some(to)) = (cli.from, cli.to) {
         Some(TimeRange::Custom(from, to))
+    } else if let (Some(since), Some(until)) = (cli.since, cli.until) {
+        Some(TimeRange::Custom(since, until))
     } else if let Some(date) = cli.since {
         Some(TimeRange::Since(date))
-    } else if let Some(date) = cli.before {
+    } else if let Some(date) = cli.until.or(cli.before) {
         Some(TimeRange::Before(date))
     } else {
         None
@@ -203,6 +651,10 @@ fn build_filter(cli: &Cli) -> Result<EntryFilter> {
         filter = filter.with_time_range(range);
     }
 
+    if cli.strict_dates {
+        filter = filter.with_strict_dates();
+    }
+
     // Repository filter
     if let Some(repo) = &cli.repo {
         filter = filter.with_repository(repo.clone());
@@ -213,12 +665,25 @@ fn build_filter(cli: &Cli) -> Result<EntryFilter> {
         filter = filter.with_task(task.clone());
     }
 
+    // Project filter
+    if let Some(project) = &cli.project {
+        filter = filter.with_project(project.clone());
+    }
+
+    // Author filter
+    if let Some(author) = &cli.author {
+        filter = filter.with_author(author.clone());
+    }
+
     Ok(filter)
 }
 
 fn convert_group_by(arg: jrnrvw::cli::GroupByArg) -> GroupBy {
     match arg {
         jrnrvw::cli::GroupByArg::Repo => GroupBy::Repository,
+        jrnrvw::cli::GroupByArg::Project => GroupBy::Project,
+        jrnrvw::cli::GroupByArg::Tag => GroupBy::Tag,
+        jrnrvw::cli::GroupByArg::Author => GroupBy::Author,
         jrnrvw::cli::GroupByArg::Task => GroupBy::Task,
         jrnrvw::cli::GroupByArg::Date => GroupBy::Date,
         jrnrvw::cli::GroupByArg::Week => GroupBy::Week,
@@ -234,6 +699,19 @@ fn convert_sort_by(arg: jrnrvw::cli::SortByArg) -> SortBy {
     }
 }
 
+/// The config's `[llm]` settings, with the backend overridden from the command line
+fn llm_config(config: Option<&jrnrvw::config::Config>, backend: Option<jrnrvw::cli::LlmArg>) -> jrnrvw::config::settings::LlmConfig {
+    let mut llm = config.map(|c| c.llm.clone()).unwrap_or_default();
+    if let Some(arg) = backend {
+        llm.backend = match arg {
+            jrnrvw::cli::LlmArg::Claude => jrnrvw::llm::LlmProvider::Claude,
+            jrnrvw::cli::LlmArg::Codex => jrnrvw::llm::LlmProvider::Codex,
+            jrnrvw::cli::LlmArg::Ollama => jrnrvw::llm::LlmProvider::Ollama,
+        };
+    }
+    llm
+}
+
 fn convert_format(arg: jrnrvw::cli::FormatArg) -> OutputFormat {
     match arg {
         jrnrvw::cli::FormatArg::Text => OutputFormat::Text,
@@ -241,6 +719,7 @@ fn convert_format(arg: jrnrvw::cli::FormatArg) -> OutputFormat {
         jrnrvw::cli::FormatArg::Json => OutputFormat::Json,
         jrnrvw::cli::FormatArg::Html => OutputFormat::Html,
         jrnrvw::cli::FormatArg::Csv => OutputFormat::Csv,
+        jrnrvw::cli::FormatArg::Dot => OutputFormat::Dot,
     }
 }
 
@@ -249,6 +728,7 @@ fn format_report(
     format: OutputFormat,
     options: &OutputOptions,
 ) -> Result<String> {
+    let _span = jrnrvw::profile::span_with("render", || format!("{:?}", format));
     match format {
         OutputFormat::Text => {
             let formatter = jrnrvw::output::text::TextFormatter::new();
@@ -270,5 +750,9 @@ fn format_report(
             let formatter = jrnrvw::output::csv::CsvFormatter::new();
             formatter.format(report, options)
         }
+        OutputFormat::Dot => {
+            let formatter = jrnrvw::output::dot::DotFormatter::new();
+            formatter.format(report, options)
+        }
     }
 }
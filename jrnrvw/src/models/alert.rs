@@ -0,0 +1,39 @@
+//! Tasks that need attention: overdue or gone stale
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Unfinished task whose due date has passed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverdueTask {
+    /// Repository the task belongs to
+    pub repository: String,
+
+    /// Task name
+    pub task: String,
+
+    /// Due date, from the newest entry that sets one
+    pub due: NaiveDate,
+
+    /// Days since the due date
+    pub days_overdue: i64,
+}
+
+/// Unfinished task nobody has written about for a while
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleTask {
+    /// Repository the task belongs to
+    pub repository: String,
+
+    /// Task name
+    pub task: String,
+
+    /// Date of the task's first entry
+    pub opened: NaiveDate,
+
+    /// Date of the task's newest entry
+    pub last_entry: NaiveDate,
+
+    /// Days from the first entry to the report's newest entry
+    pub days_open: i64,
+}
@@ -0,0 +1,270 @@
+//! Journal hygiene checks against a committed baseline
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+use super::{Report, TaskStatus};
+use crate::error::{JrnrvwError, Result};
+
+/// State of the journals that later checks are compared against
+///
+/// This is the file `jrnrvw check --update-baseline` writes, meant to be
+/// committed next to the journals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Day the baseline was taken
+    pub as_of: NaiveDate,
+
+    /// Tasks not done as of that day, by repository and name
+    pub open_tasks: Vec<OpenTask>,
+}
+
+impl Baseline {
+    /// The open tasks of a report grouped by repository, as of `as_of`
+    ///
+    /// Open and stale tasks, and tasks without any status, count as open.
+    pub fn from_report(report: &Report, as_of: NaiveDate) -> Self {
+        let mut open_tasks: Vec<OpenTask> = report
+            .repositories
+            .iter()
+            .flat_map(|repo| repo.tasks.iter().map(move |task| (repo, task)))
+            .filter(|(_, task)| task.status_as_of(as_of) != Some(TaskStatus::Done))
+            .filter_map(|(repo, task)| {
+                task.date_range().map(|(opened, _)| OpenTask {
+                    repository: repo.name.clone(),
+                    task: task.name.clone(),
+                    opened,
+                })
+            })
+            .collect();
+        open_tasks.sort();
+
+        Self { as_of, open_tasks }
+    }
+
+    /// Read a baseline written by [`Baseline::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            JrnrvwError::InvalidArgument(format!(
+                "can't read baseline {}: {} (run `jrnrvw check --update-baseline` to create it)",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Write the baseline as pretty-printed JSON, ending in a newline
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(self)?))?;
+        Ok(())
+    }
+}
+
+/// A task that is not done yet
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OpenTask {
+    pub repository: String,
+
+    pub task: String,
+
+    /// Date of the task's first entry
+    pub opened: NaiveDate,
+}
+
+impl fmt::Display for OpenTask {
+    /// `api: Fix login (opened 2025-11-03)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} (opened {})", self.repository, self.task, self.opened)
+    }
+}
+
+/// A rule `jrnrvw check` holds the journals to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckRule {
+    /// Open tasks grew by more than allowed since the baseline
+    OpenTaskGrowth,
+
+    /// A task has been open for longer than allowed
+    TaskAge,
+
+    /// Too many workdays in a row went without an entry
+    MissingEntries,
+}
+
+impl fmt::Display for CheckRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckRule::OpenTaskGrowth => write!(f, "open_task_growth"),
+            CheckRule::TaskAge => write!(f, "task_age"),
+            CheckRule::MissingEntries => write!(f, "missing_entries"),
+        }
+    }
+}
+
+/// What breaking a `jrnrvw check` rule does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// The rule is not checked
+    Off,
+
+    /// Reported, but the check still passes
+    Warn,
+
+    /// Reported, and the check exits non-zero
+    #[default]
+    Fail,
+}
+
+impl fmt::Display for RuleSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSeverity::Off => write!(f, "off"),
+            RuleSeverity::Warn => write!(f, "warn"),
+            RuleSeverity::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// A rule the journals broke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub rule: CheckRule,
+
+    pub severity: RuleSeverity,
+
+    /// What went wrong, in a sentence
+    pub message: String,
+
+    /// Tasks that broke the rule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<OpenTask>,
+
+    /// Days that broke the rule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dates: Vec<NaiveDate>,
+}
+
+/// Outcome of `jrnrvw check`, also its `--format json` output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckReport {
+    /// Day the journals were checked as of
+    pub as_of: NaiveDate,
+
+    /// Day the baseline was taken
+    pub baseline: NaiveDate,
+
+    /// No rule of severity `fail` was broken
+    pub passed: bool,
+
+    /// Broken rules, in the order the rules are listed
+    pub violations: Vec<Violation>,
+}
+
+impl CheckReport {
+    /// Results of checking as of `as_of` against `baseline`
+    pub fn new(as_of: NaiveDate, baseline: NaiveDate, violations: Vec<Violation>) -> Self {
+        let passed = violations.iter().all(|v| v.severity != RuleSeverity::Fail);
+        Self { as_of, baseline, passed, violations }
+    }
+
+    /// Violations of severity `fail`
+    pub fn failures(&self) -> usize {
+        self.violations.iter().filter(|v| v.severity == RuleSeverity::Fail).count()
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Checked as of {} against the baseline of {}: {}",
+            self.as_of,
+            self.baseline,
+            if self.passed { "passed" } else { "failed" }
+        )?;
+        for violation in &self.violations {
+            writeln!(f, "  [{}] {}: {}", violation.severity, violation.rule, violation.message)?;
+            for task in &violation.tasks {
+                writeln!(f, "    {}", task)?;
+            }
+            if !violation.dates.is_empty() {
+                let dates: Vec<String> = violation.dates.iter().map(NaiveDate::to_string).collect();
+                writeln!(f, "    {}", dates.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{JournalEntry, Repository, Task};
+    use std::path::PathBuf;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn task(name: &str, days: &[&str], status: Option<&str>) -> Task {
+        let mut task = Task::new(name.to_string());
+        for day in days {
+            let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", day)), date(day));
+            entry.status = status.map(str::to_string);
+            task.add_entry(entry);
+        }
+        task
+    }
+
+    #[test]
+    fn test_baseline_from_report() {
+        let mut api = Repository::new("api".to_string(), None);
+        api.add_task(task("auth", &["2025-11-03", "2025-11-10"], None));
+        api.add_task(task("deploy", &["2025-11-04"], Some("done")));
+        let mut web = Repository::new("web".to_string(), None);
+        web.add_task(task("css", &["2025-09-01"], Some("open")));
+        let report = Report::new(vec![web, api], None);
+
+        let baseline = Baseline::from_report(&report, date("2025-11-20"));
+        let tasks: Vec<String> = baseline.open_tasks.iter().map(OpenTask::to_string).collect();
+        assert_eq!(tasks, vec!["api: auth (opened 2025-11-03)", "web: css (opened 2025-09-01)"]);
+    }
+
+    #[test]
+    fn test_baseline_round_trips() {
+        let baseline = Baseline {
+            as_of: date("2025-11-20"),
+            open_tasks: vec![OpenTask { repository: "api".to_string(), task: "auth".to_string(), opened: date("2025-11-03") }],
+        };
+        let path = std::env::temp_dir().join("jrnrvw_baseline_round_trip.json");
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+        std::fs::remove_file(&path).ok();
+
+        let missing = Baseline::load(&path).unwrap_err().to_string();
+        assert!(missing.contains("--update-baseline"), "{}", missing);
+    }
+
+    #[test]
+    fn test_passed_ignores_warnings() {
+        let violation = |severity| Violation {
+            rule: CheckRule::TaskAge,
+            severity,
+            message: String::new(),
+            tasks: vec![],
+            dates: vec![],
+        };
+        let report = CheckReport::new(date("2025-11-20"), date("2025-11-01"), vec![violation(RuleSeverity::Warn)]);
+        assert!(report.passed);
+        assert_eq!(report.failures(), 0);
+
+        let report = CheckReport::new(date("2025-11-20"), date("2025-11-01"), vec![violation(RuleSeverity::Fail)]);
+        assert!(!report.passed);
+        assert_eq!(report.failures(), 1);
+    }
+}
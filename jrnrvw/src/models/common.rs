@@ -7,6 +7,9 @@ use crate::error::{JrnrvwError, Result};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GroupBy {
     Repository,
+    Project,
+    Tag,
+    Author,
     Task,
     Date,
     Week,
@@ -19,6 +22,9 @@ impl FromStr for GroupBy {
     fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "repo" | "repository" => Ok(GroupBy::Repository),
+            "project" => Ok(GroupBy::Project),
+            "tag" => Ok(GroupBy::Tag),
+            "author" => Ok(GroupBy::Author),
             "task" => Ok(GroupBy::Task),
             "date" => Ok(GroupBy::Date),
             "week" => Ok(GroupBy::Week),
@@ -61,6 +67,7 @@ pub enum OutputFormat {
     Json,
     Html,
     Csv,
+    Dot,
 }
 
 impl FromStr for OutputFormat {
@@ -73,6 +80,7 @@ impl FromStr for OutputFormat {
             "json" => Ok(OutputFormat::Json),
             "html" => Ok(OutputFormat::Html),
             "csv" => Ok(OutputFormat::Csv),
+            "dot" => Ok(OutputFormat::Dot),
             _ => Err(JrnrvwError::InvalidArgument(
                 format!("Invalid output format: {}", s)
             )),
@@ -87,6 +95,8 @@ mod tests {
     #[test]
     fn test_group_by_from_str() {
         assert_eq!("repo".parse::<GroupBy>().unwrap(), GroupBy::Repository);
+        assert_eq!("project".parse::<GroupBy>().unwrap(), GroupBy::Project);
+        assert_eq!("tag".parse::<GroupBy>().unwrap(), GroupBy::Tag);
         assert_eq!("task".parse::<GroupBy>().unwrap(), GroupBy::Task);
         assert_eq!("date".parse::<GroupBy>().unwrap(), GroupBy::Date);
         assert!(GroupBy::from_str("invalid").is_err());
@@ -0,0 +1,65 @@
+//! Diagnostics printed by `--ci`, one `file:line: severity: message` each
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Rule `--ci` enforces, chosen with the config's `[ci] rules`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CiRule {
+    /// Journals whose content does not parse (errors) and malformed
+    /// directives (warnings)
+    Parse,
+
+    /// Unfinished tasks past their due date
+    Overdue,
+
+    /// Entries with no date in their filename or front matter
+    Undated,
+}
+
+impl CiRule {
+    /// Every rule, the default set
+    pub const ALL: [CiRule; 3] = [CiRule::Parse, CiRule::Overdue, CiRule::Undated];
+}
+
+/// How serious a diagnostic is: only errors fail a CI run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for DiagnosticLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticLevel::Error => write!(f, "error"),
+            DiagnosticLevel::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem with one journal file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Journal file
+    pub path: PathBuf,
+
+    /// 1-based line the problem is on
+    pub line: usize,
+
+    pub level: DiagnosticLevel,
+
+    /// Rule that found the problem
+    pub rule: CiRule,
+
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}: {}", self.path.display(), self.line, self.level, self.message)
+    }
+}
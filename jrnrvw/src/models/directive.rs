@@ -0,0 +1,71 @@
+//! In-file directives recognised by the journal parser
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+/// Kind of `<!-- jrnrvw:... -->` directive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirectiveKind {
+    /// `jrnrvw:ignore` - skip the rest of the entry
+    Ignore,
+
+    /// `jrnrvw:ignore-section` - skip content until the next heading
+    IgnoreSection,
+
+    /// `jrnrvw:archive` - keep the entry in stats but out of LLM prompts
+    Archive,
+}
+
+impl DirectiveKind {
+    /// Name used inside the directive comment
+    pub fn name(&self) -> &'static str {
+        match self {
+            DirectiveKind::Ignore => "ignore",
+            DirectiveKind::IgnoreSection => "ignore-section",
+            DirectiveKind::Archive => "archive",
+        }
+    }
+
+    /// Look up a directive by the name used inside the comment
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ignore" => Some(DirectiveKind::Ignore),
+            "ignore-section" => Some(DirectiveKind::IgnoreSection),
+            "archive" => Some(DirectiveKind::Archive),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DirectiveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "jrnrvw:{}", self.name())
+    }
+}
+
+/// A directive that took effect while parsing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Directive {
+    /// Which directive fired
+    pub kind: DirectiveKind,
+
+    /// 1-based line number of the directive comment
+    pub line: usize,
+}
+
+/// A malformed directive found while parsing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectiveWarning {
+    /// 1-based line number of the directive comment
+    pub line: usize,
+
+    /// What was wrong with it
+    pub message: String,
+}
+
+impl fmt::Display for DirectiveWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
@@ -0,0 +1,26 @@
+//! Tasks folded together as duplicates
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Task names in one repository that were taken for the same task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskCluster {
+    /// Repository the names were written in
+    pub repository: String,
+
+    /// Name the task is reported under, as written in its newest entry
+    pub task: String,
+
+    /// Every name folded into the task, first seen first
+    pub members: Vec<String>,
+
+    /// Date of the oldest entry naming one of the members
+    pub first_seen: NaiveDate,
+
+    /// Date of the newest entry naming one of the members
+    pub last_seen: NaiveDate,
+
+    /// Entries naming one of the members
+    pub mentions: usize,
+}
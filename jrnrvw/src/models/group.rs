@@ -0,0 +1,36 @@
+//! Activity per group of the report
+
+use serde::{Deserialize, Serialize};
+
+use super::DateRange;
+
+/// Entries written and tasks opened and finished in one group
+///
+/// Groups are whatever `--group-by` asked for: weeks, months, days,
+/// repositories, tags and so on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupSummary {
+    /// Group name as the report lists it (`Week 45, 2025`, `November 2025`,
+    /// a repository or a tag)
+    pub name: String,
+
+    /// Days the group covers, for weeks, months and days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<DateRange>,
+
+    /// Entries in the group
+    pub entries: usize,
+
+    /// Tasks whose first entry is in the group
+    pub tasks_opened: usize,
+
+    /// Tasks finished by an entry in the group
+    pub tasks_completed: usize,
+}
+
+impl GroupSummary {
+    /// A group with no activity
+    pub fn empty(name: impl Into<String>, period: Option<DateRange>) -> Self {
+        Self { name: name.into(), period, entries: 0, tasks_opened: 0, tasks_completed: 0 }
+    }
+}
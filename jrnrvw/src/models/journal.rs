@@ -1,15 +1,26 @@
 //! Journal entry model
 
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::directive::{Directive, DirectiveWarning};
+use super::time::TimeLogged;
+#[cfg(feature = "explain")]
+use super::provenance::EntryExplanation;
+
 /// Represents a single journal entry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct JournalEntry {
-    /// Date extracted from filename
+    /// Date from the front matter, else extracted from filename
     pub date: NaiveDate,
 
+    /// Neither the front matter nor the filename gave a date, so `date` is
+    /// the file's modification date instead
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub undated: bool,
+
     /// Filename of the journal
     pub filename: String,
 
@@ -20,6 +31,15 @@ pub struct JournalEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
+    /// Tags set in the front matter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Front matter fields with no entry field of their own, written out
+    /// sorted by key so reports are stable
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "serialize_sorted")]
+    pub metadata: HashMap<String, String>,
+
     /// Task or project name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub task: Option<String>,
@@ -28,6 +48,44 @@ pub struct JournalEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repository: Option<String>,
 
+    /// Remote URL of the repository the journal lives in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    /// Project tag (from front matter or tagging rules)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
+    /// Person who wrote the journal (front matter, path, or git)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Last commit touching the file, when git metadata was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitMetadata>,
+
+    /// Task status set in the front matter (e.g. `open`, `done`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// Task priority set in the front matter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Task id set in the front matter with `id:`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+
+    /// Ids of the tasks this entry's task depends on (`blocked by: <id>`,
+    /// `depends on #42`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Date the task is due (`due: 2024-05-01` in the front matter or body,
+    /// or `📅 2024-05-01`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+
     /// List of activities from the journal
     #[serde(default)]
     pub activities: Vec<String>,
@@ -40,9 +98,80 @@ pub struct JournalEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_spent: Option<String>,
 
+    /// Time spent parsed into minutes, from the Time Spent section or
+    /// durations marked in the activities and notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_logged: Option<TimeLogged>,
+
     /// Raw content of the journal file
     #[serde(skip)]
     pub raw_content: String,
+
+    /// Marked `jrnrvw:archive`: counted in stats, left out of LLM prompts
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+
+    /// Written by an author excluded from LLM prompts: counted in stats,
+    /// left out of prompts like an archived entry
+    #[serde(skip)]
+    pub private: bool,
+
+    /// Front matter set `jrnrvw.skip`, so the entry is left out of the
+    /// analysis
+    #[serde(skip)]
+    pub skipped: bool,
+
+    /// Directives that took effect while parsing
+    #[serde(skip)]
+    pub directives: Vec<Directive>,
+
+    /// Malformed directives found while parsing
+    #[serde(skip)]
+    pub directive_warnings: Vec<DirectiveWarning>,
+
+    /// Why the file's content did not parse, leaving the entry only what
+    /// its filename gives
+    #[serde(skip)]
+    pub parse_error: Option<ParseFailure>,
+
+    /// Provenance of the extracted fields, set only in explain mode
+    #[cfg(feature = "explain")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Box<EntryExplanation>>,
+}
+
+/// Why a journal's content did not parse
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseFailure {
+    /// 1-based line the parser stopped at; 1 when it named none
+    pub line: usize,
+
+    /// What was wrong
+    pub reason: String,
+}
+
+/// Last commit touching a journal file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitMetadata {
+    /// Full commit hash
+    pub commit: String,
+
+    /// Commit author name
+    pub author: String,
+
+    /// Author date, in the author's time zone
+    pub date: DateTime<FixedOffset>,
+}
+
+impl GitMetadata {
+    /// Abbreviated hash for display
+    pub fn short_commit(&self) -> &str {
+        self.commit.get(..7).unwrap_or(&self.commit)
+    }
+}
+
+fn serialize_sorted<S: serde::Serializer>(map: &HashMap<String, String>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<std::collections::BTreeMap<_, _>>())
 }
 
 impl JournalEntry {
@@ -56,15 +185,36 @@ impl JournalEntry {
 
         Self {
             date,
+            undated: false,
             filename,
             filepath,
             title: None,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
             task: None,
             repository: None,
+            remote: None,
+            project: None,
+            author: None,
+            git: None,
+            status: None,
+            priority: None,
+            task_id: None,
+            depends_on: Vec::new(),
+            due: None,
             activities: Vec::new(),
             notes: None,
             time_spent: None,
+            time_logged: None,
             raw_content: String::new(),
+            archived: false,
+            private: false,
+            skipped: false,
+            directives: Vec::new(),
+            directive_warnings: Vec::new(),
+            parse_error: None,
+            #[cfg(feature = "explain")]
+            explanation: None,
         }
     }
 
@@ -84,6 +234,22 @@ impl JournalEntry {
         self.task.as_ref().map_or(false, |t| t == task_name)
     }
 
+    /// Check if this entry is tagged with a project
+    pub fn has_project(&self, project: &str) -> bool {
+        self.project.as_deref() == Some(project)
+    }
+
+    /// Check if this entry was written by `author`, ignoring case
+    pub fn has_author(&self, author: &str) -> bool {
+        self.author.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(author))
+    }
+
+    /// Check if this entry is tagged `tag` or belongs to project `tag`,
+    /// ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().chain(self.project.as_ref()).any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
     /// Get a short description for display
     pub fn description(&self) -> String {
         self.title
@@ -214,4 +380,16 @@ mod tests {
 
         assert!(!entry.is_in_repo("myrepo"));
     }
+
+    #[test]
+    fn test_has_project() {
+        let path = PathBuf::from("test.md");
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut entry = JournalEntry::new(path, date);
+        assert!(!entry.has_project("infra"));
+
+        entry.project = Some("infra".to_string());
+        assert!(entry.has_project("infra"));
+        assert!(!entry.has_project("web"));
+    }
 }
@@ -0,0 +1,51 @@
+//! Throughput metrics: tasks finished per week and open tasks over time
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Weekly throughput of the report's tasks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metrics {
+    /// One row per week from the week of the first task to the week of the
+    /// newest entry, oldest first; weeks without activity are kept
+    pub weeks: Vec<WeekMetrics>,
+
+    /// Mean days from a task's first entry to the day it was finished,
+    /// `None` until some task is finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub average_days_to_complete: Option<f64>,
+
+    /// Repositories left out because none of their entries has a date
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+impl Metrics {
+    /// Whether there is nothing to report
+    pub fn is_empty(&self) -> bool {
+        self.weeks.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Tasks finished each week, oldest first
+    pub fn completed_series(&self) -> Vec<usize> {
+        self.weeks.iter().map(|week| week.completed).collect()
+    }
+
+    /// Tasks open at the end of each week, oldest first
+    pub fn open_series(&self) -> Vec<usize> {
+        self.weeks.iter().map(|week| week.open).collect()
+    }
+}
+
+/// Throughput of one week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeekMetrics {
+    /// First day of the week
+    pub week: NaiveDate,
+
+    /// Tasks finished during the week
+    pub completed: usize,
+
+    /// Tasks opened by the end of the week and not finished by then
+    pub open: usize,
+}
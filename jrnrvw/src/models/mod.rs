@@ -3,10 +3,36 @@
 pub mod journal;
 pub mod repository;
 pub mod report;
+pub mod check;
 pub mod common;
+pub mod directive;
+pub mod signal;
+pub mod reference;
+pub mod time;
+pub mod task_graph;
+pub mod duplicate;
+pub mod group;
+pub mod alert;
+pub mod metrics;
+pub mod diagnostic;
+#[cfg(feature = "explain")]
+pub mod provenance;
 
 // Re-export main types
-pub use journal::JournalEntry;
-pub use repository::{Repository, Task};
-pub use report::{Report, ReportMetadata, Statistics, DateRange};
+pub use journal::{GitMetadata, JournalEntry, ParseFailure};
+pub use repository::{Repository, Task, TaskStatus, STALE_AFTER_DAYS};
+pub use check::{Baseline, CheckReport, CheckRule, OpenTask, RuleSeverity, Violation};
+pub use report::{AuthorSummary, Report, ReportMetadata, ReportWarnings, Statistics, DateRange};
 pub use common::{GroupBy, SortBy, OutputFormat};
+pub use directive::{Directive, DirectiveKind, DirectiveWarning};
+pub use signal::{Severity, Signal, SignalKind, SignalSource};
+pub use reference::{Dangling, EntryRef, Reference, ReferenceGraph, ReferenceKind};
+pub use time::{TimeLogged, TimeRow, TimeSummary};
+pub use duplicate::TaskCluster;
+pub use group::GroupSummary;
+pub use alert::{OverdueTask, StaleTask};
+pub use metrics::{Metrics, WeekMetrics};
+pub use diagnostic::{CiRule, Diagnostic, DiagnosticLevel};
+pub use task_graph::{TaskDependency, TaskGraph, TaskNode, TaskOrder, UnresolvedDependency};
+#[cfg(feature = "explain")]
+pub use provenance::{Cluster, EntryExplanation, MatchRule, Provenance, RejectReason, RejectedLine, StatusMarker};
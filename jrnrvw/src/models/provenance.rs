@@ -0,0 +1,184 @@
+//! Provenance annotations for `--explain` reports
+//!
+//! Explanations record where each extracted value came from and why nearby
+//! lines that looked like tasks were not extracted. They are only built when
+//! explain mode is requested; ordinary runs leave them unset.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::DirectiveKind;
+
+/// Rule that picked a value out of a journal line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchRule {
+    /// First line of the `## Task` section
+    TaskSection,
+
+    /// First line of the `## Repository` section
+    RepositorySection,
+
+    /// `project:` key in the front matter
+    FrontMatter,
+
+    /// `-`, `*` or `+` list item in the Activities section
+    BulletItem,
+
+    /// `1.` or `1)` list item in the Activities section
+    NumberedItem,
+
+    /// Unbulleted line in the Activities section
+    PlainLine,
+}
+
+impl MatchRule {
+    /// Short description used in text reports
+    pub fn description(&self) -> &'static str {
+        match self {
+            MatchRule::TaskSection => "## Task section",
+            MatchRule::RepositorySection => "## Repository section",
+            MatchRule::FrontMatter => "front matter",
+            MatchRule::BulletItem => "bullet item",
+            MatchRule::NumberedItem => "numbered item",
+            MatchRule::PlainLine => "plain line",
+        }
+    }
+}
+
+impl fmt::Display for MatchRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Checkbox at the start of an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusMarker {
+    /// `[x]` or `[X]`
+    Done,
+
+    /// `[ ]`
+    Open,
+}
+
+impl fmt::Display for StatusMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusMarker::Done => f.write_str("done [x]"),
+            StatusMarker::Open => f.write_str("open [ ]"),
+        }
+    }
+}
+
+/// Source of one extracted value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// 1-based line in the journal file
+    pub line: usize,
+
+    /// The line as written, trimmed
+    pub raw: String,
+
+    /// Rule that matched the line
+    pub rule: MatchRule,
+
+    /// Checkbox that set the activity's state, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusMarker>,
+}
+
+/// Why a line that looked like a task was not extracted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum RejectReason {
+    /// Dropped by an `ignore` or `ignore-section` directive
+    Ignored {
+        directive: DirectiveKind,
+        directive_line: usize,
+    },
+
+    /// In a section other than Activities
+    OutsideActivities { section: String },
+
+    /// Above the first `##` heading
+    BeforeFirstSection,
+
+    /// Starts with a number that is not followed by `.` or `)`
+    NumberWithoutDelimiter,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectReason::Ignored { directive, directive_line } => {
+                write!(f, "dropped by {} on line {}", directive, directive_line)
+            }
+            RejectReason::OutsideActivities { section } => {
+                write!(f, "in '{}' section, only Activities are extracted", section)
+            }
+            RejectReason::BeforeFirstSection => f.write_str("before the first ## heading"),
+            RejectReason::NumberWithoutDelimiter => {
+                f.write_str("starts with a number but has no '.' or ')' after it")
+            }
+        }
+    }
+}
+
+/// A line that looked like a task but was left out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedLine {
+    /// 1-based line in the journal file
+    pub line: usize,
+
+    /// The line as written, trimmed
+    pub raw: String,
+
+    /// Why it was left out
+    pub reason: RejectReason,
+}
+
+/// Report group an entry was placed in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cluster {
+    /// Top-level group (repository, project or period)
+    pub group: String,
+
+    /// Task within the group
+    pub task: String,
+
+    /// Entries in the task, this one included
+    pub size: usize,
+
+    /// How the group and task keys were chosen
+    pub reason: String,
+}
+
+/// Provenance for everything extracted from one journal entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryExplanation {
+    /// Where the task name came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<Provenance>,
+
+    /// Where an explicit repository came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<Provenance>,
+
+    /// Where the front matter project tag came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<Provenance>,
+
+    /// One per extracted activity, in order
+    #[serde(default)]
+    pub activities: Vec<Provenance>,
+
+    /// Task-like lines that were not extracted
+    #[serde(default)]
+    pub rejected: Vec<RejectedLine>,
+
+    /// Group the entry joined in the report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<Cluster>,
+}
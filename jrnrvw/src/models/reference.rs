@@ -0,0 +1,308 @@
+//! Cross-repository references between journal entries
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A journal entry at either end of a reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryRef {
+    /// Repository the entry belongs to
+    pub repository: String,
+
+    /// Task the entry is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+
+    /// Date of the entry
+    pub date: NaiveDate,
+
+    /// Path to the journal file
+    pub path: PathBuf,
+}
+
+impl fmt::Display for EntryRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.repository, self.date)
+    }
+}
+
+/// How a reference was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceKind {
+    /// Reference syntax such as `ref:infra#2024-05-02`
+    Explicit,
+
+    /// The other repository's name appearing in the text
+    Mention,
+}
+
+/// Why a reference could not be resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dangling {
+    /// No journals were found for the repository
+    UnknownRepository,
+
+    /// The repository has no entry on the given date
+    UnknownDate,
+}
+
+impl fmt::Display for Dangling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dangling::UnknownRepository => f.write_str("unknown repository"),
+            Dangling::UnknownDate => f.write_str("no entry on that date"),
+        }
+    }
+}
+
+/// One edge of the reference graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+    /// Entry the reference was written in
+    pub source: EntryRef,
+
+    /// Repository referred to, as written
+    pub repository: String,
+
+    /// Date referred to, if the reference named one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+
+    /// Reference syntax or a bare mention
+    pub kind: ReferenceKind,
+
+    /// The line reads as the source task waiting on the other repository
+    pub blocking: bool,
+
+    /// Line the reference was found on
+    pub context: String,
+
+    /// Entry the reference resolved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<EntryRef>,
+
+    /// Set when the reference points at nothing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dangling: Option<Dangling>,
+}
+
+impl Reference {
+    /// What the reference points at, e.g. `infra 2024-05-02`
+    pub fn target_label(&self) -> String {
+        match (&self.target, self.date) {
+            (Some(target), _) => target.to_string(),
+            (None, Some(date)) => format!("{} {}", self.repository, date),
+            (None, None) => self.repository.clone(),
+        }
+    }
+}
+
+/// References between entries of different repositories
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceGraph {
+    /// All references, in source entry order
+    pub edges: Vec<Reference>,
+}
+
+impl ReferenceGraph {
+    /// Whether no references were found
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// References whose source task is blocked on another repository
+    pub fn blocking(&self) -> impl Iterator<Item = &Reference> {
+        self.edges.iter().filter(|r| r.blocking)
+    }
+
+    /// References that point at no entry
+    pub fn dangling(&self) -> impl Iterator<Item = &Reference> {
+        self.edges.iter().filter(|r| r.dangling.is_some())
+    }
+
+    /// Whether there is anything for a Dependencies section to show
+    pub fn has_dependencies(&self) -> bool {
+        self.edges.iter().any(|r| r.blocking || r.dangling.is_some()) || !self.cycles().is_empty()
+    }
+
+    /// Groups of repositories that depend on each other in a loop
+    ///
+    /// Each cycle lists its repositories sorted by name; cycles are sorted
+    /// by their first repository.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut graph: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for edge in &self.edges {
+            if let Some(target) = &edge.target {
+                graph.entry(edge.source.repository.as_str()).or_default().insert(target.repository.as_str());
+                graph.entry(target.repository.as_str()).or_default();
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = strongly_connected(&graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || graph[component[0]].contains(component[0])
+            })
+            .map(|component| {
+                let mut names: Vec<String> = component.into_iter().map(str::to_string).collect();
+                names.sort();
+                names
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+}
+
+/// Tarjan's algorithm, iterative so long chains can't overflow the stack
+pub(crate) fn strongly_connected<T: Ord + Copy>(graph: &BTreeMap<T, BTreeSet<T>>) -> Vec<Vec<T>> {
+    let mut state = Tarjan::default();
+
+    for &root in graph.keys() {
+        if state.index.contains_key(&root) {
+            continue;
+        }
+
+        // Nodes being visited, each with the neighbours it has left to visit
+        let mut work = vec![state.visit(root, graph)];
+        while let Some((node, pending)) = work.last_mut() {
+            let node = *node;
+            if let Some(next) = pending.pop() {
+                if !state.index.contains_key(&next) {
+                    work.push(state.visit(next, graph));
+                } else if state.on_stack.contains(&next) {
+                    state.lower(node, state.index[&next]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                state.lower(*parent, state.low[&node]);
+            }
+            if state.low[&node] == state.index[&node] {
+                state.pop_component(node);
+            }
+        }
+    }
+
+    state.components
+}
+
+struct Tarjan<T> {
+    index: BTreeMap<T, usize>,
+    low: BTreeMap<T, usize>,
+    on_stack: BTreeSet<T>,
+    stack: Vec<T>,
+    components: Vec<Vec<T>>,
+}
+
+impl<T> Default for Tarjan<T> {
+    fn default() -> Self {
+        Self {
+            index: BTreeMap::new(),
+            low: BTreeMap::new(),
+            on_stack: BTreeSet::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+}
+
+impl<T: Ord + Copy> Tarjan<T> {
+    fn visit(&mut self, node: T, graph: &BTreeMap<T, BTreeSet<T>>) -> (T, Vec<T>) {
+        let next = self.index.len();
+        self.index.insert(node, next);
+        self.low.insert(node, next);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+        (node, graph[&node].iter().rev().copied().collect())
+    }
+
+    fn lower(&mut self, node: T, value: usize) {
+        let low = self.low.get_mut(&node).expect("visited node");
+        *low = (*low).min(value);
+    }
+
+    fn pop_component(&mut self, root: T) {
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack.remove(&member);
+            component.push(member);
+            if member == root {
+                break;
+            }
+        }
+        self.components.push(component);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(repository: &str, day: u32) -> EntryRef {
+        EntryRef {
+            repository: repository.to_string(),
+            task: None,
+            date: NaiveDate::from_ymd_opt(2024, 5, day).unwrap(),
+            path: PathBuf::from(format!("{}/2024-05-{:02}.md", repository, day)),
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> Reference {
+        Reference {
+            source: entry(from, 1),
+            repository: to.to_string(),
+            date: None,
+            kind: ReferenceKind::Mention,
+            blocking: false,
+            context: String::new(),
+            target: Some(entry(to, 1)),
+            dangling: None,
+        }
+    }
+
+    #[test]
+    fn test_no_cycles_in_chain() {
+        let graph = ReferenceGraph { edges: vec![edge("a", "b"), edge("b", "c")] };
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_finds_cycles() {
+        let graph = ReferenceGraph {
+            edges: vec![
+                edge("web", "api"),
+                edge("api", "infra"),
+                edge("infra", "web"),
+                edge("docs", "web"),
+                edge("x", "y"),
+                edge("y", "x"),
+            ],
+        };
+        assert_eq!(
+            graph.cycles(),
+            vec![
+                vec!["api".to_string(), "infra".to_string(), "web".to_string()],
+                vec!["x".to_string(), "y".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dangling_edges_are_not_part_of_cycles() {
+        let mut back = edge("b", "a");
+        back.target = None;
+        back.dangling = Some(Dangling::UnknownDate);
+
+        let graph = ReferenceGraph { edges: vec![edge("a", "b"), back] };
+        assert!(graph.cycles().is_empty());
+        assert_eq!(graph.dangling().count(), 1);
+    }
+}
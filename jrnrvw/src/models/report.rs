@@ -2,9 +2,15 @@
 
 use serde::{Serialize, Deserialize};
 use chrono::{NaiveDate, DateTime, Utc};
-use super::Repository;
+use super::{DirectiveKind, GroupSummary, JournalEntry, Metrics, OverdueTask, ReferenceGraph, Repository, Signal, StaleTask, TaskCluster, TaskGraph, TimeSummary};
+use crate::error::Result;
 
 /// Complete report structure
+///
+/// This is also the `--format json` document, so field names are part of the
+/// output format: they are snake_case, timestamps are RFC3339 date-times and
+/// dates are RFC3339 full-dates (`yyyy-mm-dd`). Optional fields are omitted
+/// when unset rather than written as `null`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Report {
     /// Report metadata
@@ -15,6 +21,53 @@ pub struct Report {
 
     /// Summary statistics
     pub statistics: Statistics,
+
+    /// Parser warnings and directive activity
+    #[serde(default)]
+    pub warnings: ReportWarnings,
+
+    /// Trend and anomaly findings, most severe first
+    #[serde(default)]
+    pub signals: Vec<Signal>,
+
+    /// References between entries of different repositories
+    #[serde(default)]
+    pub references: ReferenceGraph,
+
+    /// Dependencies between tasks, empty unless some task names one
+    #[serde(default, skip_serializing_if = "TaskGraph::is_empty")]
+    pub task_graph: TaskGraph,
+
+    /// Task names folded together as duplicates, empty unless some were
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<TaskCluster>,
+
+    /// Entries and completed tasks per author, empty unless some entry has
+    /// an author
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub team: Vec<AuthorSummary>,
+
+    /// Time logged per repository, tag and day, absent unless some entry
+    /// logged time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<TimeSummary>,
+    /// Entries and tasks opened and finished per `--group-by` group, in
+    /// the order the report lists them; periods without activity are kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupSummary>,
+
+    /// Unfinished tasks past their due date, longest overdue first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overdue: Vec<OverdueTask>,
+
+    /// Unfinished tasks without a recent entry, longest open first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale: Vec<StaleTask>,
+
+    /// Tasks finished per week, their age when finished and open tasks
+    /// over time
+    #[serde(default, skip_serializing_if = "Metrics::is_empty")]
+    pub metrics: Metrics,
 }
 
 impl Report {
@@ -43,6 +96,17 @@ impl Report {
             metadata,
             repositories,
             statistics,
+            warnings: ReportWarnings::default(),
+            signals: Vec::new(),
+            references: ReferenceGraph::default(),
+            task_graph: TaskGraph::default(),
+            duplicates: Vec::new(),
+            team: Vec::new(),
+            time: None,
+            groups: Vec::new(),
+            overdue: Vec::new(),
+            stale: Vec::new(),
+            metrics: Metrics::default(),
         }
     }
 
@@ -51,6 +115,85 @@ impl Report {
         self.statistics = stats;
         self
     }
+
+    /// Set the warnings for this report
+    pub fn with_warnings(mut self, warnings: ReportWarnings) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Set the signals for this report
+    pub fn with_signals(mut self, signals: Vec<Signal>) -> Self {
+        self.signals = signals;
+        self
+    }
+
+    /// Set the cross-repository references for this report
+    pub fn with_references(mut self, references: ReferenceGraph) -> Self {
+        self.references = references;
+        self
+    }
+
+    /// Set the task dependency graph for this report
+    pub fn with_task_graph(mut self, task_graph: TaskGraph) -> Self {
+        self.task_graph = task_graph;
+        self
+    }
+
+    /// Set the duplicate task clusters for this report
+    pub fn with_duplicates(mut self, duplicates: Vec<TaskCluster>) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Set the per-author team summary for this report
+    pub fn with_team(mut self, team: Vec<AuthorSummary>) -> Self {
+        self.team = team;
+        self
+    }
+
+    /// Set the time summary for this report
+    pub fn with_time(mut self, time: Option<TimeSummary>) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Set the per-group activity for this report
+    pub fn with_groups(mut self, groups: Vec<GroupSummary>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Set the overdue and stale tasks for this report
+    pub fn with_alerts(mut self, overdue: Vec<OverdueTask>, stale: Vec<StaleTask>) -> Self {
+        self.overdue = overdue;
+        self.stale = stale;
+        self
+    }
+
+    /// Set the throughput metrics for this report
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Serialize the report as JSON, optionally pretty-printed
+    pub fn to_json(&self, pretty: bool) -> Result<String> {
+        let json = if pretty {
+            serde_json::to_string_pretty(self)?
+        } else {
+            serde_json::to_string(self)?
+        };
+        Ok(json)
+    }
+
+    /// Load a report previously written with `--format json`
+    ///
+    /// Raw journal content and parsed directives are not part of the JSON,
+    /// so they come back empty.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
 }
 
 /// Report metadata
@@ -71,7 +214,7 @@ pub struct ReportMetadata {
 }
 
 /// Date range for filtering
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DateRange {
     /// Start date (inclusive)
     pub from: NaiveDate,
@@ -112,14 +255,42 @@ pub struct Statistics {
     /// Number of repositories
     pub repositories: usize,
 
-    /// Number of unique tasks
+    /// Number of unique tasks, after folding duplicates together unless
+    /// that was turned off
     pub unique_tasks: usize,
 
+    /// Entries naming a task, set when duplicates were folded together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_mentions: Option<usize>,
+
+    /// Tasks still being worked on
+    #[serde(default)]
+    pub open_tasks: usize,
+
+    /// Tasks finished
+    #[serde(default)]
+    pub done_tasks: usize,
+
+    /// Unfinished tasks with no recent entry
+    #[serde(default)]
+    pub stale_tasks: usize,
+
     /// Total time spent (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_time: Option<String>,
 }
 
+impl Statistics {
+    /// Unique tasks, with the mentions they were folded from if counted,
+    /// as in `23 (61 mentions)`
+    pub fn unique_tasks_label(&self) -> String {
+        match self.task_mentions {
+            Some(mentions) => format!("{} ({} mentions)", self.unique_tasks, mentions),
+            None => self.unique_tasks.to_string(),
+        }
+    }
+}
+
 impl Default for Statistics {
     fn default() -> Self {
         Self {
@@ -128,11 +299,90 @@ impl Default for Statistics {
             active_days: 0,
             repositories: 0,
             unique_tasks: 0,
+            task_mentions: None,
+            open_tasks: 0,
+            done_tasks: 0,
+            stale_tasks: 0,
             total_time: None,
         }
     }
 }
 
+/// One row of the team summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorSummary {
+    /// Author name, or `Unattributed` for entries nobody could be found for
+    pub author: String,
+
+    /// Entries written
+    pub entries: usize,
+
+    /// Finished tasks whose newest entry the author wrote
+    pub completed_tasks: usize,
+}
+
+/// Directive counts and malformed directives collected while parsing
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReportWarnings {
+    /// Times `jrnrvw:ignore` fired
+    pub ignore: usize,
+
+    /// Times `jrnrvw:ignore-section` fired
+    pub ignore_section: usize,
+
+    /// Times `jrnrvw:archive` fired
+    pub archive: usize,
+
+    /// Malformed directives as `file:line: message`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub malformed: Vec<String>,
+}
+
+impl ReportWarnings {
+    /// Collect directive activity from parsed entries
+    pub fn from_entries(entries: &[JournalEntry]) -> Self {
+        let mut warnings = Self::default();
+
+        for entry in entries {
+            for directive in &entry.directives {
+                match directive.kind {
+                    DirectiveKind::Ignore => warnings.ignore += 1,
+                    DirectiveKind::IgnoreSection => warnings.ignore_section += 1,
+                    DirectiveKind::Archive => warnings.archive += 1,
+                }
+            }
+
+            for warning in &entry.directive_warnings {
+                warnings.malformed.push(format!(
+                    "{}:{}: {}",
+                    entry.filepath.display(),
+                    warning.line,
+                    warning.message
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// Directive counts that fired, as (directive, count) pairs
+    pub fn directive_counts(&self) -> Vec<(DirectiveKind, usize)> {
+        [
+            (DirectiveKind::Ignore, self.ignore),
+            (DirectiveKind::IgnoreSection, self.ignore_section),
+            (DirectiveKind::Archive, self.archive),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect()
+    }
+
+    /// Whether there is nothing to report
+    pub fn is_empty(&self) -> bool {
+        self.directive_counts().is_empty() && self.malformed.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,5 +417,113 @@ mod tests {
         assert_eq!(report.metadata.total_entries, 0);
         assert_eq!(report.metadata.repository_count, 0);
         assert_eq!(report.statistics.total_entries, 0);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_report_warnings_from_entries() {
+        use crate::models::{Directive, DirectiveWarning};
+        use std::path::PathBuf;
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut first = JournalEntry::new(PathBuf::from("a.md"), date);
+        first.directives = vec![
+            Directive { kind: DirectiveKind::IgnoreSection, line: 4 },
+            Directive { kind: DirectiveKind::IgnoreSection, line: 9 },
+            Directive { kind: DirectiveKind::Ignore, line: 12 },
+        ];
+        let mut second = JournalEntry::new(PathBuf::from("b.md"), date);
+        second.directives = vec![Directive { kind: DirectiveKind::Archive, line: 1 }];
+        second.directive_warnings = vec![DirectiveWarning {
+            line: 7,
+            message: "unknown jrnrvw directive 'skip'".to_string(),
+        }];
+
+        let warnings = ReportWarnings::from_entries(&[first, second]);
+
+        assert_eq!(warnings.ignore, 1);
+        assert_eq!(warnings.ignore_section, 2);
+        assert_eq!(warnings.archive, 1);
+        assert_eq!(warnings.malformed, vec!["b.md:7: unknown jrnrvw directive 'skip'"]);
+        assert!(!warnings.is_empty());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        use crate::models::Task;
+        use std::path::PathBuf;
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from("journals/2025.11.13 - JRN - parser.md"), date);
+        entry.task = Some("parser".to_string());
+        entry.repository = Some("jrnrvw".to_string());
+        entry.activities = vec!["Fixed front matter".to_string()];
+        entry.time_spent = Some("2h".to_string());
+        let mut task = Task::new("parser".to_string());
+        task.add_entry(entry);
+        let mut repo = Repository::new("jrnrvw".to_string(), Some(PathBuf::from("/src/jrnrvw")));
+        repo.add_task(task);
+
+        let report = Report::new(vec![repo], Some(DateRange::new(date, date)))
+            .with_statistics(Statistics { total_entries: 1, active_days: 1, total_time: Some("2h".to_string()), ..Default::default() })
+            .with_warnings(ReportWarnings { archive: 1, ..Default::default() });
+
+        let json = report.to_json(true).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["metadata"]["period"]["from"], "2025-11-13");
+        assert!(DateTime::parse_from_rfc3339(value["metadata"]["generated_at"].as_str().unwrap()).is_ok());
+        let entry = &value["repositories"][0]["tasks"][0]["entries"][0];
+        assert_eq!(entry["date"], "2025-11-13");
+        assert_eq!(entry["time_spent"], "2h");
+        assert_eq!(value["statistics"]["date_range_days"], 0);
+
+        let loaded = Report::from_json(&json).unwrap();
+        assert_eq!(loaded.metadata.generated_at, report.metadata.generated_at);
+        assert_eq!(loaded.repositories[0].tasks[0].entries, report.repositories[0].tasks[0].entries);
+        assert_eq!(loaded.warnings.archive, 1);
+        assert_eq!(loaded.to_json(true).unwrap(), json);
+    }
+
+    #[test]
+    fn test_json_exposes_reference_edges() {
+        use crate::models::{Dangling, EntryRef, Reference, ReferenceGraph, ReferenceKind};
+        use std::path::PathBuf;
+
+        let date = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        let graph = ReferenceGraph {
+            edges: vec![Reference {
+                source: EntryRef {
+                    repository: "web".to_string(),
+                    task: Some("Dashboard".to_string()),
+                    date,
+                    path: PathBuf::from("web/2024-05-06.md"),
+                },
+                repository: "infra".to_string(),
+                date: NaiveDate::from_ymd_opt(2024, 5, 2),
+                kind: ReferenceKind::Explicit,
+                blocking: true,
+                context: "blocked on ref:infra#2024-05-02".to_string(),
+                target: None,
+                dangling: Some(Dangling::UnknownDate),
+            }],
+        };
+        let report = Report::new(vec![], None).with_references(graph.clone());
+
+        let value: serde_json::Value = serde_json::from_str(&report.to_json(false).unwrap()).unwrap();
+        let edge = &value["references"]["edges"][0];
+        assert_eq!(edge["source"]["repository"], "web");
+        assert_eq!(edge["repository"], "infra");
+        assert_eq!(edge["date"], "2024-05-02");
+        assert_eq!(edge["kind"], "explicit");
+        assert_eq!(edge["dangling"], "unknown-date");
+        assert!(edge.get("target").is_none());
+
+        assert_eq!(Report::from_json(&report.to_json(false).unwrap()).unwrap().references, graph);
+    }
+
+    #[test]
+    fn test_from_json_rejects_bad_input() {
+        assert!(Report::from_json("{}").is_err());
+        assert!(Report::from_json("not json").is_err());
     }
 }
@@ -1,9 +1,15 @@
 //! Repository and Task models
 
 use serde::{Serialize, Deserialize};
+use std::fmt;
 use std::path::PathBuf;
 use chrono::NaiveDate;
-use super::JournalEntry;
+use super::{JournalEntry, TimeLogged};
+use crate::analyzer::cache::content_hash;
+use crate::analyzer::signals::checkbox_state;
+
+/// Days without an entry after which an unfinished task counts as stale
+pub const STALE_AFTER_DAYS: i64 = 14;
 
 /// Represents a repository with its tasks
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +21,10 @@ pub struct Repository {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<PathBuf>,
 
+    /// Project tag shared by all of the repository's entries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
     /// Tasks within this repository
     pub tasks: Vec<Task>,
 }
@@ -25,12 +35,14 @@ impl Repository {
         Self {
             name,
             path,
+            project: None,
             tasks: Vec::new(),
         }
     }
 
-    /// Add a task to this repository
-    pub fn add_task(&mut self, task: Task) {
+    /// Add a task to this repository, giving it its id
+    pub fn add_task(&mut self, mut task: Task) {
+        task.assign_id(&self.name);
         self.tasks.push(task);
     }
 
@@ -71,6 +83,15 @@ pub struct Task {
     /// Task name
     pub name: String,
 
+    /// Stable id other tasks refer to it by, set once the task joins a
+    /// repository (see [`Task::assign_id`])
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+
+    /// Ids of the tasks this one depends on, as written in its entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
     /// Journal entries for this task
     pub entries: Vec<JournalEntry>,
 }
@@ -80,15 +101,41 @@ impl Task {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            id: String::new(),
+            depends_on: Vec::new(),
             entries: Vec::new(),
         }
     }
 
-    /// Add an entry to this task
+    /// Add an entry to this task, along with the dependencies it names
     pub fn add_entry(&mut self, entry: JournalEntry) {
+        for id in &entry.depends_on {
+            if !self.depends_on.contains(id) {
+                self.depends_on.push(id.clone());
+            }
+        }
         self.entries.push(entry);
     }
 
+    /// Set the task's id from its entries
+    ///
+    /// The newest `id:` set in an entry's front matter wins. Otherwise the
+    /// id is a short hash of the repository, the task's first journal file
+    /// and its name, so it stays the same from run to run.
+    pub fn assign_id(&mut self, repository: &str) {
+        let mut entries: Vec<&JournalEntry> = self.entries.iter().collect();
+        entries.sort_by(|a, b| (a.date, &a.filename).cmp(&(b.date, &b.filename)));
+
+        if let Some(id) = entries.iter().rev().find_map(|e| e.task_id.as_deref()) {
+            self.id = id.to_string();
+            return;
+        }
+
+        let file = entries.first().map_or("", |e| e.filename.as_str());
+        let key = format!("{}\n{}\n{}", repository, file, self.name);
+        self.id = content_hash(key.as_bytes())[..8].to_string();
+    }
+
     /// Get date range for this task
     pub fn date_range(&self) -> Option<(NaiveDate, NaiveDate)> {
         if self.entries.is_empty() {
@@ -105,6 +152,135 @@ impl Task {
     pub fn entry_count(&self) -> usize {
         self.entries.len()
     }
+
+    /// Time logged across the task's entries, `None` if none logged any
+    pub fn time_logged(&self) -> Option<TimeLogged> {
+        TimeLogged::total(self.entries.iter().filter_map(|e| e.time_logged))
+    }
+
+    /// Most recent entry for this task
+    pub fn latest_entry(&self) -> Option<&JournalEntry> {
+        self.entries.iter().max_by_key(|e| e.date)
+    }
+
+    /// Current status of the task
+    ///
+    /// A `status` in the newest entry's front matter wins; otherwise the
+    /// newest entry with checkbox activities decides, open while any box is
+    /// unchecked. `None` when neither is present.
+    pub fn status(&self) -> Option<TaskStatus> {
+        let mut entries: Vec<&JournalEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.date));
+
+        if let Some(status) = entries.first().and_then(|e| e.status.as_deref()) {
+            return TaskStatus::parse(status);
+        }
+
+        entries.iter().find_map(|entry| checkbox_status(entry))
+    }
+
+    /// Date the task was finished
+    ///
+    /// That is the oldest entry in the run of done entries that ends with
+    /// the newest one; entries without a status or checkboxes don't break
+    /// the run. `None` unless the task is done.
+    pub fn completed_on(&self) -> Option<NaiveDate> {
+        if self.status() != Some(TaskStatus::Done) {
+            return None;
+        }
+
+        let mut entries: Vec<&JournalEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.date));
+
+        let mut completed = None;
+        for entry in entries {
+            let state = entry
+                .status
+                .as_deref()
+                .and_then(TaskStatus::parse)
+                .or_else(|| checkbox_status(entry));
+            match state {
+                Some(TaskStatus::Done) => completed = Some(entry.date),
+                Some(_) => break,
+                None => {}
+            }
+        }
+        completed.or_else(|| self.latest_entry().map(|e| e.date))
+    }
+
+    /// Status as of `as_of`, reporting unfinished tasks with no entry in the
+    /// last [`STALE_AFTER_DAYS`] days as stale
+    pub fn status_as_of(&self, as_of: NaiveDate) -> Option<TaskStatus> {
+        let status = self.status();
+        if status == Some(TaskStatus::Done) {
+            return status;
+        }
+
+        match self.latest_entry() {
+            Some(latest) if (as_of - latest.date).num_days() > STALE_AFTER_DAYS => Some(TaskStatus::Stale),
+            _ => status,
+        }
+    }
+
+    /// Due date from the newest entry that sets one
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        let mut entries: Vec<&JournalEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.date));
+        entries.into_iter().find_map(|e| e.due)
+    }
+
+    /// Priority from the newest entry that sets one
+    pub fn priority(&self) -> Option<&str> {
+        let mut entries: Vec<&JournalEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.date));
+        entries.into_iter().find_map(|e| e.priority.as_deref())
+    }
+}
+
+/// Done when every checkbox activity in the entry is ticked, open when any
+/// is not; `None` for entries without checkboxes
+fn checkbox_status(entry: &JournalEntry) -> Option<TaskStatus> {
+    let states: Vec<bool> = entry.activities.iter().filter_map(|a| checkbox_state(a)).collect();
+    if states.is_empty() {
+        None
+    } else if states.iter().all(|&done| done) {
+        Some(TaskStatus::Done)
+    } else {
+        Some(TaskStatus::Open)
+    }
+}
+
+/// Whether a task is still being worked on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    /// Work remains
+    Open,
+    /// Finished
+    Done,
+    /// Unfinished, but nobody has written about it for a while
+    Stale,
+}
+
+impl TaskStatus {
+    /// Parse a front matter `status` value (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "open" | "todo" | "active" | "in progress" | "in-progress" | "blocked" => Some(TaskStatus::Open),
+            "done" | "closed" | "complete" | "completed" => Some(TaskStatus::Done),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStatus::Open => write!(f, "open"),
+            TaskStatus::Done => write!(f, "done"),
+            TaskStatus::Stale => write!(f, "stale"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -153,6 +329,108 @@ mod tests {
         assert_eq!(range.1, date2);
     }
 
+    #[test]
+    fn test_task_status() {
+        let mut task = Task::new("test".to_string());
+        assert_eq!(task.status(), None);
+
+        let mut older = JournalEntry::new(PathBuf::from("a.md"), NaiveDate::from_ymd_opt(2025, 11, 13).unwrap());
+        older.activities = vec!["[x] one".to_string(), "[ ] two".to_string()];
+        older.priority = Some("high".to_string());
+        task.add_entry(older);
+        assert_eq!(task.status(), Some(TaskStatus::Open));
+
+        let mut newer = JournalEntry::new(PathBuf::from("b.md"), NaiveDate::from_ymd_opt(2025, 11, 15).unwrap());
+        newer.activities = vec!["[x] two".to_string(), "notes".to_string()];
+        task.add_entry(newer.clone());
+        assert_eq!(task.status(), Some(TaskStatus::Done));
+        assert_eq!(task.priority(), Some("high"));
+
+        // Front matter overrides the checkboxes
+        newer.status = Some("In Progress".to_string());
+        task.entries[1] = newer;
+        assert_eq!(task.status(), Some(TaskStatus::Open));
+    }
+
+    #[test]
+    fn test_task_status_as_of() {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 1).unwrap();
+        let mut entry = JournalEntry::new(PathBuf::from("a.md"), date);
+        entry.activities = vec!["[ ] pending".to_string()];
+        let mut task = Task::new("test".to_string());
+        task.add_entry(entry.clone());
+
+        let fresh = date + chrono::Duration::days(STALE_AFTER_DAYS);
+        assert_eq!(task.status_as_of(fresh), Some(TaskStatus::Open));
+        assert_eq!(task.status_as_of(fresh + chrono::Duration::days(1)), Some(TaskStatus::Stale));
+
+        entry.activities = vec!["[x] finished".to_string()];
+        task.entries[0] = entry;
+        assert_eq!(task.status_as_of(fresh + chrono::Duration::days(30)), Some(TaskStatus::Done));
+    }
+
+    #[test]
+    fn test_task_completed_on() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 11, d).unwrap();
+        let entry = |d, activity: &str| {
+            let mut entry = JournalEntry::new(PathBuf::from(format!("{}.md", d)), day(d));
+            entry.activities = vec![activity.to_string()];
+            entry
+        };
+
+        let mut task = Task::new("test".to_string());
+        task.add_entry(entry(1, "[ ] start"));
+        task.add_entry(entry(3, "[x] start"));
+        assert_eq!(task.completed_on(), Some(day(3)));
+
+        // Follow-up notes after finishing don't move the date
+        task.add_entry(entry(5, "wrote it up"));
+        assert_eq!(task.completed_on(), Some(day(3)));
+
+        task.add_entry(entry(7, "[ ] reopened"));
+        assert_eq!(task.completed_on(), None);
+
+        let mut closed = entry(9, "shipped");
+        closed.status = Some("done".to_string());
+        task.add_entry(closed);
+        assert_eq!(task.completed_on(), Some(day(9)));
+    }
+
+    #[test]
+    fn test_task_id_and_dependencies() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 11, d).unwrap();
+        let mut first = JournalEntry::new(PathBuf::from("2025.11.01-login.md"), day(1));
+        first.depends_on = vec!["12".to_string(), "auth".to_string()];
+        let mut second = JournalEntry::new(PathBuf::from("2025.11.02-login.md"), day(2));
+        second.depends_on = vec!["auth".to_string(), "infra#vpc".to_string()];
+
+        let mut task = Task::new("login".to_string());
+        task.add_entry(second.clone());
+        task.add_entry(first.clone());
+        assert_eq!(task.depends_on, vec!["auth", "infra#vpc", "12"]);
+
+        // The hash only depends on the repository, first file and name
+        task.assign_id("web");
+        let mut again = Task::new("login".to_string());
+        again.add_entry(first.clone());
+        again.add_entry(second.clone());
+        again.assign_id("web");
+        assert_eq!(task.id.len(), 8);
+        assert_eq!(task.id, again.id);
+        again.assign_id("api");
+        assert_ne!(task.id, again.id);
+
+        // An explicit id wins, the newest one if it changed
+        first.task_id = Some("old".to_string());
+        second.task_id = Some("login-page".to_string());
+        let mut repo = Repository::new("web".to_string(), None);
+        let mut explicit = Task::new("login".to_string());
+        explicit.add_entry(second);
+        explicit.add_entry(first);
+        repo.add_task(explicit);
+        assert_eq!(repo.tasks[0].id, "login-page");
+    }
+
     #[test]
     fn test_task_date_range_empty() {
         let task = Task::new("test".to_string());
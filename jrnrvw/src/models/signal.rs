@@ -0,0 +1,84 @@
+//! Trend and anomaly findings surfaced in the Signals report section
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// How much attention a signal deserves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth a look, nothing more
+    Info,
+
+    /// A change that is probably not noise
+    Warning,
+
+    /// A large change that likely needs follow-up
+    Critical,
+}
+
+impl Severity {
+    /// Lowercase label used in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Which detector produced a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignalKind {
+    /// Entry volume for a repository fell against its recent average
+    VolumeDrop,
+
+    /// Task completion rate fell several weeks running
+    CompletionTrend,
+
+    /// A day's entries were far longer than usual
+    LongEntry,
+}
+
+/// A journal entry a signal was derived from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalSource {
+    /// Date of the entry
+    pub date: NaiveDate,
+
+    /// Path to the journal file
+    pub path: PathBuf,
+}
+
+impl fmt::Display for SignalSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.path.display())
+    }
+}
+
+/// A single trend or anomaly finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Signal {
+    /// How serious the finding is
+    pub severity: Severity,
+
+    /// Detector that raised it
+    pub kind: SignalKind,
+
+    /// Human-readable description
+    pub message: String,
+
+    /// Entries to look at when investigating
+    #[serde(default)]
+    pub sources: Vec<SignalSource>,
+}
@@ -0,0 +1,115 @@
+//! Dependencies between tasks
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::error::JrnrvwError;
+
+/// A task in the dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskNode {
+    /// Repository the task belongs to
+    pub repository: String,
+
+    /// Task id
+    pub id: String,
+
+    /// Task name
+    pub name: String,
+}
+
+impl TaskNode {
+    /// `<repository>#<id>`, unique across the report
+    pub fn key(&self) -> String {
+        format!("{}#{}", self.repository, self.id)
+    }
+
+    /// The task as named from a task of `repository`: just its name within
+    /// the same repository, `<name> (<repository>)` from another
+    pub fn label_in(&self, repository: &str) -> String {
+        if self.repository == repository {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, self.repository)
+        }
+    }
+}
+
+impl fmt::Display for TaskNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}#{})", self.name, self.repository, self.id)
+    }
+}
+
+/// One edge of the graph: `task` can't finish before `depends_on`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDependency {
+    /// Task that waits
+    pub task: TaskNode,
+
+    /// Task it waits on
+    pub depends_on: TaskNode,
+}
+
+/// A dependency naming no known task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedDependency {
+    /// Task the dependency was written for
+    pub task: TaskNode,
+
+    /// Id as written, without a leading `#`
+    pub reference: String,
+}
+
+/// A repository's tasks in dependency order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskOrder {
+    /// Repository name
+    pub repository: String,
+
+    /// Every task after the ones it depends on in the same repository;
+    /// tasks caught in a cycle come last, by name
+    pub tasks: Vec<TaskNode>,
+}
+
+/// Dependencies written between the report's tasks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskGraph {
+    /// Repositories with dependencies, by name
+    pub order: Vec<TaskOrder>,
+
+    /// Resolved dependencies, by waiting task
+    pub edges: Vec<TaskDependency>,
+
+    /// Dependencies that name no task
+    pub unresolved: Vec<UnresolvedDependency>,
+
+    /// Groups of tasks that depend on each other in a loop, members sorted
+    /// by repository and name
+    pub cycles: Vec<Vec<TaskNode>>,
+}
+
+impl TaskGraph {
+    /// Whether no task names a dependency
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty() && self.unresolved.is_empty()
+    }
+
+    /// One error per cycle, listing its members
+    pub fn cycle_errors(&self) -> Vec<JrnrvwError> {
+        self.cycles
+            .iter()
+            .map(|cycle| JrnrvwError::DependencyCycle(cycle.iter().map(TaskNode::to_string).collect()))
+            .collect()
+    }
+
+    /// Tasks `task` depends on, by name
+    pub fn dependencies_of<'a>(&'a self, task: &'a TaskNode) -> impl Iterator<Item = &'a TaskNode> + 'a {
+        self.edges.iter().filter(move |e| &e.task == task).map(|e| &e.depends_on)
+    }
+
+    /// Whether `task` is part of a cycle
+    pub fn in_cycle(&self, task: &TaskNode) -> bool {
+        self.cycles.iter().flatten().any(|member| member == task)
+    }
+}
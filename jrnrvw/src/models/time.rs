@@ -0,0 +1,112 @@
+//! Time logged in journals and its totals
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::ops::AddAssign;
+
+/// Time spent on an entry, a task, or any group of them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeLogged {
+    /// Whole minutes
+    pub minutes: u32,
+
+    /// Some of the time was hedged (`about 2h`, `~45m`) rather than exact
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub approximate: bool,
+}
+
+impl TimeLogged {
+    /// Time of `minutes`, exact unless `approximate`
+    pub fn new(minutes: u32, approximate: bool) -> Self {
+        Self { minutes, approximate }
+    }
+
+    /// Sum of `times`, `None` when there are none
+    pub fn total(times: impl IntoIterator<Item = TimeLogged>) -> Option<Self> {
+        times.into_iter().fold(None, |total, time| {
+            let mut total = total.unwrap_or_default();
+            total += time;
+            Some(total)
+        })
+    }
+}
+
+impl AddAssign for TimeLogged {
+    fn add_assign(&mut self, other: Self) {
+        self.minutes += other.minutes;
+        self.approximate |= other.approximate;
+    }
+}
+
+impl fmt::Display for TimeLogged {
+    /// `2h 15m`, `45m` or `3h`, prefixed with `~` when approximate
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.approximate {
+            write!(f, "~")?;
+        }
+        let (hours, minutes) = (self.minutes / 60, self.minutes % 60);
+        match (hours, minutes) {
+            (0, minutes) => write!(f, "{}m", minutes),
+            (hours, 0) => write!(f, "{}h", hours),
+            (hours, minutes) => write!(f, "{}h {}m", hours, minutes),
+        }
+    }
+}
+
+/// Time logged across the report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSummary {
+    /// Time logged by every entry
+    pub total: TimeLogged,
+
+    /// Per repository, most time first
+    pub by_repository: Vec<TimeRow>,
+
+    /// Per tag or project, most time first; an entry counts towards each of
+    /// its tags
+    pub by_tag: Vec<TimeRow>,
+
+    /// Per day, oldest first
+    pub by_day: Vec<TimeRow>,
+}
+
+/// One row of the time summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRow {
+    /// Repository, tag, or `yyyy-mm-dd` date
+    pub name: String,
+
+    /// Time logged under it
+    #[serde(flatten)]
+    pub time: TimeLogged,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TimeLogged::new(135, false).to_string(), "2h 15m");
+        assert_eq!(TimeLogged::new(45, false).to_string(), "45m");
+        assert_eq!(TimeLogged::new(180, true).to_string(), "~3h");
+    }
+
+    #[test]
+    fn test_total() {
+        assert_eq!(TimeLogged::total([]), None);
+        assert_eq!(
+            TimeLogged::total([TimeLogged::new(90, false), TimeLogged::new(30, true)]),
+            Some(TimeLogged::new(120, true))
+        );
+    }
+
+    #[test]
+    fn test_json_minutes() {
+        let row = TimeRow { name: "parser".to_string(), time: TimeLogged::new(90, false) };
+        assert_eq!(serde_json::to_string(&row).unwrap(), r#"{"name":"parser","minutes":90}"#);
+
+        let hedged = serde_json::to_string(&TimeLogged::new(120, true)).unwrap();
+        assert_eq!(hedged, r#"{"minutes":120,"approximate":true}"#);
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::error::{Result, JrnrvwError};
 use crate::output::{Formatter, OutputOptions};
-use crate::models::Report;
+use crate::models::{JournalEntry, Report, Task};
 
 /// CSV formatter
 ///
@@ -92,11 +92,130 @@ impl Formatter for CsvFormatter {
     }
 }
 
+/// One row per task, for `jrnrvw export --tasks-csv`
+///
+/// Unlike the `--format csv` report, fields are quoted per RFC 4180 and
+/// rows end in CRLF, so titles with commas or line breaks survive Excel.
+/// The file path and line point at the task in its first journal; the
+/// line is left blank when the task name does not appear in the file.
+pub fn tasks_csv(report: &Report) -> Result<String> {
+    let as_of = report.repositories.iter().filter_map(|r| r.date_range()).map(|(_, last)| last).max();
+
+    let mut wtr = export_writer();
+    write_row(&mut wtr, &[
+        "Repository",
+        "File Path",
+        "Line",
+        "Title",
+        "Status",
+        "Tags",
+        "Created",
+        "Completed",
+    ])?;
+
+    for repo in &report.repositories {
+        for task in &repo.tasks {
+            let first = task.entries.iter().min_by_key(|e| e.date);
+            let status = as_of.and_then(|as_of| task.status_as_of(as_of));
+
+            write_row(&mut wtr, &[
+                first.and_then(|e| e.repository.as_deref()).unwrap_or(&repo.name),
+                &first.map(|e| e.filepath.display().to_string()).unwrap_or_default(),
+                &first.and_then(|e| task_line(e, &task.name)).map(|l| l.to_string()).unwrap_or_default(),
+                &task.name,
+                &status.map(|s| s.to_string()).unwrap_or_default(),
+                &task_tags(task, repo.project.as_deref()).join("; "),
+                &task.date_range().map(|(created, _)| created.to_string()).unwrap_or_default(),
+                &task.completed_on().map(|d| d.to_string()).unwrap_or_default(),
+            ])?;
+        }
+    }
+
+    finish(wtr)
+}
+
+/// One row per journal entry, for `jrnrvw export --include-entries`
+pub fn entries_csv(report: &Report) -> Result<String> {
+    let mut wtr = export_writer();
+    write_row(&mut wtr, &[
+        "Repository",
+        "Task",
+        "Date",
+        "File Path",
+        "Title",
+        "Project",
+        "Status",
+        "Priority",
+        "Activities",
+        "Notes",
+        "Time Spent",
+    ])?;
+
+    for repo in &report.repositories {
+        for task in &repo.tasks {
+            for entry in &task.entries {
+                write_row(&mut wtr, &[
+                    entry.repository.as_deref().unwrap_or(&repo.name),
+                    &task.name,
+                    &entry.date.to_string(),
+                    &entry.filepath.display().to_string(),
+                    entry.title.as_deref().unwrap_or(""),
+                    entry.project.as_deref().unwrap_or(""),
+                    entry.status.as_deref().unwrap_or(""),
+                    entry.priority.as_deref().unwrap_or(""),
+                    &entry.activities.join("\n"),
+                    entry.notes.as_deref().unwrap_or(""),
+                    entry.time_spent.as_deref().unwrap_or(""),
+                ])?;
+            }
+        }
+    }
+
+    finish(wtr)
+}
+
+fn export_writer() -> csv::Writer<Vec<u8>> {
+    csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(vec![])
+}
+
+fn write_row(wtr: &mut csv::Writer<Vec<u8>>, fields: &[&str]) -> Result<()> {
+    wtr.write_record(fields)
+        .map_err(|e| JrnrvwError::ConfigError(format!("CSV write error: {}", e)))
+}
+
+fn finish(wtr: csv::Writer<Vec<u8>>) -> Result<String> {
+    let data = wtr
+        .into_inner()
+        .map_err(|e| JrnrvwError::ConfigError(format!("CSV finalization error: {}", e)))?;
+
+    String::from_utf8(data)
+        .map_err(|e| JrnrvwError::ConfigError(format!("UTF-8 conversion error: {}", e)))
+}
+
+/// 1-based line of the first mention of `name` in the entry's file
+fn task_line(entry: &JournalEntry, name: &str) -> Option<usize> {
+    entry.raw_content.lines().position(|line| line.contains(name)).map(|index| index + 1)
+}
+
+/// Distinct project tags of the task's entries, falling back to the repository's
+fn task_tags<'a>(task: &'a Task, repo_project: Option<&'a str>) -> Vec<&'a str> {
+    let mut tags: Vec<&str> = task.entries.iter().filter_map(|e| e.project.as_deref()).collect();
+    tags.sort_unstable();
+    tags.dedup();
+    if tags.is_empty() {
+        tags.extend(repo_project);
+    }
+    tags
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{
-        Repository, Task, JournalEntry, Statistics, ReportMetadata
+        Repository, Task, JournalEntry, ReportWarnings, Statistics, ReportMetadata
     };
     use chrono::{NaiveDate, Utc};
     use std::path::PathBuf;
@@ -113,6 +232,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -140,12 +270,15 @@ mod tests {
 
         let task = Task {
             name: "output-formatters".to_string(),
+            id: String::new(),
+            depends_on: Vec::new(),
             entries: vec![entry],
         };
 
         let repo = Repository {
             name: "jrnrvw".to_string(),
             path: Some(PathBuf::from("/home/user/jrnrvw")),
+            project: None,
             tasks: vec![task],
         };
 
@@ -164,7 +297,19 @@ mod tests {
                 active_days: 1,
                 date_range_days: 1,
                 total_time: None,
+                ..Default::default()
             },
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -192,12 +337,103 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format(&report, &options);
         assert!(result.is_ok());
     }
 
+    fn export_report() -> Report {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 11, d).unwrap();
+
+        let mut opened = JournalEntry::new(PathBuf::from("/work/api/2025.11.10-auth.md"), day(10));
+        opened.repository = Some("api".to_string());
+        opened.project = Some("platform".to_string());
+        opened.activities = vec!["[ ] add tokens".to_string()];
+        opened.raw_content = "# Journal\n\n## Task\nAuth, \"v2\"\n".to_string();
+
+        let mut finished = JournalEntry::new(PathBuf::from("/work/api/2025.11.12-auth.md"), day(12));
+        finished.repository = Some("api".to_string());
+        finished.project = Some("security".to_string());
+        finished.activities = vec!["[x] add tokens".to_string()];
+        finished.notes = Some("line one\nline two".to_string());
+
+        let mut pending = JournalEntry::new(PathBuf::from("/work/api/2025.11.12-docs.md"), day(12));
+        pending.repository = Some("api".to_string());
+        pending.activities = vec!["[ ] write guide".to_string()];
+
+        let mut repo = Repository::new("api".to_string(), Some(PathBuf::from("/work/api")));
+        repo.project = Some("platform".to_string());
+        repo.tasks = vec![
+            Task { name: "Auth, \"v2\"".to_string(), id: String::new(), depends_on: Vec::new(), entries: vec![opened, finished] },
+            Task { name: "Docs\nrewrite".to_string(), id: String::new(), depends_on: Vec::new(), entries: vec![pending] },
+        ];
+
+        Report {
+            metadata: ReportMetadata {
+                generated_at: Utc::now(),
+                period: None,
+                total_entries: 3,
+                repository_count: 1,
+            },
+            repositories: vec![repo],
+            statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_tasks_csv() {
+        let csv = tasks_csv(&export_report()).unwrap();
+
+        assert_eq!(
+            csv,
+            "Repository,File Path,Line,Title,Status,Tags,Created,Completed\r\n\
+             api,/work/api/2025.11.10-auth.md,4,\"Auth, \"\"v2\"\"\",done,platform; security,2025-11-10,2025-11-12\r\n\
+             api,/work/api/2025.11.12-docs.md,,\"Docs\nrewrite\",open,platform,2025-11-12,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_export_round_trips_through_a_csv_reader() {
+        let report = export_report();
+
+        let tasks = tasks_csv(&report).unwrap();
+        let mut reader = csv::Reader::from_reader(tasks.as_bytes());
+        let titles: Vec<String> = reader.records().map(|r| r.unwrap()[3].to_string()).collect();
+        assert_eq!(titles, vec!["Auth, \"v2\"", "Docs\nrewrite"]);
+
+        let entries = entries_csv(&report).unwrap();
+        let mut reader = csv::Reader::from_reader(entries.as_bytes());
+        let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(&rows[0][0], "api");
+        assert_eq!(&rows[0][1], "Auth, \"v2\"");
+        assert_eq!(&rows[1][9], "line one\nline two");
+        assert_eq!(&rows[2][8], "[ ] write guide");
+    }
+
     #[test]
     fn test_csv_format_as_tsv() {
         let formatter = CsvFormatter::new();
@@ -210,6 +446,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format_as_tsv(&report, &options);
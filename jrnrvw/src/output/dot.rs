@@ -0,0 +1,155 @@
+//! Graphviz DOT formatter for the task dependency graph
+//!
+//! Each repository with dependencies is a cluster of its tasks. Edges run
+//! from a task to the tasks waiting on it, so the graph reads in the order
+//! the work can be done. Tasks in a cycle and the edges between them are
+//! red; unresolved dependencies are dashed. Render with e.g.
+//! `jrnrvw --format dot | dot -Tsvg -o tasks.svg`.
+
+use crate::error::Result;
+use crate::models::{Report, TaskNode};
+use crate::output::{Formatter, OutputOptions};
+
+/// DOT formatter
+pub struct DotFormatter;
+
+impl DotFormatter {
+    /// Create a new DOT formatter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DotFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for DotFormatter {
+    fn format(&self, report: &Report, _options: &OutputOptions) -> Result<String> {
+        let graph = &report.task_graph;
+        let mut output = String::from("digraph tasks {\n");
+        output.push_str("    rankdir=LR;\n");
+        output.push_str("    node [shape=box];\n");
+
+        for (cluster, order) in graph.order.iter().enumerate() {
+            output.push_str(&format!("\n    subgraph cluster_{} {{\n", cluster));
+            output.push_str(&format!("        label={};\n", quote(&order.repository)));
+            for task in &order.tasks {
+                let color = if graph.in_cycle(task) { ", color=red" } else { "" };
+                output.push_str(&format!(
+                    "        {} [label={}{}];\n",
+                    quote(&task.key()),
+                    quote(&format!("{}\n#{}", task.name, task.id)),
+                    color
+                ));
+            }
+            output.push_str("    }\n");
+        }
+
+        if !graph.edges.is_empty() {
+            output.push('\n');
+        }
+        for edge in &graph.edges {
+            let color = if same_cycle(report, &edge.task, &edge.depends_on) { " [color=red]" } else { "" };
+            output.push_str(&format!(
+                "    {} -> {}{};\n",
+                quote(&edge.depends_on.key()),
+                quote(&edge.task.key()),
+                color
+            ));
+        }
+
+        if !graph.unresolved.is_empty() {
+            output.push('\n');
+        }
+        for unresolved in &graph.unresolved {
+            let missing = format!("?{}", unresolved.reference);
+            output.push_str(&format!(
+                "    {} [label={}, shape=ellipse, style=dashed];\n",
+                quote(&missing),
+                quote(&format!("#{} (unresolved)", unresolved.reference))
+            ));
+            output.push_str(&format!(
+                "    {} -> {} [style=dashed];\n",
+                quote(&missing),
+                quote(&unresolved.task.key())
+            ));
+        }
+
+        output.push_str("}\n");
+        Ok(output)
+    }
+}
+
+/// Whether both tasks belong to the same cycle
+fn same_cycle(report: &Report, a: &TaskNode, b: &TaskNode) -> bool {
+    report
+        .task_graph
+        .cycles
+        .iter()
+        .any(|cycle| cycle.contains(a) && cycle.contains(b))
+}
+
+/// A DOT string literal
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TaskDependency, TaskGraph, TaskOrder, UnresolvedDependency};
+
+    fn node(name: &str) -> TaskNode {
+        TaskNode {
+            repository: "web".to_string(),
+            id: name.to_string(),
+            name: format!("{} \"task\"", name),
+        }
+    }
+
+    #[test]
+    fn test_dot_graph() {
+        let graph = TaskGraph {
+            order: vec![TaskOrder {
+                repository: "web".to_string(),
+                tasks: vec![node("a"), node("b")],
+            }],
+            edges: vec![
+                TaskDependency { task: node("b"), depends_on: node("a") },
+                TaskDependency { task: node("a"), depends_on: node("b") },
+            ],
+            unresolved: vec![UnresolvedDependency { task: node("a"), reference: "42".to_string() }],
+            cycles: vec![vec![node("a"), node("b")]],
+        };
+        let report = Report::new(vec![], None).with_task_graph(graph);
+        let dot = DotFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+
+        assert!(dot.starts_with("digraph tasks {\n"));
+        assert!(dot.contains("subgraph cluster_0 {\n        label=\"web\";"));
+        assert!(dot.contains("\"web#a\" [label=\"a \\\"task\\\"\\n#a\", color=red];"));
+        assert!(dot.contains("\"web#a\" -> \"web#b\" [color=red];"));
+        assert!(dot.contains("\"?42\" -> \"web#a\" [style=dashed];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let report = Report::new(vec![], None);
+        let dot = DotFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert_eq!(dot, "digraph tasks {\n    rankdir=LR;\n    node [shape=box];\n}\n");
+    }
+}
@@ -1,16 +1,19 @@
 //! HTML formatter for web-based reports
 
+use serde::Serialize;
 use tera::{Tera, Context};
 use crate::error::{Result, JrnrvwError};
 use crate::output::{Formatter, OutputOptions};
-use crate::models::Report;
+use crate::models::{Report, Repository};
 
 /// HTML formatter
 ///
-/// Formats reports as HTML documents with basic styling.
-/// Uses the Tera template engine for flexible rendering.
+/// Formats reports as single self-contained HTML documents: styles and the
+/// table sorting script are inline, and every value from the journals is
+/// HTML-escaped. Uses the Tera template engine for flexible rendering.
 pub struct HtmlFormatter {
     tera: Tera,
+    repo_links: bool,
 }
 
 impl HtmlFormatter {
@@ -18,23 +21,47 @@ impl HtmlFormatter {
     pub fn new() -> Result<Self> {
         let mut tera = Tera::default();
 
-        // Register a default template
-        let template = Self::default_template();
-        tera.add_raw_template("report", &template)
-            .map_err(|e| JrnrvwError::ConfigError(format!("Template error: {}", e)))?;
+        // Journal text is untrusted: the `.html` names turn autoescaping on
+        tera.set_escape_fn(escape_html);
 
-        Ok(Self { tera })
+        // Register the default templates; both pages share one stylesheet
+        tera.add_raw_templates(vec![
+            ("styles.html", Self::styles()),
+            ("report.html", Self::default_template()),
+            ("repository.html", Self::repository_template()),
+        ])
+        .map_err(|e| JrnrvwError::ConfigError(format!("Template error: {}", e)))?;
+
+        Ok(Self { tera, repo_links: false })
     }
 
-    /// Get the default HTML template
-    fn default_template() -> String {
-        r#"<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Journal Review Report</title>
-    <style>
+    /// Link each repository in the report to `/repo/{name}`, as served by
+    /// `jrnrvw serve`
+    pub fn with_repo_links(mut self, enabled: bool) -> Self {
+        self.repo_links = enabled;
+        self
+    }
+
+    /// Render a detail page for a single repository, listing every task
+    /// and its entries
+    pub fn format_repository(&self, repo: &Repository, options: &OutputOptions) -> Result<String> {
+        let mut context = Context::new();
+
+        context.insert("repo", repo);
+        context.insert("entry_count", &repo.entry_count());
+        context.insert("date_range", &repo.date_range());
+        context.insert("show_activities", &options.include_activities);
+        context.insert("show_notes", &options.include_notes);
+        context.insert("repo_links", &self.repo_links);
+
+        self.tera
+            .render("repository.html", &context)
+            .map_err(|e| JrnrvwError::ConfigError(format!("Template rendering error: {}", e)))
+    }
+
+    /// Get the stylesheet shared by all pages
+    fn styles() -> String {
+        r#"    <style>
         body {
             font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
             line-height: 1.6;
@@ -104,6 +131,43 @@ impl HtmlFormatter {
             border-radius: 3px;
             font-size: 0.9em;
         }
+        .summary {
+            display: flex;
+            gap: 15px;
+            margin: 20px 0;
+        }
+        .summary-item {
+            flex: 1;
+            padding: 15px;
+            border-radius: 4px;
+            background-color: #f8f9fa;
+            text-align: center;
+        }
+        .summary-item .count {
+            display: block;
+            font-size: 2em;
+            font-weight: bold;
+        }
+        .summary-open .count { color: #007bff; }
+        .summary-done .count { color: #28a745; }
+        .summary-stale .count { color: #b8860b; }
+        .sortable th {
+            cursor: pointer;
+            user-select: none;
+        }
+        .sortable th[aria-sort="ascending"]::after { content: " \25B2"; }
+        .sortable th[aria-sort="descending"]::after { content: " \25BC"; }
+        .status-done { color: #28a745; }
+        .status-stale { color: #b8860b; }
+        details.repo-card > summary {
+            cursor: pointer;
+        }
+        details.repo-card > summary h3 {
+            display: inline;
+        }
+        .signal-critical { color: #dc3545; }
+        .signal-warning { color: #b8860b; }
+        .signal-info { color: #17a2b8; }
         .footer {
             margin-top: 40px;
             padding-top: 20px;
@@ -113,6 +177,18 @@ impl HtmlFormatter {
             text-align: center;
         }
     </style>
+"#.to_string()
+    }
+
+    /// Get the default HTML template
+    fn default_template() -> String {
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Journal Review Report</title>
+    {% include "styles.html" %}
 </head>
 <body>
     <div class="container">
@@ -127,6 +203,130 @@ impl HtmlFormatter {
             <p><strong>Repositories:</strong> {{ metadata.repository_count }}</p>
         </div>
 
+        <div class="summary">
+            <div class="summary-item summary-open"><span class="count">{{ statistics.open_tasks }}</span>open</div>
+            <div class="summary-item summary-done"><span class="count">{{ statistics.done_tasks }}</span>done</div>
+            <div class="summary-item summary-stale"><span class="count">{{ statistics.stale_tasks }}</span>stale</div>
+        </div>
+
+        {% if team %}
+        <h2>Team</h2>
+        <table class="stats-table">
+            <thead>
+                <tr>
+                    <th>Author</th>
+                    <th>Entries</th>
+                    <th>Completed Tasks</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for row in team %}
+                <tr>
+                    <td>{{ row.author }}</td>
+                    <td>{{ row.entries }}</td>
+                    <td>{{ row.completed_tasks }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
+        {% if time_rows %}
+        <h2>Time</h2>
+        <table class="stats-table">
+            <thead>
+                <tr>
+                    <th>Group</th>
+                    <th>Name</th>
+                    <th>Time</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for row in time_rows %}
+                <tr>
+                    <td>{{ row.group }}</td>
+                    <td>{{ row.name }}</td>
+                    <td>{{ row.time }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
+        {% if groups %}
+        <h2>Groups</h2>
+        <table class="stats-table">
+            <thead>
+                <tr>
+                    <th>Group</th>
+                    <th>Entries</th>
+                    <th>Tasks Opened</th>
+                    <th>Tasks Completed</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for group in groups %}
+                <tr>
+                    <td>{{ group.name }}</td>
+                    <td>{{ group.entries }}</td>
+                    <td>{{ group.tasks_opened }}</td>
+                    <td>{{ group.tasks_completed }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
+        {% if overdue %}
+        <h2>Overdue</h2>
+        <table class="stats-table">
+            <thead>
+                <tr>
+                    <th>Repository</th>
+                    <th>Task</th>
+                    <th>Due</th>
+                    <th>Days Overdue</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for task in overdue %}
+                <tr>
+                    <td>{{ task.repository }}</td>
+                    <td>{{ task.task }}</td>
+                    <td>{{ task.due }}</td>
+                    <td>{{ task.days_overdue }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
+        {% if stale %}
+        <h2>Stale</h2>
+        <table class="stats-table">
+            <thead>
+                <tr>
+                    <th>Repository</th>
+                    <th>Task</th>
+                    <th>Opened</th>
+                    <th>Last Entry</th>
+                    <th>Days Open</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for task in stale %}
+                <tr>
+                    <td>{{ task.repository }}</td>
+                    <td>{{ task.task }}</td>
+                    <td>{{ task.opened }}</td>
+                    <td>{{ task.last_entry }}</td>
+                    <td>{{ task.days_open }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
         {% if show_stats %}
         <h2>Statistics</h2>
         <table class="stats-table">
@@ -147,7 +347,7 @@ impl HtmlFormatter {
                 </tr>
                 <tr>
                     <td>Unique Tasks</td>
-                    <td>{{ statistics.unique_tasks }}</td>
+                    <td>{{ statistics.unique_tasks }}{% if statistics.task_mentions %} ({{ statistics.task_mentions }} mentions){% endif %}</td>
                 </tr>
                 <tr>
                     <td>Active Days</td>
@@ -164,19 +364,187 @@ impl HtmlFormatter {
         {% endif %}
 
         {% if not summary_only %}
+        {% if tasks %}
+        <h2>Tasks</h2>
+        <table class="stats-table sortable">
+            <thead>
+                <tr>
+                    <th>Task</th>
+                    <th>Repository</th>
+                    <th>Status</th>
+                    <th>Priority</th>
+                    <th>Last Entry</th>
+                    <th data-type="number">Entries</th>
+                    <th data-type="number">Time</th>
+                </tr>
+            </thead>
+            <tbody>
+                {% for task in tasks %}
+                <tr>
+                    <td>{{ task.name }}</td>
+                    <td>{{ task.repository }}</td>
+                    <td class="status-{{ task.status }}">{{ task.status }}</td>
+                    <td>{{ task.priority }}</td>
+                    <td>{{ task.last_entry }}</td>
+                    <td>{{ task.entries }}</td>
+                    <td data-value="{{ task.minutes }}">{{ task.time }}</td>
+                </tr>
+                {% endfor %}
+            </tbody>
+        </table>
+        {% endif %}
+
         <h2>Repositories</h2>
         {% for repo in repositories %}
-        <div class="repo-card">
+        <details class="repo-card" open>
+            <summary>
+            {% if repo_links %}
+            <h3><a href="/repo/{{ repo.name | urlencode }}">{{ repo.name }}</a></h3>
+            {% else %}
             <h3>{{ repo.name }}</h3>
+            {% endif %}
+            </summary>
+            {% if repo.path %}
             <p><strong>Path:</strong> <code>{{ repo.path }}</code></p>
+            {% endif %}
             <p><strong>Tasks:</strong> {{ repo.tasks | length }}</p>
             {% if show_activities %}
-            <p><strong>Total Entries:</strong> {{ repo.entry_count }}</p>
+            <p><strong>Total Entries:</strong> {{ entry_counts[repo.name] }}</p>
+            {% for task in repo.tasks %}
+            <h4>{{ task.name }}</h4>
+            <ul>
+                {% for entry in task.entries %}
+                <li>{{ entry.date }}{% if entry.title %} - {{ entry.title }}{% endif %}
+                    {% if entry.activities %}
+                    <ul>
+                        {% for activity in entry.activities %}
+                        <li>{{ activity }}</li>
+                        {% endfor %}
+                    </ul>
+                    {% endif %}
+                    {% if show_notes and entry.notes %}
+                    <p>{{ entry.notes }}</p>
+                    {% endif %}
+                </li>
+                {% endfor %}
+            </ul>
+            {% endfor %}
             {% endif %}
-        </div>
+        </details>
         {% endfor %}
         {% endif %}
 
+        {% if signals %}
+        <h2>Signals</h2>
+        <ul>
+            {% for signal in signals %}
+            <li><strong class="signal-{{ signal.severity }}">{{ signal.severity }}</strong>: {{ signal.message }}
+                {% if signal.sources %}
+                <ul>
+                    {% for source in signal.sources %}
+                    <li>{{ source.date }} <code>{{ source.path }}</code></li>
+                    {% endfor %}
+                </ul>
+                {% endif %}
+            </li>
+            {% endfor %}
+        </ul>
+        {% endif %}
+
+        {% if directive_counts or malformed_directives %}
+        <h2>Warnings</h2>
+        <ul>
+            {% for item in directive_counts %}
+            <li><code>{{ item.0 }}</code> fired {{ item.1 }} time(s)</li>
+            {% endfor %}
+            {% for warning in malformed_directives %}
+            <li>{{ warning }}</li>
+            {% endfor %}
+        </ul>
+        {% endif %}
+
+        <div class="footer">
+            <p>Generated by jrnrvw - Journal Review Tool</p>
+        </div>
+    </div>
+    <script>
+        // Click a column header to sort by it; click again to reverse
+        document.querySelectorAll("table.sortable").forEach(function (table) {
+            var headers = table.querySelectorAll("th");
+            headers.forEach(function (header, column) {
+                header.addEventListener("click", function () {
+                    var ascending = header.getAttribute("aria-sort") !== "ascending";
+                    var numeric = header.dataset.type === "number";
+                    headers.forEach(function (other) { other.removeAttribute("aria-sort"); });
+                    header.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+
+                    var body = table.tBodies[0];
+                    var rows = Array.prototype.slice.call(body.rows);
+                    rows.sort(function (a, b) {
+                        var x = a.cells[column].dataset.value || a.cells[column].textContent.trim();
+                        var y = b.cells[column].dataset.value || b.cells[column].textContent.trim();
+                        var order = numeric ? Number(x) - Number(y) : x.localeCompare(y);
+                        return ascending ? order : -order;
+                    });
+                    rows.forEach(function (row) { body.appendChild(row); });
+                });
+            });
+        });
+    </script>
+</body>
+</html>"#.to_string()
+    }
+
+    /// Get the single-repository HTML template
+    fn repository_template() -> String {
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{{ repo.name }} - Journal Review Report</title>
+    {% include "styles.html" %}
+</head>
+<body>
+    <div class="container">
+        {% if repo_links %}
+        <p><a href="/">&larr; All repositories</a></p>
+        {% endif %}
+        <h1>{{ repo.name }}</h1>
+
+        <div class="metadata">
+            {% if repo.path %}
+            <p><strong>Path:</strong> <code>{{ repo.path }}</code></p>
+            {% endif %}
+            {% if repo.project %}
+            <p><strong>Project:</strong> {{ repo.project }}</p>
+            {% endif %}
+            {% if date_range %}
+            <p><strong>Period:</strong> {{ date_range.0 }} to {{ date_range.1 }}</p>
+            {% endif %}
+            <p><strong>Total Entries:</strong> {{ entry_count }}</p>
+        </div>
+
+        {% for task in repo.tasks %}
+        <h2>{{ task.name }}</h2>
+        {% for entry in task.entries %}
+        <div class="repo-card">
+            <h3>{{ entry.date }}{% if entry.title %} - {{ entry.title }}{% endif %}</h3>
+            <p><code>{{ entry.filename }}</code>{% if entry.time_spent %} &middot; {{ entry.time_spent }}{% endif %}</p>
+            {% if show_activities and entry.activities %}
+            <ul>
+                {% for activity in entry.activities %}
+                <li>{{ activity }}</li>
+                {% endfor %}
+            </ul>
+            {% endif %}
+            {% if show_notes and entry.notes %}
+            <p>{{ entry.notes }}</p>
+            {% endif %}
+        </div>
+        {% endfor %}
+        {% endfor %}
+
         <div class="footer">
             <p>Generated by jrnrvw - Journal Review Tool</p>
         </div>
@@ -199,25 +567,140 @@ impl Formatter for HtmlFormatter {
         // Add report data to context
         context.insert("metadata", &report.metadata);
         context.insert("repositories", &report.repositories);
+        context.insert(
+            "entry_counts",
+            &report
+                .repositories
+                .iter()
+                .map(|repo| (repo.name.as_str(), repo.entry_count()))
+                .collect::<std::collections::HashMap<_, _>>(),
+        );
         context.insert("statistics", &report.statistics);
+        context.insert("team", &report.team);
+        context.insert("tasks", &task_rows(report));
+        context.insert("time_rows", &time_rows(report));
+        context.insert("groups", &report.groups);
+        context.insert("overdue", &report.overdue);
+        context.insert("stale", &report.stale);
+        context.insert("signals", &report.signals);
+        context.insert("malformed_directives", &report.warnings.malformed);
+        context.insert(
+            "directive_counts",
+            &report
+                .warnings
+                .directive_counts()
+                .into_iter()
+                .map(|(kind, count)| (kind.to_string(), count))
+                .collect::<Vec<_>>(),
+        );
 
         // Add options to context
         context.insert("show_stats", &(options.include_stats && !options.summary_only));
         context.insert("show_activities", &options.include_activities);
+        context.insert("show_notes", &options.include_notes);
         context.insert("summary_only", &options.summary_only);
         context.insert("verbose", &options.verbose);
+        context.insert("repo_links", &self.repo_links);
 
         // Render the template
         self.tera
-            .render("report", &context)
+            .render("report.html", &context)
             .map_err(|e| JrnrvwError::ConfigError(format!("Template rendering error: {}", e)))
     }
 }
 
+/// One row of the sortable task table
+#[derive(Serialize)]
+struct TaskRow<'a> {
+    name: &'a str,
+    repository: &'a str,
+    status: String,
+    priority: &'a str,
+    last_entry: String,
+    entries: usize,
+
+    /// Time logged, `-` when none
+    time: String,
+
+    /// Minutes logged, for sorting
+    minutes: u32,
+}
+
+/// Every task in the report, with its status as of the newest entry
+fn task_rows(report: &Report) -> Vec<TaskRow<'_>> {
+    let as_of = report.repositories.iter().filter_map(|r| r.date_range()).map(|(_, last)| last).max();
+
+    report
+        .repositories
+        .iter()
+        .flat_map(|repo| repo.tasks.iter().map(move |task| (repo, task)))
+        .map(|(repo, task)| TaskRow {
+            name: &task.name,
+            repository: &repo.name,
+            status: as_of
+                .and_then(|as_of| task.status_as_of(as_of))
+                .map_or_else(|| "-".to_string(), |s| s.to_string()),
+            priority: task.priority().unwrap_or("-"),
+            last_entry: task.latest_entry().map_or_else(|| "-".to_string(), |e| e.date.to_string()),
+            entries: task.entry_count(),
+            time: task.time_logged().map_or_else(|| "-".to_string(), |t| t.to_string()),
+            minutes: task.time_logged().map_or(0, |t| t.minutes),
+        })
+        .collect()
+}
+
+/// One row of the time table
+#[derive(Serialize)]
+struct TimeTableRow<'a> {
+    group: &'static str,
+    name: &'a str,
+    time: String,
+}
+
+/// The time summary as table rows, the total last; empty without one
+fn time_rows(report: &Report) -> Vec<TimeTableRow<'_>> {
+    let Some(time) = &report.time else {
+        return Vec::new();
+    };
+
+    let groups = [
+        ("Repository", &time.by_repository),
+        ("Tag", &time.by_tag),
+        ("Day", &time.by_day),
+    ];
+    let mut rows: Vec<TimeTableRow> = groups
+        .into_iter()
+        .flat_map(|(group, rows)| {
+            rows.iter().map(move |row| TimeTableRow { group, name: &row.name, time: row.time.to_string() })
+        })
+        .collect();
+    rows.push(TimeTableRow { group: "Total", name: "", time: time.total.to_string() });
+    rows
+}
+
+/// Escape text for HTML element content and quoted attributes
+///
+/// Unlike Tera's own escaper this leaves `/` alone, so paths stay readable
+/// in the page source.
+fn escape_html(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#x27;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Repository, Statistics, ReportMetadata};
+    use crate::models::{Repository, ReportWarnings, Statistics, ReportMetadata};
     use chrono::Utc;
     use std::path::PathBuf;
 
@@ -233,6 +716,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -257,9 +751,191 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format(&report, &options);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_with_warnings() {
+        let formatter = HtmlFormatter::new().unwrap();
+        let report = Report {
+            metadata: ReportMetadata {
+                generated_at: Utc::now(),
+                period: None,
+                total_entries: 0,
+                repository_count: 0,
+            },
+            repositories: vec![],
+            statistics: Statistics::default(),
+            warnings: ReportWarnings {
+                ignore_section: 2,
+                malformed: vec!["a.md:7: unknown jrnrvw directive 'skip'".to_string()],
+                ..Default::default()
+            },
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
+        };
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("<h2>Warnings</h2>"));
+        assert!(result.contains("<code>jrnrvw:ignore-section</code> fired 2 time(s)"));
+        assert!(result.contains("unknown jrnrvw directive"));
+    }
+
+    #[test]
+    fn test_with_signals() {
+        use crate::models::{Severity, Signal, SignalKind, SignalSource};
+        use chrono::NaiveDate;
+
+        let formatter = HtmlFormatter::new().unwrap();
+        let report = Report::new(vec![], None).with_signals(vec![Signal {
+            severity: Severity::Info,
+            kind: SignalKind::LongEntry,
+            message: "unusually long entry on 2024-05-10".to_string(),
+            sources: vec![SignalSource {
+                date: NaiveDate::from_ymd_opt(2024, 5, 10).unwrap(),
+                path: PathBuf::from("repo1/2024-05-10_journal.md"),
+            }],
+        }]);
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("<h2>Signals</h2>"));
+        assert!(result.contains("<strong class=\"signal-info\">info</strong>: unusually long entry"));
+        assert!(result.contains("<code>repo1/2024-05-10_journal.md</code>"));
+    }
+
+    #[test]
+    fn test_repo_links() {
+        let mut repo = Repository::new("my repo".to_string(), None);
+        repo.add_task(crate::models::Task::new("General".to_string()));
+        let report = Report::new(vec![repo], None);
+
+        let plain = HtmlFormatter::new().unwrap();
+        let result = plain.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("<h3>my repo</h3>"));
+
+        let linked = HtmlFormatter::new().unwrap().with_repo_links(true);
+        let result = linked.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains(r#"<a href="/repo/my%20repo">my repo</a>"#));
+    }
+
+    #[test]
+    fn test_format_repository() {
+        use crate::models::{JournalEntry, Task};
+        use chrono::NaiveDate;
+
+        let mut entry = JournalEntry::new(
+            PathBuf::from("repo1/2024.05.10 - JRN - parser work.md"),
+            NaiveDate::from_ymd_opt(2024, 5, 10).unwrap(),
+        );
+        entry.activities = vec!["Rewrote the tokenizer".to_string()];
+        let mut task = Task::new("parser".to_string());
+        task.add_entry(entry);
+        let mut repo = Repository::new("repo1".to_string(), None);
+        repo.add_task(task);
+
+        let formatter = HtmlFormatter::new().unwrap();
+        let result = formatter.format_repository(&repo, &OutputOptions::default()).unwrap();
+        assert!(result.contains("<h1>repo1</h1>"));
+        assert!(result.contains("<h2>parser</h2>"));
+        assert!(result.contains("2024-05-10"));
+        assert!(result.contains("<li>Rewrote the tokenizer</li>"));
+        assert!(result.contains("<p><strong>Total Entries:</strong> 1</p>"));
+    }
+
+    #[test]
+    fn test_summary_header_and_task_table() {
+        use crate::models::{JournalEntry, Task};
+        use chrono::NaiveDate;
+
+        let mut repo = Repository::new("api".to_string(), None);
+        for (name, day, activity) in [("auth", 1, "[ ] rotate keys"), ("docs", 20, "[x] published")] {
+            let mut entry = JournalEntry::new(PathBuf::from("a.md"), NaiveDate::from_ymd_opt(2025, 11, day).unwrap());
+            entry.activities = vec![activity.to_string()];
+            let mut task = Task::new(name.to_string());
+            task.add_entry(entry);
+            repo.add_task(task);
+        }
+        let report = Report::new(vec![repo], None).with_statistics(Statistics {
+            open_tasks: 3,
+            done_tasks: 1,
+            stale_tasks: 2,
+            ..Default::default()
+        });
+
+        let result = HtmlFormatter::new().unwrap().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains(r#"<div class="summary-item summary-open"><span class="count">3</span>open</div>"#));
+        assert!(result.contains(r#"<span class="count">1</span>done"#));
+        assert!(result.contains(r#"<span class="count">2</span>stale"#));
+        assert!(result.contains(r#"<table class="stats-table sortable">"#));
+        assert!(result.contains(r#"<td class="status-stale">stale</td>"#));
+        assert!(result.contains(r#"<td class="status-done">done</td>"#));
+        assert!(result.contains(r#"<details class="repo-card" open>"#));
+        assert!(result.contains("<script>"));
+        assert!(!result.contains("<link") && !result.contains(" src="));
+    }
+
+    #[test]
+    fn test_journal_text_is_escaped() {
+        use crate::models::{JournalEntry, Task};
+        use chrono::NaiveDate;
+
+        let mut entry = JournalEntry::new(PathBuf::from("a.md"), NaiveDate::from_ymd_opt(2025, 11, 1).unwrap());
+        entry.title = Some("<b>bold</b>".to_string());
+        entry.activities = vec!["<script>alert(\"x\")</script>".to_string()];
+        entry.notes = Some("a & b".to_string());
+        let mut task = Task::new("<img src=x onerror=alert(1)>".to_string());
+        task.add_entry(entry);
+        let mut repo = Repository::new("<i>repo</i>".to_string(), None);
+        repo.add_task(task);
+        let report = Report::new(vec![repo.clone()], None);
+
+        let options = OutputOptions { include_notes: true, ..Default::default() };
+        let formatter = HtmlFormatter::new().unwrap();
+        for html in [
+            formatter.format(&report, &options).unwrap(),
+            formatter.format_repository(&repo, &options).unwrap(),
+        ] {
+            assert!(!html.contains("<script>alert"));
+            assert!(!html.contains("<img") && !html.contains("<b>") && !html.contains("<i>"));
+            assert!(html.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"));
+            assert!(html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+            assert!(html.contains("a &amp; b"));
+        }
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html(r#"<a href="x">it's</a> & /path"#), "&lt;a href=&quot;x&quot;&gt;it&#x27;s&lt;/a&gt; &amp; /path");
+    }
+
+    #[test]
+    fn test_no_warnings_section_when_empty() {
+        let formatter = HtmlFormatter::new().unwrap();
+        let report = Report::new(vec![], None);
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(!result.contains("<h2>Warnings</h2>"));
+    }
 }
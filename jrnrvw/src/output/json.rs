@@ -1,7 +1,9 @@
 //! JSON formatter for machine-readable output
+//!
+//! The output is the serialized [`Report`]; see its docs for the field and
+//! date conventions. [`Report::from_json`] reads it back.
 
-use serde_json;
-use crate::error::{Result, JrnrvwError};
+use crate::error::Result;
 use crate::output::{Formatter, OutputOptions};
 use crate::models::Report;
 
@@ -19,14 +21,12 @@ impl JsonFormatter {
 
     /// Format with pretty printing
     pub fn format_pretty(&self, report: &Report, _options: &OutputOptions) -> Result<String> {
-        serde_json::to_string_pretty(report)
-            .map_err(|e| JrnrvwError::ConfigError(format!("JSON serialization error: {}", e)))
+        report.to_json(true)
     }
 
     /// Format as compact JSON
     pub fn format_compact(&self, report: &Report, _options: &OutputOptions) -> Result<String> {
-        serde_json::to_string(report)
-            .map_err(|e| JrnrvwError::ConfigError(format!("JSON serialization error: {}", e)))
+        report.to_json(false)
     }
 }
 
@@ -50,7 +50,7 @@ impl Formatter for JsonFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Repository, Statistics, ReportMetadata};
+    use crate::models::{Repository, ReportWarnings, Statistics, ReportMetadata};
     use chrono::Utc;
     use std::path::PathBuf;
 
@@ -66,6 +66,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -94,6 +105,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -122,6 +144,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format(&report, &options);
@@ -140,6 +173,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format_compact(&report, &options);
@@ -2,7 +2,7 @@
 
 use crate::error::Result;
 use crate::output::{Formatter, OutputOptions};
-use crate::models::Report;
+use crate::models::{JournalEntry, Report, Repository, Task, TaskStatus};
 
 /// Markdown formatter
 ///
@@ -48,6 +48,94 @@ impl Formatter for MarkdownFormatter {
         output.push_str(&format!("- **Repositories**: {}\n", report.metadata.repository_count));
         output.push_str("\n");
 
+        // Summary
+        format_summary(&mut output, report);
+
+        // Team summary
+        if !report.team.is_empty() {
+            output.push_str("## Team\n\n");
+            output.push_str("| Author | Entries | Completed Tasks |\n");
+            output.push_str("|--------|---------|-----------------|\n");
+            for row in &report.team {
+                output.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    escape_cell(&row.author),
+                    row.entries,
+                    row.completed_tasks
+                ));
+            }
+            output.push('\n');
+        }
+
+        // Time summary
+        if let Some(ref time) = report.time {
+            output.push_str("## Time\n\n");
+            output.push_str("| Group | Name | Time |\n");
+            output.push_str("|-------|------|------|\n");
+            for (group, rows) in [
+                ("Repository", &time.by_repository),
+                ("Tag", &time.by_tag),
+                ("Day", &time.by_day),
+            ] {
+                for row in rows {
+                    output.push_str(&format!("| {} | {} | {} |\n", group, escape_cell(&row.name), row.time));
+                }
+            }
+            output.push_str(&format!("| **Total** | | {} |\n", time.total));
+            output.push('\n');
+        }
+
+        // Activity per group
+        if !report.groups.is_empty() {
+            output.push_str("## Groups\n\n");
+            output.push_str("| Group | Entries | Tasks Opened | Tasks Completed |\n");
+            output.push_str("|-------|---------|--------------|-----------------|\n");
+            for group in &report.groups {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    escape_cell(&group.name),
+                    group.entries,
+                    group.tasks_opened,
+                    group.tasks_completed
+                ));
+            }
+            output.push('\n');
+        }
+
+        // Tasks needing attention
+        if !report.overdue.is_empty() {
+            output.push_str("## Overdue\n\n");
+            output.push_str("| Repository | Task | Due | Days Overdue |\n");
+            output.push_str("|------------|------|-----|--------------|\n");
+            for task in &report.overdue {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    escape_cell(&task.repository),
+                    escape_cell(&task.task),
+                    task.due,
+                    task.days_overdue
+                ));
+            }
+            output.push('\n');
+        }
+
+        if !report.stale.is_empty() {
+            output.push_str("## Stale\n\n");
+            output.push_str("| Repository | Task | Opened | Last Entry | Days Open |\n");
+            output.push_str("|------------|------|--------|------------|-----------|\n");
+            for task in &report.stale {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    escape_cell(&task.repository),
+                    escape_cell(&task.task),
+                    task.opened,
+                    task.last_entry,
+                    task.days_open
+                ));
+            }
+            output.push('\n');
+        }
+
         // Statistics
         if options.include_stats && !options.summary_only {
             output.push_str("## Statistics\n\n");
@@ -55,7 +143,7 @@ impl Formatter for MarkdownFormatter {
             output.push_str("|--------|-------|\n");
             output.push_str(&format!("| Total Entries | {} |\n", report.statistics.total_entries));
             output.push_str(&format!("| Repositories | {} |\n", report.statistics.repositories));
-            output.push_str(&format!("| Unique Tasks | {} |\n", report.statistics.unique_tasks));
+            output.push_str(&format!("| Unique Tasks | {} |\n", report.statistics.unique_tasks_label()));
             output.push_str(&format!("| Active Days | {} |\n", report.statistics.active_days));
 
             if let Some(ref total_time) = report.statistics.total_time {
@@ -67,39 +155,279 @@ impl Formatter for MarkdownFormatter {
 
         // Repositories
         if !options.summary_only {
-            output.push_str("## Repositories\n\n");
-
             for repo in &report.repositories {
-                output.push_str(&format!("### {}\n\n", repo.name));
-                if let Some(ref path) = repo.path {
-                    output.push_str(&format!("- **Path**: `{}`\n", path.display()));
-                }
-                output.push_str(&format!("- **Tasks**: {}\n", repo.tasks.len()));
+                format_repository(&mut output, repo, options);
+            }
+        }
 
-                if options.include_activities {
-                    output.push_str(&format!("- **Entries**: {}\n", repo.entry_count()));
+        // Signals
+        if !report.signals.is_empty() {
+            output.push_str("## Signals\n\n");
+            for signal in &report.signals {
+                output.push_str(&format!("- **{}**: {}\n", signal.severity, signal.message));
+                for source in &signal.sources {
+                    output.push_str(&format!("  - {} `{}`\n", source.date, source.path.display()));
                 }
+            }
+            output.push('\n');
+        }
 
-                if options.include_activities && !repo.tasks.is_empty() && options.verbose {
-                    output.push_str("\n#### Tasks\n\n");
-                    for task in &repo.tasks {
-                        output.push_str(&format!("- **{}**\n", task.name));
-                        output.push_str(&format!("  - Entries: {}\n", task.entries.len()));
-                    }
-                }
+        // Dependencies
+        if report.references.has_dependencies() {
+            format_dependencies(&mut output, report);
+        }
+
+        // Task dependencies
+        if !report.task_graph.is_empty() {
+            format_task_graph(&mut output, report);
+        }
 
-                output.push_str("\n");
+        // Warnings
+        if !report.warnings.is_empty() {
+            output.push_str("## Warnings\n\n");
+            for (kind, count) in report.warnings.directive_counts() {
+                output.push_str(&format!("- `{}` fired {} time(s)\n", kind, count));
+            }
+            for warning in &report.warnings.malformed {
+                output.push_str(&format!("- {}\n", warning));
             }
+            output.push_str("\n");
         }
 
         Ok(output)
     }
 }
 
+/// Per-repository task counts, with a total row
+fn format_summary(output: &mut String, report: &Report) {
+    output.push_str("## Summary\n\n");
+    output.push_str("| Repository | Tasks | Open | Done | Entries | Last Entry |\n");
+    output.push_str("|------------|-------|------|------|---------|------------|\n");
+
+    let mut totals = (0, 0, 0, 0);
+    let mut last_overall = None;
+    for repo in &report.repositories {
+        let open = count_status(&repo.tasks, TaskStatus::Open);
+        let done = count_status(&repo.tasks, TaskStatus::Done);
+        let last = repo.date_range().map(|(_, last)| last);
+
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            escape_cell(&repo.name),
+            repo.tasks.len(),
+            open,
+            done,
+            repo.entry_count(),
+            last.map_or_else(|| "-".to_string(), |d| d.to_string())
+        ));
+
+        totals.0 += repo.tasks.len();
+        totals.1 += open;
+        totals.2 += done;
+        totals.3 += repo.entry_count();
+        last_overall = last_overall.max(last);
+    }
+
+    output.push_str(&format!(
+        "| **Total** | {} | {} | {} | {} | {} |\n\n",
+        totals.0,
+        totals.1,
+        totals.2,
+        totals.3,
+        last_overall.map_or_else(|| "-".to_string(), |d| d.to_string())
+    ));
+}
+
+fn count_status(tasks: &[Task], status: TaskStatus) -> usize {
+    tasks.iter().filter(|t| t.status() == Some(status)).count()
+}
+
+/// One repository: overview, task table and (verbose) its entries
+fn format_repository(output: &mut String, repo: &Repository, options: &OutputOptions) {
+    output.push_str(&format!("## {}\n\n", repo.name));
+    if let Some(ref path) = repo.path {
+        output.push_str(&format!("- **Path**: `{}`\n", path.display()));
+    }
+    output.push_str(&format!("- **Tasks**: {}\n", repo.tasks.len()));
+
+    if options.include_activities {
+        output.push_str(&format!("- **Entries**: {}\n", repo.entry_count()));
+    }
+    output.push('\n');
+
+    if repo.tasks.is_empty() {
+        return;
+    }
+
+    output.push_str("| Task | Status | Priority | Last Entry | Entries |\n");
+    output.push_str("|------|--------|----------|------------|---------|\n");
+    for task in &repo.tasks {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            escape_cell(&task.name),
+            task.status().map_or_else(|| "-".to_string(), |s| s.to_string()),
+            task.priority().map_or_else(|| "-".to_string(), escape_cell),
+            task.latest_entry().map_or_else(|| "-".to_string(), |e| e.date.to_string()),
+            task.entry_count()
+        ));
+    }
+    output.push('\n');
+
+    if options.verbose {
+        let mut entries: Vec<(&Task, &JournalEntry)> = repo
+            .tasks
+            .iter()
+            .flat_map(|task| task.entries.iter().map(move |entry| (task, entry)))
+            .collect();
+        entries.sort_by_key(|(_, entry)| entry.date);
+
+        output.push_str("### Entries\n\n");
+        output.push_str("| Date | Task | Entry |\n");
+        output.push_str("|------|------|-------|\n");
+        for (task, entry) in entries {
+            output.push_str(&format!(
+                "| {} | {} | {} |\n",
+                entry.date,
+                escape_cell(&task.name),
+                escape_cell(&truncate(&entry_body(entry, options), options.max_entry_chars))
+            ));
+        }
+        output.push('\n');
+    }
+}
+
+/// Blocked tasks linked to what they wait on, dangling references and cycles
+fn format_dependencies(output: &mut String, report: &Report) {
+    let references = &report.references;
+    output.push_str("## Dependencies\n\n");
+
+    if references.blocking().next().is_some() {
+        output.push_str("| Task | Repository | Date | Blocked On |\n");
+        output.push_str("|------|------------|------|------------|\n");
+        for reference in references.blocking() {
+            let source = &reference.source;
+            let target = match (&reference.target, reference.dangling) {
+                (Some(target), _) => format!("[{}](<{}>)", escape_cell(&target.to_string()), target.path.display()),
+                (None, Some(dangling)) => format!("{} ({})", escape_cell(&reference.target_label()), dangling),
+                (None, None) => escape_cell(&reference.target_label()),
+            };
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                escape_cell(source.task.as_deref().unwrap_or("Untitled")),
+                escape_cell(&source.repository),
+                source.date,
+                target
+            ));
+        }
+        output.push('\n');
+    }
+
+    if references.dangling().next().is_some() {
+        output.push_str("### Dangling References\n\n");
+        for reference in references.dangling() {
+            output.push_str(&format!(
+                "- {} → {}: {} (`{}`)\n",
+                reference.source,
+                reference.target_label(),
+                reference.dangling.map(|d| d.to_string()).unwrap_or_default(),
+                reference.source.path.display()
+            ));
+        }
+        output.push('\n');
+    }
+
+    let cycles = references.cycles();
+    if !cycles.is_empty() {
+        output.push_str("### Circular Dependencies\n\n");
+        for cycle in cycles {
+            output.push_str(&format!("- {}\n", cycle.join(", ")));
+        }
+        output.push('\n');
+    }
+}
+
+/// Each repository's tasks as a numbered list in dependency order, then
+/// unresolved dependencies and cycles
+fn format_task_graph(output: &mut String, report: &Report) {
+    let graph = &report.task_graph;
+    output.push_str("## Task Dependencies\n\n");
+
+    for order in &graph.order {
+        output.push_str(&format!("### {}\n\n", order.repository));
+        for (position, task) in order.tasks.iter().enumerate() {
+            output.push_str(&format!("{}. {} (`{}`)", position + 1, task.name, task.id));
+            let after: Vec<String> = graph
+                .dependencies_of(task)
+                .map(|dependency| dependency.label_in(&order.repository))
+                .collect();
+            if !after.is_empty() {
+                output.push_str(&format!(", after {}", after.join(", ")));
+            }
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+
+    if !graph.unresolved.is_empty() {
+        output.push_str("### Unresolved Dependencies\n\n");
+        for unresolved in &graph.unresolved {
+            output.push_str(&format!("- {} → `{}`\n", unresolved.task, unresolved.reference));
+        }
+        output.push('\n');
+    }
+
+    if !graph.cycles.is_empty() {
+        output.push_str("### Dependency Cycles\n\n");
+        for error in graph.cycle_errors() {
+            output.push_str(&format!("- {}\n", error));
+        }
+        output.push('\n');
+    }
+}
+
+/// Activities and notes of an entry flattened onto one line
+fn entry_body(entry: &JournalEntry, options: &OutputOptions) -> String {
+    let mut parts = Vec::new();
+    if options.include_activities && !entry.activities.is_empty() {
+        parts.push(entry.activities.join("; "));
+    }
+    if options.include_notes {
+        if let Some(ref notes) = entry.notes {
+            parts.push(notes.clone());
+        }
+    }
+    if parts.is_empty() {
+        return entry.description();
+    }
+
+    parts.join(" — ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cut `text` to at most `max_chars` characters, marking the cut (0 = no limit)
+fn truncate(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(max_chars).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Make text safe inside a table cell
+///
+/// Pipes would end the cell and fences would open a code block, so both are
+/// backslash-escaped; line breaks become spaces.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("```", "\\`\\`\\`")
+        .replace("~~~", "\\~\\~\\~")
+        .replace(['\r', '\n'], " ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Repository, Statistics, ReportMetadata};
+    use crate::models::{Repository, ReportWarnings, Statistics, ReportMetadata};
     use chrono::Utc;
     use std::path::PathBuf;
 
@@ -115,6 +443,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -148,6 +487,17 @@ mod tests {
             },
             repositories: vec![repo],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -157,9 +507,50 @@ mod tests {
         };
 
         let result = formatter.format(&report, &options).unwrap();
-        assert!(result.contains("#### Tasks"));
-        assert!(result.contains("test_task"));
-        assert!(result.contains("Entries: 1"));
+        assert!(result.contains("## test_repo"));
+        assert!(result.contains("| test_task | - | - | 2025-11-13 | 1 |"));
+        assert!(result.contains("### Entries"));
+        assert!(result.contains("| 2025-11-13 | test_task | Untitled |"));
+    }
+
+    #[test]
+    fn test_summary_counts_task_status() {
+        use crate::models::Task;
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+        let mut repo = Repository::new("api".to_string(), None);
+        for (name, activity) in [("one", "[x] shipped"), ("two", "[ ] pending"), ("three", "plain")] {
+            let mut entry = crate::models::JournalEntry::new(PathBuf::from("test.md"), date);
+            entry.activities = vec![activity.to_string()];
+            let mut task = Task::new(name.to_string());
+            task.add_entry(entry);
+            repo.add_task(task);
+        }
+
+        let report = Report::new(vec![repo], None);
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Summary"));
+        assert!(result.contains("| api | 3 | 1 | 1 | 3 | 2025-11-13 |"));
+        assert!(result.contains("| **Total** | 3 | 1 | 1 | 3 | 2025-11-13 |"));
+        assert!(result.contains("| one | done | - | 2025-11-13 | 1 |"));
+        assert!(result.contains("| three | - | - | 2025-11-13 | 1 |"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly", 7), "exactly");
+        assert_eq!(truncate("a long body of text", 7), "a long…");
+        assert_eq!(truncate("ünïcödé", 3), "ünï…");
+        assert_eq!(truncate("no limit at all", 0), "no limit at all");
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("a | b"), "a \\| b");
+        assert_eq!(escape_cell("```rust\nfn x() {}\n```"), "\\`\\`\\`rust fn x() {} \\`\\`\\`");
+        assert_eq!(escape_cell("~~~"), "\\~\\~\\~");
     }
 
     #[test]
@@ -176,6 +567,17 @@ mod tests {
             },
             repositories: vec![repo],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -200,6 +602,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -232,6 +645,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -254,6 +678,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: stats,
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -265,6 +700,68 @@ mod tests {
         assert!(result.contains("| Total Time | 20h 15m |"));
     }
 
+    #[test]
+    fn test_time_summary() {
+        use crate::models::{TimeLogged, TimeRow, TimeSummary};
+
+        let row = |name: &str, minutes| TimeRow { name: name.to_string(), time: TimeLogged::new(minutes, false) };
+        let report = Report::new(vec![], None).with_time(Some(TimeSummary {
+            total: TimeLogged::new(150, true),
+            by_repository: vec![row("api|v2", 150)],
+            by_tag: vec![],
+            by_day: vec![row("2025-11-13", 150)],
+        }));
+
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Time"));
+        assert!(result.contains("| Repository | api\\|v2 | 2h 30m |"));
+        assert!(result.contains("| Day | 2025-11-13 | 2h 30m |"));
+        assert!(result.contains("| **Total** | | ~2h 30m |"));
+    }
+
+    #[test]
+    fn test_groups() {
+        use crate::models::GroupSummary;
+
+        let mut tag = GroupSummary::empty("ops|infra", None);
+        tag.entries = 2;
+        tag.tasks_completed = 1;
+        let report = Report::new(vec![], None).with_groups(vec![tag, GroupSummary::empty("Untagged", None)]);
+
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Groups"));
+        assert!(result.contains("| ops\\|infra | 2 | 0 | 1 |"));
+        assert!(result.contains("| Untagged | 0 | 0 | 0 |"));
+    }
+
+    #[test]
+    fn test_alerts() {
+        use crate::models::{OverdueTask, StaleTask};
+        use chrono::NaiveDate;
+
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let overdue = OverdueTask {
+            repository: "api".to_string(),
+            task: "auth|login".to_string(),
+            due: date("2025-11-03"),
+            days_overdue: 17,
+        };
+        let stale = StaleTask {
+            repository: "web".to_string(),
+            task: "css".to_string(),
+            opened: date("2025-09-01"),
+            last_entry: date("2025-10-01"),
+            days_open: 61,
+        };
+        let report = Report::new(vec![], None).with_alerts(vec![overdue], vec![stale]);
+
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Overdue"));
+        assert!(result.contains("| api | auth\\|login | 2025-11-03 | 17 |"));
+        assert!(result.contains("## Stale"));
+        assert!(result.contains("| web | css | 2025-09-01 | 2025-10-01 | 61 |"));
+    }
+
     #[test]
     fn test_markdown_default() {
         let formatter = MarkdownFormatter::default();
@@ -277,9 +774,123 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format(&report, &options);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_with_warnings() {
+        let formatter = MarkdownFormatter::new();
+        let report = Report {
+            metadata: ReportMetadata {
+                generated_at: Utc::now(),
+                period: None,
+                total_entries: 0,
+                repository_count: 0,
+            },
+            repositories: vec![],
+            statistics: Statistics::default(),
+            warnings: ReportWarnings {
+                ignore_section: 2,
+                malformed: vec!["a.md:7: unknown jrnrvw directive 'skip'".to_string()],
+                ..Default::default()
+            },
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
+        };
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Warnings"));
+        assert!(result.contains("- `jrnrvw:ignore-section` fired 2 time(s)"));
+        assert!(result.contains("- a.md:7: unknown jrnrvw directive 'skip'"));
+    }
+
+    #[test]
+    fn test_with_signals() {
+        use crate::models::{Severity, Signal, SignalKind, SignalSource};
+        use chrono::NaiveDate;
+
+        let formatter = MarkdownFormatter::new();
+        let report = Report::new(vec![], None).with_signals(vec![Signal {
+            severity: Severity::Warning,
+            kind: SignalKind::CompletionTrend,
+            message: "task completion rate trending down 3 weeks in a row (from 80% to 40%)"
+                .to_string(),
+            sources: vec![SignalSource {
+                date: NaiveDate::from_ymd_opt(2024, 5, 10).unwrap(),
+                path: PathBuf::from("repo1/2024-05-10_journal.md"),
+            }],
+        }]);
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Signals"));
+        assert!(result.contains("- **warning**: task completion rate trending down 3 weeks"));
+        assert!(result.contains("  - 2024-05-10 `repo1/2024-05-10_journal.md`"));
+    }
+
+    #[test]
+    fn test_with_dependencies() {
+        use crate::analyzer::ReferenceDetector;
+        use crate::models::JournalEntry;
+        use chrono::NaiveDate;
+
+        let entry = |repo: &str, day: u32, activity: &str| {
+            let date = NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+            let mut entry = JournalEntry::new(PathBuf::from(format!("{}/2024-05-{:02}.md", repo, day)), date);
+            entry.repository = Some(repo.to_string());
+            entry.task = Some(format!("{} task", repo));
+            entry.activities = vec![activity.to_string()];
+            entry
+        };
+        let entries = vec![
+            entry("infra", 2, "Waiting for ref:web#2024-05-06"),
+            entry("web", 6, "Blocked on ref:infra#2024-05-02, see also ref:billing#2024-05-01"),
+        ];
+        let references = ReferenceDetector::default().detect(&entries, &entries);
+        let report = Report::new(vec![], None).with_references(references);
+
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("## Dependencies"));
+        assert!(result.contains("| web task | web | 2024-05-06 | [infra 2024-05-02](<infra/2024-05-02.md>) |"));
+        assert!(result.contains("| web task | web | 2024-05-06 | billing 2024-05-01 (unknown repository) |"));
+        assert!(result.contains("### Dangling References\n\n- web 2024-05-06 → billing 2024-05-01: unknown repository (`web/2024-05-06.md`)"));
+        assert!(result.contains("### Circular Dependencies\n\n- infra, web\n"));
+    }
+
+    #[test]
+    fn test_no_dependencies_section_without_blockers() {
+        let report = Report::new(vec![], None);
+        let result = MarkdownFormatter::new().format(&report, &OutputOptions::default()).unwrap();
+        assert!(!result.contains("## Dependencies"));
+    }
+
+    #[test]
+    fn test_no_warnings_section_when_empty() {
+        let formatter = MarkdownFormatter::new();
+        let report = Report::new(vec![], None);
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(!result.contains("## Warnings"));
+    }
 }
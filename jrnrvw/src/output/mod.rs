@@ -5,6 +5,10 @@ pub mod markdown;
 pub mod json;
 pub mod html;
 pub mod csv;
+pub mod dot;
+pub mod notify;
+pub mod search;
+pub mod stream;
 
 use crate::{Report, Result};
 
@@ -17,6 +21,8 @@ pub struct OutputOptions {
     pub include_notes: bool,
     pub include_stats: bool,
     pub summary_only: bool,
+    /// Longest entry body shown before it is cut short (0 = no limit)
+    pub max_entry_chars: usize,
 }
 
 impl Default for OutputOptions {
@@ -28,6 +34,7 @@ impl Default for OutputOptions {
             include_notes: false,
             include_stats: true,
             summary_only: false,
+            max_entry_chars: 200,
         }
     }
 }
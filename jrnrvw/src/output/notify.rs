@@ -0,0 +1,356 @@
+//! Daily digest notifications for Slack and generic JSON webhooks
+//!
+//! The digest covers one day: how many entries were written, which tasks
+//! were finished, and the most severe signals. Slack gets it as Block Kit
+//! messages, split so no section exceeds Slack's per-block text limit; a
+//! generic webhook gets the digest itself as JSON.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{JrnrvwError, Result};
+use crate::models::{Report, Signal};
+
+/// Most characters Slack accepts in one section block's text
+pub const SLACK_BLOCK_LIMIT: usize = 3000;
+
+/// Signals included in a digest, most severe first
+pub const TOP_SIGNALS: usize = 3;
+
+/// How long to wait for a webhook to answer
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest `Retry-After` honoured before trying again anyway
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Where a digest is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// Slack incoming webhook
+    Slack,
+
+    /// Any endpoint accepting a JSON POST
+    Webhook,
+}
+
+/// One day's activity, as posted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Digest {
+    /// Day the digest covers
+    pub date: NaiveDate,
+
+    /// Entries written that day
+    pub entries: usize,
+
+    /// Tasks finished that day, as `task (repository)`
+    pub completed_tasks: Vec<String>,
+
+    /// The [`TOP_SIGNALS`] most severe signals in the report
+    pub signals: Vec<Signal>,
+}
+
+impl Digest {
+    /// Digest of `date` from a report covering at least that day
+    pub fn from_report(report: &Report, date: NaiveDate) -> Self {
+        let mut entries = 0;
+        let mut completed_tasks = Vec::new();
+        for repo in &report.repositories {
+            for task in &repo.tasks {
+                entries += task.entries.iter().filter(|e| e.date == date).count();
+                if task.completed_on() == Some(date) {
+                    completed_tasks.push(format!("{} ({})", task.name, repo.name));
+                }
+            }
+        }
+
+        // Stable, so equally severe signals keep the report's order
+        let mut signals = report.signals.clone();
+        signals.sort_by_key(|signal| std::cmp::Reverse(signal.severity));
+        signals.truncate(TOP_SIGNALS);
+
+        Self {
+            date,
+            entries,
+            completed_tasks,
+            signals,
+        }
+    }
+
+    /// One-line summary, used as the notification text
+    pub fn summary(&self) -> String {
+        format!(
+            "Journal digest for {}: {} {}, {} {} completed",
+            self.date,
+            self.entries,
+            if self.entries == 1 { "entry" } else { "entries" },
+            self.completed_tasks.len(),
+            if self.completed_tasks.len() == 1 { "task" } else { "tasks" },
+        )
+    }
+
+    /// Request bodies to post to a channel of `kind`, in order
+    pub fn payloads(&self, kind: ChannelKind) -> Result<Vec<Value>> {
+        match kind {
+            ChannelKind::Slack => Ok(slack_messages(self)),
+            ChannelKind::Webhook => Ok(vec![serde_json::to_value(self)?]),
+        }
+    }
+}
+
+/// Slack Block Kit messages for `digest`
+///
+/// The first message carries a header and the counts. Completed tasks and
+/// signals are packed line by line into sections of at most
+/// [`SLACK_BLOCK_LIMIT`] characters, one section per message, so a long day
+/// becomes several messages instead of one Slack rejects.
+pub fn slack_messages(digest: &Digest) -> Vec<Value> {
+    let mut lines = Vec::new();
+    if !digest.completed_tasks.is_empty() {
+        lines.push("*Completed*".to_string());
+        lines.extend(digest.completed_tasks.iter().map(|task| format!("• {}", escape(task))));
+    }
+    if !digest.signals.is_empty() {
+        lines.push("*Signals*".to_string());
+        lines.extend(digest.signals.iter().map(|signal| {
+            let icon = match signal.severity {
+                crate::models::Severity::Critical => ":rotating_light:",
+                crate::models::Severity::Warning => ":warning:",
+                crate::models::Severity::Info => ":information_source:",
+            };
+            format!("{} {}", icon, escape(&signal.message))
+        }));
+    }
+    let chunks = pack_lines(&lines, SLACK_BLOCK_LIMIT);
+
+    let counts = format!(
+        "*{}* {} · *{}* {} completed",
+        digest.entries,
+        if digest.entries == 1 { "entry" } else { "entries" },
+        digest.completed_tasks.len(),
+        if digest.completed_tasks.len() == 1 { "task" } else { "tasks" },
+    );
+    let title = format!("Journal digest for {}", digest.date);
+
+    let mut first = vec![
+        json!({ "type": "header", "text": { "type": "plain_text", "text": title } }),
+        section(&counts),
+    ];
+    let total = chunks.len().max(1);
+    let mut chunks = chunks.into_iter();
+    first.extend(chunks.next().map(|chunk| section(&chunk)));
+
+    let mut messages = vec![json!({ "text": digest.summary(), "blocks": first })];
+    for (index, chunk) in chunks.enumerate() {
+        let part = format!("{} ({}/{})", title, index + 2, total);
+        messages.push(json!({
+            "text": part,
+            "blocks": [
+                { "type": "context", "elements": [{ "type": "mrkdwn", "text": part }] },
+                section(&chunk),
+            ],
+        }));
+    }
+    messages
+}
+
+fn section(text: &str) -> Value {
+    json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } })
+}
+
+/// Join `lines` into texts of at most `limit` characters, breaking only
+/// between lines; a single line over the limit is cut short
+fn pack_lines(lines: &[String], limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for line in lines {
+        let line = truncate(line, limit);
+        let len = line.chars().count();
+        let needed = if current.is_empty() { len } else { current_len + 1 + len };
+        if needed > limit {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+            current_len += 1;
+        }
+        current.push_str(&line);
+        current_len += len;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn truncate(line: &str, limit: usize) -> String {
+    if line.chars().count() <= limit {
+        return line.to_string();
+    }
+    let mut cut: String = line.chars().take(limit - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Escape the characters Slack's mrkdwn treats as control sequences
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Posts payloads to one webhook, retrying failures that may be transient
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    /// Webhook URL; a secret, so never included in errors
+    pub url: String,
+
+    /// Attempts per payload before giving up
+    pub max_attempts: u32,
+
+    /// Wait after the first failed attempt, doubled after each further one
+    pub retry_delay: Duration,
+}
+
+impl Notifier {
+    /// Notifier for `url` with three attempts a second apart
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            max_attempts: 3,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Post each payload in order, stopping at the first that can't be
+    /// delivered
+    pub fn send(&self, payloads: &[Value]) -> Result<()> {
+        for (index, payload) in payloads.iter().enumerate() {
+            self.post(payload).map_err(|reason| {
+                JrnrvwError::Notify(format!("message {} of {}: {}", index + 1, payloads.len(), reason))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Post one payload; connection errors, 429 and 5xx answers are retried
+    fn post(&self, payload: &Value) -> std::result::Result<(), String> {
+        let mut delay = self.retry_delay;
+        for attempt in 1..=self.max_attempts.max(1) {
+            let (reason, retry_after) = match ureq::post(&self.url).timeout(TIMEOUT).send_json(payload) {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, response)) if code == 429 || code >= 500 => {
+                    let retry_after = response
+                        .header("Retry-After")
+                        .and_then(|secs| secs.trim().parse().ok())
+                        .map(|secs| Duration::from_secs(secs).min(MAX_RETRY_AFTER));
+                    (format!("HTTP {}", code), retry_after)
+                }
+                Err(ureq::Error::Status(code, response)) => {
+                    let body = response.into_string().unwrap_or_default();
+                    return Err(format!("rejected with HTTP {}: {}", code, body.trim()));
+                }
+                Err(ureq::Error::Transport(transport)) => {
+                    // Not `transport.to_string()`, which would include the URL
+                    let detail = transport.message().map(|m| format!(": {}", m)).unwrap_or_default();
+                    (format!("{}{}", transport.kind(), detail), None)
+                }
+            };
+
+            if attempt == self.max_attempts.max(1) {
+                return Err(format!("{} (after {} attempts)", reason, attempt));
+            }
+            thread::sleep(retry_after.unwrap_or(delay).max(delay));
+            delay *= 2;
+        }
+        unreachable!("the last attempt always returns")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Severity, SignalKind};
+
+    fn digest(completed: usize, name_len: usize) -> Digest {
+        Digest {
+            date: NaiveDate::from_ymd_opt(2025, 11, 20).unwrap(),
+            entries: 4,
+            completed_tasks: (0..completed).map(|i| format!("{:0>width$}", i, width = name_len)).collect(),
+            signals: Vec::new(),
+        }
+    }
+
+    fn section_texts(message: &Value) -> Vec<&str> {
+        message["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|block| block["type"] == "section")
+            .map(|block| block["text"]["text"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_small_digest_is_one_message() {
+        let mut digest = digest(2, 5);
+        digest.signals.push(Signal {
+            severity: Severity::Warning,
+            kind: SignalKind::VolumeDrop,
+            message: "api: <2 entries>".to_string(),
+            sources: Vec::new(),
+        });
+
+        let messages = slack_messages(&digest);
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["blocks"][0]["type"], "header");
+        assert_eq!(messages[0]["text"], "Journal digest for 2025-11-20: 4 entries, 2 tasks completed");
+        let texts = section_texts(&messages[0]);
+        assert_eq!(texts[0], "*4* entries · *2* tasks completed");
+        assert!(texts[1].contains("• 00000\n• 00001"));
+        assert!(texts[1].contains(":warning: api: &lt;2 entries&gt;"));
+    }
+
+    #[test]
+    fn test_long_digest_is_split_between_lines() {
+        // 100 lines of 52 characters need two sections
+        let messages = slack_messages(&digest(100, 50));
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1]["text"], "Journal digest for 2025-11-20 (2/2)");
+        let mut tasks = 0;
+        for message in &messages {
+            for text in section_texts(message) {
+                assert!(text.chars().count() <= SLACK_BLOCK_LIMIT);
+                tasks += text.lines().filter(|line| line.starts_with("• ")).count();
+            }
+        }
+        assert_eq!(tasks, 100);
+    }
+
+    #[test]
+    fn test_overlong_line_is_cut() {
+        let chunks = pack_lines(&["x".repeat(5000), "y".to_string()], SLACK_BLOCK_LIMIT);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].chars().count(), SLACK_BLOCK_LIMIT);
+        assert!(chunks[0].ends_with('…'));
+        assert_eq!(chunks[1], "y");
+    }
+
+    #[test]
+    fn test_webhook_payload_is_the_digest() {
+        let digest = digest(1, 3);
+        let payloads = digest.payloads(ChannelKind::Webhook).unwrap();
+
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0]["date"], "2025-11-20");
+        assert_eq!(payloads[0]["entries"], 4);
+        assert_eq!(payloads[0]["completed_tasks"][0], "000");
+    }
+}
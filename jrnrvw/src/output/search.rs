@@ -0,0 +1,131 @@
+//! Plain-text search results
+//!
+//! Each result is a heading with the journal's path, match line, and date,
+//! followed by its snippet in `grep -n` style: `42:` on lines with a match,
+//! `41-` on the lines of context around them.
+
+use colored::Colorize;
+use std::path::Path;
+
+use crate::analyzer::search::{MatchKind, SearchHit, SnippetLine};
+
+/// Results of searching for `query` under `root`, with paths shown
+/// relative to it
+pub fn format_results(hits: &[SearchHit], query: &str, root: &Path, colored: bool) -> String {
+    if hits.is_empty() {
+        return format!("No matches for \"{}\"\n", query);
+    }
+
+    let mut output = format!(
+        "{} {} for \"{}\"\n",
+        hits.len(),
+        if hits.len() == 1 { "match" } else { "matches" },
+        query
+    );
+    for hit in hits {
+        let path = hit.path.strip_prefix(root).unwrap_or(&hit.path);
+        let heading = format!("{}:{}", path.display(), hit.line);
+        let kind = match hit.kind {
+            MatchKind::Phrase => "phrase",
+            MatchKind::Words => "words",
+            MatchKind::Fuzzy => "fuzzy",
+        };
+        output.push_str(&format!(
+            "\n{}  {}  {} ({})\n",
+            if colored { heading.bold().to_string() } else { heading },
+            hit.date,
+            hit.title,
+            kind
+        ));
+
+        let width = hit.snippet.last().map_or(1, |line| line.number.to_string().len());
+        for line in &hit.snippet {
+            let marker = if line.highlights.is_empty() { '-' } else { ':' };
+            output.push_str(&format!(
+                "  {:>width$}{} {}\n",
+                line.number,
+                marker,
+                highlight(line, colored),
+                width = width
+            ));
+        }
+    }
+    output
+}
+
+/// A snippet line with its matched words in bold yellow
+fn highlight(line: &SnippetLine, colored: bool) -> String {
+    if !colored {
+        return line.text.clone();
+    }
+    let mut text = String::new();
+    let mut at = 0;
+    for &(start, end) in &line.highlights {
+        text.push_str(&line.text[at..start]);
+        text.push_str(&line.text[start..end].yellow().bold().to_string());
+        at = end;
+    }
+    text.push_str(&line.text[at..]);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn hit() -> SearchHit {
+        SearchHit {
+            path: PathBuf::from("/journals/api/2025.09.14 - JRN - outage.md"),
+            date: NaiveDate::from_ymd_opt(2025, 9, 14).unwrap(),
+            repository: Some("api".to_string()),
+            title: "Outage review".to_string(),
+            kind: MatchKind::Phrase,
+            span: 2,
+            occurrences: 1,
+            line: 10,
+            snippet: vec![
+                SnippetLine {
+                    number: 9,
+                    text: "Timeouts at 09:00.".to_string(),
+                    highlights: vec![],
+                },
+                SnippetLine {
+                    number: 10,
+                    text: "Root cause: pool exhaustion.".to_string(),
+                    highlights: vec![(12, 16), (17, 27)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_format_results() {
+        let text = format_results(&[hit()], "pool exhaustion", Path::new("/journals"), false);
+        assert_eq!(
+            text,
+            "1 match for \"pool exhaustion\"\n\
+             \n\
+             api/2025.09.14 - JRN - outage.md:10  2025-09-14  Outage review (phrase)\n   \
+             9- Timeouts at 09:00.\n  \
+             10: Root cause: pool exhaustion.\n"
+        );
+
+        let none = format_results(&[], "pool", Path::new("/journals"), false);
+        assert_eq!(none, "No matches for \"pool\"\n");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let line = &hit().snippet[1];
+        assert_eq!(highlight(line, false), "Root cause: pool exhaustion.");
+
+        // Whether colour codes appear depends on the terminal, but the text
+        // between the words is kept either way
+        let text = highlight(line, true);
+        assert!(text.starts_with("Root cause: "));
+        assert!(text.contains("pool"));
+        assert!(text.ends_with('.'));
+    }
+}
@@ -0,0 +1,62 @@
+//! Incremental output for streamed text such as LLM summaries
+
+use std::io::Write;
+
+use crate::error::Result;
+
+/// Write each chunk as soon as it arrives and return the full text
+///
+/// Output is flushed after every chunk so the reader sees progress. The
+/// first error stops the stream; everything before it has been written.
+pub fn write_stream<I, W>(chunks: I, out: &mut W) -> Result<String>
+where
+    I: IntoIterator<Item = Result<String>>,
+    W: Write,
+{
+    let mut text = String::new();
+    for chunk in chunks {
+        let chunk = chunk?;
+        out.write_all(chunk.as_bytes())?;
+        out.flush()?;
+        text.push_str(&chunk);
+    }
+
+    // Finish the line so a following prompt or message starts cleanly
+    if !text.is_empty() && !text.ends_with('\n') {
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::JrnrvwError;
+
+    #[test]
+    fn test_writes_chunks_and_ends_line() {
+        let mut out = Vec::new();
+        let chunks = vec![Ok("Hello".to_string()), Ok(", world".to_string())];
+        let text = write_stream(chunks, &mut out).unwrap();
+        assert_eq!(text, "Hello, world");
+        assert_eq!(out, b"Hello, world\n");
+    }
+
+    #[test]
+    fn test_stops_at_error_after_partial_output() {
+        let mut out = Vec::new();
+        let chunks = vec![
+            Ok("Half ".to_string()),
+            Err(JrnrvwError::Llm {
+                message: "connection reset".to_string(),
+                partial: "Half ".to_string(),
+            }),
+            Ok("never".to_string()),
+        ];
+        let err = write_stream(chunks, &mut out).unwrap_err();
+        assert!(matches!(err, JrnrvwError::Llm { ref partial, .. } if partial == "Half "));
+        assert_eq!(out, b"Half ");
+    }
+}
@@ -3,7 +3,8 @@
 use colored::Colorize;
 use crate::error::Result;
 use crate::output::{Formatter, OutputOptions};
-use crate::models::Report;
+use crate::analyzer::grouper::week_label;
+use crate::models::{Metrics, Report, Severity};
 
 /// Plain text formatter
 ///
@@ -64,7 +65,7 @@ impl Formatter for TextFormatter {
 
             output.push_str(&format!("  Total Entries: {}\n", report.statistics.total_entries));
             output.push_str(&format!("  Repositories: {}\n", report.statistics.repositories));
-            output.push_str(&format!("  Unique Tasks: {}\n", report.statistics.unique_tasks));
+            output.push_str(&format!("  Unique Tasks: {}\n", report.statistics.unique_tasks_label()));
             output.push_str(&format!("  Active Days: {}\n", report.statistics.active_days));
 
             if let Some(ref total_time) = report.statistics.total_time {
@@ -74,6 +75,118 @@ impl Formatter for TextFormatter {
             output.push_str("\n");
         }
 
+        // Throughput per week
+        if options.include_stats && !options.summary_only && !report.metrics.weeks.is_empty() {
+            output.push_str(&format_metrics(&report.metrics, options));
+        }
+
+        // Team summary
+        if !report.team.is_empty() {
+            let team_header = "Team";
+            if options.colored {
+                output.push_str(&team_header.bold().to_string());
+            } else {
+                output.push_str(team_header);
+            }
+            output.push('\n');
+
+            for row in &report.team {
+                output.push_str(&format!(
+                    "  {}: {} entries, {} completed tasks\n",
+                    row.author, row.entries, row.completed_tasks
+                ));
+            }
+
+            output.push('\n');
+        }
+
+        // Time summary
+        if let Some(ref time) = report.time {
+            let time_header = "Time";
+            if options.colored {
+                output.push_str(&time_header.bold().to_string());
+            } else {
+                output.push_str(time_header);
+            }
+            output.push('\n');
+
+            output.push_str(&format!("  Total: {}\n", time.total));
+            for (group, rows) in [
+                ("By repository", &time.by_repository),
+                ("By tag", &time.by_tag),
+                ("By day", &time.by_day),
+            ] {
+                if rows.is_empty() {
+                    continue;
+                }
+                output.push_str(&format!("  {}\n", group));
+                for row in rows {
+                    output.push_str(&format!("    {}: {}\n", row.name, row.time));
+                }
+            }
+
+            output.push('\n');
+        }
+
+        // Activity per group
+        if !report.groups.is_empty() {
+            let groups_header = "Groups";
+            if options.colored {
+                output.push_str(&groups_header.bold().to_string());
+            } else {
+                output.push_str(groups_header);
+            }
+            output.push('\n');
+
+            for group in &report.groups {
+                output.push_str(&format!(
+                    "  {}: {} entries, {} tasks opened, {} tasks completed\n",
+                    group.name, group.entries, group.tasks_opened, group.tasks_completed
+                ));
+            }
+
+            output.push('\n');
+        }
+
+        // Tasks needing attention
+        if !report.overdue.is_empty() {
+            let overdue_header = "Overdue";
+            if options.colored {
+                output.push_str(&overdue_header.red().bold().to_string());
+            } else {
+                output.push_str(overdue_header);
+            }
+            output.push('\n');
+
+            for task in &report.overdue {
+                output.push_str(&format!(
+                    "  {}: {} (due {}, {} days overdue)\n",
+                    task.repository, task.task, task.due, task.days_overdue
+                ));
+            }
+
+            output.push('\n');
+        }
+
+        if !report.stale.is_empty() {
+            let stale_header = "Stale";
+            if options.colored {
+                output.push_str(&stale_header.yellow().bold().to_string());
+            } else {
+                output.push_str(stale_header);
+            }
+            output.push('\n');
+
+            for task in &report.stale {
+                output.push_str(&format!(
+                    "  {}: {} (open {} days, last entry {})\n",
+                    task.repository, task.task, task.days_open, task.last_entry
+                ));
+            }
+
+            output.push('\n');
+        }
+
         // Repositories
         if !options.summary_only {
             let repos_header = "Repositories";
@@ -97,14 +210,287 @@ impl Formatter for TextFormatter {
             }
         }
 
+        // Provenance, when the entries were explained
+        #[cfg(feature = "explain")]
+        if !options.summary_only {
+            output.push_str(&format_explanations(report, options));
+        }
+
+        // Signals
+        if !report.signals.is_empty() {
+            let signals_header = "Signals";
+            if options.colored {
+                output.push_str(&format!("\n{}\n", signals_header.bold()));
+            } else {
+                output.push_str(&format!("\n{}\n", signals_header));
+            }
+
+            for signal in &report.signals {
+                let label = format!("[{}]", signal.severity);
+                let label = if !options.colored {
+                    label
+                } else {
+                    match signal.severity {
+                        Severity::Critical => label.red().bold().to_string(),
+                        Severity::Warning => label.yellow().to_string(),
+                        Severity::Info => label.cyan().to_string(),
+                    }
+                };
+                output.push_str(&format!("  {} {}\n", label, signal.message));
+                for source in &signal.sources {
+                    output.push_str(&format!("    {}\n", source));
+                }
+            }
+        }
+
+        // Dependencies
+        if report.references.has_dependencies() {
+            output.push_str(&format_dependencies(report, options));
+        }
+
+        // Task dependencies
+        if !report.task_graph.is_empty() {
+            output.push_str(&format_task_graph(report, options));
+        }
+
+        // Warnings
+        if !report.warnings.is_empty() {
+            let warnings_header = "Warnings";
+            if options.colored {
+                output.push_str(&format!("\n{}\n", warnings_header.yellow().bold()));
+            } else {
+                output.push_str(&format!("\n{}\n", warnings_header));
+            }
+
+            for (kind, count) in report.warnings.directive_counts() {
+                output.push_str(&format!("  {} fired: {}\n", kind, count));
+            }
+            for warning in &report.warnings.malformed {
+                output.push_str(&format!("  {}\n", warning));
+            }
+        }
+
         Ok(output)
     }
 }
 
+/// Characters of a sparkline, from nothing to the series' peak
+const SPARK_LEVELS: [char; 7] = ['_', '.', '-', '=', '+', '*', '#'];
+
+/// The Throughput section: sparklines of tasks completed and open per week,
+/// then a row per week
+fn format_metrics(metrics: &Metrics, options: &OutputOptions) -> String {
+    let mut output = String::new();
+
+    let metrics_header = "Throughput";
+    if options.colored {
+        output.push_str(&metrics_header.bold().to_string());
+    } else {
+        output.push_str(metrics_header);
+    }
+    output.push('\n');
+
+    output.push_str(&format!("  Completed: {}\n", sparkline(&metrics.completed_series())));
+    output.push_str(&format!("  Open:      {}\n", sparkline(&metrics.open_series())));
+    if let Some(days) = metrics.average_days_to_complete {
+        output.push_str(&format!("  Average age at completion: {:.1} days\n", days));
+    }
+
+    output.push_str(&format!("\n  {:<16} {:>9} {:>6}\n", "Week", "Completed", "Open"));
+    for week in &metrics.weeks {
+        output.push_str(&format!("  {:<16} {:>9} {:>6}\n", week_label(week.week), week.completed, week.open));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// One character per value, scaled so the largest gets the tallest and
+/// only zeros get the lowest
+fn sparkline(values: &[usize]) -> String {
+    let top = SPARK_LEVELS.len() - 1;
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match max {
+            0 => SPARK_LEVELS[0],
+            _ => SPARK_LEVELS[(value * top).div_ceil(max)],
+        })
+        .collect()
+}
+
+/// The Dependencies section: blocked tasks, dangling references and cycles
+fn format_dependencies(report: &Report, options: &OutputOptions) -> String {
+    let mut output = String::new();
+    let header = "Dependencies";
+    if options.colored {
+        output.push_str(&format!("\n{}\n", header.bold()));
+    } else {
+        output.push_str(&format!("\n{}\n", header));
+    }
+
+    for reference in report.references.blocking() {
+        let source = &reference.source;
+        output.push_str(&format!(
+            "  {}: {} ({}) blocked on {}\n",
+            source.repository,
+            source.task.as_deref().unwrap_or("Untitled"),
+            source.date,
+            reference.target_label()
+        ));
+        if let Some(ref target) = reference.target {
+            output.push_str(&format!("    {}\n", target.path.display()));
+        }
+    }
+
+    for reference in report.references.dangling() {
+        let label = if options.colored {
+            "[dangling]".yellow().to_string()
+        } else {
+            "[dangling]".to_string()
+        };
+        output.push_str(&format!(
+            "  {} {} -> {}: {}\n",
+            label,
+            reference.source,
+            reference.target_label(),
+            reference.dangling.map(|d| d.to_string()).unwrap_or_default()
+        ));
+    }
+
+    for cycle in report.references.cycles() {
+        let label = if options.colored {
+            "[circular]".red().to_string()
+        } else {
+            "[circular]".to_string()
+        };
+        output.push_str(&format!("  {} {}\n", label, cycle.join(", ")));
+    }
+
+    output
+}
+
+/// The Task Dependencies section: each repository's tasks in dependency
+/// order, then unresolved dependencies and cycles
+fn format_task_graph(report: &Report, options: &OutputOptions) -> String {
+    let graph = &report.task_graph;
+    let mut output = String::new();
+    let header = "Task Dependencies";
+    if options.colored {
+        output.push_str(&format!("\n{}\n", header.bold()));
+    } else {
+        output.push_str(&format!("\n{}\n", header));
+    }
+
+    for order in &graph.order {
+        output.push_str(&format!("  {}\n", order.repository));
+        for (position, task) in order.tasks.iter().enumerate() {
+            let after: Vec<String> = graph
+                .dependencies_of(task)
+                .map(|dependency| dependency.label_in(&order.repository))
+                .collect();
+            output.push_str(&format!("    {}. {} [{}]", position + 1, task.name, task.id));
+            if !after.is_empty() {
+                output.push_str(&format!(" after {}", after.join(", ")));
+            }
+            output.push('\n');
+        }
+    }
+
+    for unresolved in &graph.unresolved {
+        let label = if options.colored {
+            "[unresolved]".yellow().to_string()
+        } else {
+            "[unresolved]".to_string()
+        };
+        output.push_str(&format!("  {} {} -> {}\n", label, unresolved.task, unresolved.reference));
+    }
+
+    for error in graph.cycle_errors() {
+        let label = if options.colored {
+            "[cycle]".red().to_string()
+        } else {
+            "[cycle]".to_string()
+        };
+        output.push_str(&format!("  {} {}\n", label, error));
+    }
+
+    output
+}
+
+/// The Explain section: provenance for each explained entry, oldest first
+#[cfg(feature = "explain")]
+fn format_explanations(report: &Report, options: &OutputOptions) -> String {
+    let mut entries: Vec<_> = report
+        .repositories
+        .iter()
+        .flat_map(|repo| &repo.tasks)
+        .flat_map(|task| &task.entries)
+        .filter_map(|entry| Some((entry, entry.explanation.as_deref()?)))
+        .collect();
+    if entries.is_empty() {
+        return String::new();
+    }
+    entries.sort_by(|a, b| (a.0.date, &a.0.filepath).cmp(&(b.0.date, &b.0.filepath)));
+
+    let mut output = String::new();
+    let header = "Explain";
+    if options.colored {
+        output.push_str(&format!("\n{}\n", header.bold()));
+    } else {
+        output.push_str(&format!("\n{}\n", header));
+    }
+
+    for (entry, explanation) in entries {
+        output.push_str(&format!("\n  {}\n", entry.filepath.display()));
+
+        if let Some(ref cluster) = explanation.cluster {
+            output.push_str(&format!(
+                "    cluster: {} / {} ({} {})\n      {}\n",
+                cluster.group,
+                cluster.task,
+                cluster.size,
+                if cluster.size == 1 { "entry" } else { "entries" },
+                cluster.reason
+            ));
+        }
+
+        let fields = [
+            ("task", &explanation.task),
+            ("repository", &explanation.repository),
+            ("project", &explanation.project),
+        ];
+        for (label, provenance) in fields {
+            if let Some(p) = provenance {
+                output.push_str(&format!("    {}: line {} ({}): {}\n", label, p.line, p.rule, p.raw));
+            }
+        }
+
+        for p in &explanation.activities {
+            let status = p.status.map(|s| format!(", {}", s)).unwrap_or_default();
+            output.push_str(&format!("    activity: line {} ({}{}): {}\n", p.line, p.rule, status, p.raw));
+        }
+
+        for rejected in &explanation.rejected {
+            let label = if options.colored {
+                "rejected".yellow().to_string()
+            } else {
+                "rejected".to_string()
+            };
+            output.push_str(&format!(
+                "    {}: line {} ({}): {}\n",
+                label, rejected.line, rejected.reason, rejected.raw
+            ));
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Repository, Statistics, ReportMetadata};
+    use crate::models::{Repository, ReportWarnings, Statistics, ReportMetadata};
     use chrono::Utc;
     use std::path::PathBuf;
 
@@ -120,6 +506,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -146,6 +543,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -160,7 +568,7 @@ mod tests {
 
     #[test]
     fn test_with_period() {
-        use crate::models::{DateRange, Statistics};
+        use crate::models::{DateRange, ReportWarnings, Statistics};
         use chrono::NaiveDate;
 
         let formatter = TextFormatter::new();
@@ -176,6 +584,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions::default();
@@ -199,6 +618,17 @@ mod tests {
             },
             repositories: vec![repo],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -226,6 +656,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -254,6 +695,17 @@ mod tests {
             },
             repositories: vec![],
             statistics: stats,
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
 
         let options = OutputOptions {
@@ -278,9 +730,200 @@ mod tests {
             },
             repositories: vec![],
             statistics: Statistics::default(),
+            warnings: ReportWarnings::default(),
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
         };
         let options = OutputOptions::default();
         let result = formatter.format(&report, &options);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_with_warnings() {
+        let formatter = TextFormatter::new();
+        let report = Report {
+            metadata: ReportMetadata {
+                generated_at: Utc::now(),
+                period: None,
+                total_entries: 0,
+                repository_count: 0,
+            },
+            repositories: vec![],
+            statistics: Statistics::default(),
+            warnings: ReportWarnings {
+                ignore_section: 2,
+                malformed: vec!["a.md:7: unknown jrnrvw directive 'skip'".to_string()],
+                ..Default::default()
+            },
+            signals: vec![],
+            references: Default::default(),
+            task_graph: Default::default(),
+            duplicates: vec![],
+            team: vec![],
+            time: None,
+            groups: vec![],
+            overdue: vec![],
+            stale: vec![],
+            metrics: Default::default(),
+        };
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(result.contains("Warnings"));
+        assert!(result.contains("jrnrvw:ignore-section fired: 2"));
+        assert!(result.contains("a.md:7: unknown jrnrvw directive 'skip'"));
+        assert!(!result.contains("jrnrvw:archive"));
+    }
+
+    #[test]
+    fn test_with_signals() {
+        use crate::models::{Signal, SignalKind, SignalSource};
+        use chrono::NaiveDate;
+
+        let formatter = TextFormatter::new();
+        let report = Report::new(vec![], None).with_signals(vec![Signal {
+            severity: Severity::Critical,
+            kind: SignalKind::VolumeDrop,
+            message: "entries for repo1 dropped 80% versus the prior 4-week average (1 vs 5.0)"
+                .to_string(),
+            sources: vec![SignalSource {
+                date: NaiveDate::from_ymd_opt(2024, 5, 10).unwrap(),
+                path: PathBuf::from("repo1/2024-05-10_journal.md"),
+            }],
+        }]);
+
+        let options = OutputOptions { colored: false, ..Default::default() };
+        let result = formatter.format(&report, &options).unwrap();
+        assert!(result.contains("Signals"));
+        assert!(result.contains("[critical] entries for repo1 dropped 80%"));
+        assert!(result.contains("    2024-05-10 repo1/2024-05-10_journal.md"));
+    }
+
+    #[test]
+    fn test_with_dependencies() {
+        use crate::analyzer::ReferenceDetector;
+        use crate::models::JournalEntry;
+        use chrono::NaiveDate;
+
+        let entry = |repo: &str, day: u32, activity: &str| {
+            let date = NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+            let mut entry = JournalEntry::new(PathBuf::from(format!("{}/2024-05-{:02}.md", repo, day)), date);
+            entry.repository = Some(repo.to_string());
+            entry.task = Some(format!("{} task", repo));
+            entry.activities = vec![activity.to_string()];
+            entry
+        };
+        let entries = vec![
+            entry("infra", 2, "Blocked until web ships, see ref:web#2024-05-06"),
+            entry("web", 6, "Blocked on ref:infra#2024-05-02 and ref:infra#2024-05-03"),
+        ];
+        let references = ReferenceDetector::default().detect(&entries, &entries);
+        let report = Report::new(vec![], None).with_references(references);
+
+        let options = OutputOptions { colored: false, ..Default::default() };
+        let result = TextFormatter::new().format(&report, &options).unwrap();
+        assert!(result.contains("\nDependencies\n"));
+        assert!(result.contains("  web: web task (2024-05-06) blocked on infra 2024-05-02\n    infra/2024-05-02.md\n"));
+        assert!(result.contains("  [dangling] web 2024-05-06 -> infra 2024-05-03: no entry on that date\n"));
+        assert!(result.contains("  [circular] infra, web\n"));
+    }
+
+    #[test]
+    fn test_with_groups() {
+        use crate::models::GroupSummary;
+
+        let mut week = GroupSummary::empty("Week 45, 2025", None);
+        week.entries = 3;
+        week.tasks_opened = 2;
+        week.tasks_completed = 1;
+        let report = Report::new(vec![], None).with_groups(vec![week, GroupSummary::empty("Week 46, 2025", None)]);
+
+        let options = OutputOptions { colored: false, ..Default::default() };
+        let result = TextFormatter::new().format(&report, &options).unwrap();
+        assert!(result.contains("\nGroups\n  Week 45, 2025: 3 entries, 2 tasks opened, 1 tasks completed\n"));
+        assert!(result.contains("  Week 46, 2025: 0 entries, 0 tasks opened, 0 tasks completed\n"));
+    }
+
+    #[test]
+    fn test_with_alerts() {
+        use crate::models::{OverdueTask, StaleTask};
+        use chrono::NaiveDate;
+
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let overdue = OverdueTask {
+            repository: "api".to_string(),
+            task: "auth".to_string(),
+            due: date("2025-11-03"),
+            days_overdue: 17,
+        };
+        let stale = StaleTask {
+            repository: "web".to_string(),
+            task: "css".to_string(),
+            opened: date("2025-09-01"),
+            last_entry: date("2025-10-01"),
+            days_open: 61,
+        };
+        let report = Report::new(vec![], None).with_alerts(vec![overdue], vec![stale]);
+
+        let options = OutputOptions { colored: false, ..Default::default() };
+        let result = TextFormatter::new().format(&report, &options).unwrap();
+        assert!(result.contains("\nOverdue\n  api: auth (due 2025-11-03, 17 days overdue)\n"));
+        assert!(result.contains("\nStale\n  web: css (open 61 days, last entry 2025-10-01)\n"));
+
+        let result = TextFormatter::new().format(&Report::new(vec![], None), &options).unwrap();
+        assert!(!result.contains("Overdue") && !result.contains("Stale"));
+    }
+
+    #[test]
+    fn test_with_metrics() {
+        use crate::models::{Metrics, WeekMetrics};
+        use chrono::NaiveDate;
+
+        let week = |s, completed, open| WeekMetrics {
+            week: NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap(),
+            completed,
+            open,
+        };
+        let metrics = Metrics {
+            weeks: vec![week("2025-11-03", 0, 2), week("2025-11-10", 3, 6), week("2025-11-17", 1, 4)],
+            average_days_to_complete: Some(4.25),
+            skipped: vec![],
+        };
+        let report = Report::new(vec![], None).with_metrics(metrics);
+
+        let options = OutputOptions { colored: false, ..Default::default() };
+        let result = TextFormatter::new().format(&report, &options).unwrap();
+        assert!(result.contains("\nThroughput\n  Completed: _#-\n  Open:      -#+\n"));
+        assert!(result.contains("  Average age at completion: 4.2 days\n"));
+        assert!(result.contains("  Week 45, 2025            3      6\n"));
+
+        let summary = OutputOptions { colored: false, summary_only: true, ..Default::default() };
+        let result = TextFormatter::new().format(&report, &summary).unwrap();
+        assert!(!result.contains("Throughput"));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0, 1, 2, 3, 4, 5, 6]), "_.-=+*#");
+        assert_eq!(sparkline(&[0, 1, 100]), "_.#", "small values stay visible");
+        assert_eq!(sparkline(&[0, 0]), "__");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_no_warnings_section_when_empty() {
+        let formatter = TextFormatter::new();
+        let report = Report::new(vec![], None);
+
+        let result = formatter.format(&report, &OutputOptions::default()).unwrap();
+        assert!(!result.contains("Warnings"));
+    }
 }
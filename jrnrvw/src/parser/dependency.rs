@@ -0,0 +1,100 @@
+//! Task dependencies written in journals
+//!
+//! A dependency is written `blocked by: <task-id>` or `depends on: <task-id>`,
+//! or with a `#` in place of the colon, as in `depends on #42`. Several ids
+//! may follow, separated by commas (`blocked by: auth, #17`), and a task in
+//! another repository is named `<repository>#<task-id>`.
+//!
+//! A bare word after the phrase is prose rather than an id, so "depends on
+//! the new API" names no dependency.
+
+/// Phrases that introduce dependencies (matched case-insensitively)
+const PHRASES: &[&str] = &["blocked by", "depends on"];
+
+/// Task ids a line says its task depends on, in the order written and
+/// without a leading `#`
+pub fn dependency_refs(line: &str) -> Vec<String> {
+    // ASCII lower-casing keeps byte offsets valid in `line`
+    let lower = line.to_ascii_lowercase();
+    let mut found: Vec<(usize, Vec<String>)> = Vec::new();
+
+    for phrase in PHRASES {
+        let mut from = 0;
+        while let Some(offset) = lower[from..].find(phrase) {
+            let start = from + offset;
+            let end = start + phrase.len();
+            from = end;
+
+            let at_word_start = !lower[..start].ends_with(|c: char| c.is_alphanumeric());
+            if at_word_start {
+                found.push((start, ids_after(&line[end..])));
+            }
+        }
+    }
+
+    found.sort_by_key(|(start, _)| *start);
+    found.into_iter().flat_map(|(_, ids)| ids).collect()
+}
+
+/// Comma-separated ids after a phrase, which need a colon or a `#` to count
+fn ids_after(text: &str) -> Vec<String> {
+    let text = text.trim_start();
+    let mut rest = match text.strip_prefix(':') {
+        Some(after) => after.trim_start(),
+        None if text.starts_with('#') => text,
+        None => return Vec::new(),
+    };
+
+    let mut ids = Vec::new();
+    while let Some((id, after)) = take_id(rest) {
+        ids.push(id);
+        match after.trim_start().strip_prefix(',') {
+            Some(next) => rest = next.trim_start(),
+            None => break,
+        }
+    }
+    ids
+}
+
+/// The id at the start of `text` and what follows it
+fn take_id(text: &str) -> Option<(String, &str)> {
+    let text = text.strip_prefix('#').unwrap_or(text);
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '#')))
+        .unwrap_or(text.len());
+    // A full stop ends the sentence, not the id
+    let id = text[..end].trim_end_matches(['.', '-', '#']);
+    if id.is_empty() {
+        return None;
+    }
+    Some((id.to_string(), &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_forms() {
+        assert_eq!(dependency_refs("blocked by: auth-rewrite"), vec!["auth-rewrite"]);
+        assert_eq!(dependency_refs("Depends on #42."), vec!["42"]);
+        assert_eq!(dependency_refs("- [ ] deploy (blocked by #7, #9)"), vec!["7", "9"]);
+        assert_eq!(dependency_refs("Depends on: infra#vpc, dns"), vec!["infra#vpc", "dns"]);
+    }
+
+    #[test]
+    fn test_several_phrases_in_order() {
+        assert_eq!(
+            dependency_refs("depends on #3 but is also blocked by: 1"),
+            vec!["3", "1"]
+        );
+    }
+
+    #[test]
+    fn test_prose_is_not_a_dependency() {
+        assert!(dependency_refs("this depends on the new API").is_empty());
+        assert!(dependency_refs("unblocked by: nothing").is_empty());
+        assert!(dependency_refs("blocked by: #").is_empty());
+        assert!(dependency_refs("no dependencies here").is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Scanner for `<!-- jrnrvw:... -->` directive comments
+//!
+//! The journal parser feeds it the raw HTML events pulldown-cmark produces,
+//! so comments inside code blocks or inline code never reach it.
+
+use crate::models::DirectiveKind;
+
+const PREFIX: &str = "jrnrvw";
+const OPEN: &str = "<!--";
+const CLOSE: &str = "-->";
+
+/// Result of reading one directive comment: the directive, or why it is malformed
+pub type ScannedDirective = std::result::Result<DirectiveKind, String>;
+
+/// Collects HTML fragments and yields the directive comments they contain
+///
+/// Block-level HTML arrives one line per event, so a comment may span
+/// several calls to [`DirectiveScanner::feed`].
+#[derive(Debug, Default)]
+pub struct DirectiveScanner {
+    pending: String,
+    pending_line: usize,
+}
+
+impl DirectiveScanner {
+    /// Create an empty scanner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed an HTML fragment starting on `line` (1-based)
+    ///
+    /// Returns each complete `jrnrvw` comment found, with the line it opened on.
+    /// Ordinary HTML comments are ignored.
+    pub fn feed(&mut self, html: &str, line: usize) -> Vec<(usize, ScannedDirective)> {
+        if self.pending.is_empty() {
+            self.pending_line = line;
+        }
+        self.pending.push_str(html);
+
+        let mut found = Vec::new();
+        loop {
+            let Some(start) = self.pending.find(OPEN) else {
+                self.pending.clear();
+                break;
+            };
+            let start_line = self.pending_line + self.pending[..start].matches('\n').count();
+            let body_start = start + OPEN.len();
+
+            let Some(end) = self.pending[body_start..].find(CLOSE) else {
+                // Unterminated so far; keep it for the next fragment
+                self.pending.drain(..start);
+                self.pending_line = start_line;
+                break;
+            };
+
+            let body = &self.pending[body_start..body_start + end];
+            if let Some(result) = read_directive(body) {
+                found.push((start_line, result));
+            }
+
+            let consumed = body_start + end + CLOSE.len();
+            self.pending_line += self.pending[..consumed].matches('\n').count();
+            self.pending.drain(..consumed);
+        }
+
+        found
+    }
+
+    /// Finish scanning, reporting a directive comment that was never closed
+    pub fn finish(self) -> Option<(usize, String)> {
+        let body = self.pending.strip_prefix(OPEN)?;
+        if body.trim_start().starts_with(PREFIX) {
+            Some((self.pending_line, "unterminated jrnrvw directive comment".to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Read the body of an HTML comment as a directive
+///
+/// Returns `None` for comments that are not meant for jrnrvw.
+fn read_directive(body: &str) -> Option<ScannedDirective> {
+    let body = body.trim();
+    let rest = body.strip_prefix(PREFIX)?;
+
+    let Some(name) = rest.strip_prefix(':') else {
+        return Some(Err(format!(
+            "malformed jrnrvw directive '{}' (expected jrnrvw:<name>)",
+            body
+        )));
+    };
+
+    let name = name.trim();
+    Some(DirectiveKind::from_name(name).ok_or_else(|| {
+        format!("unknown jrnrvw directive '{}'", name)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_recognises_directives() {
+        let mut scanner = DirectiveScanner::new();
+
+        let found = scanner.feed("<!-- jrnrvw:ignore -->", 3);
+        assert_eq!(found, vec![(3, Ok(DirectiveKind::Ignore))]);
+
+        let found = scanner.feed("<!--jrnrvw:ignore-section--><!-- jrnrvw:archive -->", 5);
+        assert_eq!(
+            found,
+            vec![(5, Ok(DirectiveKind::IgnoreSection)), (5, Ok(DirectiveKind::Archive))]
+        );
+    }
+
+    #[test]
+    fn test_feed_skips_ordinary_comments() {
+        let mut scanner = DirectiveScanner::new();
+        assert!(scanner.feed("<!-- TODO: tidy up -->", 1).is_empty());
+        assert!(scanner.feed("<br/>", 2).is_empty());
+        assert!(scanner.finish().is_none());
+    }
+
+    #[test]
+    fn test_feed_reports_malformed_directives() {
+        let mut scanner = DirectiveScanner::new();
+
+        let found = scanner.feed("<!-- jrnrvw:skip -->", 4);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 4);
+        assert!(found[0].1.as_ref().unwrap_err().contains("unknown jrnrvw directive 'skip'"));
+
+        let found = scanner.feed("<!-- jrnrvw ignore -->", 7);
+        assert!(found[0].1.as_ref().unwrap_err().contains("expected jrnrvw:<name>"));
+    }
+
+    #[test]
+    fn test_feed_comment_spanning_fragments() {
+        let mut scanner = DirectiveScanner::new();
+
+        assert!(scanner.feed("<!--\n", 10).is_empty());
+        let found = scanner.feed("jrnrvw:archive\n", 11);
+        assert!(found.is_empty());
+        let found = scanner.feed("-->\n", 12);
+        assert_eq!(found, vec![(10, Ok(DirectiveKind::Archive))]);
+    }
+
+    #[test]
+    fn test_finish_reports_unterminated_directive() {
+        let mut scanner = DirectiveScanner::new();
+        assert!(scanner.feed("text\n<!-- jrnrvw:ignore\n", 2).is_empty());
+
+        let (line, message) = scanner.finish().unwrap();
+        assert_eq!(line, 3);
+        assert!(message.contains("unterminated"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Due dates written in journals
+//!
+//! A due date is written `due: 2024-05-01`, or with the calendar emoji used
+//! by the Obsidian Tasks plugin, `📅 2024-05-01` (the space is optional).
+//! Only ISO dates count, so "due: next Friday" sets none.
+
+use chrono::NaiveDate;
+
+/// Length of a `yyyy-mm-dd` date
+const DATE_LEN: usize = 10;
+
+/// The first due date written on a line
+pub fn due_date(line: &str) -> Option<NaiveDate> {
+    // ASCII lower-casing keeps byte offsets valid in `line`
+    let lower = line.to_ascii_lowercase();
+    let mut found: Vec<(usize, NaiveDate)> = Vec::new();
+
+    let mut from = 0;
+    while let Some(offset) = lower[from..].find("due") {
+        let start = from + offset;
+        from = start + "due".len();
+
+        let at_word_start = !lower[..start].ends_with(|c: char| c.is_alphanumeric());
+        let after = line[from..].trim_start();
+        if let Some(date) = after.strip_prefix(':').filter(|_| at_word_start).and_then(date_at) {
+            found.push((start, date));
+        }
+    }
+
+    if let Some(start) = line.find('📅') {
+        if let Some(date) = date_at(&line[start + '📅'.len_utf8()..]) {
+            found.push((start, date));
+        }
+    }
+
+    found.into_iter().min_by_key(|(start, _)| *start).map(|(_, date)| date)
+}
+
+/// Parse a due date set in the front matter, such as `2024-05-01`
+pub fn parse_due(value: &str) -> Option<NaiveDate> {
+    date_at(value).filter(|_| value.trim().len() == DATE_LEN)
+}
+
+/// The `yyyy-mm-dd` date at the start of `text`, after any spaces
+fn date_at(text: &str) -> Option<NaiveDate> {
+    let text = text.trim_start();
+    let day = text.get(..DATE_LEN)?;
+    let ends = !text[DATE_LEN..].starts_with(|c: char| c.is_alphanumeric());
+    NaiveDate::parse_from_str(day, "%Y-%m-%d").ok().filter(|_| ends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_due_date_forms() {
+        assert_eq!(due_date("Ship the release, due: 2024-05-01"), Some(date("2024-05-01")));
+        assert_eq!(due_date("Due:2024-05-01."), Some(date("2024-05-01")));
+        assert_eq!(due_date("- [ ] Renew certificate 📅 2024-05-01"), Some(date("2024-05-01")));
+        assert_eq!(due_date("- [ ] Renew certificate 📅2024-05-01"), Some(date("2024-05-01")));
+    }
+
+    #[test]
+    fn test_first_due_date_wins() {
+        assert_eq!(due_date("📅 2024-06-01, due: 2024-05-01"), Some(date("2024-06-01")));
+        assert_eq!(due_date("due: 2024-05-01 📅 2024-06-01"), Some(date("2024-05-01")));
+    }
+
+    #[test]
+    fn test_not_due_dates() {
+        assert_eq!(due_date("due: next Friday"), None);
+        assert_eq!(due_date("Overdue: 2024-05-01"), None);
+        assert_eq!(due_date("due 2024-05-01"), None);
+        assert_eq!(due_date("due: 2024-02-30"), None);
+        assert_eq!(due_date("due: 2024-05-012"), None);
+        assert_eq!(due_date("📅 soon"), None);
+    }
+
+    #[test]
+    fn test_parse_due() {
+        assert_eq!(parse_due("2024-05-01"), Some(date("2024-05-01")));
+        assert_eq!(parse_due(" 2024-05-01 "), Some(date("2024-05-01")));
+        assert_eq!(parse_due("2024-05-01 or so"), None);
+        assert_eq!(parse_due("May"), None);
+    }
+}
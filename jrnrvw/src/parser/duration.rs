@@ -0,0 +1,254 @@
+//! Durations written in journals
+//!
+//! A duration is a number of hours, minutes, or hours then minutes: `2h`,
+//! `45m`, `2h15m`, `1h 30m`, `1.5h`. Unit words (`hr`, `hours`, `min`,
+//! `minutes`) work as well as the letters.
+//!
+//! A Time Spent section counts every duration in it. Elsewhere only marked
+//! durations count: one in parentheses, as in `Fixed the lexer (2h15m)`, or
+//! after "spent", as in `spent 45m on review`. Hedged durations such as
+//! `about 2h I think`, `~45m`, `2h?` or `3hish` still count, but are flagged
+//! approximate.
+
+use crate::models::TimeLogged;
+
+/// Words that make the duration right after them approximate
+const LEADING_HEDGES: &[&str] = &[
+    "about", "around", "roughly", "approx", "approximately", "almost", "nearly",
+    "maybe", "probably", "perhaps", "ca", "circa",
+];
+
+/// Words that make every duration in their clause approximate
+const CLAUSE_HEDGES: &[&str] = &[
+    "think", "guess", "maybe", "probably", "perhaps", "roughly", "approx",
+    "approximately", "estimate", "estimated",
+];
+
+/// Unit of a quantity
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Hours,
+    Minutes,
+}
+
+/// A duration found in a line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Found {
+    /// Byte offset of the first digit
+    start: usize,
+
+    time: TimeLogged,
+}
+
+/// Total of every duration in a Time Spent section, `None` when it holds
+/// none
+pub fn parse_time_spent(section: &str) -> Option<TimeLogged> {
+    TimeLogged::total(section.lines().flat_map(find_durations).map(|found| found.time))
+}
+
+/// Total of the durations a line marks as time spent, `None` when it marks
+/// none
+pub fn marked_time(line: &str) -> Option<TimeLogged> {
+    TimeLogged::total(
+        find_durations(line)
+            .into_iter()
+            .filter(|found| is_marked(&line[..found.start]))
+            .map(|found| found.time),
+    )
+}
+
+/// Every duration in `line`, in order
+fn find_durations(line: &str) -> Vec<Found> {
+    let bytes = line.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let at_word_start = i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'.');
+        if at_word_start && bytes[i].is_ascii_digit() {
+            if let Some((minutes, end, ish)) = duration_at(line, i) {
+                let approximate = ish || hedged_before(&line[..i]) || hedged_clause(line, i, end);
+                found.push(Found { start: i, time: TimeLogged::new(minutes, approximate) });
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    found
+}
+
+/// The duration starting at `start`: its minutes, where it ends, and whether
+/// an `ish` suffix hedged it
+fn duration_at(line: &str, start: usize) -> Option<(u32, usize, bool)> {
+    let (value, unit, end, ish) = quantity_at(line, start)?;
+    let minutes = match unit {
+        Unit::Hours => value * 60.0,
+        Unit::Minutes => value,
+    };
+    if unit == Unit::Minutes || ish {
+        return Some((minutes.round() as u32, end, ish));
+    }
+
+    // Hours may be followed by minutes, with or without a space
+    let next = end + line[end..].len() - line[end..].trim_start().len();
+    match quantity_at(line, next) {
+        Some((extra, Unit::Minutes, end, ish)) => Some(((minutes + extra).round() as u32, end, ish)),
+        _ => Some((minutes.round() as u32, end, false)),
+    }
+}
+
+/// A number and its unit starting at `start`: the number, the unit, where
+/// the unit ends, and whether an `ish` suffix follows it
+fn quantity_at(line: &str, start: usize) -> Option<(f64, Unit, usize, bool)> {
+    let bytes = line.as_bytes();
+    let mut i = start;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+    }
+    if i == start {
+        return None;
+    }
+    if i + 1 < bytes.len() && bytes[i] == b'.' && bytes[i + 1].is_ascii_digit() {
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+    }
+    let value: f64 = line[start..i].parse().ok()?;
+
+    if i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+    let unit_start = i;
+    while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+        i += 1;
+    }
+    let mut word = line[unit_start..i].to_ascii_lowercase();
+    let mut ish = false;
+    if let Some(unit) = word.strip_suffix("ish") {
+        word = unit.to_string();
+        ish = true;
+    } else if line.get(i..i + 4).is_some_and(|suffix| suffix.eq_ignore_ascii_case("-ish")) {
+        i += 4;
+        ish = true;
+    }
+
+    let unit = match word.as_str() {
+        "h" | "hr" | "hrs" | "hour" | "hours" => Unit::Hours,
+        "m" | "min" | "mins" | "minute" | "minutes" => Unit::Minutes,
+        _ => return None,
+    };
+    Some((value, unit, i, ish))
+}
+
+/// Whether the text before a duration marks it: an opening parenthesis or
+/// "spent", possibly followed by hedges
+fn is_marked(before: &str) -> bool {
+    let before = strip_leading_hedges(before);
+    if before.ends_with('(') {
+        return true;
+    }
+    let before = before.trim_end_matches(':').trim_end();
+    let word_start = before.rfind(|c: char| !c.is_alphanumeric()).map_or(0, |i| i + 1);
+    before[word_start..].eq_ignore_ascii_case("spent")
+}
+
+/// Whether the duration after `before` is directly preceded by a hedge
+fn hedged_before(before: &str) -> bool {
+    strip_leading_hedges(before).len() < before.trim_end().len()
+}
+
+/// `before` without the spaces, `~` and hedge words at its end
+fn strip_leading_hedges(before: &str) -> &str {
+    let mut rest = before.trim_end();
+    loop {
+        if let Some(stripped) = rest.strip_suffix('~') {
+            rest = stripped.trim_end();
+            continue;
+        }
+        let word_start = rest.rfind(|c: char| !c.is_alphanumeric() && c != '.').map_or(0, |i| i + 1);
+        let word = rest[word_start..].trim_end_matches('.').to_ascii_lowercase();
+        if word.is_empty() || !LEADING_HEDGES.contains(&word.as_str()) {
+            return rest;
+        }
+        rest = rest[..word_start].trim_end();
+    }
+}
+
+/// Whether the clause around `start..end` hedges: a `?`, "or so", or a word
+/// such as "think" or "probably"
+fn hedged_clause(line: &str, start: usize, end: usize) -> bool {
+    let is_boundary = |c: char| matches!(c, ',' | ';' | '(' | ')' | '!');
+    let clause_start = line[..start].rfind(is_boundary).map_or(0, |i| i + 1);
+    let clause_end = line[end..].find(is_boundary).map_or(line.len(), |i| end + i);
+    // A full stop ends the clause too, unless it is a decimal point
+    let clause_end = line[end..clause_end]
+        .find(". ")
+        .map_or(clause_end, |i| end + i);
+    let clause = line[clause_start..clause_end].to_ascii_lowercase();
+    let words: Vec<&str> = clause
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    clause.contains('?')
+        || words.windows(2).any(|pair| pair == ["or", "so"])
+        || words.iter().any(|word| CLAUSE_HEDGES.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(text: &str) -> Option<u32> {
+        parse_time_spent(text).map(|time| time.minutes)
+    }
+
+    #[test]
+    fn test_grammar() {
+        assert_eq!(minutes("2h"), Some(120));
+        assert_eq!(minutes("45m"), Some(45));
+        assert_eq!(minutes("2h15m"), Some(135));
+        assert_eq!(minutes("1h 30m"), Some(90));
+        assert_eq!(minutes("2.5h"), Some(150));
+        assert_eq!(minutes("2 hours"), Some(120));
+        assert_eq!(minutes("1 hr 20 mins"), Some(80));
+        assert_eq!(minutes("nothing logged"), None);
+        // Not durations: other units, versions, words
+        assert_eq!(minutes("3 months, v2h, 10 mb"), None);
+    }
+
+    #[test]
+    fn test_time_spent_sums_lines() {
+        let time = parse_time_spent("- 1h parser\n- 30m review").unwrap();
+        assert_eq!(time, TimeLogged::new(90, false));
+    }
+
+    #[test]
+    fn test_marked_time() {
+        assert_eq!(marked_time("Fixed the lexer (2h15m)"), Some(TimeLogged::new(135, false)));
+        assert_eq!(marked_time("Spent 45m on code review"), Some(TimeLogged::new(45, false)));
+        assert_eq!(
+            marked_time("spent 1h on tests, then docs (30m)"),
+            Some(TimeLogged::new(90, false))
+        );
+        // Unmarked durations are left alone
+        assert_eq!(marked_time("Cut the build from 10m to 4m"), None);
+    }
+
+    #[test]
+    fn test_hedges_make_time_approximate() {
+        assert_eq!(parse_time_spent("about 2h I think"), Some(TimeLogged::new(120, true)));
+        assert_eq!(marked_time("spent ~45m on review"), Some(TimeLogged::new(45, true)));
+        assert_eq!(marked_time("Release notes (2h?)"), Some(TimeLogged::new(120, true)));
+        assert_eq!(marked_time("spent 3hish on it"), Some(TimeLogged::new(180, true)));
+        assert_eq!(marked_time("spent 1h or so on it"), Some(TimeLogged::new(60, true)));
+        // The hedge only reaches its own clause
+        assert_eq!(
+            marked_time("spent 1h on it, the rest probably tomorrow"),
+            Some(TimeLogged::new(60, false))
+        );
+    }
+}
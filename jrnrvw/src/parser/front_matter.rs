@@ -0,0 +1,526 @@
+//! YAML or TOML front matter at the top of a journal file
+//!
+//! A `---` fence opens YAML, of which the subset understood is what journals
+//! put there in practice: flat `key: value` pairs, `[a, b]` inline lists,
+//! `- item` block lists and `#` comments. Nested mappings and multi-line
+//! strings are rejected with the line they start on rather than being
+//! misread.
+//!
+//! A `+++` fence opens TOML, as Hugo writes it. Nested tables flatten into
+//! dotted keys, except the `jrnrvw` table, which holds per-file
+//! [`Overrides`] for the analysis.
+//!
+//! Only the first line decides the style, so a file opening with one fence
+//! treats the other as ordinary markdown.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+const YAML_FENCE: &str = "---";
+
+const TOML_FENCE: &str = "+++";
+
+/// Keys mapped onto journal entry fields; anything else is kept as metadata
+pub const KNOWN_KEYS: &[&str] = &["title", "date", "tags", "status", "project", "priority", "author", "id", "due"];
+
+/// Date layouts accepted for the `date` key
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y.%m.%d", "%Y/%m/%d"];
+
+/// Front matter split off the start of a journal
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    /// Keys (lower-cased) and their scalar values
+    pub fields: HashMap<String, String>,
+
+    /// Keys (lower-cased) and their list values
+    pub lists: HashMap<String, Vec<String>>,
+
+    /// Line each key was last set on
+    pub key_lines: HashMap<String, usize>,
+
+    /// Analysis overrides from a TOML `jrnrvw` table
+    pub overrides: Overrides,
+
+    /// Number of lines the front matter occupies, fences included
+    pub lines: usize,
+}
+
+/// Per-file analysis settings from a TOML front matter `jrnrvw` table
+///
+/// ```toml
+/// [jrnrvw]
+/// skip = true
+/// repository = "other"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Overrides {
+    /// Leave the file out of the analysis altogether
+    pub skip: bool,
+
+    /// File the entry under this repository, whatever the journal's
+    /// location or `## Repository` section say
+    pub repository: Option<String>,
+}
+
+/// Front matter that is fenced off but can't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatterError {
+    /// Line in the file, counting the opening fence as line 1
+    pub line: usize,
+
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl FrontMatter {
+    /// Look up a scalar field by key (case-insensitive)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(&key.to_lowercase()).map(String::as_str)
+    }
+
+    /// Look up a list field by key (case-insensitive)
+    pub fn list(&self, key: &str) -> Option<&[String]> {
+        self.lists.get(&key.to_lowercase()).map(Vec::as_slice)
+    }
+
+    /// Tags, written either as a list or as one comma-separated value
+    pub fn tags(&self) -> Vec<String> {
+        match (self.list("tags"), self.get("tags")) {
+            (Some(tags), _) => tags.to_vec(),
+            (None, Some(tags)) => tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// The `date` field, if set
+    ///
+    /// A time after the date (`2025-11-13T09:30` or `2025-11-13 09:30`) is
+    /// ignored.
+    pub fn date(&self) -> Result<Option<NaiveDate>, FrontMatterError> {
+        let Some(value) = self.get("date") else {
+            return Ok(None);
+        };
+        let day = value.split(['T', ' ']).next().unwrap_or(value);
+
+        DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(day, format).ok())
+            .map(Some)
+            .ok_or_else(|| FrontMatterError {
+                line: self.key_lines.get("date").copied().unwrap_or(1),
+                message: format!("invalid date `{}`", value),
+            })
+    }
+
+    /// Every key not in [`KNOWN_KEYS`], with lists joined by `, `
+    pub fn extra(&self) -> HashMap<String, String> {
+        let unknown = |key: &&String| !KNOWN_KEYS.contains(&key.as_str());
+        let scalars = self
+            .fields
+            .iter()
+            .filter(|(key, _)| unknown(key))
+            .map(|(key, value)| (key.clone(), value.clone()));
+        let lists = self
+            .lists
+            .iter()
+            .filter(|(key, _)| unknown(key))
+            .map(|(key, items)| (key.clone(), items.join(", ")));
+        scalars.chain(lists).collect()
+    }
+
+    pub(crate) fn set_scalar(&mut self, key: String, value: String, line: usize) {
+        self.lists.remove(&key);
+        self.key_lines.insert(key.clone(), line);
+        self.fields.insert(key, value);
+    }
+
+    pub(crate) fn set_list(&mut self, key: String, items: Vec<String>, line: usize) {
+        self.fields.remove(&key);
+        self.key_lines.insert(key.clone(), line);
+        self.lists.insert(key, items);
+    }
+}
+
+/// Split front matter off `content`
+///
+/// YAML front matter must open with `---` on the first line and close with
+/// `---` or `...`; TOML front matter opens and closes with `+++`. Returns
+/// `Ok(None)` and leaves the content alone otherwise, so an unclosed fence
+/// is read as ordinary markdown.
+pub fn split_front_matter(content: &str) -> Result<Option<(FrontMatter, &str)>, FrontMatterError> {
+    let Some(opening) = content.split_inclusive('\n').next() else {
+        return Ok(None);
+    };
+    match opening.trim_end() {
+        YAML_FENCE => split_yaml(content),
+        TOML_FENCE => split_toml(content),
+        _ => Ok(None),
+    }
+}
+
+/// Split off `---` YAML front matter; later keys overwrite earlier ones
+fn split_yaml(content: &str) -> Result<Option<(FrontMatter, &str)>, FrontMatterError> {
+    let mut lines = content.split_inclusive('\n');
+    let Some(opening) = lines.next() else {
+        return Ok(None);
+    };
+
+    let mut front_matter = FrontMatter { lines: 1, ..FrontMatter::default() };
+    let mut consumed = opening.len();
+    // Block list being collected, and the line its key is on
+    let mut open_list: Option<(String, usize, Vec<String>)> = None;
+    // Reported only if the fence closes, so it really is front matter
+    let mut first_error: Option<FrontMatterError> = None;
+
+    for line in lines {
+        consumed += line.len();
+        front_matter.lines += 1;
+        let number = front_matter.lines;
+
+        let trimmed = line.trim();
+        if trimmed == YAML_FENCE || trimmed == "..." {
+            if let Some((key, line, items)) = open_list.take() {
+                front_matter.set_list(key, items, line);
+            }
+            return match first_error {
+                Some(error) => Err(error),
+                None => Ok(Some((front_matter, &content[consumed..]))),
+            };
+        }
+        if first_error.is_some() || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let result = if let Some(item) = list_item(trimmed) {
+            match open_list.as_mut() {
+                Some((_, _, items)) => scalar(item).map(|item| items.push(item.to_string())),
+                None => Err("list item without a key".to_string()),
+            }
+        } else if line.starts_with([' ', '\t']) {
+            Err("nested values are not supported".to_string())
+        } else {
+            if let Some((key, line, items)) = open_list.take() {
+                front_matter.set_list(key, items, line);
+            }
+            read_pair(trimmed).map(|(key, value)| match value {
+                Value::Scalar(value) => front_matter.set_scalar(key, value, number),
+                Value::List(items) => front_matter.set_list(key, items, number),
+                Value::Empty => open_list = Some((key, number, Vec::new())),
+            })
+        };
+
+        if let Err(message) = result {
+            first_error = Some(FrontMatterError { line: number, message });
+        }
+    }
+
+    // Never closed: treat the fence as ordinary markdown
+    Ok(None)
+}
+
+/// Split off `+++` TOML front matter
+fn split_toml(content: &str) -> Result<Option<(FrontMatter, &str)>, FrontMatterError> {
+    let mut lines = content.split_inclusive('\n');
+    let Some(opening) = lines.next() else {
+        return Ok(None);
+    };
+
+    let start = opening.len();
+    let mut consumed = start;
+    let mut line_count = 1;
+    let mut closing = None;
+    for line in lines {
+        line_count += 1;
+        if line.trim() == TOML_FENCE {
+            closing = Some((consumed, consumed + line.len()));
+            break;
+        }
+        consumed += line.len();
+    }
+    // Never closed: treat the fence as ordinary markdown
+    let Some((end, body_start)) = closing else {
+        return Ok(None);
+    };
+
+    let source = &content[start..end];
+    // The opening fence is line 1; errors at the very end point at the last
+    // line before the closing fence
+    let line_at = |offset: usize| (source[..offset.min(source.len())].matches('\n').count() + 2).min(line_count - 1);
+
+    let table: toml::Table = toml::from_str(source).map_err(|e| FrontMatterError {
+        line: e.span().map_or(2, |span| line_at(span.start)),
+        message: e.message().to_string(),
+    })?;
+
+    let mut front_matter = FrontMatter { lines: line_count, ..FrontMatter::default() };
+    for (key, value) in table {
+        let line = toml_key_line(source, &key).map_or(2, |index| index + 2);
+        let key = key.to_lowercase();
+        if key == "jrnrvw" {
+            if !value.is_table() {
+                return Err(FrontMatterError { line, message: "`jrnrvw` must be a table".to_string() });
+            }
+            front_matter.overrides = value.try_into().map_err(|e: toml::de::Error| FrontMatterError {
+                line,
+                message: format!("in `jrnrvw`: {}", e.message()),
+            })?;
+        } else {
+            add_toml_value(&mut front_matter, key, value, line);
+        }
+    }
+
+    Ok(Some((front_matter, &content[body_start..])))
+}
+
+/// Index of the source line defining top-level `key`, as `key = ...`
+/// before the first table header or as a `[key]` header
+fn toml_key_line(source: &str, key: &str) -> Option<usize> {
+    let mut in_table = false;
+    source.lines().position(|line| {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            in_table = true;
+            let name = header.trim_start_matches('[').split([']', '.']).next().unwrap_or("");
+            return name.trim() == key;
+        }
+        !in_table
+            && line
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(['=', '.']))
+    })
+}
+
+/// Store a TOML value, flattening tables into dotted keys
+fn add_toml_value(front_matter: &mut FrontMatter, key: String, value: toml::Value, line: usize) {
+    match value {
+        toml::Value::Table(table) => {
+            for (inner, value) in table {
+                add_toml_value(front_matter, format!("{}.{}", key, inner.to_lowercase()), value, line);
+            }
+        }
+        toml::Value::Array(items) if items.iter().all(|item| !item.is_table() && !item.is_array()) => {
+            front_matter.set_list(key, items.into_iter().map(toml_scalar).collect(), line);
+        }
+        other => front_matter.set_scalar(key, toml_scalar(other), line),
+    }
+}
+
+/// A TOML value as front matter text; strings lose their quotes
+fn toml_scalar(value: toml::Value) -> String {
+    match value {
+        toml::Value::String(text) => text,
+        toml::Value::Datetime(datetime) => datetime.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Value on the right of a `key:`
+enum Value {
+    Scalar(String),
+    List(Vec<String>),
+    /// Nothing after the colon; a block list may follow
+    Empty,
+}
+
+fn read_pair(line: &str) -> Result<(String, Value), String> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| format!("expected `key: value`, found `{}`", line))?;
+    let key = key.trim().to_lowercase();
+    if key.is_empty() {
+        return Err("missing key before `:`".to_string());
+    }
+
+    let value = value.trim();
+    let value = if value.is_empty() {
+        Value::Empty
+    } else if let Some(inner) = value.strip_prefix('[') {
+        let inner = inner
+            .strip_suffix(']')
+            .ok_or_else(|| format!("unclosed list for `{}`", key))?;
+        let items = inner
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| scalar(item).map(str::to_string))
+            .collect::<Result<_, _>>()?;
+        Value::List(items)
+    } else if value.starts_with('{') {
+        return Err(format!("nested values are not supported (`{}`)", key));
+    } else {
+        Value::Scalar(scalar(value)?.to_string())
+    };
+    Ok((key, value))
+}
+
+/// Text of a `- item` line, if it is one
+fn list_item(trimmed: &str) -> Option<&str> {
+    match trimmed.strip_prefix('-')? {
+        "" => Some(""),
+        rest if rest.starts_with([' ', '\t']) => Some(rest.trim()),
+        _ => None,
+    }
+}
+
+/// A scalar with one pair of matching quotes stripped
+fn scalar(value: &str) -> Result<&str, String> {
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            return match rest.strip_suffix(quote) {
+                Some(inner) if !rest.is_empty() => Ok(inner),
+                _ => Err(format!("unclosed quote in `{}`", value)),
+            };
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(content: &str) -> Option<(FrontMatter, &str)> {
+        split_front_matter(content).unwrap()
+    }
+
+    #[test]
+    fn test_split_front_matter() {
+        let content = "---\nproject: infra\nTitle: \"Deploy\"\n---\n## Task\nShip it\n";
+        let (front_matter, body) = split(content).unwrap();
+
+        assert_eq!(front_matter.get("project"), Some("infra"));
+        assert_eq!(front_matter.get("title"), Some("Deploy"));
+        assert_eq!(front_matter.lines, 4);
+        assert_eq!(body, "## Task\nShip it\n");
+    }
+
+    #[test]
+    fn test_no_front_matter() {
+        assert!(split("## Task\nShip it\n").is_none());
+        assert!(split("").is_none());
+        // A thematic break later in the file is not front matter
+        assert!(split("## Task\n---\nproject: x\n---\n").is_none());
+    }
+
+    #[test]
+    fn test_unterminated_front_matter_is_ignored() {
+        assert!(split("---\nproject: infra\n## Task\n").is_none());
+        assert!(split("---\nJust some prose\n").is_none());
+    }
+
+    #[test]
+    fn test_dots_close_front_matter() {
+        let (front_matter, body) = split("---\nproject: 'web'\n...\nbody").unwrap();
+        assert_eq!(front_matter.get("PROJECT"), Some("web"));
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn test_lists_and_comments() {
+        let content = "---\n# written by hand\ntags: [infra, 'ops']\nreviewers:\n  - ana\n  - \"bo\"\nmood: calm\n---\n";
+        let (front_matter, _) = split(content).unwrap();
+
+        assert_eq!(front_matter.tags(), vec!["infra", "ops"]);
+        assert_eq!(front_matter.list("reviewers").unwrap(), ["ana", "bo"]);
+        assert_eq!(front_matter.key_lines["reviewers"], 4);
+        assert_eq!(front_matter.extra()["reviewers"], "ana, bo");
+        assert_eq!(front_matter.extra()["mood"], "calm");
+        assert!(!front_matter.extra().contains_key("tags"));
+    }
+
+    #[test]
+    fn test_comma_separated_tags() {
+        let (front_matter, _) = split("---\ntags: infra, ops ,\n---\n").unwrap();
+        assert_eq!(front_matter.tags(), vec!["infra", "ops"]);
+    }
+
+    #[test]
+    fn test_date_formats() {
+        for value in ["2025-11-13", "2025.11.13", "2025/11/13", "2025-11-13T09:30:00Z", "2025-11-13 09:30"] {
+            let (front_matter, _) = split(&format!("---\ndate: {}\n---\n", value)).unwrap();
+            assert_eq!(front_matter.date().unwrap(), NaiveDate::from_ymd_opt(2025, 11, 13), "{}", value);
+        }
+
+        let (front_matter, _) = split("---\ntitle: x\ndate: 13th of November\n---\n").unwrap();
+        let error = front_matter.date().unwrap_err();
+        assert_eq!(error.line, 3);
+        assert!(error.message.contains("13th of November"));
+    }
+
+    #[test]
+    fn test_malformed_front_matter() {
+        let cases = [
+            ("---\ntitle: ok\njust words\n---\n", 3),
+            ("---\ntags: [a, b\n---\n", 2),
+            ("---\n- orphan\n---\n", 2),
+            ("---\nauthor:\n  name: ana\n---\n", 3),
+            ("---\ntitle: \"unclosed\n---\n", 2),
+            ("---\n: value\n---\n", 2),
+        ];
+        for (content, line) in cases {
+            let error = split_front_matter(content).unwrap_err();
+            assert_eq!(error.line, line, "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let content = "+++\ntitle = \"Key rotation\"\ndate = 2025-11-20\ntags = [\"security\", \"infra\"]\nestimate = 3\n\n[review]\nBy = \"ana\"\n\n[jrnrvw]\nrepository = \"ops\"\n+++\n## Task\nRotate\n";
+        let (front_matter, body) = split(content).unwrap();
+
+        assert_eq!(front_matter.get("title"), Some("Key rotation"));
+        assert_eq!(front_matter.date().unwrap(), NaiveDate::from_ymd_opt(2025, 11, 20));
+        assert_eq!(front_matter.tags(), vec!["security", "infra"]);
+        assert_eq!(front_matter.key_lines["date"], 3);
+        assert_eq!(front_matter.extra()["estimate"], "3");
+        assert_eq!(front_matter.extra()["review.by"], "ana");
+        assert!(!front_matter.extra().keys().any(|key| key.starts_with("jrnrvw")));
+        assert_eq!(
+            front_matter.overrides,
+            Overrides { skip: false, repository: Some("ops".to_string()) }
+        );
+        assert_eq!(front_matter.lines, 12);
+        assert_eq!(body, "## Task\nRotate\n");
+    }
+
+    #[test]
+    fn test_toml_errors_point_at_lines() {
+        let error = split_front_matter("+++\ntitle = \"x\"\ntags = [\n+++\n").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let error = split_front_matter("+++\ntitle = \"x\"\n[jrnrvw]\nskp = true\n+++\n").unwrap_err();
+        assert!(error.message.contains("skp"), "{}", error.message);
+
+        let error = split_front_matter("+++\njrnrvw = \"skip\"\n+++\n").unwrap_err();
+        assert_eq!(error.line, 2);
+
+        assert!(split("+++\ntitle = \"never closed\"\n").is_none());
+    }
+
+    #[test]
+    fn test_first_fence_decides_the_style() {
+        // YAML first: the TOML block after it is body text
+        let (front_matter, body) = split("---\ntitle: yaml\n---\n+++\ntitle = \"toml\"\n+++\n").unwrap();
+        assert_eq!(front_matter.get("title"), Some("yaml"));
+        assert_eq!(body, "+++\ntitle = \"toml\"\n+++\n");
+
+        // TOML first: a `---` line inside it is no fence
+        let (front_matter, body) = split("+++\ntitle = \"toml\"\n+++\n---\ntitle: yaml\n---\n").unwrap();
+        assert_eq!(front_matter.get("title"), Some("toml"));
+        assert_eq!(body, "---\ntitle: yaml\n---\n");
+    }
+}
@@ -1,20 +1,164 @@
 //! Journal file parser using pulldown-cmark for Markdown parsing
 
+use chrono::NaiveDate;
 use pulldown_cmark::{Event, HeadingLevel, Parser, Tag};
 use std::collections::HashMap;
+use std::path::PathBuf;
+#[cfg(feature = "explain")]
+use std::ops::RangeInclusive;
 
-use crate::error::Result;
+use super::directives::DirectiveScanner;
+use super::due;
+use super::front_matter::{split_front_matter, FrontMatter, FrontMatterError, Overrides};
+use crate::error::{JrnrvwError, Result};
+use crate::models::{Directive, DirectiveKind, DirectiveWarning};
 
 /// Parsed content from a journal file
 #[derive(Debug, Clone)]
 pub struct ParsedContent {
     /// Map of section headers to their content
     pub sections: HashMap<String, String>,
+
+    /// Directives that took effect, in document order
+    pub directives: Vec<Directive>,
+
+    /// Malformed directives, with their line numbers
+    pub warnings: Vec<DirectiveWarning>,
+
+    /// Front matter fields, if the file has any
+    pub front_matter: FrontMatter,
+
+    /// Source lines behind the sections, when line tracking is on
+    #[cfg(feature = "explain")]
+    pub source_lines: Option<SourceLines>,
+}
+
+/// Where parsed section content came from in the file
+///
+/// Only collected by [`JournalParser::with_line_tracking`], for explain mode.
+#[cfg(feature = "explain")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceLines {
+    /// Line of each non-blank line of a section's content, in order
+    pub sections: HashMap<String, Vec<usize>>,
+
+    /// Level 2 headings as (line, name), in document order
+    pub headings: Vec<(usize, String)>,
+
+    /// Lines dropped by an `ignore` or `ignore-section` directive
+    pub ignored: Vec<(RangeInclusive<usize>, Directive)>,
+}
+
+#[cfg(feature = "explain")]
+impl SourceLines {
+    /// Record the start of each non-blank content line in `text`
+    ///
+    /// `pending` is the current section content before `text` is appended,
+    /// and `line` is where `text` starts in the file.
+    fn track(&mut self, section: &str, pending: &str, text: &str, line: usize) {
+        let mut at_line_start = pending.rsplit('\n').next().is_none_or(|l| l.trim().is_empty());
+        for (offset, segment) in text.split('\n').enumerate() {
+            if offset > 0 {
+                at_line_start = true;
+            }
+            if at_line_start && !segment.trim().is_empty() {
+                self.sections.entry(section.to_string()).or_default().push(line + offset);
+                at_line_start = false;
+            }
+        }
+    }
+
+    /// Directive that dropped `line`, if any
+    pub fn ignored_by(&self, line: usize) -> Option<&Directive> {
+        self.ignored
+            .iter()
+            .find(|(range, _)| range.contains(&line))
+            .map(|(_, directive)| directive)
+    }
+
+    /// Level 2 section containing `line`, if it is below the first heading
+    pub fn section_at(&self, line: usize) -> Option<&str> {
+        self.headings
+            .iter()
+            .take_while(|(start, _)| *start <= line)
+            .last()
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl ParsedContent {
+    /// Whether the entry carries a `jrnrvw:archive` directive
+    pub fn is_archived(&self) -> bool {
+        self.directives.iter().any(|d| d.kind == DirectiveKind::Archive)
+    }
+
+    /// Project tag set in the front matter
+    pub fn project(&self) -> Option<String> {
+        self.front_matter.get("project").map(str::to_string)
+    }
+
+    /// Author set in the front matter
+    pub fn author(&self) -> Option<String> {
+        self.front_matter.get("author").map(str::to_string)
+    }
+
+    /// Task status set in the front matter
+    pub fn status(&self) -> Option<String> {
+        self.front_matter.get("status").map(str::to_string)
+    }
+
+    /// Task priority set in the front matter
+    pub fn priority(&self) -> Option<String> {
+        self.front_matter.get("priority").map(str::to_string)
+    }
+
+    /// Due date set in the front matter, `None` unless it is a
+    /// `yyyy-mm-dd` date
+    pub fn due(&self) -> Option<NaiveDate> {
+        self.front_matter.get("due").and_then(due::parse_due)
+    }
+
+    /// Explicit task id set in the front matter
+    pub fn task_id(&self) -> Option<String> {
+        self.front_matter.get("id").map(str::to_string)
+    }
+
+    /// Title set in the front matter
+    pub fn title(&self) -> Option<String> {
+        self.front_matter.get("title").map(str::to_string)
+    }
+
+    /// Date set in the front matter; already checked by [`JournalParser::parse`]
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.front_matter.date().ok().flatten()
+    }
+
+    /// Tags set in the front matter
+    pub fn tags(&self) -> Vec<String> {
+        self.front_matter.tags()
+    }
+
+    /// Front matter fields jrnrvw has no use for, kept for the report
+    pub fn metadata(&self) -> HashMap<String, String> {
+        self.front_matter.extra()
+    }
+
+    /// Per-file analysis overrides from TOML front matter
+    pub fn overrides(&self) -> &Overrides {
+        &self.front_matter.overrides
+    }
 }
 
 /// Parser for journal markdown files
 pub struct JournalParser {
     content: String,
+
+    /// File the content came from, for error messages
+    path: PathBuf,
+
+    /// Collect [`SourceLines`] while parsing
+    #[cfg(feature = "explain")]
+    track_lines: bool,
 }
 
 impl JournalParser {
@@ -31,7 +175,28 @@ impl JournalParser {
     /// let parser = JournalParser::new(content);
     /// ```
     pub fn new(content: String) -> Self {
-        Self { content }
+        Self {
+            content,
+            path: PathBuf::new(),
+            #[cfg(feature = "explain")]
+            track_lines: false,
+        }
+    }
+
+    /// Name `path` as the content's source in parse errors
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Record which source lines each section's content came from
+    ///
+    /// This costs an extra line count per event, so it is only used when a
+    /// report has to explain its extraction.
+    #[cfg(feature = "explain")]
+    pub fn with_line_tracking(mut self) -> Self {
+        self.track_lines = true;
+        self
     }
 
     /// Parse the journal content and extract sections
@@ -40,9 +205,21 @@ impl JournalParser {
     /// sections based on level 2 headers (##). Each section contains all content
     /// until the next header of the same or higher level.
     ///
+    /// HTML comment directives are honoured along the way:
+    /// `<!-- jrnrvw:ignore -->` drops the rest of the entry,
+    /// `<!-- jrnrvw:ignore-section -->` drops content until the next heading and
+    /// `<!-- jrnrvw:archive -->` marks the entry as archived. Directives inside an
+    /// ignored region do not fire, except that `ignore` still ends the entry from
+    /// within an ignored section. Comments in code blocks are never directives.
+    ///
+    /// A leading `---` YAML or `+++` TOML front matter block is split off
+    /// first, so it never turns into a section; line numbers still count from
+    /// the top of the file.
+    ///
     /// # Returns
     /// * `Ok(ParsedContent)` - Successfully parsed content with sections
-    /// * `Err(JrnrvwError)` - If parsing fails
+    /// * `Err(JrnrvwError::ParseError)` - If the front matter is malformed or
+    ///   its `date` is not a date
     ///
     /// # Example
     /// ```
@@ -58,10 +235,106 @@ impl JournalParser {
         let mut current_content = String::new();
         let mut in_heading = false;
         let mut heading_level = 0;
+        let mut directives = Vec::new();
+        let mut warnings = Vec::new();
+        let mut scanner = DirectiveScanner::new();
+        let mut ignoring_section = false;
+
+        let parse_error = |error: FrontMatterError| JrnrvwError::ParseError {
+            path: self.path.clone(),
+            line: error.line,
+            reason: error.message,
+        };
+        let (front_matter, body) = split_front_matter(&self.content)
+            .map_err(parse_error)?
+            .unwrap_or_else(|| (FrontMatter::default(), &self.content));
+        front_matter.date().map_err(parse_error)?;
+
+        let line_at = |offset: usize| front_matter.lines + body[..offset].matches('\n').count() + 1;
+
+        #[cfg(feature = "explain")]
+        let mut source_lines = self.track_lines.then(SourceLines::default);
+        #[cfg(feature = "explain")]
+        let mut ignored_from: Option<Directive> = None;
+
+        let parser = Parser::new(body).into_offset_iter();
+
+        'events: for (event, range) in parser {
+            if let Event::Html(html) = &event {
+                let line = line_at(range.start);
+                for (line, scanned) in scanner.feed(html, line) {
+                    let kind = match scanned {
+                        Ok(kind) => kind,
+                        Err(message) => {
+                            warnings.push(DirectiveWarning { line, message });
+                            continue;
+                        }
+                    };
 
-        let parser = Parser::new(&self.content);
+                    if ignoring_section && kind != DirectiveKind::Ignore {
+                        continue;
+                    }
+                    directives.push(Directive { kind, line });
+
+                    #[cfg(feature = "explain")]
+                    if let Some(lines) = source_lines.as_mut() {
+                        let last_line = front_matter.lines + body.lines().count();
+                        match kind {
+                            DirectiveKind::Ignore => {
+                                // Also closes an ignored section this fires in
+                                let start = ignored_from.take().map_or(line, |d| d.line);
+                                lines.ignored.push((start..=last_line, Directive { kind, line }));
+                            }
+                            DirectiveKind::IgnoreSection => {
+                                ignored_from = Some(Directive { kind, line });
+                            }
+                            DirectiveKind::Archive => {}
+                        }
+                    }
+
+                    match kind {
+                        DirectiveKind::Ignore => break 'events,
+                        DirectiveKind::IgnoreSection => ignoring_section = true,
+                        DirectiveKind::Archive => {}
+                    }
+                }
+                continue;
+            }
+
+            if ignoring_section {
+                if matches!(event, Event::Start(Tag::Heading(..))) {
+                    ignoring_section = false;
+
+                    #[cfg(feature = "explain")]
+                    if let (Some(lines), Some(directive)) = (source_lines.as_mut(), ignored_from.take()) {
+                        let end = line_at(range.start).saturating_sub(1).max(directive.line);
+                        lines.ignored.push((directive.line..=end, directive));
+                    }
+                } else {
+                    continue;
+                }
+            }
+
+            #[cfg(feature = "explain")]
+            if let Some(lines) = source_lines.as_mut() {
+                match &event {
+                    Event::Text(text) if in_heading && heading_level == 2 => {
+                        lines.headings.push((line_at(range.start), text.to_string()));
+                    }
+                    Event::Text(text) => {
+                        if let Some(ref section) = current_section {
+                            lines.track(section, &current_content, text, line_at(range.start));
+                        }
+                    }
+                    Event::Code(_) | Event::Start(Tag::Item) => {
+                        if let Some(ref section) = current_section {
+                            lines.track(section, &current_content, "-", line_at(range.start));
+                        }
+                    }
+                    _ => {}
+                }
+            }
 
-        for event in parser {
             match event {
                 Event::Start(Tag::Heading(level, _, _)) => {
                     // Save previous section if it exists
@@ -142,7 +415,24 @@ impl JournalParser {
             }
         }
 
-        Ok(ParsedContent { sections })
+        if let Some((line, message)) = scanner.finish() {
+            warnings.push(DirectiveWarning { line, message });
+        }
+
+        #[cfg(feature = "explain")]
+        if let (Some(lines), Some(directive)) = (source_lines.as_mut(), ignored_from) {
+            let last_line = front_matter.lines + body.lines().count();
+            lines.ignored.push((directive.line..=last_line.max(directive.line), directive));
+        }
+
+        Ok(ParsedContent {
+            sections,
+            directives,
+            warnings,
+            front_matter,
+            #[cfg(feature = "explain")]
+            source_lines,
+        })
     }
 }
 
@@ -333,4 +623,282 @@ New paragraph
         assert!(notes.contains("Line one"));
         assert!(notes.contains("Line two"));
     }
+
+    fn directive_kinds(parsed: &ParsedContent) -> Vec<DirectiveKind> {
+        parsed.directives.iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn test_ignore_directive_skips_rest_of_entry() {
+        let content = r#"## Task
+Visible task
+
+## Notes
+Kept note
+<!-- jrnrvw:ignore -->
+Dropped note
+
+## Time Spent
+2h
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(result.sections.get("Task").unwrap(), "Visible task");
+        assert_eq!(result.sections.get("Notes").unwrap(), "Kept note");
+        assert!(!result.sections.contains_key("Time Spent"));
+        assert_eq!(result.directives, vec![Directive { kind: DirectiveKind::Ignore, line: 6 }]);
+        assert!(!result.is_archived());
+    }
+
+    #[test]
+    fn test_ignore_section_directive_skips_until_next_heading() {
+        let content = r#"## Notes
+<!-- jrnrvw:ignore-section -->
+Private thoughts
+
+### Follow-up
+Public follow-up
+
+## Activities
+- Public activity
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        // Any heading ends the ignored region, including subheadings
+        let notes = result.sections.get("Notes").unwrap();
+        assert!(!notes.contains("Private thoughts"));
+        assert!(notes.contains("Public follow-up"));
+        assert_eq!(result.sections.get("Activities").unwrap(), "- Public activity");
+        assert_eq!(directive_kinds(&result), vec![DirectiveKind::IgnoreSection]);
+    }
+
+    #[test]
+    fn test_inline_ignore_section_keeps_earlier_text() {
+        let content = "## Notes\nPublic part <!-- jrnrvw:ignore-section --> private part\n\n## Task\nT\n"
+            .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(result.sections.get("Notes").unwrap(), "Public part");
+        assert_eq!(result.sections.get("Task").unwrap(), "T");
+    }
+
+    #[test]
+    fn test_archive_directive_keeps_content() {
+        let content = "<!-- jrnrvw:archive -->\n## Task\nOld work\n".to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(result.is_archived());
+        assert_eq!(result.sections.get("Task").unwrap(), "Old work");
+        assert_eq!(result.directives, vec![Directive { kind: DirectiveKind::Archive, line: 1 }]);
+    }
+
+    #[test]
+    fn test_nested_directives_inside_ignored_section_are_inert() {
+        let content = r#"## Notes
+<!-- jrnrvw:ignore-section -->
+<!-- jrnrvw:archive -->
+<!-- jrnrvw:ignore-section -->
+Hidden
+
+## Task
+Shown
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(!result.is_archived());
+        assert_eq!(directive_kinds(&result), vec![DirectiveKind::IgnoreSection]);
+        assert_eq!(result.sections.get("Task").unwrap(), "Shown");
+    }
+
+    #[test]
+    fn test_ignore_inside_ignored_section_ends_entry() {
+        let content = r#"## Notes
+Before
+<!-- jrnrvw:ignore-section -->
+Hidden
+<!-- jrnrvw:ignore -->
+
+## Task
+Also hidden
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(
+            directive_kinds(&result),
+            vec![DirectiveKind::IgnoreSection, DirectiveKind::Ignore]
+        );
+        assert_eq!(result.sections.get("Notes").unwrap(), "Before");
+        assert!(!result.sections.contains_key("Task"));
+    }
+
+    #[test]
+    fn test_directives_after_ignore_do_not_fire() {
+        let content = "## Task\nT\n<!-- jrnrvw:ignore -->\n<!-- jrnrvw:archive -->\n<!-- jrnrvw:bogus -->\n"
+            .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(directive_kinds(&result), vec![DirectiveKind::Ignore]);
+        assert!(!result.is_archived());
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_directives_in_code_do_not_trigger() {
+        let content = r#"## Notes
+```markdown
+<!-- jrnrvw:ignore -->
+```
+
+    <!-- jrnrvw:archive -->
+
+Use `<!-- jrnrvw:ignore-section -->` to hide a section.
+
+## Task
+Document directives
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(result.directives.is_empty());
+        assert!(result.warnings.is_empty());
+        let notes = result.sections.get("Notes").unwrap();
+        assert!(notes.contains("<!-- jrnrvw:ignore -->"));
+        assert!(notes.contains("`<!-- jrnrvw:ignore-section -->`"));
+        assert_eq!(result.sections.get("Task").unwrap(), "Document directives");
+    }
+
+    #[test]
+    fn test_ordinary_html_comments_are_not_directives() {
+        let content = "## Notes\n<!-- remember to tidy -->\nText\n".to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(result.directives.is_empty());
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.sections.get("Notes").unwrap(), "Text");
+    }
+
+    #[test]
+    fn test_malformed_directives_warn_with_line() {
+        let content = r#"## Task
+Work
+
+<!-- jrnrvw:skip -->
+
+<!-- jrnrvw archive -->
+
+## Notes
+Still parsed
+"#
+        .to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(result.directives.is_empty());
+        assert_eq!(result.warnings.len(), 2);
+        assert_eq!(result.warnings[0].line, 4);
+        assert!(result.warnings[0].message.contains("unknown jrnrvw directive 'skip'"));
+        assert_eq!(result.warnings[1].line, 6);
+        assert!(result.warnings[1].message.contains("expected jrnrvw:<name>"));
+        assert_eq!(result.sections.get("Notes").unwrap(), "Still parsed");
+    }
+
+    #[test]
+    fn test_unterminated_directive_warns() {
+        let content = "## Task\nWork\n\n<!-- jrnrvw:ignore\n\nmore\n".to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert!(result.directives.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 4);
+        assert!(result.warnings[0].message.contains("unterminated"));
+    }
+
+    #[test]
+    fn test_front_matter_is_not_a_section() {
+        let content = "---\nproject: infra\n---\n## Task\nRotate keys\n\n<!-- jrnrvw:archive -->\n".to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(result.project(), Some("infra".to_string()));
+        assert_eq!(result.status(), None);
+        assert_eq!(result.sections.len(), 1);
+        assert_eq!(result.sections.get("Task").unwrap(), "Rotate keys");
+        // Directive lines still count the front matter
+        assert_eq!(result.directives[0].line, 7);
+    }
+
+    #[test]
+    fn test_yaml_front_matter_fields() {
+        let content = "---\ntitle: Key rotation\ndate: 2025-11-20\ntags:\n  - security\n  - infra\nstatus: done\nticket: OPS-12\n---\n## Task\nRotate keys\n".to_string();
+
+        let result = JournalParser::new(content).parse().unwrap();
+
+        assert_eq!(result.title(), Some("Key rotation".to_string()));
+        assert_eq!(result.date(), NaiveDate::from_ymd_opt(2025, 11, 20));
+        assert_eq!(result.tags(), vec!["security", "infra"]);
+        assert_eq!(result.status(), Some("done".to_string()));
+        assert_eq!(result.metadata(), HashMap::from([("ticket".to_string(), "OPS-12".to_string())]));
+        assert_eq!(result.sections.get("Task").unwrap(), "Rotate keys");
+    }
+
+    #[test]
+    fn test_malformed_front_matter_is_a_parse_error() {
+        let content = "---\ntitle: ok\ntags: [a, b\n---\n## Task\nWork\n".to_string();
+
+        let error = JournalParser::new(content).with_path("notes.md").parse().unwrap_err();
+
+        match &error {
+            JrnrvwError::ParseError { path, line, reason } => {
+                assert_eq!(path, &PathBuf::from("notes.md"));
+                assert_eq!(*line, 3);
+                assert!(reason.contains("unclosed list"));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(error.to_string().contains("notes.md:3"));
+    }
+
+    #[test]
+    fn test_invalid_front_matter_date_is_a_parse_error() {
+        let content = "---\ndate: someday\n---\n".to_string();
+
+        let error = JournalParser::new(content).parse().unwrap_err();
+
+        assert!(matches!(error, JrnrvwError::ParseError { line: 2, .. }));
+    }
+
+    #[cfg(feature = "explain")]
+    #[test]
+    fn test_line_tracking_maps_content_to_source_lines() {
+        let content = "---\nproject: x\n---\n## Activities\n- one\n- two\n  wrapped\n\nplain `code`\n<!-- jrnrvw:ignore-section -->\n- hidden\n\n## Notes\nnote\n"
+            .to_string();
+
+        let plain = JournalParser::new(content.clone()).parse().unwrap();
+        assert!(plain.source_lines.is_none());
+
+        let result = JournalParser::new(content).with_line_tracking().parse().unwrap();
+        let lines = result.source_lines.unwrap();
+
+        assert_eq!(result.sections.get("Activities").unwrap().lines().filter(|l| !l.trim().is_empty()).count(), 4);
+        assert_eq!(lines.sections.get("Activities").unwrap(), &vec![5, 6, 7, 9]);
+        assert_eq!(lines.headings, vec![(4, "Activities".to_string()), (13, "Notes".to_string())]);
+        assert_eq!(lines.ignored_by(11).map(|d| d.line), Some(10));
+        assert!(lines.ignored_by(13).is_none());
+        assert_eq!(lines.section_at(14), Some("Notes"));
+        assert_eq!(lines.section_at(2), None);
+    }
 }
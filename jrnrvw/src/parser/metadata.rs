@@ -2,6 +2,11 @@
 
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
+
+use super::{dependency, due, duration};
+use crate::models::TimeLogged;
+
 /// Extractor for metadata from parsed journal sections
 pub struct MetadataExtractor {
     sections: HashMap<String, String>,
@@ -91,26 +96,7 @@ impl MetadataExtractor {
         self.sections
             .get("Activities")
             .map(|content| {
-                content
-                    .lines()
-                    .filter_map(|line| {
-                        let trimmed = line.trim();
-                        // Handle bullet points (-, *, +) and numbered lists
-                        if trimmed.starts_with('-') || trimmed.starts_with('*') || trimmed.starts_with('+') {
-                            Some(trimmed[1..].trim().to_string())
-                        } else if trimmed.chars().next().map_or(false, |c| c.is_numeric()) {
-                            // Handle numbered lists like "1. Activity"
-                            trimmed
-                                .find(|c: char| c == '.' || c == ')')
-                                .map(|idx| trimmed[idx + 1..].trim().to_string())
-                        } else if !trimmed.is_empty() {
-                            // Plain line without bullet
-                            Some(trimmed.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
+                content.lines().filter_map(activity_from_line).collect()
             })
             .unwrap_or_default()
     }
@@ -159,6 +145,93 @@ impl MetadataExtractor {
             .map(|s| s.trim().to_string())
     }
 
+    /// Extract the time logged, in minutes
+    ///
+    /// A Time Spent section wins when it holds a duration; otherwise the
+    /// durations marked in the Activities and Notes sections are added up,
+    /// as in `Fixed the lexer (2h15m)` or `spent 45m on review`.
+    ///
+    /// # Returns
+    /// * `Some(TimeLogged)` - The total, flagged approximate if any duration was hedged
+    /// * `None` - If no duration was found
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use jrnrvw::parser::MetadataExtractor;
+    ///
+    /// let mut sections = HashMap::new();
+    /// sections.insert("Time Spent".to_string(), "about 2h I think".to_string());
+    /// let extractor = MetadataExtractor::new(sections);
+    /// let time = extractor.extract_time_logged().unwrap();
+    /// assert_eq!(time.minutes, 120);
+    /// assert!(time.approximate);
+    /// ```
+    pub fn extract_time_logged(&self) -> Option<TimeLogged> {
+        if let Some(time) = self.extract_time_spent().as_deref().and_then(duration::parse_time_spent) {
+            return Some(time);
+        }
+
+        let lines = ["Activities", "Notes"]
+            .into_iter()
+            .filter_map(|name| self.sections.get(name))
+            .flat_map(|content| content.lines());
+        TimeLogged::total(lines.filter_map(duration::marked_time))
+    }
+
+    /// Extract the ids of the tasks this one depends on
+    ///
+    /// Dependencies are written in the Activities and Notes sections as
+    /// `blocked by: <task-id>` or `depends on #42`; each id is listed once,
+    /// in the order first written.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use jrnrvw::parser::MetadataExtractor;
+    ///
+    /// let mut sections = HashMap::new();
+    /// sections.insert("Notes".to_string(), "Blocked by: auth-rewrite".to_string());
+    /// let extractor = MetadataExtractor::new(sections);
+    /// assert_eq!(extractor.extract_dependencies(), vec!["auth-rewrite".to_string()]);
+    /// ```
+    pub fn extract_dependencies(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        let lines = ["Activities", "Notes"]
+            .into_iter()
+            .filter_map(|name| self.sections.get(name))
+            .flat_map(|content| content.lines());
+        for id in lines.flat_map(dependency::dependency_refs) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// Extract the date the task is due
+    ///
+    /// A due date is written in the Activities and Notes sections as
+    /// `due: 2024-05-01` or `📅 2024-05-01`; the first one written counts.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use jrnrvw::parser::MetadataExtractor;
+    ///
+    /// let mut sections = HashMap::new();
+    /// sections.insert("Activities".to_string(), "- [ ] Renew certificate 📅 2024-05-01".to_string());
+    /// let extractor = MetadataExtractor::new(sections);
+    /// assert_eq!(extractor.extract_due_date().unwrap().to_string(), "2024-05-01");
+    /// ```
+    pub fn extract_due_date(&self) -> Option<NaiveDate> {
+        ["Activities", "Notes"]
+            .into_iter()
+            .filter_map(|name| self.sections.get(name))
+            .flat_map(|content| content.lines())
+            .find_map(due::due_date)
+    }
+
     /// Get all available section names
     ///
     /// # Returns
@@ -180,6 +253,25 @@ impl MetadataExtractor {
     }
 }
 
+/// Activity text on one line of the Activities section, if it holds one
+pub(crate) fn activity_from_line(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    // Handle bullet points (-, *, +) and numbered lists
+    if trimmed.starts_with('-') || trimmed.starts_with('*') || trimmed.starts_with('+') {
+        Some(trimmed[1..].trim().to_string())
+    } else if trimmed.chars().next().map_or(false, |c| c.is_numeric()) {
+        // Handle numbered lists like "1. Activity"
+        trimmed
+            .find(|c: char| c == '.' || c == ')')
+            .map(|idx| trimmed[idx + 1..].trim().to_string())
+    } else if !trimmed.is_empty() {
+        // Plain line without bullet
+        Some(trimmed.to_string())
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,6 +374,38 @@ mod tests {
         assert_eq!(extractor.extract_time_spent(), Some("3h".to_string()));
     }
 
+    #[test]
+    fn test_extract_time_logged() {
+        let mut sections = HashMap::new();
+        sections.insert(
+            "Activities".to_string(),
+            "- Fixed the lexer (2h15m)\n- Cut the build from 10m to 4m".to_string(),
+        );
+        sections.insert("Notes".to_string(), "Spent about 45m on review".to_string());
+
+        let extractor = MetadataExtractor::new(sections.clone());
+        assert_eq!(extractor.extract_time_logged(), Some(TimeLogged::new(180, true)));
+
+        // A Time Spent section replaces the marked durations
+        sections.insert("Time Spent".to_string(), "3h 30m".to_string());
+        let extractor = MetadataExtractor::new(sections);
+        assert_eq!(extractor.extract_time_logged(), Some(TimeLogged::new(210, false)));
+    }
+
+    #[test]
+    fn test_extract_dependencies() {
+        let mut sections = HashMap::new();
+        sections.insert(
+            "Activities".to_string(),
+            "- [ ] Ship the login page (blocked by #12)\n- Depends on: api#tokens, 12".to_string(),
+        );
+        sections.insert("Notes".to_string(), "Still depends on #14".to_string());
+        sections.insert("Task".to_string(), "Login, blocked by: #99".to_string());
+
+        let extractor = MetadataExtractor::new(sections);
+        assert_eq!(extractor.extract_dependencies(), vec!["12", "api#tokens", "14"]);
+    }
+
     #[test]
     fn test_sections() {
         let mut sections = HashMap::new();
@@ -314,7 +438,14 @@ mod tests {
             "- First item\n\n- Second item\n\n- Third item".to_string(),
         );
 
-        let parsed = ParsedContent { sections };
+        let parsed = ParsedContent {
+            sections,
+            directives: Vec::new(),
+            warnings: Vec::new(),
+            front_matter: Default::default(),
+            #[cfg(feature = "explain")]
+            source_lines: None,
+        };
         let extractor = MetadataExtractor::new(parsed.sections);
         let activities = extractor.extract_activities();
 
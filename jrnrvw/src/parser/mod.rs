@@ -1,7 +1,14 @@
-//! Markdown parsing and metadata extraction
+//! Markdown and org-mode parsing and metadata extraction
 
+pub mod dependency;
+pub mod directives;
+pub mod due;
+pub mod duration;
+pub mod front_matter;
 pub mod journal;
 pub mod metadata;
+pub mod org;
 
 pub use journal::JournalParser;
 pub use metadata::MetadataExtractor;
+pub use org::OrgParser;
@@ -0,0 +1,498 @@
+//! Emacs org-mode journal parsing
+//!
+//! An org journal yields the same [`ParsedContent`] as a Markdown one, so the
+//! entries built from either are indistinguishable:
+//!
+//! - The first `TODO` or `DONE` heading is the entry's task. `DONE` sets the
+//!   status to `done` and `TODO` to `open`, a priority cookie (`[#A]`) sets
+//!   the priority and trailing `:tag:`s add tags.
+//! - The task's `SCHEDULED:` timestamp, else its `DEADLINE:`, dates the entry.
+//! - Every other heading is a section, so `* Activities`, `* Notes`,
+//!   `* Time Spent` and `* Repository` read like their `##` counterparts.
+//!   Text directly under the task heading is its notes when there is no
+//!   Notes heading.
+//! - `#+KEY: value` lines play the part of front matter: `#+TITLE`, `#+AUTHOR`,
+//!   `#+DATE`, `#+FILETAGS` and the rest, with keys jrnrvw has no use for kept as
+//!   metadata. What the task heading says wins over them.
+//!
+//! Drawers (`:PROPERTIES:` to `:END:`), `#` comments and org's buffer
+//! settings are skipped.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::front_matter::{FrontMatter, FrontMatterError};
+use super::journal::ParsedContent;
+use crate::error::{JrnrvwError, Result};
+
+/// File extension of org journals
+pub const ORG_EXTENSION: &str = "org";
+
+/// `#+KEY` lines that configure Emacs rather than describe the journal
+const BUFFER_SETTINGS: &[&str] = &[
+    "startup", "options", "setupfile", "include", "todo", "seq_todo", "typ_todo", "property",
+    "link", "bind", "columns", "constants", "archive", "category", "language",
+];
+
+/// Whether `path` is an org journal
+pub fn is_org(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == ORG_EXTENSION)
+}
+
+/// Parser for org-mode journal files
+pub struct OrgParser {
+    content: String,
+
+    /// File the content came from, for error messages
+    path: PathBuf,
+}
+
+/// A heading, split into its parts
+#[derive(Debug, Default, PartialEq)]
+struct Heading {
+    keyword: Option<&'static str>,
+    priority: Option<char>,
+    title: String,
+    tags: Vec<String>,
+}
+
+/// The entry's task heading and what was found under it
+#[derive(Debug, Default)]
+struct Task {
+    line: usize,
+    heading: Heading,
+    scheduled: Option<(String, usize)>,
+    deadline: Option<(String, usize)>,
+    body: Vec<String>,
+}
+
+impl OrgParser {
+    /// Create a parser for the given org content
+    ///
+    /// # Example
+    /// ```
+    /// use jrnrvw::parser::OrgParser;
+    ///
+    /// let parser = OrgParser::new("* TODO Write parser\n".to_string());
+    /// let parsed = parser.parse().unwrap();
+    /// assert_eq!(parsed.sections["Task"], "Write parser");
+    /// ```
+    pub fn new(content: String) -> Self {
+        Self {
+            content,
+            path: PathBuf::new(),
+        }
+    }
+
+    /// Name `path` as the content's source in parse errors
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Parse the org content into sections and front matter fields
+    ///
+    /// # Returns
+    /// * `Ok(ParsedContent)` - Sections and fields, as a Markdown journal
+    ///   would give them
+    /// * `Err(JrnrvwError::ParseError)` - If the entry's date is not a date
+    pub fn parse(&self) -> Result<ParsedContent> {
+        let mut front_matter = FrontMatter::default();
+        let mut sections: HashMap<String, Vec<String>> = HashMap::new();
+        let mut file_tags = Vec::new();
+        let mut tags_line = 1;
+        let mut task: Option<Task> = None;
+        // Section receiving content: a heading title, or `None` for the task
+        // heading's own body and for text before the first heading
+        let mut current: Option<String> = None;
+        let mut in_task_body = false;
+        let mut in_block = false;
+        let mut in_drawer = false;
+
+        for (index, line) in self.content.lines().enumerate() {
+            let number = index + 1;
+            let trimmed = line.trim();
+            let lower = trimmed.to_ascii_lowercase();
+
+            if in_block {
+                if lower.starts_with("#+end_") {
+                    in_block = false;
+                } else {
+                    push_content(&mut sections, &current, task.as_mut(), in_task_body, line);
+                }
+                continue;
+            }
+            if in_drawer {
+                in_drawer = !trimmed.eq_ignore_ascii_case(":END:");
+                continue;
+            }
+
+            if let Some(heading) = parse_heading(line) {
+                if task.is_none() && heading.keyword.is_some() {
+                    task = Some(Task {
+                        line: number,
+                        heading,
+                        ..Task::default()
+                    });
+                    current = None;
+                    in_task_body = true;
+                } else {
+                    current = Some(heading.title);
+                    in_task_body = false;
+                }
+                continue;
+            }
+
+            if lower.starts_with("#+begin_") {
+                in_block = true;
+            } else if let Some((key, value)) = parse_keyword(trimmed) {
+                match key.as_str() {
+                    "filetags" => {
+                        file_tags = parse_tags(value);
+                        tags_line = number;
+                    }
+                    key if BUFFER_SETTINGS.contains(&key) => {}
+                    "date" => front_matter.set_scalar(key.clone(), strip_timestamp(value).to_string(), number),
+                    _ => front_matter.set_scalar(key.clone(), value.to_string(), number),
+                }
+            } else if trimmed == "#" || trimmed.starts_with("# ") {
+                // Comment
+            } else if is_drawer_start(trimmed) {
+                in_drawer = true;
+            } else if let Some(planning) = parse_planning(trimmed) {
+                if let (Some(task), true) = (task.as_mut(), in_task_body) {
+                    for (kind, date) in planning {
+                        let slot = match kind {
+                            "SCHEDULED" => &mut task.scheduled,
+                            _ => &mut task.deadline,
+                        };
+                        slot.get_or_insert((date.to_string(), number));
+                    }
+                }
+            } else {
+                push_content(&mut sections, &current, task.as_mut(), in_task_body, line);
+            }
+        }
+
+        let mut sections: HashMap<String, String> = sections
+            .into_iter()
+            .filter_map(|(name, lines)| Some((name, section_text(&lines)?)))
+            .collect();
+
+        let mut tags = file_tags;
+        if let Some(task) = task {
+            let heading = task.heading;
+            if !heading.title.is_empty() {
+                sections.insert("Task".to_string(), heading.title);
+            }
+            if let Some(notes) = section_text(&task.body) {
+                sections.entry("Notes".to_string()).or_insert(notes);
+            }
+
+            let status = if heading.keyword == Some("DONE") { "done" } else { "open" };
+            front_matter.set_scalar("status".to_string(), status.to_string(), task.line);
+            if let Some(priority) = heading.priority {
+                front_matter.set_scalar("priority".to_string(), priority.to_string(), task.line);
+            }
+            if let Some((date, line)) = task.scheduled.or(task.deadline) {
+                front_matter.set_scalar("date".to_string(), date, line);
+            }
+            if !heading.tags.is_empty() {
+                tags_line = task.line;
+            }
+            for tag in heading.tags {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        if !tags.is_empty() {
+            front_matter.set_list("tags".to_string(), tags, tags_line);
+        }
+
+        front_matter.date().map_err(|error: FrontMatterError| JrnrvwError::ParseError {
+            path: self.path.clone(),
+            line: error.line,
+            reason: error.message,
+        })?;
+
+        Ok(ParsedContent {
+            sections,
+            directives: Vec::new(),
+            warnings: Vec::new(),
+            front_matter,
+            #[cfg(feature = "explain")]
+            source_lines: None,
+        })
+    }
+}
+
+/// Add a content line to the section being read, if any
+fn push_content(
+    sections: &mut HashMap<String, Vec<String>>,
+    current: &Option<String>,
+    task: Option<&mut Task>,
+    in_task_body: bool,
+    line: &str,
+) {
+    match (current, task) {
+        (Some(section), _) => sections.entry(section.clone()).or_default().push(line.to_string()),
+        (None, Some(task)) if in_task_body => task.body.push(line.to_string()),
+        _ => {}
+    }
+}
+
+/// Section lines with their common indentation removed and blank lines
+/// trimmed from both ends; `None` when nothing is left
+fn section_text(lines: &[String]) -> Option<String> {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()?;
+    let text = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(text.trim().to_string())
+}
+
+/// Split a `* TODO [#A] Title :tag:` line into its parts
+fn parse_heading(line: &str) -> Option<Heading> {
+    let stars = line.len() - line.trim_start_matches('*').len();
+    if stars == 0 || !(line[stars..].is_empty() || line[stars..].starts_with([' ', '\t'])) {
+        return None;
+    }
+    let mut rest = line[stars..].trim();
+
+    let mut heading = Heading::default();
+    for keyword in ["TODO", "DONE"] {
+        if let Some(after) = rest.strip_prefix(keyword) {
+            if after.is_empty() || after.starts_with([' ', '\t']) {
+                heading.keyword = Some(keyword);
+                rest = after.trim_start();
+            }
+        }
+    }
+
+    if let Some(after) = rest.strip_prefix("[#") {
+        let mut chars = after.chars();
+        if let (Some(priority), Some(']')) = (chars.next(), chars.next()) {
+            heading.priority = Some(priority);
+            rest = chars.as_str().trim_start();
+        }
+    }
+
+    if let Some((title, tags)) = rest.trim_end().rsplit_once([' ', '\t']) {
+        let tags = tags.trim();
+        if tags.len() > 1 && tags.starts_with(':') && tags.ends_with(':') && !tags.contains(' ') {
+            heading.tags = parse_tags(tags);
+            rest = title;
+        }
+    } else if rest.len() > 1 && rest.starts_with(':') && rest.ends_with(':') {
+        heading.tags = parse_tags(rest);
+        rest = "";
+    }
+
+    heading.title = rest.trim().to_string();
+    Some(heading)
+}
+
+/// `:a:b:` or `a b` tags
+fn parse_tags(value: &str) -> Vec<String> {
+    value
+        .split([':', ' '])
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A `#+KEY: value` line as a lower-cased key and its value
+fn parse_keyword(line: &str) -> Option<(String, &str)> {
+    let (key, value) = line.strip_prefix("#+")?.split_once(':')?;
+    if key.is_empty() || !key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return None;
+    }
+    Some((key.to_lowercase(), value.trim()))
+}
+
+/// The dates on a planning line, such as
+/// `SCHEDULED: <2025-11-20 Thu> DEADLINE: <2025-11-21 Fri>`
+fn parse_planning(line: &str) -> Option<Vec<(&'static str, &str)>> {
+    if !["SCHEDULED:", "DEADLINE:", "CLOSED:"].iter().any(|kw| line.starts_with(kw)) {
+        return None;
+    }
+
+    let mut dates = Vec::new();
+    for kind in ["SCHEDULED", "DEADLINE"] {
+        let Some(start) = line.find(&format!("{}:", kind)) else {
+            continue;
+        };
+        let after = line[start + kind.len() + 1..].trim_start();
+        let date = strip_timestamp(after).split_whitespace().next().unwrap_or("");
+        dates.push((kind, date));
+    }
+    Some(dates)
+}
+
+/// The inside of a `<2025-11-20 Thu 10:00>` or `[…]` timestamp, or `value`
+/// itself when it isn't one
+fn strip_timestamp(value: &str) -> &str {
+    let value = value.trim();
+    for (open, close) in [('<', '>'), ('[', ']')] {
+        if let Some(inner) = value.strip_prefix(open) {
+            if let Some(end) = inner.find(close) {
+                return &inner[..end];
+            }
+        }
+    }
+    value
+}
+
+/// Whether `line` opens a drawer such as `:PROPERTIES:` or `:LOGBOOK:`
+fn is_drawer_start(line: &str) -> bool {
+    line.len() > 2
+        && line.starts_with(':')
+        && line.ends_with(':')
+        && !line.eq_ignore_ascii_case(":END:")
+        && line[1..line.len() - 1]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::MetadataExtractor;
+    use chrono::NaiveDate;
+
+    const JOURNAL: &str = "\
+#+TITLE: Parser work
+#+FILETAGS: :rust:
+#+AUTHOR: Sam
+#+STARTUP: overview
+
+* DONE [#A] Add org support :parser:rust:
+  SCHEDULED: <2025-11-20 Thu> DEADLINE: <2025-11-22 Sat>
+  :PROPERTIES:
+  :EFFORT: 2h
+  :END:
+  Org journals read like Markdown ones.
+** Activities
+   - [X] Parse headings
+   - [ ] Parse tables
+   + Wrote tests
+** Time Spent
+   2 hours
+* Repository
+jrnrvw
+";
+
+    #[test]
+    fn test_task_heading() {
+        let parsed = OrgParser::new(JOURNAL.to_string()).parse().unwrap();
+
+        assert_eq!(parsed.sections["Task"], "Add org support");
+        assert_eq!(parsed.status().as_deref(), Some("done"));
+        assert_eq!(parsed.priority().as_deref(), Some("A"));
+        assert_eq!(parsed.tags(), vec!["rust", "parser"]);
+        assert_eq!(parsed.date(), NaiveDate::from_ymd_opt(2025, 11, 20));
+        assert_eq!(parsed.title().as_deref(), Some("Parser work"));
+        assert_eq!(parsed.author().as_deref(), Some("Sam"));
+        assert!(parsed.metadata().is_empty());
+    }
+
+    #[test]
+    fn test_sections_match_markdown() {
+        let parsed = OrgParser::new(JOURNAL.to_string()).parse().unwrap();
+        let extractor = MetadataExtractor::new(parsed.sections.clone());
+
+        assert_eq!(extractor.extract_task().as_deref(), Some("Add org support"));
+        assert_eq!(
+            extractor.extract_activities(),
+            vec!["[X] Parse headings", "[ ] Parse tables", "Wrote tests"]
+        );
+        assert_eq!(extractor.extract_time_spent().as_deref(), Some("2 hours"));
+        assert_eq!(extractor.extract_repository().as_deref(), Some("jrnrvw"));
+        assert_eq!(extractor.extract_notes().as_deref(), Some("Org journals read like Markdown ones."));
+    }
+
+    #[test]
+    fn test_todo_with_deadline_only() {
+        let content = "* TODO Ship it\n  DEADLINE: <2025-12-01 Mon 17:00>\n* Notes\nNearly there\n";
+        let parsed = OrgParser::new(content.to_string()).parse().unwrap();
+
+        assert_eq!(parsed.status().as_deref(), Some("open"));
+        assert_eq!(parsed.priority(), None);
+        assert_eq!(parsed.date(), NaiveDate::from_ymd_opt(2025, 12, 1));
+        assert_eq!(parsed.sections["Notes"], "Nearly there");
+    }
+
+    #[test]
+    fn test_only_first_task_heading_counts() {
+        let content = "* Notes\nBefore\n* TODO First\n* DONE Second\n  SCHEDULED: <2025-01-02 Thu>\n";
+        let parsed = OrgParser::new(content.to_string()).parse().unwrap();
+
+        assert_eq!(parsed.sections["Task"], "First");
+        assert_eq!(parsed.status().as_deref(), Some("open"));
+        assert_eq!(parsed.date(), None);
+        assert!(!parsed.sections.contains_key("Second"));
+    }
+
+    #[test]
+    fn test_without_task_heading() {
+        let content = "#+DATE: <2025-03-04 Tue>\n* Task\nPlain task\n* Activities\n- One\n";
+        let parsed = OrgParser::new(content.to_string()).parse().unwrap();
+
+        assert_eq!(parsed.sections["Task"], "Plain task");
+        assert_eq!(parsed.status(), None);
+        assert_eq!(parsed.date(), NaiveDate::from_ymd_opt(2025, 3, 4));
+    }
+
+    #[test]
+    fn test_blocks_and_comments() {
+        let content = "* Notes\n# private\n#+BEGIN_SRC sh\n# not a comment\n#+TITLE: not a keyword\n#+END_SRC\n";
+        let parsed = OrgParser::new(content.to_string()).parse().unwrap();
+
+        assert_eq!(parsed.sections["Notes"], "# not a comment\n#+TITLE: not a keyword");
+        assert_eq!(parsed.title(), None);
+    }
+
+    #[test]
+    fn test_invalid_scheduled_date_is_an_error() {
+        let content = "* TODO Broken\n  SCHEDULED: <2025-13-40 Mon>\n";
+        let error = OrgParser::new(content.to_string()).with_path("work.org").parse().unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse journal file work.org:2: invalid date `2025-13-40`"
+        );
+    }
+
+    #[test]
+    fn test_parse_heading() {
+        assert_eq!(parse_heading("not a heading"), None);
+        assert_eq!(parse_heading("*bold* text"), None);
+        assert_eq!(
+            parse_heading("** TODO [#B] Fix :bug:urgent:"),
+            Some(Heading {
+                keyword: Some("TODO"),
+                priority: Some('B'),
+                title: "Fix".to_string(),
+                tags: vec!["bug".to_string(), "urgent".to_string()],
+            })
+        );
+        // A keyword has to be a word of its own
+        let heading = parse_heading("* TODOs for today").unwrap();
+        assert_eq!(heading.keyword, None);
+        assert_eq!(heading.title, "TODOs for today");
+    }
+
+    #[test]
+    fn test_is_org() {
+        assert!(is_org(Path::new("2025.11.20 - JRN - work.org")));
+        assert!(!is_org(Path::new("2025.11.20 - JRN - work.md")));
+    }
+}
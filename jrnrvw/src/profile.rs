@@ -0,0 +1,520 @@
+//! Lightweight timing spans for `--profile`
+//!
+//! A [`Profiler`] records spans opened on the thread that started it and on
+//! any worker thread that [`Context::attach`]es to it. When nothing is
+//! recording, [`span`] only checks a thread-local and returns an inert guard;
+//! without the `profile` Cargo feature it compiles to nothing.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+/// A finished span
+#[derive(Debug, Clone)]
+pub struct SpanRecord {
+    pub id: u64,
+
+    /// Span that was open when this one started, possibly on another thread
+    pub parent: Option<u64>,
+
+    pub name: &'static str,
+
+    /// Extra context, such as the file being parsed
+    pub detail: Option<String>,
+
+    /// 1 for the thread that started profiling, then one per attached worker
+    pub thread: u64,
+
+    /// Offset from the start of profiling
+    pub start: Duration,
+
+    pub duration: Duration,
+}
+
+struct Collector {
+    epoch: Instant,
+    #[cfg_attr(not(feature = "profile"), allow(dead_code))]
+    next_id: AtomicU64,
+    next_thread: AtomicU64,
+    spans: Mutex<Vec<SpanRecord>>,
+}
+
+/// Per-thread recording state
+struct Local {
+    collector: Arc<Collector>,
+    thread: u64,
+    /// Ids of the spans open on this thread, innermost last
+    stack: Vec<u64>,
+}
+
+thread_local! {
+    static LOCAL: RefCell<Option<Local>> = const { RefCell::new(None) };
+}
+
+fn install(local: Option<Local>) {
+    LOCAL.with(|cell| *cell.borrow_mut() = local);
+}
+
+/// Records spans on the current thread until finished or dropped
+pub struct Profiler {
+    collector: Arc<Collector>,
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl Profiler {
+    /// Start recording spans opened on this thread
+    pub fn start() -> Self {
+        let collector = Arc::new(Collector {
+            epoch: Instant::now(),
+            next_id: AtomicU64::new(1),
+            next_thread: AtomicU64::new(2),
+            spans: Mutex::new(Vec::new()),
+        });
+        install(Some(Local {
+            collector: collector.clone(),
+            thread: 1,
+            stack: Vec::new(),
+        }));
+
+        Self {
+            collector,
+            _thread_bound: PhantomData,
+        }
+    }
+
+    /// Stop recording and return every span that finished
+    pub fn finish(self) -> Profile {
+        install(None);
+        let spans = std::mem::take(&mut *self.collector.spans.lock().unwrap());
+        Profile { spans }
+    }
+}
+
+impl Drop for Profiler {
+    fn drop(&mut self) {
+        install(None);
+    }
+}
+
+/// Guard for an open span; the span ends when it is dropped
+#[must_use = "a span ends as soon as its guard is dropped"]
+pub struct Span {
+    open: Option<OpenSpan>,
+}
+
+struct OpenSpan {
+    id: u64,
+    parent: Option<u64>,
+    name: &'static str,
+    detail: Option<String>,
+    start: Instant,
+}
+
+/// Open a span named `name` on the current thread
+#[inline]
+pub fn span(name: &'static str) -> Span {
+    open(name, None::<fn() -> String>)
+}
+
+/// Open a span with extra context; `detail` is only called while recording
+#[inline]
+pub fn span_with(name: &'static str, detail: impl FnOnce() -> String) -> Span {
+    open(name, Some(detail))
+}
+
+#[cfg(feature = "profile")]
+fn open(name: &'static str, detail: Option<impl FnOnce() -> String>) -> Span {
+    if !LOCAL.with(|cell| cell.borrow().is_some()) {
+        return Span { open: None };
+    }
+    let detail = detail.map(|detail| detail());
+
+    LOCAL.with(|cell| {
+        let mut local = cell.borrow_mut();
+        let Some(local) = local.as_mut() else {
+            return Span { open: None };
+        };
+        let id = local.collector.next_id.fetch_add(1, Ordering::Relaxed);
+        let parent = local.stack.last().copied();
+        local.stack.push(id);
+
+        Span {
+            open: Some(OpenSpan {
+                id,
+                parent,
+                name,
+                detail,
+                start: Instant::now(),
+            }),
+        }
+    })
+}
+
+#[cfg(not(feature = "profile"))]
+fn open(_name: &'static str, _detail: Option<impl FnOnce() -> String>) -> Span {
+    Span { open: None }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(open) = self.open.take() else {
+            return;
+        };
+        let duration = open.start.elapsed();
+
+        LOCAL.with(|cell| {
+            let mut local = cell.borrow_mut();
+            let Some(local) = local.as_mut() else {
+                return;
+            };
+            local.stack.retain(|&id| id != open.id);
+
+            let record = SpanRecord {
+                id: open.id,
+                parent: open.parent,
+                name: open.name,
+                detail: open.detail,
+                thread: local.thread,
+                start: open.start.saturating_duration_since(local.collector.epoch),
+                duration,
+            };
+            local.collector.spans.lock().unwrap().push(record);
+        });
+    }
+}
+
+/// The profile being recorded on this thread and its innermost open span,
+/// for handing to worker threads
+pub fn context() -> Option<Context> {
+    LOCAL.with(|cell| {
+        cell.borrow().as_ref().map(|local| Context {
+            collector: local.collector.clone(),
+            parent: local.stack.last().copied(),
+        })
+    })
+}
+
+/// A way into a running profile from another thread
+#[derive(Clone)]
+pub struct Context {
+    collector: Arc<Collector>,
+    parent: Option<u64>,
+}
+
+impl Context {
+    /// Record this thread's spans as children of the span that was open when
+    /// the context was taken, until the guard is dropped
+    pub fn attach(&self) -> Attached {
+        install(Some(Local {
+            collector: self.collector.clone(),
+            thread: self.collector.next_thread.fetch_add(1, Ordering::Relaxed),
+            stack: self.parent.into_iter().collect(),
+        }));
+        Attached {
+            _thread_bound: PhantomData,
+        }
+    }
+}
+
+/// Guard that keeps a worker thread attached to a profile
+pub struct Attached {
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl Drop for Attached {
+    fn drop(&mut self) {
+        install(None);
+    }
+}
+
+/// Timing for every span with the same name
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub name: &'static str,
+
+    /// Nesting depth of the first span with this name
+    pub depth: usize,
+
+    pub count: usize,
+
+    /// Summed over all spans, including ones that ran in parallel
+    pub total: Duration,
+
+    pub p95: Duration,
+}
+
+/// Every span recorded by a [`Profiler`]
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    /// In the order they finished
+    pub spans: Vec<SpanRecord>,
+}
+
+impl Profile {
+    /// Spans directly under `parent` (top-level spans for `None`), in start order
+    pub fn children(&self, parent: Option<u64>) -> Vec<&SpanRecord> {
+        let mut children: Vec<_> = self.spans.iter().filter(|s| s.parent == parent).collect();
+        children.sort_by_key(|s| s.start);
+        children
+    }
+
+    /// One row per span name, in the order each name first started
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        let depths = self.depths();
+        let mut spans: Vec<&SpanRecord> = self.spans.iter().collect();
+        spans.sort_by_key(|s| s.start);
+
+        let mut order = Vec::new();
+        let mut durations: HashMap<&str, Vec<Duration>> = HashMap::new();
+        for span in spans {
+            let list = durations.entry(span.name).or_default();
+            if list.is_empty() {
+                order.push((span.name, depths[&span.id]));
+            }
+            list.push(span.duration);
+        }
+
+        order
+            .into_iter()
+            .map(|(name, depth)| {
+                let mut list = durations.remove(name).unwrap_or_default();
+                list.sort();
+                PhaseTiming {
+                    name,
+                    depth,
+                    count: list.len(),
+                    total: list.iter().sum(),
+                    p95: percentile(&list, 95),
+                }
+            })
+            .collect()
+    }
+
+    fn depths(&self) -> HashMap<u64, usize> {
+        let parents: HashMap<u64, Option<u64>> =
+            self.spans.iter().map(|s| (s.id, s.parent)).collect();
+
+        self.spans
+            .iter()
+            .map(|span| {
+                let mut depth = 0;
+                let mut parent = span.parent;
+                while let Some(id) = parent {
+                    depth += 1;
+                    parent = parents.get(&id).copied().flatten();
+                }
+                (span.id, depth)
+            })
+            .collect()
+    }
+
+    /// The `--profile` summary table
+    pub fn render_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!(
+            "{:<28} {:>7} {:>11} {:>11}\n",
+            "Phase", "Count", "Total", "p95"
+        ));
+
+        for phase in self.phases() {
+            let name = format!("{}{}", "  ".repeat(phase.depth), phase.name);
+            output.push_str(&format!(
+                "{:<28} {:>7} {:>11} {:>11}\n",
+                name,
+                phase.count,
+                format_duration(phase.total),
+                format_duration(phase.p95)
+            ));
+        }
+
+        if self.spans.iter().any(|s| s.thread != 1) {
+            output.push_str("Totals add up time across worker threads, so they can exceed their parent.\n");
+        }
+        output
+    }
+
+    /// The spans in Chrome trace event format, for chrome://tracing or Perfetto
+    pub fn chrome_trace(&self) -> Value {
+        let micros = |d: Duration| d.as_secs_f64() * 1_000_000.0;
+
+        let mut events: Vec<Value> = Vec::new();
+        let mut threads: Vec<u64> = self.spans.iter().map(|s| s.thread).collect();
+        threads.sort_unstable();
+        threads.dedup();
+        for thread in threads {
+            let name = if thread == 1 {
+                "main".to_string()
+            } else {
+                format!("worker {}", thread - 1)
+            };
+            events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": thread,
+                "args": { "name": name },
+            }));
+        }
+
+        for span in &self.spans {
+            let mut event = json!({
+                "name": span.name,
+                "cat": "jrnrvw",
+                "ph": "X",
+                "ts": micros(span.start),
+                "dur": micros(span.duration),
+                "pid": 1,
+                "tid": span.thread,
+            });
+            if let Some(detail) = &span.detail {
+                event["args"] = json!({ "detail": detail });
+            }
+            events.push(event);
+        }
+
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+}
+
+/// Nearest-rank percentile of sorted durations
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (sorted.len() * percent).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else {
+        format!("{:.2}ms", secs * 1000.0)
+    }
+}
+
+#[cfg(all(test, feature = "profile"))]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn busy(millis: u64) {
+        thread::sleep(Duration::from_millis(millis));
+    }
+
+    fn named<'a>(profile: &'a Profile, name: &str) -> Vec<&'a SpanRecord> {
+        profile.spans.iter().filter(|s| s.name == name).collect()
+    }
+
+    #[test]
+    fn test_nothing_recorded_without_profiler() {
+        let _span = span("orphan");
+        assert!(context().is_none());
+    }
+
+    #[test]
+    fn test_spans_nest_across_worker_threads() {
+        let profiler = Profiler::start();
+        {
+            let _run = span("run");
+            {
+                let _discovery = span("discovery");
+                busy(2);
+            }
+            {
+                let _parsing = span("parsing");
+                let context = context();
+                thread::scope(|scope| {
+                    for worker in 0..3 {
+                        let context = context.clone();
+                        scope.spawn(move || {
+                            let _attached = context.as_ref().map(Context::attach);
+                            for file in 0..2 {
+                                let _parse = span_with("parse", || format!("{}-{}", worker, file));
+                                busy(1);
+                            }
+                        });
+                    }
+                });
+            }
+        }
+        let profile = profiler.finish();
+
+        let run = named(&profile, "run")[0];
+        assert_eq!(run.parent, None);
+        let top: Vec<_> = profile.children(Some(run.id)).iter().map(|s| s.name).collect();
+        assert_eq!(top, vec!["discovery", "parsing"]);
+
+        let parsing = named(&profile, "parsing")[0];
+        let parses = named(&profile, "parse");
+        assert_eq!(parses.len(), 6);
+        assert!(parses.iter().all(|s| s.parent == Some(parsing.id)));
+        let workers: std::collections::HashSet<_> = parses.iter().map(|s| s.thread).collect();
+        assert_eq!(workers.len(), 3);
+        assert!(!workers.contains(&1));
+
+        // Spans on one thread fit inside their parent
+        let discovery = named(&profile, "discovery")[0];
+        assert!(discovery.duration + parsing.duration <= run.duration);
+        assert!(discovery.start >= run.start);
+
+        // Phase totals are the sums of their spans
+        let phases = profile.phases();
+        let names: Vec<_> = phases.iter().map(|p| (p.name, p.depth)).collect();
+        assert_eq!(
+            names,
+            vec![("run", 0), ("discovery", 1), ("parsing", 1), ("parse", 2)]
+        );
+        let parse = &phases[3];
+        assert_eq!(parse.count, 6);
+        assert_eq!(parse.total, parses.iter().map(|s| s.duration).sum());
+        assert!(parse.p95 >= Duration::from_millis(1));
+        assert!(parse.p95 <= parse.total);
+    }
+
+    #[test]
+    fn test_recording_stops_at_finish() {
+        let profiler = Profiler::start();
+        let late = span("late");
+        let profile = profiler.finish();
+        drop(late);
+
+        assert!(profile.spans.is_empty());
+        assert!(context().is_none());
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let durations: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&durations, 95), Duration::from_millis(19));
+        assert_eq!(percentile(&durations[..1], 95), Duration::from_millis(1));
+        assert_eq!(percentile(&[], 95), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_table_and_chrome_trace() {
+        let profiler = Profiler::start();
+        {
+            let _render = span_with("render", || "text".to_string());
+        }
+        let profile = profiler.finish();
+
+        let table = profile.render_table();
+        assert!(table.starts_with("Phase"));
+        assert!(table.contains("render"));
+
+        let trace = profile.chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+        let complete: Vec<_> = events.iter().filter(|e| e["ph"] == "X").collect();
+        assert_eq!(complete.len(), 1);
+        assert_eq!(complete[0]["name"], "render");
+        assert_eq!(complete[0]["args"]["detail"], "text");
+        assert!(events.iter().any(|e| e["ph"] == "M" && e["args"]["name"] == "main"));
+    }
+}
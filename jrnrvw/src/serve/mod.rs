@@ -0,0 +1,163 @@
+//! HTTP server exposing the report
+//!
+//! The analysis runs once at startup and again on a timer or on request.
+//! Each run renders a complete [`Snapshot`]; requests are always answered
+//! from the last finished one, so a refresh in progress is never visible.
+
+pub mod routes;
+pub mod state;
+
+pub use state::{ReportState, Snapshot};
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use crate::analyzer::Analysis;
+use crate::error::{JrnrvwError, Result};
+use crate::output::OutputOptions;
+
+/// Settings for `jrnrvw serve`
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    /// Address to listen on
+    pub bind: SocketAddr,
+
+    /// Time between automatic refreshes; `None` refreshes only on request
+    pub refresh_interval: Option<Duration>,
+
+    /// Bearer token every request must carry, if any
+    pub token: Option<String>,
+
+    /// Options for rendering the report pages
+    pub options: OutputOptions,
+
+    /// Log refreshes and connection errors to stderr
+    pub verbose: bool,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            bind: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            refresh_interval: None,
+            token: None,
+            options: OutputOptions {
+                colored: false,
+                ..OutputOptions::default()
+            },
+            verbose: false,
+        }
+    }
+}
+
+/// A bound server with its first report already built
+pub struct Server {
+    listener: TcpListener,
+    state: Arc<ReportState>,
+    config: ServeConfig,
+}
+
+impl Server {
+    /// Run the initial analysis and bind the listener
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn bind(analysis: Analysis, config: ServeConfig) -> Result<Self> {
+        let options = config.options.clone();
+        let state = tokio::task::spawn_blocking(move || ReportState::new(analysis, options))
+            .await
+            .map_err(|e| JrnrvwError::Server(format!("Initial analysis failed: {}", e)))??;
+
+        let listener = TcpListener::bind(config.bind).await?;
+
+        Ok(Self {
+            listener,
+            state: Arc::new(state),
+            config,
+        })
+    }
+
+    /// The address actually bound, useful when binding to port 0
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Shared report state, for triggering refreshes from outside a request
+    pub fn state(&self) -> Arc<ReportState> {
+        Arc::clone(&self.state)
+    }
+
+    /// Accept connections until the listener fails
+    pub async fn run(self) -> Result<()> {
+        if let Some(interval) = self.config.refresh_interval {
+            tokio::spawn(refresh_periodically(
+                Arc::clone(&self.state),
+                interval,
+                self.config.verbose,
+            ));
+        }
+
+        let token: Option<Arc<str>> = self.config.token.as_deref().map(Arc::from);
+
+        loop {
+            let (stream, peer) = self.listener.accept().await?;
+            let state = Arc::clone(&self.state);
+            let token = token.clone();
+            let verbose = self.config.verbose;
+
+            tokio::spawn(async move {
+                let service = service_fn(move |req| {
+                    let state = Arc::clone(&state);
+                    let token = token.clone();
+                    async move {
+                        Ok::<_, std::convert::Infallible>(routes::handle(req, state, token).await)
+                    }
+                });
+
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    if verbose {
+                        eprintln!("Connection from {} failed: {}", peer, e);
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Re-run the analysis every `interval`, keeping the old report on failure
+async fn refresh_periodically(state: Arc<ReportState>, interval: Duration, verbose: bool) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick fires immediately, and the report is already fresh
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+        match state.refresh().await {
+            Ok(_) if verbose => eprintln!("Report refreshed"),
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: refresh failed, serving previous report: {}", e),
+        }
+    }
+}
+
+/// Build a runtime, bind, and serve until the process is stopped
+pub fn serve(analysis: Analysis, config: ServeConfig) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+
+    runtime.block_on(async move {
+        let server = Server::bind(analysis, config).await?;
+        eprintln!("Serving journal report on http://{}", server.local_addr()?);
+        server.run().await
+    })
+}
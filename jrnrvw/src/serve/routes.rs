@@ -0,0 +1,186 @@
+//! Request routing and authentication
+
+use std::sync::Arc;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{self, HeaderValue};
+use hyper::{Method, Request, Response, StatusCode};
+
+use super::state::ReportState;
+
+/// Response body type used by every route
+pub type Body = Full<Bytes>;
+
+const HTML: &str = "text/html; charset=utf-8";
+const JSON: &str = "application/json";
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// Answer a single request
+///
+/// Routes:
+/// - `GET /` - HTML report
+/// - `GET /api/report` - JSON report
+/// - `GET /repo/{name}` - HTML page for one repository
+/// - `POST /api/refresh` - re-run the analysis now
+pub async fn handle<B>(
+    req: Request<B>,
+    state: Arc<ReportState>,
+    token: Option<Arc<str>>,
+) -> Response<Body> {
+    if let Some(token) = token.as_deref() {
+        if !is_authorized(&req, token) {
+            let mut response = text(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token\n");
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            return response;
+        }
+    }
+
+    let path = req.uri().path();
+    let method = req.method();
+
+    match path {
+        "/" | "/api/report" => {
+            if !is_read(method) {
+                return method_not_allowed("GET, HEAD");
+            }
+            let snapshot = state.current();
+            if path == "/" {
+                respond(StatusCode::OK, HTML, snapshot.html.clone())
+            } else {
+                respond(StatusCode::OK, JSON, snapshot.json.clone())
+            }
+        }
+        "/api/refresh" => {
+            if method != Method::POST {
+                return method_not_allowed("POST");
+            }
+            match state.refresh().await {
+                Ok(snapshot) => {
+                    let body = serde_json::json!({
+                        "refreshed": true,
+                        "generated_at": snapshot.generated_at,
+                    });
+                    respond(StatusCode::OK, JSON, body.to_string())
+                }
+                Err(e) => {
+                    let body = serde_json::json!({
+                        "refreshed": false,
+                        "error": e.to_string(),
+                        "generated_at": state.current().generated_at,
+                    });
+                    respond(StatusCode::INTERNAL_SERVER_ERROR, JSON, body.to_string())
+                }
+            }
+        }
+        _ => match path.strip_prefix("/repo/") {
+            Some(encoded) => {
+                if !is_read(method) {
+                    return method_not_allowed("GET, HEAD");
+                }
+                let page = percent_decode(encoded)
+                    .and_then(|name| state.current().repositories.get(&name).cloned());
+                match page {
+                    Some(page) => respond(StatusCode::OK, HTML, page),
+                    None => text(StatusCode::NOT_FOUND, "Unknown repository\n"),
+                }
+            }
+            None => text(StatusCode::NOT_FOUND, "Not found\n"),
+        },
+    }
+}
+
+fn is_read(method: &Method) -> bool {
+    method == Method::GET || method == Method::HEAD
+}
+
+/// Check the `Authorization: Bearer` header against the expected token
+fn is_authorized<B>(req: &Request<B>, token: &str) -> bool {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+}
+
+/// Compare without returning early on the first mismatched byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Decode `%XX` escapes in a path segment; `None` if the result is not UTF-8
+/// or an escape is malformed
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+fn respond(status: StatusCode, content_type: &'static str, body: String) -> Response<Body> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+fn text(status: StatusCode, body: &str) -> Response<Body> {
+    respond(status, TEXT, body.to_string())
+}
+
+fn method_not_allowed(allow: &'static str) -> Response<Body> {
+    let mut response = text(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed\n");
+    response
+        .headers_mut()
+        .insert(header::ALLOW, HeaderValue::from_static(allow));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("my%20repo").as_deref(), Some("my repo"));
+        assert_eq!(percent_decode("plain").as_deref(), Some("plain"));
+        assert_eq!(percent_decode("caf%C3%A9").as_deref(), Some("café"));
+        assert_eq!(percent_decode("bad%2"), None);
+        assert_eq!(percent_decode("bad%zz"), None);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let request = |auth: Option<&str>| {
+            let mut builder = Request::builder().uri("/");
+            if let Some(auth) = auth {
+                builder = builder.header(header::AUTHORIZATION, auth);
+            }
+            builder.body(()).unwrap()
+        };
+
+        assert!(is_authorized(&request(Some("Bearer s3cret")), "s3cret"));
+        assert!(!is_authorized(&request(Some("Bearer wrong")), "s3cret"));
+        assert!(!is_authorized(&request(Some("Basic s3cret")), "s3cret"));
+        assert!(!is_authorized(&request(None), "s3cret"));
+    }
+}
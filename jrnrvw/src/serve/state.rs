@@ -0,0 +1,103 @@
+//! Report snapshots and how they are refreshed
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+
+use crate::analyzer::Analysis;
+use crate::error::{JrnrvwError, Result};
+use crate::output::html::HtmlFormatter;
+use crate::output::json::JsonFormatter;
+use crate::output::{Formatter, OutputOptions};
+
+/// One fully rendered analysis run
+///
+/// Every page is rendered up front, so a snapshot is never observed half
+/// built and requests only ever copy strings out of it.
+#[derive(Debug)]
+pub struct Snapshot {
+    /// When the analysis behind this snapshot ran
+    pub generated_at: DateTime<Utc>,
+
+    /// The full HTML report
+    pub html: String,
+
+    /// The full JSON report
+    pub json: String,
+
+    /// Per-repository HTML pages, keyed by repository name
+    pub repositories: HashMap<String, String>,
+}
+
+impl Snapshot {
+    /// Run the analysis and render every page
+    pub fn build(analysis: &Analysis, options: &OutputOptions) -> Result<Self> {
+        let report = analysis.run()?;
+        let html_formatter = HtmlFormatter::new()?.with_repo_links(true);
+
+        let repositories = report
+            .repositories
+            .iter()
+            .map(|repo| Ok((repo.name.clone(), html_formatter.format_repository(repo, options)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self {
+            generated_at: report.metadata.generated_at,
+            html: html_formatter.format(&report, options)?,
+            json: JsonFormatter::new().format(&report, options)?,
+            repositories,
+        })
+    }
+}
+
+/// The snapshot currently being served, plus what is needed to replace it
+#[derive(Debug)]
+pub struct ReportState {
+    analysis: Analysis,
+    options: OutputOptions,
+    current: RwLock<Arc<Snapshot>>,
+    refreshing: Mutex<()>,
+}
+
+impl ReportState {
+    /// Run the first analysis; fails if it does
+    pub fn new(analysis: Analysis, options: OutputOptions) -> Result<Self> {
+        let snapshot = Snapshot::build(&analysis, &options)?;
+
+        Ok(Self {
+            analysis,
+            options,
+            current: RwLock::new(Arc::new(snapshot)),
+            refreshing: Mutex::new(()),
+        })
+    }
+
+    /// The snapshot to answer a request from
+    pub fn current(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Re-run the analysis and swap in the result
+    ///
+    /// The new snapshot is built on the blocking pool without holding the
+    /// read lock, so requests arriving meanwhile keep getting the previous
+    /// one. Concurrent refreshes queue up behind each other. On failure the
+    /// previous snapshot stays in place.
+    pub async fn refresh(self: &Arc<Self>) -> Result<Arc<Snapshot>> {
+        let _guard = self.refreshing.lock().await;
+
+        let state = Arc::clone(self);
+        let snapshot = tokio::task::spawn_blocking(move || {
+            Snapshot::build(&state.analysis, &state.options)
+        })
+        .await
+        .map_err(|e| JrnrvwError::Server(format!("Refresh task failed: {}", e)))??;
+
+        let snapshot = Arc::new(snapshot);
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::clone(&snapshot);
+
+        Ok(snapshot)
+    }
+}
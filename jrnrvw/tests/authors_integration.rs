@@ -0,0 +1,225 @@
+//! Integration tests for team mode: author attribution, grouping and filtering
+//!
+//! The fixture tree mixes attribution sources that disagree, to pin down
+//! the documented precedence (front matter, then path, then git blame):
+//!
+//! | journal                        | front matter | path  | git blame | author       |
+//! |--------------------------------|--------------|-------|-----------|--------------|
+//! | `alice/... - parser.md`        | -            | alice | Carol     | alice        |
+//! | `alice/... - pairing.md`       | bob          | alice | Carol     | bob          |
+//! | `... - deploy.md` (root)       | -            | -     | Carol     | Carol        |
+//! | `... - draft.md` (uncommitted) | -            | -     | -         | Unattributed |
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Carol", "-c", "user.email=carol@example.com"])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Journal tree with every attribution source in play, and its config
+fn team_journals() -> (TempDir, PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::create_dir(root.join("alice")).unwrap();
+
+    fs::write(root.join("alice/2025.11.20 - JRN - parser.md"), "## Task\nRewrite the parser\n").unwrap();
+    fs::write(
+        root.join("alice/2025.11.21 - JRN - pairing.md"),
+        "---\nauthor: bob\nstatus: done\n---\n## Task\nPair on the lexer\n",
+    )
+    .unwrap();
+    fs::write(root.join("2025.11.22 - JRN - deploy.md"), "## Task\nShip the release\n").unwrap();
+
+    git(root, &["init", "-q"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-q", "-m", "Journals"]);
+
+    // Written after the commit, so blame has nobody to name
+    fs::write(root.join("2025.11.23 - JRN - draft.md"), "## Task\nPlan next week\n").unwrap();
+
+    let config = root.join("jrnrvw.toml");
+    fs::write(&config, "[authors]\npath_component = 1\ngit_blame = true\n").unwrap();
+    (dir, config)
+}
+
+fn report(root: &Path, config: &Path, extra: &[&str]) -> Value {
+    let cache = TempDir::new().unwrap();
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(config)
+        .arg("--no-cache")
+        .arg(root)
+        .args(["--format", "json"])
+        .args(extra)
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// (author, entries, completed tasks) rows of the team summary
+fn team_rows(report: &Value) -> Vec<(String, u64, u64)> {
+    report["team"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["author"].as_str().unwrap().to_string(),
+                row["entries"].as_u64().unwrap(),
+                row["completed_tasks"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_attribution_precedence() {
+    let (dir, config) = team_journals();
+    let report = report(dir.path(), &config, &["--group-by", "author"]);
+
+    let mut authors = Vec::new();
+    for repo in report["repositories"].as_array().unwrap() {
+        for task in repo["tasks"].as_array().unwrap() {
+            for entry in task["entries"].as_array().unwrap() {
+                authors.push((
+                    repo["name"].as_str().unwrap().to_string(),
+                    entry["filename"].as_str().unwrap().to_string(),
+                    entry["author"].as_str().map(str::to_string),
+                ));
+            }
+        }
+    }
+    authors.sort();
+
+    let expected = |group: &str, file: &str, author: Option<&str>| {
+        (group.to_string(), format!("2025.11.{}.md", file), author.map(str::to_string))
+    };
+    assert_eq!(
+        authors,
+        vec![
+            expected("Carol", "22 - JRN - deploy", Some("Carol")),
+            expected("Unattributed", "23 - JRN - draft", None),
+            expected("alice", "20 - JRN - parser", Some("alice")),
+            expected("bob", "21 - JRN - pairing", Some("bob")),
+        ]
+    );
+}
+
+#[test]
+fn test_team_summary() {
+    let (dir, config) = team_journals();
+    let report = report(dir.path(), &config, &[]);
+
+    assert_eq!(
+        team_rows(&report),
+        vec![
+            ("Carol".to_string(), 1, 0),
+            ("alice".to_string(), 1, 0),
+            ("bob".to_string(), 1, 1),
+            ("Unattributed".to_string(), 1, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_author_filter() {
+    let (dir, config) = team_journals();
+    let report = report(dir.path(), &config, &["--author", "ALICE"]);
+
+    assert_eq!(report["metadata"]["total_entries"], 1);
+    assert_eq!(team_rows(&report), vec![("alice".to_string(), 1, 0)]);
+}
+
+#[test]
+fn test_team_section_in_text_report() {
+    let (dir, config) = team_journals();
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .arg("--no-cache")
+        .arg(dir.path())
+        .env("JRNRVW_CACHE_DIR", dir.path().join(".cache"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Team\n"))
+        .stdout(predicate::str::contains("  bob: 1 entries, 1 completed tasks"));
+}
+
+#[test]
+fn test_no_team_section_without_authors() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("2025.11.20 - JRN - parser.md"), "## Task\nRewrite the parser\n").unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg("--no-cache")
+        .arg(dir.path())
+        .args(["--format", "json"])
+        .env("JRNRVW_CACHE_DIR", dir.path().join(".cache"))
+        .output()
+        .unwrap();
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report.get("team").is_none());
+}
+
+#[test]
+fn test_git_metadata_attributes_by_last_commit() {
+    let (dir, _) = team_journals();
+    // No author settings: only the last commit can name anyone
+    let config = dir.path().join("plain.toml");
+    fs::write(&config, "").unwrap();
+    let report = report(dir.path(), &config, &["--git-metadata", "--group-by", "author"]);
+
+    let mut rows = Vec::new();
+    for repo in report["repositories"].as_array().unwrap() {
+        for task in repo["tasks"].as_array().unwrap() {
+            for entry in task["entries"].as_array().unwrap() {
+                rows.push((
+                    repo["name"].as_str().unwrap().to_string(),
+                    entry["filename"].as_str().unwrap().to_string(),
+                    entry["git"].clone(),
+                ));
+            }
+        }
+    }
+    rows.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let groups: Vec<&str> = rows.iter().map(|(group, _, _)| group.as_str()).collect();
+    // Front matter still wins; the uncommitted draft has no commit to go by
+    assert_eq!(groups, vec!["Carol", "bob", "Carol", "Unattributed"]);
+
+    let git = &rows[0].2;
+    assert_eq!(git["commit"].as_str().unwrap().len(), 40);
+    assert_eq!(git["author"], "Carol");
+    assert!(git["date"].as_str().is_some());
+    assert_eq!(rows[3].2, Value::Null);
+}
+
+#[test]
+fn test_git_metadata_outside_a_repository() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("2025.11.20 - JRN - parser.md"), "## Task\nRewrite the parser\n").unwrap();
+    let config = dir.path().join("jrnrvw.toml");
+    fs::write(&config, "[discovery]\ngit_metadata = true\n").unwrap();
+
+    let report = report(dir.path(), &config, &[]);
+    let entry = &report["repositories"][0]["tasks"][0]["entries"][0];
+    assert_eq!(entry["filename"], "2025.11.20 - JRN - parser.md");
+    assert!(entry.get("git").is_none());
+    assert!(report.get("team").is_none());
+}
@@ -0,0 +1,122 @@
+//! Integration tests for `--ci`: diagnostics and exit codes against fixture
+//! journal trees
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CLEAN: &str = "tests/fixtures/ci_repos/clean";
+const DIRTY: &str = "tests/fixtures/ci_repos/dirty";
+
+/// `jrnrvw --ci` with its own index cache
+fn ci(cache: &TempDir) -> Command {
+    let mut cmd = cargo_bin_cmd!("jrnrvw");
+    cmd.env("JRNRVW_CACHE_DIR", cache.path()).arg("--ci");
+    cmd
+}
+
+#[test]
+fn test_clean_tree_exits_zero() {
+    let cache = TempDir::new().unwrap();
+    ci(&cache)
+        .arg(CLEAN)
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr("0 error(s), 0 warning(s)\n");
+}
+
+#[test]
+fn test_violations_exit_one() {
+    let cache = TempDir::new().unwrap();
+    let expected = [
+        format!("{}/2025.11.03 - JRN - release.md:2: error: task 'Release 1.0' was due 2025-11-10 (", DIRTY),
+        format!("{}/2025.11.04 - JRN - migration.md:2: error: journal does not parse: invalid date `2025-13-40`\n", DIRTY),
+        format!("{}/2025.13.45 - JRN - notes.md:1: error: no date in the filename or front matter\n", DIRTY),
+        format!("{}/2025.13.45 - JRN - notes.md:7: warning: unknown jrnrvw directive 'skip'\n", DIRTY),
+    ];
+
+    // The second run takes the journals, parse errors included, from the index
+    for _ in 0..2 {
+        let output = ci(&cache).arg(DIRTY).output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<&str> = stdout.split_inclusive('\n').collect();
+        assert_eq!(lines.len(), 4, "{}", stdout);
+        assert!(lines[0].starts_with(&expected[0]), "{}", lines[0]);
+        assert!(lines[0].ends_with(" day(s) overdue)\n"), "{}", lines[0]);
+        assert_eq!(lines[1..], expected[1..]);
+        assert!(!stdout.contains('\u{1b}'));
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(stderr, "3 error(s), 1 warning(s)\nError: 3 CI violation(s)\n");
+    }
+}
+
+#[test]
+fn test_rules_from_config() {
+    let cache = TempDir::new().unwrap();
+    let config = cache.path().join("ci.toml");
+
+    fs::write(&config, "[ci]\nrules = [\"undated\"]\n").unwrap();
+    ci(&cache)
+        .arg("--config")
+        .arg(&config)
+        .arg(DIRTY)
+        .assert()
+        .code(1)
+        .stdout(format!("{}/2025.13.45 - JRN - notes.md:1: error: no date in the filename or front matter\n", DIRTY));
+
+    // Warnings alone pass
+    fs::write(&config, "[ci]\nrules = [\"parse\"]\n").unwrap();
+    ci(&cache)
+        .arg("--config")
+        .arg(&config)
+        .arg(CLEAN)
+        .assert()
+        .code(0);
+
+    // With no rules nothing is enforced
+    fs::write(&config, "[ci]\nrules = []\n").unwrap();
+    ci(&cache)
+        .arg("--config")
+        .arg(&config)
+        .arg(DIRTY)
+        .assert()
+        .code(0)
+        .stdout("");
+}
+
+#[test]
+fn test_internal_errors_exit_two() {
+    let cache = TempDir::new().unwrap();
+    let config = cache.path().join("broken.toml");
+    fs::write(&config, "[ci]\nrules = [\"spelling\"]\n").unwrap();
+
+    ci(&cache)
+        .arg("--config")
+        .arg(&config)
+        .arg(DIRTY)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Configuration error"));
+
+    ci(&cache)
+        .arg("--config")
+        .arg(cache.path().join("missing.toml"))
+        .arg(CLEAN)
+        .assert()
+        .code(2);
+
+    // Outside CI mode errors keep exiting 1
+    cargo_bin_cmd!("jrnrvw")
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .arg("--config")
+        .arg(&config)
+        .arg(CLEAN)
+        .assert()
+        .code(1);
+}
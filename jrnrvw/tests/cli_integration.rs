@@ -1,5 +1,6 @@
 //! Integration tests for CLI functionality
 
+use assert_cmd::cargo::cargo_bin_cmd;
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
@@ -33,6 +34,11 @@ fn test_json_output_format() {
     let output = cmd.output().unwrap();
     let json_str = String::from_utf8(output.stdout).unwrap();
     assert!(serde_json::from_str::<serde_json::Value>(&json_str).is_ok());
+
+    // ...and that it loads back as the same report
+    let report = jrnrvw::Report::from_json(&json_str).unwrap();
+    assert!(report.repositories.iter().any(|r| r.name == "testproject"));
+    assert_eq!(report.to_json(false).unwrap(), json_str.trim_end());
 }
 
 #[test]
@@ -46,7 +52,7 @@ fn test_markdown_output_format() {
         .stdout(predicate::str::contains("# Journal Review Report"))
         .stdout(predicate::str::contains("## Metadata"))
         .stdout(predicate::str::contains("## Statistics"))
-        .stdout(predicate::str::contains("## Repositories"));
+        .stdout(predicate::str::contains("## Summary"));
 }
 
 #[test]
@@ -64,15 +70,509 @@ fn test_csv_output_format() {
 
 #[test]
 fn test_html_output_format() {
-    // HTML formatter test - just verify it runs
-    // Template rendering is complex and tested separately
     let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
     cmd.arg(FIXTURES_DIR)
         .arg("--format")
-        .arg("html");
+        .arg("html")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("<h3>testproject</h3>"))
+        .stdout(predicate::str::contains("<h3>another-repo</h3>"));
+}
 
-    // HTML may have template issues, just verify command runs
-    let _ = cmd.output();
+#[test]
+fn test_html_report_file_is_self_contained_and_escaped() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.14 - JRN - xss.md"),
+        "## Task\nFix &lt;script&gt;alert(1)&lt;/script&gt;\n\n## Repository\nweb\n\n## Activities\n- [ ] Escape `<img src=x onerror=alert(2)>`\n",
+    )
+    .unwrap();
+    let output_file = journals.path().join("report.html");
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--format", "html", "-o"])
+        .arg(&output_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("<!DOCTYPE html>"));
+
+    let html = fs::read_to_string(&output_file).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<style>"));
+    assert!(!html.contains("<link") && !html.contains("<script src"));
+    assert!(!html.contains("<script>alert") && !html.contains("<img"));
+    assert!(html.contains("<td>Fix &lt;script&gt;alert(1)&lt;/script&gt;</td>"));
+    assert!(html.contains("Escape `&lt;img src=x onerror=alert(2)&gt;`"));
+}
+
+#[test]
+fn test_export_tasks_and_entries_csv() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.14 - JRN - export.md"),
+        "## Task\nMigrate users, then \"verify\"\n\n## Repository\napi\n\n## Activities\n- [x] Copy rows\n",
+    )
+    .unwrap();
+    let tasks_file = journals.path().join("tasks.csv");
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .args(["--repo", "api", "export"])
+        .arg(journals.path())
+        .arg("--tasks-csv")
+        .arg(&tasks_file)
+        .arg("--include-entries")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stdout.is_empty());
+
+    let tasks = fs::read_to_string(&tasks_file).unwrap();
+    let mut lines = tasks.split("\r\n");
+    assert_eq!(
+        lines.next(),
+        Some("Repository,File Path,Line,Title,Status,Tags,Created,Completed")
+    );
+    let row = lines.next().unwrap();
+    assert!(row.starts_with("api,"), "{}", row);
+    assert!(row.contains(",2,\"Migrate users, then \"\"verify\"\"\",done,,2025-11-14,2025-11-14"), "{}", row);
+
+    let entries = fs::read_to_string(journals.path().join("tasks-entries.csv")).unwrap();
+    assert!(entries.starts_with("Repository,Task,Date,File Path,"));
+    assert!(entries.contains("[x] Copy rows"));
+}
+
+#[test]
+fn test_index_cache_tracks_edits_and_clears() {
+    let journals = TempDir::new().unwrap();
+    let cache = TempDir::new().unwrap();
+    let journal = journals.path().join("2025.11.14 - JRN - cache.md");
+    fs::write(&journal, "## Task\nFirst task\n\n## Repository\napi\n").unwrap();
+
+    let run = || {
+        let output = cargo_bin_cmd!("jrnrvw")
+            .env("JRNRVW_CACHE_DIR", cache.path())
+            .arg(journals.path())
+            .args(["--format", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(run().contains("First task"));
+    assert_eq!(fs::read_dir(cache.path()).unwrap().count(), 1);
+
+    fs::write(&journal, "## Task\nSecond, longer task\n\n## Repository\napi\n").unwrap();
+    let edited = run();
+    assert!(edited.contains("Second, longer task"));
+    assert!(!edited.contains("First task"));
+
+    cargo_bin_cmd!("jrnrvw")
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .args(["cache", "clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 1 index file(s)"));
+    assert_eq!(fs::read_dir(cache.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_since_until_and_strict_dates() {
+    let journals = TempDir::new().unwrap();
+    let today = chrono::Local::now().date_naive();
+    let recent = format!("{} - JRN - recent.md", today.format("%Y.%m.%d"));
+    for (name, task) in [
+        ("2025.11.10 - JRN - early.md", "Early"),
+        ("2025.11.14 - JRN - middle.md", "Middle"),
+        ("2025.11.20 - JRN - late.md", "Late"),
+        ("2025.02.30 - JRN - undated.md", "Undated"),
+        (recent.as_str(), "Recent"),
+    ] {
+        fs::write(journals.path().join(name), format!("## Task\n{}\n", task)).unwrap();
+    }
+
+    let total_entries = |args: &[&str]| {
+        let output = cargo_bin_cmd!("jrnrvw")
+            .arg(journals.path())
+            .args(["--no-cache", "--format", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let report = jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap()).unwrap();
+        report.statistics.total_entries
+    };
+
+    // The undated journal is kept unless --strict-dates
+    assert_eq!(total_entries(&["--since", "2025-11-12", "--until", "2025-11-18"]), 2);
+    assert_eq!(total_entries(&["--since", "2025-11-12", "--until", "2025-11-18", "--strict-dates"]), 1);
+    assert_eq!(total_entries(&["--until", "2025-11-14", "--strict-dates"]), 2);
+    assert_eq!(total_entries(&["--since", "2w", "--strict-dates"]), 1);
+    assert_eq!(total_entries(&[]), 5);
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--since", "yesterday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("relative form like 7d or 2w"));
+}
+
+#[test]
+fn test_yaml_front_matter() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.02.30 - JRN - keys.md"),
+        "---\ntitle: Key rotation\ndate: 2025-11-20\ntags: [security, infra]\nticket: OPS-12\n---\n## Task\nRotate keys\n",
+    )
+    .unwrap();
+    fs::write(
+        journals.path().join("2025.11.21 - JRN - broken.md"),
+        "---\ntitle: Broken\nowner\n---\n## Task\nStill listed\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.md:3: expected `key: value`"), "{}", stderr);
+
+    let report = jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    let entries: Vec<_> = report
+        .repositories
+        .iter()
+        .flat_map(|repo| &repo.tasks)
+        .flat_map(|task| &task.entries)
+        .collect();
+    assert_eq!(entries.len(), 2);
+
+    // The front matter date stands in for the filename's invalid one
+    let keys = entries.iter().find(|e| e.title.as_deref() == Some("Key rotation")).unwrap();
+    assert_eq!(keys.date, chrono::NaiveDate::from_ymd_opt(2025, 11, 20).unwrap());
+    assert!(!keys.undated);
+    assert_eq!(keys.tags, vec!["security", "infra"]);
+    assert_eq!(keys.metadata.get("ticket").map(String::as_str), Some("OPS-12"));
+
+    // The malformed file keeps only its filename metadata
+    let broken = entries.iter().find(|e| e.filename.contains("broken")).unwrap();
+    assert!(broken.title.is_none());
+    assert!(broken.task.is_none());
+}
+
+#[test]
+fn test_time_summary() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.20 - JRN - lexer.md"),
+        "---\ntags: [parser]\n---\n## Task\nLexer\n## Repository\ncompiler\n## Activities\n- Fixed the lexer (2h15m)\n- Spent about 45m on review\n",
+    )
+    .unwrap();
+    fs::write(
+        journals.path().join("2025.11.21 - JRN - docs.md"),
+        "## Task\nDocs\n## Repository\nwebsite\n## Time Spent\n1.5h\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // Durations come out as whole minutes
+    let time = &report["time"];
+    assert_eq!(time["total"], serde_json::json!({"minutes": 270, "approximate": true}));
+    assert_eq!(time["by_repository"][0], serde_json::json!({"name": "compiler", "minutes": 180, "approximate": true}));
+    assert_eq!(time["by_repository"][1], serde_json::json!({"name": "website", "minutes": 90}));
+    assert_eq!(time["by_tag"][0]["name"], "parser");
+    assert_eq!(time["by_day"][1], serde_json::json!({"name": "2025-11-21", "minutes": 90}));
+
+    let website = report["repositories"].as_array().unwrap().iter().find(|r| r["name"] == "website").unwrap();
+    assert_eq!(website["tasks"][0]["entries"][0]["time_logged"], serde_json::json!({"minutes": 90}));
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Repository | compiler | ~3h |"))
+        .stdout(predicate::str::contains("| **Total** | | ~4h 30m |"));
+}
+
+#[test]
+fn test_task_dependencies() {
+    let journals = TempDir::new().unwrap();
+    let journal = |name: &str, content: &str| {
+        fs::write(journals.path().join(format!("2025.11.20 - JRN - {}.md", name)), content).unwrap();
+    };
+    journal("schema", "---\nid: schema\n---\n## Task\nSchema\n## Repository\nweb\n");
+    journal(
+        "login",
+        "---\nid: login\n---\n## Task\nLogin\n## Repository\nweb\n## Activities\n- [ ] Form (blocked by: schema, #99)\n",
+    );
+    journal("deploy", "---\nid: deploy\n---\n## Task\nDeploy\n## Repository\nweb\n## Notes\nDepends on #login\n");
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let graph = &report["task_graph"];
+    let order: Vec<&str> = graph["order"][0]["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(order, vec!["schema", "login", "deploy"]);
+    assert_eq!(graph["unresolved"][0]["reference"], "99");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "dot"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph tasks {"))
+        .stdout(predicate::str::contains("\"web#schema\" -> \"web#login\";"));
+
+    // Closing the loop is reported as an error, but the report still renders
+    journal("schema", "---\nid: schema\n---\n## Task\nSchema\n## Repository\nweb\n## Notes\nblocked by: deploy\n");
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--no-color"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[cycle] Dependency cycle between tasks: Deploy (web#deploy), Login (web#login), Schema (web#schema)"))
+        .stderr(predicate::str::contains(
+            "Error: Dependency cycle between tasks: Deploy (web#deploy), Login (web#login), Schema (web#schema)",
+        ));
+}
+
+#[test]
+fn test_duplicate_tasks_are_folded() {
+    let journals = TempDir::new().unwrap();
+    for (day, task) in [(18, "Fix login bug"), (19, "fix login bug."), (20, "Fix login bugs"), (20, "Write docs")] {
+        fs::write(
+            journals.path().join(format!("2025.11.{} - JRN - {}.md", day, task.replace('.', ""))),
+            format!("## Task\n{}\n## Repository\nweb\n", task),
+        )
+        .unwrap();
+    }
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(report["statistics"]["unique_tasks"], 2);
+    assert_eq!(report["statistics"]["task_mentions"], 4);
+    let cluster = &report["duplicates"][0];
+    assert_eq!(cluster["task"], "Fix login bugs");
+    assert_eq!(cluster["members"], serde_json::json!(["Fix login bug", "fix login bug.", "Fix login bugs"]));
+    assert_eq!(cluster["first_seen"], "2025-11-18");
+    assert_eq!(cluster["last_seen"], "2025-11-20");
+    assert_eq!(cluster["mentions"], 3);
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--no-color", "--stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unique Tasks: 2 (4 mentions)"));
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--no-color", "--stats", "--no-dedup"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unique Tasks: 4\n"));
+}
+
+#[test]
+fn test_toml_front_matter_and_overrides() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.20 - JRN - hugo.md"),
+        "+++\ntitle = \"From Hugo\"\ntags = [\"web\"]\nestimate = 3\n\n[review]\nby = \"ana\"\n\n[jrnrvw]\nrepository = \"other\"\n+++\n## Task\nPublish\n\n## Repository\nignored-name\n",
+    )
+    .unwrap();
+    fs::write(
+        journals.path().join("2025.11.21 - JRN - private.md"),
+        "+++\n[jrnrvw]\nskip = true\n+++\n## Task\nSecret\n",
+    )
+    .unwrap();
+    // YAML fence first, so the TOML block is body text and nothing is skipped
+    fs::write(
+        journals.path().join("2025.11.22 - JRN - mixed.md"),
+        "---\ntitle: Mixed\n---\n+++\n[jrnrvw]\nskip = true\n+++\n## Task\nKept\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json = String::from_utf8(output.stdout).unwrap();
+    let report = jrnrvw::Report::from_json(&json).unwrap();
+
+    // Metadata survives the trip through the JSON report
+    assert_eq!(report.to_json(false).unwrap(), json.trim_end());
+    assert_eq!(report.statistics.total_entries, 2);
+
+    let other = report.repositories.iter().find(|r| r.name == "other").unwrap();
+    let hugo = &other.tasks[0].entries[0];
+    assert_eq!(hugo.title.as_deref(), Some("From Hugo"));
+    assert_eq!(hugo.tags, vec!["web"]);
+    assert_eq!(hugo.metadata.get("estimate").map(String::as_str), Some("3"));
+    assert_eq!(hugo.metadata.get("review.by").map(String::as_str), Some("ana"));
+    assert!(json.find(r#""estimate":"3""#).unwrap() < json.find(r#""review.by":"ana""#).unwrap());
+
+    let entries: Vec<_> = report
+        .repositories
+        .iter()
+        .flat_map(|repo| &repo.tasks)
+        .flat_map(|task| &task.entries)
+        .collect();
+    assert!(entries.iter().any(|e| e.title.as_deref() == Some("Mixed")));
+    assert!(!entries.iter().any(|e| e.filename.contains("private")));
+}
+
+#[test]
+fn test_org_journals_match_markdown() {
+    let journals = TempDir::new().unwrap();
+    let md = journals.path().join("md");
+    let org = journals.path().join("org");
+    fs::create_dir_all(&md).unwrap();
+    fs::create_dir_all(&org).unwrap();
+    fs::write(
+        md.join("2025.11.20 - JRN - parser.md"),
+        "---\nstatus: done\npriority: A\ntags: [parser]\ndate: 2025-11-21\n---\n## Task\nAdd org support\n\n## Activities\n- [x] Parse headings\n- Wrote tests\n\n## Time Spent\n2 hours\n",
+    )
+    .unwrap();
+    fs::write(
+        org.join("2025.11.20 - JRN - parser.org"),
+        "* DONE [#A] Add org support :parser:\n  SCHEDULED: <2025-11-21 Fri>\n** Activities\n   - [x] Parse headings\n   - Wrote tests\n** Time Spent\n   2 hours\n",
+    )
+    .unwrap();
+    let config = journals.path().join("jrnrvw.toml");
+
+    let report = |include_org: bool| {
+        fs::write(&config, format!("[discovery]\ninclude_org = {}\n", include_org)).unwrap();
+        let output = cargo_bin_cmd!("jrnrvw")
+            .arg("--config")
+            .arg(&config)
+            .arg(journals.path())
+            .args(["--no-cache", "--format", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap()).unwrap()
+    };
+
+    assert_eq!(report(false).statistics.total_entries, 1);
+
+    let report = report(true);
+    assert_eq!(report.statistics.total_entries, 2);
+    let entry = |repo: &str| {
+        let repo = report.repositories.iter().find(|r| r.name == repo).unwrap();
+        let mut entry = repo.tasks[0].entries[0].clone();
+        entry.filename.clear();
+        entry.filepath.clear();
+        entry.repository = None;
+        entry
+    };
+    assert_eq!(entry("org"), entry("md"));
+    assert_eq!(entry("org").task.as_deref(), Some("Add org support"));
+}
+
+#[test]
+fn test_exclude_patterns_and_ignore_files() {
+    let journals = TempDir::new().unwrap();
+    for dir in ["app", "app/scratch", "archive"] {
+        fs::create_dir_all(journals.path().join(dir)).unwrap();
+    }
+    for path in [
+        "app/2025.11.20 - JRN - kept.md",
+        "app/scratch/2025.11.21 - JRN - scratch.md",
+        "archive/2025.01.02 - JRN - old.md",
+    ] {
+        fs::write(journals.path().join(path), "## Task\nWork\n").unwrap();
+    }
+    fs::write(journals.path().join("app/.jrnrvwignore"), "scratch/\n").unwrap();
+    let config = journals.path().join("jrnrvw.toml");
+    fs::write(&config, "[discovery]\nexclude = [\"archive/**\"]\n").unwrap();
+
+    let total_entries = |extra: &[&str]| {
+        let output = cargo_bin_cmd!("jrnrvw")
+            .arg("--config")
+            .arg(&config)
+            .arg(journals.path())
+            .args(["--no-cache", "--format", "json"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap())
+            .unwrap()
+            .statistics
+            .total_entries
+    };
+
+    assert_eq!(total_entries(&[]), 1);
+    assert_eq!(total_entries(&["--no-ignore"]), 3);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_follow_symlinks_reads_shared_journals_once() {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new().unwrap();
+    let vault = temp_dir.path().join("vault");
+    let journals = temp_dir.path().join("journals");
+    fs::create_dir_all(&vault).unwrap();
+    fs::create_dir_all(&journals).unwrap();
+    fs::write(vault.join("2025.11.20 - JRN - shared.md"), "## Task\nShared work\n").unwrap();
+    symlink(&vault, journals.join("vault")).unwrap();
+    symlink(&vault, journals.join("vault-again")).unwrap();
+    symlink(&journals, journals.join("loop")).unwrap();
+    symlink(temp_dir.path().join("nowhere"), journals.join("dangling")).unwrap();
+    let config = temp_dir.path().join("jrnrvw.toml");
+    fs::write(&config, "[discovery]\nfollow_symlinks = true\n").unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .arg(&journals)
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: broken symlink"), "{}", stderr);
+    assert!(stderr.contains("dangling"), "{}", stderr);
+
+    let report = jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(report.statistics.total_entries, 1);
 }
 
 #[test]
@@ -229,6 +729,129 @@ fn test_group_by_date() {
         .success();
 }
 
+#[test]
+fn test_group_by_week_fills_empty_weeks() {
+    let journals = TempDir::new().unwrap();
+    for (name, body) in [
+        ("2025.11.08 - JRN - saturday.md", "## Task\nAuth\n"),
+        ("2025.11.09 - JRN - sunday.md", "---\nstatus: done\n---\n## Task\nAuth\n"),
+        ("2025.11.25 - JRN - later.md", "## Task\nDeploy\n"),
+    ] {
+        fs::write(journals.path().join(name), body).unwrap();
+    }
+
+    let groups = |args: &[&str]| {
+        let output = cargo_bin_cmd!("jrnrvw")
+            .arg(journals.path())
+            .args(["--no-cache", "--format", "json", "--since", "2025-11-01", "--until", "2025-11-30"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let report = jrnrvw::Report::from_json(&String::from_utf8(output.stdout).unwrap()).unwrap();
+        report
+            .groups
+            .into_iter()
+            .map(|g| (g.name, g.entries, g.tasks_opened, g.tasks_completed))
+            .collect::<Vec<_>>()
+    };
+    let week = |name: &str, entries, opened, completed| (name.to_string(), entries, opened, completed);
+
+    assert_eq!(
+        groups(&["--group-by", "week"]),
+        vec![
+            week("Week 43, 2025", 0, 0, 0),
+            week("Week 44, 2025", 2, 1, 1),
+            week("Week 45, 2025", 0, 0, 0),
+            week("Week 46, 2025", 0, 0, 0),
+            week("Week 47, 2025", 1, 1, 0),
+        ]
+    );
+    assert_eq!(
+        groups(&["--group-by", "week", "--week-start", "sun"]),
+        vec![
+            week("Week 43, 2025", 0, 0, 0),
+            week("Week 44, 2025", 1, 1, 0),
+            week("Week 45, 2025", 1, 0, 1),
+            week("Week 46, 2025", 0, 0, 0),
+            week("Week 47, 2025", 1, 1, 0),
+            week("Week 48, 2025", 0, 0, 0),
+        ]
+    );
+    assert_eq!(groups(&["--group-by", "month"]), vec![week("November 2025", 3, 2, 1)]);
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(journals.path())
+        .args(["--no-cache", "--group-by", "week", "--week-start", "someday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Expected a weekday"));
+}
+
+#[test]
+fn test_check_against_baseline() {
+    let temp_dir = TempDir::new().unwrap();
+    let journals = temp_dir.path().join("journals");
+    fs::create_dir_all(&journals).unwrap();
+    fs::write(journals.join("2025.11.03 - JRN - auth.md"), "## Task\nAuth\n").unwrap();
+    let baseline = temp_dir.path().join("baseline.json");
+    let config = temp_dir.path().join("check.toml");
+    fs::write(&config, "[check.open_task_growth]\nmax = 1\n\n[check.missing_entries]\nmax = 3\nseverity = \"off\"\n").unwrap();
+
+    let check = || {
+        let mut cmd = cargo_bin_cmd!("jrnrvw");
+        cmd.arg(&journals)
+            .args(["--no-cache", "--config"])
+            .arg(&config)
+            .args(["--format", "json", "check", "--baseline"])
+            .arg(&baseline);
+        cmd
+    };
+
+    check()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--update-baseline"));
+
+    check().arg("--update-baseline").assert().success();
+    let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&baseline).unwrap()).unwrap();
+    assert_eq!(written["open_tasks"][0]["task"], "Auth");
+
+    // The old task only warns, so the check passes
+    let output = check().output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results["passed"], true);
+    assert_eq!(results["violations"][0]["rule"], "task_age");
+    assert_eq!(results["violations"][0]["severity"], "warn");
+
+    // Two new open tasks are one more than allowed
+    fs::write(journals.join("2025.11.04 - JRN - deploy.md"), "## Task\nDeploy\n").unwrap();
+    fs::write(journals.join("2025.11.05 - JRN - docs.md"), "## Task\nDocs\n").unwrap();
+    let output = check().output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 journal check(s) failed"));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results["passed"], false);
+    let growth = &results["violations"][0];
+    assert_eq!(growth["rule"], "open_task_growth");
+    assert_eq!(growth["severity"], "fail");
+    let tasks: Vec<&str> = growth["tasks"].as_array().unwrap().iter().map(|t| t["task"].as_str().unwrap()).collect();
+    assert_eq!(tasks, vec!["Deploy", "Docs"]);
+
+    // Text output lists the offending tasks too
+    cargo_bin_cmd!("jrnrvw")
+        .arg(&journals)
+        .args(["--no-cache", "--config"])
+        .arg(&config)
+        .args(["check", "--baseline"])
+        .arg(&baseline)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[fail] open_task_growth: 3 open tasks, 2 more than the baseline's 1"))
+        .stdout(predicate::str::contains(": Deploy (opened 2025-11-04)"));
+}
+
 #[test]
 fn test_sort_by_repo() {
     let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
@@ -247,3 +870,327 @@ fn test_reverse_sort() {
         .assert()
         .success();
 }
+
+#[test]
+fn test_project_tagging() {
+    let temp_dir = TempDir::new().unwrap();
+    let infra = temp_dir.path().join("infra-dns");
+    let web = temp_dir.path().join("web");
+    fs::create_dir_all(infra.join(".git")).unwrap();
+    fs::create_dir_all(web.join(".git")).unwrap();
+    fs::write(
+        infra.join(".git").join("config"),
+        "[remote \"origin\"]\n\turl = git@github.com:acme/infra-dns.git\n",
+    )
+    .unwrap();
+
+    fs::write(infra.join("2025.11.10 - JRN - keys.md"), "## Task\nRotate keys\n").unwrap();
+    fs::write(web.join("2025.11.11 - JRN - deploy.md"), "## Task\nDeploy\n").unwrap();
+    fs::write(
+        web.join("2025.11.12 - JRN - dns.md"),
+        "---\nproject: infra\n---\n## Task\nPoint DNS at the new site\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("tags.toml");
+    fs::write(
+        &config,
+        "[[tagging.remote]]\npattern = 'github\\.com/acme/infra-'\ntag = \"infra\"\n\n[[tagging.path]]\npattern = \"**\"\ntag = \"misc\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
+    cmd.arg(temp_dir.path())
+        .arg("--config")
+        .arg(&config)
+        .arg("--project")
+        .arg("infra")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rotate keys"))
+        .stdout(predicate::str::contains("Point DNS at the new site"))
+        .stdout(predicate::str::contains("Deploy").not());
+
+    let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
+    cmd.arg(temp_dir.path())
+        .arg("--config")
+        .arg(&config)
+        .arg("--group-by")
+        .arg("project")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name":"infra""#))
+        .stdout(predicate::str::contains(r#""name":"misc""#));
+}
+
+#[test]
+fn test_invalid_tagging_rules() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = temp_dir.path().join("tags.toml");
+    fs::write(
+        &config,
+        "[[tagging.remote]]\npattern = \".*\"\ntag = \"any\"\n\n[[tagging.remote]]\npattern = \"acme/\"\ntag = \"acme\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
+    cmd.arg(FIXTURES_DIR)
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("remote rule 1 ('.*' -> any)"))
+        .stderr(predicate::str::contains("catch-all"));
+}
+
+#[cfg(feature = "profile")]
+#[test]
+fn test_profile_table_and_trace() {
+    let temp_dir = TempDir::new().unwrap();
+    let trace = temp_dir.path().join("trace.json");
+
+    // An empty cache, so every journal is parsed
+    let mut cmd = Command::cargo_bin("jrnrvw").unwrap();
+    cmd.env("JRNRVW_CACHE_DIR", temp_dir.path().join("cache"))
+        .arg(FIXTURES_DIR)
+        .arg("--profile")
+        .arg("--profile-trace")
+        .arg(&trace)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Journal Review Report"))
+        .stderr(predicate::str::contains("Phase"))
+        .stderr(predicate::str::contains("discovery"))
+        .stderr(predicate::str::contains("render"));
+
+    let trace: serde_json::Value = serde_json::from_str(&fs::read_to_string(&trace).unwrap()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+    let parses = events.iter().filter(|e| e["name"] == "parse").count();
+    assert_eq!(parses, 3);
+}
+
+/// Config with `work` and `personal` journal trees, each holding one journal
+fn profile_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    for (tree, task) in [("work", "Rotate keys"), ("personal", "Plan the garden")] {
+        let dir = temp_dir.path().join(tree);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("2025.11.10 - JRN - notes.md"), format!("## Task\n{}\n", task)).unwrap();
+    }
+
+    let config = temp_dir.path().join("profiles.toml");
+    fs::write(
+        &config,
+        format!(
+            "default_profile = \"work\"\n\n[general]\ndefault_format = \"markdown\"\n\n\
+             [profile.work.general]\ndefault_path = '{}'\n\n\
+             [profile.personal.general]\ndefault_path = '{}'\ndefault_format = \"json\"\n",
+            temp_dir.path().join("work").display(),
+            temp_dir.path().join("personal").display()
+        ),
+    )
+    .unwrap();
+    config
+}
+
+#[test]
+fn test_config_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = profile_config(&temp_dir);
+    let run = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("jrnrvw");
+        cmd.env("JRNRVW_CACHE_DIR", temp_dir.path().join("cache"))
+            .arg("--config")
+            .arg(&config)
+            .args(args)
+            .assert()
+            .success()
+    };
+
+    // The default profile's root, with the top-level format
+    run(&[])
+        .stdout(predicate::str::contains("# Journal Review Report"))
+        .stdout(predicate::str::contains("Rotate keys"))
+        .stdout(predicate::str::contains("Plan the garden").not());
+
+    // The personal profile's root and format
+    run(&["--config-profile", "personal"])
+        .stdout(predicate::str::starts_with("{"))
+        .stdout(predicate::str::contains("Plan the garden"));
+
+    // Command-line flags win over the profile
+    run(&["--config-profile", "personal", "--format", "text"])
+        .stdout(predicate::str::starts_with("Journal Review Report"));
+    let work = temp_dir.path().join("work");
+    run(&["--config-profile", "personal", work.to_str().unwrap()])
+        .stdout(predicate::str::contains("Rotate keys"))
+        .stdout(predicate::str::contains("Plan the garden").not());
+}
+
+#[test]
+fn test_config_show() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = profile_config(&temp_dir);
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["config", "show", "--profile", "personal"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Profile: personal"))
+        .stdout(predicate::str::contains("default_format = \"json\""))
+        .stdout(predicate::str::contains("[discovery]"));
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Profile: work"))
+        .stdout(predicate::str::contains("default_format = \"markdown\""));
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["config", "show", "--profile", "home"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no profile 'home' (defined: personal, work)"));
+}
+
+#[test]
+fn test_completions_and_man() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = profile_config(&temp_dir);
+
+    cargo_bin_cmd!("jrnrvw")
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("complete -F _jrnrvw"))
+        .stdout(predicate::str::contains("serve export cache search notify check llm config completions man"))
+        .stdout(predicate::str::contains("personal").not());
+
+    // The config's profiles complete --config-profile
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("personal work"));
+
+    for shell in ["zsh", "fish", "powershell"] {
+        cargo_bin_cmd!("jrnrvw")
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("jrnrvw"));
+    }
+
+    cargo_bin_cmd!("jrnrvw")
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure();
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("man")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".TH jrnrvw 1"))
+        .stdout(predicate::str::contains(".SH SUBCOMMANDS"));
+}
+
+#[test]
+fn test_fail_on_overdue() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("2025.11.03 - JRN - release.md"),
+        "---\ndue: 2025-11-10\n---\n## Task\nRelease\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("2025.11.04 - JRN - certs.md"),
+        "## Task\nCerts\n\n## Activities\n- [ ] Renew certificate 📅 2999-01-01\n",
+    )
+    .unwrap();
+
+    let report = || {
+        let mut cmd = cargo_bin_cmd!("jrnrvw");
+        cmd.arg(temp_dir.path()).args(["--no-cache", "--format", "json"]);
+        cmd
+    };
+
+    // Without the flag an overdue task is only reported
+    let output = report().output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let overdue = json["overdue"].as_array().unwrap();
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0]["task"], "Release");
+    assert_eq!(overdue[0]["due"], "2025-11-10");
+
+    report()
+        .arg("--fail-on-overdue")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"overdue\""))
+        .stderr(predicate::str::contains("1 overdue task(s)"));
+
+    // Finishing the task clears it
+    fs::write(
+        temp_dir.path().join("2025.11.05 - JRN - release.md"),
+        "---\nstatus: done\n---\n## Task\nRelease\n",
+    )
+    .unwrap();
+    report().arg("--fail-on-overdue").assert().success();
+}
+
+#[test]
+fn test_throughput_metrics() {
+    let temp_dir = TempDir::new().unwrap();
+    for (day, task, status) in [(3, "Release", "open"), (5, "Release", "done"), (4, "Certs", "open"), (12, "Docs", "open")] {
+        fs::write(
+            temp_dir.path().join(format!("2025.11.{:02} - JRN - {}.md", day, task)),
+            format!("---\nstatus: {}\n---\n## Task\n{}\n## Repository\napi\n", status, task),
+        )
+        .unwrap();
+    }
+    // Not a real date, so the entry is undated
+    fs::write(
+        temp_dir.path().join("2025.13.40 - JRN - ideas.md"),
+        "## Task\nIdeas\n## Repository\nscratch\n",
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(temp_dir.path())
+        .args(["--no-cache", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let metrics = &report["metrics"];
+    assert_eq!(
+        metrics["weeks"],
+        serde_json::json!([
+            {"week": "2025-11-03", "completed": 1, "open": 1},
+            {"week": "2025-11-10", "completed": 0, "open": 2},
+        ])
+    );
+    assert_eq!(metrics["average_days_to_complete"], 2.0);
+    assert_eq!(metrics["skipped"], serde_json::json!(["scratch"]));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no dated entries in repository 'scratch'"));
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg(temp_dir.path())
+        .args(["--no-cache", "--no-color", "--stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Throughput\n  Completed: #_\n  Open:      =#\n"));
+}
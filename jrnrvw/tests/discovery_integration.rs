@@ -66,11 +66,15 @@ fn test_discover_journals_invalid_dates() {
     let valid_journal = temp_dir.path().join("2025.11.10 - JRN - valid.md");
     fs::write(&valid_journal, "# Valid Journal").unwrap();
 
-    // Should only discover the valid one
-    let entries = discover_journals(temp_dir.path(), vec![]).unwrap();
+    // The invalid one is kept, flagged as undated
+    let mut entries = discover_journals(temp_dir.path(), vec![]).unwrap();
+    entries.sort_by(|a, b| a.filename.cmp(&b.filename));
 
-    assert_eq!(entries.len(), 1);
-    assert!(entries[0].filename.contains("valid"));
+    assert_eq!(entries.len(), 2);
+    assert!(!entries[0].undated);
+    assert_eq!(entries[0].date, chrono::NaiveDate::from_ymd_opt(2025, 11, 10).unwrap());
+    assert!(entries[1].undated);
+    assert!(entries[1].filename.contains("invalid"));
 }
 
 #[test]
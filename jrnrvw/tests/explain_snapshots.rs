@@ -0,0 +1,77 @@
+//! Snapshot tests for `--explain` output
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the files in `tests/snapshots`
+//! after an intended change, then review the diff.
+
+#![cfg(feature = "explain")]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+use std::fs;
+
+const FIXTURES_DIR: &str = "tests/fixtures/explain_journals";
+
+fn run(args: &[&str]) -> String {
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(FIXTURES_DIR)
+        .arg("--explain")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = format!("tests/snapshots/{}", name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "{} is out of date", path);
+}
+
+#[test]
+fn test_explain_text_snapshot() {
+    let output = run(&["--no-color"]);
+
+    // Everything above the Explain section carries the generation time
+    let explain = &output[output.find("\nExplain\n").expect("no Explain section")..];
+    assert_snapshot("explain.txt", explain.trim_start());
+}
+
+#[test]
+fn test_explain_json_snapshot() {
+    let report: Value = serde_json::from_str(&run(&["--format", "json"])).unwrap();
+
+    let mut explained: Vec<Value> = report["repositories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|repo| repo["tasks"].as_array().unwrap())
+        .flat_map(|task| task["entries"].as_array().unwrap())
+        .map(|entry| {
+            serde_json::json!({
+                "filename": entry["filename"],
+                "explanation": entry["explanation"],
+            })
+        })
+        .collect();
+    explained.sort_by_key(|entry| entry["filename"].as_str().unwrap().to_string());
+
+    let mut json = serde_json::to_string_pretty(&explained).unwrap();
+    json.push('\n');
+    assert_snapshot("explain.json", &json);
+}
+
+#[test]
+fn test_explanations_are_absent_by_default() {
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(FIXTURES_DIR)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("\"explanation\""));
+}
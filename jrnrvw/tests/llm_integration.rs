@@ -0,0 +1,298 @@
+//! Integration tests for the Ollama backend against a local mock server
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::TempDir;
+
+/// Ollama stand-in serving `/api/tags` and a streamed `/api/generate`
+struct MockOllama {
+    url: String,
+    prompts: Arc<Mutex<Vec<Value>>>,
+}
+
+impl MockOllama {
+    /// Offer `models`, answering every prompt with `pieces`
+    fn start(models: &[&str], pieces: &[&str]) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let prompts = Arc::new(Mutex::new(Vec::new()));
+
+        let tags = serde_json::json!({
+            "models": models.iter().map(|name| serde_json::json!({ "name": name })).collect::<Vec<_>>()
+        })
+        .to_string();
+        let mut generated: String = pieces
+            .iter()
+            .map(|piece| format!("{}\n", serde_json::json!({ "response": piece, "done": false })))
+            .collect();
+        generated.push_str("{\"response\":\"\",\"done\":true}\n");
+
+        let recorded = Arc::clone(&prompts);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, reply) = if request_line.starts_with("GET /api/tags ") {
+                    ("200 OK", tags.clone())
+                } else if request_line.starts_with("POST /api/generate ") {
+                    recorded.lock().unwrap().push(serde_json::from_slice(&body).unwrap());
+                    ("200 OK", generated.clone())
+                } else {
+                    ("404 Not Found", "{\"error\":\"not found\"}".to_string())
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    reply.len(),
+                    reply
+                )
+                .unwrap();
+            }
+        });
+
+        Self { url, prompts }
+    }
+
+    fn prompts(&self) -> Vec<Value> {
+        self.prompts.lock().unwrap().clone()
+    }
+}
+
+fn write_config(dir: &Path, base_url: &str, model: &str) -> PathBuf {
+    let path = dir.join("jrnrvw.toml");
+    fs::write(
+        &path,
+        format!("[llm]\nbackend = \"ollama\"\nbase_url = \"{}\"\nmodel = \"{}\"\n", base_url, model),
+    )
+    .unwrap();
+    path
+}
+
+#[test]
+fn test_llm_check_reports_model_and_latency() {
+    let dir = TempDir::new().unwrap();
+    let server = MockOllama::start(&["llama3.1:latest", "mistral:7b"], &[]);
+    let config = write_config(dir.path(), &server.url, "llama3.1");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["llm", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Backend:  ollama"))
+        .stdout(predicate::str::contains(&server.url))
+        .stdout(predicate::str::contains("llama3.1"))
+        .stdout(predicate::str::contains("Latency:"));
+}
+
+#[test]
+fn test_llm_check_fails_for_missing_model() {
+    let dir = TempDir::new().unwrap();
+    let server = MockOllama::start(&["mistral:7b"], &[]);
+    let config = write_config(dir.path(), &server.url, "llama3.1");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["llm", "check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("model 'llama3.1' is not available"))
+        .stderr(predicate::str::contains("mistral:7b"));
+}
+
+#[test]
+fn test_llm_check_fails_when_unreachable() {
+    let dir = TempDir::new().unwrap();
+    // Bind then drop, so nothing is listening on the port
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let config = write_config(dir.path(), &url, "llama3.1");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["llm", "check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot reach Ollama"));
+}
+
+#[test]
+fn test_summarize_through_ollama() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.20 - JRN - parser.md"),
+        "## Task\nRewrite the parser\n",
+    )
+    .unwrap();
+    let cache = TempDir::new().unwrap();
+    let server = MockOllama::start(&["llama3.1:latest"], &["Rewrote ", "the parser."]);
+    let config = write_config(journals.path(), &server.url, "llama3.1");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .arg("--no-cache")
+        .arg(journals.path())
+        .arg("--summarize")
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rewrote the parser."));
+
+    let prompts = server.prompts();
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0]["model"], "llama3.1");
+    assert_eq!(prompts[0]["stream"], true);
+    assert!(prompts[0]["prompt"].as_str().unwrap().contains("parser"));
+}
+
+fn summarize(journals: &TempDir, cache: &TempDir, config: &Path, extra: &[&str]) -> assert_cmd::assert::Assert {
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(config)
+        .arg("--verbose")
+        .arg(journals.path())
+        .arg("--summarize")
+        .args(extra)
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .assert()
+}
+
+#[test]
+fn test_unchanged_journals_reuse_cached_summary() {
+    let journals = TempDir::new().unwrap();
+    let journal = journals.path().join("2025.11.20 - JRN - parser.md");
+    fs::write(&journal, "## Task\nRewrite the parser\n").unwrap();
+    let cache = TempDir::new().unwrap();
+    let server = MockOllama::start(&["llama3.1:latest"], &["Rewrote the parser."]);
+    let config = write_config(cache.path(), &server.url, "llama3.1");
+
+    summarize(&journals, &cache, &config, &[])
+        .success()
+        .stderr(predicate::str::contains("LLM cache: 0 hit(s), 1 miss(es)"));
+    summarize(&journals, &cache, &config, &[])
+        .success()
+        .stdout(predicate::str::contains("Rewrote the parser."))
+        .stderr(predicate::str::contains("LLM cache: 1 hit(s), 0 miss(es)"));
+    assert_eq!(server.prompts().len(), 1);
+
+    // --no-llm-cache asks again
+    summarize(&journals, &cache, &config, &["--no-llm-cache"])
+        .success()
+        .stderr(predicate::str::contains("0 hit(s), 1 miss(es)"));
+    assert_eq!(server.prompts().len(), 2);
+
+    // Changed journal content is a new prompt
+    fs::write(&journal, "## Task\nRewrite the lexer\n").unwrap();
+    summarize(&journals, &cache, &config, &[])
+        .success()
+        .stderr(predicate::str::contains("0 hit(s), 1 miss(es)"));
+    assert_eq!(server.prompts().len(), 3);
+
+    cargo_bin_cmd!("jrnrvw")
+        .args(["cache", "clear", "--llm"])
+        .env("JRNRVW_CACHE_DIR", cache.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 2 cached summary file(s)"));
+    summarize(&journals, &cache, &config, &[])
+        .success()
+        .stderr(predicate::str::contains("0 hit(s), 1 miss(es)"));
+}
+
+#[test]
+fn test_excluded_author_kept_out_of_prompt() {
+    let journals = TempDir::new().unwrap();
+    for (author, task) in [("alice", "Rewrite the parser"), ("bob", "Interview candidates")] {
+        fs::create_dir(journals.path().join(author)).unwrap();
+        fs::write(
+            journals.path().join(author).join("2025.11.20 - JRN - work.md"),
+            format!("## Task\n{}\n", task),
+        )
+        .unwrap();
+    }
+    let cache = TempDir::new().unwrap();
+    let server = MockOllama::start(&["llama3.1:latest"], &["Summary."]);
+    let config = write_config(cache.path(), &server.url, "llama3.1");
+    let mut settings = fs::read_to_string(&config).unwrap();
+    settings.push_str("\n[authors]\npath_component = 1\nexclude_from_llm = [\"bob\"]\n");
+    fs::write(&config, settings).unwrap();
+
+    summarize(&journals, &cache, &config, &["--group-by", "author"]).success();
+
+    let prompts = server.prompts();
+    let prompt = prompts[0]["prompt"].as_str().unwrap();
+    assert!(prompt.contains("Total Entries: 2"));
+    assert!(prompt.contains("Rewrite the parser"));
+    assert!(!prompt.contains("bob"));
+    assert!(!prompt.contains("Interview candidates"));
+}
+
+#[test]
+fn test_redacted_values_kept_out_of_prompt_and_restored() {
+    let journals = TempDir::new().unwrap();
+    fs::write(
+        journals.path().join("2025.11.20 - JRN - invoices.md"),
+        "## Task\nCall Jane Doe at jane@acme.com about the invoice\n",
+    )
+    .unwrap();
+    let cache = TempDir::new().unwrap();
+    let server = MockOllama::start(&["llama3.1:latest"], &["Called [PERS", "ON_1] (", "[EMAIL_1])."]);
+    let config = write_config(cache.path(), &server.url, "llama3.1");
+    let mut settings = fs::read_to_string(&config).unwrap();
+    settings.push_str("\n[[redact.patterns]]\nlabel = \"PERSON\"\nregex = 'Jane Doe'\n");
+    fs::write(&config, settings).unwrap();
+
+    summarize(&journals, &cache, &config, &["--show-redactions"])
+        .success()
+        .stdout(predicate::str::contains("Called Jane Doe (jane@acme.com)."))
+        .stderr(predicate::str::contains("Redacted 2 value(s)"))
+        .stderr(predicate::str::contains("[EMAIL_1]   jane@acme.com  (1x)"));
+
+    let prompts = server.prompts();
+    let prompt = prompts[0]["prompt"].as_str().unwrap();
+    assert!(prompt.contains("Call [PERSON_1] at [EMAIL_1] about the invoice"));
+    assert!(!prompt.contains("Jane Doe"));
+    assert!(!prompt.contains("jane@acme.com"));
+
+    // The cached summary still holds placeholders and is restored on reuse
+    let out = TempDir::new().unwrap();
+    let summary = out.path().join("summary.md");
+    summarize(&journals, &cache, &config, &["--summary-output", summary.to_str().unwrap()])
+        .success()
+        .stderr(predicate::str::contains("1 hit(s)"))
+        .stderr(predicate::str::contains("Redacted").not());
+    assert_eq!(fs::read_to_string(&summary).unwrap(), "Called Jane Doe (jane@acme.com).");
+}
@@ -0,0 +1,54 @@
+//! Snapshot tests for `--format markdown` output
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the files in `tests/snapshots`
+//! after an intended change, then review the diff.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+
+const FIXTURES_DIR: &str = "tests/fixtures/markdown_journals";
+
+/// Rendered markdown from the Summary section on; the Metadata section above
+/// it carries the generation time
+fn run(args: &[&str]) -> String {
+    let output = cargo_bin_cmd!("jrnrvw")
+        .arg(FIXTURES_DIR)
+        .args(["--format", "markdown"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let start = stdout.find("## Summary").expect("no Summary section");
+    stdout[start..].to_string()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = format!("tests/snapshots/{}", name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "{} is out of date", path);
+}
+
+#[test]
+fn test_markdown_snapshot() {
+    let output = run(&["--verbose", "--with-notes", "--max-entry-chars", "120"]);
+    assert_snapshot("markdown.md", &output);
+}
+
+#[test]
+fn test_markdown_summary_snapshot() {
+    let output = run(&["--summary"]);
+    assert_snapshot("markdown_summary.md", &output);
+}
+
+#[test]
+fn test_entry_limit_can_be_disabled() {
+    let output = run(&["--verbose", "--with-notes", "--max-entry-chars", "0"]);
+    assert!(!output.contains('…'));
+    assert!(output.contains("points at the right line instead of the end of the file. |"));
+}
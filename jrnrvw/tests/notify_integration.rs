@@ -0,0 +1,231 @@
+//! Integration tests for `jrnrvw notify` against a local mock webhook
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use jrnrvw::output::notify::Notifier;
+use predicates::prelude::*;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Webhook stand-in recording every request body it receives
+struct MockWebhook {
+    url: String,
+    bodies: Arc<Mutex<Vec<Value>>>,
+}
+
+impl MockWebhook {
+    /// Answer the first `failures` requests with 503, the rest with 200
+    fn start(failures: usize) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&bodies);
+        thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let status = if index < failures {
+                    "503 Service Unavailable"
+                } else {
+                    recorded.lock().unwrap().push(serde_json::from_slice(&body).unwrap());
+                    "200 OK"
+                };
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok", status).unwrap();
+            }
+        });
+
+        Self { url, bodies }
+    }
+
+    fn bodies(&self) -> Vec<Value> {
+        self.bodies.lock().unwrap().clone()
+    }
+}
+
+/// Journals finishing `done` tasks on 2025-11-20, plus one open entry
+fn write_journals(root: &Path, done: usize) {
+    for i in 0..done {
+        let dir = root.join(format!("repo-{}", i % 3));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("2025.11.20 - JRN - task-{}.md", i)),
+            format!(
+                "---\nstatus: done\n---\n## Task\nFinish the rather long piece of work number {:03}\n",
+                i
+            ),
+        )
+        .unwrap();
+    }
+    fs::write(root.join("2025.11.20 - JRN - open.md"), "## Task\nStill going\n").unwrap();
+    fs::write(root.join("2025.11.19 - JRN - old.md"), "---\nstatus: done\n---\n## Task\nOld work\n").unwrap();
+}
+
+fn write_config(dir: &Path, kind: &str) -> std::path::PathBuf {
+    let path = dir.join("jrnrvw.toml");
+    fs::write(
+        &path,
+        format!("[notify.channels.work]\nkind = \"{}\"\nurl_env = \"TEST_WORK_HOOK\"\n", kind),
+    )
+    .unwrap();
+    path
+}
+
+fn notify(journals: &TempDir, config: &Path, url: &str) -> assert_cmd::assert::Assert {
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(config)
+        .arg("--no-cache")
+        .arg("notify")
+        .arg(journals.path())
+        .args(["--channel-config", "work", "--date", "2025-11-20"])
+        .env("TEST_WORK_HOOK", url)
+        .assert()
+}
+
+#[test]
+fn test_slack_digest_payload() {
+    let journals = TempDir::new().unwrap();
+    write_journals(journals.path(), 2);
+    let config = write_config(journals.path(), "slack");
+    let hook = MockWebhook::start(0);
+
+    notify(&journals, &config, &hook.url)
+        .success()
+        .stderr(predicate::str::contains("Posted 1 message(s) to 'work'"));
+
+    let bodies = hook.bodies();
+    assert_eq!(bodies.len(), 1);
+    let message = &bodies[0];
+    assert_eq!(message["text"], "Journal digest for 2025-11-20: 3 entries, 2 tasks completed");
+
+    let blocks = message["blocks"].as_array().unwrap();
+    assert_eq!(blocks[0]["type"], "header");
+    assert_eq!(blocks[0]["text"]["text"], "Journal digest for 2025-11-20");
+    assert_eq!(blocks[1]["text"]["type"], "mrkdwn");
+    assert_eq!(blocks[1]["text"]["text"], "*3* entries · *2* tasks completed");
+    let tasks = blocks[2]["text"]["text"].as_str().unwrap();
+    assert!(tasks.starts_with("*Completed*\n• "), "{}", tasks);
+    assert!(tasks.contains("number 000 (repo-0)"));
+    assert!(!tasks.contains("Old work"));
+}
+
+#[test]
+fn test_long_slack_digest_is_split() {
+    let journals = TempDir::new().unwrap();
+    write_journals(journals.path(), 60);
+    let config = write_config(journals.path(), "slack");
+    let hook = MockWebhook::start(0);
+
+    notify(&journals, &config, &hook.url)
+        .success()
+        .stderr(predicate::str::contains("Posted 2 message(s)"));
+
+    let bodies = hook.bodies();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[1]["text"], "Journal digest for 2025-11-20 (2/2)");
+    assert_eq!(bodies[1]["blocks"][0]["type"], "context");
+
+    let mut listed = 0;
+    for body in &bodies {
+        for block in body["blocks"].as_array().unwrap() {
+            if block["type"] == "section" {
+                let text = block["text"]["text"].as_str().unwrap();
+                assert!(text.chars().count() <= 3000, "{} chars", text.chars().count());
+                listed += text.lines().filter(|line| line.starts_with("• ")).count();
+            }
+        }
+    }
+    assert_eq!(listed, 60);
+}
+
+#[test]
+fn test_generic_webhook_payload() {
+    let journals = TempDir::new().unwrap();
+    write_journals(journals.path(), 1);
+    let config = write_config(journals.path(), "webhook");
+    let hook = MockWebhook::start(0);
+
+    notify(&journals, &config, &hook.url).success();
+
+    let bodies = hook.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0]["date"], "2025-11-20");
+    assert_eq!(bodies[0]["entries"], 2);
+    assert_eq!(
+        bodies[0]["completed_tasks"],
+        json!(["Finish the rather long piece of work number 000 (repo-0)"])
+    );
+    assert!(bodies[0]["signals"].is_array());
+}
+
+#[test]
+fn test_missing_secret_is_reported() {
+    let journals = TempDir::new().unwrap();
+    write_journals(journals.path(), 1);
+    let config = write_config(journals.path(), "slack");
+
+    notify(&journals, &config, "")
+        .failure()
+        .stderr(predicate::str::contains("no webhook URL for notify channel 'work': set $TEST_WORK_HOOK"));
+}
+
+#[test]
+fn test_unknown_channel_is_reported() {
+    let journals = TempDir::new().unwrap();
+    write_journals(journals.path(), 1);
+    let config = write_config(journals.path(), "slack");
+
+    cargo_bin_cmd!("jrnrvw")
+        .arg("--config")
+        .arg(&config)
+        .args(["notify", "--channel-config", "home"])
+        .arg(journals.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no notify channel 'home' (configured: work)"));
+}
+
+#[test]
+fn test_transient_failures_are_retried() {
+    let hook = MockWebhook::start(2);
+    let notifier = Notifier {
+        retry_delay: Duration::from_millis(1),
+        ..Notifier::new(hook.url.clone())
+    };
+
+    notifier.send(&[json!({ "n": 1 }), json!({ "n": 2 })]).unwrap();
+    assert_eq!(hook.bodies(), vec![json!({ "n": 1 }), json!({ "n": 2 })]);
+
+    let hook = MockWebhook::start(5);
+    let notifier = Notifier {
+        retry_delay: Duration::from_millis(1),
+        ..Notifier::new(hook.url.clone())
+    };
+    let err = notifier.send(&[json!({ "n": 1 })]).unwrap_err().to_string();
+    assert!(err.contains("HTTP 503 (after 3 attempts)"), "{}", err);
+    assert!(!err.contains(&hook.url));
+}
@@ -0,0 +1,137 @@
+//! Integration tests for `jrnrvw search`, pinning its ranking on a fixture
+//! corpus
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+const FIXTURES_DIR: &str = "tests/fixtures/search_journals";
+
+/// Tasks and match kinds of `jrnrvw search` results, in ranked order
+fn ranked(args: &[&str]) -> Vec<(String, String)> {
+    let output = cargo_bin_cmd!("jrnrvw")
+        .args(["--no-cache", "--format", "json", "search"])
+        .args(args)
+        .arg(FIXTURES_DIR)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let hits: Value = serde_json::from_slice(&output.stdout).unwrap();
+    hits.as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| (hit["title"].as_str().unwrap().to_string(), hit["kind"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+fn tasks(ranked: &[(String, String)]) -> Vec<&str> {
+    ranked.iter().map(|(task, _)| task.as_str()).collect()
+}
+
+#[test]
+fn test_phrase_then_closest_words() {
+    let results = ranked(&["connection pool exhaustion"]);
+    assert_eq!(
+        results,
+        [
+            ("Investigate worker outage".to_string(), "phrase".to_string()),
+            ("Tune the database pool".to_string(), "words".to_string()),
+            ("Migrate the job queue".to_string(), "words".to_string()),
+        ]
+    );
+
+    // Case and punctuation don't matter
+    assert_eq!(ranked(&["Connection-Pool EXHAUSTION"]), results);
+}
+
+#[test]
+fn test_fuzzy_matches_rank_last() {
+    let results = ranked(&["connection pool exhaustion", "--fuzzy"]);
+    assert_eq!(
+        tasks(&results),
+        ["Investigate worker outage", "Tune the database pool", "Migrate the job queue", "Load test the worker"]
+    );
+    assert_eq!(results[3].1, "fuzzy");
+
+    // A misspelt query finds the exact spellings
+    let misspelt = ranked(&["conection exhuastion", "--fuzzy"]);
+    assert_eq!(misspelt[0], ("Load test the worker".to_string(), "words".to_string()));
+    assert_eq!(misspelt.len(), 4);
+    assert_eq!(tasks(&ranked(&["conection exhuastion"])), ["Load test the worker"]);
+}
+
+#[test]
+fn test_filters() {
+    let query = "connection pool exhaustion";
+    assert_eq!(tasks(&ranked(&[query, "--repo", "worker"])), ["Migrate the job queue"]);
+    assert_eq!(
+        tasks(&ranked(&[query, "--since", "2025-09-05"])),
+        ["Tune the database pool", "Migrate the job queue"]
+    );
+    assert_eq!(
+        tasks(&ranked(&[query, "--tag", "Database"])),
+        ["Investigate worker outage", "Tune the database pool"]
+    );
+    assert_eq!(tasks(&ranked(&["pool", "--limit", "2"])).len(), 2);
+    assert!(ranked(&[query, "--tag", "observability"]).is_empty());
+}
+
+#[test]
+fn test_text_results() {
+    cargo_bin_cmd!("jrnrvw")
+        .args(["--no-cache", "--no-color", "search", "connection pool exhaustion", "--context", "1"])
+        .arg(FIXTURES_DIR)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("3 matches for \"connection pool exhaustion\"\n"))
+        .stdout(predicate::str::contains(
+            "2025.09.02 - JRN - worker outage.md:14  2025-09-02  Investigate worker outage (phrase)\n  \
+             13- - Workers stalled at 09:00 and requests timed out\n  \
+             14: - Root cause: connection pool exhaustion under the retry storm\n  \
+             15- - Raised the pool size to 50 and capped retries\n",
+        ));
+
+    cargo_bin_cmd!("jrnrvw")
+        .args(["--no-cache", "search", "kubernetes"])
+        .arg(FIXTURES_DIR)
+        .assert()
+        .success()
+        .stdout("No matches for \"kubernetes\"\n");
+
+    cargo_bin_cmd!("jrnrvw")
+        .args(["--no-cache", "search", "--", "--"])
+        .arg(FIXTURES_DIR)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no words"));
+}
+
+#[test]
+fn test_search_reuses_the_index_cache() {
+    let temp_dir = TempDir::new().unwrap();
+    let trace = temp_dir.path().join("trace.json");
+    let search = |query: &str| {
+        cargo_bin_cmd!("jrnrvw")
+            .env("JRNRVW_CACHE_DIR", temp_dir.path().join("cache"))
+            .args(["--profile", "--profile-trace"])
+            .arg(&trace)
+            .args(["search", query])
+            .arg(FIXTURES_DIR)
+            .assert()
+            .success()
+    };
+    let parses = || {
+        let trace: Value = serde_json::from_str(&fs::read_to_string(&trace).unwrap()).unwrap();
+        trace["traceEvents"].as_array().unwrap().iter().filter(|e| e["name"] == "parse").count()
+    };
+
+    search("retry storm").stdout(predicate::str::contains("2 matches"));
+    assert_eq!(parses(), 5);
+
+    // The second search takes every journal from the index
+    search("queue").stdout(predicate::str::contains("1 match "));
+    assert_eq!(parses(), 0);
+}
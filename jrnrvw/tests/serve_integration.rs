@@ -0,0 +1,212 @@
+//! Integration tests for serve mode
+
+use jrnrvw::analyzer::Analysis;
+use jrnrvw::serve::{ServeConfig, Server};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use tempfile::TempDir;
+
+/// Write a journal for `repo` into its own directory under `root`
+fn write_journal(root: &Path, repo: &str, date: &str, task: &str) {
+    let dir = root.join(repo);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(format!("{} - JRN - {}.md", date, task)),
+        format!(
+            "# {}\n\n## Task\n{}\n\n## Activities\n- Worked on {}\n",
+            task, task, task
+        ),
+    )
+    .unwrap();
+}
+
+/// Start a server on an ephemeral port in a background thread
+fn start_server(root: &Path, token: Option<&str>) -> SocketAddr {
+    let analysis = Analysis::new(root.to_path_buf());
+    let config = ServeConfig {
+        bind: SocketAddr::from(([127, 0, 0, 1], 0)),
+        token: token.map(str::to_string),
+        ..ServeConfig::default()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async move {
+            let server = Server::bind(analysis, config).await.unwrap();
+            tx.send(server.local_addr().unwrap()).unwrap();
+            server.run().await.unwrap();
+        });
+    });
+
+    rx.recv().unwrap()
+}
+
+struct HttpResponse {
+    status: u16,
+    head: String,
+    body: String,
+}
+
+/// Send a bare HTTP/1.1 request and read the whole response
+fn request(addr: SocketAddr, method: &str, path: &str, token: Option<&str>) -> HttpResponse {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let auth = token
+        .map(|t| format!("Authorization: Bearer {}\r\n", t))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+        method, path, auth
+    )
+    .unwrap();
+
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+
+    HttpResponse {
+        status,
+        head: head.to_string(),
+        body: body.to_string(),
+    }
+}
+
+fn repository_names(json: &str) -> Vec<String> {
+    let report: serde_json::Value = serde_json::from_str(json).unwrap();
+    report["repositories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_serve_endpoints() {
+    let temp_dir = TempDir::new().unwrap();
+    write_journal(temp_dir.path(), "alpha", "2025.11.10", "parser rewrite");
+    write_journal(temp_dir.path(), "beta project", "2025.11.11", "release prep");
+    let addr = start_server(temp_dir.path(), None);
+
+    let index = request(addr, "GET", "/", None);
+    assert_eq!(index.status, 200);
+    assert!(index.head.contains("text/html"));
+    assert!(index.body.contains("Journal Review Report"));
+    assert!(index.body.contains(r#"<a href="/repo/alpha">alpha</a>"#));
+    assert!(index.body.contains(r#"<a href="/repo/beta%20project">beta project</a>"#));
+
+    let report = request(addr, "GET", "/api/report", None);
+    assert_eq!(report.status, 200);
+    assert!(report.head.contains("application/json"));
+    let mut names = repository_names(&report.body);
+    names.sort();
+    assert_eq!(names, vec!["alpha", "beta project"]);
+
+    let repo = request(addr, "GET", "/repo/beta%20project", None);
+    assert_eq!(repo.status, 200);
+    assert!(repo.body.contains("<h1>beta project</h1>"));
+    assert!(repo.body.contains("release prep"));
+
+    assert_eq!(request(addr, "GET", "/repo/gamma", None).status, 404);
+    assert_eq!(request(addr, "GET", "/nowhere", None).status, 404);
+}
+
+#[test]
+fn test_refresh_picks_up_new_journals() {
+    let temp_dir = TempDir::new().unwrap();
+    write_journal(temp_dir.path(), "alpha", "2025.11.10", "parser rewrite");
+    let addr = start_server(temp_dir.path(), None);
+
+    assert_eq!(repository_names(&request(addr, "GET", "/api/report", None).body), vec!["alpha"]);
+
+    // New journals only show up after a refresh
+    write_journal(temp_dir.path(), "beta", "2025.11.12", "docs");
+    assert_eq!(repository_names(&request(addr, "GET", "/api/report", None).body), vec!["alpha"]);
+
+    let refresh = request(addr, "GET", "/api/refresh", None);
+    assert_eq!(refresh.status, 405);
+    assert!(refresh.head.contains("allow: POST"));
+
+    let refresh = request(addr, "POST", "/api/refresh", None);
+    assert_eq!(refresh.status, 200);
+    assert!(refresh.body.contains(r#""refreshed":true"#));
+
+    let mut names = repository_names(&request(addr, "GET", "/api/report", None).body);
+    names.sort();
+    assert_eq!(names, vec!["alpha", "beta"]);
+    assert_eq!(request(addr, "GET", "/repo/beta", None).status, 200);
+}
+
+#[test]
+fn test_failed_refresh_keeps_previous_report() {
+    let temp_dir = TempDir::new().unwrap();
+    write_journal(temp_dir.path(), "alpha", "2025.11.10", "parser rewrite");
+    let addr = start_server(temp_dir.path(), None);
+
+    // With no journals left the analysis fails
+    fs::remove_dir_all(temp_dir.path().join("alpha")).unwrap();
+
+    let refresh = request(addr, "POST", "/api/refresh", None);
+    assert_eq!(refresh.status, 500);
+    assert!(refresh.body.contains(r#""refreshed":false"#));
+
+    assert_eq!(repository_names(&request(addr, "GET", "/api/report", None).body), vec!["alpha"]);
+    assert_eq!(request(addr, "GET", "/repo/alpha", None).status, 200);
+}
+
+#[test]
+fn test_requests_during_refresh_see_a_complete_report() {
+    let temp_dir = TempDir::new().unwrap();
+    for day in 10..20 {
+        write_journal(temp_dir.path(), "alpha", &format!("2025.11.{}", day), "parser rewrite");
+    }
+    let addr = start_server(temp_dir.path(), None);
+    write_journal(temp_dir.path(), "beta", "2025.11.20", "docs");
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(move || {
+                (0..10)
+                    .map(|_| repository_names(&request(addr, "GET", "/api/report", None).body).len())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let refreshers: Vec<_> = (0..2)
+        .map(|_| thread::spawn(move || request(addr, "POST", "/api/refresh", None).status))
+        .collect();
+
+    for refresher in refreshers {
+        assert_eq!(refresher.join().unwrap(), 200);
+    }
+    for reader in readers {
+        // Every response is either the old report or the new one, never a mix
+        for count in reader.join().unwrap() {
+            assert!(count == 1 || count == 2, "unexpected repository count {}", count);
+        }
+    }
+    assert_eq!(repository_names(&request(addr, "GET", "/api/report", None).body).len(), 2);
+}
+
+#[test]
+fn test_bearer_token_required() {
+    let temp_dir = TempDir::new().unwrap();
+    write_journal(temp_dir.path(), "alpha", "2025.11.10", "parser rewrite");
+    let addr = start_server(temp_dir.path(), Some("s3cret"));
+
+    let denied = request(addr, "GET", "/", None);
+    assert_eq!(denied.status, 401);
+    assert!(denied.head.contains("www-authenticate: Bearer"));
+    assert_eq!(request(addr, "GET", "/api/report", Some("wrong")).status, 401);
+    assert_eq!(request(addr, "POST", "/api/refresh", None).status, 401);
+
+    assert_eq!(request(addr, "GET", "/", Some("s3cret")).status, 200);
+    assert_eq!(request(addr, "GET", "/api/report", Some("s3cret")).status, 200);
+    assert_eq!(request(addr, "POST", "/api/refresh", Some("s3cret")).status, 200);
+}
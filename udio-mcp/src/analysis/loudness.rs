@@ -0,0 +1,236 @@
+// Loudness and peak measurement
+// Integrated loudness follows ITU-R BS.1770: K-weighting, 400 ms blocks and
+// two-stage gating. Every channel is weighted equally, so surround mixes
+// read slightly differently from a full meter.
+
+/// Blocks quieter than this never count towards integrated loudness, in LUFS
+const ABSOLUTE_GATE: f64 = -70.0;
+
+/// Blocks this far below the ungated average are dropped, in LU
+const RELATIVE_GATE: f64 = -10.0;
+
+/// Gating blocks are four 100 ms steps long, overlapping by three
+const STEPS_PER_BLOCK: usize = 4;
+
+/// Second-order IIR section in direct form I
+#[derive(Debug, Clone)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// The two K-weighting stages for one channel
+///
+/// Coefficients are derived for the actual sample rate rather than taken
+/// from the 48 kHz table in the standard.
+#[derive(Debug, Clone)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: u32) -> Self {
+        let rate = f64::from(sample_rate);
+
+        // High shelf modelling the acoustic effect of the head
+        let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+        let k = (std::f64::consts::PI * f0 / rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad::new(
+            [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        // High pass removing content below about 38 Hz
+        let (f0, q) = (38.13547087602444, 0.5003270373238773);
+        let k = (std::f64::consts::PI * f0 / rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        Self { shelf, high_pass }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(input))
+    }
+}
+
+/// Streaming loudness meter fed with interleaved samples
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    channels: usize,
+    filters: Vec<KWeighting>,
+    step_len: usize,
+    /// Position within the current 100 ms step, in frames
+    step_pos: usize,
+    /// Sum of squared weighted samples in the current step, summed over channels
+    step_energy: f64,
+    /// Mean square of each complete step
+    steps: Vec<f64>,
+    peak: f32,
+}
+
+impl LoudnessMeter {
+    /// Create a meter for audio with the given layout
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let channels = channels.max(1);
+        Self {
+            channels,
+            filters: (0..channels)
+                .map(|_| KWeighting::new(sample_rate))
+                .collect(),
+            step_len: (sample_rate as usize / 10).max(1),
+            step_pos: 0,
+            step_energy: 0.0,
+            steps: Vec::new(),
+            peak: 0.0,
+        }
+    }
+
+    /// Feed interleaved samples; a trailing partial frame is ignored
+    pub fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            for (sample, filter) in frame.iter().zip(&mut self.filters) {
+                self.peak = self.peak.max(sample.abs());
+                let weighted = filter.process(f64::from(*sample));
+                self.step_energy += weighted * weighted;
+            }
+
+            self.step_pos += 1;
+            if self.step_pos == self.step_len {
+                self.steps.push(self.step_energy / self.step_len as f64);
+                self.step_pos = 0;
+                self.step_energy = 0.0;
+            }
+        }
+    }
+
+    /// Integrated loudness in LUFS, or `None` if the audio is shorter than
+    /// one block or silent throughout
+    pub fn integrated(&self) -> Option<f64> {
+        let blocks: Vec<f64> = self
+            .steps
+            .windows(STEPS_PER_BLOCK)
+            .map(|steps| steps.iter().sum::<f64>() / STEPS_PER_BLOCK as f64)
+            .filter(|&power| loudness(power) > ABSOLUTE_GATE)
+            .collect();
+        if blocks.is_empty() {
+            return None;
+        }
+
+        let threshold = loudness(mean(&blocks)) + RELATIVE_GATE;
+        let gated: Vec<f64> = blocks
+            .into_iter()
+            .filter(|&power| loudness(power) > threshold)
+            .collect();
+        Some(loudness(mean(&gated)))
+    }
+
+    /// Highest absolute sample value in dBFS, or `None` for digital silence
+    pub fn peak_dbfs(&self) -> Option<f64> {
+        (self.peak > 0.0).then(|| 20.0 * f64::from(self.peak).log10())
+    }
+}
+
+fn loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f64, amplitude: f32, sample_rate: u32, seconds: f64) -> Vec<f32> {
+        let count = (f64::from(sample_rate) * seconds) as usize;
+        (0..count)
+            .map(|i| {
+                let t = i as f64 / f64::from(sample_rate);
+                amplitude * (2.0 * std::f64::consts::PI * frequency * t).sin() as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_full_scale_sine_reads_minus_three() {
+        // BS.1770 calibration: a 0 dBFS 997 Hz sine in one channel is -3.01 LUFS
+        for sample_rate in [44_100, 48_000] {
+            let mut meter = LoudnessMeter::new(sample_rate, 1);
+            meter.push(&sine(997.0, 1.0, sample_rate, 5.0));
+
+            let lufs = meter.integrated().unwrap();
+            assert!((lufs + 3.01).abs() < 0.1, "{} Hz: {}", sample_rate, lufs);
+        }
+    }
+
+    #[test]
+    fn test_quieter_sine_and_stereo() {
+        let mono = sine(997.0, 0.1, 48_000, 5.0);
+        let stereo: Vec<f32> = mono.iter().flat_map(|&s| [s, s]).collect();
+
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.push(&stereo);
+
+        // -20 dB, plus 3 dB for the second channel
+        let lufs = meter.integrated().unwrap();
+        assert!((lufs + 20.0).abs() < 0.1, "{}", lufs);
+        assert!((meter.peak_dbfs().unwrap() + 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_silence_is_gated_out() {
+        let mut meter = LoudnessMeter::new(48_000, 1);
+        meter.push(&vec![0.0; 48_000 * 2]);
+
+        assert_eq!(meter.integrated(), None);
+        assert_eq!(meter.peak_dbfs(), None);
+    }
+
+    #[test]
+    fn test_quiet_passages_do_not_drag_loudness_down() {
+        let mut loud = LoudnessMeter::new(48_000, 1);
+        loud.push(&sine(997.0, 0.5, 48_000, 5.0));
+
+        let mut mixed = LoudnessMeter::new(48_000, 1);
+        mixed.push(&sine(997.0, 0.5, 48_000, 5.0));
+        mixed.push(&sine(997.0, 0.001, 48_000, 5.0));
+
+        let difference = loud.integrated().unwrap() - mixed.integrated().unwrap();
+        assert!(difference.abs() < 0.2, "{}", difference);
+    }
+}
@@ -0,0 +1,321 @@
+// Audio analysis
+// Measures downloaded songs locally: duration, loudness, peak and tempo
+
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use thiserror::Error;
+
+/// Integrated loudness and peak level
+pub mod loudness;
+/// Tempo estimation from onsets
+pub mod tempo;
+
+pub use loudness::LoudnessMeter;
+pub use tempo::TempoEstimator;
+
+/// Formats the decoder is built with
+pub const SUPPORTED_FORMATS: &[&str] = &["MP3", "WAV", "FLAC", "Ogg Vorbis", "Matroska/WebM"];
+
+/// Measurements of one audio file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioAnalysis {
+    /// File that was analyzed
+    pub path: PathBuf,
+    /// SHA-256 of the file contents, hex encoded
+    pub sha256: String,
+    /// Short codec name, e.g. `"mp3"`
+    pub codec: String,
+    /// Samples per second
+    pub sample_rate: u32,
+    /// Number of channels
+    pub channels: usize,
+    /// Length of the decoded audio
+    pub duration_seconds: f64,
+    /// Integrated loudness in LUFS; `None` if the file is too short or silent
+    pub loudness_lufs: Option<f64>,
+    /// Highest sample level in dBFS; `None` for digital silence
+    pub peak_dbfs: Option<f64>,
+    /// Estimated tempo; `None` when no beat was found
+    pub bpm: Option<f64>,
+}
+
+impl AudioAnalysis {
+    /// Format duration as MM:SS
+    pub fn format_duration(&self) -> String {
+        let seconds = self.duration_seconds.round() as u64;
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Why a file could not be analyzed
+#[derive(Error, Debug)]
+pub enum AnalysisError {
+    /// The file could not be read
+    #[error("Cannot read {}: {source}", .path.display())]
+    Read {
+        /// File that was asked for
+        path: PathBuf,
+        /// Underlying I/O error
+        source: std::io::Error,
+    },
+
+    /// The container or codec is not one the decoder was built with
+    #[error("Unsupported audio format ({reason}); supported formats: {}", SUPPORTED_FORMATS.join(", "))]
+    UnsupportedFormat {
+        /// What the decoder could not handle
+        reason: String,
+    },
+
+    /// The file has no audio track
+    #[error("No audio track found")]
+    NoAudio,
+
+    /// The audio stream is corrupt
+    #[error("Decoding failed: {0}")]
+    Decode(String),
+}
+
+/// Analyzes files, remembering results by content hash
+///
+/// The same song saved under two names is only decoded once.
+#[derive(Debug, Default)]
+pub struct AudioAnalyzer {
+    cache: Mutex<HashMap<String, AudioAnalysis>>,
+}
+
+impl AudioAnalyzer {
+    /// Create an analyzer with an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Analyze a file, decoding it only if its contents are new
+    ///
+    /// This decodes the whole file; call it from a blocking context.
+    pub fn analyze(&self, path: impl AsRef<Path>) -> Result<AudioAnalysis, AnalysisError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|source| AnalysisError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let sha256 = hex_digest(&bytes);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&sha256) {
+            tracing::debug!("Analysis cache hit for {}", path.display());
+            return Ok(AudioAnalysis {
+                path: path.to_path_buf(),
+                ..cached.clone()
+            });
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str());
+        let analysis = AudioAnalysis {
+            path: path.to_path_buf(),
+            sha256: sha256.clone(),
+            ..decode_and_measure(bytes, extension)?
+        };
+
+        self.cache.lock().unwrap().insert(sha256, analysis.clone());
+        Ok(analysis)
+    }
+
+    /// Number of distinct files analyzed so far
+    pub fn cached_count(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+}
+
+/// Decode a file held in memory, measuring as packets arrive
+///
+/// `path` and `sha256` are left empty for the caller to fill in.
+fn decode_and_measure(
+    bytes: Vec<u8>,
+    extension: Option<&str>,
+) -> Result<AudioAnalysis, AnalysisError> {
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
+    }
+
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(classify)?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or(AnalysisError::NoAudio)?;
+    let track_id = track.id;
+
+    let codecs = symphonia::default::get_codecs();
+    let codec = codecs
+        .get_codec(track.codec_params.codec)
+        .map(|descriptor| descriptor.short_name.to_string())
+        .ok_or_else(|| AnalysisError::UnsupportedFormat {
+            reason: format!("codec {}", track.codec_params.codec),
+        })?;
+    let mut decoder = codecs
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(classify)?;
+
+    let mut meters: Option<(LoudnessMeter, TempoEstimator)> = None;
+    let mut sample_rate = 0;
+    let mut channels = 0;
+    let mut frames: u64 = 0;
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(classify(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A damaged packet costs a few milliseconds of audio, not the file
+            Err(SymphoniaError::DecodeError(e)) => {
+                tracing::debug!("Skipping undecodable packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(classify(e)),
+        };
+
+        let spec = *decoded.spec();
+        if decoded.frames() == 0 {
+            continue;
+        }
+        let samples =
+            buffer.get_or_insert_with(|| SampleBuffer::new(decoded.capacity() as u64, spec));
+        if samples.capacity() < decoded.capacity() * spec.channels.count() {
+            *samples = SampleBuffer::new(decoded.capacity() as u64, spec);
+        }
+        samples.copy_interleaved_ref(decoded);
+
+        sample_rate = spec.rate;
+        channels = spec.channels.count();
+        let (loudness, tempo) = meters.get_or_insert_with(|| {
+            (
+                LoudnessMeter::new(sample_rate, channels),
+                TempoEstimator::new(sample_rate),
+            )
+        });
+
+        loudness.push(samples.samples());
+        for frame in samples.samples().chunks_exact(channels) {
+            tempo.push(frame.iter().sum::<f32>() / channels as f32);
+        }
+        frames += (samples.samples().len() / channels) as u64;
+    }
+
+    let (loudness, tempo) = meters.ok_or(AnalysisError::NoAudio)?;
+    Ok(AudioAnalysis {
+        path: PathBuf::new(),
+        sha256: String::new(),
+        codec,
+        sample_rate,
+        channels,
+        duration_seconds: frames as f64 / f64::from(sample_rate),
+        loudness_lufs: loudness.integrated(),
+        peak_dbfs: loudness.peak_dbfs(),
+        bpm: tempo.bpm(),
+    })
+}
+
+/// Sort decoder errors into the format being unsupported or the data being bad
+fn classify(error: SymphoniaError) -> AnalysisError {
+    match error {
+        SymphoniaError::Unsupported(what) => AnalysisError::UnsupportedFormat {
+            reason: what.to_string(),
+        },
+        other => AnalysisError::Decode(other.to_string()),
+    }
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    digest::digest(&digest::SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_format_lists_supported_formats() {
+        let error =
+            decode_and_measure(b"definitely not audio".repeat(100), Some("xyz")).unwrap_err();
+
+        assert!(matches!(error, AnalysisError::UnsupportedFormat { .. }));
+        let message = error.to_string();
+        for format in SUPPORTED_FORMATS {
+            assert!(message.contains(format), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = AudioAnalyzer::new()
+            .analyze("/nonexistent/song.mp3")
+            .unwrap_err();
+
+        match error {
+            AnalysisError::Read { path, source } => {
+                assert_eq!(path, PathBuf::from("/nonexistent/song.mp3"));
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            other => panic!("Expected Read, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hex_digest() {
+        assert_eq!(
+            hex_digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        let analysis = AudioAnalysis {
+            path: PathBuf::from("song.mp3"),
+            sha256: String::new(),
+            codec: "mp3".to_string(),
+            sample_rate: 44_100,
+            channels: 2,
+            duration_seconds: 204.6,
+            loudness_lufs: None,
+            peak_dbfs: None,
+            bpm: None,
+        };
+        assert_eq!(analysis.format_duration(), "03:25");
+    }
+}
@@ -0,0 +1,124 @@
+// Tempo estimation
+// Autocorrelates an onset envelope and reads the beat period off the
+// strongest repetition. Good for a rough figure, not for beat tracking.
+
+/// Onset envelope resolution, in frames per second
+const FRAME_RATE: u32 = 400;
+
+/// Slowest tempo reported
+pub const MIN_BPM: f64 = 60.0;
+
+/// Fastest tempo reported
+pub const MAX_BPM: f64 = 200.0;
+
+/// A repetition at least this strong relative to the best one is taken as
+/// the beat, so the beat wins over its multiples
+const PEAK_RATIO: f64 = 0.8;
+
+/// Streaming tempo estimator fed with mono samples
+#[derive(Debug, Clone)]
+pub struct TempoEstimator {
+    hop: usize,
+    frame_pos: usize,
+    frame_energy: f64,
+    /// Log-compressed energy of each complete frame
+    frames: Vec<f64>,
+}
+
+impl TempoEstimator {
+    /// Create an estimator for audio at the given sample rate
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            hop: (sample_rate / FRAME_RATE).max(1) as usize,
+            frame_pos: 0,
+            frame_energy: 0.0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Feed one mono sample
+    pub fn push(&mut self, sample: f32) {
+        let sample = f64::from(sample);
+        self.frame_energy += sample * sample;
+        self.frame_pos += 1;
+
+        if self.frame_pos == self.hop {
+            let energy = self.frame_energy / self.hop as f64;
+            self.frames.push((1.0 + 1000.0 * energy).ln());
+            self.frame_pos = 0;
+            self.frame_energy = 0.0;
+        }
+    }
+
+    /// Beats per minute, or `None` when nothing repeats within
+    /// [`MIN_BPM`]..=[`MAX_BPM`]
+    pub fn bpm(&self) -> Option<f64> {
+        // Rises in energy mark onsets
+        let mut onsets: Vec<f64> = self
+            .frames
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).max(0.0))
+            .collect();
+        let average = onsets.iter().sum::<f64>() / onsets.len().max(1) as f64;
+        onsets.iter_mut().for_each(|onset| *onset -= average);
+
+        let frame_rate = f64::from(FRAME_RATE);
+        let min_lag = (frame_rate * 60.0 / MAX_BPM).floor() as usize;
+        let max_lag = (frame_rate * 60.0 / MIN_BPM).ceil() as usize;
+        if onsets.len() <= max_lag + 1 {
+            return None;
+        }
+
+        // Computed one lag past each end so every lag in range has neighbours
+        let correlation: Vec<f64> = (min_lag - 1..=max_lag + 1)
+            .map(|lag| onsets.iter().zip(&onsets[lag..]).map(|(a, b)| a * b).sum())
+            .collect();
+
+        let peaks: Vec<usize> = (1..correlation.len() - 1)
+            .filter(|&i| {
+                correlation[i] > 0.0
+                    && correlation[i] >= correlation[i - 1]
+                    && correlation[i] >= correlation[i + 1]
+            })
+            .collect();
+        let strongest = peaks.iter().map(|&i| correlation[i]).fold(0.0, f64::max);
+        let beat = *peaks
+            .iter()
+            .find(|&&i| correlation[i] >= PEAK_RATIO * strongest)?;
+
+        // Parabolic interpolation between neighbouring lags
+        let (before, at, after) = (
+            correlation[beat - 1],
+            correlation[beat],
+            correlation[beat + 1],
+        );
+        let curvature = before - 2.0 * at + after;
+        let offset = if curvature < 0.0 {
+            0.5 * (before - after) / curvature
+        } else {
+            0.0
+        };
+
+        let lag = (min_lag - 1 + beat) as f64 + offset;
+        Some(frame_rate * 60.0 / lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_too_short_for_an_estimate() {
+        let mut estimator = TempoEstimator::new(44_100);
+        (0..44_100).for_each(|_| estimator.push(0.5));
+        assert_eq!(estimator.bpm(), None);
+    }
+
+    #[test]
+    fn test_steady_tone_has_no_tempo() {
+        let mut estimator = TempoEstimator::new(8_000);
+        (0..8_000 * 10).for_each(|i| estimator.push(if i % 2 == 0 { 0.5 } else { -0.5 }));
+        assert_eq!(estimator.bpm(), None);
+    }
+}
@@ -1,16 +1,18 @@
 // Authentication manager
 // High-level interface for authentication operations
 
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use chromiumoxide::Page;
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
 use super::{
     credentials::{Credentials, CredentialsStore},
     login::LoginAutomation,
-    session::{Session, SessionStore},
+    session::{Session, SessionError, SessionStore},
 };
+use crate::events::{Event, EventBus};
 
 /// Manages authentication lifecycle
 pub struct AuthManager {
@@ -25,6 +27,12 @@ pub struct AuthManager {
 
     /// Currently authenticated user email
     current_user: Arc<RwLock<Option<String>>>,
+
+    /// Where expired logins are announced
+    events: Option<EventBus>,
+
+    /// Users whose expiry has been announced since they last logged in
+    expiry_announced: Arc<Mutex<HashSet<String>>>,
 }
 
 impl AuthManager {
@@ -35,6 +43,8 @@ impl AuthManager {
             session_store: Arc::new(SessionStore::default()),
             login_automation: Arc::new(LoginAutomation::default()),
             current_user: Arc::new(RwLock::new(None)),
+            events: None,
+            expiry_announced: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -49,9 +59,17 @@ impl AuthManager {
             session_store: Arc::new(session_store),
             login_automation: Arc::new(login_automation),
             current_user: Arc::new(RwLock::new(None)),
+            events: None,
+            expiry_announced: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
+    /// Announce expired sessions on an event bus
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
     /// Store user credentials securely
     pub fn store_credentials(&self, credentials: &Credentials) -> Result<()> {
         self.credentials_store
@@ -94,6 +112,12 @@ impl AuthManager {
             .store(session.clone())
             .context("Failed to store session")?;
 
+        // A later expiry is news again
+        self.expiry_announced
+            .lock()
+            .unwrap()
+            .remove(&credentials.email);
+
         // Set current user
         let mut current_user = self.current_user.write().await;
         *current_user = Some(credentials.email.clone());
@@ -116,10 +140,32 @@ impl AuthManager {
     }
 
     /// Get current session for user
+    ///
+    /// The first time a user's session is found expired,
+    /// [`Event::AuthExpired`] is published.
     pub fn get_session(&self, email: &str) -> Result<Session> {
-        self.session_store
-            .retrieve(email)
-            .context("No valid session found")
+        self.session_store.retrieve(email).map_err(|e| {
+            if let Some(SessionError::Expired(_)) = e.downcast_ref::<SessionError>() {
+                self.announce_expiry(email);
+            }
+            e.context("No valid session found")
+        })
+    }
+
+    fn announce_expiry(&self, email: &str) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        if self
+            .expiry_announced
+            .lock()
+            .unwrap()
+            .insert(email.to_string())
+        {
+            events.publish(Event::AuthExpired {
+                email: email.to_string(),
+            });
+        }
     }
 
     /// Check if user has valid session
@@ -172,7 +218,7 @@ impl AuthManager {
             return self.login_with_stored_credentials(page, email).await;
         }
 
-        bail!("No stored credentials and no valid session for: {}", email)
+        Err(SessionError::NoCredentials(email.to_string()).into())
     }
 
     /// Re-authenticate user (useful when session expires)
@@ -243,6 +289,42 @@ mod tests {
         // Verify it can be created with custom components
     }
 
+    #[test]
+    fn test_expired_session_is_announced_once() {
+        use super::super::session::Cookie;
+
+        let session_store = SessionStore::default();
+        let mut session = Session::new(
+            "user@example.com",
+            vec![Cookie::new("session_id", "abc123")],
+            3600,
+        );
+        session.expires_at = 0;
+        session_store.store(session).unwrap();
+
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+        let manager = AuthManager::with_components(
+            CredentialsStore::default(),
+            session_store,
+            LoginAutomation::default(),
+        )
+        .with_event_bus(bus);
+
+        assert!(manager.get_session("user@example.com").is_err());
+        assert!(manager.get_session("user@example.com").is_err());
+        // Missing sessions aren't expiries
+        assert!(manager.get_session("other@example.com").is_err());
+
+        assert_eq!(
+            events.try_recv().unwrap().event,
+            Event::AuthExpired {
+                email: "user@example.com".to_string()
+            }
+        );
+        assert!(events.try_recv().is_err());
+    }
+
     #[test]
     fn test_arc_wrapped_components() {
         let manager = AuthManager::new();
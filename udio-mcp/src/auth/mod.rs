@@ -16,4 +16,4 @@ pub use credentials::{Credentials, CredentialsStore};
 pub use keychain::KeychainManager;
 pub use login::LoginAutomation;
 pub use manager::AuthManager;
-pub use session::{Session, SessionStore};
+pub use session::{Session, SessionError, SessionStore};
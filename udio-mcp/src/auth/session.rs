@@ -1,9 +1,27 @@
 // Session management for authenticated users
 // Handles session lifecycle, validation, and persistence
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Why no usable session is available; callers can downcast to this to
+/// tell an expired login apart from other failures
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    /// A session existed but has expired
+    #[error("Session expired for: {0}")]
+    Expired(String),
+
+    /// No session was ever stored
+    #[error("No session found for: {0}")]
+    Missing(String),
+
+    /// No session and no stored credentials to log in with
+    #[error("No stored credentials and no valid session for: {0}")]
+    NoCredentials(String),
+}
 
 /// Authentication session with cookies and expiration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,10 +182,10 @@ impl SessionStore {
                 if session.is_valid() {
                     Ok(session.clone())
                 } else {
-                    bail!("Session expired for: {}", email)
+                    Err(SessionError::Expired(email.to_string()).into())
                 }
             }
-            None => bail!("No session found for: {}", email),
+            None => Err(SessionError::Missing(email.to_string()).into()),
         }
     }
 
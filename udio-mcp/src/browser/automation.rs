@@ -5,6 +5,18 @@ use anyhow::{bail, Context, Result};
 use chromiumoxide::element::Element;
 use chromiumoxide::Page;
 use std::time::Duration;
+use thiserror::Error;
+
+/// A wait on the page gave up; callers can downcast to this to report a
+/// timeout rather than a generic browser failure
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Timeout waiting for {operation} after {after:?}")]
+pub struct WaitTimeout {
+    /// What was being waited on
+    pub operation: &'static str,
+    /// How long the wait lasted
+    pub after: Duration,
+}
 
 /// Find an element using a list of selector fallbacks
 /// Tries each selector in order until one matches
@@ -113,7 +125,11 @@ pub async fn wait_for_element(
 
         // Check timeout
         if start.elapsed() >= timeout {
-            bail!("Timeout waiting for element after {:?}", timeout);
+            return Err(WaitTimeout {
+                operation: "element",
+                after: timeout,
+            }
+            .into());
         }
 
         // Wait before next attempt
@@ -139,7 +155,11 @@ pub async fn wait_for_element_removed(
 
         // Check timeout
         if start.elapsed() >= timeout {
-            bail!("Timeout waiting for element removal after {:?}", timeout);
+            return Err(WaitTimeout {
+                operation: "element removal",
+                after: timeout,
+            }
+            .into());
         }
 
         // Wait before next attempt
@@ -160,7 +180,10 @@ pub async fn wait_for_navigation(_page: &Page, timeout: Duration) -> Result<()>
         Ok::<(), anyhow::Error>(())
     })
     .await
-    .context("Timeout waiting for navigation")??;
+    .map_err(|_| WaitTimeout {
+        operation: "navigation",
+        after: timeout,
+    })??;
 
     tracing::debug!("Navigation completed");
     Ok(())
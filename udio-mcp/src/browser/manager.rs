@@ -5,10 +5,14 @@ use anyhow::{Context, Result};
 use chromiumoxide::{Browser, BrowserConfig as ChromeBrowserConfig};
 use futures::StreamExt;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 use super::config::BrowserConfig;
 
+/// How long to wait for a stuck tab to close before giving up on it
+const PAGE_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Manages browser lifecycle and provides access to browser instances
 pub struct BrowserManager {
     /// The browser instance (None if not launched)
@@ -19,6 +23,12 @@ pub struct BrowserManager {
 
     /// Whether the browser is currently active
     active: Arc<RwLock<bool>>,
+
+    /// Whether the browser can be reused; cleared when a tab is force-closed
+    healthy: Arc<RwLock<bool>>,
+
+    /// Tabs opened through [`BrowserManager::new_page`]
+    open_pages: Arc<RwLock<Vec<chromiumoxide::Page>>>,
 }
 
 impl BrowserManager {
@@ -28,11 +38,19 @@ impl BrowserManager {
             browser: Arc::new(RwLock::new(None)),
             config,
             active: Arc::new(RwLock::new(false)),
+            healthy: Arc::new(RwLock::new(true)),
+            open_pages: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
     /// Launch the browser if not already running
+    ///
+    /// An unhealthy browser is shut down and replaced with a fresh one.
     pub async fn launch(&self) -> Result<()> {
+        if !self.is_healthy().await {
+            self.recycle().await;
+        }
+
         let mut browser_lock = self.browser.write().await;
 
         if browser_lock.is_some() {
@@ -101,6 +119,54 @@ impl BrowserManager {
         *self.active.read().await
     }
 
+    /// Check if the browser can be reused
+    pub async fn is_healthy(&self) -> bool {
+        *self.healthy.read().await
+    }
+
+    /// Mark the browser as unfit for reuse; the next launch replaces it
+    pub async fn mark_unhealthy(&self) {
+        *self.healthy.write().await = false;
+    }
+
+    /// Force-close every tab opened through this manager
+    ///
+    /// Used after a tool call was abandoned mid-way, when its tab may still
+    /// be loading. Returns the number of tabs closed; if any were, the
+    /// browser is marked unhealthy so the next call gets a fresh one.
+    pub async fn close_open_pages(&self) -> usize {
+        let pages: Vec<_> = self.open_pages.write().await.drain(..).collect();
+        let count = pages.len();
+
+        for page in pages {
+            match tokio::time::timeout(PAGE_CLOSE_TIMEOUT, page.close()).await {
+                Ok(Ok(())) => tracing::debug!("Force-closed tab"),
+                Ok(Err(e)) => tracing::warn!("Failed to force-close tab: {}", e),
+                Err(_) => tracing::warn!("Tab did not close within {:?}", PAGE_CLOSE_TIMEOUT),
+            }
+        }
+
+        if count > 0 {
+            tracing::warn!("Force-closed {} tab(s); browser marked unhealthy", count);
+            self.mark_unhealthy().await;
+        }
+
+        count
+    }
+
+    /// Replace an unhealthy browser with a fresh one on next launch
+    async fn recycle(&self) {
+        tracing::warn!("Recycling unhealthy browser");
+
+        self.open_pages.write().await.clear();
+        if let Err(e) = self.shutdown().await {
+            tracing::warn!("Failed to close unhealthy browser: {}", e);
+        }
+
+        *self.active.write().await = false;
+        *self.healthy.write().await = true;
+    }
+
     /// Close the browser gracefully
     pub async fn shutdown(&self) -> Result<()> {
         let mut browser_lock = self.browser.write().await;
@@ -124,8 +190,8 @@ impl BrowserManager {
     /// Create a new page/tab in the browser
     /// This will launch the browser if it's not already running
     pub async fn new_page(&self, url: &str) -> Result<chromiumoxide::Page> {
-        // Ensure browser is launched
-        if !self.is_launched().await {
+        // Ensure browser is launched and fit for use
+        if !self.is_launched().await || !self.is_healthy().await {
             self.launch().await?;
         }
 
@@ -139,6 +205,7 @@ impl BrowserManager {
             .new_page(url)
             .await
             .context("Failed to create new page")?;
+        self.open_pages.write().await.push(page.clone());
 
         tracing::debug!("Page created successfully");
         Ok(page)
@@ -346,6 +413,34 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_manager_initially_healthy() {
+        let manager = BrowserManager::default();
+
+        assert!(manager.is_healthy().await);
+    }
+
+    #[tokio::test]
+    async fn test_manager_close_open_pages_without_tabs_keeps_health() {
+        let manager = BrowserManager::default();
+
+        assert_eq!(manager.close_open_pages().await, 0);
+        assert!(manager.is_healthy().await);
+    }
+
+    #[tokio::test]
+    async fn test_manager_recycle_restores_health() {
+        let manager = BrowserManager::default();
+        manager.mark_unhealthy().await;
+        assert!(!manager.is_healthy().await);
+
+        manager.recycle().await;
+
+        assert!(manager.is_healthy().await);
+        assert!(!manager.is_launched().await);
+        assert!(!manager.is_active().await);
+    }
+
     // Note: We can't test actual browser launch in CI without Chrome installed
     // These tests would be run manually or in a Docker environment with Chrome
 }
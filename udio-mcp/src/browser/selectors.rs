@@ -49,6 +49,15 @@ pub struct PlaylistSelectors {
 
     /// Song count indicator
     pub song_count: Vec<String>,
+
+    /// "Add to playlist" button on a song page
+    pub add_button: Vec<String>,
+
+    /// Playlist choices in the menu the add button opens
+    pub picker_option: Vec<String>,
+
+    /// "Remove from playlist" button, looked up inside a song row
+    pub remove_button: Vec<String>,
 }
 
 impl Default for PlaylistSelectors {
@@ -66,6 +75,18 @@ impl Default for PlaylistSelectors {
                 "h2".to_string(),
             ],
             song_count: vec![".song-count".to_string(), ".track-count".to_string()],
+            add_button: vec![
+                "[data-action='add-to-playlist']".to_string(),
+                ".add-to-playlist".to_string(),
+            ],
+            picker_option: vec![
+                "[data-testid='playlist-option']".to_string(),
+                ".playlist-picker .playlist-option".to_string(),
+            ],
+            remove_button: vec![
+                "[data-action='remove-from-playlist']".to_string(),
+                ".remove-from-playlist".to_string(),
+            ],
         }
     }
 }
@@ -260,6 +281,8 @@ mod tests {
 
         assert!(playlist_sel.container.primary().is_some());
         assert!(playlist_sel.item.primary().is_some());
+        assert!(playlist_sel.add_button.primary().is_some());
+        assert!(playlist_sel.remove_button.primary().is_some());
     }
 
     #[test]
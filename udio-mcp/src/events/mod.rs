@@ -0,0 +1,222 @@
+// Internal event bus
+// Lets components announce what happened without knowing who is listening
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+use crate::models::Song;
+
+/// Events buffered per subscriber before the slowest one starts missing some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Something that happened which subscribers may want to hear about
+///
+/// Adding an event means adding a variant here and its name to
+/// [`EventKind`]; subscribers such as webhooks pick it up without changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Event {
+    /// A song finished generating and is ready to play
+    GenerationCompleted {
+        /// The finished song
+        song: Song,
+    },
+
+    /// A playlist gained or lost songs
+    PlaylistChanged {
+        /// Playlist name
+        playlist: String,
+        /// IDs of songs added since the playlist was last seen
+        added: Vec<String>,
+        /// IDs of songs removed since the playlist was last seen
+        removed: Vec<String>,
+        /// Songs in the playlist now
+        song_count: usize,
+    },
+
+    /// A stored login is no longer valid
+    AuthExpired {
+        /// Account whose session expired
+        email: String,
+    },
+}
+
+/// Event names, as used in webhook filters and payloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// [`Event::GenerationCompleted`]
+    GenerationCompleted,
+    /// [`Event::PlaylistChanged`]
+    PlaylistChanged,
+    /// [`Event::AuthExpired`]
+    AuthExpired,
+}
+
+impl Event {
+    /// Which kind of event this is
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::GenerationCompleted { .. } => EventKind::GenerationCompleted,
+            Event::PlaylistChanged { .. } => EventKind::PlaylistChanged,
+            Event::AuthExpired { .. } => EventKind::AuthExpired,
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::GenerationCompleted => write!(f, "generation_completed"),
+            EventKind::PlaylistChanged => write!(f, "playlist_changed"),
+            EventKind::AuthExpired => write!(f, "auth_expired"),
+        }
+    }
+}
+
+/// A published event with the metadata subscribers need to deduplicate and
+/// order it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Unique per published event
+    pub id: String,
+
+    /// When the event was published (Unix timestamp)
+    pub timestamp: u64,
+
+    /// The event itself, flattened to `event` and `data` fields
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Broadcast channel for [`Event`]s
+///
+/// Cheap to clone; every clone publishes to the same subscribers. Publishing
+/// never blocks, and events published with no subscribers are dropped.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Arc<Envelope>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl EventBus {
+    /// Create a bus with no subscribers
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Announce an event to every current subscriber
+    pub fn publish(&self, event: Event) -> Arc<Envelope> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let sequence = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let envelope = Arc::new(Envelope {
+            id: format!("evt_{}_{}", timestamp, sequence),
+            timestamp,
+            event,
+        });
+
+        tracing::debug!(
+            "Publishing event {} ({})",
+            envelope.id,
+            envelope.event.kind()
+        );
+        // An error only means nobody is subscribed
+        let _ = self.sender.send(envelope.clone());
+        envelope
+    }
+
+    /// Receive every event published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<Envelope>> {
+        self.sender.subscribe()
+    }
+
+    /// Number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_serializes_with_name_and_data() {
+        let envelope = Envelope {
+            id: "evt_1_1".to_string(),
+            timestamp: 1,
+            event: Event::AuthExpired {
+                email: "user@example.com".to_string(),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            json!({
+                "id": "evt_1_1",
+                "timestamp": 1,
+                "event": "auth_expired",
+                "data": { "email": "user@example.com" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_kind_names_match_serialized_event() {
+        let event = Event::PlaylistChanged {
+            playlist: "ToPlay".to_string(),
+            added: vec!["a".to_string()],
+            removed: vec![],
+            song_count: 1,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["event"], event.kind().to_string());
+        assert_eq!(serde_json::to_value(event.kind()).unwrap(), value["event"]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_published_events() {
+        let bus = EventBus::new();
+        let mut first = bus.subscribe();
+        let mut second = bus.clone().subscribe();
+        assert_eq!(bus.subscriber_count(), 2);
+
+        let published = bus.publish(Event::AuthExpired {
+            email: "user@example.com".to_string(),
+        });
+
+        assert_eq!(first.recv().await.unwrap(), published);
+        assert_eq!(second.recv().await.unwrap(), published);
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_assigns_unique_ids() {
+        let bus = EventBus::new();
+        let event = Event::AuthExpired {
+            email: "user@example.com".to_string(),
+        };
+
+        let first = bus.publish(event.clone());
+        let second = bus.publish(event);
+        assert_ne!(first.id, second.id);
+    }
+}
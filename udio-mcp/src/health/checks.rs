@@ -0,0 +1,154 @@
+// Startup checks
+// Configuration, browser, login and cache directory, in the order they run
+
+use async_trait::async_trait;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::{CheckStatus, StartupCheck};
+use crate::auth::{AuthManager, SessionError};
+use crate::browser::BrowserManager;
+
+/// Name of the login check, whose degradation blocks mutating tools
+pub const AUTH_CHECK: &str = "auth";
+
+/// File written and removed to prove the cache directory is writable
+const PROBE_FILE: &str = ".udio-mcp-write-probe";
+
+/// Validates configuration; failing is fatal
+pub struct ConfigCheck {
+    validate: Box<dyn Fn() -> anyhow::Result<()> + Send + Sync>,
+}
+
+impl ConfigCheck {
+    /// Check with the given validation
+    pub fn new(validate: impl Fn() -> anyhow::Result<()> + Send + Sync + 'static) -> Self {
+        Self {
+            validate: Box::new(validate),
+        }
+    }
+}
+
+#[async_trait]
+impl StartupCheck for ConfigCheck {
+    fn name(&self) -> &str {
+        "config"
+    }
+
+    async fn run(&self) -> CheckStatus {
+        match (self.validate)() {
+            Ok(()) => CheckStatus::Passed,
+            Err(e) => CheckStatus::Fatal(format!("{:#}", e)),
+        }
+    }
+}
+
+/// Launches the browser; failing is fatal
+pub struct BrowserCheck {
+    browser: Arc<BrowserManager>,
+}
+
+impl BrowserCheck {
+    /// Check launching `browser`, which stays running for the tools
+    pub fn new(browser: Arc<BrowserManager>) -> Self {
+        Self { browser }
+    }
+}
+
+#[async_trait]
+impl StartupCheck for BrowserCheck {
+    fn name(&self) -> &str {
+        "browser"
+    }
+
+    async fn run(&self) -> CheckStatus {
+        match self.browser.launch().await {
+            Ok(()) => CheckStatus::Passed,
+            Err(e) => CheckStatus::Fatal(format!("{:#}", e)),
+        }
+    }
+}
+
+/// Checks the account has a valid session or stored credentials to get one
+///
+/// Without either the server still starts, degraded: mutating tools answer
+/// [`crate::mcp::error::McpError::AuthExpired`] until the login is fixed.
+pub struct AuthCheck {
+    auth: Arc<AuthManager>,
+    email: Option<String>,
+}
+
+impl AuthCheck {
+    /// Check `email`'s login; with no account configured there is nothing to
+    /// check and the check passes
+    pub fn new(auth: Arc<AuthManager>, email: Option<String>) -> Self {
+        Self { auth, email }
+    }
+}
+
+#[async_trait]
+impl StartupCheck for AuthCheck {
+    fn name(&self) -> &str {
+        AUTH_CHECK
+    }
+
+    async fn run(&self) -> CheckStatus {
+        let Some(email) = &self.email else {
+            return CheckStatus::Passed;
+        };
+
+        let error = match self.auth.get_session(email) {
+            Ok(_) => return CheckStatus::Passed,
+            // Refreshable: the next call logs in again
+            Err(_) if self.auth.has_credentials(email) => return CheckStatus::Passed,
+            Err(e) => e,
+        };
+
+        match error.downcast_ref::<SessionError>() {
+            Some(SessionError::Expired(_)) => CheckStatus::Degraded(format!(
+                "{} and no stored credentials to log in again",
+                SessionError::Expired(email.clone())
+            )),
+            _ => CheckStatus::Degraded(SessionError::NoCredentials(email.clone()).to_string()),
+        }
+    }
+}
+
+/// Checks the cache directory can be created and written; failing is fatal
+pub struct CacheDirCheck {
+    dir: PathBuf,
+}
+
+impl CacheDirCheck {
+    /// Check `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `UDIO_MCP_CACHE_DIR`, or `udio-mcp` under the system temp directory
+    pub fn dir_from_env() -> PathBuf {
+        std::env::var_os("UDIO_MCP_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("udio-mcp"))
+    }
+}
+
+#[async_trait]
+impl StartupCheck for CacheDirCheck {
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    async fn run(&self) -> CheckStatus {
+        let probe = self.dir.join(PROBE_FILE);
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&probe, b"ok"))
+            .and_then(|_| fs::remove_file(&probe));
+
+        match result {
+            Ok(()) => CheckStatus::Passed,
+            Err(e) => CheckStatus::Fatal(format!("{} is not writable: {}", self.dir.display(), e)),
+        }
+    }
+}
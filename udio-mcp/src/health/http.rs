@@ -0,0 +1,79 @@
+// Health probes over HTTP
+// Just enough HTTP/1.1 for an orchestrator's liveness and readiness probes
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::{HealthState, Readiness};
+
+/// Largest request head read before answering
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+
+/// Answer probes on `listener` until the task is dropped
+///
+/// - `GET /healthz` is 200 unless startup failed: the process is alive.
+/// - `GET /readyz` is 200 only once every check passed; a degraded server
+///   is not ready.
+///
+/// Both return the current [`super::HealthReport`] as JSON.
+pub async fn serve(listener: TcpListener, state: HealthState) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::warn!("Health probe accept failed: {}", e);
+                continue;
+            }
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, &state).await {
+                tracing::debug!("Health probe connection failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn respond(mut stream: TcpStream, state: &HealthState) -> std::io::Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_LEN {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let report = state.report();
+    let (status, body) = match (method, path) {
+        ("GET", "/healthz") if report.readiness != Readiness::Failed => ("200 OK", report),
+        ("GET", "/healthz") => ("503 Service Unavailable", report),
+        ("GET", "/readyz") if report.readiness == Readiness::Ready => ("200 OK", report),
+        ("GET", "/readyz") => ("503 Service Unavailable", report),
+        _ => {
+            let body = "not found\n";
+            let response = format!(
+                "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            return stream.write_all(response.as_bytes()).await;
+        }
+    };
+
+    let body = serde_json::to_string(&body).unwrap_or_default();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await
+}
@@ -0,0 +1,331 @@
+// Startup health checks and readiness
+// Runs ordered checks before the server accepts requests and keeps the result
+// for probes and the health tool
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+
+/// The checks run at startup
+pub mod checks;
+/// `/healthz` and `/readyz` over HTTP
+pub mod http;
+
+pub use checks::{AuthCheck, BrowserCheck, CacheDirCheck, ConfigCheck, AUTH_CHECK};
+pub use http::serve;
+
+/// How one check went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Nothing wrong
+    Passed,
+    /// The server can run, but some tools can't; carries the reason
+    Degraded(String),
+    /// The server can't run; carries the reason
+    Fatal(String),
+}
+
+/// One step of the startup sequence
+#[async_trait]
+pub trait StartupCheck: Send + Sync {
+    /// Short name shown in progress and reports
+    fn name(&self) -> &str;
+
+    /// Perform the check
+    async fn run(&self) -> CheckStatus;
+}
+
+/// Outcome of a check, as reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Nothing wrong
+    Passed,
+    /// Running with reduced functionality
+    Degraded,
+    /// Startup stopped here
+    Fatal,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Passed => write!(f, "passed"),
+            Outcome::Degraded => write!(f, "degraded"),
+            Outcome::Fatal => write!(f, "fatal"),
+        }
+    }
+}
+
+/// A finished check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Check name
+    pub name: String,
+    /// How it went
+    pub outcome: Outcome,
+    /// Why it didn't pass
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub reason: Option<String>,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus) -> Self {
+        let (outcome, reason) = match status {
+            CheckStatus::Passed => (Outcome::Passed, None),
+            CheckStatus::Degraded(reason) => (Outcome::Degraded, Some(reason)),
+            CheckStatus::Fatal(reason) => (Outcome::Fatal, Some(reason)),
+        };
+        Self {
+            name: name.to_string(),
+            outcome,
+            reason,
+        }
+    }
+}
+
+/// Whether the server is usable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Readiness {
+    /// Checks are still running
+    Starting,
+    /// Every check passed
+    Ready,
+    /// Running, but some checks found problems
+    Degraded,
+    /// A fatal check failed; the server is stopping
+    Failed,
+}
+
+impl fmt::Display for Readiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Readiness::Starting => write!(f, "starting"),
+            Readiness::Ready => write!(f, "ready"),
+            Readiness::Degraded => write!(f, "degraded"),
+            Readiness::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Readiness and the checks behind it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Overall state
+    pub readiness: Readiness,
+    /// Checks run so far, in order
+    pub checks: Vec<CheckResult>,
+}
+
+impl HealthReport {
+    /// Report before any check has run
+    pub fn starting() -> Self {
+        Self {
+            readiness: Readiness::Starting,
+            checks: Vec::new(),
+        }
+    }
+
+    /// A check's result, if it ran
+    pub fn check(&self, name: &str) -> Option<&CheckResult> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+}
+
+/// Shared view of the latest [`HealthReport`]
+#[derive(Debug, Clone)]
+pub struct HealthState {
+    report: Arc<RwLock<HealthReport>>,
+}
+
+impl HealthState {
+    /// State that reports [`Readiness::Starting`]
+    pub fn new() -> Self {
+        Self {
+            report: Arc::new(RwLock::new(HealthReport::starting())),
+        }
+    }
+
+    /// The latest report
+    pub fn report(&self) -> HealthReport {
+        self.report.read().unwrap().clone()
+    }
+
+    /// Overall state
+    pub fn readiness(&self) -> Readiness {
+        self.report.read().unwrap().readiness
+    }
+
+    /// Replace the report
+    pub fn set(&self, report: HealthReport) {
+        *self.report.write().unwrap() = report;
+    }
+
+    /// Why the login is unusable, when the auth check came back degraded
+    pub fn auth_expired(&self) -> Option<String> {
+        let report = self.report.read().unwrap();
+        report
+            .check(AUTH_CHECK)
+            .filter(|check| check.outcome == Outcome::Degraded)
+            .map(|check| check.reason.clone().unwrap_or_default())
+    }
+}
+
+impl Default for HealthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks run in order before the server starts serving
+#[derive(Default)]
+pub struct StartupSequence {
+    checks: Vec<Box<dyn StartupCheck>>,
+}
+
+impl StartupSequence {
+    /// Create an empty sequence
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a check after the existing ones
+    pub fn with_check(mut self, check: impl StartupCheck + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Run every check, writing a line of progress for each to `progress`
+    ///
+    /// The first fatal check stops the sequence. `state` is updated after
+    /// each check, so probes see how far startup got.
+    pub async fn run(&self, state: &HealthState, progress: &mut dyn Write) -> HealthReport {
+        let mut report = HealthReport::starting();
+        let total = self.checks.len();
+
+        for (index, check) in self.checks.iter().enumerate() {
+            let _ = write!(progress, "[{}/{}] {} ... ", index + 1, total, check.name());
+            let _ = progress.flush();
+
+            let result = CheckResult::new(check.name(), check.run().await);
+            let _ = match &result.reason {
+                Some(reason) => writeln!(progress, "{}: {}", result.outcome, reason),
+                None => writeln!(progress, "ok"),
+            };
+
+            let fatal = result.outcome == Outcome::Fatal;
+            report.checks.push(result);
+            if fatal {
+                report.readiness = Readiness::Failed;
+                state.set(report.clone());
+                return report;
+            }
+            state.set(report.clone());
+        }
+
+        report.readiness = if report
+            .checks
+            .iter()
+            .any(|check| check.outcome == Outcome::Degraded)
+        {
+            Readiness::Degraded
+        } else {
+            Readiness::Ready
+        };
+        state.set(report.clone());
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedCheck(&'static str, CheckStatus);
+
+    #[async_trait]
+    impl StartupCheck for FixedCheck {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn run(&self) -> CheckStatus {
+            self.1.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_passed_is_ready() {
+        let state = HealthState::new();
+        let mut progress = Vec::new();
+        let report = StartupSequence::new()
+            .with_check(FixedCheck("one", CheckStatus::Passed))
+            .with_check(FixedCheck("two", CheckStatus::Passed))
+            .run(&state, &mut progress)
+            .await;
+
+        assert_eq!(report.readiness, Readiness::Ready);
+        assert_eq!(state.report(), report);
+        assert_eq!(
+            String::from_utf8(progress).unwrap(),
+            "[1/2] one ... ok\n[2/2] two ... ok\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fatal_stops_the_sequence() {
+        let state = HealthState::new();
+        let mut progress = Vec::new();
+        let report = StartupSequence::new()
+            .with_check(FixedCheck("one", CheckStatus::Fatal("broken".into())))
+            .with_check(FixedCheck("two", CheckStatus::Passed))
+            .run(&state, &mut progress)
+            .await;
+
+        assert_eq!(report.readiness, Readiness::Failed);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(
+            String::from_utf8(progress).unwrap(),
+            "[1/2] one ... fatal: broken\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_degraded_auth_is_exposed() {
+        let state = HealthState::new();
+        assert_eq!(state.readiness(), Readiness::Starting);
+
+        StartupSequence::new()
+            .with_check(FixedCheck(
+                AUTH_CHECK,
+                CheckStatus::Degraded("expired".into()),
+            ))
+            .run(&state, &mut Vec::new())
+            .await;
+
+        assert_eq!(state.readiness(), Readiness::Degraded);
+        assert_eq!(state.auth_expired().as_deref(), Some("expired"));
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = HealthReport {
+            readiness: Readiness::Degraded,
+            checks: vec![
+                CheckResult::new("config", CheckStatus::Passed),
+                CheckResult::new("auth", CheckStatus::Degraded("expired".into())),
+            ],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["readiness"], "degraded");
+        assert_eq!(
+            json["checks"][0],
+            serde_json::json!({"name": "config", "outcome": "passed"})
+        );
+        assert_eq!(json["checks"][1]["reason"], "expired");
+    }
+}
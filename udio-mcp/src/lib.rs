@@ -4,21 +4,31 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../mcp-server-comprehensive-guide.md")]
 
+/// Local analysis of downloaded audio
+pub mod analysis;
 /// Authentication and credential management
 pub mod auth; // Stage 3: Authentication
 /// Browser automation and control
 pub mod browser; // Stage 2: Browser automation
+/// Internal event bus
+pub mod events;
+/// Startup checks and readiness probes
+pub mod health;
 /// MCP protocol implementation and server
 pub mod mcp;
 /// Data models for songs, playlists, and playback state
 pub mod models; // Stage 4: Data models
+/// Offline mode backed by a local copy of the library
+pub mod offline;
 /// Playback control functionality
 pub mod playback;
 /// Playlist management and operations
 pub mod playlist; // Stage 4: Playlist operations // Stage 5: Playback control
-                  // pub mod cache;     // To be implemented
-                  // pub mod config;    // To be implemented
-                  // pub mod utils;     // To be implemented
+/// Outbound webhook notifications
+pub mod webhooks;
+// pub mod cache;     // To be implemented
+// pub mod config;    // To be implemented
+// pub mod utils;     // To be implemented
 
 /// Testing utilities and mocks (available in all builds for testing)
 #[cfg(test)]
@@ -32,5 +42,8 @@ pub mod prelude {
     pub use crate::mcp::server::McpServer;
     pub use crate::mcp::tools::{Tool, ToolRegistry};
     pub use crate::mcp::transport::{stdio::StdioTransport, Transport};
-    pub use crate::mcp::types::{ErrorObject, Message, Notification, Request, RequestId, Response};
+    pub use crate::mcp::types::{
+        ErrorCategory, ErrorData, ErrorObject, FieldError, Message, Notification, Recovery,
+        Request, RequestId, Response,
+    };
 }
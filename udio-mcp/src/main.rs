@@ -2,17 +2,32 @@
 // This server implements the Model Context Protocol for Udio music control
 
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 use udio_mcp_server::{
+    analysis::AudioAnalyzer,
+    auth::AuthManager,
     browser::BrowserManager,
+    events::EventBus,
+    health::{
+        self, AuthCheck, BrowserCheck, CacheDirCheck, ConfigCheck, HealthState, Readiness,
+        StartupSequence,
+    },
     mcp::{
         capabilities::ServerCapabilities,
+        compat::ProtocolVersion,
         server::McpServer,
-        tools::{ControlPlaybackTool, ListPlaylistSongsTool, PlaySongTool},
+        tools::{
+            AnalyzeAudioTool, ControlPlaybackTool, EditPlaylistTool, GetPlaybackStateTool,
+            HealthTool, LibraryStatsTool, ListPlaylistSongsTool, OfflineMode, PlaySongTool,
+            SearchSongsTool, SuggestTracksTool, TimeoutConfig,
+        },
         transport::stdio::StdioTransport,
     },
+    offline::{Connectivity, LibraryStore, OfflineConfig, SiteProbe},
     playback::PlaybackController,
     playlist::PlaylistManager,
+    webhooks::{WebhookConfig, WebhookDispatcher},
 };
 
 #[tokio::main]
@@ -32,30 +47,153 @@ async fn main() -> anyhow::Result<()> {
     info!("Initializing browser manager...");
     let browser_manager = Arc::new(BrowserManager::default());
 
+    // Online reads are copied here so read-only tools still answer when
+    // Udio can't be reached
+    let library_path = LibraryStore::path_from_env(&CacheDirCheck::dir_from_env());
+    info!("Opening local library {}...", library_path.display());
+    let library = Arc::new(LibraryStore::open(&library_path)?);
+
     info!("Initializing playback controller...");
-    let playback_controller = Arc::new(PlaybackController::new());
+    let playback_controller = Arc::new(PlaybackController::new().with_library(library.clone()));
+
+    // Components announce what happens here; webhooks forward it
+    let events = EventBus::new();
+
+    // Orchestrators probe readiness over HTTP; without an address the
+    // health tool reports it instead
+    let health = HealthState::new();
+    let health_addr = std::env::var("UDIO_MCP_HEALTH_ADDR").ok();
+    if let Some(addr) = &health_addr {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!("Health probes on http://{}/healthz and /readyz", addr);
+        tokio::spawn(health::serve(listener, health.clone()));
+    }
+
+    // Check everything the tools depend on before accepting requests
+    let auth_manager = Arc::new(AuthManager::new().with_event_bus(events.clone()));
+    let startup = StartupSequence::new()
+        .with_check(ConfigCheck::new(validate_config))
+        .with_check(BrowserCheck::new(browser_manager.clone()))
+        .with_check(AuthCheck::new(
+            auth_manager,
+            std::env::var("UDIO_MCP_AUTH_EMAIL").ok(),
+        ))
+        .with_check(CacheDirCheck::new(CacheDirCheck::dir_from_env()));
+    let report = startup.run(&health, &mut std::io::stderr()).await;
+    match report.readiness {
+        Readiness::Failed => {
+            let failed = report.checks.last().expect("a failed report has checks");
+            anyhow::bail!(
+                "Startup check '{}' failed: {}",
+                failed.name,
+                failed.reason.as_deref().unwrap_or_default()
+            );
+        }
+        Readiness::Degraded => {
+            tracing::warn!("Starting degraded: mutating tools are disabled until login works")
+        }
+        _ => info!("Startup checks passed"),
+    }
+
+    if let Ok(path) = std::env::var("UDIO_MCP_WEBHOOKS") {
+        let config = WebhookConfig::load(&path)?;
+        info!(
+            "Webhooks: {} endpoint(s) from {}",
+            config.endpoints.len(),
+            path
+        );
+        WebhookDispatcher::new(config)?.spawn(&events);
+    }
 
     info!("Initializing playlist manager...");
-    let playlist_manager = Arc::new(PlaylistManager::new(browser_manager.clone()));
+    let playlist_manager = Arc::new(
+        PlaylistManager::new(browser_manager.clone())
+            .with_event_bus(events.clone())
+            .with_library(library.clone()),
+    );
 
     // Create server with capabilities
     let capabilities = ServerCapabilities::new()
         .with_tools(false) // Tools can change dynamically
         .with_logging();
 
-    let server = McpServer::with_config(Default::default(), capabilities);
+    let mut server = McpServer::with_config(Default::default(), capabilities);
+
+    // Optionally reject clients speaking an older protocol revision
+    if let Ok(min_version) = std::env::var("UDIO_MCP_MIN_PROTOCOL_VERSION") {
+        let min_version: ProtocolVersion = min_version.parse()?;
+        info!("Minimum client protocol version: {}", min_version);
+        server = server.with_min_protocol_version(min_version);
+    }
 
     // Get tool registry and register Udio tools
     let tools = server.tools();
     let mut tools_lock = tools.write().await;
 
+    // Optionally plan mutating calls instead of executing them
+    if std::env::var("UDIO_MCP_DRY_RUN").is_ok_and(|v| matches!(v.as_str(), "1" | "true")) {
+        info!("Dry-run mode: mutating tools will only describe their actions");
+        tools_lock.set_dry_run(true);
+    }
+
+    // Give each call a deadline; stuck calls get their tabs closed and the
+    // browser replaced before the next one
+    let timeouts = TimeoutConfig::from_env()?;
+    info!(
+        "Tool timeouts: read {}s, mutation {}s, generation {}s",
+        timeouts.read_secs, timeouts.mutation_secs, timeouts.generation_secs
+    );
+    tools_lock.set_timeouts(timeouts);
+    tools_lock.set_recovery(browser_manager.clone());
+
+    // Repeated browser failures switch to the local library until Udio
+    // answers a health check again
+    let offline = OfflineConfig::from_env()?;
+    let connectivity = Connectivity::new(&offline);
+    if offline.forced {
+        info!("Offline mode forced: serving the local library, mutating tools disabled");
+    } else {
+        info!(
+            "Offline after {} failed calls, checking Udio every {}s",
+            offline.failure_threshold, offline.check_interval_secs
+        );
+        let probe = SiteProbe::new("https://www.udio.com", Duration::from_secs(10))?;
+        connectivity.spawn_recovery(
+            Arc::new(probe),
+            Duration::from_secs(offline.check_interval_secs),
+        );
+    }
+    tools_lock.set_offline(OfflineMode::new(connectivity, library.clone()));
+
     info!("Registering MCP tools...");
 
     // Register list_playlist_songs tool
-    let list_playlist_tool = Arc::new(ListPlaylistSongsTool::new(playlist_manager.clone()));
+    let list_playlist_tool = Arc::new(
+        ListPlaylistSongsTool::new(playlist_manager.clone()).with_library(library.clone()),
+    );
     tools_lock.register(list_playlist_tool)?;
     info!("  ✓ list_playlist_songs");
 
+    // Register edit_playlist tool
+    tools_lock.register(Arc::new(EditPlaylistTool::new(playlist_manager.clone())))?;
+    info!("  ✓ edit_playlist");
+
+    // Register get_playback_state tool
+    let playback_state_tool = Arc::new(
+        GetPlaybackStateTool::new(browser_manager.clone(), playback_controller.clone())
+            .with_library(library.clone()),
+    );
+    tools_lock.register(playback_state_tool)?;
+    info!("  ✓ get_playback_state");
+
+    // Register search_songs, library_stats and suggest_tracks tools
+    tools_lock.register(Arc::new(SearchSongsTool::new(library.clone())))?;
+    info!("  ✓ search_songs");
+    tools_lock.register(Arc::new(LibraryStatsTool::new(library.clone())))?;
+    info!("  ✓ library_stats");
+    tools_lock.register(Arc::new(SuggestTracksTool::new(library.clone())))?;
+    info!("  ✓ suggest_tracks");
+
     // Register play_song tool
     let play_song_tool = Arc::new(PlaySongTool::new(
         browser_manager.clone(),
@@ -72,10 +210,24 @@ async fn main() -> anyhow::Result<()> {
     tools_lock.register(control_playback_tool)?;
     info!("  ✓ control_playback");
 
+    // Register analyze_audio tool
+    let analyze_audio_tool = Arc::new(AnalyzeAudioTool::new(Arc::new(AudioAnalyzer::new())));
+    tools_lock.register(analyze_audio_tool)?;
+    info!("  ✓ analyze_audio");
+
+    // Mutating tools answer AuthExpired while the login check is degraded
+    tools_lock.set_health(health.clone());
+    if health_addr.is_none() {
+        tools_lock.register(Arc::new(HealthTool::new(health)))?;
+        info!("  ✓ health");
+    }
+
+    let tool_count = tools_lock.count();
+
     // Release the write lock
     drop(tools_lock);
 
-    info!("Tool registry ready (3 tools registered)");
+    info!("Tool registry ready ({} tools registered)", tool_count);
 
     // Create stdio transport
     let transport = StdioTransport::new();
@@ -89,3 +241,17 @@ async fn main() -> anyhow::Result<()> {
     info!("Server shutdown complete");
     Ok(())
 }
+
+/// Parse every setting read from the environment, so bad values stop
+/// startup before the browser is launched
+fn validate_config() -> anyhow::Result<()> {
+    TimeoutConfig::from_env()?;
+    if let Ok(min_version) = std::env::var("UDIO_MCP_MIN_PROTOCOL_VERSION") {
+        min_version.parse::<ProtocolVersion>()?;
+    }
+    if let Ok(path) = std::env::var("UDIO_MCP_WEBHOOKS") {
+        WebhookConfig::load(&path)?;
+    }
+    OfflineConfig::from_env()?;
+    Ok(())
+}
@@ -0,0 +1,398 @@
+// MCP protocol version negotiation and compatibility layer
+// Records the revision agreed with each client and adapts responses to it
+
+use serde_json::{json, Value};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::mcp::error::{McpError, McpResult};
+use crate::mcp::tools::ToolOutput;
+
+/// A dated MCP protocol revision, e.g. "2024-11-05"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl ProtocolVersion {
+    /// First public revision: tool results are text content only
+    pub const V2024_11_05: Self = Self::new(2024, 11, 5);
+    /// Adds tool annotations
+    pub const V2025_03_26: Self = Self::new(2025, 3, 26);
+    /// Adds structured tool output, output schemas and tool titles
+    pub const V2025_06_18: Self = Self::new(2025, 6, 18);
+
+    /// Revisions this server can speak, newest first
+    pub const SUPPORTED: [Self; 3] = [Self::V2025_06_18, Self::V2025_03_26, Self::V2024_11_05];
+
+    const fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Newest revision this server speaks
+    pub fn latest() -> Self {
+        Self::SUPPORTED[0]
+    }
+
+    /// Whether tool results may carry `structuredContent`
+    pub fn supports_structured_content(&self) -> bool {
+        *self >= Self::V2025_06_18
+    }
+
+    /// Whether tool listings may carry `title` and `outputSchema`
+    pub fn supports_tool_metadata(&self) -> bool {
+        *self >= Self::V2025_06_18
+    }
+
+    /// Whether tool listings may carry `annotations`
+    pub fn supports_tool_annotations(&self) -> bool {
+        *self >= Self::V2025_03_26
+    }
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = McpError;
+
+    fn from_str(s: &str) -> McpResult<Self> {
+        let invalid = || {
+            McpError::invalid_field(
+                "protocolVersion",
+                format!("'{}' is not a valid version; expected YYYY-MM-DD", s),
+            )
+        };
+
+        let parts: Vec<&str> = s.split('-').collect();
+        let [year, month, day] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        if year.len() != 4
+            || month.len() != 2
+            || day.len() != 2
+            || !parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(invalid());
+        }
+
+        let year: u16 = year.parse().map_err(|_| invalid())?;
+        let month: u8 = month.parse().map_err(|_| invalid())?;
+        let day: u8 = day.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(year, month, day))
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Picks the protocol revision to speak with each client
+#[derive(Debug, Clone)]
+pub struct VersionNegotiator {
+    min_version: ProtocolVersion,
+}
+
+impl VersionNegotiator {
+    /// Create a negotiator accepting every supported revision
+    pub fn new() -> Self {
+        Self {
+            min_version: ProtocolVersion::V2024_11_05,
+        }
+    }
+
+    /// Builder method to reject clients older than `version`
+    pub fn with_min_version(mut self, version: ProtocolVersion) -> Self {
+        self.min_version = version;
+        self
+    }
+
+    /// Oldest client revision that will be accepted
+    pub fn min_version(&self) -> ProtocolVersion {
+        self.min_version
+    }
+
+    /// Choose the revision for a client that declared `requested`
+    ///
+    /// The newest supported revision not newer than the client's is used, so a
+    /// client ahead of the server is answered with the server's latest.
+    /// Clients older than the configured minimum are rejected.
+    pub fn negotiate(&self, requested: &str) -> McpResult<ProtocolVersion> {
+        let requested: ProtocolVersion = requested.parse()?;
+
+        if requested < self.min_version {
+            return Err(McpError::unsupported_version(format!(
+                "client speaks {} but this server requires {} or newer; please upgrade the client",
+                requested, self.min_version
+            )));
+        }
+
+        ProtocolVersion::SUPPORTED
+            .iter()
+            .copied()
+            .find(|v| *v <= requested && *v >= self.min_version)
+            .ok_or_else(|| {
+                McpError::unsupported_version(format!(
+                    "no revision shared with client {} (server supports {} to {})",
+                    requested,
+                    self.min_version,
+                    ProtocolVersion::latest()
+                ))
+            })
+    }
+}
+
+impl Default for VersionNegotiator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What the server learned about a client during initialization
+#[derive(Debug, Clone)]
+pub struct ClientSession {
+    /// Client name from `clientInfo`
+    pub client_name: String,
+    /// Client version from `clientInfo`
+    pub client_version: String,
+    /// Protocol version the client declared
+    pub requested_version: String,
+    /// Protocol version agreed for this session
+    pub protocol_version: ProtocolVersion,
+}
+
+/// Strip fields from a `tools/list` result that the revision does not know
+pub fn adapt_tools_list(version: ProtocolVersion, mut result: Value) -> Value {
+    if let Some(tools) = result.get_mut("tools").and_then(Value::as_array_mut) {
+        for tool in tools.iter_mut().filter_map(Value::as_object_mut) {
+            if !version.supports_tool_metadata() {
+                tool.remove("title");
+                tool.remove("outputSchema");
+            }
+            if !version.supports_tool_annotations() {
+                tool.remove("annotations");
+            }
+        }
+    }
+    result
+}
+
+/// Build a `tools/call` result for the revision
+///
+/// Every revision receives two text blocks: markdown for display, capped at
+/// `max_text_len` characters, followed by the JSON rendering. Revisions with
+/// structured output also receive object outputs as `structuredContent`.
+pub fn tool_call_result(
+    version: ProtocolVersion,
+    output: &dyn ToolOutput,
+    max_text_len: usize,
+) -> Value {
+    let data = output.to_json();
+    let mut result = json!({
+        "content": [
+            {
+                "type": "text",
+                "text": output.to_markdown(max_text_len)
+            },
+            {
+                "type": "text",
+                "text": data.to_string()
+            }
+        ]
+    });
+
+    if version.supports_structured_content() && data.is_object() {
+        result["structuredContent"] = data;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for text in ["2024-11-05", "2025-03-26", "2025-06-18"] {
+            let version: ProtocolVersion = text.parse().unwrap();
+            assert_eq!(version.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_versions() {
+        for text in [
+            "",
+            "2024-11",
+            "2024-11-05-1",
+            "24-11-05",
+            "2024-13-01",
+            "2024-+1-05",
+            "latest",
+        ] {
+            assert!(
+                text.parse::<ProtocolVersion>().is_err(),
+                "accepted {:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_versions_order_by_date() {
+        assert!(ProtocolVersion::V2024_11_05 < ProtocolVersion::V2025_03_26);
+        assert!(ProtocolVersion::V2025_03_26 < ProtocolVersion::V2025_06_18);
+        assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V2025_06_18);
+    }
+
+    #[test]
+    fn test_negotiate_table() {
+        // (min version, requested, expected agreed version or None for rejection)
+        let cases = [
+            (
+                ProtocolVersion::V2024_11_05,
+                "2024-11-05",
+                Some(ProtocolVersion::V2024_11_05),
+            ),
+            (
+                ProtocolVersion::V2024_11_05,
+                "2025-03-26",
+                Some(ProtocolVersion::V2025_03_26),
+            ),
+            (
+                ProtocolVersion::V2024_11_05,
+                "2025-06-18",
+                Some(ProtocolVersion::V2025_06_18),
+            ),
+            // Unknown revision between two supported ones falls back to the older
+            (
+                ProtocolVersion::V2024_11_05,
+                "2025-01-15",
+                Some(ProtocolVersion::V2024_11_05),
+            ),
+            // Client ahead of the server gets the server's latest
+            (
+                ProtocolVersion::V2024_11_05,
+                "2026-01-01",
+                Some(ProtocolVersion::V2025_06_18),
+            ),
+            (ProtocolVersion::V2024_11_05, "2024-10-07", None),
+            (ProtocolVersion::V2025_03_26, "2024-11-05", None),
+            (
+                ProtocolVersion::V2025_03_26,
+                "2025-03-26",
+                Some(ProtocolVersion::V2025_03_26),
+            ),
+        ];
+
+        for (min, requested, expected) in cases {
+            let negotiator = VersionNegotiator::new().with_min_version(min);
+            let agreed = negotiator.negotiate(requested).ok();
+            assert_eq!(agreed, expected, "min={} requested={}", min, requested);
+        }
+    }
+
+    #[test]
+    fn test_negotiate_rejection_is_clear() {
+        let negotiator = VersionNegotiator::new().with_min_version(ProtocolVersion::V2025_03_26);
+        let message = negotiator.negotiate("2024-11-05").unwrap_err().to_string();
+
+        assert!(message.contains("2024-11-05"));
+        assert!(message.contains("requires 2025-03-26 or newer"));
+    }
+
+    #[test]
+    fn test_adapt_tools_list_strips_unknown_fields() {
+        let listing = json!({
+            "tools": [{
+                "name": "play_song",
+                "title": "Play Song",
+                "description": "Play a song",
+                "inputSchema": {"type": "object"},
+                "outputSchema": {"type": "object"},
+                "annotations": {"readOnlyHint": false}
+            }]
+        });
+
+        // (version, keeps title/outputSchema, keeps annotations)
+        let cases = [
+            (ProtocolVersion::V2024_11_05, false, false),
+            (ProtocolVersion::V2025_03_26, false, true),
+            (ProtocolVersion::V2025_06_18, true, true),
+        ];
+
+        for (version, metadata, annotations) in cases {
+            let adapted = adapt_tools_list(version, listing.clone());
+            let tool = &adapted["tools"][0];
+            assert_eq!(tool.get("title").is_some(), metadata, "{}", version);
+            assert_eq!(tool.get("outputSchema").is_some(), metadata, "{}", version);
+            assert_eq!(
+                tool.get("annotations").is_some(),
+                annotations,
+                "{}",
+                version
+            );
+            assert_eq!(tool["name"], "play_song");
+            assert!(tool.get("inputSchema").is_some());
+        }
+    }
+
+    #[test]
+    fn test_tool_call_result_falls_back_to_text() {
+        let output = json!({"status": "playing"});
+
+        let old = tool_call_result(ProtocolVersion::V2025_03_26, &output, 4000);
+        assert!(old.get("structuredContent").is_none());
+        assert_eq!(old["content"][0]["type"], "text");
+        assert_eq!(old["content"][1]["text"], output.to_string());
+
+        let new = tool_call_result(ProtocolVersion::V2025_06_18, &output, 4000);
+        assert_eq!(new["structuredContent"], output);
+        assert_eq!(new["content"][1]["text"], output.to_string());
+
+        // Structured content must be an object, so scalars stay text-only
+        let scalar = tool_call_result(ProtocolVersion::V2025_06_18, &json!("done"), 4000);
+        assert!(scalar.get("structuredContent").is_none());
+    }
+
+    #[test]
+    fn test_tool_call_result_markdown_first() {
+        let state = crate::models::PlaybackState::playing("song-1", "Neon Rain").with_volume(50);
+
+        let result = tool_call_result(ProtocolVersion::V2025_06_18, &state, 4000);
+        let content = result["content"].as_array().unwrap();
+        assert_eq!(content.len(), 2);
+        assert!(content[0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("**Playing:**"));
+
+        // The JSON block and structured content come from the same model
+        let data: Value = serde_json::from_str(content[1]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(data, result["structuredContent"]);
+        assert_eq!(data["volume"], 50);
+    }
+
+    #[test]
+    fn test_tool_call_result_caps_markdown() {
+        let output = json!({"lyrics": "la ".repeat(1000)});
+
+        let result = tool_call_result(ProtocolVersion::V2025_06_18, &output, 200);
+        assert!(
+            result["content"][0]["text"]
+                .as_str()
+                .unwrap()
+                .chars()
+                .count()
+                <= 200
+        );
+        // Only the display text is capped, never the data
+        assert_eq!(result["structuredContent"], output);
+    }
+}
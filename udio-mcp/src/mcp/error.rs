@@ -1,28 +1,121 @@
 // MCP-specific error types
 // Provides structured error handling for the MCP server
 
-use crate::mcp::types::ErrorObject;
+use crate::analysis::{AnalysisError, SUPPORTED_FORMATS};
+use crate::auth::SessionError;
+use crate::browser::automation::WaitTimeout;
+use crate::mcp::types::{ErrorCategory, ErrorData, ErrorObject, FieldError, Recovery};
+use crate::models::PlaylistDiff;
+use crate::playlist::PlaylistConflict;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 /// Main error type for MCP operations
+///
+/// Every variant maps to a JSON-RPC error code and a structured
+/// [`ErrorData`] payload; see that type for the wire format.
 #[derive(Error, Debug)]
 pub enum McpError {
-    /// Protocol-level error
-    #[error("Protocol error: {0}")]
-    ProtocolError(String),
+    // Protocol errors: the request itself is malformed
+    /// Message could not be parsed
+    #[error("Parse error: {0}")]
+    ParseError(String),
 
     /// Invalid request error
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
     /// Method not found error
-    #[error("Method not found: {0}")]
+    #[error("Method '{0}' not found")]
     MethodNotFound(String),
 
-    /// Invalid parameters error
-    #[error("Invalid parameters: {0}")]
-    InvalidParams(String),
+    /// Client protocol version cannot be served
+    #[error("Unsupported protocol version: {0}")]
+    UnsupportedProtocolVersion(String),
 
+    /// One or more parameters were missing or invalid
+    #[error("Invalid parameters: {}", format_field_errors(.field_errors))]
+    Validation {
+        /// Each parameter that was missing or invalid, with the reason
+        field_errors: Vec<FieldError>,
+    },
+
+    /// A file is in a format the server cannot decode
+    #[error("{message}")]
+    UnsupportedFormat {
+        /// What was not understood
+        message: String,
+        /// Formats that would have worked
+        supported_formats: Vec<String>,
+    },
+
+    // Operational errors: the request was fine but could not be carried out
+    /// The Udio session expired or was never established
+    #[error("Authentication expired: {0}")]
+    AuthExpired(String),
+
+    /// Udio is throttling requests
+    #[error("Rate limited by Udio{}", format_retry_after(.retry_after))]
+    RateLimited {
+        /// How long Udio asked us to wait, if it said
+        retry_after: Option<Duration>,
+    },
+
+    /// Browser automation failed; the full error is logged under `diagnostic_id`
+    #[error("Browser failure: {message} (diagnostic ID {diagnostic_id})")]
+    BrowserFailure {
+        /// Short description safe to show the client
+        message: String,
+        /// Key of the full error in the server log
+        diagnostic_id: String,
+    },
+
+    /// A tool, playlist, song, or other entity does not exist
+    #[error("{entity} '{id}' not found")]
+    NotFound {
+        /// Kind of entity looked up (e.g. "playlist")
+        entity: String,
+        /// Identifier that matched nothing
+        id: String,
+    },
+
+    /// An operation did not finish in time
+    #[error(
+        "Timed out waiting for {operation}{}{}",
+        format_after(.after),
+        format_step(.step)
+    )]
+    Timeout {
+        /// What was being waited for
+        operation: String,
+        /// How long we waited, if known
+        after: Option<Duration>,
+        /// Step of the operation that was running when time ran out
+        step: Option<String>,
+    },
+
+    /// Udio is unreachable, so the tool cannot run until it comes back
+    #[error("Udio is unreachable; running offline{}", format_last_sync(.last_sync))]
+    Offline {
+        /// When the library was last synced (Unix timestamp)
+        last_sync: Option<u64>,
+    },
+
+    /// A playlist edit named a revision the playlist has moved on from
+    #[error("Playlist '{playlist}' changed: it is at revision {current_revision}, not {expected_revision}")]
+    Conflict {
+        /// Playlist that changed
+        playlist: String,
+        /// Revision the edit was made against
+        expected_revision: String,
+        /// Revision the playlist is at now
+        current_revision: String,
+        /// Changes since `expected_revision`, if it is remembered
+        diff: Option<PlaylistDiff>,
+    },
+
+    // Server errors
     /// Internal server error
     #[error("Internal server error: {0}")]
     InternalError(String),
@@ -38,45 +131,149 @@ pub enum McpError {
     /// I/O operation error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+}
+
+fn format_retry_after(retry_after: &Option<Duration>) -> String {
+    retry_after
+        .map(|d| format!("; retry after {}s", d.as_secs()))
+        .unwrap_or_default()
+}
+
+fn format_after(after: &Option<Duration>) -> String {
+    after.map(|d| format!(" after {:?}", d)).unwrap_or_default()
+}
 
-    /// Tool execution error
-    #[error("Tool execution error: {0}")]
-    ToolError(String),
+fn format_step(step: &Option<String>) -> String {
+    step.as_ref()
+        .map(|s| format!(" during '{}'", s))
+        .unwrap_or_default()
+}
 
-    /// Resource access error
-    #[error("Resource error: {0}")]
-    ResourceError(String),
+fn format_last_sync(last_sync: &Option<u64>) -> String {
+    last_sync
+        .map(|t| format!(" from the library synced at {}", t))
+        .unwrap_or_else(|| " and the library was never synced".to_string())
+}
 
-    /// Unsupported capability error
-    #[error("Capability not supported: {0}")]
-    CapabilityNotSupported(String),
+fn format_field_errors(field_errors: &[FieldError]) -> String {
+    field_errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl McpError {
-    /// Convert McpError to JSON-RPC ErrorObject
-    pub fn to_error_object(&self) -> ErrorObject {
+    /// JSON-RPC error code for this error
+    pub fn code(&self) -> i32 {
         use crate::mcp::types::error_codes::*;
 
         match self {
-            McpError::ProtocolError(msg) => ErrorObject::new(PARSE_ERROR, msg),
-            McpError::InvalidRequest(msg) => ErrorObject::new(INVALID_REQUEST, msg),
-            McpError::MethodNotFound(msg) => ErrorObject::new(METHOD_NOT_FOUND, msg),
-            McpError::InvalidParams(msg) => ErrorObject::new(INVALID_PARAMS, msg),
-            McpError::InternalError(msg) => ErrorObject::new(INTERNAL_ERROR, msg),
-            McpError::TransportError(msg) => ErrorObject::new(SERVER_ERROR_START - 1, msg),
-            McpError::SerializationError(e) => {
-                ErrorObject::new(PARSE_ERROR, format!("Serialization error: {}", e))
+            McpError::ParseError(_) => PARSE_ERROR,
+            McpError::InvalidRequest(_) => INVALID_REQUEST,
+            McpError::MethodNotFound(_) => METHOD_NOT_FOUND,
+            McpError::UnsupportedProtocolVersion(_) => INVALID_PARAMS,
+            McpError::Validation { .. } => INVALID_PARAMS,
+            McpError::UnsupportedFormat { .. } => INVALID_PARAMS,
+            McpError::AuthExpired(_) => AUTH_EXPIRED,
+            McpError::RateLimited { .. } => RATE_LIMITED,
+            McpError::BrowserFailure { .. } => BROWSER_FAILURE,
+            McpError::NotFound { .. } => NOT_FOUND,
+            McpError::Timeout { .. } => TIMEOUT,
+            McpError::Offline { .. } => OFFLINE,
+            McpError::Conflict { .. } => CONFLICT,
+            McpError::InternalError(_) => INTERNAL_ERROR,
+            McpError::TransportError(_) => TRANSPORT_ERROR,
+            McpError::SerializationError(_) => INTERNAL_ERROR,
+            McpError::IoError(_) => INTERNAL_ERROR,
+        }
+    }
+
+    /// Structured `data` payload for this error
+    pub fn data(&self) -> ErrorData {
+        match self {
+            McpError::ParseError(_) => {
+                ErrorData::new(ErrorCategory::ParseError, Recovery::FixRequest)
+            }
+            McpError::InvalidRequest(_) => {
+                ErrorData::new(ErrorCategory::InvalidRequest, Recovery::FixRequest)
+            }
+            McpError::MethodNotFound(_) => {
+                ErrorData::new(ErrorCategory::MethodNotFound, Recovery::FixRequest)
+            }
+            McpError::UnsupportedProtocolVersion(_) => {
+                ErrorData::new(ErrorCategory::UnsupportedVersion, Recovery::FixRequest)
+            }
+            McpError::Validation { field_errors } => ErrorData {
+                field_errors: field_errors.clone(),
+                ..ErrorData::new(ErrorCategory::Validation, Recovery::FixRequest)
+            },
+            McpError::UnsupportedFormat {
+                supported_formats, ..
+            } => ErrorData {
+                supported_formats: supported_formats.clone(),
+                ..ErrorData::new(ErrorCategory::UnsupportedFormat, Recovery::FixRequest)
+            },
+            McpError::AuthExpired(_) => {
+                ErrorData::new(ErrorCategory::AuthExpired, Recovery::Reauthenticate)
+            }
+            McpError::RateLimited { retry_after } => ErrorData {
+                retry_after_secs: retry_after.map(|d| d.as_secs()),
+                ..ErrorData::new(ErrorCategory::RateLimited, Recovery::Retry)
+            },
+            McpError::BrowserFailure { diagnostic_id, .. } => ErrorData {
+                diagnostic_id: Some(diagnostic_id.clone()),
+                ..ErrorData::new(ErrorCategory::BrowserFailure, Recovery::Retry)
+            },
+            McpError::NotFound { entity, id } => ErrorData {
+                entity: Some(entity.clone()),
+                id: Some(id.clone()),
+                ..ErrorData::new(ErrorCategory::NotFound, Recovery::GiveUp)
+            },
+            McpError::Timeout {
+                operation,
+                after,
+                step,
+            } => ErrorData {
+                operation: Some(operation.clone()),
+                timeout_ms: after.map(|d| d.as_millis() as u64),
+                step: step.clone(),
+                ..ErrorData::new(ErrorCategory::Timeout, Recovery::Retry)
+            },
+            McpError::Offline { last_sync } => ErrorData {
+                last_sync: *last_sync,
+                ..ErrorData::new(ErrorCategory::Offline, Recovery::Retry)
+            },
+            McpError::Conflict {
+                playlist,
+                current_revision,
+                diff,
+                ..
+            } => ErrorData {
+                entity: Some("playlist".to_string()),
+                id: Some(playlist.clone()),
+                current_revision: Some(current_revision.clone()),
+                diff: diff.clone(),
+                ..ErrorData::new(ErrorCategory::Conflict, Recovery::Refresh)
+            },
+            McpError::TransportError(_) => {
+                ErrorData::new(ErrorCategory::Transport, Recovery::Retry)
+            }
+            McpError::InternalError(_) | McpError::SerializationError(_) | McpError::IoError(_) => {
+                ErrorData::new(ErrorCategory::Internal, Recovery::GiveUp)
             }
-            McpError::IoError(e) => ErrorObject::new(INTERNAL_ERROR, format!("IO error: {}", e)),
-            McpError::ToolError(msg) => ErrorObject::new(SERVER_ERROR_START - 2, msg),
-            McpError::ResourceError(msg) => ErrorObject::new(SERVER_ERROR_START - 3, msg),
-            McpError::CapabilityNotSupported(msg) => ErrorObject::new(SERVER_ERROR_START - 4, msg),
         }
     }
 
-    /// Create a protocol error
-    pub fn protocol(msg: impl Into<String>) -> Self {
-        McpError::ProtocolError(msg.into())
+    /// Convert McpError to JSON-RPC ErrorObject
+    pub fn to_error_object(&self) -> ErrorObject {
+        let data = serde_json::to_value(self.data()).unwrap_or_default();
+        ErrorObject::with_data(self.code(), self.to_string(), data)
+    }
+
+    /// Create a parse error
+    pub fn parse_error(msg: impl Into<String>) -> Self {
+        McpError::ParseError(msg.into())
     }
 
     /// Create an invalid request error
@@ -86,18 +283,109 @@ impl McpError {
 
     /// Create a method not found error
     pub fn method_not_found(method: impl Into<String>) -> Self {
-        McpError::MethodNotFound(format!("Method '{}' not found", method.into()))
+        McpError::MethodNotFound(method.into())
+    }
+
+    /// Create a validation error for a single parameter
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        McpError::Validation {
+            field_errors: vec![FieldError::new(field, message)],
+        }
     }
 
-    /// Create an invalid params error
-    pub fn invalid_params(msg: impl Into<String>) -> Self {
-        McpError::InvalidParams(msg.into())
+    /// Create a not found error
+    pub fn not_found(entity: impl Into<String>, id: impl Into<String>) -> Self {
+        McpError::NotFound {
+            entity: entity.into(),
+            id: id.into(),
+        }
     }
 
     /// Create an internal error
     pub fn internal(msg: impl Into<String>) -> Self {
         McpError::InternalError(msg.into())
     }
+
+    /// Create an unsupported protocol version error
+    pub fn unsupported_version(msg: impl Into<String>) -> Self {
+        McpError::UnsupportedProtocolVersion(msg.into())
+    }
+
+    /// Classify a failure from the browser, playback, or playlist layers
+    ///
+    /// Expired sessions, timed-out waits and playlist conflicts keep their
+    /// own categories;
+    /// anything else becomes a [`McpError::BrowserFailure`] whose full error
+    /// chain is logged under a fresh diagnostic ID, so the client gets a
+    /// short message and an ID to quote instead of a stack of contexts.
+    pub fn browser(action: &str, error: anyhow::Error) -> Self {
+        if let Some(session) = error.chain().find_map(|e| e.downcast_ref::<SessionError>()) {
+            return McpError::AuthExpired(session.to_string());
+        }
+
+        if let Some(timeout) = error.chain().find_map(|e| e.downcast_ref::<WaitTimeout>()) {
+            return McpError::Timeout {
+                operation: timeout.operation.to_string(),
+                after: Some(timeout.after),
+                step: None,
+            };
+        }
+
+        if let Some(conflict) = error
+            .chain()
+            .find_map(|e| e.downcast_ref::<PlaylistConflict>())
+        {
+            return McpError::Conflict {
+                playlist: conflict.playlist.clone(),
+                expected_revision: conflict.expected.clone(),
+                current_revision: conflict.current.clone(),
+                diff: conflict.diff.clone(),
+            };
+        }
+
+        let diagnostic_id = next_diagnostic_id();
+        tracing::error!(diagnostic_id = %diagnostic_id, "{} failed: {:?}", action, error);
+
+        McpError::BrowserFailure {
+            message: format!("{} failed", action),
+            diagnostic_id,
+        }
+    }
+}
+
+impl From<AnalysisError> for McpError {
+    fn from(error: AnalysisError) -> Self {
+        match error {
+            AnalysisError::Read { path, source }
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                McpError::not_found("file", path.display().to_string())
+            }
+            AnalysisError::Read { source, .. } => McpError::IoError(source),
+            AnalysisError::UnsupportedFormat { .. } => McpError::UnsupportedFormat {
+                message: error.to_string(),
+                supported_formats: SUPPORTED_FORMATS.iter().map(|f| f.to_string()).collect(),
+            },
+            AnalysisError::NoAudio | AnalysisError::Decode(_) => {
+                McpError::invalid_field("path", error.to_string())
+            }
+        }
+    }
+}
+
+/// Unique-per-process ID for correlating a client-visible error with the log
+fn next_diagnostic_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    format!(
+        "{:x}-{:04x}",
+        millis,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
 }
 
 /// Result type for MCP operations
@@ -106,6 +394,7 @@ pub type McpResult<T> = Result<T, McpError>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_error_to_error_object() {
@@ -118,11 +407,11 @@ mod tests {
 
     #[test]
     fn test_error_display() {
-        let error = McpError::InvalidParams("Missing field 'name'".to_string());
+        let error = McpError::invalid_field("name", "is required");
         let error_str = error.to_string();
 
         assert!(error_str.contains("Invalid parameters"));
-        assert!(error_str.contains("Missing field"));
+        assert!(error_str.contains("name: is required"));
     }
 
     #[test]
@@ -139,10 +428,343 @@ mod tests {
 
     #[test]
     fn test_error_constructors() {
-        let _ = McpError::protocol("test");
+        let _ = McpError::parse_error("test");
         let _ = McpError::invalid_request("test");
         let _ = McpError::method_not_found("test");
-        let _ = McpError::invalid_params("test");
+        let _ = McpError::invalid_field("test", "test");
+        let _ = McpError::not_found("test", "test");
         let _ = McpError::internal("test");
+        let _ = McpError::unsupported_version("test");
+    }
+
+    #[test]
+    fn test_unsupported_version_error_object() {
+        let error = McpError::unsupported_version("client speaks 2024-10-07");
+        let error_obj = error.to_error_object();
+
+        assert_eq!(error_obj.code, -32602);
+        assert!(error_obj.message.contains("Unsupported protocol version"));
+        assert!(error_obj.message.contains("2024-10-07"));
+    }
+
+    #[test]
+    fn test_every_variant_serialized_shape() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe closed");
+        let serde_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+
+        // One case per variant; a new variant without a case here fails to
+        // compile in the exhaustive match below
+        let cases = vec![
+            (
+                McpError::parse_error("bad json"),
+                -32700,
+                json!({"category": "parse_error", "recovery": "fix_request"}),
+            ),
+            (
+                McpError::invalid_request("not initialized"),
+                -32600,
+                json!({"category": "invalid_request", "recovery": "fix_request"}),
+            ),
+            (
+                McpError::method_not_found("tools/frobnicate"),
+                -32601,
+                json!({"category": "method_not_found", "recovery": "fix_request"}),
+            ),
+            (
+                McpError::unsupported_version("too old"),
+                -32602,
+                json!({"category": "unsupported_version", "recovery": "fix_request"}),
+            ),
+            (
+                McpError::Validation {
+                    field_errors: vec![
+                        FieldError::new("song_id", "is required"),
+                        FieldError::new("limit", "must be at most 100"),
+                    ],
+                },
+                -32602,
+                json!({
+                    "category": "validation",
+                    "recovery": "fix_request",
+                    "field_errors": [
+                        {"field": "song_id", "message": "is required"},
+                        {"field": "limit", "message": "must be at most 100"}
+                    ]
+                }),
+            ),
+            (
+                McpError::UnsupportedFormat {
+                    message: "Unsupported audio format (codec alac)".to_string(),
+                    supported_formats: vec!["MP3".to_string(), "FLAC".to_string()],
+                },
+                -32602,
+                json!({
+                    "category": "unsupported_format",
+                    "recovery": "fix_request",
+                    "supported_formats": ["MP3", "FLAC"]
+                }),
+            ),
+            (
+                McpError::AuthExpired("Session expired for: a@b.c".to_string()),
+                -32010,
+                json!({"category": "auth_expired", "recovery": "reauthenticate"}),
+            ),
+            (
+                McpError::RateLimited {
+                    retry_after: Some(Duration::from_secs(30)),
+                },
+                -32011,
+                json!({"category": "rate_limited", "recovery": "retry", "retry_after_secs": 30}),
+            ),
+            (
+                McpError::BrowserFailure {
+                    message: "Play song failed".to_string(),
+                    diagnostic_id: "18f2a-0001".to_string(),
+                },
+                -32012,
+                json!({
+                    "category": "browser_failure",
+                    "recovery": "retry",
+                    "diagnostic_id": "18f2a-0001"
+                }),
+            ),
+            (
+                McpError::not_found("tool", "frobnicate"),
+                -32013,
+                json!({
+                    "category": "not_found",
+                    "recovery": "give_up",
+                    "entity": "tool",
+                    "id": "frobnicate"
+                }),
+            ),
+            (
+                McpError::Timeout {
+                    operation: "navigation".to_string(),
+                    after: Some(Duration::from_secs(10)),
+                    step: Some("Open playlist".to_string()),
+                },
+                -32014,
+                json!({
+                    "category": "timeout",
+                    "recovery": "retry",
+                    "operation": "navigation",
+                    "timeout_ms": 10000,
+                    "step": "Open playlist"
+                }),
+            ),
+            (
+                McpError::Offline {
+                    last_sync: Some(1700000000),
+                },
+                -32015,
+                json!({"category": "offline", "recovery": "retry", "last_sync": 1700000000}),
+            ),
+            (
+                McpError::Conflict {
+                    playlist: "ToPlay".to_string(),
+                    expected_revision: "9f86d081884c7d65".to_string(),
+                    current_revision: "60303ae22b998861".to_string(),
+                    diff: Some(PlaylistDiff {
+                        added: vec!["s2".to_string()],
+                        removed: vec![],
+                    }),
+                },
+                -32016,
+                json!({
+                    "category": "conflict",
+                    "recovery": "refresh",
+                    "entity": "playlist",
+                    "id": "ToPlay",
+                    "current_revision": "60303ae22b998861",
+                    "diff": {"added": ["s2"], "removed": []}
+                }),
+            ),
+            (
+                McpError::internal("boom"),
+                -32603,
+                json!({"category": "internal", "recovery": "give_up"}),
+            ),
+            (
+                McpError::TransportError("closed".to_string()),
+                -32001,
+                json!({"category": "transport", "recovery": "retry"}),
+            ),
+            (
+                McpError::SerializationError(serde_error),
+                -32603,
+                json!({"category": "internal", "recovery": "give_up"}),
+            ),
+            (
+                McpError::IoError(io_error),
+                -32603,
+                json!({"category": "internal", "recovery": "give_up"}),
+            ),
+        ];
+
+        for (error, code, data) in &cases {
+            match error {
+                McpError::ParseError(_)
+                | McpError::InvalidRequest(_)
+                | McpError::MethodNotFound(_)
+                | McpError::UnsupportedProtocolVersion(_)
+                | McpError::Validation { .. }
+                | McpError::UnsupportedFormat { .. }
+                | McpError::AuthExpired(_)
+                | McpError::RateLimited { .. }
+                | McpError::BrowserFailure { .. }
+                | McpError::NotFound { .. }
+                | McpError::Timeout { .. }
+                | McpError::Offline { .. }
+                | McpError::Conflict { .. }
+                | McpError::InternalError(_)
+                | McpError::TransportError(_)
+                | McpError::SerializationError(_)
+                | McpError::IoError(_) => {}
+            }
+
+            let serialized = serde_json::to_value(error.to_error_object()).unwrap();
+            assert_eq!(serialized["code"], *code, "code for {:?}", error);
+            assert_eq!(serialized["message"], error.to_string());
+            assert_eq!(serialized["data"], *data, "data for {:?}", error);
+
+            // Clients must be able to read the payload back
+            let parsed: ErrorData = serde_json::from_value(serialized["data"].clone()).unwrap();
+            assert_eq!(parsed, error.data());
+        }
+        assert_eq!(cases.len(), 17);
+    }
+
+    #[test]
+    fn test_rate_limited_without_retry_after() {
+        let error = McpError::RateLimited { retry_after: None };
+
+        assert_eq!(error.to_string(), "Rate limited by Udio");
+        assert!(error
+            .to_error_object()
+            .data
+            .unwrap()
+            .get("retry_after_secs")
+            .is_none());
+    }
+
+    #[test]
+    fn test_timeout_display_names_step() {
+        let error = McpError::Timeout {
+            operation: "tool 'play_song'".to_string(),
+            after: Some(Duration::from_secs(90)),
+            step: Some("Play song".to_string()),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Timed out waiting for tool 'play_song' after 90s during 'Play song'"
+        );
+    }
+
+    #[test]
+    fn test_analysis_errors() {
+        let unsupported: McpError = AnalysisError::UnsupportedFormat {
+            reason: "no suitable format reader found".to_string(),
+        }
+        .into();
+        match &unsupported {
+            McpError::UnsupportedFormat {
+                message,
+                supported_formats,
+            } => {
+                assert!(message.contains("no suitable format reader found"));
+                assert!(message.contains("MP3"));
+                assert_eq!(supported_formats.len(), SUPPORTED_FORMATS.len());
+            }
+            other => panic!("Expected UnsupportedFormat, got {:?}", other),
+        }
+
+        let missing: McpError = AnalysisError::Read {
+            path: "/music/song.mp3".into(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "gone"),
+        }
+        .into();
+        assert_eq!(missing.data().entity.as_deref(), Some("file"));
+        assert_eq!(missing.data().id.as_deref(), Some("/music/song.mp3"));
+
+        let corrupt: McpError = AnalysisError::Decode("bad frame".to_string()).into();
+        assert_eq!(corrupt.data().field_errors[0].field, "path");
+    }
+
+    #[test]
+    fn test_browser_classifies_session_errors() {
+        let error = anyhow::Error::new(SessionError::Expired("a@b.c".to_string()))
+            .context("Failed to load playlist");
+
+        match McpError::browser("Get playlist", error) {
+            McpError::AuthExpired(msg) => assert!(msg.contains("a@b.c")),
+            other => panic!("Expected AuthExpired, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_browser_classifies_timeouts() {
+        let error = anyhow::Error::new(WaitTimeout {
+            operation: "navigation",
+            after: Duration::from_secs(5),
+        })
+        .context("Failed to create browser page");
+
+        match McpError::browser("Get playlist", error) {
+            McpError::Timeout {
+                operation,
+                after,
+                step,
+            } => {
+                assert_eq!(operation, "navigation");
+                assert_eq!(after, Some(Duration::from_secs(5)));
+                assert_eq!(step, None);
+            }
+            other => panic!("Expected Timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_browser_classifies_conflicts() {
+        let error = anyhow::Error::new(PlaylistConflict {
+            playlist: "ToPlay".to_string(),
+            expected: "aaaa".to_string(),
+            current: "bbbb".to_string(),
+            diff: None,
+        });
+
+        let error = McpError::browser("Add song", error);
+        assert_eq!(
+            error.to_string(),
+            "Playlist 'ToPlay' changed: it is at revision bbbb, not aaaa"
+        );
+        let data = error.data();
+        assert_eq!(data.recovery, Recovery::Refresh);
+        assert_eq!(data.current_revision.as_deref(), Some("bbbb"));
+        assert_eq!(data.diff, None);
+    }
+
+    #[test]
+    fn test_browser_failure_hides_chain_behind_diagnostic_id() {
+        let first = McpError::browser("Play song", anyhow::anyhow!("chrome crashed"));
+        let second = McpError::browser("Play song", anyhow::anyhow!("chrome crashed"));
+
+        match (&first, &second) {
+            (
+                McpError::BrowserFailure {
+                    message,
+                    diagnostic_id: a,
+                },
+                McpError::BrowserFailure {
+                    diagnostic_id: b, ..
+                },
+            ) => {
+                assert_eq!(message, "Play song failed");
+                assert_ne!(a, b);
+            }
+            _ => panic!("Expected BrowserFailure"),
+        }
+        assert!(!first.to_string().contains("chrome crashed"));
     }
 }
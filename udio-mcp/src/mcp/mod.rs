@@ -3,6 +3,8 @@
 
 /// MCP capability definitions and advertisement
 pub mod capabilities;
+/// Protocol version negotiation and compatibility layer
+pub mod compat;
 /// Error types for MCP operations
 pub mod error;
 /// MCP protocol message definitions
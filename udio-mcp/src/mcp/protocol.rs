@@ -1,7 +1,7 @@
 // MCP protocol constants and enums
 // Defines protocol-level constants, method names, and common structures
 
-/// MCP protocol version
+/// MCP protocol version (oldest revision supported; see `compat::ProtocolVersion`)
 pub const MCP_VERSION: &str = "2024-11-05";
 
 /// Standard MCP method names
@@ -7,9 +7,10 @@ use tokio::sync::RwLock;
 
 use crate::mcp::{
     capabilities::{InitializeParams, InitializeResult, ServerCapabilities, ServerInfo},
+    compat::{self, ClientSession, ProtocolVersion, VersionNegotiator},
     error::{McpError, McpResult},
-    protocol::{self, MCP_VERSION},
-    tools::ToolRegistry,
+    protocol,
+    tools::{ToolRegistry, DEFAULT_MAX_TEXT_LENGTH},
     transport::Transport,
     types::{Message, Notification, Request, RequestId, Response},
 };
@@ -27,6 +28,15 @@ pub struct McpServer {
 
     /// Whether the server has been initialized
     initialized: Arc<RwLock<bool>>,
+
+    /// Protocol version negotiation policy
+    negotiator: VersionNegotiator,
+
+    /// Client details and agreed protocol version, recorded on initialize
+    session: Arc<RwLock<Option<ClientSession>>>,
+
+    /// Cap on the markdown text of each tool result, in characters
+    max_text_length: usize,
 }
 
 impl McpServer {
@@ -37,6 +47,9 @@ impl McpServer {
             capabilities: ServerCapabilities::new().with_tools(false),
             tools: Arc::new(RwLock::new(ToolRegistry::new())),
             initialized: Arc::new(RwLock::new(false)),
+            negotiator: VersionNegotiator::new(),
+            session: Arc::new(RwLock::new(None)),
+            max_text_length: DEFAULT_MAX_TEXT_LENGTH,
         }
     }
 
@@ -47,14 +60,49 @@ impl McpServer {
             capabilities,
             tools: Arc::new(RwLock::new(ToolRegistry::new())),
             initialized: Arc::new(RwLock::new(false)),
+            negotiator: VersionNegotiator::new(),
+            session: Arc::new(RwLock::new(None)),
+            max_text_length: DEFAULT_MAX_TEXT_LENGTH,
         }
     }
 
+    /// Builder method to reject clients older than the given protocol version
+    pub fn with_min_protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.negotiator = self.negotiator.with_min_version(version);
+        self
+    }
+
+    /// Builder method to cap the markdown text of tool results
+    ///
+    /// Long track lists are cut short with an "…and N more" footer. The JSON
+    /// rendering is never truncated.
+    pub fn with_max_text_length(mut self, max_text_length: usize) -> Self {
+        self.max_text_length = max_text_length;
+        self
+    }
+
     /// Get a reference to the tool registry
     pub fn tools(&self) -> Arc<RwLock<ToolRegistry>> {
         Arc::clone(&self.tools)
     }
 
+    /// Get the client session recorded during initialization, if any
+    pub async fn client_session(&self) -> Option<ClientSession> {
+        self.session.read().await.clone()
+    }
+
+    /// Protocol version agreed with the client
+    ///
+    /// Falls back to the oldest supported revision before initialization,
+    /// which keeps responses readable by every client.
+    async fn protocol_version(&self) -> ProtocolVersion {
+        self.session
+            .read()
+            .await
+            .as_ref()
+            .map_or(ProtocolVersion::V2024_11_05, |s| s.protocol_version)
+    }
+
     /// Run the server with the given transport
     pub async fn run<T: Transport>(&self, mut transport: T) -> McpResult<()> {
         tracing::info!("Starting MCP server: {}", self.info.name);
@@ -83,7 +131,7 @@ impl McpServer {
                             // Send parse error response
                             let error_response = Response::error(
                                 RequestId::Number(0),
-                                McpError::protocol(format!("Parse error: {}", e)).to_error_object(),
+                                McpError::parse_error(e.to_string()).to_error_object(),
                             );
                             let response_str = serde_json::to_string(&error_response)?;
                             transport.send(&response_str).await?;
@@ -161,10 +209,9 @@ impl McpServer {
     /// Handle initialize request
     async fn handle_initialize(&self, params: Option<Value>) -> McpResult<Value> {
         let params: InitializeParams = match params {
-            Some(p) => serde_json::from_value(p).map_err(|e| {
-                McpError::invalid_params(format!("Invalid initialize params: {}", e))
-            })?,
-            None => return Err(McpError::invalid_params("Missing initialize params")),
+            Some(p) => serde_json::from_value(p)
+                .map_err(|e| McpError::invalid_field("params", e.to_string()))?,
+            None => return Err(McpError::invalid_field("params", "is required")),
         };
 
         tracing::info!(
@@ -173,22 +220,33 @@ impl McpServer {
             params.client_info.version
         );
 
-        // Check protocol version
-        if params.protocol_version != MCP_VERSION {
+        // Negotiate protocol version (rejects clients below the minimum)
+        let version = self.negotiator.negotiate(&params.protocol_version)?;
+        if version.to_string() != params.protocol_version {
             tracing::warn!(
-                "Protocol version mismatch: client={}, server={}",
+                "Protocol version mismatch: client={}, using={}",
                 params.protocol_version,
-                MCP_VERSION
+                version
             );
         }
 
+        *self.session.write().await = Some(ClientSession {
+            client_name: params.client_info.name,
+            client_version: params.client_info.version,
+            requested_version: params.protocol_version,
+            protocol_version: version,
+        });
+
         // Mark as initialized
         let mut initialized = self.initialized.write().await;
         *initialized = true;
 
         // Create initialize result
-        let result =
-            InitializeResult::new(MCP_VERSION, self.capabilities.clone(), self.info.clone());
+        let result = InitializeResult::new(
+            version.to_string(),
+            self.capabilities.clone(),
+            self.info.clone(),
+        );
 
         Ok(serde_json::to_value(result)?)
     }
@@ -205,21 +263,26 @@ impl McpServer {
         let tools = self.tools.read().await;
         let tool_list = tools.list();
 
-        Ok(json!({
+        let result = json!({
             "tools": tool_list
-        }))
+        });
+
+        Ok(compat::adapt_tools_list(
+            self.protocol_version().await,
+            result,
+        ))
     }
 
     /// Handle tools/call request
     async fn handle_tools_call(&self, params: Option<Value>) -> McpResult<Value> {
         self.check_initialized().await?;
 
-        let params = params.ok_or_else(|| McpError::invalid_params("Missing tool call params"))?;
+        let params = params.ok_or_else(|| McpError::invalid_field("params", "is required"))?;
 
         let tool_name = params
             .get("name")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing 'name' parameter"))?;
+            .ok_or_else(|| McpError::invalid_field("name", "is required"))?;
 
         let tool_params = params.get("arguments").cloned().unwrap_or(json!({}));
 
@@ -228,14 +291,11 @@ impl McpServer {
         let tools = self.tools.read().await;
         let result = tools.execute(tool_name, tool_params).await?;
 
-        Ok(json!({
-            "content": [
-                {
-                    "type": "text",
-                    "text": result.to_string()
-                }
-            ]
-        }))
+        Ok(compat::tool_call_result(
+            self.protocol_version().await,
+            result.as_ref(),
+            self.max_text_length,
+        ))
     }
 
     /// Check if the server has been initialized
@@ -259,9 +319,116 @@ impl Default for McpServer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mcp::tools::Tool;
+    use crate::mcp::protocol::MCP_VERSION;
+    use crate::mcp::tools::{BoxedOutput, Tool, ToolKind};
+    use crate::models::{Playlist, Song};
     use async_trait::async_trait;
 
+    // Initialize payloads captured from real clients
+    const CLAUDE_DESKTOP_INIT: &str = r#"{
+        "protocolVersion": "2024-11-05",
+        "capabilities": {},
+        "clientInfo": {"name": "claude-ai", "version": "0.1.0"}
+    }"#;
+
+    const CURSOR_INIT: &str = r#"{
+        "protocolVersion": "2025-03-26",
+        "capabilities": {
+            "tools": true,
+            "prompts": false,
+            "resources": true,
+            "logging": false,
+            "roots": {"listChanged": false}
+        },
+        "clientInfo": {"name": "cursor-vscode", "version": "1.0.0"}
+    }"#;
+
+    const MCP_INSPECTOR_INIT: &str = r#"{
+        "protocolVersion": "2025-06-18",
+        "capabilities": {
+            "sampling": {},
+            "elicitation": {},
+            "roots": {"listChanged": true}
+        },
+        "clientInfo": {"name": "mcp-inspector", "version": "0.16.2"}
+    }"#;
+
+    const LEGACY_INIT: &str = r#"{
+        "protocolVersion": "2024-10-07",
+        "capabilities": {},
+        "clientInfo": {"name": "legacy-client", "version": "0.0.1"}
+    }"#;
+
+    // Tool returning a long track list
+    struct LongPlaylistTool;
+
+    #[async_trait]
+    impl Tool for LongPlaylistTool {
+        fn name(&self) -> &str {
+            "long_playlist"
+        }
+
+        fn description(&self) -> &str {
+            "Returns a playlist with many songs"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::ReadOnly
+        }
+
+        async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+            let songs = (1..=500)
+                .map(|i| {
+                    Song::new(
+                        format!("song-{}", i),
+                        format!("Track {}", i),
+                        180,
+                        format!("https://udio.com/songs/song-{}", i),
+                    )
+                })
+                .collect();
+            Ok(Box::new(Playlist::new("pl-1", "ToPlay").with_songs(songs)))
+        }
+    }
+
+    // Tool using the newer listing fields
+    struct StructuredTool;
+
+    #[async_trait]
+    impl Tool for StructuredTool {
+        fn name(&self) -> &str {
+            "structured_tool"
+        }
+
+        fn description(&self) -> &str {
+            "A tool with structured output"
+        }
+
+        fn title(&self) -> Option<&str> {
+            Some("Structured Tool")
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object", "properties": {}})
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            Some(json!({"type": "object", "properties": {"status": {"type": "string"}}}))
+        }
+
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::ReadOnly
+        }
+
+        async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+            Ok(Box::new(json!({"status": "playing"})))
+        }
+    }
+
     // Mock tool for testing
     struct TestTool;
 
@@ -282,8 +449,12 @@ mod tests {
             })
         }
 
-        async fn execute(&self, _params: Value) -> McpResult<Value> {
-            Ok(json!({"result": "success"}))
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::ReadOnly
+        }
+
+        async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+            Ok(Box::new(json!({"result": "success"})))
         }
     }
 
@@ -682,4 +853,191 @@ mod tests {
         assert!(*server1.initialized.read().await);
         assert!(!*server2.initialized.read().await);
     }
+
+    #[tokio::test]
+    async fn test_captured_client_payloads() {
+        // (payload, client name, agreed version, structured content, tool metadata)
+        let cases = [
+            (CLAUDE_DESKTOP_INIT, "claude-ai", "2024-11-05", false, false),
+            (CURSOR_INIT, "cursor-vscode", "2025-03-26", false, false),
+            (
+                MCP_INSPECTOR_INIT,
+                "mcp-inspector",
+                "2025-06-18",
+                true,
+                true,
+            ),
+        ];
+
+        for (payload, client, version, structured, metadata) in cases {
+            let server = McpServer::new();
+            server
+                .tools()
+                .write()
+                .await
+                .register(Arc::new(StructuredTool))
+                .unwrap();
+
+            let params: Value = serde_json::from_str(payload).unwrap();
+            let init = server.handle_initialize(Some(params)).await.unwrap();
+            assert_eq!(init["protocolVersion"], version, "{}", client);
+
+            let session = server.client_session().await.unwrap();
+            assert_eq!(session.client_name, client);
+            assert_eq!(session.requested_version, version);
+            assert_eq!(session.protocol_version.to_string(), version);
+
+            let list = server.handle_tools_list().await.unwrap();
+            let tool = &list["tools"][0];
+            assert_eq!(tool.get("title").is_some(), metadata, "{}", client);
+            assert_eq!(tool.get("outputSchema").is_some(), metadata, "{}", client);
+            assert!(tool.get("inputSchema").is_some());
+
+            let call = server
+                .handle_tools_call(Some(json!({"name": "structured_tool", "arguments": {}})))
+                .await
+                .unwrap();
+            assert_eq!(
+                call.get("structuredContent").is_some(),
+                structured,
+                "{}",
+                client
+            );
+            assert_eq!(call["content"][0]["type"], "text", "{}", client);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_min_protocol_version_table() {
+        // (minimum, payload, accepted)
+        let cases = [
+            (ProtocolVersion::V2024_11_05, LEGACY_INIT, false),
+            (ProtocolVersion::V2024_11_05, CLAUDE_DESKTOP_INIT, true),
+            (ProtocolVersion::V2025_03_26, CLAUDE_DESKTOP_INIT, false),
+            (ProtocolVersion::V2025_03_26, CURSOR_INIT, true),
+            (ProtocolVersion::V2025_06_18, CURSOR_INIT, false),
+            (ProtocolVersion::V2025_06_18, MCP_INSPECTOR_INIT, true),
+        ];
+
+        for (min, payload, accepted) in cases {
+            let server = McpServer::new().with_min_protocol_version(min);
+            let params: Value = serde_json::from_str(payload).unwrap();
+            let result = server.handle_initialize(Some(params)).await;
+
+            assert_eq!(result.is_ok(), accepted, "min={} payload={}", min, payload);
+            assert_eq!(*server.initialized.read().await, accepted);
+            assert_eq!(server.client_session().await.is_some(), accepted);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_client_gets_clear_error() {
+        let server = McpServer::new().with_min_protocol_version(ProtocolVersion::V2025_03_26);
+        let params: Value = serde_json::from_str(CLAUDE_DESKTOP_INIT).unwrap();
+
+        let request = Request::new(
+            RequestId::Number(1),
+            protocol::methods::INITIALIZE,
+            Some(params),
+        );
+        let response = server.handle_request(request).await;
+
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("2024-11-05"));
+        assert!(error.message.contains("requires 2025-03-26 or newer"));
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_carry_structured_data() {
+        let server = McpServer::new();
+        let params: Value = serde_json::from_str(CLAUDE_DESKTOP_INIT).unwrap();
+        server.handle_initialize(Some(params)).await.unwrap();
+
+        // Unknown tool
+        let request = Request::new(
+            RequestId::Number(2),
+            protocol::methods::TOOLS_CALL,
+            Some(json!({"name": "frobnicate", "arguments": {}})),
+        );
+        let error = server.handle_request(request).await.error.unwrap();
+        assert_eq!(error.code, -32013);
+        assert_eq!(
+            error.data.unwrap(),
+            json!({"category": "not_found", "recovery": "give_up", "entity": "tool", "id": "frobnicate"})
+        );
+
+        // Missing tool name
+        let request = Request::new(
+            RequestId::Number(3),
+            protocol::methods::TOOLS_CALL,
+            Some(json!({"arguments": {}})),
+        );
+        let error = server.handle_request(request).await.error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert_eq!(
+            error.data.unwrap()["field_errors"],
+            json!([{"field": "name", "message": "is required"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_newer_client_gets_server_latest() {
+        let server = McpServer::new();
+        let params = json!({
+            "protocolVersion": "2026-01-01",
+            "capabilities": {},
+            "clientInfo": {"name": "future-client", "version": "9.0.0"}
+        });
+
+        let result = server.handle_initialize(Some(params)).await.unwrap();
+        assert_eq!(
+            result["protocolVersion"],
+            ProtocolVersion::latest().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_before_negotiation_is_text_only() {
+        let server = McpServer::new();
+        *server.initialized.write().await = true;
+        server
+            .tools()
+            .write()
+            .await
+            .register(Arc::new(StructuredTool))
+            .unwrap();
+
+        let params = json!({"name": "structured_tool", "arguments": {}});
+        let result = server.handle_tools_call(Some(params)).await.unwrap();
+        assert!(result.get("structuredContent").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_markdown_respects_max_text_length() {
+        for max_len in [DEFAULT_MAX_TEXT_LENGTH, 500] {
+            let server = McpServer::new().with_max_text_length(max_len);
+            *server.initialized.write().await = true;
+            server
+                .tools()
+                .write()
+                .await
+                .register(Arc::new(LongPlaylistTool))
+                .unwrap();
+
+            let params = json!({"name": "long_playlist", "arguments": {}});
+            let result = server.handle_tools_call(Some(params)).await.unwrap();
+
+            let markdown = result["content"][0]["text"].as_str().unwrap();
+            assert!(markdown.starts_with("**ToPlay** (500 songs"));
+            assert!(markdown.contains("| # | Title | Artist | Duration | Tags |"));
+            assert!(markdown.chars().count() <= max_len);
+            assert!(markdown.contains("more\n"));
+
+            // The JSON block still carries every song
+            let data: Value =
+                serde_json::from_str(result["content"][1]["text"].as_str().unwrap()).unwrap();
+            assert_eq!(data["returned_count"], 500);
+        }
+    }
 }
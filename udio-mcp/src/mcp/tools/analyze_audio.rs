@@ -0,0 +1,134 @@
+// Analyze Audio MCP Tool
+// Measures a downloaded song without sending it anywhere
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::{watchdog, BoxedOutput, Tool, ToolKind};
+use crate::analysis::{AudioAnalyzer, SUPPORTED_FORMATS};
+use crate::mcp::error::{McpError, McpResult};
+
+/// Tool to analyze a local audio file
+pub struct AnalyzeAudioTool {
+    analyzer: Arc<AudioAnalyzer>,
+}
+
+impl AnalyzeAudioTool {
+    /// Create a new analyze audio tool
+    pub fn new(analyzer: Arc<AudioAnalyzer>) -> Self {
+        Self { analyzer }
+    }
+}
+
+#[async_trait]
+impl Tool for AnalyzeAudioTool {
+    fn name(&self) -> &str {
+        "analyze_audio"
+    }
+
+    fn description(&self) -> &str {
+        "Analyze a downloaded audio file locally. Returns duration, integrated loudness (LUFS), peak level (dBFS) and an estimated tempo (BPM). Results are cached by file contents."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Analyze Audio")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": format!(
+                        "Path to the audio file on the server's machine ({})",
+                        SUPPORTED_FORMATS.join(", ")
+                    )
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "path": {"type": "string"},
+                "sha256": {"type": "string"},
+                "codec": {"type": "string"},
+                "sample_rate": {"type": "integer"},
+                "channels": {"type": "integer"},
+                "duration_seconds": {"type": "number"},
+                "duration_formatted": {"type": "string"},
+                "loudness_lufs": {"type": ["number", "null"]},
+                "peak_dbfs": {"type": ["number", "null"]},
+                "bpm": {"type": ["number", "null"]}
+            },
+            "required": ["path", "sha256", "codec", "duration_seconds"]
+        }))
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+            .ok_or_else(|| McpError::invalid_field("path", "is required"))?;
+
+        tracing::info!("Analyzing audio file: {}", path.display());
+        watchdog::step(format!("Analyze '{}'", path.display()));
+
+        // Decoding is CPU-bound; keep it off the async workers
+        let analyzer = self.analyzer.clone();
+        let analysis = tokio::task::spawn_blocking(move || analyzer.analyze(&path))
+            .await
+            .map_err(|e| McpError::internal(format!("Analysis task failed: {}", e)))??;
+
+        Ok(Box::new(analysis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_audio_tool_metadata() {
+        let tool = AnalyzeAudioTool::new(Arc::new(AudioAnalyzer::new()));
+
+        assert_eq!(tool.name(), "analyze_audio");
+        assert!(!tool.kind().is_mutating());
+        assert!(tool.output_schema().is_some());
+
+        let schema = tool.input_schema();
+        assert_eq!(schema["required"], json!(["path"]));
+        assert!(schema["properties"]["path"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("FLAC"));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_audio_requires_path() {
+        let tool = AnalyzeAudioTool::new(Arc::new(AudioAnalyzer::new()));
+
+        let result = tool.execute(json!({})).await;
+        assert!(matches!(result, Err(McpError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_audio_missing_file_is_not_found() {
+        let tool = AnalyzeAudioTool::new(Arc::new(AudioAnalyzer::new()));
+
+        let result = tool.execute(json!({"path": "/nonexistent/song.mp3"})).await;
+        assert!(matches!(result, Err(McpError::NotFound { .. })));
+    }
+}
@@ -5,11 +5,22 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
 
-use super::Tool;
+use super::{watchdog, BoxedOutput, DryRun, DryRunPlan, PlannedAction, Tool, ToolKind};
 use crate::browser::BrowserManager;
-use crate::mcp::error::McpResult;
+use crate::mcp::error::{McpError, McpResult};
 use crate::playback::PlaybackController;
 
+/// Validation error for an action outside the schema's enum
+fn invalid_action(action: &str) -> McpError {
+    McpError::invalid_field(
+        "action",
+        format!(
+            "'{}' is not one of: pause, resume, next, previous, stop",
+            action
+        ),
+    )
+}
+
 /// Tool to control playback
 pub struct ControlPlaybackTool {
     browser_manager: Arc<BrowserManager>,
@@ -39,6 +50,10 @@ impl Tool for ControlPlaybackTool {
         "Control music playback on Udio. Supports pause, resume, next, previous, and stop actions."
     }
 
+    fn title(&self) -> Option<&str> {
+        Some("Control Playback")
+    }
+
     fn input_schema(&self) -> Value {
         json!({
             "type": "object",
@@ -53,78 +68,110 @@ impl Tool for ControlPlaybackTool {
         })
     }
 
-    async fn execute(&self, params: Value) -> McpResult<Value> {
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::Mutating(self)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
         // Extract action
         let action = params
             .get("action")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| crate::mcp::error::McpError::invalid_params("action is required"))?;
+            .ok_or_else(|| McpError::invalid_field("action", "is required"))?;
 
         tracing::info!("Playback control action: {}", action);
 
         // Ensure browser is launched
-        self.browser_manager.launch().await.map_err(|e| {
-            crate::mcp::error::McpError::internal(format!("Failed to launch browser: {}", e))
-        })?;
+        watchdog::step("Launch browser");
+        self.browser_manager
+            .launch()
+            .await
+            .map_err(|e| McpError::browser("Launch browser", e))?;
 
         // Get a page
+        watchdog::step("Create page");
         let page = self
             .browser_manager
             .new_page("https://www.udio.com")
             .await
-            .map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to create page: {}", e))
-            })?;
+            .map_err(|e| McpError::browser("Create page", e))?;
 
         // Perform action
+        watchdog::step(format!("Playback action '{}'", action));
         let state = match action {
-            "pause" => self.playback_controller.pause(&page).await.map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to pause: {}", e))
-            })?,
-            "resume" => self.playback_controller.resume(&page).await.map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to resume: {}", e))
-            })?,
-            "next" => self.playback_controller.next(&page).await.map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to skip to next: {}", e))
-            })?,
+            "pause" => self
+                .playback_controller
+                .pause(&page)
+                .await
+                .map_err(|e| McpError::browser("Pause", e))?,
+            "resume" => self
+                .playback_controller
+                .resume(&page)
+                .await
+                .map_err(|e| McpError::browser("Resume", e))?,
+            "next" => self
+                .playback_controller
+                .next(&page)
+                .await
+                .map_err(|e| McpError::browser("Skip to next", e))?,
             "previous" => self
                 .playback_controller
                 .previous(&page)
                 .await
-                .map_err(|e| {
-                    crate::mcp::error::McpError::internal(format!(
-                        "Failed to go to previous: {}",
-                        e
-                    ))
-                })?,
-            "stop" => self.playback_controller.stop(&page).await.map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to stop: {}", e))
-            })?,
-            _ => {
-                return Err(crate::mcp::error::McpError::invalid_params(format!(
-                    "Invalid action: {}. Must be one of: pause, resume, next, previous, stop",
-                    action
-                )));
-            }
+                .map_err(|e| McpError::browser("Go to previous", e))?,
+            "stop" => self
+                .playback_controller
+                .stop(&page)
+                .await
+                .map_err(|e| McpError::browser("Stop", e))?,
+            _ => return Err(invalid_action(action)),
         };
 
-        // Format response
-        let response = json!({
+        Ok(Box::new(state))
+    }
+}
+
+#[async_trait]
+impl DryRun for ControlPlaybackTool {
+    async fn plan(&self, params: &Value) -> McpResult<DryRunPlan> {
+        let action = params
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::invalid_field("action", "is required"))?;
+
+        // Cached state only; reading the page would need a browser
+        let current = self
+            .playback_controller
+            .get_state()
+            .await
+            .map_err(|e| McpError::internal(format!("Failed to read playback state: {}", e)))?;
+        let song = current
+            .current_song_title
+            .as_ref()
+            .or(current.current_song_id.as_ref())
+            .map(|song| format!(" '{}'", song))
+            .unwrap_or_default();
+
+        let description = match action {
+            "pause" => format!("would pause{}", song),
+            "resume" => format!("would resume{}", song),
+            "next" => format!("would skip{} to the next song", song),
+            "previous" => "would go back to the previous song".to_string(),
+            "stop" => format!("would stop{}", song),
+            _ => return Err(invalid_action(action)),
+        };
+
+        let mut plan = DryRunPlan::new(self.name());
+        if !self.browser_manager.is_launched().await {
+            plan = plan.with_action(PlannedAction::new("would launch the browser"));
+        }
+
+        let details = json!({
             "action": action,
-            "status": "success",
-            "playback_state": {
-                "status": state.status.to_string(),
-                "position_seconds": state.position_seconds,
-                "duration_seconds": state.duration_seconds,
-                "position_formatted": state.format_position(),
-                "duration_formatted": state.format_duration(),
-                "volume": state.volume,
-                "shuffle": state.shuffle,
-                "repeat_mode": state.repeat_mode.to_string(),
-            }
+            "current_status": current.status.to_string(),
+            "current_song_id": current.current_song_id,
         });
-
-        Ok(response)
+        Ok(plan.with_action(PlannedAction::new(description).with_details(details)))
     }
 }
 
@@ -334,4 +381,28 @@ mod tests {
         assert!(valid_actions.contains(&"resume"));
         assert!(!valid_actions.contains(&"invalid"));
     }
+
+    #[tokio::test]
+    async fn test_control_playback_dry_run_describes_action() {
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        let playback_controller = Arc::new(PlaybackController::new());
+        let tool = ControlPlaybackTool::new(browser_manager.clone(), playback_controller);
+
+        let plan = tool.plan(&json!({"action": "pause"})).await.unwrap();
+
+        assert_eq!(plan.tool, "control_playback");
+        assert_eq!(plan.actions.last().unwrap().description, "would pause");
+        assert_eq!(plan.actions.last().unwrap().details["action"], "pause");
+        assert!(!browser_manager.is_launched().await);
+    }
+
+    #[tokio::test]
+    async fn test_control_playback_dry_run_rejects_invalid_action() {
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        let playback_controller = Arc::new(PlaybackController::new());
+        let tool = ControlPlaybackTool::new(browser_manager, playback_controller);
+
+        assert!(tool.plan(&json!({"action": "rewind"})).await.is_err());
+        assert!(tool.plan(&json!({})).await.is_err());
+    }
 }
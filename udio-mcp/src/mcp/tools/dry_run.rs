@@ -0,0 +1,166 @@
+// Dry-run support for mutating tools
+// Lets a client see what a tool would change before it changes anything
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::mcp::error::McpResult;
+
+/// Name of the per-call argument that requests a dry run
+pub const DRY_RUN_PARAM: &str = "dry_run";
+
+/// Whether a tool only reads state or changes it
+///
+/// Mutating tools must hand over their dry-run implementation, so a tool
+/// cannot be declared mutating without one.
+pub enum ToolKind<'a> {
+    /// Only reads state; always executed for real
+    ReadOnly,
+    /// Changes state; planned instead of executed in dry-run mode
+    Mutating(&'a dyn DryRun),
+}
+
+impl ToolKind<'_> {
+    /// Whether the tool changes state
+    pub fn is_mutating(&self) -> bool {
+        matches!(self, ToolKind::Mutating(_))
+    }
+}
+
+/// Dry-run branch of a mutating tool
+#[async_trait]
+pub trait DryRun: Send + Sync {
+    /// Validate the parameters and describe what `execute` would do
+    ///
+    /// Read-side lookups are fine; anything that changes state in the
+    /// browser or on Udio is not.
+    async fn plan(&self, params: &Value) -> McpResult<DryRunPlan>;
+}
+
+/// One change a tool would have made
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedAction {
+    /// Short description, phrased as "would …"
+    pub description: String,
+    /// Structured detail for programs
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub details: Value,
+}
+
+impl PlannedAction {
+    /// Create an action without details
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            details: Value::Null,
+        }
+    }
+
+    /// Attach structured details
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Result of a dry run: the actions a tool would have taken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunPlan {
+    /// Tool that was planned
+    pub tool: String,
+    /// Actions in the order they would run
+    pub actions: Vec<PlannedAction>,
+}
+
+impl DryRunPlan {
+    /// Create an empty plan for a tool
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            actions: Vec::new(),
+        }
+    }
+
+    /// Append an action
+    pub fn with_action(mut self, action: PlannedAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+}
+
+/// Whether the call arguments ask for a dry run
+pub fn dry_run_requested(params: &Value) -> bool {
+    params
+        .get(DRY_RUN_PARAM)
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Add the `dry_run` argument to a tool's input schema
+pub fn with_dry_run_param(mut schema: Value) -> Value {
+    if let Some(object) = schema.as_object_mut() {
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Some(properties) = properties.as_object_mut() {
+            properties.insert(
+                DRY_RUN_PARAM.to_string(),
+                serde_json::json!({
+                    "type": "boolean",
+                    "description": "Describe the actions this call would take without performing them",
+                    "default": false
+                }),
+            );
+        }
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dry_run_requested() {
+        assert!(dry_run_requested(&json!({"dry_run": true})));
+        assert!(!dry_run_requested(&json!({"dry_run": false})));
+        assert!(!dry_run_requested(&json!({"dry_run": "yes"})));
+        assert!(!dry_run_requested(&json!({})));
+        assert!(!dry_run_requested(&Value::Null));
+    }
+
+    #[test]
+    fn test_with_dry_run_param_keeps_existing_properties() {
+        let schema = with_dry_run_param(json!({
+            "type": "object",
+            "properties": {"song_id": {"type": "string"}},
+            "required": ["song_id"]
+        }));
+
+        assert!(schema["properties"]["song_id"].is_object());
+        assert_eq!(schema["properties"]["dry_run"]["type"], "boolean");
+        assert_eq!(schema["required"], json!(["song_id"]));
+    }
+
+    #[test]
+    fn test_with_dry_run_param_adds_missing_properties() {
+        let schema = with_dry_run_param(json!({"type": "object"}));
+        assert_eq!(schema["properties"]["dry_run"]["type"], "boolean");
+    }
+
+    #[test]
+    fn test_plan_serialization() {
+        let plan = DryRunPlan::new("play_song")
+            .with_action(PlannedAction::new("would play song 'abc'"))
+            .with_action(
+                PlannedAction::new("would pause playback").with_details(json!({"action": "pause"})),
+            );
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["tool"], "play_song");
+        assert!(json["actions"][0].get("details").is_none());
+        assert_eq!(json["actions"][1]["details"]["action"], "pause");
+    }
+}
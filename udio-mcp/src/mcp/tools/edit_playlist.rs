@@ -0,0 +1,244 @@
+// Edit Playlist MCP Tool
+// Adds songs to and removes songs from a Udio playlist
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::{watchdog, BoxedOutput, DryRun, DryRunPlan, PlannedAction, Tool, ToolKind};
+use crate::mcp::error::{McpError, McpResult};
+use crate::mcp::types::FieldError;
+use crate::playlist::PlaylistManager;
+
+/// Validated arguments of an edit
+struct EditParams<'a> {
+    playlist_name: &'a str,
+    add: bool,
+    song_id: &'a str,
+    expected_revision: Option<&'a str>,
+}
+
+impl<'a> EditParams<'a> {
+    /// Extract and validate the arguments, reporting every bad one at once
+    fn parse(params: &'a Value) -> McpResult<Self> {
+        let mut field_errors = Vec::new();
+
+        let add = match params.get("action").and_then(|v| v.as_str()) {
+            Some("add") => true,
+            Some("remove") => false,
+            Some(action) => {
+                field_errors.push(("action", format!("'{}' is not one of: add, remove", action)));
+                false
+            }
+            None => {
+                field_errors.push(("action", "is required".to_string()));
+                false
+            }
+        };
+        let song_id = params
+            .get("song_id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.trim().is_empty());
+        if song_id.is_none() {
+            field_errors.push(("song_id", "is required".to_string()));
+        }
+        let expected_revision = match params.get("expected_revision") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(revision)) => Some(revision.as_str()),
+            Some(_) => {
+                field_errors.push(("expected_revision", "must be a string".to_string()));
+                None
+            }
+        };
+
+        if !field_errors.is_empty() {
+            return Err(McpError::Validation {
+                field_errors: field_errors
+                    .into_iter()
+                    .map(|(field, message)| FieldError::new(field, message))
+                    .collect(),
+            });
+        }
+
+        Ok(Self {
+            playlist_name: params
+                .get("playlist_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("ToPlay"),
+            add,
+            song_id: song_id.unwrap_or_default(),
+            expected_revision,
+        })
+    }
+}
+
+/// Tool to add songs to and remove songs from a playlist
+pub struct EditPlaylistTool {
+    playlist_manager: Arc<PlaylistManager>,
+}
+
+impl EditPlaylistTool {
+    /// Create a new edit playlist tool
+    pub fn new(playlist_manager: Arc<PlaylistManager>) -> Self {
+        Self { playlist_manager }
+    }
+}
+
+#[async_trait]
+impl Tool for EditPlaylistTool {
+    fn name(&self) -> &str {
+        "edit_playlist"
+    }
+
+    fn description(&self) -> &str {
+        "Add a song to or remove a song from a Udio playlist. Pass the revision from the last playlist read as expected_revision to fail with a conflict, instead of editing, if the playlist changed since."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Edit Playlist")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "playlist_name": {
+                    "type": "string",
+                    "description": "Name of the playlist to edit",
+                    "default": "ToPlay"
+                },
+                "action": {
+                    "type": "string",
+                    "description": "Whether to add or remove the song",
+                    "enum": ["add", "remove"]
+                },
+                "song_id": {
+                    "type": "string",
+                    "description": "Unique identifier of the song"
+                },
+                "expected_revision": {
+                    "type": "string",
+                    "description": "Revision the edit is based on, as returned by list_playlist_songs or a previous edit"
+                }
+            },
+            "required": ["action", "song_id"]
+        })
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::Mutating(self)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+        let edit = EditParams::parse(&params)?;
+
+        tracing::info!(
+            "{} song {} {} playlist '{}' (expected revision: {})",
+            if edit.add { "Adding" } else { "Removing" },
+            edit.song_id,
+            if edit.add { "to" } else { "from" },
+            edit.playlist_name,
+            edit.expected_revision.unwrap_or("any")
+        );
+
+        // Waits behind any other edit of the same playlist
+        let playlist = if edit.add {
+            watchdog::step(format!("Add song to '{}'", edit.playlist_name));
+            self.playlist_manager
+                .add_song(edit.playlist_name, edit.song_id, edit.expected_revision)
+                .await
+                .map_err(|e| McpError::browser("Add song to playlist", e))?
+        } else {
+            watchdog::step(format!("Remove song from '{}'", edit.playlist_name));
+            self.playlist_manager
+                .remove_song(edit.playlist_name, edit.song_id, edit.expected_revision)
+                .await
+                .map_err(|e| McpError::browser("Remove song from playlist", e))?
+        };
+
+        Ok(Box::new(playlist))
+    }
+}
+
+#[async_trait]
+impl DryRun for EditPlaylistTool {
+    async fn plan(&self, params: &Value) -> McpResult<DryRunPlan> {
+        let edit = EditParams::parse(params)?;
+
+        let description = if edit.add {
+            format!(
+                "would add song '{}' to playlist '{}'",
+                edit.song_id, edit.playlist_name
+            )
+        } else {
+            format!(
+                "would remove song '{}' from playlist '{}'",
+                edit.song_id, edit.playlist_name
+            )
+        };
+        let mut action = PlannedAction::new(description);
+        if let Some(revision) = edit.expected_revision {
+            action = action.with_details(json!({ "expected_revision": revision }));
+        }
+
+        Ok(DryRunPlan::new(self.name()).with_action(action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{BrowserConfig, BrowserManager};
+
+    fn tool() -> EditPlaylistTool {
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        EditPlaylistTool::new(Arc::new(PlaylistManager::new(browser_manager)))
+    }
+
+    #[test]
+    fn test_edit_playlist_metadata() {
+        let tool = tool();
+        assert_eq!(tool.name(), "edit_playlist");
+        assert!(tool.kind().is_mutating());
+
+        let schema = tool.input_schema();
+        assert_eq!(schema["required"], json!(["action", "song_id"]));
+        assert!(schema["properties"]["expected_revision"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_params_are_all_reported() {
+        let error = tool()
+            .plan(&json!({"action": "shuffle", "expected_revision": 7}))
+            .await
+            .unwrap_err();
+
+        let fields: Vec<String> = error
+            .data()
+            .field_errors
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, ["action", "song_id", "expected_revision"]);
+    }
+
+    #[tokio::test]
+    async fn test_plan() {
+        let plan = tool()
+            .plan(&json!({
+                "playlist_name": "Focus",
+                "action": "remove",
+                "song_id": "s1",
+                "expected_revision": "abc"
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(plan.actions.len(), 1);
+        assert_eq!(
+            plan.actions[0].description,
+            "would remove song 's1' from playlist 'Focus'"
+        );
+        assert_eq!(plan.actions[0].details["expected_revision"], "abc");
+    }
+}
@@ -0,0 +1,152 @@
+// Get Playback State MCP Tool
+// Reports what is playing on Udio without changing it
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::{watchdog, BoxedOutput, OfflineFallback, Tool, ToolKind};
+use crate::browser::BrowserManager;
+use crate::mcp::error::{McpError, McpResult};
+use crate::offline::LibraryStore;
+use crate::playback::PlaybackController;
+
+/// Tool to read the current playback state
+pub struct GetPlaybackStateTool {
+    browser_manager: Arc<BrowserManager>,
+    playback_controller: Arc<PlaybackController>,
+    library: Option<Arc<LibraryStore>>,
+}
+
+impl GetPlaybackStateTool {
+    /// Create a new get playback state tool
+    pub fn new(
+        browser_manager: Arc<BrowserManager>,
+        playback_controller: Arc<PlaybackController>,
+    ) -> Self {
+        Self {
+            browser_manager,
+            playback_controller,
+            library: None,
+        }
+    }
+
+    /// Answer from `library` while offline
+    pub fn with_library(mut self, library: Arc<LibraryStore>) -> Self {
+        self.library = Some(library);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for GetPlaybackStateTool {
+    fn name(&self) -> &str {
+        "get_playback_state"
+    }
+
+    fn description(&self) -> &str {
+        "Get the current playback state on Udio: the song playing, position, volume, shuffle and repeat mode."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Get Playback State")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        self.library.as_ref().map(|_| self as &dyn OfflineFallback)
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        watchdog::step("Launch browser");
+        self.browser_manager
+            .launch()
+            .await
+            .map_err(|e| McpError::browser("Launch browser", e))?;
+
+        watchdog::step("Create page");
+        let page = self
+            .browser_manager
+            .new_page("https://www.udio.com")
+            .await
+            .map_err(|e| McpError::browser("Create page", e))?;
+
+        watchdog::step("Read playback state");
+        let state = self
+            .playback_controller
+            .get_current_state(&page)
+            .await
+            .map_err(|e| McpError::browser("Read playback state", e))?;
+
+        Ok(Box::new(state))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for GetPlaybackStateTool {
+    async fn execute_offline(&self, _params: &Value) -> McpResult<BoxedOutput> {
+        let library = self.library.as_ref();
+        match library.and_then(|library| library.playback()) {
+            Some(state) => Ok(Box::new(state)),
+            // Nothing to fall back on
+            None => Err(McpError::Offline {
+                last_sync: library.and_then(|library| library.last_sync()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlaybackState;
+
+    fn tool(library: Option<Arc<LibraryStore>>) -> GetPlaybackStateTool {
+        let tool = GetPlaybackStateTool::new(
+            Arc::new(BrowserManager::default()),
+            Arc::new(PlaybackController::new()),
+        );
+        match library {
+            Some(library) => tool.with_library(library),
+            None => tool,
+        }
+    }
+
+    #[test]
+    fn test_get_playback_state_metadata() {
+        let tool = tool(None);
+        assert_eq!(tool.name(), "get_playback_state");
+        assert!(!tool.kind().is_mutating());
+        assert!(tool.offline().is_none(), "no library, no fallback");
+    }
+
+    #[tokio::test]
+    async fn test_offline_state_comes_from_library() {
+        let library = Arc::new(LibraryStore::in_memory());
+        let tool = tool(Some(library.clone()));
+
+        let error = tool.offline().unwrap().execute_offline(&json!({})).await;
+        assert!(matches!(error, Err(McpError::Offline { last_sync: None })));
+
+        library
+            .record_playback(&PlaybackState::playing("s1", "Night Drive"))
+            .unwrap();
+        let output = tool
+            .offline()
+            .unwrap()
+            .execute_offline(&json!({}))
+            .await
+            .unwrap();
+        assert_eq!(output.to_json()["song_title"], "Night Drive");
+    }
+}
@@ -0,0 +1,100 @@
+// Health MCP Tool
+// Reports the startup checks to clients that can't reach an HTTP probe
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::{BoxedOutput, Tool, ToolKind};
+use crate::health::HealthState;
+use crate::mcp::error::McpResult;
+
+/// Tool reporting whether the server is ready
+pub struct HealthTool {
+    state: HealthState,
+}
+
+impl HealthTool {
+    /// Create a health tool reporting `state`
+    pub fn new(state: HealthState) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl Tool for HealthTool {
+    fn name(&self) -> &str {
+        "health"
+    }
+
+    fn description(&self) -> &str {
+        "Report whether the server is ready: the result of each startup check (config, browser, auth, cache). A degraded server rejects mutating tools until the problem is fixed."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Server Health")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "readiness": {
+                    "type": "string",
+                    "enum": ["starting", "ready", "degraded", "failed"]
+                },
+                "checks": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "outcome": {"type": "string", "enum": ["passed", "degraded", "fatal"]},
+                            "reason": {"type": "string"}
+                        },
+                        "required": ["name", "outcome"]
+                    }
+                }
+            },
+            "required": ["readiness", "checks"]
+        }))
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        Ok(Box::new(self.state.report()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::{HealthReport, Readiness};
+
+    #[tokio::test]
+    async fn test_health_tool_reports_state() {
+        let state = HealthState::new();
+        let tool = HealthTool::new(state.clone());
+        assert!(!tool.kind().is_mutating());
+
+        let result = tool.execute(json!({})).await.unwrap().to_json();
+        assert_eq!(result["readiness"], "starting");
+
+        state.set(HealthReport {
+            readiness: Readiness::Ready,
+            checks: Vec::new(),
+        });
+        let result = tool.execute(json!({})).await.unwrap();
+        assert_eq!(result.to_json()["readiness"], "ready");
+        assert!(result.to_markdown(100).starts_with("**Ready**"));
+    }
+}
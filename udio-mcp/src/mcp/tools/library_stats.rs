@@ -0,0 +1,67 @@
+// Library Stats MCP Tool
+// Totals over the synced library
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::{BoxedOutput, OfflineFallback, Tool, ToolKind};
+use crate::mcp::error::McpResult;
+use crate::offline::LibraryStore;
+
+/// Tool reporting library totals
+pub struct LibraryStatsTool {
+    library: Arc<LibraryStore>,
+}
+
+impl LibraryStatsTool {
+    /// Create a new library stats tool over `library`
+    pub fn new(library: Arc<LibraryStore>) -> Self {
+        Self { library }
+    }
+}
+
+#[async_trait]
+impl Tool for LibraryStatsTool {
+    fn name(&self) -> &str {
+        "library_stats"
+    }
+
+    fn description(&self) -> &str {
+        "Summarize your synced Udio library: playlists, songs, artists, total length, most used tags, and when it was last synced."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Library Stats")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        Some(self)
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        Ok(Box::new(self.library.stats()))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for LibraryStatsTool {
+    async fn execute_offline(&self, _params: &Value) -> McpResult<BoxedOutput> {
+        Ok(Box::new(self.library.stats()))
+    }
+
+    fn needs_udio(&self) -> bool {
+        false
+    }
+}
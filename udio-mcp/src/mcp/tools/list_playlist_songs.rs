@@ -5,20 +5,41 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
 
-use super::Tool;
-use crate::mcp::error::McpResult;
+use super::{watchdog, BoxedOutput, OfflineFallback, Tool, ToolKind};
+use crate::mcp::error::{McpError, McpResult};
+use crate::offline::LibraryStore;
 use crate::playlist::PlaylistManager;
 
 /// Tool to list songs in a playlist
 pub struct ListPlaylistSongsTool {
     playlist_manager: Arc<PlaylistManager>,
+    library: Option<Arc<LibraryStore>>,
 }
 
 impl ListPlaylistSongsTool {
     /// Create a new list playlist songs tool
     pub fn new(playlist_manager: Arc<PlaylistManager>) -> Self {
-        Self { playlist_manager }
+        Self {
+            playlist_manager,
+            library: None,
+        }
     }
+
+    /// Answer from `library` while offline
+    pub fn with_library(mut self, library: Arc<LibraryStore>) -> Self {
+        self.library = Some(library);
+        self
+    }
+}
+
+/// Playlist name and song limit arguments
+fn playlist_params(params: &Value) -> (&str, usize) {
+    let playlist_name = params
+        .get("playlist_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("ToPlay");
+    let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(50) as usize;
+    (playlist_name, limit)
 }
 
 #[async_trait]
@@ -31,6 +52,10 @@ impl Tool for ListPlaylistSongsTool {
         "List all songs in a specific Udio playlist. Returns song metadata including title, artist, duration, and tags."
     }
 
+    fn title(&self) -> Option<&str> {
+        Some("List Playlist Songs")
+    }
+
     fn input_schema(&self) -> Value {
         json!({
             "type": "object",
@@ -52,14 +77,17 @@ impl Tool for ListPlaylistSongsTool {
         })
     }
 
-    async fn execute(&self, params: Value) -> McpResult<Value> {
-        // Extract parameters
-        let playlist_name = params
-            .get("playlist_name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("ToPlay");
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
 
-        let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(50) as usize;
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        self.library.as_ref().map(|_| self as &dyn OfflineFallback)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+        // Extract parameters
+        let (playlist_name, limit) = playlist_params(&params);
 
         tracing::info!(
             "Listing songs from playlist: {} (limit: {})",
@@ -68,48 +96,33 @@ impl Tool for ListPlaylistSongsTool {
         );
 
         // Get playlist
-        let playlist = self
+        watchdog::step(format!("Load playlist '{}'", playlist_name));
+        let mut playlist = self
             .playlist_manager
             .get_playlist(playlist_name)
             .await
-            .map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to get playlist: {}", e))
-            })?;
-
-        // Extract metadata before moving songs
-        let playlist_name = playlist.name.clone();
-        let playlist_id = playlist.id.clone();
-        let song_count = playlist.song_count;
-        let total_duration_seconds = playlist.total_duration_seconds;
-        let total_duration_formatted = playlist.format_total_duration();
-
-        // Limit songs
-        let songs: Vec<_> = playlist.songs.into_iter().take(limit).collect();
-
-        // Format response
-        let response = json!({
-            "playlist": {
-                "name": playlist_name,
-                "id": playlist_id,
-                "song_count": song_count,
-                "total_duration_seconds": total_duration_seconds,
-                "total_duration_formatted": total_duration_formatted,
-            },
-            "songs": songs.iter().map(|song| {
-                json!({
-                    "id": song.id,
-                    "title": song.title,
-                    "artist": song.artist,
-                    "duration_seconds": song.duration_seconds,
-                    "duration_formatted": song.format_duration(),
-                    "url": song.url,
-                    "tags": song.tags,
-                })
-            }).collect::<Vec<_>>(),
-            "returned_count": songs.len(),
-        });
-
-        Ok(response)
+            .map_err(|e| McpError::browser("Get playlist", e))?;
+
+        // Limit songs; song_count still reports the full playlist
+        playlist.songs.truncate(limit);
+
+        Ok(Box::new(playlist))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for ListPlaylistSongsTool {
+    async fn execute_offline(&self, params: &Value) -> McpResult<BoxedOutput> {
+        let (playlist_name, limit) = playlist_params(params);
+
+        let mut playlist = self
+            .library
+            .as_ref()
+            .and_then(|library| library.playlist(playlist_name))
+            .ok_or_else(|| McpError::not_found("synced playlist", playlist_name))?;
+        playlist.songs.truncate(limit);
+
+        Ok(Box::new(playlist))
     }
 }
 
@@ -7,19 +7,54 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::health::HealthState;
 use crate::mcp::error::{McpError, McpResult};
 
 // Concrete tool implementations
+/// Analyze audio tool implementation
+pub mod analyze_audio;
 /// Control playback tool implementation
 pub mod control_playback;
+/// Dry-run planning for mutating tools
+pub mod dry_run;
+/// Edit playlist tool implementation
+pub mod edit_playlist;
+/// Health tool implementation
+pub mod health;
+/// Get playback state tool implementation
+pub mod get_playback_state;
+/// Library stats tool implementation
+pub mod library_stats;
 /// List playlist songs tool implementation
 pub mod list_playlist_songs;
 /// Play song tool implementation
 pub mod play_song;
-
+/// Search songs tool implementation
+pub mod search_songs;
+/// Suggest tracks tool implementation
+pub mod suggest_tracks;
+
+/// Routing calls to the local library while offline
+pub mod offline;
+/// Tool output rendering
+pub mod output;
+/// Per-call deadlines and recovery from stuck calls
+pub mod watchdog;
+
+pub use analyze_audio::AnalyzeAudioTool;
 pub use control_playback::ControlPlaybackTool;
+pub use dry_run::{DryRun, DryRunPlan, PlannedAction, ToolKind};
+pub use edit_playlist::EditPlaylistTool;
+pub use get_playback_state::GetPlaybackStateTool;
+pub use health::HealthTool;
+pub use library_stats::LibraryStatsTool;
 pub use list_playlist_songs::ListPlaylistSongsTool;
+pub use offline::{OfflineFallback, OfflineMode, StaleOutput};
+pub use output::{BoxedOutput, ToolOutput, DEFAULT_MAX_TEXT_LENGTH};
 pub use play_song::PlaySongTool;
+pub use search_songs::SearchSongsTool;
+pub use suggest_tracks::SuggestTracksTool;
+pub use watchdog::{StuckTaskRecovery, TimeoutCategory, TimeoutConfig};
 
 /// Tool trait that all tools must implement
 #[async_trait]
@@ -33,9 +68,46 @@ pub trait Tool: Send + Sync {
     /// Get the JSON schema for the tool's input parameters
     fn input_schema(&self) -> Value;
 
+    /// Get a human-readable display name (protocol 2025-06-18 and later)
+    fn title(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the JSON schema for the tool's structured output (protocol 2025-06-18 and later)
+    fn output_schema(&self) -> Option<Value> {
+        None
+    }
+
+    /// Whether the tool changes state
+    ///
+    /// Mutating tools return their [`DryRun`] implementation, which the
+    /// registry calls instead of `execute` when a dry run is requested.
+    fn kind(&self) -> ToolKind<'_>;
+
+    /// How a read-only tool answers while Udio is unreachable
+    ///
+    /// Tools returning `None` run as usual when offline, which suits tools
+    /// that never leave the machine; the registry rejects offline calls to
+    /// mutating tools whatever this returns.
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        None
+    }
+
+    /// Which deadline applies to the tool's calls
+    ///
+    /// Defaults to the read or mutation deadline according to [`Tool::kind`];
+    /// tools that wait on Udio to generate audio override this.
+    fn timeout_category(&self) -> TimeoutCategory {
+        if self.kind().is_mutating() {
+            TimeoutCategory::Mutation
+        } else {
+            TimeoutCategory::Read
+        }
+    }
+
     /// Execute the tool with the given parameters
-    /// Returns a JSON value as the result
-    async fn execute(&self, params: Value) -> McpResult<Value>;
+    /// Returns an output that renders as both markdown and JSON
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput>;
 }
 
 /// Tool metadata for listing
@@ -43,20 +115,38 @@ pub trait Tool: Send + Sync {
 pub struct ToolInfo {
     /// Tool name
     pub name: String,
+    /// Human-readable display name
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub title: Option<String>,
     /// Tool description
     pub description: String,
     /// JSON schema for tool input
     #[serde(rename = "inputSchema")]
     pub input_schema: Value,
+    /// JSON schema for structured tool output
+    #[serde(
+        rename = "outputSchema",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub output_schema: Option<Value>,
 }
 
 impl ToolInfo {
     /// Create ToolInfo from a Tool trait object
     pub fn from_tool(tool: &dyn Tool) -> Self {
+        let input_schema = if tool.kind().is_mutating() {
+            dry_run::with_dry_run_param(tool.input_schema())
+        } else {
+            tool.input_schema()
+        };
+
         Self {
             name: tool.name().to_string(),
+            title: tool.title().map(str::to_string),
             description: tool.description().to_string(),
-            input_schema: tool.input_schema(),
+            input_schema,
+            output_schema: tool.output_schema(),
         }
     }
 }
@@ -64,6 +154,11 @@ impl ToolInfo {
 /// Tool registry for managing available tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
+    dry_run: bool,
+    timeouts: TimeoutConfig,
+    recovery: Option<Arc<dyn StuckTaskRecovery>>,
+    health: Option<HealthState>,
+    offline: Option<OfflineMode>,
 }
 
 impl ToolRegistry {
@@ -71,9 +166,49 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            dry_run: false,
+            timeouts: TimeoutConfig::default(),
+            recovery: None,
+            health: None,
+            offline: None,
         }
     }
 
+    /// Plan every mutating call instead of executing it
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Whether every mutating call is planned instead of executed
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Set the per-category deadlines for tool calls
+    pub fn set_timeouts(&mut self, timeouts: TimeoutConfig) {
+        self.timeouts = timeouts;
+    }
+
+    /// Per-category deadlines for tool calls
+    pub fn timeouts(&self) -> &TimeoutConfig {
+        &self.timeouts
+    }
+
+    /// Set what cleans up after a call the watchdog abandons
+    pub fn set_recovery(&mut self, recovery: Arc<dyn StuckTaskRecovery>) {
+        self.recovery = Some(recovery);
+    }
+
+    /// Reject mutating calls while the startup auth check is degraded
+    pub fn set_health(&mut self, health: HealthState) {
+        self.health = Some(health);
+    }
+
+    /// Route calls to the local library whenever `mode` is offline
+    pub fn set_offline(&mut self, mode: OfflineMode) {
+        self.offline = Some(mode);
+    }
+
     /// Register a tool
     pub fn register(&mut self, tool: Arc<dyn Tool>) -> McpResult<()> {
         let name = tool.name().to_string();
@@ -103,12 +238,73 @@ impl ToolRegistry {
     }
 
     /// Execute a tool by name with the given parameters
-    pub async fn execute(&self, name: &str, params: Value) -> McpResult<Value> {
+    ///
+    /// Mutating tools are only planned when dry-run mode is on, either for
+    /// the whole registry or through the call's `dry_run` argument. Real
+    /// calls run under the watchdog and fail with [`McpError::Timeout`] once
+    /// their category's deadline passes. While the login is unusable, real
+    /// mutating calls fail with [`McpError::AuthExpired`] without running.
+    ///
+    /// While offline, real mutating calls fail with [`McpError::Offline`]
+    /// and read-only tools with an [`OfflineFallback`] answer from the
+    /// library, marked stale. Browser failures and timeouts from tools that
+    /// go to Udio count towards going offline.
+    pub async fn execute(&self, name: &str, params: Value) -> McpResult<BoxedOutput> {
         let tool = self
             .get(name)
-            .ok_or_else(|| McpError::method_not_found(name))?;
+            .ok_or_else(|| McpError::not_found("tool", name))?;
+        let offline = self
+            .offline
+            .as_ref()
+            .filter(|mode| mode.connectivity.is_offline());
+
+        if let ToolKind::Mutating(dry_run) = tool.kind() {
+            if self.dry_run || dry_run::dry_run_requested(&params) {
+                tracing::info!("Dry run: {}", name);
+                return Ok(Box::new(dry_run.plan(&params).await?));
+            }
+
+            if let Some(mode) = offline {
+                return Err(McpError::Offline {
+                    last_sync: mode.library.last_sync(),
+                });
+            }
+
+            if let Some(reason) = self.health.as_ref().and_then(HealthState::auth_expired) {
+                return Err(McpError::AuthExpired(reason));
+            }
+        }
 
-        tool.execute(params).await
+        if let (Some(mode), Some(fallback)) = (offline, tool.offline()) {
+            tracing::info!("Offline: serving {} from the local library", name);
+            let output = fallback.execute_offline(&params).await?;
+            return Ok(Box::new(StaleOutput::new(
+                output,
+                mode.library.last_sync(),
+            )));
+        }
+
+        let deadline = self.timeouts.for_category(tool.timeout_category());
+        let result = watchdog::watch(
+            name,
+            deadline,
+            self.recovery.as_deref(),
+            tool.execute(params),
+        )
+        .await;
+
+        let needs_udio =
+            tool.kind().is_mutating() || tool.offline().is_some_and(|f| f.needs_udio());
+        if let Some(mode) = self.offline.as_ref().filter(|_| needs_udio) {
+            match &result {
+                Ok(_) => mode.connectivity.record_success(),
+                Err(McpError::BrowserFailure { .. } | McpError::Timeout { .. }) => {
+                    mode.connectivity.record_failure();
+                }
+                Err(_) => {}
+            }
+        }
+        result
     }
 
     /// Get the number of registered tools
@@ -127,6 +323,7 @@ impl Default for ToolRegistry {
 mod tests {
     use super::*;
     use serde_json::json;
+    use std::time::Duration;
 
     // Mock tool for testing
     struct MockTool {
@@ -155,11 +352,126 @@ mod tests {
             })
         }
 
-        async fn execute(&self, params: Value) -> McpResult<Value> {
-            Ok(json!({
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::ReadOnly
+        }
+
+        async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+            Ok(Box::new(json!({
                 "result": "success",
                 "params": params
-            }))
+            })))
+        }
+    }
+
+    // Mutating mock that records whether it really ran
+    struct MockMutatingTool {
+        executed: std::sync::atomic::AtomicBool,
+    }
+
+    impl MockMutatingTool {
+        fn new() -> Self {
+            Self {
+                executed: std::sync::atomic::AtomicBool::new(false),
+            }
+        }
+
+        fn executed(&self) -> bool {
+            self.executed.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl Tool for MockMutatingTool {
+        fn name(&self) -> &str {
+            "mutating_tool"
+        }
+
+        fn description(&self) -> &str {
+            "A tool that changes state"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object", "properties": {"target": {"type": "string"}}})
+        }
+
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::Mutating(self)
+        }
+
+        async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+            self.executed
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(Box::new(json!({"result": "changed"})))
+        }
+    }
+
+    #[async_trait]
+    impl DryRun for MockMutatingTool {
+        async fn plan(&self, params: &Value) -> McpResult<DryRunPlan> {
+            let target = params
+                .get("target")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::invalid_field("target", "is required"))?;
+            Ok(DryRunPlan::new(self.name())
+                .with_action(PlannedAction::new(format!("would change '{}'", target))))
+        }
+    }
+
+    // Stand-in for a browser context whose tab can hang
+    #[derive(Default)]
+    struct MockContextPool {
+        stall_next: std::sync::atomic::AtomicBool,
+        healthy: std::sync::atomic::AtomicBool,
+        recycled: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl StuckTaskRecovery for MockContextPool {
+        async fn recover(&self, _tool: &str, _step: Option<&str>) {
+            // The stalled tab had to be force-closed
+            self.healthy
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    // Read tool whose page load stalls when the pool says so
+    struct StallingTool {
+        pool: Arc<MockContextPool>,
+    }
+
+    #[async_trait]
+    impl Tool for StallingTool {
+        fn name(&self) -> &str {
+            "stalling_tool"
+        }
+
+        fn description(&self) -> &str {
+            "A tool whose page load can hang"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn kind(&self) -> ToolKind<'_> {
+            ToolKind::ReadOnly
+        }
+
+        async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+            use std::sync::atomic::Ordering;
+
+            watchdog::step("Acquire context");
+            if !self.pool.healthy.swap(true, Ordering::SeqCst) {
+                self.pool.recycled.fetch_add(1, Ordering::SeqCst);
+            }
+
+            watchdog::step("Load page");
+            if self.pool.stall_next.swap(false, Ordering::SeqCst) {
+                std::future::pending::<()>().await;
+            }
+
+            Ok(Box::new(json!({"loaded": true})))
         }
     }
 
@@ -234,7 +546,8 @@ mod tests {
         let result = registry
             .execute("test_tool", json!({"param": "value"}))
             .await
-            .unwrap();
+            .unwrap()
+            .to_json();
 
         assert_eq!(result["result"], "success");
     }
@@ -245,9 +558,12 @@ mod tests {
         let result = registry.execute("nonexistent", json!({})).await;
 
         assert!(result.is_err());
-        match result.unwrap_err() {
-            McpError::MethodNotFound(_) => (),
-            _ => panic!("Expected MethodNotFound error"),
+        match result.err().unwrap() {
+            McpError::NotFound { entity, id } => {
+                assert_eq!(entity, "tool");
+                assert_eq!(id, "nonexistent");
+            }
+            _ => panic!("Expected NotFound error"),
         }
     }
 
@@ -328,16 +644,35 @@ mod tests {
     fn test_tool_info_serialization() {
         let info = ToolInfo {
             name: "test_tool".to_string(),
+            title: None,
             description: "A test tool".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {}
             }),
+            output_schema: None,
         };
 
         let json_str = serde_json::to_string(&info).unwrap();
         assert!(json_str.contains("test_tool"));
         assert!(json_str.contains("inputSchema")); // Check camelCase rename
+        assert!(!json_str.contains("title"));
+        assert!(!json_str.contains("outputSchema"));
+    }
+
+    #[test]
+    fn test_tool_info_serialization_with_metadata() {
+        let info = ToolInfo {
+            name: "test_tool".to_string(),
+            title: Some("Test Tool".to_string()),
+            description: "A test tool".to_string(),
+            input_schema: json!({"type": "object"}),
+            output_schema: Some(json!({"type": "object"})),
+        };
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["title"], "Test Tool");
+        assert!(json.get("outputSchema").is_some());
     }
 
     #[test]
@@ -363,7 +698,11 @@ mod tests {
             description: "Test tool".to_string(),
         };
 
-        let result = tool.execute(json!({"param": "test_value"})).await.unwrap();
+        let result = tool
+            .execute(json!({"param": "test_value"}))
+            .await
+            .unwrap()
+            .to_json();
         assert_eq!(result["result"], "success");
         assert_eq!(result["params"]["param"], "test_value");
     }
@@ -419,11 +758,55 @@ mod tests {
             "extra": 123
         });
 
-        let result = registry.execute("test_tool", params.clone()).await.unwrap();
+        let result = registry
+            .execute("test_tool", params.clone())
+            .await
+            .unwrap()
+            .to_json();
         assert_eq!(result["result"], "success");
         assert_eq!(result["params"], params);
     }
 
+    #[tokio::test]
+    async fn test_degraded_auth_blocks_mutating_tools() {
+        use crate::health::{CheckResult, HealthReport, Outcome, Readiness};
+
+        let health = HealthState::new();
+        health.set(HealthReport {
+            readiness: Readiness::Degraded,
+            checks: vec![CheckResult {
+                name: crate::health::AUTH_CHECK.to_string(),
+                outcome: Outcome::Degraded,
+                reason: Some("Session expired for: a@b.c".to_string()),
+            }],
+        });
+
+        let mut registry = ToolRegistry::new();
+        let mutating = Arc::new(MockMutatingTool::new());
+        registry.register(mutating.clone()).unwrap();
+        registry
+            .register(Arc::new(MockTool {
+                name: "reader".to_string(),
+                description: "Reads".to_string(),
+            }))
+            .unwrap();
+        registry.set_health(health);
+
+        let result = registry
+            .execute("mutating_tool", json!({"target": "x"}))
+            .await;
+        assert!(matches!(result, Err(McpError::AuthExpired(ref r)) if r.contains("a@b.c")));
+        assert!(!mutating.executed());
+
+        // Reads and dry runs still work
+        assert!(registry.execute("reader", json!({})).await.is_ok());
+        let plan = registry
+            .execute("mutating_tool", json!({"target": "x", "dry_run": true}))
+            .await
+            .unwrap();
+        assert_eq!(plan.to_json()["dry_run"], true);
+    }
+
     #[tokio::test]
     async fn test_tool_registry_execute_empty_params() {
         let mut registry = ToolRegistry::new();
@@ -434,7 +817,11 @@ mod tests {
 
         registry.register(tool).unwrap();
 
-        let result = registry.execute("test_tool", json!({})).await.unwrap();
+        let result = registry
+            .execute("test_tool", json!({}))
+            .await
+            .unwrap()
+            .to_json();
         assert_eq!(result["result"], "success");
         assert!(result["params"].is_object());
     }
@@ -443,8 +830,10 @@ mod tests {
     fn test_tool_info_clone() {
         let info1 = ToolInfo {
             name: "test".to_string(),
+            title: None,
             description: "Test tool".to_string(),
             input_schema: json!({"type": "object"}),
+            output_schema: None,
         };
 
         let info2 = info1.clone();
@@ -498,4 +887,206 @@ mod tests {
             handle.await.unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_dry_run_argument_plans_mutating_tool() {
+        let mut registry = ToolRegistry::new();
+        let tool = Arc::new(MockMutatingTool::new());
+        registry.register(tool.clone()).unwrap();
+
+        let result = registry
+            .execute("mutating_tool", json!({"target": "a", "dry_run": true}))
+            .await
+            .unwrap()
+            .to_json();
+
+        assert_eq!(result["dry_run"], true);
+        assert_eq!(result["actions"][0]["description"], "would change 'a'");
+        assert!(!tool.executed());
+
+        registry
+            .execute("mutating_tool", json!({"target": "a", "dry_run": false}))
+            .await
+            .unwrap();
+        assert!(tool.executed());
+    }
+
+    #[tokio::test]
+    async fn test_global_dry_run_plans_every_mutating_call() {
+        let mut registry = ToolRegistry::new();
+        let tool = Arc::new(MockMutatingTool::new());
+        registry.register(tool.clone()).unwrap();
+        registry
+            .register(Arc::new(MockTool {
+                name: "reader".to_string(),
+                description: "Read-only".to_string(),
+            }))
+            .unwrap();
+        registry.set_dry_run(true);
+        assert!(registry.dry_run());
+
+        let result = registry
+            .execute("mutating_tool", json!({"target": "a"}))
+            .await
+            .unwrap()
+            .to_json();
+        assert_eq!(result["dry_run"], true);
+        assert!(!tool.executed());
+
+        // Read-only tools still run
+        let result = registry
+            .execute("reader", json!({}))
+            .await
+            .unwrap()
+            .to_json();
+        assert_eq!(result["result"], "success");
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_still_validates() {
+        let mut registry = ToolRegistry::new();
+        let tool = Arc::new(MockMutatingTool::new());
+        registry.register(tool.clone()).unwrap();
+
+        let result = registry
+            .execute("mutating_tool", json!({"dry_run": true}))
+            .await;
+        assert!(matches!(result, Err(McpError::Validation { .. })));
+        assert!(!tool.executed());
+    }
+
+    #[test]
+    fn test_tool_info_advertises_dry_run_for_mutating_tools() {
+        let info = ToolInfo::from_tool(&MockMutatingTool::new());
+        assert_eq!(
+            info.input_schema["properties"]["dry_run"]["type"],
+            "boolean"
+        );
+
+        let info = ToolInfo::from_tool(&MockTool {
+            name: "reader".to_string(),
+            description: "Read-only".to_string(),
+        });
+        assert!(info.input_schema["properties"].get("dry_run").is_none());
+    }
+
+    // Every real mutating tool must plan without launching the browser
+    #[tokio::test]
+    async fn test_registered_mutating_tools_support_dry_run() {
+        use crate::browser::{BrowserConfig, BrowserManager};
+        use crate::playback::PlaybackController;
+        use crate::playlist::PlaylistManager;
+
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        let playback_controller = Arc::new(PlaybackController::new());
+        let playlist_manager = Arc::new(PlaylistManager::new(browser_manager.clone()));
+
+        let mut registry = ToolRegistry::new();
+        registry
+            .register(Arc::new(ListPlaylistSongsTool::new(
+                playlist_manager.clone(),
+            )))
+            .unwrap();
+        registry
+            .register(Arc::new(EditPlaylistTool::new(playlist_manager)))
+            .unwrap();
+        registry
+            .register(Arc::new(PlaySongTool::new(
+                browser_manager.clone(),
+                playback_controller.clone(),
+            )))
+            .unwrap();
+        registry
+            .register(Arc::new(ControlPlaybackTool::new(
+                browser_manager.clone(),
+                playback_controller,
+            )))
+            .unwrap();
+
+        // Minimal valid arguments for each mutating tool
+        let sample_params = |name: &str| match name {
+            "play_song" => json!({"song_id": "song-123"}),
+            "control_playback" => json!({"action": "pause"}),
+            "edit_playlist" => json!({"action": "add", "song_id": "song-123"}),
+            other => panic!("No dry-run sample arguments for mutating tool '{}'", other),
+        };
+
+        let mut mutating = 0;
+        for (name, tool) in &registry.tools {
+            if !tool.kind().is_mutating() {
+                continue;
+            }
+            mutating += 1;
+
+            let info = ToolInfo::from_tool(tool.as_ref());
+            assert!(
+                info.input_schema["properties"].get("dry_run").is_some(),
+                "{} does not advertise dry_run",
+                name
+            );
+
+            let mut params = sample_params(name);
+            params["dry_run"] = json!(true);
+            let result = registry.execute(name, params).await.unwrap().to_json();
+            assert_eq!(result["dry_run"], true, "{} did not return a plan", name);
+            assert_eq!(result["tool"], name.as_str());
+            assert!(!result["actions"].as_array().unwrap().is_empty());
+        }
+
+        assert_eq!(mutating, 3);
+        assert!(!browser_manager.is_launched().await);
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_recycles_context_after_stalled_call() {
+        use std::sync::atomic::Ordering;
+
+        let pool = Arc::new(MockContextPool::default());
+        pool.healthy.store(true, Ordering::SeqCst);
+        pool.stall_next.store(true, Ordering::SeqCst);
+
+        let mut registry = ToolRegistry::new();
+        registry.set_timeouts(
+            TimeoutConfig::new().with_timeout(TimeoutCategory::Read, Duration::from_secs(1)),
+        );
+        registry.set_recovery(pool.clone());
+        registry
+            .register(Arc::new(StallingTool { pool: pool.clone() }))
+            .unwrap();
+
+        match registry.execute("stalling_tool", json!({})).await {
+            Err(McpError::Timeout {
+                operation, step, ..
+            }) => {
+                assert_eq!(operation, "tool 'stalling_tool'");
+                assert_eq!(step.as_deref(), Some("Load page"));
+            }
+            Err(other) => panic!("Expected Timeout, got {:?}", other),
+            Ok(_) => panic!("Expected Timeout"),
+        }
+        assert!(!pool.healthy.load(Ordering::SeqCst));
+
+        let result = registry
+            .execute("stalling_tool", json!({}))
+            .await
+            .unwrap()
+            .to_json();
+        assert_eq!(result["loaded"], true);
+        assert_eq!(pool.recycled.load(Ordering::SeqCst), 1);
+        assert!(pool.healthy.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_timeout_category_follows_kind() {
+        let read = MockTool {
+            name: "read".to_string(),
+            description: "Reads".to_string(),
+        };
+
+        assert_eq!(read.timeout_category(), TimeoutCategory::Read);
+        assert_eq!(
+            MockMutatingTool::new().timeout_category(),
+            TimeoutCategory::Mutation
+        );
+    }
 }
@@ -0,0 +1,64 @@
+// Offline routing for tools
+// Lets read-only tools answer from the local library while Udio is unreachable
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+
+use super::BoxedOutput;
+use crate::mcp::error::McpResult;
+use crate::offline::{Connectivity, LibraryStore};
+
+/// Offline branch of a read-only tool
+#[async_trait]
+pub trait OfflineFallback: Send + Sync {
+    /// Answer from the local library without touching Udio
+    ///
+    /// The registry marks the result stale; the fallback returns it as is.
+    async fn execute_offline(&self, params: &Value) -> McpResult<BoxedOutput>;
+
+    /// Whether the online `execute` goes to Udio
+    ///
+    /// Tools that only ever read the library return `false`, so their calls
+    /// say nothing about whether Udio is reachable.
+    fn needs_udio(&self) -> bool {
+        true
+    }
+}
+
+/// What the registry needs to route calls while offline
+#[derive(Clone)]
+pub struct OfflineMode {
+    /// Whether Udio is reachable
+    pub connectivity: Connectivity,
+    /// Library the fallbacks read
+    pub library: Arc<LibraryStore>,
+}
+
+impl OfflineMode {
+    /// Route by `connectivity`, reporting `library`'s sync time
+    pub fn new(connectivity: Connectivity, library: Arc<LibraryStore>) -> Self {
+        Self {
+            connectivity,
+            library,
+        }
+    }
+}
+
+/// Result served from the library instead of Udio
+///
+/// Renders as the wrapped output with `stale: true` and the library's
+/// `last_sync` added, so clients can tell how old the answer is.
+pub struct StaleOutput {
+    /// The fallback's result
+    pub output: BoxedOutput,
+    /// When the library was last synced (Unix timestamp)
+    pub last_sync: Option<u64>,
+}
+
+impl StaleOutput {
+    /// Mark `output` as served from a library synced at `last_sync`
+    pub fn new(output: BoxedOutput, last_sync: Option<u64>) -> Self {
+        Self { output, last_sync }
+    }
+}
@@ -0,0 +1,903 @@
+// Tool output rendering
+// Turns tool results into markdown for people and JSON for programs
+
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::dry_run::DryRunPlan;
+use super::offline::StaleOutput;
+use crate::analysis::AudioAnalysis;
+use crate::health::{HealthReport, Outcome};
+use crate::models::{PlaybackState, Playlist, SearchResults, Song};
+use crate::offline::{Confidence, LibraryStats, Suggestions};
+
+/// Default cap on the markdown text of a tool result, in characters
+pub const DEFAULT_MAX_TEXT_LENGTH: usize = 4000;
+
+/// A tool result that can be rendered both for display and as data
+///
+/// Both renderings come from the same value, so the markdown a client shows
+/// and the JSON it parses never disagree.
+pub trait ToolOutput: Send + Sync {
+    /// Structured rendering, sent as JSON text and `structuredContent`
+    fn to_json(&self) -> Value;
+
+    /// Human-readable rendering, at most `max_len` characters
+    ///
+    /// Long lists are cut short with an "…and N more" footer.
+    fn to_markdown(&self, max_len: usize) -> String;
+}
+
+/// Boxed output returned by [`super::Tool::execute`]
+pub type BoxedOutput = Box<dyn ToolOutput>;
+
+/// Free-form JSON, for tools without a dedicated model
+impl ToolOutput for Value {
+    fn to_json(&self) -> Value {
+        self.clone()
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        const FENCE_LEN: usize = "```json\n\n```".len();
+
+        let body = serde_json::to_string_pretty(self).unwrap_or_else(|_| self.to_string());
+        format!(
+            "```json\n{}\n```",
+            truncate(&body, max_len.saturating_sub(FENCE_LEN))
+        )
+    }
+}
+
+impl ToolOutput for Song {
+    fn to_json(&self) -> Value {
+        song_json(self)
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let mut text = format!("**{}**", inline(&self.title));
+        if let Some(artist) = &self.artist {
+            text.push_str(&format!(" by {}", inline(artist)));
+        }
+        text.push_str("\n\n");
+
+        text.push_str(&format!("- Duration: {}\n", self.format_duration()));
+        if !self.tags.is_empty() {
+            text.push_str(&format!("- Tags: {}\n", inline(&self.tags.join(", "))));
+        }
+        if let Some(album) = self.metadata.as_ref().and_then(|m| m.album.as_ref()) {
+            text.push_str(&format!("- Album: {}\n", inline(album)));
+        }
+        text.push_str(&format!("- Link: <{}>\n", self.url));
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for Playlist {
+    fn to_json(&self) -> Value {
+        json!({
+            "playlist": {
+                "name": self.name,
+                "id": self.id,
+                "song_count": self.song_count,
+                "total_duration_seconds": self.total_duration_seconds,
+                "total_duration_formatted": self.format_total_duration(),
+                "revision": self.revision,
+            },
+            "songs": self.songs.iter().map(song_json).collect::<Vec<_>>(),
+            "returned_count": self.songs.len(),
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let total = self.song_count.max(self.songs.len());
+
+        let mut text = format!(
+            "**{}** ({}, {})\n\n",
+            inline(&self.name),
+            plural(total, "song"),
+            self.format_total_duration()
+        );
+        if let Some(description) = &self.description {
+            text.push_str(&format!("{}\n\n", inline(description)));
+        }
+        if !self.revision.is_empty() {
+            text.push_str(&format!("Revision: `{}`\n\n", self.revision));
+        }
+
+        push_song_table(&mut text, &self.songs, total, max_len);
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for PlaybackState {
+    fn to_json(&self) -> Value {
+        json!({
+            "status": self.status.to_string(),
+            "song_id": self.current_song_id,
+            "song_title": self.current_song_title,
+            "position_seconds": self.position_seconds,
+            "duration_seconds": self.duration_seconds,
+            "position_formatted": self.format_position(),
+            "duration_formatted": self.format_duration(),
+            "volume": self.volume,
+            "shuffle": self.shuffle,
+            "repeat_mode": self.repeat_mode.to_string(),
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let song = self
+            .current_song_title
+            .as_ref()
+            .or(self.current_song_id.as_ref());
+
+        let mut text = match song {
+            Some(song) => format!(
+                "**{}:** {} ({} / {})\n\n",
+                self.status,
+                inline(song),
+                self.format_position(),
+                self.format_duration()
+            ),
+            None => format!("**{}**\n\n", self.status),
+        };
+
+        text.push_str(&format!("- Volume: {}%\n", self.volume));
+        text.push_str(&format!(
+            "- Shuffle: {}\n",
+            if self.shuffle { "On" } else { "Off" }
+        ));
+        text.push_str(&format!("- Repeat: {}\n", self.repeat_mode));
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for SearchResults {
+    fn to_json(&self) -> Value {
+        json!({
+            "query": self.query,
+            "total": self.total,
+            "songs": self.songs.iter().map(song_json).collect::<Vec<_>>(),
+            "returned_count": self.songs.len(),
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let total = self.total.max(self.songs.len());
+        if total == 0 {
+            return truncate(
+                &format!("No songs match \"{}\".\n", inline(&self.query)),
+                max_len,
+            );
+        }
+
+        let mut text = format!(
+            "**Search results for \"{}\"** ({})\n\n",
+            inline(&self.query),
+            plural(total, "match")
+        );
+
+        push_song_table(&mut text, &self.songs, total, max_len);
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for DryRunPlan {
+    fn to_json(&self) -> Value {
+        json!({
+            "dry_run": true,
+            "tool": self.tool,
+            "actions": self.actions,
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let mut text = format!("**Dry run:** `{}` made no changes\n\n", self.tool);
+        if self.actions.is_empty() {
+            text.push_str("_Nothing to do._\n");
+        }
+        for action in &self.actions {
+            text.push_str(&format!("- {}\n", inline(&action.description)));
+        }
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for AudioAnalysis {
+    fn to_json(&self) -> Value {
+        json!({
+            "path": self.path.display().to_string(),
+            "sha256": self.sha256,
+            "codec": self.codec,
+            "sample_rate": self.sample_rate,
+            "channels": self.channels,
+            "duration_seconds": self.duration_seconds,
+            "duration_formatted": self.format_duration(),
+            "loudness_lufs": self.loudness_lufs,
+            "peak_dbfs": self.peak_dbfs,
+            "bpm": self.bpm,
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string());
+
+        let mut text = format!("**{}**\n\n", inline(&name));
+        text.push_str(&format!("- Duration: {}\n", self.format_duration()));
+        text.push_str(&format!(
+            "- Loudness: {}\n",
+            self.loudness_lufs
+                .map_or("-".to_string(), |l| format!("{:.1} LUFS", l))
+        ));
+        text.push_str(&format!(
+            "- Peak: {}\n",
+            self.peak_dbfs
+                .map_or("silent".to_string(), |p| format!("{:.1} dBFS", p))
+        ));
+        text.push_str(&format!(
+            "- Tempo: {}\n",
+            self.bpm
+                .map_or("no clear beat".to_string(), |b| format!("~{:.0} BPM", b))
+        ));
+        text.push_str(&format!(
+            "- Format: {}, {} Hz, {}\n",
+            self.codec,
+            self.sample_rate,
+            plural(self.channels, "channel")
+        ));
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for HealthReport {
+    fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let readiness = self.readiness.to_string();
+        let mut text = format!(
+            "**{}{}**\n\n",
+            readiness[..1].to_uppercase(),
+            &readiness[1..]
+        );
+        for check in &self.checks {
+            match (&check.outcome, &check.reason) {
+                (Outcome::Passed, _) | (_, None) => {
+                    text.push_str(&format!("- {}: {}\n", check.name, check.outcome))
+                }
+                (outcome, Some(reason)) => text.push_str(&format!(
+                    "- {}: {} — {}\n",
+                    check.name,
+                    outcome,
+                    inline(reason)
+                )),
+            }
+        }
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for LibraryStats {
+    fn to_json(&self) -> Value {
+        json!({
+            "playlists": self.playlists,
+            "songs": self.songs,
+            "artists": self.artists,
+            "total_duration_seconds": self.total_duration_seconds,
+            "total_duration_formatted": format_hours(self.total_duration_seconds),
+            "top_tags": self
+                .top_tags
+                .iter()
+                .map(|(tag, songs)| json!({"tag": tag, "songs": songs}))
+                .collect::<Vec<_>>(),
+            "last_sync": self.last_sync,
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let mut text = format!(
+            "**Library:** {} in {}, {}\n\n",
+            plural(self.songs, "song"),
+            plural(self.playlists, "playlist"),
+            format_hours(self.total_duration_seconds)
+        );
+        text.push_str(&format!("- Artists: {}\n", self.artists));
+        if !self.top_tags.is_empty() {
+            let tags: Vec<String> = self
+                .top_tags
+                .iter()
+                .map(|(tag, songs)| format!("{} ({})", inline(tag), songs))
+                .collect();
+            text.push_str(&format!("- Top tags: {}\n", tags.join(", ")));
+        }
+        text.push_str(&format!("- {}\n", describe_sync(self.last_sync, now())));
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for Suggestions {
+    fn to_json(&self) -> Value {
+        json!({
+            "playlist": {"id": self.playlist_id, "name": self.playlist_name},
+            "candidates": self.candidates,
+            "confidence": self.confidence,
+            "notes": self.notes,
+            "suggestions": self
+                .suggestions
+                .iter()
+                .map(|suggestion| json!({
+                    "song": song_json(&suggestion.song),
+                    "score": suggestion.score,
+                }))
+                .collect::<Vec<_>>(),
+            "returned_count": self.suggestions.len(),
+        })
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let mut text = if self.suggestions.is_empty() {
+            format!(
+                "No songs to suggest for \"{}\".\n",
+                inline(&self.playlist_name)
+            )
+        } else {
+            format!(
+                "**Suggestions for \"{}\"** ({} of {})\n",
+                inline(&self.playlist_name),
+                plural(self.suggestions.len(), "song"),
+                plural(self.candidates, "candidate")
+            )
+        };
+        if self.confidence == Confidence::Reduced {
+            text.push_str(&format!(
+                "\n_Reduced confidence: {}._\n",
+                self.notes
+                    .iter()
+                    .map(|note| inline(note))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ));
+        }
+        if self.suggestions.is_empty() {
+            return truncate(&text, max_len);
+        }
+
+        text.push_str("\n| # | Title | Artist | Score | Tag Match | Title Match | Together |\n");
+        text.push_str("|---|-------|--------|-------|-----------|-------------|----------|\n");
+        let mut length = text.chars().count();
+        for (index, suggestion) in self.suggestions.iter().enumerate() {
+            let score = &suggestion.score;
+            let row = format!(
+                "| {} | {} | {} | {:.2} | {:.2} | {:.2} | {:.2} |\n",
+                index + 1,
+                cell(&suggestion.song.title),
+                suggestion
+                    .song
+                    .artist
+                    .as_deref()
+                    .map_or("-".to_string(), cell),
+                score.total,
+                score.tag_overlap,
+                score.title_similarity,
+                score.co_occurrence
+            );
+
+            let left_after = self.suggestions.len() - index - 1;
+            let reserve = if left_after > 0 {
+                more_footer(left_after).chars().count()
+            } else {
+                0
+            };
+            let row_length = row.chars().count();
+            if length + row_length + reserve > max_len {
+                text.push_str(&more_footer(self.suggestions.len() - index));
+                break;
+            }
+
+            text.push_str(&row);
+            length += row_length;
+        }
+
+        truncate(&text, max_len)
+    }
+}
+
+impl ToolOutput for StaleOutput {
+    fn to_json(&self) -> Value {
+        let mut json = match self.output.to_json() {
+            Value::Object(map) => map,
+            other => [("result".to_string(), other)].into_iter().collect(),
+        };
+        json.insert("stale".to_string(), Value::Bool(true));
+        json.insert("last_sync".to_string(), json!(self.last_sync));
+        Value::Object(json)
+    }
+
+    fn to_markdown(&self, max_len: usize) -> String {
+        let notice = format!(
+            "_Offline: from the local library, {}._\n\n",
+            describe_sync(self.last_sync, now()).to_lowercase()
+        );
+        let body = self
+            .output
+            .to_markdown(max_len.saturating_sub(notice.chars().count()));
+        truncate(&(notice + &body), max_len)
+    }
+}
+
+/// Current Unix time in seconds
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// How long ago the library was synced, e.g. "Synced 5 minutes ago"
+fn describe_sync(last_sync: Option<u64>, now: u64) -> String {
+    let Some(last_sync) = last_sync else {
+        return "Never synced".to_string();
+    };
+
+    let age = now.saturating_sub(last_sync);
+    let ago = match age {
+        0..=59 => return "Synced just now".to_string(),
+        60..=3599 => plural((age / 60) as usize, "minute"),
+        3600..=86399 => plural((age / 3600) as usize, "hour"),
+        _ => plural((age / 86400) as usize, "day"),
+    };
+    format!("Synced {} ago", ago)
+}
+
+/// Long durations as hours and minutes, e.g. "3h 05m"
+fn format_hours(seconds: u64) -> String {
+    format!("{}h {:02}m", seconds / 3600, seconds / 60 % 60)
+}
+
+/// JSON for one song, shared by every list output
+fn song_json(song: &Song) -> Value {
+    json!({
+        "id": song.id,
+        "title": song.title,
+        "artist": song.artist,
+        "duration_seconds": song.duration_seconds,
+        "duration_formatted": song.format_duration(),
+        "url": song.url,
+        "tags": song.tags,
+    })
+}
+
+/// Append a track table, stopping early to keep `text` within `max_len`
+///
+/// `total` counts every song in the list, including any the caller did not
+/// fetch, so the footer reports everything left out.
+fn push_song_table(text: &mut String, songs: &[Song], total: usize, max_len: usize) {
+    let total = total.max(songs.len());
+    if songs.is_empty() {
+        text.push_str("_No songs._\n");
+        return;
+    }
+
+    text.push_str("| # | Title | Artist | Duration | Tags |\n");
+    text.push_str("|---|-------|--------|----------|------|\n");
+
+    let mut length = text.chars().count();
+    for (index, song) in songs.iter().enumerate() {
+        let row = format!(
+            "| {} | {} | {} | {} | {} |\n",
+            index + 1,
+            cell(&song.title),
+            song.artist.as_deref().map_or("-".to_string(), cell),
+            song.format_duration(),
+            cell(&song.tags.join(", "))
+        );
+
+        // Leave room for the footer of whatever would still be missing
+        let left_after = total.saturating_sub(index + 1);
+        let reserve = if left_after > 0 {
+            more_footer(left_after).chars().count()
+        } else {
+            0
+        };
+
+        let row_length = row.chars().count();
+        if length + row_length + reserve > max_len {
+            text.push_str(&more_footer(total.saturating_sub(index)));
+            return;
+        }
+
+        text.push_str(&row);
+        length += row_length;
+    }
+
+    if total > songs.len() {
+        text.push_str(&more_footer(total - songs.len()));
+    }
+}
+
+fn more_footer(count: usize) -> String {
+    format!("\n…and {} more\n", count)
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match (count, noun) {
+        (1, _) => format!("1 {}", noun),
+        (_, "match") => format!("{} matches", count),
+        _ => format!("{} {}s", count, noun),
+    }
+}
+
+/// Text safe inside a table cell
+fn cell(text: &str) -> String {
+    inline(text).replace('|', "\\|")
+}
+
+/// Text safe on a single markdown line
+fn inline(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Hard limit for text that cannot be shortened more gracefully
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+
+    let mut cut: String = text.chars().take(max_len.saturating_sub(1)).collect();
+    if max_len > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PlaybackStatus, RepeatMode};
+
+    fn song(id: u32, title: &str, artist: Option<&str>, seconds: u64, tags: &[&str]) -> Song {
+        let song = Song::new(
+            format!("s{}", id),
+            title,
+            seconds,
+            format!("https://www.udio.com/songs/s{}", id),
+        )
+        .with_tags(tags.iter().map(|t| t.to_string()).collect());
+
+        match artist {
+            Some(artist) => song.with_artist(artist),
+            None => song,
+        }
+    }
+
+    fn playlist(count: u32) -> Playlist {
+        let songs = (1..=count)
+            .map(|i| song(i, &format!("Track {}", i), Some("Nova"), 125, &["lofi"]))
+            .collect();
+        Playlist::new("p1", "ToPlay").with_songs(songs)
+    }
+
+    #[test]
+    fn test_song_markdown_snapshot() {
+        let song = song(1, "Neon | Rain", Some("Nova"), 205, &["synthwave", "night"]);
+
+        assert_eq!(
+            song.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "**Neon | Rain** by Nova\n\
+             \n\
+             - Duration: 03:25\n\
+             - Tags: synthwave, night\n\
+             - Link: <https://www.udio.com/songs/s1>\n"
+        );
+    }
+
+    #[test]
+    fn test_playlist_markdown_snapshot() {
+        let playlist = Playlist::new("p1", "Late Night")
+            .with_description("For\ncoding")
+            .with_songs(vec![
+                song(1, "Neon | Rain", Some("Nova"), 205, &["synthwave"]),
+                song(2, "Untitled", None, 90, &[]),
+            ]);
+
+        assert_eq!(
+            playlist.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            format!(
+                "**Late Night** (2 songs, 04:55)\n\
+             \n\
+             For coding\n\
+             \n\
+             Revision: `{}`\n\
+             \n\
+             | # | Title | Artist | Duration | Tags |\n\
+             |---|-------|--------|----------|------|\n\
+             | 1 | Neon \\| Rain | Nova | 03:25 | synthwave |\n\
+             | 2 | Untitled | - | 01:30 |  |\n",
+                playlist.revision
+            )
+        );
+    }
+
+    #[test]
+    fn test_playback_markdown_snapshot() {
+        let state = PlaybackState::playing("s1", "Neon Rain")
+            .with_position(62, 205)
+            .with_volume(80)
+            .with_repeat_mode(RepeatMode::All);
+
+        assert_eq!(
+            state.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "**Playing:** Neon Rain (01:02 / 03:25)\n\
+             \n\
+             - Volume: 80%\n\
+             - Shuffle: Off\n\
+             - Repeat: Repeat All\n"
+        );
+
+        let mut stopped = PlaybackState::new();
+        stopped.status = PlaybackStatus::Stopped;
+        assert!(stopped
+            .to_markdown(DEFAULT_MAX_TEXT_LENGTH)
+            .starts_with("**Stopped**\n\n"));
+    }
+
+    #[test]
+    fn test_search_markdown_snapshot() {
+        let results = SearchResults::new(
+            "rain",
+            vec![song(1, "Neon Rain", Some("Nova"), 205, &["synthwave"])],
+        )
+        .with_total(12);
+
+        assert_eq!(
+            results.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "**Search results for \"rain\"** (12 matches)\n\
+             \n\
+             | # | Title | Artist | Duration | Tags |\n\
+             |---|-------|--------|----------|------|\n\
+             | 1 | Neon Rain | Nova | 03:25 | synthwave |\n\
+             \n\
+             …and 11 more\n"
+        );
+
+        let empty = SearchResults::new("silence", Vec::new());
+        assert_eq!(
+            empty.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "No songs match \"silence\".\n"
+        );
+    }
+
+    #[test]
+    fn test_long_playlist_is_truncated_with_footer() {
+        let long = playlist(200);
+
+        for max_len in [300, 1000, 4000] {
+            let text = long.to_markdown(max_len);
+            assert!(
+                text.chars().count() <= max_len,
+                "{} > {}",
+                text.len(),
+                max_len
+            );
+
+            let shown = text
+                .lines()
+                .filter(|l| l.starts_with("| ") && !l.starts_with("| #"))
+                .count();
+            assert!(shown > 0);
+            assert!(
+                text.ends_with(&format!("\n…and {} more\n", 200 - shown)),
+                "{}",
+                text
+            );
+        }
+
+        // Short lists are never cut
+        assert!(!playlist(3)
+            .to_markdown(DEFAULT_MAX_TEXT_LENGTH)
+            .contains("more"));
+    }
+
+    #[test]
+    fn test_footer_counts_songs_not_fetched() {
+        let mut playlist = playlist(5);
+        playlist.songs.truncate(2);
+
+        let text = playlist.to_markdown(DEFAULT_MAX_TEXT_LENGTH);
+        assert!(text.starts_with("**ToPlay** (5 songs,"));
+        assert!(text.ends_with("\n…and 3 more\n"));
+    }
+
+    #[test]
+    fn test_tiny_limit_is_still_respected() {
+        let text = playlist(50).to_markdown(20);
+        assert!(text.chars().count() <= 20);
+        assert!(text.ends_with('…'));
+    }
+
+    #[test]
+    fn test_json_and_markdown_share_a_model() {
+        let playlist = playlist(3);
+        let json = playlist.to_json();
+
+        assert_eq!(json["playlist"]["song_count"], 3);
+        assert_eq!(json["playlist"]["revision"], playlist.revision);
+        assert_eq!(json["returned_count"], 3);
+        assert_eq!(json["songs"][0]["title"], "Track 1");
+        assert_eq!(json["songs"][0]["duration_formatted"], "02:05");
+        assert!(playlist
+            .to_markdown(DEFAULT_MAX_TEXT_LENGTH)
+            .contains("| 1 | Track 1 | Nova | 02:05 | lofi |"));
+
+        let state = PlaybackState::playing("s1", "Track 1").with_position(10, 125);
+        assert_eq!(state.to_json()["status"], "Playing");
+        assert_eq!(state.to_json()["song_title"], "Track 1");
+
+        let single = song(7, "Solo", None, 60, &[]);
+        assert_eq!(single.to_json()["artist"], Value::Null);
+        assert_eq!(single.to_json()["url"], "https://www.udio.com/songs/s7");
+    }
+
+    #[test]
+    fn test_plain_json_renders_as_code_block() {
+        let value = json!({"result": "success"});
+        assert_eq!(value.to_json(), value);
+        assert_eq!(
+            value.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "```json\n{\n  \"result\": \"success\"\n}\n```"
+        );
+        assert!(
+            json!({"long": "x".repeat(500)})
+                .to_markdown(100)
+                .chars()
+                .count()
+                <= 100
+        );
+    }
+
+    #[test]
+    fn test_audio_analysis_markdown_snapshot() {
+        let analysis = AudioAnalysis {
+            path: "/music/Neon Rain.mp3".into(),
+            sha256: "ab12".to_string(),
+            codec: "mp3".to_string(),
+            sample_rate: 44_100,
+            channels: 2,
+            duration_seconds: 205.2,
+            loudness_lufs: Some(-9.84),
+            peak_dbfs: Some(-0.31),
+            bpm: Some(127.6),
+        };
+
+        assert_eq!(
+            analysis.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "**Neon Rain.mp3**\n\
+             \n\
+             - Duration: 03:25\n\
+             - Loudness: -9.8 LUFS\n\
+             - Peak: -0.3 dBFS\n\
+             - Tempo: ~128 BPM\n\
+             - Format: mp3, 44100 Hz, 2 channels\n"
+        );
+
+        let json = analysis.to_json();
+        assert_eq!(json["path"], "/music/Neon Rain.mp3");
+        assert_eq!(json["duration_formatted"], "03:25");
+        assert_eq!(json["bpm"], 127.6);
+
+        let silent = AudioAnalysis {
+            loudness_lufs: None,
+            peak_dbfs: None,
+            bpm: None,
+            ..analysis
+        };
+        assert_eq!(silent.to_json()["bpm"], Value::Null);
+        let text = silent.to_markdown(DEFAULT_MAX_TEXT_LENGTH);
+        assert!(text.contains("- Peak: silent\n"));
+        assert!(text.contains("- Tempo: no clear beat\n"));
+    }
+
+    #[test]
+    fn test_dry_run_plan_lists_actions() {
+        use crate::mcp::tools::dry_run::PlannedAction;
+
+        let plan = DryRunPlan::new("control_playback")
+            .with_action(PlannedAction::new("would pause 'Track 1'"));
+
+        let json = plan.to_json();
+        assert_eq!(json["dry_run"], true);
+        assert_eq!(json["tool"], "control_playback");
+        assert_eq!(json["actions"][0]["description"], "would pause 'Track 1'");
+
+        let text = plan.to_markdown(DEFAULT_MAX_TEXT_LENGTH);
+        assert!(text.starts_with("**Dry run:** `control_playback` made no changes"));
+        assert!(text.contains("- would pause 'Track 1'\n"));
+    }
+
+    #[test]
+    fn test_suggestions_markdown_snapshot() {
+        use crate::offline::{ScoreBreakdown, Suggestion};
+
+        let suggestions = Suggestions {
+            playlist_id: "p1".to_string(),
+            playlist_name: "Late Night".to_string(),
+            suggestions: vec![Suggestion {
+                song: song(2, "Neon | Rain", Some("Nova"), 205, &["synthwave"]),
+                score: ScoreBreakdown {
+                    tag_overlap: 1.0,
+                    title_similarity: 0.0,
+                    co_occurrence: 0.5,
+                    total: 0.6,
+                },
+            }],
+            candidates: 4,
+            confidence: Confidence::Reduced,
+            notes: vec!["Based on a single song".to_string()],
+        };
+
+        assert_eq!(
+            suggestions.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "**Suggestions for \"Late Night\"** (1 song of 4 candidates)\n\
+             \n\
+             _Reduced confidence: Based on a single song._\n\
+             \n\
+             | # | Title | Artist | Score | Tag Match | Title Match | Together |\n\
+             |---|-------|--------|-------|-----------|-------------|----------|\n\
+             | 1 | Neon \\| Rain | Nova | 0.60 | 1.00 | 0.00 | 0.50 |\n"
+        );
+
+        let json = suggestions.to_json();
+        assert_eq!(json["suggestions"][0]["score"]["total"], 0.6);
+        assert_eq!(json["notes"][0], "Based on a single song");
+
+        let none = Suggestions {
+            suggestions: Vec::new(),
+            confidence: Confidence::Normal,
+            notes: Vec::new(),
+            ..suggestions
+        };
+        assert_eq!(
+            none.to_markdown(DEFAULT_MAX_TEXT_LENGTH),
+            "No songs to suggest for \"Late Night\".\n"
+        );
+    }
+
+    #[test]
+    fn test_stale_output_marks_json_and_markdown() {
+        let stale = StaleOutput::new(Box::new(playlist(2)), Some(1_700_000_000));
+
+        let json = stale.to_json();
+        assert_eq!(json["stale"], true);
+        assert_eq!(json["last_sync"], 1_700_000_000);
+        assert_eq!(json["returned_count"], 2);
+
+        let text = stale.to_markdown(DEFAULT_MAX_TEXT_LENGTH);
+        assert!(text.starts_with("_Offline: from the local library, synced "));
+        assert!(text.contains("**ToPlay** (2 songs"));
+
+        // Non-object results are wrapped rather than lost
+        let wrapped = StaleOutput::new(Box::new(json!([1, 2])), None).to_json();
+        assert_eq!(wrapped["result"], json!([1, 2]));
+        assert_eq!(wrapped["last_sync"], Value::Null);
+    }
+
+    #[test]
+    fn test_describe_sync() {
+        assert_eq!(describe_sync(None, 1000), "Never synced");
+        assert_eq!(describe_sync(Some(990), 1000), "Synced just now");
+        assert_eq!(describe_sync(Some(1000), 1000 + 5 * 60), "Synced 5 minutes ago");
+        assert_eq!(describe_sync(Some(1000), 1000 + 3600), "Synced 1 hour ago");
+        assert_eq!(describe_sync(Some(1000), 1000 + 3 * 86400), "Synced 3 days ago");
+    }
+}
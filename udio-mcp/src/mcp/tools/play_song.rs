@@ -5,9 +5,9 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
 
-use super::Tool;
+use super::{watchdog, BoxedOutput, DryRun, DryRunPlan, PlannedAction, Tool, ToolKind};
 use crate::browser::BrowserManager;
-use crate::mcp::error::McpResult;
+use crate::mcp::error::{McpError, McpResult};
 use crate::playback::PlaybackController;
 
 /// Tool to play a specific song
@@ -16,6 +16,15 @@ pub struct PlaySongTool {
     playback_controller: Arc<PlaybackController>,
 }
 
+/// Extract and validate the song ID argument
+fn song_id(params: &Value) -> McpResult<&str> {
+    params
+        .get("song_id")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| McpError::invalid_field("song_id", "is required"))
+}
+
 impl PlaySongTool {
     /// Create a new play song tool
     pub fn new(
@@ -39,6 +48,10 @@ impl Tool for PlaySongTool {
         "Play a specific song on Udio by its ID. Starts playback of the requested song."
     }
 
+    fn title(&self) -> Option<&str> {
+        Some("Play Song")
+    }
+
     fn input_schema(&self) -> Value {
         json!({
             "type": "object",
@@ -52,55 +65,74 @@ impl Tool for PlaySongTool {
         })
     }
 
-    async fn execute(&self, params: Value) -> McpResult<Value> {
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::Mutating(self)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
         // Extract song ID
-        let song_id = params
-            .get("song_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| crate::mcp::error::McpError::invalid_params("song_id is required"))?;
+        let song_id = song_id(&params)?;
 
         tracing::info!("Playing song: {}", song_id);
 
         // Ensure browser is launched
-        self.browser_manager.launch().await.map_err(|e| {
-            crate::mcp::error::McpError::internal(format!("Failed to launch browser: {}", e))
-        })?;
+        watchdog::step("Launch browser");
+        self.browser_manager
+            .launch()
+            .await
+            .map_err(|e| McpError::browser("Launch browser", e))?;
 
         // Get a page (simplified - in real implementation would navigate to song)
+        watchdog::step("Create page");
         let page = self
             .browser_manager
             .new_page("https://www.udio.com")
             .await
-            .map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to create page: {}", e))
-            })?;
+            .map_err(|e| McpError::browser("Create page", e))?;
 
         // Play the song
+        watchdog::step("Play song");
         let state = self
             .playback_controller
             .play_song(&page, song_id)
             .await
-            .map_err(|e| {
-                crate::mcp::error::McpError::internal(format!("Failed to play song: {}", e))
-            })?;
-
-        // Format response
-        let response = json!({
-            "status": "playing",
-            "song_id": song_id,
-            "playback_state": {
-                "status": state.status.to_string(),
-                "position_seconds": state.position_seconds,
-                "duration_seconds": state.duration_seconds,
-                "position_formatted": state.format_position(),
-                "duration_formatted": state.format_duration(),
-                "volume": state.volume,
-                "shuffle": state.shuffle,
-                "repeat_mode": state.repeat_mode.to_string(),
-            }
-        });
-
-        Ok(response)
+            .map_err(|e| McpError::browser("Play song", e))?;
+
+        Ok(Box::new(state))
+    }
+}
+
+#[async_trait]
+impl DryRun for PlaySongTool {
+    async fn plan(&self, params: &Value) -> McpResult<DryRunPlan> {
+        let song_id = song_id(params)?;
+        let mut plan = DryRunPlan::new(self.name());
+
+        if !self.browser_manager.is_launched().await {
+            plan = plan.with_action(PlannedAction::new("would launch the browser"));
+        }
+
+        // Cached state only; reading the page would need a browser
+        let current = self
+            .playback_controller
+            .get_state()
+            .await
+            .map_err(|e| McpError::internal(format!("Failed to read playback state: {}", e)))?;
+        let description = match current.current_song_title.as_ref() {
+            Some(title) if current.is_playing() => format!(
+                "would replace '{}' with song '{}' and start playback",
+                title, song_id
+            ),
+            _ => format!("would start playback of song '{}'", song_id),
+        };
+
+        Ok(
+            plan.with_action(PlannedAction::new(description).with_details(json!({
+                "action": "play",
+                "song_id": song_id,
+                "replaces_song_id": current.current_song_id,
+            }))),
+        )
     }
 }
 
@@ -252,4 +284,34 @@ mod tests {
         let _tool2 = PlaySongTool::new(browser_clone, controller_clone);
         // Verify Arc components can be shared across tools
     }
+
+    #[tokio::test]
+    async fn test_play_song_dry_run_does_not_launch_browser() {
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        let playback_controller = Arc::new(PlaybackController::new());
+        let tool = PlaySongTool::new(browser_manager.clone(), playback_controller);
+
+        let plan = tool.plan(&json!({"song_id": "song-123"})).await.unwrap();
+
+        assert_eq!(plan.tool, "play_song");
+        assert_eq!(plan.actions.len(), 2);
+        assert_eq!(plan.actions[0].description, "would launch the browser");
+        assert_eq!(
+            plan.actions[1].description,
+            "would start playback of song 'song-123'"
+        );
+        assert_eq!(plan.actions[1].details["song_id"], "song-123");
+        assert!(!browser_manager.is_launched().await);
+    }
+
+    #[tokio::test]
+    async fn test_play_song_dry_run_validates_song_id() {
+        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
+        let playback_controller = Arc::new(PlaybackController::new());
+        let tool = PlaySongTool::new(browser_manager, playback_controller);
+
+        assert!(tool.plan(&json!({})).await.is_err());
+        assert!(tool.plan(&json!({"song_id": "  "})).await.is_err());
+        assert!(tool.kind().is_mutating());
+    }
 }
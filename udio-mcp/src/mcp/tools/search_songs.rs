@@ -0,0 +1,115 @@
+// Search Songs MCP Tool
+// Finds songs in the synced library by title, artist or tag
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::{BoxedOutput, OfflineFallback, Tool, ToolKind};
+use crate::mcp::error::{McpError, McpResult};
+use crate::offline::LibraryStore;
+
+/// Tool to search the synced library
+pub struct SearchSongsTool {
+    library: Arc<LibraryStore>,
+}
+
+impl SearchSongsTool {
+    /// Create a new search songs tool over `library`
+    pub fn new(library: Arc<LibraryStore>) -> Self {
+        Self { library }
+    }
+}
+
+#[async_trait]
+impl Tool for SearchSongsTool {
+    fn name(&self) -> &str {
+        "search_songs"
+    }
+
+    fn description(&self) -> &str {
+        "Search songs in your synced Udio playlists by title, artist, or tag. Only playlists listed before are searched."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Search Songs")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Text to look for in titles, artists and tags"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of songs to return",
+                    "minimum": 1,
+                    "maximum": 100,
+                    "default": 20
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        Some(self)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+        let query = params
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .ok_or_else(|| McpError::invalid_field("query", "is required"))?;
+        let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+
+        tracing::info!("Searching library for: {} (limit: {})", query, limit);
+        Ok(Box::new(self.library.search(query, limit)))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for SearchSongsTool {
+    async fn execute_offline(&self, params: &Value) -> McpResult<BoxedOutput> {
+        self.execute(params.clone()).await
+    }
+
+    fn needs_udio(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Playlist, Song};
+
+    #[tokio::test]
+    async fn test_search_songs() {
+        let library = Arc::new(LibraryStore::in_memory());
+        library
+            .record_playlist(&Playlist::new("p1", "Chill").with_songs(vec![
+                Song::new("1", "Night Drive", 200, "https://udio.com/songs/1"),
+                Song::new("2", "Morning Tea", 150, "https://udio.com/songs/2"),
+            ]))
+            .unwrap();
+        let tool = SearchSongsTool::new(library);
+
+        let result = tool.execute(json!({"query": "night"})).await.unwrap();
+        let json = result.to_json();
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["songs"][0]["id"], "1");
+
+        let missing = tool.execute(json!({"query": "  "})).await;
+        assert!(matches!(missing, Err(McpError::Validation { .. })));
+    }
+}
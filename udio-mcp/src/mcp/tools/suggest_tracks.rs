@@ -0,0 +1,135 @@
+// Suggest Tracks MCP Tool
+// Recommends songs from the synced library for a playlist
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use super::{BoxedOutput, OfflineFallback, Tool, ToolKind};
+use crate::mcp::error::{McpError, McpResult};
+use crate::offline::LibraryStore;
+
+/// Tool suggesting library songs to add to a playlist
+pub struct SuggestTracksTool {
+    library: Arc<LibraryStore>,
+}
+
+impl SuggestTracksTool {
+    /// Create a new suggest tracks tool over `library`
+    pub fn new(library: Arc<LibraryStore>) -> Self {
+        Self { library }
+    }
+}
+
+#[async_trait]
+impl Tool for SuggestTracksTool {
+    fn name(&self) -> &str {
+        "suggest_tracks"
+    }
+
+    fn description(&self) -> &str {
+        "Suggest songs from your synced Udio library that a playlist doesn't have yet, ranked by shared tags, similar titles, and how often they appear together in your other playlists. Each suggestion comes with its per-factor scores. Only playlists listed before are used."
+    }
+
+    fn title(&self) -> Option<&str> {
+        Some("Suggest Tracks")
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "playlist_id": {
+                    "type": "string",
+                    "description": "ID (or name) of the synced playlist to suggest songs for"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of suggestions to return",
+                    "minimum": 1,
+                    "maximum": 50,
+                    "default": 10
+                }
+            },
+            "required": ["playlist_id"]
+        })
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        Some(self)
+    }
+
+    async fn execute(&self, params: Value) -> McpResult<BoxedOutput> {
+        let playlist = params
+            .get("playlist_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|playlist| !playlist.is_empty())
+            .ok_or_else(|| McpError::invalid_field("playlist_id", "is required"))?;
+        let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+
+        tracing::info!(
+            "Suggesting songs for playlist: {} (limit: {})",
+            playlist,
+            limit
+        );
+        let suggestions = self
+            .library
+            .suggest(playlist, limit)
+            .ok_or_else(|| McpError::not_found("synced playlist", playlist))?;
+        Ok(Box::new(suggestions))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for SuggestTracksTool {
+    async fn execute_offline(&self, params: &Value) -> McpResult<BoxedOutput> {
+        self.execute(params.clone()).await
+    }
+
+    fn needs_udio(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Playlist, Song};
+
+    #[tokio::test]
+    async fn test_suggest_tracks() {
+        let library = Arc::new(LibraryStore::in_memory());
+        library
+            .record_playlist(&Playlist::new("p1", "Chill").with_songs(vec![Song::new(
+                "1",
+                "Night Drive",
+                200,
+                "https://udio.com/songs/1",
+            )]))
+            .unwrap();
+        library
+            .record_playlist(&Playlist::new("p2", "Favorites").with_songs(vec![
+                Song::new("1", "Night Drive", 200, "https://udio.com/songs/1"),
+                Song::new("2", "Morning Tea", 150, "https://udio.com/songs/2"),
+            ]))
+            .unwrap();
+        let tool = SuggestTracksTool::new(library);
+
+        let result = tool.execute(json!({"playlist_id": "p1"})).await.unwrap();
+        let json = result.to_json();
+        assert_eq!(json["playlist"]["name"], "Chill");
+        assert_eq!(json["suggestions"][0]["song"]["id"], "2");
+        assert_eq!(json["suggestions"][0]["score"]["co_occurrence"], 1.0);
+        assert_eq!(json["confidence"], "reduced");
+
+        let missing = tool.execute(json!({"playlist_id": "p9"})).await;
+        assert!(matches!(missing, Err(McpError::NotFound { .. })));
+        let blank = tool.execute(json!({"playlist_id": ""})).await;
+        assert!(matches!(blank, Err(McpError::Validation { .. })));
+    }
+}
@@ -0,0 +1,289 @@
+// Watchdog for tool execution
+// Stops a hung page load from wedging a tool call forever
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::BoxedOutput;
+use crate::browser::BrowserManager;
+use crate::mcp::error::{McpError, McpResult};
+
+tokio::task_local! {
+    static CURRENT_STEP: Arc<Mutex<Option<String>>>;
+}
+
+/// How long a tool may run, by what it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutCategory {
+    /// Reads state: listing, searching
+    Read,
+    /// Changes state: playback, playlist edits
+    Mutation,
+    /// Waits on Udio to generate audio
+    Generation,
+}
+
+impl fmt::Display for TimeoutCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutCategory::Read => write!(f, "read"),
+            TimeoutCategory::Mutation => write!(f, "mutation"),
+            TimeoutCategory::Generation => write!(f, "generation"),
+        }
+    }
+}
+
+/// Per-category deadlines for tool calls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeoutConfig {
+    /// Seconds allowed for read tools
+    #[serde(default = "default_read_secs")]
+    pub read_secs: u64,
+
+    /// Seconds allowed for mutating tools
+    #[serde(default = "default_mutation_secs")]
+    pub mutation_secs: u64,
+
+    /// Seconds allowed for generation tools
+    #[serde(default = "default_generation_secs")]
+    pub generation_secs: u64,
+}
+
+fn default_read_secs() -> u64 {
+    30
+}
+
+fn default_mutation_secs() -> u64 {
+    90
+}
+
+fn default_generation_secs() -> u64 {
+    600
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            read_secs: default_read_secs(),
+            mutation_secs: default_mutation_secs(),
+            generation_secs: default_generation_secs(),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// Create a timeout configuration with defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the deadline for one category
+    ///
+    /// Deadlines are kept in whole seconds; anything shorter rounds up to one.
+    pub fn with_timeout(self, category: TimeoutCategory, timeout: Duration) -> Self {
+        self.with_secs(category, timeout.as_secs().max(1))
+    }
+
+    fn with_secs(mut self, category: TimeoutCategory, secs: u64) -> Self {
+        match category {
+            TimeoutCategory::Read => self.read_secs = secs,
+            TimeoutCategory::Mutation => self.mutation_secs = secs,
+            TimeoutCategory::Generation => self.generation_secs = secs,
+        }
+        self
+    }
+
+    /// Deadline for a category
+    pub fn for_category(&self, category: TimeoutCategory) -> Duration {
+        Duration::from_secs(match category {
+            TimeoutCategory::Read => self.read_secs,
+            TimeoutCategory::Mutation => self.mutation_secs,
+            TimeoutCategory::Generation => self.generation_secs,
+        })
+    }
+
+    /// Defaults, overridden by `UDIO_MCP_TIMEOUT_{READ,MUTATION,GENERATION}_SECS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut config = Self::default();
+
+        for (category, var) in [
+            (TimeoutCategory::Read, "UDIO_MCP_TIMEOUT_READ_SECS"),
+            (TimeoutCategory::Mutation, "UDIO_MCP_TIMEOUT_MUTATION_SECS"),
+            (
+                TimeoutCategory::Generation,
+                "UDIO_MCP_TIMEOUT_GENERATION_SECS",
+            ),
+        ] {
+            if let Ok(value) = std::env::var(var) {
+                let secs: u64 = value.parse().ok().filter(|secs| *secs > 0).ok_or_else(|| {
+                    anyhow::anyhow!("{} must be a positive number of seconds", var)
+                })?;
+                config = config.with_secs(category, secs);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Cleans up after a tool call the watchdog had to abandon
+#[async_trait]
+pub trait StuckTaskRecovery: Send + Sync {
+    /// Close whatever the abandoned call left open
+    ///
+    /// The call's future has already been dropped; this is for state living
+    /// outside it, such as a tab still loading in the browser.
+    async fn recover(&self, tool: &str, step: Option<&str>);
+}
+
+#[async_trait]
+impl StuckTaskRecovery for BrowserManager {
+    async fn recover(&self, tool: &str, step: Option<&str>) {
+        let closed = self.close_open_pages().await;
+        tracing::info!(
+            "Recovered from stuck '{}' ({}): closed {} tab(s)",
+            tool,
+            step.unwrap_or("unknown step"),
+            closed
+        );
+    }
+}
+
+/// Record the step the current tool call is on
+///
+/// Shown in the timeout error if the watchdog fires during it. Does nothing
+/// outside a watched call.
+pub fn step(name: impl Into<String>) {
+    let name = name.into();
+    let _ = CURRENT_STEP.try_with(|current| {
+        tracing::debug!("Tool step: {}", name);
+        if let Ok(mut current) = current.lock() {
+            *current = Some(name);
+        }
+    });
+}
+
+/// Run a tool call, abandoning it at `deadline`
+///
+/// Dropping the call's future cancels any browser command still in flight;
+/// `recovery` then closes what the call had opened, so the next call starts
+/// from a clean context.
+pub async fn watch<F>(
+    tool: &str,
+    deadline: Duration,
+    recovery: Option<&dyn StuckTaskRecovery>,
+    call: F,
+) -> McpResult<BoxedOutput>
+where
+    F: Future<Output = McpResult<BoxedOutput>>,
+{
+    let current = Arc::new(Mutex::new(None));
+    let watched = CURRENT_STEP.scope(current.clone(), call);
+
+    match tokio::time::timeout(deadline, watched).await {
+        Ok(result) => result,
+        Err(_) => {
+            let step = current.lock().ok().and_then(|step| step.clone());
+            tracing::warn!(
+                "Tool '{}' timed out after {:?} during {}",
+                tool,
+                deadline,
+                step.as_deref().unwrap_or("an unknown step")
+            );
+
+            if let Some(recovery) = recovery {
+                recovery.recover(tool, step.as_deref()).await;
+            }
+
+            Err(McpError::Timeout {
+                operation: format!("tool '{}'", tool),
+                after: Some(deadline),
+                step,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_timeouts() {
+        let config = TimeoutConfig::default();
+        assert_eq!(
+            config.for_category(TimeoutCategory::Read),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            config.for_category(TimeoutCategory::Mutation),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            config.for_category(TimeoutCategory::Generation),
+            Duration::from_secs(600)
+        );
+    }
+
+    #[test]
+    fn test_config_fills_missing_fields_with_defaults() {
+        let config: TimeoutConfig = serde_json::from_value(json!({"read_secs": 5})).unwrap();
+        assert_eq!(config.read_secs, 5);
+        assert_eq!(config.mutation_secs, 90);
+        assert_eq!(config.generation_secs, 600);
+    }
+
+    #[test]
+    fn test_with_timeout_overrides_one_category() {
+        let config = TimeoutConfig::new()
+            .with_timeout(TimeoutCategory::Generation, Duration::from_secs(900));
+        assert_eq!(config.generation_secs, 900);
+        assert_eq!(config.read_secs, 30);
+    }
+
+    #[tokio::test]
+    async fn test_watch_passes_through_finished_calls() {
+        let result = watch("quick", Duration::from_secs(1), None, async {
+            step("Only step");
+            Ok(Box::new(json!({"ok": true})) as BoxedOutput)
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.to_json()["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_step_in_progress() {
+        let result = watch("stuck", Duration::from_millis(20), None, async {
+            step("Launch browser");
+            step("Load playlist page");
+            std::future::pending::<McpResult<BoxedOutput>>().await
+        })
+        .await;
+
+        match result {
+            Err(McpError::Timeout {
+                operation,
+                after,
+                step,
+            }) => {
+                assert_eq!(operation, "tool 'stuck'");
+                assert_eq!(after, Some(Duration::from_millis(20)));
+                assert_eq!(step.as_deref(), Some("Load playlist page"));
+            }
+            Err(other) => panic!("Expected Timeout, got {:?}", other),
+            Ok(_) => panic!("Expected Timeout"),
+        }
+    }
+
+    #[test]
+    fn test_step_outside_watch_is_ignored() {
+        step("Not watched");
+    }
+}
@@ -4,6 +4,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::models::PlaylistDiff;
+
 /// Request ID can be either a string or a number per JSON-RPC 2.0 spec
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
@@ -160,6 +162,193 @@ pub mod error_codes {
     pub const SERVER_ERROR_START: i32 = -32000;
     /// Server error range end
     pub const SERVER_ERROR_END: i32 = -32099;
+
+    /// Transport failure
+    pub const TRANSPORT_ERROR: i32 = -32001;
+    /// The Udio session expired or was never established
+    pub const AUTH_EXPIRED: i32 = -32010;
+    /// Udio is throttling requests
+    pub const RATE_LIMITED: i32 = -32011;
+    /// Browser automation failed
+    pub const BROWSER_FAILURE: i32 = -32012;
+    /// A tool, playlist, song, or other entity does not exist
+    pub const NOT_FOUND: i32 = -32013;
+    /// An operation did not finish in time
+    pub const TIMEOUT: i32 = -32014;
+    /// Udio is unreachable and the tool needs it
+    pub const OFFLINE: i32 = -32015;
+    /// A playlist changed since the revision an edit was made against
+    pub const CONFLICT: i32 = -32016;
+}
+
+/// Machine-readable `data` attached to every error response
+///
+/// `category` and `recovery` are always present; the remaining fields only
+/// appear for the categories listed against them. For example, a rate limit:
+///
+/// ```json
+/// {
+///   "code": -32011,
+///   "message": "Rate limited by Udio; retry after 30s",
+///   "data": {"category": "rate_limited", "recovery": "retry", "retry_after_secs": 30}
+/// }
+/// ```
+///
+/// | category | code | recovery | extra fields |
+/// |----------|------|----------|--------------|
+/// | `parse_error` | -32700 | `fix_request` | |
+/// | `invalid_request` | -32600 | `fix_request` | |
+/// | `method_not_found` | -32601 | `fix_request` | |
+/// | `unsupported_version` | -32602 | `fix_request` | |
+/// | `validation` | -32602 | `fix_request` | `field_errors` |
+/// | `unsupported_format` | -32602 | `fix_request` | `supported_formats` |
+/// | `internal` | -32603 | `give_up` | |
+/// | `transport` | -32001 | `retry` | |
+/// | `auth_expired` | -32010 | `reauthenticate` | |
+/// | `rate_limited` | -32011 | `retry` | `retry_after_secs` (if known) |
+/// | `browser_failure` | -32012 | `retry` | `diagnostic_id` |
+/// | `not_found` | -32013 | `give_up` | `entity`, `id` |
+/// | `timeout` | -32014 | `retry` | `operation`, `timeout_ms` and `step` (if known) |
+/// | `offline` | -32015 | `retry` | `last_sync` (if the library was ever synced) |
+/// | `conflict` | -32016 | `refresh` | `entity`, `id`, `current_revision` and `diff` (if the expected revision is remembered) |
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorData {
+    /// What kind of failure this is
+    pub category: ErrorCategory,
+    /// What the client should do about it
+    pub recovery: Recovery,
+    /// Seconds to wait before retrying (`rate_limited`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// Identifier to quote when reporting the failure; the server log
+    /// carries the full browser error under the same ID (`browser_failure`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic_id: Option<String>,
+    /// Kind of thing that was missing, e.g. `"tool"` (`not_found`), or
+    /// that changed (`conflict`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<String>,
+    /// Identifier that was looked up (`not_found`) or changed (`conflict`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Every rejected parameter (`validation`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<FieldError>,
+    /// Formats the server can decode (`unsupported_format`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_formats: Vec<String>,
+    /// What was being waited on (`timeout`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// How long the server waited (`timeout`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Step the tool was on when it was abandoned (`timeout`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    /// Unix time of the last library sync (`offline`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sync: Option<u64>,
+    /// Revision the playlist is at now (`conflict`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_revision: Option<String>,
+    /// Songs added and removed since the expected revision (`conflict`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<PlaylistDiff>,
+}
+
+impl ErrorData {
+    /// Data carrying only a category and recovery hint
+    pub fn new(category: ErrorCategory, recovery: Recovery) -> Self {
+        Self {
+            category,
+            recovery,
+            retry_after_secs: None,
+            diagnostic_id: None,
+            entity: None,
+            id: None,
+            field_errors: Vec::new(),
+            supported_formats: Vec::new(),
+            operation: None,
+            timeout_ms: None,
+            step: None,
+            last_sync: None,
+            current_revision: None,
+            diff: None,
+        }
+    }
+}
+
+/// Error categories reported in [`ErrorData::category`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The message was not valid JSON-RPC
+    ParseError,
+    /// The request was valid JSON but not a valid request
+    InvalidRequest,
+    /// No method or tool by that name
+    MethodNotFound,
+    /// The client's protocol version cannot be served
+    UnsupportedVersion,
+    /// Parameters were missing or invalid; see [`ErrorData::field_errors`]
+    Validation,
+    /// A file the server cannot decode
+    UnsupportedFormat,
+    /// A bug or unexpected state in the server
+    Internal,
+    /// The connection to the client failed
+    Transport,
+    /// The Udio session must be renewed
+    AuthExpired,
+    /// Udio is throttling requests; see [`ErrorData::retry_after_secs`]
+    RateLimited,
+    /// Browser automation failed; see [`ErrorData::diagnostic_id`]
+    BrowserFailure,
+    /// The named entity does not exist; see [`ErrorData::entity`] and [`ErrorData::id`]
+    NotFound,
+    /// An operation did not finish in time; see [`ErrorData::operation`]
+    Timeout,
+    /// Udio is unreachable; only the local library is available
+    Offline,
+    /// A playlist changed since the revision an edit was made against
+    Conflict,
+}
+
+/// How a client should respond to an error, reported in [`ErrorData::recovery`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recovery {
+    /// The same request may succeed later
+    Retry,
+    /// Log in to Udio again, then retry
+    Reauthenticate,
+    /// The request itself is wrong and must be changed
+    FixRequest,
+    /// Retrying will not help
+    GiveUp,
+    /// Read the current state again, then decide whether to repeat the
+    /// request against it
+    Refresh,
+}
+
+/// One rejected parameter in a `validation` error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Parameter name, e.g. `"song_id"`
+    pub field: String,
+    /// Why it was rejected
+    pub message: String,
+}
+
+impl FieldError {
+    /// Create a field error
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
 }
 
 /// MCP Message - can be a request, response, or notification
@@ -5,9 +5,12 @@
 pub mod playback;
 /// Playlist data models
 pub mod playlist;
+/// Search result models
+pub mod search;
 /// Song data models
 pub mod song;
 
 pub use playback::{PlaybackState, PlaybackStatus, RepeatMode};
-pub use playlist::Playlist;
+pub use playlist::{Playlist, PlaylistDiff};
+pub use search::SearchResults;
 pub use song::Song;
@@ -1,6 +1,7 @@
 // Playlist data model
 // Represents a collection of songs on Udio platform
 
+use ring::digest;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -39,6 +40,43 @@ pub struct Playlist {
     /// Optional owner/creator information
     #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
+
+    /// Token naming the ordered track list, see [`Playlist::revision_of`]
+    #[serde(default)]
+    pub revision: String,
+}
+
+/// Tracks added and removed between two revisions of a playlist
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistDiff {
+    /// Song IDs in the newer revision only
+    pub added: Vec<String>,
+
+    /// Song IDs in the older revision only
+    pub removed: Vec<String>,
+}
+
+impl PlaylistDiff {
+    /// Changes from the `before` song IDs to the `after` ones
+    pub fn between(before: &[String], after: &[String]) -> Self {
+        Self {
+            added: after
+                .iter()
+                .filter(|id| !before.contains(id))
+                .cloned()
+                .collect(),
+            removed: before
+                .iter()
+                .filter(|id| !after.contains(id))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Whether the same songs are in both revisions, whatever their order
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 impl Playlist {
@@ -60,7 +98,33 @@ impl Playlist {
             created_at: now,
             updated_at: now,
             owner: None,
+            revision: Self::revision_of(&[]),
+        }
+    }
+
+    /// Revision token of a track list: the first 16 hex digits of the
+    /// SHA-256 of its song IDs in order
+    ///
+    /// Any added, removed or reordered song changes the token; titles,
+    /// timestamps and other metadata do not.
+    pub fn revision_of(songs: &[Song]) -> String {
+        let mut context = digest::Context::new(&digest::SHA256);
+        for song in songs {
+            context.update(song.id.as_bytes());
+            context.update(b"\n");
         }
+        context
+            .finish()
+            .as_ref()
+            .iter()
+            .take(8)
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Song IDs in playlist order
+    pub fn song_ids(&self) -> Vec<String> {
+        self.songs.iter().map(|song| song.id.clone()).collect()
     }
 
     /// Set description
@@ -87,6 +151,7 @@ impl Playlist {
         self.total_duration_seconds = songs.iter().map(|s| s.duration_seconds).sum();
         self.songs = songs;
         self.update_timestamp();
+        self.revision = Self::revision_of(&self.songs);
         self
     }
 
@@ -96,6 +161,7 @@ impl Playlist {
         self.song_count += 1;
         self.total_duration_seconds += song.duration_seconds;
         self.update_timestamp();
+        self.revision = Self::revision_of(&self.songs);
     }
 
     /// Remove a song by ID
@@ -105,6 +171,7 @@ impl Playlist {
             self.song_count -= 1;
             self.total_duration_seconds -= song.duration_seconds;
             self.update_timestamp();
+            self.revision = Self::revision_of(&self.songs);
             Some(song)
         } else {
             None
@@ -335,6 +402,43 @@ mod tests {
         assert_eq!(sorted[2].duration_seconds, 120); // Shortest last
     }
 
+    #[test]
+    fn test_revision_follows_track_order() {
+        let mut playlist = Playlist::new("pl123", "My Playlist");
+        let empty = playlist.revision.clone();
+        assert_eq!(empty.len(), 16);
+
+        playlist.add_song(create_test_song("song1", "Song 1", 180));
+        playlist.add_song(create_test_song("song2", "Song 2", 240));
+        let two = playlist.revision.clone();
+        assert_ne!(two, empty);
+
+        // Metadata does not matter, order does
+        let retitled = Playlist::new("other", "Other").with_songs(vec![
+            create_test_song("song1", "Renamed", 1),
+            create_test_song("song2", "Song 2", 240),
+        ]);
+        assert_eq!(retitled.revision, two);
+        let swapped = Playlist::new("pl123", "My Playlist").with_songs(vec![
+            create_test_song("song2", "Song 2", 240),
+            create_test_song("song1", "Song 1", 180),
+        ]);
+        assert_ne!(swapped.revision, two);
+
+        playlist.remove_song("song2");
+        playlist.remove_song("song1");
+        assert_eq!(playlist.revision, empty);
+    }
+
+    #[test]
+    fn test_diff_between_revisions() {
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let diff = PlaylistDiff::between(&ids(&["a", "b", "c"]), &ids(&["c", "d", "a"]));
+        assert_eq!(diff.added, ids(&["d"]));
+        assert_eq!(diff.removed, ids(&["b"]));
+        assert!(PlaylistDiff::between(&ids(&["a", "b"]), &ids(&["b", "a"])).is_empty());
+    }
+
     #[test]
     fn test_songs_by_duration_empty_playlist() {
         let playlist = Playlist::new("pl123", "Empty Playlist");
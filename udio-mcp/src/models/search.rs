@@ -0,0 +1,80 @@
+// Search result model
+// Songs matching a query on Udio platform
+
+use serde::{Deserialize, Serialize};
+
+use super::song::Song;
+
+/// Songs returned for a search query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchResults {
+    /// Query as entered
+    pub query: String,
+
+    /// Matching songs, in ranking order
+    pub songs: Vec<Song>,
+
+    /// Total number of matches (may exceed the songs returned)
+    pub total: usize,
+}
+
+impl SearchResults {
+    /// Create search results where every match was returned
+    pub fn new(query: impl Into<String>, songs: Vec<Song>) -> Self {
+        let total = songs.len();
+        Self {
+            query: query.into(),
+            songs,
+            total,
+        }
+    }
+
+    /// Set the total number of matches
+    pub fn with_total(mut self, total: usize) -> Self {
+        self.total = total.max(self.songs.len());
+        self
+    }
+
+    /// Check if nothing matched
+    pub fn is_empty(&self) -> bool {
+        self.songs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: &str) -> Song {
+        Song::new(
+            id,
+            format!("Song {}", id),
+            180,
+            format!("https://udio.com/songs/{}", id),
+        )
+    }
+
+    #[test]
+    fn test_search_results_new() {
+        let results = SearchResults::new("lofi", vec![song("1"), song("2")]);
+        assert_eq!(results.query, "lofi");
+        assert_eq!(results.total, 2);
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_search_results_total_never_below_returned() {
+        let results = SearchResults::new("lofi", vec![song("1"), song("2")]).with_total(40);
+        assert_eq!(results.total, 40);
+
+        let results = SearchResults::new("lofi", vec![song("1"), song("2")]).with_total(1);
+        assert_eq!(results.total, 2);
+    }
+
+    #[test]
+    fn test_search_results_empty() {
+        let results = SearchResults::new("nothing", Vec::new());
+        assert!(results.is_empty());
+        assert_eq!(results.total, 0);
+    }
+}
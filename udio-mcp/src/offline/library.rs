@@ -0,0 +1,325 @@
+// Local library store
+// The playlists and playback state last read from Udio, kept on disk so
+// read-only tools can answer while the site is unreachable
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::suggest::{self, Suggestions};
+use crate::models::{PlaybackState, Playlist, SearchResults, Song};
+
+/// Everything the store holds, as written to disk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LibrarySnapshot {
+    /// Playlists by name, as last fetched
+    #[serde(default)]
+    pub playlists: BTreeMap<String, Playlist>,
+
+    /// Playback state as last read
+    #[serde(default)]
+    pub playback: Option<PlaybackState>,
+
+    /// When anything was last synced (Unix timestamp)
+    #[serde(default)]
+    pub last_sync: Option<u64>,
+}
+
+/// Totals over the synced library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryStats {
+    /// Playlists synced
+    pub playlists: usize,
+
+    /// Distinct songs across every playlist
+    pub songs: usize,
+
+    /// Combined length of the distinct songs, in seconds
+    pub total_duration_seconds: u64,
+
+    /// Distinct artists
+    pub artists: usize,
+
+    /// The most used tags with their song counts, most used first
+    pub top_tags: Vec<(String, usize)>,
+
+    /// When the library was last synced (Unix timestamp)
+    pub last_sync: Option<u64>,
+}
+
+/// Number of tags listed in [`LibraryStats::top_tags`]
+const TOP_TAGS: usize = 5;
+
+/// Copy of the library that survives restarts
+///
+/// Every online read is written through with [`LibraryStore::record_playlist`]
+/// or [`LibraryStore::record_playback`], so the copy is as fresh as the
+/// last successful call.
+pub struct LibraryStore {
+    /// File the snapshot is saved to; kept in memory only when unset
+    path: Option<PathBuf>,
+
+    snapshot: RwLock<LibrarySnapshot>,
+}
+
+impl LibraryStore {
+    /// Store that is never saved
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            snapshot: RwLock::new(LibrarySnapshot::default()),
+        }
+    }
+
+    /// Open the store saved at `path`, starting empty if there is none yet
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let snapshot = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("Failed to parse library {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => LibrarySnapshot::default(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read library {}", path.display()))
+            }
+        };
+
+        Ok(Self {
+            path: Some(path),
+            snapshot: RwLock::new(snapshot),
+        })
+    }
+
+    /// `UDIO_MCP_LIBRARY_PATH`, or `library.json` in `cache_dir`
+    pub fn path_from_env(cache_dir: &Path) -> PathBuf {
+        std::env::var_os("UDIO_MCP_LIBRARY_PATH")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| cache_dir.join("library.json"))
+    }
+
+    /// File the store is saved to
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Copy of everything stored
+    pub fn snapshot(&self) -> LibrarySnapshot {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// When anything was last synced (Unix timestamp)
+    pub fn last_sync(&self) -> Option<u64> {
+        self.snapshot.read().unwrap().last_sync
+    }
+
+    /// A synced playlist by name
+    pub fn playlist(&self, name: &str) -> Option<Playlist> {
+        self.snapshot.read().unwrap().playlists.get(name).cloned()
+    }
+
+    /// Playback state as last read
+    pub fn playback(&self) -> Option<PlaybackState> {
+        self.snapshot.read().unwrap().playback.clone()
+    }
+
+    /// Remember a playlist fetched from Udio
+    pub fn record_playlist(&self, playlist: &Playlist) -> Result<()> {
+        self.update(|snapshot| {
+            snapshot
+                .playlists
+                .insert(playlist.name.clone(), playlist.clone());
+        })
+    }
+
+    /// Remember the playback state read from Udio
+    pub fn record_playback(&self, state: &PlaybackState) -> Result<()> {
+        self.update(|snapshot| snapshot.playback = Some(state.clone()))
+    }
+
+    /// Songs whose title, artist or tags contain `query`, ignoring case
+    ///
+    /// Songs in several playlists are listed once, in playlist name order.
+    pub fn search(&self, query: &str, limit: usize) -> SearchResults {
+        let needle = query.to_lowercase();
+        let matches: Vec<Song> = self
+            .distinct_songs()
+            .into_iter()
+            .filter(|song| {
+                song.title.to_lowercase().contains(&needle)
+                    || song
+                        .artist
+                        .as_ref()
+                        .is_some_and(|artist| artist.to_lowercase().contains(&needle))
+                    || song
+                        .tags
+                        .iter()
+                        .any(|tag| tag.to_lowercase().contains(&needle))
+            })
+            .collect();
+
+        let total = matches.len();
+        SearchResults::new(query, matches.into_iter().take(limit).collect()).with_total(total)
+    }
+
+    /// Up to `limit` songs to add to the playlist with id (or name)
+    /// `playlist`, scored by [`super::suggest::suggest`]
+    pub fn suggest(&self, playlist: &str, limit: usize) -> Option<Suggestions> {
+        suggest::suggest(&self.snapshot.read().unwrap(), playlist, limit)
+    }
+
+    /// Totals over every synced playlist
+    pub fn stats(&self) -> LibraryStats {
+        let songs = self.distinct_songs();
+
+        let mut tags: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in songs.iter().flat_map(|song| &song.tags) {
+            *tags.entry(tag.as_str()).or_default() += 1;
+        }
+        let mut top_tags: Vec<(String, usize)> = tags
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect();
+        // Stable, so equal counts stay in name order
+        top_tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        top_tags.truncate(TOP_TAGS);
+
+        let snapshot = self.snapshot.read().unwrap();
+        LibraryStats {
+            playlists: snapshot.playlists.len(),
+            songs: songs.len(),
+            total_duration_seconds: songs.iter().map(|song| song.duration_seconds).sum(),
+            artists: songs
+                .iter()
+                .filter_map(|song| song.artist.as_deref())
+                .collect::<BTreeSet<_>>()
+                .len(),
+            top_tags,
+            last_sync: snapshot.last_sync,
+        }
+    }
+
+    /// Every synced song once, in playlist name order
+    fn distinct_songs(&self) -> Vec<Song> {
+        let snapshot = self.snapshot.read().unwrap();
+        let mut seen = BTreeSet::new();
+        snapshot
+            .playlists
+            .values()
+            .flat_map(|playlist| &playlist.songs)
+            .filter(|song| seen.insert(song.id.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Apply a change, stamp the sync time and save
+    fn update(&self, change: impl FnOnce(&mut LibrarySnapshot)) -> Result<()> {
+        // Held while saving, so concurrent updates can't interleave writes
+        let mut snapshot = self.snapshot.write().unwrap();
+        change(&mut snapshot);
+        snapshot.last_sync = Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        );
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Written aside and renamed, so a crash never leaves half a library
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_vec_pretty(&*snapshot)?)
+            .with_context(|| format!("Failed to write library {}", temp.display()))?;
+        fs::rename(&temp, path)
+            .with_context(|| format!("Failed to save library {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: &str, title: &str, artist: &str, tags: &[&str]) -> Song {
+        Song::new(id, title, 120, format!("https://udio.com/songs/{}", id))
+            .with_artist(artist)
+            .with_tags(tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    fn library() -> LibraryStore {
+        let store = LibraryStore::in_memory();
+        store
+            .record_playlist(&Playlist::new("p1", "Chill").with_songs(vec![
+                song("1", "Night Drive", "Nova", &["synthwave", "chill"]),
+                song("2", "Morning Tea", "Ash", &["lofi", "chill"]),
+            ]))
+            .unwrap();
+        store
+            .record_playlist(&Playlist::new("p2", "Favorites").with_songs(vec![
+                song("2", "Morning Tea", "Ash", &["lofi", "chill"]),
+                song("3", "Thunder", "Nova", &["rock"]),
+            ]))
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn test_search_matches_title_artist_and_tags() {
+        let library = library();
+
+        let ids = |results: SearchResults| -> Vec<String> {
+            results.songs.into_iter().map(|song| song.id).collect()
+        };
+        assert_eq!(ids(library.search("night", 10)), vec!["1"]);
+        assert_eq!(ids(library.search("NOVA", 10)), vec!["1", "3"]);
+        // A song in two playlists is one match
+        assert_eq!(ids(library.search("lofi", 10)), vec!["2"]);
+
+        let limited = library.search("chill", 1);
+        assert_eq!(limited.songs.len(), 1);
+        assert_eq!(limited.total, 2);
+    }
+
+    #[test]
+    fn test_stats_count_distinct_songs() {
+        let stats = library().stats();
+
+        assert_eq!(stats.playlists, 2);
+        assert_eq!(stats.songs, 3);
+        assert_eq!(stats.total_duration_seconds, 360);
+        assert_eq!(stats.artists, 2);
+        assert_eq!(stats.top_tags[0], ("chill".to_string(), 2));
+        assert!(stats.last_sync.is_some());
+    }
+
+    #[test]
+    fn test_saved_library_reopens() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache/library.json");
+
+        let store = LibraryStore::open(&path).unwrap();
+        assert_eq!(store.last_sync(), None);
+        store
+            .record_playlist(&Playlist::new("p1", "Chill").with_songs(vec![song(
+                "1",
+                "Night Drive",
+                "Nova",
+                &[],
+            )]))
+            .unwrap();
+        store
+            .record_playback(&PlaybackState::playing("1", "Night Drive"))
+            .unwrap();
+
+        let reopened = LibraryStore::open(&path).unwrap();
+        assert_eq!(reopened.snapshot(), store.snapshot());
+        assert_eq!(reopened.playlist("Chill").unwrap().songs.len(), 1);
+        assert!(reopened.playback().unwrap().is_playing());
+    }
+}
@@ -0,0 +1,252 @@
+// Offline mode
+// Tracks whether Udio is reachable and keeps the local copy of the library
+// that read-only tools fall back on while it is not
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Playlists and playback state synced from Udio
+pub mod library;
+/// Similarity-based suggestions over the synced library
+pub mod suggest;
+
+pub use library::{LibrarySnapshot, LibraryStats, LibraryStore};
+pub use suggest::{Confidence, ScoreBreakdown, Suggestion, Suggestions};
+
+/// Consecutive browser failures that switch to offline mode by default
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Seconds between health checks while offline by default
+pub const DEFAULT_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// When to go offline and how to come back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineConfig {
+    /// Stay offline regardless of connectivity
+    pub forced: bool,
+    /// Consecutive browser failures before going offline
+    pub failure_threshold: u32,
+    /// Seconds between health checks while offline
+    pub check_interval_secs: u64,
+}
+
+impl Default for OfflineConfig {
+    fn default() -> Self {
+        Self {
+            forced: false,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            check_interval_secs: DEFAULT_CHECK_INTERVAL_SECS,
+        }
+    }
+}
+
+impl OfflineConfig {
+    /// Read `UDIO_MCP_OFFLINE`, `UDIO_MCP_OFFLINE_AFTER_FAILURES` and
+    /// `UDIO_MCP_OFFLINE_CHECK_SECS`, keeping the defaults for unset ones
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("UDIO_MCP_OFFLINE") {
+            config.forced = match value.as_str() {
+                "1" | "true" => true,
+                "0" | "false" => false,
+                _ => anyhow::bail!("UDIO_MCP_OFFLINE must be true or false"),
+            };
+        }
+        if let Ok(value) = std::env::var("UDIO_MCP_OFFLINE_AFTER_FAILURES") {
+            config.failure_threshold =
+                value.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    anyhow::anyhow!("UDIO_MCP_OFFLINE_AFTER_FAILURES must be a positive number")
+                })?;
+        }
+        if let Ok(value) = std::env::var("UDIO_MCP_OFFLINE_CHECK_SECS") {
+            config.check_interval_secs =
+                value.parse().ok().filter(|secs| *secs > 0).ok_or_else(|| {
+                    anyhow::anyhow!("UDIO_MCP_OFFLINE_CHECK_SECS must be a positive number of seconds")
+                })?;
+        }
+
+        Ok(config)
+    }
+}
+
+/// Whether Udio can be reached right now
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// Check once; `true` when Udio answers
+    async fn is_up(&self) -> bool;
+}
+
+/// Probe requesting the Udio home page over HTTP
+pub struct SiteProbe {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl SiteProbe {
+    /// Probe `url`, giving up after `timeout`
+    pub fn new(url: impl Into<String>, timeout: Duration) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder().timeout(timeout).build()?,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl HealthProbe for SiteProbe {
+    async fn is_up(&self) -> bool {
+        match self.client.get(&self.url).send().await {
+            Ok(response) => !response.status().is_server_error(),
+            Err(e) => {
+                tracing::debug!("Health check of {} failed: {}", self.url, e);
+                false
+            }
+        }
+    }
+}
+
+/// Shared online/offline state
+///
+/// Tools report how their browser calls went; after
+/// [`OfflineConfig::failure_threshold`] failures in a row the server goes
+/// offline until a [`HealthProbe`] sees Udio again. A forced offline mode
+/// never ends on its own.
+#[derive(Debug, Clone)]
+pub struct Connectivity {
+    inner: Arc<ConnectivityInner>,
+}
+
+#[derive(Debug)]
+struct ConnectivityInner {
+    forced: bool,
+    failure_threshold: u32,
+    offline: AtomicBool,
+    failures: AtomicU32,
+}
+
+impl Connectivity {
+    /// Start online, or offline when forced
+    pub fn new(config: &OfflineConfig) -> Self {
+        Self {
+            inner: Arc::new(ConnectivityInner {
+                forced: config.forced,
+                failure_threshold: config.failure_threshold.max(1),
+                offline: AtomicBool::new(config.forced),
+                failures: AtomicU32::new(0),
+            }),
+        }
+    }
+
+    /// Whether tools should avoid Udio
+    pub fn is_offline(&self) -> bool {
+        self.inner.offline.load(Ordering::SeqCst)
+    }
+
+    /// Whether offline mode was forced by configuration
+    pub fn is_forced(&self) -> bool {
+        self.inner.forced
+    }
+
+    /// A browser call worked
+    pub fn record_success(&self) {
+        self.inner.failures.store(0, Ordering::SeqCst);
+    }
+
+    /// A browser call failed; returns `true` if this switched to offline
+    pub fn record_failure(&self) -> bool {
+        let failures = self.inner.failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures < self.inner.failure_threshold || self.inner.offline.swap(true, Ordering::SeqCst)
+        {
+            return false;
+        }
+
+        tracing::warn!(
+            "Udio unreachable after {} failed calls: serving the local library",
+            failures
+        );
+        true
+    }
+
+    /// Run `probe` once while offline; returns `true` if back online
+    pub async fn check(&self, probe: &dyn HealthProbe) -> bool {
+        if !self.is_offline() {
+            return true;
+        }
+        if self.inner.forced || !probe.is_up().await {
+            return false;
+        }
+
+        self.inner.failures.store(0, Ordering::SeqCst);
+        self.inner.offline.store(false, Ordering::SeqCst);
+        tracing::info!("Udio reachable again: back online");
+        true
+    }
+
+    /// Check `probe` every `interval` for as long as the server runs
+    pub fn spawn_recovery(
+        &self,
+        probe: Arc<dyn HealthProbe>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let connectivity = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                connectivity.check(probe.as_ref()).await;
+            }
+        })
+    }
+}
+
+impl Default for Connectivity {
+    fn default() -> Self {
+        Self::new(&OfflineConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedProbe(bool);
+
+    #[async_trait]
+    impl HealthProbe for FixedProbe {
+        async fn is_up(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_go_offline_until_probe_passes() {
+        let connectivity = Connectivity::default();
+
+        assert!(!connectivity.record_failure());
+        connectivity.record_success();
+        assert!(!connectivity.record_failure());
+        assert!(!connectivity.record_failure());
+        assert!(!connectivity.is_offline(), "a success resets the count");
+        assert!(connectivity.record_failure());
+        assert!(connectivity.is_offline());
+        assert!(!connectivity.record_failure(), "already offline");
+
+        assert!(!connectivity.check(&FixedProbe(false)).await);
+        assert!(connectivity.check(&FixedProbe(true)).await);
+        assert!(!connectivity.is_offline());
+    }
+
+    #[tokio::test]
+    async fn test_forced_offline_ignores_probe() {
+        let connectivity = Connectivity::new(&OfflineConfig {
+            forced: true,
+            ..OfflineConfig::default()
+        });
+
+        assert!(connectivity.is_offline());
+        assert!(!connectivity.check(&FixedProbe(true)).await);
+        assert!(connectivity.is_offline());
+    }
+}
@@ -0,0 +1,411 @@
+// Playlist suggestions
+// Ranks songs from the synced library that a playlist doesn't have yet,
+// using only what the local store knows: no network calls
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use super::library::LibrarySnapshot;
+use crate::models::{Playlist, Song};
+
+/// Weight of [`ScoreBreakdown::tag_overlap`] in the total
+pub const TAG_WEIGHT: f64 = 0.4;
+
+/// Weight of [`ScoreBreakdown::title_similarity`] in the total
+pub const TITLE_WEIGHT: f64 = 0.2;
+
+/// Weight of [`ScoreBreakdown::co_occurrence`] in the total
+pub const CO_OCCURRENCE_WEIGHT: f64 = 0.4;
+
+/// How a candidate scored against a playlist, each factor from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// Best tag overlap (Jaccard) with any song in the playlist
+    pub tag_overlap: f64,
+
+    /// Best title word overlap (Jaccard) with any song in the playlist
+    pub title_similarity: f64,
+
+    /// Share of the playlist's songs found alongside the candidate in some
+    /// other playlist
+    pub co_occurrence: f64,
+
+    /// Weighted sum of the factors that had data to go on
+    pub total: f64,
+}
+
+/// What the library offers to score with
+///
+/// A factor without data is left out of the total and the remaining
+/// weights are scaled up to make up for it, so totals stay between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Factors {
+    /// Some song in the library has tags
+    pub tags: bool,
+
+    /// The library has playlists besides the one suggestions are for
+    pub co_occurrence: bool,
+}
+
+impl Factors {
+    /// Weights for tags, titles and co-occurrence, summing to 1
+    fn weights(self) -> (f64, f64, f64) {
+        let tags = if self.tags { TAG_WEIGHT } else { 0.0 };
+        let co_occurrence = if self.co_occurrence {
+            CO_OCCURRENCE_WEIGHT
+        } else {
+            0.0
+        };
+        let sum = tags + TITLE_WEIGHT + co_occurrence;
+        (tags / sum, TITLE_WEIGHT / sum, co_occurrence / sum)
+    }
+}
+
+/// Score `candidate` for a playlist holding `seeds`
+///
+/// - Tag overlap is the Jaccard index of the candidate's tags and those of
+///   the playlist song sharing the most, ignoring case.
+/// - Title similarity is the same over the words of the titles.
+/// - Co-occurrence is the share of `seeds` that appear in at least one of
+///   `others` (the rest of the library's playlists) together with the
+///   candidate.
+///
+/// The total weighs them [`TAG_WEIGHT`], [`TITLE_WEIGHT`] and
+/// [`CO_OCCURRENCE_WEIGHT`], rescaled over the `factors` with data.
+pub fn score(
+    candidate: &Song,
+    seeds: &[Song],
+    others: &[&Playlist],
+    factors: Factors,
+) -> ScoreBreakdown {
+    let candidate_tags = tag_set(candidate);
+    let candidate_words = title_words(&candidate.title);
+
+    let best = |similarity: &dyn Fn(&Song) -> f64| seeds.iter().map(similarity).fold(0.0, f64::max);
+    let tag_overlap = best(&|seed| jaccard(&candidate_tags, &tag_set(seed)));
+    let title_similarity = best(&|seed| jaccard(&candidate_words, &title_words(&seed.title)));
+
+    let alongside: BTreeSet<&str> = others
+        .iter()
+        .filter(|playlist| contains(playlist, &candidate.id))
+        .flat_map(|playlist| playlist.songs.iter().map(|song| song.id.as_str()))
+        .collect();
+    let co_occurrence = if seeds.is_empty() {
+        0.0
+    } else {
+        seeds
+            .iter()
+            .filter(|seed| alongside.contains(seed.id.as_str()))
+            .count() as f64
+            / seeds.len() as f64
+    };
+
+    let (tag_weight, title_weight, co_weight) = factors.weights();
+    ScoreBreakdown {
+        tag_overlap,
+        title_similarity,
+        co_occurrence,
+        total: tag_weight * tag_overlap
+            + title_weight * title_similarity
+            + co_weight * co_occurrence,
+    }
+}
+
+/// How far suggestions can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// Every factor had data and the playlist has several songs
+    Normal,
+    /// Scored on little data; see [`Suggestions::notes`]
+    Reduced,
+}
+
+/// A song to add, with why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// The suggested song, as synced
+    pub song: Song,
+
+    /// How it scored, factor by factor
+    pub score: ScoreBreakdown,
+}
+
+/// Ranked suggestions for one playlist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suggestions {
+    /// Id of the playlist the songs are for
+    pub playlist_id: String,
+
+    /// Name of the playlist the songs are for
+    pub playlist_name: String,
+
+    /// Best first
+    pub suggestions: Vec<Suggestion>,
+
+    /// Library songs scored, i.e. those not in the playlist
+    pub candidates: usize,
+
+    /// Whether the scores had enough data behind them
+    pub confidence: Confidence,
+
+    /// Why confidence is reduced, one reason each
+    pub notes: Vec<String>,
+}
+
+/// Up to `limit` library songs for the playlist with id (or else name)
+/// `playlist`, best first
+///
+/// Songs scoring 0 are left out; ties are broken by title, then id.
+/// `None` when no synced playlist matches.
+pub fn suggest(library: &LibrarySnapshot, playlist: &str, limit: usize) -> Option<Suggestions> {
+    let target = library
+        .playlists
+        .values()
+        .find(|p| p.id == playlist)
+        .or_else(|| library.playlists.get(playlist))?;
+
+    let others: Vec<&Playlist> = library
+        .playlists
+        .values()
+        .filter(|p| p.id != target.id)
+        .collect();
+    let mut seen: BTreeSet<&str> = target.songs.iter().map(|song| song.id.as_str()).collect();
+    let candidates: Vec<&Song> = others
+        .iter()
+        .flat_map(|p| &p.songs)
+        .filter(|song| seen.insert(song.id.as_str()))
+        .collect();
+
+    let factors = Factors {
+        tags: library
+            .playlists
+            .values()
+            .flat_map(|p| &p.songs)
+            .any(|song| !song.tags.is_empty()),
+        co_occurrence: !others.is_empty(),
+    };
+
+    let mut notes = Vec::new();
+    match target.songs.len() {
+        0 => notes.push("The playlist is empty, so there is nothing to compare with".to_string()),
+        1 => notes.push("Based on a single song".to_string()),
+        _ => {}
+    }
+    if !factors.tags {
+        notes.push(
+            "No song in the library has tags; ranked on titles and shared playlists".to_string(),
+        );
+    }
+    if !factors.co_occurrence {
+        notes.push(
+            "No other playlists are synced, so songs that appear together can't be counted"
+                .to_string(),
+        );
+    }
+
+    let mut suggestions: Vec<Suggestion> = candidates
+        .iter()
+        .map(|song| Suggestion {
+            song: (*song).clone(),
+            score: score(song, &target.songs, &others, factors),
+        })
+        .filter(|suggestion| suggestion.score.total > 0.0)
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.score
+            .total
+            .total_cmp(&a.score.total)
+            .then_with(|| a.song.title.cmp(&b.song.title))
+            .then_with(|| a.song.id.cmp(&b.song.id))
+    });
+    suggestions.truncate(limit);
+
+    Some(Suggestions {
+        playlist_id: target.id.clone(),
+        playlist_name: target.name.clone(),
+        suggestions,
+        candidates: candidates.len(),
+        confidence: if notes.is_empty() {
+            Confidence::Normal
+        } else {
+            Confidence::Reduced
+        },
+        notes,
+    })
+}
+
+fn contains(playlist: &Playlist, song_id: &str) -> bool {
+    playlist.songs.iter().any(|song| song.id == song_id)
+}
+
+fn tag_set(song: &Song) -> BTreeSet<String> {
+    song.tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Lower-cased words of a title, ignoring punctuation
+fn title_words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Shared items over all items, 0 when both sets are empty
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: &str, title: &str, tags: &[&str]) -> Song {
+        Song::new(id, title, 120, format!("https://udio.com/songs/{}", id))
+            .with_tags(tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    fn playlist(id: &str, songs: Vec<Song>) -> Playlist {
+        Playlist::new(id, format!("Playlist {}", id)).with_songs(songs)
+    }
+
+    fn library(playlists: Vec<Playlist>) -> LibrarySnapshot {
+        LibrarySnapshot {
+            playlists: playlists.into_iter().map(|p| (p.name.clone(), p)).collect(),
+            ..LibrarySnapshot::default()
+        }
+    }
+
+    const ALL: Factors = Factors {
+        tags: true,
+        co_occurrence: true,
+    };
+
+    #[test]
+    fn test_score_factors() {
+        let seeds = vec![
+            song("1", "Night Drive", &["synthwave", "chill"]),
+            song("2", "Rain", &["lofi"]),
+        ];
+        let candidate = song("3", "Night Rain", &["Synthwave", "retro"]);
+        let other = playlist(
+            "p2",
+            vec![song("3", "Night Rain", &[]), song("1", "Night Drive", &[])],
+        );
+
+        let score = score(&candidate, &seeds, &[&other], ALL);
+        // {synthwave} of {synthwave, chill, retro}
+        assert!((score.tag_overlap - 1.0 / 3.0).abs() < 1e-9);
+        // "night rain" vs "rain": {rain} of {night, rain}
+        assert_eq!(score.title_similarity, 0.5);
+        // Song 1 shares a playlist with the candidate, song 2 doesn't
+        assert_eq!(score.co_occurrence, 0.5);
+        let expected = TAG_WEIGHT / 3.0 + TITLE_WEIGHT * 0.5 + CO_OCCURRENCE_WEIGHT * 0.5;
+        assert!((score.total - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_factors_rescale_weights() {
+        let seeds = vec![song("1", "Night Drive", &[])];
+        let candidate = song("2", "Night Drive", &[]);
+
+        let score = score(
+            &candidate,
+            &seeds,
+            &[],
+            Factors {
+                tags: false,
+                co_occurrence: false,
+            },
+        );
+        assert_eq!(score.title_similarity, 1.0);
+        assert_eq!(score.total, 1.0, "titles carry the whole weight");
+
+        let unrelated = song("3", "Morning", &[]);
+        let score = super::score(&unrelated, &seeds, &[], ALL);
+        assert_eq!(score.total, 0.0);
+    }
+
+    #[test]
+    fn test_suggest_ranks_and_explains() {
+        let library = library(vec![
+            playlist(
+                "p1",
+                vec![
+                    song("1", "Night Drive", &["synthwave"]),
+                    song("2", "City Lights", &["synthwave"]),
+                ],
+            ),
+            playlist(
+                "p2",
+                vec![song("1", "Night Drive", &[]), song("3", "Neon", &["rock"])],
+            ),
+            playlist(
+                "p3",
+                vec![
+                    song("4", "Night Sky", &["synthwave"]),
+                    song("5", "Morning", &["folk"]),
+                    song("2", "", &[]),
+                ],
+            ),
+            playlist("p4", vec![song("6", "Dawn", &["jazz"])]),
+        ]);
+
+        let result = suggest(&library, "p1", 10).unwrap();
+        assert_eq!(result.playlist_name, "Playlist p1");
+        assert_eq!(result.candidates, 4);
+        assert_eq!(result.confidence, Confidence::Normal);
+
+        let ids: Vec<&str> = result
+            .suggestions
+            .iter()
+            .map(|s| s.song.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["4", "5", "3"],
+            "a song scoring 0 on every factor isn't suggested"
+        );
+        assert_eq!(result.suggestions[0].score.tag_overlap, 1.0);
+        assert_eq!(result.suggestions[2].score.co_occurrence, 0.5);
+
+        assert_eq!(suggest(&library, "p1", 1).unwrap().suggestions.len(), 1);
+        assert_eq!(
+            suggest(&library, "Playlist p2", 10).unwrap().playlist_id,
+            "p2",
+            "names work too"
+        );
+        assert!(suggest(&library, "missing", 10).is_none());
+    }
+
+    #[test]
+    fn test_cold_start_reduces_confidence() {
+        let snapshot = library(vec![
+            playlist("p1", vec![song("1", "Night Drive", &[])]),
+            playlist(
+                "p2",
+                vec![song("1", "Night Drive", &[]), song("2", "Night Sky", &[])],
+            ),
+        ]);
+
+        let result = suggest(&snapshot, "p1", 10).unwrap();
+        assert_eq!(result.confidence, Confidence::Reduced);
+        assert_eq!(result.notes.len(), 2, "{:?}", result.notes);
+        assert_eq!(result.suggestions[0].song.id, "2");
+
+        let alone = library(vec![playlist("p1", vec![])]);
+        let result = suggest(&alone, "p1", 10).unwrap();
+        assert_eq!(result.candidates, 0);
+        assert!(result.suggestions.is_empty());
+        assert_eq!(result.confidence, Confidence::Reduced);
+    }
+}
@@ -9,6 +9,7 @@ use tokio::sync::RwLock;
 
 use crate::browser::{automation, selectors::Selectors};
 use crate::models::{PlaybackState, PlaybackStatus, RepeatMode};
+use crate::offline::LibraryStore;
 
 /// Controls music playback on Udio platform
 pub struct PlaybackController {
@@ -17,6 +18,9 @@ pub struct PlaybackController {
 
     /// Current playback state
     state: Arc<RwLock<PlaybackState>>,
+
+    /// Where state read from the page is kept for offline use
+    library: Option<Arc<LibraryStore>>,
 }
 
 impl PlaybackController {
@@ -25,6 +29,7 @@ impl PlaybackController {
         Self {
             selectors: Selectors::load_default(),
             state: Arc::new(RwLock::new(PlaybackState::new())),
+            library: None,
         }
     }
 
@@ -33,9 +38,16 @@ impl PlaybackController {
         Self {
             selectors,
             state: Arc::new(RwLock::new(PlaybackState::new())),
+            library: None,
         }
     }
 
+    /// Keep the playback state in a local library for offline use
+    pub fn with_library(mut self, library: Arc<LibraryStore>) -> Self {
+        self.library = Some(library);
+        self
+    }
+
     /// Play a specific song by ID
     pub async fn play_song(&self, page: &Page, song_id: &str) -> Result<PlaybackState> {
         tracing::info!("Playing song: {}", song_id);
@@ -154,7 +166,9 @@ impl PlaybackController {
             state.update_timestamp();
         }
 
-        self.get_state().await
+        let state = self.get_state().await?;
+        self.sync(&state);
+        Ok(state)
     }
 
     /// Get current playback state from the page
@@ -178,10 +192,20 @@ impl PlaybackController {
             let mut cached_state = self.state.write().await;
             *cached_state = state.clone();
         }
+        self.sync(&state);
 
         Ok(state)
     }
 
+    /// Save `state` to the library, if there is one
+    fn sync(&self, state: &PlaybackState) {
+        if let Some(library) = &self.library {
+            if let Err(e) = library.record_playback(state) {
+                tracing::warn!("Failed to save playback state to the library: {:#}", e);
+            }
+        }
+    }
+
     /// Get cached state (no page access)
     pub async fn get_state(&self) -> Result<PlaybackState> {
         Ok(self.state.read().await.clone())
@@ -27,6 +27,11 @@ impl PlaylistExtractor {
         Self { selectors }
     }
 
+    /// Selectors used to find elements
+    pub fn selectors(&self) -> &Selectors {
+        &self.selectors
+    }
+
     /// Extract playlist information from a page
     pub async fn extract_playlist(&self, page: &Page, playlist_name: &str) -> Result<Playlist> {
         tracing::info!("Extracting playlist: {}", playlist_name);
@@ -51,6 +56,7 @@ impl PlaylistExtractor {
             .unwrap()
             .as_secs();
 
+        let revision = Playlist::revision_of(&songs);
         let playlist = Playlist {
             id: playlist_id,
             name: playlist_name.to_string(),
@@ -62,6 +68,7 @@ impl PlaylistExtractor {
             created_at: now,
             updated_at: now,
             owner: None,
+            revision,
         };
 
         tracing::info!(
@@ -1,39 +1,81 @@
 // Playlist management and coordination
 // High-level interface for playlist operations
 
-use anyhow::{Context, Result};
-use std::collections::HashMap;
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
 use super::extractor::PlaylistExtractor;
+use super::source::{BrowserPlaylistSource, PlaylistSource};
 use crate::browser::BrowserManager;
-use crate::models::Playlist;
+use crate::events::{Event, EventBus};
+use crate::models::{Playlist, PlaylistDiff};
+use crate::offline::LibraryStore;
+
+/// Revisions remembered per playlist for explaining conflicts
+const REVISION_HISTORY: usize = 16;
+
+/// Revisions of one playlist with their song IDs, oldest first
+type RevisionHistory = VecDeque<(String, Vec<String>)>;
+
+/// An edit named a revision the playlist has since moved on from
+///
+/// Callers can downcast to this to report a conflict rather than a generic
+/// browser failure.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Playlist '{playlist}' is at revision {current}, not {expected}")]
+pub struct PlaylistConflict {
+    /// Playlist that changed
+    pub playlist: String,
+    /// Revision the edit was made against
+    pub expected: String,
+    /// Revision the playlist is at now
+    pub current: String,
+    /// Changes since `expected`, if that revision is recent enough to be
+    /// remembered
+    pub diff: Option<PlaylistDiff>,
+}
+
+/// Change made by [`PlaylistManager::add_song`] or
+/// [`PlaylistManager::remove_song`]
+enum Edit<'a> {
+    Add(&'a str),
+    Remove(&'a str),
+}
 
 /// Manages playlist operations
+///
+/// Loads and edits of the same playlist take turns on a per-playlist lock,
+/// so an edit always starts from what the last one left. Every playlist
+/// carries its [`Playlist::revision`] and is cached whole, so a cached
+/// revision always matches the cached songs.
 pub struct PlaylistManager {
-    /// Browser manager for page automation
-    browser_manager: Arc<BrowserManager>,
-
-    /// Playlist extractor
-    extractor: Arc<PlaylistExtractor>,
+    /// Where playlists are read and changed
+    source: Arc<dyn PlaylistSource>,
 
     /// In-memory cache of playlists
     cache: Arc<RwLock<HashMap<String, Playlist>>>,
 
-    /// Base URL for Udio
-    base_url: String,
+    /// Each playlist's recently fetched revisions, kept across cache
+    /// invalidation so changes can be announced and conflicts explained
+    history: Arc<RwLock<HashMap<String, RevisionHistory>>>,
+
+    /// One lock per playlist, taken to load or edit it
+    locks: std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>,
+
+    /// Where playlist changes are announced
+    events: Option<EventBus>,
+
+    /// Where fetched playlists are kept for offline use
+    library: Option<Arc<LibraryStore>>,
 }
 
 impl PlaylistManager {
     /// Create a new playlist manager
     pub fn new(browser_manager: Arc<BrowserManager>) -> Self {
-        Self {
-            browser_manager,
-            extractor: Arc::new(PlaylistExtractor::new()),
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            base_url: "https://www.udio.com".to_string(),
-        }
+        Self::with_source(Arc::new(BrowserPlaylistSource::new(browser_manager)))
     }
 
     /// Create with custom extractor
@@ -41,28 +83,143 @@ impl PlaylistManager {
         browser_manager: Arc<BrowserManager>,
         extractor: PlaylistExtractor,
     ) -> Self {
-        Self {
+        Self::with_source(Arc::new(BrowserPlaylistSource::with_extractor(
             browser_manager,
-            extractor: Arc::new(extractor),
+            extractor,
+        )))
+    }
+
+    /// Create reading and editing playlists through `source`
+    pub fn with_source(source: Arc<dyn PlaylistSource>) -> Self {
+        Self {
+            source,
             cache: Arc::new(RwLock::new(HashMap::new())),
-            base_url: "https://www.udio.com".to_string(),
+            history: Arc::new(RwLock::new(HashMap::new())),
+            locks: std::sync::Mutex::new(HashMap::new()),
+            events: None,
+            library: None,
         }
     }
 
+    /// Announce playlist changes on an event bus
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Keep fetched playlists in a local library for offline use
+    pub fn with_library(mut self, library: Arc<LibraryStore>) -> Self {
+        self.library = Some(library);
+        self
+    }
+
     /// Get a playlist by name
     pub async fn get_playlist(&self, playlist_name: &str) -> Result<Playlist> {
         // Check cache first
-        {
-            let cache = self.cache.read().await;
-            if let Some(playlist) = cache.get(playlist_name) {
-                tracing::debug!("Returning cached playlist: {}", playlist_name);
-                return Ok(playlist.clone());
-            }
+        if let Some(playlist) = self.cached(playlist_name).await {
+            tracing::debug!("Returning cached playlist: {}", playlist_name);
+            return Ok(playlist);
+        }
+
+        // An edit in progress would leave this fetch behind
+        let _guard = self.lock(playlist_name).await;
+        if let Some(playlist) = self.cached(playlist_name).await {
+            return Ok(playlist);
         }
 
         // Not in cache, fetch from Udio
         tracing::info!("Fetching playlist from Udio: {}", playlist_name);
-        let playlist = self.fetch_playlist(playlist_name).await?;
+        self.reload(playlist_name).await
+    }
+
+    /// Append a song to a playlist
+    ///
+    /// With an `expected_revision`, the edit is only made if the playlist
+    /// on Udio is still at that revision; otherwise it fails with a
+    /// [`PlaylistConflict`]. Returns the playlist as it is afterwards.
+    pub async fn add_song(
+        &self,
+        playlist_name: &str,
+        song_id: &str,
+        expected_revision: Option<&str>,
+    ) -> Result<Playlist> {
+        self.edit(playlist_name, Edit::Add(song_id), expected_revision)
+            .await
+    }
+
+    /// Take a song out of a playlist, checking `expected_revision` as
+    /// [`PlaylistManager::add_song`] does
+    pub async fn remove_song(
+        &self,
+        playlist_name: &str,
+        song_id: &str,
+        expected_revision: Option<&str>,
+    ) -> Result<Playlist> {
+        self.edit(playlist_name, Edit::Remove(song_id), expected_revision)
+            .await
+    }
+
+    async fn edit(
+        &self,
+        playlist_name: &str,
+        edit: Edit<'_>,
+        expected_revision: Option<&str>,
+    ) -> Result<Playlist> {
+        let _guard = self.lock(playlist_name).await;
+
+        // Check against Udio rather than the cache: other sessions edit
+        // playlists too
+        let current = self.reload(playlist_name).await?;
+        if let Some(expected) = expected_revision.filter(|r| *r != current.revision) {
+            let diff = self.diff_since(playlist_name, expected, &current).await;
+            return Err(PlaylistConflict {
+                playlist: playlist_name.to_string(),
+                expected: expected.to_string(),
+                current: current.revision,
+                diff,
+            }
+            .into());
+        }
+
+        let result = match edit {
+            Edit::Add(song_id) => self.source.add_song(playlist_name, song_id).await,
+            Edit::Remove(song_id) => self.source.remove_song(playlist_name, song_id).await,
+        };
+        if let Err(e) = result {
+            // The edit may have half happened; don't trust the cached copy
+            self.invalidate_cache(playlist_name).await;
+            return Err(e);
+        }
+
+        self.reload(playlist_name).await
+    }
+
+    /// Cached copy of a playlist
+    async fn cached(&self, playlist_name: &str) -> Option<Playlist> {
+        self.cache.read().await.get(playlist_name).cloned()
+    }
+
+    /// Wait for the playlist's turn to be loaded or edited
+    async fn lock(&self, playlist_name: &str) -> OwnedMutexGuard<()> {
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(playlist_name.to_string())
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// Fetch a playlist from Udio and cache it; callers hold its lock
+    async fn reload(&self, playlist_name: &str) -> Result<Playlist> {
+        let playlist = self.source.fetch(playlist_name).await?;
+        self.note_songs(&playlist).await;
+        if let Some(library) = &self.library {
+            if let Err(e) = library.record_playlist(&playlist) {
+                tracing::warn!("Failed to save playlist to the library: {:#}", e);
+            }
+        }
 
         // Cache the playlist
         {
@@ -73,72 +230,66 @@ impl PlaylistManager {
         Ok(playlist)
     }
 
-    /// Fetch playlist from Udio (no cache)
-    async fn fetch_playlist(&self, playlist_name: &str) -> Result<Playlist> {
-        // Launch browser if needed
-        self.browser_manager
-            .launch()
-            .await
-            .context("Failed to launch browser")?;
-
-        // Navigate to playlist page
-        let playlist_url = self.construct_playlist_url(playlist_name);
-        let page = self
-            .browser_manager
-            .new_page(&playlist_url)
-            .await
-            .context("Failed to create browser page")?;
-
-        tracing::debug!("Navigated to playlist page: {}", playlist_url);
-
-        // Wait for page to load
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    /// Changes from a remembered revision to `current`
+    async fn diff_since(
+        &self,
+        playlist_name: &str,
+        revision: &str,
+        current: &Playlist,
+    ) -> Option<PlaylistDiff> {
+        let history = self.history.read().await;
+        let (_, before) = history
+            .get(playlist_name)?
+            .iter()
+            .find(|(seen, _)| seen == revision)?;
+        Some(PlaylistDiff::between(before, &current.song_ids()))
+    }
 
-        // Extract playlist data
-        let playlist = self
-            .extractor
-            .extract_playlist(&page, playlist_name)
-            .await
-            .context("Failed to extract playlist data")?;
+    /// Remember a fetched playlist's songs, publishing
+    /// [`Event::PlaylistChanged`] if they differ from the previous fetch
+    async fn note_songs(&self, playlist: &Playlist) {
+        let current = playlist.song_ids();
+        let previous = {
+            let mut history = self.history.write().await;
+            let revisions = history.entry(playlist.name.clone()).or_default();
+            let previous = revisions.back().map(|(_, ids)| ids.clone());
+            if previous.as_ref() != Some(&current) {
+                revisions.retain(|(revision, _)| *revision != playlist.revision);
+                revisions.push_back((playlist.revision.clone(), current.clone()));
+                if revisions.len() > REVISION_HISTORY {
+                    revisions.pop_front();
+                }
+            }
+            previous
+        };
 
-        tracing::info!(
-            "Successfully fetched playlist '{}' with {} songs",
-            playlist_name,
-            playlist.song_count
-        );
+        // The first fetch is a baseline, not a change
+        let (Some(previous), Some(events)) = (previous, &self.events) else {
+            return;
+        };
 
-        Ok(playlist)
+        let diff = PlaylistDiff::between(&previous, &current);
+        if !diff.is_empty() {
+            tracing::info!(
+                "Playlist '{}' changed: {} added, {} removed",
+                playlist.name,
+                diff.added.len(),
+                diff.removed.len()
+            );
+            events.publish(Event::PlaylistChanged {
+                playlist: playlist.name.clone(),
+                added: diff.added,
+                removed: diff.removed,
+                song_count: current.len(),
+            });
+        }
     }
 
     /// List available playlists (requires navigation to playlists page)
     pub async fn list_playlists(&self) -> Result<Vec<String>> {
         tracing::info!("Listing playlists");
 
-        // Launch browser if needed
-        self.browser_manager
-            .launch()
-            .await
-            .context("Failed to launch browser")?;
-
-        // Navigate to playlists page
-        let playlists_url = format!("{}/playlists", self.base_url);
-        let _page = self
-            .browser_manager
-            .new_page(&playlists_url)
-            .await
-            .context("Failed to create browser page")?;
-
-        tracing::debug!("Navigated to playlists page: {}", playlists_url);
-
-        // Wait for page to load
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-
-        // Extract playlist names
-        // This is a simplified implementation - would need proper selector logic
-        let playlist_names = vec![
-            "ToPlay".to_string(),
-            // Add more playlists as discovered
-        ];
+        let playlist_names = self.source.list().await?;
 
         tracing::info!("Found {} playlists", playlist_names.len());
 
@@ -167,13 +318,6 @@ impl PlaylistManager {
             total_songs: cache.values().map(|p| p.song_count).sum(),
         }
     }
-
-    /// Construct URL for a playlist
-    fn construct_playlist_url(&self, playlist_name: &str) -> String {
-        // This is a simplified implementation
-        // Real implementation would need proper URL encoding and API discovery
-        format!("{}/playlists/{}", self.base_url, playlist_name)
-    }
 }
 
 /// Cache statistics
@@ -195,16 +339,7 @@ mod tests {
     fn test_playlist_manager_creation() {
         let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
         let manager = PlaylistManager::new(browser_manager);
-        assert_eq!(manager.base_url, "https://www.udio.com");
-    }
-
-    #[test]
-    fn test_construct_playlist_url() {
-        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
-        let manager = PlaylistManager::new(browser_manager);
-
-        let url = manager.construct_playlist_url("ToPlay");
-        assert_eq!(url, "https://www.udio.com/playlists/ToPlay");
+        assert!(manager.locks.lock().unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -233,17 +368,8 @@ mod tests {
         let extractor = PlaylistExtractor::new();
         let manager = PlaylistManager::with_extractor(browser_manager, extractor);
 
-        assert_eq!(manager.base_url, "https://www.udio.com");
-    }
-
-    #[test]
-    fn test_construct_url_with_special_chars() {
-        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
-        let manager = PlaylistManager::new(browser_manager);
-
-        let url = manager.construct_playlist_url("My Playlist");
-        assert!(url.contains("My Playlist"));
-        assert!(url.starts_with("https://www.udio.com/playlists/"));
+        assert!(manager.events.is_none());
+        assert!(manager.library.is_none());
     }
 
     #[tokio::test]
@@ -292,15 +418,6 @@ mod tests {
         assert!(debug_str.contains("42"));
     }
 
-    #[test]
-    fn test_base_url_https() {
-        let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
-        let manager = PlaylistManager::new(browser_manager);
-
-        assert!(manager.base_url.starts_with("https://"));
-        assert!(manager.base_url.contains("udio.com"));
-    }
-
     #[test]
     fn test_manager_arc_components() {
         let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
@@ -309,7 +426,9 @@ mod tests {
         let manager1 = PlaylistManager::new(browser_manager);
         let manager2 = PlaylistManager::new(browser_clone);
 
-        assert_eq!(manager1.base_url, manager2.base_url);
+        // Managers sharing a browser keep their own cache and history
+        assert!(!Arc::ptr_eq(&manager1.cache, &manager2.cache));
+        assert!(!Arc::ptr_eq(&manager1.history, &manager2.history));
     }
 
     #[tokio::test]
@@ -332,12 +451,189 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_playlist_url_format() {
+    #[tokio::test]
+    async fn test_playlist_changes_are_published() {
+        use crate::models::Song;
+
         let browser_manager = Arc::new(BrowserManager::new(BrowserConfig::default()));
-        let manager = PlaylistManager::new(browser_manager);
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+        let manager = PlaylistManager::new(browser_manager).with_event_bus(bus);
+
+        let with_songs = |ids: &[&str]| {
+            let mut playlist = Playlist::new("pl1", "ToPlay");
+            for id in ids {
+                playlist.add_song(Song::new(*id, "Song", 120, "https://udio.com"));
+            }
+            playlist
+        };
+
+        // First sighting and an unchanged refetch announce nothing
+        manager.note_songs(&with_songs(&["a", "b"])).await;
+        manager.note_songs(&with_songs(&["a", "b"])).await;
+        assert!(events.try_recv().is_err());
+
+        manager.note_songs(&with_songs(&["b", "c"])).await;
+        let envelope = events.try_recv().unwrap();
+        assert_eq!(
+            envelope.event,
+            Event::PlaylistChanged {
+                playlist: "ToPlay".to_string(),
+                added: vec!["c".to_string()],
+                removed: vec!["a".to_string()],
+                song_count: 2,
+            }
+        );
+    }
+
+    /// Playlists held in memory, counting edits in flight
+    #[derive(Default)]
+    struct MemorySource {
+        songs: std::sync::Mutex<HashMap<String, Vec<String>>>,
+        in_flight: std::sync::atomic::AtomicUsize,
+        most_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MemorySource {
+        fn with_playlist(name: &str, ids: &[&str]) -> Self {
+            let source = Self::default();
+            source.songs.lock().unwrap().insert(
+                name.to_string(),
+                ids.iter().map(|id| id.to_string()).collect(),
+            );
+            source
+        }
+
+        /// Change a playlist the way another session would
+        fn edit_elsewhere(&self, name: &str, ids: &[&str]) {
+            self.songs.lock().unwrap().insert(
+                name.to_string(),
+                ids.iter().map(|id| id.to_string()).collect(),
+            );
+        }
+
+        /// Apply an edit, yielding halfway so overlapping edits would show
+        async fn apply(&self, name: &str, edit: impl FnOnce(&mut Vec<String>)) -> Result<()> {
+            use std::sync::atomic::Ordering;
+
+            let running = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.most_in_flight.fetch_max(running, Ordering::SeqCst);
+            let mut ids = self
+                .songs
+                .lock()
+                .unwrap()
+                .get(name)
+                .cloned()
+                .unwrap_or_default();
+            tokio::task::yield_now().await;
+            edit(&mut ids);
+            self.songs.lock().unwrap().insert(name.to_string(), ids);
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PlaylistSource for MemorySource {
+        async fn fetch(&self, playlist_name: &str) -> Result<Playlist> {
+            use crate::models::Song;
+
+            let ids = self.songs.lock().unwrap().get(playlist_name).cloned();
+            let ids = ids.ok_or_else(|| anyhow::anyhow!("no playlist {}", playlist_name))?;
+            tokio::task::yield_now().await;
+            Ok(Playlist::new("pl1", playlist_name).with_songs(
+                ids.iter()
+                    .map(|id| Song::new(id.as_str(), "Song", 120, "https://udio.com"))
+                    .collect(),
+            ))
+        }
+
+        async fn list(&self) -> Result<Vec<String>> {
+            Ok(self.songs.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn add_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+            self.apply(playlist_name, |ids| ids.push(song_id.to_string()))
+                .await
+        }
+
+        async fn remove_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+            self.apply(playlist_name, |ids| ids.retain(|id| id != song_id))
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_edit_against_stale_revision_conflicts() {
+        let source = Arc::new(MemorySource::with_playlist("ToPlay", &["a", "b"]));
+        let manager = PlaylistManager::with_source(source.clone());
+
+        let before = manager.get_playlist("ToPlay").await.unwrap();
+        source.edit_elsewhere("ToPlay", &["b", "c"]);
+
+        let error = manager
+            .add_song("ToPlay", "d", Some(&before.revision))
+            .await
+            .unwrap_err();
+        let conflict = error.downcast_ref::<PlaylistConflict>().unwrap();
+        assert_eq!(conflict.expected, before.revision);
+        assert_eq!(
+            conflict.diff,
+            Some(PlaylistDiff {
+                added: vec!["c".to_string()],
+                removed: vec!["a".to_string()],
+            })
+        );
 
-        let url = manager.construct_playlist_url("TestPlaylist");
-        assert_eq!(url, "https://www.udio.com/playlists/TestPlaylist");
+        // The cache already holds what the conflict reported
+        let current = manager.get_playlist("ToPlay").await.unwrap();
+        assert_eq!(current.revision, conflict.current);
+        assert_eq!(current.song_ids(), ["b", "c"]);
+
+        let after = manager
+            .add_song("ToPlay", "d", Some(&current.revision))
+            .await
+            .unwrap();
+        assert_eq!(after.song_ids(), ["b", "c", "d"]);
+        assert_eq!(manager.get_playlist("ToPlay").await.unwrap(), after);
+
+        // Unknown revisions conflict without a diff
+        let error = manager
+            .remove_song("ToPlay", "b", Some("0000000000000000"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<PlaylistConflict>().unwrap().diff, None);
+    }
+
+    #[tokio::test]
+    async fn test_edits_to_one_playlist_take_turns() {
+        let source = Arc::new(MemorySource::with_playlist("ToPlay", &[]));
+        let manager = Arc::new(PlaylistManager::with_source(source.clone()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let manager = Arc::clone(&manager);
+                tokio::spawn(async move {
+                    let id = format!("song{}", i);
+                    manager.add_song("ToPlay", &id, None).await.unwrap();
+                    if i % 2 == 0 {
+                        manager.remove_song("ToPlay", &id, None).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let mut ids = manager.get_playlist("ToPlay").await.unwrap().song_ids();
+        ids.sort();
+        assert_eq!(ids, ["song1", "song3", "song5", "song7"]);
+        assert_eq!(
+            source
+                .most_in_flight
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
     }
 }
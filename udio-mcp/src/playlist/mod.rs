@@ -5,6 +5,9 @@
 pub mod extractor;
 /// Playlist manager
 pub mod manager;
+/// Reading and editing playlists on Udio
+pub mod source;
 
 pub use extractor::PlaylistExtractor;
-pub use manager::PlaylistManager;
+pub use manager::{PlaylistConflict, PlaylistManager};
+pub use source::{BrowserPlaylistSource, PlaylistSource};
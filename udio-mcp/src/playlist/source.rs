@@ -0,0 +1,224 @@
+// Where playlists are read from and changed
+// The manager goes through this trait so its caching and locking can be
+// exercised without a browser
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::extractor::PlaylistExtractor;
+use crate::browser::{automation, BrowserManager};
+use crate::models::Playlist;
+
+/// Playlists as stored on Udio
+#[async_trait]
+pub trait PlaylistSource: Send + Sync {
+    /// Read a playlist as it is now
+    async fn fetch(&self, playlist_name: &str) -> Result<Playlist>;
+
+    /// Names of the account's playlists
+    async fn list(&self) -> Result<Vec<String>>;
+
+    /// Append a song to a playlist
+    async fn add_song(&self, playlist_name: &str, song_id: &str) -> Result<()>;
+
+    /// Take a song out of a playlist
+    async fn remove_song(&self, playlist_name: &str, song_id: &str) -> Result<()>;
+}
+
+/// Reads and edits playlists on the Udio site through the browser
+pub struct BrowserPlaylistSource {
+    /// Browser manager for page automation
+    browser_manager: Arc<BrowserManager>,
+
+    /// Playlist extractor
+    extractor: PlaylistExtractor,
+
+    /// Base URL for Udio
+    base_url: String,
+}
+
+impl BrowserPlaylistSource {
+    /// Source using the default extractor
+    pub fn new(browser_manager: Arc<BrowserManager>) -> Self {
+        Self::with_extractor(browser_manager, PlaylistExtractor::new())
+    }
+
+    /// Source using a custom extractor
+    pub fn with_extractor(
+        browser_manager: Arc<BrowserManager>,
+        extractor: PlaylistExtractor,
+    ) -> Self {
+        Self {
+            browser_manager,
+            extractor,
+            base_url: "https://www.udio.com".to_string(),
+        }
+    }
+
+    /// Launch the browser if needed and open `url`
+    async fn open(&self, url: &str) -> Result<chromiumoxide::Page> {
+        self.browser_manager
+            .launch()
+            .await
+            .context("Failed to launch browser")?;
+
+        let page = self
+            .browser_manager
+            .new_page(url)
+            .await
+            .context("Failed to create browser page")?;
+
+        tracing::debug!("Navigated to: {}", url);
+
+        // Wait for page to load
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        Ok(page)
+    }
+
+    /// Construct URL for a playlist
+    fn construct_playlist_url(&self, playlist_name: &str) -> String {
+        // This is a simplified implementation
+        // Real implementation would need proper URL encoding and API discovery
+        format!("{}/playlists/{}", self.base_url, playlist_name)
+    }
+
+    /// Construct URL for a song
+    fn construct_song_url(&self, song_id: &str) -> String {
+        format!("{}/songs/{}", self.base_url, song_id)
+    }
+}
+
+#[async_trait]
+impl PlaylistSource for BrowserPlaylistSource {
+    async fn fetch(&self, playlist_name: &str) -> Result<Playlist> {
+        let page = self
+            .open(&self.construct_playlist_url(playlist_name))
+            .await?;
+
+        // Extract playlist data
+        let playlist = self
+            .extractor
+            .extract_playlist(&page, playlist_name)
+            .await
+            .context("Failed to extract playlist data")?;
+
+        tracing::info!(
+            "Successfully fetched playlist '{}' with {} songs",
+            playlist_name,
+            playlist.song_count
+        );
+
+        Ok(playlist)
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        let _page = self.open(&format!("{}/playlists", self.base_url)).await?;
+
+        // Extract playlist names
+        // This is a simplified implementation - would need proper selector logic
+        let playlist_names = vec![
+            "ToPlay".to_string(),
+            // Add more playlists as discovered
+        ];
+
+        Ok(playlist_names)
+    }
+
+    async fn add_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+        let page = self.open(&self.construct_song_url(song_id)).await?;
+        let selectors = &self.extractor.selectors().playlist;
+
+        automation::click_element(&page, &selectors.add_button)
+            .await
+            .context("Failed to open the playlist menu")?;
+        automation::wait_for_element(
+            &page,
+            &selectors.picker_option,
+            Duration::from_secs(5),
+            Duration::from_millis(200),
+        )
+        .await?;
+
+        // Pick the option naming the playlist
+        for option in
+            automation::find_elements_with_fallback(&page, &selectors.picker_option).await?
+        {
+            let name = option.inner_text().await.ok().flatten().unwrap_or_default();
+            if name.trim() == playlist_name {
+                option
+                    .click()
+                    .await
+                    .context("Failed to choose the playlist")?;
+                tracing::info!("Added song {} to playlist '{}'", song_id, playlist_name);
+                return Ok(());
+            }
+        }
+
+        anyhow::bail!(
+            "Playlist '{}' is not offered in the playlist menu",
+            playlist_name
+        )
+    }
+
+    async fn remove_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+        let page = self
+            .open(&self.construct_playlist_url(playlist_name))
+            .await?;
+
+        // The song's own row holds its remove button
+        let selectors: Vec<String> = self
+            .extractor
+            .selectors()
+            .playlist
+            .remove_button
+            .iter()
+            .map(|button| format!("[data-song-id='{}'] {}", song_id, button))
+            .collect();
+        automation::click_element(&page, &selectors)
+            .await
+            .with_context(|| format!("Song {} has no remove button", song_id))?;
+
+        tracing::info!("Removed song {} from playlist '{}'", song_id, playlist_name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::BrowserConfig;
+
+    fn source() -> BrowserPlaylistSource {
+        BrowserPlaylistSource::new(Arc::new(BrowserManager::new(BrowserConfig::default())))
+    }
+
+    #[test]
+    fn test_construct_playlist_url() {
+        let url = source().construct_playlist_url("ToPlay");
+        assert_eq!(url, "https://www.udio.com/playlists/ToPlay");
+    }
+
+    #[test]
+    fn test_construct_url_with_special_chars() {
+        let url = source().construct_playlist_url("My Playlist");
+        assert!(url.contains("My Playlist"));
+        assert!(url.starts_with("https://www.udio.com/playlists/"));
+    }
+
+    #[test]
+    fn test_construct_song_url() {
+        assert_eq!(
+            source().construct_song_url("abc123"),
+            "https://www.udio.com/songs/abc123"
+        );
+    }
+
+    #[test]
+    fn test_base_url_https() {
+        let source = source();
+        assert!(source.base_url.starts_with("https://"));
+        assert!(source.base_url.contains("udio.com"));
+    }
+}
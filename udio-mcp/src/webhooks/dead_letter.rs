@@ -0,0 +1,160 @@
+// Dead-letter log for webhooks
+// Keeps deliveries that never succeeded so they can be inspected or replayed
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::events::Envelope;
+
+/// A delivery that was given up on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Endpoint the event was meant for
+    pub url: String,
+
+    /// Attempts made before giving up
+    pub attempts: u32,
+
+    /// Why the last attempt failed
+    pub error: String,
+
+    /// When the delivery was given up on (Unix timestamp)
+    pub failed_at: u64,
+
+    /// The undelivered event
+    pub payload: Envelope,
+}
+
+impl DeadLetter {
+    /// Record a failed delivery, timestamped now
+    pub fn new(
+        url: impl Into<String>,
+        attempts: u32,
+        error: impl Into<String>,
+        payload: Envelope,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            attempts,
+            error: error.into(),
+            failed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            payload,
+        }
+    }
+}
+
+/// Append-only JSON-lines file of [`DeadLetter`]s
+pub struct DeadLetterLog {
+    /// File to append to; entries are only logged when unset
+    path: Option<PathBuf>,
+
+    /// Serializes appends from concurrent deliveries
+    write_lock: Mutex<()>,
+}
+
+impl DeadLetterLog {
+    /// Create a log writing to a file, or only to tracing when `None`
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// File entries are written to
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Record a failed delivery
+    pub async fn record(&self, letter: &DeadLetter) -> Result<()> {
+        tracing::error!(
+            "Webhook delivery of {} to {} failed after {} attempts: {}",
+            letter.payload.id,
+            letter.url,
+            letter.attempts,
+            letter.error
+        );
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut line = serde_json::to_string(letter)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .with_context(|| format!("Failed to open dead-letter log {}", path.display()))?;
+        file.write_all(line.as_bytes())
+            .await
+            .context("Failed to write dead-letter entry")?;
+        // Tokio hands writes to a background thread; wait for this one to land
+        file.flush()
+            .await
+            .context("Failed to write dead-letter entry")?;
+        Ok(())
+    }
+
+    /// Read every entry from a dead-letter file
+    pub fn read_all(path: impl AsRef<Path>) -> Result<Vec<DeadLetter>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read dead-letter log {}", path.display()))?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("Invalid dead-letter entry"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Event;
+
+    fn envelope(id: &str) -> Envelope {
+        Envelope {
+            id: id.to_string(),
+            timestamp: 1,
+            event: Event::AuthExpired {
+                email: "user@example.com".to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_appends_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dead.jsonl");
+        let log = DeadLetterLog::new(Some(path.clone()));
+
+        let first = DeadLetter::new("http://a", 5, "HTTP 500", envelope("evt_1"));
+        let second = DeadLetter::new("http://b", 1, "HTTP 410", envelope("evt_2"));
+        log.record(&first).await.unwrap();
+        log.record(&second).await.unwrap();
+
+        assert_eq!(DeadLetterLog::read_all(&path).unwrap(), vec![first, second]);
+    }
+
+    #[tokio::test]
+    async fn test_record_without_path_only_logs() {
+        let log = DeadLetterLog::new(None);
+        assert!(log.path().is_none());
+        log.record(&DeadLetter::new("http://a", 1, "boom", envelope("evt_1")))
+            .await
+            .unwrap();
+    }
+}
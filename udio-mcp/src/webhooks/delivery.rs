@@ -0,0 +1,217 @@
+// Webhook delivery
+// POSTs each event to the endpoints that want it, retrying with backoff
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use super::dead_letter::{DeadLetter, DeadLetterLog};
+use super::signature::{sign, SIGNATURE_HEADER};
+use super::{WebhookConfig, WebhookEndpoint};
+use crate::events::{Envelope, EventBus};
+
+/// Header naming the event, e.g. `generation_completed`
+pub const EVENT_HEADER: &str = "X-Udio-Event";
+
+/// Header carrying the event ID, the same across retries
+pub const DELIVERY_HEADER: &str = "X-Udio-Delivery";
+
+/// How a delivery to one endpoint ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The endpoint answered with a 2xx status
+    Delivered {
+        /// Attempts it took, including the successful one
+        attempts: u32,
+    },
+
+    /// Every attempt failed, or the endpoint refused the payload outright;
+    /// the delivery was written to the dead-letter log
+    DeadLettered {
+        /// Attempts made
+        attempts: u32,
+        /// Why the last attempt failed
+        error: String,
+    },
+}
+
+/// Why a single attempt failed
+enum AttemptError {
+    /// Network trouble, a timeout, or a status worth retrying
+    Transient(String),
+    /// A status that retrying won't fix
+    Permanent(String),
+}
+
+/// Sends bus events to the configured webhook endpoints
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    config: WebhookConfig,
+    dead_letters: DeadLetterLog,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher for a configuration
+    pub fn new(config: WebhookConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .user_agent(concat!("udio-mcp-server/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create webhook HTTP client")?;
+
+        Ok(Self {
+            client,
+            dead_letters: DeadLetterLog::new(config.dead_letter_path.clone()),
+            config,
+        })
+    }
+
+    /// The dispatcher's configuration
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    /// Deliver an event to every endpoint that accepts it, concurrently
+    ///
+    /// Returns one outcome per accepting endpoint, in configuration order.
+    pub async fn dispatch(&self, envelope: &Envelope) -> Vec<DeliveryOutcome> {
+        let kind = envelope.event.kind();
+        let deliveries = self
+            .config
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.accepts(kind))
+            .map(|endpoint| self.deliver(endpoint, envelope));
+        futures::future::join_all(deliveries).await
+    }
+
+    /// Deliver an event to one endpoint, retrying transient failures
+    pub async fn deliver(
+        &self,
+        endpoint: &WebhookEndpoint,
+        envelope: &Envelope,
+    ) -> DeliveryOutcome {
+        let body = match serde_json::to_vec(envelope) {
+            Ok(body) => body,
+            Err(e) => return self.give_up(endpoint, envelope, 0, e.to_string()).await,
+        };
+
+        let max_attempts = self.config.retry.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.attempt(endpoint, envelope, &body).await {
+                Ok(()) => {
+                    tracing::debug!(
+                        "Delivered {} to {} on attempt {}",
+                        envelope.id,
+                        endpoint.url,
+                        attempt
+                    );
+                    return DeliveryOutcome::Delivered { attempts: attempt };
+                }
+                Err(AttemptError::Permanent(error)) => {
+                    return self.give_up(endpoint, envelope, attempt, error).await;
+                }
+                Err(AttemptError::Transient(error)) if attempt >= max_attempts => {
+                    return self.give_up(endpoint, envelope, attempt, error).await;
+                }
+                Err(AttemptError::Transient(error)) => {
+                    let backoff = self.config.retry.backoff(attempt);
+                    tracing::warn!(
+                        "Webhook delivery of {} to {} failed ({}), retrying in {:?}",
+                        envelope.id,
+                        endpoint.url,
+                        error,
+                        backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Deliver every event published on the bus until it closes
+    ///
+    /// Each event is dispatched in its own task so a slow endpoint doesn't
+    /// hold up later events; deliveries may therefore arrive out of order.
+    pub fn spawn(self, bus: &EventBus) -> JoinHandle<()> {
+        let mut events = bus.subscribe();
+        let dispatcher = Arc::new(self);
+
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(envelope) => {
+                        let dispatcher = dispatcher.clone();
+                        tokio::spawn(async move {
+                            dispatcher.dispatch(&envelope).await;
+                        });
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!(
+                            "Webhook dispatcher fell behind, {} events not sent",
+                            missed
+                        );
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn attempt(
+        &self,
+        endpoint: &WebhookEndpoint,
+        envelope: &Envelope,
+        body: &[u8],
+    ) -> std::result::Result<(), AttemptError> {
+        let mut request = self
+            .client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, envelope.event.kind().to_string())
+            .header(DELIVERY_HEADER, &envelope.id)
+            .body(body.to_vec());
+        if let Some(secret) = &endpoint.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, body));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AttemptError::Transient(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let error = format!("HTTP {}", status.as_u16());
+        // Rate limits, timeouts and server errors may clear up; anything else
+        // means the endpoint doesn't want this payload
+        if status.is_server_error()
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        {
+            Err(AttemptError::Transient(error))
+        } else {
+            Err(AttemptError::Permanent(error))
+        }
+    }
+
+    async fn give_up(
+        &self,
+        endpoint: &WebhookEndpoint,
+        envelope: &Envelope,
+        attempts: u32,
+        error: String,
+    ) -> DeliveryOutcome {
+        let letter = DeadLetter::new(&endpoint.url, attempts, &error, envelope.clone());
+        if let Err(e) = self.dead_letters.record(&letter).await {
+            tracing::error!("Failed to record dead letter: {:#}", e);
+        }
+        DeliveryOutcome::DeadLettered { attempts, error }
+    }
+}
@@ -0,0 +1,249 @@
+// Outbound webhooks
+// Forwards events from the bus to configured HTTP endpoints
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::events::EventKind;
+
+/// Permanently failed deliveries
+pub mod dead_letter;
+/// Delivery with retries
+pub mod delivery;
+/// Payload signing
+pub mod signature;
+
+pub use dead_letter::{DeadLetter, DeadLetterLog};
+pub use delivery::{DeliveryOutcome, WebhookDispatcher};
+pub use signature::{sign, verify, SIGNATURE_HEADER};
+
+/// Webhook setup, usually loaded from a TOML file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Where deliveries go
+    #[serde(default)]
+    pub endpoints: Vec<WebhookEndpoint>,
+
+    /// How failed deliveries are retried
+    #[serde(default)]
+    pub retry: RetryPolicy,
+
+    /// JSON-lines file recording deliveries that never succeeded; failures
+    /// are only logged when unset
+    #[serde(default)]
+    pub dead_letter_path: Option<PathBuf>,
+
+    /// Seconds to wait for an endpoint to respond
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+fn default_request_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            retry: RetryPolicy::default(),
+            dead_letter_path: None,
+            request_timeout_secs: default_request_timeout_secs(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Load a configuration file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read webhook config {}", path.display()))?;
+        Self::from_toml(&content)
+            .with_context(|| format!("Invalid webhook config {}", path.display()))
+    }
+
+    /// Parse a configuration from TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        for endpoint in &config.endpoints {
+            url::Url::parse(&endpoint.url)
+                .with_context(|| format!("Invalid webhook URL: {}", endpoint.url))?;
+        }
+        Ok(config)
+    }
+
+    /// Request deadline for a single delivery attempt
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+}
+
+/// One destination for events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// URL the JSON payload is POSTed to
+    pub url: String,
+
+    /// Events to send; empty means all
+    #[serde(default)]
+    pub events: Vec<EventKind>,
+
+    /// Shared secret for the HMAC-SHA256 signature header; unsigned if unset
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl WebhookEndpoint {
+    /// Create an endpoint receiving every event, unsigned
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            events: Vec::new(),
+            secret: None,
+        }
+    }
+
+    /// Only send these events
+    pub fn with_events(mut self, events: Vec<EventKind>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Sign payloads with this secret
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Whether this endpoint wants an event
+    pub fn accepts(&self, kind: EventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// Exponential backoff between delivery attempts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Attempts before a delivery is dead-lettered, including the first
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Wait before the first retry, doubled for each one after
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Longest wait between attempts
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    60_000
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after a failed attempt, counting attempts from 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = WebhookConfig::from_toml(
+            r#"
+            dead_letter_path = "dead.jsonl"
+
+            [retry]
+            max_attempts = 3
+
+            [[endpoints]]
+            url = "https://hooks.example.com/udio"
+            events = ["generation_completed", "auth_expired"]
+            secret = "s3cret"
+
+            [[endpoints]]
+            url = "http://localhost:9000/all"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.dead_letter_path, Some(PathBuf::from("dead.jsonl")));
+        assert_eq!(config.retry.max_attempts, 3);
+        assert_eq!(config.retry.initial_backoff_ms, 1_000);
+        assert_eq!(config.request_timeout(), Duration::from_secs(10));
+        assert_eq!(
+            config.endpoints,
+            vec![
+                WebhookEndpoint::new("https://hooks.example.com/udio")
+                    .with_events(vec![EventKind::GenerationCompleted, EventKind::AuthExpired])
+                    .with_secret("s3cret"),
+                WebhookEndpoint::new("http://localhost:9000/all"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_rejects_bad_input() {
+        assert!(WebhookConfig::from_toml("[[endpoints]]\nurl = \"not a url\"").is_err());
+        assert!(WebhookConfig::from_toml(
+            "[[endpoints]]\nurl = \"http://localhost\"\nevents = [\"song_liked\"]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_endpoint_filters() {
+        let all = WebhookEndpoint::new("http://localhost");
+        assert!(all.accepts(EventKind::PlaylistChanged));
+
+        let filtered = all.with_events(vec![EventKind::AuthExpired]);
+        assert!(filtered.accepts(EventKind::AuthExpired));
+        assert!(!filtered.accepts(EventKind::PlaylistChanged));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff(100), Duration::from_millis(1_000));
+    }
+}
@@ -0,0 +1,184 @@
+// Audio analysis integration tests
+// Synthesizes click tracks at known tempos and runs them through the decoder
+
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use udio_mcp_server::analysis::{AnalysisError, AudioAnalyzer};
+use udio_mcp_server::mcp::error::McpError;
+use udio_mcp_server::mcp::tools::{AnalyzeAudioTool, Tool};
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// A click track: short decaying 1 kHz bursts on every beat
+fn click_track(bpm: f64, seconds: f64, amplitude: f32) -> Vec<f32> {
+    let total = (f64::from(SAMPLE_RATE) * seconds) as usize;
+    let beat = f64::from(SAMPLE_RATE) * 60.0 / bpm;
+    let click_len = (SAMPLE_RATE / 50) as usize;
+
+    let mut samples = vec![0.0f32; total];
+    let mut position = 0.0;
+    while (position as usize) < total {
+        let start = position as usize;
+        for (i, sample) in samples[start..].iter_mut().take(click_len).enumerate() {
+            let t = i as f64 / f64::from(SAMPLE_RATE);
+            let envelope = (-t * 250.0).exp();
+            *sample =
+                amplitude * (envelope * (2.0 * std::f64::consts::PI * 1000.0 * t).sin()) as f32;
+        }
+        position += beat;
+    }
+    samples
+}
+
+/// Write 16-bit PCM WAV
+fn write_wav(path: &Path, channels: u16, samples: &[f32]) {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * u32::from(channels) * 2).to_le_bytes());
+    bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn click_file(dir: &Path, bpm: f64) -> PathBuf {
+    let path = dir.join(format!("click-{}.wav", bpm));
+    write_wav(&path, 1, &click_track(bpm, 20.0, 0.8));
+    path
+}
+
+#[test]
+fn test_click_track_tempo_within_two_bpm() {
+    let dir = tempfile::tempdir().unwrap();
+    let analyzer = AudioAnalyzer::new();
+
+    for bpm in [72.0, 90.0, 120.0, 128.0, 140.0, 174.0] {
+        let analysis = analyzer.analyze(click_file(dir.path(), bpm)).unwrap();
+
+        let estimate = analysis.bpm.expect("tempo estimate");
+        assert!(
+            (estimate - bpm).abs() <= 2.0,
+            "{} BPM click track estimated at {:.2}",
+            bpm,
+            estimate
+        );
+    }
+}
+
+#[test]
+fn test_duration_loudness_and_peak() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = click_file(dir.path(), 120.0);
+
+    let analysis = AudioAnalyzer::new().analyze(&path).unwrap();
+
+    assert_eq!(analysis.codec, "pcm_s16le");
+    assert_eq!(analysis.sample_rate, SAMPLE_RATE);
+    assert_eq!(analysis.channels, 1);
+    assert!((analysis.duration_seconds - 20.0).abs() < 0.01);
+
+    // The decaying envelope keeps the clicks a little under their nominal level
+    let peak = analysis.peak_dbfs.unwrap();
+    assert!(peak < 20.0 * 0.8f64.log10() && peak > -3.0, "{}", peak);
+
+    // Mostly silence, so far quieter than the clicks' peak
+    let lufs = analysis.loudness_lufs.unwrap();
+    assert!(lufs < -10.0 && lufs > -50.0, "{}", lufs);
+}
+
+#[test]
+fn test_stereo_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stereo.wav");
+    let interleaved: Vec<f32> = click_track(100.0, 15.0, 0.5)
+        .into_iter()
+        .flat_map(|s| [s, s])
+        .collect();
+    write_wav(&path, 2, &interleaved);
+
+    let analysis = AudioAnalyzer::new().analyze(&path).unwrap();
+
+    assert_eq!(analysis.channels, 2);
+    assert!((analysis.duration_seconds - 15.0).abs() < 0.01);
+    assert!((analysis.bpm.unwrap() - 100.0).abs() <= 2.0);
+}
+
+#[test]
+fn test_results_are_cached_by_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = click_file(dir.path(), 120.0);
+    let copy = dir.path().join("renamed.wav");
+    std::fs::copy(&original, &copy).unwrap();
+
+    let analyzer = AudioAnalyzer::new();
+    let first = analyzer.analyze(&original).unwrap();
+    let second = analyzer.analyze(&copy).unwrap();
+
+    assert_eq!(analyzer.cached_count(), 1);
+    assert_eq!(first.sha256, second.sha256);
+    assert_eq!(second.path, copy);
+    assert_eq!(first.bpm, second.bpm);
+
+    analyzer.analyze(click_file(dir.path(), 90.0)).unwrap();
+    assert_eq!(analyzer.cached_count(), 2);
+}
+
+#[test]
+fn test_unsupported_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "not audio at all\n".repeat(64)).unwrap();
+
+    match AudioAnalyzer::new().analyze(&path) {
+        Err(AnalysisError::UnsupportedFormat { .. }) => {}
+        other => panic!("Expected UnsupportedFormat, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_analyze_audio_tool() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = click_file(dir.path(), 128.0);
+    let tool = AnalyzeAudioTool::new(Arc::new(AudioAnalyzer::new()));
+
+    let output = tool
+        .execute(json!({"path": path.display().to_string()}))
+        .await
+        .unwrap();
+
+    let json = output.to_json();
+    assert_eq!(json["duration_formatted"], "00:20");
+    assert!((json["bpm"].as_f64().unwrap() - 128.0).abs() <= 2.0);
+    assert!(output.to_markdown(1000).contains("- Tempo: ~128 BPM"));
+
+    let text_file = dir.path().join("lyrics.txt");
+    std::fs::write(&text_file, "la la la\n".repeat(64)).unwrap();
+    let error = match tool
+        .execute(json!({"path": text_file.display().to_string()}))
+        .await
+    {
+        Err(error) => error,
+        Ok(_) => panic!("Expected an error for a text file"),
+    };
+    assert!(matches!(error, McpError::UnsupportedFormat { .. }));
+
+    let data = serde_json::to_value(error.to_error_object()).unwrap()["data"].clone();
+    assert_eq!(data["category"], "unsupported_format");
+    assert_eq!(
+        data["supported_formats"],
+        json!(["MP3", "WAV", "FLAC", "Ogg Vorbis", "Matroska/WebM"])
+    );
+}
@@ -0,0 +1,318 @@
+// Integration tests for offline mode
+// A mock connectivity flag stands in for Udio; each tool category is routed
+// through the registry as the flag flips
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use udio_mcp_server::mcp::error::{McpError, McpResult};
+use udio_mcp_server::mcp::tools::{
+    BoxedOutput, DryRun, DryRunPlan, LibraryStatsTool, OfflineFallback, OfflineMode,
+    PlannedAction, SearchSongsTool, Tool, ToolKind, ToolRegistry,
+};
+use udio_mcp_server::models::{Playlist, Song};
+use udio_mcp_server::offline::{Connectivity, HealthProbe, LibraryStore, OfflineConfig};
+
+/// Whether the pretend Udio answers
+#[derive(Clone)]
+struct Network(Arc<AtomicBool>);
+
+impl Network {
+    fn up() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+
+    fn set(&self, up: bool) {
+        self.0.store(up, Ordering::SeqCst);
+    }
+
+    fn is_up(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Browser failure as the real tools report it
+    fn check(&self, action: &str) -> McpResult<()> {
+        if self.is_up() {
+            Ok(())
+        } else {
+            Err(McpError::browser(
+                action,
+                anyhow::anyhow!("net::ERR_NAME_NOT_RESOLVED"),
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for Network {
+    async fn is_up(&self) -> bool {
+        Network::is_up(self)
+    }
+}
+
+/// Browser-backed read, syncing what it fetches like `list_playlist_songs`
+struct GetPlaylist {
+    network: Network,
+    library: Arc<LibraryStore>,
+}
+
+#[async_trait]
+impl Tool for GetPlaylist {
+    fn name(&self) -> &str {
+        "get_playlist"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch a playlist from Udio"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({"type": "object"})
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    fn offline(&self) -> Option<&dyn OfflineFallback> {
+        Some(self)
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        self.network.check("Get playlist")?;
+        let playlist = Playlist::new("p1", "ToPlay").with_songs(vec![Song::new(
+            "s1",
+            "Night Drive",
+            200,
+            "https://www.udio.com/songs/s1",
+        )]);
+        self.library.record_playlist(&playlist).unwrap();
+        Ok(Box::new(playlist))
+    }
+}
+
+#[async_trait]
+impl OfflineFallback for GetPlaylist {
+    async fn execute_offline(&self, _params: &Value) -> McpResult<BoxedOutput> {
+        let playlist = self
+            .library
+            .playlist("ToPlay")
+            .ok_or_else(|| McpError::not_found("synced playlist", "ToPlay"))?;
+        Ok(Box::new(playlist))
+    }
+}
+
+/// Browser-backed change, counting the times it really ran
+struct PlaySong {
+    network: Network,
+    runs: AtomicUsize,
+}
+
+#[async_trait]
+impl Tool for PlaySong {
+    fn name(&self) -> &str {
+        "play_song"
+    }
+
+    fn description(&self) -> &str {
+        "Play a song on Udio"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({"type": "object"})
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::Mutating(self)
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        self.network.check("Play song")?;
+        Ok(Box::new(json!({"status": "playing"})))
+    }
+}
+
+#[async_trait]
+impl DryRun for PlaySong {
+    async fn plan(&self, _params: &Value) -> McpResult<DryRunPlan> {
+        Ok(DryRunPlan::new(self.name()).with_action(PlannedAction::new("would play 's1'")))
+    }
+}
+
+/// Tool that never leaves the machine, like `analyze_audio`
+struct LocalTool;
+
+#[async_trait]
+impl Tool for LocalTool {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn description(&self) -> &str {
+        "Work on local files"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({"type": "object"})
+    }
+
+    fn kind(&self) -> ToolKind<'_> {
+        ToolKind::ReadOnly
+    }
+
+    async fn execute(&self, _params: Value) -> McpResult<BoxedOutput> {
+        Ok(Box::new(json!({"local": true})))
+    }
+}
+
+struct Fixture {
+    registry: ToolRegistry,
+    network: Network,
+    connectivity: Connectivity,
+    play_song: Arc<PlaySong>,
+}
+
+fn fixture(config: OfflineConfig) -> Fixture {
+    let network = Network::up();
+    let library = Arc::new(LibraryStore::in_memory());
+    let connectivity = Connectivity::new(&config);
+    let play_song = Arc::new(PlaySong {
+        network: network.clone(),
+        runs: AtomicUsize::new(0),
+    });
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .register(Arc::new(GetPlaylist {
+            network: network.clone(),
+            library: library.clone(),
+        }))
+        .unwrap();
+    registry.register(play_song.clone()).unwrap();
+    registry.register(Arc::new(LocalTool)).unwrap();
+    registry
+        .register(Arc::new(SearchSongsTool::new(library.clone())))
+        .unwrap();
+    registry
+        .register(Arc::new(LibraryStatsTool::new(library.clone())))
+        .unwrap();
+    registry.set_offline(OfflineMode::new(connectivity.clone(), library));
+
+    Fixture {
+        registry,
+        network,
+        connectivity,
+        play_song,
+    }
+}
+
+impl Fixture {
+    async fn call(&self, tool: &str, params: Value) -> McpResult<Value> {
+        self.registry
+            .execute(tool, params)
+            .await
+            .map(|output| output.to_json())
+    }
+}
+
+#[tokio::test]
+async fn test_offline_routing_by_tool_category() {
+    let f = fixture(OfflineConfig::default());
+
+    // Online: real calls, nothing stale, and the playlist is synced
+    let playlist = f.call("get_playlist", json!({})).await.unwrap();
+    assert_eq!(playlist["stale"], Value::Null);
+    assert_eq!(
+        f.call("play_song", json!({})).await.unwrap()["status"],
+        "playing"
+    );
+
+    // Repeated browser failures switch to offline mode
+    f.network.set(false);
+    for _ in 0..3 {
+        let error = f.call("get_playlist", json!({})).await.unwrap_err();
+        assert!(matches!(error, McpError::BrowserFailure { .. }), "{:?}", error);
+    }
+    assert!(f.connectivity.is_offline());
+
+    // Browser-backed reads answer from the library, marked stale
+    let playlist = f.call("get_playlist", json!({})).await.unwrap();
+    assert_eq!(playlist["stale"], true);
+    assert!(playlist["last_sync"].as_u64().is_some());
+    assert_eq!(playlist["songs"][0]["title"], "Night Drive");
+
+    // Library reads are marked stale too
+    let search = f.call("search_songs", json!({"query": "night"})).await.unwrap();
+    assert_eq!(search["stale"], true);
+    assert_eq!(search["total"], 1);
+    let stats = f.call("library_stats", json!({})).await.unwrap();
+    assert_eq!(stats["stale"], true);
+    assert_eq!(stats["songs"], 1);
+
+    // Mutations fail at once without running, but can still be planned
+    let runs = f.play_song.runs.load(Ordering::SeqCst);
+    match f.call("play_song", json!({})).await.unwrap_err() {
+        McpError::Offline { last_sync } => assert!(last_sync.is_some()),
+        error => panic!("expected Offline, got {:?}", error),
+    }
+    assert_eq!(f.play_song.runs.load(Ordering::SeqCst), runs);
+    let plan = f
+        .call("play_song", json!({"dry_run": true}))
+        .await
+        .unwrap();
+    assert_eq!(plan["dry_run"], true);
+
+    // Local tools are unaffected
+    let local = f.call("local", json!({})).await.unwrap();
+    assert_eq!(local, json!({"local": true}));
+
+    // A failing health check keeps the server offline; a passing one ends it
+    assert!(!f.connectivity.check(&f.network).await);
+    f.network.set(true);
+    assert!(f.connectivity.check(&f.network).await);
+
+    let playlist = f.call("get_playlist", json!({})).await.unwrap();
+    assert_eq!(playlist["stale"], Value::Null);
+    f.call("play_song", json!({})).await.unwrap();
+    assert_eq!(f.play_song.runs.load(Ordering::SeqCst), runs + 1);
+}
+
+#[tokio::test]
+async fn test_library_reads_do_not_hide_failures() {
+    let f = fixture(OfflineConfig::default());
+    f.network.set(false);
+
+    // Library-only successes between failures must not reset the count
+    for _ in 0..3 {
+        f.call("get_playlist", json!({})).await.unwrap_err();
+        f.call("library_stats", json!({})).await.unwrap();
+        f.call("local", json!({})).await.unwrap();
+    }
+    assert!(f.connectivity.is_offline());
+}
+
+#[tokio::test]
+async fn test_forced_offline_without_a_sync() {
+    let f = fixture(OfflineConfig {
+        forced: true,
+        ..OfflineConfig::default()
+    });
+
+    // Udio answering does not end a forced offline mode
+    assert!(!f.connectivity.check(&f.network).await);
+
+    match f.call("play_song", json!({})).await.unwrap_err() {
+        McpError::Offline { last_sync } => assert_eq!(last_sync, None),
+        error => panic!("expected Offline, got {:?}", error),
+    }
+    assert_eq!(f.play_song.runs.load(Ordering::SeqCst), 0);
+
+    // Nothing was ever synced, so there is nothing to serve
+    let error = f.call("get_playlist", json!({})).await.unwrap_err();
+    assert!(matches!(error, McpError::NotFound { .. }), "{:?}", error);
+    let stats = f.call("library_stats", json!({})).await.unwrap();
+    assert_eq!(stats["stale"], true);
+    assert_eq!(stats["last_sync"], Value::Null);
+}
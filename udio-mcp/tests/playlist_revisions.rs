@@ -0,0 +1,272 @@
+// Integration tests for playlist revisions
+// Several clients edit one in-memory playlist at once through the registry;
+// those that follow the revision protocol must never lose or duplicate a
+// track
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use udio_mcp_server::mcp::error::{McpError, McpResult};
+use udio_mcp_server::mcp::tools::{EditPlaylistTool, ListPlaylistSongsTool, ToolRegistry};
+use udio_mcp_server::models::{Playlist, Song};
+use udio_mcp_server::playlist::{PlaylistManager, PlaylistSource};
+
+/// Pretend Udio holding playlists as song IDs
+///
+/// Edits read, yield and write back, so two edits running at once would
+/// lose one of them.
+#[derive(Default)]
+struct MemoryUdio {
+    playlists: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl MemoryUdio {
+    fn ids(&self, playlist_name: &str) -> Vec<String> {
+        self.playlists
+            .lock()
+            .unwrap()
+            .get(playlist_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn apply(&self, playlist_name: &str, edit: impl FnOnce(&mut Vec<String>)) -> Result<()> {
+        let mut ids = self.ids(playlist_name);
+        tokio::task::yield_now().await;
+        edit(&mut ids);
+        self.playlists
+            .lock()
+            .unwrap()
+            .insert(playlist_name.to_string(), ids);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PlaylistSource for MemoryUdio {
+    async fn fetch(&self, playlist_name: &str) -> Result<Playlist> {
+        let songs = self
+            .ids(playlist_name)
+            .iter()
+            .map(|id| Song::new(id.as_str(), id.as_str(), 120, "https://www.udio.com"))
+            .collect();
+        tokio::task::yield_now().await;
+        Ok(Playlist::new("pl1", playlist_name).with_songs(songs))
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        Ok(self.playlists.lock().unwrap().keys().cloned().collect())
+    }
+
+    async fn add_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+        self.apply(playlist_name, |ids| ids.push(song_id.to_string()))
+            .await
+    }
+
+    async fn remove_song(&self, playlist_name: &str, song_id: &str) -> Result<()> {
+        self.apply(playlist_name, |ids| {
+            if let Some(index) = ids.iter().position(|id| id == song_id) {
+                ids.remove(index);
+            }
+        })
+        .await
+    }
+}
+
+fn registry(udio: Arc<MemoryUdio>) -> Arc<ToolRegistry> {
+    let manager = Arc::new(PlaylistManager::with_source(udio));
+    let mut registry = ToolRegistry::new();
+    registry
+        .register(Arc::new(ListPlaylistSongsTool::new(manager.clone())))
+        .unwrap();
+    registry
+        .register(Arc::new(EditPlaylistTool::new(manager)))
+        .unwrap();
+    Arc::new(registry)
+}
+
+/// Revision and song IDs of the playlist as a client reads it
+async fn read(registry: &ToolRegistry) -> (String, Vec<String>) {
+    let json = registry
+        .execute(
+            "list_playlist_songs",
+            json!({"playlist_name": "Mix", "limit": 100}),
+        )
+        .await
+        .unwrap()
+        .to_json();
+    let ids = json["songs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|song| song["id"].as_str().unwrap().to_string())
+        .collect();
+    (
+        json["playlist"]["revision"].as_str().unwrap().to_string(),
+        ids,
+    )
+}
+
+fn edit(action: &str, song_id: &str, revision: &str) -> Value {
+    json!({
+        "playlist_name": "Mix",
+        "action": action,
+        "song_id": song_id,
+        "expected_revision": revision,
+    })
+}
+
+/// Read, decide and edit until the edit lands on the revision it was
+/// decided on; `decide` returns `None` when there is nothing left to do
+async fn edit_with_retries(
+    registry: &ToolRegistry,
+    decide: impl Fn(&[String]) -> Option<(&'static str, String)>,
+) -> usize {
+    let mut conflicts = 0;
+    loop {
+        let (revision, ids) = read(registry).await;
+        let Some((action, song_id)) = decide(&ids) else {
+            return conflicts;
+        };
+        match registry
+            .execute("edit_playlist", edit(action, &song_id, &revision))
+            .await
+        {
+            Ok(_) => return conflicts,
+            Err(McpError::Conflict {
+                current_revision, ..
+            }) => {
+                assert_ne!(current_revision, revision);
+                conflicts += 1;
+                assert!(conflicts < 100, "no progress");
+            }
+            Err(e) => panic!("edit failed: {}", e),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_adds_and_removes_keep_every_update() {
+    let udio = Arc::new(MemoryUdio::default());
+    udio.playlists.lock().unwrap().insert(
+        "Mix".to_string(),
+        (0..8).map(|i| format!("old{}", i)).collect(),
+    );
+    let registry = registry(udio.clone());
+
+    // Eight clients add a song each while eight others remove one each
+    let mut handles = Vec::new();
+    for i in 0..8 {
+        let adder = registry.clone();
+        handles.push(tokio::spawn(async move {
+            edit_with_retries(&adder, |_| Some(("add", format!("new{}", i)))).await
+        }));
+        let remover = registry.clone();
+        handles.push(tokio::spawn(async move {
+            edit_with_retries(&remover, |_| Some(("remove", format!("old{}", i)))).await
+        }));
+    }
+    let mut conflicts = 0;
+    for handle in handles {
+        conflicts += handle.await.unwrap();
+    }
+
+    let mut ids = udio.ids("Mix");
+    ids.sort();
+    let expected: Vec<String> = (0..8).map(|i| format!("new{}", i)).collect();
+    assert_eq!(ids, expected);
+
+    // Clients read the same revision before anyone edited, so most of them
+    // had to come back for another look
+    assert!(conflicts > 0);
+    let (revision, _) = read(&registry).await;
+    assert_eq!(
+        revision,
+        Playlist::new("pl1", "Mix")
+            .with_songs(
+                udio.ids("Mix")
+                    .iter()
+                    .map(|id| Song::new(id.as_str(), id.as_str(), 120, ""))
+                    .collect()
+            )
+            .revision
+    );
+}
+
+#[tokio::test]
+async fn test_clients_adding_the_same_track_add_it_once() {
+    let udio = Arc::new(MemoryUdio::default());
+    udio.playlists
+        .lock()
+        .unwrap()
+        .insert("Mix".to_string(), vec!["a".to_string()]);
+    let registry = registry(udio.clone());
+
+    // Each client only adds the track if its read shows it missing
+    let handles: Vec<_> = (0..6)
+        .map(|_| {
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                edit_with_retries(&registry, |ids| {
+                    (!ids.iter().any(|id| id == "b")).then(|| ("add", "b".to_string()))
+                })
+                .await
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(udio.ids("Mix"), ["a", "b"]);
+}
+
+#[tokio::test]
+async fn test_conflict_reports_the_changes() {
+    let udio = Arc::new(MemoryUdio::default());
+    udio.playlists
+        .lock()
+        .unwrap()
+        .insert("Mix".to_string(), vec!["a".to_string(), "b".to_string()]);
+    let registry = registry(udio.clone());
+
+    let (stale, _) = read(&registry).await;
+    registry
+        .execute("edit_playlist", edit("add", "c", &stale))
+        .await
+        .unwrap();
+
+    let error: McpResult<_> = registry
+        .execute("edit_playlist", edit("remove", "a", &stale))
+        .await;
+    let error = error.err().expect("edit against a stale revision");
+    let object = serde_json::to_value(error.to_error_object()).unwrap();
+    let (current, _) = read(&registry).await;
+    assert_eq!(object["code"], -32016);
+    assert_eq!(
+        object["data"],
+        json!({
+            "category": "conflict",
+            "recovery": "refresh",
+            "entity": "playlist",
+            "id": "Mix",
+            "current_revision": current,
+            "diff": {"added": ["c"], "removed": []}
+        })
+    );
+
+    // Nothing was removed
+    assert_eq!(udio.ids("Mix"), ["a", "b", "c"]);
+
+    // Edits without a revision are not checked
+    registry
+        .execute(
+            "edit_playlist",
+            json!({"playlist_name": "Mix", "action": "remove", "song_id": "a"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(udio.ids("Mix"), ["b", "c"]);
+}
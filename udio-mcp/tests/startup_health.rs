@@ -0,0 +1,190 @@
+// Integration tests for the startup checks and health probes
+// Each failure category is simulated with the real checks
+
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use udio_mcp_server::auth::{
+    AuthManager, CredentialsStore, KeychainManager, LoginAutomation, Session, SessionStore,
+};
+use udio_mcp_server::browser::{BrowserConfig, BrowserManager};
+use udio_mcp_server::health::{
+    self, AuthCheck, BrowserCheck, CacheDirCheck, CheckStatus, ConfigCheck, HealthReport,
+    HealthState, Outcome, Readiness, StartupCheck, StartupSequence,
+};
+
+const EMAIL: &str = "startup-health-test@example.com";
+
+/// Auth manager whose keychain holds nothing for [`EMAIL`]
+fn auth_with_session(session: Option<Session>) -> Arc<AuthManager> {
+    let sessions = SessionStore::new();
+    if let Some(session) = session {
+        sessions.store(session).unwrap();
+    }
+    Arc::new(AuthManager::with_components(
+        CredentialsStore::new(KeychainManager::new("udio-mcp-startup-health-test")),
+        sessions,
+        LoginAutomation::default(),
+    ))
+}
+
+async fn run(sequence: StartupSequence) -> (HealthReport, String) {
+    let mut progress = Vec::new();
+    let report = sequence.run(&HealthState::new(), &mut progress).await;
+    (report, String::from_utf8(progress).unwrap())
+}
+
+#[tokio::test]
+async fn test_bad_config_is_fatal() {
+    let cache = tempfile::tempdir().unwrap();
+    let (report, progress) = run(StartupSequence::new()
+        .with_check(ConfigCheck::new(|| {
+            anyhow::bail!("UDIO_MCP_TIMEOUT_READ_SECS must be a positive number of seconds")
+        }))
+        .with_check(CacheDirCheck::new(cache.path())))
+    .await;
+
+    assert_eq!(report.readiness, Readiness::Failed);
+    assert_eq!(report.checks.len(), 1, "later checks must not run");
+    assert_eq!(report.checks[0].outcome, Outcome::Fatal);
+    assert!(progress.starts_with("[1/2] config ... fatal: UDIO_MCP_TIMEOUT_READ_SECS"));
+}
+
+#[tokio::test]
+async fn test_browser_launch_failure_is_fatal() {
+    let browser = Arc::new(BrowserManager::new(
+        BrowserConfig::new().with_chrome_path("/nonexistent/udio-mcp/chrome"),
+    ));
+
+    let status = BrowserCheck::new(browser.clone()).run().await;
+
+    assert!(matches!(status, CheckStatus::Fatal(_)), "{:?}", status);
+    assert!(!browser.is_launched().await);
+}
+
+#[tokio::test]
+async fn test_missing_login_is_degraded() {
+    let check = AuthCheck::new(auth_with_session(None), Some(EMAIL.to_string()));
+
+    match check.run().await {
+        CheckStatus::Degraded(reason) => assert!(reason.contains(EMAIL), "{}", reason),
+        status => panic!("expected degraded, got {:?}", status),
+    }
+}
+
+#[tokio::test]
+async fn test_expired_session_is_degraded() {
+    let expired = Session::new(EMAIL, Vec::new(), 0);
+    let check = AuthCheck::new(auth_with_session(Some(expired)), Some(EMAIL.to_string()));
+
+    match check.run().await {
+        CheckStatus::Degraded(reason) => {
+            assert!(reason.starts_with("Session expired for:"), "{}", reason)
+        }
+        status => panic!("expected degraded, got {:?}", status),
+    }
+}
+
+#[tokio::test]
+async fn test_valid_session_or_no_account_passes() {
+    let valid = Session::new(EMAIL, Vec::new(), 3600);
+    let check = AuthCheck::new(auth_with_session(Some(valid)), Some(EMAIL.to_string()));
+    assert_eq!(check.run().await, CheckStatus::Passed);
+
+    let check = AuthCheck::new(auth_with_session(None), None);
+    assert_eq!(check.run().await, CheckStatus::Passed);
+}
+
+#[tokio::test]
+async fn test_unwritable_cache_dir_is_fatal() {
+    let parent = tempfile::tempdir().unwrap();
+    let file = parent.path().join("not-a-dir");
+    std::fs::write(&file, b"").unwrap();
+
+    match CacheDirCheck::new(file.join("cache")).run().await {
+        CheckStatus::Fatal(reason) => assert!(reason.contains("is not writable"), "{}", reason),
+        status => panic!("expected fatal, got {:?}", status),
+    }
+
+    let writable = parent.path().join("cache");
+    assert_eq!(
+        CacheDirCheck::new(&writable).run().await,
+        CheckStatus::Passed
+    );
+    assert_eq!(std::fs::read_dir(&writable).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_degraded_startup_continues() {
+    let cache = tempfile::tempdir().unwrap();
+    let state = HealthState::new();
+    let report = StartupSequence::new()
+        .with_check(ConfigCheck::new(|| Ok(())))
+        .with_check(AuthCheck::new(
+            auth_with_session(None),
+            Some(EMAIL.to_string()),
+        ))
+        .with_check(CacheDirCheck::new(cache.path()))
+        .run(&state, &mut Vec::new())
+        .await;
+
+    assert_eq!(report.readiness, Readiness::Degraded);
+    assert_eq!(report.checks.len(), 3);
+    assert!(state.auth_expired().unwrap().contains(EMAIL));
+}
+
+/// Send a GET and return the status code and body
+async fn get(addr: &str, path: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(format!("GET {} HTTP/1.1\r\nHost: probe\r\n\r\n", path).as_bytes())
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    let status = response[9..12].parse().unwrap();
+    let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_http_probes_follow_readiness() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let state = HealthState::new();
+    tokio::spawn(health::serve(listener, state.clone()));
+
+    // Alive but not ready while checks run
+    assert_eq!(get(&addr, "/healthz").await.0, 200);
+    assert_eq!(get(&addr, "/readyz").await.0, 503);
+
+    let cache = tempfile::tempdir().unwrap();
+    StartupSequence::new()
+        .with_check(AuthCheck::new(
+            auth_with_session(None),
+            Some(EMAIL.to_string()),
+        ))
+        .with_check(CacheDirCheck::new(cache.path()))
+        .run(&state, &mut Vec::new())
+        .await;
+    let (status, body) = get(&addr, "/readyz").await;
+    assert_eq!(status, 503);
+    let report: HealthReport = serde_json::from_str(&body).unwrap();
+    assert_eq!(report.readiness, Readiness::Degraded);
+    assert_eq!(get(&addr, "/healthz").await.0, 200);
+
+    StartupSequence::new()
+        .with_check(CacheDirCheck::new(cache.path()))
+        .run(&state, &mut Vec::new())
+        .await;
+    assert_eq!(get(&addr, "/readyz").await.0, 200);
+
+    StartupSequence::new()
+        .with_check(ConfigCheck::new(|| anyhow::bail!("bad")))
+        .run(&state, &mut Vec::new())
+        .await;
+    assert_eq!(get(&addr, "/healthz").await.0, 503);
+
+    assert_eq!(get(&addr, "/metrics").await.0, 404);
+}
@@ -0,0 +1,324 @@
+// Integration tests for webhook delivery
+// A small local HTTP server stands in for the receiving endpoint
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use udio_mcp_server::events::{Envelope, Event, EventBus, EventKind};
+use udio_mcp_server::models::Song;
+use udio_mcp_server::webhooks::{
+    verify, DeadLetterLog, DeliveryOutcome, RetryPolicy, WebhookConfig, WebhookDispatcher,
+    WebhookEndpoint,
+};
+
+/// A request the test server received
+#[derive(Debug, Clone)]
+struct Received {
+    path: String,
+    /// Header names lowercased
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Answers each request with the next scripted status, repeating the last
+struct TestServer {
+    url: String,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+impl TestServer {
+    async fn start(statuses: Vec<u16>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let log = received.clone();
+        tokio::spawn(async move {
+            let mut served = 0;
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let status = statuses[served.min(statuses.len() - 1)];
+                served += 1;
+                handle(stream, status, &log).await;
+            }
+        });
+
+        Self { url, received }
+    }
+
+    fn endpoint(&self, path: &str) -> WebhookEndpoint {
+        WebhookEndpoint::new(format!("{}{}", self.url, path))
+    }
+
+    fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Wait for requests delivered in the background
+    async fn wait_for(&self, count: usize) -> Vec<Received> {
+        for _ in 0..200 {
+            if self.received.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        self.received()
+    }
+}
+
+async fn handle(mut stream: TcpStream, status: u16, log: &Mutex<Vec<Received>>) {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.unwrap();
+        assert!(n > 0, "connection closed mid-request");
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap()
+        .to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let length: usize = headers
+        .get("content-length")
+        .map(|v| v.parse().unwrap())
+        .unwrap_or(0);
+    while buffer.len() < header_end + length {
+        let n = stream.read(&mut chunk).await.unwrap();
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    log.lock().unwrap().push(Received {
+        path,
+        headers,
+        body: buffer[header_end..header_end + length].to_vec(),
+    });
+
+    let response = format!(
+        "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    );
+    stream.write_all(response.as_bytes()).await.unwrap();
+}
+
+fn fast_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff_ms: 10,
+        max_backoff_ms: 50,
+    }
+}
+
+fn dispatcher(endpoints: Vec<WebhookEndpoint>, max_attempts: u32) -> WebhookDispatcher {
+    WebhookDispatcher::new(WebhookConfig {
+        endpoints,
+        retry: fast_retries(max_attempts),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn auth_expired() -> Envelope {
+    Envelope {
+        id: "evt_1_1".to_string(),
+        timestamp: 1_700_000_000,
+        event: Event::AuthExpired {
+            email: "user@example.com".to_string(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_payload_shape_and_signature() {
+    let server = TestServer::start(vec![200]).await;
+    let dispatcher = dispatcher(vec![server.endpoint("/hook").with_secret("s3cret")], 1);
+
+    let song = Song::new(
+        "song1",
+        "Night Drive",
+        185,
+        "https://www.udio.com/songs/song1",
+    );
+    let envelope = Envelope {
+        id: "evt_2_7".to_string(),
+        timestamp: 1_700_000_000,
+        event: Event::GenerationCompleted { song },
+    };
+
+    assert_eq!(
+        dispatcher.dispatch(&envelope).await,
+        vec![DeliveryOutcome::Delivered { attempts: 1 }]
+    );
+
+    let received = server.received();
+    assert_eq!(received.len(), 1);
+    let request = &received[0];
+    assert_eq!(request.path, "/hook");
+    assert_eq!(request.headers["content-type"], "application/json");
+    assert_eq!(request.headers["x-udio-event"], "generation_completed");
+    assert_eq!(request.headers["x-udio-delivery"], "evt_2_7");
+    assert!(verify(
+        "s3cret",
+        &request.body,
+        &request.headers["x-udio-signature"]
+    ));
+    assert!(!verify(
+        "wrong",
+        &request.body,
+        &request.headers["x-udio-signature"]
+    ));
+
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["id"], "evt_2_7");
+    assert_eq!(body["timestamp"], 1_700_000_000);
+    assert_eq!(body["event"], "generation_completed");
+    assert_eq!(body["data"]["song"]["id"], "song1");
+    assert_eq!(body["data"]["song"]["title"], "Night Drive");
+    assert_eq!(serde_json::from_value::<Envelope>(body).unwrap(), envelope);
+}
+
+#[tokio::test]
+async fn test_unsigned_without_secret() {
+    let server = TestServer::start(vec![204]).await;
+    let dispatcher = dispatcher(vec![server.endpoint("/")], 1);
+
+    dispatcher.dispatch(&auth_expired()).await;
+    assert!(!server.received()[0]
+        .headers
+        .contains_key("x-udio-signature"));
+}
+
+#[tokio::test]
+async fn test_retries_transient_failures_until_delivered() {
+    let server = TestServer::start(vec![500, 503, 429, 200]).await;
+    let dispatcher = dispatcher(vec![server.endpoint("/")], 5);
+
+    assert_eq!(
+        dispatcher.dispatch(&auth_expired()).await,
+        vec![DeliveryOutcome::Delivered { attempts: 4 }]
+    );
+
+    // Every attempt carries the same delivery ID and body
+    let received = server.received();
+    assert_eq!(received.len(), 4);
+    assert!(received
+        .iter()
+        .all(|r| r.headers["x-udio-delivery"] == "evt_1_1" && r.body == received[0].body));
+}
+
+#[tokio::test]
+async fn test_exhausted_retries_are_dead_lettered() {
+    let server = TestServer::start(vec![502]).await;
+    let dir = tempfile::tempdir().unwrap();
+    let dead_letter_path = dir.path().join("dead.jsonl");
+
+    let dispatcher = WebhookDispatcher::new(WebhookConfig {
+        endpoints: vec![server.endpoint("/")],
+        retry: fast_retries(3),
+        dead_letter_path: Some(dead_letter_path.clone()),
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        dispatcher.dispatch(&auth_expired()).await,
+        vec![DeliveryOutcome::DeadLettered {
+            attempts: 3,
+            error: "HTTP 502".to_string()
+        }]
+    );
+    assert_eq!(server.received().len(), 3);
+
+    let letters = DeadLetterLog::read_all(&dead_letter_path).unwrap();
+    assert_eq!(letters.len(), 1);
+    assert_eq!(letters[0].url, format!("{}/", server.url));
+    assert_eq!(letters[0].attempts, 3);
+    assert_eq!(letters[0].error, "HTTP 502");
+    assert_eq!(letters[0].payload, auth_expired());
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let server = TestServer::start(vec![410, 200]).await;
+    let dispatcher = dispatcher(vec![server.endpoint("/")], 5);
+
+    assert_eq!(
+        dispatcher.dispatch(&auth_expired()).await,
+        vec![DeliveryOutcome::DeadLettered {
+            attempts: 1,
+            error: "HTTP 410".to_string()
+        }]
+    );
+    assert_eq!(server.received().len(), 1);
+}
+
+#[tokio::test]
+async fn test_unreachable_endpoint_is_retried() {
+    // Bind then drop a listener to get a port nothing is listening on
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    drop(listener);
+
+    let dispatcher = dispatcher(vec![WebhookEndpoint::new(url)], 2);
+    let outcomes = dispatcher.dispatch(&auth_expired()).await;
+    assert!(matches!(
+        outcomes[..],
+        [DeliveryOutcome::DeadLettered { attempts: 2, .. }]
+    ));
+}
+
+#[tokio::test]
+async fn test_bus_events_reach_matching_endpoints() {
+    let everything = TestServer::start(vec![200]).await;
+    let auth_only = TestServer::start(vec![200]).await;
+
+    let bus = EventBus::new();
+    dispatcher(
+        vec![
+            everything.endpoint("/all"),
+            auth_only
+                .endpoint("/auth")
+                .with_events(vec![EventKind::AuthExpired]),
+        ],
+        1,
+    )
+    .spawn(&bus);
+
+    bus.publish(Event::PlaylistChanged {
+        playlist: "ToPlay".to_string(),
+        added: vec!["song2".to_string()],
+        removed: vec![],
+        song_count: 2,
+    });
+    bus.publish(Event::AuthExpired {
+        email: "user@example.com".to_string(),
+    });
+
+    let mut events: Vec<String> = everything
+        .wait_for(2)
+        .await
+        .iter()
+        .map(|r| r.headers["x-udio-event"].clone())
+        .collect();
+    events.sort();
+    assert_eq!(events, vec!["auth_expired", "playlist_changed"]);
+
+    let received = auth_only.wait_for(1).await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].headers["x-udio-event"], "auth_expired");
+}